@@ -48,9 +48,9 @@ fn benchmark_http(c: &mut Criterion) {
                             "out",
                             &["in"],
                             sinks::http::config::HttpSinkConfig {
-                                uri: out_addr.to_string().parse::<http::Uri>().unwrap().into(),
+                                uri: out_addr.to_string(),
                                 compression: *compression,
-                                method: Default::default(),
+                                method: "post".try_into().unwrap(),
                                 auth: Default::default(),
                                 headers: Default::default(),
                                 payload_prefix: Default::default(),
@@ -34,6 +34,8 @@ fn dedupe(c: &mut Criterion) {
     );
     let cache = CacheConfig {
         num_events: NonZeroUsize::new(4).unwrap(),
+        ttl_secs: None,
+        disk: None,
     };
     for param in &[
         // Measurement where field "message" is ignored. This field is
@@ -50,6 +50,8 @@ fn reduce(c: &mut Criterion) {
                 ends_when: None,
                 starts_when: None,
                 max_events: None,
+                fold: None,
+                finalizer: None,
             },
         },
     ] {
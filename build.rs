@@ -106,6 +106,47 @@ fn git_short_hash() -> std::io::Result<String> {
     })
 }
 
+/// Compiles the eBPF programs of the `ebpf` source into an object file in `OUT_DIR`.
+///
+/// This requires `clang` (or the compiler set in the `CLANG` environment variable) with the BPF
+/// target, and the kernel and `libbpf` headers.
+#[cfg(feature = "sources-ebpf")]
+fn compile_ebpf_programs() {
+    const SOURCE: &str = "src/sources/ebpf/bpf/telemetry.bpf.c";
+
+    println!("cargo:rerun-if-changed={}", SOURCE);
+    println!("cargo:rerun-if-env-changed=CLANG");
+
+    if env::var("CARGO_CFG_TARGET_OS").as_deref() != Ok("linux") {
+        return;
+    }
+
+    let (target_arch, multiarch) = match env::var("CARGO_CFG_TARGET_ARCH")
+        .expect("Cargo-provided environment variables should always exist!")
+        .as_str()
+    {
+        "x86_64" => ("x86", "x86_64-linux-gnu"),
+        "aarch64" => ("arm64", "aarch64-linux-gnu"),
+        "arm" => ("arm", "arm-linux-gnueabihf"),
+        arch => panic!(
+            "The `ebpf` source does not support the {} architecture.",
+            arch
+        ),
+    };
+    let output = Path::new(&env::var("OUT_DIR").expect("OUT_DIR environment variable not set"))
+        .join("telemetry.bpf.o");
+
+    let status = Command::new(env::var("CLANG").unwrap_or_else(|_| "clang".to_string()))
+        .args(["-O2", "-g", "-target", "bpf"])
+        .arg(format!("-D__TARGET_ARCH_{}", target_arch))
+        .arg(format!("-I/usr/include/{}", multiarch))
+        .args(["-c", SOURCE, "-o"])
+        .arg(&output)
+        .status()
+        .expect("Failed to run clang to compile the eBPF programs");
+    assert!(status.success(), "Failed to compile the eBPF programs");
+}
+
 fn main() {
     // Always rerun if the build script itself changes.
     println!("cargo:rerun-if-changed=build.rs");
@@ -116,12 +157,17 @@ fn main() {
 
     #[cfg(feature = "protobuf-build")]
     {
+        println!("cargo:rerun-if-changed=proto/containerd/services/containers/v1/containers.proto");
+        println!("cargo:rerun-if-changed=proto/containerd/services/namespaces/v1/namespace.proto");
         println!("cargo:rerun-if-changed=proto/dd_trace.proto");
         println!("cargo:rerun-if-changed=proto/dnstap.proto");
         println!("cargo:rerun-if-changed=proto/ddsketch_full.proto");
         println!("cargo:rerun-if-changed=proto/dd_metric.proto");
+        println!("cargo:rerun-if-changed=proto/google/cloud/bigquery/storage/v1/storage.proto");
         println!("cargo:rerun-if-changed=proto/google/pubsub/v1/pubsub.proto");
         println!("cargo:rerun-if-changed=proto/google/rpc/status.proto");
+        println!("cargo:rerun-if-changed=proto/jaeger/api_v2/collector.proto");
+        println!("cargo:rerun-if-changed=proto/jaeger/api_v2/model.proto");
         println!("cargo:rerun-if-changed=proto/vector.proto");
 
         // Create and store the "file descriptor set" from the compiled Protocol Buffers packages.
@@ -144,12 +190,16 @@ fn main() {
                 prost_build,
                 &[
                     "lib/vector-core/proto/event.proto",
+                    "proto/containerd/services/containers/v1/containers.proto",
+                    "proto/containerd/services/namespaces/v1/namespace.proto",
                     "proto/dnstap.proto",
                     "proto/ddsketch_full.proto",
                     "proto/dd_metric.proto",
                     "proto/dd_trace.proto",
+                    "proto/google/cloud/bigquery/storage/v1/storage.proto",
                     "proto/google/pubsub/v1/pubsub.proto",
                     "proto/google/rpc/status.proto",
+                    "proto/jaeger/api_v2/collector.proto",
                     "proto/vector.proto",
                 ],
                 &["proto/", "lib/vector-core/proto/"],
@@ -157,6 +207,9 @@ fn main() {
             .unwrap();
     }
 
+    #[cfg(feature = "sources-ebpf")]
+    compile_ebpf_programs();
+
     // We keep track of which environment variables we slurp in, and then emit stanzas at the end to
     // inform Cargo when it needs to rerun this build script.  This allows us to avoid rerunning it
     // every single time unless something _actually_ changes.
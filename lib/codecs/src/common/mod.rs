@@ -1,3 +1,5 @@
 //! A collection of common utility features used by both encoding and decoding logic.
 
 pub mod length_delimited;
+pub mod msgpack;
+pub mod varint_length_delimited;
@@ -0,0 +1,153 @@
+//! Conversions between the values of events and MessagePack values.
+
+use bytes::Bytes;
+use chrono::{DateTime, TimeZone, Utc};
+use vector_core::event::{ObjectMap, Value};
+
+/// The type of the extension holding timestamps, as defined by the MessagePack specification.
+const TIMESTAMP_EXT_TYPE: i8 = -1;
+
+/// The type of the extension holding the `EventTime` timestamps of the Fluentd forward protocol.
+const FLUENT_EVENT_TIME_EXT_TYPE: i8 = 0;
+
+/// Converts an event value to a MessagePack value.
+///
+/// Bytes are written as strings when they are valid UTF-8, and as binaries otherwise. Timestamps
+/// are written with the timestamp extension type.
+pub fn from_value(value: &Value) -> rmpv::Value {
+    match value {
+        Value::Bytes(bytes) => match std::str::from_utf8(bytes) {
+            Ok(string) => rmpv::Value::from(string),
+            Err(_) => rmpv::Value::Binary(bytes.to_vec()),
+        },
+        Value::Regex(regex) => rmpv::Value::from(regex.as_str()),
+        Value::Integer(integer) => rmpv::Value::from(*integer),
+        Value::Float(float) => rmpv::Value::F64(float.into_inner()),
+        Value::Boolean(boolean) => rmpv::Value::Boolean(*boolean),
+        Value::Timestamp(timestamp) => {
+            rmpv::Value::Ext(TIMESTAMP_EXT_TYPE, encode_timestamp(timestamp))
+        }
+        Value::Object(object) => rmpv::Value::Map(
+            object
+                .iter()
+                .map(|(key, value)| (rmpv::Value::from(key.as_str()), from_value(value)))
+                .collect(),
+        ),
+        Value::Array(array) => rmpv::Value::Array(array.iter().map(from_value).collect()),
+        Value::Null => rmpv::Value::Nil,
+    }
+}
+
+/// Converts a MessagePack value to an event value.
+///
+/// Keys of maps that aren't strings are converted to their textual representation. Extensions
+/// holding timestamps, including Fluentd `EventTime` values, are converted to timestamps, and
+/// other extensions to their raw bytes.
+pub fn to_value(value: rmpv::Value) -> Value {
+    match value {
+        rmpv::Value::Nil => Value::Null,
+        rmpv::Value::Boolean(boolean) => Value::Boolean(boolean),
+        rmpv::Value::Integer(integer) => match integer.as_i64() {
+            Some(integer) => Value::Integer(integer),
+            // Unsigned integers that don't fit in a signed integer.
+            None => Value::from_f64_or_zero(integer.as_f64().unwrap_or_default()),
+        },
+        rmpv::Value::F32(float) => Value::from_f64_or_zero(float.into()),
+        rmpv::Value::F64(float) => Value::from_f64_or_zero(float),
+        rmpv::Value::String(string) => Value::Bytes(Bytes::from(string.into_bytes())),
+        rmpv::Value::Binary(bytes) => Value::Bytes(Bytes::from(bytes)),
+        rmpv::Value::Array(array) => Value::Array(array.into_iter().map(to_value).collect()),
+        rmpv::Value::Map(map) => Value::Object(
+            map.into_iter()
+                .map(|(key, value)| {
+                    let key = match key {
+                        rmpv::Value::String(key) => {
+                            String::from_utf8_lossy(key.as_bytes()).into_owned()
+                        }
+                        key => key.to_string(),
+                    };
+                    (key.into(), to_value(value))
+                })
+                .collect::<ObjectMap>(),
+        ),
+        rmpv::Value::Ext(ext_type, data) => match decode_timestamp(ext_type, &data) {
+            Some(timestamp) => Value::Timestamp(timestamp),
+            None => Value::Bytes(Bytes::from(data)),
+        },
+    }
+}
+
+/// Encodes a timestamp in the smallest of the formats of the timestamp extension type.
+fn encode_timestamp(timestamp: &DateTime<Utc>) -> Vec<u8> {
+    let seconds = timestamp.timestamp();
+    let nanoseconds = timestamp.timestamp_subsec_nanos();
+    match u64::try_from(seconds) {
+        Ok(seconds) if seconds >> 34 == 0 => {
+            if nanoseconds == 0 && seconds <= u64::from(u32::MAX) {
+                (seconds as u32).to_be_bytes().to_vec()
+            } else {
+                ((u64::from(nanoseconds) << 34) | seconds)
+                    .to_be_bytes()
+                    .to_vec()
+            }
+        }
+        _ => {
+            let mut data = nanoseconds.to_be_bytes().to_vec();
+            data.extend_from_slice(&seconds.to_be_bytes());
+            data
+        }
+    }
+}
+
+/// Decodes the timestamp held by an extension, if it holds one.
+fn decode_timestamp(ext_type: i8, data: &[u8]) -> Option<DateTime<Utc>> {
+    let (seconds, nanoseconds) = match (ext_type, data.len()) {
+        (TIMESTAMP_EXT_TYPE, 4) => (i64::from(u32::from_be_bytes(data.try_into().ok()?)), 0),
+        (TIMESTAMP_EXT_TYPE, 8) => {
+            let value = u64::from_be_bytes(data.try_into().ok()?);
+            ((value & ((1 << 34) - 1)) as i64, (value >> 34) as u32)
+        }
+        (TIMESTAMP_EXT_TYPE, 12) => (
+            i64::from_be_bytes(data[4..].try_into().ok()?),
+            u32::from_be_bytes(data[..4].try_into().ok()?),
+        ),
+        (FLUENT_EVENT_TIME_EXT_TYPE, 8) => (
+            i64::from(u32::from_be_bytes(data[..4].try_into().ok()?)),
+            u32::from_be_bytes(data[4..].try_into().ok()?),
+        ),
+        _ => return None,
+    };
+    Utc.timestamp_opt(seconds, nanoseconds).single()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_timestamps() {
+        for (seconds, nanoseconds, length) in [
+            (1_700_000_000, 0, 4),
+            (1_700_000_000, 123_456_789, 8),
+            (-1, 500, 12),
+            (20_000_000_000, 1, 12),
+        ] {
+            let timestamp = Utc.timestamp_opt(seconds, nanoseconds).unwrap();
+            let data = encode_timestamp(&timestamp);
+
+            assert_eq!(data.len(), length);
+            assert_eq!(decode_timestamp(TIMESTAMP_EXT_TYPE, &data), Some(timestamp));
+        }
+    }
+
+    #[test]
+    fn decodes_fluent_event_times() {
+        let data = [0x65, 0x53, 0xf1, 0x00, 0x00, 0x00, 0x00, 0x01];
+
+        assert_eq!(
+            decode_timestamp(FLUENT_EVENT_TIME_EXT_TYPE, &data),
+            Some(Utc.timestamp_opt(1_700_000_000, 1).unwrap())
+        );
+        assert_eq!(decode_timestamp(1, &data), None);
+    }
+}
@@ -0,0 +1,23 @@
+use vector_config::configurable_component;
+
+/// Options for building a `VarintLengthDelimitedDecoder` or `VarintLengthDelimitedEncoder`.
+#[configurable_component]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VarintLengthDelimitedCoderOptions {
+    /// Maximum frame length
+    #[serde(default = "default_max_frame_length")]
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    pub max_frame_length: usize,
+}
+
+const fn default_max_frame_length() -> usize {
+    8 * 1_024 * 1_024
+}
+
+impl Default for VarintLengthDelimitedCoderOptions {
+    fn default() -> Self {
+        Self {
+            max_frame_length: default_max_frame_length(),
+        }
+    }
+}
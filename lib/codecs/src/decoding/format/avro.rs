@@ -1,11 +1,19 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
 use super::Deserializer;
 use crate::encoding::AvroSerializerOptions;
+use apache_avro::Schema;
 use bytes::Buf;
 use bytes::Bytes;
 use chrono::Utc;
 use lookup::event_path;
 use serde::{Deserialize, Serialize};
 use smallvec::{smallvec, SmallVec};
+use vector_common::sensitive_string::SensitiveString;
 use vector_config::configurable_component;
 use vector_core::{
     config::{log_schema, DataType, LogNamespace},
@@ -20,6 +28,9 @@ type AvroValue = apache_avro::types::Value;
 const CONFLUENT_MAGIC_BYTE: u8 = 0;
 const CONFLUENT_SCHEMA_PREFIX_LEN: usize = 5;
 
+/// How long a failure to fetch a schema from the schema registry is remembered for.
+const FAILED_FETCH_TTL: Duration = Duration::from_secs(30);
+
 /// Config used to build a `AvroDeserializer`.
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct AvroDeserializerConfig {
@@ -32,21 +43,47 @@ impl AvroDeserializerConfig {
     pub const fn new(schema: String, strip_schema_id_prefix: bool) -> Self {
         Self {
             avro_options: AvroDeserializerOptions {
-                schema,
+                schema: Some(schema),
                 strip_schema_id_prefix,
+                object_container_file: false,
+                schema_registry: None,
             },
         }
     }
 
     /// Build the `AvroDeserializer` from this configuration.
-    pub fn build(&self) -> AvroDeserializer {
-        let schema = apache_avro::Schema::parse_str(&self.avro_options.schema)
-            .map_err(|error| format!("Failed building Avro serializer: {}", error))
-            .unwrap();
-        AvroDeserializer {
+    pub fn build(&self) -> vector_common::Result<AvroDeserializer> {
+        let options = &self.avro_options;
+        let schema = options
+            .schema
+            .as_deref()
+            .map(Schema::parse_str)
+            .transpose()
+            .map_err(|error| format!("Failed building Avro deserializer: {}", error))?;
+
+        let (writer_schema, reader_schema) = match (
+            &options.schema_registry,
+            options.object_container_file,
             schema,
-            strip_schema_id_prefix: self.avro_options.strip_schema_id_prefix,
-        }
+        ) {
+            (Some(_), true, _) => {
+                return Err("`schema_registry` can't be used with `object_container_file`".into())
+            }
+            (Some(_), false, schema) => (WriterSchema::Registry(SchemaRegistry::new()), schema),
+            (None, true, schema) => (WriterSchema::Embedded, schema),
+            (None, false, Some(schema)) => (WriterSchema::Fixed(schema), None),
+            (None, false, None) => {
+                return Err("`schema` is required unless the schemas are read from \
+                        `object_container_file` or `schema_registry`"
+                    .into())
+            }
+        };
+
+        Ok(AvroDeserializer {
+            writer_schema,
+            reader_schema,
+            strip_schema_id_prefix: options.strip_schema_id_prefix,
+        })
     }
 
     /// The data type of events that are accepted by `AvroDeserializer`.
@@ -78,11 +115,15 @@ impl AvroDeserializerConfig {
     }
 }
 
-impl From<&AvroDeserializerOptions> for AvroSerializerOptions {
-    fn from(value: &AvroDeserializerOptions) -> Self {
-        Self {
-            schema: value.schema.clone(),
-        }
+impl TryFrom<&AvroDeserializerOptions> for AvroSerializerOptions {
+    type Error = vector_common::Error;
+
+    fn try_from(value: &AvroDeserializerOptions) -> Result<Self, Self::Error> {
+        let schema = value.schema.clone().ok_or(
+            "The Avro serializer requires a `schema`, which isn't set for the messages read from \
+            object container files or a schema registry",
+        )?;
+        Ok(Self { schema })
     }
 }
 /// Apache Avro serializer options.
@@ -96,33 +137,251 @@ pub struct AvroDeserializerOptions {
     /// * `Duration`
     /// * `Fixed`
     /// * `TimeMillis`
+    ///
+    /// Required, unless the schemas of the messages are read from object container files or
+    /// fetched from a schema registry. In that case, the records are resolved to this schema
+    /// when it's set.
     #[configurable(metadata(
         docs::examples = r#"{ "type": "record", "name": "log", "fields": [{ "name": "message", "type": "string" }] }"#,
         docs::additional_props_description = r#"Supports most avro data types, unsupported data types includes
         ["decimal", "duration", "local-timestamp-millis", "local-timestamp-micros"]"#,
     ))]
-    pub schema: String,
+    #[serde(default)]
+    pub schema: Option<String>,
 
     /// For Avro datum encoded in Kafka messages, the bytes are prefixed with the schema ID.  Set this to true to strip the schema ID prefix.
     /// According to [Confluent Kafka's document](https://docs.confluent.io/platform/current/schema-registry/fundamentals/serdes-develop/index.html#wire-format).
+    #[serde(default)]
     pub strip_schema_id_prefix: bool,
+
+    /// Whether each message is an [Avro object container file][container_file].
+    ///
+    /// The records of the file are decoded with the schema in its header, and each of them
+    /// becomes an event.
+    ///
+    /// [container_file]: https://avro.apache.org/docs/1.11.1/specification/#object-container-files
+    #[serde(default)]
+    pub object_container_file: bool,
+
+    /// The schema registry to fetch the schemas of the messages from.
+    ///
+    /// When set, the messages must be in the [Confluent wire format][wire_format], and each of them
+    /// is decoded with the schema of the ID it's prefixed with. The schemas are fetched once, and
+    /// reused for the following messages. A failure to fetch a schema is remembered for 30 seconds,
+    /// during which the messages prefixed with its ID are rejected without querying the registry
+    /// again.
+    ///
+    /// [wire_format]: https://docs.confluent.io/platform/current/schema-registry/fundamentals/serdes-develop/index.html#wire-format
+    #[configurable(derived)]
+    #[serde(default)]
+    pub schema_registry: Option<SchemaRegistryOptions>,
+}
+
+/// Options for fetching schemas from a [Confluent Schema Registry][schema_registry].
+///
+/// [schema_registry]: https://docs.confluent.io/platform/current/schema-registry/index.html
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct SchemaRegistryOptions {
+    /// The URL of the schema registry.
+    #[configurable(metadata(docs::examples = "http://localhost:8081"))]
+    pub url: String,
+
+    /// The username for the basic authentication to the schema registry.
+    #[configurable(metadata(docs::examples = "${SCHEMA_REGISTRY_USERNAME}"))]
+    #[serde(default)]
+    pub username: Option<String>,
+
+    /// The password for the basic authentication to the schema registry.
+    #[configurable(metadata(docs::examples = "${SCHEMA_REGISTRY_PASSWORD}"))]
+    #[serde(default)]
+    pub password: Option<SensitiveString>,
+
+    /// The timeout of the requests to the schema registry, in seconds.
+    #[serde(default = "default_timeout_secs")]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    #[configurable(metadata(docs::human_name = "Timeout"))]
+    pub timeout_secs: u64,
+}
+
+const fn default_timeout_secs() -> u64 {
+    10
+}
+
+/// The schema the messages were written with.
+#[derive(Debug, Clone)]
+enum WriterSchema {
+    /// All the messages were written with the configured schema.
+    Fixed(Schema),
+    /// Each message is an object container file, with its schema in its header.
+    Embedded,
+    /// Each message is prefixed with the ID of its schema in the registry.
+    Registry(SchemaRegistry),
+}
+
+/// Fetches schemas from a schema registry.
+///
+/// The deserializer doesn't send the requests itself, so that they go through the HTTP client,
+/// proxy and runtime of the application it's used in.
+pub trait SchemaFetcher: std::fmt::Debug + Send + Sync {
+    /// Returns the body of the response of the registry to `GET /schemas/ids/{id}`.
+    fn fetch(&self, id: u32) -> vector_common::Result<Bytes>;
+}
+
+/// A schema fetched from the schema registry, or the failure to fetch it.
+#[derive(Debug)]
+enum CachedSchema {
+    Fetched(Arc<Schema>),
+    Failed { error: String, until: Instant },
+}
+
+/// The schemas of a schema registry, fetched as they are needed.
+#[derive(Debug, Clone)]
+struct SchemaRegistry {
+    fetcher: Option<Arc<dyn SchemaFetcher>>,
+    /// The schemas fetched so far, by ID, shared by the clones of the deserializer.
+    schemas: Arc<Mutex<HashMap<u32, CachedSchema>>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SchemaResponse {
+    schema: String,
+    schema_type: Option<String>,
+}
+
+impl SchemaRegistry {
+    fn new() -> Self {
+        Self {
+            fetcher: None,
+            schemas: Default::default(),
+        }
+    }
+
+    fn schema(&self, id: u32) -> vector_common::Result<Arc<Schema>> {
+        match self.schemas.lock().expect("poisoned lock").get(&id) {
+            Some(CachedSchema::Fetched(schema)) => return Ok(Arc::clone(schema)),
+            Some(CachedSchema::Failed { error, until }) if Instant::now() < *until => {
+                return Err(error.clone().into())
+            }
+            _ => (),
+        }
+
+        let (cached, result) = match self.fetch(id) {
+            Ok(schema) => {
+                let schema = Arc::new(schema);
+                (CachedSchema::Fetched(Arc::clone(&schema)), Ok(schema))
+            }
+            Err(error) => {
+                let error = error.to_string();
+                let cached = CachedSchema::Failed {
+                    error: error.clone(),
+                    until: Instant::now() + FAILED_FETCH_TTL,
+                };
+                (cached, Err(error.into()))
+            }
+        };
+        self.schemas
+            .lock()
+            .expect("poisoned lock")
+            .insert(id, cached);
+        result
+    }
+
+    fn fetch(&self, id: u32) -> vector_common::Result<Schema> {
+        let fetcher = self
+            .fetcher
+            .as_ref()
+            .ok_or("No client was provided to fetch the schemas of the schema registry")?;
+        let body = fetcher
+            .fetch(id)
+            .map_err(|error| format!("Failed fetching Avro schema {}: {}", id, error))?;
+        let response: SchemaResponse = serde_json::from_slice(&body)
+            .map_err(|error| format!("Invalid response for Avro schema {}: {}", id, error))?;
+
+        // The registry leaves out the type of Avro schemas.
+        if let Some(schema_type) = response.schema_type.filter(|kind| kind != "AVRO") {
+            return Err(format!(
+                "Schema {} is a {} schema, not an Avro schema",
+                id, schema_type
+            )
+            .into());
+        }
+
+        Schema::parse_str(&response.schema)
+            .map_err(|error| format!("Failed parsing Avro schema {}: {}", id, error).into())
+    }
 }
 
 /// Serializer that converts bytes to an `Event` using the Apache Avro format.
 #[derive(Debug, Clone)]
 pub struct AvroDeserializer {
-    schema: apache_avro::Schema,
+    writer_schema: WriterSchema,
+    reader_schema: Option<Schema>,
     strip_schema_id_prefix: bool,
 }
 
 impl AvroDeserializer {
     /// Creates a new `AvroDeserializer`.
-    pub const fn new(schema: apache_avro::Schema, strip_schema_id_prefix: bool) -> Self {
+    pub const fn new(schema: Schema, strip_schema_id_prefix: bool) -> Self {
         Self {
-            schema,
+            writer_schema: WriterSchema::Fixed(schema),
+            reader_schema: None,
             strip_schema_id_prefix,
         }
     }
+
+    /// Sets the fetcher of the schemas of the schema registry, when the deserializer reads them
+    /// from one.
+    pub fn with_schema_fetcher(mut self, fetcher: Arc<dyn SchemaFetcher>) -> Self {
+        if let WriterSchema::Registry(registry) = &mut self.writer_schema {
+            registry.fetcher = Some(fetcher);
+        }
+        self
+    }
+
+    fn records(&self, bytes: Bytes) -> vector_common::Result<SmallVec<[AvroValue; 1]>> {
+        match &self.writer_schema {
+            WriterSchema::Fixed(schema) => {
+                let bytes = if self.strip_schema_id_prefix {
+                    confluent_schema_id(&bytes)?;
+                    bytes.slice(CONFLUENT_SCHEMA_PREFIX_LEN..)
+                } else {
+                    bytes
+                };
+                Ok(smallvec![apache_avro::from_avro_datum(
+                    schema,
+                    &mut bytes.reader(),
+                    None
+                )?])
+            }
+            WriterSchema::Embedded => {
+                let reader = match &self.reader_schema {
+                    Some(schema) => apache_avro::Reader::with_schema(schema, bytes.reader())?,
+                    None => apache_avro::Reader::new(bytes.reader())?,
+                };
+                Ok(reader.collect::<Result<_, _>>()?)
+            }
+            WriterSchema::Registry(registry) => {
+                let schema = registry.schema(confluent_schema_id(&bytes)?)?;
+                Ok(smallvec![apache_avro::from_avro_datum(
+                    &schema,
+                    &mut bytes.slice(CONFLUENT_SCHEMA_PREFIX_LEN..).reader(),
+                    self.reader_schema.as_ref()
+                )?])
+            }
+        }
+    }
+}
+
+/// Returns the schema ID of a message in the Confluent wire format.
+fn confluent_schema_id(bytes: &[u8]) -> vector_common::Result<u32> {
+    match bytes {
+        [CONFLUENT_MAGIC_BYTE, a, b, c, d, ..] => Ok(u32::from_be_bytes([*a, *b, *c, *d])),
+        _ => Err(vector_common::Error::from(
+            "Expected avro datum to be prefixed with schema id",
+        )),
+    }
 }
 
 impl Deserializer for AvroDeserializer {
@@ -136,44 +395,35 @@ impl Deserializer for AvroDeserializer {
             return Ok(smallvec![]);
         }
 
-        let bytes = if self.strip_schema_id_prefix {
-            if bytes.len() >= CONFLUENT_SCHEMA_PREFIX_LEN && bytes[0] == CONFLUENT_MAGIC_BYTE {
-                bytes.slice(CONFLUENT_SCHEMA_PREFIX_LEN..)
-            } else {
-                return Err(vector_common::Error::from(
-                    "Expected avro datum to be prefixed with schema id",
-                ));
-            }
-        } else {
-            bytes
-        };
-
-        let value = apache_avro::from_avro_datum(&self.schema, &mut bytes.reader(), None)?;
-
-        let apache_avro::types::Value::Record(fields) = value else {
-            return Err(vector_common::Error::from("Expected an avro Record"));
-        };
-
-        let mut log = LogEvent::default();
-        for (k, v) in fields {
-            log.insert(event_path!(k.as_str()), try_from(v)?);
-        }
+        self.records(bytes)?
+            .into_iter()
+            .map(|value| {
+                let apache_avro::types::Value::Record(fields) = value else {
+                    return Err(vector_common::Error::from("Expected an avro Record"));
+                };
+
+                let mut log = LogEvent::default();
+                for (k, v) in fields {
+                    log.insert(event_path!(k.as_str()), try_from(v)?);
+                }
 
-        let mut event = Event::Log(log);
-        let event = match log_namespace {
-            LogNamespace::Vector => event,
-            LogNamespace::Legacy => {
-                if let Some(timestamp_key) = log_schema().timestamp_key_target_path() {
-                    let log = event.as_mut_log();
-                    if !log.contains(timestamp_key) {
-                        let timestamp = Utc::now();
-                        log.insert(timestamp_key, timestamp);
+                let mut event = Event::Log(log);
+                let event = match log_namespace {
+                    LogNamespace::Vector => event,
+                    LogNamespace::Legacy => {
+                        if let Some(timestamp_key) = log_schema().timestamp_key_target_path() {
+                            let log = event.as_mut_log();
+                            if !log.contains(timestamp_key) {
+                                let timestamp = Utc::now();
+                                log.insert(timestamp_key, timestamp);
+                            }
+                        }
+                        event
                     }
-                }
-                event
-            }
-        };
-        Ok(smallvec![event])
+                };
+                Ok(event)
+            })
+            .collect()
     }
 }
 
@@ -235,6 +485,8 @@ pub fn try_from(value: AvroValue) -> vector_common::Result<VrlValue> {
 
 #[cfg(test)]
 mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
     use apache_avro::Schema;
     use bytes::BytesMut;
     use uuid::Uuid;
@@ -339,4 +591,199 @@ mod tests {
             &VrlValue::from(uuid)
         );
     }
+
+    fn options(schema: Option<String>) -> AvroDeserializerOptions {
+        AvroDeserializerOptions {
+            schema,
+            strip_schema_id_prefix: false,
+            object_container_file: false,
+            schema_registry: None,
+        }
+    }
+
+    fn datum(schema: &Schema, message: &str) -> Vec<u8> {
+        let value = apache_avro::to_value(Log {
+            message: message.to_owned(),
+        })
+        .unwrap();
+        apache_avro::to_avro_datum(schema, value).unwrap()
+    }
+
+    #[test]
+    fn deserialize_avro_object_container_file() {
+        let schema = get_schema();
+        let mut writer = apache_avro::Writer::new(&schema, Vec::new());
+        for message in ["first", "second"] {
+            writer
+                .append_ser(Log {
+                    message: message.to_owned(),
+                })
+                .unwrap();
+        }
+        let file = Bytes::from(writer.into_inner().unwrap());
+
+        let deserializer = AvroDeserializerConfig {
+            avro_options: AvroDeserializerOptions {
+                object_container_file: true,
+                ..options(None)
+            },
+        }
+        .build()
+        .unwrap();
+        let events = deserializer.parse(file, LogNamespace::Vector).unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(
+            events[0].as_log().get("message").unwrap(),
+            &VrlValue::from("first")
+        );
+        assert_eq!(
+            events[1].as_log().get("message").unwrap(),
+            &VrlValue::from("second")
+        );
+    }
+
+    /// Serves a single schema, counting the requests.
+    #[derive(Debug, Default)]
+    struct Fetcher {
+        schema: Option<String>,
+        requests: AtomicUsize,
+    }
+
+    impl SchemaFetcher for Fetcher {
+        fn fetch(&self, id: u32) -> vector_common::Result<Bytes> {
+            self.requests.fetch_add(1, Ordering::SeqCst);
+            match (&self.schema, id) {
+                (Some(schema), 7) => Ok(serde_json::json!({ "schema": schema }).to_string().into()),
+                _ => Err("Schema not found".into()),
+            }
+        }
+    }
+
+    fn registry_deserializer(fetcher: &Arc<Fetcher>) -> AvroDeserializer {
+        AvroDeserializerConfig {
+            avro_options: AvroDeserializerOptions {
+                schema_registry: Some(SchemaRegistryOptions {
+                    url: "http://localhost:8081".to_owned(),
+                    username: None,
+                    password: None,
+                    timeout_secs: default_timeout_secs(),
+                }),
+                ..options(None)
+            },
+        }
+        .build()
+        .unwrap()
+        .with_schema_fetcher(Arc::clone(fetcher) as Arc<dyn SchemaFetcher>)
+    }
+
+    fn prefixed(id: u32, datum: Vec<u8>) -> Bytes {
+        let mut bytes = BytesMut::new();
+        bytes.extend([CONFLUENT_MAGIC_BYTE]);
+        bytes.extend(id.to_be_bytes());
+        bytes.extend(datum);
+        bytes.freeze()
+    }
+
+    #[test]
+    fn deserialize_avro_schema_registry() {
+        let schema = get_schema();
+        let fetcher = Arc::new(Fetcher {
+            schema: Some(schema.canonical_form()),
+            ..Default::default()
+        });
+        let deserializer = registry_deserializer(&fetcher);
+
+        for message in ["first", "second"] {
+            let events = deserializer
+                .clone()
+                .parse(prefixed(7, datum(&schema, message)), LogNamespace::Vector)
+                .unwrap();
+            assert_eq!(events.len(), 1);
+            assert_eq!(
+                events[0].as_log().get("message").unwrap(),
+                &VrlValue::from(message)
+            );
+        }
+
+        // The schema is only fetched for the first message.
+        assert_eq!(fetcher.requests.load(Ordering::SeqCst), 1);
+
+        let unprefixed = Bytes::from(datum(&schema, "third"));
+        assert!(deserializer
+            .parse(unprefixed, LogNamespace::Vector)
+            .is_err());
+    }
+
+    #[test]
+    fn deserialize_avro_schema_registry_remembers_failures() {
+        let schema = get_schema();
+        let fetcher = Arc::new(Fetcher::default());
+        let deserializer = registry_deserializer(&fetcher);
+
+        for message in ["first", "second"] {
+            assert!(deserializer
+                .parse(prefixed(8, datum(&schema, message)), LogNamespace::Vector)
+                .is_err());
+        }
+
+        // The failure of the first message is reused for the second one.
+        assert_eq!(fetcher.requests.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn deserialize_avro_schema_registry_requires_fetcher() {
+        let schema = get_schema();
+        let deserializer = AvroDeserializerConfig {
+            avro_options: AvroDeserializerOptions {
+                schema_registry: Some(SchemaRegistryOptions {
+                    url: "http://localhost:8081".to_owned(),
+                    username: None,
+                    password: None,
+                    timeout_secs: default_timeout_secs(),
+                }),
+                ..options(None)
+            },
+        }
+        .build()
+        .unwrap();
+
+        assert!(deserializer
+            .parse(prefixed(7, datum(&schema, "first")), LogNamespace::Vector)
+            .is_err());
+    }
+
+    #[test]
+    fn serializer_options_require_schema() {
+        assert!(AvroSerializerOptions::try_from(&options(None)).is_err());
+        assert_eq!(
+            AvroSerializerOptions::try_from(&options(Some("\"string\"".to_owned())))
+                .unwrap()
+                .schema,
+            "\"string\""
+        );
+    }
+
+    #[test]
+    fn build_validates_options() {
+        assert!(AvroDeserializerConfig {
+            avro_options: options(None),
+        }
+        .build()
+        .is_err());
+
+        assert!(AvroDeserializerConfig {
+            avro_options: AvroDeserializerOptions {
+                object_container_file: true,
+                schema_registry: Some(SchemaRegistryOptions {
+                    url: "http://localhost:8081".to_owned(),
+                    username: None,
+                    password: None,
+                    timeout_secs: default_timeout_secs(),
+                }),
+                ..options(None)
+            },
+        }
+        .build()
+        .is_err());
+    }
 }
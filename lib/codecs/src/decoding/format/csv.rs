@@ -0,0 +1,469 @@
+use std::sync::Mutex;
+
+use bytes::Bytes;
+use chrono::Utc;
+use derivative::Derivative;
+use lookup::event_path;
+use smallvec::{smallvec, SmallVec};
+use vector_config::configurable_component;
+use vector_core::{
+    config::{log_schema, DataType, LogNamespace},
+    event::{Event, LogEvent},
+    schema,
+};
+use vrl::value::{kind::Collection, Kind, Value};
+
+use super::Deserializer;
+
+/// Config used to build a `CsvDeserializer`.
+#[configurable_component]
+#[derive(Debug, Clone, Default)]
+pub struct CsvDeserializerConfig {
+    /// CSV-specific decoding options.
+    #[serde(default, skip_serializing_if = "vector_core::serde::is_default")]
+    pub csv: CsvDeserializerOptions,
+}
+
+impl CsvDeserializerConfig {
+    /// Creates a new `CsvDeserializerConfig`.
+    pub fn new(options: CsvDeserializerOptions) -> Self {
+        Self { csv: options }
+    }
+
+    /// Build the `CsvDeserializer` from this configuration.
+    pub fn build(&self) -> CsvDeserializer {
+        CsvDeserializer::new(self.csv.clone())
+    }
+
+    /// Return the type of event build by this deserializer.
+    pub fn output_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    /// The schema produced by the deserializer.
+    pub fn schema_definition(&self, log_namespace: LogNamespace) -> schema::Definition {
+        let kind = if self.csv.infer_types {
+            Kind::bytes().or_integer().or_float().or_boolean().or_null()
+        } else {
+            Kind::bytes()
+        };
+
+        match log_namespace {
+            LogNamespace::Legacy => {
+                let mut definition =
+                    schema::Definition::empty_legacy_namespace().unknown_fields(kind.clone());
+
+                if let Some(timestamp_key) = log_schema().timestamp_key() {
+                    definition = definition.try_with_field(
+                        timestamp_key,
+                        kind.or_timestamp(),
+                        Some("timestamp"),
+                    );
+                }
+                definition
+            }
+            LogNamespace::Vector => schema::Definition::new_with_default_metadata(
+                Kind::object(Collection::from_unknown(kind)),
+                [log_namespace],
+            ),
+        }
+    }
+}
+
+/// CSV-specific decoding options.
+#[configurable_component]
+#[derive(Debug, Clone, PartialEq, Eq, Derivative)]
+#[derivative(Default)]
+pub struct CsvDeserializerOptions {
+    /// The field delimiter to use when reading CSV.
+    #[serde(default = "default_delimiter", with = "vector_core::serde::ascii_char")]
+    #[derivative(Default(value = "default_delimiter()"))]
+    pub delimiter: u8,
+
+    /// The quote character to use when reading CSV.
+    #[serde(default = "default_quote", with = "vector_core::serde::ascii_char")]
+    #[derivative(Default(value = "default_quote()"))]
+    pub quote: u8,
+
+    /// The names of the fields of the rows.
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "vector_core::serde::is_default")]
+    pub header: CsvHeader,
+
+    /// Whether the types of the values are inferred.
+    ///
+    /// When enabled, the values that are integers, floats, or booleans (`true` or `false`, in any
+    /// case) are decoded as such, and empty values are decoded as `null`. Otherwise, all the
+    /// values are decoded as strings.
+    #[serde(default, skip_serializing_if = "vector_core::serde::is_default")]
+    pub infer_types: bool,
+
+    /// How the rows that don't have a value for each field are handled.
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "vector_core::serde::is_default")]
+    pub malformed_rows: CsvMalformedRows,
+}
+
+const fn default_delimiter() -> u8 {
+    b','
+}
+
+const fn default_quote() -> u8 {
+    b'"'
+}
+
+/// The names of the fields of the rows.
+#[configurable_component]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[configurable(metadata(
+    docs::enum_tag_description = "How the names of the fields are determined."
+))]
+pub enum CsvHeader {
+    /// The first row holds the names of the fields.
+    ///
+    /// The first row of each stream, such as a file, an object, a connection, or a request
+    /// body, is the header of the following rows.
+    #[default]
+    FirstRow,
+
+    /// The names of the fields are configured, and all the rows hold values.
+    Explicit {
+        /// The names of the fields, in the order of their columns.
+        #[configurable(metadata(docs::examples = "timestamp"))]
+        #[configurable(metadata(docs::examples = "host"))]
+        #[configurable(metadata(docs::examples = "message"))]
+        fields: Vec<String>,
+    },
+
+    /// The fields are named after the position of their columns, starting with `column0`, and
+    /// all the rows hold values.
+    Positional,
+}
+
+/// How the rows that don't have a value for each field are handled.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CsvMalformedRows {
+    /// The frame of the row fails to decode, and is reported and dropped like other decoding
+    /// errors.
+    #[default]
+    Fail,
+
+    /// The row is skipped, and the other rows of the frame are decoded.
+    Skip,
+
+    /// The row is decoded into an event with its text in the `message` field, and the reason it's
+    /// malformed in the `csv_error` field, so that it can be routed apart.
+    Raw,
+}
+
+/// Deserializer that builds `Event`s from a byte frame containing CSV rows.
+#[derive(Debug)]
+pub struct CsvDeserializer {
+    options: CsvDeserializerOptions,
+    /// The names of the fields, once they are known.
+    header: Mutex<Option<Vec<String>>>,
+}
+
+impl CsvDeserializer {
+    /// Creates a new `CsvDeserializer`.
+    pub fn new(options: CsvDeserializerOptions) -> Self {
+        let header = match &options.header {
+            CsvHeader::Explicit { fields } => Some(fields.clone()),
+            CsvHeader::FirstRow | CsvHeader::Positional => None,
+        };
+
+        Self {
+            options,
+            header: Mutex::new(header),
+        }
+    }
+
+    fn value(&self, field: &[u8]) -> Value {
+        if !self.options.infer_types {
+            return Value::Bytes(Bytes::copy_from_slice(field));
+        }
+
+        let Ok(text) = std::str::from_utf8(field) else {
+            return Value::Bytes(Bytes::copy_from_slice(field));
+        };
+        if text.is_empty() {
+            Value::Null
+        } else if let Ok(integer) = text.parse::<i64>() {
+            Value::Integer(integer)
+        } else if let Some(float) = text.parse::<f64>().ok().filter(|float| float.is_finite()) {
+            Value::from_f64_or_zero(float)
+        } else if text.eq_ignore_ascii_case("true") {
+            Value::Boolean(true)
+        } else if text.eq_ignore_ascii_case("false") {
+            Value::Boolean(false)
+        } else {
+            Value::Bytes(Bytes::copy_from_slice(field))
+        }
+    }
+
+    fn malformed(
+        &self,
+        row: &[u8],
+        reason: String,
+        events: &mut SmallVec<[Event; 1]>,
+    ) -> vector_common::Result<()> {
+        match self.options.malformed_rows {
+            CsvMalformedRows::Fail => Err(format!("Malformed CSV row: {}", reason).into()),
+            CsvMalformedRows::Skip => Ok(()),
+            CsvMalformedRows::Raw => {
+                // The reader stops after the `\r` of a `\r\n` terminator, so the `\n` is read with
+                // the next row.
+                let is_terminator = |byte: &u8| matches!(byte, b'\r' | b'\n');
+                let start = row
+                    .iter()
+                    .position(|byte| !is_terminator(byte))
+                    .unwrap_or(row.len());
+                let end = row
+                    .iter()
+                    .rposition(|byte| !is_terminator(byte))
+                    .map_or(start, |end| end + 1);
+                let row = &row[start..end];
+
+                let mut log = LogEvent::default();
+                log.insert(event_path!("message"), Bytes::copy_from_slice(row));
+                log.insert(event_path!("csv_error"), reason);
+                events.push(Event::Log(log));
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Clone for CsvDeserializer {
+    /// The clone reads the header from the first row of its own stream.
+    fn clone(&self) -> Self {
+        Self::new(self.options.clone())
+    }
+}
+
+impl Deserializer for CsvDeserializer {
+    fn parse(
+        &self,
+        bytes: Bytes,
+        log_namespace: LogNamespace,
+    ) -> vector_common::Result<SmallVec<[Event; 1]>> {
+        if bytes.is_empty() {
+            return Ok(smallvec![]);
+        }
+
+        let mut reader = ::csv::ReaderBuilder::new()
+            .delimiter(self.options.delimiter)
+            .quote(self.options.quote)
+            .has_headers(false)
+            .flexible(true)
+            .from_reader(bytes.as_ref());
+        let mut header = self.header.lock().expect("poisoned lock");
+        let mut record = ::csv::ByteRecord::new();
+        let mut events = SmallVec::new();
+
+        loop {
+            let start = reader.position().byte() as usize;
+            let result = reader.read_byte_record(&mut record);
+            let row = &bytes[start..reader.position().byte() as usize];
+            match result {
+                Ok(true) => {}
+                Ok(false) => break,
+                Err(error) => {
+                    self.malformed(row, error.to_string(), &mut events)?;
+                    continue;
+                }
+            }
+
+            if header.is_none() && self.options.header == CsvHeader::FirstRow {
+                *header = Some(
+                    record
+                        .iter()
+                        .map(|name| String::from_utf8_lossy(name).into_owned())
+                        .collect(),
+                );
+                continue;
+            }
+
+            let log = match &*header {
+                Some(fields) if fields.len() != record.len() => {
+                    let reason =
+                        format!("Expected {} fields, found {}", fields.len(), record.len());
+                    self.malformed(row, reason, &mut events)?;
+                    continue;
+                }
+                Some(fields) => {
+                    let mut log = LogEvent::default();
+                    for (name, field) in fields.iter().zip(record.iter()) {
+                        log.insert(event_path!(name.as_str()), self.value(field));
+                    }
+                    log
+                }
+                None => {
+                    let mut log = LogEvent::default();
+                    for (position, field) in record.iter().enumerate() {
+                        let name = format!("column{}", position);
+                        log.insert(event_path!(name.as_str()), self.value(field));
+                    }
+                    log
+                }
+            };
+            events.push(Event::Log(log));
+        }
+
+        if log_namespace == LogNamespace::Legacy {
+            if let Some(timestamp_key) = log_schema().timestamp_key_target_path() {
+                let timestamp = Utc::now();
+                for event in &mut events {
+                    let log = event.as_mut_log();
+                    if !log.contains(timestamp_key) {
+                        log.insert(timestamp_key, timestamp);
+                    }
+                }
+            }
+        }
+
+        Ok(events)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(deserializer: &CsvDeserializer, input: &str) -> Vec<Value> {
+        deserializer
+            .parse(Bytes::from(input.to_owned()), LogNamespace::Vector)
+            .unwrap()
+            .into_iter()
+            .map(|event| event.into_log().into_parts().0)
+            .collect()
+    }
+
+    #[test]
+    fn deserialize_with_first_row_header() {
+        let deserializer = CsvDeserializerConfig::default().build();
+
+        assert!(parse(&deserializer, "host,message\n").is_empty());
+        assert_eq!(
+            parse(&deserializer, "a,\"hello, world\"\nb,bye\n"),
+            vec![
+                vrl::value!({"host": "a", "message": "hello, world"}),
+                vrl::value!({"host": "b", "message": "bye"}),
+            ]
+        );
+
+        // Clones read the header of their own streams.
+        let clone = deserializer.clone();
+        assert_eq!(
+            parse(&clone, "id,name\n1,one"),
+            vec![vrl::value!({"id": "1", "name": "one"})]
+        );
+    }
+
+    #[test]
+    fn deserialize_with_explicit_or_positional_header() {
+        let deserializer = CsvDeserializer::new(CsvDeserializerOptions {
+            delimiter: b';',
+            header: CsvHeader::Explicit {
+                fields: vec!["id".to_owned(), "name".to_owned()],
+            },
+            ..Default::default()
+        });
+        assert_eq!(
+            parse(&deserializer, "1;one\n"),
+            vec![vrl::value!({"id": "1", "name": "one"})]
+        );
+
+        let deserializer = CsvDeserializer::new(CsvDeserializerOptions {
+            header: CsvHeader::Positional,
+            ..Default::default()
+        });
+        assert_eq!(
+            parse(&deserializer, "1,one\n2\n"),
+            vec![
+                vrl::value!({"column0": "1", "column1": "one"}),
+                vrl::value!({"column0": "2"}),
+            ]
+        );
+    }
+
+    #[test]
+    fn deserialize_with_type_inference() {
+        let deserializer = CsvDeserializer::new(CsvDeserializerOptions {
+            header: CsvHeader::Positional,
+            infer_types: true,
+            ..Default::default()
+        });
+
+        assert_eq!(
+            parse(&deserializer, "12,-1.5,TRUE,false,,text,NaN\n"),
+            vec![vrl::value!({
+                "column0": 12,
+                "column1": (-1.5),
+                "column2": true,
+                "column3": false,
+                "column4": null,
+                "column5": "text",
+                "column6": "NaN",
+            })]
+        );
+    }
+
+    #[test]
+    fn deserialize_malformed_rows() {
+        let options = CsvDeserializerOptions {
+            header: CsvHeader::Explicit {
+                fields: vec!["id".to_owned(), "name".to_owned()],
+            },
+            ..Default::default()
+        };
+        let input = Bytes::from("1,one\n2\n3,three\n");
+
+        let deserializer = CsvDeserializer::new(options.clone());
+        assert!(deserializer
+            .parse(input.clone(), LogNamespace::Vector)
+            .is_err());
+
+        let deserializer = CsvDeserializer::new(CsvDeserializerOptions {
+            malformed_rows: CsvMalformedRows::Skip,
+            ..options.clone()
+        });
+        assert_eq!(
+            parse(&deserializer, "1,one\n2\n3,three\n"),
+            vec![
+                vrl::value!({"id": "1", "name": "one"}),
+                vrl::value!({"id": "3", "name": "three"}),
+            ]
+        );
+
+        let deserializer = CsvDeserializer::new(CsvDeserializerOptions {
+            malformed_rows: CsvMalformedRows::Raw,
+            ..options
+        });
+        assert_eq!(
+            parse(&deserializer, "1,one\r\n2\r\n"),
+            vec![
+                vrl::value!({"id": "1", "name": "one"}),
+                vrl::value!({"message": "2", "csv_error": "Expected 2 fields, found 1"}),
+            ]
+        );
+    }
+
+    #[test]
+    fn deserialize_adds_legacy_timestamp() {
+        let deserializer = CsvDeserializerConfig::default().build();
+        let events = deserializer
+            .parse(Bytes::from("message\nhello\n"), LogNamespace::Legacy)
+            .unwrap();
+
+        assert_eq!(events.len(), 1);
+        let log = events[0].as_log();
+        assert_eq!(log["message"], "hello".into());
+        assert!(log
+            .get(log_schema().timestamp_key_target_path().unwrap())
+            .is_some());
+    }
+}
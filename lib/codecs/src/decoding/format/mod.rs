@@ -5,24 +5,37 @@
 
 mod avro;
 mod bytes;
+mod csv;
 mod gelf;
 mod json;
+mod msgpack;
 mod native;
 mod native_json;
+#[cfg(feature = "otlp")]
+mod otlp;
 mod protobuf;
 #[cfg(feature = "syslog")]
 mod syslog;
 mod vrl;
 
+pub use self::csv::{
+    CsvDeserializer, CsvDeserializerConfig, CsvDeserializerOptions, CsvHeader, CsvMalformedRows,
+};
 use ::bytes::Bytes;
-pub use avro::{AvroDeserializer, AvroDeserializerConfig, AvroDeserializerOptions};
+pub use avro::{
+    AvroDeserializer, AvroDeserializerConfig, AvroDeserializerOptions, SchemaFetcher,
+    SchemaRegistryOptions,
+};
 use dyn_clone::DynClone;
 pub use gelf::{GelfDeserializer, GelfDeserializerConfig, GelfDeserializerOptions};
 pub use json::{JsonDeserializer, JsonDeserializerConfig, JsonDeserializerOptions};
+pub use msgpack::{MsgpackDeserializer, MsgpackDeserializerConfig};
 pub use native::{NativeDeserializer, NativeDeserializerConfig};
 pub use native_json::{
     NativeJsonDeserializer, NativeJsonDeserializerConfig, NativeJsonDeserializerOptions,
 };
+#[cfg(feature = "otlp")]
+pub use otlp::{OtlpDeserializer, OtlpDeserializerConfig, OtlpDeserializerOptions, OtlpSignal};
 pub use protobuf::{ProtobufDeserializer, ProtobufDeserializerConfig, ProtobufDeserializerOptions};
 use smallvec::SmallVec;
 #[cfg(feature = "syslog")]
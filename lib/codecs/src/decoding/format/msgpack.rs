@@ -0,0 +1,214 @@
+use bytes::Bytes;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use smallvec::SmallVec;
+use vector_core::{
+    config::{log_schema, DataType, LogNamespace},
+    event::{Event, LogEvent, Value},
+    schema,
+};
+use vrl::value::Kind;
+
+use super::Deserializer;
+use crate::common::msgpack;
+
+/// Config used to build a `MsgpackDeserializer`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct MsgpackDeserializerConfig;
+
+impl MsgpackDeserializerConfig {
+    /// Build the `MsgpackDeserializer` from this configuration.
+    pub const fn build(&self) -> MsgpackDeserializer {
+        MsgpackDeserializer
+    }
+
+    /// Return the type of event build by this deserializer.
+    pub fn output_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    /// The schema produced by the deserializer.
+    pub fn schema_definition(&self, log_namespace: LogNamespace) -> schema::Definition {
+        match log_namespace {
+            LogNamespace::Legacy => {
+                let mut definition =
+                    schema::Definition::empty_legacy_namespace().unknown_fields(Kind::any());
+
+                if let Some(timestamp_key) = log_schema().timestamp_key() {
+                    definition = definition.try_with_field(
+                        timestamp_key,
+                        // The timestamp is only inserted if the message doesn't already hold one.
+                        Kind::any(),
+                        Some("timestamp"),
+                    );
+                }
+                definition
+            }
+            LogNamespace::Vector => {
+                schema::Definition::new_with_default_metadata(Kind::any(), [log_namespace])
+            }
+        }
+    }
+}
+
+/// Deserializer that builds `Event`s from a byte frame containing [MessagePack][msgpack] values.
+///
+/// A frame can hold several consecutive values, and arrays at their root are split into multiple
+/// events, the same way JSON arrays are.
+///
+/// [msgpack]: https://msgpack.org/
+#[derive(Debug, Clone, Default)]
+pub struct MsgpackDeserializer;
+
+impl MsgpackDeserializer {
+    /// Creates a new `MsgpackDeserializer`.
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+fn to_event(value: rmpv::Value, log_namespace: LogNamespace) -> vector_common::Result<Event> {
+    match (msgpack::to_value(value), log_namespace) {
+        (value, LogNamespace::Vector) => Ok(LogEvent::from(value).into()),
+        (Value::Object(fields), LogNamespace::Legacy) => Ok(LogEvent::from(fields).into()),
+        (_, LogNamespace::Legacy) => {
+            Err("Attempted to convert a MessagePack value that isn't a map into an Event.".into())
+        }
+    }
+}
+
+impl Deserializer for MsgpackDeserializer {
+    fn parse(
+        &self,
+        bytes: Bytes,
+        log_namespace: LogNamespace,
+    ) -> vector_common::Result<SmallVec<[Event; 1]>> {
+        let mut events = SmallVec::<[Event; 1]>::new();
+        let mut input = &bytes[..];
+        while !input.is_empty() {
+            let value = rmpv::decode::read_value(&mut input)
+                .map_err(|error| format!("Error parsing MessagePack: {}", error))?;
+            match value {
+                rmpv::Value::Array(values) => {
+                    for value in values {
+                        events.push(to_event(value, log_namespace)?);
+                    }
+                }
+                value => events.push(to_event(value, log_namespace)?),
+            }
+        }
+
+        if log_namespace == LogNamespace::Legacy {
+            let timestamp = Utc::now();
+
+            if let Some(timestamp_key) = log_schema().timestamp_key_target_path() {
+                for event in &mut events {
+                    let log = event.as_mut_log();
+                    if !log.contains(timestamp_key) {
+                        log.insert(timestamp_key, timestamp);
+                    }
+                }
+            }
+        }
+
+        Ok(events)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use vrl::btreemap;
+
+    use super::*;
+
+    fn encode(value: &rmpv::Value) -> Bytes {
+        let mut bytes = Vec::new();
+        rmpv::encode::write_value(&mut bytes, value).unwrap();
+        bytes.into()
+    }
+
+    fn record() -> rmpv::Value {
+        rmpv::Value::Map(vec![
+            ("message".into(), "foo".into()),
+            ("count".into(), 3.into()),
+            ("ratio".into(), rmpv::Value::F64(0.5)),
+            ("raw".into(), rmpv::Value::Binary(vec![0xff, 0x00])),
+            (
+                "time".into(),
+                rmpv::Value::Ext(-1, 1_700_000_000_u32.to_be_bytes().to_vec()),
+            ),
+            (
+                "tags".into(),
+                rmpv::Value::Array(vec!["a".into(), rmpv::Value::Nil]),
+            ),
+            (1.into(), true.into()),
+        ])
+    }
+
+    #[test]
+    fn deserialize_msgpack() {
+        let deserializer = MsgpackDeserializer::new();
+
+        for namespace in [LogNamespace::Legacy, LogNamespace::Vector] {
+            let events = deserializer.parse(encode(&record()), namespace).unwrap();
+            assert_eq!(events.len(), 1);
+
+            let log = events[0].as_log();
+            assert_eq!(log["message"], "foo".into());
+            assert_eq!(log["count"], 3.into());
+            assert_eq!(log["ratio"], Value::from_f64_or_zero(0.5));
+            assert_eq!(log["raw"], Value::Bytes(Bytes::from_static(&[0xff, 0x00])));
+            assert_eq!(
+                log["time"],
+                Utc.timestamp_opt(1_700_000_000, 0).unwrap().into()
+            );
+            assert_eq!(log["tags"], vec![Value::from("a"), Value::Null].into());
+            assert_eq!(log["\"1\""], true.into());
+            assert_eq!(
+                log.contains(log_schema().timestamp_key_target_path().unwrap()),
+                namespace == LogNamespace::Legacy
+            );
+        }
+    }
+
+    #[test]
+    fn deserialize_consecutive_values_and_arrays() {
+        let mut input = encode(&rmpv::Value::Map(vec![("a".into(), 1.into())])).to_vec();
+        input.extend_from_slice(&encode(&rmpv::Value::Array(vec![
+            rmpv::Value::Map(vec![("b".into(), 2.into())]),
+            rmpv::Value::Map(vec![("c".into(), 3.into())]),
+        ])));
+
+        let events = MsgpackDeserializer::new()
+            .parse(input.into(), LogNamespace::Vector)
+            .unwrap();
+
+        assert_eq!(events.len(), 3);
+        assert_eq!(*events[0].as_log().value(), btreemap! { "a" => 1 }.into());
+        assert_eq!(*events[1].as_log().value(), btreemap! { "b" => 2 }.into());
+        assert_eq!(*events[2].as_log().value(), btreemap! { "c" => 3 }.into());
+    }
+
+    #[test]
+    fn deserialize_non_map_values() {
+        let input = encode(&"foo".into());
+        let deserializer = MsgpackDeserializer::new();
+
+        let events = deserializer
+            .parse(input.clone(), LogNamespace::Vector)
+            .unwrap();
+        assert_eq!(*events[0].as_log().value(), "foo".into());
+
+        assert!(deserializer.parse(input, LogNamespace::Legacy).is_err());
+    }
+
+    #[test]
+    fn deserialize_invalid_msgpack() {
+        let input = Bytes::from_static(&[0x82, 0xa1, b'a']);
+
+        assert!(MsgpackDeserializer::new()
+            .parse(input, LogNamespace::Vector)
+            .is_err());
+    }
+}
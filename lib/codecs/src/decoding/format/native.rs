@@ -39,6 +39,9 @@ impl NativeDeserializerConfig {
 }
 
 /// Deserializer that builds `Event`s from a byte frame containing Vector's native protobuf format.
+///
+/// Frames encoded with a version of the format this release can't decode are rejected, see
+/// `proto::check_native_version`.
 #[derive(Debug, Clone, Default)]
 pub struct NativeDeserializer;
 
@@ -53,8 +56,9 @@ impl Deserializer for NativeDeserializer {
         if bytes.is_empty() {
             Ok(smallvec![])
         } else {
-            let event_array = EventArray::from(proto::EventArray::decode(bytes)?);
-            Ok(event_array.into_events().collect())
+            let event_array = proto::EventArray::decode(bytes)?;
+            proto::check_native_version(event_array.version)?;
+            Ok(EventArray::from(event_array).into_events().collect())
         }
     }
 }
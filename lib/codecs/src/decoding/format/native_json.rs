@@ -2,7 +2,11 @@ use bytes::Bytes;
 use derivative::Derivative;
 use smallvec::{smallvec, SmallVec};
 use vector_config::configurable_component;
-use vector_core::{config::DataType, event::Event, schema};
+use vector_core::{
+    config::DataType,
+    event::{proto, Event},
+    schema,
+};
 use vrl::value::kind::Collection;
 use vrl::value::Kind;
 
@@ -72,6 +76,9 @@ pub struct NativeJsonDeserializerOptions {
 
 /// Deserializer that builds `Event`s from a byte frame containing Vector's native JSON
 /// representation.
+///
+/// Events with a `version` field next to them that this release can't decode are rejected, see
+/// `proto::check_native_version`.
 #[derive(Debug, Clone, Derivative)]
 #[derivative(Default)]
 pub struct NativeJsonDeserializer {
@@ -102,15 +109,34 @@ impl Deserializer for NativeJsonDeserializer {
         let events = match json {
             serde_json::Value::Array(values) => values
                 .into_iter()
-                .map(serde_json::from_value)
-                .collect::<Result<SmallVec<[Event; 1]>, _>>()?,
-            _ => smallvec![serde_json::from_value(json)?],
+                .map(parse_event)
+                .collect::<vector_common::Result<SmallVec<[Event; 1]>>>(
+            )?,
+            _ => smallvec![parse_event(json)?],
         };
 
         Ok(events)
     }
 }
 
+fn parse_event(json: serde_json::Value) -> vector_common::Result<Event> {
+    let json = match json {
+        serde_json::Value::Object(mut fields) => {
+            if let Some(version) = fields.remove("version") {
+                let version = version
+                    .as_u64()
+                    .and_then(|version| u32::try_from(version).ok())
+                    .ok_or("The `version` of a native JSON event must be an unsigned integer.")?;
+                proto::check_native_version(version)?;
+            }
+            serde_json::Value::Object(fields)
+        }
+        json => json,
+    };
+
+    Ok(serde_json::from_value(json)?)
+}
+
 #[cfg(test)]
 mod test {
     use serde_json::json;
@@ -134,4 +160,25 @@ mod test {
         let expected: SmallVec<[Event; 1]> = smallvec![event1, event2];
         assert_eq!(events, expected);
     }
+
+    #[test]
+    fn checks_native_versions() {
+        let deserializer = NativeJsonDeserializerConfig::default().build();
+
+        let json = json!({ "version": proto::NATIVE_VERSION, "log": { "a": "b" } });
+        let input = Bytes::from(serde_json::to_vec(&json).unwrap());
+        let events = deserializer.parse(input, LogNamespace::Legacy).unwrap();
+        assert_eq!(
+            events[0],
+            Event::from_json_value(json!({ "a": "b" }), LogNamespace::Legacy).unwrap()
+        );
+
+        let json = json!({ "version": proto::NATIVE_VERSION + 1, "log": { "a": "b" } });
+        let input = Bytes::from(serde_json::to_vec(&json).unwrap());
+        let error = deserializer
+            .parse(input, LogNamespace::Legacy)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("upgrade this Vector"), "{}", error);
+    }
 }
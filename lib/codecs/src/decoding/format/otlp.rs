@@ -0,0 +1,219 @@
+use bytes::Bytes;
+use opentelemetry_proto::proto::collector::{
+    logs::v1::ExportLogsServiceRequest, metrics::v1::ExportMetricsServiceRequest,
+};
+use prost::Message;
+use smallvec::SmallVec;
+use vector_config::configurable_component;
+use vector_core::{
+    config::{DataType, LogNamespace},
+    event::Event,
+    schema,
+};
+use vrl::value::Kind;
+
+use super::Deserializer;
+
+/// Config used to build an `OtlpDeserializer`.
+#[configurable_component]
+#[derive(Debug, Clone, Default)]
+pub struct OtlpDeserializerConfig {
+    /// OTLP-specific decoding options.
+    #[serde(default, skip_serializing_if = "vector_core::serde::is_default")]
+    pub otlp: OtlpDeserializerOptions,
+}
+
+impl OtlpDeserializerConfig {
+    /// Creates a new `OtlpDeserializerConfig`.
+    pub const fn new(otlp: OtlpDeserializerOptions) -> Self {
+        Self { otlp }
+    }
+
+    /// Build the `OtlpDeserializer` from this configuration.
+    pub const fn build(&self) -> OtlpDeserializer {
+        OtlpDeserializer {
+            signal: self.otlp.signal,
+        }
+    }
+
+    /// Return the type of event build by this deserializer.
+    pub fn output_type(&self) -> DataType {
+        match self.otlp.signal {
+            OtlpSignal::Logs => DataType::Log,
+            OtlpSignal::Metrics => DataType::Metric,
+        }
+    }
+
+    /// The schema produced by the deserializer.
+    pub fn schema_definition(&self, log_namespace: LogNamespace) -> schema::Definition {
+        match log_namespace {
+            LogNamespace::Legacy => {
+                schema::Definition::empty_legacy_namespace().unknown_fields(Kind::any())
+            }
+            LogNamespace::Vector => {
+                schema::Definition::new_with_default_metadata(Kind::any(), [log_namespace])
+            }
+        }
+    }
+}
+
+/// OTLP-specific decoding options.
+#[configurable_component]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OtlpDeserializerOptions {
+    /// The kind of telemetry held by the payloads.
+    ///
+    /// Payloads don't identify the kind of request they hold, so all the payloads decoded by a
+    /// component must hold the same one.
+    #[serde(default, skip_serializing_if = "vector_core::serde::is_default")]
+    pub signal: OtlpSignal,
+}
+
+/// The kind of telemetry held by OTLP payloads.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum OtlpSignal {
+    /// The payloads are `ExportLogsServiceRequest` messages, decoded as log events.
+    #[default]
+    Logs,
+
+    /// The payloads are `ExportMetricsServiceRequest` messages, decoded as metric events.
+    Metrics,
+}
+
+/// Deserializer that builds `Event`s from a byte frame containing an [OTLP][otlp] export request
+/// encoded as protobuf.
+///
+/// The records are converted the same way as with the `opentelemetry` source.
+///
+/// [otlp]: https://opentelemetry.io/docs/specs/otlp/
+#[derive(Debug, Clone, Default)]
+pub struct OtlpDeserializer {
+    signal: OtlpSignal,
+}
+
+impl OtlpDeserializer {
+    /// Creates a new `OtlpDeserializer`.
+    pub const fn new(signal: OtlpSignal) -> Self {
+        Self { signal }
+    }
+}
+
+impl Deserializer for OtlpDeserializer {
+    fn parse(
+        &self,
+        bytes: Bytes,
+        log_namespace: LogNamespace,
+    ) -> vector_common::Result<SmallVec<[Event; 1]>> {
+        let events = match self.signal {
+            OtlpSignal::Logs => ExportLogsServiceRequest::decode(bytes)
+                .map_err(|error| format!("Error parsing OTLP logs: {}", error))?
+                .resource_logs
+                .into_iter()
+                .flat_map(|resource_logs| resource_logs.into_event_iter(log_namespace))
+                .collect(),
+            OtlpSignal::Metrics => ExportMetricsServiceRequest::decode(bytes)
+                .map_err(|error| format!("Error parsing OTLP metrics: {}", error))?
+                .resource_metrics
+                .into_iter()
+                .flat_map(|resource_metrics| resource_metrics.into_event_iter())
+                .collect(),
+        };
+
+        Ok(events)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use opentelemetry_proto::proto::{
+        common::v1::{any_value::Value as PBValue, AnyValue},
+        logs::v1::{LogRecord, ResourceLogs, ScopeLogs},
+        metrics::v1::{
+            metric::Data, number_data_point::Value as PBNumber, Gauge, Metric as PBMetric,
+            NumberDataPoint, ResourceMetrics, ScopeMetrics,
+        },
+    };
+    use vector_core::event::{MetricKind, MetricValue};
+
+    use super::*;
+
+    fn logs_request() -> ExportLogsServiceRequest {
+        ExportLogsServiceRequest {
+            resource_logs: vec![ResourceLogs {
+                resource: None,
+                scope_logs: vec![ScopeLogs {
+                    scope: None,
+                    log_records: ["foo", "bar"]
+                        .into_iter()
+                        .map(|body| LogRecord {
+                            time_unix_nano: 1_700_000_000_000_000_000,
+                            body: Some(AnyValue {
+                                value: Some(PBValue::StringValue(body.into())),
+                            }),
+                            ..Default::default()
+                        })
+                        .collect(),
+                    schema_url: String::new(),
+                }],
+                schema_url: String::new(),
+            }],
+        }
+    }
+
+    #[test]
+    fn deserialize_otlp_logs() {
+        let deserializer = OtlpDeserializerConfig::default().build();
+        let input = Bytes::from(logs_request().encode_to_vec());
+
+        let events = deserializer.parse(input, LogNamespace::Legacy).unwrap();
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].as_log()["message"], "foo".into());
+        assert_eq!(events[1].as_log()["message"], "bar".into());
+    }
+
+    #[test]
+    fn deserialize_otlp_metrics() {
+        let deserializer = OtlpDeserializer::new(OtlpSignal::Metrics);
+        let request = ExportMetricsServiceRequest {
+            resource_metrics: vec![ResourceMetrics {
+                resource: None,
+                scope_metrics: vec![ScopeMetrics {
+                    scope: None,
+                    metrics: vec![PBMetric {
+                        name: "temperature".into(),
+                        data: Some(Data::Gauge(Gauge {
+                            data_points: vec![NumberDataPoint {
+                                value: Some(PBNumber::AsDouble(21.5)),
+                                ..Default::default()
+                            }],
+                        })),
+                        ..Default::default()
+                    }],
+                    schema_url: String::new(),
+                }],
+                schema_url: String::new(),
+            }],
+        };
+        let input = Bytes::from(request.encode_to_vec());
+
+        let events = deserializer.parse(input, LogNamespace::Legacy).unwrap();
+
+        assert_eq!(events.len(), 1);
+        let metric = events[0].as_metric();
+        assert_eq!(metric.name(), "temperature");
+        assert_eq!(metric.kind(), MetricKind::Absolute);
+        assert_eq!(metric.value(), &MetricValue::Gauge { value: 21.5 });
+    }
+
+    #[test]
+    fn deserialize_invalid_otlp() {
+        let deserializer = OtlpDeserializerConfig::default().build();
+
+        assert!(deserializer
+            .parse(Bytes::from_static(b"\xff\xff"), LogNamespace::Legacy)
+            .is_err());
+    }
+}
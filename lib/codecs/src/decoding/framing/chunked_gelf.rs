@@ -0,0 +1,526 @@
+use std::{
+    collections::HashMap,
+    io::Read,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use bytes::{Bytes, BytesMut};
+use flate2::read::{MultiGzDecoder, ZlibDecoder};
+use snafu::{ResultExt, Snafu};
+use tokio_util::codec::Decoder;
+use tracing::warn;
+use vector_config::configurable_component;
+
+use super::{BoxedFramingError, FramingError};
+use crate::{
+    decoding::StreamDecodingError,
+    gelf::{GELF_CHUNK_HEADER_LENGTH, GELF_CHUNK_MAGIC, GELF_MAX_CHUNKS},
+};
+
+const fn default_timeout_secs() -> u64 {
+    5
+}
+
+/// Config used to build a `ChunkedGelfDecoder`.
+#[configurable_component]
+#[derive(Debug, Clone, Default)]
+pub struct ChunkedGelfDecoderConfig {
+    /// Options for the chunked GELF decoder.
+    #[serde(default)]
+    pub chunked_gelf: ChunkedGelfDecoderOptions,
+}
+
+impl ChunkedGelfDecoderConfig {
+    /// Build the `ChunkedGelfDecoder` from this configuration.
+    pub fn build(&self) -> ChunkedGelfDecoder {
+        ChunkedGelfDecoder::new(&self.chunked_gelf)
+    }
+}
+
+/// Options for building a `ChunkedGelfDecoder`.
+#[configurable_component]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChunkedGelfDecoderOptions {
+    /// The time to wait for all the chunks of a message, after its first chunk is received.
+    ///
+    /// The chunks of messages that aren't complete in time are discarded.
+    #[serde(
+        default = "default_timeout_secs",
+        skip_serializing_if = "vector_core::serde::is_default"
+    )]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    pub timeout_secs: u64,
+
+    /// The maximum number of messages that can wait for their chunks at once.
+    ///
+    /// The chunks of new messages are discarded while the limit is reached. By default, there is no
+    /// limit enforced.
+    #[serde(default, skip_serializing_if = "vector_core::serde::is_default")]
+    pub pending_messages_limit: Option<usize>,
+
+    /// The maximum length of a message, once its chunks are reassembled and it's decompressed.
+    ///
+    /// Longer messages are discarded. By default, there is no maximum length enforced. If the
+    /// messages are received from untrusted clients, consider setting the maximum length to a
+    /// reasonably large value as a safety net, as compressed messages can be much larger than the
+    /// chunks they're sent in.
+    #[serde(default, skip_serializing_if = "vector_core::serde::is_default")]
+    pub max_length: Option<usize>,
+
+    /// The decompression applied to the messages.
+    #[serde(default, skip_serializing_if = "vector_core::serde::is_default")]
+    pub decompression: ChunkedGelfDecompression,
+}
+
+impl Default for ChunkedGelfDecoderOptions {
+    fn default() -> Self {
+        Self {
+            timeout_secs: default_timeout_secs(),
+            pending_messages_limit: None,
+            max_length: None,
+            decompression: ChunkedGelfDecompression::default(),
+        }
+    }
+}
+
+/// The decompression of GELF messages.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ChunkedGelfDecompression {
+    /// Messages are decompressed according to the magic bytes they start with, if they're
+    /// compressed with gzip or zlib.
+    #[default]
+    Auto,
+
+    /// Messages are compressed with [gzip][gzip].
+    ///
+    /// [gzip]: https://www.gzip.org/
+    Gzip,
+
+    /// Messages are compressed with [zlib][zlib].
+    ///
+    /// [zlib]: https://www.zlib.net/
+    Zlib,
+
+    /// Messages are not compressed.
+    None,
+}
+
+impl ChunkedGelfDecompression {
+    /// Detects the compression of a message from the magic bytes it starts with.
+    fn detect(message: &[u8]) -> Self {
+        match message {
+            [0x1f, 0x8b, ..] => Self::Gzip,
+            // The first byte holds the compression method, which is always 8 for zlib, and the
+            // first two bytes are a multiple of 31.
+            [first, second, ..]
+                if first & 0x0f == 8 && (u16::from(*first) << 8 | u16::from(*second)) % 31 == 0 =>
+            {
+                Self::Zlib
+            }
+            _ => Self::None,
+        }
+    }
+}
+
+/// Errors that can occur while reassembling chunked GELF messages.
+#[derive(Debug, Snafu)]
+pub enum ChunkedGelfDecoderError {
+    /// The chunk is shorter than the header of GELF chunks.
+    #[snafu(display("GELF chunk of {} bytes is shorter than a chunk header", length))]
+    InvalidChunkHeader {
+        /// The length of the chunk.
+        length: usize,
+    },
+    /// The sequence number or count of the chunk is invalid.
+    #[snafu(display("GELF chunk has the invalid sequence number {} of {}", number, count))]
+    InvalidSequence {
+        /// The sequence number of the chunk.
+        number: u8,
+        /// The number of chunks of the message.
+        count: u8,
+    },
+    /// The chunk doesn't have the same number of chunks as the previous chunks of its message.
+    #[snafu(display(
+        "GELF chunk of a message of {} chunks was received for a message of {} chunks",
+        count,
+        expected
+    ))]
+    InconsistentSequenceCount {
+        /// The number of chunks of the message, according to the chunk.
+        count: u8,
+        /// The number of chunks of the message, according to its previous chunks.
+        expected: usize,
+    },
+    /// Too many messages are already waiting for chunks.
+    #[snafu(display(
+        "GELF chunk of a new message discarded, as {} messages are already waiting for chunks",
+        limit
+    ))]
+    PendingMessagesLimitReached {
+        /// The maximum number of messages waiting for chunks.
+        limit: usize,
+    },
+    /// The message is longer than the maximum length.
+    #[snafu(display("GELF message is longer than the maximum of {} bytes", max_length))]
+    MaxLengthExceeded {
+        /// The maximum length of messages.
+        max_length: usize,
+    },
+    /// The message couldn't be decompressed.
+    #[snafu(display("Failed to decompress the {} GELF message: {}", compression, source))]
+    Decompression {
+        /// The compression of the message.
+        compression: &'static str,
+        /// The decompression error.
+        source: std::io::Error,
+    },
+}
+
+impl StreamDecodingError for ChunkedGelfDecoderError {
+    fn can_continue(&self) -> bool {
+        true
+    }
+}
+
+impl FramingError for ChunkedGelfDecoderError {}
+
+impl From<ChunkedGelfDecoderError> for BoxedFramingError {
+    fn from(error: ChunkedGelfDecoderError) -> Self {
+        Box::new(error)
+    }
+}
+
+/// The chunks received for a message.
+#[derive(Debug)]
+struct PendingMessage {
+    chunks: Vec<Option<Bytes>>,
+    received: usize,
+    length: usize,
+    first_received: Instant,
+}
+
+/// A decoder for reassembling the [chunked GELF][chunking] messages of message-based transports,
+/// such as UDP, and decompressing them.
+///
+/// Each message of the transport is either a whole GELF message, or a chunk of one. The clones
+/// of the decoder share the chunks that they received, so that the chunks of a message can be
+/// decoded by different clones.
+///
+/// [chunking]: https://go2docs.graylog.org/current/getting_in_log_data/gelf.html#GELFviaUDP
+#[derive(Debug, Clone)]
+pub struct ChunkedGelfDecoder {
+    timeout: Duration,
+    pending_messages_limit: Option<usize>,
+    max_length: Option<usize>,
+    decompression: ChunkedGelfDecompression,
+    pending: Arc<Mutex<HashMap<[u8; 8], PendingMessage>>>,
+}
+
+impl ChunkedGelfDecoder {
+    /// Creates a new `ChunkedGelfDecoder`.
+    pub fn new(options: &ChunkedGelfDecoderOptions) -> Self {
+        Self {
+            timeout: Duration::from_secs(options.timeout_secs),
+            pending_messages_limit: options.pending_messages_limit,
+            max_length: options.max_length,
+            decompression: options.decompression,
+            pending: Default::default(),
+        }
+    }
+
+    /// Decodes a message of the transport, returning the GELF message once all its chunks are
+    /// received.
+    fn decode_message(&self, message: Bytes) -> Result<Option<Bytes>, ChunkedGelfDecoderError> {
+        if !message.starts_with(&GELF_CHUNK_MAGIC) {
+            return self.decompress(message).map(Some);
+        }
+        if message.len() < GELF_CHUNK_HEADER_LENGTH {
+            return InvalidChunkHeaderSnafu {
+                length: message.len(),
+            }
+            .fail();
+        }
+
+        let id = <[u8; 8]>::try_from(&message[2..10]).expect("8 bytes of message ID");
+        let (number, count) = (message[10], message[11]);
+        if count == 0 || usize::from(count) > GELF_MAX_CHUNKS || number >= count {
+            return InvalidSequenceSnafu { number, count }.fail();
+        }
+        let payload = message.slice(GELF_CHUNK_HEADER_LENGTH..);
+        if count == 1 {
+            return self.decompress(payload).map(Some);
+        }
+
+        let mut pending = self.pending.lock().expect("poisoned lock");
+        self.evict_expired(&mut pending);
+
+        if !pending.contains_key(&id) {
+            if let Some(limit) = self.pending_messages_limit {
+                if pending.len() >= limit {
+                    return PendingMessagesLimitReachedSnafu { limit }.fail();
+                }
+            }
+        }
+        let message = pending.entry(id).or_insert_with(|| PendingMessage {
+            chunks: vec![None; usize::from(count)],
+            received: 0,
+            length: 0,
+            first_received: Instant::now(),
+        });
+
+        if message.chunks.len() != usize::from(count) {
+            let expected = message.chunks.len();
+            pending.remove(&id);
+            return InconsistentSequenceCountSnafu { count, expected }.fail();
+        }
+        // Chunks received more than once are ignored.
+        let chunk = &mut message.chunks[usize::from(number)];
+        if chunk.is_none() {
+            message.length += payload.len();
+            message.received += 1;
+            *chunk = Some(payload);
+        }
+
+        if let Some(max_length) = self.max_length {
+            if message.length > max_length {
+                pending.remove(&id);
+                return MaxLengthExceededSnafu { max_length }.fail();
+            }
+        }
+        if message.received < message.chunks.len() {
+            return Ok(None);
+        }
+
+        let message = pending.remove(&id).expect("pending message");
+        drop(pending);
+
+        let mut reassembled = BytesMut::with_capacity(message.length);
+        for chunk in message.chunks.into_iter().flatten() {
+            reassembled.extend_from_slice(&chunk);
+        }
+        self.decompress(reassembled.freeze()).map(Some)
+    }
+
+    /// Discards the messages that didn't receive all their chunks in time.
+    fn evict_expired(&self, pending: &mut HashMap<[u8; 8], PendingMessage>) {
+        pending.retain(|_, message| {
+            let expired = message.first_received.elapsed() >= self.timeout;
+            if expired {
+                warn!(
+                    message = "Discarding chunked GELF message that didn't receive all its chunks in time.",
+                    received = message.received,
+                    chunks = message.chunks.len(),
+                    internal_log_rate_limit = true
+                );
+            }
+            !expired
+        });
+    }
+
+    fn decompress(&self, message: Bytes) -> Result<Bytes, ChunkedGelfDecoderError> {
+        let decompression = match self.decompression {
+            ChunkedGelfDecompression::Auto => ChunkedGelfDecompression::detect(&message),
+            decompression => decompression,
+        };
+        let (compression, reader) = match decompression {
+            ChunkedGelfDecompression::Gzip => (
+                "gzip",
+                Box::new(MultiGzDecoder::new(&message[..])) as Box<dyn Read + '_>,
+            ),
+            ChunkedGelfDecompression::Zlib => {
+                ("zlib", Box::new(ZlibDecoder::new(&message[..])) as _)
+            }
+            ChunkedGelfDecompression::Auto | ChunkedGelfDecompression::None => {
+                self.check_length(message.len())?;
+                return Ok(message);
+            }
+        };
+
+        // Messages aren't decompressed further than the maximum length, so that small messages
+        // can't take up memory by decompressing into large ones.
+        let limit = self
+            .max_length
+            .map_or(u64::MAX, |max_length| max_length as u64 + 1);
+        let mut decompressed = Vec::new();
+        reader
+            .take(limit)
+            .read_to_end(&mut decompressed)
+            .context(DecompressionSnafu { compression })?;
+        self.check_length(decompressed.len())?;
+        Ok(decompressed.into())
+    }
+
+    fn check_length(&self, length: usize) -> Result<(), ChunkedGelfDecoderError> {
+        match self.max_length {
+            Some(max_length) if length > max_length => MaxLengthExceededSnafu { max_length }.fail(),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Decoder for ChunkedGelfDecoder {
+    type Item = Bytes;
+    type Error = BoxedFramingError;
+
+    fn decode(&mut self, _src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        // Each message of the transport is decoded as a whole once it's fully read.
+        Ok(None)
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        if src.is_empty() {
+            return Ok(None);
+        }
+        let message = src.split().freeze();
+        Ok(self.decode_message(message)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use flate2::{
+        write::{GzEncoder, ZlibEncoder},
+        Compression,
+    };
+
+    use super::*;
+
+    fn chunk(id: u64, number: u8, count: u8, payload: &[u8]) -> BytesMut {
+        let mut chunk = BytesMut::from(&GELF_CHUNK_MAGIC[..]);
+        chunk.extend_from_slice(&id.to_be_bytes());
+        chunk.extend_from_slice(&[number, count]);
+        chunk.extend_from_slice(payload);
+        chunk
+    }
+
+    fn decode(decoder: &mut ChunkedGelfDecoder, mut message: BytesMut) -> Option<Bytes> {
+        assert_eq!(decoder.decode(&mut message).unwrap(), None);
+        decoder.decode_eof(&mut message).unwrap()
+    }
+
+    #[test]
+    fn passes_through_unchunked_messages() {
+        let mut decoder = ChunkedGelfDecoderConfig::default().build();
+
+        assert_eq!(
+            decode(&mut decoder, BytesMut::from(r#"{"short_message":"foo"}"#)),
+            Some(Bytes::from(r#"{"short_message":"foo"}"#))
+        );
+        assert_eq!(
+            decode(&mut decoder, chunk(1, 0, 1, b"bar")),
+            Some(Bytes::from("bar"))
+        );
+    }
+
+    #[test]
+    fn reassembles_chunks() {
+        let mut decoder = ChunkedGelfDecoderConfig::default().build();
+        // The clones share the chunks received for each message.
+        let mut clone = decoder.clone();
+
+        assert_eq!(decode(&mut decoder, chunk(1, 2, 3, b"baz")), None);
+        assert_eq!(decode(&mut clone, chunk(2, 1, 2, b"two")), None);
+        assert_eq!(decode(&mut clone, chunk(1, 0, 3, b"foo")), None);
+        // Duplicated chunks are ignored.
+        assert_eq!(decode(&mut decoder, chunk(1, 0, 3, b"oof")), None);
+        assert_eq!(
+            decode(&mut decoder, chunk(1, 1, 3, b"bar")),
+            Some(Bytes::from("foobarbaz"))
+        );
+        assert_eq!(
+            decode(&mut decoder, chunk(2, 0, 2, b"one")),
+            Some(Bytes::from("onetwo"))
+        );
+        assert!(decoder.pending.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn decompresses_messages() {
+        let message = br#"{"short_message":"foo"}"#;
+        let mut gzip = GzEncoder::new(Vec::new(), Compression::default());
+        gzip.write_all(message).unwrap();
+        let gzip = gzip.finish().unwrap();
+        let mut zlib = ZlibEncoder::new(Vec::new(), Compression::default());
+        zlib.write_all(message).unwrap();
+        let zlib = zlib.finish().unwrap();
+
+        let mut decoder = ChunkedGelfDecoderConfig::default().build();
+        assert_eq!(
+            decode(&mut decoder, BytesMut::from(&zlib[..])),
+            Some(Bytes::from(&message[..]))
+        );
+        let (first, second) = gzip.split_at(gzip.len() / 2);
+        assert_eq!(decode(&mut decoder, chunk(1, 0, 2, first)), None);
+        assert_eq!(
+            decode(&mut decoder, chunk(1, 1, 2, second)),
+            Some(Bytes::from(&message[..]))
+        );
+
+        // The messages are only decompressed with the configured compression.
+        let mut decoder = ChunkedGelfDecoder::new(&ChunkedGelfDecoderOptions {
+            decompression: ChunkedGelfDecompression::None,
+            ..Default::default()
+        });
+        assert_eq!(
+            decode(&mut decoder, BytesMut::from(&gzip[..])),
+            Some(Bytes::from(gzip.clone()))
+        );
+        let mut decoder = ChunkedGelfDecoder::new(&ChunkedGelfDecoderOptions {
+            decompression: ChunkedGelfDecompression::Zlib,
+            ..Default::default()
+        });
+        assert!(decoder.decode_eof(&mut BytesMut::from(&gzip[..])).is_err());
+    }
+
+    #[test]
+    fn discards_expired_messages() {
+        let mut decoder = ChunkedGelfDecoder::new(&ChunkedGelfDecoderOptions {
+            timeout_secs: 0,
+            ..Default::default()
+        });
+
+        assert_eq!(decode(&mut decoder, chunk(1, 0, 2, b"foo")), None);
+        // The first chunk expired when the second one is received.
+        assert_eq!(decode(&mut decoder, chunk(1, 1, 2, b"bar")), None);
+        let pending = decoder.pending.lock().unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending.values().next().unwrap().received, 1);
+    }
+
+    #[test]
+    fn enforces_limits() {
+        let mut decoder = ChunkedGelfDecoder::new(&ChunkedGelfDecoderOptions {
+            pending_messages_limit: Some(1),
+            max_length: Some(5),
+            ..Default::default()
+        });
+
+        assert_eq!(decode(&mut decoder, chunk(1, 0, 2, b"foo")), None);
+        assert!(matches!(
+            decoder.decode_message(chunk(2, 0, 2, b"bar").freeze()),
+            Err(ChunkedGelfDecoderError::PendingMessagesLimitReached { limit: 1 })
+        ));
+        assert!(matches!(
+            decoder.decode_message(chunk(1, 1, 2, b"bar").freeze()),
+            Err(ChunkedGelfDecoderError::MaxLengthExceeded { max_length: 5 })
+        ));
+        assert!(decoder.pending.lock().unwrap().is_empty());
+
+        assert!(matches!(
+            decoder.decode_message(chunk(3, 2, 2, b"baz").freeze()),
+            Err(ChunkedGelfDecoderError::InvalidSequence {
+                number: 2,
+                count: 2
+            })
+        ));
+        assert!(matches!(
+            decoder.decode_message(Bytes::from_static(&[0x1e, 0x0f, 1, 2])),
+            Err(ChunkedGelfDecoderError::InvalidChunkHeader { length: 4 })
+        ));
+    }
+}
@@ -5,9 +5,12 @@
 
 mod bytes;
 mod character_delimited;
+mod chunked_gelf;
 mod length_delimited;
+mod multiline;
 mod newline_delimited;
 mod octet_counting;
+mod varint_length_delimited;
 
 use std::fmt::Debug;
 
@@ -15,8 +18,16 @@ use ::bytes::Bytes;
 pub use character_delimited::{
     CharacterDelimitedDecoder, CharacterDelimitedDecoderConfig, CharacterDelimitedDecoderOptions,
 };
+pub use chunked_gelf::{
+    ChunkedGelfDecoder, ChunkedGelfDecoderConfig, ChunkedGelfDecoderError,
+    ChunkedGelfDecoderOptions, ChunkedGelfDecompression,
+};
 use dyn_clone::DynClone;
 pub use length_delimited::{LengthDelimitedDecoder, LengthDelimitedDecoderConfig};
+pub use multiline::{
+    MultilineDecoder, MultilineDecoderConfig, MultilineDecoderError, MultilineDecoderOptions,
+    MultilineMode,
+};
 pub use newline_delimited::{
     NewlineDelimitedDecoder, NewlineDelimitedDecoderConfig, NewlineDelimitedDecoderOptions,
 };
@@ -24,6 +35,9 @@ pub use octet_counting::{
     OctetCountingDecoder, OctetCountingDecoderConfig, OctetCountingDecoderOptions,
 };
 use tokio_util::codec::LinesCodecError;
+pub use varint_length_delimited::{
+    VarintLengthDelimitedDecoder, VarintLengthDelimitedDecoderConfig,
+};
 
 pub use self::bytes::{BytesDecoder, BytesDecoderConfig};
 use super::StreamDecodingError;
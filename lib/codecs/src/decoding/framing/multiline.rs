@@ -0,0 +1,384 @@
+use std::time::{Duration, Instant};
+
+use bytes::{Bytes, BytesMut};
+use regex::bytes::Regex;
+use snafu::{ResultExt, Snafu};
+use tokio_util::codec::Decoder;
+use vector_config::configurable_component;
+
+use super::{BoxedFramingError, NewlineDelimitedDecoder};
+
+/// Mode of operation of the line aggregator.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MultilineMode {
+    /// All consecutive lines matching this pattern are included in the group.
+    ///
+    /// The first line (the line that matched the start pattern) does not need to match the `ContinueThrough` pattern.
+    ///
+    /// This is useful in cases such as a Java stack trace, where some indicator in the line (such as a leading
+    /// whitespace) indicates that it is an extension of the proceeding line.
+    ContinueThrough,
+
+    /// All consecutive lines matching this pattern, plus one additional line, are included in the group.
+    ///
+    /// This is useful in cases where a log message ends with a continuation marker, such as a backslash, indicating
+    /// that the following line is part of the same message.
+    ContinuePast,
+
+    /// All consecutive lines not matching this pattern are included in the group.
+    ///
+    /// This is useful where a log line contains a marker indicating that it begins a new message.
+    HaltBefore,
+
+    /// All consecutive lines, up to and including the first line matching this pattern, are included in the group.
+    ///
+    /// This is useful where a log line ends with a termination marker, such as a semicolon.
+    HaltWith,
+}
+
+/// Config used to build a `MultilineDecoder`.
+#[configurable_component]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultilineDecoderConfig {
+    /// Options for the multiline decoder.
+    pub multiline: MultilineDecoderOptions,
+}
+
+impl MultilineDecoderConfig {
+    /// Build the `MultilineDecoder` from this configuration.
+    pub fn build(&self) -> Result<MultilineDecoder, MultilineDecoderError> {
+        MultilineDecoder::new(&self.multiline)
+    }
+}
+
+/// Options for building a `MultilineDecoder`.
+#[configurable_component]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MultilineDecoderOptions {
+    /// Regular expression pattern that is used to match the start of a new message.
+    #[configurable(metadata(docs::examples = "^[\\s]+"))]
+    #[configurable(metadata(docs::examples = "\\\\$"))]
+    #[configurable(metadata(docs::examples = "^(INFO|ERROR) "))]
+    #[configurable(metadata(docs::examples = ";$"))]
+    pub start_pattern: String,
+
+    /// Regular expression pattern that is used to determine whether or not more lines should be read.
+    ///
+    /// This setting must be configured in conjunction with `mode`.
+    #[configurable(metadata(docs::examples = "^[\\s]+"))]
+    #[configurable(metadata(docs::examples = "\\\\$"))]
+    #[configurable(metadata(docs::examples = "^(INFO|ERROR) "))]
+    #[configurable(metadata(docs::examples = ";$"))]
+    pub condition_pattern: String,
+
+    /// Aggregation mode.
+    ///
+    /// This setting must be configured in conjunction with `condition_pattern`.
+    pub mode: MultilineMode,
+
+    /// The maximum amount of time to wait for the next additional line, in milliseconds.
+    ///
+    /// Once this timeout is reached, the buffered message is flushed, even if incomplete, as soon
+    /// as more data is received or the stream ends.
+    #[configurable(metadata(docs::examples = 1000))]
+    #[configurable(metadata(docs::examples = 600000))]
+    #[configurable(metadata(docs::human_name = "Timeout"))]
+    #[configurable(metadata(docs::type_unit = "milliseconds"))]
+    pub timeout_ms: u64,
+}
+
+/// An error that occurred while building a `MultilineDecoder`.
+#[derive(Debug, Snafu)]
+pub enum MultilineDecoderError {
+    /// The start pattern is not a valid regular expression.
+    #[snafu(display(
+        "unable to parse multiline start pattern from {:?}: {}",
+        start_pattern,
+        source
+    ))]
+    InvalidStartPattern {
+        /// The invalid pattern.
+        start_pattern: String,
+        /// The error parsing the pattern.
+        source: regex::Error,
+    },
+
+    /// The condition pattern is not a valid regular expression.
+    #[snafu(display(
+        "unable to parse multiline condition pattern from {:?}: {}",
+        condition_pattern,
+        source
+    ))]
+    InvalidConditionPattern {
+        /// The invalid pattern.
+        condition_pattern: String,
+        /// The error parsing the pattern.
+        source: regex::Error,
+    },
+}
+
+/// A codec for handling lines that are aggregated into multi-line messages, such as stack traces,
+/// the same way the `file` source does.
+///
+/// The lines of each message are joined with newlines. As decoders only run when data is received,
+/// a message whose timeout is reached is flushed along with the next data, or at the end of the
+/// stream.
+#[derive(Debug, Clone)]
+pub struct MultilineDecoder {
+    lines: NewlineDelimitedDecoder,
+    start_pattern: Regex,
+    condition_pattern: Regex,
+    mode: MultilineMode,
+    timeout: Duration,
+    /// The message being aggregated, and when its last line was received.
+    aggregate: Option<(BytesMut, Instant)>,
+    /// A message that is complete, but has to wait for the one preceding it to be returned.
+    pending: Option<Bytes>,
+}
+
+impl MultilineDecoder {
+    /// Creates a new `MultilineDecoder`.
+    pub fn new(options: &MultilineDecoderOptions) -> Result<Self, MultilineDecoderError> {
+        let start_pattern =
+            Regex::new(&options.start_pattern).with_context(|_| InvalidStartPatternSnafu {
+                start_pattern: options.start_pattern.clone(),
+            })?;
+        let condition_pattern = Regex::new(&options.condition_pattern).with_context(|_| {
+            InvalidConditionPatternSnafu {
+                condition_pattern: options.condition_pattern.clone(),
+            }
+        })?;
+
+        Ok(Self {
+            lines: NewlineDelimitedDecoder::new(),
+            start_pattern,
+            condition_pattern,
+            mode: options.mode,
+            timeout: Duration::from_millis(options.timeout_ms),
+            aggregate: None,
+            pending: None,
+        })
+    }
+
+    /// Starts a new message with the line if it matches the start pattern, or returns it as-is.
+    fn start_message(&mut self, line: Bytes) -> Option<Bytes> {
+        if self.start_pattern.is_match(&line) {
+            self.aggregate = Some((BytesMut::from(&line[..]), Instant::now()));
+            None
+        } else {
+            Some(line)
+        }
+    }
+
+    /// Handles a line, returning the message it completes, if any.
+    fn handle_line(&mut self, line: Bytes) -> Option<Bytes> {
+        let Some((mut message, _)) = self.aggregate.take() else {
+            return self.start_message(line);
+        };
+
+        let condition_matched = self.condition_pattern.is_match(&line);
+        match (self.mode, condition_matched) {
+            // The line continues the message.
+            (MultilineMode::ContinueThrough, true)
+            | (MultilineMode::ContinuePast, true)
+            | (MultilineMode::HaltBefore, false)
+            | (MultilineMode::HaltWith, false) => {
+                message.extend_from_slice(b"\n");
+                message.extend_from_slice(&line);
+                self.aggregate = Some((message, Instant::now()));
+                None
+            }
+            // The line ends the message.
+            (MultilineMode::ContinuePast, false) | (MultilineMode::HaltWith, true) => {
+                message.extend_from_slice(b"\n");
+                message.extend_from_slice(&line);
+                Some(message.freeze())
+            }
+            // The message ended before the line, which may start the next one.
+            (MultilineMode::ContinueThrough, false) | (MultilineMode::HaltBefore, true) => {
+                self.pending = self.start_message(line);
+                Some(message.freeze())
+            }
+        }
+    }
+
+    /// Takes the message being aggregated if no line was added to it within the timeout.
+    fn take_expired(&mut self) -> Option<Bytes> {
+        match &self.aggregate {
+            Some((_, last_line)) if last_line.elapsed() >= self.timeout => {
+                self.aggregate.take().map(|(message, _)| message.freeze())
+            }
+            _ => None,
+        }
+    }
+}
+
+impl Decoder for MultilineDecoder {
+    type Item = Bytes;
+    type Error = BoxedFramingError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        if let Some(message) = self.pending.take().or_else(|| self.take_expired()) {
+            return Ok(Some(message));
+        }
+
+        while let Some(line) = self.lines.decode(src)? {
+            if let Some(message) = self.handle_line(line) {
+                return Ok(Some(message));
+            }
+        }
+        Ok(None)
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        if let Some(message) = self.pending.take() {
+            return Ok(Some(message));
+        }
+
+        while let Some(line) = self.lines.decode_eof(src)? {
+            if let Some(message) = self.handle_line(line) {
+                return Ok(Some(message));
+            }
+        }
+        Ok(self.aggregate.take().map(|(message, _)| message.freeze()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decoder(
+        mode: MultilineMode,
+        start_pattern: &str,
+        condition_pattern: &str,
+    ) -> MultilineDecoder {
+        MultilineDecoder::new(&MultilineDecoderOptions {
+            start_pattern: start_pattern.to_string(),
+            condition_pattern: condition_pattern.to_string(),
+            mode,
+            timeout_ms: 60_000,
+        })
+        .unwrap()
+    }
+
+    fn decode_all(decoder: &mut MultilineDecoder, input: &str) -> Vec<Bytes> {
+        let mut input = BytesMut::from(input);
+        let mut messages = Vec::new();
+        while let Some(message) = decoder.decode(&mut input).unwrap() {
+            messages.push(message);
+        }
+        while let Some(message) = decoder.decode_eof(&mut input).unwrap() {
+            messages.push(message);
+        }
+        messages
+    }
+
+    #[test]
+    fn decode_continue_through() {
+        let mut decoder = decoder(MultilineMode::ContinueThrough, "^[^\\s]", "^[\\s]+");
+        let input = "Exception in thread \"main\"\n  at Foo.bar\n  at Foo.main\nsome usual line\n";
+
+        assert_eq!(
+            decode_all(&mut decoder, input),
+            [
+                "Exception in thread \"main\"\n  at Foo.bar\n  at Foo.main",
+                "some usual line"
+            ]
+        );
+    }
+
+    #[test]
+    fn decode_continue_past() {
+        let mut decoder = decoder(MultilineMode::ContinuePast, "\\\\$", "\\\\$");
+        let input = "some usual line\nfirst part \\\nsecond part \\\nlast part\nanother line\n";
+
+        assert_eq!(
+            decode_all(&mut decoder, input),
+            [
+                "some usual line",
+                "first part \\\nsecond part \\\nlast part",
+                "another line"
+            ]
+        );
+    }
+
+    #[test]
+    fn decode_halt_before() {
+        let mut decoder = decoder(MultilineMode::HaltBefore, "", "^(INFO|ERROR) ");
+        let input = "INFO first\nERROR second\n  detail\nINFO third\n";
+
+        assert_eq!(
+            decode_all(&mut decoder, input),
+            ["INFO first", "ERROR second\n  detail", "INFO third"]
+        );
+    }
+
+    #[test]
+    fn decode_halt_with() {
+        let mut decoder = decoder(MultilineMode::HaltWith, "[^;]$", ";$");
+        let input = "one;\nfirst part\nsecond part;\nlast\n";
+
+        assert_eq!(
+            decode_all(&mut decoder, input),
+            ["one;", "first part\nsecond part;", "last"]
+        );
+    }
+
+    #[test]
+    fn decode_waits_for_more_lines() {
+        let mut decoder = decoder(MultilineMode::ContinueThrough, "^[^\\s]", "^[\\s]+");
+
+        let mut input = BytesMut::from("Exception\n  at Foo.bar\n");
+        assert_eq!(decoder.decode(&mut input).unwrap(), None);
+
+        input.extend_from_slice(b"  at Foo.main\nnext\n");
+        assert_eq!(
+            decoder.decode(&mut input).unwrap().unwrap(),
+            "Exception\n  at Foo.bar\n  at Foo.main"
+        );
+        assert_eq!(decoder.decode(&mut input).unwrap(), None);
+        assert_eq!(decoder.decode_eof(&mut input).unwrap().unwrap(), "next");
+    }
+
+    #[test]
+    fn decode_flushes_expired_messages() {
+        let mut decoder = MultilineDecoder::new(&MultilineDecoderOptions {
+            start_pattern: "^[^\\s]".to_string(),
+            condition_pattern: "^[\\s]+".to_string(),
+            mode: MultilineMode::ContinueThrough,
+            timeout_ms: 10,
+        })
+        .unwrap();
+
+        let mut input = BytesMut::from("Exception\n  at Foo.bar\n");
+        assert_eq!(decoder.decode(&mut input).unwrap(), None);
+
+        std::thread::sleep(Duration::from_millis(20));
+        input.extend_from_slice(b"  at Foo.main\n");
+        assert_eq!(
+            decoder.decode(&mut input).unwrap().unwrap(),
+            "Exception\n  at Foo.bar"
+        );
+        assert_eq!(
+            decoder.decode(&mut input).unwrap().unwrap(),
+            "  at Foo.main"
+        );
+    }
+
+    #[test]
+    fn build_rejects_invalid_patterns() {
+        let config = MultilineDecoderConfig {
+            multiline: MultilineDecoderOptions {
+                start_pattern: "(".to_string(),
+                condition_pattern: "^[\\s]+".to_string(),
+                mode: MultilineMode::ContinueThrough,
+                timeout_ms: 1000,
+            },
+        };
+
+        assert!(config.build().is_err());
+    }
+}
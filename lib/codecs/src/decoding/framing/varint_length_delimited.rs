@@ -0,0 +1,168 @@
+use std::io;
+
+use bytes::{Buf, Bytes, BytesMut};
+use derivative::Derivative;
+use tokio_util::codec::Decoder;
+use vector_config::configurable_component;
+
+use crate::common::varint_length_delimited::VarintLengthDelimitedCoderOptions;
+
+use super::BoxedFramingError;
+
+/// The maximum length of a varint encoding a 64-bit integer.
+const MAX_VARINT_LENGTH: usize = 10;
+
+/// Config used to build a `VarintLengthDelimitedDecoder`.
+#[configurable_component]
+#[derive(Debug, Clone, Derivative)]
+#[derivative(Default)]
+pub struct VarintLengthDelimitedDecoderConfig {
+    /// Options for the varint length delimited decoder.
+    #[serde(default, skip_serializing_if = "vector_core::serde::is_default")]
+    pub varint_length_delimited: VarintLengthDelimitedCoderOptions,
+}
+
+impl VarintLengthDelimitedDecoderConfig {
+    /// Build the `VarintLengthDelimitedDecoder` from this configuration.
+    pub fn build(&self) -> VarintLengthDelimitedDecoder {
+        VarintLengthDelimitedDecoder::new(&self.varint_length_delimited)
+    }
+}
+
+/// A codec for handling bytes sequences prefixed with their length as a varint, the way streams
+/// of Protocol Buffers messages are delimited.
+#[derive(Debug, Clone)]
+pub struct VarintLengthDelimitedDecoder {
+    max_frame_length: usize,
+}
+
+impl VarintLengthDelimitedDecoder {
+    /// Creates a new `VarintLengthDelimitedDecoder`.
+    pub const fn new(config: &VarintLengthDelimitedCoderOptions) -> Self {
+        Self {
+            max_frame_length: config.max_frame_length,
+        }
+    }
+}
+
+impl Default for VarintLengthDelimitedDecoder {
+    fn default() -> Self {
+        Self::new(&VarintLengthDelimitedCoderOptions::default())
+    }
+}
+
+/// Reads the varint at the start of the buffer, returning its value and its length, or `None` if
+/// the buffer doesn't hold all of it yet.
+fn decode_varint(src: &[u8]) -> io::Result<Option<(u64, usize)>> {
+    let mut value = 0;
+    for (index, byte) in src.iter().take(MAX_VARINT_LENGTH).enumerate() {
+        // The last byte of a 64-bit varint only holds its highest bit.
+        if index == MAX_VARINT_LENGTH - 1 && *byte > 1 {
+            break;
+        }
+        value |= u64::from(byte & 0x7f) << (7 * index);
+        if byte & 0x80 == 0 {
+            return Ok(Some((value, index + 1)));
+        }
+    }
+
+    if src.len() < MAX_VARINT_LENGTH {
+        Ok(None)
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "invalid varint frame length",
+        ))
+    }
+}
+
+impl Decoder for VarintLengthDelimitedDecoder {
+    type Item = Bytes;
+    type Error = BoxedFramingError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        let Some((length, header_length)) = decode_varint(src)? else {
+            return Ok(None);
+        };
+
+        let length = match usize::try_from(length) {
+            Ok(length) if length <= self.max_frame_length => length,
+            _ => {
+                return Err(Box::new(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!(
+                        "frame of {} bytes exceeds the maximum frame length of {} bytes",
+                        length, self.max_frame_length
+                    ),
+                )))
+            }
+        };
+
+        let frame_length = header_length + length;
+        if src.len() < frame_length {
+            src.reserve(frame_length - src.len());
+            return Ok(None);
+        }
+
+        src.advance(header_length);
+        Ok(Some(src.split_to(length).freeze()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_frames() {
+        let mut input = BytesMut::from("\x03foo\x00\x06barbaz");
+        let mut decoder = VarintLengthDelimitedDecoder::default();
+
+        assert_eq!(decoder.decode(&mut input).unwrap().unwrap(), "foo");
+        assert_eq!(decoder.decode(&mut input).unwrap().unwrap(), "");
+        assert_eq!(decoder.decode(&mut input).unwrap().unwrap(), "barbaz");
+        assert_eq!(decoder.decode(&mut input).unwrap(), None);
+    }
+
+    #[test]
+    fn decode_multibyte_length() {
+        let mut input = BytesMut::from(&[0xac, 0x02][..]);
+        input.extend_from_slice(&[b'a'; 300]);
+        let mut decoder = VarintLengthDelimitedDecoder::default();
+
+        assert_eq!(
+            decoder.decode(&mut input).unwrap().unwrap(),
+            &[b'a'; 300][..]
+        );
+        assert!(input.is_empty());
+    }
+
+    #[test]
+    fn decode_incomplete_frames() {
+        let mut decoder = VarintLengthDelimitedDecoder::default();
+
+        let mut input = BytesMut::from(&[0xac][..]);
+        assert_eq!(decoder.decode(&mut input).unwrap(), None);
+
+        let mut input = BytesMut::from("\x03fo");
+        assert_eq!(decoder.decode(&mut input).unwrap(), None);
+        input.extend_from_slice(b"o");
+        assert_eq!(decoder.decode(&mut input).unwrap().unwrap(), "foo");
+
+        let mut input = BytesMut::from("\x03fo");
+        assert!(decoder.decode_eof(&mut input).is_err());
+    }
+
+    #[test]
+    fn decode_rejects_invalid_lengths() {
+        let mut decoder = VarintLengthDelimitedDecoder::new(&VarintLengthDelimitedCoderOptions {
+            max_frame_length: 4,
+        });
+
+        let mut input = BytesMut::from("\x05hello");
+        assert!(decoder.decode(&mut input).is_err());
+
+        let mut input = BytesMut::from(&[0xff; 10][..]);
+        assert!(decoder.decode(&mut input).is_err());
+    }
+}
@@ -9,20 +9,27 @@ use crate::decoding::format::{VrlDeserializer, VrlDeserializerConfig};
 use bytes::{Bytes, BytesMut};
 pub use error::StreamDecodingError;
 pub use format::{
-    BoxedDeserializer, BytesDeserializer, BytesDeserializerConfig, GelfDeserializer,
+    BoxedDeserializer, BytesDeserializer, BytesDeserializerConfig, CsvDeserializer,
+    CsvDeserializerConfig, CsvDeserializerOptions, CsvHeader, CsvMalformedRows, GelfDeserializer,
     GelfDeserializerConfig, GelfDeserializerOptions, JsonDeserializer, JsonDeserializerConfig,
-    JsonDeserializerOptions, NativeDeserializer, NativeDeserializerConfig, NativeJsonDeserializer,
-    NativeJsonDeserializerConfig, NativeJsonDeserializerOptions, ProtobufDeserializer,
-    ProtobufDeserializerConfig, ProtobufDeserializerOptions,
+    JsonDeserializerOptions, MsgpackDeserializer, MsgpackDeserializerConfig, NativeDeserializer,
+    NativeDeserializerConfig, NativeJsonDeserializer, NativeJsonDeserializerConfig,
+    NativeJsonDeserializerOptions, ProtobufDeserializer, ProtobufDeserializerConfig,
+    ProtobufDeserializerOptions,
 };
+#[cfg(feature = "otlp")]
+pub use format::{OtlpDeserializer, OtlpDeserializerConfig, OtlpDeserializerOptions, OtlpSignal};
 #[cfg(feature = "syslog")]
 pub use format::{SyslogDeserializer, SyslogDeserializerConfig, SyslogDeserializerOptions};
 pub use framing::{
     BoxedFramer, BoxedFramingError, BytesDecoder, BytesDecoderConfig, CharacterDelimitedDecoder,
-    CharacterDelimitedDecoderConfig, CharacterDelimitedDecoderOptions, FramingError,
-    LengthDelimitedDecoder, LengthDelimitedDecoderConfig, NewlineDelimitedDecoder,
-    NewlineDelimitedDecoderConfig, NewlineDelimitedDecoderOptions, OctetCountingDecoder,
-    OctetCountingDecoderConfig, OctetCountingDecoderOptions,
+    CharacterDelimitedDecoderConfig, CharacterDelimitedDecoderOptions, ChunkedGelfDecoder,
+    ChunkedGelfDecoderConfig, ChunkedGelfDecoderError, ChunkedGelfDecoderOptions,
+    ChunkedGelfDecompression, FramingError, LengthDelimitedDecoder, LengthDelimitedDecoderConfig,
+    MultilineDecoder, MultilineDecoderConfig, MultilineDecoderError, MultilineDecoderOptions,
+    MultilineMode, NewlineDelimitedDecoder, NewlineDelimitedDecoderConfig,
+    NewlineDelimitedDecoderOptions, OctetCountingDecoder, OctetCountingDecoderConfig,
+    OctetCountingDecoderOptions, VarintLengthDelimitedDecoder, VarintLengthDelimitedDecoderConfig,
 };
 use smallvec::SmallVec;
 use std::fmt::Debug;
@@ -88,9 +95,22 @@ pub enum FramingConfig {
     /// Byte frames which are delimited by a chosen character.
     CharacterDelimited(CharacterDelimitedDecoderConfig),
 
+    /// Byte frames which are [chunked GELF][chunking] messages, reassembled from their chunks and
+    /// decompressed.
+    ///
+    /// This is meant for message-based transports such as UDP, which Graylog clients send chunked
+    /// and compressed messages over.
+    ///
+    /// [chunking]: https://go2docs.graylog.org/current/getting_in_log_data/gelf.html#GELFviaUDP
+    ChunkedGelf(ChunkedGelfDecoderConfig),
+
     /// Byte frames which are prefixed by an unsigned big-endian 32-bit integer indicating the length.
     LengthDelimited(LengthDelimitedDecoderConfig),
 
+    /// Byte frames which are lines aggregated into multi-line messages, such as stack traces, the
+    /// same way the `file` source aggregates them.
+    Multiline(MultilineDecoderConfig),
+
     /// Byte frames which are delimited by a newline character.
     NewlineDelimited(NewlineDelimitedDecoderConfig),
 
@@ -98,6 +118,14 @@ pub enum FramingConfig {
     ///
     /// [octet_counting]: https://tools.ietf.org/html/rfc6587#section-3.4.1
     OctetCounting(OctetCountingDecoderConfig),
+
+    /// Byte frames which are prefixed by an unsigned [varint][varint] indicating the length.
+    ///
+    /// This is the framing of streams of Protocol Buffers messages, as written by
+    /// `writeDelimitedTo` in Java or `SerializeDelimitedToOstream` in C++.
+    ///
+    /// [varint]: https://protobuf.dev/programming-guides/encoding/#varints
+    VarintLengthDelimited(VarintLengthDelimitedDecoderConfig),
 }
 
 impl From<BytesDecoderConfig> for FramingConfig {
@@ -112,12 +140,24 @@ impl From<CharacterDelimitedDecoderConfig> for FramingConfig {
     }
 }
 
+impl From<ChunkedGelfDecoderConfig> for FramingConfig {
+    fn from(config: ChunkedGelfDecoderConfig) -> Self {
+        Self::ChunkedGelf(config)
+    }
+}
+
 impl From<LengthDelimitedDecoderConfig> for FramingConfig {
     fn from(config: LengthDelimitedDecoderConfig) -> Self {
         Self::LengthDelimited(config)
     }
 }
 
+impl From<MultilineDecoderConfig> for FramingConfig {
+    fn from(config: MultilineDecoderConfig) -> Self {
+        Self::Multiline(config)
+    }
+}
+
 impl From<NewlineDelimitedDecoderConfig> for FramingConfig {
     fn from(config: NewlineDelimitedDecoderConfig) -> Self {
         Self::NewlineDelimited(config)
@@ -130,16 +170,27 @@ impl From<OctetCountingDecoderConfig> for FramingConfig {
     }
 }
 
+impl From<VarintLengthDelimitedDecoderConfig> for FramingConfig {
+    fn from(config: VarintLengthDelimitedDecoderConfig) -> Self {
+        Self::VarintLengthDelimited(config)
+    }
+}
+
 impl FramingConfig {
     /// Build the `Framer` from this configuration.
-    pub fn build(&self) -> Framer {
-        match self {
+    pub fn build(&self) -> vector_common::Result<Framer> {
+        Ok(match self {
             FramingConfig::Bytes => Framer::Bytes(BytesDecoderConfig.build()),
             FramingConfig::CharacterDelimited(config) => Framer::CharacterDelimited(config.build()),
+            FramingConfig::ChunkedGelf(config) => Framer::ChunkedGelf(config.build()),
             FramingConfig::LengthDelimited(config) => Framer::LengthDelimited(config.build()),
+            FramingConfig::Multiline(config) => Framer::Multiline(config.build()?),
             FramingConfig::NewlineDelimited(config) => Framer::NewlineDelimited(config.build()),
             FramingConfig::OctetCounting(config) => Framer::OctetCounting(config.build()),
-        }
+            FramingConfig::VarintLengthDelimited(config) => {
+                Framer::VarintLengthDelimited(config.build())
+            }
+        })
     }
 }
 
@@ -150,12 +201,18 @@ pub enum Framer {
     Bytes(BytesDecoder),
     /// Uses a `CharacterDelimitedDecoder` for framing.
     CharacterDelimited(CharacterDelimitedDecoder),
+    /// Uses a `ChunkedGelfDecoder` for framing.
+    ChunkedGelf(ChunkedGelfDecoder),
     /// Uses a `LengthDelimitedDecoder` for framing.
     LengthDelimited(LengthDelimitedDecoder),
+    /// Uses a `MultilineDecoder` for framing.
+    Multiline(MultilineDecoder),
     /// Uses a `NewlineDelimitedDecoder` for framing.
     NewlineDelimited(NewlineDelimitedDecoder),
     /// Uses a `OctetCountingDecoder` for framing.
     OctetCounting(OctetCountingDecoder),
+    /// Uses a `VarintLengthDelimitedDecoder` for framing.
+    VarintLengthDelimited(VarintLengthDelimitedDecoder),
     /// Uses an opaque `Framer` implementation for framing.
     Boxed(BoxedFramer),
 }
@@ -168,9 +225,12 @@ impl tokio_util::codec::Decoder for Framer {
         match self {
             Framer::Bytes(framer) => framer.decode(src),
             Framer::CharacterDelimited(framer) => framer.decode(src),
+            Framer::ChunkedGelf(framer) => framer.decode(src),
             Framer::LengthDelimited(framer) => framer.decode(src),
+            Framer::Multiline(framer) => framer.decode(src),
             Framer::NewlineDelimited(framer) => framer.decode(src),
             Framer::OctetCounting(framer) => framer.decode(src),
+            Framer::VarintLengthDelimited(framer) => framer.decode(src),
             Framer::Boxed(framer) => framer.decode(src),
         }
     }
@@ -179,9 +239,12 @@ impl tokio_util::codec::Decoder for Framer {
         match self {
             Framer::Bytes(framer) => framer.decode_eof(src),
             Framer::CharacterDelimited(framer) => framer.decode_eof(src),
+            Framer::ChunkedGelf(framer) => framer.decode_eof(src),
             Framer::LengthDelimited(framer) => framer.decode_eof(src),
+            Framer::Multiline(framer) => framer.decode_eof(src),
             Framer::NewlineDelimited(framer) => framer.decode_eof(src),
             Framer::OctetCounting(framer) => framer.decode_eof(src),
+            Framer::VarintLengthDelimited(framer) => framer.decode_eof(src),
             Framer::Boxed(framer) => framer.decode_eof(src),
         }
     }
@@ -202,6 +265,22 @@ pub enum DeserializerConfig {
     /// [json]: https://www.json.org/
     Json(JsonDeserializerConfig),
 
+    /// Decodes the raw bytes as [CSV][csv] rows.
+    ///
+    /// Each row becomes an event, with a field for each of its columns.
+    ///
+    /// [csv]: https://datatracker.ietf.org/doc/html/rfc4180
+    Csv(CsvDeserializerConfig),
+
+    /// Decodes the raw bytes as [MessagePack][msgpack].
+    ///
+    /// Each value of the frame becomes an event, and the values of arrays at its root become
+    /// separate events. Timestamps, including the `EventTime` values of the Fluentd forward
+    /// protocol, are decoded as timestamps.
+    ///
+    /// [msgpack]: https://msgpack.org/
+    Msgpack,
+
     /// Decodes the raw bytes as [protobuf][protobuf].
     ///
     /// [protobuf]: https://protobuf.dev/
@@ -233,6 +312,15 @@ pub enum DeserializerConfig {
     /// [experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
     NativeJson(NativeJsonDeserializerConfig),
 
+    #[cfg(feature = "otlp")]
+    /// Decodes the raw bytes as an [OTLP][otlp] export request encoded as protobuf.
+    ///
+    /// Each log record or metric data point of the request becomes an event, converted the same
+    /// way as with the `opentelemetry` source.
+    ///
+    /// [otlp]: https://opentelemetry.io/docs/specs/otlp/
+    Otlp(OtlpDeserializerConfig),
+
     /// Decodes the raw bytes as a [GELF][gelf] message.
     ///
     /// This codec is experimental for the following reason:
@@ -285,6 +373,18 @@ impl From<SyslogDeserializerConfig> for DeserializerConfig {
     }
 }
 
+impl From<CsvDeserializerConfig> for DeserializerConfig {
+    fn from(config: CsvDeserializerConfig) -> Self {
+        Self::Csv(config)
+    }
+}
+
+impl From<MsgpackDeserializerConfig> for DeserializerConfig {
+    fn from(_: MsgpackDeserializerConfig) -> Self {
+        Self::Msgpack
+    }
+}
+
 impl From<GelfDeserializerConfig> for DeserializerConfig {
     fn from(config: GelfDeserializerConfig) -> Self {
         Self::Gelf(config)
@@ -303,6 +403,13 @@ impl From<NativeJsonDeserializerConfig> for DeserializerConfig {
     }
 }
 
+#[cfg(feature = "otlp")]
+impl From<OtlpDeserializerConfig> for DeserializerConfig {
+    fn from(config: OtlpDeserializerConfig) -> Self {
+        Self::Otlp(config)
+    }
+}
+
 impl DeserializerConfig {
     /// Build the `Deserializer` from this configuration.
     pub fn build(&self) -> vector_common::Result<Deserializer> {
@@ -311,10 +418,14 @@ impl DeserializerConfig {
                 AvroDeserializerConfig {
                     avro_options: avro.clone(),
                 }
-                .build(),
+                .build()?,
             )),
             DeserializerConfig::Bytes => Ok(Deserializer::Bytes(BytesDeserializerConfig.build())),
             DeserializerConfig::Json(config) => Ok(Deserializer::Json(config.build())),
+            DeserializerConfig::Csv(config) => Ok(Deserializer::Csv(config.build())),
+            DeserializerConfig::Msgpack => {
+                Ok(Deserializer::Msgpack(MsgpackDeserializerConfig.build()))
+            }
             DeserializerConfig::Protobuf(config) => Ok(Deserializer::Protobuf(config.build()?)),
             #[cfg(feature = "syslog")]
             DeserializerConfig::Syslog(config) => Ok(Deserializer::Syslog(config.build())),
@@ -322,6 +433,8 @@ impl DeserializerConfig {
                 Ok(Deserializer::Native(NativeDeserializerConfig.build()))
             }
             DeserializerConfig::NativeJson(config) => Ok(Deserializer::NativeJson(config.build())),
+            #[cfg(feature = "otlp")]
+            DeserializerConfig::Otlp(config) => Ok(Deserializer::Otlp(config.build())),
             DeserializerConfig::Gelf(config) => Ok(Deserializer::Gelf(config.build())),
             DeserializerConfig::Vrl(config) => Ok(Deserializer::Vrl(config.build()?)),
         }
@@ -334,11 +447,14 @@ impl DeserializerConfig {
             DeserializerConfig::Native => FramingConfig::LengthDelimited(Default::default()),
             DeserializerConfig::Bytes
             | DeserializerConfig::Json(_)
+            | DeserializerConfig::Csv(_)
             | DeserializerConfig::Gelf(_)
             | DeserializerConfig::NativeJson(_) => {
                 FramingConfig::NewlineDelimited(Default::default())
             }
-            DeserializerConfig::Protobuf(_) => FramingConfig::Bytes,
+            DeserializerConfig::Msgpack | DeserializerConfig::Protobuf(_) => FramingConfig::Bytes,
+            #[cfg(feature = "otlp")]
+            DeserializerConfig::Otlp(_) => FramingConfig::Bytes,
             #[cfg(feature = "syslog")]
             DeserializerConfig::Syslog(_) => FramingConfig::NewlineDelimited(Default::default()),
             DeserializerConfig::Vrl(_) => FramingConfig::Bytes,
@@ -354,11 +470,15 @@ impl DeserializerConfig {
             .output_type(),
             DeserializerConfig::Bytes => BytesDeserializerConfig.output_type(),
             DeserializerConfig::Json(config) => config.output_type(),
+            DeserializerConfig::Csv(config) => config.output_type(),
+            DeserializerConfig::Msgpack => MsgpackDeserializerConfig.output_type(),
             DeserializerConfig::Protobuf(config) => config.output_type(),
             #[cfg(feature = "syslog")]
             DeserializerConfig::Syslog(config) => config.output_type(),
             DeserializerConfig::Native => NativeDeserializerConfig.output_type(),
             DeserializerConfig::NativeJson(config) => config.output_type(),
+            #[cfg(feature = "otlp")]
+            DeserializerConfig::Otlp(config) => config.output_type(),
             DeserializerConfig::Gelf(config) => config.output_type(),
             DeserializerConfig::Vrl(config) => config.output_type(),
         }
@@ -373,11 +493,17 @@ impl DeserializerConfig {
             .schema_definition(log_namespace),
             DeserializerConfig::Bytes => BytesDeserializerConfig.schema_definition(log_namespace),
             DeserializerConfig::Json(config) => config.schema_definition(log_namespace),
+            DeserializerConfig::Csv(config) => config.schema_definition(log_namespace),
+            DeserializerConfig::Msgpack => {
+                MsgpackDeserializerConfig.schema_definition(log_namespace)
+            }
             DeserializerConfig::Protobuf(config) => config.schema_definition(log_namespace),
             #[cfg(feature = "syslog")]
             DeserializerConfig::Syslog(config) => config.schema_definition(log_namespace),
             DeserializerConfig::Native => NativeDeserializerConfig.schema_definition(log_namespace),
             DeserializerConfig::NativeJson(config) => config.schema_definition(log_namespace),
+            #[cfg(feature = "otlp")]
+            DeserializerConfig::Otlp(config) => config.schema_definition(log_namespace),
             DeserializerConfig::Gelf(config) => config.schema_definition(log_namespace),
             DeserializerConfig::Vrl(config) => config.schema_definition(log_namespace),
         }
@@ -406,6 +532,8 @@ impl DeserializerConfig {
                 "application/octet-stream"
             }
             (DeserializerConfig::Protobuf(_), _) => "application/octet-stream",
+            (DeserializerConfig::Msgpack, _) => "application/msgpack",
+            (DeserializerConfig::Csv(_), _) => "text/csv",
             (
                 DeserializerConfig::Json(_)
                 | DeserializerConfig::NativeJson(_)
@@ -414,6 +542,8 @@ impl DeserializerConfig {
                 | DeserializerConfig::Vrl(_),
                 _,
             ) => "text/plain",
+            #[cfg(feature = "otlp")]
+            (DeserializerConfig::Otlp(_), _) => "application/x-protobuf",
             #[cfg(feature = "syslog")]
             (DeserializerConfig::Syslog(_), _) => "text/plain",
         }
@@ -429,6 +559,10 @@ pub enum Deserializer {
     Bytes(BytesDeserializer),
     /// Uses a `JsonDeserializer` for deserialization.
     Json(JsonDeserializer),
+    /// Uses a `CsvDeserializer` for deserialization.
+    Csv(CsvDeserializer),
+    /// Uses a `MsgpackDeserializer` for deserialization.
+    Msgpack(MsgpackDeserializer),
     /// Uses a `ProtobufDeserializer` for deserialization.
     Protobuf(ProtobufDeserializer),
     #[cfg(feature = "syslog")]
@@ -438,6 +572,9 @@ pub enum Deserializer {
     Native(NativeDeserializer),
     /// Uses a `NativeDeserializer` for deserialization.
     NativeJson(NativeJsonDeserializer),
+    #[cfg(feature = "otlp")]
+    /// Uses an `OtlpDeserializer` for deserialization.
+    Otlp(OtlpDeserializer),
     /// Uses an opaque `Deserializer` implementation for deserialization.
     Boxed(BoxedDeserializer),
     /// Uses a `GelfDeserializer` for deserialization.
@@ -456,11 +593,15 @@ impl format::Deserializer for Deserializer {
             Deserializer::Avro(deserializer) => deserializer.parse(bytes, log_namespace),
             Deserializer::Bytes(deserializer) => deserializer.parse(bytes, log_namespace),
             Deserializer::Json(deserializer) => deserializer.parse(bytes, log_namespace),
+            Deserializer::Csv(deserializer) => deserializer.parse(bytes, log_namespace),
+            Deserializer::Msgpack(deserializer) => deserializer.parse(bytes, log_namespace),
             Deserializer::Protobuf(deserializer) => deserializer.parse(bytes, log_namespace),
             #[cfg(feature = "syslog")]
             Deserializer::Syslog(deserializer) => deserializer.parse(bytes, log_namespace),
             Deserializer::Native(deserializer) => deserializer.parse(bytes, log_namespace),
             Deserializer::NativeJson(deserializer) => deserializer.parse(bytes, log_namespace),
+            #[cfg(feature = "otlp")]
+            Deserializer::Otlp(deserializer) => deserializer.parse(bytes, log_namespace),
             Deserializer::Boxed(deserializer) => deserializer.parse(bytes, log_namespace),
             Deserializer::Gelf(deserializer) => deserializer.parse(bytes, log_namespace),
             Deserializer::Vrl(deserializer) => deserializer.parse(bytes, log_namespace),
@@ -0,0 +1,64 @@
+//! Serializers that encode all the events of a batch together into a single payload, for the
+//! formats that can't be written one event at a time.
+
+use vector_config::configurable_component;
+use vector_core::event::Event;
+
+use super::format::{ParquetSerializer, ParquetSerializerOptions};
+
+/// Configures how batches of events are encoded together into a single payload.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(tag = "codec", rename_all = "snake_case")]
+#[configurable(metadata(
+    docs::enum_tag_description = "The codec to use for encoding batches of events."
+))]
+pub enum BatchSerializerConfig {
+    /// Encodes each batch of events as an [Apache Parquet][apache_parquet] file.
+    ///
+    /// [apache_parquet]: https://parquet.apache.org/
+    Parquet {
+        /// Apache Parquet-specific encoder options.
+        #[serde(default)]
+        parquet: ParquetSerializerOptions,
+    },
+}
+
+impl BatchSerializerConfig {
+    /// Build the `BatchSerializer` from this configuration.
+    pub fn build(&self) -> vector_common::Result<BatchSerializer> {
+        match self {
+            Self::Parquet { parquet } => parquet.build().map(BatchSerializer::Parquet),
+        }
+    }
+}
+
+/// Serializers that encode all the events of a batch into a single payload.
+#[derive(Clone, Debug)]
+pub enum BatchSerializer {
+    /// Uses a `ParquetSerializer` for serialization.
+    Parquet(ParquetSerializer),
+}
+
+impl BatchSerializer {
+    /// Encodes the events into a single payload.
+    pub fn encode(&self, events: &[Event]) -> vector_common::Result<Vec<u8>> {
+        match self {
+            Self::Parquet(serializer) => serializer.encode(events),
+        }
+    }
+
+    /// The content type of the payloads of this serializer.
+    pub const fn content_type(&self) -> &'static str {
+        match self {
+            Self::Parquet(_) => "application/vnd.apache.parquet",
+        }
+    }
+
+    /// The filename extension of the payloads of this serializer.
+    pub const fn extension(&self) -> &'static str {
+        match self {
+            Self::Parquet(_) => "parquet",
+        }
+    }
+}
@@ -0,0 +1,292 @@
+//! Splitting of encoded events into chunks, for message-based transports that limit the size of
+//! their messages.
+
+use std::io::Write;
+
+use bytes::{BufMut, Bytes, BytesMut};
+use flate2::write::{GzEncoder, ZlibEncoder};
+use vector_config::configurable_component;
+
+use super::BuildError;
+use crate::gelf::{GELF_CHUNK_HEADER_LENGTH, GELF_CHUNK_MAGIC, GELF_MAX_CHUNKS};
+
+const fn default_max_chunk_size() -> usize {
+    8192
+}
+
+/// Chunking configuration.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(tag = "method", rename_all = "snake_case")]
+#[configurable(metadata(docs::enum_tag_description = "The chunking method."))]
+pub enum ChunkingConfig {
+    /// Encoded events are compressed and split into [GELF chunks][chunking], as read by the
+    /// Graylog UDP inputs.
+    ///
+    /// [chunking]: https://go2docs.graylog.org/current/getting_in_log_data/gelf.html#GELFviaUDP
+    Gelf(GelfChunkerConfig),
+}
+
+impl ChunkingConfig {
+    /// Build the `Chunker` from this configuration.
+    pub fn build(&self) -> Result<Chunker, BuildError> {
+        match self {
+            ChunkingConfig::Gelf(config) => config.build().map(Chunker::Gelf),
+        }
+    }
+}
+
+/// Config used to build a `GelfChunker`.
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+pub struct GelfChunkerConfig {
+    /// Options for the GELF chunker.
+    #[serde(default)]
+    pub gelf: GelfChunkerOptions,
+}
+
+impl GelfChunkerConfig {
+    /// Build the `GelfChunker` from this configuration.
+    pub fn build(&self) -> Result<GelfChunker, BuildError> {
+        let max_chunk_size = self.gelf.max_chunk_size;
+        if max_chunk_size <= GELF_CHUNK_HEADER_LENGTH {
+            return Err(format!(
+                "The maximum chunk size must be larger than the {} bytes of the chunk header.",
+                GELF_CHUNK_HEADER_LENGTH
+            )
+            .into());
+        }
+        Ok(GelfChunker {
+            max_chunk_size,
+            compression: self.gelf.compression,
+        })
+    }
+}
+
+/// Options for building a `GelfChunker`.
+#[configurable_component]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GelfChunkerOptions {
+    /// The maximum size of the chunks, including their header.
+    ///
+    /// Messages that fit in a chunk are sent as-is. Larger messages are split into chunks, of which
+    /// there can be at most 128. Graylog accepts chunks of up to 8192 bytes, but smaller chunks, of
+    /// up to 1420 bytes, are less likely to be fragmented by the network.
+    #[serde(default = "default_max_chunk_size")]
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    pub max_chunk_size: usize,
+
+    /// The compression applied to messages before they're split into chunks.
+    #[serde(default, skip_serializing_if = "vector_core::serde::is_default")]
+    pub compression: GelfCompression,
+}
+
+impl Default for GelfChunkerOptions {
+    fn default() -> Self {
+        Self {
+            max_chunk_size: default_max_chunk_size(),
+            compression: GelfCompression::default(),
+        }
+    }
+}
+
+/// The compression of GELF messages.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum GelfCompression {
+    /// Messages are not compressed.
+    #[default]
+    None,
+
+    /// Messages are compressed with [gzip][gzip].
+    ///
+    /// [gzip]: https://www.gzip.org/
+    Gzip,
+
+    /// Messages are compressed with [zlib][zlib].
+    ///
+    /// [zlib]: https://www.zlib.net/
+    Zlib,
+}
+
+/// Chunkers that split encoded events into the messages of a transport.
+#[derive(Clone, Debug)]
+pub enum Chunker {
+    /// Uses a `GelfChunker` for chunking.
+    Gelf(GelfChunker),
+}
+
+impl Chunker {
+    /// Splits the encoded event into the messages sent for it.
+    pub fn chunk(&self, bytes: Bytes) -> vector_common::Result<Vec<Bytes>> {
+        match self {
+            Chunker::Gelf(chunker) => chunker.chunk(bytes),
+        }
+    }
+}
+
+/// A chunker that compresses GELF messages, and splits the ones that are too large into
+/// [GELF chunks][chunking].
+///
+/// [chunking]: https://go2docs.graylog.org/current/getting_in_log_data/gelf.html#GELFviaUDP
+#[derive(Clone, Debug)]
+pub struct GelfChunker {
+    max_chunk_size: usize,
+    compression: GelfCompression,
+}
+
+impl GelfChunker {
+    /// Compresses the message, and splits it into chunks if it's larger than the maximum chunk
+    /// size.
+    pub fn chunk(&self, message: Bytes) -> vector_common::Result<Vec<Bytes>> {
+        let message = self.compress(message)?;
+        if message.len() <= self.max_chunk_size {
+            return Ok(vec![message]);
+        }
+
+        let payload_size = self.max_chunk_size - GELF_CHUNK_HEADER_LENGTH;
+        let count = message.len().div_ceil(payload_size);
+        if count > GELF_MAX_CHUNKS {
+            return Err(format!(
+                "The GELF message of {} bytes would be split into {} chunks, more than the maximum of {}.",
+                message.len(),
+                count,
+                GELF_MAX_CHUNKS
+            )
+            .into());
+        }
+
+        let id = rand::random::<u64>().to_be_bytes();
+        Ok(message
+            .chunks(payload_size)
+            .enumerate()
+            .map(|(number, payload)| {
+                let mut chunk = BytesMut::with_capacity(GELF_CHUNK_HEADER_LENGTH + payload.len());
+                chunk.put_slice(&GELF_CHUNK_MAGIC);
+                chunk.put_slice(&id);
+                chunk.put_u8(number as u8);
+                chunk.put_u8(count as u8);
+                chunk.put_slice(payload);
+                chunk.freeze()
+            })
+            .collect())
+    }
+
+    fn compress(&self, message: Bytes) -> std::io::Result<Bytes> {
+        let compressed = match self.compression {
+            GelfCompression::None => return Ok(message),
+            GelfCompression::Gzip => {
+                let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder.write_all(&message)?;
+                encoder.finish()?
+            }
+            GelfCompression::Zlib => {
+                let mut encoder = ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder.write_all(&message)?;
+                encoder.finish()?
+            }
+        };
+        Ok(compressed.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fmt::Write;
+
+    use tokio_util::codec::Decoder;
+
+    use super::*;
+    use crate::decoding::{ChunkedGelfDecoderConfig, ChunkedGelfDecoderOptions};
+
+    fn chunker(max_chunk_size: usize, compression: GelfCompression) -> GelfChunker {
+        GelfChunkerConfig {
+            gelf: GelfChunkerOptions {
+                max_chunk_size,
+                compression,
+            },
+        }
+        .build()
+        .unwrap()
+    }
+
+    #[test]
+    fn sends_small_messages_whole() {
+        let chunks = chunker(100, GelfCompression::None)
+            .chunk(Bytes::from(r#"{"short_message":"foo"}"#))
+            .unwrap();
+
+        assert_eq!(chunks, vec![Bytes::from(r#"{"short_message":"foo"}"#)]);
+    }
+
+    #[test]
+    fn splits_large_messages() {
+        let message = Bytes::from("abcdefghij".repeat(10));
+        let chunks = chunker(32, GelfCompression::None)
+            .chunk(message.clone())
+            .unwrap();
+
+        // Each chunk holds 20 bytes after its header.
+        assert_eq!(chunks.len(), 5);
+        for (number, chunk) in chunks.iter().enumerate() {
+            assert_eq!(chunk.len(), 32);
+            assert_eq!(chunk[..2], GELF_CHUNK_MAGIC);
+            assert_eq!(chunk[2..10], chunks[0][2..10]);
+            assert_eq!(chunk[10..12], [number as u8, 5]);
+        }
+        let payload = chunks
+            .iter()
+            .flat_map(|chunk| chunk[GELF_CHUNK_HEADER_LENGTH..].to_vec())
+            .collect::<Vec<_>>();
+        assert_eq!(payload, message);
+    }
+
+    #[test]
+    fn round_trips_through_the_decoder() {
+        let message = Bytes::from((0..1000).fold(String::new(), |mut message, index| {
+            write!(message, "{:x}", index * 7919).unwrap();
+            message
+        }));
+        let mut decoder = ChunkedGelfDecoderConfig {
+            chunked_gelf: ChunkedGelfDecoderOptions::default(),
+        }
+        .build();
+
+        for compression in [
+            GelfCompression::None,
+            GelfCompression::Gzip,
+            GelfCompression::Zlib,
+        ] {
+            let chunks = chunker(256, compression).chunk(message.clone()).unwrap();
+            assert!(chunks.len() > 1);
+
+            let (last, chunks) = chunks.split_last().unwrap();
+            for chunk in chunks.iter().rev() {
+                assert_eq!(
+                    decoder.decode_eof(&mut BytesMut::from(&chunk[..])).unwrap(),
+                    None
+                );
+            }
+            assert_eq!(
+                decoder.decode_eof(&mut BytesMut::from(&last[..])).unwrap(),
+                Some(message.clone())
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_messages_with_too_many_chunks() {
+        assert!(chunker(13, GelfCompression::None)
+            .chunk(Bytes::from(vec![b'a'; 129]))
+            .is_err());
+        assert!(GelfChunkerConfig {
+            gelf: GelfChunkerOptions {
+                max_chunk_size: 12,
+                compression: GelfCompression::None,
+            },
+        }
+        .build()
+        .is_err());
+    }
+}
@@ -0,0 +1,388 @@
+use std::{collections::BTreeMap, fmt::Write as _};
+
+use bytes::BytesMut;
+use derivative::Derivative;
+use lookup::lookup_v2::ConfigTargetPath;
+use tokio_util::codec::Encoder;
+use vector_core::{
+    config::DataType,
+    event::{Event, LogEvent, Value},
+    schema,
+};
+
+use crate::encoding::BuildError;
+
+/// The severity of the messages that have no valid severity, in the middle of the scale.
+const DEFAULT_SEVERITY: &str = "5";
+
+/// The names of the severities, as written by CEF producers.
+const SEVERITIES: [&str; 5] = ["Unknown", "Low", "Medium", "High", "Very-High"];
+
+/// The version of the CEF format.
+#[crate::configurable_component]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CefVersion {
+    /// Version 0 of the format, which all CEF consumers read.
+    #[default]
+    V0,
+
+    /// Version 1 of the format, which adds the extensions holding IPv6 addresses.
+    V1,
+}
+
+impl CefVersion {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::V0 => "0",
+            Self::V1 => "1",
+        }
+    }
+}
+
+/// Config used to build a `CefSerializer`.
+#[crate::configurable_component]
+#[derive(Debug, Clone, Default)]
+pub struct CefSerializerConfig {
+    /// Options for the CEF serializer.
+    #[serde(default, skip_serializing_if = "vector_core::serde::is_default")]
+    pub cef: CefSerializerOptions,
+}
+
+impl CefSerializerConfig {
+    /// Creates a new `CefSerializerConfig`.
+    pub const fn new(cef: CefSerializerOptions) -> Self {
+        Self { cef }
+    }
+
+    /// Build the `CefSerializer` from this configuration.
+    pub fn build(&self) -> Result<CefSerializer, BuildError> {
+        if let Some(key) = self
+            .cef
+            .extensions
+            .keys()
+            .find(|key| key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric()))
+        {
+            return Err(format!(
+                "CEF extension key {key:?} must only contain ASCII letters and digits"
+            )
+            .into());
+        }
+
+        Ok(CefSerializer::new(self.cef.clone()))
+    }
+
+    /// The data type of events that are accepted by `CefSerializer`.
+    pub fn input_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    /// The schema required by the serializer.
+    pub fn schema_requirement(&self) -> schema::Requirement {
+        schema::Requirement::empty()
+    }
+}
+
+/// CEF serializer options.
+///
+/// The vendor, product, and version of the device are the same for all the messages, and the
+/// other header fields and the extensions are taken from the fields of the log events.
+#[crate::configurable_component]
+#[derive(Debug, Clone, Derivative, PartialEq)]
+#[derivative(Default)]
+pub struct CefSerializerOptions {
+    /// The version of the CEF format.
+    #[serde(default, skip_serializing_if = "vector_core::serde::is_default")]
+    pub version: CefVersion,
+
+    /// The vendor of the device that sends the messages.
+    #[serde(default = "default_device")]
+    #[derivative(Default(value = "default_device()"))]
+    pub device_vendor: String,
+
+    /// The product of the device that sends the messages.
+    #[serde(default = "default_device")]
+    #[derivative(Default(value = "default_device()"))]
+    pub device_product: String,
+
+    /// The version of the device that sends the messages.
+    ///
+    /// If not set, the header field is left empty.
+    #[configurable(metadata(docs::examples = "1.0"))]
+    #[serde(default, skip_serializing_if = "vector_core::serde::is_default")]
+    pub device_version: Option<String>,
+
+    /// The field of the log events that holds the ID of the type of the messages, such as a
+    /// signature ID.
+    ///
+    /// If the field isn't set, `0` is used.
+    #[configurable(metadata(docs::examples = "event_id"))]
+    #[serde(default, skip_serializing_if = "vector_core::serde::is_default")]
+    pub device_event_class_id_key: Option<ConfigTargetPath>,
+
+    /// The field of the log events that holds the human-readable description of the messages.
+    ///
+    /// If not set, the message of the events is used.
+    #[configurable(metadata(docs::examples = "title"))]
+    #[serde(default, skip_serializing_if = "vector_core::serde::is_default")]
+    pub name_key: Option<ConfigTargetPath>,
+
+    /// The field of the log events that holds the severity of the messages.
+    ///
+    /// The field can hold a number from `0` to `10`, or the name of a severity, such as `Low` or
+    /// `Very-High`. If the field isn't set, or doesn't hold a valid severity, `5` is used.
+    #[configurable(metadata(docs::examples = "severity"))]
+    #[serde(default, skip_serializing_if = "vector_core::serde::is_default")]
+    pub severity_key: Option<ConfigTargetPath>,
+
+    /// The extensions of the messages, mapping their keys to the fields of the log events that
+    /// hold their values.
+    ///
+    /// The keys must only contain ASCII letters and digits, such as the keys of the CEF
+    /// dictionary, like `src` or `suser`. Timestamps are written as milliseconds since the Unix
+    /// epoch, and the extensions whose field isn't set are left out.
+    #[configurable(metadata(docs::examples = "extensions_examples()"))]
+    #[configurable(metadata(
+        docs::additional_props_description = "The field of the log events that holds the value of the extension."
+    ))]
+    #[serde(default, skip_serializing_if = "vector_core::serde::is_default")]
+    pub extensions: BTreeMap<String, ConfigTargetPath>,
+}
+
+fn default_device() -> String {
+    "Vector".to_owned()
+}
+
+fn extensions_examples() -> BTreeMap<String, String> {
+    BTreeMap::from([
+        ("msg".to_owned(), "message".to_owned()),
+        ("src".to_owned(), "client.ip".to_owned()),
+        ("suser".to_owned(), "user.name".to_owned()),
+    ])
+}
+
+/// Parses a severity from its number or its name.
+fn parse_severity(value: &str) -> Option<String> {
+    let value = value.trim();
+    match value.parse::<u8>() {
+        Ok(severity) => (severity <= 10).then(|| severity.to_string()),
+        Err(_) => SEVERITIES
+            .iter()
+            .find(|name| name.eq_ignore_ascii_case(value))
+            .map(|name| (*name).to_owned()),
+    }
+}
+
+/// Writes a header field, escaping the characters that delimit the header and truncating the
+/// field to its maximum length.
+///
+/// Header fields can't span multiple lines, so line breaks are replaced with spaces.
+fn write_header_field(output: &mut String, value: &str, max_len: usize) {
+    for c in value.chars().take(max_len) {
+        match c {
+            '\\' | '|' => {
+                output.push('\\');
+                output.push(c);
+            }
+            '\n' | '\r' => output.push(' '),
+            c => output.push(c),
+        }
+    }
+    output.push('|');
+}
+
+/// Writes the value of an extension, escaping the characters that delimit the extensions.
+fn write_extension_value(output: &mut String, value: &str) {
+    for c in value.chars() {
+        match c {
+            '\\' | '=' => {
+                output.push('\\');
+                output.push(c);
+            }
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            c => output.push(c),
+        }
+    }
+}
+
+/// Serializer that converts an `Event` to bytes using the CEF format.
+#[derive(Debug, Clone)]
+pub struct CefSerializer {
+    options: CefSerializerOptions,
+}
+
+impl CefSerializer {
+    /// Creates a new `CefSerializer`.
+    pub const fn new(options: CefSerializerOptions) -> Self {
+        Self { options }
+    }
+}
+
+/// Returns the string held by a field of the log event, if the field is set.
+fn get_string(log: &LogEvent, key: Option<&ConfigTargetPath>) -> Option<String> {
+    key.and_then(|key| log.get(key)).map(|value| match value {
+        Value::Timestamp(timestamp) => timestamp.timestamp_millis().to_string(),
+        value => value.to_string_lossy().into_owned(),
+    })
+}
+
+impl Encoder<Event> for CefSerializer {
+    type Error = vector_common::Error;
+
+    fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
+        let log = event.into_log();
+        let options = &self.options;
+
+        let device_event_class_id = get_string(&log, options.device_event_class_id_key.as_ref());
+        let name = match &options.name_key {
+            Some(key) => get_string(&log, Some(key)),
+            None => log
+                .get_message()
+                .map(|message| message.to_string_lossy().into_owned()),
+        };
+        let severity = get_string(&log, options.severity_key.as_ref())
+            .and_then(|severity| parse_severity(&severity));
+
+        let mut output = format!("CEF:{}|", options.version.as_str());
+        write_header_field(&mut output, &options.device_vendor, 63);
+        write_header_field(&mut output, &options.device_product, 63);
+        write_header_field(
+            &mut output,
+            options.device_version.as_deref().unwrap_or_default(),
+            31,
+        );
+        write_header_field(
+            &mut output,
+            device_event_class_id.as_deref().unwrap_or("0"),
+            1023,
+        );
+        write_header_field(&mut output, name.as_deref().unwrap_or_default(), 512);
+        write_header_field(
+            &mut output,
+            severity.as_deref().unwrap_or(DEFAULT_SEVERITY),
+            10,
+        );
+
+        let mut separator = "";
+        for (key, field) in &options.extensions {
+            if let Some(value) = get_string(&log, Some(field)) {
+                _ = write!(output, "{separator}{key}=");
+                write_extension_value(&mut output, &value);
+                separator = " ";
+            }
+        }
+
+        buffer.extend_from_slice(output.as_bytes());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+
+    fn options() -> CefSerializerOptions {
+        CefSerializerOptions {
+            device_vendor: "Security".to_owned(),
+            device_product: "threatmanager".to_owned(),
+            device_version: Some("1.0".to_owned()),
+            device_event_class_id_key: Some("signature".into()),
+            name_key: Some("title".into()),
+            severity_key: Some("severity".into()),
+            extensions: BTreeMap::from([
+                ("src".to_owned(), "client.ip".into()),
+                ("msg".to_owned(), "message".into()),
+                ("rt".to_owned(), "timestamp".into()),
+                ("suser".to_owned(), "user".into()),
+            ]),
+            ..Default::default()
+        }
+    }
+
+    fn event() -> Event {
+        let mut log = LogEvent::from("Detected a threat.\nNo action needed.");
+        log.insert("signature", 100);
+        log.insert("title", "worm successfully stopped");
+        log.insert("severity", 10);
+        log.insert("client.ip", "10.0.0.1");
+        log.insert(
+            "timestamp",
+            Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap(),
+        );
+        log.into()
+    }
+
+    fn serialize(options: CefSerializerOptions, event: Event) -> String {
+        let mut buffer = BytesMut::new();
+        CefSerializerConfig::new(options)
+            .build()
+            .unwrap()
+            .encode(event, &mut buffer)
+            .unwrap();
+        String::from_utf8(buffer.to_vec()).unwrap()
+    }
+
+    #[test]
+    fn serializes_cef() {
+        assert_eq!(
+            serialize(options(), event()),
+            "CEF:0|Security|threatmanager|1.0|100|worm successfully stopped|10|\
+             msg=Detected a threat.\\nNo action needed. rt=1704164645000 src=10.0.0.1"
+        );
+    }
+
+    #[test]
+    fn escapes_fields() {
+        let mut event = event();
+        event
+            .as_mut_log()
+            .insert("title", "pipe | and \\ backslash\r\n");
+        event.as_mut_log().insert("client.ip", "a=b\\c|d");
+
+        let options = CefSerializerOptions {
+            version: CefVersion::V1,
+            device_vendor: "Vendor|Inc".to_owned(),
+            ..options()
+        };
+
+        assert_eq!(
+            serialize(options, event),
+            "CEF:1|Vendor\\|Inc|threatmanager|1.0|100|pipe \\| and \\\\ backslash  |10|\
+             msg=Detected a threat.\\nNo action needed. rt=1704164645000 src=a\\=b\\\\c|d"
+        );
+    }
+
+    #[test]
+    fn falls_back_to_defaults() {
+        let mut event = event();
+        event.as_mut_log().insert("severity", 11);
+
+        assert_eq!(
+            serialize(CefSerializerOptions::default(), event.clone()),
+            "CEF:0|Vector|Vector||0|Detected a threat. No action needed.|5|"
+        );
+        assert!(serialize(options(), event).contains("|5|"));
+    }
+
+    #[test]
+    fn parses_severities() {
+        assert_eq!(parse_severity("0").as_deref(), Some("0"));
+        assert_eq!(parse_severity(" 10 ").as_deref(), Some("10"));
+        assert_eq!(parse_severity("11"), None);
+        assert_eq!(parse_severity("very-high").as_deref(), Some("Very-High"));
+        assert_eq!(parse_severity("critical"), None);
+    }
+
+    #[test]
+    fn rejects_invalid_extension_keys() {
+        let config = CefSerializerConfig::new(CefSerializerOptions {
+            extensions: BTreeMap::from([("source ip".to_owned(), "client.ip".into())]),
+            ..Default::default()
+        });
+
+        assert!(config.build().is_err());
+    }
+}
@@ -1,3 +1,5 @@
+use std::fmt::Write as _;
+
 use bytes::{BufMut, BytesMut};
 use tokio_util::codec::Encoder;
 use vector_core::{config::DataType, event::Event, schema};
@@ -14,17 +16,36 @@ pub struct JsonSerializerConfig {
     /// metric.  When set to `full`, all metric tags are exposed as separate assignments.
     #[serde(default, skip_serializing_if = "vector_core::serde::is_default")]
     pub metric_tag_values: MetricTagValues,
+
+    /// Options for the JSON serializer.
+    #[serde(default, skip_serializing_if = "vector_core::serde::is_default")]
+    pub json: JsonSerializerOptions,
 }
 
 impl JsonSerializerConfig {
     /// Creates a new `JsonSerializerConfig`.
     pub const fn new(metric_tag_values: MetricTagValues) -> Self {
-        Self { metric_tag_values }
+        Self {
+            metric_tag_values,
+            json: JsonSerializerOptions::new(),
+        }
+    }
+
+    /// Sets whether the events are encoded as canonical JSON.
+    pub const fn with_canonical(mut self, canonical: bool) -> Self {
+        self.json.canonical = canonical;
+        self
+    }
+
+    /// Sets the options of the JSON serializer.
+    pub fn with_options(mut self, options: JsonSerializerOptions) -> Self {
+        self.json = options;
+        self
     }
 
     /// Build the `JsonSerializer` from this configuration.
-    pub const fn build(&self) -> JsonSerializer {
-        JsonSerializer::new(self.metric_tag_values)
+    pub fn build(&self) -> JsonSerializer {
+        JsonSerializer::new(self.metric_tag_values).with_options(self.json.clone())
     }
 
     /// The data type of events that are accepted by `JsonSerializer`.
@@ -40,16 +61,111 @@ impl JsonSerializerConfig {
     }
 }
 
+/// The format of the floating-point numbers of canonical JSON.
+#[crate::configurable_component]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum JsonFloatFormat {
+    /// The representation of the ECMAScript `Number.prototype.toString` method, such as `100` or
+    /// `1e+21`, as required by the [JSON Canonicalization Scheme][jcs].
+    ///
+    /// [jcs]: https://datatracker.ietf.org/doc/html/rfc8785
+    #[default]
+    Ecmascript,
+
+    /// The shortest representation that parses back to the same number, always with a fractional
+    /// part or an exponent, such as `100.0` or `1e21`, as written by the non-canonical encoding.
+    Shortest,
+}
+
+/// Options for the JSON serializer.
+#[crate::configurable_component]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct JsonSerializerOptions {
+    /// Encode the events as canonical JSON, as specified by the
+    /// [JSON Canonicalization Scheme][jcs].
+    ///
+    /// The keys of all objects are sorted by their UTF-16 code units, no whitespace is written
+    /// between the tokens, and floating-point numbers are written as set by `float_format`, so that
+    /// the same event is always encoded to the same bytes, such as for computing content hashes or
+    /// signatures over the encoded events.
+    ///
+    /// [jcs]: https://datatracker.ietf.org/doc/html/rfc8785
+    #[serde(default)]
+    pub canonical: bool,
+
+    /// The format of the floating-point numbers.
+    ///
+    /// This is only used when `canonical` is enabled.
+    #[serde(default, skip_serializing_if = "vector_core::serde::is_default")]
+    pub float_format: JsonFloatFormat,
+
+    /// The keys of the encoded events that are kept, when encoding canonical JSON.
+    ///
+    /// Unlike `only_fields`, which selects fields of log events before they are encoded, this
+    /// applies to the keys of the encoded JSON objects of all types of events, such as the `name`
+    /// or `tags` of metrics.
+    #[serde(default, skip_serializing_if = "vector_core::serde::is_default")]
+    #[configurable(metadata(docs::examples = "message"))]
+    pub only_keys: Option<Vec<String>>,
+
+    /// The keys of the encoded events that are removed, when encoding canonical JSON.
+    ///
+    /// Like `only_keys`, this applies to the keys of the encoded JSON objects of all types of
+    /// events, after `only_keys`.
+    #[serde(default, skip_serializing_if = "vector_core::serde::is_default")]
+    #[configurable(metadata(docs::examples = "password"))]
+    pub except_keys: Option<Vec<String>>,
+
+    /// The keys of the encoded events that are written first, in this order, when encoding
+    /// canonical JSON.
+    ///
+    /// The other keys are written after them, in the canonical order. The keys of nested objects
+    /// are always written in the canonical order.
+    #[serde(default, skip_serializing_if = "vector_core::serde::is_default")]
+    #[configurable(metadata(docs::examples = "timestamp", docs::examples = "message"))]
+    pub field_order: Vec<String>,
+}
+
+impl JsonSerializerOptions {
+    /// Creates the default `JsonSerializerOptions`, which encode non-canonical JSON.
+    pub const fn new() -> Self {
+        Self {
+            canonical: false,
+            float_format: JsonFloatFormat::Ecmascript,
+            only_keys: None,
+            except_keys: None,
+            field_order: Vec::new(),
+        }
+    }
+}
+
 /// Serializer that converts an `Event` to bytes using the JSON format.
 #[derive(Debug, Clone)]
 pub struct JsonSerializer {
     metric_tag_values: MetricTagValues,
+    options: JsonSerializerOptions,
 }
 
 impl JsonSerializer {
     /// Creates a new `JsonSerializer`.
     pub const fn new(metric_tag_values: MetricTagValues) -> Self {
-        Self { metric_tag_values }
+        Self {
+            metric_tag_values,
+            options: JsonSerializerOptions::new(),
+        }
+    }
+
+    /// Sets whether the events are encoded as canonical JSON.
+    pub const fn with_canonical(mut self, canonical: bool) -> Self {
+        self.options.canonical = canonical;
+        self
+    }
+
+    /// Sets the options of the serializer.
+    pub fn with_options(mut self, options: JsonSerializerOptions) -> Self {
+        self.options = options;
+        self
     }
 
     /// Encode event and represent it as JSON value.
@@ -61,12 +177,164 @@ impl JsonSerializer {
         }
         .map_err(|e| e.to_string().into())
     }
+
+    /// Writes the top-level object of canonical JSON, applying the key options.
+    fn write_canonical_event(&self, value: &serde_json::Value, buffer: &mut String) {
+        let float_format = self.options.float_format;
+        let serde_json::Value::Object(object) = value else {
+            return write_canonical(value, float_format, buffer);
+        };
+
+        let mut entries = object
+            .iter()
+            .filter(|(key, _)| {
+                self.options
+                    .only_keys
+                    .as_ref()
+                    .map_or(true, |keys| keys.contains(key))
+                    && self
+                        .options
+                        .except_keys
+                        .as_ref()
+                        .map_or(true, |keys| !keys.contains(key))
+            })
+            .collect::<Vec<_>>();
+        entries.sort_by(|(a, _), (b, _)| {
+            let position = |key: &String| {
+                self.options
+                    .field_order
+                    .iter()
+                    .position(|field| field == key)
+                    .unwrap_or(usize::MAX)
+            };
+            position(a)
+                .cmp(&position(b))
+                .then_with(|| a.encode_utf16().cmp(b.encode_utf16()))
+        });
+
+        write_canonical_object(entries, float_format, buffer);
+    }
+}
+
+/// Writes a JSON value with the keys of its objects in the canonical order.
+fn write_canonical(value: &serde_json::Value, float_format: JsonFloatFormat, buffer: &mut String) {
+    match value {
+        serde_json::Value::Number(number) => match number.as_f64() {
+            Some(float) if number.is_f64() => write_float(float, float_format, buffer),
+            _ => write!(buffer, "{number}").expect("writing to a string should not fail"),
+        },
+        serde_json::Value::Array(array) => {
+            buffer.push('[');
+            for (index, item) in array.iter().enumerate() {
+                if index > 0 {
+                    buffer.push(',');
+                }
+                write_canonical(item, float_format, buffer);
+            }
+            buffer.push(']');
+        }
+        serde_json::Value::Object(object) => {
+            let mut entries = object.iter().collect::<Vec<_>>();
+            entries.sort_by(|(a, _), (b, _)| a.encode_utf16().cmp(b.encode_utf16()));
+            write_canonical_object(entries, float_format, buffer);
+        }
+        // Strings are escaped the same way by `serde_json` and by the canonicalization scheme.
+        _ => buffer.push_str(&value.to_string()),
+    }
+}
+
+fn write_canonical_object(
+    entries: Vec<(&String, &serde_json::Value)>,
+    float_format: JsonFloatFormat,
+    buffer: &mut String,
+) {
+    buffer.push('{');
+    for (index, (key, value)) in entries.into_iter().enumerate() {
+        if index > 0 {
+            buffer.push(',');
+        }
+        buffer.push_str(&serde_json::Value::from(key.as_str()).to_string());
+        buffer.push(':');
+        write_canonical(value, float_format, buffer);
+    }
+    buffer.push('}');
+}
+
+/// Writes a finite floating-point number.
+fn write_float(value: f64, float_format: JsonFloatFormat, buffer: &mut String) {
+    match float_format {
+        JsonFloatFormat::Ecmascript => write_ecmascript_float(value, buffer),
+        JsonFloatFormat::Shortest => buffer.push_str(&serde_json::Value::from(value).to_string()),
+    }
+}
+
+/// Writes a finite floating-point number the way the ECMAScript `Number.prototype.toString` method
+/// does, from the shortest digits that parse back to the same number.
+fn write_ecmascript_float(value: f64, buffer: &mut String) {
+    if value == 0.0 {
+        // Negative zero is written as `0` as well.
+        buffer.push('0');
+        return;
+    }
+    if value < 0.0 {
+        buffer.push('-');
+    }
+
+    let scientific = format!("{:e}", value.abs());
+    let (mantissa, exponent) = scientific
+        .split_once('e')
+        .expect("scientific notation should have an exponent");
+    let digits = mantissa.replace('.', "");
+    let exponent = exponent
+        .parse::<i32>()
+        .expect("scientific notation should have a valid exponent");
+
+    // The position of the decimal point relative to the digits.
+    let point = exponent + 1;
+    let length = digits.len() as i32;
+    if length <= point && point <= 21 {
+        buffer.push_str(&digits);
+        buffer.push_str(&"0".repeat((point - length) as usize));
+    } else if 0 < point && point <= 21 {
+        let (integer, fraction) = digits.split_at(point as usize);
+        write!(buffer, "{integer}.{fraction}").expect("writing to a string should not fail");
+    } else if -6 < point && point <= 0 {
+        buffer.push_str("0.");
+        buffer.push_str(&"0".repeat(-point as usize));
+        buffer.push_str(&digits);
+    } else {
+        let (first, rest) = digits.split_at(1);
+        buffer.push_str(first);
+        if !rest.is_empty() {
+            write!(buffer, ".{rest}").expect("writing to a string should not fail");
+        }
+        let sign = if exponent < 0 { '-' } else { '+' };
+        write!(buffer, "e{sign}{}", exponent.abs()).expect("writing to a string should not fail");
+    }
 }
 
 impl Encoder<Event> for JsonSerializer {
     type Error = vector_common::Error;
 
     fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
+        if self.options.canonical {
+            let value = match event {
+                Event::Log(log) => serde_json::to_value(&log),
+                Event::Metric(mut metric) => {
+                    if self.metric_tag_values == MetricTagValues::Single {
+                        metric.reduce_tags_to_single();
+                    }
+                    serde_json::to_value(&metric)
+                }
+                Event::Trace(trace) => serde_json::to_value(&trace),
+            }?;
+
+            let mut json = String::new();
+            self.write_canonical_event(&value, &mut json);
+            buffer.put_slice(json.as_bytes());
+            return Ok(());
+        }
+
         let writer = buffer.writer();
         match event {
             Event::Log(log) => serde_json::to_writer(writer, &log),
@@ -191,6 +459,7 @@ mod tests {
         let bytes = serialize(
             JsonSerializerConfig {
                 metric_tag_values: MetricTagValues::Full,
+                ..Default::default()
             },
             metric2(),
         );
@@ -206,6 +475,7 @@ mod tests {
         let bytes = serialize(
             JsonSerializerConfig {
                 metric_tag_values: MetricTagValues::Single,
+                ..Default::default()
             },
             metric2(),
         );
@@ -216,6 +486,146 @@ mod tests {
         );
     }
 
+    #[test]
+    fn serialize_canonical_log() {
+        let event = Event::Log(LogEvent::from(btreemap! {
+            "z" => Value::from(btreemap! {
+                "b" => Value::from_f64_or_zero(1.5),
+                "a" => Value::from(vec![Value::from_f64_or_zero(100.0), Value::from("\u{e9}\n")]),
+            }),
+            "\u{fb01}" => Value::from(true),
+            "\u{1f600}" => Value::Null,
+        }));
+        let bytes = serialize(canonical(), event);
+
+        // In UTF-16, the surrogates of `\u{1f600}` are sorted before `\u{fb01}`.
+        assert_eq!(
+            bytes,
+            "{\"z\":{\"a\":[100,\"\u{e9}\\n\"],\"b\":1.5},\"\u{1f600}\":null,\"\u{fb01}\":true}"
+        );
+    }
+
+    #[test]
+    fn serialize_canonical_floats() {
+        let event = Event::Log(LogEvent::from(btreemap! {
+            "floats" => Value::from(vec![
+                Value::from_f64_or_zero(100.0),
+                Value::from_f64_or_zero(-0.0),
+                Value::from_f64_or_zero(123.456),
+                Value::from_f64_or_zero(-0.000_001),
+                Value::from_f64_or_zero(1e-7),
+                Value::from_f64_or_zero(1e21),
+                Value::from_f64_or_zero(1.5e300),
+            ]),
+            "integer" => Value::from(7),
+        }));
+        let bytes = serialize(canonical(), event);
+
+        assert_eq!(
+            bytes,
+            r#"{"floats":[100,0,123.456,-0.000001,1e-7,1e+21,1.5e+300],"integer":7}"#
+        );
+    }
+
+    #[test]
+    fn serialize_canonical_shortest_floats() {
+        let event = Event::Log(LogEvent::from(btreemap! {
+            "floats" => Value::from(vec![
+                Value::from_f64_or_zero(100.0),
+                Value::from_f64_or_zero(123.456),
+                Value::from_f64_or_zero(1e21),
+            ]),
+            "integer" => Value::from(7),
+        }));
+        let bytes = serialize(
+            canonical().with_options(JsonSerializerOptions {
+                canonical: true,
+                float_format: JsonFloatFormat::Shortest,
+                ..Default::default()
+            }),
+            event,
+        );
+
+        assert_eq!(bytes, r#"{"floats":[100.0,123.456,1e21],"integer":7}"#);
+    }
+
+    #[test]
+    fn serialize_canonical_keys() {
+        let event = Event::Log(LogEvent::from(btreemap! {
+            "message" => Value::from("foo"),
+            "password" => Value::from("bar"),
+            "timestamp" => Value::from("2024-01-01T00:00:00Z"),
+            "user" => Value::from(btreemap! {
+                "password" => Value::from("baz"),
+            }),
+            "other" => Value::from(1),
+        }));
+        let options = JsonSerializerOptions {
+            canonical: true,
+            only_keys: Some(vec![
+                "message".to_owned(),
+                "password".to_owned(),
+                "timestamp".to_owned(),
+                "user".to_owned(),
+            ]),
+            except_keys: Some(vec!["password".to_owned()]),
+            field_order: vec!["timestamp".to_owned(), "missing".to_owned()],
+            ..Default::default()
+        };
+        let bytes = serialize(canonical().with_options(options), event);
+
+        // The keys of nested objects are neither filtered nor reordered.
+        assert_eq!(
+            bytes,
+            r#"{"timestamp":"2024-01-01T00:00:00Z","message":"foo","user":{"password":"baz"}}"#
+        );
+    }
+
+    #[test]
+    fn serialize_canonical_metric_keys() {
+        let options = JsonSerializerOptions {
+            canonical: true,
+            except_keys: Some(vec!["kind".to_owned()]),
+            field_order: vec!["name".to_owned()],
+            ..Default::default()
+        };
+        let bytes = serialize(canonical().with_options(options), metric2());
+
+        assert_eq!(
+            bytes,
+            r#"{"name":"counter","counter":{"value":1},"tags":{"a":"second"}}"#
+        );
+    }
+
+    #[test]
+    fn key_options_only_apply_to_canonical_json() {
+        let event = Event::Log(LogEvent::from(btreemap! {
+            "message" => Value::from("foo"),
+            "password" => Value::from("bar"),
+        }));
+        let options = JsonSerializerOptions {
+            except_keys: Some(vec!["password".to_owned()]),
+            ..Default::default()
+        };
+        let bytes = serialize(JsonSerializerConfig::default().with_options(options), event);
+
+        assert_eq!(bytes, r#"{"message":"foo","password":"bar"}"#);
+    }
+
+    #[test]
+    fn serialize_canonical_metric() {
+        let bytes = serialize(canonical(), metric2());
+
+        assert_eq!(
+            bytes,
+            r#"{"counter":{"value":1},"kind":"incremental","name":"counter","tags":{"a":"second"}}"#
+        );
+    }
+
+    fn canonical() -> JsonSerializerConfig {
+        JsonSerializerConfig::new(MetricTagValues::default()).with_canonical(true)
+    }
+
     fn metric2() -> Event {
         Event::Metric(
             Metric::new(
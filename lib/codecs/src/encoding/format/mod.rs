@@ -4,29 +4,48 @@
 #![deny(missing_docs)]
 
 mod avro;
+mod cef;
 mod common;
 mod csv;
 mod gelf;
 mod json;
 mod logfmt;
+mod msgpack;
 mod native;
 mod native_json;
+#[cfg(feature = "otlp")]
+mod otlp;
+#[cfg(feature = "parquet")]
+mod parquet;
 mod protobuf;
 mod raw_message;
+mod syslog;
 mod text;
 
 use std::fmt::Debug;
 
 pub use self::csv::{CsvSerializer, CsvSerializerConfig};
 pub use avro::{AvroSerializer, AvroSerializerConfig, AvroSerializerOptions};
+pub use cef::{CefSerializer, CefSerializerConfig, CefSerializerOptions, CefVersion};
 use dyn_clone::DynClone;
 pub use gelf::{GelfSerializer, GelfSerializerConfig};
-pub use json::{JsonSerializer, JsonSerializerConfig};
+pub use json::{JsonFloatFormat, JsonSerializer, JsonSerializerConfig, JsonSerializerOptions};
 pub use logfmt::{LogfmtSerializer, LogfmtSerializerConfig};
+pub use msgpack::{MsgpackSerializer, MsgpackSerializerConfig};
 pub use native::{NativeSerializer, NativeSerializerConfig};
 pub use native_json::{NativeJsonSerializer, NativeJsonSerializerConfig};
-pub use protobuf::{ProtobufSerializer, ProtobufSerializerConfig, ProtobufSerializerOptions};
+#[cfg(feature = "otlp")]
+pub use otlp::{OtlpSerializer, OtlpSerializerConfig};
+#[cfg(feature = "parquet")]
+pub use parquet::{ParquetCompression, ParquetSerializer, ParquetSerializerOptions};
+pub use protobuf::{
+    ProtobufFieldMapping, ProtobufSerializer, ProtobufSerializerConfig, ProtobufSerializerOptions,
+};
 pub use raw_message::{RawMessageSerializer, RawMessageSerializerConfig};
+pub use syslog::{
+    parse_facility, parse_severity, SyslogHeader, SyslogRfc, SyslogSerializer,
+    SyslogSerializerConfig, SyslogSerializerOptions, DEFAULT_FACILITY, DEFAULT_SEVERITY,
+};
 pub use text::{TextSerializer, TextSerializerConfig};
 use vector_core::event::Event;
 
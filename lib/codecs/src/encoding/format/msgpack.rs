@@ -0,0 +1,143 @@
+use bytes::{BufMut, BytesMut};
+use serde::{Deserialize, Serialize};
+use tokio_util::codec::Encoder;
+use vector_core::{config::DataType, event::Event, schema};
+
+use crate::common::msgpack;
+
+/// Config used to build a `MsgpackSerializer`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct MsgpackSerializerConfig;
+
+impl MsgpackSerializerConfig {
+    /// Creates a new `MsgpackSerializerConfig`.
+    pub const fn new() -> Self {
+        Self
+    }
+
+    /// Build the `MsgpackSerializer` from this configuration.
+    pub const fn build(&self) -> MsgpackSerializer {
+        MsgpackSerializer
+    }
+
+    /// The data type of events that are accepted by `MsgpackSerializer`.
+    pub fn input_type(&self) -> DataType {
+        DataType::all()
+    }
+
+    /// The schema required by the serializer.
+    pub fn schema_requirement(&self) -> schema::Requirement {
+        schema::Requirement::empty()
+    }
+}
+
+/// Serializer that converts an `Event` to bytes using the [MessagePack][msgpack] format.
+///
+/// [msgpack]: https://msgpack.org/
+#[derive(Debug, Clone)]
+pub struct MsgpackSerializer;
+
+impl MsgpackSerializer {
+    /// Creates a new `MsgpackSerializer`.
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl Encoder<Event> for MsgpackSerializer {
+    type Error = vector_common::Error;
+
+    fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
+        let value = match event {
+            Event::Log(log) => msgpack::from_value(log.value()),
+            // Serializing the metric directly would write its structs as arrays, and its enums as
+            // their indices, so it goes through the structure of the JSON codec instead.
+            Event::Metric(metric) => rmpv::ext::to_value(serde_json::to_value(&metric)?)?,
+            Event::Trace(trace) => msgpack::from_value(trace.value()),
+        };
+        rmpv::encode::write_value(&mut buffer.writer(), &value)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+    use chrono::{TimeZone, Utc};
+    use vector_core::{
+        config::LogNamespace,
+        event::{LogEvent, Metric, MetricKind, MetricValue, Value},
+    };
+    use vrl::btreemap;
+
+    use super::*;
+    use crate::decoding::format::{Deserializer, MsgpackDeserializer};
+
+    fn encode(event: Event) -> BytesMut {
+        let mut buffer = BytesMut::new();
+        MsgpackSerializer::new().encode(event, &mut buffer).unwrap();
+        buffer
+    }
+
+    #[test]
+    fn serialize_msgpack_log() {
+        let event = Event::Log(LogEvent::from(btreemap! {
+            "message" => "foo",
+            "count" => 3,
+            "raw" => Value::Bytes(Bytes::from_static(&[0xff, 0x00])),
+            "tags" => vec![Value::from("a"), Value::Null],
+        }));
+
+        let bytes = encode(event);
+
+        assert_eq!(
+            rmpv::decode::read_value(&mut &bytes[..]).unwrap(),
+            rmpv::Value::Map(vec![
+                ("count".into(), 3.into()),
+                ("message".into(), "foo".into()),
+                ("raw".into(), rmpv::Value::Binary(vec![0xff, 0x00])),
+                (
+                    "tags".into(),
+                    rmpv::Value::Array(vec!["a".into(), rmpv::Value::Nil])
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn serialize_msgpack_metric() {
+        let metric = Metric::new(
+            "foos",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 100.0 },
+        );
+
+        let bytes = encode(Event::Metric(metric.clone()));
+        let value = rmpv::decode::read_value(&mut &bytes[..]).unwrap();
+
+        // Metrics are serialized with the same structure as with the JSON codec.
+        assert_eq!(
+            rmpv::ext::from_value::<serde_json::Value>(value).unwrap(),
+            serde_json::to_value(&metric).unwrap()
+        );
+    }
+
+    #[test]
+    fn round_trips_msgpack_logs() {
+        let log = LogEvent::from(btreemap! {
+            "message" => "foo",
+            "ratio" => Value::from_f64_or_zero(0.5),
+            "raw" => Value::Bytes(Bytes::from_static(&[0xff, 0x00])),
+            "time" => Utc.timestamp_opt(1_700_000_000, 123_456_789).unwrap(),
+            "nested" => btreemap! { "flag" => true },
+        });
+
+        let bytes = encode(Event::Log(log.clone()));
+        let events = MsgpackDeserializer::new()
+            .parse(bytes.freeze(), LogNamespace::Vector)
+            .unwrap();
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].as_log().value(), log.value());
+    }
+}
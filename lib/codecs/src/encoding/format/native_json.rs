@@ -1,7 +1,11 @@
 use bytes::{BufMut, BytesMut};
 use serde::{Deserialize, Serialize};
 use tokio_util::codec::Encoder;
-use vector_core::{config::DataType, event::Event, schema};
+use vector_core::{
+    config::DataType,
+    event::{proto::NATIVE_VERSION, Event},
+    schema,
+};
 
 /// Config used to build a `NativeJsonSerializer`.
 #[derive(Debug, Clone, Default, Deserialize, Serialize)]
@@ -25,9 +29,34 @@ impl NativeJsonSerializerConfig {
 }
 
 /// Serializer that converts an `Event` to bytes using the JSON format.
+///
+/// The version of the native encoding is written in the `version` field next to the event, unless
+/// it's `0`, so that the events stay readable by the releases of Vector that predate the version.
 #[derive(Debug, Clone)]
 pub struct NativeJsonSerializer;
 
+/// An event along with the version of the native encoding it's encoded with.
+#[derive(Serialize)]
+struct VersionedEvent<'a> {
+    #[serde(skip_serializing_if = "is_unversioned")]
+    version: u32,
+    #[serde(flatten)]
+    event: &'a Event,
+}
+
+impl<'a> VersionedEvent<'a> {
+    const fn new(event: &'a Event) -> Self {
+        Self {
+            version: NATIVE_VERSION,
+            event,
+        }
+    }
+}
+
+const fn is_unversioned(version: &u32) -> bool {
+    *version == 0
+}
+
 impl NativeJsonSerializer {
     /// Creates a new `NativeJsonSerializer`.
     pub const fn new() -> Self {
@@ -36,7 +65,7 @@ impl NativeJsonSerializer {
 
     /// Encode event and represent it as native JSON value.
     pub fn to_json_value(&self, event: Event) -> Result<serde_json::Value, vector_common::Error> {
-        serde_json::to_value(&event).map_err(|e| e.to_string().into())
+        serde_json::to_value(VersionedEvent::new(&event)).map_err(|e| e.to_string().into())
     }
 }
 
@@ -45,7 +74,7 @@ impl Encoder<Event> for NativeJsonSerializer {
 
     fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
         let writer = buffer.writer();
-        serde_json::to_writer(writer, &event).map_err(Into::into)
+        serde_json::to_writer(writer, &VersionedEvent::new(&event)).map_err(Into::into)
     }
 }
 
@@ -0,0 +1,150 @@
+use bytes::BytesMut;
+use opentelemetry_proto::encode::{logs_request, metrics_request, ResourceRecord};
+use prost::Message;
+use serde::{Deserialize, Serialize};
+use tokio_util::codec::Encoder;
+use vector_core::{config::DataType, event::Event, schema};
+
+/// Config used to build an `OtlpSerializer`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct OtlpSerializerConfig;
+
+impl OtlpSerializerConfig {
+    /// Creates a new `OtlpSerializerConfig`.
+    pub const fn new() -> Self {
+        Self
+    }
+
+    /// Build the `OtlpSerializer` from this configuration.
+    pub const fn build(&self) -> OtlpSerializer {
+        OtlpSerializer
+    }
+
+    /// The data type of events that are accepted by `OtlpSerializer`.
+    pub fn input_type(&self) -> DataType {
+        DataType::Log | DataType::Metric
+    }
+
+    /// The schema required by the serializer.
+    pub fn schema_requirement(&self) -> schema::Requirement {
+        schema::Requirement::empty()
+    }
+}
+
+/// Serializer that converts an `Event` to bytes holding an [OTLP][otlp] export request encoded as
+/// protobuf.
+///
+/// Logs are encoded as an `ExportLogsServiceRequest` and metrics as an
+/// `ExportMetricsServiceRequest`, the same way as with the `opentelemetry` sink, with a single
+/// record per request.
+///
+/// [otlp]: https://opentelemetry.io/docs/specs/otlp/
+#[derive(Debug, Clone)]
+pub struct OtlpSerializer;
+
+impl OtlpSerializer {
+    /// Creates a new `OtlpSerializer`.
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl Encoder<Event> for OtlpSerializer {
+    type Error = vector_common::Error;
+
+    fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
+        match event {
+            Event::Log(log) => logs_request([ResourceRecord::from(log)]).encode(buffer)?,
+            Event::Metric(metric) => {
+                let record = ResourceRecord::from_metric(&metric)
+                    .ok_or("Sketches are not supported by OTLP.")?;
+                metrics_request([record]).encode(buffer)?
+            }
+            Event::Trace(_) => return Err("Traces are not supported by the OTLP codec.".into()),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use opentelemetry_proto::proto::{
+        collector::{logs::v1::ExportLogsServiceRequest, metrics::v1::ExportMetricsServiceRequest},
+        common::v1::any_value::Value as PBValue,
+    };
+    use vector_core::{
+        config::LogNamespace,
+        event::{metric::MetricSketch, LogEvent, Metric, MetricKind, MetricValue},
+        metric_tags,
+        metrics::AgentDDSketch,
+    };
+
+    use super::*;
+    use crate::decoding::format::{Deserializer, OtlpDeserializer, OtlpSignal};
+
+    fn encode(event: Event) -> Result<BytesMut, vector_common::Error> {
+        let mut buffer = BytesMut::new();
+        OtlpSerializer::new().encode(event, &mut buffer)?;
+        Ok(buffer)
+    }
+
+    #[test]
+    fn serialize_otlp_log() {
+        let mut log = LogEvent::from("foo");
+        log.insert("severity_text", "INFO");
+
+        let bytes = encode(Event::Log(log)).unwrap();
+        let request = ExportLogsServiceRequest::decode(bytes).unwrap();
+
+        assert_eq!(request.resource_logs.len(), 1);
+        let records = &request.resource_logs[0].scope_logs[0].log_records;
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].severity_text, "INFO");
+        assert_eq!(
+            records[0].body.as_ref().unwrap().value,
+            Some(PBValue::StringValue("foo".into()))
+        );
+    }
+
+    #[test]
+    fn round_trips_otlp_metrics() {
+        let metric = Metric::new(
+            "requests",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 100.0 },
+        )
+        .with_tags(Some(metric_tags!("resource.service.name" => "api")));
+
+        let bytes = encode(Event::Metric(metric)).unwrap();
+        let request = ExportMetricsServiceRequest::decode(bytes.clone()).unwrap();
+        assert_eq!(request.resource_metrics.len(), 1);
+
+        let events = OtlpDeserializer::new(OtlpSignal::Metrics)
+            .parse(bytes.freeze(), LogNamespace::Legacy)
+            .unwrap();
+
+        assert_eq!(events.len(), 1);
+        let metric = events[0].as_metric();
+        assert_eq!(metric.name(), "requests");
+        assert_eq!(metric.kind(), MetricKind::Incremental);
+        assert_eq!(metric.value(), &MetricValue::Counter { value: 100.0 });
+        assert_eq!(
+            metric.tag_value("resource.service.name"),
+            Some("api".into())
+        );
+    }
+
+    #[test]
+    fn rejects_sketches_and_traces() {
+        let sketch = Metric::new(
+            "latency",
+            MetricKind::Incremental,
+            MetricValue::Sketch {
+                sketch: MetricSketch::AgentDDSketch(AgentDDSketch::with_agent_defaults()),
+            },
+        );
+
+        assert!(encode(Event::Metric(sketch)).is_err());
+        assert!(encode(Event::Trace(Default::default())).is_err());
+    }
+}
@@ -0,0 +1,676 @@
+use std::{collections::BTreeMap, num::NonZeroUsize, sync::Arc};
+
+use chrono::{DateTime, NaiveDate, Utc};
+use parquet::{
+    basic::{
+        Compression, ConvertedType, GzipLevel, LogicalType, Repetition, TimeUnit,
+        Type as PhysicalType, ZstdLevel,
+    },
+    column::writer::ColumnWriter,
+    data_type::ByteArray,
+    file::{properties::WriterProperties, writer::SerializedFileWriter},
+    schema::{
+        parser::parse_message_type,
+        types::{Type, TypePtr},
+    },
+};
+use vector_config::configurable_component;
+use vector_core::{event::Event, serde::is_default};
+use vrl::value::Value;
+
+fn default_row_group_size() -> NonZeroUsize {
+    NonZeroUsize::new(100_000).expect("non-zero")
+}
+
+/// Apache Parquet serializer options.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct ParquetSerializerOptions {
+    /// The schema of the Parquet files, in the message type format of Parquet.
+    ///
+    /// The top-level fields of the schema are the columns of the files, and are read from the
+    /// fields of the events with the same names. Only `required` and `optional` fields of the
+    /// `boolean`, `int32`, `int64`, `float`, `double`, and `binary` types are supported, with
+    /// the `DATE` annotation for `int32` fields and the `TIMESTAMP` annotation for `int64` fields.
+    ///
+    /// If not set, the schema of each file is inferred from the fields of its events: each
+    /// top-level field is encoded into an `optional` column of the type of its values. Objects,
+    /// arrays, and fields with values of different types are encoded as strings.
+    #[configurable(metadata(
+        docs::examples = "message log { required binary message (STRING); optional int64 status; optional int64 timestamp (TIMESTAMP(MICROS, true)); }"
+    ))]
+    #[configurable(metadata(docs::human_name = "Schema"))]
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub schema: Option<String>,
+
+    /// The compression codec of the column chunks.
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub compression: ParquetCompression,
+
+    /// The maximum number of rows of a row group.
+    ///
+    /// Larger row groups compress better and are read faster, at the cost of more memory when
+    /// writing and reading them.
+    #[serde(default = "default_row_group_size")]
+    pub row_group_size: NonZeroUsize,
+}
+
+impl Default for ParquetSerializerOptions {
+    fn default() -> Self {
+        Self {
+            schema: None,
+            compression: ParquetCompression::default(),
+            row_group_size: default_row_group_size(),
+        }
+    }
+}
+
+/// Compression codec of the column chunks of Parquet files.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ParquetCompression {
+    /// No compression.
+    None,
+
+    /// [Snappy][snappy] compression.
+    ///
+    /// [snappy]: https://github.com/google/snappy/blob/main/docs/README.md
+    #[default]
+    Snappy,
+
+    /// [Gzip][gzip] compression.
+    ///
+    /// [gzip]: https://www.gzip.org/
+    Gzip,
+
+    /// [Zstandard][zstd] compression.
+    ///
+    /// [zstd]: https://facebook.github.io/zstd/
+    Zstd,
+}
+
+impl From<ParquetCompression> for Compression {
+    fn from(compression: ParquetCompression) -> Self {
+        match compression {
+            ParquetCompression::None => Compression::UNCOMPRESSED,
+            ParquetCompression::Snappy => Compression::SNAPPY,
+            ParquetCompression::Gzip => Compression::GZIP(GzipLevel::default()),
+            ParquetCompression::Zstd => Compression::ZSTD(ZstdLevel::default()),
+        }
+    }
+}
+
+impl ParquetSerializerOptions {
+    /// Build the `ParquetSerializer` from these options.
+    pub fn build(&self) -> vector_common::Result<ParquetSerializer> {
+        let schema = self
+            .schema
+            .as_deref()
+            .map(|schema| {
+                let root = parse_message_type(schema)
+                    .map_err(|error| format!("Failed parsing Parquet schema: {}", error))?;
+                ParquetSchema::from_type(root).map(Arc::new)
+            })
+            .transpose()?;
+
+        let properties = WriterProperties::builder()
+            .set_compression(self.compression.into())
+            .build();
+
+        Ok(ParquetSerializer {
+            schema,
+            properties: Arc::new(properties),
+            row_group_size: self.row_group_size.get(),
+        })
+    }
+}
+
+/// The unit of the values of a timestamp column.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum TimestampUnit {
+    Millis,
+    Micros,
+    Nanos,
+}
+
+/// The type of the values of a column, as read from the events.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ColumnKind {
+    Boolean,
+    Int32,
+    Int64,
+    Float,
+    Double,
+    Date,
+    Timestamp(TimestampUnit),
+    String,
+}
+
+impl ColumnKind {
+    fn from_type(field: &Type) -> vector_common::Result<Self> {
+        let info = field.get_basic_info();
+        let logical_type = info.logical_type();
+        let converted_type = info.converted_type();
+
+        Ok(match field.get_physical_type() {
+            PhysicalType::BOOLEAN => Self::Boolean,
+            PhysicalType::INT32 => {
+                if matches!(logical_type, Some(LogicalType::Date))
+                    || converted_type == ConvertedType::DATE
+                {
+                    Self::Date
+                } else {
+                    Self::Int32
+                }
+            }
+            PhysicalType::INT64 => match (logical_type, converted_type) {
+                (Some(LogicalType::Timestamp { unit, .. }), _) => Self::Timestamp(match unit {
+                    TimeUnit::MILLIS(_) => TimestampUnit::Millis,
+                    TimeUnit::MICROS(_) => TimestampUnit::Micros,
+                    TimeUnit::NANOS(_) => TimestampUnit::Nanos,
+                }),
+                (_, ConvertedType::TIMESTAMP_MILLIS) => Self::Timestamp(TimestampUnit::Millis),
+                (_, ConvertedType::TIMESTAMP_MICROS) => Self::Timestamp(TimestampUnit::Micros),
+                _ => Self::Int64,
+            },
+            PhysicalType::FLOAT => Self::Float,
+            PhysicalType::DOUBLE => Self::Double,
+            PhysicalType::BYTE_ARRAY => Self::String,
+            other => {
+                return Err(format!(
+                    "The type `{}` of the Parquet field `{}` is not supported.",
+                    other,
+                    field.name()
+                )
+                .into())
+            }
+        })
+    }
+
+    fn infer(value: &Value) -> Option<Self> {
+        match value {
+            Value::Null => None,
+            Value::Boolean(_) => Some(Self::Boolean),
+            Value::Integer(_) => Some(Self::Int64),
+            Value::Float(_) => Some(Self::Double),
+            Value::Timestamp(_) => Some(Self::Timestamp(TimestampUnit::Micros)),
+            _ => Some(Self::String),
+        }
+    }
+
+    const fn merge(self, other: Self) -> Self {
+        match (self, other) {
+            (Self::Int64, Self::Double) | (Self::Double, Self::Int64) => Self::Double,
+            (Self::Boolean, Self::Boolean)
+            | (Self::Int64, Self::Int64)
+            | (Self::Double, Self::Double) => self,
+            (Self::Timestamp(_), Self::Timestamp(_)) => self,
+            _ => Self::String,
+        }
+    }
+
+    fn to_type(self, name: &str) -> vector_common::Result<Type> {
+        let (physical_type, logical_type) = match self {
+            Self::Boolean => (PhysicalType::BOOLEAN, None),
+            Self::Int32 => (PhysicalType::INT32, None),
+            Self::Int64 => (PhysicalType::INT64, None),
+            Self::Float => (PhysicalType::FLOAT, None),
+            Self::Double => (PhysicalType::DOUBLE, None),
+            Self::Date => (PhysicalType::INT32, Some(LogicalType::Date)),
+            Self::Timestamp(unit) => (
+                PhysicalType::INT64,
+                Some(LogicalType::Timestamp {
+                    is_adjusted_to_u_t_c: true,
+                    unit: match unit {
+                        TimestampUnit::Millis => TimeUnit::MILLIS(Default::default()),
+                        TimestampUnit::Micros => TimeUnit::MICROS(Default::default()),
+                        TimestampUnit::Nanos => TimeUnit::NANOS(Default::default()),
+                    },
+                }),
+            ),
+            Self::String => (PhysicalType::BYTE_ARRAY, Some(LogicalType::String)),
+        };
+
+        Ok(Type::primitive_type_builder(name, physical_type)
+            .with_repetition(Repetition::OPTIONAL)
+            .with_logical_type(logical_type)
+            .build()?)
+    }
+}
+
+#[derive(Debug)]
+struct Column {
+    name: String,
+    kind: ColumnKind,
+    optional: bool,
+}
+
+/// The schema of a Parquet file, and the columns of its top-level fields.
+#[derive(Debug)]
+struct ParquetSchema {
+    root: TypePtr,
+    columns: Vec<Column>,
+}
+
+impl ParquetSchema {
+    fn from_type(root: Type) -> vector_common::Result<Self> {
+        let columns = root
+            .get_fields()
+            .iter()
+            .map(|field| {
+                if !field.is_primitive() {
+                    return Err(format!(
+                        "The Parquet field `{}` is a group, which is not supported.",
+                        field.name()
+                    )
+                    .into());
+                }
+                let optional = match field.get_basic_info().repetition() {
+                    Repetition::REQUIRED => false,
+                    Repetition::OPTIONAL => true,
+                    Repetition::REPEATED => {
+                        return Err(format!(
+                            "The Parquet field `{}` is repeated, which is not supported.",
+                            field.name()
+                        )
+                        .into())
+                    }
+                };
+                Ok(Column {
+                    name: field.name().to_string(),
+                    kind: ColumnKind::from_type(field)?,
+                    optional,
+                })
+            })
+            .collect::<vector_common::Result<Vec<_>>>()?;
+
+        Ok(Self {
+            root: Arc::new(root),
+            columns,
+        })
+    }
+
+    /// Infers the schema of the top-level fields of the events.
+    fn infer(events: &[Event]) -> vector_common::Result<Self> {
+        let mut kinds = BTreeMap::<&str, ColumnKind>::new();
+        for event in events {
+            let Some(fields) = event.maybe_as_log().and_then(|log| log.as_map()) else {
+                continue;
+            };
+            for (name, value) in fields {
+                if let Some(kind) = ColumnKind::infer(value) {
+                    kinds
+                        .entry(name.as_str())
+                        .and_modify(|existing| *existing = existing.merge(kind))
+                        .or_insert(kind);
+                }
+            }
+        }
+
+        if kinds.is_empty() {
+            return Err("Unable to infer a Parquet schema, as the events have no fields.".into());
+        }
+
+        let fields = kinds
+            .iter()
+            .map(|(name, kind)| kind.to_type(name).map(Arc::new))
+            .collect::<vector_common::Result<Vec<_>>>()?;
+        let root = Type::group_type_builder("vector")
+            .with_fields(fields)
+            .build()?;
+
+        Self::from_type(root)
+    }
+}
+
+/// Serializer that encodes batches of events as Apache Parquet files.
+#[derive(Clone, Debug)]
+pub struct ParquetSerializer {
+    schema: Option<Arc<ParquetSchema>>,
+    properties: Arc<WriterProperties>,
+    row_group_size: usize,
+}
+
+impl ParquetSerializer {
+    /// Encodes the events into a Parquet file, with one row per event.
+    pub fn encode(&self, events: &[Event]) -> vector_common::Result<Vec<u8>> {
+        let inferred;
+        let schema = match &self.schema {
+            Some(schema) => schema.as_ref(),
+            None => {
+                inferred = ParquetSchema::infer(events)?;
+                &inferred
+            }
+        };
+
+        let mut buffer = Vec::new();
+        let mut writer = SerializedFileWriter::new(
+            &mut buffer,
+            Arc::clone(&schema.root),
+            Arc::clone(&self.properties),
+        )?;
+
+        for rows in events.chunks(self.row_group_size) {
+            let mut row_group = writer.next_row_group()?;
+            let mut columns = schema.columns.iter();
+            while let Some(mut column_writer) = row_group.next_column()? {
+                let column = columns
+                    .next()
+                    .expect("the schema should have as many columns as top-level fields");
+                write_column(column_writer.untyped(), column, rows)?;
+                column_writer.close()?;
+            }
+            row_group.close()?;
+        }
+        writer.close()?;
+
+        Ok(buffer)
+    }
+}
+
+fn write_column(
+    writer: &mut ColumnWriter<'_>,
+    column: &Column,
+    rows: &[Event],
+) -> vector_common::Result<()> {
+    match (writer, column.kind) {
+        (ColumnWriter::BoolColumnWriter(writer), ColumnKind::Boolean) => {
+            let (values, levels) = collect(column, rows, |value| match value {
+                Value::Boolean(boolean) => Some(*boolean),
+                _ => None,
+            })?;
+            writer.write_batch(&values, column.levels(&levels), None)?;
+        }
+        (ColumnWriter::Int32ColumnWriter(writer), ColumnKind::Int32) => {
+            let (values, levels) = collect(column, rows, |value| match value {
+                Value::Integer(integer) => i32::try_from(*integer).ok(),
+                _ => None,
+            })?;
+            writer.write_batch(&values, column.levels(&levels), None)?;
+        }
+        (ColumnWriter::Int32ColumnWriter(writer), ColumnKind::Date) => {
+            let (values, levels) = collect(column, rows, to_date)?;
+            writer.write_batch(&values, column.levels(&levels), None)?;
+        }
+        (ColumnWriter::Int64ColumnWriter(writer), ColumnKind::Int64) => {
+            let (values, levels) = collect(column, rows, |value| match value {
+                Value::Integer(integer) => Some(*integer),
+                _ => None,
+            })?;
+            writer.write_batch(&values, column.levels(&levels), None)?;
+        }
+        (ColumnWriter::Int64ColumnWriter(writer), ColumnKind::Timestamp(unit)) => {
+            let (values, levels) = collect(column, rows, |value| to_timestamp(value, unit))?;
+            writer.write_batch(&values, column.levels(&levels), None)?;
+        }
+        (ColumnWriter::FloatColumnWriter(writer), ColumnKind::Float) => {
+            let (values, levels) = collect(column, rows, |value| match value {
+                Value::Float(float) => Some(float.into_inner() as f32),
+                Value::Integer(integer) => Some(*integer as f32),
+                _ => None,
+            })?;
+            writer.write_batch(&values, column.levels(&levels), None)?;
+        }
+        (ColumnWriter::DoubleColumnWriter(writer), ColumnKind::Double) => {
+            let (values, levels) = collect(column, rows, |value| match value {
+                Value::Float(float) => Some(float.into_inner()),
+                Value::Integer(integer) => Some(*integer as f64),
+                _ => None,
+            })?;
+            writer.write_batch(&values, column.levels(&levels), None)?;
+        }
+        (ColumnWriter::ByteArrayColumnWriter(writer), ColumnKind::String) => {
+            let (values, levels) = collect(column, rows, to_byte_array)?;
+            writer.write_batch(&values, column.levels(&levels), None)?;
+        }
+        _ => unreachable!("the column writers should match the types of the schema"),
+    }
+    Ok(())
+}
+
+impl Column {
+    /// The definition levels of a column, which are only written for optional columns.
+    fn levels<'a>(&self, levels: &'a [i16]) -> Option<&'a [i16]> {
+        self.optional.then_some(levels)
+    }
+}
+
+/// Collects the non-null values of a column, and the definition levels of its rows.
+fn collect<T>(
+    column: &Column,
+    rows: &[Event],
+    convert: impl Fn(&Value) -> Option<T>,
+) -> vector_common::Result<(Vec<T>, Vec<i16>)> {
+    let mut values = Vec::with_capacity(rows.len());
+    let mut levels = Vec::with_capacity(rows.len());
+    for event in rows {
+        match lookup(event, &column.name) {
+            Some(value) => {
+                let converted = convert(value).ok_or_else(|| {
+                    format!(
+                        "The value of the field `{}` can't be encoded as a `{:?}` column: {}",
+                        column.name,
+                        column.kind,
+                        value.to_string_lossy()
+                    )
+                })?;
+                values.push(converted);
+                levels.push(1);
+            }
+            None if column.optional => levels.push(0),
+            None => return Err(format!("The required field `{}` is missing.", column.name).into()),
+        }
+    }
+    Ok((values, levels))
+}
+
+/// Looks up the value of a column, from the top-level field of the same name or else the path.
+fn lookup<'a>(event: &'a Event, name: &str) -> Option<&'a Value> {
+    let log = event.maybe_as_log()?;
+    log.as_map()
+        .and_then(|fields| fields.get(name))
+        .or_else(|| log.parse_path_and_get_value(name).ok().flatten())
+        .filter(|value| !value.is_null())
+}
+
+fn to_date(value: &Value) -> Option<i32> {
+    let date = match value {
+        Value::Timestamp(timestamp) => timestamp.date_naive(),
+        Value::Bytes(bytes) => {
+            NaiveDate::parse_from_str(std::str::from_utf8(bytes).ok()?, "%Y-%m-%d").ok()?
+        }
+        _ => return None,
+    };
+    let epoch = NaiveDate::from_ymd_opt(1970, 1, 1).expect("valid date");
+    i32::try_from(date.signed_duration_since(epoch).num_days()).ok()
+}
+
+fn to_timestamp(value: &Value, unit: TimestampUnit) -> Option<i64> {
+    let timestamp = match value {
+        Value::Timestamp(timestamp) => *timestamp,
+        Value::Bytes(bytes) => DateTime::parse_from_rfc3339(std::str::from_utf8(bytes).ok()?)
+            .ok()?
+            .with_timezone(&Utc),
+        _ => return None,
+    };
+    match unit {
+        TimestampUnit::Millis => Some(timestamp.timestamp_millis()),
+        TimestampUnit::Micros => Some(timestamp.timestamp_micros()),
+        TimestampUnit::Nanos => timestamp.timestamp_nanos_opt(),
+    }
+}
+
+fn to_byte_array(value: &Value) -> Option<ByteArray> {
+    Some(match value {
+        Value::Bytes(bytes) => ByteArray::from(bytes.clone()),
+        Value::Object(_) | Value::Array(_) => ByteArray::from(serde_json::to_vec(value).ok()?),
+        _ => ByteArray::from(value.to_string_lossy().into_owned().into_bytes()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+    use chrono::TimeZone;
+    use parquet::{
+        file::reader::{FileReader, SerializedFileReader},
+        record::Field,
+    };
+    use vector_core::event::LogEvent;
+
+    use super::*;
+
+    fn serializer(options: serde_json::Value) -> ParquetSerializer {
+        serde_json::from_value::<ParquetSerializerOptions>(options)
+            .unwrap()
+            .build()
+            .unwrap()
+    }
+
+    fn event(fields: Value) -> Event {
+        Event::Log(LogEvent::from(fields))
+    }
+
+    fn read(file: Vec<u8>) -> (SerializedFileReader<Bytes>, Vec<Vec<(String, Field)>>) {
+        let reader = SerializedFileReader::new(Bytes::from(file)).unwrap();
+        let rows = reader
+            .get_row_iter(None)
+            .unwrap()
+            .map(|row| {
+                row.unwrap()
+                    .get_column_iter()
+                    .map(|(name, field)| (name.clone(), field.clone()))
+                    .collect()
+            })
+            .collect();
+        (reader, rows)
+    }
+
+    #[test]
+    fn infers_schema() {
+        let timestamp = Utc.with_ymd_and_hms(2024, 3, 1, 12, 0, 0).unwrap();
+        let events = vec![
+            event(vrl::value!({ "message": "first", "status": 200, "ok": true })),
+            event(vrl::value!({ "message": "second", "duration": 1.5, "tags": ["a"] })),
+            event(vrl::value!({ "status": "unknown", "duration": 2 })),
+        ];
+        let mut last = events[2].clone();
+        last.as_mut_log().insert("timestamp", timestamp);
+
+        let file = serializer(serde_json::json!({}))
+            .encode(&[events, vec![last]].concat())
+            .unwrap();
+        let (reader, rows) = read(file);
+
+        let schema = reader.metadata().file_metadata().schema();
+        let types = schema
+            .get_fields()
+            .iter()
+            .map(|field| (field.name(), field.get_physical_type()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            types,
+            vec![
+                ("duration", PhysicalType::DOUBLE),
+                ("message", PhysicalType::BYTE_ARRAY),
+                ("ok", PhysicalType::BOOLEAN),
+                ("status", PhysicalType::BYTE_ARRAY),
+                ("tags", PhysicalType::BYTE_ARRAY),
+                ("timestamp", PhysicalType::INT64),
+            ]
+        );
+
+        assert_eq!(rows.len(), 4);
+        assert_eq!(
+            rows[1],
+            vec![
+                ("duration".to_string(), Field::Double(1.5)),
+                ("message".to_string(), Field::Str("second".to_string())),
+                ("ok".to_string(), Field::Null),
+                ("status".to_string(), Field::Null),
+                ("tags".to_string(), Field::Str(r#"["a"]"#.to_string())),
+                ("timestamp".to_string(), Field::Null),
+            ]
+        );
+        assert_eq!(rows[0][3].1, Field::Str("200".to_string()));
+        assert_eq!(
+            rows[3][5].1,
+            Field::TimestampMicros(timestamp.timestamp_micros())
+        );
+    }
+
+    #[test]
+    fn encodes_configured_schema() {
+        let serializer = serializer(serde_json::json!({
+            "schema": "message log { required binary message (STRING); optional int32 status; optional int64 timestamp (TIMESTAMP(MILLIS, true)); optional int32 day (DATE); }",
+            "compression": "zstd",
+            "row_group_size": 2,
+        }));
+        let events = (0..5_i64)
+            .map(|index| {
+                event(vrl::value!({
+                    "message": (format!("event {}", index)),
+                    "status": (index * 100),
+                    "timestamp": "2024-03-01T12:00:00Z",
+                    "day": "2024-03-01",
+                    "ignored": true,
+                }))
+            })
+            .collect::<Vec<_>>();
+
+        let (reader, rows) = read(serializer.encode(&events).unwrap());
+
+        assert_eq!(reader.metadata().num_row_groups(), 3);
+        assert_eq!(rows.len(), 5);
+        assert_eq!(
+            rows[4],
+            vec![
+                ("message".to_string(), Field::Str("event 4".to_string())),
+                ("status".to_string(), Field::Int(400)),
+                (
+                    "timestamp".to_string(),
+                    Field::TimestampMillis(
+                        Utc.with_ymd_and_hms(2024, 3, 1, 12, 0, 0)
+                            .unwrap()
+                            .timestamp_millis()
+                    )
+                ),
+                ("day".to_string(), Field::Date(19783)),
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_events() {
+        let serializer = serializer(serde_json::json!({
+            "schema": "message log { required binary message (STRING); optional int64 status; }",
+        }));
+
+        assert!(serializer
+            .encode(&[event(vrl::value!({ "status": 200 }))])
+            .unwrap_err()
+            .to_string()
+            .contains("required field `message`"));
+        assert!(serializer
+            .encode(&[event(vrl::value!({ "message": "a", "status": "ok" }))])
+            .unwrap_err()
+            .to_string()
+            .contains("field `status`"));
+    }
+
+    #[test]
+    fn rejects_unsupported_schemas() {
+        for schema in [
+            "message log { repeated binary tags (STRING); }",
+            "message log { optional group http { optional int64 status; } }",
+            "message log { optional int96 time; }",
+        ] {
+            let options = ParquetSerializerOptions {
+                schema: Some(schema.to_string()),
+                ..Default::default()
+            };
+            assert!(options.build().is_err(), "{}", schema);
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use crate::encoding::BuildError;
 use bytes::BytesMut;
+use lookup::lookup_v2::ConfigValuePath;
 use prost::Message;
 use prost_reflect::MessageDescriptor;
 use std::path::PathBuf;
@@ -25,7 +26,10 @@ impl ProtobufSerializerConfig {
             &self.protobuf.desc_file,
             &self.protobuf.message_type,
         )?;
-        Ok(ProtobufSerializer { message_descriptor })
+        Ok(ProtobufSerializer {
+            message_descriptor,
+            field_mappings: self.protobuf.field_mappings.clone(),
+        })
     }
 
     /// The data type of events that are accepted by `ProtobufSerializer`.
@@ -54,6 +58,28 @@ pub struct ProtobufSerializerOptions {
     /// The name of the message type to use for serializing.
     #[configurable(metadata(docs::examples = "package.Message"))]
     pub message_type: String,
+
+    /// Rules moving fields of the events to other paths before they are serialized.
+    ///
+    /// The fields of the message are set from the fields of the event with the same name, and
+    /// the fields of nested messages from the fields of nested objects. Event fields at other
+    /// paths can be moved to the paths of the message fields they are serialized as. The rules are
+    /// applied together, so fields can be swapped.
+    #[serde(default)]
+    pub field_mappings: Vec<ProtobufFieldMapping>,
+}
+
+/// A rule moving an event field to the path of a message field.
+#[crate::configurable_component]
+#[derive(Debug, Clone)]
+pub struct ProtobufFieldMapping {
+    /// The path of the field in the event.
+    #[configurable(metadata(docs::examples = "user.name"))]
+    pub from: ConfigValuePath,
+
+    /// The path of the field in the message.
+    #[configurable(metadata(docs::examples = "username"))]
+    pub to: ConfigValuePath,
 }
 
 /// Serializer that converts an `Event` to bytes using the Protobuf format.
@@ -61,12 +87,32 @@ pub struct ProtobufSerializerOptions {
 pub struct ProtobufSerializer {
     /// The protobuf message definition to use for serialization.
     message_descriptor: MessageDescriptor,
+    /// The rules moving event fields to the paths of message fields.
+    field_mappings: Vec<ProtobufFieldMapping>,
 }
 
 impl ProtobufSerializer {
     /// Creates a new `ProtobufSerializer`.
     pub fn new(message_descriptor: MessageDescriptor) -> Self {
-        Self { message_descriptor }
+        Self {
+            message_descriptor,
+            field_mappings: Vec::new(),
+        }
+    }
+
+    /// Moves the fields of the value according to the field mappings.
+    fn map_fields(&self, mut value: Value) -> Value {
+        let fields = self
+            .field_mappings
+            .iter()
+            .map(|mapping| (&mapping.to, value.remove(&mapping.from.0, true)))
+            .collect::<Vec<_>>();
+        for (to, field) in fields {
+            if let Some(field) = field {
+                value.insert(&to.0, field);
+            }
+        }
+        value
     }
 
     /// Get a description of the message type used in serialization.
@@ -80,13 +126,14 @@ impl Encoder<Event> for ProtobufSerializer {
 
     fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
         let message = match event {
-            Event::Log(log) => {
-                vrl::protobuf::encode_message(&self.message_descriptor, log.into_parts().0)
-            }
+            Event::Log(log) => vrl::protobuf::encode_message(
+                &self.message_descriptor,
+                self.map_fields(log.into_parts().0),
+            ),
             Event::Metric(_) => unimplemented!(),
             Event::Trace(trace) => vrl::protobuf::encode_message(
                 &self.message_descriptor,
-                Value::Object(trace.into_parts().0),
+                self.map_fields(Value::Object(trace.into_parts().0)),
             ),
         }?;
         message.encode(buffer).map_err(Into::into)
@@ -0,0 +1,521 @@
+use std::fmt::Write as _;
+
+use bytes::{BufMut, BytesMut};
+use chrono::{DateTime, SecondsFormat, Utc};
+use lookup::lookup_v2::ConfigTargetPath;
+use tokio_util::codec::Encoder;
+use vector_core::{
+    config::DataType,
+    event::{Event, LogEvent, Value},
+    schema,
+};
+use vrl::value::Kind;
+
+/// The names of the facilities, indexed by their code.
+const FACILITIES: [&str; 24] = [
+    "kern",
+    "user",
+    "mail",
+    "daemon",
+    "auth",
+    "syslog",
+    "lpr",
+    "news",
+    "uucp",
+    "cron",
+    "authpriv",
+    "ftp",
+    "ntp",
+    "security",
+    "console",
+    "solaris-cron",
+    "local0",
+    "local1",
+    "local2",
+    "local3",
+    "local4",
+    "local5",
+    "local6",
+    "local7",
+];
+
+/// The names of the severities, indexed by their code.
+const SEVERITIES: [&str; 8] = [
+    "emerg", "alert", "crit", "err", "warning", "notice", "info", "debug",
+];
+
+/// The facility of the messages that have no valid facility, `user`.
+pub const DEFAULT_FACILITY: u8 = 1;
+
+/// The severity of the messages that have no valid severity, `info`.
+pub const DEFAULT_SEVERITY: u8 = 6;
+
+/// The application name of the messages that have none.
+const DEFAULT_APP_NAME: &str = "vector";
+
+const NILVALUE: &str = "-";
+
+/// The format of the syslog messages.
+#[crate::configurable_component]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SyslogRfc {
+    /// The format of [RFC 5424][rfc5424].
+    ///
+    /// [rfc5424]: https://datatracker.ietf.org/doc/html/rfc5424
+    #[default]
+    Rfc5424,
+
+    /// The legacy BSD format of [RFC 3164][rfc3164].
+    ///
+    /// [rfc3164]: https://datatracker.ietf.org/doc/html/rfc3164
+    Rfc3164,
+}
+
+/// Config used to build a `SyslogSerializer`.
+#[crate::configurable_component]
+#[derive(Debug, Clone, Default)]
+pub struct SyslogSerializerConfig {
+    /// Options for the syslog serializer.
+    #[serde(default, skip_serializing_if = "vector_core::serde::is_default")]
+    pub syslog: SyslogSerializerOptions,
+}
+
+impl SyslogSerializerConfig {
+    /// Creates a new `SyslogSerializerConfig`.
+    pub const fn new(syslog: SyslogSerializerOptions) -> Self {
+        Self { syslog }
+    }
+
+    /// Build the `SyslogSerializer` from this configuration.
+    pub fn build(&self) -> SyslogSerializer {
+        SyslogSerializer::new(self.syslog.clone())
+    }
+
+    /// The data type of events that are accepted by `SyslogSerializer`.
+    pub fn input_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    /// The schema required by the serializer.
+    pub fn schema_requirement(&self) -> schema::Requirement {
+        schema::Requirement::empty()
+            .optional_meaning("host", Kind::bytes())
+            .optional_meaning("timestamp", Kind::timestamp())
+    }
+}
+
+/// Syslog serializer options.
+///
+/// The header fields of the messages are taken from the fields of the log events, and the message
+/// of the events is written as the body of the messages.
+#[crate::configurable_component]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SyslogSerializerOptions {
+    /// The format of the syslog messages.
+    #[serde(default, skip_serializing_if = "vector_core::serde::is_default")]
+    pub rfc: SyslogRfc,
+
+    /// The field of the log events that holds the facility of the messages.
+    ///
+    /// The field can hold the name of a facility, such as `local0`, or its numerical code, such as
+    /// `16`. If the field isn't set, or doesn't hold a valid facility, `user` is used.
+    #[configurable(metadata(docs::examples = "facility"))]
+    #[serde(default, skip_serializing_if = "vector_core::serde::is_default")]
+    pub facility_key: Option<ConfigTargetPath>,
+
+    /// The field of the log events that holds the severity of the messages.
+    ///
+    /// The field can hold the name of a severity, such as `err` or `error`, or its numerical code,
+    /// such as `3`. If the field isn't set, or doesn't hold a valid severity, `info` is used.
+    #[configurable(metadata(docs::examples = "severity"))]
+    #[serde(default, skip_serializing_if = "vector_core::serde::is_default")]
+    pub severity_key: Option<ConfigTargetPath>,
+
+    /// The field of the log events that holds the hostname of the messages.
+    ///
+    /// If not set, the host of the events is used.
+    #[configurable(metadata(docs::examples = "hostname"))]
+    #[serde(default, skip_serializing_if = "vector_core::serde::is_default")]
+    pub hostname_key: Option<ConfigTargetPath>,
+
+    /// The field of the log events that holds the name of the application that the messages
+    /// come from.
+    ///
+    /// This is written as the `APP-NAME` of RFC 5424 messages, and as the `TAG` of RFC 3164
+    /// messages. If the field isn't set, `vector` is used.
+    #[configurable(metadata(docs::examples = "appname"))]
+    #[serde(default, skip_serializing_if = "vector_core::serde::is_default")]
+    pub app_name_key: Option<ConfigTargetPath>,
+
+    /// The field of the log events that holds the ID of the process that the messages come from.
+    #[configurable(metadata(docs::examples = "procid"))]
+    #[serde(default, skip_serializing_if = "vector_core::serde::is_default")]
+    pub proc_id_key: Option<ConfigTargetPath>,
+
+    /// The field of the log events that holds the type of the messages.
+    ///
+    /// This is only written to RFC 5424 messages.
+    #[configurable(metadata(docs::examples = "msgid"))]
+    #[serde(default, skip_serializing_if = "vector_core::serde::is_default")]
+    pub msg_id_key: Option<ConfigTargetPath>,
+
+    /// The field of the log events that holds the structured data of the messages.
+    ///
+    /// The field must be an object of structured data elements, keyed by their ID, that are each
+    /// an object of parameters. Arrays of values are written as repeated parameters. This is only
+    /// written to RFC 5424 messages.
+    #[configurable(metadata(docs::examples = "structured_data"))]
+    #[serde(default, skip_serializing_if = "vector_core::serde::is_default")]
+    pub structured_data_key: Option<ConfigTargetPath>,
+}
+
+/// Parses a facility from its name or its code.
+pub fn parse_facility(value: &str) -> Option<u8> {
+    parse_code(value, &FACILITIES)
+}
+
+/// Parses a severity from its name or its code.
+pub fn parse_severity(value: &str) -> Option<u8> {
+    let value = value.trim().to_ascii_lowercase();
+    let value = match value.as_str() {
+        "emergency" | "panic" => "emerg",
+        "critical" => "crit",
+        "error" => "err",
+        "warn" => "warning",
+        "informational" => "info",
+        value => value,
+    };
+    parse_code(value, &SEVERITIES)
+}
+
+fn parse_code(value: &str, names: &[&str]) -> Option<u8> {
+    let value = value.trim();
+    match value.parse::<u8>() {
+        Ok(code) => ((code as usize) < names.len()).then_some(code),
+        Err(_) => names
+            .iter()
+            .position(|name| name.eq_ignore_ascii_case(value))
+            .map(|code| code as u8),
+    }
+}
+
+/// Replaces the characters that are not printable US-ASCII, or are otherwise not allowed in a
+/// header field, and truncates the field to its maximum length.
+fn sanitize(value: &str, max_len: usize, is_allowed: impl Fn(char) -> bool) -> String {
+    value
+        .chars()
+        .map(|c| {
+            if c.is_ascii_graphic() && is_allowed(c) {
+                c
+            } else {
+                '_'
+            }
+        })
+        .take(max_len)
+        .collect()
+}
+
+/// Writes a header field of an RFC 5424 message, or the nil value if the field is empty.
+fn write_header_field(output: &mut String, value: Option<&str>, max_len: usize) {
+    output.push(' ');
+    match value.map(|value| sanitize(value, max_len, |_| true)) {
+        Some(value) if !value.is_empty() => output.push_str(&value),
+        _ => output.push_str(NILVALUE),
+    }
+}
+
+/// The names of structured data elements and parameters can't contain `=`, `]`, `"`, or spaces.
+fn sanitize_sd_name(name: &str) -> String {
+    sanitize(name, 32, |c| !matches!(c, '=' | ']' | '"'))
+}
+
+/// Writes the structured data held by the given value, which must be an object of elements
+/// holding objects of parameters, or the nil value if there is none.
+fn write_structured_data(output: &mut String, structured_data: Option<&Value>) {
+    output.push(' ');
+
+    let elements = match structured_data {
+        Some(Value::Object(elements)) if !elements.is_empty() => elements,
+        _ => {
+            output.push_str(NILVALUE);
+            return;
+        }
+    };
+
+    for (id, params) in elements {
+        output.push('[');
+        output.push_str(&sanitize_sd_name(id));
+        if let Value::Object(params) = params {
+            for (name, value) in params {
+                // Arrays are written as repeated parameters.
+                let values = match value {
+                    Value::Array(values) => values.iter().collect(),
+                    value => vec![value],
+                };
+                for value in values {
+                    _ = write!(output, " {}=\"", sanitize_sd_name(name));
+                    for c in value.to_string_lossy().chars() {
+                        if matches!(c, '"' | '\\' | ']') {
+                            output.push('\\');
+                        }
+                        output.push(c);
+                    }
+                    output.push('"');
+                }
+            }
+        }
+        output.push(']');
+    }
+}
+
+/// The header of a syslog message, which comes before its body.
+#[derive(Debug, Clone)]
+pub struct SyslogHeader<'a> {
+    /// The code of the facility.
+    pub facility: u8,
+    /// The code of the severity.
+    pub severity: u8,
+    /// The time of the message.
+    pub timestamp: DateTime<Utc>,
+    /// The hostname of the machine that sent the message.
+    pub hostname: Option<&'a str>,
+    /// The name of the application that the message comes from.
+    pub app_name: Option<&'a str>,
+    /// The ID of the process that the message comes from.
+    pub proc_id: Option<&'a str>,
+    /// The type of the message.
+    pub msg_id: Option<&'a str>,
+    /// The structured data elements of the message.
+    pub structured_data: Option<&'a Value>,
+}
+
+impl SyslogHeader<'_> {
+    /// Writes the header in the given format.
+    ///
+    /// The fields are sanitized and truncated to fit the format, and the fields that aren't part
+    /// of the format are left out.
+    pub fn write(&self, rfc: SyslogRfc) -> String {
+        let mut header = format!("<{}>", self.facility * 8 + self.severity);
+        match rfc {
+            SyslogRfc::Rfc5424 => {
+                _ = write!(
+                    header,
+                    "1 {}",
+                    self.timestamp.to_rfc3339_opts(SecondsFormat::Micros, true)
+                );
+                write_header_field(&mut header, self.hostname, 255);
+                write_header_field(&mut header, self.app_name, 48);
+                write_header_field(&mut header, self.proc_id, 128);
+                write_header_field(&mut header, self.msg_id, 32);
+                write_structured_data(&mut header, self.structured_data);
+            }
+            SyslogRfc::Rfc3164 => {
+                let hostname = self
+                    .hostname
+                    .map(|hostname| sanitize(hostname, 255, |_| true))
+                    .filter(|hostname| !hostname.is_empty());
+                let tag = self
+                    .app_name
+                    .map(|app_name| sanitize(app_name, 32, |c| !matches!(c, ':' | '[' | ']')))
+                    .filter(|tag| !tag.is_empty());
+
+                _ = write!(header, "{}", self.timestamp.format("%b %e %H:%M:%S"));
+                header.push(' ');
+                header.push_str(hostname.as_deref().unwrap_or(NILVALUE));
+                header.push(' ');
+                header.push_str(tag.as_deref().unwrap_or(DEFAULT_APP_NAME));
+                if let Some(proc_id) = self.proc_id.filter(|proc_id| !proc_id.is_empty()) {
+                    _ = write!(header, "[{}]", sanitize(proc_id, 128, |c| c != ']'));
+                }
+                header.push(':');
+            }
+        }
+
+        header
+    }
+}
+
+/// Serializer that converts an `Event` to bytes using the syslog format.
+#[derive(Debug, Clone)]
+pub struct SyslogSerializer {
+    options: SyslogSerializerOptions,
+}
+
+impl SyslogSerializer {
+    /// Creates a new `SyslogSerializer`.
+    pub const fn new(options: SyslogSerializerOptions) -> Self {
+        Self { options }
+    }
+}
+
+/// Returns the string held by a field of the log event, if the field is set.
+fn get_string(log: &LogEvent, key: Option<&ConfigTargetPath>) -> Option<String> {
+    key.and_then(|key| log.get(key))
+        .map(|value| value.to_string_lossy().into_owned())
+}
+
+impl Encoder<Event> for SyslogSerializer {
+    type Error = vector_common::Error;
+
+    fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
+        let log = event.into_log();
+        let options = &self.options;
+
+        let facility = get_string(&log, options.facility_key.as_ref())
+            .and_then(|facility| parse_facility(&facility))
+            .unwrap_or(DEFAULT_FACILITY);
+        let severity = get_string(&log, options.severity_key.as_ref())
+            .and_then(|severity| parse_severity(&severity))
+            .unwrap_or(DEFAULT_SEVERITY);
+        let hostname = match &options.hostname_key {
+            Some(key) => get_string(&log, Some(key)),
+            None => log
+                .get_host()
+                .map(|host| host.to_string_lossy().into_owned()),
+        };
+        let app_name = get_string(&log, options.app_name_key.as_ref());
+        let proc_id = get_string(&log, options.proc_id_key.as_ref());
+        let msg_id = get_string(&log, options.msg_id_key.as_ref());
+
+        let header = SyslogHeader {
+            facility,
+            severity,
+            timestamp: log
+                .get_timestamp()
+                .and_then(Value::as_timestamp)
+                .copied()
+                .unwrap_or_else(Utc::now),
+            hostname: hostname.as_deref(),
+            app_name: Some(app_name.as_deref().unwrap_or(DEFAULT_APP_NAME)),
+            proc_id: proc_id.as_deref(),
+            msg_id: msg_id.as_deref(),
+            structured_data: options
+                .structured_data_key
+                .as_ref()
+                .and_then(|key| log.get(key)),
+        };
+
+        buffer.extend_from_slice(header.write(options.rfc).as_bytes());
+        if let Some(message) = log.get_message() {
+            let message = message.to_string_lossy();
+            if !message.is_empty() {
+                buffer.put_u8(b' ');
+                buffer.extend_from_slice(message.as_bytes());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Timelike};
+
+    use super::*;
+
+    fn options() -> SyslogSerializerOptions {
+        SyslogSerializerOptions {
+            rfc: SyslogRfc::Rfc5424,
+            facility_key: Some("facility".into()),
+            severity_key: Some("severity".into()),
+            hostname_key: None,
+            app_name_key: Some("app".into()),
+            proc_id_key: Some("pid".into()),
+            msg_id_key: Some("msgid".into()),
+            structured_data_key: Some("sd".into()),
+        }
+    }
+
+    fn event() -> Event {
+        let mut log = LogEvent::from("'su root' failed for lonvick on /dev/pts/8");
+        log.insert("facility", "auth");
+        log.insert("severity", "crit");
+        log.insert("app", "su");
+        log.insert("pid", 42);
+        log.insert("msgid", "ID47");
+        log.insert("host", "mymachine.example.com");
+        log.insert(
+            "timestamp",
+            Utc.with_ymd_and_hms(2003, 10, 11, 22, 14, 15)
+                .unwrap()
+                .with_nanosecond(3_000_000)
+                .unwrap(),
+        );
+        log.into()
+    }
+
+    fn serialize(options: SyslogSerializerOptions, event: Event) -> String {
+        let mut buffer = BytesMut::new();
+        SyslogSerializer::new(options)
+            .encode(event, &mut buffer)
+            .unwrap();
+        String::from_utf8(buffer.to_vec()).unwrap()
+    }
+
+    #[test]
+    fn serializes_rfc5424() {
+        let mut event = event();
+        event.as_mut_log().insert(
+            "sd",
+            vrl::value!({
+                "exampleSDID@32473": {
+                    "eventSource": "Application",
+                    "iut": 3,
+                },
+                "examplePriority@32473": {
+                    "class": ["high", "low"],
+                },
+            }),
+        );
+
+        assert_eq!(
+            serialize(options(), event),
+            "<34>1 2003-10-11T22:14:15.003000Z mymachine.example.com su 42 ID47 \
+             [examplePriority@32473 class=\"high\" class=\"low\"]\
+             [exampleSDID@32473 eventSource=\"Application\" iut=\"3\"] \
+             'su root' failed for lonvick on /dev/pts/8"
+        );
+    }
+
+    #[test]
+    fn serializes_rfc3164() {
+        let options = SyslogSerializerOptions {
+            rfc: SyslogRfc::Rfc3164,
+            ..options()
+        };
+
+        assert_eq!(
+            serialize(options, event()),
+            "<34>Oct 11 22:14:15 mymachine.example.com su[42]: \
+             'su root' failed for lonvick on /dev/pts/8"
+        );
+    }
+
+    #[test]
+    fn falls_back_to_defaults() {
+        let mut event = event();
+        event.as_mut_log().insert("facility", "not-a-facility");
+        event.as_mut_log().insert("severity", 8);
+
+        assert_eq!(
+            serialize(SyslogSerializerOptions::default(), event.clone()),
+            "<14>1 2003-10-11T22:14:15.003000Z mymachine.example.com vector - - - \
+             'su root' failed for lonvick on /dev/pts/8"
+        );
+        assert!(serialize(options(), event).starts_with("<14>"));
+    }
+
+    #[test]
+    fn parses_priority_codes() {
+        assert_eq!(parse_facility("local7"), Some(23));
+        assert_eq!(parse_facility("LOCAL0"), Some(16));
+        assert_eq!(parse_facility("3"), Some(3));
+        assert_eq!(parse_facility("24"), None);
+        assert_eq!(parse_severity("Error"), Some(3));
+        assert_eq!(parse_severity("warn"), Some(4));
+        assert_eq!(parse_severity("7"), Some(7));
+        assert_eq!(parse_severity("trace"), None);
+    }
+}
@@ -0,0 +1,108 @@
+use std::io;
+
+use bytes::BytesMut;
+use derivative::Derivative;
+use tokio_util::codec::Encoder;
+use vector_config::configurable_component;
+
+use crate::common::varint_length_delimited::VarintLengthDelimitedCoderOptions;
+
+use super::BoxedFramingError;
+
+/// Config used to build a `VarintLengthDelimitedEncoder`.
+#[configurable_component]
+#[derive(Debug, Clone, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+pub struct VarintLengthDelimitedEncoderConfig {
+    /// Options for the varint length delimited encoder.
+    #[serde(default, skip_serializing_if = "vector_core::serde::is_default")]
+    pub varint_length_delimited: VarintLengthDelimitedCoderOptions,
+}
+
+impl VarintLengthDelimitedEncoderConfig {
+    /// Build the `VarintLengthDelimitedEncoder` from this configuration.
+    pub fn build(&self) -> VarintLengthDelimitedEncoder {
+        VarintLengthDelimitedEncoder::new(&self.varint_length_delimited)
+    }
+}
+
+/// An encoder for handling bytes that are prefixed with their length as a varint, the way streams
+/// of Protocol Buffers messages are delimited.
+#[derive(Debug, Clone)]
+pub struct VarintLengthDelimitedEncoder {
+    max_frame_length: usize,
+}
+
+impl VarintLengthDelimitedEncoder {
+    /// Creates a new `VarintLengthDelimitedEncoder`.
+    pub const fn new(config: &VarintLengthDelimitedCoderOptions) -> Self {
+        Self {
+            max_frame_length: config.max_frame_length,
+        }
+    }
+}
+
+impl Default for VarintLengthDelimitedEncoder {
+    fn default() -> Self {
+        Self::new(&VarintLengthDelimitedCoderOptions::default())
+    }
+}
+
+impl Encoder<()> for VarintLengthDelimitedEncoder {
+    type Error = BoxedFramingError;
+
+    fn encode(&mut self, _: (), buffer: &mut BytesMut) -> Result<(), BoxedFramingError> {
+        let length = buffer.len();
+        if length > self.max_frame_length {
+            return Err(Box::new(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "frame of {} bytes exceeds the maximum frame length of {} bytes",
+                    length, self.max_frame_length
+                ),
+            )));
+        }
+
+        let frame = buffer.split();
+        buffer.reserve(prost::encoding::encoded_len_varint(length as u64) + length);
+        prost::encoding::encode_varint(length as u64, buffer);
+        buffer.unsplit(frame);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode() {
+        let mut codec = VarintLengthDelimitedEncoder::default();
+
+        let mut buffer = BytesMut::from("abc");
+        codec.encode((), &mut buffer).unwrap();
+
+        assert_eq!(&buffer[..], b"\x03abc");
+    }
+
+    #[test]
+    fn encode_multibyte_length() {
+        let mut codec = VarintLengthDelimitedEncoder::default();
+
+        let mut buffer = BytesMut::from(&[b'a'; 300][..]);
+        codec.encode((), &mut buffer).unwrap();
+
+        assert_eq!(&buffer[..2], &[0xac, 0x02]);
+        assert_eq!(&buffer[2..], &[b'a'; 300][..]);
+    }
+
+    #[test]
+    fn encode_rejects_long_frames() {
+        let mut codec = VarintLengthDelimitedEncoder::new(&VarintLengthDelimitedCoderOptions {
+            max_frame_length: 2,
+        });
+
+        let mut buffer = BytesMut::from("abc");
+        assert!(codec.encode((), &mut buffer).is_err());
+    }
+}
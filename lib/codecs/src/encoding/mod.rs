@@ -1,24 +1,40 @@
 //! A collection of support structures that are used in the process of encoding
 //! events into bytes.
 
+#[cfg(feature = "parquet")]
+mod batch;
+mod chunking;
 pub mod format;
 pub mod framing;
 
 use std::fmt::Debug;
 
+#[cfg(feature = "parquet")]
+pub use batch::{BatchSerializer, BatchSerializerConfig};
 use bytes::BytesMut;
+pub use chunking::{
+    Chunker, ChunkingConfig, GelfChunker, GelfChunkerConfig, GelfChunkerOptions, GelfCompression,
+};
 pub use format::{
-    AvroSerializer, AvroSerializerConfig, AvroSerializerOptions, CsvSerializer,
-    CsvSerializerConfig, GelfSerializer, GelfSerializerConfig, JsonSerializer,
-    JsonSerializerConfig, LogfmtSerializer, LogfmtSerializerConfig, NativeJsonSerializer,
-    NativeJsonSerializerConfig, NativeSerializer, NativeSerializerConfig, ProtobufSerializer,
-    ProtobufSerializerConfig, ProtobufSerializerOptions, RawMessageSerializer,
-    RawMessageSerializerConfig, TextSerializer, TextSerializerConfig,
+    AvroSerializer, AvroSerializerConfig, AvroSerializerOptions, CefSerializer,
+    CefSerializerConfig, CefSerializerOptions, CefVersion, CsvSerializer, CsvSerializerConfig,
+    GelfSerializer, GelfSerializerConfig, JsonFloatFormat, JsonSerializer, JsonSerializerConfig,
+    JsonSerializerOptions, LogfmtSerializer, LogfmtSerializerConfig, MsgpackSerializer,
+    MsgpackSerializerConfig, NativeJsonSerializer, NativeJsonSerializerConfig, NativeSerializer,
+    NativeSerializerConfig, ProtobufFieldMapping, ProtobufSerializer, ProtobufSerializerConfig,
+    ProtobufSerializerOptions, RawMessageSerializer, RawMessageSerializerConfig, SyslogRfc,
+    SyslogSerializer, SyslogSerializerConfig, SyslogSerializerOptions, TextSerializer,
+    TextSerializerConfig,
 };
+#[cfg(feature = "otlp")]
+pub use format::{OtlpSerializer, OtlpSerializerConfig};
+#[cfg(feature = "parquet")]
+pub use format::{ParquetCompression, ParquetSerializer, ParquetSerializerOptions};
 pub use framing::{
     BoxedFramer, BoxedFramingError, BytesEncoder, BytesEncoderConfig, CharacterDelimitedEncoder,
     CharacterDelimitedEncoderConfig, CharacterDelimitedEncoderOptions, LengthDelimitedEncoder,
     LengthDelimitedEncoderConfig, NewlineDelimitedEncoder, NewlineDelimitedEncoderConfig,
+    VarintLengthDelimitedEncoder, VarintLengthDelimitedEncoderConfig,
 };
 use vector_config::configurable_component;
 use vector_core::{config::DataType, event::Event, schema};
@@ -71,6 +87,14 @@ pub enum FramingConfig {
 
     /// Event data is delimited by a newline (LF) character.
     NewlineDelimited,
+
+    /// Event data is prefixed with its length in bytes as an unsigned [varint][varint].
+    ///
+    /// This is the framing of streams of Protocol Buffers messages, as read by
+    /// `parseDelimitedFrom` in Java or `ParseDelimitedFromZeroCopyStream` in C++.
+    ///
+    /// [varint]: https://protobuf.dev/programming-guides/encoding/#varints
+    VarintLengthDelimited(VarintLengthDelimitedEncoderConfig),
 }
 
 impl From<BytesEncoderConfig> for FramingConfig {
@@ -97,6 +121,12 @@ impl From<NewlineDelimitedEncoderConfig> for FramingConfig {
     }
 }
 
+impl From<VarintLengthDelimitedEncoderConfig> for FramingConfig {
+    fn from(config: VarintLengthDelimitedEncoderConfig) -> Self {
+        Self::VarintLengthDelimited(config)
+    }
+}
+
 impl FramingConfig {
     /// Build the `Framer` from this configuration.
     pub fn build(&self) -> Framer {
@@ -107,6 +137,9 @@ impl FramingConfig {
             FramingConfig::NewlineDelimited => {
                 Framer::NewlineDelimited(NewlineDelimitedEncoderConfig.build())
             }
+            FramingConfig::VarintLengthDelimited(config) => {
+                Framer::VarintLengthDelimited(config.build())
+            }
         }
     }
 }
@@ -122,6 +155,8 @@ pub enum Framer {
     LengthDelimited(LengthDelimitedEncoder),
     /// Uses a `NewlineDelimitedEncoder` for framing.
     NewlineDelimited(NewlineDelimitedEncoder),
+    /// Uses a `VarintLengthDelimitedEncoder` for framing.
+    VarintLengthDelimited(VarintLengthDelimitedEncoder),
     /// Uses an opaque `Encoder` implementation for framing.
     Boxed(BoxedFramer),
 }
@@ -150,6 +185,12 @@ impl From<NewlineDelimitedEncoder> for Framer {
     }
 }
 
+impl From<VarintLengthDelimitedEncoder> for Framer {
+    fn from(encoder: VarintLengthDelimitedEncoder) -> Self {
+        Self::VarintLengthDelimited(encoder)
+    }
+}
+
 impl From<BoxedFramer> for Framer {
     fn from(encoder: BoxedFramer) -> Self {
         Self::Boxed(encoder)
@@ -165,6 +206,7 @@ impl tokio_util::codec::Encoder<()> for Framer {
             Framer::CharacterDelimited(framer) => framer.encode((), buffer),
             Framer::LengthDelimited(framer) => framer.encode((), buffer),
             Framer::NewlineDelimited(framer) => framer.encode((), buffer),
+            Framer::VarintLengthDelimited(framer) => framer.encode((), buffer),
             Framer::Boxed(framer) => framer.encode((), buffer),
         }
     }
@@ -184,6 +226,14 @@ pub enum SerializerConfig {
         avro: AvroSerializerOptions,
     },
 
+    /// Encodes an event as a [Common Event Format (CEF)][cef] message.
+    ///
+    /// The header fields of the message and its extensions are taken from the fields of the
+    /// event, for security information and event management (SIEM) systems such as ArcSight.
+    ///
+    /// [cef]: https://www.microfocus.com/documentation/arcsight/arcsight-smartconnectors-8.4/pdfdoc/cef-implementation-standard/cef-implementation-standard.pdf
+    Cef(CefSerializerConfig),
+
     /// Encodes an event as a CSV message.
     ///
     /// This codec must be configured with fields to encode.
@@ -218,6 +268,14 @@ pub enum SerializerConfig {
     /// [logfmt]: https://brandur.org/logfmt
     Logfmt,
 
+    /// Encodes an event as [MessagePack][msgpack].
+    ///
+    /// Timestamps are encoded with the timestamp extension type, and bytes that aren't valid UTF-8
+    /// are encoded as binaries.
+    ///
+    /// [msgpack]: https://msgpack.org/
+    Msgpack,
+
     /// Encodes an event in the [native Protocol Buffers format][vector_native_protobuf].
     ///
     /// This codec is **[experimental][experimental]**.
@@ -234,6 +292,16 @@ pub enum SerializerConfig {
     /// [experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
     NativeJson,
 
+    #[cfg(feature = "otlp")]
+    /// Encodes an event as an [OTLP][otlp] export request encoded as protobuf.
+    ///
+    /// Logs are encoded as `ExportLogsServiceRequest` messages and metrics as
+    /// `ExportMetricsServiceRequest` messages, converted the same way as with the `opentelemetry`
+    /// sink. Traces and sketches are not supported.
+    ///
+    /// [otlp]: https://opentelemetry.io/docs/specs/otlp/
+    Otlp,
+
     /// Encodes an event as a [Protobuf][protobuf] message.
     ///
     /// [protobuf]: https://protobuf.dev/
@@ -248,6 +316,16 @@ pub enum SerializerConfig {
     /// could lead to the encoding emitting empty strings for the given event.
     RawMessage,
 
+    /// Encodes an event as a [syslog][syslog] message.
+    ///
+    /// The message of the event is written as the body of the message, after a header in the
+    /// format of [RFC 5424][rfc5424] or of [RFC 3164][rfc3164] taken from the fields of the event.
+    ///
+    /// [syslog]: https://en.wikipedia.org/wiki/Syslog
+    /// [rfc5424]: https://datatracker.ietf.org/doc/html/rfc5424
+    /// [rfc3164]: https://datatracker.ietf.org/doc/html/rfc3164
+    Syslog(SyslogSerializerConfig),
+
     /// Plain text encoding.
     ///
     /// This encoding uses the `message` field of a log event. For metrics, it uses an
@@ -265,6 +343,12 @@ impl From<AvroSerializerConfig> for SerializerConfig {
     }
 }
 
+impl From<CefSerializerConfig> for SerializerConfig {
+    fn from(config: CefSerializerConfig) -> Self {
+        Self::Cef(config)
+    }
+}
+
 impl From<CsvSerializerConfig> for SerializerConfig {
     fn from(config: CsvSerializerConfig) -> Self {
         Self::Csv(config)
@@ -289,6 +373,12 @@ impl From<LogfmtSerializerConfig> for SerializerConfig {
     }
 }
 
+impl From<MsgpackSerializerConfig> for SerializerConfig {
+    fn from(_: MsgpackSerializerConfig) -> Self {
+        Self::Msgpack
+    }
+}
+
 impl From<NativeSerializerConfig> for SerializerConfig {
     fn from(_: NativeSerializerConfig) -> Self {
         Self::Native
@@ -301,6 +391,13 @@ impl From<NativeJsonSerializerConfig> for SerializerConfig {
     }
 }
 
+#[cfg(feature = "otlp")]
+impl From<OtlpSerializerConfig> for SerializerConfig {
+    fn from(_: OtlpSerializerConfig) -> Self {
+        Self::Otlp
+    }
+}
+
 impl From<ProtobufSerializerConfig> for SerializerConfig {
     fn from(config: ProtobufSerializerConfig) -> Self {
         Self::Protobuf(config)
@@ -313,6 +410,12 @@ impl From<RawMessageSerializerConfig> for SerializerConfig {
     }
 }
 
+impl From<SyslogSerializerConfig> for SerializerConfig {
+    fn from(config: SyslogSerializerConfig) -> Self {
+        Self::Syslog(config)
+    }
+}
+
 impl From<TextSerializerConfig> for SerializerConfig {
     fn from(config: TextSerializerConfig) -> Self {
         Self::Text(config)
@@ -326,18 +429,23 @@ impl SerializerConfig {
             SerializerConfig::Avro { avro } => Ok(Serializer::Avro(
                 AvroSerializerConfig::new(avro.schema.clone()).build()?,
             )),
+            SerializerConfig::Cef(config) => Ok(Serializer::Cef(config.build()?)),
             SerializerConfig::Csv(config) => Ok(Serializer::Csv(config.build()?)),
             SerializerConfig::Gelf => Ok(Serializer::Gelf(GelfSerializerConfig::new().build())),
             SerializerConfig::Json(config) => Ok(Serializer::Json(config.build())),
             SerializerConfig::Logfmt => Ok(Serializer::Logfmt(LogfmtSerializerConfig.build())),
+            SerializerConfig::Msgpack => Ok(Serializer::Msgpack(MsgpackSerializerConfig.build())),
             SerializerConfig::Native => Ok(Serializer::Native(NativeSerializerConfig.build())),
             SerializerConfig::NativeJson => {
                 Ok(Serializer::NativeJson(NativeJsonSerializerConfig.build()))
             }
+            #[cfg(feature = "otlp")]
+            SerializerConfig::Otlp => Ok(Serializer::Otlp(OtlpSerializerConfig.build())),
             SerializerConfig::Protobuf(config) => Ok(Serializer::Protobuf(config.build()?)),
             SerializerConfig::RawMessage => {
                 Ok(Serializer::RawMessage(RawMessageSerializerConfig.build()))
             }
+            SerializerConfig::Syslog(config) => Ok(Serializer::Syslog(config.build())),
             SerializerConfig::Text(config) => Ok(Serializer::Text(config.build())),
         }
     }
@@ -357,16 +465,23 @@ impl SerializerConfig {
             //
             // [1]: https://avro.apache.org/docs/1.11.1/specification/_print/#message-framing
             SerializerConfig::Avro { .. }
+            | SerializerConfig::Msgpack
             | SerializerConfig::Native
             | SerializerConfig::Protobuf(_) => {
                 FramingConfig::LengthDelimited(LengthDelimitedEncoderConfig::default())
             }
-            SerializerConfig::Csv(_)
+            #[cfg(feature = "otlp")]
+            SerializerConfig::Otlp => {
+                FramingConfig::LengthDelimited(LengthDelimitedEncoderConfig::default())
+            }
+            SerializerConfig::Cef(_)
+            | SerializerConfig::Csv(_)
             | SerializerConfig::Gelf
             | SerializerConfig::Json(_)
             | SerializerConfig::Logfmt
             | SerializerConfig::NativeJson
             | SerializerConfig::RawMessage
+            | SerializerConfig::Syslog(_)
             | SerializerConfig::Text(_) => FramingConfig::NewlineDelimited,
         }
     }
@@ -377,14 +492,19 @@ impl SerializerConfig {
             SerializerConfig::Avro { avro } => {
                 AvroSerializerConfig::new(avro.schema.clone()).input_type()
             }
+            SerializerConfig::Cef(config) => config.input_type(),
             SerializerConfig::Csv(config) => config.input_type(),
             SerializerConfig::Gelf { .. } => GelfSerializerConfig::input_type(),
             SerializerConfig::Json(config) => config.input_type(),
             SerializerConfig::Logfmt => LogfmtSerializerConfig.input_type(),
+            SerializerConfig::Msgpack => MsgpackSerializerConfig.input_type(),
             SerializerConfig::Native => NativeSerializerConfig.input_type(),
             SerializerConfig::NativeJson => NativeJsonSerializerConfig.input_type(),
+            #[cfg(feature = "otlp")]
+            SerializerConfig::Otlp => OtlpSerializerConfig.input_type(),
             SerializerConfig::Protobuf(config) => config.input_type(),
             SerializerConfig::RawMessage => RawMessageSerializerConfig.input_type(),
+            SerializerConfig::Syslog(config) => config.input_type(),
             SerializerConfig::Text(config) => config.input_type(),
         }
     }
@@ -395,14 +515,19 @@ impl SerializerConfig {
             SerializerConfig::Avro { avro } => {
                 AvroSerializerConfig::new(avro.schema.clone()).schema_requirement()
             }
+            SerializerConfig::Cef(config) => config.schema_requirement(),
             SerializerConfig::Csv(config) => config.schema_requirement(),
             SerializerConfig::Gelf { .. } => GelfSerializerConfig::schema_requirement(),
             SerializerConfig::Json(config) => config.schema_requirement(),
             SerializerConfig::Logfmt => LogfmtSerializerConfig.schema_requirement(),
+            SerializerConfig::Msgpack => MsgpackSerializerConfig.schema_requirement(),
             SerializerConfig::Native => NativeSerializerConfig.schema_requirement(),
             SerializerConfig::NativeJson => NativeJsonSerializerConfig.schema_requirement(),
+            #[cfg(feature = "otlp")]
+            SerializerConfig::Otlp => OtlpSerializerConfig.schema_requirement(),
             SerializerConfig::Protobuf(config) => config.schema_requirement(),
             SerializerConfig::RawMessage => RawMessageSerializerConfig.schema_requirement(),
+            SerializerConfig::Syslog(config) => config.schema_requirement(),
             SerializerConfig::Text(config) => config.schema_requirement(),
         }
     }
@@ -413,6 +538,8 @@ impl SerializerConfig {
 pub enum Serializer {
     /// Uses an `AvroSerializer` for serialization.
     Avro(AvroSerializer),
+    /// Uses a `CefSerializer` for serialization.
+    Cef(CefSerializer),
     /// Uses a `CsvSerializer` for serialization.
     Csv(CsvSerializer),
     /// Uses a `GelfSerializer` for serialization.
@@ -421,14 +548,21 @@ pub enum Serializer {
     Json(JsonSerializer),
     /// Uses a `LogfmtSerializer` for serialization.
     Logfmt(LogfmtSerializer),
+    /// Uses a `MsgpackSerializer` for serialization.
+    Msgpack(MsgpackSerializer),
     /// Uses a `NativeSerializer` for serialization.
     Native(NativeSerializer),
     /// Uses a `NativeJsonSerializer` for serialization.
     NativeJson(NativeJsonSerializer),
+    #[cfg(feature = "otlp")]
+    /// Uses an `OtlpSerializer` for serialization.
+    Otlp(OtlpSerializer),
     /// Uses a `ProtobufSerializer` for serialization.
     Protobuf(ProtobufSerializer),
     /// Uses a `RawMessageSerializer` for serialization.
     RawMessage(RawMessageSerializer),
+    /// Uses a `SyslogSerializer` for serialization.
+    Syslog(SyslogSerializer),
     /// Uses a `TextSerializer` for serialization.
     Text(TextSerializer),
 }
@@ -439,12 +573,17 @@ impl Serializer {
         match self {
             Serializer::Json(_) | Serializer::NativeJson(_) | Serializer::Gelf(_) => true,
             Serializer::Avro(_)
+            | Serializer::Cef(_)
             | Serializer::Csv(_)
             | Serializer::Logfmt(_)
+            | Serializer::Msgpack(_)
             | Serializer::Text(_)
             | Serializer::Native(_)
             | Serializer::Protobuf(_)
-            | Serializer::RawMessage(_) => false,
+            | Serializer::RawMessage(_)
+            | Serializer::Syslog(_) => false,
+            #[cfg(feature = "otlp")]
+            Serializer::Otlp(_) => false,
         }
     }
 
@@ -460,14 +599,19 @@ impl Serializer {
             Serializer::Json(serializer) => serializer.to_json_value(event),
             Serializer::NativeJson(serializer) => serializer.to_json_value(event),
             Serializer::Avro(_)
+            | Serializer::Cef(_)
             | Serializer::Csv(_)
             | Serializer::Logfmt(_)
+            | Serializer::Msgpack(_)
             | Serializer::Text(_)
             | Serializer::Native(_)
             | Serializer::Protobuf(_)
-            | Serializer::RawMessage(_) => {
+            | Serializer::RawMessage(_)
+            | Serializer::Syslog(_) => {
                 panic!("Serializer does not support JSON")
             }
+            #[cfg(feature = "otlp")]
+            Serializer::Otlp(_) => panic!("Serializer does not support JSON"),
         }
     }
 }
@@ -478,6 +622,12 @@ impl From<AvroSerializer> for Serializer {
     }
 }
 
+impl From<CefSerializer> for Serializer {
+    fn from(serializer: CefSerializer) -> Self {
+        Self::Cef(serializer)
+    }
+}
+
 impl From<CsvSerializer> for Serializer {
     fn from(serializer: CsvSerializer) -> Self {
         Self::Csv(serializer)
@@ -502,6 +652,12 @@ impl From<LogfmtSerializer> for Serializer {
     }
 }
 
+impl From<MsgpackSerializer> for Serializer {
+    fn from(serializer: MsgpackSerializer) -> Self {
+        Self::Msgpack(serializer)
+    }
+}
+
 impl From<NativeSerializer> for Serializer {
     fn from(serializer: NativeSerializer) -> Self {
         Self::Native(serializer)
@@ -514,6 +670,13 @@ impl From<NativeJsonSerializer> for Serializer {
     }
 }
 
+#[cfg(feature = "otlp")]
+impl From<OtlpSerializer> for Serializer {
+    fn from(serializer: OtlpSerializer) -> Self {
+        Self::Otlp(serializer)
+    }
+}
+
 impl From<ProtobufSerializer> for Serializer {
     fn from(serializer: ProtobufSerializer) -> Self {
         Self::Protobuf(serializer)
@@ -526,6 +689,12 @@ impl From<RawMessageSerializer> for Serializer {
     }
 }
 
+impl From<SyslogSerializer> for Serializer {
+    fn from(serializer: SyslogSerializer) -> Self {
+        Self::Syslog(serializer)
+    }
+}
+
 impl From<TextSerializer> for Serializer {
     fn from(serializer: TextSerializer) -> Self {
         Self::Text(serializer)
@@ -538,14 +707,19 @@ impl tokio_util::codec::Encoder<Event> for Serializer {
     fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
         match self {
             Serializer::Avro(serializer) => serializer.encode(event, buffer),
+            Serializer::Cef(serializer) => serializer.encode(event, buffer),
             Serializer::Csv(serializer) => serializer.encode(event, buffer),
             Serializer::Gelf(serializer) => serializer.encode(event, buffer),
             Serializer::Json(serializer) => serializer.encode(event, buffer),
             Serializer::Logfmt(serializer) => serializer.encode(event, buffer),
+            Serializer::Msgpack(serializer) => serializer.encode(event, buffer),
             Serializer::Native(serializer) => serializer.encode(event, buffer),
             Serializer::NativeJson(serializer) => serializer.encode(event, buffer),
+            #[cfg(feature = "otlp")]
+            Serializer::Otlp(serializer) => serializer.encode(event, buffer),
             Serializer::Protobuf(serializer) => serializer.encode(event, buffer),
             Serializer::RawMessage(serializer) => serializer.encode(event, buffer),
+            Serializer::Syslog(serializer) => serializer.encode(event, buffer),
             Serializer::Text(serializer) => serializer.encode(event, buffer),
         }
     }
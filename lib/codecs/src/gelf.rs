@@ -72,3 +72,15 @@ pub(crate) static GELF_TARGET_PATHS: Lazy<GelfTargetPaths> = Lazy::new(|| GelfTa
 /// As Graylog itself will produce GELF with any existing field names on the Graylog GELF Output,
 /// vector is more lenient, too, at least allowing the additional `@` character.
 pub static VALID_FIELD_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^[\w\.\-@]*$").unwrap());
+
+/// The magic bytes that start the chunks of [chunked][chunking] GELF messages.
+///
+/// [chunking]: https://go2docs.graylog.org/current/getting_in_log_data/gelf.html#GELFviaUDP
+pub(crate) const GELF_CHUNK_MAGIC: [u8; 2] = [0x1e, 0x0f];
+
+/// The length of the header of GELF chunks: the magic bytes, the 8 bytes of the ID of the message,
+/// the sequence number of the chunk, and the number of chunks of the message.
+pub(crate) const GELF_CHUNK_HEADER_LENGTH: usize = 12;
+
+/// The maximum number of chunks of a GELF message.
+pub(crate) const GELF_MAX_CHUNKS: usize = 128;
@@ -11,23 +11,32 @@ pub mod gelf;
 
 pub use decoding::{
     BytesDecoder, BytesDecoderConfig, BytesDeserializer, BytesDeserializerConfig,
-    CharacterDelimitedDecoder, CharacterDelimitedDecoderConfig, GelfDeserializer,
-    GelfDeserializerConfig, JsonDeserializer, JsonDeserializerConfig, LengthDelimitedDecoder,
-    LengthDelimitedDecoderConfig, NativeDeserializer, NativeDeserializerConfig,
-    NativeJsonDeserializer, NativeJsonDeserializerConfig, NewlineDelimitedDecoder,
-    NewlineDelimitedDecoderConfig, OctetCountingDecoder, OctetCountingDecoderConfig,
-    StreamDecodingError,
+    CharacterDelimitedDecoder, CharacterDelimitedDecoderConfig, ChunkedGelfDecoder,
+    ChunkedGelfDecoderConfig, GelfDeserializer, GelfDeserializerConfig, JsonDeserializer,
+    JsonDeserializerConfig, LengthDelimitedDecoder, LengthDelimitedDecoderConfig,
+    MsgpackDeserializer, MsgpackDeserializerConfig, MultilineDecoder, MultilineDecoderConfig,
+    NativeDeserializer, NativeDeserializerConfig, NativeJsonDeserializer,
+    NativeJsonDeserializerConfig, NewlineDelimitedDecoder, NewlineDelimitedDecoderConfig,
+    OctetCountingDecoder, OctetCountingDecoderConfig, StreamDecodingError,
+    VarintLengthDelimitedDecoder, VarintLengthDelimitedDecoderConfig,
 };
+#[cfg(feature = "otlp")]
+pub use decoding::{OtlpDeserializer, OtlpDeserializerConfig};
 #[cfg(feature = "syslog")]
 pub use decoding::{SyslogDeserializer, SyslogDeserializerConfig};
 pub use encoding::{
-    BytesEncoder, BytesEncoderConfig, CharacterDelimitedEncoder, CharacterDelimitedEncoderConfig,
-    CsvSerializer, CsvSerializerConfig, GelfSerializer, GelfSerializerConfig, JsonSerializer,
-    JsonSerializerConfig, LengthDelimitedEncoder, LengthDelimitedEncoderConfig, LogfmtSerializer,
-    LogfmtSerializerConfig, NativeJsonSerializer, NativeJsonSerializerConfig, NativeSerializer,
-    NativeSerializerConfig, NewlineDelimitedEncoder, NewlineDelimitedEncoderConfig,
-    RawMessageSerializer, RawMessageSerializerConfig, TextSerializer, TextSerializerConfig,
+    BytesEncoder, BytesEncoderConfig, CefSerializer, CefSerializerConfig,
+    CharacterDelimitedEncoder, CharacterDelimitedEncoderConfig, CsvSerializer, CsvSerializerConfig,
+    GelfSerializer, GelfSerializerConfig, JsonSerializer, JsonSerializerConfig,
+    JsonSerializerOptions, LengthDelimitedEncoder, LengthDelimitedEncoderConfig, LogfmtSerializer,
+    LogfmtSerializerConfig, MsgpackSerializer, MsgpackSerializerConfig, NativeJsonSerializer,
+    NativeJsonSerializerConfig, NativeSerializer, NativeSerializerConfig, NewlineDelimitedEncoder,
+    NewlineDelimitedEncoderConfig, RawMessageSerializer, RawMessageSerializerConfig,
+    SyslogSerializer, SyslogSerializerConfig, TextSerializer, TextSerializerConfig,
+    VarintLengthDelimitedEncoder, VarintLengthDelimitedEncoderConfig,
 };
+#[cfg(feature = "otlp")]
+pub use encoding::{OtlpSerializer, OtlpSerializerConfig};
 pub use gelf::{gelf_fields, VALID_FIELD_REGEX};
 use vector_config::configurable_component;
 
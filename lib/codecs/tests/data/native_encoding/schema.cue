@@ -1,4 +1,8 @@
-#Event: {log: #Log} | {metric: #Metric} | {trace: #Trace}
+#Event: {
+	// The version of the native encoding, omitted when it's `0`.
+	version?: int & >=0
+	{log: #Log} | {metric: #Metric} | {trace: #Trace}
+}
 
 #Log: {...}
 
@@ -9,8 +9,12 @@ use codecs::{
     decoding::format::Deserializer, encoding::format::Serializer, NativeDeserializerConfig,
     NativeJsonDeserializerConfig, NativeJsonSerializerConfig, NativeSerializerConfig,
 };
+use prost::Message;
 use similar_asserts::assert_eq;
-use vector_core::{config::LogNamespace, event::Event};
+use vector_core::{
+    config::LogNamespace,
+    event::{proto, Event, EventArray, LogEvent},
+};
 
 #[test]
 fn pre_v24_fixtures_match() {
@@ -49,6 +53,21 @@ fn roundtrip_current_native_proto_fixtures() {
     );
 }
 
+#[test]
+fn rejects_newer_native_proto_versions() {
+    let events = EventArray::from(LogEvent::from("foo"));
+    let mut array = proto::EventArray::from(events);
+    array.version = proto::NATIVE_VERSION + 1;
+
+    let error = NativeDeserializerConfig
+        .build()
+        .parse(array.encode_to_vec().into(), LogNamespace::Legacy)
+        .unwrap_err()
+        .to_string();
+
+    assert!(error.contains("upgrade this Vector"), "{}", error);
+}
+
 /// The event proto file was changed in v0.24. This test ensures we can still load the old version
 /// binary and that when serialized and deserialized in the new format we still get the same event.
 #[test]
@@ -9,7 +9,11 @@ use codecs::decoding::format::Deserializer;
 use codecs::decoding::{
     ProtobufDeserializer, ProtobufDeserializerConfig, ProtobufDeserializerOptions,
 };
-use codecs::encoding::{ProtobufSerializer, ProtobufSerializerConfig, ProtobufSerializerOptions};
+use codecs::encoding::{
+    ProtobufFieldMapping, ProtobufSerializer, ProtobufSerializerConfig, ProtobufSerializerOptions,
+};
+use vector_core::event::{Event, LogEvent};
+use vrl::value;
 
 fn test_data_dir() -> PathBuf {
     PathBuf::from(std::env::var_os("CARGO_MANIFEST_DIR").unwrap()).join("tests/data/protobuf")
@@ -29,6 +33,7 @@ fn build_serializer_pair(
         protobuf: ProtobufSerializerOptions {
             desc_file: desc_file.clone(),
             message_type: message_type.clone(),
+            field_mappings: Vec::new(),
         },
     }
     .build()
@@ -66,3 +71,43 @@ fn roundtrip_coding() {
         .unwrap();
     assert_eq!(events_original, events_encoded);
 }
+
+#[test]
+fn encode_mapped_fields() {
+    let desc_file = test_data_dir().join("protos/test_protobuf.desc");
+    let mut serializer = ProtobufSerializerConfig {
+        protobuf: ProtobufSerializerOptions {
+            desc_file: desc_file.clone(),
+            message_type: "test_protobuf.Person".into(),
+            field_mappings: vec![
+                ProtobufFieldMapping {
+                    from: "user.name".into(),
+                    to: "name".into(),
+                },
+                ProtobufFieldMapping {
+                    from: "user.contact".into(),
+                    to: "email".into(),
+                },
+            ],
+        },
+    }
+    .build()
+    .unwrap();
+    let (_, deserializer) = build_serializer_pair(desc_file, "test_protobuf.Person".into());
+
+    let event = Event::Log(LogEvent::from(value!({
+        "id": 7,
+        "user": {"name": "someone", "contact": "someone@example.com"},
+    })));
+    let mut message = BytesMut::new();
+    serializer.encode(event, &mut message).unwrap();
+
+    let events = deserializer
+        .parse(message.into(), LogNamespace::Vector)
+        .unwrap();
+    assert_eq!(1, events.len());
+    let log = events[0].as_log();
+    assert_eq!(log["name"], "someone".into());
+    assert_eq!(log["email"], "someone@example.com".into());
+    assert_eq!(log["id"], 7.into());
+}
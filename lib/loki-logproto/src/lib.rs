@@ -15,8 +15,8 @@ pub mod util {
 
     const NANOS_RANGE: i64 = 1_000_000_000;
 
-    // (<Timestamp in nanos>, <Line>)
-    pub struct Entry(pub i64, pub String);
+    // (<Timestamp in nanos>, <Line>, <Structured metadata>)
+    pub struct Entry(pub i64, pub String, pub Vec<(String, String)>);
 
     impl From<Entry> for logproto::EntryAdapter {
         fn from(entry: Entry) -> Self {
@@ -26,6 +26,11 @@ pub mod util {
                     nanos: (entry.0 % NANOS_RANGE) as i32,
                 }),
                 line: entry.1,
+                structured_metadata: entry
+                    .2
+                    .into_iter()
+                    .map(|(name, value)| logproto::LabelPairAdapter { name, value })
+                    .collect(),
             }
         }
     }
@@ -75,7 +80,7 @@ pub mod util {
 
 #[cfg(test)]
 mod tests {
-    use super::util;
+    use super::{logproto, util};
     use crate::util::{Batch, Entry, Stream};
     use chrono::prelude::*;
     use std::collections::HashMap;
@@ -104,6 +109,7 @@ mod tests {
         let entry1 = Entry(
             ts1.timestamp_nanos_opt().expect("Timestamp out of range"),
             "hello".into(),
+            vec![],
         );
         let ts2 = Utc
             .timestamp_opt(1640244791, 0)
@@ -112,6 +118,7 @@ mod tests {
         let entry2 = Entry(
             ts2.timestamp_nanos_opt().expect("Timestamp out of range"),
             "world".into(),
+            vec![],
         );
         let labels = vec![("source".into(), "protobuf-test".into())]
             .into_iter()
@@ -127,4 +134,21 @@ mod tests {
         let buf = batch.encode();
         assert_eq!(expect, buf);
     }
+
+    #[test]
+    fn encode_structured_metadata() {
+        let entry = Entry(
+            1_640_244_790_000_000_000,
+            "hello".into(),
+            vec![("trace_id".into(), "abc".into())],
+        );
+        let entry = logproto::EntryAdapter::from(entry);
+        assert_eq!(
+            entry.structured_metadata,
+            vec![logproto::LabelPairAdapter {
+                name: "trace_id".into(),
+                value: "abc".into(),
+            }]
+        );
+    }
 }
@@ -14,7 +14,12 @@ use super::proto::{
     resource::v1::Resource,
 };
 
-const SOURCE_NAME: &str = "opentelemetry";
+mod metrics;
+mod spans;
+
+pub(crate) use self::metrics::{RESOURCE_TAG_PREFIX, SCOPE_TAG_PREFIX};
+
+pub(crate) const SOURCE_NAME: &str = "opentelemetry";
 
 pub const RESOURCE_KEY: &str = "resources";
 pub const ATTRIBUTES_KEY: &str = "attributes";
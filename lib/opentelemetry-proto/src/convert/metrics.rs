@@ -0,0 +1,545 @@
+use chrono::{DateTime, TimeZone, Utc};
+use vector_core::event::{
+    metric::{Bucket, Quantile},
+    Event, Metric, MetricKind, MetricTags, MetricValue,
+};
+use vrl::value::Value;
+
+use crate::proto::{
+    common::v1::{InstrumentationScope, KeyValue},
+    metrics::v1::{
+        exponential_histogram_data_point::Buckets, metric::Data, number_data_point,
+        AggregationTemporality, DataPointFlags, ExponentialHistogramDataPoint, HistogramDataPoint,
+        NumberDataPoint, ResourceMetrics, SummaryDataPoint,
+    },
+    resource::v1::Resource,
+};
+
+/// Prefix of the tags holding resource attributes.
+pub(crate) const RESOURCE_TAG_PREFIX: &str = "resource.";
+
+/// Prefix of the tags describing the instrumentation scope.
+pub(crate) const SCOPE_TAG_PREFIX: &str = "scope.";
+
+impl ResourceMetrics {
+    pub fn into_event_iter(self) -> impl Iterator<Item = Event> {
+        let resource_tags = resource_tags(self.resource);
+
+        self.scope_metrics
+            .into_iter()
+            .flat_map(move |scope_metrics| {
+                let mut base_tags = resource_tags.clone();
+                extend_with_scope(&mut base_tags, scope_metrics.scope);
+
+                scope_metrics
+                    .metrics
+                    .into_iter()
+                    .flat_map(move |metric| {
+                        let name = metric.name;
+                        metric
+                            .data
+                            .map(|data| data_into_metrics(&name, data, &base_tags))
+                            .unwrap_or_default()
+                    })
+                    .map(Event::Metric)
+            })
+    }
+}
+
+fn resource_tags(resource: Option<Resource>) -> MetricTags {
+    let mut tags = MetricTags::default();
+    if let Some(resource) = resource {
+        extend_with_attributes(&mut tags, RESOURCE_TAG_PREFIX, resource.attributes);
+    }
+    tags
+}
+
+fn extend_with_scope(tags: &mut MetricTags, scope: Option<InstrumentationScope>) {
+    let Some(scope) = scope else {
+        return;
+    };
+    if !scope.name.is_empty() {
+        tags.replace(format!("{SCOPE_TAG_PREFIX}name"), scope.name);
+    }
+    if !scope.version.is_empty() {
+        tags.replace(format!("{SCOPE_TAG_PREFIX}version"), scope.version);
+    }
+    extend_with_attributes(tags, SCOPE_TAG_PREFIX, scope.attributes);
+}
+
+/// Adds attributes as tags. Tags are flat strings, so non-string values are rendered in their
+/// string form.
+fn extend_with_attributes(tags: &mut MetricTags, prefix: &str, attributes: Vec<KeyValue>) {
+    for kv in attributes {
+        let value = kv
+            .value
+            .and_then(|av| av.value)
+            .map(Value::from)
+            .unwrap_or(Value::Null);
+        tags.replace(
+            format!("{prefix}{}", kv.key),
+            value.to_string_lossy().into_owned(),
+        );
+    }
+}
+
+fn timestamp(time_unix_nano: u64) -> Option<DateTime<Utc>> {
+    (time_unix_nano > 0).then(|| Utc.timestamp_nanos(time_unix_nano as i64))
+}
+
+/// Points flagged with "no recorded value" mark a stale series and carry no data.
+fn has_no_recorded_value(flags: u32) -> bool {
+    flags & DataPointFlags::NoRecordedValueMask as u32 != 0
+}
+
+/// Delta temporality maps to incremental metrics, while cumulative (and unspecified) temporality
+/// maps to absolute ones.
+fn metric_kind(temporality: i32) -> MetricKind {
+    if temporality == AggregationTemporality::Delta as i32 {
+        MetricKind::Incremental
+    } else {
+        MetricKind::Absolute
+    }
+}
+
+fn build_metric(
+    name: &str,
+    kind: MetricKind,
+    value: MetricValue,
+    base_tags: &MetricTags,
+    attributes: Vec<KeyValue>,
+    time_unix_nano: u64,
+) -> Metric {
+    let mut tags = base_tags.clone();
+    extend_with_attributes(&mut tags, "", attributes);
+
+    Metric::new(name, kind, value)
+        .with_tags((!tags.is_empty()).then_some(tags))
+        .with_timestamp(timestamp(time_unix_nano))
+}
+
+// https://github.com/open-telemetry/opentelemetry-specification/blob/v1.15.0/specification/metrics/data-model.md
+fn data_into_metrics(name: &str, data: Data, base_tags: &MetricTags) -> Vec<Metric> {
+    match data {
+        Data::Gauge(gauge) => gauge
+            .data_points
+            .into_iter()
+            .filter(|point| !has_no_recorded_value(point.flags))
+            .map(|point| {
+                let value = MetricValue::Gauge {
+                    value: number_value(&point),
+                };
+                build_metric(
+                    name,
+                    MetricKind::Absolute,
+                    value,
+                    base_tags,
+                    point.attributes,
+                    point.time_unix_nano,
+                )
+            })
+            .collect(),
+        Data::Sum(sum) => {
+            let kind = metric_kind(sum.aggregation_temporality);
+            sum.data_points
+                .into_iter()
+                .filter(|point| !has_no_recorded_value(point.flags))
+                .map(|point| {
+                    let value = number_value(&point);
+                    // Non-monotonic sums, such as up/down counters, can decrease and are better
+                    // represented by gauges.
+                    let value = if sum.is_monotonic {
+                        MetricValue::Counter { value }
+                    } else {
+                        MetricValue::Gauge { value }
+                    };
+                    build_metric(
+                        name,
+                        kind,
+                        value,
+                        base_tags,
+                        point.attributes,
+                        point.time_unix_nano,
+                    )
+                })
+                .collect()
+        }
+        Data::Histogram(histogram) => {
+            let kind = metric_kind(histogram.aggregation_temporality);
+            histogram
+                .data_points
+                .into_iter()
+                .filter(|point| !has_no_recorded_value(point.flags))
+                .map(|point| {
+                    let time_unix_nano = point.time_unix_nano;
+                    let (value, attributes) = histogram_value(point);
+                    build_metric(name, kind, value, base_tags, attributes, time_unix_nano)
+                })
+                .collect()
+        }
+        Data::ExponentialHistogram(histogram) => {
+            let kind = metric_kind(histogram.aggregation_temporality);
+            histogram
+                .data_points
+                .into_iter()
+                .filter(|point| !has_no_recorded_value(point.flags))
+                .map(|point| {
+                    let time_unix_nano = point.time_unix_nano;
+                    let (value, attributes) = exponential_histogram_value(point);
+                    build_metric(name, kind, value, base_tags, attributes, time_unix_nano)
+                })
+                .collect()
+        }
+        Data::Summary(summary) => summary
+            .data_points
+            .into_iter()
+            .filter(|point| !has_no_recorded_value(point.flags))
+            .map(|point| {
+                let time_unix_nano = point.time_unix_nano;
+                let (value, attributes) = summary_value(point);
+                build_metric(
+                    name,
+                    MetricKind::Absolute,
+                    value,
+                    base_tags,
+                    attributes,
+                    time_unix_nano,
+                )
+            })
+            .collect(),
+    }
+}
+
+fn number_value(point: &NumberDataPoint) -> f64 {
+    match point.value {
+        Some(number_data_point::Value::AsDouble(value)) => value,
+        Some(number_data_point::Value::AsInt(value)) => value as f64,
+        None => 0.0,
+    }
+}
+
+fn histogram_value(point: HistogramDataPoint) -> (MetricValue, Vec<KeyValue>) {
+    // There is one more bucket count than explicit bounds, the last one counting the observations
+    // above the highest bound. Like other aggregated histograms, that implicit `+Inf` bucket is
+    // only represented through the total count.
+    let buckets = point
+        .explicit_bounds
+        .iter()
+        .zip(point.bucket_counts.iter())
+        .map(|(&upper_limit, &count)| Bucket { upper_limit, count })
+        .collect();
+
+    let value = MetricValue::AggregatedHistogram {
+        buckets,
+        count: point.count,
+        sum: point.sum.unwrap_or_default(),
+    };
+    (value, point.attributes)
+}
+
+/// Converts an exponential histogram into an aggregated histogram, with one bucket per populated
+/// exponential bucket.
+///
+/// The upper bound of the positive bucket at index `i` is `base^(offset + i + 1)`, with
+/// `base = 2^(2^-scale)`. Negative buckets mirror them below zero, and the zero bucket is bounded
+/// by the zero threshold.
+fn exponential_histogram_value(
+    point: ExponentialHistogramDataPoint,
+) -> (MetricValue, Vec<KeyValue>) {
+    let base = 2f64.powf(2f64.powi(-point.scale));
+    let mut buckets = Vec::new();
+
+    if let Some(Buckets {
+        offset,
+        bucket_counts,
+    }) = point.negative
+    {
+        // The most negative bucket comes first, so walk the indices downwards.
+        for (index, count) in bucket_counts.into_iter().enumerate().rev() {
+            buckets.push(Bucket {
+                upper_limit: -base.powi(offset + index as i32),
+                count,
+            });
+        }
+    }
+
+    buckets.push(Bucket {
+        upper_limit: point.zero_threshold,
+        count: point.zero_count,
+    });
+
+    if let Some(Buckets {
+        offset,
+        bucket_counts,
+    }) = point.positive
+    {
+        for (index, count) in bucket_counts.into_iter().enumerate() {
+            buckets.push(Bucket {
+                upper_limit: base.powi(offset + index as i32 + 1),
+                count,
+            });
+        }
+    }
+
+    let value = MetricValue::AggregatedHistogram {
+        buckets,
+        count: point.count,
+        sum: point.sum.unwrap_or_default(),
+    };
+    (value, point.attributes)
+}
+
+fn summary_value(point: SummaryDataPoint) -> (MetricValue, Vec<KeyValue>) {
+    let quantiles = point
+        .quantile_values
+        .into_iter()
+        .map(|quantile| Quantile {
+            quantile: quantile.quantile,
+            value: quantile.value,
+        })
+        .collect();
+
+    let value = MetricValue::AggregatedSummary {
+        quantiles,
+        count: point.count,
+        sum: point.sum,
+    };
+    (value, point.attributes)
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_core::metric_tags;
+
+    use super::*;
+    use crate::proto::{
+        common::v1::{any_value, AnyValue},
+        metrics::v1::{
+            summary_data_point::ValueAtQuantile, ExponentialHistogram, Gauge, Histogram,
+            Metric as PBMetric, ScopeMetrics, Sum, Summary,
+        },
+    };
+
+    fn kv(key: &str, value: any_value::Value) -> KeyValue {
+        KeyValue {
+            key: key.into(),
+            value: Some(AnyValue { value: Some(value) }),
+        }
+    }
+
+    fn convert(data: Data) -> Vec<Metric> {
+        let resource_metrics = ResourceMetrics {
+            resource: Some(Resource {
+                attributes: vec![kv(
+                    "service.name",
+                    any_value::Value::StringValue("checkout".into()),
+                )],
+                dropped_attributes_count: 0,
+            }),
+            scope_metrics: vec![ScopeMetrics {
+                scope: Some(InstrumentationScope {
+                    name: "meter".into(),
+                    version: "1.0".into(),
+                    attributes: vec![],
+                    dropped_attributes_count: 0,
+                }),
+                metrics: vec![PBMetric {
+                    name: "requests".into(),
+                    description: String::new(),
+                    unit: String::new(),
+                    data: Some(data),
+                }],
+                schema_url: String::new(),
+            }],
+            schema_url: String::new(),
+        };
+
+        resource_metrics
+            .into_event_iter()
+            .map(Event::into_metric)
+            .collect()
+    }
+
+    fn number_point(value: number_data_point::Value, flags: u32) -> NumberDataPoint {
+        NumberDataPoint {
+            attributes: vec![kv("code", any_value::Value::IntValue(200))],
+            start_time_unix_nano: 0,
+            time_unix_nano: 1_700_000_000_000_000_000,
+            exemplars: vec![],
+            flags,
+            value: Some(value),
+        }
+    }
+
+    #[test]
+    fn gauge_with_resource_scope_and_point_tags() {
+        let metrics = convert(Data::Gauge(Gauge {
+            data_points: vec![number_point(number_data_point::Value::AsDouble(1.5), 0)],
+        }));
+
+        assert_eq!(metrics.len(), 1);
+        let metric = &metrics[0];
+        assert_eq!(metric.name(), "requests");
+        assert_eq!(metric.kind(), MetricKind::Absolute);
+        assert_eq!(metric.value(), &MetricValue::Gauge { value: 1.5 });
+        assert_eq!(
+            metric.tags(),
+            Some(&metric_tags!(
+                "resource.service.name" => "checkout",
+                "scope.name" => "meter",
+                "scope.version" => "1.0",
+                "code" => "200",
+            ))
+        );
+        assert_eq!(
+            metric.timestamp(),
+            Some(Utc.timestamp_nanos(1_700_000_000_000_000_000))
+        );
+    }
+
+    #[test]
+    fn sums_follow_temporality_and_monotonicity() {
+        let sum = |temporality: AggregationTemporality, is_monotonic| {
+            convert(Data::Sum(Sum {
+                data_points: vec![number_point(number_data_point::Value::AsInt(3), 0)],
+                aggregation_temporality: temporality as i32,
+                is_monotonic,
+            }))
+            .remove(0)
+        };
+
+        let delta = sum(AggregationTemporality::Delta, true);
+        assert_eq!(delta.kind(), MetricKind::Incremental);
+        assert_eq!(delta.value(), &MetricValue::Counter { value: 3.0 });
+
+        let cumulative = sum(AggregationTemporality::Cumulative, true);
+        assert_eq!(cumulative.kind(), MetricKind::Absolute);
+        assert_eq!(cumulative.value(), &MetricValue::Counter { value: 3.0 });
+
+        let up_down = sum(AggregationTemporality::Cumulative, false);
+        assert_eq!(up_down.value(), &MetricValue::Gauge { value: 3.0 });
+    }
+
+    #[test]
+    fn points_without_recorded_value_are_skipped() {
+        let metrics = convert(Data::Gauge(Gauge {
+            data_points: vec![number_point(
+                number_data_point::Value::AsDouble(1.0),
+                DataPointFlags::NoRecordedValueMask as u32,
+            )],
+        }));
+
+        assert!(metrics.is_empty());
+    }
+
+    #[test]
+    fn histogram_buckets() {
+        let metric = convert(Data::Histogram(Histogram {
+            data_points: vec![HistogramDataPoint {
+                attributes: vec![],
+                start_time_unix_nano: 0,
+                time_unix_nano: 0,
+                count: 6,
+                sum: Some(12.0),
+                bucket_counts: vec![1, 2, 3],
+                explicit_bounds: vec![1.0, 5.0],
+                exemplars: vec![],
+                flags: 0,
+                min: None,
+                max: None,
+            }],
+            aggregation_temporality: AggregationTemporality::Delta as i32,
+        }))
+        .remove(0);
+
+        assert_eq!(metric.kind(), MetricKind::Incremental);
+        assert_eq!(
+            metric.value(),
+            &MetricValue::AggregatedHistogram {
+                buckets: vec![
+                    Bucket {
+                        upper_limit: 1.0,
+                        count: 1
+                    },
+                    Bucket {
+                        upper_limit: 5.0,
+                        count: 2
+                    },
+                ],
+                count: 6,
+                sum: 12.0,
+            }
+        );
+        assert_eq!(metric.timestamp(), None);
+    }
+
+    #[test]
+    fn exponential_histogram_buckets() {
+        let metric = convert(Data::ExponentialHistogram(ExponentialHistogram {
+            data_points: vec![ExponentialHistogramDataPoint {
+                attributes: vec![],
+                start_time_unix_nano: 0,
+                time_unix_nano: 0,
+                count: 7,
+                sum: Some(10.0),
+                scale: 0,
+                zero_count: 1,
+                positive: Some(Buckets {
+                    offset: 0,
+                    bucket_counts: vec![2, 3],
+                }),
+                negative: Some(Buckets {
+                    offset: 1,
+                    bucket_counts: vec![1],
+                }),
+                flags: 0,
+                exemplars: vec![],
+                min: None,
+                max: None,
+                zero_threshold: 0.0,
+            }],
+            aggregation_temporality: AggregationTemporality::Cumulative as i32,
+        }))
+        .remove(0);
+
+        let MetricValue::AggregatedHistogram { buckets, count, .. } = metric.value() else {
+            panic!("unexpected value: {:?}", metric.value());
+        };
+        let limits = buckets.iter().map(|b| b.upper_limit).collect::<Vec<_>>();
+        let counts = buckets.iter().map(|b| b.count).collect::<Vec<_>>();
+        assert_eq!(limits, [-2.0, 0.0, 2.0, 4.0]);
+        assert_eq!(counts, [1, 1, 2, 3]);
+        assert_eq!(*count, 7);
+    }
+
+    #[test]
+    fn summary_quantiles() {
+        let metric = convert(Data::Summary(Summary {
+            data_points: vec![SummaryDataPoint {
+                attributes: vec![],
+                start_time_unix_nano: 0,
+                time_unix_nano: 0,
+                count: 4,
+                sum: 8.0,
+                quantile_values: vec![ValueAtQuantile {
+                    quantile: 0.5,
+                    value: 2.0,
+                }],
+                flags: 0,
+            }],
+        }))
+        .remove(0);
+
+        assert_eq!(
+            metric.value(),
+            &MetricValue::AggregatedSummary {
+                quantiles: vec![Quantile {
+                    quantile: 0.5,
+                    value: 2.0
+                }],
+                count: 4,
+                sum: 8.0,
+            }
+        );
+    }
+}
@@ -0,0 +1,192 @@
+use bytes::Bytes;
+use chrono::{DateTime, TimeZone, Utc};
+use lookup::event_path;
+use vector_core::event::{Event, TraceEvent};
+use vrl::value::{ObjectMap, Value};
+
+use super::{
+    kv_list_into_value, ATTRIBUTES_KEY, DROPPED_ATTRIBUTES_COUNT_KEY, RESOURCE_KEY, SOURCE_NAME,
+    SPAN_ID_KEY, TRACE_ID_KEY,
+};
+use crate::proto::{
+    resource::v1::Resource,
+    trace::v1::{
+        span::{Event as SpanEvent, Link},
+        ResourceSpans, Span, Status,
+    },
+};
+
+const TRACE_STATE_KEY: &str = "trace_state";
+const PARENT_SPAN_ID_KEY: &str = "parent_span_id";
+const NAME_KEY: &str = "name";
+const KIND_KEY: &str = "kind";
+const START_TIME_KEY: &str = "start_time_unix_nano";
+const END_TIME_KEY: &str = "end_time_unix_nano";
+const EVENTS_KEY: &str = "events";
+const DROPPED_EVENTS_COUNT_KEY: &str = "dropped_events_count";
+const LINKS_KEY: &str = "links";
+const DROPPED_LINKS_COUNT_KEY: &str = "dropped_links_count";
+const STATUS_KEY: &str = "status";
+const SCOPE_KEY: &str = "scope";
+
+impl ResourceSpans {
+    pub fn into_event_iter(self) -> impl Iterator<Item = Event> {
+        let resource = self.resource;
+        let now = Utc::now();
+
+        self.scope_spans.into_iter().flat_map(move |scope_spans| {
+            let resource = resource.clone();
+            let scope = scope_spans.scope.map(|scope| {
+                let mut map = ObjectMap::new();
+                if !scope.name.is_empty() {
+                    map.insert("name".into(), scope.name.into());
+                }
+                if !scope.version.is_empty() {
+                    map.insert("version".into(), scope.version.into());
+                }
+                if !scope.attributes.is_empty() {
+                    map.insert(ATTRIBUTES_KEY.into(), kv_list_into_value(scope.attributes));
+                }
+                Value::Object(map)
+            });
+
+            scope_spans.spans.into_iter().map(move |span| {
+                ResourceSpan {
+                    resource: resource.clone(),
+                    scope: scope.clone(),
+                    span,
+                }
+                .into_event(now)
+            })
+        })
+    }
+}
+
+struct ResourceSpan {
+    resource: Option<Resource>,
+    scope: Option<Value>,
+    span: Span,
+}
+
+fn timestamp_nanos(nanos: u64) -> Value {
+    Value::from(Utc.timestamp_nanos(nanos as i64))
+}
+
+fn to_hex(id: &[u8]) -> Value {
+    Value::Bytes(Bytes::from(hex::encode(id)))
+}
+
+// https://github.com/open-telemetry/opentelemetry-specification/blob/v1.15.0/specification/trace/api.md#span
+impl ResourceSpan {
+    fn into_event(self, now: DateTime<Utc>) -> Event {
+        let mut trace = TraceEvent::default();
+        let span = self.span;
+
+        trace.insert(event_path!(TRACE_ID_KEY), to_hex(&span.trace_id));
+        trace.insert(event_path!(SPAN_ID_KEY), to_hex(&span.span_id));
+        trace.insert(event_path!(TRACE_STATE_KEY), span.trace_state);
+        trace.insert(
+            event_path!(PARENT_SPAN_ID_KEY),
+            to_hex(&span.parent_span_id),
+        );
+        trace.insert(event_path!(NAME_KEY), span.name);
+        trace.insert(event_path!(KIND_KEY), span.kind);
+        trace.insert(
+            event_path!(START_TIME_KEY),
+            timestamp_nanos(span.start_time_unix_nano),
+        );
+        trace.insert(
+            event_path!(END_TIME_KEY),
+            timestamp_nanos(span.end_time_unix_nano),
+        );
+        if !span.attributes.is_empty() {
+            trace.insert(
+                event_path!(ATTRIBUTES_KEY),
+                kv_list_into_value(span.attributes),
+            );
+        }
+        trace.insert(
+            event_path!(DROPPED_ATTRIBUTES_COUNT_KEY),
+            span.dropped_attributes_count,
+        );
+        if !span.events.is_empty() {
+            trace.insert(
+                event_path!(EVENTS_KEY),
+                span.events.into_iter().map(Value::from).collect::<Vec<_>>(),
+            );
+        }
+        trace.insert(
+            event_path!(DROPPED_EVENTS_COUNT_KEY),
+            span.dropped_events_count,
+        );
+        if !span.links.is_empty() {
+            trace.insert(
+                event_path!(LINKS_KEY),
+                span.links.into_iter().map(Value::from).collect::<Vec<_>>(),
+            );
+        }
+        trace.insert(
+            event_path!(DROPPED_LINKS_COUNT_KEY),
+            span.dropped_links_count,
+        );
+        if let Some(status) = span.status {
+            trace.insert(event_path!(STATUS_KEY), Value::from(status));
+        }
+        if let Some(resource) = self.resource {
+            if !resource.attributes.is_empty() {
+                trace.insert(
+                    event_path!(RESOURCE_KEY),
+                    kv_list_into_value(resource.attributes),
+                );
+            }
+        }
+        if let Some(scope) = self.scope {
+            trace.insert(event_path!(SCOPE_KEY), scope);
+        }
+        trace.insert(event_path!("ingest_timestamp"), now);
+        trace.insert(event_path!("source_type"), SOURCE_NAME);
+
+        trace.into()
+    }
+}
+
+impl From<SpanEvent> for Value {
+    fn from(event: SpanEvent) -> Self {
+        let mut map = ObjectMap::new();
+        map.insert(NAME_KEY.into(), event.name.into());
+        map.insert(
+            "time_unix_nano".into(),
+            timestamp_nanos(event.time_unix_nano),
+        );
+        map.insert(ATTRIBUTES_KEY.into(), kv_list_into_value(event.attributes));
+        map.insert(
+            DROPPED_ATTRIBUTES_COUNT_KEY.into(),
+            Value::Integer(event.dropped_attributes_count as i64),
+        );
+        Value::Object(map)
+    }
+}
+
+impl From<Link> for Value {
+    fn from(link: Link) -> Self {
+        let mut map = ObjectMap::new();
+        map.insert(TRACE_ID_KEY.into(), to_hex(&link.trace_id));
+        map.insert(SPAN_ID_KEY.into(), to_hex(&link.span_id));
+        map.insert(TRACE_STATE_KEY.into(), link.trace_state.into());
+        map.insert(ATTRIBUTES_KEY.into(), kv_list_into_value(link.attributes));
+        map.insert(
+            DROPPED_ATTRIBUTES_COUNT_KEY.into(),
+            Value::Integer(link.dropped_attributes_count as i64),
+        );
+        Value::Object(map)
+    }
+}
+
+impl From<Status> for Value {
+    fn from(status: Status) -> Self {
+        let mut map = ObjectMap::new();
+        map.insert("message".into(), status.message.into());
+        map.insert("code".into(), status.code.into());
+        Value::Object(map)
+    }
+}
@@ -0,0 +1,444 @@
+//! Conversion of Vector events into OTLP messages, the inverse of the conversions of `convert`.
+
+use chrono::Utc;
+use lookup::{event_path, path};
+use vector_core::{
+    config::{log_schema, LogNamespace},
+    event::LogEvent,
+};
+use vrl::value::Value;
+
+use super::convert::{
+    ATTRIBUTES_KEY, DROPPED_ATTRIBUTES_COUNT_KEY, FLAGS_KEY, OBSERVED_TIMESTAMP_KEY, RESOURCE_KEY,
+    SEVERITY_NUMBER_KEY, SEVERITY_TEXT_KEY, SOURCE_NAME, SPAN_ID_KEY, TRACE_ID_KEY,
+};
+use super::proto::{
+    collector::{
+        logs::v1::ExportLogsServiceRequest, metrics::v1::ExportMetricsServiceRequest,
+        trace::v1::ExportTraceServiceRequest,
+    },
+    common::v1::{
+        any_value::Value as PBValue, AnyValue, ArrayValue, InstrumentationScope, KeyValue,
+        KeyValueList,
+    },
+    logs::v1::{LogRecord, ResourceLogs, ScopeLogs},
+    metrics::v1::{Metric as PBMetric, ResourceMetrics, ScopeMetrics},
+    resource::v1::Resource,
+    trace::v1::{ResourceSpans, ScopeSpans, Span},
+};
+
+mod metrics;
+mod spans;
+
+/// A record converted from an event, with the resource and the instrumentation scope it belongs
+/// to.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ResourceRecord<T> {
+    pub resource: Resource,
+    pub scope: Option<InstrumentationScope>,
+    pub record: T,
+}
+
+impl<T> ResourceRecord<T> {
+    /// Sets an attribute of the resource, replacing the attribute of the same key.
+    pub fn insert_resource_attribute(&mut self, key: String, value: AnyValue) {
+        let attributes = &mut self.resource.attributes;
+        match attributes.iter_mut().find(|kv| kv.key == key) {
+            Some(kv) => kv.value = Some(value),
+            None => attributes.push(KeyValue {
+                key,
+                value: Some(value),
+            }),
+        }
+    }
+}
+
+impl From<Value> for AnyValue {
+    fn from(value: Value) -> Self {
+        let value = match value {
+            Value::Bytes(bytes) => match String::from_utf8(bytes.to_vec()) {
+                Ok(string) => PBValue::StringValue(string),
+                Err(error) => PBValue::BytesValue(error.into_bytes()),
+            },
+            Value::Regex(regex) => PBValue::StringValue(regex.to_string()),
+            Value::Integer(value) => PBValue::IntValue(value),
+            Value::Float(value) => PBValue::DoubleValue(value.into_inner()),
+            Value::Boolean(value) => PBValue::BoolValue(value),
+            Value::Timestamp(timestamp) => {
+                PBValue::StringValue(timestamp.to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true))
+            }
+            Value::Array(values) => PBValue::ArrayValue(ArrayValue {
+                values: values.into_iter().map(Into::into).collect(),
+            }),
+            Value::Object(_) => PBValue::KvlistValue(KeyValueList {
+                values: value_into_kv_list(Some(value)),
+            }),
+            Value::Null => return AnyValue { value: None },
+        };
+        AnyValue { value: Some(value) }
+    }
+}
+
+/// Converts the fields of an object into key/value pairs, other values having none.
+fn value_into_kv_list(value: Option<Value>) -> Vec<KeyValue> {
+    match value {
+        Some(Value::Object(map)) => map
+            .into_iter()
+            .map(|(key, value)| KeyValue {
+                key: key.into(),
+                value: Some(value.into()),
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn value_into_string(value: Option<Value>) -> String {
+    match value {
+        Some(Value::Bytes(bytes)) => String::from_utf8_lossy(&bytes).into_owned(),
+        Some(Value::Null) | None => String::new(),
+        Some(value) => value.to_string_lossy().into_owned(),
+    }
+}
+
+fn value_into_integer(value: Option<Value>) -> i64 {
+    match value {
+        Some(Value::Integer(value)) => value,
+        Some(Value::Float(value)) => value.into_inner() as i64,
+        _ => 0,
+    }
+}
+
+/// Converts timestamps, or integer nanoseconds, into nanoseconds since the epoch, `0` standing
+/// for missing timestamps.
+fn value_into_nanos(value: Option<Value>) -> u64 {
+    match value {
+        Some(Value::Timestamp(timestamp)) => {
+            timestamp.timestamp_nanos_opt().unwrap_or_default().max(0) as u64
+        }
+        Some(Value::Integer(nanos)) => nanos.max(0) as u64,
+        _ => 0,
+    }
+}
+
+/// Decodes the hexadecimal trace and span IDs, invalid IDs being left empty.
+fn value_into_id(value: Option<Value>) -> Vec<u8> {
+    match value {
+        Some(Value::Bytes(bytes)) => hex::decode(bytes).unwrap_or_default(),
+        _ => Vec::new(),
+    }
+}
+
+fn resource_from_value(value: Option<Value>) -> Resource {
+    Resource {
+        attributes: value_into_kv_list(value),
+        dropped_attributes_count: 0,
+    }
+}
+
+/// Removes a field set by the `opentelemetry` source, from the metadata of the source with the
+/// `Vector` namespace.
+fn take_field(log: &mut LogEvent, namespace: LogNamespace, key: &str) -> Option<Value> {
+    match namespace {
+        LogNamespace::Vector => log
+            .metadata_mut()
+            .value_mut()
+            .remove(path!(SOURCE_NAME, key), true),
+        LogNamespace::Legacy => log.remove(event_path!(key)),
+    }
+}
+
+// https://github.com/open-telemetry/opentelemetry-specification/blob/v1.15.0/specification/logs/data-model.md
+impl From<LogEvent> for ResourceRecord<LogRecord> {
+    /// Converts the fields set by the `opentelemetry` source back into the fields of the record.
+    ///
+    /// With the `Legacy` namespace, the message is the body of the record, and the other fields of
+    /// the event are added to its attributes. With the `Vector` namespace, the whole event is the
+    /// body of the record, and the fields of the record are read from the metadata of the source.
+    fn from(mut log: LogEvent) -> Self {
+        let namespace = log.namespace();
+        let mut take = |key: &str| take_field(&mut log, namespace, key);
+
+        let resource = resource_from_value(take(RESOURCE_KEY));
+        let mut attributes = value_into_kv_list(take(ATTRIBUTES_KEY));
+        let trace_id = value_into_id(take(TRACE_ID_KEY));
+        let span_id = value_into_id(take(SPAN_ID_KEY));
+        let severity_text = value_into_string(take(SEVERITY_TEXT_KEY));
+        let severity_number = value_into_integer(take(SEVERITY_NUMBER_KEY)) as i32;
+        let flags = value_into_integer(take(FLAGS_KEY)) as u32;
+        let dropped_attributes_count =
+            value_into_integer(take(DROPPED_ATTRIBUTES_COUNT_KEY)) as u32;
+        let observed_time_unix_nano = value_into_nanos(take(OBSERVED_TIMESTAMP_KEY));
+
+        let (time_unix_nano, body) = match namespace {
+            LogNamespace::Vector => {
+                let time = value_into_nanos(take_field(&mut log, namespace, "timestamp"));
+                let (value, _metadata) = log.into_parts();
+                (time, (!value.is_null()).then(|| value.into()))
+            }
+            LogNamespace::Legacy => {
+                let time = log_schema()
+                    .timestamp_key_target_path()
+                    .and_then(|path| log.remove(path));
+                let body = log_schema()
+                    .message_key_target_path()
+                    .and_then(|path| log.remove(path));
+                if let Some(path) = log_schema().source_type_key_target_path() {
+                    log.remove(path);
+                }
+
+                let (value, _metadata) = log.into_parts();
+                for kv in value_into_kv_list(Some(value)) {
+                    if !attributes.iter().any(|attribute| attribute.key == kv.key) {
+                        attributes.push(kv);
+                    }
+                }
+                (value_into_nanos(time), body.map(Into::into))
+            }
+        };
+
+        ResourceRecord {
+            resource,
+            scope: None,
+            record: LogRecord {
+                time_unix_nano,
+                // The time the event was observed at is unknown, and the closest one is the time
+                // of the event.
+                observed_time_unix_nano: if observed_time_unix_nano > 0 {
+                    observed_time_unix_nano
+                } else {
+                    time_unix_nano
+                },
+                severity_number,
+                severity_text,
+                body,
+                attributes,
+                dropped_attributes_count,
+                flags,
+                trace_id,
+                span_id,
+            },
+        }
+    }
+}
+
+fn now_nanos() -> u64 {
+    Utc::now().timestamp_nanos_opt().unwrap_or_default().max(0) as u64
+}
+
+/// The records of a resource, grouped by instrumentation scope.
+type ScopeGroups<T> = Vec<(Option<InstrumentationScope>, Vec<T>)>;
+
+/// Groups the records by resource, then by instrumentation scope, in the order they come in.
+fn group<T>(
+    records: impl IntoIterator<Item = ResourceRecord<T>>,
+) -> Vec<(Resource, ScopeGroups<T>)> {
+    let mut groups: Vec<(Resource, ScopeGroups<T>)> = Vec::new();
+    for record in records {
+        let index = match groups
+            .iter()
+            .position(|(resource, _)| *resource == record.resource)
+        {
+            Some(index) => index,
+            None => {
+                groups.push((record.resource, Vec::new()));
+                groups.len() - 1
+            }
+        };
+
+        let scopes = &mut groups[index].1;
+        match scopes.iter_mut().find(|(scope, _)| *scope == record.scope) {
+            Some((_, records)) => records.push(record.record),
+            None => scopes.push((record.scope, vec![record.record])),
+        }
+    }
+    groups
+}
+
+fn non_empty(resource: Resource) -> Option<Resource> {
+    (!resource.attributes.is_empty()).then_some(resource)
+}
+
+/// Builds the request exporting the log records.
+pub fn logs_request(
+    records: impl IntoIterator<Item = ResourceRecord<LogRecord>>,
+) -> ExportLogsServiceRequest {
+    ExportLogsServiceRequest {
+        resource_logs: group(records)
+            .into_iter()
+            .map(|(resource, scopes)| ResourceLogs {
+                resource: non_empty(resource),
+                scope_logs: scopes
+                    .into_iter()
+                    .map(|(scope, log_records)| ScopeLogs {
+                        scope,
+                        log_records,
+                        schema_url: String::new(),
+                    })
+                    .collect(),
+                schema_url: String::new(),
+            })
+            .collect(),
+    }
+}
+
+/// Builds the request exporting the metrics.
+pub fn metrics_request(
+    records: impl IntoIterator<Item = ResourceRecord<PBMetric>>,
+) -> ExportMetricsServiceRequest {
+    ExportMetricsServiceRequest {
+        resource_metrics: group(records)
+            .into_iter()
+            .map(|(resource, scopes)| ResourceMetrics {
+                resource: non_empty(resource),
+                scope_metrics: scopes
+                    .into_iter()
+                    .map(|(scope, metrics)| ScopeMetrics {
+                        scope,
+                        metrics,
+                        schema_url: String::new(),
+                    })
+                    .collect(),
+                schema_url: String::new(),
+            })
+            .collect(),
+    }
+}
+
+/// Builds the request exporting the spans.
+pub fn traces_request(
+    records: impl IntoIterator<Item = ResourceRecord<Span>>,
+) -> ExportTraceServiceRequest {
+    ExportTraceServiceRequest {
+        resource_spans: group(records)
+            .into_iter()
+            .map(|(resource, scopes)| ResourceSpans {
+                resource: non_empty(resource),
+                scope_spans: scopes
+                    .into_iter()
+                    .map(|(scope, spans)| ScopeSpans {
+                        scope,
+                        spans,
+                        schema_url: String::new(),
+                    })
+                    .collect(),
+                schema_url: String::new(),
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_core::event::Event;
+
+    use super::*;
+    use crate::proto::logs::v1::SeverityNumber;
+
+    fn kv(key: &str, value: PBValue) -> KeyValue {
+        KeyValue {
+            key: key.into(),
+            value: Some(AnyValue { value: Some(value) }),
+        }
+    }
+
+    fn resource_logs(log_record: LogRecord) -> ResourceLogs {
+        ResourceLogs {
+            resource: Some(Resource {
+                attributes: vec![kv("service.name", PBValue::StringValue("api".into()))],
+                dropped_attributes_count: 0,
+            }),
+            scope_logs: vec![ScopeLogs {
+                scope: None,
+                log_records: vec![log_record],
+                schema_url: String::new(),
+            }],
+            schema_url: String::new(),
+        }
+    }
+
+    fn log_record() -> LogRecord {
+        LogRecord {
+            time_unix_nano: 1_700_000_000_000_000_000,
+            observed_time_unix_nano: 1_700_000_001_000_000_000,
+            severity_number: SeverityNumber::Warn as i32,
+            severity_text: "WARN".into(),
+            body: Some(AnyValue {
+                value: Some(PBValue::StringValue("hello".into())),
+            }),
+            attributes: vec![kv("http.status_code", PBValue::IntValue(503))],
+            dropped_attributes_count: 0,
+            flags: 1,
+            trace_id: vec![1; 16],
+            span_id: vec![2; 8],
+        }
+    }
+
+    fn round_trip(log_namespace: LogNamespace) -> ExportLogsServiceRequest {
+        let records = resource_logs(log_record())
+            .into_event_iter(log_namespace)
+            .map(|event| match event {
+                Event::Log(log) => ResourceRecord::from(log),
+                _ => unreachable!("logs are converted into log events"),
+            });
+        logs_request(records)
+    }
+
+    #[test]
+    fn converts_logs_back_legacy_namespace() {
+        assert_eq!(
+            round_trip(LogNamespace::Legacy).resource_logs,
+            vec![resource_logs(log_record())]
+        );
+    }
+
+    #[test]
+    fn converts_logs_back_vector_namespace() {
+        assert_eq!(
+            round_trip(LogNamespace::Vector).resource_logs,
+            vec![resource_logs(log_record())]
+        );
+    }
+
+    #[test]
+    fn adds_fields_to_attributes() {
+        let mut log = LogEvent::from_str_legacy("hello");
+        log.insert("host", "web-1");
+        let record = ResourceRecord::<LogRecord>::from(log);
+
+        assert_eq!(
+            record.record.body,
+            Some(AnyValue {
+                value: Some(PBValue::StringValue("hello".into()))
+            })
+        );
+        assert_eq!(
+            record.record.attributes,
+            vec![kv("host", PBValue::StringValue("web-1".into()))]
+        );
+        assert!(record.resource.attributes.is_empty());
+    }
+
+    #[test]
+    fn groups_records_by_resource() {
+        let record = |service: &str| {
+            let mut record = ResourceRecord {
+                resource: Resource::default(),
+                scope: None,
+                record: log_record(),
+            };
+            record.insert_resource_attribute(
+                "service.name".into(),
+                AnyValue {
+                    value: Some(PBValue::StringValue(service.into())),
+                },
+            );
+            record
+        };
+
+        let request = logs_request([record("api"), record("web"), record("api")]);
+        assert_eq!(request.resource_logs.len(), 2);
+        assert_eq!(request.resource_logs[0].scope_logs[0].log_records.len(), 2);
+        assert_eq!(request.resource_logs[1].scope_logs[0].log_records.len(), 1);
+    }
+}
@@ -0,0 +1,351 @@
+use vector_core::event::{
+    metric::{Bucket, Quantile},
+    Metric, MetricKind, MetricValue,
+};
+
+use super::{now_nanos, ResourceRecord};
+use crate::{
+    convert::{RESOURCE_TAG_PREFIX, SCOPE_TAG_PREFIX},
+    proto::{
+        common::v1::{any_value::Value as PBValue, AnyValue, InstrumentationScope, KeyValue},
+        metrics::v1::{
+            metric::Data, number_data_point, summary_data_point::ValueAtQuantile,
+            AggregationTemporality, Gauge, Histogram, HistogramDataPoint, Metric as PBMetric,
+            NumberDataPoint, Sum, Summary, SummaryDataPoint,
+        },
+        resource::v1::Resource,
+    },
+};
+
+/// The upper bounds of the buckets of the histograms that distributions are converted into.
+const DISTRIBUTION_BUCKETS: [f64; 11] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+fn string_value(value: &str) -> Option<AnyValue> {
+    Some(AnyValue {
+        value: Some(PBValue::StringValue(value.to_string())),
+    })
+}
+
+/// Incremental metrics map to delta temporality, and absolute ones to cumulative temporality.
+fn temporality(kind: MetricKind) -> i32 {
+    match kind {
+        MetricKind::Incremental => AggregationTemporality::Delta as i32,
+        MetricKind::Absolute => AggregationTemporality::Cumulative as i32,
+    }
+}
+
+fn number_point(value: f64, attributes: Vec<KeyValue>, time_unix_nano: u64) -> NumberDataPoint {
+    NumberDataPoint {
+        attributes,
+        time_unix_nano,
+        value: Some(number_data_point::Value::AsDouble(value)),
+        ..Default::default()
+    }
+}
+
+fn histogram_point(
+    buckets: &[Bucket],
+    count: u64,
+    sum: f64,
+    attributes: Vec<KeyValue>,
+    time_unix_nano: u64,
+) -> HistogramDataPoint {
+    // The bucket of the observations above the highest bound is implicit in OTLP, and counts the
+    // observations which aren't counted by the other buckets.
+    let (explicit_bounds, mut bucket_counts): (Vec<f64>, Vec<u64>) = buckets
+        .iter()
+        .filter(|bucket| bucket.upper_limit.is_finite())
+        .map(|bucket| (bucket.upper_limit, bucket.count))
+        .unzip();
+    let counted = bucket_counts.iter().sum::<u64>();
+    bucket_counts.push(count.saturating_sub(counted));
+
+    HistogramDataPoint {
+        attributes,
+        time_unix_nano,
+        count,
+        sum: Some(sum),
+        bucket_counts,
+        explicit_bounds,
+        ..Default::default()
+    }
+}
+
+fn summary_point(
+    quantiles: &[Quantile],
+    count: u64,
+    sum: f64,
+    attributes: Vec<KeyValue>,
+    time_unix_nano: u64,
+) -> SummaryDataPoint {
+    SummaryDataPoint {
+        attributes,
+        time_unix_nano,
+        count,
+        sum,
+        quantile_values: quantiles
+            .iter()
+            .map(|quantile| ValueAtQuantile {
+                quantile: quantile.quantile,
+                value: quantile.value,
+            })
+            .collect(),
+        ..Default::default()
+    }
+}
+
+fn value_into_data(
+    value: &MetricValue,
+    kind: MetricKind,
+    attributes: Vec<KeyValue>,
+    time_unix_nano: u64,
+) -> Option<Data> {
+    let data = match value {
+        MetricValue::Counter { value } => Data::Sum(Sum {
+            data_points: vec![number_point(*value, attributes, time_unix_nano)],
+            aggregation_temporality: temporality(kind),
+            is_monotonic: true,
+        }),
+        // Incremental gauges are changes of the values, which are non-monotonic sums.
+        MetricValue::Gauge { value } => match kind {
+            MetricKind::Absolute => Data::Gauge(Gauge {
+                data_points: vec![number_point(*value, attributes, time_unix_nano)],
+            }),
+            MetricKind::Incremental => Data::Sum(Sum {
+                data_points: vec![number_point(*value, attributes, time_unix_nano)],
+                aggregation_temporality: temporality(kind),
+                is_monotonic: false,
+            }),
+        },
+        // Sets are exported as the number of their values.
+        MetricValue::Set { values } => Data::Gauge(Gauge {
+            data_points: vec![number_point(
+                values.len() as f64,
+                attributes,
+                time_unix_nano,
+            )],
+        }),
+        MetricValue::Distribution { .. } => {
+            let histogram = value.distribution_to_agg_histogram(&DISTRIBUTION_BUCKETS)?;
+            return value_into_data(&histogram, kind, attributes, time_unix_nano);
+        }
+        MetricValue::AggregatedHistogram {
+            buckets,
+            count,
+            sum,
+        } => Data::Histogram(Histogram {
+            data_points: vec![histogram_point(
+                buckets,
+                *count,
+                *sum,
+                attributes,
+                time_unix_nano,
+            )],
+            aggregation_temporality: temporality(kind),
+        }),
+        MetricValue::AggregatedSummary {
+            quantiles,
+            count,
+            sum,
+        } => Data::Summary(Summary {
+            data_points: vec![summary_point(
+                quantiles,
+                *count,
+                *sum,
+                attributes,
+                time_unix_nano,
+            )],
+        }),
+        MetricValue::Sketch { .. } => return None,
+    };
+    Some(data)
+}
+
+// https://github.com/open-telemetry/opentelemetry-specification/blob/v1.15.0/specification/metrics/data-model.md
+impl ResourceRecord<PBMetric> {
+    /// Converts a metric into an OTLP metric with a single data point.
+    ///
+    /// The tags prefixed by `resource.` are the attributes of the resource, and the ones prefixed
+    /// by `scope.` describe the instrumentation scope, as set by the `opentelemetry` source. The
+    /// other tags are the attributes of the data point.
+    ///
+    /// Returns `None` for sketches, which have no OTLP equivalent.
+    pub fn from_metric(metric: &Metric) -> Option<Self> {
+        let mut resource = Resource::default();
+        let mut scope = InstrumentationScope::default();
+        let mut attributes = Vec::new();
+        for (key, value) in metric
+            .tags()
+            .into_iter()
+            .flat_map(|tags| tags.iter_single())
+        {
+            if let Some(key) = key.strip_prefix(RESOURCE_TAG_PREFIX) {
+                resource.attributes.push(KeyValue {
+                    key: key.to_string(),
+                    value: string_value(value),
+                });
+            } else if let Some(key) = key.strip_prefix(SCOPE_TAG_PREFIX) {
+                match key {
+                    "name" => scope.name = value.to_string(),
+                    "version" => scope.version = value.to_string(),
+                    _ => scope.attributes.push(KeyValue {
+                        key: key.to_string(),
+                        value: string_value(value),
+                    }),
+                }
+            } else {
+                attributes.push(KeyValue {
+                    key: key.to_string(),
+                    value: string_value(value),
+                });
+            }
+        }
+
+        let time_unix_nano = metric
+            .timestamp()
+            .and_then(|timestamp| timestamp.timestamp_nanos_opt())
+            .map_or_else(now_nanos, |nanos| nanos.max(0) as u64);
+        let data = value_into_data(metric.value(), metric.kind(), attributes, time_unix_nano)?;
+
+        let name = match metric.namespace() {
+            Some(namespace) => format!("{}.{}", namespace, metric.name()),
+            None => metric.name().to_string(),
+        };
+
+        Some(ResourceRecord {
+            resource,
+            scope: (scope != InstrumentationScope::default()).then_some(scope),
+            record: PBMetric {
+                name,
+                data: Some(data),
+                ..Default::default()
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_core::{
+        event::{metric::Sample, Event},
+        metric_tags,
+    };
+
+    use super::*;
+    use crate::proto::metrics::v1::{ResourceMetrics, ScopeMetrics};
+
+    fn sum_metrics() -> ResourceMetrics {
+        ResourceMetrics {
+            resource: Some(Resource {
+                attributes: vec![KeyValue {
+                    key: "service.name".into(),
+                    value: string_value("api"),
+                }],
+                dropped_attributes_count: 0,
+            }),
+            scope_metrics: vec![ScopeMetrics {
+                scope: Some(InstrumentationScope {
+                    name: "http".into(),
+                    version: "1.0".into(),
+                    ..Default::default()
+                }),
+                metrics: vec![PBMetric {
+                    name: "requests".into(),
+                    data: Some(Data::Sum(Sum {
+                        data_points: vec![number_point(
+                            12.0,
+                            vec![KeyValue {
+                                key: "method".into(),
+                                value: string_value("GET"),
+                            }],
+                            1_700_000_000_000_000_000,
+                        )],
+                        aggregation_temporality: AggregationTemporality::Delta as i32,
+                        is_monotonic: true,
+                    })),
+                    ..Default::default()
+                }],
+                schema_url: String::new(),
+            }],
+            schema_url: String::new(),
+        }
+    }
+
+    #[test]
+    fn converts_metrics_back() {
+        let records = sum_metrics().into_event_iter().map(|event| match event {
+            Event::Metric(metric) => ResourceRecord::from_metric(&metric).unwrap(),
+            _ => unreachable!("metrics are converted into metric events"),
+        });
+
+        assert_eq!(
+            super::super::metrics_request(records).resource_metrics,
+            vec![sum_metrics()]
+        );
+    }
+
+    #[test]
+    fn converts_histograms() {
+        let metric = Metric::new(
+            "latency",
+            MetricKind::Absolute,
+            MetricValue::AggregatedHistogram {
+                buckets: vec![
+                    Bucket {
+                        upper_limit: 0.1,
+                        count: 3,
+                    },
+                    Bucket {
+                        upper_limit: 1.0,
+                        count: 2,
+                    },
+                ],
+                count: 6,
+                sum: 4.5,
+            },
+        )
+        .with_namespace(Some("app"))
+        .with_tags(Some(metric_tags!("resource.host.name" => "web-1")));
+
+        let record = ResourceRecord::from_metric(&metric).unwrap();
+        assert_eq!(record.record.name, "app.latency");
+        assert_eq!(record.resource.attributes[0].key, "host.name");
+        let Some(Data::Histogram(histogram)) = record.record.data else {
+            panic!("expected a histogram");
+        };
+        let point = &histogram.data_points[0];
+        assert_eq!(point.explicit_bounds, vec![0.1, 1.0]);
+        assert_eq!(point.bucket_counts, vec![3, 2, 1]);
+        assert_eq!(
+            histogram.aggregation_temporality,
+            AggregationTemporality::Cumulative as i32
+        );
+    }
+
+    #[test]
+    fn converts_distributions_into_histograms() {
+        let metric = Metric::new(
+            "latency",
+            MetricKind::Incremental,
+            MetricValue::Distribution {
+                samples: vec![Sample {
+                    value: 0.2,
+                    rate: 2,
+                }],
+                statistic: vector_core::event::metric::StatisticKind::Histogram,
+            },
+        );
+
+        let record = ResourceRecord::from_metric(&metric).unwrap();
+        let Some(Data::Histogram(histogram)) = record.record.data else {
+            panic!("expected a histogram");
+        };
+        assert_eq!(histogram.data_points[0].count, 2);
+        assert_eq!(
+            histogram.aggregation_temporality,
+            AggregationTemporality::Delta as i32
+        );
+    }
+}
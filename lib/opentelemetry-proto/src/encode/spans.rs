@@ -0,0 +1,206 @@
+use lookup::event_path;
+use vector_core::event::TraceEvent;
+use vrl::value::{ObjectMap, Value};
+
+use super::{
+    resource_from_value, value_into_id, value_into_integer, value_into_kv_list, value_into_nanos,
+    value_into_string, ResourceRecord,
+};
+use crate::{
+    convert::{
+        ATTRIBUTES_KEY, DROPPED_ATTRIBUTES_COUNT_KEY, RESOURCE_KEY, SPAN_ID_KEY, TRACE_ID_KEY,
+    },
+    proto::{
+        common::v1::InstrumentationScope,
+        trace::v1::{
+            span::{Event as SpanEvent, Link},
+            Span, Status,
+        },
+    },
+};
+
+const TRACE_STATE_KEY: &str = "trace_state";
+const PARENT_SPAN_ID_KEY: &str = "parent_span_id";
+const NAME_KEY: &str = "name";
+const KIND_KEY: &str = "kind";
+const START_TIME_KEY: &str = "start_time_unix_nano";
+const END_TIME_KEY: &str = "end_time_unix_nano";
+const EVENTS_KEY: &str = "events";
+const DROPPED_EVENTS_COUNT_KEY: &str = "dropped_events_count";
+const LINKS_KEY: &str = "links";
+const DROPPED_LINKS_COUNT_KEY: &str = "dropped_links_count";
+const STATUS_KEY: &str = "status";
+const SCOPE_KEY: &str = "scope";
+
+fn objects(value: Option<Value>) -> Vec<ObjectMap> {
+    match value {
+        Some(Value::Array(values)) => values
+            .into_iter()
+            .filter_map(|value| match value {
+                Value::Object(map) => Some(map),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn scope_from_value(value: Option<Value>) -> Option<InstrumentationScope> {
+    let Some(Value::Object(mut map)) = value else {
+        return None;
+    };
+    Some(InstrumentationScope {
+        name: value_into_string(map.remove("name")),
+        version: value_into_string(map.remove("version")),
+        attributes: value_into_kv_list(map.remove(ATTRIBUTES_KEY)),
+        dropped_attributes_count: 0,
+    })
+}
+
+fn span_event(mut map: ObjectMap) -> SpanEvent {
+    SpanEvent {
+        time_unix_nano: value_into_nanos(map.remove("time_unix_nano")),
+        name: value_into_string(map.remove(NAME_KEY)),
+        attributes: value_into_kv_list(map.remove(ATTRIBUTES_KEY)),
+        dropped_attributes_count: value_into_integer(map.remove(DROPPED_ATTRIBUTES_COUNT_KEY))
+            as u32,
+    }
+}
+
+fn link(mut map: ObjectMap) -> Link {
+    Link {
+        trace_id: value_into_id(map.remove(TRACE_ID_KEY)),
+        span_id: value_into_id(map.remove(SPAN_ID_KEY)),
+        trace_state: value_into_string(map.remove(TRACE_STATE_KEY)),
+        attributes: value_into_kv_list(map.remove(ATTRIBUTES_KEY)),
+        dropped_attributes_count: value_into_integer(map.remove(DROPPED_ATTRIBUTES_COUNT_KEY))
+            as u32,
+    }
+}
+
+fn status(value: Option<Value>) -> Option<Status> {
+    let Some(Value::Object(mut map)) = value else {
+        return None;
+    };
+    Some(Status {
+        message: value_into_string(map.remove("message")),
+        code: value_into_integer(map.remove("code")) as i32,
+    })
+}
+
+// https://github.com/open-telemetry/opentelemetry-specification/blob/v1.15.0/specification/trace/api.md#span
+impl From<TraceEvent> for ResourceRecord<Span> {
+    /// Converts the fields set by the `opentelemetry` source back into the fields of the span.
+    fn from(mut trace: TraceEvent) -> Self {
+        let mut take = |key: &str| trace.remove(event_path!(key));
+
+        ResourceRecord {
+            resource: resource_from_value(take(RESOURCE_KEY)),
+            scope: scope_from_value(take(SCOPE_KEY)),
+            record: Span {
+                trace_id: value_into_id(take(TRACE_ID_KEY)),
+                span_id: value_into_id(take(SPAN_ID_KEY)),
+                trace_state: value_into_string(take(TRACE_STATE_KEY)),
+                parent_span_id: value_into_id(take(PARENT_SPAN_ID_KEY)),
+                name: value_into_string(take(NAME_KEY)),
+                kind: value_into_integer(take(KIND_KEY)) as i32,
+                start_time_unix_nano: value_into_nanos(take(START_TIME_KEY)),
+                end_time_unix_nano: value_into_nanos(take(END_TIME_KEY)),
+                attributes: value_into_kv_list(take(ATTRIBUTES_KEY)),
+                dropped_attributes_count: value_into_integer(take(DROPPED_ATTRIBUTES_COUNT_KEY))
+                    as u32,
+                events: objects(take(EVENTS_KEY))
+                    .into_iter()
+                    .map(span_event)
+                    .collect(),
+                dropped_events_count: value_into_integer(take(DROPPED_EVENTS_COUNT_KEY)) as u32,
+                links: objects(take(LINKS_KEY)).into_iter().map(link).collect(),
+                dropped_links_count: value_into_integer(take(DROPPED_LINKS_COUNT_KEY)) as u32,
+                status: status(take(STATUS_KEY)),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_core::event::Event;
+
+    use super::*;
+    use crate::proto::{
+        common::v1::{any_value::Value as PBValue, AnyValue, KeyValue},
+        resource::v1::Resource,
+        trace::v1::{ResourceSpans, ScopeSpans},
+    };
+
+    fn kv(key: &str, value: &str) -> KeyValue {
+        KeyValue {
+            key: key.into(),
+            value: Some(AnyValue {
+                value: Some(PBValue::StringValue(value.into())),
+            }),
+        }
+    }
+
+    fn resource_spans() -> ResourceSpans {
+        ResourceSpans {
+            resource: Some(Resource {
+                attributes: vec![kv("service.name", "api")],
+                dropped_attributes_count: 0,
+            }),
+            scope_spans: vec![ScopeSpans {
+                scope: Some(InstrumentationScope {
+                    name: "http".into(),
+                    version: "1.0".into(),
+                    ..Default::default()
+                }),
+                spans: vec![Span {
+                    trace_id: vec![1; 16],
+                    span_id: vec![2; 8],
+                    trace_state: "vendor=value".into(),
+                    parent_span_id: vec![3; 8],
+                    name: "GET /".into(),
+                    kind: 2,
+                    start_time_unix_nano: 1_700_000_000_000_000_000,
+                    end_time_unix_nano: 1_700_000_000_500_000_000,
+                    attributes: vec![kv("http.method", "GET")],
+                    dropped_attributes_count: 0,
+                    events: vec![SpanEvent {
+                        time_unix_nano: 1_700_000_000_100_000_000,
+                        name: "retry".into(),
+                        attributes: vec![kv("attempt", "2")],
+                        dropped_attributes_count: 0,
+                    }],
+                    dropped_events_count: 0,
+                    links: vec![Link {
+                        trace_id: vec![4; 16],
+                        span_id: vec![5; 8],
+                        trace_state: String::new(),
+                        attributes: Vec::new(),
+                        dropped_attributes_count: 0,
+                    }],
+                    dropped_links_count: 0,
+                    status: Some(Status {
+                        message: "unavailable".into(),
+                        code: 2,
+                    }),
+                }],
+                schema_url: String::new(),
+            }],
+            schema_url: String::new(),
+        }
+    }
+
+    #[test]
+    fn converts_spans_back() {
+        let records = resource_spans().into_event_iter().map(|event| match event {
+            Event::Trace(trace) => ResourceRecord::from(trace),
+            _ => unreachable!("spans are converted into trace events"),
+        });
+
+        assert_eq!(
+            super::super::traces_request(records).resource_spans,
+            vec![resource_spans()]
+        );
+    }
+}
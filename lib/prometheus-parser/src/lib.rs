@@ -76,11 +76,20 @@ pub struct SummaryQuantile {
     pub value: f64,
 }
 
+/// An exemplar attached to a remote write time series, linking an observation to a trace.
+#[derive(Debug, Default, PartialEq)]
+pub struct Exemplar {
+    pub labels: BTreeMap<String, String>,
+    pub value: f64,
+    pub timestamp: i64,
+}
+
 #[derive(Debug, Default, PartialEq)]
 pub struct SummaryMetric {
     pub quantiles: Vec<SummaryQuantile>,
     pub sum: f64,
     pub count: u64,
+    pub exemplars: Vec<Exemplar>,
 }
 
 #[derive(Debug, Default, PartialEq, PartialOrd)]
@@ -94,11 +103,16 @@ pub struct HistogramMetric {
     pub buckets: Vec<HistogramBucket>,
     pub sum: f64,
     pub count: u64,
+    pub exemplars: Vec<Exemplar>,
+    /// The native histogram of a remote write request the buckets were converted from, which is
+    /// kept as the conversion loses its resolution.
+    pub native: Option<proto::Histogram>,
 }
 
 #[derive(Debug, Default, PartialEq)]
 pub struct SimpleMetric {
     pub value: f64,
+    pub exemplars: Vec<Exemplar>,
 }
 
 type MetricMap<T> = IndexMap<GroupKey, T>;
@@ -123,9 +137,9 @@ impl GroupKind {
         }
     }
 
-    fn new_untyped(key: GroupKey, value: f64) -> Self {
+    fn new_untyped(key: GroupKey, value: f64, exemplars: Vec<Exemplar>) -> Self {
         let mut metrics = IndexMap::default();
-        metrics.insert(key, SimpleMetric { value });
+        metrics.insert(key, SimpleMetric { value, exemplars });
         Self::Untyped(metrics)
     }
 
@@ -141,11 +155,12 @@ impl GroupKind {
 
     /// Err(_) if there are irrecoverable error.
     /// Ok(Some(metric)) if this metric belongs to another group.
-    /// Ok(None) pushed successfully.
+    /// Ok(None) pushed successfully, in which case the exemplars are moved to the metric.
     fn try_push(
         &mut self,
         prefix_len: usize,
         metric: Metric,
+        exemplars: &mut Vec<Exemplar>,
     ) -> Result<Option<Metric>, ParserError> {
         let suffix = &metric.name[prefix_len..];
         let mut key = GroupKey {
@@ -166,7 +181,13 @@ impl GroupKind {
                         value,
                     }));
                 }
-                metrics.insert(key, SimpleMetric { value });
+                metrics.insert(
+                    key,
+                    SimpleMetric {
+                        value,
+                        exemplars: std::mem::take(exemplars),
+                    },
+                );
             }
             Self::Histogram(ref mut metrics) => match suffix {
                 "_bucket" => {
@@ -175,17 +196,21 @@ impl GroupKind {
                         .map_err(Into::into)
                         .context(ParseLabelValueSnafu)?;
                     let count = try_f64_to_u64(metric.value)?;
-                    matching_group(metrics, key)
-                        .buckets
-                        .push(HistogramBucket { bucket, count });
+                    let group = matching_group(metrics, key);
+                    group.buckets.push(HistogramBucket { bucket, count });
+                    group.exemplars.append(exemplars);
                 }
                 "_sum" => {
                     let sum = metric.value;
-                    matching_group(metrics, key).sum = sum;
+                    let group = matching_group(metrics, key);
+                    group.sum = sum;
+                    group.exemplars.append(exemplars);
                 }
                 "_count" => {
                     let count = try_f64_to_u64(metric.value)?;
-                    matching_group(metrics, key).count = count;
+                    let group = matching_group(metrics, key);
+                    group.count = count;
+                    group.exemplars.append(exemplars);
                 }
                 _ => {
                     return Ok(Some(Metric {
@@ -206,17 +231,21 @@ impl GroupKind {
                     let (_, quantile) = line::Metric::parse_value(&quantile)
                         .map_err(Into::into)
                         .context(ParseLabelValueSnafu)?;
-                    matching_group(metrics, key)
-                        .quantiles
-                        .push(SummaryQuantile { quantile, value });
+                    let group = matching_group(metrics, key);
+                    group.quantiles.push(SummaryQuantile { quantile, value });
+                    group.exemplars.append(exemplars);
                 }
                 "_sum" => {
                     let sum = metric.value;
-                    matching_group(metrics, key).sum = sum;
+                    let group = matching_group(metrics, key);
+                    group.sum = sum;
+                    group.exemplars.append(exemplars);
                 }
                 "_count" => {
                     let count = try_f64_to_u64(metric.value)?;
-                    matching_group(metrics, key).count = count;
+                    let group = matching_group(metrics, key);
+                    group.count = count;
+                    group.exemplars.append(exemplars);
                 }
                 _ => {
                     return Ok(Some(Metric {
@@ -266,7 +295,7 @@ impl MetricGroup {
         let key = GroupKey { timestamp, labels };
         MetricGroup {
             name,
-            metrics: GroupKind::new_untyped(key, value),
+            metrics: GroupKind::new_untyped(key, value, Vec::new()),
         }
     }
 
@@ -277,7 +306,8 @@ impl MetricGroup {
         if !metric.name.starts_with(&self.name) {
             return Ok(Some(metric));
         }
-        self.metrics.try_push(self.name.len(), metric)
+        self.metrics
+            .try_push(self.name.len(), metric, &mut Vec::new())
     }
 }
 
@@ -355,6 +385,7 @@ impl MetricGroupSet {
         name: &str,
         labels: &BTreeMap<String, String>,
         sample: proto::Sample,
+        exemplars: &mut Vec<Exemplar>,
     ) -> Result<(), ParserError> {
         let (_, basename, group) = self.get_group(name);
         if let Some(metric) = group.try_push(
@@ -365,17 +396,47 @@ impl MetricGroupSet {
                 value: sample.value,
                 timestamp: Some(sample.timestamp),
             },
+            exemplars,
         )? {
             let key = GroupKey {
                 timestamp: metric.timestamp,
                 labels: metric.labels,
             };
-            let group = GroupKind::new_untyped(key, metric.value);
+            let group = GroupKind::new_untyped(key, metric.value, std::mem::take(exemplars));
             self.0.insert(metric.name, group);
         }
         Ok(())
     }
 
+    fn insert_histogram(
+        &mut self,
+        name: &str,
+        labels: &BTreeMap<String, String>,
+        histogram: proto::Histogram,
+        exemplars: &mut Vec<Exemplar>,
+    ) -> Result<(), ParserError> {
+        let group = self
+            .0
+            .entry(name.into())
+            .or_insert_with(|| GroupKind::new(MetricKind::Histogram));
+        if matches!(group, GroupKind::Untyped(metrics) if metrics.is_empty()) {
+            *group = GroupKind::new(MetricKind::Histogram);
+        }
+        let GroupKind::Histogram(metrics) = group else {
+            return Err(ParserError::MultipleMetricKinds { name: name.into() });
+        };
+
+        let key = GroupKey {
+            timestamp: Some(histogram.timestamp),
+            labels: labels.clone(),
+        };
+        let mut metric = native_histogram(&histogram);
+        metric.exemplars.append(exemplars);
+        metric.native = Some(histogram);
+        metrics.insert(key, metric);
+        Ok(())
+    }
+
     fn finish(self) -> Vec<MetricGroup> {
         self.0
             .into_iter()
@@ -408,14 +469,143 @@ pub fn parse_request(request: proto::WriteRequest) -> Result<Vec<MetricGroup>, P
             None => return Err(ParserError::RequestNoNameLabel),
         };
 
+        // Exemplars are attached to the first sample or histogram of their time series.
+        let mut exemplars = timeseries
+            .exemplars
+            .into_iter()
+            .map(|exemplar| Exemplar {
+                labels: exemplar
+                    .labels
+                    .into_iter()
+                    .map(|label| (label.name, label.value))
+                    .collect(),
+                value: exemplar.value,
+                timestamp: exemplar.timestamp,
+            })
+            .collect();
+
+        for histogram in timeseries.histograms {
+            groups.insert_histogram(&name, &labels, histogram, &mut exemplars)?;
+        }
         for sample in timeseries.samples {
-            groups.insert_sample(&name, &labels, sample)?;
+            groups.insert_sample(&name, &labels, sample, &mut exemplars)?;
         }
     }
 
     Ok(groups.finish())
 }
 
+/// Converts a native histogram into the cumulative buckets of a classic histogram.
+///
+/// With a schema of `n`, positive bucket `i` covers `(base^(i-1), base^i]` where
+/// `base = 2^(2^-n)`, negative buckets mirror the positive ones below zero, and the zero bucket
+/// covers `[-zero_threshold, zero_threshold]`. The counts of float histograms are rounded to the
+/// nearest integer.
+///
+/// This is lossy, as classic buckets only have an upper bound: negative bucket `i` becomes a
+/// bucket up to `-base^(i-1)`, the zero bucket one up to `zero_threshold` which also covers
+/// the negative values closer to zero, and the schema is lost, so that the buckets of histograms
+/// with different schemas or spans no longer line up.
+fn native_histogram(histogram: &proto::Histogram) -> HistogramMetric {
+    use proto::histogram::{Count, ZeroCount};
+
+    let growth = 2f64.powi(-histogram.schema);
+    let bound = |index: i32| 2f64.powf(f64::from(index) * growth);
+    let negative = native_bucket_counts(
+        &histogram.negative_spans,
+        &histogram.negative_deltas,
+        &histogram.negative_counts,
+    );
+    let positive = native_bucket_counts(
+        &histogram.positive_spans,
+        &histogram.positive_deltas,
+        &histogram.positive_counts,
+    );
+    let zero_count = match histogram.zero_count {
+        Some(ZeroCount::ZeroCountInt(count)) => count as f64,
+        Some(ZeroCount::ZeroCountFloat(count)) => count,
+        None => 0.0,
+    };
+    let count = match histogram.count {
+        Some(Count::CountInt(count)) => count,
+        Some(Count::CountFloat(count)) => count.round() as u64,
+        None => 0,
+    };
+
+    let mut buckets = Vec::with_capacity(negative.len() + positive.len() + 2);
+    let mut cumulative = 0.0;
+    // The most negative bucket has the highest index, so it comes last.
+    for (index, bucket_count) in negative.into_iter().rev() {
+        cumulative += bucket_count;
+        buckets.push(HistogramBucket {
+            bucket: -bound(index - 1),
+            count: cumulative.round() as u64,
+        });
+    }
+    cumulative += zero_count;
+    buckets.push(HistogramBucket {
+        bucket: histogram.zero_threshold,
+        count: cumulative.round() as u64,
+    });
+    for (index, bucket_count) in positive {
+        cumulative += bucket_count;
+        buckets.push(HistogramBucket {
+            bucket: bound(index),
+            count: cumulative.round() as u64,
+        });
+    }
+    buckets.push(HistogramBucket {
+        bucket: f64::INFINITY,
+        count,
+    });
+
+    HistogramMetric {
+        buckets,
+        sum: histogram.sum,
+        count,
+        exemplars: Vec::new(),
+        native: None,
+    }
+}
+
+/// Expands the spans of a native histogram into `(index, count)` pairs for each bucket.
+///
+/// Integer histograms encode each count as a delta to the previous bucket, float histograms
+/// carry the counts as they are.
+fn native_bucket_counts(
+    spans: &[proto::BucketSpan],
+    deltas: &[i64],
+    counts: &[f64],
+) -> Vec<(i32, f64)> {
+    let mut buckets = Vec::new();
+    let mut index = 0;
+    let mut position = 0;
+    let mut current = 0i64;
+
+    for span in spans {
+        // The offset of the first span is the index of its first bucket, the offsets of the
+        // following spans are the gaps to the end of the previous span.
+        index += span.offset;
+        for _ in 0..span.length {
+            let count = if deltas.is_empty() {
+                counts.get(position).copied()
+            } else {
+                deltas.get(position).map(|delta| {
+                    current += delta;
+                    current as f64
+                })
+            };
+            let Some(count) = count else {
+                return buckets;
+            };
+            buckets.push((index, count));
+            position += 1;
+            index += 1;
+        }
+    }
+    buckets
+}
+
 impl From<proto::MetricType> for MetricKind {
     fn from(kind: proto::MetricType) -> Self {
         use proto::MetricType::*;
@@ -461,7 +651,10 @@ mod test {
                     timestamp: $timestamp,
                     labels: $labels,
                 },
-                &SimpleMetric { value: $value },
+                &SimpleMetric {
+                    value: $value,
+                    exemplars: vec![],
+                },
             )
         };
     }
@@ -563,6 +756,8 @@ mod test {
                     ],
                     count: 144320,
                     sum: 53423.0,
+                    exemplars: vec![],
+                    native: None,
                 },
             ));
         });
@@ -579,6 +774,8 @@ mod test {
                     ],
                     count: 10,
                     sum: 5.0,
+                    exemplars: vec![],
+                    native: None,
                 },
             ));
         });
@@ -599,6 +796,7 @@ mod test {
                     ],
                     count: 4588206224,
                     sum: 1.7560473e+07,
+                    exemplars: vec![],
                 },
             ));
         });
@@ -736,6 +934,8 @@ mod test {
                     samples: vec![
                         $( proto::Sample { value: $sample as f64, timestamp: $timestamp as i64 }, )*
                     ],
+                    exemplars: vec![],
+                    histograms: vec![],
                 }, )* ],
             }
         };
@@ -839,6 +1039,8 @@ mod test {
                         ],
                         count: 19,
                         sum: 12.0,
+                        exemplars: vec![],
+                        native: None,
                     })
             );
         });
@@ -878,6 +1080,7 @@ mod test {
                         ],
                         count: 21,
                         sum: 12.0,
+                        exemplars: vec![],
                     })
             );
         });
@@ -886,4 +1089,137 @@ mod test {
             assert_eq!(metrics.get_index(0).unwrap(), simple_metric!(Some(1395066367700), labels!(), 24.0));
         });
     }
+
+    fn label(name: &str, value: &str) -> proto::Label {
+        proto::Label {
+            name: name.into(),
+            value: value.into(),
+        }
+    }
+
+    #[test]
+    fn parse_request_exemplars() {
+        let mut request = write_request!(
+            ["one" = Histogram],
+            [
+                [__name__ => "one_bucket", le => "1"] => [ 15 @ 1395066367700 ],
+                [__name__ => "one_bucket", le => "+Inf"] => [ 19 @ 1395066367700 ],
+                [__name__ => "one_total"] => [ 24 @ 1395066367700 ]
+            ]
+        );
+        request.timeseries[0].exemplars.push(proto::Exemplar {
+            labels: vec![label("trace_id", "abc123")],
+            value: 0.3,
+            timestamp: 1395066367600,
+        });
+        request.timeseries[2].exemplars.push(proto::Exemplar {
+            labels: vec![],
+            value: 1.0,
+            timestamp: 1395066367650,
+        });
+
+        let parsed = parse_request(request).unwrap();
+        match_group!(parsed[0], "one", Histogram => |metrics: &MetricMap<HistogramMetric>| {
+            let (_, metric) = metrics.get_index(0).unwrap();
+            assert_eq!(metric.exemplars, vec![Exemplar {
+                labels: labels!(trace_id => "abc123"),
+                value: 0.3,
+                timestamp: 1395066367600,
+            }]);
+        });
+        match_group!(parsed[1], "one_total", Untyped => |metrics: &MetricMap<SimpleMetric>| {
+            let (_, metric) = metrics.get_index(0).unwrap();
+            assert_eq!(metric.exemplars.len(), 1);
+            assert_eq!(metric.exemplars[0].value, 1.0);
+        });
+    }
+
+    #[test]
+    fn parse_request_native_histogram() {
+        let mut request = write_request!(["one" = Histogram], [[__name__ => "one"] => []]);
+        let histogram = proto::Histogram {
+            count: Some(proto::histogram::Count::CountInt(9)),
+            sum: 25.0,
+            // base 2: bucket `i` covers (2^(i-1), 2^i]
+            schema: 0,
+            zero_threshold: 0.001,
+            zero_count: Some(proto::histogram::ZeroCount::ZeroCountInt(1)),
+            negative_spans: vec![proto::BucketSpan {
+                offset: 1,
+                length: 1,
+            }],
+            negative_deltas: vec![2],
+            negative_counts: vec![],
+            // buckets 0, 1, then 3 after a gap of one
+            positive_spans: vec![
+                proto::BucketSpan {
+                    offset: 0,
+                    length: 2,
+                },
+                proto::BucketSpan {
+                    offset: 1,
+                    length: 1,
+                },
+            ],
+            positive_deltas: vec![3, -1, -1],
+            positive_counts: vec![],
+            reset_hint: proto::histogram::ResetHint::No as i32,
+            timestamp: 1395066367700,
+        };
+        request.timeseries[0].histograms.push(histogram.clone());
+
+        let parsed = parse_request(request).unwrap();
+        assert_eq!(parsed.len(), 1);
+        match_group!(parsed[0], "one", Histogram => |metrics: &MetricMap<HistogramMetric>| {
+            assert_eq!(
+                metrics.get_index(0).unwrap(), (
+                    &GroupKey {
+                        timestamp: Some(1395066367700),
+                        labels: labels!(),
+                    },
+                    &HistogramMetric {
+                        buckets: vec![
+                            HistogramBucket { bucket: -1.0, count: 2 },
+                            HistogramBucket { bucket: 0.001, count: 3 },
+                            HistogramBucket { bucket: 1.0, count: 6 },
+                            HistogramBucket { bucket: 2.0, count: 8 },
+                            HistogramBucket { bucket: 8.0, count: 9 },
+                            HistogramBucket { bucket: f64::INFINITY, count: 9 },
+                        ],
+                        count: 9,
+                        sum: 25.0,
+                        exemplars: vec![],
+                        native: Some(histogram),
+                    })
+            );
+        });
+    }
+
+    #[test]
+    fn parse_request_float_native_histogram() {
+        let mut request = write_request!([], [[__name__ => "one"] => []]);
+        request.timeseries[0].histograms.push(proto::Histogram {
+            count: Some(proto::histogram::Count::CountFloat(3.5)),
+            sum: 4.0,
+            schema: 1,
+            positive_spans: vec![proto::BucketSpan {
+                offset: 1,
+                length: 2,
+            }],
+            positive_counts: vec![1.5, 2.0],
+            ..Default::default()
+        });
+
+        let parsed = parse_request(request).unwrap();
+        match_group!(parsed[0], "one", Histogram => |metrics: &MetricMap<HistogramMetric>| {
+            let (_, metric) = metrics.get_index(0).unwrap();
+            let bounds = metric.buckets.iter().map(|b| b.bucket).collect::<Vec<_>>();
+            let counts = metric.buckets.iter().map(|b| b.count).collect::<Vec<_>>();
+            assert_eq!(bounds.len(), 4);
+            assert_eq!(bounds[0], 0.0);
+            assert!((bounds[1] - 2f64.sqrt()).abs() < 1e-12);
+            assert_eq!(bounds[2], 2.0);
+            assert_eq!(counts, vec![0, 2, 4, 4]);
+        });
+    }
 }
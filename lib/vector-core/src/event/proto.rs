@@ -3,6 +3,7 @@ use std::sync::Arc;
 
 use chrono::TimeZone;
 use ordered_float::NotNan;
+use snafu::Snafu;
 
 use super::{MetricTags, WithMetadata};
 use crate::{event, metrics::AgentDDSketch};
@@ -18,6 +19,60 @@ use vrl::value::{ObjectMap, Value as VrlValue};
 
 use super::{array, metric::MetricSketch, EventMetadata};
 
+/// The version of the native encoding of events written by this release of Vector.
+///
+/// The version must be bumped whenever the encoding changes in a way that older releases can't
+/// decode without losing data, such as moving metric metadata to a new field, so that they reject
+/// the events instead of silently dropping what they don't know about. Version `0` is the encoding
+/// of the releases that predate the version, which is not written by protobuf and thus keeps the
+/// events readable by them.
+pub const NATIVE_VERSION: u32 = 0;
+
+/// The oldest version of the native encoding of events this release of Vector can decode.
+///
+/// Releases decode all the versions up to their own, unless support for the oldest ones is dropped
+/// by raising this version.
+pub const MIN_NATIVE_VERSION: u32 = 0;
+
+/// An error returned for events encoded with a version of the native encoding this release of
+/// Vector can't decode.
+#[derive(Debug, Snafu)]
+pub enum NativeVersionError {
+    #[snafu(display(
+        "events are encoded with version {} of the native encoding, which is newer than the \
+         version {} supported by this Vector; upgrade this Vector to at least the release of the \
+         one encoding the events",
+        version,
+        NATIVE_VERSION
+    ))]
+    TooNew { version: u32 },
+
+    #[snafu(display(
+        "events are encoded with version {} of the native encoding, which is older than the \
+         version {} supported by this Vector; upgrade the Vector encoding the events",
+        version,
+        MIN_NATIVE_VERSION
+    ))]
+    TooOld { version: u32 },
+}
+
+/// Checks that events encoded with the given version of the native encoding can be decoded by
+/// this release of Vector.
+///
+/// # Errors
+///
+/// If the version is outside of `MIN_NATIVE_VERSION..=NATIVE_VERSION`, an error describing which
+/// side needs to be upgraded is returned.
+pub fn check_native_version(version: u32) -> Result<(), NativeVersionError> {
+    if version > NATIVE_VERSION {
+        Err(NativeVersionError::TooNew { version })
+    } else if !(MIN_NATIVE_VERSION..=NATIVE_VERSION).contains(&version) {
+        Err(NativeVersionError::TooOld { version })
+    } else {
+        Ok(())
+    }
+}
+
 impl event_array::Events {
     // We can't use the standard `From` traits here because the actual
     // type of `LogArray` and `TraceArray` are the same.
@@ -44,7 +99,10 @@ impl From<array::EventArray> for EventArray {
             array::EventArray::Metrics(array) => event_array::Events::from_metrics(array),
             array::EventArray::Traces(array) => event_array::Events::from_traces(array),
         });
-        Self { events }
+        Self {
+            events,
+            version: NATIVE_VERSION,
+        }
     }
 }
 
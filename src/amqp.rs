@@ -1,7 +1,40 @@
 //! Functionality supporting both the `[crate::sources::amqp]` source and `[crate::sinks::amqp]` sink.
-use lapin::tcp::{OwnedIdentity, OwnedTLSConfig};
+use lapin::{
+    auth::SASLMechanism,
+    tcp::{OwnedIdentity, OwnedTLSConfig},
+    uri::AMQPUri,
+};
 use vector_lib::configurable::configurable_component;
 
+/// The SASL mechanism used to authenticate to the AMQP server.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum AmqpSaslMechanism {
+    /// Authenticate with the user and password of the connection string.
+    Plain,
+
+    /// Authenticate with the user and password of the connection string, encoded as an AMQP
+    /// table.
+    #[serde(rename = "amqplain")]
+    AmqPlain,
+
+    /// Authenticate with the client certificate configured in the `tls` section.
+    ///
+    /// With RabbitMQ, this requires the `rabbitmq_auth_mechanism_ssl` plugin.
+    External,
+}
+
+impl From<AmqpSaslMechanism> for SASLMechanism {
+    fn from(mechanism: AmqpSaslMechanism) -> Self {
+        match mechanism {
+            AmqpSaslMechanism::Plain => SASLMechanism::Plain,
+            AmqpSaslMechanism::AmqPlain => SASLMechanism::AMQPlain,
+            AmqpSaslMechanism::External => SASLMechanism::External,
+        }
+    }
+}
+
 /// AMQP connection options.
 #[configurable_component]
 #[derive(Clone, Debug)]
@@ -21,6 +54,12 @@ pub(crate) struct AmqpConfig {
     ))]
     pub(crate) connection_string: String,
 
+    /// The SASL mechanism used to authenticate to the AMQP server.
+    ///
+    /// Defaults to the `auth_mechanism` parameter of the connection string if it is set, or
+    /// to `plain` otherwise.
+    pub(crate) sasl_mechanism: Option<AmqpSaslMechanism>,
+
     #[configurable(derived)]
     pub(crate) tls: Option<crate::tls::TlsConfig>,
 }
@@ -29,6 +68,7 @@ impl Default for AmqpConfig {
     fn default() -> Self {
         Self {
             connection_string: "amqp://127.0.0.1/%2f".to_string(),
+            sasl_mechanism: None,
             tls: None,
         }
     }
@@ -62,7 +102,10 @@ impl AmqpConfig {
     pub(crate) async fn connect(
         &self,
     ) -> Result<(lapin::Connection, lapin::Channel), Box<dyn std::error::Error + Send + Sync>> {
-        let addr = self.connection_string.clone();
+        let mut uri = self.connection_string.parse::<AMQPUri>()?;
+        if let Some(mechanism) = self.sasl_mechanism {
+            uri.query.auth_mechanism = Some(mechanism.into());
+        }
         let conn = match &self.tls {
             Some(tls) => {
                 let cert_chain = if let Some(ca) = &tls.ca_file {
@@ -87,14 +130,16 @@ impl AmqpConfig {
                     identity,
                     cert_chain,
                 };
-                lapin::Connection::connect_with_config(
-                    &addr,
+                lapin::Connection::connect_uri_with_config(
+                    uri,
                     lapin::ConnectionProperties::default(),
                     tls_config,
                 )
                 .await
             }
-            None => lapin::Connection::connect(&addr, lapin::ConnectionProperties::default()).await,
+            None => {
+                lapin::Connection::connect_uri(uri, lapin::ConnectionProperties::default()).await
+            }
         }?;
         let channel = conn.create_channel().await?;
         Ok((conn, channel))
@@ -1,8 +1,10 @@
+use std::sync::Arc;
+
 use serde::{Deserialize, Serialize};
-use vector_lib::codecs::decoding::{DeserializerConfig, FramingConfig};
+use vector_lib::codecs::decoding::{Deserializer, DeserializerConfig, FramingConfig};
 use vector_lib::config::LogNamespace;
 
-use crate::codecs::Decoder;
+use crate::{codecs::Decoder, common::schema_registry::RegistrySchemaFetcher};
 
 /// Config used to build a `Decoder`.
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -43,10 +45,22 @@ impl DecodingConfig {
     /// Builds a `Decoder` from the provided configuration.
     pub fn build(&self) -> vector_lib::Result<Decoder> {
         // Build the framer.
-        let framer = self.framing.build();
+        let framer = self.framing.build()?;
 
         // Build the deserializer.
-        let deserializer = self.decoding.build()?;
+        let deserializer = match (self.decoding.build()?, &self.decoding) {
+            // The schemas of the registry are fetched through the HTTP client of Vector.
+            (Deserializer::Avro(deserializer), DeserializerConfig::Avro { avro }) => {
+                match &avro.schema_registry {
+                    Some(options) => Deserializer::Avro(
+                        deserializer
+                            .with_schema_fetcher(Arc::new(RegistrySchemaFetcher::new(options)?)),
+                    ),
+                    None => Deserializer::Avro(deserializer),
+                }
+            }
+            (deserializer, _) => deserializer,
+        };
 
         Ok(Decoder::new(framer, deserializer).with_log_namespace(self.log_namespace))
     }
@@ -0,0 +1,23 @@
+use vector_lib::codecs::encoding::{BatchSerializer, Framer};
+
+use super::Encoder;
+
+/// The encoder of the payloads of sinks that support both framed and batch encodings.
+#[derive(Clone, Debug)]
+pub enum EncoderKind {
+    /// Events are serialized one by one, and framed.
+    Framed(Encoder<Framer>),
+
+    /// Events are serialized together by a `BatchSerializer`.
+    Batch(BatchSerializer),
+}
+
+impl EncoderKind {
+    /// The content type of the payloads of this encoder.
+    pub const fn content_type(&self) -> &'static str {
+        match self {
+            Self::Framed(encoder) => encoder.content_type(),
+            Self::Batch(serializer) => serializer.content_type(),
+        }
+    }
+}
@@ -104,7 +104,7 @@ impl EncodingConfigWithFraming {
                 SinkType::StreamBased => NewlineDelimitedEncoder::new().into(),
                 SinkType::MessageBased => CharacterDelimitedEncoder::new(b',').into(),
             },
-            (None, Serializer::Avro(_) | Serializer::Native(_)) => {
+            (None, Serializer::Avro(_) | Serializer::Msgpack(_) | Serializer::Native(_)) => {
                 LengthDelimitedEncoder::default().into()
             }
             (None, Serializer::Gelf(_)) => {
@@ -117,12 +117,16 @@ impl EncodingConfigWithFraming {
                 // https://developers.google.com/protocol-buffers/docs/techniques#streaming
                 LengthDelimitedEncoder::default().into()
             }
+            #[cfg(feature = "codecs-otlp")]
+            (None, Serializer::Otlp(_)) => LengthDelimitedEncoder::default().into(),
             (
                 None,
-                Serializer::Csv(_)
+                Serializer::Cef(_)
+                | Serializer::Csv(_)
                 | Serializer::Logfmt(_)
                 | Serializer::NativeJson(_)
                 | Serializer::RawMessage(_)
+                | Serializer::Syslog(_)
                 | Serializer::Text(_),
             ) => NewlineDelimitedEncoder::new().into(),
         };
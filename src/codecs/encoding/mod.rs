@@ -1,7 +1,16 @@
+#[cfg(feature = "codecs-parquet")]
+mod batch;
 mod config;
 mod encoder;
 mod transformer;
 
+#[cfg(feature = "codecs-parquet")]
+pub use batch::EncoderKind;
 pub use config::{EncodingConfig, EncodingConfigWithFraming, SinkType};
 pub use encoder::Encoder;
 pub use transformer::{TimestampFormat, Transformer};
+#[cfg(feature = "codecs-parquet")]
+pub use vector_lib::codecs::encoding::{
+    BatchSerializer, BatchSerializerConfig, ParquetCompression, ParquetSerializer,
+    ParquetSerializerOptions,
+};
@@ -8,6 +8,11 @@ mod encoding;
 mod ready_frames;
 
 pub use decoding::{Decoder, DecodingConfig};
+#[cfg(feature = "codecs-parquet")]
+pub use encoding::{
+    BatchSerializer, BatchSerializerConfig, EncoderKind, ParquetCompression, ParquetSerializer,
+    ParquetSerializerOptions,
+};
 pub use encoding::{
     Encoder, EncodingConfig, EncodingConfigWithFraming, SinkType, TimestampFormat, Transformer,
 };
@@ -0,0 +1,255 @@
+//! A client of the [Confluent Schema Registry][schema_registry], used by the `kafka` sink to
+//! register the Avro schema of its records, and by the `avro` decoder to fetch the schemas of the
+//! messages it decodes.
+//!
+//! [schema_registry]: https://docs.confluent.io/platform/current/schema-registry/index.html
+
+use std::time::Duration;
+
+use bytes::Bytes;
+use http::{
+    header::{ACCEPT, CONTENT_TYPE},
+    Request, StatusCode,
+};
+use hyper::Body;
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use serde::Deserialize;
+use snafu::{ResultExt, Snafu};
+use tokio::runtime::{Handle, RuntimeFlavor};
+use vector_lib::codecs::decoding::format::{SchemaFetcher, SchemaRegistryOptions};
+
+use crate::{
+    config::ProxyConfig,
+    http::{Auth, HttpClient, HttpError},
+    tls::{MaybeTlsSettings, TlsSettings},
+};
+
+const CONTENT_TYPE_SCHEMA_REGISTRY: &str = "application/vnd.schemaregistry.v1+json";
+
+/// The characters escaped in subjects, which may contain any character, when used as a path
+/// segment.
+const SUBJECT: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+#[derive(Debug, Snafu)]
+pub(crate) enum SchemaRegistryError {
+    #[snafu(display("{}", source))]
+    Http { source: HttpError },
+    #[snafu(display("Failed to build the request: {}", source))]
+    Build { source: http::Error },
+    #[snafu(display("The Schema Registry responded with status {}: {}", status, body))]
+    Status { status: StatusCode, body: String },
+    #[snafu(display("Invalid response of the Schema Registry: {}", source))]
+    Response { source: serde_json::Error },
+    #[snafu(display("The request to the Schema Registry timed out"))]
+    Timeout,
+}
+
+impl SchemaRegistryError {
+    /// Whether the request may succeed if retried.
+    ///
+    /// Transport errors and server errors are retried, while client errors, such as invalid
+    /// credentials or a schema incompatible with the subject, won't be resolved by retrying.
+    pub(crate) fn is_retriable(&self) -> bool {
+        match self {
+            Self::Http { .. } | Self::Timeout => true,
+            Self::Status { status, .. } => status.is_server_error(),
+            Self::Build { .. } | Self::Response { .. } => false,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct SchemaIdResponse {
+    id: u32,
+}
+
+/// A client of the Schema Registry.
+#[derive(Clone, Debug)]
+pub(crate) struct SchemaRegistryClient {
+    client: HttpClient,
+    url: String,
+    auth: Option<Auth>,
+}
+
+impl SchemaRegistryClient {
+    pub(crate) fn new(
+        url: &str,
+        auth: Option<Auth>,
+        tls_settings: impl Into<MaybeTlsSettings>,
+        proxy: &ProxyConfig,
+    ) -> crate::Result<Self> {
+        Ok(Self {
+            client: HttpClient::new(tls_settings, proxy)?,
+            url: url.trim_end_matches('/').to_owned(),
+            auth,
+        })
+    }
+
+    fn subject_url(&self, subject: &str) -> String {
+        format!(
+            "{}/subjects/{}",
+            self.url,
+            utf8_percent_encode(subject, SUBJECT)
+        )
+    }
+
+    /// Gets the ID of a schema under a subject, registering it first when `register` is set.
+    ///
+    /// Registering a schema which is already registered under the subject returns its existing ID.
+    pub(crate) async fn schema_id(
+        &self,
+        subject: &str,
+        schema: &str,
+        register: bool,
+    ) -> Result<u32, SchemaRegistryError> {
+        let mut url = self.subject_url(subject);
+        if register {
+            url.push_str("/versions");
+        }
+        let body = serde_json::json!({ "schema": schema }).to_string();
+        let request = Request::post(url)
+            .header(CONTENT_TYPE, CONTENT_TYPE_SCHEMA_REGISTRY)
+            .body(Body::from(body))
+            .context(BuildSnafu)?;
+
+        let body = self.send(request).await?;
+        let response: SchemaIdResponse = serde_json::from_slice(&body).context(ResponseSnafu)?;
+        Ok(response.id)
+    }
+
+    /// Gets the schema with the given ID, returning the body of the response.
+    pub(crate) async fn schema(&self, id: u32) -> Result<Bytes, SchemaRegistryError> {
+        let request = Request::get(format!("{}/schemas/ids/{}", self.url, id))
+            .header(ACCEPT, CONTENT_TYPE_SCHEMA_REGISTRY)
+            .body(Body::empty())
+            .context(BuildSnafu)?;
+
+        self.send(request).await
+    }
+
+    async fn send(&self, mut request: Request<Body>) -> Result<Bytes, SchemaRegistryError> {
+        if let Some(auth) = &self.auth {
+            auth.apply(&mut request);
+        }
+
+        let response = self.client.send(request).await.context(HttpSnafu)?;
+        let status = response.status();
+        let body = hyper::body::to_bytes(response.into_body())
+            .await
+            .map_err(|source| SchemaRegistryError::Http {
+                source: HttpError::CallRequest { source },
+            })?;
+        if !status.is_success() {
+            return StatusSnafu {
+                status,
+                body: String::from_utf8_lossy(&body).into_owned(),
+            }
+            .fail();
+        }
+        Ok(body)
+    }
+}
+
+/// Fetches the schemas of the `avro` decoder from the Schema Registry.
+///
+/// A single fetcher, and so a single client, is shared by all the clones of the decoder.
+#[derive(Debug)]
+pub(crate) struct RegistrySchemaFetcher {
+    client: SchemaRegistryClient,
+    timeout: Duration,
+    runtime: Handle,
+}
+
+impl RegistrySchemaFetcher {
+    pub(crate) fn new(options: &SchemaRegistryOptions) -> crate::Result<Self> {
+        let auth = options.username.as_ref().map(|user| Auth::Basic {
+            user: user.clone(),
+            password: options.password.clone().unwrap_or_default(),
+        });
+        let runtime = Handle::try_current()
+            .map_err(|_| "The `schema_registry` option requires a Tokio runtime.")?;
+
+        Ok(Self {
+            client: SchemaRegistryClient::new(
+                &options.url,
+                auth,
+                TlsSettings::from_options(&None)?,
+                &ProxyConfig::from_env(),
+            )?,
+            timeout: Duration::from_secs(options.timeout_secs),
+            runtime,
+        })
+    }
+}
+
+impl SchemaFetcher for RegistrySchemaFetcher {
+    fn fetch(&self, id: u32) -> vector_lib::Result<Bytes> {
+        let fetch = async {
+            tokio::time::timeout(self.timeout, self.client.schema(id))
+                .await
+                .map_err(|_| SchemaRegistryError::Timeout)?
+        };
+
+        // Decoding isn't async, so the thread decoding the message waits for the response, while
+        // the request runs on the runtime. On a worker of the runtime, the other tasks of the
+        // worker are handed over to the other workers in the meantime.
+        let body = match Handle::try_current() {
+            Ok(current) if current.runtime_flavor() == RuntimeFlavor::CurrentThread => {
+                return Err(
+                    "Schemas can't be fetched from a single-threaded runtime, as the runtime \
+                    would be blocked while waiting for the response."
+                        .into(),
+                )
+            }
+            Ok(_) => tokio::task::block_in_place(|| self.runtime.block_on(fetch)),
+            Err(_) => self.runtime.block_on(fetch),
+        }?;
+        Ok(body)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn client() -> SchemaRegistryClient {
+        SchemaRegistryClient::new(
+            "http://localhost:8081/",
+            None,
+            None,
+            &ProxyConfig::default(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn encodes_subjects() {
+        assert_eq!(
+            client().subject_url("logs-value"),
+            "http://localhost:8081/subjects/logs-value"
+        );
+        assert_eq!(
+            client().subject_url("team/logs value"),
+            "http://localhost:8081/subjects/team%2Flogs%20value"
+        );
+    }
+
+    #[test]
+    fn retries_transport_and_server_errors_only() {
+        let status = |status| SchemaRegistryError::Status {
+            status,
+            body: String::new(),
+        };
+        assert!(SchemaRegistryError::Timeout.is_retriable());
+        assert!(status(StatusCode::INTERNAL_SERVER_ERROR).is_retriable());
+        assert!(status(StatusCode::SERVICE_UNAVAILABLE).is_retriable());
+        assert!(!status(StatusCode::UNAUTHORIZED).is_retriable());
+        assert!(!status(StatusCode::FORBIDDEN).is_retriable());
+        assert!(!status(StatusCode::CONFLICT).is_retriable());
+        assert!(!status(StatusCode::UNPROCESSABLE_ENTITY).is_retriable());
+    }
+}
@@ -110,14 +110,14 @@ impl ResourceCodec {
         let (framer, deserializer) = match self {
             Self::Decoding(config) => return config.build(),
             Self::Encoding(config) => (
-                encoder_framing_to_decoding_framer(config.config().default_stream_framing()),
+                encoder_framing_to_decoding_framer(config.config().default_stream_framing())?,
                 serializer_config_to_deserializer(config.config())?,
             ),
             Self::EncodingWithFraming(config) => {
                 let (maybe_framing, serializer) = config.config();
                 let framing = maybe_framing.clone().unwrap_or(FramingConfig::Bytes);
                 (
-                    encoder_framing_to_decoding_framer(framing),
+                    encoder_framing_to_decoding_framer(framing)?,
                     serializer_config_to_deserializer(serializer)?,
                 )
             }
@@ -157,6 +157,7 @@ fn deserializer_config_to_serializer(config: &DeserializerConfig) -> encoding::S
                 protobuf: vector_lib::codecs::encoding::ProtobufSerializerOptions {
                     desc_file: config.protobuf.desc_file.clone(),
                     message_type: config.protobuf.message_type.clone(),
+                    field_mappings: Vec::new(),
                 },
             })
         }
@@ -165,11 +166,18 @@ fn deserializer_config_to_serializer(config: &DeserializerConfig) -> encoding::S
         // immediately barfing.
         #[cfg(feature = "codecs-syslog")]
         DeserializerConfig::Syslog { .. } => SerializerConfig::Logfmt,
+        DeserializerConfig::Msgpack => SerializerConfig::Msgpack,
         DeserializerConfig::Native => SerializerConfig::Native,
         DeserializerConfig::NativeJson { .. } => SerializerConfig::NativeJson,
+        #[cfg(feature = "codecs-otlp")]
+        DeserializerConfig::Otlp(_) => SerializerConfig::Otlp,
         DeserializerConfig::Gelf { .. } => SerializerConfig::Gelf,
-        DeserializerConfig::Avro { avro } => SerializerConfig::Avro { avro: avro.into() },
-        DeserializerConfig::Vrl { .. } => unimplemented!(),
+        DeserializerConfig::Avro { avro } => SerializerConfig::Avro {
+            avro: avro
+                .try_into()
+                .expect("the Avro decoder must have a `schema` to encode with"),
+        },
+        DeserializerConfig::Csv { .. } | DeserializerConfig::Vrl { .. } => unimplemented!(),
     };
 
     serializer_config
@@ -196,6 +204,15 @@ fn decoder_framing_to_encoding_framer(framing: &decoding::FramingConfig) -> enco
         // TODO: There's no equivalent octet counting framer for encoding... although
         // there's no particular reason that would make it hard to write.
         decoding::FramingConfig::OctetCounting(_) => todo!(),
+        decoding::FramingConfig::ChunkedGelf(_) => todo!(),
+        decoding::FramingConfig::Multiline(_) => todo!(),
+        decoding::FramingConfig::VarintLengthDelimited(config) => {
+            encoding::FramingConfig::VarintLengthDelimited(
+                encoding::VarintLengthDelimitedEncoderConfig {
+                    varint_length_delimited: config.varint_length_delimited.clone(),
+                },
+            )
+        }
     };
 
     framing_config.build()
@@ -206,12 +223,17 @@ fn serializer_config_to_deserializer(
 ) -> vector_lib::Result<decoding::Deserializer> {
     let deserializer_config = match config {
         SerializerConfig::Avro { .. } => todo!(),
+        SerializerConfig::Cef(_) => todo!(),
         SerializerConfig::Csv { .. } => todo!(),
         SerializerConfig::Gelf => DeserializerConfig::Gelf(Default::default()),
         SerializerConfig::Json(_) => DeserializerConfig::Json(Default::default()),
         SerializerConfig::Logfmt => todo!(),
+        SerializerConfig::Msgpack => DeserializerConfig::Msgpack,
         SerializerConfig::Native => DeserializerConfig::Native,
         SerializerConfig::NativeJson => DeserializerConfig::NativeJson(Default::default()),
+        // The signal of the payloads depends on the events that were encoded.
+        #[cfg(feature = "codecs-otlp")]
+        SerializerConfig::Otlp => todo!(),
         SerializerConfig::Protobuf(config) => {
             DeserializerConfig::Protobuf(vector_lib::codecs::decoding::ProtobufDeserializerConfig {
                 protobuf: vector_lib::codecs::decoding::ProtobufDeserializerOptions {
@@ -221,12 +243,15 @@ fn serializer_config_to_deserializer(
             })
         }
         SerializerConfig::RawMessage | SerializerConfig::Text(_) => DeserializerConfig::Bytes,
+        SerializerConfig::Syslog(_) => todo!(),
     };
 
     deserializer_config.build()
 }
 
-fn encoder_framing_to_decoding_framer(framing: encoding::FramingConfig) -> decoding::Framer {
+fn encoder_framing_to_decoding_framer(
+    framing: encoding::FramingConfig,
+) -> vector_lib::Result<decoding::Framer> {
     let framing_config = match framing {
         encoding::FramingConfig::Bytes => decoding::FramingConfig::Bytes,
         encoding::FramingConfig::CharacterDelimited(config) => {
@@ -245,6 +270,13 @@ fn encoder_framing_to_decoding_framer(framing: encoding::FramingConfig) -> decod
         encoding::FramingConfig::NewlineDelimited => {
             decoding::FramingConfig::NewlineDelimited(Default::default())
         }
+        encoding::FramingConfig::VarintLengthDelimited(config) => {
+            decoding::FramingConfig::VarintLengthDelimited(
+                decoding::VarintLengthDelimitedDecoderConfig {
+                    varint_length_delimited: config.varint_length_delimited.clone(),
+                },
+            )
+        }
     };
 
     framing_config.build()
@@ -10,7 +10,13 @@ use tonic::{
 };
 use tower::Service;
 use vector_lib::shutdown::ShutdownSignal;
-use vector_lib::{event::Event, tls::MaybeTlsSettings};
+use vector_lib::{
+    event::{
+        proto::{MIN_NATIVE_VERSION, NATIVE_VERSION},
+        Event,
+    },
+    tls::MaybeTlsSettings,
+};
 
 use crate::{
     components::validation::{
@@ -63,6 +69,8 @@ impl VectorService for EventForwardService {
     ) -> Result<tonic::Response<HealthCheckResponse>, Status> {
         let message = HealthCheckResponse {
             status: ServingStatus::Serving.into(),
+            min_version: MIN_NATIVE_VERSION,
+            version: NATIVE_VERSION,
         };
 
         Ok(tonic::Response::new(message))
@@ -104,6 +112,7 @@ impl InputEdge {
             while let Some(test_event) = rx.recv().await {
                 let request = PushEventsRequest {
                     events: vec![test_event.into_event().into()],
+                    version: NATIVE_VERSION,
                 };
 
                 if let Err(e) = client.push_events(request).await {
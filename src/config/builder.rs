@@ -8,7 +8,10 @@ use serde_json::Value;
 use vector_lib::config::GlobalOptions;
 use vector_lib::configurable::configurable_component;
 
-use crate::{enrichment_tables::EnrichmentTables, providers::Providers, secrets::SecretBackends};
+use crate::{
+    enrichment_tables::EnrichmentTables, providers::Providers, secrets::SecretBackends,
+    sinks::util::service::RequestLimitsConfig,
+};
 
 #[cfg(feature = "api")]
 use super::api;
@@ -77,6 +80,12 @@ pub struct ConfigBuilder {
     #[serde(default)]
     pub secret: IndexMap<ComponentKey, SecretBackends>,
 
+    /// All configured request limits groups.
+    ///
+    /// The sinks that reference the same group share its concurrency and rate limits.
+    #[serde(default)]
+    pub request_limits: IndexMap<String, RequestLimitsConfig>,
+
     /// The duration in seconds to wait for graceful shutdown after SIGINT or SIGTERM are received.
     /// After the duration has passed, Vector will force shutdown. Default value is 60 seconds. This
     /// value can be set using a [cli arg](crate::cli::RootOpts::graceful_shutdown_limit_secs).
@@ -106,6 +115,8 @@ struct ConfigBuilderHash<'a> {
     tests: &'a Vec<TestDefinition<String>>,
     provider: &'a Option<Providers>,
     secret: BTreeMap<&'a ComponentKey, &'a SecretBackends>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    request_limits: BTreeMap<&'a String, &'a RequestLimitsConfig>,
 }
 
 #[cfg(feature = "enterprise")]
@@ -185,6 +196,7 @@ impl<'a> From<&'a ConfigBuilder> for ConfigBuilderHash<'a> {
             tests: &value.tests,
             provider: &value.provider,
             secret: value.secret.iter().collect(),
+            request_limits: value.request_limits.iter().collect(),
         }
     }
 }
@@ -205,6 +217,7 @@ impl From<Config> for ConfigBuilder {
             transforms,
             tests,
             secret,
+            request_limits,
             graceful_shutdown_duration,
             hash: _,
         } = config;
@@ -236,6 +249,7 @@ impl From<Config> for ConfigBuilder {
             provider: None,
             tests,
             secret,
+            request_limits,
             graceful_shutdown_duration,
             allow_empty: false,
         }
@@ -379,6 +393,11 @@ impl ConfigBuilder {
                 errors.push(format!("duplicate secret id found: {}", k));
             }
         });
+        with.request_limits.keys().for_each(|k| {
+            if self.request_limits.contains_key(k) {
+                errors.push(format!("duplicate request_limits name found: {}", k));
+            }
+        });
         if !errors.is_empty() {
             return Err(errors);
         }
@@ -389,6 +408,7 @@ impl ConfigBuilder {
         self.transforms.extend(with.transforms);
         self.tests.extend(with.tests);
         self.secret.extend(with.secret);
+        self.request_limits.extend(with.request_limits);
 
         Ok(())
     }
@@ -31,6 +31,10 @@ pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<
         errors.extend(type_errors);
     }
 
+    if let Err(request_limits_errors) = validation::check_request_limits(&builder) {
+        errors.extend(request_limits_errors);
+    }
+
     if let Err(output_errors) = validation::check_outputs(&builder) {
         errors.extend(output_errors);
     }
@@ -56,6 +60,7 @@ pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<
         tests,
         provider: _,
         secret,
+        request_limits,
         graceful_shutdown_duration,
         allow_empty: _,
     } = builder;
@@ -113,6 +118,7 @@ pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<
             transforms,
             tests,
             secret,
+            request_limits,
             graceful_shutdown_duration,
         };
 
@@ -18,10 +18,24 @@ impl ConfigDiff {
     }
 
     pub fn new(old: &Config, new: &Config) -> Self {
+        let mut sinks = Difference::new(&old.sinks, &new.sinks);
+
+        // Sinks are rebuilt when the request limits group they reference changes, so that they
+        // are limited by the new limits of the group.
+        for (key, sink) in new.sinks.iter() {
+            if let Some(name) = sink.request_limits() {
+                if old.sinks.contains_key(key)
+                    && old.request_limits.get(name) != new.request_limits.get(name)
+                {
+                    sinks.to_change.insert(key.clone());
+                }
+            }
+        }
+
         ConfigDiff {
             sources: Difference::new(&old.sources, &new.sources),
             transforms: Difference::new(&old.transforms, &new.transforms),
-            sinks: Difference::new(&old.sinks, &new.sinks),
+            sinks,
             enrichment_tables: Difference::new(&old.enrichment_tables, &new.enrichment_tables),
         }
     }
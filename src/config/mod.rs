@@ -19,7 +19,10 @@ pub use vector_lib::configurable::component::{
 };
 use vector_lib::configurable::configurable_component;
 
-use crate::{conditions, event::Metric, secrets::SecretBackends, serde::OneOrMany};
+use crate::{
+    conditions, event::Metric, secrets::SecretBackends, serde::OneOrMany,
+    sinks::util::service::RequestLimitsConfig,
+};
 
 pub mod api;
 mod builder;
@@ -119,6 +122,7 @@ pub struct Config {
     pub enrichment_tables: IndexMap<ComponentKey, EnrichmentTableOuter>,
     tests: Vec<TestDefinition>,
     secret: IndexMap<ComponentKey, SecretBackends>,
+    pub request_limits: IndexMap<String, RequestLimitsConfig>,
     pub graceful_shutdown_duration: Option<Duration>,
 }
 
@@ -566,8 +570,14 @@ mod tests {
                 let c2 = config::load_from_str(config, format).unwrap();
                 match (
                     config::warnings(&c2),
-                    topology::TopologyPieces::build(&c, &diff, HashMap::new(), Default::default())
-                        .await,
+                    topology::TopologyPieces::build(
+                        &c,
+                        &diff,
+                        HashMap::new(),
+                        &Default::default(),
+                        Default::default(),
+                    )
+                    .await,
                 ) {
                     (warnings, Ok(_pieces)) => Ok(warnings),
                     (_, Err(errors)) => Err(errors),
@@ -647,6 +657,32 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn unsupported_request_limits() {
+        let err = load(
+            r#"
+            [request_limits.cluster]
+            concurrency = 10
+
+            [sources.in]
+            type = "test_basic"
+
+            [sinks.out]
+            type = "test_basic"
+            inputs = ["in"]
+            request_limits = "cluster"
+            "#,
+            Format::Toml,
+        )
+        .await
+        .unwrap_err();
+
+        assert_eq!(
+            err,
+            vec!["Sink \"out\" does not support the request_limits option"]
+        );
+    }
+
     #[tokio::test]
     #[cfg(unix)]
     async fn conflicting_stdin_and_fd_resources() {
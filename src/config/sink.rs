@@ -16,7 +16,10 @@ use vector_lib::{
 
 use super::{id::Inputs, schema, ComponentKey, ProxyConfig, Resource};
 use crate::extra_context::ExtraContext;
-use crate::sinks::{util::UriSerde, Healthcheck};
+use crate::sinks::{
+    util::{service::RequestLimitsGroup, UriSerde},
+    Healthcheck,
+};
 
 pub type BoxedSink = Box<dyn SinkConfig>;
 
@@ -74,6 +77,15 @@ where
     #[serde(default, skip_serializing_if = "vector_lib::serde::is_default")]
     proxy: ProxyConfig,
 
+    /// The name of the request limits group to limit the requests of this sink with.
+    ///
+    /// The requests of all of the sinks that reference the same group count against the
+    /// concurrency and rate limits configured for it in the top-level `request_limits` section.
+    #[configurable(metadata(docs::advanced))]
+    #[configurable(metadata(docs::examples = "elasticsearch_cluster"))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    request_limits: Option<String>,
+
     #[serde(flatten)]
     #[configurable(metadata(docs::hidden))]
     pub inner: BoxedSink,
@@ -95,6 +107,7 @@ where
             healthcheck_uri: None,
             inner: inner.into(),
             proxy: Default::default(),
+            request_limits: None,
         }
     }
 
@@ -131,6 +144,10 @@ where
         &self.proxy
     }
 
+    pub fn request_limits(&self) -> Option<&str> {
+        self.request_limits.as_deref()
+    }
+
     pub(super) fn map_inputs<U>(self, f: impl Fn(&T) -> U) -> SinkOuter<U>
     where
         U: Configurable + Serialize,
@@ -151,6 +168,7 @@ where
             healthcheck: self.healthcheck,
             healthcheck_uri: self.healthcheck_uri,
             proxy: self.proxy,
+            request_limits: self.request_limits,
         }
     }
 }
@@ -226,6 +244,14 @@ pub trait SinkConfig: DynClone + NamedComponent + core::fmt::Debug + Send + Sync
 
     /// Gets the acknowledgements configuration for this sink.
     fn acknowledgements(&self) -> &AcknowledgementsConfig;
+
+    /// Whether this sink limits its requests with the request limits group it references.
+    ///
+    /// Sinks which support it apply the group passed in `SinkContext::request_limits` to their
+    /// requests, and the `request_limits` option is rejected on the others.
+    fn supports_request_limits(&self) -> bool {
+        false
+    }
 }
 
 dyn_clone::clone_trait_object!(SinkConfig);
@@ -242,6 +268,9 @@ pub struct SinkContext {
     /// Extra context data provided by the running app and shared across all components. This can be
     /// used to pass shared settings or other data from outside the components.
     pub extra_context: ExtraContext,
+
+    /// The request limits group referenced by the sink, shared with the other sinks referencing it.
+    pub request_limits: Option<RequestLimitsGroup>,
 }
 
 impl Default for SinkContext {
@@ -254,6 +283,7 @@ impl Default for SinkContext {
             app_name: crate::get_app_name().to_string(),
             app_name_slug: crate::get_slugified_app_name(),
             extra_context: Default::default(),
+            request_limits: None,
         }
     }
 }
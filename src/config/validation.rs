@@ -149,6 +149,37 @@ pub fn check_resources(config: &ConfigBuilder) -> Result<(), Vec<String>> {
     }
 }
 
+/// Check that the sinks only reference request limits groups that are configured, and only when
+/// they support them.
+pub fn check_request_limits(config: &ConfigBuilder) -> Result<(), Vec<String>> {
+    let errors = config
+        .sinks
+        .iter()
+        .filter_map(|(key, sink)| {
+            let name = sink.request_limits()?;
+            if !sink.inner.supports_request_limits() {
+                Some(format!(
+                    "Sink \"{}\" does not support the request_limits option",
+                    key
+                ))
+            } else if !config.request_limits.contains_key(name) {
+                Some(format!(
+                    "Sink \"{}\" references undefined request_limits group \"{}\"",
+                    key, name
+                ))
+            } else {
+                None
+            }
+        })
+        .collect::<Vec<_>>();
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
 /// To avoid collisions between `output` metric tags, check that a component
 /// does not have a named output with the name [`DEFAULT_OUTPUT`]
 pub fn check_outputs(config: &ConfigBuilder) -> Result<(), Vec<String>> {
@@ -4,8 +4,9 @@
 //!
 //! [maxmind]: https://dev.maxmind.com/geoip/geoip2/downloadable
 //! [geolite]: https://dev.maxmind.com/geoip/geoip2/geolite2/#Download_Access
-use std::{collections::BTreeMap, fs, net::IpAddr, sync::Arc, time::SystemTime};
+use std::{collections::BTreeMap, fs, net::IpAddr, path::Path, sync::Arc, time::SystemTime};
 
+use arc_swap::ArcSwap;
 use maxminddb::{
     geoip2::{City, ConnectionType, Isp},
     MaxMindDBError, Reader,
@@ -17,6 +18,11 @@ use vrl::value::{ObjectMap, Value};
 
 use crate::config::{EnrichmentTableConfig, GenerateConfig};
 
+mod update;
+
+pub use update::AutoUpdateConfig;
+use update::Updater;
+
 // MaxMind GeoIP database files have a type field we can use to recognize specific
 // products. If it is an unknown type, an error will be returned.
 #[derive(Copy, Clone, Debug)]
@@ -68,6 +74,9 @@ pub struct GeoipConfig {
     /// [locale_docs]: https://support.maxmind.com/hc/en-us/articles/4414877149467-IP-Geolocation-Data#h_01FRRGRYTGZB29ERDBZCX3MR8Q
     #[serde(default = "default_locale")]
     pub locale: String,
+
+    #[configurable(derived)]
+    pub auto_update: Option<AutoUpdateConfig>,
 }
 
 fn default_locale() -> String {
@@ -87,6 +96,7 @@ impl GenerateConfig for GeoipConfig {
         toml::Value::try_from(Self {
             path: "/path/to/GeoLite2-City.mmdb".to_string(),
             locale: default_locale(),
+            auto_update: None,
         })
         .unwrap()
     }
@@ -95,25 +105,42 @@ impl GenerateConfig for GeoipConfig {
 impl EnrichmentTableConfig for GeoipConfig {
     async fn build(
         &self,
-        _: &crate::config::GlobalOptions,
+        globals: &crate::config::GlobalOptions,
     ) -> crate::Result<Box<dyn Table + Send + Sync>> {
-        Ok(Box::new(Geoip::new(self.clone())?))
+        let updater = self
+            .auto_update
+            .as_ref()
+            .map(|auto_update| Updater::new(auto_update.clone(), &self.path, &globals.proxy))
+            .transpose()?;
+
+        // Without a database to start with, the first one is downloaded before the table is used.
+        if let Some(updater) = &updater {
+            if !Path::new(&self.path).exists() {
+                updater.update().await?;
+            }
+        }
+
+        let geoip = Geoip::new(self.clone())?;
+        if let Some(updater) = updater {
+            tokio::spawn(updater.run(Arc::downgrade(&geoip.database)));
+        }
+        Ok(Box::new(geoip))
     }
 }
 
-#[derive(Clone)]
-/// A struct that implements [vector_lib::enrichment::Table] to handle loading enrichment data from a GeoIP database.
-pub struct Geoip {
-    config: GeoipConfig,
-    dbreader: Arc<maxminddb::Reader<Vec<u8>>>,
-    dbkind: DatabaseKind,
+/// A GeoIP database loaded from a file.
+struct Database {
+    reader: Reader<Vec<u8>>,
+    kind: DatabaseKind,
     last_modified: SystemTime,
 }
 
-impl Geoip {
-    /// Creates a new GeoIP struct from the provided config.
-    pub fn new(config: GeoipConfig) -> crate::Result<Self> {
-        let dbreader = Arc::new(Reader::open_readfile(config.path.clone())?);
+impl Database {
+    /// Loads a database from a file, checking that it is supported.
+    fn open(path: impl AsRef<Path>) -> crate::Result<Self> {
+        let path = path.as_ref();
+        let last_modified = fs::metadata(path)?.modified()?;
+        let dbreader = Reader::open_readfile(path)?;
         let dbkind =
             DatabaseKind::try_from(dbreader.metadata.database_type.as_str()).map_err(|_| {
                 format!(
@@ -131,15 +158,33 @@ impl Geoip {
         };
 
         match result {
-            Ok(_) | Err(MaxMindDBError::AddressNotFoundError(_)) => Ok(Geoip {
-                last_modified: fs::metadata(&config.path)?.modified()?,
-                dbreader,
-                dbkind,
-                config,
+            Ok(_) | Err(MaxMindDBError::AddressNotFoundError(_)) => Ok(Self {
+                reader: dbreader,
+                kind: dbkind,
+                last_modified,
             }),
             Err(error) => Err(error.into()),
         }
     }
+}
+
+#[derive(Clone)]
+/// A struct that implements [vector_lib::enrichment::Table] to handle loading enrichment data from a GeoIP database.
+pub struct Geoip {
+    config: GeoipConfig,
+    /// The database, replaced when a new version is installed by the updater.
+    database: Arc<ArcSwap<Database>>,
+}
+
+impl Geoip {
+    /// Creates a new GeoIP struct from the provided config.
+    pub fn new(config: GeoipConfig) -> crate::Result<Self> {
+        let database = Database::open(&config.path)?;
+        Ok(Geoip {
+            config,
+            database: Arc::new(ArcSwap::from_pointee(database)),
+        })
+    }
 
     fn lookup(&self, ip: IpAddr, select: Option<&[String]>) -> Option<ObjectMap> {
         let mut map = ObjectMap::new();
@@ -158,9 +203,10 @@ impl Geoip {
             };
         }
 
-        match self.dbkind {
+        let database = self.database.load();
+        match database.kind {
             DatabaseKind::Asn | DatabaseKind::Isp => {
-                let data = self.dbreader.lookup::<Isp>(ip).ok()?;
+                let data = database.reader.lookup::<Isp>(ip).ok()?;
 
                 add_field!("autonomous_system_number", data.autonomous_system_number);
                 add_field!(
@@ -171,7 +217,7 @@ impl Geoip {
                 add_field!("organization", data.organization);
             }
             DatabaseKind::City => {
-                let data = self.dbreader.lookup::<City>(ip).ok()?;
+                let data = database.reader.lookup::<City>(ip).ok()?;
 
                 add_field!(
                     "city_name",
@@ -221,7 +267,7 @@ impl Geoip {
                 add_field!("postal_code", data.postal.and_then(|p| p.code));
             }
             DatabaseKind::ConnectionType => {
-                let data = self.dbreader.lookup::<ConnectionType>(ip).ok()?;
+                let data = database.reader.lookup::<ConnectionType>(ip).ok()?;
 
                 add_field!("connection_type", data.connection_type);
             }
@@ -311,7 +357,7 @@ impl Table for Geoip {
     fn needs_reload(&self) -> bool {
         matches!(fs::metadata(&self.config.path)
             .and_then(|metadata| metadata.modified()),
-            Ok(modified) if modified > self.last_modified)
+            Ok(modified) if modified > self.database.load().last_modified)
     }
 }
 
@@ -457,6 +503,7 @@ mod tests {
         let result = Geoip::new(GeoipConfig {
             path: "tests/data/custom-type.mmdb".to_string(),
             locale: default_locale(),
+            auto_update: None,
         });
 
         assert!(result.is_err());
@@ -470,6 +517,7 @@ mod tests {
         Geoip::new(GeoipConfig {
             path: database.to_string(),
             locale: default_locale(),
+            auto_update: None,
         })
         .unwrap()
         .find_table_rows(
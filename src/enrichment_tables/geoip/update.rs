@@ -0,0 +1,384 @@
+//! Automatic updates of the GeoIP databases from MaxMind.
+use std::{
+    fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+    sync::{Arc, Weak},
+    time::Duration,
+};
+
+use arc_swap::ArcSwap;
+use bytes::Bytes;
+use flate2::read::GzDecoder;
+use http::{Request, StatusCode};
+use hyper::Body;
+use sha2::{Digest, Sha256};
+use snafu::{ResultExt, Snafu};
+use vector_lib::configurable::configurable_component;
+use vector_lib::sensitive_string::SensitiveString;
+
+use super::Database;
+use crate::{
+    config::ProxyConfig,
+    http::{HttpClient, HttpError},
+    internal_events::{GeoipDatabaseUpdateError, GeoipDatabaseUpdated},
+    tls::TlsSettings,
+};
+
+/// Configuration for the automatic updates of the database from [MaxMind][maxmind].
+///
+/// The database is downloaded to `path` when it doesn't exist, and updated when MaxMind publishes a
+/// new version of it. The new versions are checked against their published checksum, written next
+/// to `path`, and then moved to `path` and loaded without interrupting the lookups.
+///
+/// [maxmind]: https://www.maxmind.com/
+#[configurable_component]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct AutoUpdateConfig {
+    /// The [license key][license_key] of the MaxMind account used to download the database.
+    ///
+    /// [license_key]: https://support.maxmind.com/hc/en-us/articles/4407111582235-Generate-a-License-Key
+    #[configurable(metadata(docs::examples = "${MAXMIND_LICENSE_KEY}"))]
+    pub license_key: SensitiveString,
+
+    /// The edition of the database to download.
+    #[configurable(metadata(docs::examples = "GeoLite2-City"))]
+    #[configurable(metadata(docs::examples = "GeoIP2-ISP"))]
+    pub edition_id: String,
+
+    /// The interval between the checks for new versions of the database, in seconds.
+    ///
+    /// MaxMind updates most databases weekly. The database file is also reloaded at this interval
+    /// if it is replaced by another process.
+    #[serde(default = "default_interval_secs")]
+    #[configurable(metadata(docs::human_name = "Update Interval"))]
+    pub interval_secs: u64,
+
+    /// The endpoint from which the database is downloaded.
+    #[serde(default = "default_endpoint")]
+    #[configurable(metadata(docs::examples = "https://download.maxmind.com/app/geoip_download"))]
+    pub endpoint: String,
+}
+
+const fn default_interval_secs() -> u64 {
+    24 * 60 * 60
+}
+
+fn default_endpoint() -> String {
+    "https://download.maxmind.com/app/geoip_download".to_string()
+}
+
+#[derive(Debug, Snafu)]
+pub enum UpdateError {
+    #[snafu(display("Failed to download the {}: {}", what, source))]
+    Request {
+        what: &'static str,
+        source: HttpError,
+    },
+    #[snafu(display("Failed to read the {}: {}", what, source))]
+    Response {
+        what: &'static str,
+        source: hyper::Error,
+    },
+    #[snafu(display("Unexpected status {} downloading the {}", status, what))]
+    Status {
+        what: &'static str,
+        status: StatusCode,
+    },
+    #[snafu(display("Invalid checksum: {:?}", checksum))]
+    InvalidChecksum { checksum: String },
+    #[snafu(display(
+        "Checksum mismatch of the downloaded archive, expected {} but found {}",
+        expected,
+        actual
+    ))]
+    ChecksumMismatch { expected: String, actual: String },
+    #[snafu(display("Invalid archive: {}", source))]
+    InvalidArchive { source: io::Error },
+    #[snafu(display("The archive does not contain a database"))]
+    MissingDatabase,
+    #[snafu(display("Invalid database: {}", source))]
+    InvalidDatabase { source: crate::Error },
+    #[snafu(display("Failed to install the database to {:?}: {}", path, source))]
+    Install { path: PathBuf, source: io::Error },
+}
+
+/// Downloads the new versions of a database, and loads them into its table.
+pub struct Updater {
+    config: AutoUpdateConfig,
+    path: PathBuf,
+    client: HttpClient,
+}
+
+impl Updater {
+    pub fn new(
+        config: AutoUpdateConfig,
+        path: impl Into<PathBuf>,
+        proxy: &ProxyConfig,
+    ) -> crate::Result<Self> {
+        let proxy = ProxyConfig::merge_with_env(proxy, &ProxyConfig::default());
+        Ok(Self {
+            config,
+            path: path.into(),
+            client: HttpClient::new(TlsSettings::from_options(&None)?, &proxy)?,
+        })
+    }
+
+    /// The file storing the checksum of the archive of the installed database.
+    fn checksum_path(&self) -> PathBuf {
+        sibling(&self.path, "sha256")
+    }
+
+    async fn get(&self, suffix: &str, what: &'static str) -> Result<Bytes, UpdateError> {
+        let query = url::form_urlencoded::Serializer::new(String::new())
+            .append_pair("edition_id", &self.config.edition_id)
+            .append_pair("license_key", self.config.license_key.inner())
+            .append_pair("suffix", suffix)
+            .finish();
+        let request = Request::get(format!("{}?{}", self.config.endpoint, query))
+            .body(Body::empty())
+            .expect("valid request");
+
+        let response = self
+            .client
+            .send(request)
+            .await
+            .context(RequestSnafu { what })?;
+        match response.status() {
+            StatusCode::OK => hyper::body::to_bytes(response.into_body())
+                .await
+                .context(ResponseSnafu { what }),
+            status => Err(UpdateError::Status { what, status }),
+        }
+    }
+
+    /// Downloads and installs the database if a new version of it is available, returning whether
+    /// it was installed.
+    pub async fn update(&self) -> Result<bool, UpdateError> {
+        let checksum = self.get("tar.gz.sha256", "checksum").await?;
+        let checksum = parse_checksum(&checksum)?;
+        let installed = fs::read_to_string(self.checksum_path()).ok();
+        if self.path.exists() && installed.as_deref().map(str::trim) == Some(checksum.as_str()) {
+            return Ok(false);
+        }
+
+        let archive = self.get("tar.gz", "database").await?;
+        let actual = hex::encode(Sha256::digest(&archive));
+        if actual != checksum {
+            return Err(UpdateError::ChecksumMismatch {
+                expected: checksum,
+                actual,
+            });
+        }
+        let database = extract_database(&archive)?;
+
+        // The database is written next to its final path, so that it can be checked, and then
+        // atomically moved in place.
+        let staging = sibling(&self.path, "download");
+        let install =
+            |path: &Path, contents: &[u8]| fs::write(path, contents).context(InstallSnafu { path });
+        install(&staging, &database)?;
+        if let Err(error) = Database::open(&staging) {
+            _ = fs::remove_file(&staging);
+            return Err(UpdateError::InvalidDatabase { source: error });
+        }
+        fs::rename(&staging, &self.path).context(InstallSnafu { path: &self.path })?;
+        install(&self.checksum_path(), checksum.as_bytes())?;
+
+        Ok(true)
+    }
+
+    /// Reloads the database if its file changed since it was loaded.
+    fn reload(&self, database: &ArcSwap<Database>) -> crate::Result<bool> {
+        let modified = fs::metadata(&self.path)?.modified()?;
+        if modified == database.load().last_modified {
+            return Ok(false);
+        }
+
+        database.store(Arc::new(Database::open(&self.path)?));
+        Ok(true)
+    }
+
+    /// Periodically updates the database, until its table is dropped.
+    pub async fn run(self, database: Weak<ArcSwap<Database>>) {
+        let mut interval = tokio::time::interval(Duration::from_secs(self.config.interval_secs));
+        loop {
+            interval.tick().await;
+            let Some(database) = database.upgrade() else {
+                break;
+            };
+
+            if let Err(error) = self.update().await {
+                emit!(GeoipDatabaseUpdateError {
+                    error: error.to_string(),
+                    path: &self.path,
+                });
+            }
+            match self.reload(&database) {
+                Ok(true) => emit!(GeoipDatabaseUpdated { path: &self.path }),
+                Ok(false) => {}
+                Err(error) => emit!(GeoipDatabaseUpdateError {
+                    error: error.to_string(),
+                    path: &self.path,
+                }),
+            }
+        }
+    }
+}
+
+/// Returns the path of a file next to `path`, with an additional extension.
+fn sibling(path: &Path, extension: &str) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".");
+    name.push(extension);
+    path.with_file_name(name)
+}
+
+/// Parses a checksum published by MaxMind, in the format of `sha256sum`.
+fn parse_checksum(checksum: &[u8]) -> Result<String, UpdateError> {
+    let checksum = String::from_utf8_lossy(checksum);
+    match checksum.split_whitespace().next() {
+        Some(hash) if hash.len() == 64 && hash.bytes().all(|b| b.is_ascii_hexdigit()) => {
+            Ok(hash.to_ascii_lowercase())
+        }
+        _ => Err(UpdateError::InvalidChecksum {
+            checksum: checksum.into_owned(),
+        }),
+    }
+}
+
+/// Extracts the database from an archive in the gzipped tar format distributed by MaxMind.
+fn extract_database(archive: &[u8]) -> Result<Vec<u8>, UpdateError> {
+    const BLOCK: usize = 512;
+
+    let mut tar = Vec::new();
+    GzDecoder::new(archive)
+        .read_to_end(&mut tar)
+        .context(InvalidArchiveSnafu)?;
+    let invalid = |message: &str| UpdateError::InvalidArchive {
+        source: io::Error::new(io::ErrorKind::InvalidData, message),
+    };
+
+    let mut offset = 0;
+    while let Some(header) = tar.get(offset..offset + BLOCK) {
+        // The archive ends with empty blocks.
+        if header.iter().all(|&b| b == 0) {
+            break;
+        }
+
+        let field = |range: std::ops::Range<usize>| {
+            let field = &header[range];
+            let end = field.iter().position(|&b| b == 0).unwrap_or(field.len());
+            String::from_utf8_lossy(&field[..end]).trim().to_string()
+        };
+        let name = field(0..100);
+        let size = usize::from_str_radix(&field(124..136), 8)
+            .map_err(|_| invalid("invalid size of an entry"))?;
+        let regular_file = matches!(header[156], b'0' | 0);
+
+        let start = offset + BLOCK;
+        let contents = tar
+            .get(start..start + size)
+            .ok_or_else(|| invalid("truncated entry"))?;
+        if regular_file && name.ends_with(".mmdb") {
+            return Ok(contents.to_vec());
+        }
+        offset = start + size.div_ceil(BLOCK) * BLOCK;
+    }
+
+    Err(UpdateError::MissingDatabase)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use flate2::{write::GzEncoder, Compression};
+
+    use super::*;
+
+    fn archive(entries: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut tar = Vec::new();
+        for (name, contents) in entries {
+            let mut header = [0; 512];
+            header[..name.len()].copy_from_slice(name.as_bytes());
+            let size = format!("{:011o}", contents.len());
+            header[124..135].copy_from_slice(size.as_bytes());
+            header[156] = b'0';
+            tar.extend_from_slice(&header);
+            tar.extend_from_slice(contents);
+            tar.resize(tar.len().div_ceil(512) * 512, 0);
+        }
+        tar.extend_from_slice(&[0; 1024]);
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&tar).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    #[test]
+    fn extracts_the_database() {
+        let archive = archive(&[
+            ("GeoLite2-City_20240101/COPYRIGHT.txt", b"Copyright"),
+            ("GeoLite2-City_20240101/GeoLite2-City.mmdb", b"database"),
+        ]);
+        assert_eq!(extract_database(&archive).unwrap(), b"database");
+
+        let archive = self::archive(&[("GeoLite2-City_20240101/LICENSE.txt", b"License")]);
+        assert!(matches!(
+            extract_database(&archive),
+            Err(UpdateError::MissingDatabase)
+        ));
+    }
+
+    #[test]
+    fn parses_checksums() {
+        let checksum = "B6A9C1C1A3B1D6A9D2C1A0E5E8F2F1A3B6A9C1C1A3B1D6A9D2C1A0E5E8F2F1A3";
+        assert_eq!(
+            parse_checksum(format!("{}  GeoLite2-City_20240101.tar.gz\n", checksum).as_bytes())
+                .unwrap(),
+            checksum.to_ascii_lowercase()
+        );
+        assert!(parse_checksum(b"Invalid license key").is_err());
+    }
+
+    #[test]
+    fn reloads_the_database() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("GeoIP2-City.mmdb");
+        fs::copy("tests/data/GeoIP2-City-Test.mmdb", &path).unwrap();
+
+        let updater = Updater::new(
+            AutoUpdateConfig {
+                license_key: "key".to_string().into(),
+                edition_id: "GeoIP2-City".to_string(),
+                interval_secs: default_interval_secs(),
+                endpoint: default_endpoint(),
+            },
+            &path,
+            &ProxyConfig::default(),
+        )
+        .unwrap();
+        let database = ArcSwap::from_pointee(Database::open(&path).unwrap());
+        assert!(!updater.reload(&database).unwrap());
+
+        // The database is replaced by a different one.
+        let staging = sibling(&path, "download");
+        fs::copy("tests/data/GeoIP2-ISP-Test.mmdb", &staging).unwrap();
+        let modified = database.load().last_modified + Duration::from_secs(1);
+        fs::File::options()
+            .write(true)
+            .open(&staging)
+            .unwrap()
+            .set_modified(modified)
+            .unwrap();
+        fs::rename(&staging, &path).unwrap();
+
+        assert!(updater.reload(&database).unwrap());
+        assert!(matches!(
+            database.load().kind,
+            super::super::DatabaseKind::Isp
+        ));
+    }
+}
@@ -40,6 +40,86 @@ mod s3 {
         }
     }
 
+    #[derive(Debug)]
+    pub struct S3ObjectProcessingError<'a> {
+        pub bucket: &'a str,
+        pub key: &'a str,
+        pub error: &'a ProcessingError,
+    }
+
+    impl<'a> InternalEvent for S3ObjectProcessingError<'a> {
+        fn emit(self) {
+            error!(
+                message = "Failed to process S3 object.",
+                bucket = %self.bucket,
+                key = %self.key,
+                error = %self.error,
+                error_code = "failed_processing_s3_object",
+                error_type = error_type::READER_FAILED,
+                stage = error_stage::PROCESSING,
+                internal_log_rate_limit = true,
+            );
+            counter!(
+                "component_errors_total", 1,
+                "error_code" => "failed_processing_s3_object",
+                "error_type" => error_type::READER_FAILED,
+                "stage" => error_stage::PROCESSING,
+            );
+        }
+    }
+
+    #[derive(Debug)]
+    pub struct S3ListObjectsError<'a, E> {
+        pub bucket: &'a str,
+        pub error: &'a E,
+    }
+
+    impl<'a, E: std::fmt::Display> InternalEvent for S3ListObjectsError<'a, E> {
+        fn emit(self) {
+            error!(
+                message = "Failed to list S3 objects.",
+                bucket = %self.bucket,
+                error = %self.error,
+                error_code = "failed_listing_s3_objects",
+                error_type = error_type::REQUEST_FAILED,
+                stage = error_stage::RECEIVING,
+                internal_log_rate_limit = true,
+            );
+            counter!(
+                "component_errors_total", 1,
+                "error_code" => "failed_listing_s3_objects",
+                "error_type" => error_type::REQUEST_FAILED,
+                "stage" => error_stage::RECEIVING,
+            );
+        }
+    }
+
+    #[derive(Debug)]
+    pub struct S3ListCheckpointWriteError<'a> {
+        pub path: &'a std::path::Path,
+        pub error: std::io::Error,
+    }
+
+    impl<'a> InternalEvent for S3ListCheckpointWriteError<'a> {
+        fn emit(self) {
+            error!(
+                message = "Could not write S3 listing checkpoint.",
+                path = ?self.path,
+                error = %self.error,
+                error_code = "failed_writing_s3_list_checkpoint",
+                error_type = error_type::IO_FAILED,
+                stage = error_stage::PROCESSING,
+                internal_log_rate_limit = true,
+            );
+            counter!(
+                "component_errors_total", 1,
+                "error_code" => "failed_writing_s3_list_checkpoint",
+                "error_type" => error_type::IO_FAILED,
+                "stage" => error_stage::PROCESSING,
+            );
+        }
+    }
+
     #[derive(Debug)]
     pub struct SqsMessageDeleteSucceeded {
         pub message_ids: Vec<DeleteMessageBatchResultEntry>,
@@ -0,0 +1,153 @@
+use metrics::counter;
+use vector_lib::internal_event::InternalEvent;
+use vector_lib::internal_event::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct AzureQueueMessageReceiveError<'a, E> {
+    pub error: &'a E,
+}
+
+impl<'a, E: std::fmt::Display> InternalEvent for AzureQueueMessageReceiveError<'a, E> {
+    fn emit(self) {
+        error!(
+            message = "Failed to fetch messages from the storage queue.",
+            error = %self.error,
+            error_code = "failed_fetching_queue_messages",
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "failed_fetching_queue_messages",
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct AzureQueueMessageProcessingError<'a, E> {
+    pub message_id: &'a str,
+    pub error: &'a E,
+}
+
+impl<'a, E: std::fmt::Display> InternalEvent for AzureQueueMessageProcessingError<'a, E> {
+    fn emit(self) {
+        error!(
+            message = "Failed to process storage queue message.",
+            message_id = %self.message_id,
+            error = %self.error,
+            error_code = "failed_processing_queue_message",
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "failed_processing_queue_message",
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct AzureQueueMessageDeleteError<'a, E> {
+    pub message_id: &'a str,
+    pub error: &'a E,
+}
+
+impl<'a, E: std::fmt::Display> InternalEvent for AzureQueueMessageDeleteError<'a, E> {
+    fn emit(self) {
+        error!(
+            message = "Failed to delete storage queue message.",
+            message_id = %self.message_id,
+            error = %self.error,
+            error_code = "failed_deleting_queue_message",
+            error_type = error_type::ACKNOWLEDGMENT_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "failed_deleting_queue_message",
+            "error_type" => error_type::ACKNOWLEDGMENT_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct AzureQueueMessageUpdateError<'a, E> {
+    pub message_id: &'a str,
+    pub error: &'a E,
+}
+
+impl<'a, E: std::fmt::Display> InternalEvent for AzureQueueMessageUpdateError<'a, E> {
+    fn emit(self) {
+        error!(
+            message = "Failed to extend the visibility timeout of storage queue message.",
+            message_id = %self.message_id,
+            error = %self.error,
+            error_code = "failed_updating_queue_message",
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "failed_updating_queue_message",
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct AzureAppendBlobReadError<'a, E> {
+    pub container: &'a str,
+    pub blob: &'a str,
+    pub error: &'a E,
+}
+
+impl<'a, E: std::fmt::Display> InternalEvent for AzureAppendBlobReadError<'a, E> {
+    fn emit(self) {
+        error!(
+            message = "Failed to read data appended to blob.",
+            container = %self.container,
+            blob = %self.blob,
+            error = %self.error,
+            error_code = "failed_reading_append_blob",
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "failed_reading_append_blob",
+            "error_type" => error_type::READER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct AzureBlobEventIgnored<'a> {
+    pub subject: &'a str,
+    pub event_type: &'a str,
+}
+
+impl<'a> InternalEvent for AzureBlobEventIgnored<'a> {
+    fn emit(self) {
+        debug!(
+            message = "Ignored blob event that was not a blob created event.",
+            subject = %self.subject,
+            event_type = %self.event_type,
+        );
+        counter!(
+            "azure_blob_event_ignored_total", 1,
+            "ignore_type" => "invalid_event_type",
+        );
+    }
+}
@@ -0,0 +1,57 @@
+use std::time::Duration;
+
+use metrics::{counter, register_histogram, Histogram};
+use vector_lib::event::EventStatus;
+use vector_lib::internal_event::InternalEvent;
+use vector_lib::internal_event::{error_stage, error_type, ComponentEventsDropped, UNINTENTIONAL};
+
+registered_event! {
+    BlackholeBatchSize => {
+        batch_size: Histogram = register_histogram!("blackhole_batch_size_events"),
+    }
+
+    fn emit(&self, count: usize) {
+        self.batch_size.record(count as f64);
+    }
+}
+
+registered_event! {
+    BlackholeEventLatency => {
+        latency: Histogram = register_histogram!("blackhole_event_latency_seconds"),
+    }
+
+    fn emit(&self, latency: Duration) {
+        self.latency.record(latency);
+    }
+}
+
+#[derive(Debug)]
+pub struct BlackholeInjectedError {
+    pub count: usize,
+    pub status: EventStatus,
+}
+
+impl InternalEvent for BlackholeInjectedError {
+    fn emit(self) {
+        let reason = "Injected error.";
+        error!(
+            message = reason,
+            status = ?self.status,
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::SENDING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+
+        if self.status == EventStatus::Rejected {
+            emit!(ComponentEventsDropped::<UNINTENTIONAL> {
+                count: self.count,
+                reason,
+            });
+        }
+    }
+}
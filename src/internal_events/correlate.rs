@@ -0,0 +1,64 @@
+use metrics::counter;
+use vector_lib::internal_event::InternalEvent;
+use vector_lib::internal_event::{
+    error_stage, error_type, ComponentEventsDropped, INTENTIONAL, UNINTENTIONAL,
+};
+
+#[derive(Debug)]
+pub struct CorrelateUnmatchedEventDropped;
+
+impl InternalEvent for CorrelateUnmatchedEventDropped {
+    fn emit(self) {
+        let reason = "Event not joined within the window.";
+
+        debug!(message = reason, internal_log_rate_limit = true);
+        emit!(ComponentEventsDropped::<INTENTIONAL> { count: 1, reason });
+    }
+}
+
+#[derive(Debug)]
+pub struct CorrelateSpillWriteError<E> {
+    pub error: E,
+}
+
+impl<E: std::fmt::Display> InternalEvent for CorrelateSpillWriteError<E> {
+    fn emit(self) {
+        error!(
+            message = "Failed to spill event to disk, keeping it in memory.",
+            error = %self.error,
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::WRITER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct CorrelateSpillReadError<E> {
+    pub error: E,
+}
+
+impl<E: std::fmt::Display> InternalEvent for CorrelateSpillReadError<E> {
+    fn emit(self) {
+        let reason = "Failed to read spilled event from disk.";
+
+        error!(
+            message = reason,
+            error = %self.error,
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::READER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason });
+    }
+}
@@ -1,4 +1,7 @@
-use vector_lib::internal_event::{ComponentEventsDropped, InternalEvent, INTENTIONAL};
+use metrics::counter;
+use vector_lib::internal_event::{
+    error_stage, error_type, ComponentEventsDropped, InternalEvent, INTENTIONAL,
+};
 
 #[derive(Debug)]
 pub struct DedupeEventsDropped {
@@ -13,3 +16,25 @@ impl InternalEvent for DedupeEventsDropped {
         });
     }
 }
+
+#[derive(Debug)]
+pub struct DedupeCacheError {
+    pub error: sled::Error,
+}
+
+impl InternalEvent for DedupeCacheError {
+    fn emit(self) {
+        error!(
+            message = "Failed to use the disk cache, event passed through.",
+            error = %self.error,
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::WRITER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
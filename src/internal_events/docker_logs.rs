@@ -104,6 +104,28 @@ impl InternalEvent for DockerLogsCommunicationError<'_> {
     }
 }
 
+#[derive(Debug)]
+pub struct DockerLogsContainerdListError {
+    pub error: tonic::Status,
+}
+
+impl InternalEvent for DockerLogsContainerdListError {
+    fn emit(self) {
+        error!(
+            message = "Failed to list the containers of containerd.",
+            error = %self.error,
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
 #[derive(Debug)]
 pub struct DockerLogsContainerMetadataFetchError<'a> {
     pub error: Error,
@@ -163,7 +185,7 @@ pub struct DockerLogsLoggingDriverUnsupportedError<'a> {
 impl InternalEvent for DockerLogsLoggingDriverUnsupportedError<'_> {
     fn emit(self) {
         error!(
-            message = "Docker engine is not using either the `jsonfile` or `journald` logging driver. Please enable one of these logging drivers to get logs from the Docker daemon.",
+            message = "Container engine is not using a logging driver that supports reading logs. Please enable the `json-file`, `local` or `journald` logging driver for Docker, or the `k8s-file` or `journald` log driver for Podman.",
             error = ?self.error,
             error_type = error_type::CONFIGURATION_FAILED,
             stage = error_stage::RECEIVING,
@@ -0,0 +1,68 @@
+use std::fmt::Display;
+
+use metrics::counter;
+use vector_lib::internal_event::{
+    error_stage, error_type, ComponentEventsDropped, InternalEvent, UNINTENTIONAL,
+};
+
+#[derive(Debug)]
+pub struct EbpfReadError<E> {
+    pub error: E,
+    pub map: &'static str,
+}
+
+impl<E: Display> InternalEvent for EbpfReadError<E> {
+    fn emit(self) {
+        error!(
+            message = "Failed to read eBPF map.",
+            map = %self.map,
+            error = %self.error,
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::READER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct EbpfMalformedEvent {
+    pub size: usize,
+}
+
+impl InternalEvent for EbpfMalformedEvent {
+    fn emit(self) {
+        let reason = "Received malformed event from the eBPF programs.";
+        error!(
+            message = reason,
+            size = self.size,
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason });
+    }
+}
+
+#[derive(Debug)]
+pub struct EbpfEventsLost {
+    pub count: u64,
+}
+
+impl InternalEvent for EbpfEventsLost {
+    fn emit(self) {
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> {
+            count: self.count as usize,
+            reason: "The eBPF ring buffer was full.",
+        });
+    }
+}
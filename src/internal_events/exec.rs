@@ -90,6 +90,58 @@ impl InternalEvent for ExecTimeoutError<'_> {
     }
 }
 
+#[derive(Debug)]
+pub struct ExecRuntimeLimitExceededError<'a> {
+    pub command: &'a str,
+    pub maximum_runtime_secs: u64,
+}
+
+impl InternalEvent for ExecRuntimeLimitExceededError<'_> {
+    fn emit(self) {
+        error!(
+            message = "Command exceeded its maximum runtime, killing it.",
+            command = %self.command,
+            maximum_runtime_secs = %self.maximum_runtime_secs,
+            error_type = error_type::TIMED_OUT,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "command" => self.command.to_owned(),
+            "error_type" => error_type::TIMED_OUT,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct ExecOutputLimitExceededError<'a> {
+    pub command: &'a str,
+    pub maximum_output_size_bytes: usize,
+}
+
+impl InternalEvent for ExecOutputLimitExceededError<'_> {
+    fn emit(self) {
+        error!(
+            message = "Command exceeded its maximum output size, killing it.",
+            command = %self.command,
+            maximum_output_size_bytes = %self.maximum_output_size_bytes,
+            error_code = "maximum_output_size_exceeded",
+            error_type = error_type::COMMAND_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "command" => self.command.to_owned(),
+            "error_code" => "maximum_output_size_exceeded",
+            "error_type" => error_type::COMMAND_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
 #[derive(Debug)]
 pub struct ExecCommandExecuted<'a> {
     pub command: &'a str,
@@ -0,0 +1,119 @@
+use std::time::Duration;
+
+use metrics::counter;
+use vector_lib::internal_event::InternalEvent;
+use vector_lib::internal_event::{error_stage, error_type, ComponentEventsDropped, UNINTENTIONAL};
+
+use super::prelude::io_error_code;
+
+#[derive(Debug)]
+pub struct ExecTransformSpawnError<'a> {
+    pub command: &'a str,
+    pub error: std::io::Error,
+}
+
+impl InternalEvent for ExecTransformSpawnError<'_> {
+    fn emit(self) {
+        error!(
+            message = "Unable to start the command.",
+            command = %self.command,
+            error = ?self.error,
+            error_type = error_type::COMMAND_FAILED,
+            error_code = %io_error_code(&self.error),
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::COMMAND_FAILED,
+            "error_code" => io_error_code(&self.error),
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct ExecTransformExitedError<'a> {
+    pub command: &'a str,
+    pub count: usize,
+}
+
+impl InternalEvent for ExecTransformExitedError<'_> {
+    fn emit(self) {
+        let reason = "The command exited before writing the output of the events.";
+        error!(
+            message = reason,
+            command = %self.command,
+            error_type = error_type::COMMAND_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::COMMAND_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+        if self.count > 0 {
+            emit!(ComponentEventsDropped::<UNINTENTIONAL> {
+                count: self.count,
+                reason,
+            });
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ExecTransformTimeoutError<'a> {
+    pub command: &'a str,
+    pub timeout: Duration,
+    pub count: usize,
+}
+
+impl InternalEvent for ExecTransformTimeoutError<'_> {
+    fn emit(self) {
+        let reason = "The command did not write the output of an event in time, killing it.";
+        error!(
+            message = reason,
+            command = %self.command,
+            timeout_secs = self.timeout.as_secs_f64(),
+            error_type = error_type::TIMED_OUT,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::TIMED_OUT,
+            "stage" => error_stage::PROCESSING,
+        );
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> {
+            count: self.count,
+            reason,
+        });
+    }
+}
+
+#[derive(Debug)]
+pub struct ExecTransformInvalidOutputError<'a> {
+    pub command: &'a str,
+    pub error: String,
+}
+
+impl InternalEvent for ExecTransformInvalidOutputError<'_> {
+    fn emit(self) {
+        let reason = "The command wrote an invalid output for an event.";
+        error!(
+            message = reason,
+            command = %self.command,
+            error = %self.error,
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason });
+    }
+}
@@ -0,0 +1,13 @@
+use vector_lib::internal_event::{ComponentEventsDropped, InternalEvent, INTENTIONAL};
+
+#[derive(Debug)]
+pub struct ExplodeEmptyArray;
+
+impl InternalEvent for ExplodeEmptyArray {
+    fn emit(self) {
+        emit!(ComponentEventsDropped::<INTENTIONAL> {
+            count: 1,
+            reason: "Event contained an empty array to explode.",
+        });
+    }
+}
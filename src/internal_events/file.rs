@@ -5,7 +5,11 @@ use vector_lib::{
     internal_event::{ComponentEventsDropped, InternalEvent, UNINTENTIONAL},
 };
 
-#[cfg(any(feature = "sources-file", feature = "sources-kubernetes_logs"))]
+#[cfg(any(
+    feature = "sources-docker_logs",
+    feature = "sources-file",
+    feature = "sources-kubernetes_logs"
+))]
 pub use self::source::*;
 
 use vector_lib::internal_event::{error_stage, error_type};
@@ -100,7 +104,11 @@ impl<'a, P: std::fmt::Debug> InternalEvent for FileIoError<'a, P> {
     }
 }
 
-#[cfg(any(feature = "sources-file", feature = "sources-kubernetes_logs"))]
+#[cfg(any(
+    feature = "sources-docker_logs",
+    feature = "sources-file",
+    feature = "sources-kubernetes_logs"
+))]
 mod source {
     use std::{io::Error, path::Path, time::Duration};
 
@@ -39,3 +39,27 @@ impl<'a> InternalEvent for FluentMessageDecodeError<'a> {
         );
     }
 }
+
+#[derive(Debug)]
+pub struct FluentHandshakeError {
+    pub reason: &'static str,
+}
+
+impl InternalEvent for FluentHandshakeError {
+    fn emit(self) {
+        error!(
+            message = "Fluent client failed to authenticate, closing connection.",
+            reason = %self.reason,
+            error_code = "authentication_failed",
+            error_type = error_type::CONNECTION_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "authentication_failed",
+            "error_type" => error_type::CONNECTION_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
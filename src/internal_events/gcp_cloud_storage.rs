@@ -0,0 +1,127 @@
+use metrics::counter;
+use vector_lib::internal_event::InternalEvent;
+use vector_lib::internal_event::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct GcsNotificationReceiveError<'a, E> {
+    pub error: &'a E,
+}
+
+impl<'a, E: std::fmt::Display> InternalEvent for GcsNotificationReceiveError<'a, E> {
+    fn emit(self) {
+        error!(
+            message = "Failed to pull object notifications from Pub/Sub.",
+            error = %self.error,
+            error_code = "failed_pulling_notifications",
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "failed_pulling_notifications",
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct GcsNotificationProcessingError<'a, E> {
+    pub message_id: &'a str,
+    pub error: &'a E,
+}
+
+impl<'a, E: std::fmt::Display> InternalEvent for GcsNotificationProcessingError<'a, E> {
+    fn emit(self) {
+        error!(
+            message = "Failed to process object notification.",
+            message_id = %self.message_id,
+            error = %self.error,
+            error_code = "failed_processing_notification",
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "failed_processing_notification",
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct GcsNotificationAcknowledgeError<'a, E> {
+    pub count: usize,
+    pub error: &'a E,
+}
+
+impl<'a, E: std::fmt::Display> InternalEvent for GcsNotificationAcknowledgeError<'a, E> {
+    fn emit(self) {
+        error!(
+            message = "Failed to acknowledge object notifications.",
+            count = %self.count,
+            error = %self.error,
+            error_code = "failed_acknowledging_notifications",
+            error_type = error_type::ACKNOWLEDGMENT_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "failed_acknowledging_notifications",
+            "error_type" => error_type::ACKNOWLEDGMENT_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct GcsNotificationModifyAckDeadlineError<'a, E> {
+    pub count: usize,
+    pub error: &'a E,
+}
+
+impl<'a, E: std::fmt::Display> InternalEvent for GcsNotificationModifyAckDeadlineError<'a, E> {
+    fn emit(self) {
+        error!(
+            message = "Failed to extend the acknowledgement deadline of object notifications.",
+            count = %self.count,
+            error = %self.error,
+            error_code = "failed_extending_ack_deadline",
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "failed_extending_ack_deadline",
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct GcsNotificationIgnored<'a> {
+    pub bucket: &'a str,
+    pub object: &'a str,
+    pub event_type: &'a str,
+}
+
+impl<'a> InternalEvent for GcsNotificationIgnored<'a> {
+    fn emit(self) {
+        debug!(
+            message = "Ignored object notification for an event that was not OBJECT_FINALIZE.",
+            bucket = %self.bucket,
+            object = %self.object,
+            event_type = %self.event_type,
+        );
+        counter!(
+            "gcs_notification_ignored_total", 1,
+            "ignore_type" => "invalid_event_type",
+        );
+    }
+}
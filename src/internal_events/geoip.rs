@@ -0,0 +1,37 @@
+use std::path::Path;
+
+use vector_lib::internal_event::InternalEvent;
+use vector_lib::internal_event::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct GeoipDatabaseUpdated<'a> {
+    pub path: &'a Path,
+}
+
+impl<'a> InternalEvent for GeoipDatabaseUpdated<'a> {
+    fn emit(self) {
+        info!(
+            message = "Loaded a new version of the GeoIP database.",
+            path = ?self.path,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct GeoipDatabaseUpdateError<'a> {
+    pub error: String,
+    pub path: &'a Path,
+}
+
+impl<'a> InternalEvent for GeoipDatabaseUpdateError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to update the GeoIP database, keeping the current version.",
+            error = %self.error,
+            path = ?self.path,
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+    }
+}
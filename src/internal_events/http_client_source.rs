@@ -83,3 +83,52 @@ impl InternalEvent for HttpClientHttpError {
         );
     }
 }
+
+#[derive(Debug)]
+pub struct HttpClientPaginationError {
+    pub error: String,
+    pub url: String,
+}
+
+impl InternalEvent for HttpClientPaginationError {
+    fn emit(self) {
+        error!(
+            message = "Failed to find the next page of the response.",
+            url = %self.url,
+            error = %self.error,
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "url" => self.url,
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct HttpClientWatermarkSetError {
+    pub error: std::io::Error,
+    pub filename: String,
+}
+
+impl InternalEvent for HttpClientWatermarkSetError {
+    fn emit(self) {
+        error!(
+            message = "Could not set watermark checkpoint.",
+            filename = ?self.filename,
+            error = %self.error,
+            error_type = error_type::IO_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::IO_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
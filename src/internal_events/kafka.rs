@@ -180,3 +180,53 @@ impl InternalEvent for KafkaHeaderExtractionError<'_> {
         );
     }
 }
+
+#[derive(Debug)]
+pub struct KafkaTransactionError {
+    pub error: rdkafka::error::KafkaError,
+    pub operation: &'static str,
+}
+
+impl InternalEvent for KafkaTransactionError {
+    fn emit(self) {
+        error!(
+            message = "Failed to complete transaction operation.",
+            error = %self.error,
+            operation = self.operation,
+            error_code = "kafka_transaction",
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::SENDING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "kafka_transaction",
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct KafkaSchemaRegistryError<E> {
+    pub error: E,
+}
+
+impl<E: std::fmt::Display> InternalEvent for KafkaSchemaRegistryError<E> {
+    fn emit(self) {
+        error!(
+            message = "Failed to get the ID of the schema from the Schema Registry.",
+            error = %self.error,
+            error_code = "kafka_schema_registry",
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::SENDING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "kafka_schema_registry",
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+    }
+}
@@ -0,0 +1,26 @@
+use kube::runtime::watcher;
+use metrics::counter;
+use vector_lib::internal_event::InternalEvent;
+use vector_lib::internal_event::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct KubernetesEventsWatchError {
+    pub error: watcher::Error,
+}
+
+impl InternalEvent for KubernetesEventsWatchError {
+    fn emit(self) {
+        error!(
+            message = "Failed to watch Kubernetes events. Retrying.",
+            error = %self.error,
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
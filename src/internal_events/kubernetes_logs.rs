@@ -192,3 +192,57 @@ impl<E: std::fmt::Display> InternalEvent for KubernetesLifecycleError<E> {
         });
     }
 }
+
+const INVALID_POD_ANNOTATION: &str = "invalid_pod_annotation";
+
+#[derive(Debug)]
+pub struct KubernetesLogsInvalidPodAnnotation<'a> {
+    pub annotation: &'static str,
+    pub value: &'a str,
+    pub error: &'a dyn std::error::Error,
+}
+
+impl InternalEvent for KubernetesLogsInvalidPodAnnotation<'_> {
+    fn emit(self) {
+        error!(
+            message = "Ignoring invalid pod annotation.",
+            annotation = %self.annotation,
+            value = %self.value,
+            error = %self.error,
+            error_code = INVALID_POD_ANNOTATION,
+            error_type = error_type::CONFIGURATION_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => INVALID_POD_ANNOTATION,
+            "error_type" => error_type::CONFIGURATION_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct KubernetesLogsParserHintError<'a> {
+    pub parser: &'static str,
+    pub error: &'a dyn std::error::Error,
+}
+
+impl InternalEvent for KubernetesLogsParserHintError<'_> {
+    fn emit(self) {
+        warn!(
+            message = "Failed to parse log message using the pod's parser hint; forwarding it unparsed.",
+            parser = %self.parser,
+            error = %self.error,
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
@@ -26,10 +26,18 @@ mod aws_kinesis;
 mod aws_kinesis_firehose;
 #[cfg(any(feature = "sources-aws_s3", feature = "sources-aws_sqs",))]
 mod aws_sqs;
+#[cfg(feature = "sources-azure_blob")]
+mod azure_blob;
 mod batch;
+#[cfg(feature = "sinks-blackhole")]
+mod blackhole;
 mod codecs;
 mod common;
 mod conditions;
+#[cfg(feature = "transforms-correlate")]
+mod correlate;
+#[cfg(feature = "sources-database")]
+mod database;
 #[cfg(feature = "sources-datadog_agent")]
 mod datadog_agent;
 #[cfg(feature = "sinks-datadog_metrics")]
@@ -44,19 +52,29 @@ mod demo_logs;
 mod dnstap;
 #[cfg(feature = "sources-docker_logs")]
 mod docker_logs;
+#[cfg(feature = "sources-ebpf")]
+mod ebpf;
 mod encoding_transcode;
 #[cfg(feature = "sources-eventstoredb_metrics")]
 mod eventstoredb_metrics;
 #[cfg(feature = "sources-exec")]
 mod exec;
+#[cfg(feature = "transforms-exec")]
+mod exec_transform;
 #[cfg(any(feature = "sources-file-descriptor", feature = "sources-stdin"))]
 mod file_descriptor;
+#[cfg(feature = "transforms-explode")]
+mod explode;
 #[cfg(feature = "transforms-filter")]
 mod filter;
 #[cfg(feature = "sources-fluent")]
 mod fluent;
+#[cfg(feature = "sources-gcp_cloud_storage")]
+mod gcp_cloud_storage;
 #[cfg(feature = "sources-gcp_pubsub")]
 mod gcp_pubsub;
+#[cfg(feature = "enrichment-tables-geoip")]
+mod geoip;
 #[cfg(any(feature = "sources-vector", feature = "sources-opentelemetry"))]
 mod grpc;
 mod heartbeat;
@@ -76,6 +94,8 @@ mod internal_metrics;
 mod journald;
 #[cfg(any(feature = "sources-kafka", feature = "sinks-kafka"))]
 mod kafka;
+#[cfg(feature = "sources-kubernetes_events")]
+mod kubernetes_events;
 #[cfg(feature = "sources-kubernetes_logs")]
 mod kubernetes_logs;
 #[cfg(feature = "transforms-log_to_metric")]
@@ -87,16 +107,26 @@ mod loki;
 mod lua;
 #[cfg(feature = "transforms-metric_to_log")]
 mod metric_to_log;
+#[cfg(feature = "sources-mongodb_changestream")]
+mod mongodb_changestream;
 #[cfg(feature = "sources-mongodb_metrics")]
 mod mongodb_metrics;
+#[cfg(feature = "sources-mysql_cdc")]
+mod mysql_cdc;
 #[cfg(feature = "sinks-mqtt")]
 mod mqtt;
 #[cfg(feature = "sources-nginx_metrics")]
 mod nginx_metrics;
 mod open;
+#[cfg(feature = "sinks-pagerduty")]
+mod pagerduty;
 mod parser;
+#[cfg(feature = "transforms-pii_redact")]
+mod pii_redact;
 #[cfg(feature = "sources-postgresql_metrics")]
 mod postgresql_metrics;
+#[cfg(feature = "sources-probe")]
+mod probe;
 mod process;
 #[cfg(any(
     feature = "sources-prometheus-scrape",
@@ -106,31 +136,52 @@ mod process;
 mod prometheus;
 #[cfg(any(feature = "sinks-pulsar", feature = "sources-pulsar"))]
 mod pulsar;
+#[cfg(feature = "transforms-quota")]
+mod quota;
 #[cfg(feature = "sources-redis")]
 mod redis;
 #[cfg(feature = "transforms-reduce")]
 mod reduce;
 mod remap;
 mod sample;
+#[cfg(feature = "transforms-schema_enforce")]
+mod schema_enforce;
 #[cfg(feature = "sinks-sematext")]
 mod sematext_metrics;
+#[cfg(feature = "transforms-shed")]
+mod shed;
 mod socket;
 #[cfg(any(feature = "sources-splunk_hec", feature = "sinks-splunk_hec"))]
 mod splunk_hec;
+#[cfg(feature = "sources-splunk_s2s")]
+mod splunk_s2s;
 #[cfg(feature = "sinks-statsd")]
 mod statsd_sink;
+#[cfg(feature = "sinks-syslog")]
+mod syslog_sink;
 #[cfg(feature = "transforms-tag_cardinality_limit")]
 mod tag_cardinality_limit;
 mod tcp;
 mod template;
 #[cfg(feature = "transforms-throttle")]
 mod throttle;
+#[cfg(feature = "transforms-tokenize")]
+mod tokenize;
+#[cfg(feature = "transforms-trace_sampler")]
+mod trace_sampler;
 mod udp;
 mod unix;
+#[cfg(feature = "transforms-wasm")]
+mod wasm;
 #[cfg(feature = "sinks-websocket")]
 mod websocket;
+#[cfg(feature = "sinks-websocket_server")]
+mod websocket_server;
+#[cfg(feature = "transforms-window_aggregate")]
+mod window_aggregate;
 
 #[cfg(any(
+    feature = "sources-docker_logs",
     feature = "sources-file",
     feature = "sources-kubernetes_logs",
     feature = "sinks-file",
@@ -138,8 +189,12 @@ mod websocket;
 mod file;
 mod windows;
 
+#[cfg(feature = "sources-mongodb_changestream")]
+pub(crate) use mongodb_changestream::*;
 #[cfg(feature = "sources-mongodb_metrics")]
 pub(crate) use mongodb_metrics::*;
+#[cfg(feature = "sources-mysql_cdc")]
+pub(crate) use mysql_cdc::*;
 
 #[cfg(feature = "transforms-aggregate")]
 pub(crate) use self::aggregate::*;
@@ -166,7 +221,15 @@ pub(crate) use self::aws_kinesis::*;
 pub(crate) use self::aws_kinesis_firehose::*;
 #[cfg(any(feature = "sources-aws_s3", feature = "sources-aws_sqs",))]
 pub(crate) use self::aws_sqs::*;
+#[cfg(feature = "sources-azure_blob")]
+pub(crate) use self::azure_blob::*;
+#[cfg(feature = "sinks-blackhole")]
+pub(crate) use self::blackhole::*;
 pub(crate) use self::codecs::*;
+#[cfg(feature = "transforms-correlate")]
+pub(crate) use self::correlate::*;
+#[cfg(feature = "sources-database")]
+pub(crate) use self::database::*;
 #[cfg(feature = "sources-datadog_agent")]
 pub(crate) use self::datadog_agent::*;
 #[cfg(feature = "sinks-datadog_metrics")]
@@ -181,11 +244,16 @@ pub(crate) use self::demo_logs::*;
 pub(crate) use self::dnstap::*;
 #[cfg(feature = "sources-docker_logs")]
 pub(crate) use self::docker_logs::*;
+#[cfg(feature = "sources-ebpf")]
+pub(crate) use self::ebpf::*;
 #[cfg(feature = "sources-eventstoredb_metrics")]
 pub(crate) use self::eventstoredb_metrics::*;
 #[cfg(feature = "sources-exec")]
 pub(crate) use self::exec::*;
+#[cfg(feature = "transforms-exec")]
+pub(crate) use self::exec_transform::*;
 #[cfg(any(
+    feature = "sources-docker_logs",
     feature = "sources-file",
     feature = "sources-kubernetes_logs",
     feature = "sinks-file",
@@ -193,12 +261,18 @@ pub(crate) use self::exec::*;
 pub(crate) use self::file::*;
 #[cfg(any(feature = "sources-file-descriptor", feature = "sources-stdin"))]
 pub(crate) use self::file_descriptor::*;
+#[cfg(feature = "transforms-explode")]
+pub(crate) use self::explode::*;
 #[cfg(feature = "transforms-filter")]
 pub(crate) use self::filter::*;
 #[cfg(feature = "sources-fluent")]
 pub(crate) use self::fluent::*;
+#[cfg(feature = "sources-gcp_cloud_storage")]
+pub(crate) use self::gcp_cloud_storage::*;
 #[cfg(feature = "sources-gcp_pubsub")]
 pub(crate) use self::gcp_pubsub::*;
+#[cfg(feature = "enrichment-tables-geoip")]
+pub(crate) use self::geoip::*;
 #[cfg(any(feature = "sources-vector", feature = "sources-opentelemetry"))]
 pub(crate) use self::grpc::*;
 #[cfg(feature = "sources-host_metrics")]
@@ -215,6 +289,8 @@ pub(crate) use self::internal_metrics::*;
 pub(crate) use self::journald::*;
 #[cfg(any(feature = "sources-kafka", feature = "sinks-kafka"))]
 pub(crate) use self::kafka::*;
+#[cfg(feature = "sources-kubernetes_events")]
+pub(crate) use self::kubernetes_events::*;
 #[cfg(feature = "sources-kubernetes_logs")]
 pub(crate) use self::kubernetes_logs::*;
 #[cfg(feature = "transforms-log_to_metric")]
@@ -231,10 +307,16 @@ pub(crate) use self::metric_to_log::*;
 pub(crate) use self::mqtt::*;
 #[cfg(feature = "sources-nginx_metrics")]
 pub(crate) use self::nginx_metrics::*;
+#[cfg(feature = "sinks-pagerduty")]
+pub(crate) use self::pagerduty::*;
 #[allow(unused_imports)]
 pub(crate) use self::parser::*;
+#[cfg(feature = "transforms-pii_redact")]
+pub(crate) use self::pii_redact::*;
 #[cfg(feature = "sources-postgresql_metrics")]
 pub(crate) use self::postgresql_metrics::*;
+#[cfg(feature = "sources-probe")]
+pub(crate) use self::probe::*;
 #[cfg(any(
     feature = "sources-prometheus-scrape",
     feature = "sources-prometheus-remote-write",
@@ -243,6 +325,8 @@ pub(crate) use self::postgresql_metrics::*;
 pub(crate) use self::prometheus::*;
 #[cfg(any(feature = "sinks-pulsar", feature = "sources-pulsar"))]
 pub(crate) use self::pulsar::*;
+#[cfg(feature = "transforms-quota")]
+pub(crate) use self::quota::*;
 #[cfg(feature = "sources-redis")]
 pub(crate) use self::redis::*;
 #[cfg(feature = "transforms-reduce")]
@@ -251,20 +335,38 @@ pub(crate) use self::reduce::*;
 pub(crate) use self::remap::*;
 #[cfg(feature = "transforms-impl-sample")]
 pub(crate) use self::sample::*;
+#[cfg(feature = "transforms-schema_enforce")]
+pub(crate) use self::schema_enforce::*;
 #[cfg(feature = "sinks-sematext")]
 pub(crate) use self::sematext_metrics::*;
+#[cfg(feature = "transforms-shed")]
+pub(crate) use self::shed::*;
 #[cfg(any(feature = "sources-splunk_hec", feature = "sinks-splunk_hec"))]
 pub(crate) use self::splunk_hec::*;
+#[cfg(feature = "sources-splunk_s2s")]
+pub(crate) use self::splunk_s2s::*;
 #[cfg(feature = "sinks-statsd")]
 pub(crate) use self::statsd_sink::*;
+#[cfg(feature = "sinks-syslog")]
+pub(crate) use self::syslog_sink::*;
 #[cfg(feature = "transforms-tag_cardinality_limit")]
 pub(crate) use self::tag_cardinality_limit::*;
 #[cfg(feature = "transforms-throttle")]
 pub(crate) use self::throttle::*;
+#[cfg(feature = "transforms-tokenize")]
+pub(crate) use self::tokenize::*;
+#[cfg(feature = "transforms-trace_sampler")]
+pub(crate) use self::trace_sampler::*;
 #[cfg(unix)]
 pub(crate) use self::unix::*;
+#[cfg(feature = "transforms-wasm")]
+pub(crate) use self::wasm::*;
 #[cfg(feature = "sinks-websocket")]
 pub(crate) use self::websocket::*;
+#[cfg(feature = "sinks-websocket_server")]
+pub(crate) use self::websocket_server::*;
+#[cfg(feature = "transforms-window_aggregate")]
+pub(crate) use self::window_aggregate::*;
 #[cfg(windows)]
 pub(crate) use self::windows::*;
 pub use self::{
@@ -0,0 +1,68 @@
+use metrics::counter;
+use vector_lib::internal_event::{error_stage, error_type};
+use vector_lib::internal_event::{ComponentEventsDropped, InternalEvent, UNINTENTIONAL};
+
+#[derive(Debug)]
+pub struct PagerDutyInvalidEventAction<'a> {
+    pub value: &'a str,
+}
+
+impl<'a> InternalEvent for PagerDutyInvalidEventAction<'a> {
+    fn emit(self) {
+        let reason = "Invalid PagerDuty event action.";
+        error!(
+            message = reason,
+            value = self.value,
+            error_type = error_type::CONVERSION_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::CONVERSION_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason });
+    }
+}
+
+#[derive(Debug)]
+pub struct PagerDutyMissingDedupKey {
+    pub event_action: &'static str,
+}
+
+impl InternalEvent for PagerDutyMissingDedupKey {
+    fn emit(self) {
+        let reason = "PagerDuty events that acknowledge or resolve alerts require a dedup key.";
+        error!(
+            message = reason,
+            event_action = self.event_action,
+            error_type = error_type::CONDITION_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::CONDITION_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason });
+    }
+}
+
+#[derive(Debug)]
+pub struct PagerDutyInvalidSeverity<'a> {
+    pub value: &'a str,
+}
+
+impl<'a> InternalEvent for PagerDutyInvalidSeverity<'a> {
+    fn emit(self) {
+        warn!(
+            message = "Invalid PagerDuty severity, using `error` instead.",
+            value = self.value,
+            error_type = error_type::CONVERSION_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+    }
+}
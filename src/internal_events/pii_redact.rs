@@ -0,0 +1,19 @@
+use metrics::counter;
+use vector_lib::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct PiiRedacted {
+    pub detector: &'static str,
+    pub count: usize,
+}
+
+impl InternalEvent for PiiRedacted {
+    fn emit(self) {
+        trace!(
+            message = "Redacted personally identifiable information.",
+            detector = self.detector,
+            count = self.count,
+        );
+        counter!("pii_redactions_total", self.count as u64, "detector" => self.detector);
+    }
+}
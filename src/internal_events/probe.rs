@@ -0,0 +1,27 @@
+use metrics::counter;
+use vector_lib::internal_event::InternalEvent;
+use vector_lib::internal_event::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct ProbeIcmpSocketError<'a> {
+    pub error: std::io::Error,
+    pub host: &'a str,
+}
+
+impl<'a> InternalEvent for ProbeIcmpSocketError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to send ICMP echo requests.",
+            host = %self.host,
+            error = %self.error,
+            error_type = error_type::IO_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::IO_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
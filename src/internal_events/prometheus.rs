@@ -40,6 +40,33 @@ impl<'a> InternalEvent for PrometheusParseError<'a> {
     }
 }
 
+#[cfg(feature = "sources-prometheus-scrape")]
+#[derive(Debug)]
+pub struct PrometheusServiceDiscoveryError<'a> {
+    pub mechanism: &'static str,
+    pub error: &'a dyn std::fmt::Display,
+}
+
+#[cfg(feature = "sources-prometheus-scrape")]
+impl<'a> InternalEvent for PrometheusServiceDiscoveryError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Service discovery failed, keeping previously discovered targets.",
+            mechanism = self.mechanism,
+            error = %self.error,
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::READER_FAILED,
+            "stage" => error_stage::RECEIVING,
+            "mechanism" => self.mechanism,
+        );
+    }
+}
+
 #[derive(Debug)]
 pub struct PrometheusRemoteWriteParseError {
     pub error: prost::DecodeError,
@@ -0,0 +1,39 @@
+use metrics::{counter, gauge};
+use vector_lib::internal_event::{ComponentEventsDropped, InternalEvent, INTENTIONAL};
+
+#[derive(Debug)]
+pub struct QuotaUsage<'a> {
+    pub key: Option<&'a str>,
+    pub events: u64,
+    pub bytes: u64,
+}
+
+impl<'a> InternalEvent for QuotaUsage<'a> {
+    fn emit(self) {
+        let key = self.key.unwrap_or_default().to_owned();
+        gauge!("quota_usage_events", self.events as f64, "key" => key.clone());
+        gauge!("quota_usage_bytes", self.bytes as f64, "key" => key);
+    }
+}
+
+#[derive(Debug)]
+pub struct QuotaExceeded<'a> {
+    pub key: Option<&'a str>,
+    pub dropped: bool,
+}
+
+impl<'a> InternalEvent for QuotaExceeded<'a> {
+    fn emit(self) {
+        let message = "Quota exceeded.";
+        let key = self.key.unwrap_or_default().to_owned();
+
+        debug!(message, key, internal_log_rate_limit = true);
+        counter!("quota_exceeded_events_total", 1, "key" => key);
+        if self.dropped {
+            emit!(ComponentEventsDropped::<INTENTIONAL> {
+                count: 1,
+                reason: message
+            });
+        }
+    }
+}
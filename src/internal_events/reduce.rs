@@ -1,5 +1,6 @@
 use metrics::counter;
 use vector_lib::internal_event::InternalEvent;
+use vector_lib::internal_event::{error_stage, error_type, ComponentEventsDropped, INTENTIONAL};
 
 #[derive(Debug)]
 pub struct ReduceStaleEventFlushed;
@@ -9,3 +10,63 @@ impl InternalEvent for ReduceStaleEventFlushed {
         counter!("stale_events_flushed_total", 1);
     }
 }
+
+#[derive(Debug)]
+pub struct ReduceFoldError {
+    pub error: String,
+}
+
+impl InternalEvent for ReduceFoldError {
+    fn emit(self) {
+        error!(
+            message = "Fold program failed, the event isn't folded into the reduced event.",
+            error = ?self.error,
+            error_type = error_type::SCRIPT_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::SCRIPT_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct ReduceFinalizerError {
+    pub error: String,
+}
+
+impl InternalEvent for ReduceFinalizerError {
+    fn emit(self) {
+        error!(
+            message = "Finalizer failed, emitting the reduced event unmodified.",
+            error = ?self.error,
+            error_type = error_type::SCRIPT_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::SCRIPT_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct ReduceFinalizerAbort;
+
+impl InternalEvent for ReduceFinalizerAbort {
+    fn emit(self) {
+        debug!(
+            message = "Finalizer aborted.",
+            internal_log_rate_limit = true
+        );
+        emit!(ComponentEventsDropped::<INTENTIONAL> {
+            count: 1,
+            reason: "Finalizer aborted.",
+        });
+    }
+}
@@ -1,4 +1,7 @@
-use vector_lib::internal_event::{ComponentEventsDropped, InternalEvent, INTENTIONAL};
+use metrics::counter;
+use vector_lib::internal_event::{
+    error_stage, error_type, ComponentEventsDropped, InternalEvent, INTENTIONAL,
+};
 
 #[derive(Debug)]
 pub struct SampleEventDiscarded;
@@ -11,3 +14,25 @@ impl InternalEvent for SampleEventDiscarded {
         })
     }
 }
+
+#[derive(Debug)]
+pub struct SampleRateExpressionError<'a> {
+    pub error: &'a str,
+}
+
+impl<'a> InternalEvent for SampleRateExpressionError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Rate expression failed, using the configured rate.",
+            error = %self.error,
+            error_type = error_type::SCRIPT_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::SCRIPT_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
@@ -0,0 +1,27 @@
+use metrics::counter;
+use vector_lib::internal_event::InternalEvent;
+use vector_lib::internal_event::{error_stage, error_type, ComponentEventsDropped, INTENTIONAL};
+
+#[derive(Debug)]
+pub struct SchemaEnforceViolation<'a> {
+    pub violations: &'a str,
+}
+
+impl InternalEvent for SchemaEnforceViolation<'_> {
+    fn emit(self) {
+        let reason = "Event does not conform to the schema.";
+        error!(
+            message = reason,
+            violations = %self.violations,
+            error_type = error_type::CONDITION_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::CONDITION_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+        emit!(ComponentEventsDropped::<INTENTIONAL> { count: 1, reason });
+    }
+}
@@ -0,0 +1,45 @@
+use metrics::counter;
+use vector_lib::internal_event::InternalEvent;
+use vector_lib::internal_event::{error_stage, error_type, ComponentEventsDropped, INTENTIONAL};
+
+#[derive(Debug)]
+pub struct ShedEventDropped {
+    pub priority: i64,
+}
+
+impl InternalEvent for ShedEventDropped {
+    fn emit(self) {
+        let reason = "Event shed to relieve backpressure.";
+
+        debug!(
+            message = reason,
+            priority = self.priority,
+            internal_log_rate_limit = true
+        );
+        counter!("shed_events_total", 1, "priority" => self.priority.to_string());
+
+        emit!(ComponentEventsDropped::<INTENTIONAL> { count: 1, reason })
+    }
+}
+
+#[derive(Debug)]
+pub struct ShedPriorityError<'a> {
+    pub error: &'a str,
+}
+
+impl<'a> InternalEvent for ShedPriorityError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Priority expression failed, using the default priority.",
+            error = %self.error,
+            error_type = error_type::SCRIPT_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::SCRIPT_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
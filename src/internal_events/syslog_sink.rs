@@ -0,0 +1,21 @@
+use vector_lib::internal_event::InternalEvent;
+use vector_lib::internal_event::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct SyslogInvalidFieldValue<'a> {
+    pub field: &'static str,
+    pub value: &'a str,
+}
+
+impl<'a> InternalEvent for SyslogInvalidFieldValue<'a> {
+    fn emit(self) {
+        warn!(
+            message = "Invalid value for syslog field, using the default value instead.",
+            field = self.field,
+            value = self.value,
+            error_type = error_type::CONVERSION_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+    }
+}
@@ -110,6 +110,33 @@ impl InternalEvent for TcpSocketTlsConnectionError {
     }
 }
 
+#[derive(Debug)]
+pub struct TcpSocketPeerRejected {
+    pub peer_addr: SocketAddr,
+    pub subject: Option<String>,
+}
+
+impl InternalEvent for TcpSocketPeerRejected {
+    fn emit(self) {
+        error!(
+            message = "Rejected connection from a peer that is not permitted.",
+            peer_addr = %self.peer_addr,
+            subject = ?self.subject,
+            error_code = "peer_not_permitted",
+            error_type = error_type::CONNECTION_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "peer_not_permitted",
+            "error_type" => error_type::CONNECTION_FAILED,
+            "stage" => error_stage::RECEIVING,
+            "mode" => "tcp",
+        );
+    }
+}
+
 #[derive(Debug)]
 pub struct TcpSendAckError {
     pub error: std::io::Error,
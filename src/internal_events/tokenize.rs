@@ -0,0 +1,30 @@
+use metrics::counter;
+use vector_lib::internal_event::{
+    error_stage, error_type, ComponentEventsDropped, InternalEvent, UNINTENTIONAL,
+};
+
+#[derive(Debug)]
+pub struct TokenizeError<'a> {
+    pub field: &'a str,
+    pub error: crate::Error,
+}
+
+impl<'a> InternalEvent for TokenizeError<'a> {
+    fn emit(self) {
+        let reason = "Failed to replace the value of a field.";
+        error!(
+            message = reason,
+            field = %self.field,
+            error = %self.error,
+            error_type = error_type::CONVERSION_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::CONVERSION_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason });
+    }
+}
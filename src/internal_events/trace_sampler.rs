@@ -0,0 +1,37 @@
+use metrics::counter;
+use vector_lib::internal_event::InternalEvent;
+use vector_lib::internal_event::{ComponentEventsDropped, INTENTIONAL};
+
+#[derive(Debug)]
+pub struct TraceSamplerTraceDecided {
+    pub sampled: bool,
+}
+
+impl InternalEvent for TraceSamplerTraceDecided {
+    fn emit(self) {
+        let decision = if self.sampled { "sampled" } else { "dropped" };
+        counter!("sampling_decisions_total", 1, "decision" => decision);
+    }
+}
+
+#[derive(Debug)]
+pub struct TraceSamplerSpansDropped {
+    pub count: usize,
+}
+
+impl InternalEvent for TraceSamplerSpansDropped {
+    fn emit(self) {
+        let reason = "Trace not sampled by any policy.";
+
+        debug!(
+            message = reason,
+            count = self.count,
+            internal_log_rate_limit = true
+        );
+
+        emit!(ComponentEventsDropped::<INTENTIONAL> {
+            count: self.count,
+            reason
+        })
+    }
+}
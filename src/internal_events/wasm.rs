@@ -0,0 +1,90 @@
+use std::path::Path;
+
+use metrics::counter;
+use vector_lib::internal_event::InternalEvent;
+use vector_lib::internal_event::{error_stage, error_type, ComponentEventsDropped, UNINTENTIONAL};
+
+use crate::transforms::wasm::{BuildError, GuestError};
+
+#[derive(Debug)]
+pub struct WasmModuleReloaded<'a> {
+    pub path: &'a Path,
+}
+
+impl InternalEvent for WasmModuleReloaded<'_> {
+    fn emit(self) {
+        info!(message = "Reloaded the WebAssembly module.", path = ?self.path);
+    }
+}
+
+#[derive(Debug)]
+pub struct WasmModuleReloadError {
+    pub error: BuildError,
+}
+
+impl InternalEvent for WasmModuleReloadError {
+    fn emit(self) {
+        error!(
+            message = "Unable to reload the WebAssembly module, keeping the previous one.",
+            error = %self.error,
+            error_type = error_type::CONFIGURATION_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::CONFIGURATION_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct WasmProcessError {
+    pub error: GuestError,
+}
+
+impl InternalEvent for WasmProcessError {
+    fn emit(self) {
+        let reason = "Error in the WebAssembly module.";
+        error!(
+            message = reason,
+            error = %self.error,
+            error_code = self.error.error_code(),
+            error_type = error_type::SCRIPT_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => self.error.error_code(),
+            "error_type" => error_type::SCRIPT_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason });
+    }
+}
+
+#[derive(Debug)]
+pub struct WasmInvalidOutputError {
+    pub error: String,
+}
+
+impl InternalEvent for WasmInvalidOutputError {
+    fn emit(self) {
+        let reason = "The WebAssembly module returned an invalid output for an event.";
+        error!(
+            message = reason,
+            error = %self.error,
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason });
+    }
+}
@@ -0,0 +1,87 @@
+use std::error::Error;
+use std::net::SocketAddr;
+
+use metrics::counter;
+use vector_lib::internal_event::InternalEvent;
+
+use vector_lib::internal_event::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct WsListenerConnectionEstablished {
+    pub client: SocketAddr,
+}
+
+impl InternalEvent for WsListenerConnectionEstablished {
+    fn emit(self) {
+        debug!(message = "Client connected.", client = %self.client);
+        counter!("connection_established_total", 1);
+    }
+
+    fn name(&self) -> Option<&'static str> {
+        Some("WsListenerConnectionEstablished")
+    }
+}
+
+#[derive(Debug)]
+pub struct WsListenerConnectionError {
+    pub error: Box<dyn Error + Send + Sync>,
+}
+
+impl InternalEvent for WsListenerConnectionError {
+    fn emit(self) {
+        error!(
+            message = "WebSocket connection error.",
+            error = %self.error,
+            error_code = "ws_connection_error",
+            error_type = error_type::CONNECTION_FAILED,
+            stage = error_stage::SENDING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "ws_connection_error",
+            "error_type" => error_type::CONNECTION_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+    }
+
+    fn name(&self) -> Option<&'static str> {
+        Some("WsListenerConnectionError")
+    }
+}
+
+#[derive(Debug)]
+pub struct WsListenerConnectionShutdown {
+    pub client: SocketAddr,
+}
+
+impl InternalEvent for WsListenerConnectionShutdown {
+    fn emit(self) {
+        debug!(message = "Client disconnected.", client = %self.client);
+        counter!("connection_shutdown_total", 1);
+    }
+
+    fn name(&self) -> Option<&'static str> {
+        Some("WsListenerConnectionShutdown")
+    }
+}
+
+#[derive(Debug)]
+pub struct WsListenerMessageDropped {
+    pub client: SocketAddr,
+}
+
+impl InternalEvent for WsListenerMessageDropped {
+    fn emit(self) {
+        warn!(
+            message = "Dropped message for client that isn't keeping up.",
+            client = %self.client,
+            internal_log_rate_limit = true,
+        );
+        counter!("websocket_server_dropped_messages_total", 1);
+    }
+
+    fn name(&self) -> Option<&'static str> {
+        Some("WsListenerMessageDropped")
+    }
+}
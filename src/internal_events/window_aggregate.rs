@@ -0,0 +1,41 @@
+use metrics::counter;
+use vector_lib::internal_event::InternalEvent;
+use vector_lib::internal_event::{error_stage, error_type, ComponentEventsDropped, INTENTIONAL};
+
+#[derive(Debug)]
+pub struct WindowAggregateFinalizerError {
+    pub error: String,
+}
+
+impl InternalEvent for WindowAggregateFinalizerError {
+    fn emit(self) {
+        error!(
+            message = "Window finalizer failed, emitting the summary event unmodified.",
+            error = ?self.error,
+            error_type = error_type::CONVERSION_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::CONVERSION_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct WindowAggregateFinalizerAbort;
+
+impl InternalEvent for WindowAggregateFinalizerAbort {
+    fn emit(self) {
+        debug!(
+            message = "Window finalizer aborted.",
+            internal_log_rate_limit = true
+        );
+        emit!(ComponentEventsDropped::<INTENTIONAL> {
+            count: 1,
+            reason: "Window finalizer aborted.",
+        });
+    }
+}
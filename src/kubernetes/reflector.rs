@@ -14,15 +14,19 @@ use tokio_util::time::DelayQueue;
 use super::meta_cache::{MetaCache, MetaDescribe};
 
 /// Handles events from a [`kube::runtime::watcher()`] to delay the application of Deletion events.
-pub async fn custom_reflector<K, W>(
+///
+/// `on_applied` is called with each event once it is applied to the store.
+pub async fn custom_reflector<K, W, F>(
     mut store: store::Writer<K>,
     mut meta_cache: MetaCache,
     stream: W,
     delay_deletion: Duration,
+    mut on_applied: F,
 ) where
     K: Resource + Clone + std::fmt::Debug,
     K::DynamicType: Eq + Hash + Clone,
     W: Stream<Item = watcher::Result<watcher::Event<K>>>,
+    F: FnMut(&watcher::Event<K>),
 {
     pin!(stream);
     let mut delay_queue = DelayQueue::default();
@@ -36,6 +40,7 @@ pub async fn custom_reflector<K, W>(
                             watcher::Event::Applied(ref obj) => {
                                 trace!(message = "Processing Applied event.", ?event);
                                 store.apply_watcher_event(&event);
+                                on_applied(&event);
                                 let meta_descr = MetaDescribe::from_meta(obj.meta());
                                 meta_cache.store(meta_descr);
                             }
@@ -50,6 +55,7 @@ pub async fn custom_reflector<K, W>(
                                 trace!(message = "Processing Restarted event.", ?event);
                                 delay_queue.clear();
                                 store.apply_watcher_event(&event);
+                                on_applied(&event);
                                 meta_cache.clear();
                             }
                         }
@@ -74,9 +80,13 @@ pub async fn custom_reflector<K, W>(
                                 if !meta_cache.contains(&meta_desc) {
                                     trace!(message = "Processing Deleted event.", ?event);
                                     store.apply_watcher_event(&event);
+                                    on_applied(&event);
                                 }
                             },
-                            _ => store.apply_watcher_event(&event),
+                            _ => {
+                                store.apply_watcher_event(&event);
+                                on_applied(&event);
+                            }
                         }
                     },
                     // DelayQueue returns None if the queue is exhausted,
@@ -127,6 +137,7 @@ mod tests {
             meta_cache,
             rx,
             Duration::from_secs(1),
+            |_| {},
         ));
         tokio::time::sleep(Duration::from_secs(1)).await;
         assert_eq!(store.get(&ObjectRef::from_obj(&cm)).as_deref(), Some(&cm));
@@ -156,6 +167,7 @@ mod tests {
             meta_cache,
             rx,
             Duration::from_secs(2),
+            |_| {},
         ));
         // Ensure the Resource is still available after deletion
         tokio::time::sleep(Duration::from_secs(1)).await;
@@ -193,6 +205,7 @@ mod tests {
             meta_cache,
             rx,
             Duration::from_secs(2),
+            |_| {},
         ));
         tokio::time::sleep(Duration::from_secs(1)).await;
         // Ensure the Resource is still available after deletion
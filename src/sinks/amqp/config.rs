@@ -55,6 +55,13 @@ pub struct AmqpSinkConfig {
     /// AMQP message properties.
     pub(crate) properties: Option<AmqpPropertiesConfig>,
 
+    /// If set to true, messages are published as mandatory.
+    ///
+    /// The broker returns mandatory messages which can't be routed to any queue instead of
+    /// dropping them, and their events are rejected rather than acknowledged.
+    #[serde(default)]
+    pub(crate) mandatory: bool,
+
     #[serde(flatten)]
     pub(crate) connection: AmqpConfig,
 
@@ -76,6 +83,7 @@ impl Default for AmqpSinkConfig {
             exchange: Template::try_from("vector").unwrap(),
             routing_key: None,
             properties: None,
+            mandatory: false,
             encoding: TextSerializerConfig::default().into(),
             connection: AmqpConfig::default(),
             acknowledgements: AcknowledgementsConfig::default(),
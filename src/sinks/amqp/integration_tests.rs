@@ -5,14 +5,20 @@ use crate::{
     shutdown::ShutdownSignal,
     template::Template,
     test_util::{
-        components::{run_and_assert_sink_compliance, SINK_TAGS},
+        components::{
+            run_and_assert_sink_compliance, run_and_assert_sink_error, COMPONENT_ERROR_TAGS,
+            SINK_TAGS,
+        },
         random_lines_with_stream, random_string,
     },
     SourceSender,
 };
 use futures::StreamExt;
 use std::{collections::HashSet, sync::Arc, time::Duration};
-use vector_lib::config::LogNamespace;
+use vector_lib::{
+    config::LogNamespace,
+    event::{BatchNotifier, BatchStatus},
+};
 
 pub fn make_config() -> AmqpSinkConfig {
     let mut config = AmqpSinkConfig {
@@ -53,6 +59,42 @@ async fn amqp_round_trip_plaintext() {
     amqp_round_trip().await;
 }
 
+#[tokio::test]
+async fn amqp_mandatory_unroutable() {
+    crate::test_util::trace_init();
+
+    let mut config = make_config();
+    let exchange = format!("test-{}-exchange", random_string(10));
+    config.exchange = Template::try_from(exchange.as_str()).unwrap();
+    config.mandatory = true;
+
+    await_connection(&config.connection).await;
+    let (_conn, channel) = config.connection.connect().await.unwrap();
+    let exchange_opts = lapin::options::ExchangeDeclareOptions {
+        auto_delete: true,
+        ..Default::default()
+    };
+    channel
+        .exchange_declare(
+            &exchange,
+            lapin::ExchangeKind::Fanout,
+            exchange_opts,
+            lapin::types::FieldTable::default(),
+        )
+        .await
+        .unwrap();
+
+    let cx = SinkContext::default();
+    let (sink, _healthcheck) = config.build(cx).await.unwrap();
+
+    // No queue is bound to the exchange, so the messages are returned by the broker.
+    let (batch, receiver) = BatchNotifier::new_with_receiver();
+    let (_, events) = random_lines_with_stream(100, 10, Some(batch));
+    run_and_assert_sink_error(sink, events, &COMPONENT_ERROR_TAGS).await;
+
+    assert_eq!(receiver.await, BatchStatus::Rejected);
+}
+
 async fn amqp_happy_path() {
     let mut config = make_config();
     let exchange = format!("test-{}-exchange", random_string(10));
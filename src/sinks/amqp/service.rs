@@ -3,7 +3,7 @@
 use crate::sinks::prelude::*;
 use bytes::Bytes;
 use futures::future::BoxFuture;
-use lapin::{options::BasicPublishOptions, BasicProperties};
+use lapin::{options::BasicPublishOptions, publisher_confirm::Confirmation, BasicProperties};
 use snafu::Snafu;
 use std::{
     sync::Arc,
@@ -80,6 +80,7 @@ impl DriverResponse for AmqpResponse {
 /// The tower service that handles the actual sending of data to `AMQP`.
 pub(super) struct AmqpService {
     pub(super) channel: Arc<lapin::Channel>,
+    pub(super) options: BasicPublishOptions,
 }
 
 #[derive(Debug, Snafu)]
@@ -92,6 +93,9 @@ pub(super) enum AmqpError {
 
     #[snafu(display("Received Negative Acknowledgement from AMQP broker."))]
     Nack,
+
+    #[snafu(display("Message returned by AMQP broker ({}): {}", reply_code, reply_text))]
+    Returned { reply_code: u16, reply_text: String },
 }
 
 impl Service<AmqpRequest> for AmqpService {
@@ -107,6 +111,7 @@ impl Service<AmqpRequest> for AmqpService {
 
     fn call(&mut self, req: AmqpRequest) -> Self::Future {
         let channel = Arc::clone(&self.channel);
+        let options = self.options;
 
         Box::pin(async move {
             let byte_size = req.body.len();
@@ -114,7 +119,7 @@ impl Service<AmqpRequest> for AmqpService {
                 .basic_publish(
                     &req.exchange,
                     &req.routing_key,
-                    BasicPublishOptions::default(),
+                    options,
                     req.body.as_ref(),
                     req.properties,
                 )
@@ -122,7 +127,13 @@ impl Service<AmqpRequest> for AmqpService {
 
             match fut {
                 Ok(result) => match result.await {
-                    Ok(lapin::publisher_confirm::Confirmation::Nack(_)) => Err(AmqpError::Nack),
+                    Ok(Confirmation::Nack(_)) => Err(AmqpError::Nack),
+                    // Mandatory messages which can't be routed are returned before they are
+                    // acknowledged.
+                    Ok(Confirmation::Ack(Some(message))) => Err(AmqpError::Returned {
+                        reply_code: message.reply_code,
+                        reply_text: message.reply_text.to_string(),
+                    }),
                     Err(error) => Err(AmqpError::AcknowledgementFailed { error }),
                     Ok(_) => Ok(AmqpResponse {
                         json_size: req.metadata.into_events_estimated_json_encoded_byte_size(),
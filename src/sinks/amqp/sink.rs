@@ -1,7 +1,10 @@
 //! The sink for the `AMQP` sink that wires together the main stream that takes the
 //! event and sends it to `AMQP`.
 use crate::sinks::prelude::*;
-use lapin::{options::ConfirmSelectOptions, BasicProperties};
+use lapin::{
+    options::{BasicPublishOptions, ConfirmSelectOptions},
+    BasicProperties,
+};
 use serde::Serialize;
 use std::sync::Arc;
 
@@ -31,6 +34,7 @@ pub(super) struct AmqpSink {
     exchange: Template,
     routing_key: Option<Template>,
     properties: Option<AmqpPropertiesConfig>,
+    mandatory: bool,
     transformer: Transformer,
     encoder: crate::codecs::Encoder<()>,
 }
@@ -60,6 +64,7 @@ impl AmqpSink {
             exchange: config.exchange,
             routing_key: config.routing_key,
             properties: config.properties,
+            mandatory: config.mandatory,
             transformer,
             encoder,
         })
@@ -116,6 +121,10 @@ impl AmqpSink {
         };
         let service = ServiceBuilder::new().service(AmqpService {
             channel: Arc::clone(&self.channel),
+            options: BasicPublishOptions {
+                mandatory: self.mandatory,
+                ..Default::default()
+            },
         });
 
         input
@@ -12,6 +12,7 @@ use crate::{
 };
 
 use super::{
+    partitioning::DynamicPartitioningConfig,
     record::{Record, SendRecord},
     request_builder::KinesisRequestBuilder,
     sink::{BatchKinesisRequest, KinesisSink},
@@ -87,6 +88,7 @@ impl KinesisSinkBaseConfig {
 pub fn build_sink<C, R, RR, E, RT>(
     config: &KinesisSinkBaseConfig,
     partition_key_field: Option<ConfigValuePath>,
+    dynamic_partitioning: Option<DynamicPartitioningConfig>,
     batch_settings: BatcherSettings,
     client: C,
     retry_logic: RT,
@@ -129,6 +131,7 @@ where
         service,
         request_builder,
         partition_key_field,
+        dynamic_partitioning,
         _phantom: PhantomData,
     };
     Ok(VectorSink::from_event_streamsink(sink))
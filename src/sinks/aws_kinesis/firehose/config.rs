@@ -4,6 +4,7 @@ use aws_sdk_firehose::operation::{
 use aws_smithy_runtime_api::client::{orchestrator::HttpResponse, result::SdkError};
 use futures::FutureExt;
 use snafu::Snafu;
+use vector_lib::codecs::encoding::SerializerConfig;
 use vector_lib::configurable::configurable_component;
 
 use crate::sinks::util::retries::RetryAction;
@@ -11,13 +12,14 @@ use crate::{
     aws::{create_client, is_retriable_error, ClientBuilder},
     config::{AcknowledgementsConfig, GenerateConfig, Input, ProxyConfig, SinkConfig, SinkContext},
     sinks::{
-        util::{retries::RetryLogic, BatchConfig, SinkBatchSettings},
+        util::{retries::RetryLogic, BatchConfig, Compression, SinkBatchSettings},
         Healthcheck, VectorSink,
     },
 };
 
 use super::{
     build_sink,
+    partitioning::DynamicPartitioningConfig,
     record::{KinesisFirehoseClient, KinesisFirehoseRecord},
     KinesisClient, KinesisError, KinesisRecord, KinesisResponse, KinesisSinkBaseConfig,
 };
@@ -33,6 +35,14 @@ enum HealthcheckError {
     StreamNamesMismatch { name: String, stream_name: String },
 }
 
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("`dynamic_partitioning` requires the `json` codec"))]
+    DynamicPartitioningCodec,
+    #[snafu(display("`dynamic_partitioning` requires uncompressed records"))]
+    DynamicPartitioningCompression,
+}
+
 pub struct KinesisFirehoseClientBuilder;
 
 impl ClientBuilder for KinesisFirehoseClientBuilder {
@@ -70,6 +80,9 @@ pub struct KinesisFirehoseSinkConfig {
     #[configurable(derived)]
     #[serde(default)]
     pub batch: BatchConfig<KinesisFirehoseDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    pub dynamic_partitioning: Option<DynamicPartitioningConfig>,
 }
 
 impl KinesisFirehoseSinkConfig {
@@ -116,6 +129,16 @@ impl KinesisFirehoseSinkConfig {
 #[typetag::serde(name = "aws_kinesis_firehose")]
 impl SinkConfig for KinesisFirehoseSinkConfig {
     async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        // The delivery stream parses the records as JSON objects to extract their partition keys.
+        if self.dynamic_partitioning.is_some() {
+            if !matches!(self.base.encoding.config(), SerializerConfig::Json(_)) {
+                return Err(Box::new(BuildError::DynamicPartitioningCodec));
+            }
+            if !matches!(self.base.compression, Compression::None) {
+                return Err(Box::new(BuildError::DynamicPartitioningCompression));
+            }
+        }
+
         let client = self.create_client(&cx.proxy).await?;
         let healthcheck = self.clone().healthcheck(client.clone()).boxed();
 
@@ -135,6 +158,7 @@ impl SinkConfig for KinesisFirehoseSinkConfig {
         >(
             &self.base,
             self.base.partition_key_field.clone(),
+            self.dynamic_partitioning.clone(),
             batch_settings,
             KinesisFirehoseClient { client },
             KinesisRetryLogic {
@@ -65,7 +65,11 @@ async fn firehose_put_records_without_partition_key() {
         partition_key_field: None,
     };
 
-    let config = KinesisFirehoseSinkConfig { batch, base };
+    let config = KinesisFirehoseSinkConfig {
+        batch,
+        base,
+        dynamic_partitioning: None,
+    };
 
     let cx = SinkContext::default();
 
@@ -166,7 +170,11 @@ async fn firehose_put_records_with_partition_key() {
         partition_key_field: Some(partition_key.clone()),
     };
 
-    let config = KinesisFirehoseSinkConfig { batch, base };
+    let config = KinesisFirehoseSinkConfig {
+        batch,
+        base,
+        dynamic_partitioning: None,
+    };
 
     let cx = SinkContext::default();
 
@@ -11,6 +11,7 @@ use aws_sdk_firehose::{
 
 pub use super::{
     config::{build_sink, KinesisSinkBaseConfig},
+    partitioning,
     record::{Record, SendRecord},
     request_builder,
     service::{KinesisResponse, KinesisService},
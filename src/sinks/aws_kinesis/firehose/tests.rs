@@ -1,14 +1,17 @@
 #![cfg(test)]
 
-use vector_lib::codecs::JsonSerializerConfig;
+use vector_lib::codecs::{JsonSerializerConfig, TextSerializerConfig};
 
 use super::*;
 use crate::{
     aws::RegionOrEndpoint,
     config::{SinkConfig, SinkContext},
     sinks::{
-        aws_kinesis::firehose::config::{
-            KinesisFirehoseDefaultBatchSettings, MAX_PAYLOAD_EVENTS, MAX_PAYLOAD_SIZE,
+        aws_kinesis::{
+            firehose::config::{
+                KinesisFirehoseDefaultBatchSettings, MAX_PAYLOAD_EVENTS, MAX_PAYLOAD_SIZE,
+            },
+            partitioning::DynamicPartitioningConfig,
         },
         util::{batch::BatchError, BatchConfig, Compression},
     },
@@ -38,7 +41,11 @@ async fn check_batch_size() {
         partition_key_field: None,
     };
 
-    let config = KinesisFirehoseSinkConfig { batch, base };
+    let config = KinesisFirehoseSinkConfig {
+        batch,
+        base,
+        dynamic_partitioning: None,
+    };
 
     let cx = SinkContext::default();
     let res = config.build(cx).await;
@@ -69,7 +76,11 @@ async fn check_batch_events() {
         partition_key_field: None,
     };
 
-    let config = KinesisFirehoseSinkConfig { batch, base };
+    let config = KinesisFirehoseSinkConfig {
+        batch,
+        base,
+        dynamic_partitioning: None,
+    };
 
     let cx = SinkContext::default();
     let res = config.build(cx).await;
@@ -81,3 +92,37 @@ async fn check_batch_events() {
         }))
     );
 }
+
+#[tokio::test]
+async fn check_dynamic_partitioning_codec() {
+    // The delivery stream can only extract partition keys from JSON records.
+    let base = KinesisSinkBaseConfig {
+        stream_name: String::from("test"),
+        region: RegionOrEndpoint::with_both("us-east-1", "http://localhost:4566"),
+        encoding: TextSerializerConfig::default().into(),
+        compression: Compression::None,
+        request: Default::default(),
+        tls: None,
+        auth: Default::default(),
+        request_retry_partial: false,
+        acknowledgements: Default::default(),
+        partition_key_field: None,
+    };
+
+    let config = KinesisFirehoseSinkConfig {
+        batch: Default::default(),
+        base,
+        dynamic_partitioning: Some(DynamicPartitioningConfig {
+            keys: [("tenant".to_string(), "{{ tenant_id }}".try_into().unwrap())].into(),
+            field: "partition_keys".into(),
+        }),
+    };
+
+    let cx = SinkContext::default();
+    let res = config.build(cx).await;
+
+    assert_eq!(
+        res.err().map(|e| e.to_string()),
+        Some("`dynamic_partitioning` requires the `json` codec".to_string())
+    );
+}
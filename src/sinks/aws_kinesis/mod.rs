@@ -5,6 +5,7 @@ pub mod streams;
 pub mod firehose;
 
 pub mod config;
+pub mod partitioning;
 pub mod record;
 pub mod request_builder;
 pub mod service;
@@ -0,0 +1,123 @@
+use std::collections::BTreeMap;
+
+use vector_lib::configurable::configurable_component;
+use vector_lib::lookup::lookup_v2::ConfigValuePath;
+use vrl::path::PathPrefix;
+
+use crate::{
+    event::{LogEvent, ObjectMap, Value},
+    internal_events::TemplateRenderingError,
+    template::Template,
+};
+
+fn default_field() -> ConfigValuePath {
+    ConfigValuePath::from("partition_keys")
+}
+
+/// Configuration for the [dynamic partitioning][dynamic_partitioning] of a delivery stream.
+///
+/// The partition keys are rendered for each event and embedded into its record, where the delivery
+/// stream extracts them with inline parsing to organize the S3 prefixes, without a Lambda function
+/// processing the records.
+///
+/// [dynamic_partitioning]: https://docs.aws.amazon.com/firehose/latest/dev/dynamic-partitioning.html
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct DynamicPartitioningConfig {
+    /// The partition keys of the records.
+    ///
+    /// Each key is a template rendered from the fields of the event. Events whose keys fail to
+    /// render are dropped.
+    #[configurable(metadata(
+        docs::additional_props_description = "A partition key and its template."
+    ))]
+    #[configurable(metadata(docs::examples = "keys_examples()"))]
+    pub keys: BTreeMap<String, Template>,
+
+    /// The field that the partition keys are embedded into.
+    ///
+    /// The delivery stream is configured to extract each key from this field. For example, the
+    /// `tenant` key is extracted with `{tenant: .partition_keys.tenant}` as its JQ expression, and
+    /// used in the S3 prefix as `!{partitionKeyFromQuery:tenant}`.
+    ///
+    /// The field is added before the `encoding` options are applied, so it must be kept by
+    /// `encoding.only_fields` when that is set.
+    #[serde(default = "default_field")]
+    #[configurable(metadata(docs::examples = "firehose.partition_keys"))]
+    pub field: ConfigValuePath,
+}
+
+fn keys_examples() -> BTreeMap<String, String> {
+    BTreeMap::from([
+        ("service".to_string(), "{{ service }}".to_string()),
+        ("tenant".to_string(), "{{ tenant_id }}".to_string()),
+    ])
+}
+
+impl DynamicPartitioningConfig {
+    /// Embeds the partition keys rendered from the event into it.
+    ///
+    /// Returns `false` if any of the keys fails to render, in which case the event is dropped.
+    pub fn embed(&self, log: &mut LogEvent) -> bool {
+        let mut keys = ObjectMap::new();
+        for (key, template) in &self.keys {
+            match template.render_string(&*log) {
+                Ok(value) => {
+                    keys.insert(key.as_str().into(), Value::from(value));
+                }
+                Err(error) => {
+                    emit!(TemplateRenderingError {
+                        error,
+                        field: Some(key.as_str()),
+                        drop_event: true,
+                    });
+                    return false;
+                }
+            }
+        }
+
+        log.insert((PathPrefix::Event, &self.field), keys);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vrl::value;
+
+    use super::*;
+
+    fn config(keys: &[(&str, &str)]) -> DynamicPartitioningConfig {
+        DynamicPartitioningConfig {
+            keys: keys
+                .iter()
+                .map(|(key, template)| (key.to_string(), Template::try_from(*template).unwrap()))
+                .collect(),
+            field: default_field(),
+        }
+    }
+
+    #[test]
+    fn embeds_rendered_partition_keys() {
+        let mut log = LogEvent::from("disk full");
+        log.insert("tenant_id", "acme");
+        log.insert("service", "api");
+
+        assert!(
+            config(&[("tenant", "{{ tenant_id }}"), ("service", "{{ service }}")]).embed(&mut log)
+        );
+        assert_eq!(
+            log.get("partition_keys").unwrap(),
+            &value!({"service": "api", "tenant": "acme"})
+        );
+    }
+
+    #[test]
+    fn drops_events_with_missing_partition_keys() {
+        let mut log = LogEvent::from("disk full");
+
+        assert!(!config(&[("tenant", "{{ tenant_id }}")]).embed(&mut log));
+        assert!(log.get("partition_keys").is_none());
+    }
+}
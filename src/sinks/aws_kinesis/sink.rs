@@ -13,6 +13,7 @@ use crate::{
 };
 
 use super::{
+    partitioning::DynamicPartitioningConfig,
     record::Record,
     request_builder::{KinesisRequest, KinesisRequestBuilder},
 };
@@ -30,6 +31,7 @@ pub struct KinesisSink<S, R> {
     pub service: S,
     pub request_builder: KinesisRequestBuilder<R>,
     pub partition_key_field: Option<ConfigValuePath>,
+    pub dynamic_partitioning: Option<DynamicPartitioningConfig>,
     pub _phantom: PhantomData<R>,
 }
 
@@ -47,7 +49,12 @@ where
         input
             .filter_map(|event| {
                 // Panic: This sink only accepts Logs, so this should never panic
-                let log = event.into_log();
+                let mut log = event.into_log();
+                if let Some(dynamic_partitioning) = self.dynamic_partitioning.as_ref() {
+                    if !dynamic_partitioning.embed(&mut log) {
+                        return future::ready(None);
+                    }
+                }
                 let processed = process_log(log, self.partition_key_field.as_ref());
 
                 future::ready(processed)
@@ -137,6 +137,7 @@ impl SinkConfig for KinesisStreamsSinkConfig {
         >(
             &self.base,
             self.base.partition_key_field.clone(),
+            None,
             batch_settings,
             KinesisStreamClient { client },
             KinesisRetryLogic {
@@ -5,13 +5,14 @@ use vector_lib::codecs::{
     TextSerializerConfig,
 };
 use vector_lib::configurable::configurable_component;
+use vector_lib::lookup::lookup_v2::ConfigValuePath;
 use vector_lib::sink::VectorSink;
 use vector_lib::TimeZone;
 
 use super::sink::S3RequestOptions;
 use crate::{
     aws::{AwsAuthentication, RegionOrEndpoint},
-    codecs::{Encoder, EncodingConfigWithFraming, SinkType},
+    codecs::{BatchSerializerConfig, Encoder, EncoderKind, EncodingConfigWithFraming, SinkType},
     config::{AcknowledgementsConfig, GenerateConfig, Input, ProxyConfig, SinkConfig, SinkContext},
     sinks::{
         s3_common::{
@@ -57,6 +58,20 @@ pub struct S3SinkConfig {
     #[configurable(metadata(docs::examples = "application_id={{ application_id }}/date=%F"))]
     pub key_prefix: String,
 
+    /// Fields of the events to partition objects by, in the style of Hive.
+    ///
+    /// For each field, in order, a `field=value/` partition is appended to the key prefix, which is
+    /// separated from the partitions by a `/` if it doesn't end with one. Values are escaped as Hive
+    /// escapes partition paths, and events without a value for a field are written to the
+    /// `__HIVE_DEFAULT_PARTITION__` partition of that field.
+    ///
+    /// Partitioned tables of query engines such as Athena, Hive, and Spark can then read the
+    /// objects directly, usually with the partition fields excluded from the objects with the
+    /// `except_fields` option of `encoding`.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "region", docs::examples = "service"))]
+    pub partition_fields: Vec<ConfigValuePath>,
+
     /// The timestamp format for the time component of the object key.
     ///
     /// By default, object keys are appended with a timestamp that reflects when the objects are
@@ -113,6 +128,15 @@ pub struct S3SinkConfig {
     #[serde(default = "Compression::gzip_default")]
     pub compression: Compression,
 
+    /// Configures encoding all the events of a batch together into a single object.
+    ///
+    /// When set, this is used instead of the `codec` and `framing` of `encoding`, and objects are
+    /// not compressed with `compression`, as the batch codecs compress their own data. Objects are
+    /// written once the batch is full or times out, as configured with `batch`.
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch_encoding: Option<BatchSerializerConfig>,
+
     #[configurable(derived)]
     #[serde(default)]
     pub batch: BatchConfig<BulkSizeBasedDefaultBatchSettings>,
@@ -154,6 +178,7 @@ impl GenerateConfig for S3SinkConfig {
         toml::Value::try_from(Self {
             bucket: "".to_owned(),
             key_prefix: default_key_prefix(),
+            partition_fields: Vec::new(),
             filename_time_format: default_filename_time_format(),
             filename_append_uuid: true,
             filename_extension: None,
@@ -161,6 +186,7 @@ impl GenerateConfig for S3SinkConfig {
             region: RegionOrEndpoint::default(),
             encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
             compression: Compression::gzip_default(),
+            batch_encoding: None,
             batch: BatchConfig::default(),
             request: TowerRequestConfig::default(),
             tls: Some(TlsConfig::default()),
@@ -183,7 +209,11 @@ impl SinkConfig for S3SinkConfig {
     }
 
     fn input(&self) -> Input {
-        Input::new(self.encoding.config().1.input_type())
+        if self.batch_encoding.is_some() {
+            Input::log()
+        } else {
+            Input::new(self.encoding.config().1.input_type())
+        }
     }
 
     fn acknowledgements(&self) -> &AcknowledgementsConfig {
@@ -224,20 +254,41 @@ impl S3SinkConfig {
             .map(|ssekms_key_id| Template::try_from(ssekms_key_id.as_str()))
             .transpose()?;
 
-        let partitioner = S3KeyPartitioner::new(key_prefix, ssekms_key_id);
+        let partition_fields = self
+            .partition_fields
+            .iter()
+            .map(|field| field.0.clone())
+            .collect();
+
+        let partitioner = S3KeyPartitioner::new(key_prefix, ssekms_key_id, partition_fields);
 
         let transformer = self.encoding.transformer();
-        let (framer, serializer) = self.encoding.build(SinkType::MessageBased)?;
-        let encoder = Encoder::<Framer>::new(framer, serializer);
+        let mut api_options = self.options.clone();
+        let mut filename_extension = self.filename_extension.clone();
+        let (encoder, compression) = match &self.batch_encoding {
+            Some(batch_encoding) => {
+                let serializer = batch_encoding.build()?;
+                api_options
+                    .content_type
+                    .get_or_insert_with(|| serializer.content_type().to_string());
+                filename_extension.get_or_insert_with(|| serializer.extension().to_string());
+                (EncoderKind::Batch(serializer), Compression::None)
+            }
+            None => {
+                let (framer, serializer) = self.encoding.build(SinkType::MessageBased)?;
+                let encoder = Encoder::<Framer>::new(framer, serializer);
+                (EncoderKind::Framed(encoder), self.compression)
+            }
+        };
 
         let request_options = S3RequestOptions {
             bucket: self.bucket.clone(),
-            api_options: self.options.clone(),
-            filename_extension: self.filename_extension.clone(),
+            api_options,
+            filename_extension,
             filename_time_format: self.filename_time_format.clone(),
             filename_append_uuid: self.filename_append_uuid,
             encoder: (transformer, encoder),
-            compression: self.compression,
+            compression,
             filename_tz_offset: offset,
         };
 
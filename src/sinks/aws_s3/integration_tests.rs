@@ -17,9 +17,14 @@ use aws_smithy_runtime_api::client::result::SdkError;
 use bytes::Buf;
 use flate2::read::MultiGzDecoder;
 use futures::{stream, Stream};
+use parquet::{
+    file::reader::{FileReader, SerializedFileReader},
+    record::RowAccessor,
+};
 use similar_asserts::assert_eq;
 use tokio_stream::StreamExt;
 use vector_lib::codecs::{encoding::FramingConfig, TextSerializerConfig};
+use vector_lib::lookup::lookup_v2::ConfigValuePath;
 use vector_lib::{
     config::proxy::ProxyConfig,
     event::{BatchNotifier, BatchStatus, BatchStatusReceiver, Event, EventArray, LogEvent},
@@ -28,6 +33,7 @@ use vector_lib::{
 use super::S3SinkConfig;
 use crate::{
     aws::{create_client, AwsAuthentication, RegionOrEndpoint},
+    codecs::BatchSerializerConfig,
     common::s3::S3ClientBuilder,
     config::SinkContext,
     sinks::{
@@ -40,7 +46,7 @@ use crate::{
             run_and_assert_sink_compliance, run_and_assert_sink_error, AWS_SINK_TAGS,
             COMPONENT_ERROR_TAGS,
         },
-        random_lines_with_stream, random_string,
+        generate_events_with_stream, random_lines_with_stream, random_string,
     },
 };
 
@@ -296,6 +302,109 @@ async fn s3_zstd() {
     assert_eq!(lines, response_lines);
 }
 
+#[tokio::test]
+async fn s3_parquet() {
+    let cx = SinkContext::default();
+
+    let bucket = uuid::Uuid::new_v4().to_string();
+
+    create_bucket(&bucket, false).await;
+
+    let batch_size = 1_000;
+    let batch_multiplier = 3;
+    let config = S3SinkConfig {
+        compression: Compression::gzip_default(),
+        batch_encoding: Some(BatchSerializerConfig::Parquet {
+            parquet: Default::default(),
+        }),
+        filename_time_format: "%s%f".into(),
+        ..config(&bucket, batch_size)
+    };
+
+    let prefix = config.key_prefix.clone();
+    let service = config.create_service(&cx.globals.proxy).await.unwrap();
+    let sink = config.build_processor(service, cx).unwrap();
+
+    let (lines, events, receiver) = make_events_batch(100, batch_size * batch_multiplier);
+    run_and_assert_sink_compliance(sink, events, &AWS_SINK_TAGS).await;
+    assert_eq!(receiver.await, BatchStatus::Delivered);
+
+    let keys = get_keys(&bucket, prefix).await;
+    assert_eq!(keys.len(), batch_multiplier);
+
+    let mut response_lines: Vec<String> = Vec::new();
+    let mut key_stream = stream::iter(keys);
+    while let Some(key) = key_stream.next().await {
+        assert!(key.ends_with(".parquet"));
+
+        let obj = get_object(&bucket, key).await;
+        assert_eq!(obj.content_encoding, None);
+        assert_eq!(
+            obj.content_type,
+            Some("application/vnd.apache.parquet".to_string())
+        );
+
+        response_lines.append(&mut get_parquet_messages(obj).await);
+    }
+
+    assert_eq!(lines, response_lines);
+}
+
+#[tokio::test]
+async fn s3_insert_message_into_hive_partitions() {
+    let cx = SinkContext::default();
+
+    let bucket = uuid::Uuid::new_v4().to_string();
+
+    create_bucket(&bucket, false).await;
+
+    let mut config = config(&bucket, 1000000);
+    config.key_prefix = "test-prefix".to_string();
+    config.partition_fields = vec![ConfigValuePath::from("region")];
+    let prefix = config.key_prefix.clone();
+    let service = config.create_service(&cx.globals.proxy).await.unwrap();
+    let sink = config.build_processor(service, cx).unwrap();
+
+    let (batch, receiver) = BatchNotifier::new_with_receiver();
+    let (input, events) = generate_events_with_stream(
+        |index| {
+            let mut log = LogEvent::from(random_string(100));
+            log.insert("region", ["us-east-1", "eu-west-1"][index % 2]);
+            log.into()
+        },
+        10,
+        Some(batch),
+    );
+    let mut lines = input
+        .iter()
+        .map(|event| {
+            event
+                .as_log()
+                .get("message")
+                .unwrap()
+                .to_string_lossy()
+                .into_owned()
+        })
+        .collect::<Vec<_>>();
+    run_and_assert_sink_compliance(sink, events, &AWS_SINK_TAGS).await;
+    assert_eq!(receiver.await, BatchStatus::Delivered);
+
+    let mut response_lines = Vec::new();
+    for key in get_keys(&bucket, prefix).await {
+        let key_parts = key.split('/').collect::<Vec<_>>();
+        assert_eq!(key_parts.len(), 3);
+        assert_eq!(key_parts[0], "test-prefix");
+        assert!(["region=us-east-1", "region=eu-west-1"].contains(&key_parts[1]));
+
+        let obj = get_object(&bucket, key).await;
+        response_lines.append(&mut get_lines(obj).await);
+    }
+
+    response_lines.sort();
+    lines.sort();
+    assert_eq!(lines, response_lines);
+}
+
 // NOTE: this test doesn't actually validate anything because localstack
 // doesn't enforce the required Content-MD5 header on the request for
 // buckets with object lock enabled
@@ -425,6 +534,7 @@ async fn s3_flush_on_exhaustion() {
         S3SinkConfig {
             bucket: bucket.to_string(),
             key_prefix: random_string(10) + "/date=%F",
+            partition_fields: Vec::new(),
             filename_time_format: default_filename_time_format(),
             filename_append_uuid: true,
             filename_extension: None,
@@ -432,6 +542,7 @@ async fn s3_flush_on_exhaustion() {
             region: RegionOrEndpoint::with_both("us-east-1", s3_address()),
             encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
             compression: Compression::None,
+            batch_encoding: None,
             batch,
             request: TowerRequestConfig::default(),
             tls: Default::default(),
@@ -508,6 +619,7 @@ fn config(bucket: &str, batch_size: usize) -> S3SinkConfig {
     S3SinkConfig {
         bucket: bucket.to_string(),
         key_prefix: random_string(10) + "/date=%F",
+        partition_fields: Vec::new(),
         filename_time_format: default_filename_time_format(),
         filename_append_uuid: true,
         filename_extension: None,
@@ -515,6 +627,7 @@ fn config(bucket: &str, batch_size: usize) -> S3SinkConfig {
         region: RegionOrEndpoint::with_both("us-east-1", s3_address()),
         encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
         compression: Compression::None,
+        batch_encoding: None,
         batch,
         request: TowerRequestConfig::default(),
         tls: Default::default(),
@@ -611,6 +724,16 @@ async fn get_zstd_lines(obj: GetObjectOutput) -> Vec<String> {
     buf_read.lines().map(|l| l.unwrap()).collect()
 }
 
+async fn get_parquet_messages(obj: GetObjectOutput) -> Vec<String> {
+    let body = obj.body.collect().await.unwrap().into_bytes();
+    let reader = SerializedFileReader::new(body).unwrap();
+    reader
+        .get_row_iter(None)
+        .unwrap()
+        .map(|row| row.unwrap().get_string(0).unwrap().clone())
+        .collect()
+}
+
 async fn get_object_output_body(obj: GetObjectOutput) -> impl std::io::Read {
     obj.body.collect().await.unwrap().reader()
 }
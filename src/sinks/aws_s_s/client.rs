@@ -2,15 +2,15 @@ use std::future::Future;
 
 use aws_smithy_runtime_api::client::{orchestrator::HttpResponse, result::SdkError};
 
-use super::{request_builder::SendMessageEntry, service::SendMessageResponse};
+use super::{request_builder::SendMessageBatch, service::SendMessageResponse};
 
 pub(super) trait Client<R>
 where
     R: std::fmt::Debug + std::fmt::Display + std::error::Error,
 {
-    fn send_message(
+    fn send_messages(
         &self,
-        entry: SendMessageEntry,
+        batch: SendMessageBatch,
         byte_size: usize,
     ) -> impl Future<Output = Result<SendMessageResponse, SdkError<R, HttpResponse>>> + Send;
 }
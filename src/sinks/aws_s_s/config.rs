@@ -19,8 +19,8 @@ pub(super) enum BuildError {
     MessageGroupIdMissing,
     #[snafu(display("`message_group_id` is not allowed with non-FIFO queue."))]
     MessageGroupIdNotAllowed,
-    #[snafu(display("invalid topic template: {}", source))]
-    TopicTemplate { source: TemplateParseError },
+    #[snafu(display("invalid message_group_id template: {}", source))]
+    MessageGroupIdTemplate { source: TemplateParseError },
     #[snafu(display("invalid message_deduplication_id template: {}", source))]
     MessageDeduplicationIdTemplate { source: TemplateParseError },
 }
@@ -35,7 +35,8 @@ pub(super) struct BaseSSSinkConfig {
 
     /// The tag that specifies that a message belongs to a specific message group.
     ///
-    /// Can be applied only to FIFO queues.
+    /// This value is a template, and is required for FIFO queues, whose messages are ordered within
+    /// their group. Can be applied only to FIFO queues.
     #[configurable(metadata(docs::examples = "vector"))]
     #[configurable(metadata(docs::examples = "vector-%Y-%m-%d"))]
     pub(super) message_group_id: Option<String>,
@@ -82,7 +83,7 @@ pub(super) fn message_group_id(
 ) -> crate::Result<Option<Template>> {
     match (message_group_id.as_ref(), fifo) {
         (Some(value), true) => Ok(Some(
-            Template::try_from(value.clone()).context(TopicTemplateSnafu)?,
+            Template::try_from(value.clone()).context(MessageGroupIdTemplateSnafu)?,
         )),
         (Some(_), false) => Err(Box::new(BuildError::MessageGroupIdNotAllowed)),
         (None, true) => Err(Box::new(BuildError::MessageGroupIdMissing)),
@@ -93,7 +94,7 @@ pub(super) fn message_deduplication_id(
     message_deduplication_id: Option<String>,
 ) -> crate::Result<Option<Template>> {
     Ok(message_deduplication_id
-        .clone()
         .map(Template::try_from)
-        .transpose()?)
+        .transpose()
+        .context(MessageDeduplicationIdTemplateSnafu)?)
 }
@@ -8,8 +8,8 @@ use crate::{
     event::{Event, EventFinalizers, Finalizable},
     internal_events::TemplateRenderingError,
     sinks::util::{
-        metadata::RequestMetadataBuilder, request_builder::EncodeResult, Compression,
-        EncodedLength, RequestBuilder,
+        metadata::RequestMetadataBuilder, processed_event::ProcessedEvent,
+        request_builder::EncodeResult, Compression, EncodedLength, RequestBuilder,
     },
     template::Template,
 };
@@ -17,36 +17,90 @@ use crate::{
 #[derive(Clone)]
 pub(super) struct SSMetadata {
     pub(super) finalizers: EventFinalizers,
+    pub(super) message_ids: MessageIds,
+}
+
+/// The message group and deduplication IDs rendered for an event.
+#[derive(Clone, Debug, Default)]
+pub(super) struct MessageIds {
     pub(super) message_group_id: Option<String>,
     pub(super) message_deduplication_id: Option<String>,
 }
 
+impl ByteSizeOf for MessageIds {
+    fn allocated_bytes(&self) -> usize {
+        self.message_group_id.size_of() + self.message_deduplication_id.size_of()
+    }
+}
+
+/// The templates of the message group and deduplication IDs.
+#[derive(Clone)]
+pub(super) struct MessageIdTemplates {
+    pub(super) message_group_id: Option<Template>,
+    pub(super) message_deduplication_id: Option<Template>,
+}
+
+impl MessageIdTemplates {
+    fn render(
+        template: Option<&Template>,
+        event: &Event,
+        field: &'static str,
+    ) -> Result<Option<String>, ()> {
+        template
+            .map(|template| {
+                template.render_string(event).map_err(|error| {
+                    emit!(TemplateRenderingError {
+                        error,
+                        field: Some(field),
+                        drop_event: true,
+                    });
+                })
+            })
+            .transpose()
+    }
+
+    /// Renders the message IDs of an event, dropping the event if they fail to render.
+    ///
+    /// FIFO queues and topics refuse messages without a group ID, so these are not sent without
+    /// the IDs they are configured with.
+    pub(super) fn process(&self, event: Event) -> Option<ProcessedEvent<Event, MessageIds>> {
+        let message_group_id =
+            Self::render(self.message_group_id.as_ref(), &event, "message_group_id").ok()?;
+        let message_deduplication_id = Self::render(
+            self.message_deduplication_id.as_ref(),
+            &event,
+            "message_deduplication_id",
+        )
+        .ok()?;
+
+        Some(ProcessedEvent {
+            event,
+            metadata: MessageIds {
+                message_group_id,
+                message_deduplication_id,
+            },
+        })
+    }
+}
+
 #[derive(Clone)]
 pub(super) struct SSRequestBuilder {
     encoder: (Transformer, Encoder<()>),
-    message_group_id: Option<Template>,
-    message_deduplication_id: Option<Template>,
 }
 
 impl SSRequestBuilder {
-    pub(super) fn new(
-        message_group_id: Option<Template>,
-        message_deduplication_id: Option<Template>,
-        encoding_config: EncodingConfig,
-    ) -> crate::Result<Self> {
+    pub(super) fn new(encoding_config: EncodingConfig) -> crate::Result<Self> {
         let transformer = encoding_config.transformer();
         let serializer = encoding_config.build()?;
         let encoder = Encoder::<()>::new(serializer);
 
         Ok(Self {
             encoder: (transformer, encoder),
-            message_group_id,
-            message_deduplication_id,
         })
     }
 }
 
-impl RequestBuilder<Event> for SSRequestBuilder {
+impl RequestBuilder<ProcessedEvent<Event, MessageIds>> for SSRequestBuilder {
     type Metadata = SSMetadata;
     type Events = Event;
     type Encoder = (Transformer, Encoder<()>);
@@ -64,43 +118,14 @@ impl RequestBuilder<Event> for SSRequestBuilder {
 
     fn split_input(
         &self,
-        mut event: Event,
+        input: ProcessedEvent<Event, MessageIds>,
     ) -> (Self::Metadata, RequestMetadataBuilder, Self::Events) {
-        let message_group_id = match self.message_group_id {
-            Some(ref tpl) => match tpl.render_string(&event) {
-                Ok(value) => Some(value),
-                Err(error) => {
-                    emit!(TemplateRenderingError {
-                        error,
-                        field: Some("message_group_id"),
-                        drop_event: true,
-                    });
-                    None
-                }
-            },
-            None => None,
-        };
-        let message_deduplication_id = match self.message_deduplication_id {
-            Some(ref tpl) => match tpl.render_string(&event) {
-                Ok(value) => Some(value),
-                Err(error) => {
-                    emit!(TemplateRenderingError {
-                        error,
-                        field: Some("message_deduplication_id"),
-                        drop_event: true,
-                    });
-                    None
-                }
-            },
-            None => None,
-        };
-
+        let mut event = input.event;
         let builder = RequestMetadataBuilder::from_event(&event);
 
         let metadata = SSMetadata {
             finalizers: event.take_finalizers(),
-            message_group_id,
-            message_deduplication_id,
+            message_ids: input.metadata,
         };
         (metadata, builder, event)
     }
@@ -116,8 +141,8 @@ impl RequestBuilder<Event> for SSRequestBuilder {
 
         SendMessageEntry {
             message_body,
-            message_group_id: client_metadata.message_group_id,
-            message_deduplication_id: client_metadata.message_deduplication_id,
+            message_group_id: client_metadata.message_ids.message_group_id,
+            message_deduplication_id: client_metadata.message_ids.message_deduplication_id,
             finalizers: client_metadata.finalizers,
             metadata,
         }
@@ -162,3 +187,40 @@ impl MetaDescriptive for SendMessageEntry {
         &mut self.metadata
     }
 }
+
+/// A batch of messages sent with a single request.
+#[derive(Clone)]
+pub(super) struct SendMessageBatch {
+    pub(super) entries: Vec<SendMessageEntry>,
+    pub(super) metadata: RequestMetadata,
+}
+
+impl SendMessageBatch {
+    pub(super) fn new(entries: Vec<SendMessageEntry>) -> Self {
+        let metadata =
+            RequestMetadata::from_batch(entries.iter().map(|entry| entry.get_metadata().clone()));
+        Self { entries, metadata }
+    }
+}
+
+impl ByteSizeOf for SendMessageBatch {
+    fn allocated_bytes(&self) -> usize {
+        self.entries.allocated_bytes()
+    }
+}
+
+impl Finalizable for SendMessageBatch {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        self.entries.take_finalizers()
+    }
+}
+
+impl MetaDescriptive for SendMessageBatch {
+    fn get_metadata(&self) -> &RequestMetadata {
+        &self.metadata
+    }
+
+    fn metadata_mut(&mut self) -> &mut RequestMetadata {
+        &mut self.metadata
+    }
+}
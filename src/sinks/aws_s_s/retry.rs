@@ -3,7 +3,10 @@ use std::marker::PhantomData;
 use aws_smithy_runtime_api::client::{orchestrator::HttpResponse, result::SdkError};
 
 use super::service::SendMessageResponse;
-use crate::{aws::is_retriable_error, sinks::util::retries::RetryLogic};
+use crate::{
+    aws::is_retriable_error,
+    sinks::util::retries::{RetryAction, RetryLogic},
+};
 
 #[derive(Debug)]
 pub(super) struct SSRetryLogic<E> {
@@ -31,6 +34,23 @@ where
     fn is_retriable_error(&self, error: &Self::Error) -> bool {
         is_retriable_error(error)
     }
+
+    fn should_retry_response(&self, response: &Self::Response) -> RetryAction {
+        match response.failure_reason() {
+            None => RetryAction::Successful,
+            // The whole batch is sent again. FIFO queues and topics deduplicate the messages that
+            // were already sent, while standard ones receive them again.
+            Some(reason)
+                if response
+                    .failures
+                    .iter()
+                    .any(|failure| !failure.sender_fault) =>
+            {
+                RetryAction::Retry(reason.into())
+            }
+            Some(reason) => RetryAction::DontRetry(reason.into()),
+        }
+    }
 }
 
 impl<E> Clone for SSRetryLogic<E>
@@ -43,3 +63,37 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sinks::aws_s_s::service::SendMessageFailure;
+
+    fn response(sender_faults: &[bool]) -> SendMessageResponse {
+        SendMessageResponse {
+            byte_size: 0,
+            json_byte_size: Default::default(),
+            failures: sender_faults
+                .iter()
+                .map(|sender_fault| SendMessageFailure {
+                    code: "InternalError".to_string(),
+                    message: None,
+                    sender_fault: *sender_fault,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn retries_batches_with_service_faults() {
+        let logic = SSRetryLogic::<std::io::Error>::new();
+
+        assert!(logic.should_retry_response(&response(&[])).is_successful());
+        assert!(logic
+            .should_retry_response(&response(&[true, false]))
+            .is_retryable());
+        assert!(logic
+            .should_retry_response(&response(&[true]))
+            .is_not_retryable());
+    }
+}
@@ -9,7 +9,7 @@ use vector_lib::request_metadata::GroupedCountByteSize;
 use vector_lib::stream::DriverResponse;
 use vector_lib::{event::EventStatus, ByteSizeOf};
 
-use super::{client::Client, request_builder::SendMessageEntry};
+use super::{client::Client, request_builder::SendMessageBatch};
 
 pub(super) struct SSService<C, E>
 where
@@ -46,7 +46,7 @@ where
     }
 }
 
-impl<C, E> Service<SendMessageEntry> for SSService<C, E>
+impl<C, E> Service<SendMessageBatch> for SSService<C, E>
 where
     C: Client<E> + Clone + Send + Sync + 'static,
     E: std::fmt::Debug + std::fmt::Display + std::error::Error + Sync + Send + 'static,
@@ -61,22 +61,52 @@ where
     }
 
     // Emission of internal events for errors and dropped events is handled upstream by the caller.
-    fn call(&mut self, entry: SendMessageEntry) -> Self::Future {
-        let byte_size = entry.size_of();
+    fn call(&mut self, batch: SendMessageBatch) -> Self::Future {
+        let byte_size = batch.size_of();
         let client = self.client.clone();
 
-        Box::pin(async move { client.send_message(entry, byte_size).await })
+        Box::pin(async move { client.send_messages(batch, byte_size).await })
     }
 }
 
+/// A message of a batch that failed to be sent.
+#[derive(Debug)]
+pub(super) struct SendMessageFailure {
+    pub(super) code: String,
+    pub(super) message: Option<String>,
+    /// Whether the message failed because of the request, rather than of the service.
+    pub(super) sender_fault: bool,
+}
+
 pub(super) struct SendMessageResponse {
     pub(crate) byte_size: usize,
     pub(crate) json_byte_size: GroupedCountByteSize,
+    pub(super) failures: Vec<SendMessageFailure>,
+}
+
+impl SendMessageResponse {
+    /// Describes the first failure of the batch.
+    pub(super) fn failure_reason(&self) -> Option<String> {
+        self.failures.first().map(|failure| {
+            format!(
+                "{} of the messages failed to be sent: {}: {}",
+                self.failures.len(),
+                failure.code,
+                failure.message.as_deref().unwrap_or("no message")
+            )
+        })
+    }
 }
 
 impl DriverResponse for SendMessageResponse {
     fn event_status(&self) -> EventStatus {
-        EventStatus::Delivered
+        if self.failures.is_empty() {
+            EventStatus::Delivered
+        } else if self.failures.iter().all(|failure| failure.sender_fault) {
+            EventStatus::Rejected
+        } else {
+            EventStatus::Errored
+        }
     }
 
     fn events_sent(&self) -> &GroupedCountByteSize {
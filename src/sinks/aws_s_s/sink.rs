@@ -1,13 +1,23 @@
-use super::{client::Client, request_builder::SSRequestBuilder, service::SSService};
+use super::{
+    client::Client,
+    request_builder::{MessageIdTemplates, SSRequestBuilder, SendMessageBatch},
+    service::SSService,
+};
 use crate::sinks::aws_s_s::retry::SSRetryLogic;
 use crate::sinks::prelude::*;
 
+/// The maximum number of messages of a batch request.
+pub(super) const MAX_BATCH_EVENTS: usize = 10;
+
+/// The maximum size of the messages of a batch request.
+pub(super) const MAX_BATCH_BYTES: usize = 262_144;
+
 #[derive(Clone, Copy, Debug, Default)]
-pub(crate) struct SqsSinkDefaultBatchSettings;
+pub(crate) struct SSSinkDefaultBatchSettings;
 
-impl SinkBatchSettings for SqsSinkDefaultBatchSettings {
+impl SinkBatchSettings for SSSinkDefaultBatchSettings {
     const MAX_EVENTS: Option<usize> = Some(1);
-    const MAX_BYTES: Option<usize> = Some(262_144);
+    const MAX_BYTES: Option<usize> = Some(MAX_BATCH_BYTES);
     const TIMEOUT_SECS: f64 = 1.0;
 }
 
@@ -17,7 +27,9 @@ where
     C: Client<E> + Clone + Send + Sync + 'static,
     E: std::fmt::Debug + std::fmt::Display + std::error::Error + Sync + Send + 'static,
 {
+    message_ids: MessageIdTemplates,
     request_builder: SSRequestBuilder,
+    batch_settings: BatcherSettings,
     service: SSService<C, E>,
     request: TowerRequestConfig,
 }
@@ -28,12 +40,16 @@ where
     E: std::fmt::Debug + std::fmt::Display + std::error::Error + Sync + Send + 'static,
 {
     pub(super) fn new(
+        message_ids: MessageIdTemplates,
         request_builder: SSRequestBuilder,
+        batch_settings: BatcherSettings,
         request: TowerRequestConfig,
         publisher: C,
     ) -> crate::Result<Self> {
         Ok(SSSink {
+            message_ids,
             request_builder,
+            batch_settings,
             service: SSService::new(publisher),
             request,
         })
@@ -46,7 +62,11 @@ where
             .settings(request, retry_logic)
             .service(self.service);
 
+        let message_ids = self.message_ids;
+        let batch_settings = self.batch_settings;
+
         input
+            .filter_map(|event| future::ready(message_ids.process(event)))
             .request_builder(
                 default_request_builder_concurrency_limit(),
                 self.request_builder,
@@ -57,6 +77,10 @@ where
                 })
                 .ok()
             })
+            // Messages are batched in the order they are received, which is kept within the
+            // message groups of FIFO queues and topics.
+            .batched(batch_settings.as_byte_size_config())
+            .map(SendMessageBatch::new)
             .into_driver(service)
             .run()
             .await
@@ -1,7 +1,6 @@
-use super::{Client, SendMessageEntry, SendMessageResponse};
+use super::{Client, SendMessageBatch, SendMessageResponse};
 use aws_sdk_sns::operation::publish::PublishError;
 use aws_smithy_runtime_api::client::{orchestrator::HttpResponse, result::SdkError};
-use futures::TryFutureExt;
 use tracing::Instrument;
 
 #[derive(Clone, Debug)]
@@ -17,26 +16,31 @@ impl SnsMessagePublisher {
 }
 
 impl Client<PublishError> for SnsMessagePublisher {
-    async fn send_message(
+    async fn send_messages(
         &self,
-        entry: SendMessageEntry,
+        batch: SendMessageBatch,
         byte_size: usize,
     ) -> Result<SendMessageResponse, SdkError<PublishError, HttpResponse>> {
-        self.client
-            .publish()
-            .message(entry.message_body)
-            .set_message_group_id(entry.message_group_id)
-            .set_message_deduplication_id(entry.message_deduplication_id)
-            .topic_arn(self.topic_arn.clone())
-            .send()
-            .map_ok(|_| SendMessageResponse {
-                byte_size,
-                json_byte_size: entry
-                    .metadata
-                    .events_estimated_json_encoded_byte_size()
-                    .clone(),
-            })
-            .instrument(info_span!("request").or_current())
-            .await
+        // Batches of the `aws_sns` sink hold a single message.
+        for entry in batch.entries {
+            self.client
+                .publish()
+                .message(entry.message_body)
+                .set_message_group_id(entry.message_group_id)
+                .set_message_deduplication_id(entry.message_deduplication_id)
+                .topic_arn(self.topic_arn.clone())
+                .send()
+                .instrument(info_span!("request").or_current())
+                .await?;
+        }
+
+        Ok(SendMessageResponse {
+            byte_size,
+            json_byte_size: batch
+                .metadata
+                .events_estimated_json_encoded_byte_size()
+                .clone(),
+            failures: Vec::new(),
+        })
     }
 }
@@ -9,10 +9,11 @@ use vector_lib::configurable::configurable_component;
 
 use super::{
     client::SnsMessagePublisher, message_deduplication_id, message_group_id, BaseSSSinkConfig,
-    SSRequestBuilder, SSSink,
+    MessageIdTemplates, SSRequestBuilder, SSSink, SSSinkDefaultBatchSettings,
 };
 use crate::aws::create_client;
 use crate::aws::ClientBuilder;
+use crate::sinks::util::BatchConfig;
 
 /// Configuration for the `aws_sns` sink.
 #[configurable_component(sink(
@@ -78,12 +79,17 @@ impl SinkConfig for SnsSinkConfig {
         let message_deduplication_id =
             message_deduplication_id(self.base_config.message_deduplication_id.clone());
 
+        // Messages are published one at a time.
+        let batch_settings =
+            BatchConfig::<SSSinkDefaultBatchSettings>::default().into_batcher_settings()?;
+
         let sink = SSSink::new(
-            SSRequestBuilder::new(
-                message_group_id?,
-                message_deduplication_id?,
-                self.base_config.encoding.clone(),
-            )?,
+            MessageIdTemplates {
+                message_group_id: message_group_id?,
+                message_deduplication_id: message_deduplication_id?,
+            },
+            SSRequestBuilder::new(self.base_config.encoding.clone())?,
+            batch_settings,
             self.base_config.request,
             publisher,
         )?;
@@ -6,7 +6,7 @@ mod integration_tests;
 use super::{
     client::Client,
     config::{message_deduplication_id, message_group_id, BaseSSSinkConfig},
-    request_builder::{SSRequestBuilder, SendMessageEntry},
+    request_builder::{MessageIdTemplates, SSRequestBuilder, SendMessageBatch},
     service::SendMessageResponse,
-    sink::SSSink,
+    sink::{SSSink, SSSinkDefaultBatchSettings},
 };
@@ -1,5 +1,7 @@
-use super::{Client, SendMessageEntry, SendMessageResponse};
-use aws_sdk_sqs::operation::send_message::SendMessageError;
+use super::{Client, SendMessageBatch, SendMessageFailure, SendMessageResponse};
+use aws_sdk_sqs::{
+    operation::send_message_batch::SendMessageBatchError, types::SendMessageBatchRequestEntry,
+};
 use aws_smithy_runtime_api::client::{orchestrator::HttpResponse, result::SdkError};
 use futures::TryFutureExt;
 use tracing::Instrument;
@@ -16,25 +18,48 @@ impl SqsMessagePublisher {
     }
 }
 
-impl Client<SendMessageError> for SqsMessagePublisher {
-    async fn send_message(
+impl Client<SendMessageBatchError> for SqsMessagePublisher {
+    async fn send_messages(
         &self,
-        entry: SendMessageEntry,
+        batch: SendMessageBatch,
         byte_size: usize,
-    ) -> Result<SendMessageResponse, SdkError<SendMessageError, HttpResponse>> {
+    ) -> Result<SendMessageResponse, SdkError<SendMessageBatchError, HttpResponse>> {
+        // The IDs of the entries only need to be unique within the batch.
+        let entries = batch
+            .entries
+            .into_iter()
+            .enumerate()
+            .map(|(id, entry)| {
+                SendMessageBatchRequestEntry::builder()
+                    .id(id.to_string())
+                    .message_body(entry.message_body)
+                    .set_message_group_id(entry.message_group_id)
+                    .set_message_deduplication_id(entry.message_deduplication_id)
+                    .build()
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(SdkError::construction_failure)?;
+
         self.client
-            .send_message()
-            .message_body(entry.message_body)
-            .set_message_group_id(entry.message_group_id)
-            .set_message_deduplication_id(entry.message_deduplication_id)
+            .send_message_batch()
+            .set_entries(Some(entries))
             .queue_url(self.queue_url.clone())
             .send()
-            .map_ok(|_| SendMessageResponse {
+            .map_ok(|output| SendMessageResponse {
                 byte_size,
-                json_byte_size: entry
+                json_byte_size: batch
                     .metadata
                     .events_estimated_json_encoded_byte_size()
                     .clone(),
+                failures: output
+                    .failed()
+                    .iter()
+                    .map(|failed| SendMessageFailure {
+                        code: failed.code().to_string(),
+                        message: failed.message().map(ToString::to_string),
+                        sender_fault: failed.sender_fault(),
+                    })
+                    .collect(),
             })
             .instrument(info_span!("request").or_current())
             .await
@@ -9,9 +9,10 @@ use vector_lib::configurable::configurable_component;
 
 use super::{
     client::SqsMessagePublisher, message_deduplication_id, message_group_id, BaseSSSinkConfig,
-    SSRequestBuilder, SSSink,
+    MessageIdTemplates, SSRequestBuilder, SSSink, SSSinkDefaultBatchSettings, MAX_BATCH_BYTES,
+    MAX_BATCH_EVENTS,
 };
-use crate::{aws::create_client, common::sqs::SqsClientBuilder};
+use crate::{aws::create_client, common::sqs::SqsClientBuilder, sinks::util::BatchConfig};
 
 /// Configuration for the `aws_sqs` sink.
 #[configurable_component(sink(
@@ -33,6 +34,10 @@ pub(super) struct SqsSinkConfig {
 
     #[serde(flatten)]
     pub(super) base_config: BaseSSSinkConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub(super) batch: BatchConfig<SSSinkDefaultBatchSettings>,
 }
 
 impl GenerateConfig for SqsSinkConfig {
@@ -78,12 +83,20 @@ impl SinkConfig for SqsSinkConfig {
         let message_deduplication_id =
             message_deduplication_id(self.base_config.message_deduplication_id.clone());
 
+        let batch_settings = self
+            .batch
+            .validate()?
+            .limit_max_events(MAX_BATCH_EVENTS)?
+            .limit_max_bytes(MAX_BATCH_BYTES)?
+            .into_batcher_settings()?;
+
         let sink = SSSink::new(
-            SSRequestBuilder::new(
-                message_group_id?,
-                message_deduplication_id?,
-                self.base_config.encoding.clone(),
-            )?,
+            MessageIdTemplates {
+                message_group_id: message_group_id?,
+                message_deduplication_id: message_deduplication_id?,
+            },
+            SSRequestBuilder::new(self.base_config.encoding.clone())?,
+            batch_settings,
             self.base_config.request,
             publisher,
         )?;
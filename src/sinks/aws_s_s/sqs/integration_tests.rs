@@ -14,6 +14,7 @@ use crate::{
     aws::{create_client, AwsAuthentication, RegionOrEndpoint},
     common::sqs::SqsClientBuilder,
     config::ProxyConfig,
+    sinks::util::BatchConfig,
     test_util::{
         components::{run_and_assert_sink_compliance, AWS_SINK_TAGS},
         random_lines_with_stream, random_string,
@@ -63,6 +64,7 @@ async fn sqs_send_message_batch() {
         region: RegionOrEndpoint::with_both("us-east-1", sqs_address().as_str()),
         queue_url: queue_url.clone(),
         base_config,
+        batch: Default::default(),
     };
 
     healthcheck(client.clone(), config.queue_url.clone())
@@ -101,6 +103,63 @@ async fn sqs_send_message_batch() {
     assert_eq!(input_lines.len(), response.messages.unwrap().len());
 }
 
+#[tokio::test]
+async fn sqs_send_message_batch_fifo() {
+    let queue_name = format!("{}.fifo", gen_queue_name());
+    ensure_queue(queue_name.clone()).await;
+    let queue_url = get_queue_url(queue_name.clone()).await;
+
+    let client = create_test_client().await;
+
+    let base_config = BaseSSSinkConfig {
+        encoding: TextSerializerConfig::default().into(),
+        message_group_id: Some("vector".to_string()),
+        message_deduplication_id: Some("{{ message }}".to_string()),
+        request: Default::default(),
+        tls: Default::default(),
+        assume_role: None,
+        auth: Default::default(),
+        acknowledgements: Default::default(),
+    };
+
+    let mut batch = BatchConfig::default();
+    batch.max_events = Some(10);
+
+    let config = SqsSinkConfig {
+        region: RegionOrEndpoint::with_both("us-east-1", sqs_address().as_str()),
+        queue_url: queue_url.clone(),
+        base_config,
+        batch,
+    };
+
+    let cx = SinkContext::default();
+
+    let sink = config.build(cx).await.unwrap().0;
+
+    let (input_lines, events) = random_lines_with_stream(100, 10, None);
+    run_and_assert_sink_compliance(sink, events, &AWS_SINK_TAGS).await;
+
+    sleep(Duration::from_secs(1)).await;
+
+    let response = client
+        .receive_message()
+        .max_number_of_messages(input_lines.len() as i32)
+        .queue_url(queue_url)
+        .send()
+        .await
+        .unwrap();
+
+    let output_lines = response
+        .messages
+        .unwrap()
+        .into_iter()
+        .map(|e| e.body.unwrap())
+        .collect::<Vec<_>>();
+
+    // The messages of a group are received in the order they were sent.
+    assert_eq!(output_lines, input_lines);
+}
+
 async fn ensure_queue(queue_name: String) {
     let client = create_test_client().await;
 
@@ -7,7 +7,7 @@ mod integration_tests;
 use super::{
     client::Client,
     config::{message_deduplication_id, message_group_id, BaseSSSinkConfig},
-    request_builder::{SSRequestBuilder, SendMessageEntry},
-    service::SendMessageResponse,
-    sink::SSSink,
+    request_builder::{MessageIdTemplates, SSRequestBuilder, SendMessageBatch},
+    service::{SendMessageFailure, SendMessageResponse},
+    sink::{SSSink, SSSinkDefaultBatchSettings, MAX_BATCH_BYTES, MAX_BATCH_EVENTS},
 };
@@ -0,0 +1,186 @@
+//! Configuration for the `axiom` sink.
+
+use bytes::Bytes;
+use http::StatusCode;
+use vector_lib::codecs::{encoding::Framer, JsonSerializerConfig, NewlineDelimitedEncoderConfig};
+use vector_lib::{config::log_schema, sensitive_string::SensitiveString};
+
+use super::{
+    request_builder::AxiomRequestBuilder,
+    service::{error_reason, AxiomRetryLogic, AxiomServiceRequestBuilder},
+    sink::AxiomSink,
+};
+use crate::{
+    http::HttpClient,
+    sinks::{
+        prelude::*,
+        util::http::{validate_headers, HttpService, RequestConfig},
+    },
+};
+
+static CLOUD_URL: &str = "https://api.axiom.co";
+
+/// The default batch settings of the `axiom` sink.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AxiomDefaultBatchSettings;
+
+impl SinkBatchSettings for AxiomDefaultBatchSettings {
+    const MAX_EVENTS: Option<usize> = None;
+    const MAX_BYTES: Option<usize> = Some(10_000_000);
+    const TIMEOUT_SECS: f64 = 1.0;
+}
+
+/// Configuration for the `axiom` sink.
+#[configurable_component(sink("axiom", "Deliver log events to Axiom."))]
+#[derive(Clone, Debug)]
+pub struct AxiomConfig {
+    /// URI of the Axiom endpoint to send data to.
+    ///
+    /// Only required if not using Axiom Cloud.
+    #[configurable(validation(format = "uri"))]
+    #[configurable(metadata(docs::examples = "https://axiom.my-domain.com"))]
+    #[configurable(metadata(docs::examples = "${AXIOM_URL}"))]
+    url: Option<String>,
+
+    /// The Axiom organization ID.
+    ///
+    /// Only required when using personal tokens.
+    #[configurable(metadata(docs::examples = "${AXIOM_ORG_ID}"))]
+    #[configurable(metadata(docs::examples = "123abc"))]
+    org_id: Option<String>,
+
+    /// The Axiom API token.
+    #[configurable(metadata(docs::examples = "${AXIOM_TOKEN}"))]
+    #[configurable(metadata(docs::examples = "123abc"))]
+    token: SensitiveString,
+
+    /// The Axiom dataset to write to.
+    #[configurable(metadata(docs::examples = "${AXIOM_DATASET}"))]
+    #[configurable(metadata(docs::examples = "vector.dev"))]
+    #[configurable(metadata(docs::examples = "{{ service }}-logs"))]
+    dataset: Template,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    request: RequestConfig,
+
+    #[configurable(derived)]
+    #[serde(default = "Compression::zstd_default")]
+    compression: Compression,
+
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "crate::serde::is_default")]
+    encoding: Transformer,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    batch: BatchConfig<AxiomDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::is_default"
+    )]
+    acknowledgements: AcknowledgementsConfig,
+}
+
+impl GenerateConfig for AxiomConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"token = "${AXIOM_TOKEN}"
+            dataset = "${AXIOM_DATASET}"
+            url = "${AXIOM_URL}"
+            org_id = "${AXIOM_ORG_ID}""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "axiom")]
+impl SinkConfig for AxiomConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let batch_settings = self.batch.validate()?.into_batcher_settings()?;
+
+        let tls_settings = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new(tls_settings, cx.proxy())?;
+
+        let service_request_builder = AxiomServiceRequestBuilder {
+            endpoint: self.url.clone().unwrap_or_else(|| CLOUD_URL.to_string()),
+            token: self.token.clone(),
+            org_id: self.org_id.clone(),
+            timestamp_field: log_schema().timestamp_key().map(ToString::to_string),
+            compression: self.compression,
+            headers: validate_headers(&self.request.headers)?,
+        };
+
+        let healthcheck = healthcheck(
+            client.clone(),
+            service_request_builder.clone(),
+            self.dataset.clone(),
+        )
+        .boxed();
+
+        let service: HttpService<AxiomServiceRequestBuilder, String> =
+            HttpService::new(client, service_request_builder);
+
+        let service = ServiceBuilder::new()
+            .settings(self.request.tower.into_settings(), AxiomRetryLogic)
+            .service(service);
+
+        let sink = AxiomSink {
+            service,
+            batch_settings,
+            dataset: self.dataset.clone(),
+            request_builder: AxiomRequestBuilder {
+                encoder: (
+                    self.encoding.clone(),
+                    Encoder::<Framer>::new(
+                        NewlineDelimitedEncoderConfig.build().into(),
+                        JsonSerializerConfig::default().build().into(),
+                    ),
+                ),
+                compression: self.compression,
+            },
+        };
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::new(DataType::Metric | DataType::Log)
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+/// Checks that the token can ingest into the dataset by ingesting no events into it.
+///
+/// This is skipped when the dataset is templated, as it is only known once events are rendered.
+async fn healthcheck(
+    client: HttpClient,
+    request_builder: AxiomServiceRequestBuilder,
+    dataset: Template,
+) -> crate::Result<()> {
+    if dataset.is_dynamic() {
+        return Ok(());
+    }
+
+    let request = request_builder
+        .ingest_request(dataset.get_ref(), Compression::None, Bytes::new())?
+        .map(hyper::Body::from);
+    let response = client.send(request).await?;
+
+    let status = response.status();
+    let body = hyper::body::to_bytes(response.into_body()).await?;
+    match status {
+        StatusCode::OK => Ok(()),
+        status => Err(error_reason(status, &body).into()),
+    }
+}
@@ -0,0 +1,19 @@
+//! The Axiom [`vector_lib::sink::VectorSink`].
+//!
+//! This module contains the [`vector_lib::sink::VectorSink`] instance that is responsible for
+//! taking a stream of [`vector_lib::event::Event`]s and ingesting them into Axiom datasets
+//! through its native ingest API.
+
+mod config;
+mod request_builder;
+mod service;
+mod sink;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "axiom-integration-tests")]
+#[cfg(test)]
+mod integration_tests;
+
+pub use self::config::AxiomConfig;
@@ -0,0 +1,249 @@
+//! Service implementation for the `axiom` sink.
+
+use bytes::Bytes;
+use http::{
+    header::{AUTHORIZATION, CONTENT_ENCODING, CONTENT_LENGTH, CONTENT_TYPE},
+    HeaderName, HeaderValue, Request, StatusCode, Uri,
+};
+use indexmap::IndexMap;
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+use serde::Deserialize;
+use snafu::ResultExt;
+use vector_lib::sensitive_string::SensitiveString;
+
+use crate::{
+    http::HttpError,
+    sinks::{
+        prelude::*,
+        util::{
+            http::{HttpRequest, HttpResponse, HttpServiceRequestBuilder},
+            retries::RetryAction,
+        },
+        HTTPRequestBuilderSnafu, UriParseSnafu,
+    },
+};
+
+const HTTP_HEADER_AXIOM_ORG_ID: &str = "X-Axiom-Org-Id";
+
+/// The status of an ingest, as returned by Axiom.
+#[derive(Debug, Deserialize)]
+struct IngestStatus {
+    #[serde(default)]
+    ingested: u64,
+    #[serde(default)]
+    failed: u64,
+    #[serde(default)]
+    failures: Vec<IngestFailure>,
+}
+
+#[derive(Debug, Deserialize)]
+struct IngestFailure {
+    error: String,
+}
+
+/// The body of the error responses of Axiom.
+#[derive(Debug, Deserialize)]
+struct ErrorResponse {
+    message: String,
+}
+
+/// Describes why Axiom refused a request, from its status and the message of its body.
+pub(super) fn error_reason(status: StatusCode, body: &[u8]) -> String {
+    let message = serde_json::from_slice::<ErrorResponse>(body)
+        .map(|response| response.message)
+        .unwrap_or_else(|_| String::from_utf8_lossy(body).into_owned());
+    let reason = match status {
+        StatusCode::UNAUTHORIZED => "invalid API token",
+        StatusCode::FORBIDDEN => "the API token is not allowed to ingest into the dataset",
+        StatusCode::NOT_FOUND => "dataset not found",
+        StatusCode::PAYLOAD_TOO_LARGE => "request too large, lower `batch.max_bytes`",
+        StatusCode::TOO_MANY_REQUESTS => "rate limited",
+        _ if status.is_server_error() => "server error",
+        _ => "client error",
+    };
+    format!("{}, {}: {}", reason, status, message)
+}
+
+#[derive(Debug, Default, Clone)]
+pub(super) struct AxiomRetryLogic;
+
+impl RetryLogic for AxiomRetryLogic {
+    type Error = HttpError;
+    type Response = HttpResponse;
+
+    fn is_retriable_error(&self, _error: &Self::Error) -> bool {
+        true
+    }
+
+    fn should_retry_response(&self, response: &Self::Response) -> RetryAction {
+        let status = response.http_response.status();
+        let body = response.http_response.body();
+
+        match status {
+            StatusCode::TOO_MANY_REQUESTS => RetryAction::Retry(error_reason(status, body).into()),
+            _ if status.is_server_error() => RetryAction::Retry(error_reason(status, body).into()),
+            _ if status.is_success() => match serde_json::from_slice::<IngestStatus>(body) {
+                // Axiom ingests the events it accepts even when others fail, such as those with
+                // too many fields, so the batch is not retried.
+                Ok(ingest) if ingest.failed > 0 => RetryAction::DontRetry(
+                    format!(
+                        "{} of {} events failed to be ingested: {}",
+                        ingest.failed,
+                        ingest.ingested + ingest.failed,
+                        ingest
+                            .failures
+                            .first()
+                            .map_or("unknown error", |failure| failure.error.as_str())
+                    )
+                    .into(),
+                ),
+                _ => RetryAction::Successful,
+            },
+            _ => RetryAction::DontRetry(error_reason(status, body).into()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct AxiomServiceRequestBuilder {
+    pub(super) endpoint: String,
+    pub(super) token: SensitiveString,
+    pub(super) org_id: Option<String>,
+    pub(super) timestamp_field: Option<String>,
+    pub(super) compression: Compression,
+    pub(super) headers: IndexMap<HeaderName, HeaderValue>,
+}
+
+impl AxiomServiceRequestBuilder {
+    /// Builds an ingest request of ndjson events into a dataset.
+    pub(super) fn ingest_request(
+        &self,
+        dataset: &str,
+        compression: Compression,
+        payload: Bytes,
+    ) -> crate::Result<Request<Bytes>> {
+        let uri = ingest_uri(&self.endpoint, dataset, self.timestamp_field.as_deref())?;
+
+        let mut builder = Request::post(&uri)
+            .header(CONTENT_TYPE, "application/x-ndjson")
+            .header(CONTENT_LENGTH, payload.len())
+            .header(AUTHORIZATION, format!("Bearer {}", self.token.inner()));
+        if let Some(ce) = compression.content_encoding() {
+            builder = builder.header(CONTENT_ENCODING, ce);
+        }
+        if let Some(org_id) = &self.org_id {
+            builder = builder.header(HTTP_HEADER_AXIOM_ORG_ID, org_id);
+        }
+        for (name, value) in &self.headers {
+            builder = builder.header(name, value);
+        }
+
+        builder
+            .body(payload)
+            .context(HTTPRequestBuilderSnafu)
+            .map_err(Into::into)
+    }
+}
+
+impl HttpServiceRequestBuilder<String> for AxiomServiceRequestBuilder {
+    fn build(&self, mut request: HttpRequest<String>) -> Result<Request<Bytes>, crate::Error> {
+        let payload = request.take_payload();
+        self.ingest_request(request.get_additional_metadata(), self.compression, payload)
+    }
+}
+
+/// The URI of the ingest API of a dataset.
+fn ingest_uri(endpoint: &str, dataset: &str, timestamp_field: Option<&str>) -> crate::Result<Uri> {
+    let mut uri = format!(
+        "{}/v1/datasets/{}/ingest",
+        endpoint.trim_end_matches('/'),
+        utf8_percent_encode(dataset, NON_ALPHANUMERIC)
+    );
+    if let Some(timestamp_field) = timestamp_field {
+        let query = url::form_urlencoded::Serializer::new(String::new())
+            .append_pair("timestamp-field", timestamp_field)
+            .finish();
+        uri.push('?');
+        uri.push_str(&query);
+    }
+
+    uri.parse::<Uri>()
+        .context(UriParseSnafu)
+        .map_err(Into::into)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(status: StatusCode, body: &'static str) -> HttpResponse {
+        HttpResponse {
+            http_response: http::Response::builder()
+                .status(status)
+                .body(Bytes::from(body))
+                .unwrap(),
+            events_byte_size: Default::default(),
+            raw_byte_size: 0,
+        }
+    }
+
+    #[test]
+    fn ingest_uris() {
+        assert_eq!(
+            ingest_uri("https://api.axiom.co", "vector.dev", Some("timestamp")).unwrap(),
+            "https://api.axiom.co/v1/datasets/vector%2Edev/ingest?timestamp-field=timestamp"
+        );
+        assert_eq!(
+            ingest_uri("https://axiom.my-domain.com/", "logs", None).unwrap(),
+            "https://axiom.my-domain.com/v1/datasets/logs/ingest"
+        );
+    }
+
+    #[test]
+    fn retries_transient_errors() {
+        let logic = AxiomRetryLogic;
+
+        assert!(logic
+            .should_retry_response(&response(StatusCode::TOO_MANY_REQUESTS, ""))
+            .is_retryable());
+        assert!(logic
+            .should_retry_response(&response(StatusCode::BAD_GATEWAY, ""))
+            .is_retryable());
+        assert!(logic
+            .should_retry_response(&response(
+                StatusCode::FORBIDDEN,
+                r#"{"code": 403, "message": "Forbidden"}"#
+            ))
+            .is_not_retryable());
+        assert!(logic
+            .should_retry_response(&response(StatusCode::NOT_FOUND, ""))
+            .is_not_retryable());
+        assert!(logic
+            .should_retry_response(&response(
+                StatusCode::OK,
+                r#"{"ingested": 2, "failed": 0, "failures": []}"#
+            ))
+            .is_successful());
+        assert!(logic
+            .should_retry_response(&response(
+                StatusCode::OK,
+                r#"{"ingested": 1, "failed": 1, "failures": [{"timestamp": "2024-01-01T00:00:00Z", "error": "too many fields"}]}"#
+            ))
+            .is_not_retryable());
+    }
+
+    #[test]
+    fn describes_errors() {
+        assert_eq!(
+            error_reason(
+                StatusCode::UNAUTHORIZED,
+                br#"{"code": 401, "message": "invalid token"}"#
+            ),
+            "invalid API token, 401 Unauthorized: invalid token"
+        );
+        assert_eq!(
+            error_reason(StatusCode::BAD_REQUEST, b"bad request"),
+            "client error, 400 Bad Request: bad request"
+        );
+    }
+}
@@ -0,0 +1,96 @@
+//! Implementation of the `axiom` sink.
+
+use super::request_builder::AxiomRequestBuilder;
+use crate::sinks::{
+    prelude::*,
+    util::http::{HttpJsonBatchSizer, HttpRequest},
+};
+
+/// Partitions events by the dataset they are ingested into.
+struct DatasetPartitioner {
+    dataset: Template,
+}
+
+impl Partitioner for DatasetPartitioner {
+    type Item = Event;
+    type Key = Option<String>;
+
+    fn partition(&self, item: &Self::Item) -> Self::Key {
+        self.dataset
+            .render_string(item)
+            .map_err(|error| {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some("dataset"),
+                    drop_event: true,
+                });
+            })
+            .ok()
+    }
+}
+
+pub(super) struct AxiomSink<S> {
+    pub(super) service: S,
+    pub(super) batch_settings: BatcherSettings,
+    pub(super) dataset: Template,
+    pub(super) request_builder: AxiomRequestBuilder,
+}
+
+impl<S> AxiomSink<S>
+where
+    S: Service<HttpRequest<String>> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: std::fmt::Debug + Into<crate::Error> + Send,
+{
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let batch_settings = self.batch_settings;
+
+        input
+            // Batch the input stream by dataset, with size calculation based on the estimated
+            // encoded json size.
+            .batched_partitioned(
+                DatasetPartitioner {
+                    dataset: self.dataset,
+                },
+                || batch_settings.as_item_size_config(HttpJsonBatchSizer),
+            )
+            // Drop the events the dataset couldn't be rendered for.
+            .filter_map(
+                |(dataset, batch)| async move { dataset.map(move |dataset| (dataset, batch)) },
+            )
+            // Build requests with default concurrency limit.
+            .request_builder(
+                default_request_builder_concurrency_limit(),
+                self.request_builder,
+            )
+            // Filter out any errors that occurred in the request building.
+            .filter_map(|request| async move {
+                match request {
+                    Err(error) => {
+                        emit!(SinkRequestBuildError { error });
+                        None
+                    }
+                    Ok(req) => Some(req),
+                }
+            })
+            // Generate the driver that will send requests and handle retries,
+            // event finalization, and logging/internal metric reporting.
+            .into_driver(self.service)
+            .run()
+            .await
+    }
+}
+
+#[async_trait::async_trait]
+impl<S> StreamSink<Event> for AxiomSink<S>
+where
+    S: Service<HttpRequest<String>> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: std::fmt::Debug + Into<crate::Error> + Send,
+{
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
@@ -0,0 +1,134 @@
+//! Unit tests for the `axiom` sink.
+
+use std::io::Read;
+
+use bytes::Buf;
+use futures::{stream, StreamExt};
+use http::{Response, StatusCode};
+use hyper::Body;
+use indoc::indoc;
+use serde_json::json;
+
+use super::config::AxiomConfig;
+use crate::{
+    sinks::{prelude::*, util::test::build_test_server_generic},
+    test_util::{
+        components::{run_and_assert_sink_compliance, HTTP_SINK_TAGS},
+        next_addr,
+    },
+};
+
+fn event(service: &str, message: &str) -> Event {
+    let mut log = LogEvent::from(message);
+    log.insert("service", service);
+    log.into()
+}
+
+#[test]
+fn generate_config() {
+    crate::test_util::test_generate_config::<AxiomConfig>();
+}
+
+#[tokio::test]
+async fn ingests_zstd_compressed_ndjson_into_datasets() {
+    let addr = next_addr();
+    let (rx, _trigger, server) = build_test_server_generic(addr, || {
+        Response::builder()
+            .status(StatusCode::OK)
+            .body(Body::from(
+                r#"{"ingested": 1, "failed": 0, "failures": []}"#,
+            ))
+            .unwrap()
+    });
+    tokio::spawn(server);
+
+    let config: AxiomConfig = toml::from_str(&format!(
+        indoc! {r#"
+            url = "http://{}"
+            token = "xaat-1234"
+            org_id = "vector-org"
+            dataset = "{{{{ service }}}}-logs"
+            encoding.only_fields = ["message"]
+        "#},
+        addr
+    ))
+    .unwrap();
+    let (sink, _healthcheck) = config.build(SinkContext::default()).await.unwrap();
+
+    let events = vec![
+        event("web", "disk full"),
+        event("api", "out of memory"),
+        event("web", "disk still full"),
+    ];
+    run_and_assert_sink_compliance(sink, stream::iter(events), &HTTP_SINK_TAGS).await;
+
+    let mut requests = rx
+        .take(2)
+        .map(|(parts, body)| {
+            assert_eq!(parts.method, "POST");
+            assert_eq!(parts.uri.query(), Some("timestamp-field=timestamp"));
+            assert_eq!(parts.headers["authorization"], "Bearer xaat-1234");
+            assert_eq!(parts.headers["x-axiom-org-id"], "vector-org");
+            assert_eq!(parts.headers["content-type"], "application/x-ndjson");
+            assert_eq!(parts.headers["content-encoding"], "zstd");
+
+            let mut ndjson = String::new();
+            zstd::Decoder::new(body.reader())
+                .unwrap()
+                .read_to_string(&mut ndjson)
+                .unwrap();
+            let events = ndjson
+                .lines()
+                .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+                .collect::<Vec<_>>();
+            (parts.uri.path().to_string(), events)
+        })
+        .collect::<Vec<_>>()
+        .await;
+    requests.sort_by(|a, b| a.0.cmp(&b.0));
+
+    assert_eq!(
+        requests,
+        [
+            (
+                "/v1/datasets/api%2Dlogs/ingest".to_string(),
+                vec![json!({"message": "out of memory"})]
+            ),
+            (
+                "/v1/datasets/web%2Dlogs/ingest".to_string(),
+                vec![
+                    json!({"message": "disk full"}),
+                    json!({"message": "disk still full"})
+                ]
+            ),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn healthcheck_reports_invalid_tokens() {
+    let addr = next_addr();
+    let (_rx, _trigger, server) = build_test_server_generic(addr, || {
+        Response::builder()
+            .status(StatusCode::UNAUTHORIZED)
+            .body(Body::from(r#"{"code": 401, "message": "invalid token"}"#))
+            .unwrap()
+    });
+    tokio::spawn(server);
+
+    let config: AxiomConfig = toml::from_str(&format!(
+        indoc! {r#"
+            url = "http://{}"
+            token = "xaat-1234"
+            dataset = "vector.dev"
+        "#},
+        addr
+    ))
+    .unwrap();
+    let (_sink, healthcheck) = config.build(SinkContext::default()).await.unwrap();
+
+    assert_eq!(
+        healthcheck.await.unwrap_err().to_string(),
+        "invalid API token, 401 Unauthorized: invalid token"
+    );
+}
@@ -9,7 +9,7 @@ use vector_lib::sensitive_string::SensitiveString;
 use super::request_builder::AzureBlobRequestOptions;
 use crate::sinks::util::service::TowerRequestConfigDefaults;
 use crate::{
-    codecs::{Encoder, EncodingConfigWithFraming, SinkType},
+    codecs::{BatchSerializerConfig, Encoder, EncoderKind, EncodingConfigWithFraming, SinkType},
     config::{AcknowledgementsConfig, DataType, GenerateConfig, Input, SinkConfig, SinkContext},
     sinks::{
         azure_common::{
@@ -133,6 +133,15 @@ pub struct AzureBlobSinkConfig {
     #[serde(default = "Compression::gzip_default")]
     pub compression: Compression,
 
+    /// Configures encoding all the events of a batch together into a single blob.
+    ///
+    /// When set, this is used instead of the `codec` and `framing` of `encoding`, and blobs are
+    /// not compressed with `compression`, as the batch codecs compress their own data. Blobs are
+    /// written once the batch is full or times out, as configured with `batch`.
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch_encoding: Option<BatchSerializerConfig>,
+
     #[configurable(derived)]
     #[serde(default)]
     pub batch: BatchConfig<BulkSizeBasedDefaultBatchSettings>,
@@ -166,6 +175,7 @@ impl GenerateConfig for AzureBlobSinkConfig {
             blob_append_uuid: Some(true),
             encoding: (Some(NewlineDelimitedEncoderConfig::new()), JsonSerializerConfig::default()).into(),
             compression: Compression::gzip_default(),
+            batch_encoding: None,
             batch: BatchConfig::default(),
             request: TowerRequestConfig::default(),
             acknowledgements: Default::default(),
@@ -196,7 +206,11 @@ impl SinkConfig for AzureBlobSinkConfig {
     }
 
     fn input(&self) -> Input {
-        Input::new(self.encoding.config().1.input_type() & DataType::Log)
+        if self.batch_encoding.is_some() {
+            Input::log()
+        } else {
+            Input::new(self.encoding.config().1.input_type() & DataType::Log)
+        }
     }
 
     fn acknowledgements(&self) -> &AcknowledgementsConfig {
@@ -228,15 +242,24 @@ impl AzureBlobSinkConfig {
             .unwrap_or(DEFAULT_FILENAME_APPEND_UUID);
 
         let transformer = self.encoding.transformer();
-        let (framer, serializer) = self.encoding.build(SinkType::MessageBased)?;
-        let encoder = Encoder::<Framer>::new(framer, serializer);
+        let (encoder, compression) = match &self.batch_encoding {
+            Some(batch_encoding) => (
+                EncoderKind::Batch(batch_encoding.build()?),
+                Compression::None,
+            ),
+            None => {
+                let (framer, serializer) = self.encoding.build(SinkType::MessageBased)?;
+                let encoder = Encoder::<Framer>::new(framer, serializer);
+                (EncoderKind::Framed(encoder), self.compression)
+            }
+        };
 
         let request_options = AzureBlobRequestOptions {
             container_name: self.container_name.clone(),
             blob_time_format,
             blob_append_uuid,
             encoder: (transformer, encoder),
-            compression: self.compression,
+            compression,
         };
 
         let sink = AzureBlobSink::new(
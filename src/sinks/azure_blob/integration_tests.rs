@@ -232,6 +232,7 @@ impl AzureBlobSinkConfig {
                 blob_append_uuid: None,
                 encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
                 compression: Compression::None,
+                batch_encoding: None,
                 batch: Default::default(),
                 request: TowerRequestConfig::default(),
                 acknowledgements: Default::default(),
@@ -12,7 +12,10 @@ use super::request_builder::AzureBlobRequestOptions;
 use crate::codecs::EncodingConfigWithFraming;
 use crate::event::{Event, LogEvent};
 use crate::sinks::util::{request_builder::RequestBuilder, Compression};
-use crate::{codecs::Encoder, sinks::util::request_builder::EncodeResult};
+use crate::{
+    codecs::{BatchSerializerConfig, Encoder, EncoderKind},
+    sinks::util::request_builder::EncodeResult,
+};
 
 fn default_config(encoding: EncodingConfigWithFraming) -> AzureBlobSinkConfig {
     AzureBlobSinkConfig {
@@ -25,6 +28,7 @@ fn default_config(encoding: EncodingConfigWithFraming) -> AzureBlobSinkConfig {
         blob_append_uuid: Default::default(),
         encoding,
         compression: Compression::gzip_default(),
+        batch_encoding: None,
         batch: Default::default(),
         request: Default::default(),
         acknowledgements: Default::default(),
@@ -61,10 +65,10 @@ fn azure_blob_build_request_without_compression() {
         blob_append_uuid,
         encoder: (
             Default::default(),
-            Encoder::<Framer>::new(
+            EncoderKind::Framed(Encoder::<Framer>::new(
                 NewlineDelimitedEncoder::new().into(),
                 TextSerializerConfig::default().build().into(),
-            ),
+            )),
         ),
         compression,
     };
@@ -109,10 +113,10 @@ fn azure_blob_build_request_with_compression() {
         blob_append_uuid,
         encoder: (
             Default::default(),
-            Encoder::<Framer>::new(
+            EncoderKind::Framed(Encoder::<Framer>::new(
                 NewlineDelimitedEncoder::new().into(),
                 TextSerializerConfig::default().build().into(),
-            ),
+            )),
         ),
         compression,
     };
@@ -157,10 +161,10 @@ fn azure_blob_build_request_with_time_format() {
         blob_append_uuid,
         encoder: (
             Default::default(),
-            Encoder::<Framer>::new(
+            EncoderKind::Framed(Encoder::<Framer>::new(
                 NewlineDelimitedEncoder::new().into(),
                 TextSerializerConfig::default().build().into(),
-            ),
+            )),
         ),
         compression,
     };
@@ -208,10 +212,10 @@ fn azure_blob_build_request_with_uuid() {
         blob_append_uuid,
         encoder: (
             Default::default(),
-            Encoder::<Framer>::new(
+            EncoderKind::Framed(Encoder::<Framer>::new(
                 NewlineDelimitedEncoder::new().into(),
                 TextSerializerConfig::default().build().into(),
-            ),
+            )),
         ),
         compression,
     };
@@ -230,3 +234,47 @@ fn azure_blob_build_request_with_uuid() {
     assert_eq!(request.content_encoding, None);
     assert_eq!(request.content_type, "text/plain");
 }
+
+#[test]
+fn azure_blob_build_request_with_parquet_encoding() {
+    let log = Event::Log(LogEvent::from("test message"));
+    let container_name = String::from("logs");
+    let sink_config = AzureBlobSinkConfig {
+        blob_prefix: "blob".try_into().unwrap(),
+        container_name: container_name.clone(),
+        ..default_config((None::<FramingConfig>, TextSerializerConfig::default()).into())
+    };
+
+    let key = sink_config
+        .key_partitioner()
+        .unwrap()
+        .partition(&log)
+        .expect("key wasn't provided");
+
+    let serializer = BatchSerializerConfig::Parquet {
+        parquet: Default::default(),
+    }
+    .build()
+    .unwrap();
+    let request_options = AzureBlobRequestOptions {
+        container_name,
+        blob_time_format: String::from(""),
+        blob_append_uuid: false,
+        encoder: (Default::default(), EncoderKind::Batch(serializer)),
+        compression: Compression::None,
+    };
+
+    let mut byte_size = GroupedCountByteSize::new_untagged();
+    byte_size.add_event(&log, log.estimated_json_encoded_size_of());
+
+    let (metadata, request_metadata_builder, _events) =
+        request_options.split_input((key, vec![log]));
+
+    let payload = EncodeResult::uncompressed(Bytes::new(), byte_size);
+    let request_metadata = request_metadata_builder.build(&payload);
+    let request = request_options.build_request(metadata, request_metadata, payload);
+
+    assert_eq!(request.metadata.partition_key, "blob.parquet".to_string());
+    assert_eq!(request.content_encoding, None);
+    assert_eq!(request.content_type, "application/vnd.apache.parquet");
+}
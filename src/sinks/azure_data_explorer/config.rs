@@ -0,0 +1,357 @@
+use std::sync::Arc;
+
+use azure_core::auth::TokenCredential;
+use azure_identity::{
+    AutoRefreshingTokenCredential, ClientSecretCredential, DefaultAzureCredential,
+    TokenCredentialOptions,
+};
+use http::Uri;
+use vector_lib::{
+    codecs::{encoding::Framer, encoding::SerializerConfig, NewlineDelimitedEncoderConfig},
+    sensitive_string::SensitiveString,
+};
+
+use super::{
+    ingestion::KustoClient,
+    request_builder::AzureDataExplorerRequestBuilder,
+    service::{AzureDataExplorerRetryLogic, AzureDataExplorerService},
+    sink::AzureDataExplorerSink,
+};
+use crate::{http::HttpClient, sinks::prelude::*};
+
+/// The maximum size of the data of requests with streaming ingestion, before compression.
+const MAX_STREAMING_BYTES: usize = 4 * 1024 * 1024;
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AzureDataExplorerDefaultBatchSettings;
+
+impl SinkBatchSettings for AzureDataExplorerDefaultBatchSettings {
+    const MAX_EVENTS: Option<usize> = None;
+    const MAX_BYTES: Option<usize> = Some(MAX_STREAMING_BYTES);
+    const TIMEOUT_SECS: f64 = 30.0;
+}
+
+/// The method used to ingest the batches of events.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum IngestionMode {
+    /// Batches are uploaded to the temporary storage of the cluster and queued for ingestion.
+    ///
+    /// The cluster ingests the queued data in larger batches of its own, which is the most
+    /// efficient method, at the cost of a latency of up to several minutes.
+    #[default]
+    Queued,
+
+    /// Batches are sent directly to the cluster, which ingests them within seconds.
+    ///
+    /// The [streaming ingestion policy][streaming_policy] must be enabled on the database or the
+    /// table, and batches are limited to 4 MiB before compression.
+    ///
+    /// [streaming_policy]: https://learn.microsoft.com/en-us/azure/data-explorer/kusto/management/streamingingestionpolicy
+    Streaming,
+}
+
+/// The format of the data ingested into the tables.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum DataFormat {
+    Json,
+    Csv,
+}
+
+impl DataFormat {
+    pub(super) const fn as_str(self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Csv => "csv",
+        }
+    }
+}
+
+/// Authentication with an Azure Active Directory application.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct AzureDataExplorerAuth {
+    /// The ID of the Azure Active Directory tenant of the application.
+    #[configurable(metadata(docs::examples = "00000000-0000-0000-0000-000000000000"))]
+    pub tenant_id: String,
+
+    /// The ID of the application.
+    #[configurable(metadata(docs::examples = "00000000-0000-0000-0000-000000000000"))]
+    pub client_id: String,
+
+    /// A client secret of the application.
+    #[configurable(metadata(docs::examples = "${AZURE_CLIENT_SECRET}"))]
+    pub client_secret: SensitiveString,
+}
+
+/// Configuration for the `azure_data_explorer` sink.
+#[configurable_component(sink(
+    "azure_data_explorer",
+    "Ingest log events into Azure Data Explorer (Kusto) tables."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct AzureDataExplorerConfig {
+    /// The URI of the cluster.
+    #[configurable(metadata(docs::examples = "https://mycluster.westeurope.kusto.windows.net"))]
+    pub cluster_uri: String,
+
+    /// The URI of the data management endpoint of the cluster, used by queued ingestion.
+    ///
+    /// Defaults to the URI of the cluster with its host prefixed by `ingest-`.
+    #[configurable(metadata(
+        docs::examples = "https://ingest-mycluster.westeurope.kusto.windows.net"
+    ))]
+    pub ingestion_uri: Option<String>,
+
+    /// The database that events are ingested into.
+    #[configurable(metadata(docs::examples = "logs"))]
+    #[configurable(metadata(docs::examples = "{{ environment }}"))]
+    pub database: Template,
+
+    /// The table that events are ingested into.
+    #[configurable(metadata(docs::examples = "Events"))]
+    #[configurable(metadata(docs::examples = "{{ application }}_Events"))]
+    pub table: Template,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub ingestion_mode: IngestionMode,
+
+    /// The name of the [ingestion mapping][mapping] of the table that events are ingested with.
+    ///
+    /// The kind of the mapping must match the codec of the encoding, `json` or `csv`. Without a
+    /// mapping, the fields of JSON events are ingested into the columns of the same names, and
+    /// the fields of CSV events into the columns in order.
+    ///
+    /// [mapping]: https://learn.microsoft.com/en-us/azure/data-explorer/kusto/management/mappings
+    #[configurable(metadata(docs::examples = "EventsMapping"))]
+    pub mapping_reference: Option<String>,
+
+    #[configurable(derived)]
+    pub auth: Option<AzureDataExplorerAuth>,
+
+    #[configurable(derived)]
+    pub encoding: EncodingConfig,
+
+    #[configurable(derived)]
+    #[serde(default = "Compression::gzip_default")]
+    pub compression: Compression,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch: BatchConfig<AzureDataExplorerDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::is_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+impl GenerateConfig for AzureDataExplorerConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"cluster_uri = "https://mycluster.westeurope.kusto.windows.net"
+            database = "logs"
+            table = "Events"
+            encoding.codec = "json""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "azure_data_explorer")]
+impl SinkConfig for AzureDataExplorerConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let format = self.format()?;
+        if !matches!(self.compression, Compression::None | Compression::Gzip(_)) {
+            return Err(
+                "The `azure_data_explorer` sink only supports the `none` and `gzip` compressions."
+                    .into(),
+            );
+        }
+
+        let mut batch = self.batch.validate()?;
+        if self.ingestion_mode == IngestionMode::Streaming {
+            batch = batch.limit_max_bytes(MAX_STREAMING_BYTES)?;
+        }
+        let batch_settings = batch.into_batcher_settings()?;
+
+        let tls_settings = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new(tls_settings, &cx.proxy)?;
+        let kusto = KustoClient::new(
+            client,
+            self.credential(),
+            self.cluster_uri.parse()?,
+            self.ingestion_uri()?,
+        );
+
+        let healthcheck = Box::pin(healthcheck(kusto.clone(), self.ingestion_mode));
+
+        let service = ServiceBuilder::new()
+            .settings(self.request.into_settings(), AzureDataExplorerRetryLogic)
+            .service(AzureDataExplorerService::new(
+                kusto,
+                self.ingestion_mode,
+                format,
+                self.mapping_reference.clone(),
+                self.compression,
+            ));
+
+        let request_builder = AzureDataExplorerRequestBuilder {
+            compression: self.compression,
+            encoder: (
+                self.encoding.transformer(),
+                Encoder::<Framer>::new(
+                    NewlineDelimitedEncoderConfig.build().into(),
+                    self.encoding.build()?,
+                ),
+            ),
+        };
+
+        let sink = AzureDataExplorerSink {
+            batch_settings,
+            service,
+            database: self.database.clone(),
+            table: self.table.clone(),
+            request_builder,
+        };
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::new(self.encoding.config().input_type() & DataType::Log)
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+impl AzureDataExplorerConfig {
+    fn format(&self) -> crate::Result<DataFormat> {
+        match self.encoding.config() {
+            SerializerConfig::Json(_) => Ok(DataFormat::Json),
+            SerializerConfig::Csv(_) => Ok(DataFormat::Csv),
+            _ => Err(
+                "The `azure_data_explorer` sink only supports the `json` and `csv` codecs.".into(),
+            ),
+        }
+    }
+
+    fn credential(&self) -> Arc<dyn TokenCredential> {
+        let credential: Arc<dyn TokenCredential> = match &self.auth {
+            Some(auth) => Arc::new(ClientSecretCredential::new(
+                azure_core::new_http_client(),
+                auth.tenant_id.clone(),
+                auth.client_id.clone(),
+                auth.client_secret.inner().to_string(),
+                TokenCredentialOptions::default(),
+            )),
+            // Reads the credentials from the environment, a managed identity, or the Azure CLI.
+            None => Arc::new(DefaultAzureCredential::default()),
+        };
+        Arc::new(AutoRefreshingTokenCredential::new(credential))
+    }
+
+    fn ingestion_uri(&self) -> crate::Result<Uri> {
+        match &self.ingestion_uri {
+            Some(uri) => Ok(uri.parse()?),
+            None => default_ingestion_uri(&self.cluster_uri.parse()?),
+        }
+    }
+}
+
+/// Prefixes the host of the cluster with `ingest-`, as the data management endpoints of clusters
+/// are named.
+fn default_ingestion_uri(cluster_uri: &Uri) -> crate::Result<Uri> {
+    let host = cluster_uri
+        .host()
+        .ok_or("The URI of the cluster must have a host.")?;
+    let mut authority = format!("ingest-{}", host);
+    if let Some(port) = cluster_uri.port_u16() {
+        authority = format!("{}:{}", authority, port);
+    }
+
+    let mut parts = cluster_uri.clone().into_parts();
+    parts.authority = Some(authority.parse()?);
+    Ok(Uri::from_parts(parts)?)
+}
+
+async fn healthcheck(kusto: KustoClient, mode: IngestionMode) -> crate::Result<()> {
+    match mode {
+        IngestionMode::Queued => kusto.ingestion_resources().await.map(|_| ())?,
+        IngestionMode::Streaming => kusto.show_version().await?,
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<AzureDataExplorerConfig>();
+    }
+
+    #[test]
+    fn derives_ingestion_uri() {
+        let uri = default_ingestion_uri(
+            &"https://mycluster.westeurope.kusto.windows.net"
+                .parse()
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            uri.to_string(),
+            "https://ingest-mycluster.westeurope.kusto.windows.net/"
+        );
+
+        let uri = default_ingestion_uri(&"http://localhost:8080".parse().unwrap()).unwrap();
+        assert_eq!(uri.to_string(), "http://ingest-localhost:8080/");
+    }
+
+    #[test]
+    fn parses_formats() {
+        let config: AzureDataExplorerConfig = toml::from_str(
+            r#"
+            cluster_uri = "https://mycluster.westeurope.kusto.windows.net"
+            database = "logs"
+            table = "Events"
+            ingestion_mode = "streaming"
+            mapping_reference = "EventsMapping"
+            encoding.codec = "csv"
+            encoding.csv.fields = ["timestamp", "message"]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.ingestion_mode, IngestionMode::Streaming);
+        assert_eq!(config.format().unwrap(), DataFormat::Csv);
+
+        let config: AzureDataExplorerConfig = toml::from_str(
+            r#"
+            cluster_uri = "https://mycluster.westeurope.kusto.windows.net"
+            database = "logs"
+            table = "Events"
+            encoding.codec = "text"
+            "#,
+        )
+        .unwrap();
+        assert!(config.format().is_err());
+    }
+}
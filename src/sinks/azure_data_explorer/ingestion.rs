@@ -0,0 +1,518 @@
+//! The client of the REST APIs of the cluster, and of the storage used by queued ingestion.
+
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use azure_core::auth::TokenCredential;
+use bytes::Bytes;
+use http::{
+    header::{AUTHORIZATION, CONTENT_ENCODING, CONTENT_LENGTH, CONTENT_TYPE},
+    Request, StatusCode, Uri,
+};
+use hyper::Body;
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+use tokio::sync::Mutex;
+
+use super::config::DataFormat;
+use crate::{
+    http::{HttpClient, HttpError},
+    sinks::prelude::*,
+};
+
+/// The duration that the ingestion resources of the cluster are cached for.
+const RESOURCES_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// The version of the storage services APIs.
+const STORAGE_API_VERSION: &str = "2021-08-06";
+
+#[derive(Debug, Snafu)]
+pub(super) enum KustoError {
+    #[snafu(display("{}", source))]
+    Http { source: HttpError },
+    #[snafu(display("Failed to get an Azure Active Directory token: {}", source))]
+    Token { source: azure_core::Error },
+    #[snafu(display("Failed to build the request: {}", source))]
+    Build { source: http::Error },
+    #[snafu(display("{} failed with status {}: {}", operation, status, body))]
+    Status {
+        operation: &'static str,
+        status: StatusCode,
+        body: String,
+    },
+    #[snafu(display("Invalid response to a management command: {}", source))]
+    Response { source: serde_json::Error },
+    #[snafu(display("Missing ingestion resources: {}", message))]
+    Resources { message: String },
+}
+
+impl KustoError {
+    pub(super) fn is_retriable(&self) -> bool {
+        match self {
+            Self::Http { source } => source.is_retriable(),
+            Self::Token { .. } | Self::Response { .. } | Self::Resources { .. } => true,
+            Self::Build { .. } => false,
+            Self::Status { status, .. } => {
+                status.is_server_error()
+                    || *status == StatusCode::TOO_MANY_REQUESTS
+                    || *status == StatusCode::REQUEST_TIMEOUT
+            }
+        }
+    }
+}
+
+/// A container or a queue of the storage of the cluster, authorized by a shared access signature.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) struct StorageUri {
+    base: String,
+    sas: String,
+}
+
+impl StorageUri {
+    fn parse(uri: &str) -> Self {
+        let (base, sas) = uri.split_once('?').unwrap_or((uri, ""));
+        Self {
+            base: base.trim_end_matches('/').to_string(),
+            sas: sas.to_string(),
+        }
+    }
+
+    fn with_path(&self, path: &str) -> String {
+        format!("{}/{}?{}", self.base, path, self.sas)
+    }
+}
+
+/// The storage of the cluster that data is queued for ingestion with.
+#[derive(Debug)]
+pub(super) struct IngestionResources {
+    containers: Vec<StorageUri>,
+    queues: Vec<StorageUri>,
+    authorization_context: String,
+}
+
+impl IngestionResources {
+    fn from_rows(rows: Vec<Vec<serde_json::Value>>, authorization_context: String) -> Self {
+        let mut resources = Self {
+            containers: Vec::new(),
+            queues: Vec::new(),
+            authorization_context,
+        };
+        for row in rows {
+            match (
+                row.first().and_then(|v| v.as_str()),
+                row.get(1).and_then(|v| v.as_str()),
+            ) {
+                (Some("TempStorage"), Some(uri)) => {
+                    resources.containers.push(StorageUri::parse(uri))
+                }
+                (Some("SecuredReadyForAggregationQueue"), Some(uri)) => {
+                    resources.queues.push(StorageUri::parse(uri))
+                }
+                _ => (),
+            }
+        }
+        resources
+    }
+}
+
+#[derive(Deserialize)]
+struct ManagementResponse {
+    #[serde(rename = "Tables")]
+    tables: Vec<ManagementTable>,
+}
+
+#[derive(Deserialize)]
+struct ManagementTable {
+    #[serde(rename = "Rows")]
+    rows: Vec<Vec<serde_json::Value>>,
+}
+
+/// The message queued for the ingestion of a blob.
+#[derive(Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct IngestionMessage<'a> {
+    id: String,
+    blob_path: String,
+    raw_data_size: usize,
+    database_name: &'a str,
+    table_name: &'a str,
+    retain_blob_on_success: bool,
+    flush_immediately: bool,
+    /// Only failures are reported.
+    report_level: u8,
+    /// Failures are reported to the failed ingestions queue.
+    report_method: u8,
+    additional_properties: AdditionalProperties<'a>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AdditionalProperties<'a> {
+    authorization_context: &'a str,
+    format: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ingestion_mapping_reference: Option<&'a str>,
+}
+
+/// The data of a batch to ingest into a table.
+pub(super) struct Ingestion<'a> {
+    pub(super) database: &'a str,
+    pub(super) table: &'a str,
+    pub(super) format: DataFormat,
+    pub(super) mapping_reference: Option<&'a str>,
+    pub(super) compression: Compression,
+    pub(super) body: Bytes,
+    /// The size of the data before compression.
+    pub(super) raw_size: usize,
+}
+
+#[derive(Clone)]
+pub(super) struct KustoClient {
+    client: HttpClient,
+    credential: Arc<dyn TokenCredential>,
+    cluster_uri: Uri,
+    ingestion_uri: Uri,
+    resources: Arc<Mutex<Option<(Instant, Arc<IngestionResources>)>>>,
+    next_resource: Arc<AtomicUsize>,
+}
+
+impl KustoClient {
+    pub(super) fn new(
+        client: HttpClient,
+        credential: Arc<dyn TokenCredential>,
+        cluster_uri: Uri,
+        ingestion_uri: Uri,
+    ) -> Self {
+        Self {
+            client,
+            credential,
+            cluster_uri,
+            ingestion_uri,
+            resources: Arc::default(),
+            next_resource: Arc::default(),
+        }
+    }
+
+    /// Gets a token for the endpoint, the tokens being issued for the URI of the endpoint.
+    async fn token(&self, endpoint: &Uri) -> Result<String, KustoError> {
+        let resource = format!(
+            "{}://{}",
+            endpoint.scheme_str().unwrap_or("https"),
+            endpoint
+                .authority()
+                .map_or("", |authority| authority.as_str())
+        );
+        let token = self
+            .credential
+            .get_token(&resource)
+            .await
+            .context(TokenSnafu)?;
+        Ok(format!("Bearer {}", token.token.secret()))
+    }
+
+    async fn send(
+        &self,
+        operation: &'static str,
+        request: Request<Body>,
+    ) -> Result<Bytes, KustoError> {
+        let response = self.client.send(request).await.context(HttpSnafu)?;
+        let status = response.status();
+        let body = hyper::body::to_bytes(response.into_body())
+            .await
+            .map_err(|source| KustoError::Http {
+                source: HttpError::CallRequest { source },
+            })?;
+
+        if status.is_success() {
+            Ok(body)
+        } else {
+            StatusSnafu {
+                operation,
+                status,
+                body: String::from_utf8_lossy(&body).into_owned(),
+            }
+            .fail()
+        }
+    }
+
+    /// Runs a management command on the endpoint, returning the rows of its primary result.
+    async fn management(
+        &self,
+        endpoint: &Uri,
+        command: &str,
+    ) -> Result<Vec<Vec<serde_json::Value>>, KustoError> {
+        let body = serde_json::json!({ "csl": command, "db": "NetDefaultDB" }).to_string();
+        let request = Request::post(endpoint_path(endpoint, "v1/rest/mgmt"))
+            .header(AUTHORIZATION, self.token(endpoint).await?)
+            .header(CONTENT_TYPE, "application/json; charset=utf-8")
+            .body(Body::from(body))
+            .context(BuildSnafu)?;
+
+        let body = self.send("Management command", request).await?;
+        let response: ManagementResponse = serde_json::from_slice(&body).context(ResponseSnafu)?;
+        Ok(response
+            .tables
+            .into_iter()
+            .next()
+            .map(|table| table.rows)
+            .unwrap_or_default())
+    }
+
+    pub(super) async fn show_version(&self) -> Result<(), KustoError> {
+        self.management(&self.cluster_uri, ".show version")
+            .await
+            .map(|_| ())
+    }
+
+    /// Gets the storage of the cluster that data is queued for ingestion with, which is cached.
+    pub(super) async fn ingestion_resources(&self) -> Result<Arc<IngestionResources>, KustoError> {
+        let mut cached = self.resources.lock().await;
+        if let Some((fetched, resources)) = cached.as_ref() {
+            if fetched.elapsed() < RESOURCES_TTL {
+                return Ok(Arc::clone(resources));
+            }
+        }
+
+        let rows = self
+            .management(&self.ingestion_uri, ".get ingestion resources")
+            .await?;
+        let authorization_context = self
+            .management(&self.ingestion_uri, ".get kusto identity token")
+            .await?
+            .first()
+            .and_then(|row| row.first())
+            .and_then(|value| value.as_str())
+            .map(Into::into)
+            .ok_or_else(|| KustoError::Resources {
+                message: "the identity token of the cluster is missing".into(),
+            })?;
+
+        let resources = IngestionResources::from_rows(rows, authorization_context);
+        if resources.containers.is_empty() || resources.queues.is_empty() {
+            return ResourcesSnafu {
+                message: "the cluster has no temporary storage or ingestion queues",
+            }
+            .fail();
+        }
+
+        let resources = Arc::new(resources);
+        *cached = Some((Instant::now(), Arc::clone(&resources)));
+        Ok(resources)
+    }
+
+    /// Sends the data to the cluster with streaming ingestion.
+    pub(super) async fn ingest_streaming(
+        &self,
+        ingestion: Ingestion<'_>,
+    ) -> Result<(), KustoError> {
+        let path = streaming_path(
+            ingestion.database,
+            ingestion.table,
+            ingestion.format,
+            ingestion.mapping_reference,
+        );
+
+        let mut builder = Request::post(endpoint_path(&self.cluster_uri, &path))
+            .header(AUTHORIZATION, self.token(&self.cluster_uri).await?)
+            .header(CONTENT_LENGTH, ingestion.body.len());
+        if let Some(encoding) = ingestion.compression.content_encoding() {
+            builder = builder.header(CONTENT_ENCODING, encoding);
+        }
+        let request = builder
+            .body(Body::from(ingestion.body))
+            .context(BuildSnafu)?;
+
+        self.send("Streaming ingestion", request).await.map(|_| ())
+    }
+
+    /// Uploads the data to a blob of the temporary storage of the cluster, and queues the blob
+    /// for ingestion.
+    ///
+    /// The containers and queues of the cluster are used in turn.
+    pub(super) async fn ingest_queued(&self, ingestion: Ingestion<'_>) -> Result<(), KustoError> {
+        let resources = self.ingestion_resources().await?;
+        let next = self.next_resource.fetch_add(1, Ordering::Relaxed);
+        let container = &resources.containers[next % resources.containers.len()];
+        let queue = &resources.queues[next % resources.queues.len()];
+
+        let id = uuid::Uuid::new_v4().to_string();
+        let mut blob_name = format!(
+            "{}__{}__{}.{}",
+            ingestion.database,
+            ingestion.table,
+            id,
+            ingestion.format.as_str()
+        );
+        // The compression of blobs is detected from their extension.
+        if let Compression::Gzip(_) = ingestion.compression {
+            blob_name.push_str(".gz");
+        }
+        let blob_path = container.with_path(
+            &percent_encoding::utf8_percent_encode(&blob_name, percent_encoding::NON_ALPHANUMERIC)
+                .to_string(),
+        );
+
+        let request = Request::put(&blob_path)
+            .header("x-ms-blob-type", "BlockBlob")
+            .header("x-ms-version", STORAGE_API_VERSION)
+            .header(CONTENT_LENGTH, ingestion.body.len())
+            .body(Body::from(ingestion.body))
+            .context(BuildSnafu)?;
+        self.send("Blob upload", request).await?;
+
+        let message = IngestionMessage {
+            id,
+            blob_path,
+            raw_data_size: ingestion.raw_size,
+            database_name: ingestion.database,
+            table_name: ingestion.table,
+            retain_blob_on_success: false,
+            flush_immediately: false,
+            report_level: 0,
+            report_method: 0,
+            additional_properties: AdditionalProperties {
+                authorization_context: &resources.authorization_context,
+                format: ingestion.format.as_str(),
+                ingestion_mapping_reference: ingestion.mapping_reference,
+            },
+        };
+        let request = Request::post(queue.with_path("messages"))
+            .header("x-ms-version", STORAGE_API_VERSION)
+            .header(CONTENT_TYPE, "application/xml")
+            .body(Body::from(queue_message(&message)))
+            .context(BuildSnafu)?;
+        self.send("Queueing the ingestion", request)
+            .await
+            .map(|_| ())
+    }
+}
+
+fn endpoint_path(endpoint: &Uri, path: &str) -> String {
+    format!("{}/{}", endpoint.to_string().trim_end_matches('/'), path)
+}
+
+fn streaming_path(
+    database: &str,
+    table: &str,
+    format: DataFormat,
+    mapping_reference: Option<&str>,
+) -> String {
+    let encode =
+        |value| percent_encoding::utf8_percent_encode(value, percent_encoding::NON_ALPHANUMERIC);
+    let mut path = format!(
+        "v1/rest/ingest/{}/{}?streamFormat={}",
+        encode(database),
+        encode(table),
+        format.as_str()
+    );
+    if let Some(mapping) = mapping_reference {
+        path.push_str(&format!("&mappingName={}", encode(mapping)));
+    }
+    path
+}
+
+/// Wraps the message as the Base64-encoded text of a message of a storage queue.
+fn queue_message(message: &IngestionMessage<'_>) -> String {
+    let json = serde_json::to_vec(message).expect("ingestion messages are serializable");
+    format!(
+        "<QueueMessage><MessageText>{}</MessageText></QueueMessage>",
+        openssl::base64::encode_block(&json)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_ingestion_resources() {
+        let response: ManagementResponse = serde_json::from_str(
+            r#"{"Tables":[{"TableName":"Table_0","Columns":[],"Rows":[
+                ["SecuredReadyForAggregationQueue","https://account.queue.core.windows.net/readyforaggregation-secured?sv=2018&sig=abc"],
+                ["FailedIngestionsQueue","https://account.queue.core.windows.net/failedingestions?sv=2018&sig=abc"],
+                ["TempStorage","https://account.blob.core.windows.net/20240102-ingestdata-e5c334ee?sv=2018&sig=abc"]
+            ]}]}"#,
+        )
+        .unwrap();
+        let rows = response.tables.into_iter().next().unwrap().rows;
+
+        let resources = IngestionResources::from_rows(rows, "context".to_string());
+        assert_eq!(
+            resources.queues,
+            [StorageUri {
+                base: "https://account.queue.core.windows.net/readyforaggregation-secured"
+                    .to_string(),
+                sas: "sv=2018&sig=abc".to_string(),
+            }]
+        );
+        assert_eq!(
+            resources.containers[0].with_path("blob.json"),
+            "https://account.blob.core.windows.net/20240102-ingestdata-e5c334ee/blob.json?sv=2018&sig=abc"
+        );
+    }
+
+    #[test]
+    fn builds_streaming_paths() {
+        assert_eq!(
+            streaming_path("logs", "Events", DataFormat::Json, None),
+            "v1/rest/ingest/logs/Events?streamFormat=json"
+        );
+        assert_eq!(
+            streaming_path("my logs", "Events", DataFormat::Csv, Some("Csv_Mapping")),
+            "v1/rest/ingest/my%20logs/Events?streamFormat=csv&mappingName=Csv%5FMapping"
+        );
+    }
+
+    #[test]
+    fn encodes_queue_messages() {
+        let message = IngestionMessage {
+            id: "id".to_string(),
+            blob_path: "https://account.blob.core.windows.net/container/blob.json.gz?sig=abc"
+                .to_string(),
+            raw_data_size: 42,
+            database_name: "logs",
+            table_name: "Events",
+            retain_blob_on_success: false,
+            flush_immediately: false,
+            report_level: 0,
+            report_method: 0,
+            additional_properties: AdditionalProperties {
+                authorization_context: "context",
+                format: "json",
+                ingestion_mapping_reference: Some("EventsMapping"),
+            },
+        };
+
+        let xml = queue_message(&message);
+        let text = xml
+            .strip_prefix("<QueueMessage><MessageText>")
+            .and_then(|xml| xml.strip_suffix("</MessageText></QueueMessage>"))
+            .unwrap();
+        let json: serde_json::Value =
+            serde_json::from_slice(&openssl::base64::decode_block(text).unwrap()).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "Id": "id",
+                "BlobPath": "https://account.blob.core.windows.net/container/blob.json.gz?sig=abc",
+                "RawDataSize": 42,
+                "DatabaseName": "logs",
+                "TableName": "Events",
+                "RetainBlobOnSuccess": false,
+                "FlushImmediately": false,
+                "ReportLevel": 0,
+                "ReportMethod": 0,
+                "AdditionalProperties": {
+                    "authorizationContext": "context",
+                    "format": "json",
+                    "ingestionMappingReference": "EventsMapping",
+                },
+            })
+        );
+    }
+}
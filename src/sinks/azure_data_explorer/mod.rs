@@ -0,0 +1,19 @@
+//! The `azure_data_explorer` sink.
+//!
+//! This sink ingests log events into the tables of [Azure Data Explorer][adx] (Kusto) clusters,
+//! authenticating with Azure Active Directory.
+//!
+//! With queued ingestion, each batch is uploaded as a blob to one of the temporary storage
+//! containers of the cluster, and a message describing the blob is posted to one of its
+//! ingestion queues, from which the cluster ingests the blob. With streaming ingestion, batches
+//! are sent directly to the cluster, which ingests them with a lower latency.
+//!
+//! [adx]: https://learn.microsoft.com/en-us/azure/data-explorer/
+
+mod config;
+mod ingestion;
+mod request_builder;
+mod service;
+mod sink;
+
+pub use self::config::AzureDataExplorerConfig;
@@ -0,0 +1,56 @@
+//! `RequestBuilder` implementation for the `azure_data_explorer` sink.
+
+use bytes::Bytes;
+use vector_lib::codecs::encoding::Framer;
+
+use super::{service::AzureDataExplorerRequest, sink::PartitionKey};
+use crate::sinks::prelude::*;
+
+pub(super) struct AzureDataExplorerRequestBuilder {
+    pub(super) compression: Compression,
+    pub(super) encoder: (Transformer, Encoder<Framer>),
+}
+
+impl RequestBuilder<(PartitionKey, Vec<Event>)> for AzureDataExplorerRequestBuilder {
+    type Metadata = (PartitionKey, EventFinalizers);
+    type Events = Vec<Event>;
+    type Encoder = (Transformer, Encoder<Framer>);
+    type Payload = Bytes;
+    type Request = AzureDataExplorerRequest;
+    type Error = std::io::Error;
+
+    fn compression(&self) -> Compression {
+        self.compression
+    }
+
+    fn encoder(&self) -> &Self::Encoder {
+        &self.encoder
+    }
+
+    fn split_input(
+        &self,
+        input: (PartitionKey, Vec<Event>),
+    ) -> (Self::Metadata, RequestMetadataBuilder, Self::Events) {
+        let (key, mut events) = input;
+
+        let finalizers = events.take_finalizers();
+        let builder = RequestMetadataBuilder::from_events(&events);
+        ((key, finalizers), builder, events)
+    }
+
+    fn build_request(
+        &self,
+        metadata: Self::Metadata,
+        request_metadata: RequestMetadata,
+        payload: EncodeResult<Self::Payload>,
+    ) -> Self::Request {
+        let (key, finalizers) = metadata;
+        AzureDataExplorerRequest {
+            database: key.database,
+            table: key.table,
+            body: payload.into_payload(),
+            finalizers,
+            metadata: request_metadata,
+        }
+    }
+}
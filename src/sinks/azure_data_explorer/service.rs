@@ -0,0 +1,138 @@
+//! Service implementation for the `azure_data_explorer` sink.
+
+use std::task::{Context, Poll};
+
+use bytes::Bytes;
+
+use super::{
+    config::{DataFormat, IngestionMode},
+    ingestion::{Ingestion, KustoClient, KustoError},
+};
+use crate::sinks::prelude::*;
+
+#[derive(Clone)]
+pub(super) struct AzureDataExplorerRequest {
+    pub(super) database: String,
+    pub(super) table: String,
+    pub(super) body: Bytes,
+    pub(super) finalizers: EventFinalizers,
+    pub(super) metadata: RequestMetadata,
+}
+
+impl Finalizable for AzureDataExplorerRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        std::mem::take(&mut self.finalizers)
+    }
+}
+
+impl MetaDescriptive for AzureDataExplorerRequest {
+    fn get_metadata(&self) -> &RequestMetadata {
+        &self.metadata
+    }
+
+    fn metadata_mut(&mut self) -> &mut RequestMetadata {
+        &mut self.metadata
+    }
+}
+
+#[derive(Debug)]
+pub(super) struct AzureDataExplorerResponse {
+    events_byte_size: GroupedCountByteSize,
+    byte_size: usize,
+}
+
+impl DriverResponse for AzureDataExplorerResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> &GroupedCountByteSize {
+        &self.events_byte_size
+    }
+
+    fn bytes_sent(&self) -> Option<usize> {
+        Some(self.byte_size)
+    }
+}
+
+#[derive(Clone, Default)]
+pub(super) struct AzureDataExplorerRetryLogic;
+
+impl RetryLogic for AzureDataExplorerRetryLogic {
+    type Error = KustoError;
+    type Response = AzureDataExplorerResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        error.is_retriable()
+    }
+}
+
+/// Ingests the batches of requests into their table.
+///
+/// With queued ingestion, requests are completed once their data is queued for ingestion, the
+/// data being ingested by the cluster afterwards.
+#[derive(Clone)]
+pub(super) struct AzureDataExplorerService {
+    kusto: KustoClient,
+    mode: IngestionMode,
+    format: DataFormat,
+    mapping_reference: Option<String>,
+    compression: Compression,
+}
+
+impl AzureDataExplorerService {
+    pub(super) const fn new(
+        kusto: KustoClient,
+        mode: IngestionMode,
+        format: DataFormat,
+        mapping_reference: Option<String>,
+        compression: Compression,
+    ) -> Self {
+        Self {
+            kusto,
+            mode,
+            format,
+            mapping_reference,
+            compression,
+        }
+    }
+}
+
+impl Service<AzureDataExplorerRequest> for AzureDataExplorerService {
+    type Response = AzureDataExplorerResponse;
+    type Error = KustoError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: AzureDataExplorerRequest) -> Self::Future {
+        let service = self.clone();
+
+        Box::pin(async move {
+            let byte_size = request.body.len();
+            let ingestion = Ingestion {
+                database: &request.database,
+                table: &request.table,
+                format: service.format,
+                mapping_reference: service.mapping_reference.as_deref(),
+                compression: service.compression,
+                body: request.body.clone(),
+                raw_size: request.metadata.request_encoded_size(),
+            };
+
+            match service.mode {
+                IngestionMode::Queued => service.kusto.ingest_queued(ingestion).await?,
+                IngestionMode::Streaming => service.kusto.ingest_streaming(ingestion).await?,
+            }
+
+            Ok(AzureDataExplorerResponse {
+                events_byte_size: request
+                    .metadata
+                    .into_events_estimated_json_encoded_byte_size(),
+                byte_size,
+            })
+        })
+    }
+}
@@ -0,0 +1,92 @@
+//! Implementation of the `azure_data_explorer` sink.
+
+use super::{
+    request_builder::AzureDataExplorerRequestBuilder,
+    service::{AzureDataExplorerRetryLogic, AzureDataExplorerService},
+};
+use crate::sinks::prelude::*;
+
+/// The database and table that events are ingested into.
+#[derive(Hash, Eq, PartialEq, Clone, Debug)]
+pub(super) struct PartitionKey {
+    pub(super) database: String,
+    pub(super) table: String,
+}
+
+/// Partitions events by the database and table they are ingested into.
+struct KeyPartitioner {
+    database: Template,
+    table: Template,
+}
+
+impl KeyPartitioner {
+    fn render(template: &Template, item: &Event, field: &'static str) -> Option<String> {
+        template
+            .render_string(item)
+            .map_err(|error| {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some(field),
+                    drop_event: true,
+                });
+            })
+            .ok()
+    }
+}
+
+impl Partitioner for KeyPartitioner {
+    type Item = Event;
+    type Key = Option<PartitionKey>;
+
+    fn partition(&self, item: &Self::Item) -> Self::Key {
+        let database = Self::render(&self.database, item, "database")?;
+        let table = Self::render(&self.table, item, "table")?;
+        Some(PartitionKey { database, table })
+    }
+}
+
+pub(super) struct AzureDataExplorerSink {
+    pub(super) batch_settings: BatcherSettings,
+    pub(super) service: Svc<AzureDataExplorerService, AzureDataExplorerRetryLogic>,
+    pub(super) database: Template,
+    pub(super) table: Template,
+    pub(super) request_builder: AzureDataExplorerRequestBuilder,
+}
+
+impl AzureDataExplorerSink {
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let batch_settings = self.batch_settings;
+        let partitioner = KeyPartitioner {
+            database: self.database,
+            table: self.table,
+        };
+
+        input
+            .batched_partitioned(partitioner, || batch_settings.as_byte_size_config())
+            .filter_map(|(key, batch)| async move { key.map(move |key| (key, batch)) })
+            .request_builder(
+                default_request_builder_concurrency_limit(),
+                self.request_builder,
+            )
+            .filter_map(|request| async {
+                match request {
+                    Err(error) => {
+                        emit!(SinkRequestBuildError { error });
+                        None
+                    }
+                    Ok(request) => Some(request),
+                }
+            })
+            .into_driver(self.service)
+            .protocol("https")
+            .run()
+            .await
+    }
+}
+
+#[async_trait::async_trait]
+impl StreamSink<Event> for AzureDataExplorerSink {
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
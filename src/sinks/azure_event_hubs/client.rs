@@ -0,0 +1,224 @@
+//! The client of the HTTPS API of Event Hubs.
+
+use std::{
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use azure_core::auth::TokenCredential;
+use base64::prelude::{Engine as _, BASE64_STANDARD};
+use bytes::Bytes;
+use hmac::{Hmac, Mac};
+use http::{
+    header::{AUTHORIZATION, CONTENT_TYPE},
+    Request, StatusCode,
+};
+use hyper::Body;
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use sha2::Sha256;
+use snafu::{ResultExt, Snafu};
+use vector_lib::sensitive_string::SensitiveString;
+
+use crate::http::{HttpClient, HttpError};
+
+/// The version of the HTTPS API of Event Hubs.
+const API_VERSION: &str = "2014-01";
+
+/// The resource that Azure Active Directory tokens are issued for.
+const AAD_RESOURCE: &str = "https://eventhubs.azure.net";
+
+/// The content type of the batches of events.
+const BATCH_CONTENT_TYPE: &str = "application/vnd.microsoft.servicebus.json";
+
+/// The duration that shared access signatures are valid for.
+const SAS_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// The characters escaped in the components of URIs, as escaped by `encodeURIComponent`.
+const URI_COMPONENT: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+#[derive(Debug, Snafu)]
+pub(super) enum EventHubsError {
+    #[snafu(display("{}", source))]
+    Http { source: HttpError },
+    #[snafu(display("Failed to get an Azure Active Directory token: {}", source))]
+    Token { source: azure_core::Error },
+    #[snafu(display("Failed to build the request: {}", source))]
+    Build { source: http::Error },
+    #[snafu(display("Sending events failed with status {}: {}", status, body))]
+    Status { status: StatusCode, body: String },
+}
+
+impl EventHubsError {
+    pub(super) fn is_retriable(&self) -> bool {
+        match self {
+            Self::Http { source } => source.is_retriable(),
+            Self::Token { .. } => true,
+            Self::Build { .. } => false,
+            // Event Hubs responds with `503 Service Unavailable` when the namespace is throttled.
+            Self::Status { status, .. } => {
+                status.is_server_error()
+                    || *status == StatusCode::TOO_MANY_REQUESTS
+                    || *status == StatusCode::REQUEST_TIMEOUT
+            }
+        }
+    }
+}
+
+/// The credential that requests are authorized with.
+pub(super) enum Credential {
+    /// A shared access key of the namespace or of the event hubs, signing shared access
+    /// signatures.
+    SharedAccessKey {
+        key_name: String,
+        key: SensitiveString,
+    },
+    /// Azure Active Directory.
+    Token(Arc<dyn TokenCredential>),
+}
+
+#[derive(Clone)]
+pub(super) struct EventHubsClient {
+    client: HttpClient,
+    namespace: String,
+    credential: Arc<Credential>,
+}
+
+impl EventHubsClient {
+    pub(super) fn new(client: HttpClient, namespace: String, credential: Credential) -> Self {
+        Self {
+            client,
+            namespace,
+            credential: Arc::new(credential),
+        }
+    }
+
+    /// The URI of the event hub, which shared access signatures are issued for.
+    fn resource_uri(&self, event_hub: &str) -> String {
+        format!(
+            "https://{}/{}",
+            self.namespace,
+            utf8_percent_encode(event_hub, URI_COMPONENT)
+        )
+    }
+
+    async fn authorization(&self, resource_uri: &str) -> Result<String, EventHubsError> {
+        match self.credential.as_ref() {
+            Credential::SharedAccessKey { key_name, key } => {
+                let expiry = (SystemTime::now() + SAS_TTL)
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs();
+                Ok(shared_access_signature(
+                    resource_uri,
+                    key_name,
+                    key.inner(),
+                    expiry,
+                ))
+            }
+            Credential::Token(credential) => {
+                let token = credential
+                    .get_token(AAD_RESOURCE)
+                    .await
+                    .context(TokenSnafu)?;
+                Ok(format!("Bearer {}", token.token.secret()))
+            }
+        }
+    }
+
+    /// Sends the batch of events, in the batch format of Event Hubs, to the event hub.
+    pub(super) async fn send_batch(
+        &self,
+        event_hub: &str,
+        body: Bytes,
+    ) -> Result<(), EventHubsError> {
+        let resource_uri = self.resource_uri(event_hub);
+        let request = Request::post(format!(
+            "{}/messages?api-version={}",
+            resource_uri, API_VERSION
+        ))
+        .header(AUTHORIZATION, self.authorization(&resource_uri).await?)
+        .header(CONTENT_TYPE, BATCH_CONTENT_TYPE)
+        .body(Body::from(body))
+        .context(BuildSnafu)?;
+
+        let response = self.client.send(request).await.context(HttpSnafu)?;
+        let status = response.status();
+        if status.is_success() {
+            return Ok(());
+        }
+
+        let body = hyper::body::to_bytes(response.into_body())
+            .await
+            .map_err(|source| EventHubsError::Http {
+                source: HttpError::CallRequest { source },
+            })?;
+        StatusSnafu {
+            status,
+            body: String::from_utf8_lossy(&body).into_owned(),
+        }
+        .fail()
+    }
+
+    /// Checks that the credential can authorize requests, Event Hubs having no API to check the
+    /// rights of senders without sending events.
+    pub(super) async fn healthcheck(&self) -> Result<(), EventHubsError> {
+        self.authorization(&self.resource_uri("")).await.map(|_| ())
+    }
+}
+
+/// Builds a shared access signature for the resource, signed with the key.
+fn shared_access_signature(resource_uri: &str, key_name: &str, key: &str, expiry: u64) -> String {
+    let resource = utf8_percent_encode(resource_uri, URI_COMPONENT).to_string();
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(key.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(format!("{}\n{}", resource, expiry).as_bytes());
+    let signature = BASE64_STANDARD.encode(mac.finalize().into_bytes());
+
+    format!(
+        "SharedAccessSignature sr={}&sig={}&se={}&skn={}",
+        resource,
+        utf8_percent_encode(&signature, URI_COMPONENT),
+        expiry,
+        utf8_percent_encode(key_name, URI_COMPONENT)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signs_shared_access_signatures() {
+        assert_eq!(
+            shared_access_signature(
+                "https://mynamespace.servicebus.windows.net/logs",
+                "RootManageSharedAccessKey",
+                "c2VjcmV0",
+                1704067200,
+            ),
+            "SharedAccessSignature \
+             sr=https%3A%2F%2Fmynamespace.servicebus.windows.net%2Flogs\
+             &sig=FoTcWOZgOiK6Ys7hmfD0nShNVlKH3tjFjmj%2Bwh4n5c4%3D\
+             &se=1704067200\
+             &skn=RootManageSharedAccessKey"
+        );
+    }
+
+    #[test]
+    fn retries_throttled_requests() {
+        assert!(EventHubsError::Status {
+            status: StatusCode::SERVICE_UNAVAILABLE,
+            body: String::new(),
+        }
+        .is_retriable());
+        assert!(!EventHubsError::Status {
+            status: StatusCode::UNAUTHORIZED,
+            body: String::new(),
+        }
+        .is_retriable());
+    }
+}
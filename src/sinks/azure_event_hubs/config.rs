@@ -0,0 +1,253 @@
+use std::{num::NonZeroUsize, sync::Arc};
+
+use azure_core::auth::TokenCredential;
+use azure_identity::{
+    AutoRefreshingTokenCredential, ClientSecretCredential, DefaultAzureCredential,
+    TokenCredentialOptions,
+};
+use vector_lib::sensitive_string::SensitiveString;
+
+use super::{
+    client::{Credential, EventHubsClient},
+    encoder::EventHubsEncoder,
+    service::{AzureEventHubsRetryLogic, AzureEventHubsService},
+    sink::AzureEventHubsSink,
+};
+use crate::{http::HttpClient, sinks::prelude::*};
+
+/// The maximum size of the messages of the namespaces of the Standard and higher tiers.
+const MAX_MESSAGE_SIZE: usize = 1024 * 1024;
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AzureEventHubsDefaultBatchSettings;
+
+impl SinkBatchSettings for AzureEventHubsDefaultBatchSettings {
+    const MAX_EVENTS: Option<usize> = Some(1000);
+    const MAX_BYTES: Option<usize> = Some(MAX_MESSAGE_SIZE);
+    const TIMEOUT_SECS: f64 = 1.0;
+}
+
+const fn default_max_request_size() -> NonZeroUsize {
+    match NonZeroUsize::new(MAX_MESSAGE_SIZE) {
+        Some(size) => size,
+        None => unreachable!(),
+    }
+}
+
+/// The authentication of the requests to the namespace.
+///
+/// If not set, requests are authenticated with Azure Active Directory, with the credentials read
+/// from the environment, a managed identity, or the Azure CLI.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields, rename_all = "snake_case", tag = "strategy")]
+#[configurable(metadata(docs::enum_tag_description = "The authentication strategy to use."))]
+pub enum AzureEventHubsAuth {
+    /// Authentication with a shared access key of the namespace or of the event hubs.
+    ///
+    /// Requests are authorized with [shared access signatures][sas] signed with the key, whose
+    /// policy must have the `Send` claim.
+    ///
+    /// [sas]: https://learn.microsoft.com/en-us/azure/event-hubs/authenticate-shared-access-signature
+    SharedAccessKey {
+        /// The name of the shared access policy of the key.
+        #[configurable(metadata(docs::examples = "RootManageSharedAccessKey"))]
+        key_name: String,
+
+        /// The shared access key.
+        #[configurable(metadata(docs::examples = "${EVENT_HUBS_SHARED_ACCESS_KEY}"))]
+        key: SensitiveString,
+    },
+
+    /// Authentication with an Azure Active Directory application.
+    ///
+    /// The application must be assigned the `Azure Event Hubs Data Sender` role.
+    ClientSecret {
+        /// The ID of the Azure Active Directory tenant of the application.
+        #[configurable(metadata(docs::examples = "00000000-0000-0000-0000-000000000000"))]
+        tenant_id: String,
+
+        /// The ID of the application.
+        #[configurable(metadata(docs::examples = "00000000-0000-0000-0000-000000000000"))]
+        client_id: String,
+
+        /// A client secret of the application.
+        #[configurable(metadata(docs::examples = "${AZURE_CLIENT_SECRET}"))]
+        client_secret: SensitiveString,
+    },
+}
+
+/// Configuration for the `azure_event_hubs` sink.
+#[configurable_component(sink(
+    "azure_event_hubs",
+    "Publish observability events to Azure Event Hubs."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct AzureEventHubsConfig {
+    /// The fully qualified name of the Event Hubs namespace.
+    #[configurable(metadata(docs::examples = "mynamespace.servicebus.windows.net"))]
+    pub namespace: String,
+
+    /// The event hub that events are sent to.
+    #[configurable(metadata(docs::examples = "logs"))]
+    #[configurable(metadata(docs::examples = "{{ application }}-logs"))]
+    pub event_hub: Template,
+
+    /// The partition key that events are sent with.
+    ///
+    /// Events with the same partition key are sent to the same partition of the event hub, in
+    /// order. If not set, Event Hubs distributes the events across the partitions.
+    #[configurable(metadata(docs::examples = "{{ host }}"))]
+    pub partition_key: Option<Template>,
+
+    #[configurable(derived)]
+    pub auth: Option<AzureEventHubsAuth>,
+
+    /// The maximum size of the requests, which must not exceed the maximum size of the messages
+    /// of the namespace: 256 KiB for the Basic tier, and 1 MiB for the other tiers.
+    ///
+    /// Batches are split into as many requests as needed, and events larger than a request on
+    /// their own are rejected.
+    #[serde(default = "default_max_request_size")]
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    pub max_request_size: NonZeroUsize,
+
+    #[configurable(derived)]
+    pub encoding: EncodingConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch: BatchConfig<AzureEventHubsDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::is_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+impl GenerateConfig for AzureEventHubsConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"namespace = "mynamespace.servicebus.windows.net"
+            event_hub = "logs"
+            encoding.codec = "json""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "azure_event_hubs")]
+impl SinkConfig for AzureEventHubsConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let batch_settings = self.batch.validate()?.into_batcher_settings()?;
+
+        let tls_settings = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new(tls_settings, &cx.proxy)?;
+        let client = EventHubsClient::new(client, self.namespace.clone(), self.credential());
+
+        let healthcheck = {
+            let client = client.clone();
+            Box::pin(async move { Ok(client.healthcheck().await?) })
+        };
+
+        let service = ServiceBuilder::new()
+            .settings(self.request.into_settings(), AzureEventHubsRetryLogic)
+            .service(AzureEventHubsService::new(client));
+
+        let encoder = EventHubsEncoder {
+            transformer: self.encoding.transformer(),
+            encoder: Encoder::<()>::new(self.encoding.build()?),
+            max_request_size: self.max_request_size.get(),
+        };
+
+        let sink = AzureEventHubsSink {
+            batch_settings,
+            service,
+            event_hub: self.event_hub.clone(),
+            partition_key: self.partition_key.clone(),
+            encoder,
+        };
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::new(self.encoding.config().input_type() & (DataType::Log | DataType::Metric))
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+impl AzureEventHubsConfig {
+    fn credential(&self) -> Credential {
+        let credential: Arc<dyn TokenCredential> = match &self.auth {
+            Some(AzureEventHubsAuth::SharedAccessKey { key_name, key }) => {
+                return Credential::SharedAccessKey {
+                    key_name: key_name.clone(),
+                    key: key.clone(),
+                }
+            }
+            Some(AzureEventHubsAuth::ClientSecret {
+                tenant_id,
+                client_id,
+                client_secret,
+            }) => Arc::new(ClientSecretCredential::new(
+                azure_core::new_http_client(),
+                tenant_id.clone(),
+                client_id.clone(),
+                client_secret.inner().to_string(),
+                TokenCredentialOptions::default(),
+            )),
+            // Reads the credentials from the environment, a managed identity, or the Azure CLI.
+            None => Arc::new(DefaultAzureCredential::default()),
+        };
+        Credential::Token(Arc::new(AutoRefreshingTokenCredential::new(credential)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<AzureEventHubsConfig>();
+    }
+
+    #[test]
+    fn parses_auth() {
+        let config: AzureEventHubsConfig = toml::from_str(
+            r#"
+            namespace = "mynamespace.servicebus.windows.net"
+            event_hub = "logs"
+            partition_key = "{{ host }}"
+            max_request_size = 262144
+            auth.strategy = "shared_access_key"
+            auth.key_name = "RootManageSharedAccessKey"
+            auth.key = "c2VjcmV0"
+            encoding.codec = "json"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.max_request_size.get(), 256 * 1024);
+        assert!(matches!(
+            config.credential(),
+            Credential::SharedAccessKey { key_name, .. } if key_name == "RootManageSharedAccessKey"
+        ));
+    }
+}
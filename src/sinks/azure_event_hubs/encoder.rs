@@ -0,0 +1,232 @@
+//! Encoding of events into the batches of requests.
+
+use std::num::NonZeroUsize;
+
+use bytes::{Bytes, BytesMut};
+use serde::Serialize;
+use tokio_util::codec::Encoder as _;
+
+use super::{service::AzureEventHubsRequest, sink::PartitionKey};
+use crate::sinks::prelude::*;
+
+/// An event of a batch, in the batch format of Event Hubs.
+#[derive(Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct EventData<'a> {
+    body: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    broker_properties: Option<BrokerProperties<'a>>,
+}
+
+#[derive(Clone, Copy, Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct BrokerProperties<'a> {
+    partition_key: &'a str,
+}
+
+/// The batch of events of a request being built.
+struct PendingRequest {
+    body: Vec<u8>,
+    count: usize,
+    byte_size: GroupedCountByteSize,
+    finalizers: EventFinalizers,
+}
+
+impl PendingRequest {
+    fn new() -> Self {
+        Self {
+            body: Vec::new(),
+            count: 0,
+            byte_size: telemetry().create_request_count_byte_size(),
+            finalizers: EventFinalizers::default(),
+        }
+    }
+
+    /// The size of the body with the entry appended, the entries being separated by commas and
+    /// enclosed in brackets.
+    fn size_with(&self, entry: &[u8]) -> usize {
+        self.body.len() + entry.len() + 2
+    }
+
+    fn push(&mut self, entry: &[u8], byte_size: GroupedCountByteSize, finalizers: EventFinalizers) {
+        self.body.push(if self.count == 0 { b'[' } else { b',' });
+        self.body.extend_from_slice(entry);
+        self.count += 1;
+        self.byte_size += byte_size;
+        self.finalizers.merge(finalizers);
+    }
+
+    fn finish(mut self, event_hub: &str) -> AzureEventHubsRequest {
+        self.body.push(b']');
+        let request_size = self.body.len();
+        let metadata = RequestMetadataBuilder::new(self.count, request_size, self.byte_size)
+            .with_request_size(NonZeroUsize::new(request_size).unwrap_or(NonZeroUsize::MIN));
+
+        AzureEventHubsRequest {
+            event_hub: event_hub.to_string(),
+            body: Bytes::from(self.body),
+            finalizers: self.finalizers,
+            metadata,
+        }
+    }
+}
+
+/// Encodes the events of batches into requests no larger than the maximum size of the messages
+/// of the namespace.
+pub(super) struct EventHubsEncoder {
+    pub(super) transformer: Transformer,
+    pub(super) encoder: Encoder<()>,
+    pub(super) max_request_size: usize,
+}
+
+impl EventHubsEncoder {
+    /// Encodes the events into requests to the event hub of the partition, rejecting the events
+    /// which can't be encoded or which are larger than a request on their own.
+    pub(super) fn encode(
+        &mut self,
+        key: PartitionKey,
+        events: Vec<Event>,
+    ) -> Vec<AzureEventHubsRequest> {
+        let broker_properties = key
+            .partition_key
+            .as_deref()
+            .map(|partition_key| BrokerProperties { partition_key });
+        let mut requests = Vec::new();
+        let mut pending = PendingRequest::new();
+
+        for mut event in events {
+            let event_finalizers = event.take_finalizers();
+            self.transformer.transform(&mut event);
+
+            let mut event_size = telemetry().create_request_count_byte_size();
+            event_size.add_event(&event, event.estimated_json_encoded_size_of());
+
+            let mut bytes = BytesMut::new();
+            if self.encoder.encode(event, &mut bytes).is_err() {
+                // The serializer emits the error and the dropped event.
+                event_finalizers.update_status(EventStatus::Rejected);
+                continue;
+            }
+            let Ok(body) = std::str::from_utf8(&bytes) else {
+                emit!(SinkRequestBuildError {
+                    error: "The encoded event is not valid UTF-8, as batches of events require."
+                });
+                event_finalizers.update_status(EventStatus::Rejected);
+                continue;
+            };
+
+            let entry = serde_json::to_vec(&EventData {
+                body,
+                broker_properties,
+            })
+            .expect("serializing events to JSON should not fail");
+
+            if PendingRequest::new().size_with(&entry) > self.max_request_size {
+                emit!(SinkRequestBuildError {
+                    error: format!(
+                        "The encoded event of {} bytes is larger than the maximum request size of {} bytes.",
+                        entry.len(),
+                        self.max_request_size
+                    )
+                });
+                event_finalizers.update_status(EventStatus::Rejected);
+                continue;
+            }
+
+            if pending.count > 0 && pending.size_with(&entry) > self.max_request_size {
+                requests.push(
+                    std::mem::replace(&mut pending, PendingRequest::new()).finish(&key.event_hub),
+                );
+            }
+            pending.push(&entry, event_size, event_finalizers);
+        }
+
+        if pending.count > 0 {
+            requests.push(pending.finish(&key.event_hub));
+        }
+        requests
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_lib::codecs::encoding::{JsonSerializerConfig, Serializer};
+    use vector_lib::event::{BatchNotifier, BatchStatus, LogEvent};
+
+    use super::*;
+
+    fn encoder(max_request_size: usize) -> EventHubsEncoder {
+        EventHubsEncoder {
+            transformer: Transformer::default(),
+            encoder: Encoder::<()>::new(Serializer::Json(JsonSerializerConfig::default().build())),
+            max_request_size,
+        }
+    }
+
+    fn log(message: String) -> Event {
+        LogEvent::from(vrl::value!({ "message": (message) })).into()
+    }
+
+    fn key(partition_key: Option<&str>) -> PartitionKey {
+        PartitionKey {
+            event_hub: "logs".to_string(),
+            partition_key: partition_key.map(Into::into),
+        }
+    }
+
+    #[test]
+    fn encodes_batches() {
+        let requests = encoder(1024 * 1024).encode(
+            key(Some("host-1")),
+            vec![log("first".to_string()), log("second".to_string())],
+        );
+
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].event_hub, "logs");
+        let body: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!([
+                {
+                    "Body": r#"{"message":"first"}"#,
+                    "BrokerProperties": { "PartitionKey": "host-1" },
+                },
+                {
+                    "Body": r#"{"message":"second"}"#,
+                    "BrokerProperties": { "PartitionKey": "host-1" },
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn splits_batches_by_size() {
+        let events = (0..10)
+            .map(|index| log(format!("event {}", index)))
+            .collect();
+        // Each entry is 36 bytes, so that three of them fit in a request of 112 bytes.
+        let requests = encoder(120).encode(key(None), events);
+
+        assert_eq!(
+            requests
+                .iter()
+                .map(|request| request.metadata.event_count())
+                .collect::<Vec<_>>(),
+            vec![3, 3, 3, 1]
+        );
+        for request in requests {
+            assert!(request.body.len() <= 120);
+            serde_json::from_slice::<serde_json::Value>(&request.body).unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn rejects_oversized_events() {
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        let events = vec![log("x".repeat(200)).with_batch_notifier(&batch)];
+        drop(batch);
+
+        assert!(encoder(120).encode(key(None), events).is_empty());
+        assert_eq!(receiver.await, BatchStatus::Rejected);
+    }
+}
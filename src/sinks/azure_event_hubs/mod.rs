@@ -0,0 +1,18 @@
+//! The `azure_event_hubs` sink.
+//!
+//! This sink publishes events to the event hubs of [Azure Event Hubs][event_hubs] namespaces,
+//! authenticating with a shared access signature or with Azure Active Directory.
+//!
+//! Batches of events are partitioned by event hub and partition key, and sent with the batch
+//! format of the HTTPS API of Event Hubs, each batch being split into requests no larger than
+//! the maximum size of the messages of the namespace.
+//!
+//! [event_hubs]: https://learn.microsoft.com/en-us/azure/event-hubs/
+
+mod client;
+mod config;
+mod encoder;
+mod service;
+mod sink;
+
+pub use self::config::AzureEventHubsConfig;
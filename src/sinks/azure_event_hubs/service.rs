@@ -0,0 +1,106 @@
+//! Service implementation for the `azure_event_hubs` sink.
+
+use std::task::{Context, Poll};
+
+use bytes::Bytes;
+
+use super::client::{EventHubsClient, EventHubsError};
+use crate::sinks::prelude::*;
+
+#[derive(Clone)]
+pub(super) struct AzureEventHubsRequest {
+    pub(super) event_hub: String,
+    /// The events, in the batch format of Event Hubs.
+    pub(super) body: Bytes,
+    pub(super) finalizers: EventFinalizers,
+    pub(super) metadata: RequestMetadata,
+}
+
+impl Finalizable for AzureEventHubsRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        std::mem::take(&mut self.finalizers)
+    }
+}
+
+impl MetaDescriptive for AzureEventHubsRequest {
+    fn get_metadata(&self) -> &RequestMetadata {
+        &self.metadata
+    }
+
+    fn metadata_mut(&mut self) -> &mut RequestMetadata {
+        &mut self.metadata
+    }
+}
+
+#[derive(Debug)]
+pub(super) struct AzureEventHubsResponse {
+    events_byte_size: GroupedCountByteSize,
+    byte_size: usize,
+}
+
+impl DriverResponse for AzureEventHubsResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> &GroupedCountByteSize {
+        &self.events_byte_size
+    }
+
+    fn bytes_sent(&self) -> Option<usize> {
+        Some(self.byte_size)
+    }
+}
+
+#[derive(Clone, Default)]
+pub(super) struct AzureEventHubsRetryLogic;
+
+impl RetryLogic for AzureEventHubsRetryLogic {
+    type Error = EventHubsError;
+    type Response = AzureEventHubsResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        error.is_retriable()
+    }
+}
+
+/// Sends the batches of events of requests to their event hub.
+#[derive(Clone)]
+pub(super) struct AzureEventHubsService {
+    client: EventHubsClient,
+}
+
+impl AzureEventHubsService {
+    pub(super) const fn new(client: EventHubsClient) -> Self {
+        Self { client }
+    }
+}
+
+impl Service<AzureEventHubsRequest> for AzureEventHubsService {
+    type Response = AzureEventHubsResponse;
+    type Error = EventHubsError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: AzureEventHubsRequest) -> Self::Future {
+        let service = self.clone();
+
+        Box::pin(async move {
+            let byte_size = request.body.len();
+            service
+                .client
+                .send_batch(&request.event_hub, request.body)
+                .await?;
+
+            Ok(AzureEventHubsResponse {
+                events_byte_size: request
+                    .metadata
+                    .into_events_estimated_json_encoded_byte_size(),
+                byte_size,
+            })
+        })
+    }
+}
@@ -0,0 +1,118 @@
+//! Implementation of the `azure_event_hubs` sink.
+
+use futures::stream;
+
+use super::{
+    encoder::EventHubsEncoder,
+    service::{AzureEventHubsRetryLogic, AzureEventHubsService},
+};
+use crate::sinks::prelude::*;
+
+/// The event hub and partition key that events are sent with.
+#[derive(Hash, Eq, PartialEq, Clone, Debug)]
+pub(super) struct PartitionKey {
+    pub(super) event_hub: String,
+    pub(super) partition_key: Option<String>,
+}
+
+/// Partitions events by the event hub and partition key they are sent with.
+struct KeyPartitioner {
+    event_hub: Template,
+    partition_key: Option<Template>,
+}
+
+impl KeyPartitioner {
+    fn render(template: &Template, item: &Event, field: &'static str) -> Option<String> {
+        template
+            .render_string(item)
+            .map_err(|error| {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some(field),
+                    drop_event: true,
+                });
+            })
+            .ok()
+    }
+}
+
+impl Partitioner for KeyPartitioner {
+    type Item = Event;
+    type Key = Option<PartitionKey>;
+
+    fn partition(&self, item: &Self::Item) -> Self::Key {
+        let event_hub = Self::render(&self.event_hub, item, "event_hub")?;
+        let partition_key = match &self.partition_key {
+            Some(template) => Some(Self::render(template, item, "partition_key")?),
+            None => None,
+        };
+        Some(PartitionKey {
+            event_hub,
+            partition_key,
+        })
+    }
+}
+
+pub(super) struct AzureEventHubsSink {
+    pub(super) batch_settings: BatcherSettings,
+    pub(super) service: Svc<AzureEventHubsService, AzureEventHubsRetryLogic>,
+    pub(super) event_hub: Template,
+    pub(super) partition_key: Option<Template>,
+    pub(super) encoder: EventHubsEncoder,
+}
+
+impl AzureEventHubsSink {
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let batch_settings = self.batch_settings;
+        let mut encoder = self.encoder;
+        let partitioner = KeyPartitioner {
+            event_hub: self.event_hub,
+            partition_key: self.partition_key,
+        };
+
+        input
+            .batched_partitioned(partitioner, || batch_settings.as_byte_size_config())
+            .filter_map(|(key, batch)| async move { key.map(move |key| (key, batch)) })
+            .flat_map(move |(key, events)| stream::iter(encoder.encode(key, events)))
+            .into_driver(self.service)
+            .protocol("https")
+            .run()
+            .await
+    }
+}
+
+#[async_trait::async_trait]
+impl StreamSink<Event> for AzureEventHubsSink {
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_lib::event::LogEvent;
+
+    use super::*;
+
+    #[test]
+    fn partitions_by_event_hub_and_key() {
+        let partitioner = KeyPartitioner {
+            event_hub: Template::try_from("{{ service }}-logs").unwrap(),
+            partition_key: Some(Template::try_from("{{ host }}").unwrap()),
+        };
+
+        let mut log = LogEvent::from("hello");
+        log.insert("service", "api");
+        log.insert("host", "host-1");
+        assert_eq!(
+            partitioner.partition(&log.clone().into()),
+            Some(PartitionKey {
+                event_hub: "api-logs".to_string(),
+                partition_key: Some("host-1".to_string()),
+            })
+        );
+
+        log.remove("host");
+        assert_eq!(partitioner.partition(&log.into()), None);
+    }
+}
@@ -1,14 +1,32 @@
 use std::time::Duration;
 
 use futures::{future, FutureExt};
+use rand::distributions::{Bernoulli, Distribution, Uniform};
+use rand_distr::{Exp, Normal};
 use serde_with::serde_as;
+use snafu::Snafu;
 use vector_lib::configurable::configurable_component;
 
 use crate::{
     config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
+    event::EventStatus,
     sinks::{blackhole::sink::BlackholeSink, Healthcheck, VectorSink},
 };
 
+#[derive(Debug, Snafu)]
+pub enum BuildError {
+    #[snafu(display("`errors.rate` must be between 0 and 1, got {}", rate))]
+    InvalidErrorRate { rate: f64 },
+    #[snafu(display("`latency.min_ms` must not be greater than `latency.max_ms`"))]
+    InvalidUniformLatency,
+    #[snafu(display(
+        "`latency.mean_ms` and `latency.std_dev_ms` must be finite, and `latency.std_dev_ms` not negative"
+    ))]
+    InvalidNormalLatency,
+    #[snafu(display("`latency.mean_ms` must be finite and positive"))]
+    InvalidExponentialLatency,
+}
+
 const fn default_print_interval_secs() -> Duration {
     Duration::from_secs(0)
 }
@@ -39,6 +57,12 @@ pub struct BlackholeConfig {
     #[configurable(metadata(docs::examples = 1000))]
     pub rate: Option<usize>,
 
+    #[configurable(derived)]
+    pub latency: Option<LatencyConfig>,
+
+    #[configurable(derived)]
+    pub errors: Option<ErrorsConfig>,
+
     #[configurable(derived)]
     #[serde(
         default,
@@ -52,7 +76,7 @@ pub struct BlackholeConfig {
 #[typetag::serde(name = "blackhole")]
 impl SinkConfig for BlackholeConfig {
     async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
-        let sink = BlackholeSink::new(self.clone());
+        let sink = BlackholeSink::new(self.clone())?;
         let healthcheck = future::ok(()).boxed();
 
         Ok((VectorSink::Stream(Box::new(sink)), healthcheck))
@@ -67,6 +91,160 @@ impl SinkConfig for BlackholeConfig {
     }
 }
 
+/// The artificial latency applied before acknowledging each batch of events.
+///
+/// The latency is sampled for each batch, and the batches are acknowledged concurrently, as a
+/// downstream system would with pipelined requests.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(tag = "distribution", rename_all = "snake_case", deny_unknown_fields)]
+#[configurable(metadata(
+    docs::enum_tag_description = "The distribution that the latency is sampled from."
+))]
+pub enum LatencyConfig {
+    /// Every batch is acknowledged after the same latency.
+    Constant {
+        /// The latency, in milliseconds.
+        #[configurable(metadata(docs::examples = 50))]
+        latency_ms: u64,
+    },
+
+    /// The latency is sampled uniformly between a minimum and a maximum.
+    Uniform {
+        /// The minimum latency, in milliseconds.
+        #[configurable(metadata(docs::examples = 10))]
+        min_ms: u64,
+
+        /// The maximum latency, in milliseconds.
+        #[configurable(metadata(docs::examples = 100))]
+        max_ms: u64,
+    },
+
+    /// The latency is sampled from a normal distribution.
+    ///
+    /// Negative samples are clamped to no latency.
+    Normal {
+        /// The mean latency, in milliseconds.
+        #[configurable(metadata(docs::examples = 50.0))]
+        mean_ms: f64,
+
+        /// The standard deviation of the latency, in milliseconds.
+        #[configurable(metadata(docs::examples = 10.0))]
+        std_dev_ms: f64,
+    },
+
+    /// The latency is sampled from an exponential distribution.
+    ///
+    /// This models a system whose requests mostly complete quickly, with a long tail of slow
+    /// requests.
+    Exponential {
+        /// The mean latency, in milliseconds.
+        #[configurable(metadata(docs::examples = 50.0))]
+        mean_ms: f64,
+    },
+}
+
+impl LatencyConfig {
+    pub(super) fn build(&self) -> Result<Latency, BuildError> {
+        Ok(match *self {
+            Self::Constant { latency_ms } => Latency::Constant(Duration::from_millis(latency_ms)),
+            Self::Uniform { min_ms, max_ms } => {
+                if min_ms > max_ms {
+                    return Err(BuildError::InvalidUniformLatency);
+                }
+                Latency::Uniform(Uniform::new_inclusive(min_ms, max_ms))
+            }
+            Self::Normal {
+                mean_ms,
+                std_dev_ms,
+            } => {
+                if !mean_ms.is_finite() || !std_dev_ms.is_finite() {
+                    return Err(BuildError::InvalidNormalLatency);
+                }
+                Latency::Normal(
+                    Normal::new(mean_ms, std_dev_ms)
+                        .map_err(|_| BuildError::InvalidNormalLatency)?,
+                )
+            }
+            Self::Exponential { mean_ms } => {
+                if !mean_ms.is_finite() || mean_ms <= 0.0 {
+                    return Err(BuildError::InvalidExponentialLatency);
+                }
+                Latency::Exponential(
+                    Exp::new(1.0 / mean_ms).map_err(|_| BuildError::InvalidExponentialLatency)?,
+                )
+            }
+        })
+    }
+}
+
+/// The distribution of the latency, in milliseconds.
+#[derive(Clone, Debug)]
+pub(super) enum Latency {
+    Constant(Duration),
+    Uniform(Uniform<u64>),
+    Normal(Normal<f64>),
+    Exponential(Exp<f64>),
+}
+
+impl Latency {
+    pub(super) fn sample(&self) -> Duration {
+        let mut rng = rand::thread_rng();
+        let millis = match self {
+            Self::Constant(latency) => return *latency,
+            Self::Uniform(uniform) => return Duration::from_millis(uniform.sample(&mut rng)),
+            Self::Normal(normal) => normal.sample(&mut rng),
+            Self::Exponential(exp) => exp.sample(&mut rng),
+        };
+        Duration::try_from_secs_f64(millis.max(0.0) / 1000.0).unwrap_or(Duration::MAX)
+    }
+}
+
+/// Errors injected into the acknowledgement of batches.
+///
+/// This can be used to validate how the sources and the topology upstream handle failed
+/// deliveries.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ErrorsConfig {
+    /// The ratio of batches that fail, between `0` and `1`.
+    #[configurable(metadata(docs::examples = 0.01))]
+    pub rate: f64,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub status: InjectedStatus,
+}
+
+impl ErrorsConfig {
+    pub(super) fn build(&self) -> Result<Bernoulli, BuildError> {
+        Bernoulli::new(self.rate).map_err(|_| BuildError::InvalidErrorRate { rate: self.rate })
+    }
+}
+
+/// The status that failed batches are acknowledged with.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum InjectedStatus {
+    /// The batches fail with a transient error, and sources that support it can retry them.
+    #[default]
+    Errored,
+
+    /// The batches are rejected, as a permanent failure, and their events are dropped.
+    Rejected,
+}
+
+impl From<InjectedStatus> for EventStatus {
+    fn from(status: InjectedStatus) -> Self {
+        match status {
+            InjectedStatus::Errored => EventStatus::Errored,
+            InjectedStatus::Rejected => EventStatus::Rejected,
+        }
+    }
+}
+
 impl GenerateConfig for BlackholeConfig {
     fn generate_config() -> toml::Value {
         toml::Value::try_from(Self::default()).unwrap()
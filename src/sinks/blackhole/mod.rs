@@ -6,15 +6,24 @@ pub use config::BlackholeConfig;
 #[cfg(test)]
 mod tests {
 
-    use std::time::Duration;
+    use std::time::{Duration, Instant};
+
+    use vector_lib::event::{BatchNotifier, BatchStatus};
 
     use crate::{
         sinks::{
-            blackhole::{config::BlackholeConfig, sink::BlackholeSink},
+            blackhole::{
+                config::{BlackholeConfig, ErrorsConfig, InjectedStatus, LatencyConfig},
+                sink::BlackholeSink,
+            },
             VectorSink,
         },
         test_util::{
-            components::run_and_assert_nonsending_sink_compliance, random_events_with_stream,
+            components::{
+                run_and_assert_nonsending_sink_compliance, run_and_assert_sink_error,
+                COMPONENT_ERROR_TAGS,
+            },
+            random_events_with_stream,
         },
     };
 
@@ -23,12 +32,91 @@ mod tests {
         let config = BlackholeConfig {
             print_interval_secs: Duration::from_secs(10),
             rate: None,
+            latency: None,
+            errors: None,
             acknowledgements: Default::default(),
         };
-        let sink = BlackholeSink::new(config);
+        let sink = BlackholeSink::new(config).unwrap();
         let sink = VectorSink::Stream(Box::new(sink));
 
         let (_input_lines, events) = random_events_with_stream(100, 10, None);
         run_and_assert_nonsending_sink_compliance(sink, events, &[]).await;
     }
+
+    #[tokio::test]
+    async fn delays_acknowledgements() {
+        let config = BlackholeConfig {
+            latency: Some(LatencyConfig::Constant { latency_ms: 200 }),
+            ..Default::default()
+        };
+        let sink = BlackholeSink::new(config).unwrap();
+        let sink = VectorSink::Stream(Box::new(sink));
+
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        let (_input_lines, events) = random_events_with_stream(100, 10, Some(batch));
+        let start = Instant::now();
+        run_and_assert_nonsending_sink_compliance(sink, events, &[]).await;
+
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+        assert!(start.elapsed() >= Duration::from_millis(200));
+    }
+
+    #[tokio::test]
+    async fn injects_errors() {
+        let config = BlackholeConfig {
+            errors: Some(ErrorsConfig {
+                rate: 1.0,
+                status: InjectedStatus::Rejected,
+            }),
+            ..Default::default()
+        };
+        let sink = BlackholeSink::new(config).unwrap();
+        let sink = VectorSink::Stream(Box::new(sink));
+
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        let (_input_lines, events) = random_events_with_stream(100, 10, Some(batch));
+        run_and_assert_sink_error(sink, events, &COMPONENT_ERROR_TAGS).await;
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Rejected));
+    }
+
+    #[test]
+    fn rejects_invalid_injections() {
+        let build = |latency: Option<LatencyConfig>, rate: Option<f64>| {
+            BlackholeSink::new(BlackholeConfig {
+                latency,
+                errors: rate.map(|rate| ErrorsConfig {
+                    rate,
+                    status: InjectedStatus::Errored,
+                }),
+                ..Default::default()
+            })
+        };
+
+        assert!(build(
+            Some(LatencyConfig::Uniform {
+                min_ms: 10,
+                max_ms: 100
+            }),
+            Some(0.5)
+        )
+        .is_ok());
+        assert!(build(
+            Some(LatencyConfig::Uniform {
+                min_ms: 100,
+                max_ms: 10
+            }),
+            None
+        )
+        .is_err());
+        assert!(build(
+            Some(LatencyConfig::Normal {
+                mean_ms: 50.0,
+                std_dev_ms: -1.0
+            }),
+            None
+        )
+        .is_err());
+        assert!(build(Some(LatencyConfig::Exponential { mean_ms: 0.0 }), None).is_err());
+        assert!(build(None, Some(1.5)).is_err());
+    }
 }
@@ -7,40 +7,86 @@ use std::{
 };
 
 use async_trait::async_trait;
-use futures::{stream::BoxStream, StreamExt};
+use chrono::{DateTime, Utc};
+use futures::{
+    future::{self, BoxFuture},
+    stream::{BoxStream, FuturesUnordered},
+    FutureExt, StreamExt,
+};
+use rand::distributions::{Bernoulli, Distribution};
 use tokio::{
     select,
     sync::watch,
-    time::{interval, sleep_until},
+    time::{interval, sleep, sleep_until},
 };
 use vector_lib::internal_event::{
     ByteSize, BytesSent, CountByteSize, EventsSent, InternalEventHandle as _, Output, Protocol,
 };
-use vector_lib::EstimatedJsonEncodedSizeOf;
+use vector_lib::{lookup::event_path, EstimatedJsonEncodedSizeOf};
 
 use crate::{
-    event::{EventArray, EventContainer},
-    sinks::{blackhole::config::BlackholeConfig, util::StreamSink},
+    event::{EventArray, EventContainer, EventRef, EventStatus, Finalizable, Value},
+    internal_events::{BlackholeBatchSize, BlackholeEventLatency, BlackholeInjectedError},
+    sinks::{
+        blackhole::config::{BlackholeConfig, BuildError, Latency},
+        util::StreamSink,
+    },
 };
 
 pub struct BlackholeSink {
     total_events: Arc<AtomicUsize>,
     total_raw_bytes: Arc<AtomicUsize>,
     config: BlackholeConfig,
+    latency: Option<Latency>,
+    errors: Option<(Bernoulli, EventStatus)>,
     last: Option<Instant>,
 }
 
 impl BlackholeSink {
-    pub fn new(config: BlackholeConfig) -> Self {
-        BlackholeSink {
+    pub fn new(config: BlackholeConfig) -> Result<Self, BuildError> {
+        let latency = config
+            .latency
+            .as_ref()
+            .map(|latency| latency.build())
+            .transpose()?;
+        let errors = config
+            .errors
+            .as_ref()
+            .map(|errors| Ok((errors.build()?, errors.status.into())))
+            .transpose()?;
+
+        Ok(BlackholeSink {
             config,
             total_events: Arc::new(AtomicUsize::new(0)),
             total_raw_bytes: Arc::new(AtomicUsize::new(0)),
+            latency,
+            errors,
             last: None,
+        })
+    }
+
+    /// The status that a batch is acknowledged with, which fails at the rate of injected errors.
+    fn sample_status(&self) -> EventStatus {
+        match &self.errors {
+            Some((rate, status)) if rate.sample(&mut rand::thread_rng()) => *status,
+            _ => EventStatus::Delivered,
         }
     }
 }
 
+/// The time elapsed since an event was created, if it has a timestamp.
+fn event_latency(event: EventRef<'_>, now: DateTime<Utc>) -> Option<Duration> {
+    let timestamp = match event {
+        EventRef::Log(log) => log.get_timestamp().and_then(Value::as_timestamp).copied(),
+        EventRef::Metric(metric) => metric.timestamp(),
+        EventRef::Trace(trace) => trace
+            .get(event_path!("timestamp"))
+            .and_then(Value::as_timestamp)
+            .copied(),
+    }?;
+    (now - timestamp).to_std().ok()
+}
+
 #[async_trait]
 impl StreamSink<EventArray> for BlackholeSink {
     async fn run(mut self: Box<Self>, mut input: BoxStream<'_, EventArray>) -> Result<(), ()> {
@@ -78,7 +124,19 @@ impl StreamSink<EventArray> for BlackholeSink {
             });
         }
 
-        while let Some(events) = input.next().await {
+        let batch_size = register!(BlackholeBatchSize);
+        let end_to_end_latency = register!(BlackholeEventLatency);
+        let mut pending_acks = FuturesUnordered::<BoxFuture<'static, ()>>::new();
+
+        loop {
+            let mut events = select! {
+                Some(()) = pending_acks.next() => continue,
+                events = input.next() => match events {
+                    Some(events) => events,
+                    None => break,
+                },
+            };
+
             if let Some(rate) = self.config.rate {
                 let factor: f32 = 1.0 / rate as f32;
                 let secs: f32 = factor * (events.len() as f32);
@@ -87,17 +145,45 @@ impl StreamSink<EventArray> for BlackholeSink {
                 self.last = Some(until);
             }
 
-            let message_len = events.estimated_json_encoded_size_of();
+            batch_size.emit(events.len());
+            let now = Utc::now();
+            events
+                .iter_events()
+                .filter_map(|event| event_latency(event, now))
+                .for_each(|latency| end_to_end_latency.emit(latency));
 
-            _ = self.total_events.fetch_add(events.len(), Ordering::AcqRel);
-            _ = self
-                .total_raw_bytes
-                .fetch_add(message_len.get(), Ordering::AcqRel);
+            let finalizers = events.take_finalizers();
+            let status = self.sample_status();
+            if status == EventStatus::Delivered {
+                let message_len = events.estimated_json_encoded_size_of();
 
-            events_sent.emit(CountByteSize(events.len(), message_len));
-            bytes_sent.emit(ByteSize(message_len.get()));
+                _ = self.total_events.fetch_add(events.len(), Ordering::AcqRel);
+                _ = self
+                    .total_raw_bytes
+                    .fetch_add(message_len.get(), Ordering::AcqRel);
+
+                events_sent.emit(CountByteSize(events.len(), message_len));
+                bytes_sent.emit(ByteSize(message_len.get()));
+            } else {
+                emit!(BlackholeInjectedError {
+                    count: events.len(),
+                    status,
+                });
+            }
+
+            match &self.latency {
+                Some(latency) => pending_acks.push(
+                    sleep(latency.sample())
+                        .map(move |()| finalizers.update_status(status))
+                        .boxed(),
+                ),
+                None => finalizers.update_status(status),
+            }
         }
 
+        // Acknowledge the batches that are still delayed.
+        pending_acks.for_each(|()| future::ready(())).await;
+
         // Notify the reporting task to shutdown.
         _ = shutdown.send(());
 
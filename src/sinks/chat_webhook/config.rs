@@ -0,0 +1,268 @@
+//! Configuration for the `chat_webhook` sink.
+
+use std::{num::NonZeroU32, sync::Arc, time::Duration};
+
+use governor::{Quota, RateLimiter};
+use snafu::Snafu;
+
+use super::{
+    encoder::ChatWebhookEncoder,
+    request_builder::ChatWebhookRequestBuilder,
+    service::{ChannelRateLimitService, ChatWebhookServiceRequestBuilder},
+    sink::ChatWebhookSink,
+};
+use crate::{
+    http::HttpClient,
+    sinks::{
+        prelude::*,
+        util::http::{http_response_retry_logic, HttpService},
+    },
+};
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display(
+        "The threshold and window of `channel_rate_limit` must be greater than zero"
+    ))]
+    ZeroChannelRateLimit,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ChatWebhookDefaultBatchSettings;
+
+impl SinkBatchSettings for ChatWebhookDefaultBatchSettings {
+    const MAX_EVENTS: Option<usize> = Some(20);
+    const MAX_BYTES: Option<usize> = None;
+    const TIMEOUT_SECS: f64 = 5.0;
+}
+
+/// The chat platform of the incoming webhooks.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ChatPlatform {
+    /// [Slack][slack] incoming webhooks.
+    ///
+    /// Messages are sent as text, with their title in bold.
+    ///
+    /// [slack]: https://api.slack.com/messaging/webhooks
+    Slack,
+
+    /// [Microsoft Teams][teams] incoming webhooks, or the webhooks of Workflows.
+    ///
+    /// Messages are sent as Adaptive Cards, with their title and each of their lines in their own
+    /// text block.
+    ///
+    /// [teams]: https://learn.microsoft.com/en-us/microsoftteams/platform/webhooks-and-connectors/how-to/connectors-using
+    Teams,
+
+    /// [Discord][discord] webhooks.
+    ///
+    /// Messages are sent as embeds, and the mentions that they hold don't notify anyone.
+    ///
+    /// [discord]: https://discord.com/developers/docs/resources/webhook#execute-webhook
+    Discord,
+}
+
+/// The rate limit of the messages sent to each webhook.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ChannelRateLimitConfig {
+    /// The number of messages that can be sent to each webhook within the `window_secs` time
+    /// window.
+    #[serde(default = "default_threshold")]
+    pub threshold: u32,
+
+    /// The time window of the `threshold` option.
+    #[serde(default = "default_window_secs")]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    pub window_secs: f64,
+}
+
+impl Default for ChannelRateLimitConfig {
+    fn default() -> Self {
+        Self {
+            threshold: default_threshold(),
+            window_secs: default_window_secs(),
+        }
+    }
+}
+
+const fn default_threshold() -> u32 {
+    1
+}
+
+const fn default_window_secs() -> f64 {
+    1.0
+}
+
+/// Configuration for the `chat_webhook` sink.
+#[configurable_component(sink(
+    "chat_webhook",
+    "Send log events as messages to the incoming webhooks of Slack, Microsoft Teams, or Discord."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ChatWebhookSinkConfig {
+    #[configurable(derived)]
+    pub platform: ChatPlatform,
+
+    /// The URL of the incoming webhook to send messages to.
+    ///
+    /// Events are batched by the URL rendered for them, so that each message only holds the
+    /// events sent to its webhook.
+    #[configurable(metadata(docs::examples = "${SLACK_WEBHOOK_URL}"))]
+    #[configurable(metadata(
+        docs::examples = "https://hooks.slack.com/services/{{ team }}/{{ channel }}/{{ token }}"
+    ))]
+    pub url: Template,
+
+    /// The title of the messages.
+    ///
+    /// Events are also batched by the title rendered for them, so that each message only holds
+    /// the events that share its title. If the title of an event can't be rendered, its message
+    /// has no title.
+    #[configurable(metadata(docs::examples = "Critical events on {{ host }}"))]
+    pub title: Option<Template>,
+
+    /// The line rendered for each event.
+    ///
+    /// The messages hold the line rendered for each of their events, in the order in which the
+    /// events were received.
+    #[serde(default = "default_message")]
+    #[configurable(metadata(docs::examples = "{{ timestamp }} {{ host }}: {{ message }}"))]
+    pub message: Template,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub channel_rate_limit: ChannelRateLimitConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch: BatchConfig<ChatWebhookDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::is_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+fn default_message() -> Template {
+    Template::try_from("{{ message }}").unwrap()
+}
+
+impl GenerateConfig for ChatWebhookSinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"platform = "slack"
+            url = "${SLACK_WEBHOOK_URL}"
+            title = "Critical events on {{ host }}""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "chat_webhook")]
+impl SinkConfig for ChatWebhookSinkConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let quota = self.channel_quota()?;
+
+        let tls_settings = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new(tls_settings, cx.proxy())?;
+
+        let service = ChannelRateLimitService {
+            inner: HttpService::new(client, ChatWebhookServiceRequestBuilder),
+            limiter: Arc::new(RateLimiter::keyed(quota)),
+        };
+        let service = ServiceBuilder::new()
+            .settings(self.request.into_settings(), http_response_retry_logic())
+            .service(service);
+
+        let sink = ChatWebhookSink {
+            batch_settings: self.batch.validate()?.into_batcher_settings()?,
+            service,
+            url: self.url.clone(),
+            title: self.title.clone(),
+            request_builder: ChatWebhookRequestBuilder {
+                encoder: ChatWebhookEncoder {
+                    platform: self.platform,
+                    message: self.message.clone(),
+                },
+            },
+        };
+
+        // Webhooks can't be checked without posting a message to their channel.
+        let healthcheck = future::ok(()).boxed();
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+impl ChatWebhookSinkConfig {
+    /// The quota of the messages sent to each webhook, which allows bursts of up to `threshold`
+    /// messages.
+    fn channel_quota(&self) -> Result<Quota, BuildError> {
+        let ChannelRateLimitConfig {
+            threshold,
+            window_secs,
+        } = self.channel_rate_limit;
+        let threshold = NonZeroU32::new(threshold).ok_or(BuildError::ZeroChannelRateLimit)?;
+        if !window_secs.is_finite() || window_secs <= 0.0 {
+            return Err(BuildError::ZeroChannelRateLimit);
+        }
+
+        Quota::with_period(Duration::from_secs_f64(
+            window_secs / f64::from(threshold.get()),
+        ))
+        .map(|quota| quota.allow_burst(threshold))
+        .ok_or(BuildError::ZeroChannelRateLimit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<ChatWebhookSinkConfig>();
+    }
+
+    #[test]
+    fn rejects_zero_channel_rate_limits() {
+        let mut config: ChatWebhookSinkConfig = toml::from_str(
+            r#"platform = "discord"
+            url = "https://discord.com/api/webhooks/1/token""#,
+        )
+        .unwrap();
+        assert!(config.channel_quota().is_ok());
+
+        for (threshold, window_secs) in [(0, 1.0), (1, 0.0)] {
+            config.channel_rate_limit = ChannelRateLimitConfig {
+                threshold,
+                window_secs,
+            };
+            assert!(config.channel_quota().is_err());
+        }
+    }
+}
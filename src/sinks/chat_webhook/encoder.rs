@@ -0,0 +1,262 @@
+//! Encoding for the `chat_webhook` sink.
+
+use std::io;
+
+use serde_json::{json, Value as JsonValue};
+
+use super::config::ChatPlatform;
+use crate::sinks::{
+    prelude::*,
+    util::encoding::{write_all, Encoder as SinkEncoder},
+};
+
+/// The maximum length of the text of Slack messages, in characters.
+const SLACK_MAX_TEXT_LENGTH: usize = 40_000;
+
+/// The maximum length of the title of Discord embeds, in characters.
+const DISCORD_MAX_TITLE_LENGTH: usize = 256;
+
+/// The maximum length of the description of Discord embeds, in characters.
+const DISCORD_MAX_DESCRIPTION_LENGTH: usize = 4_096;
+
+impl ChatPlatform {
+    /// Builds the payload of a message with the given title and lines, in the format of the
+    /// incoming webhooks of the platform.
+    pub(super) fn payload(self, title: Option<&str>, lines: &[String]) -> JsonValue {
+        match self {
+            Self::Slack => slack_payload(title, lines),
+            Self::Teams => teams_payload(title, lines),
+            Self::Discord => discord_payload(title, lines),
+        }
+    }
+}
+
+/// Builds a Slack message, whose text starts with the title in bold.
+fn slack_payload(title: Option<&str>, lines: &[String]) -> JsonValue {
+    let mut text = String::new();
+    if let Some(title) = title {
+        text.push('*');
+        text.push_str(&escape_slack(title));
+        text.push_str("*\n");
+    }
+    text.push_str(&escape_slack(&lines.join("\n")));
+    truncate(&mut text, SLACK_MAX_TEXT_LENGTH);
+
+    json!({ "text": text })
+}
+
+/// Escapes the control characters of the text of Slack messages, so that the lines of events
+/// aren't taken as links or mentions.
+fn escape_slack(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Builds an Adaptive Card, with the title and each line in their own text block.
+fn teams_payload(title: Option<&str>, lines: &[String]) -> JsonValue {
+    let title = title.map(|title| {
+        json!({
+            "type": "TextBlock",
+            "text": title,
+            "size": "Medium",
+            "weight": "Bolder",
+            "wrap": true,
+        })
+    });
+    let body = title
+        .into_iter()
+        .chain(lines.iter().map(|line| {
+            json!({
+                "type": "TextBlock",
+                "text": line,
+                "wrap": true,
+            })
+        }))
+        .collect::<Vec<_>>();
+
+    json!({
+        "type": "message",
+        "attachments": [{
+            "contentType": "application/vnd.microsoft.card.adaptive",
+            "content": {
+                "$schema": "http://adaptivecards.io/schemas/adaptive-card.json",
+                "type": "AdaptiveCard",
+                "version": "1.4",
+                "body": body,
+            },
+        }],
+    })
+}
+
+/// Builds a Discord message with an embed, whose mentions don't notify anyone.
+fn discord_payload(title: Option<&str>, lines: &[String]) -> JsonValue {
+    let mut description = lines.join("\n");
+    truncate(&mut description, DISCORD_MAX_DESCRIPTION_LENGTH);
+
+    let mut embed = json!({ "description": description });
+    if let Some(title) = title {
+        let mut title = title.to_string();
+        truncate(&mut title, DISCORD_MAX_TITLE_LENGTH);
+        embed["title"] = title.into();
+    }
+
+    json!({
+        "embeds": [embed],
+        "allowed_mentions": { "parse": [] },
+    })
+}
+
+/// Truncates a text to a number of characters, ending it with an ellipsis if it's truncated.
+fn truncate(text: &mut String, max_length: usize) {
+    if let Some((index, _)) = text.char_indices().nth(max_length - 1) {
+        if text[index..].chars().nth(1).is_some() {
+            text.truncate(index);
+            text.push('…');
+        }
+    }
+}
+
+/// Encodes batches of events into the message of the platform, with a line per event.
+pub(super) struct ChatWebhookEncoder {
+    pub(super) platform: ChatPlatform,
+    pub(super) message: Template,
+}
+
+impl SinkEncoder<(Option<String>, Vec<Event>)> for ChatWebhookEncoder {
+    fn encode_input(
+        &self,
+        (title, events): (Option<String>, Vec<Event>),
+        writer: &mut dyn io::Write,
+    ) -> io::Result<(usize, GroupedCountByteSize)> {
+        let mut byte_size = telemetry().create_request_count_byte_size();
+        let mut lines = Vec::with_capacity(events.len());
+        for event in &events {
+            byte_size.add_event(event, event.estimated_json_encoded_size_of());
+
+            match self.message.render_string(event) {
+                Ok(line) => lines.push(line),
+                Err(error) => emit!(TemplateRenderingError {
+                    error,
+                    field: Some("message"),
+                    drop_event: false,
+                }),
+            }
+        }
+
+        let body = serde_json::to_vec(&self.platform.payload(title.as_deref(), &lines))?;
+
+        write_all(writer, events.len(), &body).map(|()| (body.len(), byte_size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines() -> Vec<String> {
+        vec![
+            "db-1: disk full".to_string(),
+            "db-2: <!channel> & out of memory".to_string(),
+        ]
+    }
+
+    #[test]
+    fn builds_slack_payloads() {
+        assert_eq!(
+            ChatPlatform::Slack.payload(Some("Critical <events>"), &lines()),
+            json!({
+                "text": "*Critical &lt;events&gt;*\ndb-1: disk full\ndb-2: &lt;!channel&gt; &amp; out of memory",
+            })
+        );
+        assert_eq!(
+            ChatPlatform::Slack.payload(None, &lines()[..1]),
+            json!({ "text": "db-1: disk full" })
+        );
+    }
+
+    #[test]
+    fn builds_teams_payloads() {
+        let payload = ChatPlatform::Teams.payload(Some("Critical events"), &lines());
+
+        assert_eq!(payload["type"], "message");
+        let content = &payload["attachments"][0]["content"];
+        assert_eq!(content["type"], "AdaptiveCard");
+        assert_eq!(
+            content["body"],
+            json!([
+                {
+                    "type": "TextBlock",
+                    "text": "Critical events",
+                    "size": "Medium",
+                    "weight": "Bolder",
+                    "wrap": true,
+                },
+                { "type": "TextBlock", "text": "db-1: disk full", "wrap": true },
+                {
+                    "type": "TextBlock",
+                    "text": "db-2: <!channel> & out of memory",
+                    "wrap": true,
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn builds_discord_payloads() {
+        assert_eq!(
+            ChatPlatform::Discord.payload(Some("Critical events"), &lines()),
+            json!({
+                "embeds": [{
+                    "title": "Critical events",
+                    "description": "db-1: disk full\ndb-2: <!channel> & out of memory",
+                }],
+                "allowed_mentions": { "parse": [] },
+            })
+        );
+    }
+
+    #[test]
+    fn truncates_texts() {
+        let mut text = "abcdef".to_string();
+        truncate(&mut text, 6);
+        assert_eq!(text, "abcdef");
+
+        truncate(&mut text, 4);
+        assert_eq!(text, "abc…");
+
+        let mut text = "ééééé".to_string();
+        truncate(&mut text, 3);
+        assert_eq!(text, "éé…");
+    }
+
+    #[test]
+    fn renders_lines_per_event() {
+        let encoder = ChatWebhookEncoder {
+            platform: ChatPlatform::Slack,
+            message: Template::try_from("{{ host }}: {{ message }}").unwrap(),
+        };
+
+        let mut event = LogEvent::from("disk full");
+        event.insert("host", "db-1");
+        // The line of events it can't be rendered for is left out of the message.
+        let no_host = LogEvent::from("no host");
+
+        let mut output = Vec::new();
+        let (len, _) = encoder
+            .encode_input(
+                (
+                    Some("Alerts".to_string()),
+                    vec![event.into(), no_host.into()],
+                ),
+                &mut output,
+            )
+            .unwrap();
+
+        assert_eq!(len, output.len());
+        assert_eq!(
+            serde_json::from_slice::<JsonValue>(&output).unwrap(),
+            json!({ "text": "*Alerts*\ndb-1: disk full" })
+        );
+    }
+}
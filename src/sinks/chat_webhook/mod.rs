@@ -0,0 +1,24 @@
+//! The `chat_webhook` sink.
+//!
+//! This sink sends log events as messages to the incoming webhooks of chat platforms, such as
+//! [Slack][slack], [Microsoft Teams][teams], and [Discord][discord]. Events are batched by the
+//! webhook and the title rendered for them, and each batch is sent as a digest message that
+//! holds the line rendered for each of its events, in the payload format of the platform.
+//!
+//! Each webhook posts to a single channel, so the messages sent to each webhook are rate limited
+//! on their own, on top of the rate limit of the requests of the sink.
+//!
+//! [slack]: https://api.slack.com/messaging/webhooks
+//! [teams]: https://learn.microsoft.com/en-us/microsoftteams/platform/webhooks-and-connectors/how-to/connectors-using
+//! [discord]: https://discord.com/developers/docs/resources/webhook#execute-webhook
+
+mod config;
+mod encoder;
+mod request_builder;
+mod service;
+mod sink;
+
+#[cfg(test)]
+mod tests;
+
+pub use self::config::ChatWebhookSinkConfig;
@@ -0,0 +1,50 @@
+//! `RequestBuilder` implementation for the `chat_webhook` sink.
+
+use std::io;
+
+use bytes::Bytes;
+
+use super::{encoder::ChatWebhookEncoder, sink::ChannelKey};
+use crate::sinks::{prelude::*, util::http::HttpRequest};
+
+pub(super) struct ChatWebhookRequestBuilder {
+    pub(super) encoder: ChatWebhookEncoder,
+}
+
+impl RequestBuilder<(ChannelKey, Vec<Event>)> for ChatWebhookRequestBuilder {
+    type Metadata = (String, EventFinalizers);
+    type Events = (Option<String>, Vec<Event>);
+    type Encoder = ChatWebhookEncoder;
+    type Payload = Bytes;
+    type Request = HttpRequest<String>;
+    type Error = io::Error;
+
+    fn compression(&self) -> Compression {
+        Compression::None
+    }
+
+    fn encoder(&self) -> &Self::Encoder {
+        &self.encoder
+    }
+
+    fn split_input(
+        &self,
+        input: (ChannelKey, Vec<Event>),
+    ) -> (Self::Metadata, RequestMetadataBuilder, Self::Events) {
+        let (ChannelKey { url, title }, mut events) = input;
+
+        let finalizers = events.take_finalizers();
+        let builder = RequestMetadataBuilder::from_events(&events);
+        ((url, finalizers), builder, (title, events))
+    }
+
+    fn build_request(
+        &self,
+        metadata: Self::Metadata,
+        request_metadata: RequestMetadata,
+        payload: EncodeResult<Self::Payload>,
+    ) -> Self::Request {
+        let (url, finalizers) = metadata;
+        HttpRequest::new(payload.into_payload(), finalizers, request_metadata, url)
+    }
+}
@@ -0,0 +1,69 @@
+//! Service implementation for the `chat_webhook` sink.
+
+use std::{
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use bytes::Bytes;
+use governor::DefaultKeyedRateLimiter;
+use http::{header::CONTENT_TYPE, Request};
+use snafu::ResultExt;
+
+use crate::sinks::{
+    prelude::*,
+    util::http::{HttpRequest, HttpServiceRequestBuilder},
+    HTTPRequestBuilderSnafu,
+};
+
+/// Posts the messages to the URL of their webhook.
+#[derive(Debug, Clone)]
+pub(super) struct ChatWebhookServiceRequestBuilder;
+
+impl HttpServiceRequestBuilder<String> for ChatWebhookServiceRequestBuilder {
+    fn build(&self, mut request: HttpRequest<String>) -> Result<Request<Bytes>, crate::Error> {
+        Request::post(request.get_additional_metadata())
+            .header(CONTENT_TYPE, "application/json")
+            .body(request.take_payload())
+            .context(HTTPRequestBuilderSnafu)
+            .map_err(Into::into)
+    }
+}
+
+/// Delays the messages sent to each webhook, until they're within the rate limit of the
+/// webhook.
+///
+/// This wraps the HTTP service, so that the messages that are retried are rate limited as well.
+#[derive(Clone)]
+pub(super) struct ChannelRateLimitService<S> {
+    pub(super) inner: S,
+    pub(super) limiter: Arc<DefaultKeyedRateLimiter<String>>,
+}
+
+impl<S> Service<HttpRequest<String>> for ChannelRateLimitService<S>
+where
+    S: Service<HttpRequest<String>> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: HttpRequest<String>) -> Self::Future {
+        // Take the service that is ready, and leave a clone of it in its place.
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let limiter = Arc::clone(&self.limiter);
+
+        Box::pin(async move {
+            limiter
+                .until_key_ready(request.get_additional_metadata())
+                .await;
+            inner.call(request).await
+        })
+    }
+}
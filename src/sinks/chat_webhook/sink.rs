@@ -0,0 +1,108 @@
+//! Implementation of the `chat_webhook` sink.
+
+use super::request_builder::ChatWebhookRequestBuilder;
+use crate::sinks::{prelude::*, util::http::HttpRequest};
+
+/// The webhook and the title of the message that events are sent in.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub(super) struct ChannelKey {
+    pub(super) url: String,
+    pub(super) title: Option<String>,
+}
+
+/// Partitions events by the webhook and the title of the message they are sent in.
+struct ChannelPartitioner {
+    url: Template,
+    title: Option<Template>,
+}
+
+impl Partitioner for ChannelPartitioner {
+    type Item = Event;
+    type Key = Option<ChannelKey>;
+
+    fn partition(&self, item: &Self::Item) -> Self::Key {
+        let url = self
+            .url
+            .render_string(item)
+            .map_err(|error| {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some("url"),
+                    drop_event: true,
+                });
+            })
+            .ok()?;
+
+        let title = self.title.as_ref().and_then(|title| {
+            title
+                .render_string(item)
+                .map_err(|error| {
+                    emit!(TemplateRenderingError {
+                        error,
+                        field: Some("title"),
+                        drop_event: false,
+                    });
+                })
+                .ok()
+        });
+
+        Some(ChannelKey { url, title })
+    }
+}
+
+pub(super) struct ChatWebhookSink<S> {
+    pub(super) batch_settings: BatcherSettings,
+    pub(super) service: S,
+    pub(super) url: Template,
+    pub(super) title: Option<Template>,
+    pub(super) request_builder: ChatWebhookRequestBuilder,
+}
+
+impl<S> ChatWebhookSink<S>
+where
+    S: Service<HttpRequest<String>> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: std::fmt::Debug + Into<crate::Error> + Send,
+{
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let batch_settings = self.batch_settings;
+        let partitioner = ChannelPartitioner {
+            url: self.url,
+            title: self.title,
+        };
+
+        input
+            .batched_partitioned(partitioner, || batch_settings.as_byte_size_config())
+            .filter_map(|(key, batch)| async move { key.map(move |key| (key, batch)) })
+            .request_builder(
+                default_request_builder_concurrency_limit(),
+                self.request_builder,
+            )
+            .filter_map(|request| async {
+                match request {
+                    Err(error) => {
+                        emit!(SinkRequestBuildError { error });
+                        None
+                    }
+                    Ok(req) => Some(req),
+                }
+            })
+            .into_driver(self.service)
+            .run()
+            .await
+    }
+}
+
+#[async_trait::async_trait]
+impl<S> StreamSink<Event> for ChatWebhookSink<S>
+where
+    S: Service<HttpRequest<String>> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: std::fmt::Debug + Into<crate::Error> + Send,
+{
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
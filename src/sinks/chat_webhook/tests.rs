@@ -0,0 +1,82 @@
+use futures::StreamExt;
+use indoc::indoc;
+use serde_json::json;
+
+use super::ChatWebhookSinkConfig;
+use crate::{
+    config::{SinkConfig, SinkContext},
+    event::{Event, LogEvent},
+    sinks::util::test::build_test_server,
+    test_util::{
+        self,
+        components::{run_and_assert_sink_compliance, HTTP_SINK_TAGS},
+    },
+};
+
+fn event(channel: &str, host: &str, message: &str) -> Event {
+    let mut log = LogEvent::from(message);
+    log.insert("channel", channel);
+    log.insert("host", host);
+    log.into()
+}
+
+#[tokio::test]
+async fn sends_digests_by_channel() {
+    let addr = test_util::next_addr();
+    let (rx, trigger, server) = build_test_server(addr);
+    tokio::spawn(server);
+
+    let config: ChatWebhookSinkConfig = toml::from_str(&format!(
+        indoc! {r#"
+            platform = "slack"
+            url = "http://{}/services/{{{{ channel }}}}"
+            title = "Critical events on {{{{ host }}}}"
+            message = "{{{{ message }}}}"
+            channel_rate_limit.threshold = 10
+        "#},
+        addr
+    ))
+    .unwrap();
+
+    let (sink, _healthcheck) = config.build(SinkContext::default()).await.unwrap();
+    let events = vec![
+        event("ops", "db-1", "disk full"),
+        event("dev", "db-1", "slow query"),
+        event("ops", "db-1", "disk still full"),
+        event("ops", "db-2", "out of memory"),
+    ];
+    run_and_assert_sink_compliance(sink, futures::stream::iter(events), &HTTP_SINK_TAGS).await;
+
+    drop(trigger);
+
+    let mut messages = rx
+        .map(|(parts, body)| {
+            assert_eq!(parts.method, "POST");
+            assert_eq!(parts.headers["content-type"], "application/json");
+            (
+                parts.uri.path().to_string(),
+                serde_json::from_slice::<serde_json::Value>(&body).unwrap(),
+            )
+        })
+        .collect::<Vec<_>>()
+        .await;
+    messages.sort_by_key(|(path, body)| (path.clone(), body.to_string()));
+
+    assert_eq!(
+        messages,
+        [
+            (
+                "/services/dev".to_string(),
+                json!({ "text": "*Critical events on db-1*\nslow query" }),
+            ),
+            (
+                "/services/ops".to_string(),
+                json!({ "text": "*Critical events on db-1*\ndisk full\ndisk still full" }),
+            ),
+            (
+                "/services/ops".to_string(),
+                json!({ "text": "*Critical events on db-2*\nout of memory" }),
+            ),
+        ]
+    );
+}
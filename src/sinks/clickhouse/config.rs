@@ -1,6 +1,7 @@
 //! Configuration for the `Clickhouse` sink.
 
 use super::{
+    native,
     request_builder::ClickhouseRequestBuilder,
     service::{ClickhouseRetryLogic, ClickhouseServiceRequestBuilder},
     sink::{ClickhouseSink, PartitionKey},
@@ -49,16 +50,71 @@ impl fmt::Display for Format {
     }
 }
 
+/// The protocol used to insert data into ClickHouse.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Protocol {
+    /// The [HTTP interface][http], with rows encoded in the configured `format`.
+    ///
+    /// [http]: https://clickhouse.com/docs/en/interfaces/http
+    #[default]
+    Http,
+
+    /// The [native protocol][native] over TCP, with rows encoded into columnar blocks of the types
+    /// of the columns of the table.
+    ///
+    /// Requires ClickHouse 20.1 or later.
+    ///
+    /// [native]: https://clickhouse.com/docs/en/interfaces/tcp
+    Native,
+}
+
+/// The compression of the data sent over the native protocol.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum NativeCompression {
+    /// No compression.
+    None,
+
+    /// [LZ4][lz4] compression.
+    ///
+    /// [lz4]: https://lz4.org/
+    #[default]
+    Lz4,
+}
+
+/// Options for the native protocol.
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct NativeConfig {
+    /// The compression of the blocks of rows.
+    ///
+    /// The `compression` option only applies to the HTTP interface.
+    #[serde(default)]
+    pub compression: NativeCompression,
+}
+
 /// Configuration for the `clickhouse` sink.
 #[configurable_component(sink("clickhouse", "Deliver log data to a ClickHouse database."))]
 #[derive(Clone, Debug, Default)]
 #[serde(deny_unknown_fields)]
 pub struct ClickhouseConfig {
     /// The endpoint of the ClickHouse server.
+    ///
+    /// With the native protocol, this is the address of its TCP port, which defaults to `9000`.
+    /// The `tls` or `https` scheme connects with TLS, to the default port `9440`.
     #[serde(alias = "host")]
     #[configurable(metadata(docs::examples = "http://localhost:8123"))]
+    #[configurable(metadata(docs::examples = "tcp://localhost:9000"))]
     pub endpoint: UriSerde,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    pub protocol: Protocol,
+
     /// The table that data is inserted into.
     #[configurable(metadata(docs::examples = "mytable"))]
     pub table: Template,
@@ -83,6 +139,16 @@ pub struct ClickhouseConfig {
     #[serde(default)]
     pub insert_random_shard: bool,
 
+    /// Sets `async_insert`, allowing ClickHouse to buffer inserted rows and write them to the table in larger parts.
+    ///
+    /// Also sets `wait_for_async_insert`, so that events are only acknowledged once written to the table.
+    #[serde(default)]
+    pub async_insert: bool,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub native: NativeConfig,
+
     #[configurable(derived)]
     #[serde(default = "Compression::gzip_default")]
     pub compression: Compression,
@@ -120,6 +186,16 @@ impl_generate_config_from_default!(ClickhouseConfig);
 #[typetag::serde(name = "clickhouse")]
 impl SinkConfig for ClickhouseConfig {
     async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let database = self.database.clone().unwrap_or_else(|| {
+            "default"
+                .try_into()
+                .expect("'default' should be a valid template")
+        });
+
+        if self.protocol == Protocol::Native {
+            return native::build(self, database);
+        }
+
         let endpoint = self.endpoint.with_default_parts().uri;
 
         let auth = self.auth.choose_one(&self.endpoint.auth)?;
@@ -134,6 +210,7 @@ impl SinkConfig for ClickhouseConfig {
             skip_unknown_fields: self.skip_unknown_fields,
             date_time_best_effort: self.date_time_best_effort,
             insert_random_shard: self.insert_random_shard,
+            async_insert: self.async_insert,
             compression: self.compression,
         };
 
@@ -148,12 +225,6 @@ impl SinkConfig for ClickhouseConfig {
 
         let batch_settings = self.batch.into_batcher_settings()?;
 
-        let database = self.database.clone().unwrap_or_else(|| {
-            "default"
-                .try_into()
-                .expect("'default' should be a valid template")
-        });
-
         let request_builder = ClickhouseRequestBuilder {
             compression: self.compression,
             encoding: (
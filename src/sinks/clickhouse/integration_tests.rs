@@ -19,7 +19,7 @@ use vector_lib::event::{BatchNotifier, BatchStatus, BatchStatusReceiver, Event,
 use vector_lib::lookup::PathPrefix;
 use warp::Filter;
 
-use super::*;
+use super::{config::Protocol, *};
 use crate::{
     codecs::{TimestampFormat, Transformer},
     config::{log_schema, SinkConfig, SinkContext},
@@ -34,6 +34,10 @@ fn clickhouse_address() -> String {
     std::env::var("CLICKHOUSE_ADDRESS").unwrap_or_else(|_| "http://localhost:8123".into())
 }
 
+fn clickhouse_native_address() -> String {
+    std::env::var("CLICKHOUSE_NATIVE_ADDRESS").unwrap_or_else(|_| "tcp://localhost:9000".into())
+}
+
 #[tokio::test]
 async fn insert_events() {
     trace_init();
@@ -83,6 +87,72 @@ async fn insert_events() {
     assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
 }
 
+#[tokio::test]
+async fn insert_events_native() {
+    trace_init();
+
+    let table = gen_table();
+    let host = clickhouse_address();
+
+    let mut batch = BatchConfig::default();
+    batch.max_events = Some(1);
+
+    let config = ClickhouseConfig {
+        endpoint: clickhouse_native_address().parse().unwrap(),
+        protocol: Protocol::Native,
+        table: table.clone().try_into().unwrap(),
+        async_insert: true,
+        batch,
+        request: TowerRequestConfig {
+            retry_attempts: 1,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let client = ClickhouseClient::new(host);
+    client
+        .create_table(
+            &table,
+            "host String, timestamp DateTime64(3, 'UTC'), message LowCardinality(String), items Array(String), count Nullable(UInt32)",
+        )
+        .await;
+
+    let (sink, healthcheck) = config.build(SinkContext::default()).await.unwrap();
+    healthcheck.await.unwrap();
+
+    let (mut input_event, mut receiver) = make_event();
+    input_event
+        .as_mut_log()
+        .insert("items", vec!["item1", "item2"]);
+    input_event.as_mut_log().insert("unknown", "mysteries");
+
+    run_and_assert_sink_compliance(sink, stream::once(ready(input_event.clone())), &SINK_TAGS)
+        .await;
+
+    let output = client.select_all(&table).await;
+    assert_eq!(1, output.rows);
+
+    let timestamp = input_event
+        .as_log()
+        .get_timestamp()
+        .unwrap()
+        .as_timestamp()
+        .unwrap()
+        .format("%Y-%m-%d %H:%M:%S%.3f")
+        .to_string();
+    let expected = serde_json::json!({
+        "host": "example.com",
+        "timestamp": timestamp,
+        "message": "raw log line",
+        "items": ["item1", "item2"],
+        "count": null,
+    });
+    assert_eq!(expected, output.data[0]);
+
+    assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+}
+
 #[tokio::test]
 async fn skip_unknown_fields() {
     trace_init();
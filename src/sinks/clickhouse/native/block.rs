@@ -0,0 +1,722 @@
+//! Columnar encoding of events into the blocks of the native protocol.
+
+use std::net::{IpAddr, Ipv4Addr};
+
+use bytes::{BufMut, BytesMut};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use snafu::Snafu;
+use vector_lib::event::{LogEvent, Value};
+
+use super::protocol::{put_string, put_uvarint};
+
+#[derive(Debug, Snafu)]
+pub enum EncodeError {
+    #[snafu(display("Unsupported type `{}` of column `{}`", type_name, column))]
+    UnsupportedType { column: String, type_name: String },
+    #[snafu(display(
+        "Cannot encode {} value of field `{}` as the type `{}` of its column",
+        kind,
+        column,
+        type_name
+    ))]
+    InvalidValue {
+        column: String,
+        type_name: String,
+        kind: String,
+    },
+}
+
+/// The type of a column, as named by ClickHouse.
+#[derive(Clone, Debug, PartialEq)]
+pub(super) enum ColumnType {
+    Bool,
+    UInt8,
+    UInt16,
+    UInt32,
+    UInt64,
+    Int8,
+    Int16,
+    Int32,
+    Int64,
+    Float32,
+    Float64,
+    String,
+    FixedString(usize),
+    Uuid,
+    Date,
+    Date32,
+    DateTime,
+    DateTime64(u32),
+    Ipv4,
+    Ipv6,
+    Enum8(Vec<(String, i8)>),
+    Enum16(Vec<(String, i16)>),
+    Nullable(Box<ColumnType>),
+    Array(Box<ColumnType>),
+    Map(Box<ColumnType>, Box<ColumnType>),
+}
+
+impl ColumnType {
+    /// Parses the name of a type, returning `None` if the type is not supported.
+    pub(super) fn parse(name: &str) -> Option<Self> {
+        let name = name.trim();
+        Some(match name {
+            "Bool" | "Boolean" => Self::Bool,
+            "UInt8" => Self::UInt8,
+            "UInt16" => Self::UInt16,
+            "UInt32" => Self::UInt32,
+            "UInt64" => Self::UInt64,
+            "Int8" => Self::Int8,
+            "Int16" => Self::Int16,
+            "Int32" => Self::Int32,
+            "Int64" => Self::Int64,
+            "Float32" => Self::Float32,
+            "Float64" => Self::Float64,
+            "String" => Self::String,
+            "UUID" => Self::Uuid,
+            "Date" => Self::Date,
+            "Date32" => Self::Date32,
+            "DateTime" => Self::DateTime,
+            "IPv4" => Self::Ipv4,
+            "IPv6" => Self::Ipv6,
+            _ => {
+                let (wrapper, arguments) = name.strip_suffix(')')?.split_once('(')?;
+                let arguments = split_arguments(arguments);
+                match (wrapper, arguments.as_slice()) {
+                    ("Nullable", [inner]) => Self::Nullable(Box::new(Self::parse(inner)?)),
+                    // The server is asked to convert these columns to their inner type.
+                    ("LowCardinality", [inner]) => Self::parse(inner)?,
+                    ("Array", [inner]) => Self::Array(Box::new(Self::parse(inner)?)),
+                    ("Map", [key, value]) => {
+                        Self::Map(Box::new(Self::parse(key)?), Box::new(Self::parse(value)?))
+                    }
+                    ("FixedString", [size]) => Self::FixedString(size.trim().parse().ok()?),
+                    // The time zone only matters to the representation of the values.
+                    ("DateTime", [_time_zone]) => Self::DateTime,
+                    ("DateTime64", [precision, ..]) => {
+                        let precision = precision.trim().parse().ok()?;
+                        (precision <= 9).then_some(Self::DateTime64(precision))?
+                    }
+                    ("Enum8", values) => Self::Enum8(parse_enum_values(values)?),
+                    ("Enum16", values) => Self::Enum16(parse_enum_values(values)?),
+                    _ => return None,
+                }
+            }
+        })
+    }
+}
+
+/// Splits the arguments of a type on the commas that are not nested in another type or quoted.
+fn split_arguments(arguments: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let (mut depth, mut quoted, mut escaped, mut start) = (0, false, false, 0);
+    for (index, c) in arguments.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if quoted => escaped = true,
+            '\'' => quoted = !quoted,
+            '(' if !quoted => depth += 1,
+            ')' if !quoted => depth -= 1,
+            ',' if !quoted && depth == 0 => {
+                parts.push(&arguments[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&arguments[start..]);
+    parts
+}
+
+/// Parses the `'name' = value` pairs of an enum type.
+fn parse_enum_values<T: std::str::FromStr>(values: &[&str]) -> Option<Vec<(String, T)>> {
+    values
+        .iter()
+        .map(|pair| {
+            let (name, value) = pair.rsplit_once('=')?;
+            let name = name.trim().strip_prefix('\'')?.strip_suffix('\'')?;
+            let name = name.replace("\\'", "'").replace("\\\\", "\\");
+            Some((name, value.trim().parse().ok()?))
+        })
+        .collect()
+}
+
+/// The values of a column, serialized into the streams of its type.
+#[derive(Debug)]
+enum ColumnData {
+    Scalar {
+        column_type: ColumnType,
+        values: BytesMut,
+    },
+    Nullable {
+        nulls: BytesMut,
+        values: Box<ColumnData>,
+    },
+    Array {
+        offsets: BytesMut,
+        len: u64,
+        values: Box<ColumnData>,
+    },
+    Map {
+        offsets: BytesMut,
+        len: u64,
+        keys: Box<ColumnData>,
+        values: Box<ColumnData>,
+    },
+}
+
+impl ColumnData {
+    fn new(column_type: &ColumnType) -> Self {
+        match column_type {
+            ColumnType::Nullable(inner) => Self::Nullable {
+                nulls: BytesMut::new(),
+                values: Box::new(Self::new(inner)),
+            },
+            ColumnType::Array(inner) => Self::Array {
+                offsets: BytesMut::new(),
+                len: 0,
+                values: Box::new(Self::new(inner)),
+            },
+            ColumnType::Map(key, value) => Self::Map {
+                offsets: BytesMut::new(),
+                len: 0,
+                keys: Box::new(Self::new(key)),
+                values: Box::new(Self::new(value)),
+            },
+            column_type => Self::Scalar {
+                column_type: column_type.clone(),
+                values: BytesMut::new(),
+            },
+        }
+    }
+
+    /// Appends a value, or the default value of the type if it is missing or null.
+    ///
+    /// Returns the kind of the value if it cannot be represented by the type.
+    fn push(&mut self, value: Option<&Value>) -> Result<(), String> {
+        let value = value.filter(|value| !value.is_null());
+        match self {
+            Self::Scalar {
+                column_type,
+                values,
+            } => match value {
+                Some(value) => encode_scalar(column_type, value, values)
+                    .ok_or_else(|| value.kind_str().to_string()),
+                None => {
+                    encode_default(column_type, values);
+                    Ok(())
+                }
+            },
+            Self::Nullable { nulls, values } => {
+                nulls.put_u8(value.is_none().into());
+                values.push(value)
+            }
+            Self::Array {
+                offsets,
+                len,
+                values,
+            } => {
+                match value {
+                    Some(Value::Array(items)) => {
+                        for item in items {
+                            values.push(Some(item))?;
+                        }
+                        *len += items.len() as u64;
+                    }
+                    Some(value) => return Err(value.kind_str().to_string()),
+                    None => {}
+                }
+                offsets.put_u64_le(*len);
+                Ok(())
+            }
+            Self::Map {
+                offsets,
+                len,
+                keys,
+                values,
+            } => {
+                match value {
+                    Some(Value::Object(map)) => {
+                        for (key, value) in map {
+                            keys.push(Some(&Value::from(key.as_str())))?;
+                            values.push(Some(value))?;
+                        }
+                        *len += map.len() as u64;
+                    }
+                    Some(value) => return Err(value.kind_str().to_string()),
+                    None => {}
+                }
+                offsets.put_u64_le(*len);
+                Ok(())
+            }
+        }
+    }
+
+    fn write(&self, out: &mut BytesMut) {
+        match self {
+            Self::Scalar { values, .. } => out.put_slice(values),
+            Self::Nullable { nulls, values } => {
+                out.put_slice(nulls);
+                values.write(out);
+            }
+            Self::Array {
+                offsets, values, ..
+            } => {
+                out.put_slice(offsets);
+                values.write(out);
+            }
+            Self::Map {
+                offsets,
+                keys,
+                values,
+                ..
+            } => {
+                out.put_slice(offsets);
+                keys.write(out);
+                values.write(out);
+            }
+        }
+    }
+}
+
+/// A column of a block.
+#[derive(Debug)]
+struct Column {
+    name: String,
+    type_name: String,
+    data: ColumnData,
+}
+
+/// A block of rows, encoded column by column.
+#[derive(Debug)]
+pub(super) struct Block {
+    columns: Vec<Column>,
+    rows: usize,
+}
+
+impl Block {
+    /// Creates an empty block with the given columns, as named and typed in the sample block
+    /// sent by the server for an insert.
+    pub(super) fn new(columns: &[(String, String)]) -> Result<Self, EncodeError> {
+        let columns = columns
+            .iter()
+            .map(|(name, type_name)| {
+                let column_type =
+                    ColumnType::parse(type_name).ok_or_else(|| EncodeError::UnsupportedType {
+                        column: name.clone(),
+                        type_name: type_name.clone(),
+                    })?;
+                Ok(Column {
+                    name: name.clone(),
+                    type_name: type_name.clone(),
+                    data: ColumnData::new(&column_type),
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { columns, rows: 0 })
+    }
+
+    pub(super) const fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Appends a row with the fields of a log event named after the columns.
+    ///
+    /// Columns are looked up as top-level fields first, and as paths when they are named like
+    /// the nested fields of the event, such as `kubernetes.pod_name`. Missing fields are set to
+    /// the default value of their column.
+    pub(super) fn push(&mut self, log: &LogEvent) -> Result<(), EncodeError> {
+        for column in &mut self.columns {
+            let value = log
+                .as_map()
+                .and_then(|map| map.get(column.name.as_str()))
+                .or_else(|| log.parse_path_and_get_value(&column.name).ok().flatten());
+            column
+                .data
+                .push(value)
+                .map_err(|kind| EncodeError::InvalidValue {
+                    column: column.name.clone(),
+                    type_name: column.type_name.clone(),
+                    kind,
+                })?;
+        }
+        self.rows += 1;
+        Ok(())
+    }
+
+    /// Serializes the block, as sent in a `Data` packet.
+    pub(super) fn write(&self, out: &mut BytesMut) {
+        write_header(out, self.columns.len(), self.rows);
+        for column in &self.columns {
+            put_string(out, column.name.as_bytes());
+            put_string(out, column.type_name.as_bytes());
+            if self.rows > 0 {
+                column.data.write(out);
+            }
+        }
+    }
+
+    /// Serializes an empty block, which marks the end of the data sent by the client.
+    pub(super) fn write_empty(out: &mut BytesMut) {
+        write_header(out, 0, 0);
+    }
+}
+
+fn write_header(out: &mut BytesMut, columns: usize, rows: usize) {
+    // The block info: field 1 is `is_overflows`, field 2 is `bucket_num`, and field 0 ends it.
+    put_uvarint(out, 1);
+    out.put_u8(0);
+    put_uvarint(out, 2);
+    out.put_i32_le(-1);
+    put_uvarint(out, 0);
+
+    put_uvarint(out, columns as u64);
+    put_uvarint(out, rows as u64);
+}
+
+fn encode_default(column_type: &ColumnType, out: &mut BytesMut) {
+    let size = match column_type {
+        ColumnType::String => 1,
+        ColumnType::FixedString(size) => *size,
+        ColumnType::Bool | ColumnType::UInt8 | ColumnType::Int8 | ColumnType::Enum8(_) => 1,
+        ColumnType::UInt16 | ColumnType::Int16 | ColumnType::Date | ColumnType::Enum16(_) => 2,
+        ColumnType::UInt32
+        | ColumnType::Int32
+        | ColumnType::Float32
+        | ColumnType::Date32
+        | ColumnType::DateTime
+        | ColumnType::Ipv4 => 4,
+        ColumnType::UInt64
+        | ColumnType::Int64
+        | ColumnType::Float64
+        | ColumnType::DateTime64(_) => 8,
+        ColumnType::Uuid | ColumnType::Ipv6 => 16,
+        ColumnType::Nullable(_) | ColumnType::Array(_) | ColumnType::Map(..) => {
+            unreachable!("composite types are not scalar")
+        }
+    };
+    // Zero is the default value of every type, and the length of an empty string.
+    out.put_bytes(0, size);
+}
+
+/// Encodes a value, returning `None` if it cannot be represented by the type.
+fn encode_scalar(column_type: &ColumnType, value: &Value, out: &mut BytesMut) -> Option<()> {
+    match column_type {
+        ColumnType::Bool => out.put_u8(to_bool(value)?.into()),
+        ColumnType::UInt8 => out.put_u8(to_integer(value)?),
+        ColumnType::UInt16 => out.put_u16_le(to_integer(value)?),
+        ColumnType::UInt32 => out.put_u32_le(to_integer(value)?),
+        ColumnType::UInt64 => out.put_u64_le(to_integer(value)?),
+        ColumnType::Int8 => out.put_i8(to_integer(value)?),
+        ColumnType::Int16 => out.put_i16_le(to_integer(value)?),
+        ColumnType::Int32 => out.put_i32_le(to_integer(value)?),
+        ColumnType::Int64 => out.put_i64_le(to_integer(value)?),
+        ColumnType::Float32 => out.put_f32_le(to_float(value)? as f32),
+        ColumnType::Float64 => out.put_f64_le(to_float(value)?),
+        ColumnType::String => put_string(out, &to_bytes(value)),
+        ColumnType::FixedString(size) => {
+            let bytes = to_bytes(value);
+            if bytes.len() > *size {
+                return None;
+            }
+            out.put_slice(&bytes);
+            out.put_bytes(0, size - bytes.len());
+        }
+        ColumnType::Uuid => {
+            let uuid = uuid::Uuid::parse_str(value.as_str()?.trim())
+                .ok()?
+                .as_u128();
+            // The two halves of the UUID are stored as little endian integers.
+            out.put_u64_le((uuid >> 64) as u64);
+            out.put_u64_le(uuid as u64);
+        }
+        ColumnType::Date => out.put_u16_le(to_days(value)?.try_into().ok()?),
+        ColumnType::Date32 => out.put_i32_le(to_days(value)?.try_into().ok()?),
+        ColumnType::DateTime => out.put_u32_le(to_timestamp(value)?.timestamp().try_into().ok()?),
+        ColumnType::DateTime64(precision) => {
+            let timestamp = to_timestamp(value)?;
+            let ticks = timestamp
+                .timestamp()
+                .checked_mul(10_i64.pow(*precision))?
+                .checked_add(
+                    i64::from(timestamp.timestamp_subsec_nanos()) / 10_i64.pow(9 - precision),
+                )?;
+            out.put_i64_le(ticks);
+        }
+        ColumnType::Ipv4 => {
+            let address: Ipv4Addr = value.as_str()?.trim().parse().ok()?;
+            out.put_u32_le(address.into());
+        }
+        ColumnType::Ipv6 => {
+            let address = match value.as_str()?.trim().parse().ok()? {
+                IpAddr::V4(address) => address.to_ipv6_mapped(),
+                IpAddr::V6(address) => address,
+            };
+            out.put_slice(&address.octets());
+        }
+        ColumnType::Enum8(values) => out.put_i8(to_enum(values, value)?),
+        ColumnType::Enum16(values) => out.put_i16_le(to_enum(values, value)?),
+        ColumnType::Nullable(_) | ColumnType::Array(_) | ColumnType::Map(..) => {
+            unreachable!("composite types are not scalar")
+        }
+    }
+    Some(())
+}
+
+fn to_bool(value: &Value) -> Option<bool> {
+    match value {
+        Value::Boolean(boolean) => Some(*boolean),
+        Value::Integer(0) => Some(false),
+        Value::Integer(1) => Some(true),
+        Value::Bytes(_) => match value.as_str()?.trim() {
+            "true" => Some(true),
+            "false" => Some(false),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn to_integer<T: TryFrom<i64> + std::str::FromStr>(value: &Value) -> Option<T> {
+    match value {
+        Value::Integer(integer) => (*integer).try_into().ok(),
+        Value::Float(float) if float.fract() == 0.0 => (float.into_inner() as i64).try_into().ok(),
+        Value::Boolean(boolean) => i64::from(*boolean).try_into().ok(),
+        Value::Bytes(_) => value.as_str()?.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+fn to_float(value: &Value) -> Option<f64> {
+    match value {
+        Value::Float(float) => Some(float.into_inner()),
+        Value::Integer(integer) => Some(*integer as f64),
+        Value::Bytes(_) => value.as_str()?.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+fn to_bytes(value: &Value) -> Vec<u8> {
+    match value {
+        Value::Bytes(bytes) => bytes.to_vec(),
+        Value::Object(_) | Value::Array(_) => {
+            serde_json::to_vec(value).expect("values are serializable")
+        }
+        value => value.to_string_lossy().into_owned().into_bytes(),
+    }
+}
+
+/// Converts timestamps, Unix timestamps in seconds, and RFC 3339 or ClickHouse formatted strings.
+fn to_timestamp(value: &Value) -> Option<DateTime<Utc>> {
+    match value {
+        Value::Timestamp(timestamp) => Some(*timestamp),
+        Value::Integer(seconds) => Utc.timestamp_opt(*seconds, 0).single(),
+        Value::Float(seconds) => {
+            let nanoseconds = (seconds.fract() * 1e9).round() as u32;
+            Utc.timestamp_opt(seconds.trunc() as i64, nanoseconds)
+                .single()
+        }
+        Value::Bytes(_) => {
+            let string = value.as_str()?;
+            let string = string.trim();
+            DateTime::parse_from_rfc3339(string)
+                .map(|timestamp| timestamp.with_timezone(&Utc))
+                .or_else(|_| {
+                    NaiveDateTime::parse_from_str(string, "%Y-%m-%d %H:%M:%S%.f")
+                        .map(|timestamp| Utc.from_utc_datetime(&timestamp))
+                })
+                .ok()
+        }
+        _ => None,
+    }
+}
+
+/// Converts dates to the number of days since the Unix epoch, which is how integers are read.
+fn to_days(value: &Value) -> Option<i64> {
+    let date = match value {
+        Value::Integer(days) => return Some(*days),
+        Value::Bytes(_) => match NaiveDate::parse_from_str(value.as_str()?.trim(), "%Y-%m-%d") {
+            Ok(date) => date,
+            Err(_) => to_timestamp(value)?.date_naive(),
+        },
+        value => to_timestamp(value)?.date_naive(),
+    };
+    let epoch = NaiveDate::from_ymd_opt(1970, 1, 1).expect("the epoch is a valid date");
+    Some((date - epoch).num_days())
+}
+
+fn to_enum<T: Copy + TryFrom<i64> + PartialEq>(values: &[(String, T)], value: &Value) -> Option<T> {
+    match value {
+        Value::Bytes(_) => {
+            let name = value.as_str()?;
+            values
+                .iter()
+                .find_map(|(candidate, value)| (*candidate == *name).then_some(*value))
+        }
+        Value::Integer(integer) => {
+            let integer = T::try_from(*integer).ok()?;
+            values
+                .iter()
+                .any(|(_, value)| *value == integer)
+                .then_some(integer)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Timelike;
+    use vector_lib::lookup::event_path;
+
+    use super::*;
+
+    fn encode(columns: &[(&str, &str)], events: &[LogEvent]) -> BytesMut {
+        let columns = columns
+            .iter()
+            .map(|(name, type_name)| (name.to_string(), type_name.to_string()))
+            .collect::<Vec<_>>();
+        let mut block = Block::new(&columns).unwrap();
+        for event in events {
+            block.push(event).unwrap();
+        }
+        let mut out = BytesMut::new();
+        block.write(&mut out);
+        out
+    }
+
+    /// The serialized block header, and the name and type of the single column.
+    fn header(rows: u8, name: &str, type_name: &str) -> Vec<u8> {
+        let mut header = vec![1, 0, 2, 0xff, 0xff, 0xff, 0xff, 0, 1, rows];
+        header.push(name.len() as u8);
+        header.extend(name.as_bytes());
+        header.push(type_name.len() as u8);
+        header.extend(type_name.as_bytes());
+        header
+    }
+
+    #[test]
+    fn parses_types() {
+        assert_eq!(
+            ColumnType::parse("Nullable(DateTime64(3, 'UTC'))"),
+            Some(ColumnType::Nullable(Box::new(ColumnType::DateTime64(3))))
+        );
+        assert_eq!(
+            ColumnType::parse("Map(LowCardinality(String), Array(UInt16))"),
+            Some(ColumnType::Map(
+                Box::new(ColumnType::String),
+                Box::new(ColumnType::Array(Box::new(ColumnType::UInt16)))
+            ))
+        );
+        assert_eq!(
+            ColumnType::parse("Enum8('info' = 1, 'it\\'s, bad' = -2)"),
+            Some(ColumnType::Enum8(vec![
+                ("info".to_string(), 1),
+                ("it's, bad".to_string(), -2)
+            ]))
+        );
+        assert_eq!(
+            ColumnType::parse("DateTime('Europe/Paris')"),
+            Some(ColumnType::DateTime)
+        );
+        assert_eq!(ColumnType::parse("Decimal(9, 2)"), None);
+        assert_eq!(ColumnType::parse("Tuple(String, UInt8)"), None);
+    }
+
+    #[test]
+    fn encodes_scalar_columns() {
+        let events = [
+            LogEvent::from_iter([("count", Value::from(1))]),
+            LogEvent::from_iter([("count", Value::from("2"))]),
+            LogEvent::default(),
+        ];
+        let mut expected = header(3, "count", "UInt16");
+        expected.extend([1, 0, 2, 0, 0, 0]);
+        assert_eq!(encode(&[("count", "UInt16")], &events)[..], expected[..]);
+
+        let events = [LogEvent::from_iter([("message", Value::from("hello"))])];
+        let mut expected = header(1, "message", "String");
+        expected.extend(b"\x05hello");
+        assert_eq!(encode(&[("message", "String")], &events)[..], expected[..]);
+    }
+
+    #[test]
+    fn encodes_timestamps() {
+        let timestamp = Utc
+            .with_ymd_and_hms(2024, 1, 2, 3, 4, 5)
+            .unwrap()
+            .with_nanosecond(678_000_000)
+            .unwrap();
+        let events = [
+            LogEvent::from_iter([("timestamp", Value::from(timestamp))]),
+            LogEvent::from_iter([("timestamp", Value::from("2024-01-02T03:04:05.678Z"))]),
+        ];
+
+        let ticks = 1_704_164_645_678_i64.to_le_bytes();
+        let mut expected = header(2, "timestamp", "DateTime64(3)");
+        expected.extend(ticks);
+        expected.extend(ticks);
+        assert_eq!(
+            encode(&[("timestamp", "DateTime64(3)")], &events)[..],
+            expected[..]
+        );
+
+        let seconds = 1_704_164_645_u32.to_le_bytes();
+        let mut expected = header(2, "timestamp", "DateTime");
+        expected.extend(seconds);
+        expected.extend(seconds);
+        assert_eq!(
+            encode(&[("timestamp", "DateTime")], &events)[..],
+            expected[..]
+        );
+    }
+
+    #[test]
+    fn encodes_composite_columns() {
+        let events = [
+            LogEvent::from_iter([("tags", Value::from(vec![Value::from("a"), Value::Null]))]),
+            LogEvent::default(),
+            LogEvent::from_iter([("tags", Value::from(vec![Value::from("b")]))]),
+        ];
+        let mut expected = header(3, "tags", "Array(Nullable(String))");
+        // The offsets of the end of each row in the nested column.
+        for offset in [2_u64, 2, 3] {
+            expected.extend(offset.to_le_bytes());
+        }
+        // The null map of the nested column, then its values.
+        expected.extend([0, 1, 0]);
+        expected.extend(b"\x01a\x00\x01b");
+        assert_eq!(
+            encode(&[("tags", "Array(Nullable(String))")], &events)[..],
+            expected[..]
+        );
+    }
+
+    #[test]
+    fn looks_up_nested_fields() {
+        let mut event = LogEvent::default();
+        event.insert(event_path!("kubernetes", "pod_name"), "vector-0");
+        let mut expected = header(1, "kubernetes.pod_name", "String");
+        expected.extend(b"\x08vector-0");
+        assert_eq!(
+            encode(&[("kubernetes.pod_name", "String")], &[event])[..],
+            expected[..]
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_values() {
+        let columns = [("count".to_string(), "UInt8".to_string())];
+        let mut block = Block::new(&columns).unwrap();
+        let error = block
+            .push(&LogEvent::from_iter([("count", Value::from(256))]))
+            .unwrap_err();
+        assert!(matches!(error, EncodeError::InvalidValue { .. }));
+
+        let columns = [("amount".to_string(), "Decimal(9, 2)".to_string())];
+        assert!(matches!(
+            Block::new(&columns),
+            Err(EncodeError::UnsupportedType { .. })
+        ));
+    }
+}
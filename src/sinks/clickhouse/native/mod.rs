@@ -0,0 +1,162 @@
+//! The native protocol of the `clickhouse` sink.
+//!
+//! Events are inserted over the [native protocol][native] of ClickHouse with a query of the
+//! following structure: `INSERT INTO "my_db"."my_table" VALUES`. The server responds with the
+//! names and types of the columns of the table, and the events of a batch are encoded column by
+//! column into a block of these types, compressed with LZ4 unless disabled.
+//!
+//! [native]: https://clickhouse.com/docs/en/interfaces/tcp
+
+mod block;
+mod protocol;
+mod service;
+mod sink;
+
+use self::{
+    protocol::{Connection, ConnectionOptions},
+    service::{NativeRetryLogic, NativeService},
+    sink::NativeSink,
+};
+use super::config::{ClickhouseConfig, NativeCompression};
+use crate::{
+    http::{Auth, MaybeAuth},
+    sinks::prelude::*,
+};
+use vector_lib::tls::MaybeTlsSettings;
+
+/// The default port of the native protocol.
+const DEFAULT_PORT: u16 = 9000;
+
+/// The default port of the native protocol over TLS.
+const DEFAULT_TLS_PORT: u16 = 9440;
+
+pub(super) fn build(
+    config: &ClickhouseConfig,
+    database: Template,
+) -> crate::Result<(VectorSink, Healthcheck)> {
+    let options = connection_options(config)?;
+
+    let mut settings = vec![
+        // Columns of the `LowCardinality` types are sent as columns of their inner type.
+        ("low_cardinality_allow_in_native_format", "0"),
+    ];
+    if config.async_insert {
+        settings.push(("async_insert", "1"));
+        settings.push(("wait_for_async_insert", "1"));
+    }
+    if config.insert_random_shard {
+        settings.push(("insert_distributed_one_random_shard", "1"));
+    }
+
+    let service = ServiceBuilder::new()
+        .settings(config.request.into_settings(), NativeRetryLogic)
+        .service(NativeService::new(options.clone(), settings));
+
+    let sink = NativeSink {
+        batch_settings: config.batch.into_batcher_settings()?,
+        service,
+        database,
+        table: config.table.clone(),
+        format: config.format,
+        transformer: config.encoding.clone(),
+    };
+
+    let healthcheck = Box::pin(healthcheck(options));
+
+    Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+}
+
+fn connection_options(config: &ClickhouseConfig) -> crate::Result<ConnectionOptions> {
+    let endpoint = config.endpoint.with_default_parts().uri;
+    let host = endpoint
+        .host()
+        .ok_or("The endpoint of the native protocol must have a host.")?
+        .to_string();
+
+    let tls = match endpoint.scheme_str() {
+        Some("https" | "tls") => MaybeTlsSettings::tls_client(&config.tls)?,
+        _ => MaybeTlsSettings::Raw(()),
+    };
+    let port = endpoint.port_u16().unwrap_or(if tls.is_tls() {
+        DEFAULT_TLS_PORT
+    } else {
+        DEFAULT_PORT
+    });
+
+    let (user, password) = match config.auth.choose_one(&config.endpoint.auth)? {
+        Some(Auth::Basic { user, password }) => (user, password.inner().to_string()),
+        Some(Auth::Bearer { .. }) => {
+            return Err("Bearer authentication is not supported by the native protocol.".into())
+        }
+        None => ("default".to_string(), String::new()),
+    };
+
+    Ok(ConnectionOptions {
+        host,
+        port,
+        tls,
+        user,
+        password,
+        compression: config.native.compression == NativeCompression::Lz4,
+    })
+}
+
+async fn healthcheck(options: ConnectionOptions) -> crate::Result<()> {
+    let mut connection = Connection::connect(&options).await?;
+    connection.ping().await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(config: &str) -> ConnectionOptions {
+        let config: ClickhouseConfig = toml::from_str(config).unwrap();
+        connection_options(&config).unwrap()
+    }
+
+    #[test]
+    fn parses_endpoint() {
+        let plain = options(
+            r#"
+            endpoint = "tcp://clickhouse"
+            table = "logs"
+            protocol = "native"
+            "#,
+        );
+        assert_eq!(plain.host, "clickhouse");
+        assert_eq!(plain.port, DEFAULT_PORT);
+        assert!(plain.tls.is_raw());
+        assert_eq!(plain.user, "default");
+        assert!(plain.compression);
+
+        let tls = options(
+            r#"
+            endpoint = "tls://clickhouse"
+            table = "logs"
+            protocol = "native"
+            native.compression = "none"
+            auth.strategy = "basic"
+            auth.user = "vector"
+            auth.password = "secret"
+            "#,
+        );
+        assert_eq!(tls.port, DEFAULT_TLS_PORT);
+        assert!(tls.tls.is_tls());
+        assert_eq!(tls.user, "vector");
+        assert_eq!(tls.password, "secret");
+        assert!(!tls.compression);
+
+        assert_eq!(
+            options(
+                r#"
+                endpoint = "tcp://clickhouse:9001"
+                table = "logs"
+                "#
+            )
+            .port,
+            9001
+        );
+    }
+}
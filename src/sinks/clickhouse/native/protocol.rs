@@ -0,0 +1,563 @@
+//! The client side of the ClickHouse native protocol.
+//!
+//! Reference: <https://clickhouse.com/docs/en/native-protocol/basics>
+
+use std::{fmt, io, net::SocketAddr};
+
+use bytes::{BufMut, BytesMut};
+use snafu::{OptionExt, ResultExt, Snafu};
+use tokio::{
+    io::{AsyncRead, AsyncReadExt, AsyncWriteExt, BufStream},
+    net::TcpStream,
+};
+use vector_lib::tls::{MaybeTlsSettings, MaybeTlsStream, TlsError};
+
+use super::block::{Block, EncodeError};
+use crate::dns::{DnsError, Resolver};
+
+/// The revision of the protocol implemented by the client, the first one that sends settings as
+/// strings.
+///
+/// The server speaks the lower of its revision and the revision of the client, so the fields
+/// added to the packets by later revisions are neither sent nor received.
+const REVISION: u64 = 54429;
+
+const CLIENT_NAME: &str = "Vector";
+
+/// The maximum size of the data compressed in a frame, as used by ClickHouse.
+const MAX_FRAME_DATA_SIZE: usize = 1 << 20;
+
+/// The maximum size of a compressed frame accepted by ClickHouse.
+const MAX_FRAME_SIZE: usize = 1 << 30;
+
+/// The size of the header of a compressed frame that follows its checksum: the compression
+/// method, and the compressed and decompressed sizes.
+const FRAME_HEADER_SIZE: usize = 9;
+
+const LZ4_METHOD: u8 = 0x82;
+const NONE_METHOD: u8 = 0x02;
+
+/// A setting is important when the server fails the query if it does not know the setting.
+const SETTING_IMPORTANT: u64 = 0x01;
+
+/// The processing stage of a query where it is complete.
+const STAGE_COMPLETE: u64 = 2;
+
+mod client_packet {
+    pub(super) const HELLO: u64 = 0;
+    pub(super) const QUERY: u64 = 1;
+    pub(super) const DATA: u64 = 2;
+    pub(super) const PING: u64 = 4;
+}
+
+mod server_packet {
+    pub(super) const HELLO: u64 = 0;
+    pub(super) const DATA: u64 = 1;
+    pub(super) const EXCEPTION: u64 = 2;
+    pub(super) const PROGRESS: u64 = 3;
+    pub(super) const PONG: u64 = 4;
+    pub(super) const END_OF_STREAM: u64 = 5;
+    pub(super) const PROFILE_INFO: u64 = 6;
+    pub(super) const TABLE_COLUMNS: u64 = 11;
+}
+
+/// An exception raised by the server.
+#[derive(Debug)]
+pub struct Exception {
+    pub code: i32,
+    pub message: String,
+}
+
+impl fmt::Display for Exception {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Code: {}. {}", self.code, self.message)
+    }
+}
+
+#[derive(Debug, Snafu)]
+pub enum NativeError {
+    #[snafu(display("Failed to resolve {}: {}", host, source))]
+    Resolve { host: String, source: DnsError },
+    #[snafu(display("No addresses found for {}", host))]
+    NoAddresses { host: String },
+    #[snafu(display("Failed to connect to {}: {}", address, source))]
+    Connect {
+        address: SocketAddr,
+        source: TlsError,
+    },
+    #[snafu(display("Connection error: {}", source))]
+    Io { source: io::Error },
+    #[snafu(display("Server error: {}", exception))]
+    Server { exception: Exception },
+    #[snafu(display(
+        "The server revision {} is older than the oldest supported revision {}",
+        revision,
+        REVISION
+    ))]
+    UnsupportedRevision { revision: u64 },
+    #[snafu(display("Unexpected packet {} from the server", packet))]
+    UnexpectedPacket { packet: u64 },
+    #[snafu(display("Invalid data from the server: {}", reason))]
+    InvalidData { reason: &'static str },
+    #[snafu(display("{}", source))]
+    Encode { source: EncodeError },
+}
+
+impl From<io::Error> for NativeError {
+    fn from(source: io::Error) -> Self {
+        Self::Io { source }
+    }
+}
+
+/// The options of the connections to a server.
+#[derive(Clone, Debug)]
+pub(super) struct ConnectionOptions {
+    pub(super) host: String,
+    pub(super) port: u16,
+    pub(super) tls: MaybeTlsSettings,
+    pub(super) user: String,
+    pub(super) password: String,
+    /// Whether the data sent and received is compressed with LZ4.
+    pub(super) compression: bool,
+}
+
+/// A connection to a server, after the handshake.
+pub(super) struct Connection {
+    stream: BufStream<MaybeTlsStream<TcpStream>>,
+    compression: bool,
+    hostname: String,
+}
+
+impl Connection {
+    pub(super) async fn connect(options: &ConnectionOptions) -> Result<Self, NativeError> {
+        let host = &options.host;
+        let ip = Resolver
+            .lookup_ip(host.clone())
+            .await
+            .context(ResolveSnafu { host })?
+            .next()
+            .context(NoAddressesSnafu { host })?;
+        let address = SocketAddr::new(ip, options.port);
+        let stream = options
+            .tls
+            .connect(host, &address)
+            .await
+            .context(ConnectSnafu { address })?;
+
+        let mut connection = Self {
+            stream: BufStream::new(stream),
+            compression: options.compression,
+            hostname: crate::get_hostname().unwrap_or_default(),
+        };
+        connection.hello(&options.user, &options.password).await?;
+        Ok(connection)
+    }
+
+    async fn hello(&mut self, user: &str, password: &str) -> Result<(), NativeError> {
+        let mut out = BytesMut::new();
+        put_uvarint(&mut out, client_packet::HELLO);
+        put_string(&mut out, CLIENT_NAME.as_bytes());
+        put_client_version(&mut out);
+        // The default database of the user, as inserts name the database.
+        put_string(&mut out, b"");
+        put_string(&mut out, user.as_bytes());
+        put_string(&mut out, password.as_bytes());
+        self.send(&out).await?;
+
+        match read_uvarint(&mut self.stream).await? {
+            server_packet::HELLO => {
+                let _name = read_string(&mut self.stream).await?;
+                let _major_version = read_uvarint(&mut self.stream).await?;
+                let _minor_version = read_uvarint(&mut self.stream).await?;
+                let revision = read_uvarint(&mut self.stream).await?;
+                if revision < REVISION {
+                    return UnsupportedRevisionSnafu { revision }.fail();
+                }
+                let _time_zone = read_string(&mut self.stream).await?;
+                let _display_name = read_string(&mut self.stream).await?;
+                let _patch_version = read_uvarint(&mut self.stream).await?;
+                Ok(())
+            }
+            packet => Err(self.unexpected(packet).await),
+        }
+    }
+
+    /// Checks that the server is responsive.
+    pub(super) async fn ping(&mut self) -> Result<(), NativeError> {
+        let mut out = BytesMut::new();
+        put_uvarint(&mut out, client_packet::PING);
+        self.send(&out).await?;
+
+        loop {
+            match read_uvarint(&mut self.stream).await? {
+                server_packet::PONG => return Ok(()),
+                server_packet::PROGRESS => self.skip_progress().await?,
+                packet => return Err(self.unexpected(packet).await),
+            }
+        }
+    }
+
+    /// Runs an `INSERT` query, with the settings given as name and value pairs.
+    ///
+    /// The server responds to the query with the names and types of the columns of the table,
+    /// from which `build_block` builds the block of rows that is inserted.
+    ///
+    /// Returns the number of bytes of data sent. The connection must be dropped if an error is
+    /// returned, as it may be left in the middle of the query.
+    pub(super) async fn insert(
+        &mut self,
+        query: &str,
+        settings: &[(&str, &str)],
+        build_block: impl FnOnce(&[(String, String)]) -> Result<Block, EncodeError>,
+    ) -> Result<usize, NativeError> {
+        let mut out = BytesMut::new();
+        self.put_query(&mut out, query, settings);
+        // No external tables are sent with the query.
+        self.put_data(&mut out, Block::write_empty)?;
+        self.send(&out).await?;
+
+        let columns = loop {
+            match read_uvarint(&mut self.stream).await? {
+                server_packet::DATA => break self.read_sample_block().await?,
+                server_packet::TABLE_COLUMNS => {
+                    let _table = read_string(&mut self.stream).await?;
+                    let _description = read_string(&mut self.stream).await?;
+                }
+                server_packet::PROGRESS => self.skip_progress().await?,
+                packet => return Err(self.unexpected(packet).await),
+            }
+        };
+
+        let block = build_block(&columns).context(EncodeSnafu)?;
+        let mut out = BytesMut::new();
+        self.put_data(&mut out, |out| block.write(out))?;
+        self.put_data(&mut out, Block::write_empty)?;
+        self.send(&out).await?;
+
+        loop {
+            match read_uvarint(&mut self.stream).await? {
+                server_packet::END_OF_STREAM => return Ok(out.len()),
+                server_packet::PROGRESS => self.skip_progress().await?,
+                server_packet::PROFILE_INFO => self.skip_profile_info().await?,
+                server_packet::TABLE_COLUMNS => {
+                    let _table = read_string(&mut self.stream).await?;
+                    let _description = read_string(&mut self.stream).await?;
+                }
+                packet => return Err(self.unexpected(packet).await),
+            }
+        }
+    }
+
+    fn put_query(&self, out: &mut BytesMut, query: &str, settings: &[(&str, &str)]) {
+        put_uvarint(out, client_packet::QUERY);
+        // The query ID is generated by the server.
+        put_string(out, b"");
+
+        // The client info, of an initial query over TCP.
+        out.put_u8(1);
+        put_string(out, b"");
+        put_string(out, b"");
+        put_string(out, b"0.0.0.0:0");
+        out.put_u8(1);
+        put_string(out, b"");
+        put_string(out, self.hostname.as_bytes());
+        put_string(out, CLIENT_NAME.as_bytes());
+        put_client_version(out);
+        // The quota key, and the patch version of the client.
+        put_string(out, b"");
+        put_uvarint(out, version(env!("CARGO_PKG_VERSION_PATCH")));
+
+        for (name, value) in settings {
+            put_string(out, name.as_bytes());
+            put_uvarint(out, SETTING_IMPORTANT);
+            put_string(out, value.as_bytes());
+        }
+        put_string(out, b"");
+
+        put_uvarint(out, STAGE_COMPLETE);
+        put_uvarint(out, self.compression.into());
+        put_string(out, query.as_bytes());
+    }
+
+    /// Appends a `Data` packet with the block written by `write_block`, compressing it if
+    /// compression is enabled.
+    fn put_data(
+        &self,
+        out: &mut BytesMut,
+        write_block: impl FnOnce(&mut BytesMut),
+    ) -> Result<(), NativeError> {
+        put_uvarint(out, client_packet::DATA);
+        // The name of the table, only set for external tables.
+        put_string(out, b"");
+        if self.compression {
+            let mut block = BytesMut::new();
+            write_block(&mut block);
+            put_compressed(out, &block)?;
+        } else {
+            write_block(out);
+        }
+        Ok(())
+    }
+
+    async fn send(&mut self, data: &[u8]) -> Result<(), NativeError> {
+        self.stream.write_all(data).await?;
+        self.stream.flush().await?;
+        Ok(())
+    }
+
+    /// Reads the names and types of the columns of the block the server sends for an insert.
+    async fn read_sample_block(&mut self) -> Result<Vec<(String, String)>, NativeError> {
+        let _table = read_string(&mut self.stream).await?;
+        if self.compression {
+            let frame = read_compressed(&mut self.stream).await?;
+            read_block_columns(&mut frame.as_slice()).await
+        } else {
+            read_block_columns(&mut self.stream).await
+        }
+    }
+
+    async fn skip_progress(&mut self) -> Result<(), NativeError> {
+        // The read, total, and written rows and bytes.
+        for _ in 0..5 {
+            read_uvarint(&mut self.stream).await?;
+        }
+        Ok(())
+    }
+
+    async fn skip_profile_info(&mut self) -> Result<(), NativeError> {
+        for _ in 0..3 {
+            read_uvarint(&mut self.stream).await?;
+        }
+        self.stream.read_u8().await?;
+        read_uvarint(&mut self.stream).await?;
+        self.stream.read_u8().await?;
+        Ok(())
+    }
+
+    /// Reads the exception sent in place of the expected packet, or fails on other packets.
+    async fn unexpected(&mut self, packet: u64) -> NativeError {
+        if packet != server_packet::EXCEPTION {
+            return NativeError::UnexpectedPacket { packet };
+        }
+        match read_exception(&mut self.stream).await {
+            Ok(exception) => NativeError::Server { exception },
+            Err(source) => NativeError::Io { source },
+        }
+    }
+}
+
+pub(super) fn put_uvarint(out: &mut BytesMut, mut value: u64) {
+    while value >= 0x80 {
+        out.put_u8(value as u8 | 0x80);
+        value >>= 7;
+    }
+    out.put_u8(value as u8);
+}
+
+pub(super) fn put_string(out: &mut BytesMut, value: &[u8]) {
+    put_uvarint(out, value.len() as u64);
+    out.put_slice(value);
+}
+
+fn put_client_version(out: &mut BytesMut) {
+    put_uvarint(out, version(env!("CARGO_PKG_VERSION_MAJOR")));
+    put_uvarint(out, version(env!("CARGO_PKG_VERSION_MINOR")));
+    put_uvarint(out, REVISION);
+}
+
+fn version(part: &str) -> u64 {
+    part.parse().unwrap_or_default()
+}
+
+/// Appends data as LZ4 compressed frames, each preceded by its checksum.
+fn put_compressed(out: &mut BytesMut, data: &[u8]) -> Result<(), NativeError> {
+    for chunk in data.chunks(MAX_FRAME_DATA_SIZE) {
+        let compressed = lz4::block::compress(chunk, None, false)?;
+        let mut frame = BytesMut::with_capacity(FRAME_HEADER_SIZE + compressed.len());
+        frame.put_u8(LZ4_METHOD);
+        frame.put_u32_le((FRAME_HEADER_SIZE + compressed.len()) as u32);
+        frame.put_u32_le(chunk.len() as u32);
+        frame.put_slice(&compressed);
+
+        out.put_u128_le(checksum(&frame));
+        out.put_slice(&frame);
+    }
+    Ok(())
+}
+
+/// Reads a compressed frame, returning its decompressed data.
+async fn read_compressed<R: AsyncRead + Unpin>(reader: &mut R) -> Result<Vec<u8>, NativeError> {
+    let expected_checksum = reader.read_u128_le().await?;
+    let mut header = [0; FRAME_HEADER_SIZE];
+    reader.read_exact(&mut header).await?;
+    let size = u32::from_le_bytes(header[1..5].try_into().unwrap()) as usize;
+    let data_size = u32::from_le_bytes(header[5..9].try_into().unwrap()) as usize;
+    if !(FRAME_HEADER_SIZE..=MAX_FRAME_SIZE).contains(&size) || data_size > MAX_FRAME_SIZE {
+        return InvalidDataSnafu {
+            reason: "invalid compressed frame size",
+        }
+        .fail();
+    }
+
+    let mut frame = vec![0; size];
+    frame[..FRAME_HEADER_SIZE].copy_from_slice(&header);
+    reader.read_exact(&mut frame[FRAME_HEADER_SIZE..]).await?;
+    if checksum(&frame) != expected_checksum {
+        return InvalidDataSnafu {
+            reason: "checksum mismatch of compressed frame",
+        }
+        .fail();
+    }
+
+    let data = &frame[FRAME_HEADER_SIZE..];
+    match header[0] {
+        LZ4_METHOD => Ok(lz4::block::decompress(data, Some(data_size as i32))?),
+        NONE_METHOD => Ok(data.to_vec()),
+        _ => InvalidDataSnafu {
+            reason: "unsupported compression method",
+        }
+        .fail(),
+    }
+}
+
+/// The checksum of a compressed frame: its CityHash v1.0.2, with the two halves of the hash
+/// swapped to be written as a little endian integer.
+fn checksum(frame: &[u8]) -> u128 {
+    cityhash_rs::cityhash_102_128(frame).rotate_right(64)
+}
+
+async fn read_uvarint<R: AsyncRead + Unpin>(reader: &mut R) -> io::Result<u64> {
+    let mut value = 0;
+    for shift in (0..64).step_by(7) {
+        let byte = reader.read_u8().await?;
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(io::Error::new(
+        io::ErrorKind::InvalidData,
+        "varint is too long",
+    ))
+}
+
+async fn read_string<R: AsyncRead + Unpin>(reader: &mut R) -> io::Result<String> {
+    let len = read_uvarint(reader).await? as usize;
+    if len > MAX_FRAME_SIZE {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "string is too long",
+        ));
+    }
+    let mut bytes = vec![0; len];
+    reader.read_exact(&mut bytes).await?;
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// Reads the columns of a block without rows, as their names and types.
+async fn read_block_columns<R: AsyncRead + Unpin>(
+    reader: &mut R,
+) -> Result<Vec<(String, String)>, NativeError> {
+    loop {
+        match read_uvarint(reader).await? {
+            0 => break,
+            1 => {
+                let _is_overflows = reader.read_u8().await?;
+            }
+            2 => {
+                let _bucket_num = reader.read_i32_le().await?;
+            }
+            _ => {
+                return InvalidDataSnafu {
+                    reason: "unknown block info field",
+                }
+                .fail()
+            }
+        }
+    }
+
+    let columns = read_uvarint(reader).await?;
+    let rows = read_uvarint(reader).await?;
+    if rows != 0 {
+        return InvalidDataSnafu {
+            reason: "unexpected rows in the sample block",
+        }
+        .fail();
+    }
+
+    let mut names_and_types = Vec::new();
+    for _ in 0..columns {
+        let name = read_string(reader).await?;
+        let type_name = read_string(reader).await?;
+        names_and_types.push((name, type_name));
+    }
+    Ok(names_and_types)
+}
+
+async fn read_exception<R: AsyncRead + Unpin>(reader: &mut R) -> io::Result<Exception> {
+    let exception = read_exception_fields(reader).await?;
+    // Nested exceptions are already part of the message.
+    while reader.read_u8().await? != 0 {
+        read_exception_fields(reader).await?;
+    }
+    Ok(exception)
+}
+
+async fn read_exception_fields<R: AsyncRead + Unpin>(reader: &mut R) -> io::Result<Exception> {
+    let code = reader.read_i32_le().await?;
+    let _name = read_string(reader).await?;
+    let message = read_string(reader).await?;
+    let _stack_trace = read_string(reader).await?;
+    Ok(Exception { code, message })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_uvarints() {
+        let mut out = BytesMut::new();
+        for value in [0, 127, 128, 54429] {
+            put_uvarint(&mut out, value);
+        }
+        assert_eq!(&out[..], &[0, 0x7f, 0x80, 0x01, 0x9d, 0xa9, 0x03]);
+    }
+
+    #[tokio::test]
+    async fn compresses_frames() {
+        let data = b"vector vector vector vector vector".repeat(100);
+        let mut out = BytesMut::new();
+        put_compressed(&mut out, &data).unwrap();
+        assert!(out.len() < data.len());
+        assert_eq!(out[16], LZ4_METHOD);
+
+        let decompressed = read_compressed(&mut &out[..]).await.unwrap();
+        assert_eq!(decompressed, data);
+
+        out[20] ^= 0xff;
+        assert!(matches!(
+            read_compressed(&mut &out[..]).await,
+            Err(NativeError::InvalidData { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn reads_sample_blocks() {
+        let mut out = BytesMut::new();
+        Block::new(&[
+            ("message".to_string(), "String".to_string()),
+            ("timestamp".to_string(), "DateTime64(3)".to_string()),
+        ])
+        .unwrap()
+        .write(&mut out);
+
+        assert_eq!(
+            read_block_columns(&mut &out[..]).await.unwrap(),
+            vec![
+                ("message".to_string(), "String".to_string()),
+                ("timestamp".to_string(), "DateTime64(3)".to_string()),
+            ]
+        );
+    }
+}
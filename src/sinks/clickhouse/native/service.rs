@@ -0,0 +1,197 @@
+//! Service implementation for the native protocol of the `Clickhouse` sink.
+
+use std::{
+    num::NonZeroUsize,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
+
+use super::{
+    block::Block,
+    protocol::{Connection, ConnectionOptions, NativeError},
+};
+use crate::sinks::{clickhouse::sink::PartitionKey, prelude::*};
+
+/// The error codes of the exceptions raised for data that doesn't match the table, which are not
+/// retried: `INCORRECT_DATA` and `TYPE_MISMATCH`.
+const NON_RETRIABLE_CODES: [i32; 2] = [117, 53];
+
+#[derive(Clone)]
+pub(super) struct NativeRequest {
+    key: PartitionKey,
+    events: Vec<Event>,
+    finalizers: EventFinalizers,
+    metadata: RequestMetadata,
+}
+
+impl NativeRequest {
+    pub(super) fn new(
+        key: PartitionKey,
+        mut events: Vec<Event>,
+        transformer: &Transformer,
+    ) -> Self {
+        let finalizers = events.take_finalizers();
+        for event in &mut events {
+            transformer.transform(event);
+        }
+        let builder = RequestMetadataBuilder::from_events(&events);
+        let size = events
+            .iter()
+            .map(|event| event.estimated_json_encoded_size_of().get())
+            .sum::<usize>();
+        let metadata =
+            builder.with_request_size(NonZeroUsize::new(size).unwrap_or(NonZeroUsize::MIN));
+
+        Self {
+            key,
+            events,
+            finalizers,
+            metadata,
+        }
+    }
+}
+
+impl Finalizable for NativeRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        std::mem::take(&mut self.finalizers)
+    }
+}
+
+impl MetaDescriptive for NativeRequest {
+    fn get_metadata(&self) -> &RequestMetadata {
+        &self.metadata
+    }
+
+    fn metadata_mut(&mut self) -> &mut RequestMetadata {
+        &mut self.metadata
+    }
+}
+
+#[derive(Debug)]
+pub(super) struct NativeResponse {
+    events_byte_size: GroupedCountByteSize,
+    bytes_sent: usize,
+}
+
+impl DriverResponse for NativeResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> &GroupedCountByteSize {
+        &self.events_byte_size
+    }
+
+    fn bytes_sent(&self) -> Option<usize> {
+        Some(self.bytes_sent)
+    }
+}
+
+#[derive(Clone, Default)]
+pub(super) struct NativeRetryLogic;
+
+impl RetryLogic for NativeRetryLogic {
+    type Error = NativeError;
+    type Response = NativeResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        match error {
+            NativeError::Server { exception } => !NON_RETRIABLE_CODES.contains(&exception.code),
+            NativeError::UnsupportedRevision { .. } | NativeError::Encode { .. } => false,
+            _ => true,
+        }
+    }
+}
+
+/// Inserts the events of requests over a pool of connections, opened as needed and kept open
+/// between requests.
+#[derive(Clone)]
+pub(super) struct NativeService {
+    options: Arc<ConnectionOptions>,
+    settings: Arc<Vec<(&'static str, &'static str)>>,
+    connections: Arc<Mutex<Vec<Connection>>>,
+}
+
+impl NativeService {
+    pub(super) fn new(
+        options: ConnectionOptions,
+        settings: Vec<(&'static str, &'static str)>,
+    ) -> Self {
+        Self {
+            options: Arc::new(options),
+            settings: Arc::new(settings),
+            connections: Arc::default(),
+        }
+    }
+}
+
+impl Service<NativeRequest> for NativeService {
+    type Response = NativeResponse;
+    type Error = NativeError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: NativeRequest) -> Self::Future {
+        let service = self.clone();
+        let events_byte_size = request
+            .metadata
+            .events_estimated_json_encoded_byte_size()
+            .clone();
+
+        Box::pin(async move {
+            let pooled = service.connections.lock().expect("poisoned lock").pop();
+            let mut connection = match pooled {
+                Some(connection) => connection,
+                None => Connection::connect(&service.options).await?,
+            };
+
+            let query = format!(
+                "INSERT INTO {}.{} VALUES",
+                quote_identifier(&request.key.database),
+                quote_identifier(&request.key.table)
+            );
+            let events = &request.events;
+            let bytes_sent = connection
+                .insert(&query, &service.settings, |columns| {
+                    let mut block = Block::new(columns)?;
+                    for event in events {
+                        block.push(event.as_log())?;
+                    }
+                    Ok(block)
+                })
+                .await?;
+
+            service
+                .connections
+                .lock()
+                .expect("poisoned lock")
+                .push(connection);
+
+            Ok(NativeResponse {
+                events_byte_size,
+                bytes_sent,
+            })
+        })
+    }
+}
+
+fn quote_identifier(identifier: &str) -> String {
+    format!(
+        "\"{}\"",
+        identifier.replace('\\', "\\\\").replace('"', "\\\"")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quotes_identifiers() {
+        assert_eq!(quote_identifier("my_table"), "\"my_table\"");
+        assert_eq!(quote_identifier("my_\"table\""), "\"my_\\\"table\\\"\"");
+    }
+}
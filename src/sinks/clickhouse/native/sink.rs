@@ -0,0 +1,42 @@
+//! Implementation of the native protocol of the `clickhouse` sink.
+
+use super::service::{NativeRequest, NativeRetryLogic, NativeService};
+use crate::sinks::{
+    clickhouse::{config::Format, sink::KeyPartitioner},
+    prelude::*,
+};
+
+pub(super) struct NativeSink {
+    pub(super) batch_settings: BatcherSettings,
+    pub(super) service: Svc<NativeService, NativeRetryLogic>,
+    pub(super) database: Template,
+    pub(super) table: Template,
+    pub(super) format: Format,
+    pub(super) transformer: Transformer,
+}
+
+impl NativeSink {
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let batch_settings = self.batch_settings;
+        let transformer = self.transformer;
+
+        input
+            .batched_partitioned(
+                KeyPartitioner::new(self.database, self.table, self.format),
+                || batch_settings.as_byte_size_config(),
+            )
+            .filter_map(|(key, batch)| async move { key.map(move |k| (k, batch)) })
+            .map(|(key, events)| NativeRequest::new(key, events, &transformer))
+            .into_driver(self.service)
+            .protocol("tcp")
+            .run()
+            .await
+    }
+}
+
+#[async_trait::async_trait]
+impl StreamSink<Event> for NativeSink {
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
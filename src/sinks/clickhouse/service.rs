@@ -66,6 +66,7 @@ pub(super) struct ClickhouseServiceRequestBuilder {
     pub(super) skip_unknown_fields: bool,
     pub(super) date_time_best_effort: bool,
     pub(super) insert_random_shard: bool,
+    pub(super) async_insert: bool,
     pub(super) compression: Compression,
 }
 
@@ -84,6 +85,7 @@ impl HttpServiceRequestBuilder<PartitionKey> for ClickhouseServiceRequestBuilder
             self.skip_unknown_fields,
             self.date_time_best_effort,
             self.insert_random_shard,
+            self.async_insert,
         )?;
 
         let auth: Option<Auth> = self.auth.clone();
@@ -107,6 +109,7 @@ impl HttpServiceRequestBuilder<PartitionKey> for ClickhouseServiceRequestBuilder
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn set_uri_query(
     uri: &Uri,
     database: &str,
@@ -115,6 +118,7 @@ fn set_uri_query(
     skip_unknown: bool,
     date_time_best_effort: bool,
     insert_random_shard: bool,
+    async_insert: bool,
 ) -> crate::Result<Uri> {
     let query = url::form_urlencoded::Serializer::new(String::new())
         .append_pair(
@@ -144,6 +148,9 @@ fn set_uri_query(
     if insert_random_shard {
         uri.push_str("insert_distributed_one_random_shard=1&")
     }
+    if async_insert {
+        uri.push_str("async_insert=1&wait_for_async_insert=1&")
+    }
     uri.push_str(query.as_str());
 
     uri.parse::<Uri>()
@@ -165,6 +172,7 @@ mod tests {
             false,
             true,
             false,
+            false,
         )
         .unwrap();
         assert_eq!(uri.to_string(), "http://localhost:80/?\
@@ -180,6 +188,7 @@ mod tests {
             false,
             false,
             false,
+            false,
         )
         .unwrap();
         assert_eq!(uri.to_string(), "http://localhost:80/?\
@@ -194,6 +203,7 @@ mod tests {
             true,
             true,
             false,
+            false,
         )
         .unwrap();
         assert_eq!(uri.to_string(), "http://localhost:80/?\
@@ -201,6 +211,22 @@ mod tests {
                                      input_format_skip_unknown_fields=1&\
                                      date_time_input_format=best_effort&\
                                      query=INSERT+INTO+%22my_database%22.%22my_%5C%22table%5C%22%22+FORMAT+JSONAsObject");
+
+        let uri = set_uri_query(
+            &"http://localhost:80".parse().unwrap(),
+            "my_database",
+            "my_table",
+            Format::JsonEachRow,
+            false,
+            false,
+            false,
+            true,
+        )
+        .unwrap();
+        assert_eq!(uri.to_string(), "http://localhost:80/?\
+                                     input_format_import_nested_json=1&\
+                                     async_insert=1&wait_for_async_insert=1&\
+                                     query=INSERT+INTO+%22my_database%22.%22my_table%22+FORMAT+JSONEachRow");
     }
 
     #[test]
@@ -213,6 +239,7 @@ mod tests {
             false,
             false,
             false,
+            false,
         )
         .unwrap_err();
     }
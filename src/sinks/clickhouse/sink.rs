@@ -90,14 +90,14 @@ pub(super) struct PartitionKey {
 }
 
 /// KeyPartitioner that partitions events by (database, table) pair.
-struct KeyPartitioner {
+pub(super) struct KeyPartitioner {
     database: Template,
     table: Template,
     format: Format,
 }
 
 impl KeyPartitioner {
-    const fn new(database: Template, table: Template, format: Format) -> Self {
+    pub(super) const fn new(database: Template, table: Template, format: Format) -> Self {
         Self {
             database,
             table,
@@ -9,7 +9,10 @@ use vector_lib::configurable::configurable_component;
 use crate::{
     codecs::{Encoder, EncodingConfigWithFraming, SinkType},
     config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
-    sinks::{console::sink::WriterSink, Healthcheck, VectorSink},
+    sinks::{
+        console::{display::DisplayConfig, sink::WriterSink},
+        Healthcheck, VectorSink,
+    },
 };
 
 /// The [standard stream][standard_streams] to write to.
@@ -47,6 +50,9 @@ pub struct ConsoleSinkConfig {
     #[serde(flatten)]
     pub encoding: EncodingConfigWithFraming,
 
+    #[configurable(derived)]
+    pub display: Option<DisplayConfig>,
+
     #[configurable(derived)]
     #[serde(
         default,
@@ -65,6 +71,7 @@ impl GenerateConfig for ConsoleSinkConfig {
         toml::Value::try_from(Self {
             target: Target::Stdout,
             encoding: (None::<FramingConfig>, JsonSerializerConfig::default()).into(),
+            display: None,
             acknowledgements: Default::default(),
         })
         .unwrap()
@@ -78,17 +85,24 @@ impl SinkConfig for ConsoleSinkConfig {
         let transformer = self.encoding.transformer();
         let (framer, serializer) = self.encoding.build(SinkType::StreamBased)?;
         let encoder = Encoder::<Framer>::new(framer, serializer);
+        let display = self
+            .display
+            .as_ref()
+            .map(|display| display.build(&self.target))
+            .transpose()?;
 
         let sink: VectorSink = match self.target {
             Target::Stdout => VectorSink::from_event_streamsink(WriterSink {
                 output: io::stdout(),
                 transformer,
                 encoder,
+                display,
             }),
             Target::Stderr => VectorSink::from_event_streamsink(WriterSink {
                 output: io::stderr(),
                 transformer,
                 encoder,
+                display,
             }),
         };
 
@@ -96,7 +110,11 @@ impl SinkConfig for ConsoleSinkConfig {
     }
 
     fn input(&self) -> Input {
-        Input::new(self.encoding.config().1.input_type())
+        if self.display.is_some() {
+            Input::all()
+        } else {
+            Input::new(self.encoding.config().1.input_type())
+        }
     }
 
     fn acknowledgements(&self) -> &AcknowledgementsConfig {
@@ -0,0 +1,354 @@
+use std::{borrow::Cow, io::IsTerminal, num::NonZeroUsize};
+
+use vector_lib::configurable::configurable_component;
+use vector_lib::lookup::lookup_v2::ConfigValuePath;
+use vrl::path::PathPrefix;
+
+use super::config::Target;
+use crate::event::{Event, LogEvent, Value};
+
+const RESET: &str = "\x1b[0m";
+const BOLD: &str = "\x1b[1m";
+const DIM: &str = "\x1b[2m";
+const RED: &str = "\x1b[31m";
+const GREEN: &str = "\x1b[32m";
+const YELLOW: &str = "\x1b[33m";
+const BLUE: &str = "\x1b[34m";
+const MAGENTA: &str = "\x1b[35m";
+const CYAN: &str = "\x1b[36m";
+
+fn default_level_field() -> ConfigValuePath {
+    ConfigValuePath::from("level")
+}
+
+fn default_column_width() -> NonZeroUsize {
+    NonZeroUsize::new(24).unwrap()
+}
+
+/// Configuration for displaying events for humans, instead of encoding them.
+///
+/// When set, events are displayed with this configuration, and only the transformation options of
+/// `encoding`, such as `encoding.only_fields`, are applied to them.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct DisplayConfig {
+    #[configurable(derived)]
+    pub mode: DisplayMode,
+
+    /// The fields of the events to display.
+    ///
+    /// In `pretty` mode, the fields are displayed below the timestamp, level, and message of each
+    /// event, and all of the fields are displayed when this isn't set. In `table` mode, each field
+    /// is a column of the table, and at least one must be set.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "timestamp"))]
+    #[configurable(metadata(docs::examples = "host"))]
+    #[configurable(metadata(docs::examples = "message"))]
+    pub fields: Vec<ConfigValuePath>,
+
+    /// The field that contains the level of log events, such as `info` or `error`.
+    ///
+    /// The level colors the events, when colors are enabled.
+    #[serde(default = "default_level_field")]
+    #[configurable(metadata(docs::examples = "severity"))]
+    pub level_field: ConfigValuePath,
+
+    /// The width of the columns in `table` mode, in characters.
+    ///
+    /// Longer values are truncated, except in the last column.
+    #[serde(default = "default_column_width")]
+    pub column_width: NonZeroUsize,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub color: ColorMode,
+}
+
+/// How events are displayed.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DisplayMode {
+    /// Each event is displayed on a line with its timestamp, level, and message, followed by one
+    /// line for each of its fields.
+    Pretty,
+
+    /// Each event is displayed on a row of a table, whose columns are set by `fields`.
+    Table,
+}
+
+/// Whether the output is colorized.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ColorMode {
+    /// Colorize the output when the target is a terminal.
+    #[default]
+    Auto,
+
+    /// Always colorize the output, such as when it's piped into `less -R`.
+    Always,
+
+    /// Never colorize the output.
+    Never,
+}
+
+impl ColorMode {
+    fn use_color(self, target: &Target) -> bool {
+        match self {
+            Self::Auto => match target {
+                Target::Stdout => std::io::stdout().is_terminal(),
+                Target::Stderr => std::io::stderr().is_terminal(),
+            },
+            Self::Always => true,
+            Self::Never => false,
+        }
+    }
+}
+
+impl DisplayConfig {
+    pub(super) fn build(&self, target: &Target) -> crate::Result<EventDisplay> {
+        if self.mode == DisplayMode::Table && self.fields.is_empty() {
+            return Err("`display.fields` must be set in `table` mode".into());
+        }
+
+        Ok(EventDisplay {
+            config: self.clone(),
+            color: self.color.use_color(target),
+        })
+    }
+}
+
+/// Displays events as text, following a [`DisplayConfig`].
+#[derive(Clone, Debug)]
+pub struct EventDisplay {
+    config: DisplayConfig,
+    color: bool,
+}
+
+impl EventDisplay {
+    /// The header of the output, which is the row of column names in `table` mode.
+    pub fn header(&self) -> Option<String> {
+        (self.config.mode == DisplayMode::Table).then(|| {
+            let names = self
+                .config
+                .fields
+                .iter()
+                .map(|field| field.0.to_string())
+                .collect::<Vec<_>>();
+            let row = self.row(names.iter().map(|name| Cow::Borrowed(name.as_str())), None);
+            format!("{}\n", self.paint(&row, BOLD))
+        })
+    }
+
+    /// Displays an event, including its trailing newline.
+    pub fn display(&self, event: &Event) -> String {
+        let log = match event {
+            Event::Log(log) => log,
+            Event::Trace(trace) => trace.as_ref(),
+            Event::Metric(metric) => return format!("{}\n", metric),
+        };
+
+        match self.config.mode {
+            DisplayMode::Pretty => self.pretty(log),
+            DisplayMode::Table => {
+                let level = self.level(log);
+                let cells = self.config.fields.iter().map(|field| {
+                    log.get((PathPrefix::Event, field))
+                        .map(|value| Cow::Owned(value.to_string_lossy().replace('\n', " ")))
+                        .unwrap_or_default()
+                });
+                let row = self.row(cells, level.as_deref());
+                format!("{}\n", row)
+            }
+        }
+    }
+
+    fn pretty(&self, log: &LogEvent) -> String {
+        let mut header = Vec::new();
+        if let Some(timestamp) = log.get_timestamp() {
+            header.push(self.paint(&timestamp.to_string_lossy(), DIM));
+        }
+        if let Some(level) = self.level(log) {
+            header.push(self.paint(&format!("{:<5}", level.to_uppercase()), level_color(&level)));
+        }
+        if let Some(message) = log.get_message() {
+            header.push(self.paint(&message.to_string_lossy(), BOLD));
+        }
+
+        let mut output = String::new();
+        if !header.is_empty() {
+            output.push_str(&header.join(" "));
+            output.push('\n');
+        }
+
+        let mut push_field = |name: &str, value: &Value| {
+            output.push_str(&format!(
+                "  {}: {}\n",
+                self.paint(name, CYAN),
+                value.to_string_lossy()
+            ));
+        };
+        if self.config.fields.is_empty() {
+            let displayed = [
+                log.timestamp_path().map(ToString::to_string),
+                Some(self.config.level_field.0.to_string()),
+                log.message_path().map(ToString::to_string),
+            ];
+            for (name, value) in log.all_event_fields().into_iter().flatten() {
+                // The paths in the header are prefixed with `.`, as event paths.
+                let displayed_in_header = displayed
+                    .iter()
+                    .flatten()
+                    .any(|path| path.trim_start_matches('.') == name.as_str());
+                if !displayed_in_header {
+                    push_field(&name, value);
+                }
+            }
+        } else {
+            for field in &self.config.fields {
+                if let Some(value) = log.get((PathPrefix::Event, field)) {
+                    push_field(&field.0.to_string(), value);
+                }
+            }
+        }
+
+        output
+    }
+
+    fn level(&self, log: &LogEvent) -> Option<String> {
+        log.get((PathPrefix::Event, &self.config.level_field))
+            .map(|level| level.to_string_lossy().into_owned())
+    }
+
+    /// Pads or truncates the cells to the width of the columns.
+    fn row<'a>(&self, cells: impl Iterator<Item = Cow<'a, str>>, level: Option<&str>) -> String {
+        let width = self.config.column_width.get();
+        let columns = self.config.fields.len();
+        cells
+            .enumerate()
+            .map(|(index, cell)| {
+                let cell = if index + 1 == columns {
+                    cell.into_owned()
+                } else if cell.chars().count() > width {
+                    let mut truncated = cell.chars().take(width - 1).collect::<String>();
+                    truncated.push('…');
+                    truncated
+                } else {
+                    format!("{:<width$}", cell)
+                };
+
+                match level {
+                    Some(level) if self.config.fields[index] == self.config.level_field => {
+                        self.paint(&cell, level_color(level))
+                    }
+                    _ => cell,
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    }
+
+    fn paint(&self, text: &str, color: &str) -> String {
+        if self.color && !color.is_empty() {
+            format!("{}{}{}", color, text, RESET)
+        } else {
+            text.to_string()
+        }
+    }
+}
+
+/// The color of a level, which is empty for unknown levels.
+fn level_color(level: &str) -> &'static str {
+    match level.to_ascii_lowercase().as_str() {
+        "emerg" | "emergency" | "alert" | "crit" | "critical" | "fatal" | "err" | "error" => RED,
+        "warn" | "warning" => YELLOW,
+        "notice" | "info" | "information" => GREEN,
+        "debug" => BLUE,
+        "trace" => MAGENTA,
+        _ => "",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use vector_lib::lookup::event_path;
+
+    use super::*;
+
+    fn display(mode: DisplayMode, fields: &[&str], color: ColorMode) -> EventDisplay {
+        DisplayConfig {
+            mode,
+            fields: fields
+                .iter()
+                .map(|field| ConfigValuePath::from(*field))
+                .collect(),
+            level_field: default_level_field(),
+            column_width: NonZeroUsize::new(8).unwrap(),
+            color,
+        }
+        .build(&Target::Stdout)
+        .unwrap()
+    }
+
+    fn event() -> Event {
+        let mut log = LogEvent::from("disk full");
+        log.insert(
+            event_path!("timestamp"),
+            Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap(),
+        );
+        log.insert(event_path!("level"), "error");
+        log.insert(event_path!("host"), "db-1.example.com");
+        log.into()
+    }
+
+    #[test]
+    fn displays_pretty_events() {
+        let display = display(DisplayMode::Pretty, &[], ColorMode::Never);
+
+        assert_eq!(display.header(), None);
+        assert_eq!(
+            display.display(&event()),
+            "2024-01-02T03:04:05Z ERROR disk full\n  host: db-1.example.com\n"
+        );
+    }
+
+    #[test]
+    fn displays_colored_levels() {
+        let display = display(DisplayMode::Pretty, &["host"], ColorMode::Always);
+
+        assert_eq!(
+            display.display(&event()),
+            "\x1b[2m2024-01-02T03:04:05Z\x1b[0m \x1b[31mERROR\x1b[0m \x1b[1mdisk full\x1b[0m\n  \x1b[36mhost\x1b[0m: db-1.example.com\n"
+        );
+    }
+
+    #[test]
+    fn displays_table_rows() {
+        let display = display(
+            DisplayMode::Table,
+            &["level", "host", "message"],
+            ColorMode::Never,
+        );
+
+        assert_eq!(display.header().unwrap(), "level     host      message\n");
+        assert_eq!(display.display(&event()), "error     db-1.ex…  disk full\n");
+    }
+
+    #[test]
+    fn requires_table_fields() {
+        let config = DisplayConfig {
+            mode: DisplayMode::Table,
+            fields: Vec::new(),
+            level_field: default_level_field(),
+            column_width: default_column_width(),
+            color: ColorMode::Never,
+        };
+
+        assert!(config.build(&Target::Stdout).is_err());
+    }
+}
@@ -1,4 +1,6 @@
 mod config;
+mod display;
 mod sink;
 
 pub use config::{ConsoleSinkConfig, Target};
+pub use display::{ColorMode, DisplayConfig, DisplayMode};
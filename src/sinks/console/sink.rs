@@ -11,6 +11,7 @@ use vector_lib::{
     EstimatedJsonEncodedSizeOf,
 };
 
+use super::display::EventDisplay;
 use crate::{
     codecs::{Encoder, Transformer},
     event::{Event, EventStatus, Finalizable},
@@ -21,6 +22,7 @@ pub struct WriterSink<T> {
     pub output: T,
     pub transformer: Transformer,
     pub encoder: Encoder<Framer>,
+    pub display: Option<EventDisplay>,
 }
 
 #[async_trait]
@@ -31,16 +33,25 @@ where
     async fn run(mut self: Box<Self>, mut input: BoxStream<'_, Event>) -> Result<(), ()> {
         let bytes_sent = register!(BytesSent::from(Protocol("console".into(),)));
         let events_sent = register!(EventsSent::from(Output(None)));
+        let mut header = self.display.as_ref().and_then(EventDisplay::header);
         while let Some(mut event) = input.next().await {
             let event_byte_size = event.estimated_json_encoded_size_of();
             self.transformer.transform(&mut event);
 
             let finalizers = event.take_finalizers();
             let mut bytes = BytesMut::new();
-            self.encoder.encode(event, &mut bytes).map_err(|_| {
-                // Error is handled by `Encoder`.
-                finalizers.update_status(EventStatus::Errored);
-            })?;
+            match &self.display {
+                Some(display) => {
+                    if let Some(header) = header.take() {
+                        bytes.extend_from_slice(header.as_bytes());
+                    }
+                    bytes.extend_from_slice(display.display(&event).as_bytes());
+                }
+                None => self.encoder.encode(event, &mut bytes).map_err(|_| {
+                    // Error is handled by `Encoder`.
+                    finalizers.update_status(EventStatus::Errored);
+                })?,
+            }
 
             match self.output.write_all(&bytes).await {
                 Err(error) => {
@@ -89,6 +100,7 @@ mod test {
             output: Vec::new(),
             transformer: Default::default(),
             encoder,
+            display: None,
         };
 
         run_and_assert_sink_compliance(
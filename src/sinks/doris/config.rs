@@ -0,0 +1,212 @@
+//! Configuration for the `doris` sink.
+
+use std::collections::BTreeMap;
+
+use http::{HeaderName, HeaderValue, Request, StatusCode, Uri};
+use hyper::Body;
+
+use super::{
+    encoding::DorisEncodingConfig,
+    request_builder::DorisRequestBuilder,
+    service::{DorisRetryLogic, DorisService},
+    sink::DorisSink,
+};
+use crate::{
+    http::{Auth, HttpClient, MaybeAuth},
+    sinks::{
+        prelude::*,
+        util::{RealtimeSizeBasedDefaultBatchSettings, UriSerde},
+    },
+};
+
+fn default_label_prefix() -> String {
+    "vector".to_string()
+}
+
+/// Configuration for the `doris` sink.
+#[configurable_component(sink(
+    "doris",
+    "Load log events into Apache Doris or StarRocks tables with Stream Load."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct DorisConfig {
+    /// The HTTP endpoint of the frontend the Stream Loads are sent to.
+    ///
+    /// The frontend redirects the Stream Loads to a backend, which must be reachable by Vector
+    /// at the address the frontend redirects to.
+    #[configurable(metadata(docs::examples = "http://localhost:8030"))]
+    pub endpoint: UriSerde,
+
+    /// The database that contains the table that data is loaded into.
+    #[configurable(metadata(docs::examples = "mydatabase"))]
+    #[configurable(metadata(docs::examples = "logs_{{ team }}"))]
+    pub database: Template,
+
+    /// The table that data is loaded into.
+    #[configurable(metadata(docs::examples = "mytable"))]
+    #[configurable(metadata(docs::examples = "{{ service }}_logs"))]
+    pub table: Template,
+
+    /// The prefix of the labels of the Stream Loads.
+    ///
+    /// Each batch is loaded with a label made of this prefix and a random UUID, which is kept
+    /// when its load is retried, so that the batch is loaded at most once. Labels are unique
+    /// within a database, so different prefixes can be used to tell the loads of different
+    /// sinks apart.
+    #[serde(default = "default_label_prefix")]
+    #[configurable(metadata(docs::examples = "vector_app_logs"))]
+    pub label_prefix: String,
+
+    /// Additional Stream Load parameters, sent as the headers of the loads.
+    ///
+    /// This can be used to map the fields of the events to the columns of the table with
+    /// `columns`, or to tolerate rows that fail to load with `max_filter_ratio`.
+    #[serde(default)]
+    #[configurable(metadata(
+        docs::additional_props_description = "A Stream Load parameter and its value."
+    ))]
+    #[configurable(metadata(docs::examples = "headers_examples()"))]
+    pub headers: BTreeMap<String, String>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub encoding: DorisEncodingConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch: BatchConfig<RealtimeSizeBasedDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    pub auth: Option<Auth>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::is_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+fn headers_examples() -> BTreeMap<String, String> {
+    BTreeMap::from([
+        ("columns".to_string(), "ts, host, message".to_string()),
+        ("max_filter_ratio".to_string(), "0.1".to_string()),
+    ])
+}
+
+impl GenerateConfig for DorisConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"endpoint = "http://localhost:8030"
+            database = "mydatabase"
+            table = "mytable"
+            auth.strategy = "basic"
+            auth.user = "root"
+            auth.password = """#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "doris")]
+impl SinkConfig for DorisConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let endpoint = self.endpoint.with_default_parts().uri;
+        let auth = self.auth.choose_one(&self.endpoint.auth)?;
+
+        let mut headers = self
+            .encoding
+            .format_headers()
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value))
+            .collect::<BTreeMap<_, _>>();
+        headers.extend(self.headers.clone());
+        let headers = headers
+            .into_iter()
+            .map(|(name, value)| {
+                Ok((
+                    HeaderName::try_from(name.as_str())
+                        .map_err(|_| format!("Invalid Stream Load header name {:?}", name))?,
+                    HeaderValue::try_from(value.as_str()).map_err(|_| {
+                        format!("Invalid value of the Stream Load header {:?}", name)
+                    })?,
+                ))
+            })
+            .collect::<crate::Result<Vec<_>>>()?;
+
+        let tls_settings = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new(tls_settings, &cx.proxy)?;
+
+        let healthcheck = healthcheck(client.clone(), endpoint.clone(), auth.clone()).boxed();
+
+        let service = ServiceBuilder::new()
+            .settings(self.request.into_settings(), DorisRetryLogic)
+            .service(DorisService {
+                client,
+                endpoint,
+                auth,
+                headers,
+            });
+
+        let sink = DorisSink {
+            batch_settings: self.batch.into_batcher_settings()?,
+            service,
+            database: self.database.clone(),
+            table: self.table.clone(),
+            request_builder: DorisRequestBuilder {
+                encoder: self.encoding.build()?,
+                label_prefix: self.label_prefix.clone(),
+            },
+        };
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+/// Checks that the frontend is up with its `/api/health` endpoint.
+async fn healthcheck(client: HttpClient, endpoint: Uri, auth: Option<Auth>) -> crate::Result<()> {
+    let mut uri = endpoint.to_string();
+    if !uri.ends_with('/') {
+        uri.push('/');
+    }
+    uri.push_str("api/health");
+
+    let mut request = Request::get(uri).body(Body::empty())?;
+    if let Some(auth) = auth {
+        auth.apply(&mut request);
+    }
+
+    let response = client.send(request).await?;
+    match response.status() {
+        StatusCode::OK => Ok(()),
+        status => Err(HealthcheckError::UnexpectedStatus { status }.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<DorisConfig>();
+    }
+}
@@ -0,0 +1,100 @@
+//! Encoding for the `doris` sink.
+
+use vector_lib::codecs::{
+    encoding::{Framer, FramingConfig, SerializerConfig},
+    CharacterDelimitedEncoderConfig, CsvSerializerConfig, JsonSerializerConfig,
+};
+use vector_lib::configurable::configurable_component;
+
+use crate::codecs::{Encoder, EncodingConfig, Transformer};
+
+/// Serializer configuration for Doris.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(tag = "codec", rename_all = "snake_case")]
+#[configurable(metadata(docs::enum_tag_description = "The codec to use for encoding events."))]
+pub(super) enum DorisSerializerConfig {
+    /// Encodes an event as a CSV row.
+    ///
+    /// This codec must be configured with fields to encode. The rows are loaded into the columns
+    /// of the table in the order of the fields, unless the `columns` Stream Load header is set.
+    Csv(
+        /// Options for the CSV encoder.
+        CsvSerializerConfig,
+    ),
+
+    /// Encodes an event as a [JSON][json] object.
+    ///
+    /// The fields of the objects are loaded into the columns of the same name.
+    ///
+    /// [json]: https://www.json.org/
+    Json(
+        /// Encoding options specific to the Json serializer.
+        JsonSerializerConfig,
+    ),
+}
+
+impl From<DorisSerializerConfig> for SerializerConfig {
+    fn from(config: DorisSerializerConfig) -> Self {
+        match config {
+            DorisSerializerConfig::Csv(config) => Self::Csv(config),
+            DorisSerializerConfig::Json(config) => Self::Json(config),
+        }
+    }
+}
+
+impl Default for DorisSerializerConfig {
+    fn default() -> Self {
+        Self::Json(JsonSerializerConfig::default())
+    }
+}
+
+/// Encoding configuration for Doris.
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+#[configurable(description = "Configures how events are encoded into raw bytes.")]
+pub struct DorisEncodingConfig {
+    #[serde(flatten)]
+    encoding: DorisSerializerConfig,
+
+    #[serde(flatten)]
+    transformer: Transformer,
+}
+
+impl DorisEncodingConfig {
+    /// Builds the encoder of batches of events.
+    ///
+    /// JSON objects are encoded into an array, and CSV rows are delimited by newlines.
+    pub(super) fn build(&self) -> crate::Result<(Transformer, Encoder<Framer>)> {
+        let framer = match self.encoding {
+            DorisSerializerConfig::Csv(_) => FramingConfig::NewlineDelimited,
+            DorisSerializerConfig::Json(_) => {
+                FramingConfig::CharacterDelimited(CharacterDelimitedEncoderConfig::new(b','))
+            }
+        };
+        let encoding = EncodingConfig::new(self.encoding.clone().into(), self.transformer.clone());
+        let serializer = encoding.build()?;
+
+        Ok((
+            encoding.transformer(),
+            Encoder::<Framer>::new(framer.build(), serializer),
+        ))
+    }
+
+    /// The Stream Load headers that describe the format of the encoded batches.
+    pub(super) fn format_headers(&self) -> Vec<(&'static str, String)> {
+        match &self.encoding {
+            DorisSerializerConfig::Csv(config) => vec![
+                ("format", "csv".to_string()),
+                (
+                    "column_separator",
+                    char::from(config.csv.delimiter).to_string(),
+                ),
+            ],
+            DorisSerializerConfig::Json(_) => vec![
+                ("format", "json".to_string()),
+                ("strip_outer_array", "true".to_string()),
+            ],
+        }
+    }
+}
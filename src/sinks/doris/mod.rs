@@ -0,0 +1,29 @@
+//! The `doris` sink.
+//!
+//! This sink loads log events into the tables of [Apache Doris][doris] and [StarRocks][starrocks]
+//! with their [Stream Load][stream_load] HTTP interface, encoded as JSON arrays or as CSV. The
+//! database and the table of each event are rendered from templates, so events are batched by the
+//! table they're loaded into.
+//!
+//! Each batch is loaded with a label of its own, which is kept when its load is retried. Doris and
+//! StarRocks refuse to load the same label twice, so a batch that was loaded without Vector
+//! receiving the response isn't loaded a second time when it's retried, and is acknowledged once
+//! the response to the retry reports that its label was loaded already.
+//!
+//! The frontends answer Stream Loads with a redirect to the backend that loads the data, which is
+//! followed by the service.
+//!
+//! [doris]: https://doris.apache.org/
+//! [starrocks]: https://www.starrocks.io/
+//! [stream_load]: https://doris.apache.org/docs/data-operate/import/stream-load-manual
+
+mod config;
+mod encoding;
+mod request_builder;
+mod service;
+mod sink;
+
+#[cfg(test)]
+mod tests;
+
+pub use self::config::DorisConfig;
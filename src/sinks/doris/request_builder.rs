@@ -0,0 +1,86 @@
+//! `RequestBuilder` implementation for the `doris` sink.
+
+use std::io;
+
+use bytes::Bytes;
+use uuid::Uuid;
+use vector_lib::codecs::encoding::Framer;
+
+use super::sink::PartitionKey;
+use crate::sinks::prelude::*;
+
+/// A Stream Load of a batch of events into a table.
+#[derive(Clone)]
+pub(super) struct DorisRequest {
+    pub(super) key: PartitionKey,
+    /// The label of the load, which is kept when it's retried.
+    pub(super) label: String,
+    pub(super) payload: Bytes,
+    pub(super) finalizers: EventFinalizers,
+    pub(super) metadata: RequestMetadata,
+}
+
+impl Finalizable for DorisRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        std::mem::take(&mut self.finalizers)
+    }
+}
+
+impl MetaDescriptive for DorisRequest {
+    fn get_metadata(&self) -> &RequestMetadata {
+        &self.metadata
+    }
+
+    fn metadata_mut(&mut self) -> &mut RequestMetadata {
+        &mut self.metadata
+    }
+}
+
+pub(super) struct DorisRequestBuilder {
+    pub(super) encoder: (Transformer, Encoder<Framer>),
+    pub(super) label_prefix: String,
+}
+
+impl RequestBuilder<(PartitionKey, Vec<Event>)> for DorisRequestBuilder {
+    type Metadata = (PartitionKey, EventFinalizers);
+    type Events = Vec<Event>;
+    type Encoder = (Transformer, Encoder<Framer>);
+    type Payload = Bytes;
+    type Request = DorisRequest;
+    type Error = io::Error;
+
+    fn compression(&self) -> Compression {
+        Compression::None
+    }
+
+    fn encoder(&self) -> &Self::Encoder {
+        &self.encoder
+    }
+
+    fn split_input(
+        &self,
+        input: (PartitionKey, Vec<Event>),
+    ) -> (Self::Metadata, RequestMetadataBuilder, Self::Events) {
+        let (key, mut events) = input;
+
+        let finalizers = events.take_finalizers();
+        let builder = RequestMetadataBuilder::from_events(&events);
+        ((key, finalizers), builder, events)
+    }
+
+    fn build_request(
+        &self,
+        metadata: Self::Metadata,
+        request_metadata: RequestMetadata,
+        payload: EncodeResult<Self::Payload>,
+    ) -> Self::Request {
+        let (key, finalizers) = metadata;
+        DorisRequest {
+            key,
+            label: format!("{}_{}", self.label_prefix, Uuid::new_v4().simple()),
+            payload: payload.into_payload(),
+            finalizers,
+            metadata: request_metadata,
+        }
+    }
+}
@@ -0,0 +1,308 @@
+//! Service implementation for the `doris` sink.
+
+use std::task::{Context, Poll};
+
+use http::{
+    header::{CONTENT_LENGTH, EXPECT, LOCATION},
+    HeaderName, HeaderValue, Request, Response, StatusCode, Uri,
+};
+use hyper::Body;
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+use serde::Deserialize;
+use snafu::{ResultExt, Snafu};
+
+use super::{request_builder::DorisRequest, sink::PartitionKey};
+use crate::{
+    http::{Auth, HttpClient, HttpError},
+    internal_events::EndpointBytesSent,
+    sinks::{prelude::*, util::uri::protocol_endpoint},
+};
+
+/// The number of redirects followed for a Stream Load, from the frontend to a backend.
+const MAX_REDIRECTS: usize = 3;
+
+#[derive(Debug, Snafu)]
+pub(super) enum DorisError {
+    #[snafu(display("Invalid URI of the Stream Load: {}", source))]
+    InvalidUri { source: http::uri::InvalidUri },
+    #[snafu(display("Failed to build the request: {}", source))]
+    BuildRequest { source: http::Error },
+    #[snafu(display("Failed to send the request: {}", source))]
+    SendRequest { source: HttpError },
+    #[snafu(display("Failed to read the response: {}", source))]
+    ReadResponse { source: hyper::Error },
+    #[snafu(display("Invalid redirect from the server: missing or invalid location"))]
+    InvalidRedirect,
+    #[snafu(display("Server responded with an error: {}: {}", status, body))]
+    UnexpectedStatus { status: StatusCode, body: String },
+    #[snafu(display("Failed to parse the result of the Stream Load: {}", source))]
+    ParseResult { source: serde_json::Error },
+    #[snafu(display("Stream Load {} failed with status {:?}: {}", label, status, message))]
+    LoadFailed {
+        label: String,
+        status: String,
+        message: String,
+    },
+    #[snafu(display("Stream Load {} is still running from a previous attempt", label))]
+    LoadRunning { label: String },
+}
+
+/// The result of a Stream Load, as returned by the backend that ran it.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct StreamLoadResult {
+    status: String,
+    #[serde(default)]
+    message: String,
+    existing_job_status: Option<String>,
+    #[serde(rename = "ErrorURL")]
+    error_url: Option<String>,
+}
+
+impl StreamLoadResult {
+    fn into_result(self, label: String) -> Result<(), DorisError> {
+        match self.status.as_str() {
+            // A load whose publication timed out was committed, and becomes visible later on.
+            "Success" | "Publish Timeout" => Ok(()),
+            // The load was retried after an attempt that loaded the batch without its response
+            // being received.
+            "Label Already Exists" => match self.existing_job_status.as_deref() {
+                Some("FINISHED" | "VISIBLE" | "COMMITTED") => Ok(()),
+                _ => Err(DorisError::LoadRunning { label }),
+            },
+            _ => {
+                let message = match self.error_url {
+                    Some(error_url) => format!("{} (rejected rows: {})", self.message, error_url),
+                    None => self.message,
+                };
+                Err(DorisError::LoadFailed {
+                    label,
+                    status: self.status,
+                    message,
+                })
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(super) struct DorisRetryLogic;
+
+impl RetryLogic for DorisRetryLogic {
+    type Error = DorisError;
+    type Response = DorisResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        match error {
+            DorisError::SendRequest { .. }
+            | DorisError::ReadResponse { .. }
+            | DorisError::LoadRunning { .. } => true,
+            DorisError::UnexpectedStatus { status, .. } => {
+                status.is_server_error()
+                    || *status == StatusCode::TOO_MANY_REQUESTS
+                    || *status == StatusCode::REQUEST_TIMEOUT
+            }
+            // Failed loads are mostly refused because of the data, such as rows that don't
+            // match the columns of the table.
+            DorisError::InvalidUri { .. }
+            | DorisError::BuildRequest { .. }
+            | DorisError::InvalidRedirect
+            | DorisError::ParseResult { .. }
+            | DorisError::LoadFailed { .. } => false,
+        }
+    }
+}
+
+pub(super) struct DorisResponse {
+    events_byte_size: GroupedCountByteSize,
+}
+
+impl DriverResponse for DorisResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> &GroupedCountByteSize {
+        &self.events_byte_size
+    }
+}
+
+#[derive(Clone)]
+pub(super) struct DorisService {
+    pub(super) client: HttpClient,
+    pub(super) endpoint: Uri,
+    pub(super) auth: Option<Auth>,
+    /// The Stream Load headers sent with each load, including those of the format.
+    pub(super) headers: Vec<(HeaderName, HeaderValue)>,
+}
+
+impl DorisService {
+    fn stream_load_uri(&self, key: &PartitionKey) -> Result<Uri, DorisError> {
+        let mut uri = self.endpoint.to_string();
+        if !uri.ends_with('/') {
+            uri.push('/');
+        }
+        uri.push_str(&format!(
+            "api/{}/{}/_stream_load",
+            utf8_percent_encode(&key.database, NON_ALPHANUMERIC),
+            utf8_percent_encode(&key.table, NON_ALPHANUMERIC),
+        ));
+        uri.parse().context(InvalidUriSnafu)
+    }
+
+    fn build_request(
+        &self,
+        uri: &Uri,
+        request: &DorisRequest,
+    ) -> Result<Request<Body>, DorisError> {
+        let mut builder = Request::put(uri)
+            .header(EXPECT, "100-continue")
+            .header("label", &request.label)
+            .header(CONTENT_LENGTH, request.payload.len());
+        for (name, value) in &self.headers {
+            builder = builder.header(name, value);
+        }
+        if let Some(auth) = &self.auth {
+            builder = auth.apply_builder(builder);
+        }
+
+        builder
+            .body(Body::from(request.payload.clone()))
+            .context(BuildRequestSnafu)
+    }
+}
+
+impl Service<DorisRequest> for DorisService {
+    type Response = DorisResponse;
+    type Error = DorisError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, mut request: DorisRequest) -> Self::Future {
+        let service = self.clone();
+        let metadata = std::mem::take(request.metadata_mut());
+
+        Box::pin(async move {
+            let mut uri = service.stream_load_uri(&request.key)?;
+            let mut redirects = 0;
+            let response = loop {
+                let response = service
+                    .client
+                    .send(service.build_request(&uri, &request)?)
+                    .await
+                    .context(SendRequestSnafu)?;
+
+                if !response.status().is_redirection() || redirects == MAX_REDIRECTS {
+                    break response;
+                }
+                uri = redirect_location(&uri, &response).ok_or(DorisError::InvalidRedirect)?;
+                redirects += 1;
+            };
+
+            let status = response.status();
+            let body = hyper::body::to_bytes(response.into_body())
+                .await
+                .context(ReadResponseSnafu)?;
+            if !status.is_success() {
+                return Err(DorisError::UnexpectedStatus {
+                    status,
+                    body: String::from_utf8_lossy(&body).into_owned(),
+                });
+            }
+
+            serde_json::from_slice::<StreamLoadResult>(&body)
+                .context(ParseResultSnafu)?
+                .into_result(request.label)?;
+
+            let (protocol, endpoint) = protocol_endpoint(uri);
+            emit!(EndpointBytesSent {
+                byte_size: request.payload.len(),
+                protocol: &protocol,
+                endpoint: &endpoint,
+            });
+
+            Ok(DorisResponse {
+                events_byte_size: metadata.into_events_estimated_json_encoded_byte_size(),
+            })
+        })
+    }
+}
+
+/// The location that a response redirects to, resolved against the URI of the request.
+fn redirect_location(uri: &Uri, response: &Response<Body>) -> Option<Uri> {
+    let location = response.headers().get(LOCATION)?.to_str().ok()?;
+    let location = location.parse::<Uri>().ok()?;
+    if location.authority().is_some() {
+        return Some(location);
+    }
+
+    let mut parts = uri.clone().into_parts();
+    parts.path_and_query = location.into_parts().path_and_query;
+    Uri::from_parts(parts).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(json: &str) -> Result<(), DorisError> {
+        serde_json::from_str::<StreamLoadResult>(json)
+            .unwrap()
+            .into_result("vector_label".to_string())
+    }
+
+    #[test]
+    fn interprets_stream_load_results() {
+        assert!(
+            result(r#"{"Label": "vector_label", "Status": "Success", "Message": "OK"}"#).is_ok()
+        );
+        assert!(result(r#"{"Status": "Publish Timeout", "Message": "publish timeout"}"#).is_ok());
+        assert!(result(
+            r#"{"Status": "Label Already Exists", "ExistingJobStatus": "FINISHED", "Message": ""}"#
+        )
+        .is_ok());
+
+        assert!(matches!(
+            result(
+                r#"{"Status": "Label Already Exists", "ExistingJobStatus": "RUNNING", "Message": ""}"#
+            ),
+            Err(DorisError::LoadRunning { .. })
+        ));
+
+        let error = result(
+            r#"{"Status": "Fail", "Message": "too many filtered rows", "ErrorURL": "http://be:8040/api/_load_error_log?file=x"}"#,
+        )
+        .unwrap_err();
+        assert!(!DorisRetryLogic.is_retriable_error(&error));
+        assert_eq!(
+            error.to_string(),
+            "Stream Load vector_label failed with status \"Fail\": too many filtered rows \
+             (rejected rows: http://be:8040/api/_load_error_log?file=x)"
+        );
+    }
+
+    #[test]
+    fn resolves_redirect_locations() {
+        let uri = "http://fe:8030/api/db/table/_stream_load".parse().unwrap();
+        let redirect = |location: &str| {
+            Response::builder()
+                .status(StatusCode::TEMPORARY_REDIRECT)
+                .header(LOCATION, location)
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        assert_eq!(
+            redirect_location(&uri, &redirect("http://be:8040/api/db/table/_stream_load?"))
+                .unwrap(),
+            "http://be:8040/api/db/table/_stream_load?"
+        );
+        assert_eq!(
+            redirect_location(&uri, &redirect("/api/db/table/_stream_load?be=1")).unwrap(),
+            "http://fe:8030/api/db/table/_stream_load?be=1"
+        );
+    }
+}
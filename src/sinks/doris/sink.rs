@@ -0,0 +1,100 @@
+//! Implementation of the `doris` sink.
+
+use super::request_builder::{DorisRequest, DorisRequestBuilder};
+use crate::sinks::prelude::*;
+
+/// The table that a batch of events is loaded into.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub(super) struct PartitionKey {
+    pub(super) database: String,
+    pub(super) table: String,
+}
+
+/// Partitions events by the table they're loaded into.
+struct KeyPartitioner {
+    database: Template,
+    table: Template,
+}
+
+impl KeyPartitioner {
+    fn render(template: &Template, item: &Event, field: &'static str) -> Option<String> {
+        template
+            .render_string(item)
+            .map_err(|error| {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some(field),
+                    drop_event: true,
+                });
+            })
+            .ok()
+    }
+}
+
+impl Partitioner for KeyPartitioner {
+    type Item = Event;
+    type Key = Option<PartitionKey>;
+
+    fn partition(&self, item: &Self::Item) -> Self::Key {
+        let database = Self::render(&self.database, item, "database")?;
+        let table = Self::render(&self.table, item, "table")?;
+        Some(PartitionKey { database, table })
+    }
+}
+
+pub(super) struct DorisSink<S> {
+    pub(super) batch_settings: BatcherSettings,
+    pub(super) service: S,
+    pub(super) database: Template,
+    pub(super) table: Template,
+    pub(super) request_builder: DorisRequestBuilder,
+}
+
+impl<S> DorisSink<S>
+where
+    S: Service<DorisRequest> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: std::fmt::Debug + Into<crate::Error> + Send,
+{
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let batch_settings = self.batch_settings;
+        let partitioner = KeyPartitioner {
+            database: self.database,
+            table: self.table,
+        };
+
+        input
+            .batched_partitioned(partitioner, || batch_settings.as_byte_size_config())
+            .filter_map(|(key, batch)| async move { key.map(move |key| (key, batch)) })
+            .request_builder(
+                default_request_builder_concurrency_limit(),
+                self.request_builder,
+            )
+            .filter_map(|request| async {
+                match request {
+                    Err(error) => {
+                        emit!(SinkRequestBuildError { error });
+                        None
+                    }
+                    Ok(req) => Some(req),
+                }
+            })
+            .into_driver(self.service)
+            .run()
+            .await
+    }
+}
+
+#[async_trait::async_trait]
+impl<S> StreamSink<Event> for DorisSink<S>
+where
+    S: Service<DorisRequest> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: std::fmt::Debug + Into<crate::Error> + Send,
+{
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
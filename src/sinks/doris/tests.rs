@@ -0,0 +1,178 @@
+use std::net::SocketAddr;
+
+use futures::{channel::mpsc, StreamExt};
+use http::{header::LOCATION, Response, StatusCode};
+use hyper::Body;
+use indoc::indoc;
+use serde_json::json;
+use stream_cancel::Trigger;
+use vector_lib::event::{BatchNotifier, BatchStatus};
+
+use super::DorisConfig;
+use crate::{
+    config::{SinkConfig, SinkContext},
+    event::{Event, LogEvent},
+    sinks::util::test::build_test_server_generic,
+    test_util::{
+        components::{
+            run_and_assert_sink_compliance, run_and_assert_sink_error, COMPONENT_ERROR_TAGS,
+            HTTP_SINK_TAGS,
+        },
+        next_addr, random_events_with_stream,
+    },
+};
+
+const SUCCESS: &str = r#"{"Label": "vector", "Status": "Success", "Message": "OK"}"#;
+
+/// Runs a backend that answers Stream Loads with the given result.
+fn backend(
+    result: &'static str,
+) -> (
+    SocketAddr,
+    mpsc::Receiver<(http::request::Parts, bytes::Bytes)>,
+    Trigger,
+) {
+    let addr = next_addr();
+    let (rx, trigger, server) =
+        build_test_server_generic(addr, move || Response::new(Body::from(result)));
+    tokio::spawn(server);
+    (addr, rx, trigger)
+}
+
+fn event(service: &str, message: &str) -> Event {
+    let mut log = LogEvent::from(message);
+    log.insert("host", "db-1");
+    log.insert("service", service);
+    log.into()
+}
+
+#[tokio::test]
+async fn loads_through_the_redirect_of_the_frontend() {
+    let (backend_addr, mut rx, _trigger) = backend(SUCCESS);
+    let frontend_addr = next_addr();
+    let (_rx, _frontend_trigger, frontend) = build_test_server_generic(frontend_addr, move || {
+        Response::builder()
+            .status(StatusCode::TEMPORARY_REDIRECT)
+            .header(
+                LOCATION,
+                format!("http://{}/api/mydatabase/logs/_stream_load?", backend_addr),
+            )
+            .body(Body::empty())
+            .unwrap()
+    });
+    tokio::spawn(frontend);
+
+    let config: DorisConfig = toml::from_str(&format!(
+        indoc! {r#"
+            endpoint = "http://{}"
+            database = "mydatabase"
+            table = "logs"
+            auth.strategy = "basic"
+            auth.user = "root"
+            auth.password = ""
+            encoding.only_fields = ["host", "message"]
+        "#},
+        frontend_addr
+    ))
+    .unwrap();
+    let (sink, _healthcheck) = config.build(SinkContext::default()).await.unwrap();
+
+    let events = vec![event("web", "disk full"), event("web", "out of memory")];
+    run_and_assert_sink_compliance(sink, futures::stream::iter(events), &HTTP_SINK_TAGS).await;
+
+    let (parts, body) = rx.next().await.unwrap();
+    assert_eq!(parts.method, "PUT");
+    assert_eq!(parts.uri.path(), "/api/mydatabase/logs/_stream_load");
+    assert!(parts.headers["label"]
+        .to_str()
+        .unwrap()
+        .starts_with("vector_"));
+    assert_eq!(parts.headers["format"], "json");
+    assert_eq!(parts.headers["strip_outer_array"], "true");
+    assert_eq!(parts.headers["expect"], "100-continue");
+    assert_eq!(parts.headers["authorization"], "Basic cm9vdDo=");
+    assert_eq!(
+        serde_json::from_slice::<serde_json::Value>(&body).unwrap(),
+        json!([
+            {"host": "db-1", "message": "disk full"},
+            {"host": "db-1", "message": "out of memory"},
+        ])
+    );
+}
+
+#[tokio::test]
+async fn routes_events_to_their_table() {
+    let (addr, rx, _trigger) = backend(SUCCESS);
+
+    let config: DorisConfig = toml::from_str(&format!(
+        indoc! {r#"
+            endpoint = "http://{}"
+            database = "mydatabase"
+            table = "logs_{{{{ service }}}}"
+            headers.columns = "host, message"
+            encoding.codec = "csv"
+            encoding.csv.fields = ["host", "message"]
+        "#},
+        addr
+    ))
+    .unwrap();
+    let (sink, _healthcheck) = config.build(SinkContext::default()).await.unwrap();
+
+    let events = vec![
+        event("web", "disk full"),
+        event("api", "out of memory"),
+        event("web", "disk still full"),
+    ];
+    run_and_assert_sink_compliance(sink, futures::stream::iter(events), &HTTP_SINK_TAGS).await;
+
+    let mut loads = rx
+        .take(2)
+        .map(|(parts, body)| {
+            assert_eq!(parts.headers["format"], "csv");
+            assert_eq!(parts.headers["column_separator"], ",");
+            assert_eq!(parts.headers["columns"], "host, message");
+            (
+                parts.uri.path().to_string(),
+                String::from_utf8(body.to_vec()).unwrap(),
+            )
+        })
+        .collect::<Vec<_>>()
+        .await;
+    loads.sort();
+
+    assert_eq!(
+        loads,
+        [
+            (
+                "/api/mydatabase/logs_api/_stream_load".to_string(),
+                "db-1,out of memory\n".to_string()
+            ),
+            (
+                "/api/mydatabase/logs_web/_stream_load".to_string(),
+                "db-1,disk full\ndb-1,disk still full\n".to_string()
+            ),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn rejects_events_of_failed_loads() {
+    let (addr, _rx, _trigger) =
+        backend(r#"{"Label": "vector", "Status": "Fail", "Message": "too many filtered rows"}"#);
+
+    let config: DorisConfig = toml::from_str(&format!(
+        indoc! {r#"
+            endpoint = "http://{}"
+            database = "mydatabase"
+            table = "logs"
+        "#},
+        addr
+    ))
+    .unwrap();
+    let (sink, _healthcheck) = config.build(SinkContext::default()).await.unwrap();
+
+    let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+    let (_input, events) = random_events_with_stream(100, 10, Some(batch));
+    run_and_assert_sink_error(sink, events, &COMPONENT_ERROR_TAGS).await;
+    assert_eq!(receiver.try_recv(), Ok(BatchStatus::Rejected));
+}
@@ -16,7 +16,8 @@ use crate::{
     http::{HttpClient, MaybeAuth},
     sinks::{
         elasticsearch::{
-            ElasticsearchAuthConfig, ElasticsearchCommonMode, ElasticsearchConfig, ParseError,
+            ElasticsearchAuthConfig, ElasticsearchCommonMode, ElasticsearchConfig,
+            OpenSearchServiceType, ParseError,
         },
         util::auth::Auth,
         util::{http::RequestConfig, UriSerde},
@@ -37,6 +38,7 @@ pub struct ElasticsearchCommon {
     pub request: RequestConfig,
     pub query_params: HashMap<String, String>,
     pub metric_to_log: MetricToLog,
+    pub service_type: OpenSearchServiceType,
 }
 
 impl ElasticsearchCommon {
@@ -87,6 +89,30 @@ impl ElasticsearchCommon {
             None => None,
         };
 
+        let service_type = config.opensearch_service_type;
+        if service_type == OpenSearchServiceType::Serverless {
+            #[cfg(feature = "aws-core")]
+            let aws_auth = matches!(auth, Some(Auth::Aws { .. }));
+            #[cfg(not(feature = "aws-core"))]
+            let aws_auth = false;
+            if !aws_auth {
+                return Err(ParseError::ServerlessRequiresAwsAuth.into());
+            }
+            if !matches!(
+                config.api_version,
+                ElasticsearchApiVersion::Auto | ElasticsearchApiVersion::V8
+            ) {
+                return Err(ParseError::ServerlessApiVersion.into());
+            }
+            if config
+                .pipeline
+                .as_ref()
+                .is_some_and(|pipeline| !pipeline.is_empty())
+            {
+                return Err(ParseError::ServerlessPipeline.into());
+            }
+        }
+
         let base_url = uri.uri.to_string().trim_end_matches('/').to_owned();
 
         let mode = config.common_mode()?;
@@ -122,6 +148,12 @@ impl ElasticsearchCommon {
             }
         }
 
+        if let ElasticsearchCommonMode::DataStream(data_stream) = &mode {
+            if !data_stream.auto_create {
+                query_params.insert("require_data_stream".into(), "true".into());
+            }
+        }
+
         let bulk_url = {
             let mut query = url::form_urlencoded::Serializer::new(String::new());
             for (p, v) in &query_params {
@@ -150,8 +182,23 @@ impl ElasticsearchCommon {
                 ElasticsearchApiVersion::V6 => 6,
                 ElasticsearchApiVersion::V7 => 7,
                 ElasticsearchApiVersion::V8 => 8,
+                // OpenSearch Serverless doesn't expose its version, and implements the API of
+                // Elasticsearch 8.x without document types.
+                ElasticsearchApiVersion::Auto
+                    if service_type == OpenSearchServiceType::Serverless =>
+                {
+                    8
+                }
                 ElasticsearchApiVersion::Auto => {
-                    match get_version(&base_url, &auth, &request, &tls_settings, proxy_config).await
+                    match get_version(
+                        &base_url,
+                        &auth,
+                        &service_type,
+                        &request,
+                        &tls_settings,
+                        proxy_config,
+                    )
+                    .await
                     {
                         Ok(version) => {
                             debug!(message = "Auto-detected Elasticsearch API version.", %version);
@@ -210,6 +257,7 @@ impl ElasticsearchCommon {
             request,
             tls_settings,
             metric_to_log,
+            service_type,
         })
     }
 
@@ -250,9 +298,15 @@ impl ElasticsearchCommon {
     }
 
     pub async fn healthcheck(self, client: HttpClient) -> crate::Result<()> {
+        if self.service_type == OpenSearchServiceType::Serverless {
+            warn!(message = "Amazon OpenSearch Serverless doesn't support healthchecks. Skipping healthcheck.");
+            return Ok(());
+        }
+
         match get(
             &self.base_url,
             &self.auth,
+            &self.service_type,
             &self.request,
             client,
             "/_cluster/health",
@@ -268,16 +322,18 @@ impl ElasticsearchCommon {
 
 #[cfg(feature = "aws-core")]
 pub async fn sign_request(
+    service_type: &OpenSearchServiceType,
     request: &mut http::Request<Bytes>,
     credentials_provider: &aws_credential_types::provider::SharedCredentialsProvider,
     region: &Option<aws_types::region::Region>,
 ) -> crate::Result<()> {
-    crate::aws::sign_request("es", request, credentials_provider, region).await
+    crate::aws::sign_request(service_type.as_str(), request, credentials_provider, region).await
 }
 
 async fn get_version(
     base_url: &str,
     auth: &Option<Auth>,
+    service_type: &OpenSearchServiceType,
     request: &RequestConfig,
     tls_settings: &TlsSettings,
     proxy_config: &ProxyConfig,
@@ -292,7 +348,7 @@ async fn get_version(
     }
 
     let client = HttpClient::new(tls_settings.clone(), proxy_config)?;
-    let response = get(base_url, auth, request, client, "/")
+    let response = get(base_url, auth, service_type, request, client, "/")
         .await
         .map_err(|error| format!("Failed to get Elasticsearch API version: {}", error))?;
 
@@ -316,6 +372,8 @@ async fn get_version(
 async fn get(
     base_url: &str,
     auth: &Option<Auth>,
+    #[cfg_attr(not(feature = "aws-core"), allow(unused_variables))]
+    service_type: &OpenSearchServiceType,
     request: &RequestConfig,
     client: HttpClient,
     path: &str,
@@ -338,7 +396,7 @@ async fn get(
                 region,
             } => {
                 let region = region.clone();
-                sign_request(&mut request, provider, &Some(region)).await?;
+                sign_request(service_type, &mut request, provider, &Some(region)).await?;
             }
         }
     }
@@ -19,7 +19,7 @@ use crate::{
             service::{ElasticsearchService, HttpRequestBuilder},
             sink::ElasticsearchSink,
             ElasticsearchApiVersion, ElasticsearchAuthConfig, ElasticsearchCommon,
-            ElasticsearchCommonMode, ElasticsearchMode, VersionType,
+            ElasticsearchCommonMode, ElasticsearchMode, OpenSearchServiceType, VersionType,
         },
         util::{
             http::RequestConfig, service::HealthConfig, BatchConfig, Compression,
@@ -152,6 +152,13 @@ pub struct ElasticsearchConfig {
     #[cfg(feature = "aws-core")]
     pub aws: Option<crate::aws::RegionOrEndpoint>,
 
+    /// The type of Amazon OpenSearch service that the endpoints belong to.
+    ///
+    /// An OpenSearch Serverless collection requires the `aws` authentication strategy.
+    #[serde(default)]
+    #[configurable(derived)]
+    pub opensearch_service_type: OpenSearchServiceType,
+
     #[serde(default)]
     #[configurable(derived)]
     pub tls: Option<TlsConfig>,
@@ -214,6 +221,7 @@ impl Default for ElasticsearchConfig {
             query: None,
             #[cfg(feature = "aws-core")]
             aws: None,
+            opensearch_service_type: Default::default(),
             tls: None,
             endpoint_health: None,
             bulk: BulkConfig::default(), // the default mode is Bulk
@@ -340,6 +348,14 @@ pub struct DataStreamConfig {
     /// This ensures that fields match the name of the data stream that is receiving events.
     #[serde(default = "DataStreamConfig::default_sync_fields")]
     pub sync_fields: bool,
+
+    /// Whether or not bulk requests can create the data streams that don't exist yet.
+    ///
+    /// Elasticsearch creates a data stream when it receives a document for a data stream that
+    /// doesn't exist and matches an index template with data streams enabled, but otherwise
+    /// creates a regular index. If disabled, such documents are rejected instead.
+    #[serde(default = "crate::serde::default_true")]
+    pub auto_create: bool,
 }
 
 impl Default for DataStreamConfig {
@@ -350,6 +366,7 @@ impl Default for DataStreamConfig {
             namespace: Self::default_namespace(),
             auto_routing: Self::default_auto_routing(),
             sync_fields: Self::default_sync_fields(),
+            auto_create: true,
         }
     }
 }
@@ -502,7 +519,11 @@ impl SinkConfig for ElasticsearchConfig {
 
         let client = HttpClient::new(common.tls_settings.clone(), cx.proxy())?;
 
-        let request_limits = self.request.tower.into_settings();
+        let request_limits = self
+            .request
+            .tower
+            .into_settings()
+            .with_request_limits(cx.request_limits.clone());
 
         let health_config = self.endpoint_health.clone().unwrap_or_default();
 
@@ -552,6 +573,10 @@ impl SinkConfig for ElasticsearchConfig {
     fn acknowledgements(&self) -> &AcknowledgementsConfig {
         &self.acknowledgements
     }
+
+    fn supports_request_limits(&self) -> bool {
+        true
+    }
 }
 
 #[cfg(test)]
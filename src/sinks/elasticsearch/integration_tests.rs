@@ -66,7 +66,15 @@ impl ElasticsearchCommon {
                 Auth::Aws {
                     credentials_provider: provider,
                     region,
-                } => sign_request(&mut request, provider, &Some(region.clone())).await?,
+                } => {
+                    sign_request(
+                        &self.service_type,
+                        &mut request,
+                        provider,
+                        &Some(region.clone()),
+                    )
+                    .await?
+                }
             }
         }
 
@@ -155,6 +155,38 @@ impl TryFrom<&str> for VersionType {
     }
 }
 
+/// Amazon OpenSearch service types.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub enum OpenSearchServiceType {
+    /// Elasticsearch or an Amazon OpenSearch Service managed domain.
+    Managed,
+
+    /// An Amazon OpenSearch Serverless collection.
+    ///
+    /// Requests are signed for the `aoss` service, and the API version is not probed, as
+    /// OpenSearch Serverless doesn't expose it.
+    Serverless,
+}
+
+impl Default for OpenSearchServiceType {
+    fn default() -> Self {
+        Self::Managed
+    }
+}
+
+#[allow(clippy::trivially_copy_pass_by_ref)]
+impl OpenSearchServiceType {
+    /// The name of the service that AWS requests are signed for.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Managed => "es",
+            Self::Serverless => "aoss",
+        }
+    }
+}
+
 impl_generate_config_from_default!(ElasticsearchConfig);
 
 #[derive(Debug, Clone)]
@@ -318,4 +350,10 @@ pub enum ParseError {
     ExternalVersioningWithoutDocumentID,
     #[snafu(display("Your version field will be ignored because you use internal versioning"))]
     ExternalVersionIgnoredWithInternalVersioning,
+    #[snafu(display("Amazon OpenSearch Serverless requires AWS authentication"))]
+    ServerlessRequiresAwsAuth,
+    #[snafu(display("Amazon OpenSearch Serverless requires `api_version` to be `auto` or `v8`"))]
+    ServerlessApiVersion,
+    #[snafu(display("Amazon OpenSearch Serverless doesn't support ingest pipelines"))]
+    ServerlessPipeline,
 }
@@ -16,7 +16,7 @@ use vector_lib::{
     request_metadata::{GroupedCountByteSize, MetaDescriptive, RequestMetadata},
 };
 
-use super::{ElasticsearchCommon, ElasticsearchConfig};
+use super::{ElasticsearchCommon, ElasticsearchConfig, OpenSearchServiceType};
 use crate::{
     event::{EventFinalizers, EventStatus, Finalizable},
     http::HttpClient,
@@ -97,6 +97,7 @@ pub struct HttpRequestBuilder {
     pub auth: Option<Auth>,
     pub compression: Compression,
     pub http_request_config: RequestConfig,
+    pub service_type: OpenSearchServiceType,
 }
 
 impl HttpRequestBuilder {
@@ -107,6 +108,7 @@ impl HttpRequestBuilder {
             auth: common.auth.clone(),
             query_params: common.query_params.clone(),
             compression: config.compression,
+            service_type: common.service_type,
         }
     }
 
@@ -145,6 +147,7 @@ impl HttpRequestBuilder {
                     region,
                 } => {
                     crate::sinks::elasticsearch::sign_request(
+                        &self.service_type,
                         &mut request,
                         provider,
                         &Some(region.clone()),
@@ -8,7 +8,8 @@ use crate::{
     sinks::{
         elasticsearch::{
             sink::process_log, BulkAction, BulkConfig, DataStreamConfig, ElasticsearchApiVersion,
-            ElasticsearchCommon, ElasticsearchConfig, ElasticsearchMode, VersionType,
+            ElasticsearchCommon, ElasticsearchConfig, ElasticsearchMode, OpenSearchServiceType,
+            VersionType,
         },
         util::encoding::Encoder,
     },
@@ -648,3 +649,75 @@ async fn datastream_index_name() {
         assert_eq!(processed_event.index, test_case.want, "{test_case:?}");
     }
 }
+
+#[tokio::test]
+async fn datastream_mode_without_auto_create_requires_data_stream() {
+    let config = ElasticsearchConfig {
+        endpoints: vec![String::from("https://example.com")],
+        mode: ElasticsearchMode::DataStream,
+        api_version: ElasticsearchApiVersion::V8,
+        data_stream: Some(DataStreamConfig {
+            auto_create: false,
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let es = ElasticsearchCommon::parse_single(&config).await.unwrap();
+
+    assert!(es
+        .bulk_uri
+        .query()
+        .unwrap()
+        .contains("require_data_stream=true"));
+}
+
+#[tokio::test]
+async fn serverless_requires_aws_auth() {
+    let config = ElasticsearchConfig {
+        endpoints: vec![String::from("https://example.com")],
+        opensearch_service_type: OpenSearchServiceType::Serverless,
+        ..Default::default()
+    };
+    let error = ElasticsearchCommon::parse_single(&config)
+        .await
+        .unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "Amazon OpenSearch Serverless requires AWS authentication"
+    );
+}
+
+#[cfg(feature = "aws-core")]
+#[tokio::test]
+async fn serverless_skips_version_probe() {
+    let config = ElasticsearchConfig {
+        endpoints: vec![String::from("https://example.aoss.amazonaws.com")],
+        opensearch_service_type: OpenSearchServiceType::Serverless,
+        auth: Some(crate::sinks::elasticsearch::ElasticsearchAuthConfig::Aws(
+            crate::aws::AwsAuthentication::test_auth(),
+        )),
+        aws: Some(crate::aws::RegionOrEndpoint::with_region(
+            "us-east-1".to_string(),
+        )),
+        ..Default::default()
+    };
+    let es = ElasticsearchCommon::parse_single(&config).await.unwrap();
+
+    assert_eq!(es.service_type.as_str(), "aoss");
+    assert!(es.request_builder.encoder.suppress_type_name);
+
+    let pipeline = ElasticsearchConfig {
+        pipeline: Some("pipeline-name".to_string()),
+        ..config.clone()
+    };
+    assert!(ElasticsearchCommon::parse_single(&pipeline).await.is_err());
+
+    let api_version = ElasticsearchConfig {
+        api_version: ElasticsearchApiVersion::V7,
+        ..config
+    };
+    assert!(ElasticsearchCommon::parse_single(&api_version)
+        .await
+        .is_err());
+}
@@ -1,5 +1,6 @@
 use std::convert::TryFrom;
-use std::time::{Duration, Instant};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
 
 use async_compression::tokio::write::{GzipEncoder, ZstdEncoder};
 use async_trait::async_trait;
@@ -13,6 +14,8 @@ use serde_with::serde_as;
 use tokio::{
     fs::{self, File},
     io::AsyncWriteExt,
+    sync::Mutex,
+    task::JoinSet,
 };
 use tokio_util::codec::Encoder as _;
 use vector_lib::codecs::{
@@ -22,13 +25,17 @@ use vector_lib::codecs::{
 use vector_lib::configurable::configurable_component;
 use vector_lib::{
     internal_event::{CountByteSize, EventsSent, InternalEventHandle as _, Output, Registered},
+    json_size::JsonSize,
     EstimatedJsonEncodedSizeOf, TimeZone,
 };
 
 use crate::{
-    codecs::{Encoder, EncodingConfigWithFraming, SinkType, Transformer},
+    codecs::{
+        BatchSerializer, BatchSerializerConfig, Encoder, EncodingConfigWithFraming, SinkType,
+        Transformer,
+    },
     config::{AcknowledgementsConfig, DataType, GenerateConfig, Input, SinkConfig, SinkContext},
-    event::{Event, EventStatus, Finalizable},
+    event::{Event, EventFinalizers, EventStatus, Finalizable},
     expiring_hash_map::ExpiringHashMap,
     internal_events::{
         FileBytesSent, FileInternalMetricsConfig, FileIoError, FileOpen, TemplateRenderingError,
@@ -38,8 +45,10 @@ use crate::{
 };
 
 mod bytes_path;
+mod rotation;
 
 use bytes_path::BytesPath;
+pub use rotation::RotationConfig;
 
 /// Configuration for the `file` sink.
 #[serde_as]
@@ -74,6 +83,25 @@ pub struct FileSinkConfig {
     #[serde(default, skip_serializing_if = "crate::serde::is_default")]
     pub compression: Compression,
 
+    /// Configures encoding all the events of a file together, for the formats that can't be
+    /// appended to.
+    ///
+    /// When set, this is used instead of the `codec` and `framing` of `encoding`, and files are
+    /// not compressed with `compression`, as the batch codecs compress their own data. The events
+    /// of each path are kept in memory and written to a new file once they have been idle for
+    /// `idle_timeout_secs`, once they are due for rotation according to `rotation`, or when Vector
+    /// stops. The file already at the path, if any, is rotated first.
+    ///
+    /// The size compared to `rotation.max_bytes` is the estimated size of the events kept in
+    /// memory, so setting it bounds the memory used for each path.
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch_encoding: Option<BatchSerializerConfig>,
+
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "crate::serde::is_default")]
+    pub rotation: RotationConfig,
+
     #[configurable(derived)]
     #[serde(
         default,
@@ -98,6 +126,8 @@ impl GenerateConfig for FileSinkConfig {
             idle_timeout: default_idle_timeout(),
             encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
             compression: Default::default(),
+            batch_encoding: None,
+            rotation: Default::default(),
             acknowledgements: Default::default(),
             timezone: Default::default(),
             internal_metrics: Default::default(),
@@ -179,6 +209,32 @@ impl OutFile {
     }
 }
 
+/// A file opened by the sink, along with what is needed to know when it's rotated.
+struct OpenFile {
+    file: OutFile,
+    bytes: u64,
+    created: SystemTime,
+}
+
+/// The events kept in memory for a path until they are written together with the batch encoding.
+struct Batch {
+    events: Vec<Event>,
+    finalizers: EventFinalizers,
+    byte_size: JsonSize,
+    created: SystemTime,
+}
+
+impl Batch {
+    fn new() -> Self {
+        Self {
+            events: Vec::new(),
+            finalizers: EventFinalizers::default(),
+            byte_size: JsonSize::zero(),
+            created: SystemTime::now(),
+        }
+    }
+}
+
 #[async_trait::async_trait]
 #[typetag::serde(name = "file")]
 impl SinkConfig for FileSinkConfig {
@@ -194,7 +250,11 @@ impl SinkConfig for FileSinkConfig {
     }
 
     fn input(&self) -> Input {
-        Input::new(self.encoding.config().1.input_type() & DataType::Log)
+        if self.batch_encoding.is_some() {
+            Input::log()
+        } else {
+            Input::new(self.encoding.config().1.input_type() & DataType::Log)
+        }
     }
 
     fn acknowledgements(&self) -> &AcknowledgementsConfig {
@@ -206,9 +266,14 @@ pub struct FileSink {
     path: Template,
     transformer: Transformer,
     encoder: Encoder<Framer>,
+    batch_serializer: Option<BatchSerializer>,
     idle_timeout: Duration,
-    files: ExpiringHashMap<Bytes, OutFile>,
+    files: ExpiringHashMap<Bytes, OpenFile>,
+    batches: ExpiringHashMap<Bytes, Batch>,
     compression: Compression,
+    rotation: RotationConfig,
+    rotations: JoinSet<()>,
+    rotations_lock: Arc<Mutex<()>>,
     events_sent: Registered<EventsSent>,
     include_file_metric_tag: bool,
 }
@@ -218,6 +283,15 @@ impl FileSink {
         let transformer = config.encoding.transformer();
         let (framer, serializer) = config.encoding.build(SinkType::StreamBased)?;
         let encoder = Encoder::<Framer>::new(framer, serializer);
+        let batch_serializer = config
+            .batch_encoding
+            .as_ref()
+            .map(BatchSerializerConfig::build)
+            .transpose()?;
+        if batch_serializer.is_some() && config.compression != Compression::None {
+            return Err("`compression` can't be used together with `batch_encoding`".into());
+        }
+        config.rotation.validate(config.compression)?;
 
         let offset = config
             .timezone
@@ -228,9 +302,14 @@ impl FileSink {
             path: config.path.clone().with_tz_offset(offset),
             transformer,
             encoder,
+            batch_serializer,
             idle_timeout: config.idle_timeout,
             files: ExpiringHashMap::default(),
+            batches: ExpiringHashMap::default(),
             compression: config.compression,
+            rotation: config.rotation,
+            rotations: JoinSet::new(),
+            rotations_lock: Default::default(),
             events_sent: register!(EventsSent::from(Output(None))),
             include_file_metric_tag: config.internal_metrics.include_file_tag,
         })
@@ -270,10 +349,22 @@ impl FileSink {
                             // If we got `None` - terminate the processing.
                             debug!(message = "Receiver exhausted, terminating the processing loop.");
 
+                            // Write the events kept for the batch encoding.
+                            let paths = self
+                                .batches
+                                .iter_mut()
+                                .map(|(path, _)| path.clone())
+                                .collect::<Vec<_>>();
+                            for path in paths {
+                                if let Some((batch, _)) = self.batches.remove(&path) {
+                                    self.write_batch(&path, batch).await;
+                                }
+                            }
+
                             // Close all the open files.
                             debug!(message = "Closing all the open files.");
                             for (path, file) in self.files.iter_mut() {
-                                if let Err(error) = file.close().await {
+                                if let Err(error) = file.file.close().await {
                                     emit!(FileIoError {
                                         error,
                                         code: "failed_closing_file",
@@ -290,6 +381,9 @@ impl FileSink {
                                 count: 0
                             });
 
+                            // Wait for the rotated files to be compressed and pruned.
+                            while self.rotations.join_next().await.is_some() {}
+
                             break;
                         }
                     }
@@ -302,7 +396,7 @@ impl FileSink {
                         Some((mut expired_file, path)) => {
                             // We got an expired file. All we really want is to
                             // flush and close it.
-                            if let Err(error) = expired_file.close().await {
+                            if let Err(error) = expired_file.file.close().await {
                                 emit!(FileIoError {
                                     error,
                                     code: "failed_closing_file",
@@ -318,6 +412,12 @@ impl FileSink {
                         }
                     }
                 }
+                result = self.batches.next_expired(), if !self.batches.is_empty() => {
+                    if let Some((batch, path)) = result {
+                        self.write_batch(&path, batch).await;
+                    }
+                }
+                _ = self.rotations.join_next(), if !self.rotations.is_empty() => {}
             }
         }
 
@@ -340,13 +440,18 @@ impl FileSink {
         let next_deadline = self.deadline_at();
         trace!(message = "Computed next deadline.", next_deadline = ?next_deadline, path = ?path);
 
+        if self.batch_serializer.is_some() {
+            self.buffer_event(path, event, next_deadline).await;
+            return;
+        }
+
         let file = if let Some(file) = self.files.reset_at(&path, next_deadline) {
             trace!(message = "Working with an already opened file.", path = ?path);
             file
         } else {
             trace!(message = "Opening new file.", ?path);
-            let file = match open_file(BytesPath::new(path.clone())).await {
-                Ok(file) => file,
+            let (file, bytes, created) = match open_file(BytesPath::new(path.clone())).await {
+                Ok(opened) => opened,
                 Err(error) => {
                     // We couldn't open the file for this event.
                     // Maybe other events will work though! Just log
@@ -363,7 +468,11 @@ impl FileSink {
                 }
             };
 
-            let outfile = OutFile::new(file, self.compression);
+            let outfile = OpenFile {
+                file: OutFile::new(file, self.compression),
+                bytes,
+                created,
+            };
 
             self.files.insert_at(path.clone(), outfile, next_deadline);
             emit!(FileOpen {
@@ -375,8 +484,12 @@ impl FileSink {
         trace!(message = "Writing an event to file.", path = ?path);
         let event_size = event.estimated_json_encoded_size_of();
         let finalizers = event.take_finalizers();
-        match write_event_to_file(file, event, &self.transformer, &mut self.encoder).await {
+        match write_event_to_file(&mut file.file, event, &self.transformer, &mut self.encoder).await
+        {
             Ok(byte_size) => {
+                file.bytes += byte_size as u64;
+                let rotate = self.rotation.is_due(file.bytes, file.created);
+
                 finalizers.update_status(EventStatus::Delivered);
                 self.events_sent.emit(CountByteSize(1, event_size));
                 emit!(FileBytesSent {
@@ -384,6 +497,10 @@ impl FileSink {
                     file: String::from_utf8_lossy(&path),
                     include_file_metric_tag: self.include_file_metric_tag,
                 });
+
+                if rotate {
+                    self.rotate(&path).await;
+                }
             }
             Err(error) => {
                 finalizers.update_status(EventStatus::Errored);
@@ -397,22 +514,170 @@ impl FileSink {
             }
         }
     }
+
+    /// Keeps the event in memory with the other events of its path, until they are written
+    /// together with the batch encoding.
+    async fn buffer_event(&mut self, path: Bytes, mut event: Event, deadline: Instant) {
+        self.transformer.transform(&mut event);
+        let byte_size = event.estimated_json_encoded_size_of();
+        let finalizers = event.take_finalizers();
+
+        let batch = if let Some(batch) = self.batches.reset_at(&path, deadline) {
+            batch
+        } else {
+            self.batches.insert_at(path.clone(), Batch::new(), deadline);
+            self.batches.get_mut(&path).unwrap()
+        };
+        batch.events.push(event);
+        batch.finalizers.merge(finalizers);
+        batch.byte_size += byte_size;
+
+        if self
+            .rotation
+            .is_due(batch.byte_size.get() as u64, batch.created)
+        {
+            if let Some((batch, _)) = self.batches.remove(&path) {
+                self.write_batch(&path, batch).await;
+            }
+        }
+    }
+
+    /// Encodes the events kept for the path into a new file, rotating the file already at the path
+    /// if there is one.
+    async fn write_batch(&mut self, path: &Bytes, batch: Batch) {
+        let Batch {
+            events,
+            finalizers,
+            byte_size: event_size,
+            ..
+        } = batch;
+        let count = events.len();
+
+        trace!(message = "Writing a batch of events to file.", path = ?path, count);
+        let serializer = self
+            .batch_serializer
+            .as_ref()
+            .expect("batches are only kept with a batch encoding");
+        let result = match serializer.encode(&events) {
+            Ok(payload) => {
+                drop(events);
+                self.replace_file(path, &payload)
+                    .await
+                    .map(|()| payload.len())
+            }
+            Err(error) => Err(std::io::Error::new(std::io::ErrorKind::InvalidData, error)),
+        };
+
+        match result {
+            Ok(byte_size) => {
+                finalizers.update_status(EventStatus::Delivered);
+                self.events_sent.emit(CountByteSize(count, event_size));
+                emit!(FileBytesSent {
+                    byte_size,
+                    file: String::from_utf8_lossy(path),
+                    include_file_metric_tag: self.include_file_metric_tag,
+                });
+            }
+            Err(error) => {
+                finalizers.update_status(EventStatus::Errored);
+                emit!(FileIoError {
+                    code: "failed_writing_file",
+                    message: "Failed to write the file.",
+                    error,
+                    path,
+                    dropped_events: count,
+                });
+            }
+        }
+    }
+
+    /// Writes the payload to a new file at the path, after rotating the file already there.
+    async fn replace_file(&mut self, path: &Bytes, payload: &[u8]) -> std::io::Result<()> {
+        let path = BytesPath::new(path.clone()).as_ref().to_path_buf();
+        if fs::try_exists(&path).await? {
+            let rotated = rotation::rename(&path).await?;
+            debug!(message = "Rotated file.", ?path, ?rotated);
+            self.finish_rotation(path.clone(), rotated);
+        }
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+        let mut file = File::create(&path).await?;
+        file.write_all(payload).await?;
+        file.sync_all().await
+    }
+
+    /// Closes the file at the path and renames it, so that the events written afterwards go to a
+    /// new file. The rotated file is then compressed and pruned in the background.
+    async fn rotate(&mut self, path: &Bytes) {
+        let Some((mut file, _)) = self.files.remove(path) else {
+            return;
+        };
+        emit!(FileOpen {
+            count: self.files.len()
+        });
+
+        if let Err(error) = file.file.close().await {
+            emit!(FileIoError {
+                error,
+                code: "failed_closing_file",
+                message: "Failed to close file.",
+                path,
+                dropped_events: 0,
+            });
+        }
+
+        let path = BytesPath::new(path.clone()).as_ref().to_path_buf();
+        match rotation::rename(&path).await {
+            Ok(rotated) => {
+                debug!(message = "Rotated file.", ?path, ?rotated);
+                self.finish_rotation(path, rotated);
+            }
+            Err(error) => emit!(FileIoError {
+                error,
+                code: "failed_rotating_file",
+                message: "Failed to rotate the file.",
+                path: &path,
+                dropped_events: 0,
+            }),
+        }
+    }
+
+    /// Compresses and prunes the rotated file in the background.
+    fn finish_rotation(&mut self, path: std::path::PathBuf, rotated: std::path::PathBuf) {
+        let rotation = self.rotation;
+        let lock = Arc::clone(&self.rotations_lock);
+        self.rotations.spawn(async move {
+            // Rotated files are finished one at a time, so that files aren't pruned while they're
+            // compressed.
+            let _guard = lock.lock().await;
+            rotation.finish(path, rotated).await
+        });
+    }
 }
 
-async fn open_file(path: impl AsRef<std::path::Path>) -> std::io::Result<File> {
+/// Opens the file at the path for appending, returning it along with its size and the time it was
+/// created at.
+async fn open_file(path: impl AsRef<std::path::Path>) -> std::io::Result<(File, u64, SystemTime)> {
     let parent = path.as_ref().parent();
 
     if let Some(parent) = parent {
         fs::create_dir_all(parent).await?;
     }
 
-    fs::OpenOptions::new()
+    let file = fs::OpenOptions::new()
         .read(false)
         .write(true)
         .create(true)
         .append(true)
         .open(path)
-        .await
+        .await?;
+
+    let metadata = file.metadata().await?;
+    // Not all platforms and filesystems record the creation time of files.
+    let created = metadata.created().unwrap_or_else(|_| SystemTime::now());
+    Ok((file, metadata.len(), created))
 }
 
 async fn write_event_to_file(
@@ -471,6 +736,8 @@ mod tests {
             idle_timeout: default_idle_timeout(),
             encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
             compression: Compression::None,
+            batch_encoding: None,
+            rotation: Default::default(),
             acknowledgements: Default::default(),
             timezone: Default::default(),
             internal_metrics: FileInternalMetricsConfig {
@@ -497,6 +764,8 @@ mod tests {
             idle_timeout: default_idle_timeout(),
             encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
             compression: Compression::Gzip,
+            batch_encoding: None,
+            rotation: Default::default(),
             acknowledgements: Default::default(),
             timezone: Default::default(),
             internal_metrics: FileInternalMetricsConfig {
@@ -523,6 +792,8 @@ mod tests {
             idle_timeout: default_idle_timeout(),
             encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
             compression: Compression::Zstd,
+            batch_encoding: None,
+            rotation: Default::default(),
             acknowledgements: Default::default(),
             timezone: Default::default(),
             internal_metrics: FileInternalMetricsConfig {
@@ -554,6 +825,8 @@ mod tests {
             idle_timeout: default_idle_timeout(),
             encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
             compression: Compression::None,
+            batch_encoding: None,
+            rotation: Default::default(),
             acknowledgements: Default::default(),
             timezone: Default::default(),
             internal_metrics: FileInternalMetricsConfig {
@@ -625,6 +898,131 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn rotation() {
+        let directory = temp_dir();
+        let path = directory.join("vector.log");
+
+        let config = FileSinkConfig {
+            path: path.to_str().unwrap().try_into().unwrap(),
+            idle_timeout: default_idle_timeout(),
+            encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
+            compression: Compression::None,
+            batch_encoding: None,
+            rotation: RotationConfig {
+                // Each line is 65 bytes long with its newline, so files are rotated every two lines.
+                max_bytes: Some(130),
+                compression: Compression::Gzip,
+                keep_files: Some(2),
+                ..Default::default()
+            },
+            acknowledgements: Default::default(),
+            timezone: Default::default(),
+            internal_metrics: FileInternalMetricsConfig {
+                include_file_tag: true,
+            },
+        };
+
+        let (input, _events) = random_lines_with_stream(64, 10, None);
+
+        run_assert_log_sink(config, input.clone()).await;
+
+        let mut rotated = std::fs::read_dir(&directory)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect::<Vec<_>>();
+        rotated.sort();
+        assert_eq!(rotated.len(), 2);
+        assert!(rotated
+            .iter()
+            .all(|rotated| rotated.extension().unwrap() == "gz"));
+
+        let output = rotated
+            .into_iter()
+            .flat_map(lines_from_gzip_file)
+            .collect::<Vec<_>>();
+        assert_eq!(input[6..].to_vec(), output);
+    }
+
+    #[tokio::test]
+    async fn parquet_batches() {
+        let directory = temp_dir();
+        let path = directory.join("vector.parquet");
+
+        let config = FileSinkConfig {
+            path: path.to_str().unwrap().try_into().unwrap(),
+            idle_timeout: default_idle_timeout(),
+            encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
+            compression: Compression::None,
+            batch_encoding: Some(BatchSerializerConfig::Parquet {
+                parquet: Default::default(),
+            }),
+            rotation: Default::default(),
+            acknowledgements: Default::default(),
+            timezone: Default::default(),
+            internal_metrics: FileInternalMetricsConfig {
+                include_file_tag: true,
+            },
+        };
+
+        let (first, _) = random_lines_with_stream(16, 10, None);
+        run_assert_log_sink(config.clone(), first.clone()).await;
+        let (second, _) = random_lines_with_stream(16, 5, None);
+        run_assert_log_sink(config, second.clone()).await;
+
+        // The file written first is rotated when the second one replaces it.
+        let mut files = std::fs::read_dir(&directory)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect::<Vec<_>>();
+        files.sort();
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0], path);
+        assert_eq!(messages_from_parquet_file(&files[0]), second);
+        assert_eq!(messages_from_parquet_file(&files[1]), first);
+    }
+
+    #[test]
+    fn batch_encoding_without_compression() {
+        let config = FileSinkConfig {
+            path: temp_file().try_into().unwrap(),
+            idle_timeout: default_idle_timeout(),
+            encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
+            compression: Compression::Gzip,
+            batch_encoding: Some(BatchSerializerConfig::Parquet {
+                parquet: Default::default(),
+            }),
+            rotation: Default::default(),
+            acknowledgements: Default::default(),
+            timezone: Default::default(),
+            internal_metrics: Default::default(),
+        };
+
+        assert!(FileSink::new(&config, SinkContext::default()).is_err());
+    }
+
+    fn messages_from_parquet_file(path: &std::path::Path) -> Vec<String> {
+        use parquet::{
+            file::reader::{FileReader, SerializedFileReader},
+            record::Field,
+        };
+
+        let reader = SerializedFileReader::new(std::fs::File::open(path).unwrap()).unwrap();
+        reader
+            .get_row_iter(None)
+            .unwrap()
+            .map(|row| {
+                row.unwrap()
+                    .get_column_iter()
+                    .find_map(|(name, field)| match (name.as_str(), field) {
+                        ("message", Field::Str(message)) => Some(message.clone()),
+                        _ => None,
+                    })
+                    .unwrap()
+            })
+            .collect()
+    }
+
     #[tokio::test]
     async fn reopening() {
         trace_init();
@@ -636,6 +1034,8 @@ mod tests {
             idle_timeout: Duration::from_secs(1),
             encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
             compression: Compression::None,
+            batch_encoding: None,
+            rotation: Default::default(),
             acknowledgements: Default::default(),
             timezone: Default::default(),
             internal_metrics: FileInternalMetricsConfig {
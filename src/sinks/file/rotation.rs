@@ -0,0 +1,262 @@
+//! Rotation of the files written by the `file` sink.
+
+use std::{
+    ffi::{OsStr, OsString},
+    io,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use chrono::Utc;
+use tokio::{
+    fs::{self, File},
+    io::AsyncReadExt,
+};
+use vector_lib::configurable::configurable_component;
+
+use super::{Compression, OutFile};
+use crate::internal_events::FileIoError;
+
+/// The format of the time appended to the path of rotated files.
+///
+/// Rotated files sort by the time they were rotated at when sorted by name.
+const TIMESTAMP_FORMAT: &str = "%Y%m%dT%H%M%S%3f";
+
+/// Rotation configuration.
+///
+/// Files are rotated by appending the time of the rotation to their path, so that the events
+/// written afterwards go to a new file. Rotation is disabled unless `max_bytes` or `max_age_secs`
+/// is set.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct RotationConfig {
+    /// The size that a file can grow to before it is rotated.
+    ///
+    /// This is the size of the data written to the file before it is compressed by the
+    /// `compression` option.
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    #[configurable(metadata(docs::examples = 104857600))]
+    #[configurable(metadata(docs::human_name = "Maximum Size"))]
+    pub max_bytes: Option<u64>,
+
+    /// The age that a file can reach before it is rotated.
+    ///
+    /// The age of a file is counted from the time it was created, and is checked when events are
+    /// written to it.
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    #[configurable(metadata(docs::examples = 86400))]
+    #[configurable(metadata(docs::human_name = "Maximum Age"))]
+    pub max_age_secs: Option<u64>,
+
+    /// The compression applied to files once they are rotated.
+    ///
+    /// The extension of the compression format is appended to the path of the compressed files.
+    /// This can't be used together with the `compression` option, which compresses files while
+    /// they are written.
+    #[serde(default, skip_serializing_if = "crate::serde::is_default")]
+    pub compression: Compression,
+
+    /// The number of rotated files of each path to keep.
+    ///
+    /// The oldest rotated files are removed once there are more of them.
+    #[configurable(metadata(docs::examples = 10))]
+    pub keep_files: Option<usize>,
+
+    /// The number of days to keep rotated files for.
+    #[configurable(metadata(docs::type_unit = "days"))]
+    #[configurable(metadata(docs::examples = 7))]
+    pub keep_days: Option<u64>,
+}
+
+impl RotationConfig {
+    pub(super) fn validate(&self, compression: Compression) -> crate::Result<()> {
+        let enabled = self.max_bytes.is_some() || self.max_age_secs.is_some();
+        if !enabled
+            && (self.compression != Compression::None
+                || self.keep_files.is_some()
+                || self.keep_days.is_some())
+        {
+            return Err(
+                "`rotation.max_bytes` or `rotation.max_age_secs` must be set to rotate files"
+                    .into(),
+            );
+        }
+        if self.compression != Compression::None && compression != Compression::None {
+            return Err("`rotation.compression` can't be used together with `compression`".into());
+        }
+        Ok(())
+    }
+
+    /// Whether a file that was created at the given time and holds the given number of bytes is
+    /// rotated.
+    pub(super) fn is_due(&self, bytes: u64, created: SystemTime) -> bool {
+        self.max_bytes.is_some_and(|max_bytes| bytes >= max_bytes)
+            || self.max_age_secs.is_some_and(|max_age_secs| {
+                created
+                    .elapsed()
+                    .is_ok_and(|age| age >= Duration::from_secs(max_age_secs))
+            })
+    }
+
+    /// Compresses the rotated file, and removes the rotated files of its path that are no longer
+    /// kept.
+    pub(super) async fn finish(self, path: PathBuf, rotated: PathBuf) {
+        if let Err(error) = compress(&rotated, self.compression).await {
+            emit!(FileIoError {
+                error,
+                code: "failed_compressing_file",
+                message: "Failed to compress the rotated file.",
+                path: &rotated,
+                dropped_events: 0,
+            });
+        }
+
+        if let Err(error) = self.prune(&path).await {
+            emit!(FileIoError {
+                error,
+                code: "failed_removing_rotated_files",
+                message: "Failed to remove the rotated files that are no longer kept.",
+                path: &path,
+                dropped_events: 0,
+            });
+        }
+    }
+
+    async fn prune(&self, path: &Path) -> io::Result<()> {
+        if self.keep_files.is_none() && self.keep_days.is_none() {
+            return Ok(());
+        }
+        let Some(file_name) = path.file_name() else {
+            return Ok(());
+        };
+        let directory = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent,
+            _ => Path::new("."),
+        };
+
+        let mut rotated = Vec::new();
+        let mut entries = fs::read_dir(directory).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            if is_rotated_from(&entry.file_name(), file_name) {
+                let modified = entry.metadata().await?.modified()?;
+                rotated.push((entry.file_name(), modified));
+            }
+        }
+        // Newest first.
+        rotated.sort_unstable_by(|(a, _), (b, _)| b.cmp(a));
+
+        let keep_files = self.keep_files.unwrap_or(usize::MAX);
+        let keep_for = self
+            .keep_days
+            .map(|days| Duration::from_secs(days * 24 * 60 * 60));
+        for (index, (name, modified)) in rotated.into_iter().enumerate() {
+            let expired =
+                keep_for.is_some_and(|keep_for| modified.elapsed().is_ok_and(|age| age > keep_for));
+            if index >= keep_files || expired {
+                match fs::remove_file(directory.join(name)).await {
+                    // The file may have been removed by the rotation of another file already.
+                    Err(error) if error.kind() == io::ErrorKind::NotFound => {}
+                    result => result?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Renames the file at the path by appending the current time to it, returning the path of the
+/// rotated file.
+pub(super) async fn rename(path: &Path) -> io::Result<PathBuf> {
+    loop {
+        let rotated = with_extension(path, &Utc::now().format(TIMESTAMP_FORMAT).to_string());
+        // Files rotated within the same millisecond would otherwise overwrite each other.
+        if !fs::try_exists(&rotated).await? {
+            fs::rename(path, &rotated).await?;
+            return Ok(rotated);
+        }
+        tokio::time::sleep(Duration::from_millis(1)).await;
+    }
+}
+
+async fn compress(path: &Path, compression: Compression) -> io::Result<()> {
+    let extension = match compression {
+        Compression::None => return Ok(()),
+        Compression::Gzip => "gz",
+        Compression::Zstd => "zst",
+    };
+
+    let mut source = File::open(path).await?;
+    let mut compressed = OutFile::new(
+        File::create(with_extension(path, extension)).await?,
+        compression,
+    );
+    let mut buffer = vec![0; 64 * 1024];
+    loop {
+        let read = source.read(&mut buffer).await?;
+        if read == 0 {
+            break;
+        }
+        compressed.write_all(&buffer[..read]).await?;
+    }
+    compressed.close().await?;
+
+    fs::remove_file(path).await
+}
+
+fn with_extension(path: &Path, extension: &str) -> PathBuf {
+    let mut path = OsString::from(path);
+    path.push(".");
+    path.push(extension);
+    path.into()
+}
+
+/// Whether the file name is that of a file rotated from the given file name.
+fn is_rotated_from(name: &OsStr, file_name: &OsStr) -> bool {
+    let (Some(name), Some(file_name)) = (name.to_str(), file_name.to_str()) else {
+        return false;
+    };
+    let Some(suffix) = name
+        .strip_prefix(file_name)
+        .and_then(|suffix| suffix.strip_prefix('.'))
+    else {
+        return false;
+    };
+    let timestamp = suffix
+        .strip_suffix(".gz")
+        .or_else(|| suffix.strip_suffix(".zst"))
+        .unwrap_or(suffix);
+
+    timestamp.len() == 18
+        && timestamp.char_indices().all(|(index, c)| match index {
+            8 => c == 'T',
+            _ => c.is_ascii_digit(),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recognizes_rotated_files() {
+        let file_name = OsStr::new("vector.log");
+
+        for name in [
+            "vector.log.20261014T120000123",
+            "vector.log.20261014T120000123.gz",
+            "vector.log.20261014T120000123.zst",
+        ] {
+            assert!(is_rotated_from(OsStr::new(name), file_name), "{}", name);
+        }
+        for name in [
+            "vector.log",
+            "vector.log.gz",
+            "vector.log.1",
+            "vector.log.20261014T120000123.bak",
+            "other.log.20261014T120000123",
+        ] {
+            assert!(!is_rotated_from(OsStr::new(name), file_name), "{}", name);
+        }
+    }
+}
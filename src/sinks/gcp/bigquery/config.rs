@@ -0,0 +1,291 @@
+use http::{uri::Scheme, Uri};
+use snafu::{ResultExt, Snafu};
+use tonic::transport::{Certificate, ClientTlsConfig, Endpoint, Identity};
+use vector_lib::codecs::encoding::{
+    ProtobufSerializerConfig, ProtobufSerializerOptions, Serializer,
+};
+
+use super::{
+    encoder::RowEncoder,
+    proto::{self, big_query_write_client::BigQueryWriteClient, ProtoSchema},
+    service::{routed, AuthInterceptor, BigqueryRetryLogic, BigqueryService, Client},
+    sink::BigquerySink,
+};
+use crate::{
+    gcp::{GcpAuthConfig, GcpAuthenticator, Scope},
+    sinks::prelude::*,
+};
+
+const DEFAULT_ENDPOINT: &str = "https://bigquerystorage.googleapis.com";
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Invalid endpoint URI: {}", source))]
+    Uri { source: http::uri::InvalidUri },
+    #[snafu(display("Could not create endpoint: {}", source))]
+    Endpoint { source: tonic::transport::Error },
+    #[snafu(display("Could not set up endpoint TLS settings: {}", source))]
+    EndpointTls { source: tonic::transport::Error },
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BigqueryDefaultBatchSettings;
+
+impl SinkBatchSettings for BigqueryDefaultBatchSettings {
+    const MAX_EVENTS: Option<usize> = None;
+    // Leaves room for the schema under the 10 MB limit of the requests appending rows.
+    const MAX_BYTES: Option<usize> = Some(9_000_000);
+    const TIMEOUT_SECS: f64 = 1.0;
+}
+
+/// The type of the write streams that rows are appended to.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BigqueryWriteMode {
+    /// Rows are appended to the default stream of the table.
+    ///
+    /// The rows are available as soon as they are acknowledged by BigQuery.
+    #[default]
+    Committed,
+
+    /// The rows of each batch are appended to a new pending stream, which is committed once all
+    /// of them are appended.
+    ///
+    /// The rows of a batch become available all at once, and none of them are written when the
+    /// batch fails, at the cost of more requests per batch.
+    Pending,
+}
+
+/// Configuration for the `gcp_bigquery` sink.
+#[configurable_component(sink(
+    "gcp_bigquery",
+    "Write log events to Google Cloud BigQuery with the Storage Write API."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct BigqueryConfig {
+    /// The project of the dataset.
+    #[configurable(metadata(docs::examples = "my-project"))]
+    pub project: String,
+
+    /// The dataset of the table.
+    #[configurable(metadata(docs::examples = "my_dataset"))]
+    pub dataset: String,
+
+    /// The table that rows are written to.
+    ///
+    /// Events can be routed to the tables of date-sharded tables, or to the partitions of
+    /// partitioned tables with a partition decorator such as `logs$20240102`.
+    #[configurable(metadata(docs::examples = "logs"))]
+    #[configurable(metadata(docs::examples = "logs_{{ %Y%m%d }}"))]
+    #[configurable(metadata(docs::examples = "logs${{ %Y%m%d }}"))]
+    pub table: Template,
+
+    /// The Protobuf message type that events are serialized into.
+    ///
+    /// The fields of the message type are matched to the columns of the table by name, and the
+    /// message type must be self-contained: the messages it refers to must be nested inside of
+    /// it. The types of the fields must be compatible with the [types of their
+    /// columns][data_types].
+    ///
+    /// [data_types]: https://cloud.google.com/bigquery/docs/write-api#data_type_conversions
+    pub schema: ProtobufSerializerOptions,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub write_mode: BigqueryWriteMode,
+
+    /// The endpoint of the Storage Write API.
+    #[configurable(metadata(docs::examples = "https://bigquerystorage.googleapis.com"))]
+    #[serde(default = "default_endpoint")]
+    pub endpoint: String,
+
+    #[serde(flatten)]
+    pub auth: GcpAuthConfig,
+
+    #[configurable(derived)]
+    #[serde(skip_serializing_if = "crate::serde::is_default", default)]
+    pub encoding: Transformer,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch: BatchConfig<BigqueryDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::is_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+fn default_endpoint() -> String {
+    DEFAULT_ENDPOINT.to_string()
+}
+
+impl GenerateConfig for BigqueryConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"project = "my-project"
+            dataset = "my_dataset"
+            table = "logs"
+            schema.desc_file = "/etc/vector/logs.desc"
+            schema.message_type = "vector.LogRow""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "gcp_bigquery")]
+impl SinkConfig for BigqueryConfig {
+    async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let serializer = ProtobufSerializerConfig {
+            protobuf: self.schema.clone(),
+        }
+        .build()?;
+        let writer_schema = ProtoSchema {
+            proto_descriptor: Some(serializer.descriptor_proto().clone()),
+        };
+
+        let auth = self.auth.build(Scope::BigQueryInsertdata).await?;
+        auth.spawn_regenerate_token();
+        let client = self.client(auth)?;
+
+        let service = ServiceBuilder::new()
+            .settings(self.request.into_settings(), BigqueryRetryLogic)
+            .service(BigqueryService::new(
+                client.clone(),
+                writer_schema,
+                self.write_mode,
+            ));
+
+        let sink = BigquerySink {
+            batch_settings: self.batch.into_batcher_settings()?,
+            service,
+            table: self.table.clone(),
+            parent: format!("projects/{}/datasets/{}/tables", self.project, self.dataset),
+            encoder: RowEncoder {
+                encoder: Encoder::<()>::new(Serializer::Protobuf(serializer)),
+                transformer: self.encoding.clone(),
+            },
+        };
+
+        // The table can only be checked when it isn't templated.
+        let healthcheck = match self.table.is_dynamic() {
+            true => healthcheck_noop(),
+            false => healthcheck(
+                client,
+                format!(
+                    "projects/{}/datasets/{}/tables/{}",
+                    self.project, self.dataset, self.table
+                ),
+            )
+            .boxed(),
+        };
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+impl BigqueryConfig {
+    fn client(&self, auth: GcpAuthenticator) -> crate::Result<Client> {
+        let uri: Uri = self.endpoint.parse().context(UriSnafu)?;
+
+        let tls = TlsSettings::from_options(&self.tls)?;
+        let host = uri.host().unwrap_or("bigquerystorage.googleapis.com");
+        let mut tls_config = ClientTlsConfig::new().domain_name(host);
+        if let Some((cert, key)) = tls.identity_pem() {
+            tls_config = tls_config.identity(Identity::from_pem(cert, key));
+        }
+        for authority in tls.authorities_pem() {
+            tls_config = tls_config.ca_certificate(Certificate::from_pem(authority));
+        }
+
+        let mut endpoint: Endpoint = uri.to_string().parse().context(EndpointSnafu)?;
+        if uri.scheme() != Some(&Scheme::HTTP) {
+            endpoint = endpoint.tls_config(tls_config).context(EndpointTlsSnafu)?;
+        }
+
+        Ok(BigQueryWriteClient::with_interceptor(
+            endpoint.connect_lazy(),
+            AuthInterceptor { auth },
+        ))
+    }
+}
+
+fn healthcheck_noop() -> Healthcheck {
+    Box::pin(async { Ok(()) })
+}
+
+async fn healthcheck(mut client: Client, table: String) -> crate::Result<()> {
+    let name = format!("{}/streams/_default", table);
+    client
+        .get_write_stream(routed(
+            proto::GetWriteStreamRequest {
+                name: name.clone(),
+                view: proto::WriteStreamView::Basic.into(),
+            },
+            "name",
+            &name,
+        ))
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<BigqueryConfig>();
+    }
+
+    #[test]
+    fn parses_write_mode() {
+        let config: BigqueryConfig = toml::from_str(
+            r#"
+            project = "my-project"
+            dataset = "my_dataset"
+            table = "logs${{ %Y%m%d }}"
+            schema.desc_file = "tests/data/protobuf/test_grpc_ingest.desc"
+            schema.message_type = "test_grpc_ingest.LogRecord"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.write_mode, BigqueryWriteMode::Committed);
+        assert_eq!(config.endpoint, DEFAULT_ENDPOINT);
+        assert!(config.table.is_dynamic());
+
+        let config: BigqueryConfig = toml::from_str(
+            r#"
+            project = "my-project"
+            dataset = "my_dataset"
+            table = "logs"
+            schema.desc_file = "tests/data/protobuf/test_grpc_ingest.desc"
+            schema.message_type = "test_grpc_ingest.LogRecord"
+            write_mode = "pending"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.write_mode, BigqueryWriteMode::Pending);
+    }
+}
@@ -0,0 +1,125 @@
+//! Encoding of events into the serialized Protobuf rows of requests.
+
+use std::num::NonZeroUsize;
+
+use bytes::BytesMut;
+use tokio_util::codec::Encoder as _;
+
+use super::service::BigqueryRequest;
+use crate::sinks::prelude::*;
+
+/// Serializes events into rows of the message type of the schema.
+pub(super) struct RowEncoder {
+    pub(super) encoder: Encoder<()>,
+    pub(super) transformer: Transformer,
+}
+
+impl RowEncoder {
+    /// Encodes the events into a request to the table, rejecting the events which can't be
+    /// serialized.
+    ///
+    /// Returns `None` if none of the events could be serialized.
+    pub(super) fn encode(&mut self, table: String, events: Vec<Event>) -> Option<BigqueryRequest> {
+        let mut rows = Vec::with_capacity(events.len());
+        let mut finalizers = EventFinalizers::default();
+        let mut byte_size = telemetry().create_request_count_byte_size();
+        let mut request_size = 0;
+
+        for mut event in events {
+            let event_finalizers = event.take_finalizers();
+            self.transformer.transform(&mut event);
+
+            let mut event_size = telemetry().create_request_count_byte_size();
+            event_size.add_event(&event, event.estimated_json_encoded_size_of());
+
+            let mut row = BytesMut::new();
+            match self.encoder.encode(event, &mut row) {
+                Ok(()) => {
+                    byte_size += event_size;
+                    request_size += row.len();
+                    finalizers.merge(event_finalizers);
+                    rows.push(row.to_vec());
+                }
+                // The serializer emits the error and the dropped event.
+                Err(_) => event_finalizers.update_status(EventStatus::Rejected),
+            }
+        }
+
+        if rows.is_empty() {
+            return None;
+        }
+
+        let metadata = RequestMetadataBuilder::new(rows.len(), request_size, byte_size)
+            .with_request_size(NonZeroUsize::new(request_size).unwrap_or(NonZeroUsize::MIN));
+
+        Some(BigqueryRequest {
+            table,
+            rows,
+            finalizers,
+            metadata,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use vector_lib::codecs::encoding::{
+        ProtobufSerializerConfig, ProtobufSerializerOptions, Serializer,
+    };
+    use vector_lib::event::{BatchNotifier, BatchStatus, LogEvent};
+
+    use super::*;
+
+    fn encoder() -> RowEncoder {
+        let serializer = ProtobufSerializerConfig {
+            protobuf: ProtobufSerializerOptions {
+                desc_file: PathBuf::from(std::env::var_os("CARGO_MANIFEST_DIR").unwrap())
+                    .join("tests/data/protobuf/test_grpc_ingest.desc"),
+                message_type: "test_grpc_ingest.LogRecord".to_string(),
+                field_mappings: Vec::new(),
+            },
+        }
+        .build()
+        .unwrap();
+
+        RowEncoder {
+            encoder: Encoder::<()>::new(Serializer::Protobuf(serializer)),
+            transformer: Transformer::default(),
+        }
+    }
+
+    #[test]
+    fn encodes_rows() {
+        let mut log = LogEvent::from("hello");
+        log.insert("severity", 3);
+
+        let request = encoder()
+            .encode(
+                "projects/p/datasets/d/tables/logs".to_string(),
+                vec![log.into()],
+            )
+            .unwrap();
+
+        assert_eq!(request.table, "projects/p/datasets/d/tables/logs");
+        assert_eq!(request.rows.len(), 1);
+        assert_eq!(request.metadata.event_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn rejects_unserializable_events() {
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        let mut log = LogEvent::from("hello").with_batch_notifier(&batch);
+        log.insert("severity", "high");
+        drop(batch);
+
+        assert!(encoder()
+            .encode(
+                "projects/p/datasets/d/tables/logs".to_string(),
+                vec![log.into()]
+            )
+            .is_none());
+        assert_eq!(receiver.await, BatchStatus::Rejected);
+    }
+}
@@ -0,0 +1,51 @@
+//! The `gcp_bigquery` sink.
+//!
+//! This sink writes log events as rows of [BigQuery][bigquery] tables with the
+//! [Storage Write API][write_api]. Events are serialized into the Protobuf message type configured
+//! as the schema of the rows, and the descriptor of the message type is sent along with the rows
+//! so that BigQuery can decode them.
+//!
+//! In the `committed` write mode, the rows are appended to the default stream of their table, and
+//! are available as soon as they are acknowledged. In the `pending` write mode, the rows of each
+//! batch are appended to a new pending stream, which is committed once all of them are appended.
+//!
+//! [bigquery]: https://cloud.google.com/bigquery
+//! [write_api]: https://cloud.google.com/bigquery/docs/write-api
+
+mod config;
+mod encoder;
+mod service;
+mod sink;
+
+// prost emits some generated code that includes clones on `Arc`
+// objects, which causes a clippy ding on this block. We don't
+// directly control the generated code, so allow this lint here.
+#[allow(clippy::clone_on_ref_ptr)]
+// https://github.com/hyperium/tonic/issues/1350
+#[allow(clippy::missing_const_for_fn)]
+#[allow(warnings)]
+mod proto {
+    // The generated code refers to the messages of other packages relative to its own package.
+    pub mod google {
+        pub mod cloud {
+            pub mod bigquery {
+                pub mod storage {
+                    pub mod v1 {
+                        include!(concat!(
+                            env!("OUT_DIR"),
+                            "/google.cloud.bigquery.storage.v1.rs"
+                        ));
+                    }
+                }
+            }
+        }
+
+        pub mod rpc {
+            include!(concat!(env!("OUT_DIR"), "/google.rpc.rs"));
+        }
+    }
+
+    pub use self::google::cloud::bigquery::storage::v1::*;
+}
+
+pub use self::config::BigqueryConfig;
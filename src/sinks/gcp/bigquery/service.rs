@@ -0,0 +1,342 @@
+//! Service implementation for the `gcp_bigquery` sink.
+
+use std::task::{Context, Poll};
+
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+use snafu::Snafu;
+use tonic::{
+    metadata::MetadataValue, service::interceptor::InterceptedService, transport::Channel, Code,
+    Request, Status,
+};
+
+use super::{
+    config::BigqueryWriteMode,
+    proto::{
+        self, append_rows_request, append_rows_response,
+        big_query_write_client::BigQueryWriteClient, write_stream, AppendRowsRequest, ProtoRows,
+        ProtoSchema,
+    },
+};
+use crate::{gcp::GcpAuthenticator, sinks::prelude::*};
+
+/// The codes of the errors returned by the Storage Write API which are retried.
+const RETRIABLE_CODES: [Code; 6] = [
+    Code::Unavailable,
+    Code::Internal,
+    Code::Aborted,
+    Code::DeadlineExceeded,
+    Code::ResourceExhausted,
+    Code::Unknown,
+];
+
+pub(super) type Client = BigQueryWriteClient<InterceptedService<Channel, AuthInterceptor>>;
+
+#[derive(Debug, Snafu)]
+pub(super) enum BigqueryError {
+    #[snafu(display("BigQuery request failed: {}", source))]
+    Request { source: Status },
+    #[snafu(display("BigQuery failed to append rows: {}", message))]
+    Append { code: Code, message: String },
+    #[snafu(display("BigQuery rejected row {}: {}", index, message))]
+    Row { index: i64, message: String },
+    #[snafu(display("BigQuery failed to commit the stream: {}", message))]
+    Commit { message: String },
+    #[snafu(display("BigQuery closed the stream without a response"))]
+    NoResponse,
+}
+
+impl From<Status> for BigqueryError {
+    fn from(source: Status) -> Self {
+        Self::Request { source }
+    }
+}
+
+/// Authorizes the requests with the current token of the authenticator.
+#[derive(Clone)]
+pub(super) struct AuthInterceptor {
+    pub(super) auth: GcpAuthenticator,
+}
+
+impl tonic::service::Interceptor for AuthInterceptor {
+    fn call(&mut self, mut request: Request<()>) -> Result<Request<()>, Status> {
+        if let Some(token) = self.auth.make_token() {
+            let authorization = MetadataValue::try_from(&token).map_err(|_| {
+                Status::new(
+                    Code::FailedPrecondition,
+                    "Invalid token text returned by GCP",
+                )
+            })?;
+            request
+                .metadata_mut()
+                .insert("authorization", authorization);
+        }
+        Ok(request)
+    }
+}
+
+/// Wraps the message in a request routed to the resource by the given parameter, as BigQuery
+/// requires.
+pub(super) fn routed<T>(message: T, param: &str, resource: &str) -> Request<T> {
+    let mut request = Request::new(message);
+    let params = format!(
+        "{}={}",
+        param,
+        utf8_percent_encode(resource, NON_ALPHANUMERIC)
+    );
+    if let Ok(value) = MetadataValue::try_from(params) {
+        request
+            .metadata_mut()
+            .insert("x-goog-request-params", value);
+    }
+    request
+}
+
+#[derive(Clone)]
+pub(super) struct BigqueryRequest {
+    /// The path of the table, `projects/{project}/datasets/{dataset}/tables/{table}`.
+    pub(super) table: String,
+    pub(super) rows: Vec<Vec<u8>>,
+    pub(super) finalizers: EventFinalizers,
+    pub(super) metadata: RequestMetadata,
+}
+
+impl Finalizable for BigqueryRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        std::mem::take(&mut self.finalizers)
+    }
+}
+
+impl MetaDescriptive for BigqueryRequest {
+    fn get_metadata(&self) -> &RequestMetadata {
+        &self.metadata
+    }
+
+    fn metadata_mut(&mut self) -> &mut RequestMetadata {
+        &mut self.metadata
+    }
+}
+
+#[derive(Debug)]
+pub(super) struct BigqueryResponse {
+    events_byte_size: GroupedCountByteSize,
+}
+
+impl DriverResponse for BigqueryResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> &GroupedCountByteSize {
+        &self.events_byte_size
+    }
+}
+
+#[derive(Clone, Default)]
+pub(super) struct BigqueryRetryLogic;
+
+impl RetryLogic for BigqueryRetryLogic {
+    type Error = BigqueryError;
+    type Response = BigqueryResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        match error {
+            BigqueryError::Request { source } => RETRIABLE_CODES.contains(&source.code()),
+            BigqueryError::Append { code, .. } => RETRIABLE_CODES.contains(code),
+            // The rows of the request are only written once all of them are valid.
+            BigqueryError::Row { .. } => false,
+            BigqueryError::Commit { .. } | BigqueryError::NoResponse => true,
+        }
+    }
+}
+
+/// Appends the rows of requests to the write streams of their table.
+#[derive(Clone)]
+pub(super) struct BigqueryService {
+    client: Client,
+    writer_schema: ProtoSchema,
+    write_mode: BigqueryWriteMode,
+}
+
+impl BigqueryService {
+    pub(super) const fn new(
+        client: Client,
+        writer_schema: ProtoSchema,
+        write_mode: BigqueryWriteMode,
+    ) -> Self {
+        Self {
+            client,
+            writer_schema,
+            write_mode,
+        }
+    }
+}
+
+impl Service<BigqueryRequest> for BigqueryService {
+    type Response = BigqueryResponse;
+    type Error = BigqueryError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: BigqueryRequest) -> Self::Future {
+        let mut service = self.clone();
+        let events_byte_size = request
+            .metadata
+            .events_estimated_json_encoded_byte_size()
+            .clone();
+
+        Box::pin(async move {
+            match service.write_mode {
+                BigqueryWriteMode::Committed => {
+                    let stream = format!("{}/streams/_default", request.table);
+                    service.append(stream, None, request.rows).await?;
+                }
+                BigqueryWriteMode::Pending => service.write_pending(request).await?,
+            }
+            Ok(BigqueryResponse { events_byte_size })
+        })
+    }
+}
+
+impl BigqueryService {
+    /// Writes the rows to a new pending stream, which is committed once they are all appended, so
+    /// that the rows of the request are written either entirely or not at all.
+    async fn write_pending(&mut self, request: BigqueryRequest) -> Result<(), BigqueryError> {
+        let stream = self
+            .client
+            .create_write_stream(routed(
+                proto::CreateWriteStreamRequest {
+                    parent: request.table.clone(),
+                    write_stream: Some(proto::WriteStream {
+                        r#type: write_stream::Type::Pending.into(),
+                        ..Default::default()
+                    }),
+                },
+                "parent",
+                &request.table,
+            ))
+            .await?
+            .into_inner()
+            .name;
+
+        self.append(stream.clone(), Some(0), request.rows).await?;
+
+        self.client
+            .finalize_write_stream(routed(
+                proto::FinalizeWriteStreamRequest {
+                    name: stream.clone(),
+                },
+                "name",
+                &stream,
+            ))
+            .await?;
+
+        let response = self
+            .client
+            .batch_commit_write_streams(routed(
+                proto::BatchCommitWriteStreamsRequest {
+                    parent: request.table.clone(),
+                    write_streams: vec![stream],
+                },
+                "parent",
+                &request.table,
+            ))
+            .await?
+            .into_inner();
+
+        match response.stream_errors.first() {
+            Some(error) => CommitSnafu {
+                message: error.error_message.clone(),
+            }
+            .fail(),
+            None => Ok(()),
+        }
+    }
+
+    /// Appends the rows to the stream, at the given offset for the streams that aren't the default
+    /// stream.
+    async fn append(
+        &mut self,
+        stream: String,
+        offset: Option<i64>,
+        rows: Vec<Vec<u8>>,
+    ) -> Result<(), BigqueryError> {
+        let request = AppendRowsRequest {
+            write_stream: stream.clone(),
+            offset,
+            rows: Some(append_rows_request::Rows::ProtoRows(
+                append_rows_request::ProtoData {
+                    writer_schema: Some(self.writer_schema.clone()),
+                    rows: Some(ProtoRows {
+                        serialized_rows: rows,
+                    }),
+                },
+            )),
+            ..Default::default()
+        };
+
+        let mut responses = self
+            .client
+            .append_rows(routed(tokio_stream::once(request), "write_stream", &stream))
+            .await?
+            .into_inner();
+
+        let response = responses
+            .message()
+            .await?
+            .ok_or(BigqueryError::NoResponse)?;
+
+        if let Some(error) = response.row_errors.first() {
+            return RowSnafu {
+                index: error.index,
+                message: error.message.clone(),
+            }
+            .fail();
+        }
+
+        match response.response {
+            Some(append_rows_response::Response::AppendResult(_)) => Ok(()),
+            Some(append_rows_response::Response::Error(status)) => AppendSnafu {
+                code: Code::from(status.code),
+                message: status.message,
+            }
+            .fail(),
+            None => Err(BigqueryError::NoResponse),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn routes_requests() {
+        let request = routed(
+            (),
+            "write_stream",
+            "projects/p/datasets/d/tables/logs$20240102/streams/_default",
+        );
+        assert_eq!(
+            request.metadata().get("x-goog-request-params").unwrap(),
+            "write_stream=projects%2Fp%2Fdatasets%2Fd%2Ftables%2Flogs%2420240102%2Fstreams%2F%5Fdefault"
+        );
+    }
+
+    #[test]
+    fn retries_transient_errors() {
+        let logic = BigqueryRetryLogic;
+        assert!(logic.is_retriable_error(&BigqueryError::Request {
+            source: Status::unavailable("unavailable")
+        }));
+        assert!(!logic.is_retriable_error(&BigqueryError::Request {
+            source: Status::invalid_argument("invalid")
+        }));
+        assert!(!logic.is_retriable_error(&BigqueryError::Row {
+            index: 0,
+            message: "invalid".into()
+        }));
+    }
+}
@@ -0,0 +1,94 @@
+//! Implementation of the `gcp_bigquery` sink.
+
+use super::{
+    encoder::RowEncoder,
+    service::{BigqueryRetryLogic, BigqueryService},
+};
+use crate::sinks::prelude::*;
+
+/// Partitions events by the path of the table they are written to.
+struct TablePartitioner {
+    table: Template,
+    parent: String,
+}
+
+impl Partitioner for TablePartitioner {
+    type Item = Event;
+    type Key = Option<String>;
+
+    fn partition(&self, item: &Self::Item) -> Self::Key {
+        self.table
+            .render_string(item)
+            .map_err(|error| {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some("table"),
+                    drop_event: true,
+                });
+            })
+            .ok()
+            .map(|table| format!("{}/{}", self.parent, table))
+    }
+}
+
+pub(super) struct BigquerySink {
+    pub(super) batch_settings: BatcherSettings,
+    pub(super) service: Svc<BigqueryService, BigqueryRetryLogic>,
+    pub(super) table: Template,
+    /// The path of the tables of the dataset, `projects/{project}/datasets/{dataset}/tables`.
+    pub(super) parent: String,
+    pub(super) encoder: RowEncoder,
+}
+
+impl BigquerySink {
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let batch_settings = self.batch_settings;
+        let mut encoder = self.encoder;
+        let partitioner = TablePartitioner {
+            table: self.table,
+            parent: self.parent,
+        };
+
+        input
+            .batched_partitioned(partitioner, || batch_settings.as_byte_size_config())
+            .filter_map(|(table, batch)| async move { table.map(move |table| (table, batch)) })
+            .filter_map(move |(table, events)| {
+                let request = encoder.encode(table, events);
+                async move { request }
+            })
+            .into_driver(self.service)
+            .protocol("grpc")
+            .run()
+            .await
+    }
+}
+
+#[async_trait::async_trait]
+impl StreamSink<Event> for BigquerySink {
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_lib::event::LogEvent;
+
+    use super::*;
+
+    #[test]
+    fn partitions_by_table_path() {
+        let partitioner = TablePartitioner {
+            table: Template::try_from("logs${{ day }}").unwrap(),
+            parent: "projects/p/datasets/d/tables".to_string(),
+        };
+
+        let mut log = LogEvent::from("hello");
+        log.insert("day", "20240102");
+        assert_eq!(
+            partitioner.partition(&log.into()).as_deref(),
+            Some("projects/p/datasets/d/tables/logs$20240102")
+        );
+        assert_eq!(partitioner.partition(&LogEvent::from("hello").into()), None);
+    }
+}
@@ -0,0 +1,334 @@
+//! This sink sends data to the Google Chronicle ingestion API, either as unstructured log entries,
+//! which Chronicle parses according to their log type, or as events already normalized to the
+//! [Unified Data Model][udm].
+//!
+//! See <https://cloud.google.com/chronicle/docs/reference/ingestion-api> for more information.
+//!
+//! [udm]: https://cloud.google.com/chronicle/docs/event-processing/udm-overview
+use std::collections::HashMap;
+
+use goauth::scopes::Scope;
+use indoc::indoc;
+use tower::ServiceBuilder;
+use vector_lib::codecs::encoding::SerializerConfig;
+use vector_lib::configurable::configurable_component;
+use vector_lib::{
+    config::{AcknowledgementsConfig, Input},
+    sink::VectorSink,
+};
+use vrl::value::Kind;
+
+use super::chronicle_unstructured::{
+    build_healthcheck, ChronicleError, ChronicleRequestBuilder, ChronicleService,
+    ChronicleUnstructuredDefaultBatchSettings, ChronicleUnstructuredTowerRequestConfigDefaults,
+    Region,
+};
+use crate::{
+    codecs::EncodingConfig,
+    config::{GenerateConfig, SinkConfig, SinkContext},
+    gcp::{GcpAuthConfig, GcpAuthenticator},
+    http::HttpClient,
+    schema,
+    sinks::{
+        gcs_common::{config::GcsRetryLogic, sink::GcsSink},
+        util::{partitioner::KeyPartitioner, BatchConfig, TowerRequestConfig},
+        Healthcheck,
+    },
+    template::Template,
+    tls::{TlsConfig, TlsSettings},
+};
+
+/// The type of the entries sent to Chronicle.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ChronicleEventType {
+    /// Unstructured log entries, parsed by Chronicle according to their `log_type`.
+    ///
+    /// The entries are the events encoded with the configured codec.
+    #[default]
+    Unstructured,
+
+    /// Events of the [Unified Data Model][udm], already normalized by Vector.
+    ///
+    /// The events are encoded with the `json` codec, and must be objects with the fields of UDM
+    /// events, such as `metadata` and `principal`. The `namespace` and `labels` options don't apply
+    /// to UDM events, which carry their own.
+    ///
+    /// [udm]: https://cloud.google.com/chronicle/docs/reference/udm-field-list
+    Udm,
+}
+
+impl ChronicleEventType {
+    /// The path of the ingestion API endpoint that the entries are sent to.
+    const fn path(self) -> &'static str {
+        match self {
+            ChronicleEventType::Unstructured => "v2/unstructuredlogentries:batchCreate",
+            ChronicleEventType::Udm => "v2/udmevents:batchCreate",
+        }
+    }
+}
+
+/// Configuration for the `gcp_chronicle` sink.
+#[configurable_component(sink(
+    "gcp_chronicle",
+    "Send log entries and UDM events to Google Chronicle."
+))]
+#[derive(Clone, Debug)]
+pub struct ChronicleConfig {
+    /// The endpoint to send data to.
+    #[configurable(metadata(
+        docs::examples = "127.0.0.1:8080",
+        docs::examples = "example.com:12345"
+    ))]
+    pub endpoint: Option<String>,
+
+    /// The GCP region to use.
+    #[configurable(derived)]
+    pub region: Option<Region>,
+
+    /// The Unique identifier (UUID) corresponding to the Chronicle instance.
+    #[configurable(validation(format = "uuid"))]
+    #[configurable(metadata(docs::examples = "c8c65bfa-5f2c-42d4-9189-64bb7b939f2c"))]
+    pub customer_id: String,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub event_type: ChronicleEventType,
+
+    /// The type of log entries in a request.
+    ///
+    /// This must be one of the [supported log types][unstructured_log_types_doc], otherwise
+    /// Chronicle rejects the entry with an error. Required for unstructured log entries.
+    ///
+    /// [unstructured_log_types_doc]: https://cloud.google.com/chronicle/docs/ingestion/parser-list/supported-default-parsers
+    #[configurable(metadata(docs::examples = "WINDOWS_DNS", docs::examples = "{{ log_type }}"))]
+    pub log_type: Option<Template>,
+
+    /// User-configured environment namespace to identify the data domain the logs originated from.
+    #[configurable(metadata(docs::examples = "production"))]
+    pub namespace: Option<String>,
+
+    /// A set of labels that are attached to each batch of events.
+    #[configurable(metadata(docs::examples = "chronicle_labels_examples()"))]
+    #[configurable(metadata(docs::additional_props_description = "A Chronicle label."))]
+    pub labels: Option<HashMap<String, String>>,
+
+    #[serde(flatten)]
+    pub auth: GcpAuthConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch: BatchConfig<ChronicleUnstructuredDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    pub encoding: EncodingConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: TowerRequestConfig<ChronicleUnstructuredTowerRequestConfigDefaults>,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::is_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+fn chronicle_labels_examples() -> HashMap<String, String> {
+    let mut examples = HashMap::new();
+    examples.insert("source".to_string(), "vector".to_string());
+    examples.insert("tenant".to_string(), "marketing".to_string());
+    examples
+}
+
+impl GenerateConfig for ChronicleConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(indoc! {r#"
+            credentials_path = "/path/to/credentials.json"
+            customer_id = "customer_id"
+            region = "us"
+            log_type = "log_type"
+            encoding.codec = "text"
+        "#})
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "gcp_chronicle")]
+impl SinkConfig for ChronicleConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        self.validate()?;
+
+        let creds = self.auth.build(Scope::MalachiteIngestion).await?;
+
+        let tls = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new(tls, cx.proxy())?;
+
+        let endpoint = self.create_endpoint(self.event_type.path())?;
+
+        // For the healthcheck we see if we can fetch the list of available log types.
+        let healthcheck_endpoint = self.create_endpoint("v2/logtypes")?;
+
+        let healthcheck = build_healthcheck(client.clone(), &healthcheck_endpoint, creds.clone())?;
+        creds.spawn_regenerate_token();
+        let sink = self.build_sink(client, endpoint, creds)?;
+
+        Ok((sink, healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        match self.event_type {
+            ChronicleEventType::Unstructured => {
+                let requirement =
+                    schema::Requirement::empty().required_meaning("timestamp", Kind::timestamp());
+
+                Input::log().with_schema_requirement(requirement)
+            }
+            // UDM events carry their timestamps in their metadata.
+            ChronicleEventType::Udm => Input::log(),
+        }
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+impl ChronicleConfig {
+    fn validate(&self) -> Result<(), ChronicleError> {
+        match self.event_type {
+            ChronicleEventType::Unstructured if self.log_type.is_none() => {
+                Err(ChronicleError::LogTypeRequired)
+            }
+            ChronicleEventType::Udm
+                if !matches!(self.encoding.config(), SerializerConfig::Json(_)) =>
+            {
+                Err(ChronicleError::UdmRequiresJson)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn build_sink(
+        &self,
+        client: HttpClient,
+        base_url: String,
+        creds: GcpAuthenticator,
+    ) -> crate::Result<VectorSink> {
+        use crate::sinks::util::service::ServiceBuilderExt;
+
+        let request = self.request.into_settings();
+
+        let batch_settings = self.batch.into_batcher_settings()?;
+
+        let partitioner = self.key_partitioner()?;
+
+        let svc = ServiceBuilder::new()
+            .settings(request, GcsRetryLogic)
+            .service(ChronicleService::new(client, base_url, creds));
+
+        let request_settings = ChronicleRequestBuilder::new(self)?;
+
+        let sink = GcsSink::new(svc, request_settings, partitioner, batch_settings, "http");
+
+        Ok(VectorSink::from_event_streamsink(sink))
+    }
+
+    fn key_partitioner(&self) -> crate::Result<KeyPartitioner> {
+        // UDM events have no log type, so they are all sent in the same batches.
+        let template = match &self.log_type {
+            Some(log_type) if self.event_type == ChronicleEventType::Unstructured => {
+                log_type.clone()
+            }
+            _ => Template::try_from("")?,
+        };
+        Ok(KeyPartitioner::new(template))
+    }
+
+    fn create_endpoint(&self, path: &str) -> Result<String, ChronicleError> {
+        Ok(format!(
+            "{}/{}",
+            match (&self.endpoint, self.region) {
+                (Some(endpoint), None) => endpoint.trim_end_matches('/'),
+                (None, Some(region)) => region.endpoint(),
+                (Some(_), Some(_)) => return Err(ChronicleError::BothRegionAndEndpoint),
+                (None, None) => return Err(ChronicleError::RegionOrEndpoint),
+            },
+            path
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(config: &str) -> ChronicleConfig {
+        toml::from_str(config).unwrap()
+    }
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<ChronicleConfig>();
+    }
+
+    #[test]
+    fn creates_endpoints_by_event_type() {
+        let unstructured = config(indoc! {r#"
+            customer_id = "customer_id"
+            region = "eu"
+            log_type = "WINDOWS_DNS"
+            encoding.codec = "text"
+        "#});
+        assert!(unstructured.validate().is_ok());
+        assert_eq!(
+            unstructured
+                .create_endpoint(unstructured.event_type.path())
+                .unwrap(),
+            "https://europe-malachiteingestion-pa.googleapis.com/v2/unstructuredlogentries:batchCreate"
+        );
+
+        let udm = config(indoc! {r#"
+            customer_id = "customer_id"
+            endpoint = "http://localhost:8080/"
+            event_type = "udm"
+            encoding.codec = "json"
+        "#});
+        assert!(udm.validate().is_ok());
+        assert_eq!(
+            udm.create_endpoint(udm.event_type.path()).unwrap(),
+            "http://localhost:8080/v2/udmevents:batchCreate"
+        );
+    }
+
+    #[test]
+    fn validates_event_types() {
+        let unstructured = config(indoc! {r#"
+            customer_id = "customer_id"
+            region = "us"
+            encoding.codec = "text"
+        "#});
+        assert!(matches!(
+            unstructured.validate(),
+            Err(ChronicleError::LogTypeRequired)
+        ));
+
+        let udm = config(indoc! {r#"
+            customer_id = "customer_id"
+            region = "us"
+            event_type = "udm"
+            encoding.codec = "text"
+        "#});
+        assert!(matches!(
+            udm.validate(),
+            Err(ChronicleError::UdmRequiresJson)
+        ));
+    }
+}
@@ -3,7 +3,6 @@
 //! for more information.
 use bytes::{Bytes, BytesMut};
 use futures_util::{future::BoxFuture, task::Poll};
-use goauth::scopes::Scope;
 use http::{header::HeaderValue, Request, StatusCode, Uri};
 use hyper::Body;
 use indoc::indoc;
@@ -13,7 +12,7 @@ use snafu::Snafu;
 use std::collections::HashMap;
 use std::io;
 use tokio_util::codec::Encoder as _;
-use tower::{Service, ServiceBuilder};
+use tower::Service;
 use vector_lib::configurable::configurable_component;
 use vector_lib::request_metadata::{GroupedCountByteSize, MetaDescriptive, RequestMetadata};
 use vector_lib::{
@@ -24,6 +23,7 @@ use vector_lib::{
 };
 use vrl::value::Kind;
 
+use super::chronicle::{ChronicleConfig, ChronicleEventType};
 use crate::sinks::util::service::TowerRequestConfigDefaults;
 use crate::{
     codecs::{self, EncodingConfig},
@@ -32,22 +32,17 @@ use crate::{
     http::HttpClient,
     schema,
     sinks::{
-        gcs_common::{
-            config::{healthcheck_response, GcsRetryLogic},
-            service::GcsResponse,
-            sink::GcsSink,
-        },
+        gcs_common::{config::healthcheck_response, service::GcsResponse},
         util::{
             encoding::{as_tracked_write, Encoder},
             metadata::RequestMetadataBuilder,
-            partitioner::KeyPartitioner,
             request_builder::EncodeResult,
             BatchConfig, Compression, RequestBuilder, SinkBatchSettings, TowerRequestConfig,
         },
         Healthcheck,
     },
     template::{Template, TemplateParseError},
-    tls::{TlsConfig, TlsSettings},
+    tls::TlsConfig,
 };
 
 #[derive(Debug, Snafu)]
@@ -77,7 +72,7 @@ pub enum Region {
 
 impl Region {
     /// Each region has a its own endpoint.
-    const fn endpoint(self) -> &'static str {
+    pub(super) const fn endpoint(self) -> &'static str {
         match self {
             Region::Eu => "https://europe-malachiteingestion-pa.googleapis.com",
             Region::Us => "https://malachiteingestion-pa.googleapis.com",
@@ -217,27 +212,17 @@ pub enum ChronicleError {
     RegionOrEndpoint,
     #[snafu(display("You can only specify one of region or endpoint"))]
     BothRegionAndEndpoint,
+    #[snafu(display("log_type is required for unstructured log entries"))]
+    LogTypeRequired,
+    #[snafu(display("UDM events must be encoded with the json codec"))]
+    UdmRequiresJson,
 }
 
 #[async_trait::async_trait]
 #[typetag::serde(name = "gcp_chronicle_unstructured")]
 impl SinkConfig for ChronicleUnstructuredConfig {
     async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
-        let creds = self.auth.build(Scope::MalachiteIngestion).await?;
-
-        let tls = TlsSettings::from_options(&self.tls)?;
-        let client = HttpClient::new(tls, cx.proxy())?;
-
-        let endpoint = self.create_endpoint("v2/unstructuredlogentries:batchCreate")?;
-
-        // For the healthcheck we see if we can fetch the list of available log types.
-        let healthcheck_endpoint = self.create_endpoint("v2/logtypes")?;
-
-        let healthcheck = build_healthcheck(client.clone(), &healthcheck_endpoint, creds.clone())?;
-        creds.spawn_regenerate_token();
-        let sink = self.build_sink(client, endpoint, creds)?;
-
-        Ok((sink, healthcheck))
+        ChronicleConfig::from(self.clone()).build(cx).await
     }
 
     fn input(&self) -> Input {
@@ -252,47 +237,23 @@ impl SinkConfig for ChronicleUnstructuredConfig {
     }
 }
 
-impl ChronicleUnstructuredConfig {
-    fn build_sink(
-        &self,
-        client: HttpClient,
-        base_url: String,
-        creds: GcpAuthenticator,
-    ) -> crate::Result<VectorSink> {
-        use crate::sinks::util::service::ServiceBuilderExt;
-
-        let request = self.request.into_settings();
-
-        let batch_settings = self.batch.into_batcher_settings()?;
-
-        let partitioner = self.key_partitioner()?;
-
-        let svc = ServiceBuilder::new()
-            .settings(request, GcsRetryLogic)
-            .service(ChronicleService::new(client, base_url, creds));
-
-        let request_settings = ChronicleRequestBuilder::new(self)?;
-
-        let sink = GcsSink::new(svc, request_settings, partitioner, batch_settings, "http");
-
-        Ok(VectorSink::from_event_streamsink(sink))
-    }
-
-    fn key_partitioner(&self) -> crate::Result<KeyPartitioner> {
-        Ok(KeyPartitioner::new(self.log_type.clone()))
-    }
-
-    fn create_endpoint(&self, path: &str) -> Result<String, ChronicleError> {
-        Ok(format!(
-            "{}/{}",
-            match (&self.endpoint, self.region) {
-                (Some(endpoint), None) => endpoint.trim_end_matches('/'),
-                (None, Some(region)) => region.endpoint(),
-                (Some(_), Some(_)) => return Err(ChronicleError::BothRegionAndEndpoint),
-                (None, None) => return Err(ChronicleError::RegionOrEndpoint),
-            },
-            path
-        ))
+impl From<ChronicleUnstructuredConfig> for ChronicleConfig {
+    fn from(config: ChronicleUnstructuredConfig) -> Self {
+        Self {
+            endpoint: config.endpoint,
+            region: config.region,
+            customer_id: config.customer_id,
+            event_type: ChronicleEventType::Unstructured,
+            log_type: Some(config.log_type),
+            namespace: config.namespace,
+            labels: config.labels,
+            auth: config.auth,
+            batch: config.batch,
+            encoding: config.encoding,
+            request: config.request,
+            tls: config.tls,
+            acknowledgements: config.acknowledgements,
+        }
     }
 }
 
@@ -330,11 +291,18 @@ struct ChronicleRequestBody {
     entries: Vec<serde_json::Value>,
 }
 
+#[derive(Clone, Debug, Serialize)]
+struct ChronicleUdmRequestBody {
+    customer_id: String,
+    events: Vec<serde_json::Value>,
+}
+
 #[derive(Clone, Debug)]
 struct ChronicleEncoder {
     customer_id: String,
     namespace: Option<String>,
     labels: Option<Vec<Label>>,
+    event_type: ChronicleEventType,
     encoder: codecs::Encoder<()>,
     transformer: codecs::Transformer,
 }
@@ -363,6 +331,13 @@ impl Encoder<(String, Vec<Event>)> for ChronicleEncoder {
 
                 encoder.encode(event, &mut bytes).ok()?;
 
+                if self.event_type == ChronicleEventType::Udm {
+                    // UDM events are sent as the objects they are encoded to.
+                    return serde_json::from_slice::<serde_json::Value>(&bytes)
+                        .ok()
+                        .filter(serde_json::Value::is_object);
+                }
+
                 let mut value = json!({
                     "log_text": String::from_utf8_lossy(&bytes),
                 });
@@ -379,13 +354,19 @@ impl Encoder<(String, Vec<Event>)> for ChronicleEncoder {
             })
             .collect::<Vec<_>>();
 
-        let json = json!(ChronicleRequestBody {
-            customer_id: self.customer_id.clone(),
-            namespace: self.namespace.clone(),
-            labels: self.labels.clone(),
-            log_type: partition_key,
-            entries: events,
-        });
+        let json = match self.event_type {
+            ChronicleEventType::Unstructured => json!(ChronicleRequestBody {
+                customer_id: self.customer_id.clone(),
+                namespace: self.namespace.clone(),
+                labels: self.labels.clone(),
+                log_type: partition_key,
+                entries: events,
+            }),
+            ChronicleEventType::Udm => json!(ChronicleUdmRequestBody {
+                customer_id: self.customer_id.clone(),
+                events,
+            }),
+        };
 
         let size = as_tracked_write::<_, _, io::Error>(writer, &json, |writer, json| {
             serde_json::to_writer(writer, json)?;
@@ -400,7 +381,7 @@ impl Encoder<(String, Vec<Event>)> for ChronicleEncoder {
 // request. All possible values are pre-computed for direct use in
 // producing a request.
 #[derive(Clone, Debug)]
-struct ChronicleRequestBuilder {
+pub(super) struct ChronicleRequestBuilder {
     encoder: ChronicleEncoder,
 }
 
@@ -468,7 +449,7 @@ struct Label {
 }
 
 impl ChronicleRequestBuilder {
-    fn new(config: &ChronicleUnstructuredConfig) -> crate::Result<Self> {
+    pub(super) fn new(config: &ChronicleConfig) -> crate::Result<Self> {
         let transformer = config.encoding.transformer();
         let serializer = config.encoding.config().build()?;
         let encoder = crate::codecs::Encoder::<()>::new(serializer);
@@ -483,6 +464,7 @@ impl ChronicleRequestBuilder {
                     })
                     .collect::<Vec<_>>()
             }),
+            event_type: config.event_type,
             encoder,
             transformer,
         };
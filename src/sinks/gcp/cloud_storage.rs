@@ -12,23 +12,29 @@ use uuid::Uuid;
 use vector_lib::codecs::encoding::Framer;
 use vector_lib::configurable::configurable_component;
 use vector_lib::event::{EventFinalizers, Finalizable};
+use vector_lib::partition::Partitioner;
 use vector_lib::{request_metadata::RequestMetadata, TimeZone};
 
 use crate::sinks::util::metadata::RequestMetadataBuilder;
 use crate::sinks::util::service::TowerRequestConfigDefaults;
 use crate::{
-    codecs::{Encoder, EncodingConfigWithFraming, SinkType, Transformer},
+    codecs::{
+        BatchSerializerConfig, Encoder, EncoderKind, EncodingConfigWithFraming, SinkType,
+        Transformer,
+    },
     config::{AcknowledgementsConfig, DataType, GenerateConfig, Input, SinkConfig, SinkContext},
     event::Event,
     gcp::{GcpAuthConfig, GcpAuthenticator, Scope},
     http::{get_http_scheme_from_uri, HttpClient},
+    internal_events::TemplateRenderingError,
     serde::json::to_string,
     sinks::{
         gcs_common::{
             config::{
-                build_healthcheck, GcsPredefinedAcl, GcsRetryLogic, GcsStorageClass, BASE_URL,
+                build_healthcheck, GcsPredefinedAcl, GcsRetentionConfig, GcsRetryLogic,
+                GcsStorageClass, BASE_URL,
             },
-            service::{GcsRequest, GcsRequestSettings, GcsService},
+            service::{GcsObjectUpdate, GcsRequest, GcsRequestSettings, GcsService},
             sink::GcsSink,
         },
         util::{
@@ -47,6 +53,8 @@ use crate::{
 pub enum GcsHealthcheckError {
     #[snafu(display("key_prefix template parse error: {}", source))]
     KeyPrefixTemplate { source: TemplateParseError },
+    #[snafu(display("metadata template parse error: {}", source))]
+    MetadataTemplate { source: TemplateParseError },
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -84,13 +92,65 @@ pub struct GcsSinkConfig {
 
     /// The set of metadata `key:value` pairs for the created objects.
     ///
+    /// Values can be templates, in which case events are written to different objects depending
+    /// on the values rendered from their fields. Events whose values fail to render are dropped.
+    ///
     /// For more information, see the [custom metadata][custom_metadata] documentation.
     ///
     /// [custom_metadata]: https://cloud.google.com/storage/docs/metadata#custom-metadata
     #[configurable(metadata(docs::additional_props_description = "A key/value pair."))]
+    #[configurable(metadata(docs::examples = "metadata_examples()"))]
     #[configurable(metadata(docs::advanced))]
     metadata: Option<HashMap<String, String>>,
 
+    /// The Cloud KMS key that the created objects are encrypted with.
+    ///
+    /// By default, objects are encrypted with the default key of the bucket, if any, or with a key
+    /// managed by Google. The service account of the Cloud Storage service agent must be allowed
+    /// to use the key.
+    ///
+    /// For more information, see the [customer-managed encryption keys][cmek] documentation.
+    ///
+    /// [cmek]: https://cloud.google.com/storage/docs/encryption/customer-managed-keys
+    #[configurable(metadata(
+        docs::examples = "projects/my-project/locations/us/keyRings/my-key-ring/cryptoKeys/my-key"
+    ))]
+    #[configurable(metadata(docs::advanced))]
+    kms_key_name: Option<String>,
+
+    /// Whether to place a temporary hold on the created objects.
+    ///
+    /// Objects under a hold can't be deleted or replaced until the hold is released. Holds are set
+    /// once the objects are created, which requires the `storage.objects.update` permission.
+    ///
+    /// For more information, see the [object holds][object_holds] documentation.
+    ///
+    /// [object_holds]: https://cloud.google.com/storage/docs/object-holds
+    #[serde(default)]
+    #[configurable(metadata(docs::advanced))]
+    temporary_hold: bool,
+
+    /// Whether to place an event-based hold on the created objects.
+    ///
+    /// Like temporary holds, event-based holds prevent the objects from being deleted or replaced
+    /// until they are released. Releasing an event-based hold also starts the retention period of
+    /// the bucket for the object, if any.
+    ///
+    /// For more information, see the [object holds][object_holds] documentation.
+    ///
+    /// [object_holds]: https://cloud.google.com/storage/docs/object-holds
+    #[serde(default)]
+    #[configurable(metadata(docs::advanced))]
+    event_based_hold: bool,
+
+    /// The retention of the created objects.
+    ///
+    /// This is set once the objects are created, which requires the
+    /// `storage.objects.setRetention` permission.
+    #[configurable(derived)]
+    #[configurable(metadata(docs::advanced))]
+    retention: Option<GcsRetentionConfig>,
+
     /// A prefix to apply to all object keys.
     ///
     /// Prefixes are useful for partitioning objects, such as by creating an object key that
@@ -151,6 +211,15 @@ pub struct GcsSinkConfig {
     #[serde(default)]
     compression: Compression,
 
+    /// Configures encoding all the events of a batch together into a single object.
+    ///
+    /// When set, this is used instead of the `codec` and `framing` of `encoding`, and objects are
+    /// not compressed with `compression`, as the batch codecs compress their own data. Objects are
+    /// written once the batch is full or times out, as configured with `batch`.
+    #[configurable(derived)]
+    #[serde(default)]
+    batch_encoding: Option<BatchSerializerConfig>,
+
     #[configurable(derived)]
     #[serde(default)]
     batch: BatchConfig<BulkSizeBasedDefaultBatchSettings>,
@@ -182,6 +251,19 @@ fn default_time_format() -> String {
     "%s".to_string()
 }
 
+fn metadata_examples() -> HashMap<String, String> {
+    HashMap::from([
+        (
+            "x-goog-meta-retention-class".to_string(),
+            "audit".to_string(),
+        ),
+        (
+            "x-goog-meta-tenant".to_string(),
+            "{{ tenant_id }}".to_string(),
+        ),
+    ])
+}
+
 #[cfg(test)]
 fn default_config(encoding: EncodingConfigWithFraming) -> GcsSinkConfig {
     GcsSinkConfig {
@@ -189,12 +271,17 @@ fn default_config(encoding: EncodingConfigWithFraming) -> GcsSinkConfig {
         acl: Default::default(),
         storage_class: Default::default(),
         metadata: Default::default(),
+        kms_key_name: Default::default(),
+        temporary_hold: false,
+        event_based_hold: false,
+        retention: Default::default(),
         key_prefix: Default::default(),
         filename_time_format: default_time_format(),
         filename_append_uuid: true,
         filename_extension: Default::default(),
         encoding,
         compression: Compression::gzip_default(),
+        batch_encoding: None,
         batch: Default::default(),
         request: Default::default(),
         auth: Default::default(),
@@ -237,7 +324,11 @@ impl SinkConfig for GcsSinkConfig {
     }
 
     fn input(&self) -> Input {
-        Input::new(self.encoding.config().1.input_type() & DataType::Log)
+        if self.batch_encoding.is_some() {
+            Input::log()
+        } else {
+            Input::new(self.encoding.config().1.input_type() & DataType::Log)
+        }
     }
 
     fn acknowledgements(&self) -> &AcknowledgementsConfig {
@@ -257,13 +348,23 @@ impl GcsSinkConfig {
 
         let batch_settings = self.batch.into_batcher_settings()?;
 
-        let partitioner = self.key_partitioner()?;
+        let partitioner = self.partitioner()?;
 
         let protocol = get_http_scheme_from_uri(&base_url.parse::<Uri>().unwrap());
 
+        let object_update = (self.temporary_hold
+            || self.event_based_hold
+            || self.retention.is_some())
+        .then(|| GcsObjectUpdate {
+            objects_url: format!("{}storage/v1/b/{}/o/", BASE_URL, self.bucket),
+            temporary_hold: self.temporary_hold,
+            event_based_hold: self.event_based_hold,
+            retention: self.retention,
+        });
+
         let svc = ServiceBuilder::new()
             .settings(request, GcsRetryLogic)
-            .service(GcsService::new(client, base_url, auth));
+            .service(GcsService::new(client, base_url, auth, object_update));
 
         let request_settings = RequestSettings::new(self, cx)?;
 
@@ -278,6 +379,56 @@ impl GcsSinkConfig {
                 .context(KeyPrefixTemplateSnafu)?,
         ))
     }
+
+    fn partitioner(&self) -> crate::Result<GcsPartitioner> {
+        Ok(GcsPartitioner {
+            key_prefix: self.key_partitioner()?,
+            metadata: split_metadata(self.metadata.as_ref())?.1,
+        })
+    }
+}
+
+// The prefix and the templated metadata of the objects that a batch of events is written to.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+struct GcsPartitionKey {
+    key_prefix: String,
+    metadata: Vec<(HeaderName, String)>,
+}
+
+struct GcsPartitioner {
+    key_prefix: KeyPartitioner,
+    metadata: Vec<(HeaderName, Template)>,
+}
+
+impl Partitioner for GcsPartitioner {
+    type Item = Event;
+    type Key = Option<GcsPartitionKey>;
+
+    fn partition(&self, item: &Self::Item) -> Self::Key {
+        let key_prefix = self.key_prefix.partition(item)?;
+        let metadata = self
+            .metadata
+            .iter()
+            .map(|(name, template)| {
+                template
+                    .render_string(item)
+                    .map(|value| (name.clone(), value))
+                    .map_err(|error| {
+                        emit!(TemplateRenderingError {
+                            error,
+                            field: Some("metadata"),
+                            drop_event: true,
+                        });
+                    })
+                    .ok()
+            })
+            .collect::<Option<_>>()?;
+
+        Some(GcsPartitionKey {
+            key_prefix,
+            metadata,
+        })
+    }
 }
 
 // Settings required to produce a request that do not change per
@@ -293,15 +444,15 @@ struct RequestSettings {
     extension: String,
     time_format: String,
     append_uuid: bool,
-    encoder: (Transformer, Encoder<Framer>),
+    encoder: (Transformer, EncoderKind),
     compression: Compression,
     tz_offset: Option<FixedOffset>,
 }
 
-impl RequestBuilder<(String, Vec<Event>)> for RequestSettings {
-    type Metadata = (String, EventFinalizers);
+impl RequestBuilder<(GcsPartitionKey, Vec<Event>)> for RequestSettings {
+    type Metadata = (GcsPartitionKey, EventFinalizers);
     type Events = Vec<Event>;
-    type Encoder = (Transformer, Encoder<Framer>);
+    type Encoder = (Transformer, EncoderKind);
     type Payload = Bytes;
     type Request = GcsRequest;
     type Error = io::Error;
@@ -316,7 +467,7 @@ impl RequestBuilder<(String, Vec<Event>)> for RequestSettings {
 
     fn split_input(
         &self,
-        input: (String, Vec<Event>),
+        input: (GcsPartitionKey, Vec<Event>),
     ) -> (Self::Metadata, RequestMetadataBuilder, Self::Events) {
         let (partition_key, mut events) = input;
         let finalizers = events.take_finalizers();
@@ -349,11 +500,11 @@ impl RequestBuilder<(String, Vec<Event>)> for RequestSettings {
             }
         };
 
-        let key = format!("{}{}.{}", key, filename, self.extension);
+        let object_key = format!("{}{}.{}", key.key_prefix, filename, self.extension);
         let body = payload.into_payload();
 
         GcsRequest {
-            key,
+            key: object_key,
             body,
             finalizers,
             settings: GcsRequestSettings {
@@ -362,6 +513,7 @@ impl RequestBuilder<(String, Vec<Event>)> for RequestSettings {
                 content_encoding: self.content_encoding.clone(),
                 storage_class: self.storage_class.clone(),
                 headers: self.headers.clone(),
+                rendered_headers: key.metadata,
             },
             metadata,
         }
@@ -371,32 +523,40 @@ impl RequestBuilder<(String, Vec<Event>)> for RequestSettings {
 impl RequestSettings {
     fn new(config: &GcsSinkConfig, cx: SinkContext) -> crate::Result<Self> {
         let transformer = config.encoding.transformer();
-        let (framer, serializer) = config.encoding.build(SinkType::MessageBased)?;
-        let encoder = Encoder::<Framer>::new(framer, serializer);
+        let (encoder, compression) = match &config.batch_encoding {
+            Some(batch_encoding) => (
+                EncoderKind::Batch(batch_encoding.build()?),
+                Compression::None,
+            ),
+            None => {
+                let (framer, serializer) = config.encoding.build(SinkType::MessageBased)?;
+                let encoder = Encoder::<Framer>::new(framer, serializer);
+                (EncoderKind::Framed(encoder), config.compression)
+            }
+        };
         let acl = config
             .acl
             .map(|acl| HeaderValue::from_str(&to_string(acl)).unwrap());
         let content_type = HeaderValue::from_str(encoder.content_type()).unwrap();
-        let content_encoding = config
-            .compression
+        let content_encoding = compression
             .content_encoding()
             .map(|ce| HeaderValue::from_str(&to_string(ce)).unwrap());
         let storage_class = config.storage_class.unwrap_or_default();
         let storage_class = HeaderValue::from_str(&to_string(storage_class)).unwrap();
-        let metadata = config
-            .metadata
-            .as_ref()
-            .map(|metadata| {
-                metadata
-                    .iter()
-                    .map(make_header)
-                    .collect::<Result<Vec<_>, _>>()
-            })
-            .unwrap_or_else(|| Ok(vec![]))?;
-        let extension = config
-            .filename_extension
-            .clone()
-            .unwrap_or_else(|| config.compression.extension().into());
+        let (mut metadata, _) = split_metadata(config.metadata.as_ref())?;
+        if let Some(kms_key_name) = &config.kms_key_name {
+            metadata.push((
+                HeaderName::from_static("x-goog-encryption-kms-key-name"),
+                HeaderValue::from_str(kms_key_name)?,
+            ));
+        }
+        let extension = config.filename_extension.clone().unwrap_or_else(|| {
+            match &encoder {
+                EncoderKind::Batch(serializer) => serializer.extension(),
+                EncoderKind::Framed(_) => compression.extension(),
+            }
+            .into()
+        });
         let time_format = config.filename_time_format.clone();
         let append_uuid = config.filename_append_uuid;
         let offset = config
@@ -413,13 +573,33 @@ impl RequestSettings {
             extension,
             time_format,
             append_uuid,
-            compression: config.compression,
+            compression,
             encoder: (transformer, encoder),
             tz_offset: offset,
         })
     }
 }
 
+// Split the metadata into the headers with a static value, and the ones with a value templated
+// from the event fields.
+fn split_metadata(
+    metadata: Option<&HashMap<String, String>>,
+) -> crate::Result<(Vec<(HeaderName, HeaderValue)>, Vec<(HeaderName, Template)>)> {
+    let mut headers = Vec::new();
+    let mut templates = Vec::new();
+    for (name, value) in metadata.into_iter().flatten() {
+        if value.contains("{{") {
+            templates.push((
+                HeaderName::from_bytes(name.as_bytes())?,
+                Template::try_from(value.as_str()).context(MetadataTemplateSnafu)?,
+            ));
+        } else {
+            headers.push(make_header((name, value))?);
+        }
+    }
+    Ok((headers, templates))
+}
+
 // Make a header pair from a key-value string pair
 fn make_header((name, value): (&String, &String)) -> crate::Result<(HeaderName, HeaderValue)> {
     Ok((
@@ -520,7 +700,7 @@ mod tests {
         };
         let log = LogEvent::default().into();
         let key = sink_config
-            .key_partitioner()
+            .partitioner()
             .unwrap()
             .partition(&log)
             .expect("key wasn't provided");
@@ -551,4 +731,65 @@ mod tests {
         let req = build_request(None, true, Compression::gzip_default());
         assert_ne!(req.key, "key/date.log.gz".to_string());
     }
+
+    #[test]
+    fn gcs_build_parquet_request_settings() {
+        let sink_config = GcsSinkConfig {
+            batch_encoding: Some(BatchSerializerConfig::Parquet {
+                parquet: Default::default(),
+            }),
+            ..default_config((None::<FramingConfig>, TextSerializerConfig::default()).into())
+        };
+        let settings = request_settings(&sink_config, SinkContext::default());
+
+        assert_eq!(settings.extension, "parquet");
+        assert_eq!(settings.content_type, "application/vnd.apache.parquet");
+        assert_eq!(settings.content_encoding, None);
+        assert_eq!(settings.compression, Compression::None);
+        assert!(matches!(settings.encoder.1, EncoderKind::Batch(_)));
+    }
+
+    #[test]
+    fn gcs_build_request_metadata() {
+        let sink_config = GcsSinkConfig {
+            metadata: Some(HashMap::from([
+                ("x-goog-meta-class".to_string(), "audit".to_string()),
+                ("x-goog-meta-tenant".to_string(), "{{ tenant }}".to_string()),
+            ])),
+            kms_key_name: Some("projects/p/locations/us/keyRings/r/cryptoKeys/k".to_string()),
+            ..default_config((None::<FramingConfig>, TextSerializerConfig::default()).into())
+        };
+        let partitioner = sink_config.partitioner().unwrap();
+
+        let mut log = LogEvent::from("message");
+        log.insert("tenant", "acme");
+        let key = partitioner.partition(&log.into()).unwrap();
+        assert_eq!(
+            key.metadata,
+            [(
+                HeaderName::from_static("x-goog-meta-tenant"),
+                "acme".to_string()
+            )]
+        );
+
+        // Events without the fields of the templated metadata are dropped.
+        assert!(partitioner
+            .partition(&LogEvent::from("message").into())
+            .is_none());
+
+        let settings = request_settings(&sink_config, SinkContext::default());
+        assert_eq!(
+            settings.headers,
+            [
+                (
+                    HeaderName::from_static("x-goog-meta-class"),
+                    HeaderValue::from_static("audit")
+                ),
+                (
+                    HeaderName::from_static("x-goog-encryption-kms-key-name"),
+                    HeaderValue::from_static("projects/p/locations/us/keyRings/r/cryptoKeys/k")
+                ),
+            ]
+        );
+    }
 }
@@ -3,6 +3,8 @@ use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
 use vector_lib::configurable::configurable_component;
 
+pub mod bigquery;
+pub mod chronicle;
 pub mod chronicle_unstructured;
 pub mod cloud_storage;
 pub mod pubsub;
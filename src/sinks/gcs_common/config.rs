@@ -1,3 +1,5 @@
+use std::num::NonZeroU64;
+
 use futures::FutureExt;
 use http::{StatusCode, Uri};
 use hyper::Body;
@@ -97,6 +99,39 @@ pub enum GcsStorageClass {
     Archive,
 }
 
+/// GCS object retention modes.
+///
+/// For more information, see [Object Retention Lock][object_retention].
+///
+/// [object_retention]: https://cloud.google.com/storage/docs/object-lock
+#[configurable_component]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GcsRetentionMode {
+    /// The retention of the objects can be shortened or removed by users with the
+    /// `storage.objects.overrideUnlockedRetention` permission.
+    Unlocked,
+
+    /// The retention of the objects can only be extended, and the objects can't be deleted or
+    /// replaced until it expires.
+    Locked,
+}
+
+/// GCS object retention configuration.
+///
+/// The bucket must have Object Retention Lock enabled.
+#[configurable_component]
+#[derive(Clone, Copy, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct GcsRetentionConfig {
+    #[configurable(derived)]
+    pub mode: GcsRetentionMode,
+
+    /// How long the objects are retained for, from the moment they are created.
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    #[configurable(metadata(docs::examples = 2592000))]
+    pub retain_for_secs: NonZeroU64,
+}
+
 #[derive(Debug, Snafu)]
 pub enum GcsError {
     #[snafu(display("Bucket {:?} not found", bucket))]
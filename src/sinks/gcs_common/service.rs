@@ -1,16 +1,20 @@
 use std::task::Poll;
 
 use bytes::Bytes;
-use futures::future::BoxFuture;
+use chrono::{DateTime, Duration, SecondsFormat, Utc};
+use futures::future::{self, BoxFuture};
 use http::{
     header::{HeaderName, HeaderValue},
     Request, Uri,
 };
 use hyper::Body;
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+use serde_json::json;
 use tower::Service;
 use vector_lib::request_metadata::{GroupedCountByteSize, MetaDescriptive, RequestMetadata};
 use vector_lib::stream::DriverResponse;
 
+use super::config::GcsRetentionConfig;
 use crate::{
     event::{EventFinalizers, EventStatus, Finalizable},
     gcp::GcpAuthenticator,
@@ -22,18 +26,70 @@ pub struct GcsService {
     client: HttpClient,
     base_url: String,
     auth: GcpAuthenticator,
+    object_update: Option<GcsObjectUpdate>,
 }
 
 impl GcsService {
-    pub const fn new(client: HttpClient, base_url: String, auth: GcpAuthenticator) -> GcsService {
+    pub const fn new(
+        client: HttpClient,
+        base_url: String,
+        auth: GcpAuthenticator,
+        object_update: Option<GcsObjectUpdate>,
+    ) -> GcsService {
         GcsService {
             client,
             base_url,
             auth,
+            object_update,
         }
     }
 }
 
+// The holds and retention of the created objects. These can't be set with the XML API that objects
+// are written with, so they are set by updating the objects with the JSON API once written.
+#[derive(Clone, Debug)]
+pub struct GcsObjectUpdate {
+    pub objects_url: String,
+    pub temporary_hold: bool,
+    pub event_based_hold: bool,
+    pub retention: Option<GcsRetentionConfig>,
+}
+
+impl GcsObjectUpdate {
+    fn build_request(&self, key: &str) -> Result<Request<Body>, http::Error> {
+        let mut body = serde_json::Map::new();
+        if self.temporary_hold {
+            body.insert("temporaryHold".into(), true.into());
+        }
+        if self.event_based_hold {
+            body.insert("eventBasedHold".into(), true.into());
+        }
+        if let Some(retention) = self.retention {
+            let retain_until = i64::try_from(retention.retain_for_secs.get())
+                .ok()
+                .and_then(Duration::try_seconds)
+                .and_then(|retain_for| Utc::now().checked_add_signed(retain_for))
+                .unwrap_or(DateTime::<Utc>::MAX_UTC);
+            body.insert(
+                "retention".into(),
+                json!({
+                    "mode": retention.mode,
+                    "retainUntilTime": retain_until.to_rfc3339_opts(SecondsFormat::Secs, true),
+                }),
+            );
+        }
+
+        let uri = format!(
+            "{}{}",
+            self.objects_url,
+            utf8_percent_encode(key, NON_ALPHANUMERIC)
+        );
+        Request::patch(uri)
+            .header("content-type", "application/json")
+            .body(Body::from(serde_json::Value::Object(body).to_string()))
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct GcsRequest {
     pub key: String,
@@ -69,6 +125,7 @@ pub struct GcsRequestSettings {
     pub content_encoding: Option<HeaderValue>,
     pub storage_class: HeaderValue,
     pub headers: Vec<(HeaderName, HeaderValue)>,
+    pub rendered_headers: Vec<(HeaderName, String)>,
 }
 
 #[derive(Debug)]
@@ -131,14 +188,75 @@ impl Service<GcsRequest> for GcsService {
         for (p, v) in settings.headers {
             headers.insert(p, v);
         }
+        for (p, v) in settings.rendered_headers {
+            match HeaderValue::from_str(&v) {
+                Ok(v) => headers.insert(p, v),
+                Err(error) => {
+                    return Box::pin(future::err(HttpError::BuildRequest {
+                        source: error.into(),
+                    }))
+                }
+            };
+        }
 
         let mut http_request = builder.body(Body::from(request.body)).unwrap();
         self.auth.apply(&mut http_request);
 
+        let object_update = self
+            .object_update
+            .as_ref()
+            .map(|update| update.build_request(&request.key));
+
+        let auth = self.auth.clone();
         let mut client = self.client.clone();
         Box::pin(async move {
-            let result = client.call(http_request).await;
-            result.map(|inner| GcsResponse { inner, metadata })
+            let mut inner = client.call(http_request).await?;
+
+            if let Some(object_update) = object_update {
+                if inner.status().is_success() {
+                    let mut update_request =
+                        object_update.map_err(|source| HttpError::BuildRequest { source })?;
+                    auth.apply(&mut update_request);
+                    inner = client.call(update_request).await?;
+                }
+            }
+
+            Ok(GcsResponse { inner, metadata })
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU64;
+
+    use super::*;
+    use crate::sinks::gcs_common::config::GcsRetentionMode;
+
+    #[tokio::test]
+    async fn object_update_request() {
+        let update = GcsObjectUpdate {
+            objects_url: "https://storage.googleapis.com/storage/v1/b/my-bucket/o/".to_string(),
+            temporary_hold: true,
+            event_based_hold: false,
+            retention: Some(GcsRetentionConfig {
+                mode: GcsRetentionMode::Locked,
+                retain_for_secs: NonZeroU64::new(86400).unwrap(),
+            }),
+        };
+
+        let request = update.build_request("date=2024-01-01/logs.gz").unwrap();
+        assert_eq!(request.method(), http::Method::PATCH);
+        assert_eq!(
+            request.uri(),
+            "https://storage.googleapis.com/storage/v1/b/my-bucket/o/date%3D2024%2D01%2D01%2Flogs%2Egz"
+        );
+
+        let body = hyper::body::to_bytes(request.into_body()).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["temporaryHold"], true);
+        assert!(body.get("eventBasedHold").is_none());
+        assert_eq!(body["retention"]["mode"], "Locked");
+        assert!(body["retention"]["retainUntilTime"].is_string());
+    }
+}
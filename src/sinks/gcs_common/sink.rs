@@ -1,20 +1,20 @@
-use std::fmt;
+use std::{fmt, hash::Hash};
 
-use crate::sinks::{prelude::*, util::partitioner::KeyPartitioner};
+use crate::sinks::prelude::*;
 
-pub struct GcsSink<Svc, RB> {
+pub struct GcsSink<Svc, RB, P> {
     service: Svc,
     request_builder: RB,
-    partitioner: KeyPartitioner,
+    partitioner: P,
     batcher_settings: BatcherSettings,
     protocol: &'static str,
 }
 
-impl<Svc, RB> GcsSink<Svc, RB> {
+impl<Svc, RB, P> GcsSink<Svc, RB, P> {
     pub const fn new(
         service: Svc,
         request_builder: RB,
-        partitioner: KeyPartitioner,
+        partitioner: P,
         batcher_settings: BatcherSettings,
         protocol: &'static str,
     ) -> Self {
@@ -28,15 +28,17 @@ impl<Svc, RB> GcsSink<Svc, RB> {
     }
 }
 
-impl<Svc, RB> GcsSink<Svc, RB>
+impl<Svc, RB, P, K> GcsSink<Svc, RB, P>
 where
     Svc: Service<RB::Request> + Send + 'static,
     Svc::Future: Send + 'static,
     Svc::Response: DriverResponse + Send + 'static,
     Svc::Error: fmt::Debug + Into<crate::Error> + Send,
-    RB: RequestBuilder<(String, Vec<Event>)> + Send + Sync + 'static,
+    RB: RequestBuilder<(K, Vec<Event>)> + Send + Sync + 'static,
     RB::Error: fmt::Display + Send,
     RB::Request: Finalizable + MetaDescriptive + Send,
+    P: Partitioner<Item = Event, Key = Option<K>> + Unpin + Send,
+    K: Hash + Eq + Clone + Send + 'static,
 {
     async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
         let partitioner = self.partitioner;
@@ -47,7 +49,7 @@ where
         input
             .batched_partitioned(partitioner, || settings.as_byte_size_config())
             .filter_map(|(key, batch)| async move {
-                // A `TemplateRenderingError` will have been emitted by `KeyPartitioner` if the key here is `None`,
+                // A `TemplateRenderingError` will have been emitted by the partitioner if the key here is `None`,
                 // thus no further `EventsDropped` event needs emitting at this stage.
                 key.map(move |k| (k, batch))
             })
@@ -69,15 +71,17 @@ where
 }
 
 #[async_trait]
-impl<Svc, RB> StreamSink<Event> for GcsSink<Svc, RB>
+impl<Svc, RB, P, K> StreamSink<Event> for GcsSink<Svc, RB, P>
 where
     Svc: Service<RB::Request> + Send + 'static,
     Svc::Future: Send + 'static,
     Svc::Response: DriverResponse + Send + 'static,
     Svc::Error: fmt::Debug + Into<crate::Error> + Send,
-    RB: RequestBuilder<(String, Vec<Event>)> + Send + Sync + 'static,
+    RB: RequestBuilder<(K, Vec<Event>)> + Send + Sync + 'static,
     RB::Error: fmt::Display + Send,
     RB::Request: Finalizable + MetaDescriptive + Send,
+    P: Partitioner<Item = Event, Key = Option<K>> + Unpin + Send,
+    K: Hash + Eq + Clone + Send + 'static,
 {
     async fn run(mut self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
         self.run_inner(input).await
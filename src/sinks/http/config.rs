@@ -1,8 +1,11 @@
 //! Configuration for the `http` sink.
 
+use std::str::FromStr;
+
 use http::{header::AUTHORIZATION, HeaderName, HeaderValue, Method, Request, StatusCode};
 use hyper::Body;
 use indexmap::IndexMap;
+use snafu::ResultExt;
 use vector_lib::codecs::{
     encoding::{Framer, Serializer},
     CharacterDelimitedEncoder,
@@ -14,15 +17,18 @@ use crate::{
     sinks::{
         prelude::*,
         util::{
-            http::{http_response_retry_logic, HttpService, RequestConfig},
+            http::{http_response_retry_logic, HeaderValidationError, HttpService, RequestConfig},
             RealtimeSizeBasedDefaultBatchSettings, UriSerde,
         },
+        UriParseSnafu,
     },
 };
 
 use super::{
-    encoder::HttpEncoder, request_builder::HttpRequestBuilder, service::HttpSinkRequestBuilder,
-    sink::HttpSink,
+    encoder::HttpEncoder,
+    request_builder::HttpRequestBuilder,
+    service::HttpSinkRequestBuilder,
+    sink::{HttpSink, HttpSinkPartitioner},
 };
 
 const CONTENT_TYPE_TEXT: &str = "text/plain";
@@ -37,12 +43,25 @@ pub struct HttpSinkConfig {
     /// The full URI to make HTTP requests to.
     ///
     /// This should include the protocol and host, but can also include the port, path, and any other valid part of a URI.
+    ///
+    /// If the URI references event fields, events are batched by the URI rendered for them, so that each
+    /// request is sent to the URI of its events. Otherwise, the URI is used as is, and any
+    /// percent-encoded characters in it are kept.
     #[configurable(metadata(docs::examples = "https://10.22.212.22:9000/endpoint"))]
-    pub uri: UriSerde,
+    #[configurable(metadata(
+        docs::examples = "https://{{ tenant }}.example.com/logs?stream={{ stream }}"
+    ))]
+    #[configurable(metadata(docs::templateable))]
+    pub uri: String,
 
     /// The HTTP method to use when making the request.
-    #[serde(default)]
-    pub method: HttpMethod,
+    ///
+    /// One of `get`, `head`, `post`, `put`, `delete`, `options`, `trace`, or `patch`. If the method
+    /// is templated, events are batched by the method rendered for them.
+    #[serde(default = "default_method")]
+    #[configurable(metadata(docs::examples = "put"))]
+    #[configurable(metadata(docs::examples = "{{ http_method }}"))]
+    pub method: Template,
 
     #[configurable(derived)]
     pub auth: Option<Auth>,
@@ -150,6 +169,42 @@ impl From<HttpMethod> for Method {
     }
 }
 
+impl FromStr for HttpMethod {
+    type Err = crate::Error;
+
+    fn from_str(method: &str) -> Result<Self, Self::Err> {
+        match method.to_ascii_lowercase().as_str() {
+            "get" => Ok(Self::Get),
+            "head" => Ok(Self::Head),
+            "post" => Ok(Self::Post),
+            "put" => Ok(Self::Put),
+            "delete" => Ok(Self::Delete),
+            "options" => Ok(Self::Options),
+            "trace" => Ok(Self::Trace),
+            "patch" => Ok(Self::Patch),
+            _ => Err(format!("Unsupported HTTP method {:?}", method).into()),
+        }
+    }
+}
+
+pub(super) fn default_method() -> Template {
+    Template::try_from("post").unwrap()
+}
+
+/// Parses the template of a URI or header value, if it references event fields.
+///
+/// Values that don't reference event fields aren't parsed as templates, so that the `%` of their
+/// percent-encoded characters isn't taken for a time format specifier.
+fn field_template(field: &str, value: &str) -> crate::Result<Option<Template>> {
+    if !value.contains("{{") {
+        return Ok(None);
+    }
+
+    Template::try_from(value)
+        .map(Some)
+        .map_err(|error| format!("Invalid template for {}: {}", field, error).into())
+}
+
 impl HttpSinkConfig {
     fn build_http_client(&self, cx: &SinkContext) -> crate::Result<HttpClient> {
         let tls = TlsSettings::from_options(&self.tls)?;
@@ -204,6 +259,46 @@ pub(super) fn validate_headers(
     Ok(headers)
 }
 
+/// Splits the headers into the ones with a static value, and the ones with a value templated from
+/// the event fields.
+pub(super) fn split_headers(
+    headers: &IndexMap<String, String>,
+    configures_auth: bool,
+) -> crate::Result<(
+    IndexMap<HeaderName, HeaderValue>,
+    Vec<(HeaderName, Template)>,
+)> {
+    let mut static_headers = IndexMap::new();
+    let mut template_headers = Vec::new();
+
+    for (name, value) in headers {
+        match field_template("header value", value)? {
+            Some(template) => {
+                let name = HeaderName::from_bytes(name.as_bytes()).map_err(|source| {
+                    HeaderValidationError::InvalidHeaderName {
+                        name: name.clone(),
+                        source,
+                    }
+                })?;
+                if configures_auth && name == AUTHORIZATION {
+                    return Err(
+                        "Authorization header can not be used with defined auth options".into(),
+                    );
+                }
+                template_headers.push((name, template));
+            }
+            None => {
+                static_headers.insert(name.clone(), value.clone());
+            }
+        }
+    }
+
+    Ok((
+        validate_headers(&static_headers, configures_auth)?,
+        template_headers,
+    ))
+}
+
 pub(super) fn validate_payload_wrapper(
     payload_prefix: &str,
     payload_suffix: &str,
@@ -236,7 +331,22 @@ impl SinkConfig for HttpSinkConfig {
         let mut request = self.request.clone();
         request.add_old_option(self.headers.clone());
 
-        let headers = validate_headers(&request.headers, self.auth.is_some())?;
+        let (headers, template_headers) = split_headers(&request.headers, self.auth.is_some())?;
+
+        let (uri, auth, uri_template) = match field_template("uri", &self.uri)? {
+            Some(template) => (None, self.auth.clone(), Some(template)),
+            None => {
+                let uri = self.uri.parse::<UriSerde>().context(UriParseSnafu)?;
+                let auth = self.auth.choose_one(&uri.auth)?;
+                (Some(uri.with_default_parts().uri), auth, None)
+            }
+        };
+
+        let (method, method_template) = if self.method.is_dynamic() {
+            (None, Some(self.method.clone()))
+        } else {
+            (Some(self.method.get_ref().parse::<HttpMethod>()?), None)
+        };
 
         let (payload_prefix, payload_suffix) =
             validate_payload_wrapper(&self.payload_prefix, &self.payload_suffix, &encoder)?;
@@ -275,24 +385,28 @@ impl SinkConfig for HttpSinkConfig {
                 .to_string()
         });
 
-        let http_sink_request_builder = HttpSinkRequestBuilder::new(
-            self.uri.with_default_parts(),
-            self.method,
-            self.auth.choose_one(&self.uri.auth)?,
-            headers,
-            content_type,
-            content_encoding,
-        );
+        let http_sink_request_builder =
+            HttpSinkRequestBuilder::new(uri, method, auth, headers, content_type, content_encoding);
 
         let service = HttpService::new(client, http_sink_request_builder);
 
-        let request_limits = self.request.tower.into_settings();
+        let request_limits = self
+            .request
+            .tower
+            .into_settings()
+            .with_request_limits(cx.request_limits.clone());
 
         let service = ServiceBuilder::new()
             .settings(request_limits, http_response_retry_logic())
             .service(service);
 
-        let sink = HttpSink::new(service, batch_settings, request_builder);
+        let partitioner = HttpSinkPartitioner {
+            uri: uri_template,
+            method: method_template,
+            headers: template_headers,
+        };
+
+        let sink = HttpSink::new(service, batch_settings, partitioner, request_builder);
 
         Ok((VectorSink::from_event_streamsink(sink), healthcheck))
     }
@@ -304,6 +418,10 @@ impl SinkConfig for HttpSinkConfig {
     fn acknowledgements(&self) -> &AcknowledgementsConfig {
         &self.acknowledgements
     }
+
+    fn supports_request_limits(&self) -> bool {
+        true
+    }
 }
 
 #[cfg(test)]
@@ -313,14 +431,12 @@ mod tests {
 
     impl ValidatableComponent for HttpSinkConfig {
         fn validation_configuration() -> ValidationConfiguration {
-            use std::str::FromStr;
             use vector_lib::codecs::{JsonSerializerConfig, MetricTagValues};
             use vector_lib::config::LogNamespace;
 
             let config = HttpSinkConfig {
-                uri: UriSerde::from_str("http://127.0.0.1:9000/endpoint")
-                    .expect("should never fail to parse"),
-                method: HttpMethod::Post,
+                uri: "http://127.0.0.1:9000/endpoint".to_owned(),
+                method: default_method(),
                 encoding: EncodingConfigWithFraming::new(
                     None,
                     JsonSerializerConfig::new(MetricTagValues::Full).into(),
@@ -339,7 +455,10 @@ mod tests {
 
             let external_resource = ExternalResource::new(
                 ResourceDirection::Push,
-                HttpResourceConfig::from_parts(config.uri.uri.clone(), Some(config.method.into())),
+                HttpResourceConfig::from_parts(
+                    http::Uri::from_str(&config.uri).expect("should never fail to parse"),
+                    Some(Method::POST),
+                ),
                 config.encoding.clone(),
             );
 
@@ -8,23 +8,25 @@ use http::{
 use indexmap::IndexMap;
 
 use crate::{
-    http::Auth,
+    http::{Auth, MaybeAuth},
     sinks::{
         util::{
             http::{HttpRequest, HttpServiceRequestBuilder},
             UriSerde,
         },
-        HTTPRequestBuilderSnafu,
+        HTTPRequestBuilderSnafu, UriParseSnafu,
     },
 };
 use snafu::ResultExt;
 
-use super::config::HttpMethod;
+use super::{config::HttpMethod, sink::PartitionKey};
 
 #[derive(Debug, Clone)]
 pub(super) struct HttpSinkRequestBuilder {
-    uri: UriSerde,
-    method: HttpMethod,
+    /// The URI of the requests, unless it is rendered from their events.
+    uri: Option<Uri>,
+    /// The method of the requests, unless it is rendered from their events.
+    method: Option<HttpMethod>,
     auth: Option<Auth>,
     headers: IndexMap<HeaderName, HeaderValue>,
     content_type: Option<String>,
@@ -34,8 +36,8 @@ pub(super) struct HttpSinkRequestBuilder {
 impl HttpSinkRequestBuilder {
     /// Creates a new `HttpSinkRequestBuilder`
     pub(super) const fn new(
-        uri: UriSerde,
-        method: HttpMethod,
+        uri: Option<Uri>,
+        method: Option<HttpMethod>,
         auth: Option<Auth>,
         headers: IndexMap<HeaderName, HeaderValue>,
         content_type: Option<String>,
@@ -52,10 +54,31 @@ impl HttpSinkRequestBuilder {
     }
 }
 
-impl HttpServiceRequestBuilder<()> for HttpSinkRequestBuilder {
-    fn build(&self, mut request: HttpRequest<()>) -> Result<Request<Bytes>, crate::Error> {
-        let method: Method = self.method.into();
-        let uri: Uri = self.uri.uri.clone();
+impl HttpServiceRequestBuilder<PartitionKey> for HttpSinkRequestBuilder {
+    fn build(
+        &self,
+        mut request: HttpRequest<PartitionKey>,
+    ) -> Result<Request<Bytes>, crate::Error> {
+        let key = request.get_additional_metadata();
+
+        let (uri, auth) = match (&key.uri, &self.uri) {
+            (Some(uri), _) => {
+                let uri = uri.parse::<UriSerde>().context(UriParseSnafu)?;
+                let auth = self.auth.choose_one(&uri.auth)?;
+                (uri.with_default_parts().uri, auth)
+            }
+            (None, Some(uri)) => (uri.clone(), self.auth.clone()),
+            (None, None) => unreachable!("The URI is rendered for the requests if it isn't set."),
+        };
+
+        let method: Method = match (&key.method, self.method) {
+            (Some(method), _) => method.parse::<HttpMethod>()?.into(),
+            (None, Some(method)) => method.into(),
+            (None, None) => {
+                unreachable!("The method is rendered for the requests if it isn't set.")
+            }
+        };
+
         let mut builder = Request::builder().method(method).uri(uri);
 
         if let Some(content_type) = &self.content_type {
@@ -75,13 +98,17 @@ impl HttpServiceRequestBuilder<()> for HttpSinkRequestBuilder {
             headers.insert(header, value.clone());
         }
 
+        for (header, value) in key.headers.iter() {
+            headers.insert(header, HeaderValue::from_str(value)?);
+        }
+
         // The request building should not have errors at this point
         let mut request = builder
             .body(request.take_payload())
             .context(HTTPRequestBuilderSnafu)
             .map_err(Into::<crate::Error>::into)?;
 
-        if let Some(auth) = &self.auth {
+        if let Some(auth) = auth {
             auth.apply(&mut request);
         }
 
@@ -1,18 +1,82 @@
 //! Implementation of the `http` sink.
 
+use http::HeaderName;
+
 use crate::sinks::{prelude::*, util::http::HttpRequest};
 
 use super::{batch::HttpBatchSizer, request_builder::HttpRequestBuilder};
 
+/// The parts of a request that are rendered from the fields of its events.
+///
+/// The parts that aren't templated are left unset.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub(super) struct PartitionKey {
+    pub(super) uri: Option<String>,
+    pub(super) method: Option<String>,
+    pub(super) headers: Vec<(HeaderName, String)>,
+}
+
+/// Partitions events by the parts of the request they are sent in.
+pub(super) struct HttpSinkPartitioner {
+    pub(super) uri: Option<Template>,
+    pub(super) method: Option<Template>,
+    pub(super) headers: Vec<(HeaderName, Template)>,
+}
+
+impl HttpSinkPartitioner {
+    fn render(template: &Template, item: &Event, field: &'static str) -> Option<String> {
+        template
+            .render_string(item)
+            .map_err(|error| {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some(field),
+                    drop_event: true,
+                });
+            })
+            .ok()
+    }
+}
+
+impl Partitioner for HttpSinkPartitioner {
+    type Item = Event;
+    type Key = Option<PartitionKey>;
+
+    fn partition(&self, item: &Self::Item) -> Self::Key {
+        let uri = match &self.uri {
+            Some(uri) => Some(Self::render(uri, item, "uri")?),
+            None => None,
+        };
+        let method = match &self.method {
+            Some(method) => Some(Self::render(method, item, "method")?),
+            None => None,
+        };
+        let headers = self
+            .headers
+            .iter()
+            .map(|(name, value)| {
+                Self::render(value, item, "request.headers").map(|value| (name.clone(), value))
+            })
+            .collect::<Option<_>>()?;
+
+        Some(PartitionKey {
+            uri,
+            method,
+            headers,
+        })
+    }
+}
+
 pub(super) struct HttpSink<S> {
     service: S,
     batch_settings: BatcherSettings,
+    partitioner: HttpSinkPartitioner,
     request_builder: HttpRequestBuilder,
 }
 
 impl<S> HttpSink<S>
 where
-    S: Service<HttpRequest<()>> + Send + 'static,
+    S: Service<HttpRequest<PartitionKey>> + Send + 'static,
     S::Future: Send + 'static,
     S::Response: DriverResponse + Send + 'static,
     S::Error: std::fmt::Debug + Into<crate::Error> + Send,
@@ -21,21 +85,31 @@ where
     pub(super) const fn new(
         service: S,
         batch_settings: BatcherSettings,
+        partitioner: HttpSinkPartitioner,
         request_builder: HttpRequestBuilder,
     ) -> Self {
         Self {
             service,
             batch_settings,
+            partitioner,
             request_builder,
         }
     }
 
     async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let batch_settings = self.batch_settings;
+        let encoder = self.request_builder.encoder.encoder.clone();
+
         input
-            // Batch the input stream with size calculation based on the configured codec
-            .batched(self.batch_settings.as_item_size_config(HttpBatchSizer {
-                encoder: self.request_builder.encoder.encoder.clone(),
-            }))
+            // Batch the input stream by the rendered parts of the requests, with size calculation
+            // based on the configured codec
+            .batched_partitioned(self.partitioner, || {
+                batch_settings.as_item_size_config(HttpBatchSizer {
+                    encoder: encoder.clone(),
+                })
+            })
+            // Drop the events the parts of the requests couldn't be rendered for.
+            .filter_map(|(key, batch)| async move { key.map(move |key| (key, batch)) })
             // Build requests with default concurrency limit.
             .request_builder(
                 default_request_builder_concurrency_limit(),
@@ -62,7 +136,7 @@ where
 #[async_trait::async_trait]
 impl<S> StreamSink<Event> for HttpSink<S>
 where
-    S: Service<HttpRequest<()>> + Send + 'static,
+    S: Service<HttpRequest<PartitionKey>> + Send + 'static,
     S::Future: Send + 'static,
     S::Response: DriverResponse + Send + 'static,
     S::Error: std::fmt::Debug + Into<crate::Error> + Send,
@@ -37,7 +37,7 @@ use crate::{
 
 use super::{
     config::HttpSinkConfig,
-    config::{validate_headers, validate_payload_wrapper},
+    config::{split_headers, validate_headers, validate_payload_wrapper},
     encoder::HttpEncoder,
 };
 
@@ -49,7 +49,7 @@ fn generate_config() {
 fn default_cfg(encoding: EncodingConfigWithFraming) -> HttpSinkConfig {
     HttpSinkConfig {
         uri: Default::default(),
-        method: Default::default(),
+        method: super::config::default_method(),
         auth: Default::default(),
         headers: Default::default(),
         compression: Default::default(),
@@ -143,6 +143,49 @@ fn http_catches_bad_header_names() {
     );
 }
 
+#[test]
+fn http_splits_templated_headers() {
+    let config = r#"
+        uri = "http://$IN_ADDR/frames"
+        encoding.codec = "text"
+        [request.headers]
+        Accept = "text/plain"
+        X-Tenant = "{{ tenant }}"
+        X-Encoded = "a%20b"
+        "#;
+    let config: HttpSinkConfig = toml::from_str(config).unwrap();
+
+    let (static_headers, template_headers) = split_headers(&config.request.headers, false).unwrap();
+
+    assert_eq!(
+        static_headers.keys().collect::<Vec<_>>(),
+        ["accept", "x-encoded"]
+    );
+    assert_eq!(static_headers[1], "a%20b");
+    assert_eq!(template_headers.len(), 1);
+    assert_eq!(template_headers[0].0, "x-tenant");
+    assert_eq!(template_headers[0].1.get_ref(), "{{ tenant }}");
+}
+
+#[test]
+fn http_catches_templated_authorization_header_with_auth() {
+    let config = r#"
+        uri = "http://$IN_ADDR/frames"
+        encoding.codec = "text"
+        [request.headers]
+        Authorization = "Bearer {{ token }}"
+        "#;
+    let config: HttpSinkConfig = toml::from_str(config).unwrap();
+
+    assert!(split_headers(&config.request.headers, false).is_ok());
+    assert_eq!(
+        split_headers(&config.request.headers, true)
+            .unwrap_err()
+            .to_string(),
+        "Authorization header can not be used with defined auth options"
+    );
+}
+
 #[test]
 fn http_validates_payload_prefix_and_suffix() {
     let config = r#"
@@ -264,6 +307,99 @@ async fn http_passes_custom_headers() {
     .await;
 }
 
+#[tokio::test]
+async fn http_keeps_percent_encoding_of_static_uri() {
+    let config = r#"
+        uri = "http://localhost:9000/logs?source=a%20b"
+        encoding.codec = "json"
+        "#;
+    let config: HttpSinkConfig = toml::from_str(config).unwrap();
+
+    config.build(SinkContext::default()).await.unwrap();
+}
+
+#[tokio::test]
+async fn http_templates_uri_method_and_headers() {
+    let in_addr = next_addr();
+
+    let config = format!(
+        r#"
+        uri = "http://{in_addr}/tenants/{{{{ tenant }}}}?source=vector"
+        method = "{{{{ http_method }}}}"
+        encoding.codec = "json"
+        framing.method = "newline_delimited"
+        [request.headers]
+        X-Tenant = "{{{{ tenant }}}}"
+        X-Static = "static"
+    "#
+    );
+    let config: HttpSinkConfig = toml::from_str(&config).unwrap();
+    let (sink, _) = config.build(SinkContext::default()).await.unwrap();
+
+    let (rx, trigger, server) = build_test_server(in_addr);
+    tokio::spawn(server);
+
+    let events = [
+        ("a", "put", "one"),
+        ("b", "post", "two"),
+        ("a", "put", "three"),
+    ]
+    .into_iter()
+    .map(|(tenant, method, message)| {
+        let mut log = LogEvent::from(message);
+        log.insert("tenant", tenant);
+        log.insert("http_method", method);
+        Event::Log(log)
+    })
+    .collect::<Vec<_>>();
+    components::run_and_assert_sink_compliance(sink, stream::iter(events), &HTTP_SINK_TAGS).await;
+    drop(trigger);
+
+    let mut requests = rx
+        .map(|(parts, body)| {
+            assert_eq!(Some("source=vector"), parts.uri.query());
+            assert_eq!(
+                Some("static"),
+                parts.headers.get("x-static").map(|v| v.to_str().unwrap())
+            );
+            let messages = body
+                .split(|byte| *byte == b'\n')
+                .filter(|line| !line.is_empty())
+                .map(|line| {
+                    let line: serde_json::Value = serde_json::from_slice(line).unwrap();
+                    line["message"].as_str().unwrap().to_owned()
+                })
+                .collect::<Vec<_>>();
+            (
+                parts.uri.path().to_owned(),
+                parts.method,
+                parts.headers["x-tenant"].to_str().unwrap().to_owned(),
+                messages,
+            )
+        })
+        .collect::<Vec<_>>()
+        .await;
+    requests.sort_by(|a, b| a.0.cmp(&b.0));
+
+    assert_eq!(
+        requests,
+        [
+            (
+                "/tenants/a".to_owned(),
+                Method::PUT,
+                "a".to_owned(),
+                vec!["one".to_owned(), "three".to_owned()]
+            ),
+            (
+                "/tenants/b".to_owned(),
+                Method::POST,
+                "b".to_owned(),
+                vec!["two".to_owned()]
+            ),
+        ]
+    );
+}
+
 #[tokio::test]
 async fn retries_on_no_connection() {
     components::assert_sink_compliance(&HTTP_SINK_TAGS, async {
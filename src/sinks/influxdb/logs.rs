@@ -242,14 +242,15 @@ impl SinkConfig for InfluxDbLogsConfig {
     }
 }
 
-struct InfluxDbLogsEncoder {
-    protocol_version: ProtocolVersion,
-    measurement: String,
-    tags: HashSet<KeyString>,
-    transformer: Transformer,
-    host_key: OwnedValuePath,
-    message_key: OwnedValuePath,
-    source_type_key: OwnedValuePath,
+#[derive(Clone)]
+pub(super) struct InfluxDbLogsEncoder {
+    pub(super) protocol_version: ProtocolVersion,
+    pub(super) measurement: String,
+    pub(super) tags: HashSet<KeyString>,
+    pub(super) transformer: Transformer,
+    pub(super) host_key: OwnedValuePath,
+    pub(super) message_key: OwnedValuePath,
+    pub(super) source_type_key: OwnedValuePath,
 }
 
 impl HttpEventEncoder<BytesMut> for InfluxDbLogsEncoder {
@@ -380,7 +381,7 @@ impl InfluxDbLogsConfig {
     }
 }
 
-fn to_field(value: &Value) -> Field {
+pub(in crate::sinks) fn to_field(value: &Value) -> Field {
     match value {
         Value::Integer(num) => Field::Int(*num),
         Value::Float(num) => Field::Float(num.into_inner()),
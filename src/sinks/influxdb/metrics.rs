@@ -272,7 +272,7 @@ impl MetricNormalize for InfluxMetricNormalize {
     }
 }
 
-fn encode_events(
+pub(super) fn encode_events(
     protocol_version: ProtocolVersion,
     events: Vec<Metric>,
     default_namespace: Option<&str>,
@@ -313,7 +313,7 @@ fn encode_events(
     output
 }
 
-fn get_type_and_fields(
+pub(in crate::sinks) fn get_type_and_fields(
     value: &MetricValue,
     quantiles: &[f64],
 ) -> (&'static str, Option<HashMap<KeyString, Field>>) {
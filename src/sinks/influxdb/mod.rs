@@ -1,5 +1,6 @@
 pub mod logs;
 pub mod metrics;
+pub mod v3;
 
 use std::collections::HashMap;
 
@@ -0,0 +1,242 @@
+//! Configuration for the `influxdb3` sink.
+
+use std::collections::HashSet;
+
+use http::{Request, StatusCode};
+use hyper::Body;
+use vector_lib::{config::log_schema, event::KeyString, sensitive_string::SensitiveString};
+
+use super::{
+    encoder::InfluxDb3Encoder,
+    request_builder::InfluxDb3RequestBuilder,
+    service::InfluxDb3ServiceRequestBuilder,
+    sink::{InfluxDb3Sink, PartitionKey},
+};
+use crate::{
+    http::HttpClient,
+    sinks::{
+        influxdb::{
+            encode_uri, logs::InfluxDbLogsEncoder, metrics::default_summary_quantiles,
+            ProtocolVersion,
+        },
+        prelude::*,
+        util::{
+            http::{http_response_retry_logic, HttpService},
+            statistic::validate_quantiles,
+        },
+    },
+};
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct InfluxDb3DefaultBatchSettings;
+
+impl SinkBatchSettings for InfluxDb3DefaultBatchSettings {
+    const MAX_EVENTS: Option<usize> = None;
+    const MAX_BYTES: Option<usize> = Some(1_000_000);
+    const TIMEOUT_SECS: f64 = 1.0;
+}
+
+/// Configuration for the `influxdb3` sink.
+#[configurable_component(sink("influxdb3", "Deliver log and metric event data to InfluxDB 3.x."))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct InfluxDb3Config {
+    /// The endpoint to send data to.
+    ///
+    /// This should be a full HTTP URI, including the scheme, host, and port.
+    #[configurable(metadata(docs::examples = "https://cluster-id.a.influxdb.io"))]
+    #[configurable(metadata(docs::examples = "http://localhost:8181"))]
+    pub endpoint: String,
+
+    /// The database that events are written to.
+    ///
+    /// Events are batched by database, so that the events of each tenant can be written to their
+    /// own database.
+    #[configurable(metadata(docs::examples = "vector"))]
+    #[configurable(metadata(docs::examples = "{{ tenant }}-metrics"))]
+    pub database: Template,
+
+    /// The [database token][token_docs] to authenticate with.
+    ///
+    /// The token must have write permissions on all the databases that events are written to.
+    ///
+    /// [token_docs]: https://docs.influxdata.com/influxdb/cloud-dedicated/admin/tokens/
+    #[configurable(metadata(docs::examples = "${INFLUXDB_TOKEN}"))]
+    pub token: SensitiveString,
+
+    /// The name of the measurement that log events are written to.
+    #[serde(default = "default_measurement")]
+    #[configurable(metadata(docs::examples = "vector-logs"))]
+    pub measurement: String,
+
+    /// The list of names of log fields that should be added as tags to each measurement.
+    ///
+    /// By default Vector adds `metric_type` as well as the configured `log_schema.host_key` and
+    /// `log_schema.source_type_key` options.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "field1"))]
+    #[configurable(metadata(docs::examples = "parent.child_field"))]
+    pub tags: Vec<KeyString>,
+
+    /// Sets the default namespace for any metrics sent.
+    ///
+    /// This namespace is only used if a metric has no existing namespace. When a namespace is
+    /// present, it is used as a prefix to the metric name, and separated with a period (`.`).
+    #[configurable(metadata(docs::examples = "service"))]
+    pub default_namespace: Option<String>,
+
+    /// The list of quantiles to calculate when sending distribution metrics.
+    #[serde(default = "default_summary_quantiles")]
+    pub quantiles: Vec<f64>,
+
+    #[configurable(derived)]
+    #[serde(default = "Compression::gzip_default")]
+    pub compression: Compression,
+
+    #[configurable(derived)]
+    #[serde(skip_serializing_if = "crate::serde::is_default", default)]
+    pub encoding: Transformer,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch: BatchConfig<InfluxDb3DefaultBatchSettings>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::is_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+fn default_measurement() -> String {
+    "logs".to_string()
+}
+
+impl GenerateConfig for InfluxDb3Config {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"endpoint = "https://cluster-id.a.influxdb.io"
+            database = "vector"
+            token = "${INFLUXDB_TOKEN}""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "influxdb3")]
+impl SinkConfig for InfluxDb3Config {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        validate_quantiles(&self.quantiles)?;
+
+        let tls_settings = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new(tls_settings, cx.proxy())?;
+
+        let service_request_builder = InfluxDb3ServiceRequestBuilder {
+            endpoint: self.endpoint.clone(),
+            token: self.token.clone(),
+            compression: self.compression,
+        };
+
+        let service: HttpService<InfluxDb3ServiceRequestBuilder, PartitionKey> =
+            HttpService::new(client.clone(), service_request_builder);
+
+        let service = ServiceBuilder::new()
+            .settings(self.request.into_settings(), http_response_retry_logic())
+            .service(service);
+
+        let request_builder = InfluxDb3RequestBuilder {
+            compression: self.compression,
+            encoder: InfluxDb3Encoder {
+                logs: self.logs_encoder(),
+                default_namespace: self.default_namespace.clone(),
+                quantiles: self.quantiles.clone(),
+            },
+        };
+
+        let sink = InfluxDb3Sink {
+            batch_settings: self.batch.validate()?.into_batcher_settings()?,
+            service,
+            database: self.database.clone(),
+            request_builder,
+        };
+
+        let healthcheck = Box::pin(healthcheck(
+            client,
+            self.endpoint.clone(),
+            self.token.clone(),
+        ));
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::new(DataType::Log | DataType::Metric)
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+impl InfluxDb3Config {
+    fn logs_encoder(&self) -> InfluxDbLogsEncoder {
+        InfluxDbLogsEncoder {
+            protocol_version: ProtocolVersion::V2,
+            measurement: self.measurement.clone(),
+            tags: self.tags.iter().cloned().collect::<HashSet<_>>(),
+            transformer: self.encoding.clone(),
+            host_key: log_schema()
+                .host_key()
+                .cloned()
+                .expect("global log_schema.host_key to be valid path"),
+            message_key: log_schema()
+                .message_key()
+                .cloned()
+                .expect("global log_schema.message_key to be valid path"),
+            source_type_key: log_schema()
+                .source_type_key()
+                .cloned()
+                .expect("global log_schema.source_type_key to be valid path"),
+        }
+    }
+}
+
+// https://docs.influxdata.com/influxdb3/core/reference/api/
+async fn healthcheck(
+    client: HttpClient,
+    endpoint: String,
+    token: SensitiveString,
+) -> crate::Result<()> {
+    let uri = encode_uri(&endpoint, "ping", &[])?;
+    let request = Request::get(uri)
+        .header("Authorization", format!("Bearer {}", token.inner()))
+        .body(Body::empty())
+        .unwrap();
+
+    let response = client.send(request).await?;
+
+    match response.status() {
+        StatusCode::OK | StatusCode::NO_CONTENT => Ok(()),
+        status => Err(HealthcheckError::UnexpectedStatus { status }.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<InfluxDb3Config>();
+    }
+}
@@ -0,0 +1,136 @@
+//! Encoding of events into the line protocol for the `influxdb3` sink.
+
+use std::io;
+
+use bytes::{BufMut, BytesMut};
+
+use crate::sinks::{
+    influxdb::{logs::InfluxDbLogsEncoder, metrics::encode_events, ProtocolVersion},
+    prelude::*,
+    util::http::HttpEventEncoder,
+};
+
+/// Encodes batches of log and metric events into lines of the line protocol.
+#[derive(Clone)]
+pub(super) struct InfluxDb3Encoder {
+    pub(super) logs: InfluxDbLogsEncoder,
+    pub(super) default_namespace: Option<String>,
+    pub(super) quantiles: Vec<f64>,
+}
+
+impl encoding::Encoder<Vec<Event>> for InfluxDb3Encoder {
+    fn encode_input(
+        &self,
+        input: Vec<Event>,
+        writer: &mut dyn io::Write,
+    ) -> io::Result<(usize, GroupedCountByteSize)> {
+        let mut byte_size = telemetry().create_request_count_byte_size();
+        for event in &input {
+            byte_size.add_event(event, event.estimated_json_encoded_size_of());
+        }
+
+        let len = input.len();
+        // The logs encoder records the tags it finds on the events, so each batch gets its own.
+        let mut logs = self.logs.clone();
+        let mut metrics = Vec::new();
+        let mut body = BytesMut::new();
+        for event in input {
+            match event {
+                Event::Log(_) => {
+                    if let Some(line) = logs.encode_event(event) {
+                        body.extend_from_slice(&line);
+                    }
+                }
+                Event::Metric(metric) => metrics.push(metric),
+                Event::Trace(_) => {}
+            }
+        }
+
+        if !metrics.is_empty() {
+            body.extend_from_slice(&encode_events(
+                ProtocolVersion::V2,
+                metrics,
+                self.default_namespace.as_deref(),
+                None,
+                &self.quantiles,
+            ));
+            body.put_u8(b'\n');
+        }
+
+        write_all(writer, len, body.as_ref())?;
+
+        Ok((body.len(), byte_size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use vector_lib::{
+        config::log_schema,
+        event::{Metric, MetricKind, MetricValue},
+        metric_tags,
+    };
+
+    use super::*;
+    use crate::sinks::influxdb::test_util::{assert_fields, split_line_protocol, ts};
+
+    fn encoder() -> InfluxDb3Encoder {
+        InfluxDb3Encoder {
+            logs: InfluxDbLogsEncoder {
+                protocol_version: ProtocolVersion::V2,
+                measurement: "logs".to_string(),
+                tags: HashSet::from(["region".into()]),
+                transformer: Default::default(),
+                host_key: log_schema().host_key().cloned().unwrap(),
+                message_key: log_schema().message_key().cloned().unwrap(),
+                source_type_key: log_schema().source_type_key().cloned().unwrap(),
+            },
+            default_namespace: Some("vector".to_string()),
+            quantiles: vec![0.5],
+        }
+    }
+
+    fn encode(events: Vec<Event>) -> String {
+        let mut output = Vec::new();
+        encoding::Encoder::encode_input(&encoder(), events, &mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn encodes_logs_and_metrics() {
+        let mut log = LogEvent::from("hello");
+        log.insert("region", "eu-west");
+        log.insert("status", 200);
+        log.insert("request.path", "/");
+        log.insert("timestamp", ts());
+
+        let gauge = Metric::new(
+            "uptime",
+            MetricKind::Absolute,
+            MetricValue::Gauge { value: 12.0 },
+        )
+        .with_tags(Some(metric_tags!("region" => "eu-west")))
+        .with_timestamp(Some(ts()));
+
+        let output = encode(vec![log.into(), gauge.into()]);
+        let lines = output.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 2);
+        assert!(output.ends_with('\n'));
+
+        let (measurement, tags, fields, timestamp) = split_line_protocol(lines[0]);
+        assert_eq!(measurement, "logs");
+        assert_eq!(tags, "metric_type=logs,region=eu-west");
+        assert_fields(
+            fields.to_string(),
+            ["message=\"hello\"", "request.path=\"/\"", "status=200i"].to_vec(),
+        );
+        assert_eq!(timestamp, "1542182950000000011");
+
+        assert_eq!(
+            lines[1],
+            "vector.uptime,metric_type=gauge,region=eu-west value=12 1542182950000000011"
+        );
+    }
+}
@@ -0,0 +1,32 @@
+//! The `influxdb3` sink.
+//!
+//! This sink writes log and metric events to InfluxDB 3.x, such as InfluxDB Cloud Dedicated and
+//! InfluxDB Clustered, through the HTTP write API of the [InfluxDB line protocol][ilp]. InfluxDB 3.x
+//! serves queries over Arrow Flight, but writes are only accepted over HTTP.
+//!
+//! Events are written to the database rendered from the `database` template, authenticated with
+//! a database token, and batched by database, so that the events of each tenant can be routed to
+//! their own database.
+//!
+//! Events are converted to lines of the line protocol the same way as by the `influxdb_logs` and
+//! `influxdb_metrics` sinks:
+//!
+//! - Log events are written to the configured measurement. The fields listed in `tags`, along
+//!   with the host and source type of the events, are written as tags, and the other fields as
+//!   fields of the type of their values, with the paths of nested fields as their names. The
+//!   timestamp of the events is written as the timestamp of the lines.
+//! - Metric events are written to the measurement named `{namespace}.{name}` after them, with
+//!   their tags and a `metric_type` tag, and their values as fields, such as `value` for gauges.
+//!
+//! [ilp]: https://docs.influxdata.com/influxdb/cloud-dedicated/reference/syntax/line-protocol/
+
+mod config;
+mod encoder;
+mod request_builder;
+mod service;
+mod sink;
+
+#[cfg(test)]
+mod tests;
+
+pub use self::config::InfluxDb3Config;
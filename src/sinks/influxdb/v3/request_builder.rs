@@ -0,0 +1,51 @@
+//! `RequestBuilder` implementation for the `influxdb3` sink.
+
+use std::io;
+
+use bytes::Bytes;
+
+use super::{encoder::InfluxDb3Encoder, sink::PartitionKey};
+use crate::sinks::{prelude::*, util::http::HttpRequest};
+
+pub(super) struct InfluxDb3RequestBuilder {
+    pub(super) compression: Compression,
+    pub(super) encoder: InfluxDb3Encoder,
+}
+
+impl RequestBuilder<(PartitionKey, Vec<Event>)> for InfluxDb3RequestBuilder {
+    type Metadata = (PartitionKey, EventFinalizers);
+    type Events = Vec<Event>;
+    type Encoder = InfluxDb3Encoder;
+    type Payload = Bytes;
+    type Request = HttpRequest<PartitionKey>;
+    type Error = io::Error;
+
+    fn compression(&self) -> Compression {
+        self.compression
+    }
+
+    fn encoder(&self) -> &Self::Encoder {
+        &self.encoder
+    }
+
+    fn split_input(
+        &self,
+        input: (PartitionKey, Vec<Event>),
+    ) -> (Self::Metadata, RequestMetadataBuilder, Self::Events) {
+        let (key, mut events) = input;
+
+        let finalizers = events.take_finalizers();
+        let builder = RequestMetadataBuilder::from_events(&events);
+        ((key, finalizers), builder, events)
+    }
+
+    fn build_request(
+        &self,
+        metadata: Self::Metadata,
+        request_metadata: RequestMetadata,
+        payload: EncodeResult<Self::Payload>,
+    ) -> Self::Request {
+        let (key, finalizers) = metadata;
+        HttpRequest::new(payload.into_payload(), finalizers, request_metadata, key)
+    }
+}
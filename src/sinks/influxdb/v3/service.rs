@@ -0,0 +1,60 @@
+//! Service implementation for the `influxdb3` sink.
+
+use bytes::Bytes;
+use http::{
+    header::{AUTHORIZATION, CONTENT_ENCODING, CONTENT_LENGTH, CONTENT_TYPE},
+    Request,
+};
+use snafu::ResultExt;
+use vector_lib::sensitive_string::SensitiveString;
+
+use super::sink::PartitionKey;
+use crate::sinks::{
+    influxdb::encode_uri,
+    prelude::*,
+    util::http::{HttpRequest, HttpServiceRequestBuilder},
+    HTTPRequestBuilderSnafu,
+};
+
+#[derive(Debug, Clone)]
+pub(super) struct InfluxDb3ServiceRequestBuilder {
+    pub(super) endpoint: String,
+    pub(super) token: SensitiveString,
+    pub(super) compression: Compression,
+}
+
+impl HttpServiceRequestBuilder<PartitionKey> for InfluxDb3ServiceRequestBuilder {
+    fn build(
+        &self,
+        mut request: HttpRequest<PartitionKey>,
+    ) -> Result<Request<Bytes>, crate::Error> {
+        // InfluxDB 3.x accepts writes to a database through the v2 compatible write API, with the
+        // database as the bucket.
+        // https://docs.influxdata.com/influxdb/cloud-dedicated/reference/api/
+        let uri = encode_uri(
+            &self.endpoint,
+            "api/v2/write",
+            &[
+                (
+                    "bucket",
+                    Some(request.get_additional_metadata().database.clone()),
+                ),
+                ("precision", Some("ns".to_owned())),
+            ],
+        )?;
+        let payload = request.take_payload();
+
+        let mut builder = Request::post(uri)
+            .header(CONTENT_TYPE, "text/plain; charset=utf-8")
+            .header(CONTENT_LENGTH, payload.len())
+            .header(AUTHORIZATION, format!("Bearer {}", self.token.inner()));
+        if let Some(ce) = self.compression.content_encoding() {
+            builder = builder.header(CONTENT_ENCODING, ce);
+        }
+
+        builder
+            .body(payload)
+            .context(HTTPRequestBuilderSnafu)
+            .map_err(Into::into)
+    }
+}
@@ -0,0 +1,103 @@
+//! Implementation of the `influxdb3` sink.
+
+use super::request_builder::InfluxDb3RequestBuilder;
+use crate::sinks::{
+    influxdb::metrics::InfluxMetricNormalize,
+    prelude::*,
+    util::{buffer::metrics::MetricNormalizer, http::HttpRequest},
+};
+
+/// The database that events are written to.
+#[derive(Hash, Eq, PartialEq, Clone, Debug)]
+pub(super) struct PartitionKey {
+    pub(super) database: String,
+}
+
+/// Partitions events by the database they are written to.
+struct DatabasePartitioner {
+    database: Template,
+}
+
+impl Partitioner for DatabasePartitioner {
+    type Item = Event;
+    type Key = Option<PartitionKey>;
+
+    fn partition(&self, item: &Self::Item) -> Self::Key {
+        let database = self
+            .database
+            .render_string(item)
+            .map_err(|error| {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some("database"),
+                    drop_event: true,
+                });
+            })
+            .ok()?;
+        Some(PartitionKey { database })
+    }
+}
+
+pub(super) struct InfluxDb3Sink<S> {
+    pub(super) batch_settings: BatcherSettings,
+    pub(super) service: S,
+    pub(super) database: Template,
+    pub(super) request_builder: InfluxDb3RequestBuilder,
+}
+
+impl<S> InfluxDb3Sink<S>
+where
+    S: Service<HttpRequest<PartitionKey>> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: std::fmt::Debug + Into<crate::Error> + Send,
+{
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let batch_settings = self.batch_settings;
+        let partitioner = DatabasePartitioner {
+            database: self.database,
+        };
+        let mut normalizer = MetricNormalizer::<InfluxMetricNormalize>::default();
+
+        input
+            // Counters are written as the difference to their previous value, the same way as by
+            // the `influxdb_metrics` sink.
+            .filter_map(move |event| {
+                future::ready(match event {
+                    Event::Metric(metric) => normalizer.normalize(metric).map(Event::Metric),
+                    event => Some(event),
+                })
+            })
+            .batched_partitioned(partitioner, || batch_settings.as_byte_size_config())
+            .filter_map(|(key, batch)| async move { key.map(move |key| (key, batch)) })
+            .request_builder(
+                default_request_builder_concurrency_limit(),
+                self.request_builder,
+            )
+            .filter_map(|request| async {
+                match request {
+                    Err(error) => {
+                        emit!(SinkRequestBuildError { error });
+                        None
+                    }
+                    Ok(req) => Some(req),
+                }
+            })
+            .into_driver(self.service)
+            .run()
+            .await
+    }
+}
+
+#[async_trait::async_trait]
+impl<S> StreamSink<Event> for InfluxDb3Sink<S>
+where
+    S: Service<HttpRequest<PartitionKey>> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: std::fmt::Debug + Into<crate::Error> + Send,
+{
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
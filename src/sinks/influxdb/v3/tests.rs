@@ -0,0 +1,79 @@
+use futures::StreamExt;
+use indoc::indoc;
+
+use super::InfluxDb3Config;
+use crate::{
+    config::SinkConfig,
+    event::{Event, LogEvent},
+    sinks::{influxdb::test_util::ts, util::test::build_test_server},
+    test_util::{
+        self,
+        components::{run_and_assert_sink_compliance, HTTP_SINK_TAGS},
+    },
+};
+
+fn log(tenant: &str, message: &str) -> Event {
+    let mut log = LogEvent::from(message);
+    log.insert("tenant", tenant);
+    log.insert("timestamp", ts());
+    log.into()
+}
+
+#[tokio::test]
+async fn writes_events_by_database() {
+    let addr = test_util::next_addr();
+    let (rx, trigger, server) = build_test_server(addr);
+    tokio::spawn(server);
+
+    let config: InfluxDb3Config = toml::from_str(&format!(
+        indoc! {r#"
+            endpoint = "http://{}"
+            database = "{{{{ tenant }}}}-logs"
+            token = "my-token"
+            tags = ["tenant"]
+            compression = "none"
+        "#},
+        addr
+    ))
+    .unwrap();
+
+    let (sink, _healthcheck) = config
+        .build(crate::config::SinkContext::default())
+        .await
+        .unwrap();
+    let events = vec![log("a", "hello"), log("b", "world"), log("a", "again")];
+    run_and_assert_sink_compliance(sink, futures::stream::iter(events), &HTTP_SINK_TAGS).await;
+
+    drop(trigger);
+
+    let mut requests = rx
+        .map(|(parts, body)| {
+            assert_eq!(parts.method, "POST");
+            assert_eq!(parts.uri.path(), "/api/v2/write");
+            assert_eq!(parts.headers["authorization"], "Bearer my-token");
+            (
+                parts.uri.query().unwrap().to_string(),
+                String::from_utf8(body.to_vec()).unwrap(),
+            )
+        })
+        .collect::<Vec<_>>()
+        .await;
+    requests.sort();
+
+    assert_eq!(
+        requests,
+        [
+            (
+                "bucket=a-logs&precision=ns".to_string(),
+                "logs,metric_type=logs,tenant=a message=\"hello\" 1542182950000000011\n\
+                 logs,metric_type=logs,tenant=a message=\"again\" 1542182950000000011\n"
+                    .to_string(),
+            ),
+            (
+                "bucket=b-logs&precision=ns".to_string(),
+                "logs,metric_type=logs,tenant=b message=\"world\" 1542182950000000011\n"
+                    .to_string(),
+            ),
+        ]
+    );
+}
@@ -0,0 +1,217 @@
+use std::sync::Arc;
+
+use http::{uri::Scheme, Uri};
+use snafu::{ResultExt, Snafu};
+use tokio::sync::Mutex;
+use tonic::transport::{Certificate, ClientTlsConfig, Endpoint, Identity};
+
+use super::{
+    encoder::BatchEncoder,
+    proto::collector_service_client::CollectorServiceClient,
+    service::{JaegerRetryLogic, JaegerService, Transport},
+    sink::JaegerSink,
+};
+use crate::sinks::{prelude::*, util::service::net::UdpConnectorConfig};
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Invalid endpoint URI: {}", source))]
+    Uri { source: http::uri::InvalidUri },
+    #[snafu(display("Could not create endpoint: {}", source))]
+    Endpoint { source: tonic::transport::Error },
+    #[snafu(display("Could not set up endpoint TLS settings: {}", source))]
+    EndpointTls { source: tonic::transport::Error },
+}
+
+/// The default maximum size of the datagrams sent to agents, which is the one of Jaeger clients.
+const fn default_max_packet_size() -> usize {
+    65_000
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct JaegerDefaultBatchSettings;
+
+impl SinkBatchSettings for JaegerDefaultBatchSettings {
+    const MAX_EVENTS: Option<usize> = None;
+    // Stays under the default limit of 4 MiB of the messages received by Jaeger collectors.
+    const MAX_BYTES: Option<usize> = Some(4_000_000);
+    const TIMEOUT_SECS: f64 = 1.0;
+}
+
+/// Configuration of the gRPC endpoint of a Jaeger collector.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct GrpcConfig {
+    /// The gRPC endpoint of the collector.
+    #[configurable(metadata(docs::examples = "http://localhost:14250"))]
+    #[configurable(metadata(docs::examples = "https://jaeger-collector.example.com:14250"))]
+    pub endpoint: String,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+}
+
+/// Configuration of the UDP endpoint of a Jaeger agent.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct UdpConfig {
+    #[serde(flatten)]
+    pub socket: UdpConnectorConfig,
+
+    /// The maximum size of the datagrams sent to the agent.
+    ///
+    /// Spans are split into as many datagrams as needed, and spans which can't fit into a
+    /// datagram on their own are rejected. This must not be larger than the maximum packet size
+    /// of the agent.
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    #[serde(default = "default_max_packet_size")]
+    pub max_packet_size: usize,
+}
+
+/// The endpoint that spans are exported to.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+#[configurable(metadata(docs::enum_tag_description = "The endpoint that spans are exported to."))]
+pub enum Mode {
+    /// Post spans to a Jaeger collector over gRPC, in the Protobuf model of Jaeger.
+    Grpc(GrpcConfig),
+
+    /// Emit spans to a Jaeger agent over UDP, in the Thrift compact protocol.
+    Udp(UdpConfig),
+}
+
+/// Configuration for the `jaeger` sink.
+#[configurable_component(sink("jaeger", "Export trace events to Jaeger."))]
+#[derive(Clone, Debug)]
+pub struct JaegerConfig {
+    #[serde(flatten)]
+    pub mode: Mode,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch: BatchConfig<JaegerDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::is_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+impl GenerateConfig for JaegerConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"mode = "grpc"
+            endpoint = "http://localhost:14250""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "jaeger")]
+impl SinkConfig for JaegerConfig {
+    async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let batch_settings = self.batch.into_batcher_settings()?;
+
+        let (transport, encoder, healthcheck, protocol) = match &self.mode {
+            Mode::Grpc(config) => (
+                Transport::Grpc(CollectorServiceClient::new(config.channel()?)),
+                BatchEncoder::default(),
+                Box::pin(async { Ok(()) }) as Healthcheck,
+                "grpc",
+            ),
+            Mode::Udp(config) => {
+                let connector = config.socket.as_connector();
+                (
+                    Transport::Udp {
+                        service: Arc::new(Mutex::new(connector.service())),
+                        max_packet_size: config.max_packet_size,
+                    },
+                    BatchEncoder {
+                        max_packet_size: Some(config.max_packet_size),
+                    },
+                    connector.healthcheck(),
+                    "udp",
+                )
+            }
+        };
+
+        let service = ServiceBuilder::new()
+            .settings(self.request.into_settings(), JaegerRetryLogic)
+            .service(JaegerService::new(transport));
+
+        let sink = JaegerSink {
+            batch_settings,
+            service,
+            encoder,
+            protocol,
+        };
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::trace()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+impl GrpcConfig {
+    fn channel(&self) -> crate::Result<tonic::transport::Channel> {
+        let uri: Uri = self.endpoint.parse().context(UriSnafu)?;
+
+        let mut endpoint: Endpoint = uri.to_string().parse().context(EndpointSnafu)?;
+        if uri.scheme() != Some(&Scheme::HTTP) {
+            let tls = TlsSettings::from_options(&self.tls)?;
+            let mut tls_config = ClientTlsConfig::new();
+            if let Some(host) = uri.host() {
+                tls_config = tls_config.domain_name(host);
+            }
+            if let Some((cert, key)) = tls.identity_pem() {
+                tls_config = tls_config.identity(Identity::from_pem(cert, key));
+            }
+            for authority in tls.authorities_pem() {
+                tls_config = tls_config.ca_certificate(Certificate::from_pem(authority));
+            }
+            endpoint = endpoint.tls_config(tls_config).context(EndpointTlsSnafu)?;
+        }
+
+        Ok(endpoint.connect_lazy())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<JaegerConfig>();
+    }
+
+    #[test]
+    fn parses_udp_mode() {
+        let config: JaegerConfig = toml::from_str(
+            r#"
+            mode = "udp"
+            address = "localhost:6831"
+            "#,
+        )
+        .unwrap();
+        match config.mode {
+            Mode::Udp(config) => assert_eq!(config.max_packet_size, 65_000),
+            Mode::Grpc(_) => panic!("expected the udp mode"),
+        }
+    }
+}
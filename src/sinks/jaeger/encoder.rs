@@ -0,0 +1,129 @@
+//! Encoding of trace events into the batches of spans of requests.
+
+use std::num::NonZeroUsize;
+
+use indexmap::IndexMap;
+use prost::Message;
+
+use super::{model::span_from_trace, proto::Batch, service::JaegerRequest, thrift};
+use crate::sinks::prelude::*;
+
+/// Converts trace events into spans, grouped into a batch per process.
+#[derive(Default)]
+pub(super) struct BatchEncoder {
+    /// The maximum size of the datagrams that spans are sent in, with the `udp` mode.
+    pub(super) max_packet_size: Option<usize>,
+}
+
+impl BatchEncoder {
+    /// Encodes the events into a request, rejecting the spans too large for a datagram.
+    ///
+    /// Returns `None` if none of the events could be encoded.
+    pub(super) fn encode(&self, events: Vec<Event>) -> Option<JaegerRequest> {
+        let mut batches: IndexMap<Vec<u8>, Batch> = IndexMap::new();
+        let mut finalizers = EventFinalizers::default();
+        let mut byte_size = telemetry().create_request_count_byte_size();
+        let mut count = 0;
+
+        for mut event in events {
+            let event_finalizers = event.take_finalizers();
+            let mut event_size = telemetry().create_request_count_byte_size();
+            event_size.add_event(&event, event.estimated_json_encoded_size_of());
+
+            let Event::Trace(trace) = event else {
+                // The sink only accepts traces.
+                event_finalizers.update_status(EventStatus::Rejected);
+                continue;
+            };
+            let (process, span) = span_from_trace(trace);
+
+            if let Some(max_packet_size) = self.max_packet_size {
+                if !thrift::fits_datagram(&process, &span, max_packet_size) {
+                    emit!(SinkRequestBuildError {
+                        error: format!(
+                            "Span {:?} is larger than the maximum packet size of {} bytes.",
+                            span.operation_name, max_packet_size
+                        )
+                    });
+                    event_finalizers.update_status(EventStatus::Rejected);
+                    continue;
+                }
+            }
+
+            // Processes hold floating point tags, so they are grouped by their encoding.
+            batches
+                .entry(process.encode_to_vec())
+                .or_insert_with(|| Batch {
+                    spans: Vec::new(),
+                    process: Some(process),
+                })
+                .spans
+                .push(span);
+            count += 1;
+            byte_size += event_size;
+            finalizers.merge(event_finalizers);
+        }
+
+        if count == 0 {
+            return None;
+        }
+
+        let batches: Vec<Batch> = batches.into_values().collect();
+        let request_size = batches.iter().map(Message::encoded_len).sum();
+        let metadata = RequestMetadataBuilder::new(count, request_size, byte_size)
+            .with_request_size(NonZeroUsize::new(request_size).unwrap_or(NonZeroUsize::MIN));
+
+        Some(JaegerRequest {
+            batches,
+            finalizers,
+            metadata,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_lib::event::{BatchNotifier, BatchStatus, TraceEvent};
+
+    use super::*;
+
+    fn trace(service: &str, name: &str) -> Event {
+        let mut trace = TraceEvent::default();
+        trace.insert("resources", vrl::value!({"service.name": service}));
+        trace.insert("name", name);
+        trace.into()
+    }
+
+    #[test]
+    fn groups_spans_by_process() {
+        let request = BatchEncoder::default()
+            .encode(vec![
+                trace("api", "GET /"),
+                trace("db", "SELECT"),
+                trace("api", "POST /"),
+            ])
+            .unwrap();
+
+        assert_eq!(request.batches.len(), 2);
+        assert_eq!(
+            request.batches[0].process.as_ref().unwrap().service_name,
+            "api"
+        );
+        assert_eq!(request.batches[0].spans.len(), 2);
+        assert_eq!(request.batches[1].spans.len(), 1);
+        assert_eq!(request.metadata.event_count(), 3);
+    }
+
+    #[tokio::test]
+    async fn rejects_spans_larger_than_datagrams() {
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        let event = trace("api", &"a".repeat(1000)).with_batch_notifier(&batch);
+        drop(batch);
+
+        let encoder = BatchEncoder {
+            max_packet_size: Some(500),
+        };
+        assert!(encoder.encode(vec![event]).is_none());
+        assert_eq!(receiver.await, BatchStatus::Rejected);
+    }
+}
@@ -0,0 +1,31 @@
+//! The `jaeger` sink.
+//!
+//! This sink sends trace events as spans to [Jaeger][jaeger], either to a collector with the
+//! gRPC API of its Protobuf model, or to an agent with the UDP transport of its Thrift model, as
+//! Jaeger clients do. Trace events are read as the OTLP spans of the `opentelemetry` source and
+//! converted into Jaeger spans, which are grouped into a batch per process.
+//!
+//! With the `udp` mode, the batches are split into as many `emitBatch` datagrams as their spans
+//! require, and the spans too large to fit into a datagram of their own are rejected.
+//!
+//! [jaeger]: https://www.jaegertracing.io/
+
+mod config;
+mod encoder;
+mod model;
+mod service;
+mod sink;
+mod thrift;
+
+// prost emits some generated code that includes clones on `Arc`
+// objects, which causes a clippy ding on this block. We don't
+// directly control the generated code, so allow this lint here.
+#[allow(clippy::clone_on_ref_ptr)]
+// https://github.com/hyperium/tonic/issues/1350
+#[allow(clippy::missing_const_for_fn)]
+#[allow(warnings)]
+mod proto {
+    include!(concat!(env!("OUT_DIR"), "/jaeger.api_v2.rs"));
+}
+
+pub use self::config::JaegerConfig;
@@ -0,0 +1,369 @@
+//! Conversion of trace events into the spans of the Protobuf model of Jaeger.
+//!
+//! Trace events are read as OTLP spans, as received by the `opentelemetry` source, and converted
+//! following the [translation of OpenTelemetry spans to Jaeger][jaeger_translation].
+//!
+//! [jaeger_translation]: https://opentelemetry.io/docs/specs/otel/trace/sdk_exporters/jaeger/
+
+use vector_lib::{
+    event::TraceEvent,
+    opentelemetry::{
+        encode::ResourceRecord,
+        proto::{
+            common::v1::{any_value::Value as PBValue, AnyValue, KeyValue as OtlpKeyValue},
+            trace::v1::{span::SpanKind, status::StatusCode, Span as OtlpSpan},
+        },
+    },
+};
+
+use super::proto::{KeyValue, Log, Process, Span, SpanRef, SpanRefType, ValueType};
+
+/// The service name of the resources without a `service.name` attribute, as the OpenTelemetry
+/// SDKs name them.
+const UNKNOWN_SERVICE: &str = "unknown_service";
+const SERVICE_NAME_KEY: &str = "service.name";
+
+/// The flag of the spans that were sampled.
+const SAMPLED_FLAG: u32 = 1;
+
+fn string_tag(key: impl Into<String>, value: impl Into<String>) -> KeyValue {
+    KeyValue {
+        key: key.into(),
+        v_type: ValueType::String.into(),
+        v_str: value.into(),
+        ..Default::default()
+    }
+}
+
+fn bool_tag(key: impl Into<String>, value: bool) -> KeyValue {
+    KeyValue {
+        key: key.into(),
+        v_type: ValueType::Bool.into(),
+        v_bool: value,
+        ..Default::default()
+    }
+}
+
+/// Renders the arrays and maps of attributes as JSON, as Jaeger tags have no such types.
+fn any_value_to_json(value: PBValue) -> serde_json::Value {
+    match value {
+        PBValue::StringValue(value) => value.into(),
+        PBValue::BoolValue(value) => value.into(),
+        PBValue::IntValue(value) => value.into(),
+        PBValue::DoubleValue(value) => value.into(),
+        PBValue::BytesValue(value) => hex::encode(value).into(),
+        PBValue::ArrayValue(array) => array
+            .values
+            .into_iter()
+            .map(|value| {
+                value
+                    .value
+                    .map_or(serde_json::Value::Null, any_value_to_json)
+            })
+            .collect(),
+        PBValue::KvlistValue(list) => list
+            .values
+            .into_iter()
+            .map(|kv| {
+                let value = kv
+                    .value
+                    .and_then(|value| value.value)
+                    .map_or(serde_json::Value::Null, any_value_to_json);
+                (kv.key, value)
+            })
+            .collect::<serde_json::Map<_, _>>()
+            .into(),
+    }
+}
+
+fn tag(key: String, value: Option<AnyValue>) -> KeyValue {
+    match value.and_then(|value| value.value) {
+        Some(PBValue::StringValue(value)) => string_tag(key, value),
+        Some(PBValue::BoolValue(value)) => bool_tag(key, value),
+        Some(PBValue::IntValue(value)) => KeyValue {
+            key,
+            v_type: ValueType::Int64.into(),
+            v_int64: value,
+            ..Default::default()
+        },
+        Some(PBValue::DoubleValue(value)) => KeyValue {
+            key,
+            v_type: ValueType::Float64.into(),
+            v_float64: value,
+            ..Default::default()
+        },
+        Some(PBValue::BytesValue(value)) => KeyValue {
+            key,
+            v_type: ValueType::Binary.into(),
+            v_binary: value,
+            ..Default::default()
+        },
+        Some(value) => string_tag(key, any_value_to_json(value).to_string()),
+        None => string_tag(key, ""),
+    }
+}
+
+fn tags(attributes: Vec<OtlpKeyValue>) -> Vec<KeyValue> {
+    attributes
+        .into_iter()
+        .map(|kv| tag(kv.key, kv.value))
+        .collect()
+}
+
+fn timestamp(nanos: u64) -> prost_types::Timestamp {
+    prost_types::Timestamp {
+        seconds: (nanos / 1_000_000_000) as i64,
+        nanos: (nanos % 1_000_000_000) as i32,
+    }
+}
+
+fn duration(nanos: u64) -> prost_types::Duration {
+    prost_types::Duration {
+        seconds: (nanos / 1_000_000_000) as i64,
+        nanos: (nanos % 1_000_000_000) as i32,
+    }
+}
+
+/// Converts the trace event into a span, and the process it was emitted by.
+pub(super) fn span_from_trace(trace: TraceEvent) -> (Process, Span) {
+    let ResourceRecord {
+        resource,
+        scope,
+        record: span,
+    } = ResourceRecord::<OtlpSpan>::from(trace);
+
+    let mut service_name = None;
+    let process_tags = resource
+        .attributes
+        .into_iter()
+        .filter_map(|kv| match (kv.key.as_str(), &kv.value) {
+            (
+                SERVICE_NAME_KEY,
+                Some(AnyValue {
+                    value: Some(PBValue::StringValue(name)),
+                }),
+            ) => {
+                service_name = Some(name.clone());
+                None
+            }
+            _ => Some(tag(kv.key, kv.value)),
+        })
+        .collect();
+    let process = Process {
+        service_name: service_name.unwrap_or_else(|| UNKNOWN_SERVICE.to_string()),
+        tags: process_tags,
+    };
+
+    let mut references = Vec::new();
+    if !span.parent_span_id.is_empty() {
+        references.push(SpanRef {
+            trace_id: span.trace_id.clone(),
+            span_id: span.parent_span_id.clone(),
+            ref_type: SpanRefType::ChildOf.into(),
+        });
+    }
+    references.extend(span.links.iter().map(|link| SpanRef {
+        trace_id: link.trace_id.clone(),
+        span_id: link.span_id.clone(),
+        ref_type: SpanRefType::FollowsFrom.into(),
+    }));
+
+    let kind = match span.kind() {
+        SpanKind::Server => Some("server"),
+        SpanKind::Client => Some("client"),
+        SpanKind::Producer => Some("producer"),
+        SpanKind::Consumer => Some("consumer"),
+        SpanKind::Internal => Some("internal"),
+        SpanKind::Unspecified => None,
+    };
+
+    let mut tags = tags(span.attributes);
+    if let Some(kind) = kind {
+        tags.push(string_tag("span.kind", kind));
+    }
+    if let Some(scope) = scope {
+        if !scope.name.is_empty() {
+            tags.push(string_tag("otel.scope.name", scope.name));
+        }
+        if !scope.version.is_empty() {
+            tags.push(string_tag("otel.scope.version", scope.version));
+        }
+    }
+    if let Some(status) = span.status {
+        match status.code() {
+            StatusCode::Unset => {}
+            StatusCode::Ok => tags.push(string_tag("otel.status_code", "OK")),
+            StatusCode::Error => {
+                tags.push(string_tag("otel.status_code", "ERROR"));
+                tags.push(bool_tag("error", true));
+            }
+        }
+        if !status.message.is_empty() {
+            tags.push(string_tag("otel.status_description", status.message));
+        }
+    }
+
+    let logs = span
+        .events
+        .into_iter()
+        .map(|event| {
+            let mut fields = vec![string_tag("event", event.name)];
+            fields.extend(self::tags(event.attributes));
+            Log {
+                timestamp: Some(timestamp(event.time_unix_nano)),
+                fields,
+            }
+        })
+        .collect();
+
+    let span = Span {
+        trace_id: span.trace_id,
+        span_id: span.span_id,
+        operation_name: span.name,
+        references,
+        flags: SAMPLED_FLAG,
+        start_time: Some(timestamp(span.start_time_unix_nano)),
+        duration: Some(duration(
+            span.end_time_unix_nano
+                .saturating_sub(span.start_time_unix_nano),
+        )),
+        tags,
+        logs,
+        ..Default::default()
+    };
+
+    (process, span)
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_lib::{
+        event::Event,
+        opentelemetry::proto::{
+            common::v1::KeyValueList,
+            resource::v1::Resource,
+            trace::v1::{span::Event as SpanEvent, ResourceSpans, ScopeSpans, Status},
+        },
+    };
+
+    use super::*;
+
+    fn kv(key: &str, value: PBValue) -> OtlpKeyValue {
+        OtlpKeyValue {
+            key: key.into(),
+            value: Some(AnyValue { value: Some(value) }),
+        }
+    }
+
+    fn trace(resource_attributes: Vec<OtlpKeyValue>) -> TraceEvent {
+        let spans = ResourceSpans {
+            resource: Some(Resource {
+                attributes: resource_attributes,
+                dropped_attributes_count: 0,
+            }),
+            scope_spans: vec![ScopeSpans {
+                scope: None,
+                spans: vec![OtlpSpan {
+                    trace_id: hex::decode("0000000000000001000000000000000a").unwrap(),
+                    span_id: hex::decode("000000000000000b").unwrap(),
+                    parent_span_id: hex::decode("000000000000000c").unwrap(),
+                    name: "GET /users".into(),
+                    kind: SpanKind::Server.into(),
+                    start_time_unix_nano: 1_500_000_000_000_000_000,
+                    end_time_unix_nano: 1_500_000_000_250_000_000,
+                    attributes: vec![
+                        kv("http.status_code", PBValue::IntValue(500)),
+                        kv("http.route", PBValue::StringValue("/users".into())),
+                    ],
+                    events: vec![SpanEvent {
+                        time_unix_nano: 1_500_000_000_100_000_000,
+                        name: "exception".into(),
+                        attributes: vec![kv(
+                            "exception.type",
+                            PBValue::StringValue("Timeout".into()),
+                        )],
+                        dropped_attributes_count: 0,
+                    }],
+                    status: Some(Status {
+                        message: "internal error".into(),
+                        code: StatusCode::Error.into(),
+                    }),
+                    ..Default::default()
+                }],
+                schema_url: String::new(),
+            }],
+            schema_url: String::new(),
+        };
+
+        match spans.into_event_iter().next() {
+            Some(Event::Trace(trace)) => trace,
+            _ => unreachable!("spans are converted into trace events"),
+        }
+    }
+
+    fn find<'a>(tags: &'a [KeyValue], key: &str) -> &'a KeyValue {
+        tags.iter().find(|tag| tag.key == key).unwrap()
+    }
+
+    #[test]
+    fn converts_processes() {
+        let (process, _) = span_from_trace(trace(vec![
+            kv("service.name", PBValue::StringValue("api".into())),
+            kv("host.name", PBValue::StringValue("web-1".into())),
+        ]));
+        assert_eq!(process.service_name, "api");
+        assert_eq!(process.tags, vec![string_tag("host.name", "web-1")]);
+
+        let (process, _) = span_from_trace(trace(Vec::new()));
+        assert_eq!(process.service_name, UNKNOWN_SERVICE);
+    }
+
+    #[test]
+    fn converts_spans() {
+        let (_, span) = span_from_trace(trace(Vec::new()));
+
+        assert_eq!(
+            span.trace_id,
+            hex::decode("0000000000000001000000000000000a").unwrap()
+        );
+        assert_eq!(span.span_id, hex::decode("000000000000000b").unwrap());
+        assert_eq!(span.operation_name, "GET /users");
+        assert_eq!(
+            span.references,
+            vec![SpanRef {
+                trace_id: span.trace_id.clone(),
+                span_id: hex::decode("000000000000000c").unwrap(),
+                ref_type: SpanRefType::ChildOf.into(),
+            }]
+        );
+        assert_eq!(span.start_time.unwrap().seconds, 1_500_000_000);
+        assert_eq!(span.duration.unwrap().nanos, 250_000_000);
+
+        assert_eq!(find(&span.tags, "http.status_code").v_int64, 500);
+        assert_eq!(find(&span.tags, "http.route").v_str, "/users");
+        assert_eq!(find(&span.tags, "span.kind").v_str, "server");
+        assert_eq!(find(&span.tags, "otel.status_code").v_str, "ERROR");
+        assert!(find(&span.tags, "error").v_bool);
+        assert_eq!(
+            find(&span.tags, "otel.status_description").v_str,
+            "internal error"
+        );
+
+        assert_eq!(span.logs.len(), 1);
+        assert_eq!(find(&span.logs[0].fields, "event").v_str, "exception");
+        assert_eq!(
+            find(&span.logs[0].fields, "exception.type").v_str,
+            "Timeout"
+        );
+    }
+
+    #[test]
+    fn renders_structured_attributes_as_json() {
+        let value = AnyValue {
+            value: Some(PBValue::KvlistValue(KeyValueList {
+                values: vec![kv("a", PBValue::IntValue(1))],
+            })),
+        };
+        assert_eq!(tag("map".to_string(), Some(value)).v_str, r#"{"a":1}"#);
+    }
+}
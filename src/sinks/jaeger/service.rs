@@ -0,0 +1,195 @@
+//! Service implementation for the `jaeger` sink.
+
+use std::{
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use prost::Message;
+use snafu::{ResultExt, Snafu};
+use tokio::sync::Mutex;
+use tonic::{transport::Channel, Code};
+use tower::ServiceExt;
+
+use super::{
+    proto::{self, collector_service_client::CollectorServiceClient, PostSpansRequest},
+    thrift,
+};
+use crate::sinks::{
+    prelude::*,
+    util::service::net::{NetError, NetworkService},
+};
+
+/// The codes of the gRPC errors which are retried, as the ones of the OTLP specification.
+const RETRIABLE_CODES: [Code; 6] = [
+    Code::Cancelled,
+    Code::DeadlineExceeded,
+    Code::Aborted,
+    Code::OutOfRange,
+    Code::Unavailable,
+    Code::DataLoss,
+];
+
+#[derive(Debug, Snafu)]
+pub(super) enum JaegerError {
+    #[snafu(display("Jaeger request failed: {}", source))]
+    Grpc { source: tonic::Status },
+    #[snafu(display("Failed to send spans to the Jaeger agent: {}", source))]
+    Udp { source: NetError },
+}
+
+#[derive(Clone)]
+pub(super) struct JaegerRequest {
+    /// The batches of spans, one per process.
+    pub(super) batches: Vec<proto::Batch>,
+    pub(super) finalizers: EventFinalizers,
+    pub(super) metadata: RequestMetadata,
+}
+
+impl Finalizable for JaegerRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        std::mem::take(&mut self.finalizers)
+    }
+}
+
+impl MetaDescriptive for JaegerRequest {
+    fn get_metadata(&self) -> &RequestMetadata {
+        &self.metadata
+    }
+
+    fn metadata_mut(&mut self) -> &mut RequestMetadata {
+        &mut self.metadata
+    }
+}
+
+#[derive(Debug)]
+pub(super) struct JaegerResponse {
+    events_byte_size: GroupedCountByteSize,
+    byte_size: usize,
+}
+
+impl DriverResponse for JaegerResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> &GroupedCountByteSize {
+        &self.events_byte_size
+    }
+
+    fn bytes_sent(&self) -> Option<usize> {
+        Some(self.byte_size)
+    }
+}
+
+#[derive(Clone, Default)]
+pub(super) struct JaegerRetryLogic;
+
+impl RetryLogic for JaegerRetryLogic {
+    type Error = JaegerError;
+    type Response = JaegerResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        match error {
+            JaegerError::Grpc { source } => RETRIABLE_CODES.contains(&source.code()),
+            // The agent may not be listening yet, or the socket may have to be reconnected.
+            JaegerError::Udp { .. } => true,
+        }
+    }
+}
+
+/// The transport that spans are sent with.
+#[derive(Clone)]
+pub(super) enum Transport {
+    /// Posts the batches to a collector.
+    Grpc(CollectorServiceClient<Channel>),
+    /// Emits the batches to an agent, in Thrift datagrams of at most the given size.
+    Udp {
+        service: Arc<Mutex<NetworkService>>,
+        max_packet_size: usize,
+    },
+}
+
+#[derive(Clone)]
+pub(super) struct JaegerService {
+    transport: Transport,
+}
+
+impl JaegerService {
+    pub(super) const fn new(transport: Transport) -> Self {
+        Self { transport }
+    }
+}
+
+impl Service<JaegerRequest> for JaegerService {
+    type Response = JaegerResponse;
+    type Error = JaegerError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: JaegerRequest) -> Self::Future {
+        let service = self.clone();
+
+        Box::pin(async move {
+            let byte_size = match service.transport {
+                Transport::Grpc(mut client) => {
+                    let mut byte_size = 0;
+                    for batch in request.batches {
+                        byte_size += batch.encoded_len();
+                        client
+                            .post_spans(PostSpansRequest { batch: Some(batch) })
+                            .await
+                            .context(GrpcSnafu)?;
+                    }
+                    byte_size
+                }
+                Transport::Udp {
+                    service,
+                    max_packet_size,
+                } => {
+                    // Datagrams are sent one at a time, in order, over the connected socket.
+                    let mut service = service.lock().await;
+                    let mut byte_size = 0;
+                    for batch in &request.batches {
+                        for datagram in thrift::emit_batch_datagrams(batch, max_packet_size) {
+                            byte_size += service
+                                .ready()
+                                .await
+                                .context(UdpSnafu)?
+                                .call(datagram)
+                                .await
+                                .context(UdpSnafu)?;
+                        }
+                    }
+                    byte_size
+                }
+            };
+
+            Ok(JaegerResponse {
+                events_byte_size: request
+                    .metadata
+                    .into_events_estimated_json_encoded_byte_size(),
+                byte_size,
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retries_transient_errors() {
+        let logic = JaegerRetryLogic;
+        assert!(logic.is_retriable_error(&JaegerError::Grpc {
+            source: tonic::Status::unavailable("unavailable")
+        }));
+        assert!(!logic.is_retriable_error(&JaegerError::Grpc {
+            source: tonic::Status::invalid_argument("invalid")
+        }));
+    }
+}
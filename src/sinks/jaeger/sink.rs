@@ -0,0 +1,40 @@
+//! Implementation of the `jaeger` sink.
+
+use super::{
+    encoder::BatchEncoder,
+    service::{JaegerRetryLogic, JaegerService},
+};
+use crate::sinks::prelude::*;
+
+pub(super) struct JaegerSink {
+    pub(super) batch_settings: BatcherSettings,
+    pub(super) service: Svc<JaegerService, JaegerRetryLogic>,
+    pub(super) encoder: BatchEncoder,
+    /// The protocol of the transport, `grpc` or `udp`.
+    pub(super) protocol: &'static str,
+}
+
+impl JaegerSink {
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let batch_settings = self.batch_settings;
+        let encoder = self.encoder;
+
+        input
+            .batched(batch_settings.as_byte_size_config())
+            .filter_map(move |events| {
+                let request = encoder.encode(events);
+                async move { request }
+            })
+            .into_driver(self.service)
+            .protocol(self.protocol)
+            .run()
+            .await
+    }
+}
+
+#[async_trait::async_trait]
+impl StreamSink<Event> for JaegerSink {
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
@@ -0,0 +1,426 @@
+//! Encoding of batches into the `emitBatch` calls of the [Thrift compact protocol][compact], as
+//! received by the UDP endpoint of Jaeger agents.
+//!
+//! The messages are those of [`jaeger.thrift`][jaeger_thrift], written from the Protobuf model of
+//! the collector, which differs from the Thrift model in its value types and in carrying the IDs
+//! as bytes.
+//!
+//! [compact]: https://github.com/apache/thrift/blob/master/doc/specs/thrift-compact-protocol.md
+//! [jaeger_thrift]: https://github.com/jaegertracing/jaeger-idl/blob/main/thrift/jaeger.thrift
+
+use super::proto::{Batch, KeyValue, Log, Process, Span, SpanRef, SpanRefType, ValueType};
+
+const PROTOCOL_ID: u8 = 0x82;
+const VERSION: u8 = 1;
+const MESSAGE_TYPE_ONEWAY: u8 = 4;
+
+const TYPE_BOOLEAN_TRUE: u8 = 1;
+const TYPE_BOOLEAN_FALSE: u8 = 2;
+const TYPE_I32: u8 = 5;
+const TYPE_I64: u8 = 6;
+const TYPE_DOUBLE: u8 = 7;
+const TYPE_BINARY: u8 = 8;
+const TYPE_LIST: u8 = 9;
+const TYPE_STRUCT: u8 = 12;
+
+// The values of the `TagType` enum of `jaeger.thrift`.
+const TAG_TYPE_STRING: i32 = 0;
+const TAG_TYPE_DOUBLE: i32 = 1;
+const TAG_TYPE_BOOL: i32 = 2;
+const TAG_TYPE_LONG: i32 = 3;
+const TAG_TYPE_BINARY: i32 = 4;
+
+/// The size of the message header and of the framing of the batch in a datagram, besides its
+/// process and spans, with room for the largest list header.
+const DATAGRAM_OVERHEAD: usize = 2 + 1 + 1 + "emitBatch".len() + 1 + 1 + 1 + 1 + 5 + 1 + 1;
+
+/// Writes the values of the compact protocol, keeping track of the IDs of the fields of the
+/// structs being written, as the headers of fields are relative to the previous field.
+#[derive(Default)]
+struct Writer {
+    buf: Vec<u8>,
+    last_field_id: i16,
+    parent_field_ids: Vec<i16>,
+}
+
+impl Writer {
+    fn varint(&mut self, mut value: u64) {
+        while value >= 0x80 {
+            self.buf.push((value as u8) | 0x80);
+            value >>= 7;
+        }
+        self.buf.push(value as u8);
+    }
+
+    fn i32(&mut self, value: i32) {
+        self.varint(((value << 1) ^ (value >> 31)) as u32 as u64);
+    }
+
+    fn i64(&mut self, value: i64) {
+        self.varint(((value << 1) ^ (value >> 63)) as u64);
+    }
+
+    fn binary(&mut self, value: &[u8]) {
+        self.varint(value.len() as u64);
+        self.buf.extend_from_slice(value);
+    }
+
+    fn field(&mut self, id: i16, field_type: u8) {
+        let delta = id - self.last_field_id;
+        if (1..=15).contains(&delta) {
+            self.buf.push(((delta as u8) << 4) | field_type);
+        } else {
+            self.buf.push(field_type);
+            self.i32(id as i32);
+        }
+        self.last_field_id = id;
+    }
+
+    fn struct_begin(&mut self) {
+        self.parent_field_ids.push(self.last_field_id);
+        self.last_field_id = 0;
+    }
+
+    fn struct_end(&mut self) {
+        self.buf.push(0);
+        self.last_field_id = self.parent_field_ids.pop().unwrap_or_default();
+    }
+
+    fn list_begin(&mut self, element_type: u8, len: usize) {
+        if len < 15 {
+            self.buf.push(((len as u8) << 4) | element_type);
+        } else {
+            self.buf.push(0xf0 | element_type);
+            self.varint(len as u64);
+        }
+    }
+
+    fn i32_field(&mut self, id: i16, value: i32) {
+        self.field(id, TYPE_I32);
+        self.i32(value);
+    }
+
+    fn i64_field(&mut self, id: i16, value: i64) {
+        self.field(id, TYPE_I64);
+        self.i64(value);
+    }
+
+    fn binary_field(&mut self, id: i16, value: &[u8]) {
+        self.field(id, TYPE_BINARY);
+        self.binary(value);
+    }
+
+    fn bool_field(&mut self, id: i16, value: bool) {
+        self.field(
+            id,
+            if value {
+                TYPE_BOOLEAN_TRUE
+            } else {
+                TYPE_BOOLEAN_FALSE
+            },
+        );
+    }
+
+    fn double_field(&mut self, id: i16, value: f64) {
+        self.field(id, TYPE_DOUBLE);
+        self.buf.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn struct_list_field<T>(&mut self, id: i16, values: &[T], write: impl Fn(&mut Self, &T)) {
+        self.field(id, TYPE_LIST);
+        self.list_begin(TYPE_STRUCT, values.len());
+        for value in values {
+            self.struct_begin();
+            write(self, value);
+            self.struct_end();
+        }
+    }
+
+    /// Writes a struct already encoded on its own, whose fields are relative to the start of the
+    /// struct.
+    fn encoded_struct(&mut self, encoded: &[u8]) {
+        self.buf.extend_from_slice(encoded);
+    }
+
+    fn tag(&mut self, tag: &KeyValue) {
+        self.binary_field(1, tag.key.as_bytes());
+        match tag.v_type() {
+            ValueType::String => {
+                self.i32_field(2, TAG_TYPE_STRING);
+                self.binary_field(3, tag.v_str.as_bytes());
+            }
+            ValueType::Float64 => {
+                self.i32_field(2, TAG_TYPE_DOUBLE);
+                self.double_field(4, tag.v_float64);
+            }
+            ValueType::Bool => {
+                self.i32_field(2, TAG_TYPE_BOOL);
+                self.bool_field(5, tag.v_bool);
+            }
+            ValueType::Int64 => {
+                self.i32_field(2, TAG_TYPE_LONG);
+                self.i64_field(6, tag.v_int64);
+            }
+            ValueType::Binary => {
+                self.i32_field(2, TAG_TYPE_BINARY);
+                self.binary_field(7, &tag.v_binary);
+            }
+        }
+    }
+
+    fn log(&mut self, log: &Log) {
+        self.i64_field(1, log.timestamp.as_ref().map_or(0, timestamp_micros));
+        self.struct_list_field(2, &log.fields, Self::tag);
+    }
+
+    fn span_ref(&mut self, span_ref: &SpanRef) {
+        let (trace_id_high, trace_id_low) = trace_id(&span_ref.trace_id);
+        self.i32_field(1, span_ref.ref_type);
+        self.i64_field(2, trace_id_low);
+        self.i64_field(3, trace_id_high);
+        self.i64_field(4, span_id(&span_ref.span_id));
+    }
+
+    fn span(&mut self, span: &Span) {
+        let (trace_id_high, trace_id_low) = trace_id(&span.trace_id);
+        // The parent of the span is its first reference to a span of the same trace.
+        let parent_span_id = span
+            .references
+            .iter()
+            .find(|span_ref| {
+                span_ref.ref_type() == SpanRefType::ChildOf && span_ref.trace_id == span.trace_id
+            })
+            .map_or(0, |span_ref| span_id(&span_ref.span_id));
+
+        self.i64_field(1, trace_id_low);
+        self.i64_field(2, trace_id_high);
+        self.i64_field(3, span_id(&span.span_id));
+        self.i64_field(4, parent_span_id);
+        self.binary_field(5, span.operation_name.as_bytes());
+        if !span.references.is_empty() {
+            self.struct_list_field(6, &span.references, Self::span_ref);
+        }
+        self.i32_field(7, span.flags as i32);
+        self.i64_field(8, span.start_time.as_ref().map_or(0, timestamp_micros));
+        self.i64_field(
+            9,
+            span.duration.as_ref().map_or(0, |duration| {
+                duration.seconds * 1_000_000 + duration.nanos as i64 / 1_000
+            }),
+        );
+        if !span.tags.is_empty() {
+            self.struct_list_field(10, &span.tags, Self::tag);
+        }
+        if !span.logs.is_empty() {
+            self.struct_list_field(11, &span.logs, Self::log);
+        }
+    }
+
+    fn process(&mut self, process: &Process) {
+        self.binary_field(1, process.service_name.as_bytes());
+        if !process.tags.is_empty() {
+            self.struct_list_field(2, &process.tags, Self::tag);
+        }
+    }
+}
+
+fn timestamp_micros(timestamp: &prost_types::Timestamp) -> i64 {
+    timestamp.seconds * 1_000_000 + timestamp.nanos as i64 / 1_000
+}
+
+/// Splits the 16 bytes of a trace ID into its high and low halves.
+fn trace_id(bytes: &[u8]) -> (i64, i64) {
+    let mut id = [0; 16];
+    let len = bytes.len().min(16);
+    id[16 - len..].copy_from_slice(&bytes[bytes.len() - len..]);
+    (
+        i64::from_be_bytes(id[..8].try_into().expect("8 bytes")),
+        i64::from_be_bytes(id[8..].try_into().expect("8 bytes")),
+    )
+}
+
+fn span_id(bytes: &[u8]) -> i64 {
+    let mut id = [0; 8];
+    let len = bytes.len().min(8);
+    id[8 - len..].copy_from_slice(&bytes[bytes.len() - len..]);
+    i64::from_be_bytes(id)
+}
+
+fn encode_struct(write: impl FnOnce(&mut Writer)) -> Vec<u8> {
+    let mut writer = Writer::default();
+    write(&mut writer);
+    writer.struct_end();
+    writer.buf
+}
+
+/// Returns whether the span fits into a datagram of the given size on its own.
+pub(super) fn fits_datagram(process: &Process, span: &Span, max_size: usize) -> bool {
+    let process = encode_struct(|writer| writer.process(process));
+    let span = encode_struct(|writer| writer.span(span));
+    DATAGRAM_OVERHEAD + process.len() + span.len() <= max_size
+}
+
+/// Encodes the batch into `emitBatch` calls, splitting its spans into as many datagrams of at most
+/// the given size as needed.
+///
+/// Spans which don't fit into a datagram on their own are sent in a datagram of their own, which
+/// the agent may reject.
+pub(super) fn emit_batch_datagrams(batch: &Batch, max_size: usize) -> Vec<Vec<u8>> {
+    let process = encode_struct(|writer| {
+        if let Some(process) = &batch.process {
+            writer.process(process);
+        }
+    });
+    let spans = batch
+        .spans
+        .iter()
+        .map(|span| encode_struct(|writer| writer.span(span)));
+
+    let mut datagrams = Vec::new();
+    let mut pending: Vec<Vec<u8>> = Vec::new();
+    let mut pending_size = DATAGRAM_OVERHEAD + process.len();
+    for span in spans {
+        if !pending.is_empty() && pending_size + span.len() > max_size {
+            datagrams.push(emit_batch(&process, &pending));
+            pending.clear();
+            pending_size = DATAGRAM_OVERHEAD + process.len();
+        }
+        pending_size += span.len();
+        pending.push(span);
+    }
+    if !pending.is_empty() {
+        datagrams.push(emit_batch(&process, &pending));
+    }
+    datagrams
+}
+
+/// Encodes a oneway `Agent.emitBatch` call with the encoded process and spans.
+fn emit_batch(process: &[u8], spans: &[Vec<u8>]) -> Vec<u8> {
+    let mut writer = Writer::default();
+    writer.buf.push(PROTOCOL_ID);
+    writer.buf.push(VERSION | (MESSAGE_TYPE_ONEWAY << 5));
+    // The agent doesn't reply to oneway calls, so their sequence IDs are unused.
+    writer.varint(0);
+    writer.binary(b"emitBatch");
+
+    // The arguments of the call.
+    writer.struct_begin();
+    writer.field(1, TYPE_STRUCT);
+    writer.struct_begin();
+    writer.field(1, TYPE_STRUCT);
+    writer.encoded_struct(process);
+    writer.field(2, TYPE_LIST);
+    writer.list_begin(TYPE_STRUCT, spans.len());
+    for span in spans {
+        writer.encoded_struct(span);
+    }
+    writer.struct_end();
+    writer.struct_end();
+    writer.buf
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(operation_name: &str) -> Span {
+        Span {
+            trace_id: vec![0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 2],
+            span_id: vec![0, 0, 0, 0, 0, 0, 0, 3],
+            operation_name: operation_name.to_string(),
+            flags: 1,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn encodes_tags() {
+        let tag = KeyValue {
+            key: "a".to_string(),
+            v_type: ValueType::String.into(),
+            v_str: "b".to_string(),
+            ..Default::default()
+        };
+        let encoded = encode_struct(|writer| writer.tag(&tag));
+        assert_eq!(
+            encoded,
+            [0x18, 0x01, b'a', 0x15, 0x00, 0x18, 0x01, b'b', 0x00]
+        );
+
+        let tag = KeyValue {
+            key: "a".to_string(),
+            v_type: ValueType::Int64.into(),
+            v_int64: -2,
+            ..Default::default()
+        };
+        let encoded = encode_struct(|writer| writer.tag(&tag));
+        // The field ID of the value is 6, 4 after the type.
+        assert_eq!(encoded, [0x18, 0x01, b'a', 0x15, 0x06, 0x46, 0x03, 0x00]);
+    }
+
+    #[test]
+    fn splits_trace_ids() {
+        assert_eq!(
+            trace_id(&[0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 2]),
+            (1, 2)
+        );
+        assert_eq!(trace_id(&[1]), (0, 1));
+        assert_eq!(span_id(&[0, 0, 0, 0, 0, 0, 1, 0]), 256);
+    }
+
+    #[test]
+    fn encodes_emit_batch_calls() {
+        let batch = Batch {
+            spans: vec![span("get")],
+            process: Some(Process {
+                service_name: "api".to_string(),
+                tags: Vec::new(),
+            }),
+        };
+
+        let datagrams = emit_batch_datagrams(&batch, 65_000);
+        assert_eq!(datagrams.len(), 1);
+        let datagram = &datagrams[0];
+        assert_eq!(&datagram[..3], [PROTOCOL_ID, 0x81, 0x00]);
+        assert_eq!(&datagram[3..13], b"\x09emitBatch");
+        // The batch, as the first argument, then its process.
+        assert_eq!(&datagram[13..15], [0x1c, 0x1c]);
+        assert!(datagram.ends_with(&[0x00, 0x00]));
+    }
+
+    #[test]
+    fn splits_batches_into_datagrams() {
+        let process = Process {
+            service_name: "api".to_string(),
+            tags: Vec::new(),
+        };
+        let batch = Batch {
+            spans: (0..100)
+                .map(|i| span(&format!("operation-{}", i)))
+                .collect(),
+            process: Some(process.clone()),
+        };
+        assert!(fits_datagram(&process, &batch.spans[0], 200));
+        assert!(!fits_datagram(&process, &batch.spans[0], 50));
+
+        let datagrams = emit_batch_datagrams(&batch, 500);
+        assert!(datagrams.len() > 1);
+        assert!(datagrams.iter().all(|datagram| datagram.len() <= 500));
+
+        let spans: usize = datagrams
+            .iter()
+            .map(|datagram| {
+                // The list header of the spans follows the process, which ends at the stop of its
+                // struct.
+                let process_end = 15 + encode_struct(|writer| writer.process(&process)).len();
+                let header = datagram[process_end + 1];
+                if header >> 4 == 0x0f {
+                    datagram[process_end + 2] as usize
+                } else {
+                    (header >> 4) as usize
+                }
+            })
+            .sum();
+        assert_eq!(spans, 100);
+    }
+}
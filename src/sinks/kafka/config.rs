@@ -12,7 +12,10 @@ use crate::{
     kafka::{KafkaAuthConfig, KafkaCompression},
     serde::json::to_string,
     sinks::{
-        kafka::sink::{healthcheck, KafkaSink},
+        kafka::{
+            schema_registry::SchemaRegistryConfig,
+            sink::{healthcheck, KafkaSink},
+        },
         prelude::*,
     },
 };
@@ -59,6 +62,10 @@ pub struct KafkaSinkConfig {
     #[configurable(derived)]
     pub encoding: EncodingConfig,
 
+    #[configurable(derived)]
+    #[configurable(metadata(docs::advanced))]
+    pub schema_registry: Option<SchemaRegistryConfig>,
+
     // These batching options will **not** override librdkafka_options values.
     #[configurable(derived)]
     #[configurable(metadata(docs::advanced))]
@@ -111,6 +118,28 @@ pub struct KafkaSinkConfig {
     #[configurable(metadata(docs::examples = "headers"))]
     pub headers_key: Option<ConfigTargetPath>,
 
+    /// Whether or not the producer is idempotent.
+    ///
+    /// An idempotent producer writes each record exactly once, and in order, to its partition,
+    /// even when it retries sending the record to the brokers.
+    #[serde(default)]
+    #[configurable(metadata(docs::advanced))]
+    pub idempotent: bool,
+
+    /// The transactional ID of the producer.
+    ///
+    /// When set, the events of each batch, as configured by the `batch` options, are written in a
+    /// transaction. The transaction is committed once all of its records are delivered, and aborted
+    /// if any of them fails to be delivered, so that consumers reading with the `read_committed`
+    /// isolation level never see the records of failed batches. Combined with end-to-end
+    /// acknowledgements, this avoids the duplicates of the batches sent again by sources.
+    ///
+    /// The ID must be unique to the sink, and stable across restarts of Vector, so that the brokers
+    /// can fence the previous instances of the producer. Transactional producers are idempotent.
+    #[configurable(metadata(docs::examples = "vector-kafka-sink"))]
+    #[configurable(metadata(docs::advanced))]
+    pub transactional_id: Option<String>,
+
     #[configurable(derived)]
     #[serde(
         default,
@@ -166,6 +195,17 @@ impl KafkaSinkConfig {
                     &self.message_timeout_ms.as_millis().to_string(),
                 );
 
+            if self.idempotent || self.transactional_id.is_some() {
+                client_config.set("enable.idempotence", "true");
+            }
+            if let Some(transactional_id) = &self.transactional_id {
+                // The records of a transaction must be delivered before it times out.
+                client_config.set("transactional.id", transactional_id).set(
+                    "transaction.timeout.ms",
+                    &self.message_timeout_ms.as_millis().to_string(),
+                );
+            }
+
             if let Some(value) = self.batch.timeout_secs {
                 // Delay in milliseconds to wait for messages in the producer queue to accumulate before
                 // constructing message batches (MessageSets) to transmit to brokers. A higher value
@@ -243,6 +283,7 @@ impl GenerateConfig for KafkaSinkConfig {
             topic: Template::try_from("topic-1234".to_owned()).unwrap(),
             key_field: Some(ConfigTargetPath::try_from("user_id".to_owned()).unwrap()),
             encoding: JsonSerializerConfig::default().into(),
+            schema_registry: None,
             batch: Default::default(),
             compression: KafkaCompression::None,
             auth: Default::default(),
@@ -250,6 +291,8 @@ impl GenerateConfig for KafkaSinkConfig {
             message_timeout_ms: default_message_timeout_ms(),
             librdkafka_options: Default::default(),
             headers_key: None,
+            idempotent: false,
+            transactional_id: None,
             acknowledgements: Default::default(),
         })
         .unwrap()
@@ -259,9 +302,19 @@ impl GenerateConfig for KafkaSinkConfig {
 #[async_trait::async_trait]
 #[typetag::serde(name = "kafka")]
 impl SinkConfig for KafkaSinkConfig {
-    async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
-        let sink = KafkaSink::new(self.clone())?;
-        let hc = healthcheck(self.clone()).boxed();
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let mut sink = KafkaSink::new(self.clone())?;
+        let schema_registry = self
+            .schema_registry
+            .as_ref()
+            .map(|schema_registry| {
+                schema_registry.build(self.encoding.config(), &self.topic, cx.proxy())
+            })
+            .transpose()?;
+        if let Some(schema_registry) = schema_registry.clone() {
+            sink = sink.with_schema_registry(schema_registry);
+        }
+        let hc = healthcheck(self.clone(), schema_registry).boxed();
         Ok((VectorSink::from_event_streamsink(sink), hc))
     }
 
@@ -285,4 +338,32 @@ mod tests {
     fn generate_config() {
         KafkaSinkConfig::generate_config();
     }
+
+    #[test]
+    fn configures_transactional_producer() {
+        let config: KafkaSinkConfig = toml::from_str(
+            r#"
+            bootstrap_servers = "localhost:9092"
+            topic = "logs"
+            encoding.codec = "json"
+            transactional_id = "vector-kafka-sink"
+            message_timeout_ms = 120000
+            "#,
+        )
+        .unwrap();
+
+        let producer_config = config.to_rdkafka(KafkaRole::Producer).unwrap();
+        assert_eq!(producer_config.get("enable.idempotence"), Some("true"));
+        assert_eq!(
+            producer_config.get("transactional.id"),
+            Some("vector-kafka-sink")
+        );
+        assert_eq!(
+            producer_config.get("transaction.timeout.ms"),
+            Some("120000")
+        );
+
+        let consumer_config = config.to_rdkafka(KafkaRole::Consumer).unwrap();
+        assert_eq!(consumer_config.get("transactional.id"), None);
+    }
 }
@@ -5,7 +5,10 @@ use vector_lib::lookup::OwnedTargetPath;
 use crate::{
     internal_events::KafkaHeaderExtractionError,
     sinks::{
-        kafka::service::{KafkaRequest, KafkaRequestMetadata},
+        kafka::{
+            schema_registry::wire_format,
+            service::{KafkaRequest, KafkaRequestMetadata},
+        },
         prelude::*,
     },
 };
@@ -14,6 +17,9 @@ pub struct KafkaRequestBuilder {
     pub key_field: Option<OwnedTargetPath>,
     pub headers_key: Option<OwnedTargetPath>,
     pub encoder: (Transformer, Encoder<()>),
+    /// The ID of the Avro schema of the records in the Schema Registry, which prefixes the records
+    /// in the Confluent wire format.
+    pub schema_id: Option<u32>,
 }
 
 impl RequestBuilder<(String, Event)> for KafkaRequestBuilder {
@@ -56,8 +62,12 @@ impl RequestBuilder<(String, Event)> for KafkaRequestBuilder {
         request_metadata: RequestMetadata,
         payload: EncodeResult<Self::Payload>,
     ) -> Self::Request {
+        let body = payload.into_payload();
         KafkaRequest {
-            body: payload.into_payload(),
+            body: match self.schema_id {
+                Some(schema_id) => wire_format(schema_id, &body),
+                None => body,
+            },
             metadata,
             request_metadata,
         }
@@ -0,0 +1,204 @@
+//! Registration of the Avro schema of the records in a Confluent Schema Registry, and the
+//! Confluent wire format of the records.
+
+use bytes::{BufMut, Bytes, BytesMut};
+use vector_lib::codecs::encoding::SerializerConfig;
+
+use crate::{
+    common::schema_registry::{SchemaRegistryClient, SchemaRegistryError},
+    config::ProxyConfig,
+    http::Auth,
+    internal_events::KafkaSchemaRegistryError,
+    sinks::{prelude::*, util::retries::ExponentialBackoff},
+};
+
+/// The magic byte of the records in the Confluent wire format.
+const MAGIC_BYTE: u8 = 0;
+
+/// Configuration of the Confluent Schema Registry that the Avro schema of the records is
+/// registered in.
+///
+/// The records are written in the [Confluent wire format][wire_format], prefixed by the ID of
+/// their schema in the registry, so that they can be read by the Confluent deserializers.
+///
+/// [wire_format]: https://docs.confluent.io/platform/current/schema-registry/fundamentals/serdes-develop/index.html#wire-format
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SchemaRegistryConfig {
+    /// The URL of the Schema Registry.
+    #[configurable(metadata(docs::examples = "http://localhost:8081"))]
+    pub url: String,
+
+    /// The subject that the schema is registered under.
+    ///
+    /// Defaults to the topic suffixed by `-value`, as the default subject name strategy of the
+    /// Confluent serializers names subjects, which requires the topic not to be templated.
+    #[configurable(metadata(docs::examples = "logs-value"))]
+    pub subject: Option<String>,
+
+    /// Whether or not to register the schema under the subject.
+    ///
+    /// Registering a schema which is already registered under the subject returns its existing
+    /// ID. When disabled, the schema must already be registered under the subject, and is only
+    /// looked up.
+    #[serde(default = "crate::serde::default_true")]
+    pub auto_register: bool,
+
+    #[configurable(derived)]
+    pub auth: Option<Auth>,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+}
+
+impl SchemaRegistryConfig {
+    /// Builds the client registering the Avro schema of the encoding, under the subject of the topic.
+    pub(super) fn build(
+        &self,
+        encoding: &SerializerConfig,
+        topic: &Template,
+        proxy: &ProxyConfig,
+    ) -> crate::Result<SchemaRegistration> {
+        let SerializerConfig::Avro { avro } = encoding else {
+            return Err("The `schema_registry` option requires the `avro` codec.".into());
+        };
+        let subject =
+            match &self.subject {
+                Some(subject) => subject.clone(),
+                None if topic.is_dynamic() => return Err(
+                    "The `schema_registry.subject` option must be set when `topic` is templated."
+                        .into(),
+                ),
+                None => format!("{}-value", topic.get_ref()),
+            };
+
+        let tls_settings = TlsSettings::from_options(&self.tls)?;
+        Ok(SchemaRegistration {
+            client: SchemaRegistryClient::new(&self.url, self.auth.clone(), tls_settings, proxy)?,
+            subject,
+            schema: avro.schema.clone(),
+            auto_register: self.auto_register,
+        })
+    }
+}
+
+/// Registers or looks up the Avro schema of the records under their subject.
+#[derive(Clone)]
+pub(super) struct SchemaRegistration {
+    client: SchemaRegistryClient,
+    subject: String,
+    schema: String,
+    auto_register: bool,
+}
+
+impl SchemaRegistration {
+    /// Gets the ID of the schema in the registry.
+    pub(super) async fn schema_id(&self) -> Result<u32, SchemaRegistryError> {
+        self.client
+            .schema_id(&self.subject, &self.schema, self.auto_register)
+            .await
+    }
+
+    /// Gets the ID of the schema in the registry, retrying while the registry can't be reached or
+    /// fails to respond.
+    pub(super) async fn schema_id_backoff(&self) -> Result<u32, SchemaRegistryError> {
+        let mut backoff = ExponentialBackoff::from_millis(2)
+            .factor(250)
+            .max_delay(std::time::Duration::from_secs(60));
+
+        loop {
+            match self.schema_id().await {
+                Ok(id) => return Ok(id),
+                Err(error) if error.is_retriable() => {
+                    emit!(KafkaSchemaRegistryError { error });
+                    tokio::time::sleep(backoff.next().unwrap()).await;
+                }
+                Err(error) => return Err(error),
+            }
+        }
+    }
+}
+
+/// Prefixes the Avro datum of a record with the magic byte and the ID of its schema, in the
+/// Confluent wire format.
+pub(super) fn wire_format(schema_id: u32, datum: &[u8]) -> Bytes {
+    let mut record = BytesMut::with_capacity(5 + datum.len());
+    record.put_u8(MAGIC_BYTE);
+    record.put_u32(schema_id);
+    record.put_slice(datum);
+    record.freeze()
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_lib::codecs::encoding::AvroSerializerOptions;
+
+    use super::*;
+
+    fn config(subject: Option<&str>) -> SchemaRegistryConfig {
+        SchemaRegistryConfig {
+            url: "http://localhost:8081/".to_string(),
+            subject: subject.map(Into::into),
+            auto_register: true,
+            auth: None,
+            tls: None,
+        }
+    }
+
+    fn avro() -> SerializerConfig {
+        SerializerConfig::Avro {
+            avro: AvroSerializerOptions {
+                schema: r#"{"type": "record", "name": "log", "fields": [{"name": "message", "type": "string"}]}"#.to_string(),
+            },
+        }
+    }
+
+    #[test]
+    fn derives_subjects_from_topics() {
+        let client = config(None)
+            .build(
+                &avro(),
+                &Template::try_from("logs").unwrap(),
+                &ProxyConfig::default(),
+            )
+            .unwrap();
+        assert_eq!(client.subject, "logs-value");
+
+        let client = config(Some("events-value"))
+            .build(
+                &avro(),
+                &Template::try_from("logs-{{ app }}").unwrap(),
+                &ProxyConfig::default(),
+            )
+            .unwrap();
+        assert_eq!(client.subject, "events-value");
+
+        assert!(config(None)
+            .build(
+                &avro(),
+                &Template::try_from("logs-{{ app }}").unwrap(),
+                &ProxyConfig::default(),
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn requires_avro_codec() {
+        assert!(config(None)
+            .build(
+                &SerializerConfig::Json(Default::default()),
+                &Template::try_from("logs").unwrap(),
+                &ProxyConfig::default(),
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn encodes_wire_format() {
+        assert_eq!(
+            wire_format(258, b"\x06bar"),
+            Bytes::from_static(b"\x00\x00\x00\x01\x02\x06bar")
+        );
+    }
+}
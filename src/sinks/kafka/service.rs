@@ -9,13 +9,15 @@ use std::{
 
 use bytes::Bytes;
 use rdkafka::{
-    error::KafkaError,
+    error::{KafkaError, KafkaResult},
     message::OwnedHeaders,
-    producer::{FutureProducer, FutureRecord},
+    producer::{DeliveryFuture, FutureProducer, FutureRecord, Producer},
     types::RDKafkaErrorCode,
 };
 
-use crate::{kafka::KafkaStatisticsContext, sinks::prelude::*};
+use crate::{
+    internal_events::KafkaTransactionError, kafka::KafkaStatisticsContext, sinks::prelude::*,
+};
 
 pub struct KafkaRequest {
     pub body: Bytes,
@@ -31,6 +33,45 @@ pub struct KafkaRequestMetadata {
     pub topic: String,
 }
 
+impl KafkaRequest {
+    fn raw_byte_size(&self) -> usize {
+        self.body.len() + self.metadata.key.as_ref().map_or(0, |x| x.len())
+    }
+}
+
+impl ByteSizeOf for KafkaRequest {
+    fn size_of(&self) -> usize {
+        self.raw_byte_size()
+    }
+
+    fn allocated_bytes(&self) -> usize {
+        0
+    }
+}
+
+/// The records of a batch, written to Kafka in a single transaction.
+pub struct KafkaTransactionRequest {
+    pub requests: Vec<KafkaRequest>,
+    pub finalizers: EventFinalizers,
+    pub request_metadata: RequestMetadata,
+}
+
+impl From<Vec<KafkaRequest>> for KafkaTransactionRequest {
+    fn from(mut requests: Vec<KafkaRequest>) -> Self {
+        let finalizers = requests.take_finalizers();
+        let request_metadata = RequestMetadata::from_batch(
+            requests
+                .iter()
+                .map(|request| request.get_metadata().clone()),
+        );
+        Self {
+            requests,
+            finalizers,
+            request_metadata,
+        }
+    }
+}
+
 pub struct KafkaResponse {
     event_byte_size: GroupedCountByteSize,
     raw_byte_size: usize,
@@ -66,6 +107,22 @@ impl MetaDescriptive for KafkaRequest {
     }
 }
 
+impl Finalizable for KafkaTransactionRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        std::mem::take(&mut self.finalizers)
+    }
+}
+
+impl MetaDescriptive for KafkaTransactionRequest {
+    fn get_metadata(&self) -> &RequestMetadata {
+        &self.request_metadata
+    }
+
+    fn metadata_mut(&mut self) -> &mut RequestMetadata {
+        &mut self.request_metadata
+    }
+}
+
 /// BlockedRecordState manages state for a record blocked from being enqueued on the producer.
 struct BlockedRecordState {
     records_blocked: Arc<AtomicUsize>,
@@ -99,6 +156,120 @@ impl KafkaService {
             records_blocked: Arc::new(AtomicUsize::new(0)),
         }
     }
+
+    /// Enqueues the record on the producer, and returns the future of its delivery.
+    async fn enqueue(
+        &self,
+        body: Bytes,
+        metadata: KafkaRequestMetadata,
+    ) -> Result<DeliveryFuture, KafkaError> {
+        let mut record = FutureRecord::to(&metadata.topic).payload(body.as_ref());
+        if let Some(key) = &metadata.key {
+            record = record.key(&key[..]);
+        }
+        if let Some(timestamp) = metadata.timestamp_millis {
+            record = record.timestamp(timestamp);
+        }
+        if let Some(headers) = metadata.headers {
+            record = record.headers(headers);
+        }
+
+        // Manually poll [FutureProducer::send_result] instead of [FutureProducer::send] to track
+        // records that fail to be enqueued on the producer.
+        let mut blocked_state: Option<BlockedRecordState> = None;
+        loop {
+            match self.kafka_producer.send_result(record) {
+                // Record was successfully enqueued on the producer.
+                Ok(fut) => {
+                    // Drop the blocked state (if any), as the producer is no longer blocked.
+                    drop(blocked_state.take());
+                    return Ok(fut);
+                }
+                // Producer queue is full.
+                Err((
+                    KafkaError::MessageProduction(RDKafkaErrorCode::QueueFull),
+                    original_record,
+                )) => {
+                    if blocked_state.is_none() {
+                        blocked_state =
+                            Some(BlockedRecordState::new(Arc::clone(&self.records_blocked)));
+                    }
+                    record = original_record;
+                    tokio::time::sleep(Duration::from_millis(100)).await;
+                }
+                // A different error occurred.
+                Err((err, _)) => return Err(err),
+            };
+        }
+    }
+
+    /// Runs an operation on the transaction of the producer, which may block until it completes.
+    async fn transaction<F>(&self, operation: F) -> Result<(), KafkaError>
+    where
+        F: FnOnce(&FutureProducer<KafkaStatisticsContext>) -> KafkaResult<()> + Send + 'static,
+    {
+        let producer = self.kafka_producer.clone();
+        tokio::task::spawn_blocking(move || operation(&producer))
+            .await
+            .expect("transaction operation unexpectedly panicked")
+    }
+
+    /// Initializes the transactions of the producer, fencing the previous producers with the same
+    /// transactional ID.
+    pub(crate) async fn init_transactions(&self, timeout: Duration) -> Result<(), KafkaError> {
+        self.transaction(move |producer| producer.init_transactions(timeout))
+            .await
+    }
+
+    /// Writes the records of the requests in a transaction, which is aborted if any of them fails
+    /// to be delivered or if it fails to be committed.
+    async fn write_transaction(
+        &self,
+        requests: Vec<KafkaRequest>,
+        timeout: Duration,
+    ) -> Result<(), KafkaError> {
+        self.transaction(|producer| producer.begin_transaction())
+            .await?;
+
+        let mut result = Ok(());
+        let mut deliveries = Vec::with_capacity(requests.len());
+        for request in requests {
+            match self.enqueue(request.body, request.metadata).await {
+                Ok(delivery) => deliveries.push(delivery),
+                Err(error) => {
+                    result = Err(error);
+                    break;
+                }
+            }
+        }
+        if result.is_ok() {
+            for delivery in deliveries {
+                if let Err((error, _)) = delivery.await.expect("producer unexpectedly dropped") {
+                    result = Err(error);
+                    break;
+                }
+            }
+        }
+        if result.is_ok() {
+            result = self
+                .transaction(move |producer| producer.commit_transaction(timeout))
+                .await;
+        }
+
+        if result.is_err() {
+            // The records of the transaction which were written are discarded by the brokers.
+            if let Err(error) = self
+                .transaction(move |producer| producer.abort_transaction(timeout))
+                .await
+            {
+                emit!(KafkaTransactionError {
+                    error,
+                    operation: "abort",
+                });
+            }
+        }
+        result
+    }
 }
 
 impl Service<KafkaRequest> for KafkaService {
@@ -120,58 +291,68 @@ impl Service<KafkaRequest> for KafkaService {
         let this = self.clone();
 
         Box::pin(async move {
-            let raw_byte_size =
-                request.body.len() + request.metadata.key.as_ref().map_or(0, |x| x.len());
+            let raw_byte_size = request.raw_byte_size();
             let event_byte_size = request
                 .request_metadata
                 .into_events_estimated_json_encoded_byte_size();
 
-            let mut record =
-                FutureRecord::to(&request.metadata.topic).payload(request.body.as_ref());
-            if let Some(key) = &request.metadata.key {
-                record = record.key(&key[..]);
-            }
-            if let Some(timestamp) = request.metadata.timestamp_millis {
-                record = record.timestamp(timestamp);
-            }
-            if let Some(headers) = request.metadata.headers {
-                record = record.headers(headers);
-            }
+            this.enqueue(request.body, request.metadata)
+                .await?
+                .await
+                .expect("producer unexpectedly dropped")
+                .map(|_| KafkaResponse {
+                    event_byte_size,
+                    raw_byte_size,
+                })
+                .map_err(|(err, _)| err)
+        })
+    }
+}
 
-            // Manually poll [FutureProducer::send_result] instead of [FutureProducer::send] to track
-            // records that fail to be enqueued on the producer.
-            let mut blocked_state: Option<BlockedRecordState> = None;
-            loop {
-                match this.kafka_producer.send_result(record) {
-                    // Record was successfully enqueued on the producer.
-                    Ok(fut) => {
-                        // Drop the blocked state (if any), as the producer is no longer blocked.
-                        drop(blocked_state.take());
-                        return fut
-                            .await
-                            .expect("producer unexpectedly dropped")
-                            .map(|_| KafkaResponse {
-                                event_byte_size,
-                                raw_byte_size,
-                            })
-                            .map_err(|(err, _)| err);
-                    }
-                    // Producer queue is full.
-                    Err((
-                        KafkaError::MessageProduction(RDKafkaErrorCode::QueueFull),
-                        original_record,
-                    )) => {
-                        if blocked_state.is_none() {
-                            blocked_state =
-                                Some(BlockedRecordState::new(Arc::clone(&this.records_blocked)));
-                        }
-                        record = original_record;
-                        tokio::time::sleep(Duration::from_millis(100)).await;
-                    }
-                    // A different error occurred.
-                    Err((err, _)) => return Err(err),
-                };
-            }
+/// Writes the batches of records in transactions, which must be sequential.
+#[derive(Clone)]
+pub struct KafkaTransactionalService {
+    service: KafkaService,
+    /// The timeout of the commits and aborts of the transactions.
+    timeout: Duration,
+}
+
+impl KafkaTransactionalService {
+    pub(crate) const fn new(service: KafkaService, timeout: Duration) -> Self {
+        Self { service, timeout }
+    }
+}
+
+impl Service<KafkaTransactionRequest> for KafkaTransactionalService {
+    type Response = KafkaResponse;
+    type Error = KafkaError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Service::<KafkaRequest>::poll_ready(&mut self.service, cx)
+    }
+
+    fn call(&mut self, request: KafkaTransactionRequest) -> Self::Future {
+        let this = self.clone();
+
+        Box::pin(async move {
+            let raw_byte_size = request
+                .requests
+                .iter()
+                .map(KafkaRequest::raw_byte_size)
+                .sum();
+            let event_byte_size = request
+                .request_metadata
+                .into_events_estimated_json_encoded_byte_size();
+
+            this.service
+                .write_transaction(request.requests, this.timeout)
+                .await?;
+
+            Ok(KafkaResponse {
+                event_byte_size,
+                raw_byte_size,
+            })
         })
     }
 }
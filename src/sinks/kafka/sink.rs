@@ -11,8 +11,13 @@ use vrl::path::OwnedTargetPath;
 
 use super::config::{KafkaRole, KafkaSinkConfig};
 use crate::{
+    internal_events::{KafkaSchemaRegistryError, KafkaTransactionError},
     kafka::KafkaStatisticsContext,
-    sinks::kafka::{request_builder::KafkaRequestBuilder, service::KafkaService},
+    sinks::kafka::{
+        request_builder::KafkaRequestBuilder,
+        schema_registry::SchemaRegistration,
+        service::{KafkaService, KafkaTransactionRequest, KafkaTransactionalService},
+    },
     sinks::prelude::*,
 };
 
@@ -32,6 +37,11 @@ pub struct KafkaSink {
     topic: Template,
     key_field: Option<OwnedTargetPath>,
     headers_key: Option<OwnedTargetPath>,
+    /// The batches of the transactions, when the producer is transactional.
+    transaction_batch_settings: Option<BatcherSettings>,
+    /// The timeout of the operations on the transactions.
+    transaction_timeout: Duration,
+    schema_registry: Option<SchemaRegistration>,
 }
 
 pub(crate) fn create_producer(
@@ -53,6 +63,10 @@ impl KafkaSink {
         let transformer = config.encoding.transformer();
         let serializer = config.encoding.build()?;
         let encoder = Encoder::<()>::new(serializer);
+        let transaction_batch_settings = match config.transactional_id {
+            Some(_) => Some(config.batch.into_batcher_settings()?),
+            None => None,
+        };
 
         Ok(KafkaSink {
             headers_key: config.headers_key.map(|key| key.0),
@@ -61,17 +75,37 @@ impl KafkaSink {
             service: KafkaService::new(producer),
             topic: config.topic,
             key_field: config.key_field.map(|key| key.0),
+            transaction_batch_settings,
+            transaction_timeout: config.message_timeout_ms,
+            schema_registry: None,
         })
     }
 
+    /// Writes the records in the Confluent wire format, with the ID of their schema in the registry.
+    pub(crate) fn with_schema_registry(mut self, schema_registry: SchemaRegistration) -> Self {
+        self.schema_registry = Some(schema_registry);
+        self
+    }
+
     async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let schema_id = match &self.schema_registry {
+            Some(schema_registry) => match schema_registry.schema_id_backoff().await {
+                Ok(id) => Some(id),
+                Err(error) => {
+                    emit!(KafkaSchemaRegistryError { error });
+                    return Err(());
+                }
+            },
+            None => None,
+        };
         let request_builder = KafkaRequestBuilder {
             key_field: self.key_field,
             headers_key: self.headers_key,
             encoder: (self.transformer, self.encoder),
+            schema_id,
         };
 
-        input
+        let requests = input
             .filter_map(|event| {
                 // Compute the topic.
                 future::ready(
@@ -97,15 +131,51 @@ impl KafkaSink {
                     }
                     Ok(req) => Some(req),
                 }
-            })
-            .into_driver(self.service)
+            });
+
+        let Some(batch_settings) = self.transaction_batch_settings else {
+            return requests
+                .into_driver(self.service)
+                .protocol("kafka")
+                .run()
+                .await;
+        };
+
+        if let Err(error) = self
+            .service
+            .init_transactions(self.transaction_timeout)
+            .await
+        {
+            emit!(KafkaTransactionError {
+                error,
+                operation: "init",
+            });
+            return Err(());
+        }
+
+        // The transactions of a producer are sequential.
+        let service =
+            ServiceBuilder::new()
+                .concurrency_limit(1)
+                .service(KafkaTransactionalService::new(
+                    self.service,
+                    self.transaction_timeout,
+                ));
+
+        requests
+            .batched(batch_settings.as_byte_size_config())
+            .map(KafkaTransactionRequest::from)
+            .into_driver(service)
             .protocol("kafka")
             .run()
             .await
     }
 }
 
-pub(crate) async fn healthcheck(config: KafkaSinkConfig) -> crate::Result<()> {
+pub(super) async fn healthcheck(
+    config: KafkaSinkConfig,
+    schema_registry: Option<SchemaRegistration>,
+) -> crate::Result<()> {
     trace!("Healthcheck started.");
     let client = config.to_rdkafka(KafkaRole::Consumer).unwrap();
     let topic = match config.topic.render_string(&LogEvent::from_str_legacy("")) {
@@ -128,6 +198,12 @@ pub(crate) async fn healthcheck(config: KafkaSinkConfig) -> crate::Result<()> {
             .map(|_| ())
     })
     .await??;
+
+    // Registering the schema fails on the same client errors, such as invalid credentials or an
+    // incompatible schema, as when the sink starts.
+    if let Some(schema_registry) = schema_registry {
+        schema_registry.schema_id().await?;
+    }
     trace!("Healthcheck completed.");
     Ok(())
 }
@@ -57,6 +57,7 @@ mod integration_test {
             topic: Template::try_from(topic.clone()).unwrap(),
             key_field: None,
             encoding: TextSerializerConfig::default().into(),
+            schema_registry: None,
             batch: BatchConfig::default(),
             compression: KafkaCompression::None,
             auth: KafkaAuthConfig::default(),
@@ -64,9 +65,11 @@ mod integration_test {
             message_timeout_ms: Duration::from_millis(300000),
             librdkafka_options: HashMap::new(),
             headers_key: None,
+            idempotent: false,
+            transactional_id: None,
             acknowledgements: Default::default(),
         };
-        self::sink::healthcheck(config).await.unwrap();
+        self::sink::healthcheck(config, None).await.unwrap();
     }
 
     #[tokio::test]
@@ -152,6 +155,7 @@ mod integration_test {
             topic: Template::try_from(format!("{}-%Y%m%d", topic)).unwrap(),
             compression: KafkaCompression::None,
             encoding: TextSerializerConfig::default().into(),
+            schema_registry: None,
             key_field: None,
             auth: KafkaAuthConfig {
                 sasl: None,
@@ -162,11 +166,13 @@ mod integration_test {
             batch,
             librdkafka_options,
             headers_key: None,
+            idempotent: false,
+            transactional_id: None,
             acknowledgements: Default::default(),
         };
         config.clone().to_rdkafka(KafkaRole::Consumer)?;
         config.clone().to_rdkafka(KafkaRole::Producer)?;
-        self::sink::healthcheck(config.clone()).await?;
+        self::sink::healthcheck(config.clone(), None).await?;
         KafkaSink::new(config)
     }
 
@@ -274,6 +280,74 @@ mod integration_test {
         .await;
     }
 
+    #[tokio::test]
+    async fn kafka_transactional() {
+        let topic = format!("test-{}", random_string(10));
+        let config = KafkaSinkConfig {
+            bootstrap_servers: kafka_address(9091),
+            topic: Template::try_from(topic.clone()).unwrap(),
+            key_field: None,
+            encoding: TextSerializerConfig::default().into(),
+            schema_registry: None,
+            batch: BatchConfig::default(),
+            compression: KafkaCompression::None,
+            auth: KafkaAuthConfig::default(),
+            socket_timeout_ms: Duration::from_millis(60000),
+            message_timeout_ms: Duration::from_millis(300000),
+            librdkafka_options: HashMap::new(),
+            headers_key: None,
+            idempotent: false,
+            transactional_id: Some(format!("vector-{}", random_string(10))),
+            acknowledgements: Default::default(),
+        };
+
+        let num_events = 1000;
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        let (input, events) = random_lines_with_stream(100, num_events, Some(batch));
+
+        assert_sink_compliance(&SINK_TAGS, async move {
+            let sink = KafkaSink::new(config).unwrap();
+            let sink = VectorSink::from_event_streamsink(sink);
+            sink.run(events).await
+        })
+        .await
+        .expect("Running sink failed");
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+
+        // read back the committed records from the beginning
+        let mut client_config = rdkafka::ClientConfig::new();
+        client_config.set("bootstrap.servers", kafka_address(9091));
+        client_config.set("group.id", &random_string(10));
+        client_config.set("isolation.level", "read_committed");
+
+        let mut tpl = TopicPartitionList::new();
+        tpl.add_partition(&topic, 0)
+            .set_offset(Offset::Beginning)
+            .unwrap();
+
+        let consumer: BaseConsumer = client_config.create().unwrap();
+        consumer.assign(&tpl).unwrap();
+
+        // loop instead of iter so we can set a timeout
+        let mut failures = 0;
+        let mut out = Vec::new();
+        while failures < 100 {
+            match consumer.poll(Duration::from_secs(3)) {
+                Some(Ok(msg)) => {
+                    let s: &str = msg.payload_view().unwrap().unwrap();
+                    out.push(s.to_owned());
+                }
+                None if out.len() >= input.len() => break,
+                _ => {
+                    failures += 1;
+                    thread::sleep(Duration::from_millis(50));
+                }
+            }
+        }
+
+        assert_eq!(out, input);
+    }
+
     async fn kafka_happy_path(
         server: String,
         sasl: Option<KafkaSaslConfig>,
@@ -303,6 +377,7 @@ mod integration_test {
             topic: Template::try_from(format!("{}-%Y%m%d", topic)).unwrap(),
             key_field: None,
             encoding: TextSerializerConfig::default().into(),
+            schema_registry: None,
             batch: BatchConfig::default(),
             compression,
             auth: kafka_auth.clone(),
@@ -310,6 +385,8 @@ mod integration_test {
             message_timeout_ms: Duration::from_millis(300000),
             librdkafka_options: HashMap::new(),
             headers_key: Some(headers_key.clone()),
+            idempotent: false,
+            transactional_id: None,
             acknowledgements: Default::default(),
         };
         let topic = format!("{}-{}", topic, chrono::Utc::now().format("%Y%m%d"));
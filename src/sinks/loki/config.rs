@@ -62,10 +62,31 @@ pub struct LokiConfig {
     #[configurable(metadata(docs::additional_props_description = "A Loki label."))]
     pub labels: HashMap<Template, Template>,
 
+    /// A set of [structured metadata][structured_metadata] that is attached to each event.
+    ///
+    /// Structured metadata is stored alongside the log lines without being indexed, so it can hold
+    /// high-cardinality values, such as trace IDs, that would be too costly as labels, while still
+    /// being queryable. Requires Loki 3.0 or newer.
+    ///
+    /// Both keys and values are templateable, and keys ending with `*` expand objects into
+    /// multiple pairs in the same way as labels. See [Label expansion][label_expansion] for more
+    /// information.
+    ///
+    /// [structured_metadata]: https://grafana.com/docs/loki/latest/get-started/labels/structured-metadata/
+    /// [label_expansion]: https://vector.dev/docs/reference/configuration/sinks/loki/#label-expansion
+    #[configurable(metadata(docs::examples = "loki_structured_metadata_examples()"))]
+    #[configurable(metadata(docs::additional_props_description = "A structured metadata pair."))]
+    #[serde(default)]
+    pub structured_metadata: HashMap<Template, Template>,
+
     /// Whether or not to delete fields from the event when they are used as labels.
     #[serde(default = "crate::serde::default_false")]
     pub remove_label_fields: bool,
 
+    /// Whether or not to delete fields from the event when they are used as structured metadata.
+    #[serde(default = "crate::serde::default_false")]
+    pub remove_structured_metadata_fields: bool,
+
     /// Whether or not to remove the timestamp from the event payload.
     ///
     /// The timestamp is still sent as event metadata for Loki to use for indexing.
@@ -119,6 +140,16 @@ fn loki_labels_examples() -> HashMap<String, String> {
     examples
 }
 
+fn loki_structured_metadata_examples() -> HashMap<String, String> {
+    let mut examples = HashMap::new();
+    examples.insert("trace_id".to_string(), "{{ trace_id }}".to_string());
+    examples.insert(
+        "\"pod_annotations_*\"".to_string(),
+        "{{ kubernetes.pod_annotations }}".to_string(),
+    );
+    examples
+}
+
 #[derive(Clone, Copy, Debug, Default)]
 pub struct LokiDefaultBatchSettings;
 
@@ -194,6 +225,7 @@ impl SinkConfig for LokiConfig {
             }
         }
 
+        let request_limits = cx.request_limits.clone();
         let client = self.build_client(cx)?;
 
         let config = LokiConfig {
@@ -201,7 +233,7 @@ impl SinkConfig for LokiConfig {
             ..self.clone()
         };
 
-        let sink = LokiSink::new(config.clone(), client.clone())?;
+        let sink = LokiSink::new(config.clone(), client.clone(), request_limits)?;
 
         let healthcheck = healthcheck(config, client).boxed();
 
@@ -219,6 +251,11 @@ impl SinkConfig for LokiConfig {
     fn acknowledgements(&self) -> &AcknowledgementsConfig {
         &self.acknowledgements
     }
+
+    fn supports_request_limits(&self) -> bool {
+        // The requests are sent one at a time unless out of order events are accepted.
+        matches!(self.out_of_order_action, OutOfOrderAction::Accept)
+    }
 }
 
 pub fn valid_label_name(label: &Template) -> bool {
@@ -43,11 +43,12 @@ impl Encoder<Vec<LokiRecord>> for LokiBatchEncoder {
                             let labels = stream.stream;
                             let entries = stream
                                 .values
-                                .iter()
+                                .into_iter()
                                 .map(|event| {
                                     loki_logproto::util::Entry(
                                         event.timestamp,
                                         String::from_utf8_lossy(&event.event).into_owned(),
+                                        event.structured_metadata,
                                     )
                                 })
                                 .collect();
@@ -130,11 +131,16 @@ impl From<Vec<LokiRecord>> for LokiBatch {
 pub struct LokiEvent {
     pub timestamp: i64,
     pub event: Bytes,
+    pub structured_metadata: Vec<(String, String)>,
 }
 
 impl ByteSizeOf for LokiEvent {
     fn allocated_bytes(&self) -> usize {
-        self.timestamp.allocated_bytes() + self.event.allocated_bytes()
+        self.timestamp.allocated_bytes()
+            + self.event.allocated_bytes()
+            + self.structured_metadata.iter().fold(0, |res, item| {
+                res + item.0.allocated_bytes() + item.1.allocated_bytes()
+            })
     }
 }
 
@@ -143,10 +149,24 @@ impl Serialize for LokiEvent {
     where
         S: serde::Serializer,
     {
-        let mut seq = serializer.serialize_seq(Some(2))?;
+        let len = if self.structured_metadata.is_empty() {
+            2
+        } else {
+            3
+        };
+        let mut seq = serializer.serialize_seq(Some(len))?;
         seq.serialize_element(&self.timestamp.to_string())?;
         let event = String::from_utf8_lossy(&self.event);
         seq.serialize_element(&event)?;
+        // Structured metadata is sent as an object, the third element of the entry.
+        if !self.structured_metadata.is_empty() {
+            let structured_metadata: HashMap<&str, &str> = self
+                .structured_metadata
+                .iter()
+                .map(|(key, value)| (key.as_str(), value.as_str()))
+                .collect();
+            seq.serialize_element(&structured_metadata)?;
+        }
         seq.end()
     }
 }
@@ -19,7 +19,7 @@ use crate::{
         LokiEventUnlabeledError, LokiOutOfOrderEventDroppedError, LokiOutOfOrderEventRewritten,
         SinkRequestBuildError,
     },
-    sinks::prelude::*,
+    sinks::{prelude::*, util::service::RequestLimitsGroup},
 };
 
 #[derive(Clone)]
@@ -135,16 +135,23 @@ pub(super) struct EventEncoder {
     transformer: Transformer,
     encoder: Encoder<()>,
     labels: HashMap<Template, Template>,
+    structured_metadata: HashMap<Template, Template>,
     remove_label_fields: bool,
+    remove_structured_metadata_fields: bool,
     remove_timestamp: bool,
 }
 
 impl EventEncoder {
-    fn build_labels(&self, event: &Event) -> Vec<(String, String)> {
+    /// Renders the pairs of labels or of structured metadata, expanding the objects of the keys
+    /// ending with `*` into multiple pairs.
+    fn build_pairs(
+        templates: &HashMap<Template, Template>,
+        event: &Event,
+    ) -> Vec<(String, String)> {
         let mut static_labels: HashMap<String, String> = HashMap::new();
         let mut dynamic_labels: HashMap<String, String> = HashMap::new();
 
-        for (key_template, value_template) in self.labels.iter() {
+        for (key_template, value_template) in templates.iter() {
             let key = key_template.render_string(event);
             let value = value_template.render_string(event);
 
@@ -233,12 +240,19 @@ impl EventEncoder {
 
     fn remove_label_fields(&self, event: &mut Event) {
         if self.remove_label_fields {
-            for template in self.labels.values() {
-                if let Some(fields) = template.get_fields() {
-                    for field in fields {
-                        if let Ok(path) = parse_target_path(field.as_str()) {
-                            event.as_mut_log().remove(&path);
-                        }
+            Self::remove_template_fields(&self.labels, event);
+        }
+        if self.remove_structured_metadata_fields {
+            Self::remove_template_fields(&self.structured_metadata, event);
+        }
+    }
+
+    fn remove_template_fields(templates: &HashMap<Template, Template>, event: &mut Event) {
+        for template in templates.values() {
+            if let Some(fields) = template.get_fields() {
+                for field in fields {
+                    if let Ok(path) = parse_target_path(field.as_str()) {
+                        event.as_mut_log().remove(&path);
                     }
                 }
             }
@@ -249,7 +263,8 @@ impl EventEncoder {
         let tenant_id = self.key_partitioner.partition(&event);
         let finalizers = event.take_finalizers();
         let json_byte_size = event.estimated_json_encoded_size_of();
-        let mut labels = self.build_labels(&event);
+        let mut labels = Self::build_pairs(&self.labels, &event);
+        let structured_metadata = Self::build_pairs(&self.structured_metadata, &event);
         self.remove_label_fields(&mut event);
 
         let timestamp = match event.as_log().get_timestamp() {
@@ -284,6 +299,7 @@ impl EventEncoder {
             event: LokiEvent {
                 timestamp,
                 event: bytes.freeze(),
+                structured_metadata,
             },
             partition,
             finalizers,
@@ -377,7 +393,11 @@ pub struct LokiSink {
 
 impl LokiSink {
     #[allow(clippy::missing_const_for_fn)] // const cannot run destructor
-    pub fn new(config: LokiConfig, client: HttpClient) -> crate::Result<Self> {
+    pub fn new(
+        config: LokiConfig,
+        client: HttpClient,
+        request_limits: Option<RequestLimitsGroup>,
+    ) -> crate::Result<Self> {
         let compression = config.compression;
 
         // if Vector is configured to allow events with out of order timestamps, then we can
@@ -387,9 +407,13 @@ impl LokiSink {
         // timestamps across requests. We used to support concurrency across partitions (Loki
         // streams) but this was lost in #9506. Rather than try to re-add it, since Loki no longer
         // requires in-order processing for version >= 2.4, instead we just keep the static limit
-        // of 1 for now.
+        // of 1 for now, which is also why only the sinks accepting out of order events can share
+        // the limits of a request limits group.
         let request_limits = match config.out_of_order_action {
-            OutOfOrderAction::Accept => config.request.into_settings(),
+            OutOfOrderAction::Accept => config
+                .request
+                .into_settings()
+                .with_request_limits(request_limits),
             OutOfOrderAction::Drop | OutOfOrderAction::RewriteTimestamp => {
                 let mut settings = config.request.into_settings();
                 settings.concurrency = Some(1);
@@ -425,7 +449,9 @@ impl LokiSink {
                 transformer,
                 encoder,
                 labels: config.labels,
+                structured_metadata: config.structured_metadata,
                 remove_label_fields: config.remove_label_fields,
+                remove_structured_metadata_fields: config.remove_structured_metadata_fields,
                 remove_timestamp: config.remove_timestamp,
             },
             batch_settings: config.batch.into_batcher_settings()?,
@@ -529,7 +555,9 @@ mod tests {
             transformer: Default::default(),
             encoder: Encoder::<()>::new(JsonSerializerConfig::default().build().into()),
             labels: HashMap::default(),
+            structured_metadata: HashMap::default(),
             remove_label_fields: false,
+            remove_structured_metadata_fields: false,
             remove_timestamp: false,
         };
         let mut event = Event::Log(LogEvent::from("hello world"));
@@ -572,7 +600,9 @@ mod tests {
             transformer: Default::default(),
             encoder: Encoder::<()>::new(JsonSerializerConfig::default().build().into()),
             labels,
+            structured_metadata: HashMap::default(),
             remove_label_fields: false,
+            remove_structured_metadata_fields: false,
             remove_timestamp: false,
         };
         let mut event = Event::Log(LogEvent::from("hello world"));
@@ -601,6 +631,54 @@ mod tests {
         assert_eq!(labels["test_key_two"], "baz".to_string());
     }
 
+    #[test]
+    fn encoder_with_structured_metadata() {
+        let mut labels = HashMap::default();
+        labels.insert(
+            Template::try_from("static").unwrap(),
+            Template::try_from("value").unwrap(),
+        );
+        let mut structured_metadata = HashMap::default();
+        structured_metadata.insert(
+            Template::try_from("trace_id").unwrap(),
+            Template::try_from("{{ trace_id }}").unwrap(),
+        );
+        structured_metadata.insert(
+            Template::try_from("attr_*").unwrap(),
+            Template::try_from("{{ attributes }}").unwrap(),
+        );
+        let mut encoder = EventEncoder {
+            key_partitioner: KeyPartitioner::new(None),
+            transformer: Default::default(),
+            encoder: Encoder::<()>::new(JsonSerializerConfig::default().build().into()),
+            labels,
+            structured_metadata,
+            remove_label_fields: false,
+            remove_structured_metadata_fields: true,
+            remove_timestamp: false,
+        };
+        let mut event = Event::Log(LogEvent::from("hello world"));
+        let log = event.as_mut_log();
+        log.insert("trace_id", "abc123");
+        log.insert("attributes.user", "alice");
+
+        let record = encoder.encode_event(event).unwrap();
+        assert_eq!(
+            record.labels,
+            vec![("static".to_string(), "value".to_string())]
+        );
+
+        let structured_metadata: HashMap<String, String> =
+            record.event.structured_metadata.into_iter().collect();
+        assert_eq!(structured_metadata.len(), 2);
+        assert_eq!(structured_metadata["trace_id"], "abc123".to_string());
+        assert_eq!(structured_metadata["attr_user"], "alice".to_string());
+
+        let line = String::from_utf8_lossy(&record.event.event);
+        assert!(!line.contains("trace_id"));
+        assert!(!line.contains("attributes"));
+    }
+
     #[test]
     fn encoder_with_dynamic_labels() -> Result<(), serde_json::Error> {
         let mut labels = HashMap::default();
@@ -622,7 +700,9 @@ mod tests {
             transformer: Default::default(),
             encoder: Encoder::<()>::new(JsonSerializerConfig::default().build().into()),
             labels,
+            structured_metadata: HashMap::default(),
             remove_label_fields: false,
+            remove_structured_metadata_fields: false,
             remove_timestamp: false,
         };
 
@@ -672,7 +752,9 @@ mod tests {
             transformer: Default::default(),
             encoder: Encoder::<()>::new(JsonSerializerConfig::default().build().into()),
             labels,
+            structured_metadata: HashMap::default(),
             remove_label_fields: false,
+            remove_structured_metadata_fields: false,
             remove_timestamp: false,
         };
 
@@ -710,7 +792,9 @@ mod tests {
             transformer: Default::default(),
             encoder: Encoder::<()>::new(JsonSerializerConfig::default().build().into()),
             labels,
+            structured_metadata: HashMap::default(),
             remove_label_fields: false,
+            remove_structured_metadata_fields: false,
             remove_timestamp: false,
         };
 
@@ -731,7 +815,9 @@ mod tests {
             transformer: Default::default(),
             encoder: Encoder::<()>::new(JsonSerializerConfig::default().build().into()),
             labels: HashMap::default(),
+            structured_metadata: HashMap::default(),
             remove_label_fields: false,
+            remove_structured_metadata_fields: false,
             remove_timestamp: true,
         };
         let mut event = Event::Log(LogEvent::from("hello world"));
@@ -761,7 +847,9 @@ mod tests {
             transformer: Default::default(),
             encoder: Encoder::<()>::new(JsonSerializerConfig::default().build().into()),
             labels,
+            structured_metadata: HashMap::default(),
             remove_label_fields: true,
+            remove_structured_metadata_fields: false,
             remove_timestamp: false,
         };
         let mut event = Event::Log(LogEvent::from("hello world"));
@@ -783,7 +871,9 @@ mod tests {
             transformer: Default::default(),
             encoder: Encoder::<()>::new(JsonSerializerConfig::default().build().into()),
             labels: HashMap::default(),
+            structured_metadata: HashMap::default(),
             remove_label_fields: false,
+            remove_structured_metadata_fields: false,
             remove_timestamp: false,
         };
         let base = chrono::Utc::now();
@@ -1,6 +1,6 @@
 use vector_lib::config::proxy::ProxyConfig;
 
-use super::{config::LokiConfig, healthcheck::healthcheck, sink::LokiSink};
+use super::{config::LokiConfig, event::LokiEvent, healthcheck::healthcheck, sink::LokiSink};
 use crate::{
     http::HttpClient,
     sinks::prelude::*,
@@ -25,7 +25,7 @@ async fn interpolate_labels() {
     )
     .unwrap();
     let client = config.build_client(cx).unwrap();
-    let mut sink = LokiSink::new(config, client).unwrap();
+    let mut sink = LokiSink::new(config, client, None).unwrap();
 
     let mut e1 = Event::Log(LogEvent::from("hello world"));
 
@@ -66,7 +66,7 @@ async fn use_label_from_dropped_fields() {
     )
     .unwrap();
     let client = config.build_client(cx).unwrap();
-    let mut sink = LokiSink::new(config, client).unwrap();
+    let mut sink = LokiSink::new(config, client, None).unwrap();
 
     let mut e1 = Event::Log(LogEvent::from("hello world"));
 
@@ -146,3 +146,25 @@ async fn healthcheck_grafana_cloud() {
         .await
         .expect("healthcheck failed");
 }
+
+#[test]
+fn serialize_structured_metadata() {
+    let event = LokiEvent {
+        timestamp: 1,
+        event: "hello".into(),
+        structured_metadata: vec![("trace_id".to_string(), "abc123".to_string())],
+    };
+    assert_eq!(
+        serde_json::to_value(&event).unwrap(),
+        serde_json::json!(["1", "hello", {"trace_id": "abc123"}])
+    );
+
+    let event = LokiEvent {
+        structured_metadata: Vec::new(),
+        ..event
+    };
+    assert_eq!(
+        serde_json::to_value(&event).unwrap(),
+        serde_json::json!(["1", "hello"])
+    );
+}
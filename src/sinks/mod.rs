@@ -28,10 +28,16 @@ pub mod axiom;
 pub mod azure_blob;
 #[cfg(feature = "sinks-azure_blob")]
 pub mod azure_common;
+#[cfg(feature = "sinks-azure_data_explorer")]
+pub mod azure_data_explorer;
+#[cfg(feature = "sinks-azure_event_hubs")]
+pub mod azure_event_hubs;
 #[cfg(feature = "sinks-azure_monitor_logs")]
 pub mod azure_monitor_logs;
 #[cfg(feature = "sinks-blackhole")]
 pub mod blackhole;
+#[cfg(feature = "sinks-chat_webhook")]
+pub mod chat_webhook;
 #[cfg(feature = "sinks-clickhouse")]
 pub mod clickhouse;
 #[cfg(feature = "sinks-console")]
@@ -45,6 +51,8 @@ pub mod databend;
     feature = "sinks-datadog_traces"
 ))]
 pub mod datadog;
+#[cfg(feature = "sinks-doris")]
+pub mod doris;
 #[cfg(feature = "sinks-elasticsearch")]
 pub mod elasticsearch;
 #[cfg(feature = "sinks-file")]
@@ -63,6 +71,8 @@ pub mod http;
 pub mod humio;
 #[cfg(any(feature = "sinks-influxdb", feature = "prometheus-integration-tests"))]
 pub mod influxdb;
+#[cfg(feature = "sinks-jaeger")]
+pub mod jaeger;
 #[cfg(feature = "sinks-kafka")]
 pub mod kafka;
 #[cfg(feature = "sinks-loki")]
@@ -77,30 +87,50 @@ pub mod nats;
 pub mod new_relic;
 #[cfg(feature = "sinks-webhdfs")]
 pub mod opendal_common;
+#[cfg(feature = "sinks-opentelemetry")]
+pub mod opentelemetry;
+#[cfg(feature = "sinks-pagerduty")]
+pub mod pagerduty;
 #[cfg(feature = "sinks-papertrail")]
 pub mod papertrail;
+#[cfg(feature = "sinks-postgres")]
+pub mod postgres;
 #[cfg(feature = "sinks-prometheus")]
 pub mod prometheus;
 #[cfg(feature = "sinks-pulsar")]
 pub mod pulsar;
+#[cfg(feature = "sinks-questdb")]
+pub mod questdb;
+#[cfg(feature = "sinks-quickwit")]
+pub mod quickwit;
 #[cfg(feature = "sinks-redis")]
 pub mod redis;
 #[cfg(all(feature = "sinks-aws_s3", feature = "aws-core"))]
 pub mod s3_common;
 #[cfg(feature = "sinks-sematext")]
 pub mod sematext;
+#[cfg(feature = "sinks-smtp")]
+pub mod smtp;
+#[cfg(feature = "sinks-snowflake")]
+pub mod snowflake;
 #[cfg(feature = "sinks-socket")]
 pub mod socket;
 #[cfg(feature = "sinks-splunk_hec")]
 pub mod splunk_hec;
 #[cfg(feature = "sinks-statsd")]
 pub mod statsd;
+#[cfg(feature = "sinks-syslog")]
+pub mod syslog;
 #[cfg(feature = "sinks-vector")]
 pub mod vector;
+#[cfg(feature = "sinks-victoriametrics")]
+pub mod victoriametrics;
 #[cfg(feature = "sinks-webhdfs")]
 pub mod webhdfs;
 #[cfg(feature = "sinks-websocket")]
 pub mod websocket;
+#[cfg(feature = "sinks-websocket_server")]
+pub mod websocket_server;
 
 pub use vector_lib::{config::Input, sink::VectorSink};
 
@@ -66,6 +66,13 @@ pub struct MqttSinkConfig {
     #[configurable(derived)]
     #[serde(default = "default_qos")]
     pub quality_of_service: MqttQoS,
+
+    /// If set to true, the messages are published as retained messages.
+    ///
+    /// The broker keeps the last retained message of each topic, and sends it to the clients
+    /// subscribing to the topic later, such as to provide the latest state of a device.
+    #[serde(default)]
+    pub retain: bool,
 }
 
 /// Supported Quality of Service types for MQTT.
@@ -127,6 +134,7 @@ impl Default for MqttSinkConfig {
             encoding: JsonSerializerConfig::default().into(),
             acknowledgements: AcknowledgementsConfig::default(),
             quality_of_service: MqttQoS::default(),
+            retain: false,
         }
     }
 }
@@ -194,7 +202,7 @@ impl MqttSinkConfig {
         }
         if let Some(tls) = tls.tls() {
             let ca = tls.authorities_pem().flatten().collect();
-            let client_auth = None;
+            let client_auth = tls.identity_pem();
             let alpn = Some(vec!["mqtt".into()]);
             options.set_transport(Transport::Tls(TlsConfiguration::Simple {
                 ca,
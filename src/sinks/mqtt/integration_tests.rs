@@ -102,3 +102,54 @@ async fn mqtt_happy() {
 
     assert_eq!(messages, input);
 }
+
+#[tokio::test]
+async fn mqtt_retained() {
+    trace_init();
+
+    let topic = "test-retained";
+    let cnf = MqttSinkConfig {
+        host: mqtt_broker_address(),
+        port: mqtt_broker_port(),
+        topic: Template::try_from(topic).expect("Cannot parse the topic template"),
+        quality_of_service: MqttQoS::AtLeastOnce,
+        retain: true,
+        ..Default::default()
+    };
+
+    let cx = SinkContext::default();
+    let (sink, _healthcheck) = cnf.build(cx).await.expect("Cannot build the sink");
+
+    let (input, events) = random_lines_with_stream(100, 1, None);
+    run_and_assert_sink_compliance(sink, events, &SINK_TAGS).await;
+
+    // The retained message is sent to clients subscribing after it was published.
+    let mut mqtt_options = MqttOptions::new(
+        "integration-test-retained-consumer",
+        mqtt_broker_address(),
+        mqtt_broker_port(),
+    );
+    mqtt_options.set_keep_alive(Duration::from_secs(5));
+
+    let (client, mut eventloop) = AsyncClient::new(mqtt_options, 10);
+    client
+        .subscribe(topic, QoS::AtLeastOnce)
+        .await
+        .expect("Cannot subscribe to the topic");
+
+    let publish = tokio::time::timeout(Duration::from_secs(10), async {
+        loop {
+            if let Event::Incoming(Incoming::Publish(publish)) =
+                eventloop.poll().await.expect("Cannot extract the message")
+            {
+                break publish;
+            }
+        }
+    })
+    .await
+    .expect("Timed out waiting for the retained message");
+
+    assert!(publish.retain);
+    let message = serde_json::from_slice::<serde_json::Value>(&publish.payload).unwrap();
+    assert_eq!(message["message"].as_str().unwrap(), input[0]);
+}
@@ -0,0 +1,243 @@
+use http::{uri::Scheme, Uri};
+use indexmap::IndexMap;
+use snafu::{ResultExt, Snafu};
+use tonic::{
+    metadata::{MetadataKey, MetadataMap, MetadataValue},
+    transport::{Certificate, ClientTlsConfig, Endpoint, Identity},
+};
+
+use super::{
+    encoder::OtlpEncoder,
+    service::{OpentelemetryRetryLogic, OpentelemetryService, Transport},
+    sink::OpentelemetrySink,
+};
+use crate::{
+    http::HttpClient,
+    sinks::{
+        prelude::*,
+        util::http::{validate_headers, RequestConfig},
+    },
+};
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Invalid endpoint URI: {}", source))]
+    Uri { source: http::uri::InvalidUri },
+    #[snafu(display("Could not create endpoint: {}", source))]
+    Endpoint { source: tonic::transport::Error },
+    #[snafu(display("Could not set up endpoint TLS settings: {}", source))]
+    EndpointTls { source: tonic::transport::Error },
+    #[snafu(display("Invalid gRPC metadata {:?}", name))]
+    Metadata { name: String },
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct OpentelemetryDefaultBatchSettings;
+
+impl SinkBatchSettings for OpentelemetryDefaultBatchSettings {
+    const MAX_EVENTS: Option<usize> = None;
+    // Stays under the default limit of 4 MiB of the messages received by OpenTelemetry
+    // collectors, the estimated size of the events being larger than their size in Protobuf.
+    const MAX_BYTES: Option<usize> = Some(4_000_000);
+    const TIMEOUT_SECS: f64 = 1.0;
+}
+
+/// The transport protocol of OTLP.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum OtlpProtocol {
+    /// OTLP over gRPC, usually on port 4317.
+    #[default]
+    Grpc,
+
+    /// OTLP over HTTP with binary Protobuf payloads, usually on port 4318.
+    ///
+    /// Logs, metrics, and traces are sent to the `/v1/logs`, `/v1/metrics`, and `/v1/traces`
+    /// paths of the endpoint.
+    Http,
+}
+
+/// Configuration for the `opentelemetry` sink.
+#[configurable_component(sink(
+    "opentelemetry",
+    "Export observability events over OTLP to OpenTelemetry-compatible backends."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct OpentelemetryConfig {
+    /// The endpoint of the OTLP receiver.
+    ///
+    /// With the `http` protocol, this is the base URL that the paths of the signals are appended
+    /// to.
+    #[configurable(metadata(docs::examples = "http://localhost:4317"))]
+    #[configurable(metadata(docs::examples = "https://otlp.example.com:4318"))]
+    pub endpoint: String,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub protocol: OtlpProtocol,
+
+    /// Attributes set on the resources of the exported events, rendered from the events.
+    ///
+    /// These are added to the attributes of the resources read from the events, as set by the
+    /// `opentelemetry` source, and replace the ones with the same keys. Attributes whose template
+    /// fails to render are omitted.
+    #[configurable(metadata(docs::examples = "resource_attributes_examples()"))]
+    #[configurable(metadata(
+        docs::additional_props_description = "A resource attribute, and the template of its value."
+    ))]
+    #[serde(default)]
+    pub resource_attributes: IndexMap<String, Template>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub compression: Compression,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch: BatchConfig<OpentelemetryDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: RequestConfig,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::is_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+fn resource_attributes_examples() -> IndexMap<String, String> {
+    IndexMap::from_iter([
+        ("service.name".to_owned(), "{{ service }}".to_owned()),
+        ("deployment.environment".to_owned(), "production".to_owned()),
+    ])
+}
+
+impl GenerateConfig for OpentelemetryConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(r#"endpoint = "http://localhost:4317""#).unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "opentelemetry")]
+impl SinkConfig for OpentelemetryConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        if !matches!(self.compression, Compression::None | Compression::Gzip(_)) {
+            return Err(
+                "The `opentelemetry` sink only supports the `none` and `gzip` compressions.".into(),
+            );
+        }
+
+        let batch_settings = self.batch.into_batcher_settings()?;
+        let transport = match self.protocol {
+            OtlpProtocol::Grpc => self.grpc_transport()?,
+            OtlpProtocol::Http => {
+                let tls_settings = TlsSettings::from_options(&self.tls)?;
+                Transport::Http {
+                    client: HttpClient::new(tls_settings, &cx.proxy)?,
+                    endpoint: self.endpoint.parse::<Uri>().context(UriSnafu)?,
+                    headers: validate_headers(&self.request.headers)?,
+                }
+            }
+        };
+
+        let service = ServiceBuilder::new()
+            .settings(self.request.tower.into_settings(), OpentelemetryRetryLogic)
+            .service(OpentelemetryService::new(transport, self.compression));
+
+        let sink = OpentelemetrySink {
+            batch_settings,
+            service,
+            encoder: OtlpEncoder {
+                resource_attributes: self.resource_attributes.clone(),
+            },
+            protocol: match self.protocol {
+                OtlpProtocol::Grpc => "grpc",
+                OtlpProtocol::Http => "http",
+            },
+        };
+
+        Ok((
+            VectorSink::from_event_streamsink(sink),
+            Box::pin(async { Ok(()) }),
+        ))
+    }
+
+    fn input(&self) -> Input {
+        Input::all()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+impl OpentelemetryConfig {
+    fn grpc_transport(&self) -> crate::Result<Transport> {
+        let uri: Uri = self.endpoint.parse().context(UriSnafu)?;
+
+        let mut endpoint: Endpoint = uri.to_string().parse().context(EndpointSnafu)?;
+        if uri.scheme() != Some(&Scheme::HTTP) {
+            let tls = TlsSettings::from_options(&self.tls)?;
+            let mut tls_config = ClientTlsConfig::new();
+            if let Some(host) = uri.host() {
+                tls_config = tls_config.domain_name(host);
+            }
+            if let Some((cert, key)) = tls.identity_pem() {
+                tls_config = tls_config.identity(Identity::from_pem(cert, key));
+            }
+            for authority in tls.authorities_pem() {
+                tls_config = tls_config.ca_certificate(Certificate::from_pem(authority));
+            }
+            endpoint = endpoint.tls_config(tls_config).context(EndpointTlsSnafu)?;
+        }
+
+        Ok(Transport::Grpc {
+            channel: endpoint.connect_lazy(),
+            metadata: grpc_metadata(&self.request.headers)?,
+        })
+    }
+}
+
+/// Converts the headers of the requests into the metadata of gRPC requests, whose keys are
+/// lowercase.
+fn grpc_metadata(headers: &IndexMap<String, String>) -> Result<MetadataMap, BuildError> {
+    let mut metadata = MetadataMap::new();
+    for (name, value) in headers {
+        let key = MetadataKey::from_bytes(name.to_lowercase().as_bytes())
+            .map_err(|_| BuildError::Metadata { name: name.clone() })?;
+        let value = MetadataValue::try_from(value.as_str())
+            .map_err(|_| BuildError::Metadata { name: name.clone() })?;
+        metadata.insert(key, value);
+    }
+    Ok(metadata)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<OpentelemetryConfig>();
+    }
+
+    #[test]
+    fn converts_headers_into_metadata() {
+        let headers = IndexMap::from_iter([("X-API-Key".to_owned(), "secret".to_owned())]);
+        let metadata = grpc_metadata(&headers).unwrap();
+        assert_eq!(metadata.get("x-api-key").unwrap(), "secret");
+
+        let headers = IndexMap::from_iter([("invalid key".to_owned(), "value".to_owned())]);
+        assert!(grpc_metadata(&headers).is_err());
+    }
+}
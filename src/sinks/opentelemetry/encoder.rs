@@ -0,0 +1,136 @@
+//! Encoding of events into the export requests of OTLP.
+
+use std::num::NonZeroUsize;
+
+use indexmap::IndexMap;
+use vector_lib::{
+    internal_event::{ComponentEventsDropped, INTENTIONAL},
+    opentelemetry::{
+        encode::{logs_request, metrics_request, traces_request, ResourceRecord},
+        proto::common::v1::{any_value, AnyValue},
+    },
+};
+
+use super::{
+    service::{ExportRequest, OpentelemetryRequest},
+    sink::Signal,
+};
+use crate::sinks::prelude::*;
+
+/// Converts events into the records of their signal, with the configured resource attributes.
+pub(super) struct OtlpEncoder {
+    pub(super) resource_attributes: IndexMap<String, Template>,
+}
+
+impl OtlpEncoder {
+    /// Renders the resource attributes from the event, omitting the ones that fail to render.
+    fn render_resource_attributes(&self, event: &Event) -> Vec<(String, AnyValue)> {
+        self.resource_attributes
+            .iter()
+            .filter_map(|(key, template)| {
+                let value = template
+                    .render_string(event)
+                    .map_err(|error| {
+                        emit!(TemplateRenderingError {
+                            error,
+                            field: Some("resource_attributes"),
+                            drop_event: false,
+                        });
+                    })
+                    .ok()?;
+                Some((
+                    key.clone(),
+                    AnyValue {
+                        value: Some(any_value::Value::StringValue(value)),
+                    },
+                ))
+            })
+            .collect()
+    }
+
+    fn with_resource_attributes<T>(
+        mut record: ResourceRecord<T>,
+        attributes: Vec<(String, AnyValue)>,
+    ) -> ResourceRecord<T> {
+        for (key, value) in attributes {
+            record.insert_resource_attribute(key, value);
+        }
+        record
+    }
+
+    /// Encodes the events of the signal into an export request.
+    ///
+    /// Sketches have no OTLP equivalent and are rejected. Returns `None` if none of the events
+    /// could be encoded.
+    pub(super) fn encode(
+        &self,
+        signal: Signal,
+        events: Vec<Event>,
+    ) -> Option<OpentelemetryRequest> {
+        let mut finalizers = EventFinalizers::default();
+        let mut byte_size = telemetry().create_request_count_byte_size();
+        let mut logs = Vec::new();
+        let mut metrics = Vec::new();
+        let mut spans = Vec::new();
+        let mut dropped = 0;
+
+        for mut event in events {
+            let event_finalizers = event.take_finalizers();
+            let attributes = self.render_resource_attributes(&event);
+
+            let mut event_size = telemetry().create_request_count_byte_size();
+            event_size.add_event(&event, event.estimated_json_encoded_size_of());
+
+            match event {
+                Event::Log(log) => logs.push(Self::with_resource_attributes(
+                    ResourceRecord::from(log),
+                    attributes,
+                )),
+                Event::Trace(trace) => spans.push(Self::with_resource_attributes(
+                    ResourceRecord::from(trace),
+                    attributes,
+                )),
+                Event::Metric(metric) => match ResourceRecord::from_metric(&metric) {
+                    Some(record) => {
+                        metrics.push(Self::with_resource_attributes(record, attributes))
+                    }
+                    None => {
+                        dropped += 1;
+                        event_finalizers.update_status(EventStatus::Rejected);
+                        continue;
+                    }
+                },
+            }
+            byte_size += event_size;
+            finalizers.merge(event_finalizers);
+        }
+
+        if dropped > 0 {
+            emit!(ComponentEventsDropped::<INTENTIONAL> {
+                count: dropped,
+                reason: "Sketches are not supported by OTLP."
+            });
+        }
+
+        // The events are partitioned by signal, so only the records of the signal are set.
+        let count = logs.len() + metrics.len() + spans.len();
+        if count == 0 {
+            return None;
+        }
+        let export = match signal {
+            Signal::Logs => ExportRequest::Logs(logs_request(logs)),
+            Signal::Metrics => ExportRequest::Metrics(metrics_request(metrics)),
+            Signal::Traces => ExportRequest::Traces(traces_request(spans)),
+        };
+
+        let request_size = export.encoded_len();
+        let metadata = RequestMetadataBuilder::new(count, request_size, byte_size)
+            .with_request_size(NonZeroUsize::new(request_size).unwrap_or(NonZeroUsize::MIN));
+
+        Some(OpentelemetryRequest {
+            export,
+            finalizers,
+            metadata,
+        })
+    }
+}
@@ -0,0 +1,15 @@
+//! The `opentelemetry` sink.
+//!
+//! This sink exports log, metric, and trace events to an [OTLP][otlp] receiver, such as the
+//! OpenTelemetry Collector, with either the gRPC or the HTTP transport of the protocol. Events
+//! are converted into the records of their signal, the inverse of the conversions of the
+//! `opentelemetry` source, and grouped into export requests by resource and instrumentation scope.
+//!
+//! [otlp]: https://opentelemetry.io/docs/specs/otlp/
+
+mod config;
+mod encoder;
+mod service;
+mod sink;
+
+pub use self::config::OpentelemetryConfig;
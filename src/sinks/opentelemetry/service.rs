@@ -0,0 +1,425 @@
+//! Service implementation for the `opentelemetry` sink.
+
+use std::{
+    io::Write,
+    task::{Context, Poll},
+};
+
+use bytes::Bytes;
+use http::{
+    header::{CONTENT_ENCODING, CONTENT_TYPE},
+    HeaderName, HeaderValue, Request, StatusCode, Uri,
+};
+use hyper::Body;
+use indexmap::IndexMap;
+use prost::Message;
+use snafu::{ResultExt, Snafu};
+use tonic::{codec::CompressionEncoding, metadata::MetadataMap, transport::Channel, Code};
+use vector_lib::opentelemetry::proto::collector::{
+    logs::v1::{
+        logs_service_client::LogsServiceClient, ExportLogsServiceRequest, ExportLogsServiceResponse,
+    },
+    metrics::v1::{
+        metrics_service_client::MetricsServiceClient, ExportMetricsServiceRequest,
+        ExportMetricsServiceResponse,
+    },
+    trace::v1::{
+        trace_service_client::TraceServiceClient, ExportTraceServiceRequest,
+        ExportTraceServiceResponse,
+    },
+};
+
+use crate::{
+    http::{HttpClient, HttpError},
+    sinks::{prelude::*, util::Compressor},
+};
+
+/// The codes of the gRPC errors which are retried, as listed by the OTLP specification.
+const RETRIABLE_CODES: [Code; 6] = [
+    Code::Cancelled,
+    Code::DeadlineExceeded,
+    Code::Aborted,
+    Code::OutOfRange,
+    Code::Unavailable,
+    Code::DataLoss,
+];
+
+#[derive(Debug, Snafu)]
+pub(super) enum OpentelemetryError {
+    #[snafu(display("OTLP request failed: {}", source))]
+    Grpc { source: tonic::Status },
+    #[snafu(display("{}", source))]
+    Http { source: HttpError },
+    #[snafu(display("Failed to build the request: {}", source))]
+    Build { source: http::Error },
+    #[snafu(display("Failed to compress the request: {}", source))]
+    Compression { source: std::io::Error },
+    #[snafu(display("OTLP request failed with status {}: {}", status, body))]
+    Status { status: StatusCode, body: String },
+}
+
+/// An export request of one of the signals.
+#[derive(Clone, Debug)]
+pub(super) enum ExportRequest {
+    Logs(ExportLogsServiceRequest),
+    Metrics(ExportMetricsServiceRequest),
+    Traces(ExportTraceServiceRequest),
+}
+
+impl ExportRequest {
+    pub(super) fn encoded_len(&self) -> usize {
+        match self {
+            Self::Logs(request) => request.encoded_len(),
+            Self::Metrics(request) => request.encoded_len(),
+            Self::Traces(request) => request.encoded_len(),
+        }
+    }
+
+    fn encode_to_vec(&self) -> Vec<u8> {
+        match self {
+            Self::Logs(request) => request.encode_to_vec(),
+            Self::Metrics(request) => request.encode_to_vec(),
+            Self::Traces(request) => request.encode_to_vec(),
+        }
+    }
+
+    /// The path of the signal under the endpoint, with the `http` protocol.
+    const fn path(&self) -> &'static str {
+        match self {
+            Self::Logs(_) => "v1/logs",
+            Self::Metrics(_) => "v1/metrics",
+            Self::Traces(_) => "v1/traces",
+        }
+    }
+}
+
+#[derive(Clone)]
+pub(super) struct OpentelemetryRequest {
+    pub(super) export: ExportRequest,
+    pub(super) finalizers: EventFinalizers,
+    pub(super) metadata: RequestMetadata,
+}
+
+impl Finalizable for OpentelemetryRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        std::mem::take(&mut self.finalizers)
+    }
+}
+
+impl MetaDescriptive for OpentelemetryRequest {
+    fn get_metadata(&self) -> &RequestMetadata {
+        &self.metadata
+    }
+
+    fn metadata_mut(&mut self) -> &mut RequestMetadata {
+        &mut self.metadata
+    }
+}
+
+#[derive(Debug)]
+pub(super) struct OpentelemetryResponse {
+    events_byte_size: GroupedCountByteSize,
+    byte_size: usize,
+}
+
+impl DriverResponse for OpentelemetryResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> &GroupedCountByteSize {
+        &self.events_byte_size
+    }
+
+    fn bytes_sent(&self) -> Option<usize> {
+        Some(self.byte_size)
+    }
+}
+
+#[derive(Clone, Default)]
+pub(super) struct OpentelemetryRetryLogic;
+
+impl RetryLogic for OpentelemetryRetryLogic {
+    type Error = OpentelemetryError;
+    type Response = OpentelemetryResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        match error {
+            OpentelemetryError::Grpc { source } => RETRIABLE_CODES.contains(&source.code()),
+            OpentelemetryError::Http { source } => source.is_retriable(),
+            OpentelemetryError::Build { .. } | OpentelemetryError::Compression { .. } => false,
+            OpentelemetryError::Status { status, .. } => matches!(
+                *status,
+                StatusCode::TOO_MANY_REQUESTS
+                    | StatusCode::BAD_GATEWAY
+                    | StatusCode::SERVICE_UNAVAILABLE
+                    | StatusCode::GATEWAY_TIMEOUT
+            ),
+        }
+    }
+}
+
+/// The transport that requests are exported with.
+#[derive(Clone)]
+pub(super) enum Transport {
+    Grpc {
+        channel: Channel,
+        metadata: MetadataMap,
+    },
+    Http {
+        client: HttpClient,
+        endpoint: Uri,
+        headers: IndexMap<HeaderName, HeaderValue>,
+    },
+}
+
+#[derive(Clone)]
+pub(super) struct OpentelemetryService {
+    transport: Transport,
+    compression: Compression,
+}
+
+impl OpentelemetryService {
+    pub(super) const fn new(transport: Transport, compression: Compression) -> Self {
+        Self {
+            transport,
+            compression,
+        }
+    }
+}
+
+impl Service<OpentelemetryRequest> for OpentelemetryService {
+    type Response = OpentelemetryResponse;
+    type Error = OpentelemetryError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: OpentelemetryRequest) -> Self::Future {
+        let service = self.clone();
+
+        Box::pin(async move {
+            let byte_size = request.export.encoded_len();
+            match &service.transport {
+                Transport::Grpc { channel, metadata } => {
+                    service
+                        .export_grpc(channel.clone(), metadata, request.export)
+                        .await?
+                }
+                Transport::Http {
+                    client,
+                    endpoint,
+                    headers,
+                } => {
+                    service
+                        .export_http(client, endpoint, headers, request.export)
+                        .await?
+                }
+            }
+
+            Ok(OpentelemetryResponse {
+                events_byte_size: request
+                    .metadata
+                    .into_events_estimated_json_encoded_byte_size(),
+                byte_size,
+            })
+        })
+    }
+}
+
+impl OpentelemetryService {
+    fn grpc_request<T>(message: T, metadata: &MetadataMap) -> tonic::Request<T> {
+        let mut request = tonic::Request::new(message);
+        *request.metadata_mut() = metadata.clone();
+        request
+    }
+
+    async fn export_grpc(
+        &self,
+        channel: Channel,
+        metadata: &MetadataMap,
+        export: ExportRequest,
+    ) -> Result<(), OpentelemetryError> {
+        let compressed = self.compression.is_compressed();
+        let partial_success = match export {
+            ExportRequest::Logs(request) => {
+                let mut client = LogsServiceClient::new(channel);
+                if compressed {
+                    client = client.send_compressed(CompressionEncoding::Gzip);
+                }
+                let response = client
+                    .export(Self::grpc_request(request, metadata))
+                    .await
+                    .context(GrpcSnafu)?;
+                logs_partial_success(response.into_inner())
+            }
+            ExportRequest::Metrics(request) => {
+                let mut client = MetricsServiceClient::new(channel);
+                if compressed {
+                    client = client.send_compressed(CompressionEncoding::Gzip);
+                }
+                let response = client
+                    .export(Self::grpc_request(request, metadata))
+                    .await
+                    .context(GrpcSnafu)?;
+                metrics_partial_success(response.into_inner())
+            }
+            ExportRequest::Traces(request) => {
+                let mut client = TraceServiceClient::new(channel);
+                if compressed {
+                    client = client.send_compressed(CompressionEncoding::Gzip);
+                }
+                let response = client
+                    .export(Self::grpc_request(request, metadata))
+                    .await
+                    .context(GrpcSnafu)?;
+                traces_partial_success(response.into_inner())
+            }
+        };
+        report_partial_success(partial_success);
+        Ok(())
+    }
+
+    async fn export_http(
+        &self,
+        client: &HttpClient,
+        endpoint: &Uri,
+        headers: &IndexMap<HeaderName, HeaderValue>,
+        export: ExportRequest,
+    ) -> Result<(), OpentelemetryError> {
+        let mut compressor = Compressor::from(self.compression);
+        compressor
+            .write_all(&export.encode_to_vec())
+            .context(CompressionSnafu)?;
+        let body = compressor.finish().context(CompressionSnafu)?.freeze();
+
+        let uri = format!(
+            "{}/{}",
+            endpoint.to_string().trim_end_matches('/'),
+            export.path()
+        );
+        let mut builder = Request::post(uri).header(CONTENT_TYPE, "application/x-protobuf");
+        if let Some(encoding) = self.compression.content_encoding() {
+            builder = builder.header(CONTENT_ENCODING, encoding);
+        }
+        for (name, value) in headers {
+            builder = builder.header(name, value);
+        }
+        let request = builder.body(Body::from(body)).context(BuildSnafu)?;
+
+        let response = client.send(request).await.context(HttpSnafu)?;
+        let status = response.status();
+        let body = hyper::body::to_bytes(response.into_body())
+            .await
+            .map_err(|source| OpentelemetryError::Http {
+                source: HttpError::CallRequest { source },
+            })?;
+
+        if !status.is_success() {
+            return StatusSnafu {
+                status,
+                body: String::from_utf8_lossy(&body).into_owned(),
+            }
+            .fail();
+        }
+
+        report_partial_success(http_partial_success(&export, body));
+        Ok(())
+    }
+}
+
+/// The number of records rejected by a successful export, and the reason.
+type PartialSuccess = Option<(i64, String)>;
+
+fn logs_partial_success(response: ExportLogsServiceResponse) -> PartialSuccess {
+    response
+        .partial_success
+        .map(|partial| (partial.rejected_log_records, partial.error_message))
+}
+
+fn metrics_partial_success(response: ExportMetricsServiceResponse) -> PartialSuccess {
+    response
+        .partial_success
+        .map(|partial| (partial.rejected_data_points, partial.error_message))
+}
+
+fn traces_partial_success(response: ExportTraceServiceResponse) -> PartialSuccess {
+    response
+        .partial_success
+        .map(|partial| (partial.rejected_spans, partial.error_message))
+}
+
+/// Decodes the partial success of the response, the responses which aren't Protobuf messages
+/// being ignored.
+fn http_partial_success(export: &ExportRequest, body: Bytes) -> PartialSuccess {
+    match export {
+        ExportRequest::Logs(_) => ExportLogsServiceResponse::decode(body)
+            .ok()
+            .and_then(logs_partial_success),
+        ExportRequest::Metrics(_) => ExportMetricsServiceResponse::decode(body)
+            .ok()
+            .and_then(metrics_partial_success),
+        ExportRequest::Traces(_) => ExportTraceServiceResponse::decode(body)
+            .ok()
+            .and_then(traces_partial_success),
+    }
+}
+
+/// Records rejected by a partial success aren't retried, as the OTLP specification requires.
+fn report_partial_success(partial_success: PartialSuccess) {
+    if let Some((rejected, error_message)) = partial_success {
+        if rejected > 0 || !error_message.is_empty() {
+            warn!(
+                message = "OTLP receiver rejected some of the records.",
+                rejected,
+                %error_message,
+                internal_log_rate_limit = true,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_lib::opentelemetry::proto::collector::logs::v1::ExportLogsPartialSuccess;
+
+    use super::*;
+
+    #[test]
+    fn retries_transient_errors() {
+        let logic = OpentelemetryRetryLogic;
+        assert!(logic.is_retriable_error(&OpentelemetryError::Grpc {
+            source: tonic::Status::unavailable("unavailable")
+        }));
+        assert!(!logic.is_retriable_error(&OpentelemetryError::Grpc {
+            source: tonic::Status::invalid_argument("invalid")
+        }));
+        assert!(logic.is_retriable_error(&OpentelemetryError::Status {
+            status: StatusCode::SERVICE_UNAVAILABLE,
+            body: String::new()
+        }));
+        assert!(!logic.is_retriable_error(&OpentelemetryError::Status {
+            status: StatusCode::BAD_REQUEST,
+            body: String::new()
+        }));
+    }
+
+    #[test]
+    fn decodes_partial_successes() {
+        let export = ExportRequest::Logs(ExportLogsServiceRequest::default());
+        let response = ExportLogsServiceResponse {
+            partial_success: Some(ExportLogsPartialSuccess {
+                rejected_log_records: 2,
+                error_message: "invalid records".into(),
+            }),
+        };
+
+        assert_eq!(
+            http_partial_success(&export, response.encode_to_vec().into()),
+            Some((2, "invalid records".to_string()))
+        );
+        assert_eq!(http_partial_success(&export, Bytes::from("{}")), None);
+    }
+}
@@ -0,0 +1,89 @@
+//! Implementation of the `opentelemetry` sink.
+
+use super::{
+    encoder::OtlpEncoder,
+    service::{OpentelemetryRetryLogic, OpentelemetryService},
+};
+use crate::sinks::prelude::*;
+
+/// The OTLP signal that events are exported as.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub(super) enum Signal {
+    Logs,
+    Metrics,
+    Traces,
+}
+
+/// Partitions events by signal, as each export request only carries the records of one signal.
+struct SignalPartitioner;
+
+impl Partitioner for SignalPartitioner {
+    type Item = Event;
+    type Key = Signal;
+
+    fn partition(&self, item: &Self::Item) -> Self::Key {
+        match item {
+            Event::Log(_) => Signal::Logs,
+            Event::Metric(_) => Signal::Metrics,
+            Event::Trace(_) => Signal::Traces,
+        }
+    }
+}
+
+pub(super) struct OpentelemetrySink {
+    pub(super) batch_settings: BatcherSettings,
+    pub(super) service: Svc<OpentelemetryService, OpentelemetryRetryLogic>,
+    pub(super) encoder: OtlpEncoder,
+    pub(super) protocol: &'static str,
+}
+
+impl OpentelemetrySink {
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let batch_settings = self.batch_settings;
+        let encoder = self.encoder;
+
+        input
+            .batched_partitioned(SignalPartitioner, || batch_settings.as_byte_size_config())
+            .filter_map(move |(signal, events)| {
+                let request = encoder.encode(signal, events);
+                async move { request }
+            })
+            .into_driver(self.service)
+            .protocol(self.protocol)
+            .run()
+            .await
+    }
+}
+
+#[async_trait::async_trait]
+impl StreamSink<Event> for OpentelemetrySink {
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_lib::event::{LogEvent, Metric, MetricKind, MetricValue, TraceEvent};
+
+    use super::*;
+
+    #[test]
+    fn partitions_by_signal() {
+        let metric = Metric::new(
+            "requests",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 1.0 },
+        );
+
+        assert_eq!(
+            SignalPartitioner.partition(&LogEvent::from("hello").into()),
+            Signal::Logs
+        );
+        assert_eq!(SignalPartitioner.partition(&metric.into()), Signal::Metrics);
+        assert_eq!(
+            SignalPartitioner.partition(&TraceEvent::default().into()),
+            Signal::Traces
+        );
+    }
+}
@@ -0,0 +1,293 @@
+//! Rendering of the PagerDuty alerts of log events.
+
+use serde::Serialize;
+
+use crate::{
+    internal_events::{
+        PagerDutyInvalidEventAction, PagerDutyInvalidSeverity, PagerDutyMissingDedupKey,
+    },
+    sinks::prelude::*,
+};
+
+/// The maximum length of the summary of alerts, in characters.
+const MAX_SUMMARY_LENGTH: usize = 1024;
+
+/// The action of a PagerDuty event on the alert of its dedup key.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(super) enum EventAction {
+    Trigger,
+    Acknowledge,
+    Resolve,
+}
+
+impl EventAction {
+    fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "trigger" => Some(Self::Trigger),
+            "acknowledge" => Some(Self::Acknowledge),
+            "resolve" => Some(Self::Resolve),
+            _ => None,
+        }
+    }
+
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Trigger => "trigger",
+            Self::Acknowledge => "acknowledge",
+            Self::Resolve => "resolve",
+        }
+    }
+}
+
+/// The severity of a PagerDuty alert.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(super) enum Severity {
+    Critical,
+    Error,
+    Warning,
+    Info,
+}
+
+impl Severity {
+    /// Maps the name or the numerical code of a syslog severity, or the name of a common log
+    /// level, to the severity of PagerDuty it matches the closest.
+    fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "critical" | "crit" | "emergency" | "emerg" | "panic" | "alert" | "fatal" | "0"
+            | "1" | "2" => Some(Self::Critical),
+            "error" | "err" | "3" => Some(Self::Error),
+            "warning" | "warn" | "notice" | "4" | "5" => Some(Self::Warning),
+            "info" | "informational" | "debug" | "trace" | "6" | "7" => Some(Self::Info),
+            _ => None,
+        }
+    }
+}
+
+/// The fields of the payload of the alerts triggered by events.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(super) struct AlertPayload {
+    pub(super) summary: String,
+    pub(super) source: String,
+    pub(super) severity: Severity,
+    pub(super) component: Option<String>,
+    pub(super) group: Option<String>,
+    pub(super) class: Option<String>,
+}
+
+/// A log event, with the PagerDuty event rendered for it.
+pub(super) struct PagerDutyAlert {
+    pub(super) event: Event,
+    pub(super) event_action: EventAction,
+    pub(super) dedup_key: Option<String>,
+    /// The payload of the alert, which is only sent with the events that trigger alerts.
+    pub(super) payload: Option<AlertPayload>,
+}
+
+/// Renders the PagerDuty event of log events.
+#[derive(Clone, Debug)]
+pub(super) struct PagerDutyAlertBuilder {
+    pub(super) event_action: Template,
+    pub(super) dedup_key: Option<Template>,
+    pub(super) summary: Template,
+    pub(super) source: Option<Template>,
+    pub(super) severity: Template,
+    pub(super) component: Option<Template>,
+    pub(super) group: Option<Template>,
+    pub(super) class: Option<Template>,
+    pub(super) local_hostname: Option<String>,
+}
+
+impl PagerDutyAlertBuilder {
+    /// Renders the PagerDuty event of a log event, or drops the log event if the event can't be
+    /// sent.
+    pub(super) fn build(&self, event: Event) -> Option<PagerDutyAlert> {
+        let event_action = render(&self.event_action, "event_action", &event, true)?;
+        let Some(event_action) = EventAction::parse(&event_action) else {
+            emit!(PagerDutyInvalidEventAction {
+                value: &event_action,
+            });
+            return None;
+        };
+
+        let dedup_key = match &self.dedup_key {
+            Some(dedup_key) => Some(render(dedup_key, "dedup_key", &event, true)?),
+            None => None,
+        };
+        if event_action != EventAction::Trigger && dedup_key.is_none() {
+            emit!(PagerDutyMissingDedupKey {
+                event_action: event_action.as_str(),
+            });
+            return None;
+        }
+
+        let payload = match event_action {
+            EventAction::Trigger => Some(self.payload(&event)?),
+            EventAction::Acknowledge | EventAction::Resolve => None,
+        };
+
+        Some(PagerDutyAlert {
+            event,
+            event_action,
+            dedup_key,
+            payload,
+        })
+    }
+
+    fn payload(&self, event: &Event) -> Option<AlertPayload> {
+        let mut summary = render(&self.summary, "summary", event, true)?;
+        if let Some((index, _)) = summary.char_indices().nth(MAX_SUMMARY_LENGTH) {
+            summary.truncate(index);
+        }
+
+        let source = match &self.source {
+            Some(source) => render(source, "source", event, false),
+            None => event
+                .as_log()
+                .get_host()
+                .map(|host| host.to_string_lossy().into_owned()),
+        }
+        .or_else(|| self.local_hostname.clone())
+        .unwrap_or_else(|| "vector".to_string());
+
+        let severity = render(&self.severity, "severity", event, false)
+            .and_then(|severity| {
+                let parsed = Severity::parse(&severity);
+                if parsed.is_none() {
+                    emit!(PagerDutyInvalidSeverity { value: &severity });
+                }
+                parsed
+            })
+            .unwrap_or(Severity::Error);
+
+        let render_optional = |template: &Option<Template>, field| {
+            template
+                .as_ref()
+                .and_then(|template| render(template, field, event, false))
+        };
+
+        Some(AlertPayload {
+            summary,
+            source,
+            severity,
+            component: render_optional(&self.component, "component"),
+            group: render_optional(&self.group, "group"),
+            class: render_optional(&self.class, "class"),
+        })
+    }
+}
+
+fn render(
+    template: &Template,
+    field: &'static str,
+    event: &Event,
+    drop_event: bool,
+) -> Option<String> {
+    template
+        .render_string(event)
+        .map_err(|error| {
+            emit!(TemplateRenderingError {
+                error,
+                field: Some(field),
+                drop_event,
+            })
+        })
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn builder() -> PagerDutyAlertBuilder {
+        PagerDutyAlertBuilder {
+            event_action: Template::try_from("{{ action }}").unwrap(),
+            dedup_key: Some(Template::try_from("{{ host }}-{{ check }}").unwrap()),
+            summary: Template::try_from("{{ message }}").unwrap(),
+            source: None,
+            severity: Template::try_from("{{ level }}").unwrap(),
+            component: Some(Template::try_from("{{ check }}").unwrap()),
+            group: None,
+            class: None,
+            local_hostname: Some("vector-host".to_string()),
+        }
+    }
+
+    fn event(action: &str, level: Option<&str>) -> Event {
+        let mut log = LogEvent::from("disk full");
+        log.insert("action", action);
+        log.insert("host", "db-1");
+        log.insert("check", "disk");
+        if let Some(level) = level {
+            log.insert("level", level);
+        }
+        log.into()
+    }
+
+    #[test]
+    fn maps_severities() {
+        for (value, severity) in [
+            ("emerg", Severity::Critical),
+            ("FATAL", Severity::Critical),
+            ("2", Severity::Critical),
+            ("err", Severity::Error),
+            ("notice", Severity::Warning),
+            ("warn", Severity::Warning),
+            ("debug", Severity::Info),
+            ("6", Severity::Info),
+        ] {
+            assert_eq!(Severity::parse(value), Some(severity), "{value}");
+        }
+        assert_eq!(Severity::parse("bogus"), None);
+    }
+
+    #[test]
+    fn renders_triggers() {
+        let alert = builder().build(event("trigger", Some("crit"))).unwrap();
+
+        assert_eq!(alert.event_action, EventAction::Trigger);
+        assert_eq!(alert.dedup_key.as_deref(), Some("db-1-disk"));
+        assert_eq!(
+            alert.payload,
+            Some(AlertPayload {
+                summary: "disk full".to_string(),
+                source: "db-1".to_string(),
+                severity: Severity::Critical,
+                component: Some("disk".to_string()),
+                group: None,
+                class: None,
+            })
+        );
+    }
+
+    #[test]
+    fn defaults_invalid_severities_to_error() {
+        let alert = builder().build(event("trigger", Some("bogus"))).unwrap();
+        assert_eq!(alert.payload.unwrap().severity, Severity::Error);
+
+        let alert = builder().build(event("trigger", None)).unwrap();
+        assert_eq!(alert.payload.unwrap().severity, Severity::Error);
+    }
+
+    #[test]
+    fn renders_resolves_without_payload() {
+        let alert = builder().build(event("Resolve", None)).unwrap();
+
+        assert_eq!(alert.event_action, EventAction::Resolve);
+        assert_eq!(alert.dedup_key.as_deref(), Some("db-1-disk"));
+        assert_eq!(alert.payload, None);
+    }
+
+    #[test]
+    fn drops_invalid_events() {
+        assert!(builder().build(event("escalate", None)).is_none());
+
+        let builder = PagerDutyAlertBuilder {
+            dedup_key: None,
+            ..builder()
+        };
+        assert!(builder.build(event("acknowledge", None)).is_none());
+        assert!(builder.build(event("trigger", None)).is_some());
+    }
+}
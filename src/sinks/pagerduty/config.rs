@@ -0,0 +1,207 @@
+//! Configuration for the `pagerduty` sink.
+
+use http::Uri;
+use vector_lib::sensitive_string::SensitiveString;
+
+use super::{
+    alert::PagerDutyAlertBuilder, encoder::PagerDutyEncoder,
+    request_builder::PagerDutyRequestBuilder, service::PagerDutyServiceRequestBuilder,
+    sink::PagerDutySink,
+};
+use crate::{
+    http::HttpClient,
+    sinks::{
+        prelude::*,
+        util::{
+            http::{http_response_retry_logic, HttpService},
+            service::TowerRequestConfigDefaults,
+        },
+    },
+};
+
+#[derive(Clone, Copy, Debug)]
+pub struct PagerDutyTowerRequestConfigDefaults;
+
+impl TowerRequestConfigDefaults for PagerDutyTowerRequestConfigDefaults {
+    const RATE_LIMIT_DURATION_SECS: u64 = 60;
+    const RATE_LIMIT_NUM: u64 = 120;
+}
+
+/// Configuration for the `pagerduty` sink.
+#[configurable_component(sink(
+    "pagerduty",
+    "Trigger, acknowledge, and resolve PagerDuty alerts through the Events API v2."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct PagerDutySinkConfig {
+    /// The endpoint of the Events API v2 to send events to.
+    ///
+    /// This only needs to be changed for accounts in the EU service region, whose endpoint is
+    /// `https://events.eu.pagerduty.com/v2/enqueue`.
+    #[serde(default = "default_endpoint")]
+    #[configurable(metadata(docs::examples = "https://events.eu.pagerduty.com/v2/enqueue"))]
+    pub endpoint: String,
+
+    /// The integration key of the Events API v2 integration of the service that alerts are sent to.
+    #[configurable(metadata(docs::examples = "${PAGERDUTY_ROUTING_KEY}"))]
+    pub routing_key: SensitiveString,
+
+    /// The action of the events on their alert.
+    ///
+    /// This must render to `trigger`, `acknowledge`, or `resolve`. Events that acknowledge or
+    /// resolve alerts must have a `dedup_key`.
+    #[serde(default = "default_event_action")]
+    #[configurable(metadata(docs::examples = "{{ action }}"))]
+    pub event_action: Template,
+
+    /// The key that identifies the alert of the events.
+    ///
+    /// Events with the same dedup key trigger, acknowledge, and resolve the same alert. If not
+    /// set, PagerDuty generates a new key for each triggered alert, and events can't acknowledge
+    /// or resolve alerts.
+    #[configurable(metadata(docs::examples = "{{ host }}-{{ check }}"))]
+    pub dedup_key: Option<Template>,
+
+    /// The summary of the alerts.
+    ///
+    /// The summary is truncated to 1024 characters.
+    #[serde(default = "default_summary")]
+    #[configurable(metadata(docs::examples = "{{ host }}: {{ message }}"))]
+    pub summary: Template,
+
+    /// The affected system of the alerts.
+    ///
+    /// If not set, the host of the events is used, or the hostname of the machine that Vector runs
+    /// on for the events that have no host.
+    #[configurable(metadata(docs::examples = "{{ service }}"))]
+    pub source: Option<Template>,
+
+    /// The severity of the alerts.
+    ///
+    /// This can be a severity of PagerDuty, which is `critical`, `error`, `warning`, or `info`,
+    /// or the name or numerical code of a syslog severity, or the name of a common log level,
+    /// which are mapped to the severity of PagerDuty they match the closest. If the severity of
+    /// an event can't be rendered or isn't valid, `error` is used instead.
+    #[serde(default = "default_severity")]
+    #[configurable(metadata(docs::examples = "critical"))]
+    #[configurable(metadata(docs::examples = "{{ level }}"))]
+    pub severity: Template,
+
+    /// The component of the affected system that is responsible for the alerts.
+    #[configurable(metadata(docs::examples = "{{ check }}"))]
+    pub component: Option<Template>,
+
+    /// The logical grouping of the components of the affected system.
+    #[configurable(metadata(docs::examples = "{{ cluster }}"))]
+    pub group: Option<Template>,
+
+    /// The class or type of the alerts.
+    #[configurable(metadata(docs::examples = "{{ kind }}"))]
+    pub class: Option<Template>,
+
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "crate::serde::is_default")]
+    pub encoding: Transformer,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: TowerRequestConfig<PagerDutyTowerRequestConfigDefaults>,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::is_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+fn default_endpoint() -> String {
+    "https://events.pagerduty.com/v2/enqueue".to_string()
+}
+
+fn default_event_action() -> Template {
+    Template::try_from("trigger").unwrap()
+}
+
+fn default_summary() -> Template {
+    Template::try_from("{{ message }}").unwrap()
+}
+
+fn default_severity() -> Template {
+    Template::try_from("error").unwrap()
+}
+
+impl GenerateConfig for PagerDutySinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"routing_key = "${PAGERDUTY_ROUTING_KEY}"
+            dedup_key = "{{ host }}""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "pagerduty")]
+impl SinkConfig for PagerDutySinkConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let endpoint = self.endpoint.parse::<Uri>()?;
+
+        let tls_settings = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new(tls_settings, cx.proxy())?;
+
+        let service = HttpService::new(client, PagerDutyServiceRequestBuilder { endpoint });
+        let service = ServiceBuilder::new()
+            .settings(self.request.into_settings(), http_response_retry_logic())
+            .service(service);
+
+        let sink = PagerDutySink {
+            service,
+            alert_builder: PagerDutyAlertBuilder {
+                event_action: self.event_action.clone(),
+                dedup_key: self.dedup_key.clone(),
+                summary: self.summary.clone(),
+                source: self.source.clone(),
+                severity: self.severity.clone(),
+                component: self.component.clone(),
+                group: self.group.clone(),
+                class: self.class.clone(),
+                local_hostname: crate::get_hostname().ok(),
+            },
+            request_builder: PagerDutyRequestBuilder {
+                encoder: PagerDutyEncoder {
+                    routing_key: self.routing_key.clone(),
+                    transformer: self.encoding.clone(),
+                },
+            },
+        };
+
+        // The Events API has no endpoint to check that doesn't trigger an alert.
+        let healthcheck = future::ok(()).boxed();
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<PagerDutySinkConfig>();
+    }
+}
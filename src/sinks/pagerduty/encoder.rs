@@ -0,0 +1,183 @@
+//! Encoding for the `pagerduty` sink.
+
+use std::io;
+
+use chrono::SecondsFormat;
+use serde::Serialize;
+use vector_lib::sensitive_string::SensitiveString;
+
+use super::alert::{EventAction, PagerDutyAlert, Severity};
+use crate::sinks::{
+    prelude::*,
+    util::encoding::{write_all, Encoder as SinkEncoder},
+};
+
+/// The name of the client that sends the events, which PagerDuty shows on the alerts.
+const CLIENT: &str = "Vector";
+
+/// An event of the Events API v2.
+#[derive(Serialize)]
+struct EventBody<'a> {
+    routing_key: &'a str,
+    event_action: EventAction,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dedup_key: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    payload: Option<PayloadBody<'a>>,
+    client: &'static str,
+}
+
+#[derive(Serialize)]
+struct PayloadBody<'a> {
+    summary: &'a str,
+    source: &'a str,
+    severity: Severity,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    timestamp: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    component: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    group: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    class: Option<&'a str>,
+    custom_details: &'a LogEvent,
+}
+
+/// Encodes alerts into the JSON events of the Events API v2, with their log event as the custom
+/// details of the alerts they trigger.
+pub(super) struct PagerDutyEncoder {
+    pub(super) routing_key: SensitiveString,
+    pub(super) transformer: Transformer,
+}
+
+impl SinkEncoder<PagerDutyAlert> for PagerDutyEncoder {
+    fn encode_input(
+        &self,
+        alert: PagerDutyAlert,
+        writer: &mut dyn io::Write,
+    ) -> io::Result<(usize, GroupedCountByteSize)> {
+        let PagerDutyAlert {
+            mut event,
+            event_action,
+            dedup_key,
+            payload,
+        } = alert;
+
+        self.transformer.transform(&mut event);
+
+        let mut byte_size = telemetry().create_request_count_byte_size();
+        byte_size.add_event(&event, event.estimated_json_encoded_size_of());
+
+        let log = event.as_log();
+        let timestamp = log
+            .get_timestamp()
+            .and_then(Value::as_timestamp)
+            .map(|timestamp| timestamp.to_rfc3339_opts(SecondsFormat::Millis, true));
+
+        let body = serde_json::to_vec(&EventBody {
+            routing_key: self.routing_key.inner(),
+            event_action,
+            dedup_key: dedup_key.as_deref(),
+            payload: payload.as_ref().map(|payload| PayloadBody {
+                summary: &payload.summary,
+                source: &payload.source,
+                severity: payload.severity,
+                timestamp,
+                component: payload.component.as_deref(),
+                group: payload.group.as_deref(),
+                class: payload.class.as_deref(),
+                custom_details: log,
+            }),
+            client: CLIENT,
+        })?;
+
+        write_all(writer, 1, &body).map(|()| (body.len(), byte_size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use serde_json::json;
+
+    use super::*;
+    use crate::sinks::pagerduty::alert::AlertPayload;
+
+    fn encode(alert: PagerDutyAlert) -> serde_json::Value {
+        let encoder = PagerDutyEncoder {
+            routing_key: "routing-key".to_string().into(),
+            transformer: Transformer::default(),
+        };
+
+        let mut output = Vec::new();
+        let (len, _) = encoder.encode_input(alert, &mut output).unwrap();
+        assert_eq!(len, output.len());
+        serde_json::from_slice(&output).unwrap()
+    }
+
+    #[test]
+    fn encodes_triggers() {
+        let mut log = LogEvent::from("disk full");
+        log.insert(
+            "timestamp",
+            Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap(),
+        );
+        log.insert("host", "db-1");
+
+        let body = encode(PagerDutyAlert {
+            event: log.into(),
+            event_action: EventAction::Trigger,
+            dedup_key: Some("db-1-disk".to_string()),
+            payload: Some(AlertPayload {
+                summary: "disk full".to_string(),
+                source: "db-1".to_string(),
+                severity: Severity::Critical,
+                component: Some("disk".to_string()),
+                group: None,
+                class: None,
+            }),
+        });
+
+        assert_eq!(
+            body,
+            json!({
+                "routing_key": "routing-key",
+                "event_action": "trigger",
+                "dedup_key": "db-1-disk",
+                "payload": {
+                    "summary": "disk full",
+                    "source": "db-1",
+                    "severity": "critical",
+                    "timestamp": "2024-01-02T03:04:05.000Z",
+                    "component": "disk",
+                    "custom_details": {
+                        "message": "disk full",
+                        "timestamp": "2024-01-02T03:04:05Z",
+                        "host": "db-1",
+                    },
+                },
+                "client": "Vector",
+            })
+        );
+    }
+
+    #[test]
+    fn encodes_resolves() {
+        let body = encode(PagerDutyAlert {
+            event: LogEvent::from("disk ok").into(),
+            event_action: EventAction::Resolve,
+            dedup_key: Some("db-1-disk".to_string()),
+            payload: None,
+        });
+
+        assert_eq!(
+            body,
+            json!({
+                "routing_key": "routing-key",
+                "event_action": "resolve",
+                "dedup_key": "db-1-disk",
+                "client": "Vector",
+            })
+        );
+    }
+}
@@ -0,0 +1,24 @@
+//! The `pagerduty` sink.
+//!
+//! This sink sends log events to the [PagerDuty Events API v2][events_api], as events that
+//! trigger, acknowledge, or resolve alerts. The action, dedup key, and the fields of the payload
+//! of the alerts are rendered from templates for each event, and the severity rendered for them is
+//! mapped to the severities of PagerDuty, so that the names of the syslog severities and of the
+//! common log levels can be used as is. The log events are sent as the custom details of the
+//! alerts they trigger.
+//!
+//! The Events API only accepts a single event per request, so events aren't batched.
+//!
+//! [events_api]: https://developer.pagerduty.com/docs/events-api-v2/overview/
+
+mod alert;
+mod config;
+mod encoder;
+mod request_builder;
+mod service;
+mod sink;
+
+#[cfg(test)]
+mod tests;
+
+pub use self::config::PagerDutySinkConfig;
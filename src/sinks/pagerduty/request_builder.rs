@@ -0,0 +1,47 @@
+//! `RequestBuilder` implementation for the `pagerduty` sink.
+
+use std::io;
+
+use bytes::Bytes;
+
+use super::{alert::PagerDutyAlert, encoder::PagerDutyEncoder};
+use crate::sinks::{prelude::*, util::http::HttpRequest};
+
+pub(super) struct PagerDutyRequestBuilder {
+    pub(super) encoder: PagerDutyEncoder,
+}
+
+impl RequestBuilder<PagerDutyAlert> for PagerDutyRequestBuilder {
+    type Metadata = EventFinalizers;
+    type Events = PagerDutyAlert;
+    type Encoder = PagerDutyEncoder;
+    type Payload = Bytes;
+    type Request = HttpRequest<()>;
+    type Error = io::Error;
+
+    fn compression(&self) -> Compression {
+        Compression::None
+    }
+
+    fn encoder(&self) -> &Self::Encoder {
+        &self.encoder
+    }
+
+    fn split_input(
+        &self,
+        mut alert: PagerDutyAlert,
+    ) -> (Self::Metadata, RequestMetadataBuilder, Self::Events) {
+        let finalizers = alert.event.take_finalizers();
+        let builder = RequestMetadataBuilder::from_event(&alert.event);
+        (finalizers, builder, alert)
+    }
+
+    fn build_request(
+        &self,
+        metadata: Self::Metadata,
+        request_metadata: RequestMetadata,
+        payload: EncodeResult<Self::Payload>,
+    ) -> Self::Request {
+        HttpRequest::new(payload.into_payload(), metadata, request_metadata, ())
+    }
+}
@@ -0,0 +1,25 @@
+//! Service implementation for the `pagerduty` sink.
+
+use bytes::Bytes;
+use http::{header::CONTENT_TYPE, Request, Uri};
+use snafu::ResultExt;
+
+use crate::sinks::{
+    util::http::{HttpRequest, HttpServiceRequestBuilder},
+    HTTPRequestBuilderSnafu,
+};
+
+#[derive(Debug, Clone)]
+pub(super) struct PagerDutyServiceRequestBuilder {
+    pub(super) endpoint: Uri,
+}
+
+impl HttpServiceRequestBuilder<()> for PagerDutyServiceRequestBuilder {
+    fn build(&self, mut request: HttpRequest<()>) -> Result<Request<Bytes>, crate::Error> {
+        Request::post(&self.endpoint)
+            .header(CONTENT_TYPE, "application/json")
+            .body(request.take_payload())
+            .context(HTTPRequestBuilderSnafu)
+            .map_err(Into::into)
+    }
+}
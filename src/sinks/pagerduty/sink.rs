@@ -0,0 +1,55 @@
+//! Implementation of the `pagerduty` sink.
+
+use super::{alert::PagerDutyAlertBuilder, request_builder::PagerDutyRequestBuilder};
+use crate::sinks::{prelude::*, util::http::HttpRequest};
+
+pub(super) struct PagerDutySink<S> {
+    pub(super) service: S,
+    pub(super) alert_builder: PagerDutyAlertBuilder,
+    pub(super) request_builder: PagerDutyRequestBuilder,
+}
+
+impl<S> PagerDutySink<S>
+where
+    S: Service<HttpRequest<()>> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: std::fmt::Debug + Into<crate::Error> + Send,
+{
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let alert_builder = self.alert_builder;
+
+        input
+            // The Events API only accepts a single event per request, so events aren't batched.
+            .filter_map(move |event| future::ready(alert_builder.build(event)))
+            .request_builder(
+                default_request_builder_concurrency_limit(),
+                self.request_builder,
+            )
+            .filter_map(|request| async {
+                match request {
+                    Err(error) => {
+                        emit!(SinkRequestBuildError { error });
+                        None
+                    }
+                    Ok(req) => Some(req),
+                }
+            })
+            .into_driver(self.service)
+            .run()
+            .await
+    }
+}
+
+#[async_trait::async_trait]
+impl<S> StreamSink<Event> for PagerDutySink<S>
+where
+    S: Service<HttpRequest<()>> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: std::fmt::Debug + Into<crate::Error> + Send,
+{
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
@@ -0,0 +1,86 @@
+use futures::StreamExt;
+use indoc::indoc;
+use serde_json::json;
+
+use super::PagerDutySinkConfig;
+use crate::{
+    config::{SinkConfig, SinkContext},
+    event::{Event, LogEvent},
+    sinks::util::test::build_test_server,
+    test_util::{
+        self,
+        components::{run_and_assert_sink_compliance, HTTP_SINK_TAGS},
+    },
+};
+
+fn event(action: &str, message: &str, level: &str) -> Event {
+    let mut log = LogEvent::from(message);
+    log.insert("action", action);
+    log.insert("host", "db-1");
+    log.insert("level", level);
+    log.into()
+}
+
+#[tokio::test]
+async fn sends_events() {
+    let addr = test_util::next_addr();
+    let (rx, trigger, server) = build_test_server(addr);
+    tokio::spawn(server);
+
+    let config: PagerDutySinkConfig = toml::from_str(&format!(
+        indoc! {r#"
+            endpoint = "http://{}/v2/enqueue"
+            routing_key = "routing-key"
+            event_action = "{{{{ action }}}}"
+            dedup_key = "{{{{ host }}}}-disk"
+            severity = "{{{{ level }}}}"
+            encoding.only_fields = ["message"]
+        "#},
+        addr
+    ))
+    .unwrap();
+
+    let (sink, _healthcheck) = config.build(SinkContext::default()).await.unwrap();
+    let events = vec![
+        event("trigger", "disk full", "warn"),
+        event("resolve", "disk ok", "info"),
+    ];
+    run_and_assert_sink_compliance(sink, futures::stream::iter(events), &HTTP_SINK_TAGS).await;
+
+    drop(trigger);
+
+    let mut bodies = rx
+        .map(|(parts, body)| {
+            assert_eq!(parts.method, "POST");
+            assert_eq!(parts.uri.path(), "/v2/enqueue");
+            assert_eq!(parts.headers["content-type"], "application/json");
+            serde_json::from_slice::<serde_json::Value>(&body).unwrap()
+        })
+        .collect::<Vec<_>>()
+        .await;
+    bodies.sort_by_key(|body| body["event_action"].as_str().unwrap().to_string());
+
+    assert_eq!(
+        bodies,
+        [
+            json!({
+                "routing_key": "routing-key",
+                "event_action": "resolve",
+                "dedup_key": "db-1-disk",
+                "client": "Vector",
+            }),
+            json!({
+                "routing_key": "routing-key",
+                "event_action": "trigger",
+                "dedup_key": "db-1-disk",
+                "payload": {
+                    "summary": "disk full",
+                    "source": "db-1",
+                    "severity": "warning",
+                    "custom_details": {"message": "disk full"},
+                },
+                "client": "Vector",
+            }),
+        ]
+    );
+}
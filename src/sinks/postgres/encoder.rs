@@ -0,0 +1,313 @@
+//! Encoding of events into the rows of a table, converting their values to the types of the
+//! columns.
+
+use std::{collections::BTreeMap, error::Error};
+
+use bytes::BytesMut;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use snafu::Snafu;
+use tokio_postgres::types::{to_sql_checked, IsNull, ToSql, Type};
+use vector_lib::{
+    event::{LogEvent, Value},
+    lookup::{lookup_v2::ConfigValuePath, PathPrefix},
+};
+
+#[derive(Debug, Snafu)]
+pub enum EncodeError {
+    #[snafu(display("Unsupported type `{}` of column `{}`", type_name, column))]
+    UnsupportedType { column: String, type_name: String },
+    #[snafu(display(
+        "Cannot encode {} value of field `{}` as the type `{}` of its column",
+        kind,
+        column,
+        type_name
+    ))]
+    InvalidValue {
+        column: String,
+        type_name: String,
+        kind: String,
+    },
+}
+
+/// Encodes events into rows of the mapped columns, followed by the catch-all column if any.
+pub(super) struct RowEncoder {
+    columns: Vec<(String, ConfigValuePath)>,
+    catch_all_column: Option<String>,
+}
+
+impl RowEncoder {
+    pub(super) fn new(
+        columns: BTreeMap<String, ConfigValuePath>,
+        catch_all_column: Option<String>,
+    ) -> Self {
+        Self {
+            columns: columns.into_iter().collect(),
+            catch_all_column,
+        }
+    }
+
+    /// The names of the columns of the rows, in order.
+    pub(super) fn columns(&self) -> impl Iterator<Item = &str> {
+        self.columns
+            .iter()
+            .map(|(column, _)| column.as_str())
+            .chain(self.catch_all_column.as_deref())
+    }
+
+    /// Encodes the event into a row of the columns of the given types.
+    pub(super) fn encode(
+        &self,
+        log: &LogEvent,
+        types: &[Type],
+    ) -> Result<Vec<SqlValue>, EncodeError> {
+        let mut row = self
+            .columns
+            .iter()
+            .zip(types)
+            .map(|((column, field), ty)| {
+                SqlValue::convert(log.get((PathPrefix::Event, &field.0)), column, ty)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if let (Some(column), Some(ty)) = (&self.catch_all_column, types.get(self.columns.len())) {
+            let mut remaining = log.value().clone();
+            for (_, field) in &self.columns {
+                remaining.remove(&field.0, true);
+            }
+            row.push(SqlValue::convert(Some(&remaining), column, ty)?);
+        }
+
+        Ok(row)
+    }
+}
+
+/// A value converted to the type of its column.
+#[derive(Debug)]
+pub(super) enum SqlValue {
+    Null,
+    Bool(bool),
+    Int2(i16),
+    Int4(i32),
+    Int8(i64),
+    Float4(f32),
+    Float8(f64),
+    Text(String),
+    Bytea(Vec<u8>),
+    Json(serde_json::Value),
+    Timestamptz(DateTime<Utc>),
+    Timestamp(NaiveDateTime),
+    Date(NaiveDate),
+}
+
+impl SqlValue {
+    /// Converts the value of a field to the type of its column, missing and null values being
+    /// converted to `NULL`.
+    fn convert(value: Option<&Value>, column: &str, ty: &Type) -> Result<Self, EncodeError> {
+        let value = value.filter(|value| !value.is_null());
+        let converted = match *ty {
+            Type::BOOL => value.map(|value| value.as_boolean().map(Self::Bool)),
+            Type::INT2 => value.map(|value| integer(value).map(Self::Int2)),
+            Type::INT4 => value.map(|value| integer(value).map(Self::Int4)),
+            Type::INT8 => value.map(|value| integer(value).map(Self::Int8)),
+            Type::FLOAT4 => value.map(|value| float(value).map(|float| Self::Float4(float as f32))),
+            Type::FLOAT8 => value.map(|value| float(value).map(Self::Float8)),
+            Type::TEXT | Type::VARCHAR | Type::BPCHAR | Type::NAME => {
+                value.map(|value| Some(Self::Text(text(value))))
+            }
+            Type::BYTEA => {
+                value.map(|value| value.as_bytes().map(|bytes| Self::Bytea(bytes.to_vec())))
+            }
+            Type::JSON | Type::JSONB => {
+                value.map(|value| serde_json::to_value(value).ok().map(Self::Json))
+            }
+            Type::TIMESTAMPTZ => value.map(|value| timestamp(value).map(Self::Timestamptz)),
+            Type::TIMESTAMP => {
+                value.map(|value| timestamp(value).map(|ts| Self::Timestamp(ts.naive_utc())))
+            }
+            Type::DATE => value.map(|value| timestamp(value).map(|ts| Self::Date(ts.date_naive()))),
+            _ => {
+                return UnsupportedTypeSnafu {
+                    column,
+                    type_name: ty.to_string(),
+                }
+                .fail()
+            }
+        };
+
+        match (converted, value) {
+            (None, _) => Ok(Self::Null),
+            (Some(Some(converted)), _) => Ok(converted),
+            (Some(None), value) => InvalidValueSnafu {
+                column,
+                type_name: ty.to_string(),
+                kind: value.map_or("null", Value::kind_str),
+            }
+            .fail(),
+        }
+    }
+}
+
+fn integer<T: TryFrom<i64>>(value: &Value) -> Option<T> {
+    value.as_integer().and_then(|int| T::try_from(int).ok())
+}
+
+fn float(value: &Value) -> Option<f64> {
+    match value {
+        Value::Float(float) => Some(float.into_inner()),
+        Value::Integer(int) => Some(*int as f64),
+        _ => None,
+    }
+}
+
+fn text(value: &Value) -> String {
+    match value {
+        Value::Object(_) | Value::Array(_) => {
+            serde_json::to_string(value).expect("values are serializable as JSON")
+        }
+        value => value.to_string_lossy().into_owned(),
+    }
+}
+
+fn timestamp(value: &Value) -> Option<DateTime<Utc>> {
+    match value {
+        Value::Timestamp(ts) => Some(*ts),
+        Value::Bytes(bytes) => std::str::from_utf8(bytes)
+            .ok()
+            .and_then(|string| DateTime::parse_from_rfc3339(string).ok())
+            .map(|ts| ts.with_timezone(&Utc)),
+        _ => None,
+    }
+}
+
+impl ToSql for SqlValue {
+    fn to_sql(
+        &self,
+        ty: &Type,
+        out: &mut BytesMut,
+    ) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        match self {
+            Self::Null => Ok(IsNull::Yes),
+            Self::Bool(value) => value.to_sql(ty, out),
+            Self::Int2(value) => value.to_sql(ty, out),
+            Self::Int4(value) => value.to_sql(ty, out),
+            Self::Int8(value) => value.to_sql(ty, out),
+            Self::Float4(value) => value.to_sql(ty, out),
+            Self::Float8(value) => value.to_sql(ty, out),
+            Self::Text(value) => value.to_sql(ty, out),
+            Self::Bytea(value) => value.to_sql(ty, out),
+            Self::Json(value) => value.to_sql(ty, out),
+            Self::Timestamptz(value) => value.to_sql(ty, out),
+            Self::Timestamp(value) => value.to_sql(ty, out),
+            Self::Date(value) => value.to_sql(ty, out),
+        }
+    }
+
+    // Values are converted to the types of their columns before they are written.
+    fn accepts(_ty: &Type) -> bool {
+        true
+    }
+
+    to_sql_checked!();
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use vrl::btreemap;
+
+    use super::*;
+
+    fn encoder(columns: &[(&str, &str)], catch_all_column: Option<&str>) -> RowEncoder {
+        RowEncoder::new(
+            columns
+                .iter()
+                .map(|&(column, field)| {
+                    (
+                        column.to_string(),
+                        ConfigValuePath::try_from(field.to_string()).unwrap(),
+                    )
+                })
+                .collect(),
+            catch_all_column.map(Into::into),
+        )
+    }
+
+    #[test]
+    fn encodes_rows() {
+        let timestamp = Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap();
+        let log = LogEvent::from(btreemap! {
+            "message" => "hello",
+            "status" => 200,
+            "time" => "2024-01-02T03:04:05Z",
+            "request" => btreemap! {
+                "path" => "/cart",
+                "method" => "GET",
+            },
+        });
+        let encoder = encoder(
+            &[
+                ("message", "message"),
+                ("path", "request.path"),
+                ("status", "status"),
+                ("time", "time"),
+            ],
+            Some("attributes"),
+        );
+
+        assert_eq!(
+            encoder.columns().collect::<Vec<_>>(),
+            ["message", "path", "status", "time", "attributes"]
+        );
+
+        let row = encoder
+            .encode(
+                &log,
+                &[
+                    Type::TEXT,
+                    Type::VARCHAR,
+                    Type::INT4,
+                    Type::TIMESTAMPTZ,
+                    Type::JSONB,
+                ],
+            )
+            .unwrap();
+        assert!(matches!(&row[0], SqlValue::Text(message) if message == "hello"));
+        assert!(matches!(&row[1], SqlValue::Text(path) if path == "/cart"));
+        assert!(matches!(row[2], SqlValue::Int4(200)));
+        assert!(matches!(row[3], SqlValue::Timestamptz(ts) if ts == timestamp));
+        assert!(
+            matches!(&row[4], SqlValue::Json(attributes) if *attributes == serde_json::json!({"request": {"method": "GET"}}))
+        );
+    }
+
+    #[test]
+    fn encodes_missing_fields_as_null() {
+        let encoder = encoder(&[("status", "status")], None);
+
+        let row = encoder.encode(&LogEvent::default(), &[Type::INT8]).unwrap();
+        assert!(matches!(row[0], SqlValue::Null));
+    }
+
+    #[test]
+    fn rejects_invalid_values() {
+        let log = LogEvent::from(btreemap! {
+            "status" => "ok",
+            "big" => 1_i64 << 40,
+        });
+
+        let error = encoder(&[("status", "status")], None)
+            .encode(&log, &[Type::INT8])
+            .unwrap_err();
+        assert!(matches!(error, EncodeError::InvalidValue { kind, .. } if kind == "string"));
+
+        let error = encoder(&[("big", "big")], None)
+            .encode(&log, &[Type::INT4])
+            .unwrap_err();
+        assert!(matches!(error, EncodeError::InvalidValue { .. }));
+
+        let error = encoder(&[("status", "status")], None)
+            .encode(&log, &[Type::POINT])
+            .unwrap_err();
+        assert!(matches!(error, EncodeError::UnsupportedType { .. }));
+    }
+}
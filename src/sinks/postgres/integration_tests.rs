@@ -0,0 +1,155 @@
+use chrono::{TimeZone, Utc};
+use futures::{stream, StreamExt};
+use tokio_postgres::NoTls;
+use vector_lib::event::{BatchNotifier, BatchStatus, LogEvent};
+use vrl::btreemap;
+
+use super::config::PostgresConfig;
+use crate::{
+    config::{SinkConfig, SinkContext},
+    event::Event,
+    test_util::{
+        components::{run_and_assert_sink_compliance, SINK_TAGS},
+        random_string, trace_init,
+    },
+};
+
+fn pg_host() -> String {
+    std::env::var("PG_HOST").unwrap_or_else(|_| "localhost".into())
+}
+
+fn pg_url() -> String {
+    std::env::var("PG_URL")
+        .unwrap_or_else(|_| format!("postgres://vector:vector@{}/postgres", pg_host()))
+}
+
+async fn client() -> tokio_postgres::Client {
+    let (client, connection) = tokio_postgres::connect(&pg_url(), NoTls).await.unwrap();
+    tokio::spawn(connection);
+    client
+}
+
+async fn insert_events(method: &str) {
+    trace_init();
+
+    let table = format!("test_{}", random_string(10).to_lowercase());
+    let client = client().await;
+    client
+        .batch_execute(&format!(
+            "CREATE TABLE {} (time timestamptz, host text, status integer, attributes jsonb)",
+            table
+        ))
+        .await
+        .unwrap();
+
+    let config: PostgresConfig = toml::from_str(&format!(
+        r#"
+        connection_string = "{}"
+        table = "{}"
+        columns.time = "timestamp"
+        columns.host = "host"
+        columns.status = "status"
+        catch_all_column = "attributes"
+        method = "{}"
+        "#,
+        pg_url(),
+        table,
+        method
+    ))
+    .unwrap();
+    let (sink, healthcheck) = config.build(SinkContext::default()).await.unwrap();
+    healthcheck.await.unwrap();
+
+    let timestamp = Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap();
+    let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+    let events = (0..3_i64)
+        .map(|i| {
+            let mut log = LogEvent::from(btreemap! {
+                "host" => "web-1",
+                "status" => 200 + i,
+                "message" => format!("request {}", i),
+            })
+            .with_batch_notifier(&batch);
+            log.insert("timestamp", timestamp);
+            Event::Log(log)
+        })
+        .collect::<Vec<_>>();
+    drop(batch);
+
+    run_and_assert_sink_compliance(sink, stream::iter(events), &SINK_TAGS).await;
+    assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+
+    let rows = client
+        .query(
+            &format!(
+                "SELECT time, host, status, attributes FROM {} ORDER BY status",
+                table
+            ),
+            &[],
+        )
+        .await
+        .unwrap();
+    assert_eq!(rows.len(), 3);
+    for (i, row) in rows.iter().enumerate() {
+        assert_eq!(row.get::<_, chrono::DateTime<Utc>>(0), timestamp);
+        assert_eq!(row.get::<_, String>(1), "web-1");
+        assert_eq!(row.get::<_, i32>(2), 200 + i as i32);
+        assert_eq!(
+            row.get::<_, serde_json::Value>(3),
+            serde_json::json!({ "message": format!("request {}", i) })
+        );
+    }
+}
+
+#[tokio::test]
+async fn inserts_events_with_copy() {
+    insert_events("copy").await;
+}
+
+#[tokio::test]
+async fn inserts_events_with_insert() {
+    insert_events("insert").await;
+}
+
+#[tokio::test]
+async fn rejects_invalid_values() {
+    trace_init();
+
+    let table = format!("test_{}", random_string(10).to_lowercase());
+    let client = client().await;
+    client
+        .batch_execute(&format!("CREATE TABLE {} (status integer)", table))
+        .await
+        .unwrap();
+
+    let config: PostgresConfig = toml::from_str(&format!(
+        r#"
+        connection_string = "{}"
+        table = "{}"
+        columns.status = "status"
+        "#,
+        pg_url(),
+        table
+    ))
+    .unwrap();
+    let (sink, _healthcheck) = config.build(SinkContext::default()).await.unwrap();
+
+    let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+    let events = vec![
+        Event::Log(LogEvent::from(btreemap! { "status" => 200 }).with_batch_notifier(&batch)),
+        Event::Log(LogEvent::from(btreemap! { "status" => "ok" }).with_batch_notifier(&batch)),
+    ];
+    drop(batch);
+
+    sink.run(stream::iter(events).map(Into::into))
+        .await
+        .unwrap();
+    assert_eq!(receiver.try_recv(), Ok(BatchStatus::Rejected));
+
+    // The transaction of the batch is rolled back.
+    let rows = client
+        .query(&format!("SELECT status FROM {}", table), &[])
+        .await
+        .unwrap();
+    assert!(rows.is_empty());
+}
@@ -0,0 +1,21 @@
+//! The `postgres` sink.
+//!
+//! This sink inserts log events into a table of [PostgreSQL][postgres], or of databases built on
+//! it such as [TimescaleDB][timescaledb]. The fields of the events are written to the columns they
+//! are mapped to by `columns`, converted to the types of the columns, and the fields which aren't
+//! mapped to the `catch_all_column` as a JSON object.
+//!
+//! The events of a batch are inserted in a single transaction, with a `COPY` statement or with
+//! multi-row `INSERT` statements, over a pool of connections.
+//!
+//! [postgres]: https://www.postgresql.org/
+//! [timescaledb]: https://www.timescale.com/
+
+mod config;
+mod encoder;
+#[cfg(all(test, feature = "postgres-integration-tests"))]
+mod integration_tests;
+mod service;
+mod sink;
+
+pub use self::config::PostgresConfig;
@@ -0,0 +1,339 @@
+//! Service implementation for the `postgres` sink.
+
+use std::{
+    collections::BTreeMap,
+    num::NonZeroUsize,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
+
+use futures::pin_mut;
+use postgres_openssl::MakeTlsConnector;
+use snafu::{ResultExt, Snafu};
+use tokio_postgres::{binary_copy::BinaryCopyInWriter, types::ToSql, Client, Config, NoTls};
+use vector_lib::lookup::lookup_v2::ConfigValuePath;
+
+use super::{
+    config::InsertMethod,
+    encoder::{EncodeError, RowEncoder},
+};
+use crate::sinks::prelude::*;
+
+/// The maximum number of parameters of a statement supported by the protocol.
+const MAX_PARAMETERS: usize = u16::MAX as usize;
+
+/// The classes of the SQLSTATE codes of errors which are retried: connection exceptions,
+/// transaction rollbacks such as serialization failures and deadlocks, insufficient resources,
+/// and operator interventions such as shutdowns.
+const RETRIABLE_CLASSES: [&str; 4] = ["08", "40", "53", "57"];
+
+#[derive(Debug, Snafu)]
+pub(super) enum PostgresError {
+    #[snafu(display("Failed to connect to PostgreSQL: {}", source))]
+    Connect { source: tokio_postgres::Error },
+    #[snafu(display("PostgreSQL query failed: {}", source))]
+    Query { source: tokio_postgres::Error },
+    #[snafu(display("{}", source))]
+    Encode { source: EncodeError },
+}
+
+/// Opens the connections to the database.
+#[derive(Clone)]
+pub(super) struct Connector {
+    config: Config,
+    tls: Option<MakeTlsConnector>,
+}
+
+impl Connector {
+    pub(super) const fn new(config: Config, tls: Option<MakeTlsConnector>) -> Self {
+        Self { config, tls }
+    }
+
+    pub(super) async fn connect(&self) -> Result<Client, PostgresError> {
+        let client = match &self.tls {
+            Some(connector) => {
+                let (client, connection) = self
+                    .config
+                    .connect(connector.clone())
+                    .await
+                    .context(ConnectSnafu)?;
+                tokio::spawn(connection);
+                client
+            }
+            None => {
+                let (client, connection) =
+                    self.config.connect(NoTls).await.context(ConnectSnafu)?;
+                tokio::spawn(connection);
+                client
+            }
+        };
+        Ok(client)
+    }
+}
+
+#[derive(Clone)]
+pub(super) struct PostgresRequest {
+    table: String,
+    events: Vec<Event>,
+    finalizers: EventFinalizers,
+    metadata: RequestMetadata,
+}
+
+impl PostgresRequest {
+    pub(super) fn new(table: String, mut events: Vec<Event>, transformer: &Transformer) -> Self {
+        let finalizers = events.take_finalizers();
+        for event in &mut events {
+            transformer.transform(event);
+        }
+        let builder = RequestMetadataBuilder::from_events(&events);
+        let size = events
+            .iter()
+            .map(|event| event.estimated_json_encoded_size_of().get())
+            .sum::<usize>();
+        let metadata =
+            builder.with_request_size(NonZeroUsize::new(size).unwrap_or(NonZeroUsize::MIN));
+
+        Self {
+            table,
+            events,
+            finalizers,
+            metadata,
+        }
+    }
+}
+
+impl Finalizable for PostgresRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        std::mem::take(&mut self.finalizers)
+    }
+}
+
+impl MetaDescriptive for PostgresRequest {
+    fn get_metadata(&self) -> &RequestMetadata {
+        &self.metadata
+    }
+
+    fn metadata_mut(&mut self) -> &mut RequestMetadata {
+        &mut self.metadata
+    }
+}
+
+#[derive(Debug)]
+pub(super) struct PostgresResponse {
+    events_byte_size: GroupedCountByteSize,
+}
+
+impl DriverResponse for PostgresResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> &GroupedCountByteSize {
+        &self.events_byte_size
+    }
+}
+
+#[derive(Clone, Default)]
+pub(super) struct PostgresRetryLogic;
+
+impl RetryLogic for PostgresRetryLogic {
+    type Error = PostgresError;
+    type Response = PostgresResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        match error {
+            PostgresError::Connect { .. } => true,
+            // Errors without a code are raised by the client, such as for closed connections.
+            PostgresError::Query { source } => source.code().map_or(true, |code| {
+                RETRIABLE_CLASSES.contains(&code.code().get(..2).unwrap_or_default())
+            }),
+            PostgresError::Encode { .. } => false,
+        }
+    }
+}
+
+/// Inserts the events of requests over a pool of connections, opened as needed and kept open
+/// between requests.
+///
+/// The rows of each request are inserted in a transaction, so that requests are inserted either
+/// entirely or not at all, and can be retried.
+#[derive(Clone)]
+pub(super) struct PostgresService {
+    connector: Connector,
+    encoder: Arc<RowEncoder>,
+    method: InsertMethod,
+    clients: Arc<Mutex<Vec<Client>>>,
+}
+
+impl PostgresService {
+    pub(super) fn new(
+        connector: Connector,
+        columns: BTreeMap<String, ConfigValuePath>,
+        catch_all_column: Option<String>,
+        method: InsertMethod,
+    ) -> Self {
+        Self {
+            connector,
+            encoder: Arc::new(RowEncoder::new(columns, catch_all_column)),
+            method,
+            clients: Arc::default(),
+        }
+    }
+}
+
+impl Service<PostgresRequest> for PostgresService {
+    type Response = PostgresResponse;
+    type Error = PostgresError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: PostgresRequest) -> Self::Future {
+        let service = self.clone();
+        let events_byte_size = request
+            .metadata
+            .events_estimated_json_encoded_byte_size()
+            .clone();
+
+        Box::pin(async move {
+            let pooled = service.clients.lock().expect("poisoned lock").pop();
+            let mut client = match pooled {
+                Some(client) if !client.is_closed() => client,
+                _ => service.connector.connect().await?,
+            };
+
+            let result = service.insert(&mut client, &request).await;
+
+            // Query errors leave the connection usable, so it is only dropped once it has been
+            // closed.
+            if !client.is_closed() {
+                service.clients.lock().expect("poisoned lock").push(client);
+            }
+
+            result.map(|()| PostgresResponse { events_byte_size })
+        })
+    }
+}
+
+impl PostgresService {
+    async fn insert(
+        &self,
+        client: &mut Client,
+        request: &PostgresRequest,
+    ) -> Result<(), PostgresError> {
+        let transaction = client.transaction().await.context(QuerySnafu)?;
+
+        let table = quote_table(&request.table);
+        let columns = self
+            .encoder
+            .columns()
+            .map(quote_identifier)
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        // The types of the columns, which the values of the events are converted to.
+        let statement = transaction
+            .prepare(&format!("SELECT {} FROM {} LIMIT 0", columns, table))
+            .await
+            .context(QuerySnafu)?;
+        let types = statement
+            .columns()
+            .iter()
+            .map(|column| column.type_().clone())
+            .collect::<Vec<_>>();
+
+        let rows = request
+            .events
+            .iter()
+            .map(|event| self.encoder.encode(event.as_log(), &types))
+            .collect::<Result<Vec<_>, _>>()
+            .context(EncodeSnafu)?;
+
+        match self.method {
+            InsertMethod::Copy => {
+                let sink = transaction
+                    .copy_in(&format!("COPY {} ({}) FROM STDIN BINARY", table, columns))
+                    .await
+                    .context(QuerySnafu)?;
+                let writer = BinaryCopyInWriter::new(sink, &types);
+                pin_mut!(writer);
+                for row in &rows {
+                    let params = row
+                        .iter()
+                        .map(|value| value as &(dyn ToSql + Sync))
+                        .collect::<Vec<_>>();
+                    writer.as_mut().write(&params).await.context(QuerySnafu)?;
+                }
+                writer.finish().await.context(QuerySnafu)?;
+            }
+            InsertMethod::Insert => {
+                let rows_per_statement = (MAX_PARAMETERS / types.len()).max(1);
+                for chunk in rows.chunks(rows_per_statement) {
+                    let query = insert_query(&table, &columns, types.len(), chunk.len());
+                    let params = chunk
+                        .iter()
+                        .flatten()
+                        .map(|value| value as &(dyn ToSql + Sync))
+                        .collect::<Vec<_>>();
+                    transaction
+                        .execute(&query, &params)
+                        .await
+                        .context(QuerySnafu)?;
+                }
+            }
+        }
+
+        transaction.commit().await.context(QuerySnafu)
+    }
+}
+
+/// Builds a multi-row `INSERT` statement, with the parameters of each row in turn.
+fn insert_query(table: &str, columns: &str, column_count: usize, row_count: usize) -> String {
+    let rows = (0..row_count)
+        .map(|row| {
+            let params = (1..=column_count)
+                .map(|column| format!("${}", row * column_count + column))
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!("({})", params)
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!("INSERT INTO {} ({}) VALUES {}", table, columns, rows)
+}
+
+/// Quotes the table, and its schema if it is qualified.
+fn quote_table(table: &str) -> String {
+    match table.split_once('.') {
+        Some((schema, table)) => {
+            format!("{}.{}", quote_identifier(schema), quote_identifier(table))
+        }
+        None => quote_identifier(table),
+    }
+}
+
+fn quote_identifier(identifier: &str) -> String {
+    format!("\"{}\"", identifier.replace('"', "\"\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quotes_tables() {
+        assert_eq!(quote_table("logs"), "\"logs\"");
+        assert_eq!(quote_table("public.logs"), "\"public\".\"logs\"");
+        assert_eq!(quote_table("my\"logs"), "\"my\"\"logs\"");
+    }
+
+    #[test]
+    fn builds_insert_queries() {
+        assert_eq!(
+            insert_query("\"logs\"", "\"a\", \"b\"", 2, 2),
+            "INSERT INTO \"logs\" (\"a\", \"b\") VALUES ($1, $2), ($3, $4)"
+        );
+    }
+}
@@ -0,0 +1,59 @@
+//! Implementation of the `postgres` sink.
+
+use super::service::{PostgresRequest, PostgresRetryLogic, PostgresService};
+use crate::sinks::prelude::*;
+
+/// Partitions events by the table they are inserted into.
+struct TablePartitioner {
+    table: Template,
+}
+
+impl Partitioner for TablePartitioner {
+    type Item = Event;
+    type Key = Option<String>;
+
+    fn partition(&self, item: &Self::Item) -> Self::Key {
+        self.table
+            .render_string(item)
+            .map_err(|error| {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some("table"),
+                    drop_event: true,
+                });
+            })
+            .ok()
+    }
+}
+
+pub(super) struct PostgresSink {
+    pub(super) batch_settings: BatcherSettings,
+    pub(super) service: Svc<PostgresService, PostgresRetryLogic>,
+    pub(super) table: Template,
+    pub(super) transformer: Transformer,
+}
+
+impl PostgresSink {
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let batch_settings = self.batch_settings;
+        let transformer = self.transformer;
+
+        input
+            .batched_partitioned(TablePartitioner { table: self.table }, || {
+                batch_settings.as_byte_size_config()
+            })
+            .filter_map(|(table, batch)| async move { table.map(move |table| (table, batch)) })
+            .map(|(table, events)| PostgresRequest::new(table, events, &transformer))
+            .into_driver(self.service)
+            .protocol("postgres")
+            .run()
+            .await
+    }
+}
+
+#[async_trait::async_trait]
+impl StreamSink<Event> for PostgresSink {
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
@@ -1,21 +1,22 @@
 use std::{collections::BTreeMap, fmt::Write as _};
 
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use indexmap::map::IndexMap;
 use vector_lib::event::metric::{samples_to_buckets, MetricSketch, MetricTags, Quantile};
 use vector_lib::prometheus::parser::{proto, METRIC_NAME_LABEL};
+use vector_lib::{event::Value, lookup::path};
 
 use crate::{
     event::metric::{Metric, MetricKind, MetricValue, StatisticKind},
     sinks::util::{encode_namespace, statistic::DistributionStatistic},
 };
 
-pub(super) trait MetricCollector {
+pub(crate) trait MetricCollector {
     type Output;
 
     fn new() -> Self;
 
-    fn emit_metadata(&mut self, name: &str, fullname: &str, value: &MetricValue);
+    fn emit_metadata(&mut self, name: &str, fullname: &str, r#type: proto::MetricType);
 
     fn emit_value(
         &mut self,
@@ -27,6 +28,34 @@ pub(super) trait MetricCollector {
         extra: Option<(&str, String)>,
     );
 
+    /// Attaches an exemplar to a series. Only remote write requests carry exemplars, so they are
+    /// ignored by default.
+    fn emit_exemplar(
+        &mut self,
+        _name: &str,
+        _suffix: &str,
+        _tags: Option<&MetricTags>,
+        _extra: Option<(&str, String)>,
+        _exemplar: proto::Exemplar,
+    ) {
+    }
+
+    /// Whether distributions and sketches are emitted as native histograms. Only remote write
+    /// requests carry native histograms, so they are not emitted by default.
+    fn native_histograms(&self) -> bool {
+        false
+    }
+
+    /// Emits the native histogram of a series.
+    fn emit_histogram(
+        &mut self,
+        _timestamp_millis: Option<i64>,
+        _name: &str,
+        _tags: Option<&MetricTags>,
+        _histogram: proto::Histogram,
+    ) {
+    }
+
     fn finish(self) -> Self::Output;
 
     fn encode_metric(
@@ -42,9 +71,26 @@ pub(super) trait MetricCollector {
 
         if metric.kind() == MetricKind::Absolute {
             let tags = metric.tags();
-            self.emit_metadata(metric.name(), name, metric.value());
+            let histogram = self
+                .native_histograms()
+                .then(|| {
+                    stored_native_histogram(metric).or_else(|| native_histogram(metric.value()))
+                })
+                .flatten();
+            let is_native = histogram.is_some();
+            let r#type = if is_native {
+                proto::MetricType::Histogram
+            } else {
+                prometheus_metric_type(metric.value())
+            };
+            self.emit_metadata(metric.name(), name, r#type);
+            if let Some(histogram) = histogram {
+                self.emit_histogram(timestamp, name, tags, histogram);
+            }
 
             match metric.value() {
+                // Native histograms replace the classic series of distributions and sketches.
+                _ if is_native => {}
                 MetricValue::Counter { value } => {
                     self.emit_value(timestamp, name, "", *value, tags, None);
                 }
@@ -214,10 +260,148 @@ pub(super) trait MetricCollector {
                     }
                 },
             }
+
+            for exemplar in exemplars(metric) {
+                match metric.value() {
+                    // Exemplars of native histograms belong to their series.
+                    _ if is_native => self.emit_exemplar(name, "", tags, None, exemplar),
+                    MetricValue::Counter { .. } | MetricValue::Gauge { .. } => {
+                        self.emit_exemplar(name, "", tags, None, exemplar);
+                    }
+                    MetricValue::AggregatedHistogram { buckets, .. } => {
+                        // Exemplars belong to the bucket their value falls in.
+                        let le = buckets
+                            .iter()
+                            .map(|bucket| bucket.upper_limit)
+                            .find(|limit| !limit.is_infinite() && exemplar.value <= *limit)
+                            .map_or_else(|| "+Inf".to_string(), |limit| limit.to_string());
+                        self.emit_exemplar(name, "_bucket", tags, Some(("le", le)), exemplar);
+                    }
+                    _ => {}
+                }
+            }
         }
     }
 }
 
+/// Reads back the exemplars the `prometheus_remote_write` source stores in the metric metadata.
+fn exemplars(metric: &Metric) -> Vec<proto::Exemplar> {
+    let Some(Value::Array(exemplars)) = metric
+        .metadata()
+        .value()
+        .get(path!("prometheus_remote_write", "exemplars"))
+    else {
+        return Vec::new();
+    };
+
+    exemplars
+        .iter()
+        .filter_map(|exemplar| {
+            let value = exemplar.get(path!("value"))?.as_float()?.into_inner();
+            let timestamp = exemplar
+                .get(path!("timestamp"))?
+                .as_timestamp()
+                .map(DateTime::timestamp_millis)?;
+            let labels = match exemplar.get(path!("labels")) {
+                Some(Value::Object(labels)) => labels
+                    .iter()
+                    .map(|(name, value)| proto::Label {
+                        name: name.to_string(),
+                        value: value.to_string_lossy().into_owned(),
+                    })
+                    .collect(),
+                _ => Vec::new(),
+            };
+            Some(proto::Exemplar {
+                labels,
+                value,
+                timestamp,
+            })
+        })
+        .collect()
+}
+
+/// Reads back the native histogram the `prometheus_remote_write` source stores in the metric
+/// metadata, which keeps the resolution that its buckets lose.
+fn stored_native_histogram(metric: &Metric) -> Option<proto::Histogram> {
+    use proto::histogram::{Count, ResetHint, ZeroCount};
+
+    if !matches!(metric.value(), MetricValue::AggregatedHistogram { .. }) {
+        return None;
+    }
+    let histogram = metric
+        .metadata()
+        .value()
+        .get(path!("prometheus_remote_write", "native_histogram"))?;
+
+    let float = |value: Option<&Value>| {
+        value
+            .and_then(Value::as_float)
+            .map_or(f64::NAN, |value| value.into_inner())
+    };
+    let spans = |field: &str| -> Option<Vec<proto::BucketSpan>> {
+        match histogram.get(path!(field)) {
+            Some(Value::Array(spans)) => spans
+                .iter()
+                .map(|span| {
+                    Some(proto::BucketSpan {
+                        offset: span.get(path!("offset"))?.as_integer()?.try_into().ok()?,
+                        length: span.get(path!("length"))?.as_integer()?.try_into().ok()?,
+                    })
+                })
+                .collect(),
+            _ => Some(Vec::new()),
+        }
+    };
+    let deltas = |field: &str| -> Option<Vec<i64>> {
+        match histogram.get(path!(field)) {
+            Some(Value::Array(deltas)) => deltas.iter().map(Value::as_integer).collect(),
+            _ => Some(Vec::new()),
+        }
+    };
+    let counts = |field: &str| match histogram.get(path!(field)) {
+        Some(Value::Array(counts)) => counts.iter().map(|count| float(Some(count))).collect(),
+        _ => Vec::new(),
+    };
+
+    // Integer histograms have integer counts and deltas, float histograms float counts.
+    let count = match histogram.get(path!("count")) {
+        Some(Value::Integer(count)) => Some(Count::CountInt(*count as u64)),
+        Some(Value::Float(count)) => Some(Count::CountFloat(count.into_inner())),
+        _ => None,
+    };
+    let zero_count = match histogram.get(path!("zero_count")) {
+        Some(Value::Integer(count)) => Some(ZeroCount::ZeroCountInt(*count as u64)),
+        Some(Value::Float(count)) => Some(ZeroCount::ZeroCountFloat(count.into_inner())),
+        _ => None,
+    };
+    let reset_hint = histogram
+        .get(path!("reset_hint"))
+        .and_then(Value::as_str)
+        .and_then(|hint| ResetHint::from_str_name(&hint.to_uppercase()))
+        .unwrap_or(ResetHint::Unknown);
+
+    Some(proto::Histogram {
+        count,
+        sum: float(histogram.get(path!("sum"))),
+        schema: histogram
+            .get(path!("schema"))?
+            .as_integer()?
+            .try_into()
+            .ok()?,
+        zero_threshold: float(histogram.get(path!("zero_threshold"))),
+        zero_count,
+        negative_spans: spans("negative_spans")?,
+        negative_deltas: deltas("negative_deltas")?,
+        negative_counts: counts("negative_counts"),
+        positive_spans: spans("positive_spans")?,
+        positive_deltas: deltas("positive_deltas")?,
+        positive_counts: counts("positive_counts"),
+        reset_hint: reset_hint as i32,
+        timestamp: 0,
+    })
+}
+
 pub(super) struct StringCollector {
     // BTreeMap ensures we get sorted output, which whilst not required is preferable
     processed: BTreeMap<String, String>,
@@ -231,9 +415,9 @@ impl MetricCollector for StringCollector {
         Self { processed }
     }
 
-    fn emit_metadata(&mut self, name: &str, fullname: &str, value: &MetricValue) {
+    fn emit_metadata(&mut self, name: &str, fullname: &str, r#type: proto::MetricType) {
         if !self.processed.contains_key(fullname) {
-            let header = Self::encode_header(name, fullname, value);
+            let header = Self::encode_header(name, fullname, r#type);
             self.processed.insert(fullname.into(), header);
         }
     }
@@ -288,11 +472,13 @@ impl StringCollector {
         .ok();
     }
 
-    fn encode_header(name: &str, fullname: &str, value: &MetricValue) -> String {
-        let r#type = prometheus_metric_type(value).as_str();
+    fn encode_header(name: &str, fullname: &str, r#type: proto::MetricType) -> String {
         format!(
             "# HELP {} {}\n# TYPE {} {}\n",
-            fullname, name, fullname, r#type
+            fullname,
+            name,
+            fullname,
+            r#type.as_str()
         )
     }
 
@@ -316,13 +502,28 @@ impl StringCollector {
 
 type Labels = Vec<proto::Label>;
 
+#[derive(Default)]
+struct Series {
+    samples: Vec<proto::Sample>,
+    exemplars: Vec<proto::Exemplar>,
+    histograms: Vec<proto::Histogram>,
+}
+
 pub(super) struct TimeSeries {
-    buffer: IndexMap<Labels, Vec<proto::Sample>>,
+    buffer: IndexMap<Labels, Series>,
     metadata: IndexMap<String, proto::MetricMetadata>,
     timestamp: Option<i64>,
+    native_histograms: bool,
 }
 
 impl TimeSeries {
+    /// Emits distributions and sketches as native histograms rather than as classic histograms
+    /// and summaries.
+    pub(super) const fn with_native_histograms(mut self, native_histograms: bool) -> Self {
+        self.native_histograms = native_histograms;
+        self
+    }
+
     fn make_labels(
         tags: Option<&MetricTags>,
         name: &str,
@@ -364,19 +565,20 @@ impl MetricCollector for TimeSeries {
             buffer: Default::default(),
             metadata: Default::default(),
             timestamp: None,
+            native_histograms: false,
         }
     }
 
-    fn emit_metadata(&mut self, name: &str, fullname: &str, value: &MetricValue) {
-        if !self.metadata.contains_key(name) {
-            let r#type = prometheus_metric_type(value);
+    fn emit_metadata(&mut self, name: &str, fullname: &str, r#type: proto::MetricType) {
+        // Metrics of the same name in different namespaces are different families.
+        if !self.metadata.contains_key(fullname) {
             let metadata = proto::MetricMetadata {
                 r#type: r#type as i32,
                 metric_family_name: fullname.into(),
                 help: name.into(),
                 unit: String::new(),
             };
-            self.metadata.insert(name.into(), metadata);
+            self.metadata.insert(fullname.into(), metadata);
         }
     }
 
@@ -393,14 +595,54 @@ impl MetricCollector for TimeSeries {
         self.buffer
             .entry(Self::make_labels(tags, name, suffix, extra))
             .or_default()
+            .samples
             .push(proto::Sample { value, timestamp });
     }
 
+    fn emit_exemplar(
+        &mut self,
+        name: &str,
+        suffix: &str,
+        tags: Option<&MetricTags>,
+        extra: Option<(&str, String)>,
+        exemplar: proto::Exemplar,
+    ) {
+        self.buffer
+            .entry(Self::make_labels(tags, name, suffix, extra))
+            .or_default()
+            .exemplars
+            .push(exemplar);
+    }
+
+    fn native_histograms(&self) -> bool {
+        self.native_histograms
+    }
+
+    fn emit_histogram(
+        &mut self,
+        timestamp_millis: Option<i64>,
+        name: &str,
+        tags: Option<&MetricTags>,
+        mut histogram: proto::Histogram,
+    ) {
+        histogram.timestamp = timestamp_millis.unwrap_or_else(|| self.default_timestamp());
+        self.buffer
+            .entry(Self::make_labels(tags, name, "", None))
+            .or_default()
+            .histograms
+            .push(histogram);
+    }
+
     fn finish(self) -> proto::WriteRequest {
         let timeseries = self
             .buffer
             .into_iter()
-            .map(|(labels, samples)| proto::TimeSeries { labels, samples })
+            .map(|(labels, series)| proto::TimeSeries {
+                labels,
+                samples: series.samples,
+                exemplars: series.exemplars,
+                histograms: series.histograms,
+            })
             .collect::<Vec<_>>();
         let metadata = self
             .metadata
@@ -414,6 +656,138 @@ impl MetricCollector for TimeSeries {
     }
 }
 
+/// The schema of the native histograms that distributions and sketches are converted to.
+///
+/// Its buckets grow by a factor of `2^(2^-5)`, about 2.2%, which is the finest schema coarser
+/// than the bins of sketches, which grow by about 1.6%.
+const NATIVE_HISTOGRAM_SCHEMA: i32 = 5;
+
+/// Converts distributions and sketches into native histograms, or `None` for the other metric
+/// values, which have no native histogram form.
+///
+/// The samples of distributions are counted in the bucket they fall in, and so are the bins of
+/// sketches, taking the midpoint of each bin as its value.
+fn native_histogram(value: &MetricValue) -> Option<proto::Histogram> {
+    let mut histogram = NativeHistogram::default();
+    match value {
+        MetricValue::Distribution {
+            samples,
+            statistic: StatisticKind::Histogram,
+        } => {
+            for sample in samples {
+                histogram.observe(sample.value, u64::from(sample.rate));
+            }
+        }
+        MetricValue::Sketch {
+            sketch: MetricSketch::AgentDDSketch(ddsketch),
+        } => {
+            let config = ddsketch.config();
+            let bin_value = |key: i16| {
+                let lower = config.bin_lower_bound(key);
+                key.checked_add(1)
+                    .map_or(lower, |next| (lower + config.bin_lower_bound(next)) / 2.0)
+            };
+            // The values of the zero bin are smaller than the lower bound of the first bin.
+            histogram.zero_threshold = config.bin_lower_bound(1);
+            let (keys, counts) = ddsketch.bin_map().into_parts();
+            for (key, count) in keys.into_iter().zip(counts) {
+                let value = match key.cmp(&0) {
+                    std::cmp::Ordering::Equal => 0.0,
+                    std::cmp::Ordering::Greater => bin_value(key),
+                    std::cmp::Ordering::Less => -bin_value(key.saturating_neg()),
+                };
+                histogram.observe(value, u64::from(count));
+            }
+            histogram.sum = ddsketch.sum().unwrap_or(0.0);
+        }
+        _ => return None,
+    }
+    Some(histogram.finish())
+}
+
+/// A native histogram being built from observations.
+#[derive(Default)]
+struct NativeHistogram {
+    count: u64,
+    sum: f64,
+    zero_threshold: f64,
+    zero_count: u64,
+    negative: BTreeMap<i32, u64>,
+    positive: BTreeMap<i32, u64>,
+}
+
+impl NativeHistogram {
+    fn observe(&mut self, value: f64, count: u64) {
+        if !value.is_finite() {
+            return;
+        }
+
+        self.count += count;
+        self.sum += value * count as f64;
+        if value.abs() <= self.zero_threshold {
+            self.zero_count += count;
+        } else {
+            // Bucket `i` covers `(base^(i-1), base^i]`, with `base = 2^(2^-schema)`.
+            let index = (value.abs().log2() * 2f64.powi(NATIVE_HISTOGRAM_SCHEMA)).ceil() as i32;
+            let buckets = if value > 0.0 {
+                &mut self.positive
+            } else {
+                &mut self.negative
+            };
+            *buckets.entry(index).or_default() += count;
+        }
+    }
+
+    fn finish(self) -> proto::Histogram {
+        use proto::histogram::{Count, ZeroCount};
+
+        let (negative_spans, negative_deltas) = native_spans(&self.negative);
+        let (positive_spans, positive_deltas) = native_spans(&self.positive);
+        proto::Histogram {
+            count: Some(Count::CountInt(self.count)),
+            sum: self.sum,
+            schema: NATIVE_HISTOGRAM_SCHEMA,
+            zero_threshold: self.zero_threshold,
+            zero_count: Some(ZeroCount::ZeroCountInt(self.zero_count)),
+            negative_spans,
+            negative_deltas,
+            negative_counts: Vec::new(),
+            positive_spans,
+            positive_deltas,
+            positive_counts: Vec::new(),
+            reset_hint: proto::histogram::ResetHint::Unknown as i32,
+            timestamp: 0,
+        }
+    }
+}
+
+/// Encodes the counts of buckets into the spans of consecutive buckets of a native histogram, and
+/// the delta of the count of each bucket to the previous one.
+fn native_spans(buckets: &BTreeMap<i32, u64>) -> (Vec<proto::BucketSpan>, Vec<i64>) {
+    let mut spans: Vec<proto::BucketSpan> = Vec::new();
+    let mut deltas = Vec::with_capacity(buckets.len());
+    let mut previous: Option<(i32, i64)> = None;
+    for (&index, &count) in buckets {
+        let count = count as i64;
+        match (previous, spans.last_mut()) {
+            (Some((previous_index, _)), Some(span)) if index == previous_index + 1 => {
+                span.length += 1;
+            }
+            (Some((previous_index, _)), _) => spans.push(proto::BucketSpan {
+                offset: index - previous_index - 1,
+                length: 1,
+            }),
+            (None, _) => spans.push(proto::BucketSpan {
+                offset: index,
+                length: 1,
+            }),
+        }
+        deltas.push(count - previous.map_or(0, |(_, previous_count)| previous_count));
+        previous = Some((index, count));
+    }
+    (spans, deltas)
+}
+
 const fn prometheus_metric_type(metric_value: &MetricValue) -> proto::MetricType {
     use proto::MetricType;
     match metric_value {
@@ -439,8 +813,9 @@ mod tests {
 
     use chrono::{DateTime, TimeZone, Timelike};
     use indoc::indoc;
+    use ordered_float::NotNan;
     use similar_asserts::assert_eq;
-    use vector_lib::metric_tags;
+    use vector_lib::{metric_tags, metrics::AgentDDSketch};
 
     use super::{super::default_summary_quantiles, *};
     use crate::{
@@ -490,6 +865,8 @@ mod tests {
                                 value: $svalue,
                                 timestamp: $timestamp,
                             }],
+                            exemplars: vec![],
+                            histograms: vec![],
                         },
                     )*
                 ],
@@ -911,6 +1288,263 @@ mod tests {
         assert!(encoded.timeseries[0].samples[0].timestamp >= now);
     }
 
+    #[test]
+    fn encodes_exemplars_request() {
+        let exemplar = Value::Object(
+            [
+                (
+                    "labels".into(),
+                    Value::Object([("trace_id".into(), "abc".into())].into_iter().collect()),
+                ),
+                ("value".into(), Value::from(NotNan::new(0.7).unwrap())),
+                ("timestamp".into(), Value::from(timestamp())),
+            ]
+            .into_iter()
+            .collect(),
+        );
+        let mut metric = Metric::new(
+            "latency".to_owned(),
+            MetricKind::Absolute,
+            MetricValue::AggregatedHistogram {
+                buckets: vector_lib::buckets![0.5 => 1, 1.0 => 2],
+                count: 3,
+                sum: 2.0,
+            },
+        );
+        metric.metadata_mut().value_mut().insert(
+            path!("prometheus_remote_write", "exemplars"),
+            Value::Array(vec![exemplar]),
+        );
+
+        let request = encode_one::<TimeSeries>(None, &[], &[], &metric);
+        let series = request
+            .timeseries
+            .iter()
+            .find(|series| !series.exemplars.is_empty())
+            .expect("exemplar was not encoded");
+        assert!(series.labels.contains(&proto::Label {
+            name: "le".into(),
+            value: "1".into(),
+        }));
+        assert_eq!(
+            series.exemplars,
+            vec![proto::Exemplar {
+                labels: vec![proto::Label {
+                    name: "trace_id".into(),
+                    value: "abc".into(),
+                }],
+                value: 0.7,
+                timestamp: 1612325106789,
+            }]
+        );
+    }
+
+    fn encode_native(metric: &Metric) -> proto::WriteRequest {
+        let mut time_series = TimeSeries::new().with_native_histograms(true);
+        time_series.encode_metric(None, &[], &[], metric);
+        time_series.finish()
+    }
+
+    #[test]
+    fn encodes_native_histograms_request() {
+        let metric = Metric::new(
+            "latency".to_owned(),
+            MetricKind::Absolute,
+            MetricValue::Distribution {
+                samples: vector_lib::samples![1.0 => 2, 2.0 => 1, 0.0 => 1, -1.0 => 1],
+                statistic: StatisticKind::Histogram,
+            },
+        )
+        .with_timestamp(Some(timestamp()));
+
+        let request = encode_native(&metric);
+        assert_eq!(request.timeseries.len(), 1);
+        assert_eq!(
+            request.timeseries[0].labels,
+            vec![proto::Label {
+                name: METRIC_NAME_LABEL.into(),
+                value: "latency".into(),
+            }]
+        );
+        assert!(request.timeseries[0].samples.is_empty());
+        assert_eq!(
+            request.timeseries[0].histograms,
+            vec![proto::Histogram {
+                count: Some(proto::histogram::Count::CountInt(5)),
+                sum: 3.0,
+                schema: 5,
+                zero_threshold: 0.0,
+                zero_count: Some(proto::histogram::ZeroCount::ZeroCountInt(1)),
+                negative_spans: vec![proto::BucketSpan {
+                    offset: 0,
+                    length: 1
+                }],
+                negative_deltas: vec![1],
+                negative_counts: vec![],
+                // 1 is the upper bound of bucket 0, and 2 of bucket 32.
+                positive_spans: vec![
+                    proto::BucketSpan {
+                        offset: 0,
+                        length: 1
+                    },
+                    proto::BucketSpan {
+                        offset: 31,
+                        length: 1
+                    },
+                ],
+                positive_deltas: vec![2, -1],
+                positive_counts: vec![],
+                reset_hint: proto::histogram::ResetHint::Unknown as i32,
+                timestamp: 1612325106789,
+            }]
+        );
+        assert_eq!(
+            request.metadata[0].r#type,
+            proto::MetricType::Histogram as i32
+        );
+    }
+
+    #[test]
+    fn encodes_sketch_native_histograms_request() {
+        let mut ddsketch = AgentDDSketch::with_agent_defaults();
+        ddsketch.insert_many(&[1.0, 2.0, 4.0, 4.0]);
+        let metric = Metric::new(
+            "latency".to_owned(),
+            MetricKind::Absolute,
+            MetricValue::Sketch {
+                sketch: MetricSketch::AgentDDSketch(ddsketch),
+            },
+        );
+
+        let request = encode_native(&metric);
+        let histogram = &request.timeseries[0].histograms[0];
+        assert_eq!(histogram.count, Some(proto::histogram::Count::CountInt(4)));
+        assert_eq!(histogram.sum, 11.0);
+        // Each value falls in its own bucket, or the one above it as bins are a little wider.
+        let mut count = 0;
+        let counts = histogram
+            .positive_deltas
+            .iter()
+            .map(|delta| {
+                count += delta;
+                count
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(counts, vec![1, 1, 2]);
+        assert_eq!(
+            request.metadata[0].r#type,
+            proto::MetricType::Histogram as i32
+        );
+    }
+
+    #[test]
+    fn encodes_stored_native_histograms_request() {
+        let span = |offset: i64, length: i64| {
+            Value::Object(
+                [
+                    ("offset".into(), offset.into()),
+                    ("length".into(), length.into()),
+                ]
+                .into_iter()
+                .collect(),
+            )
+        };
+        let histogram = Value::Object(
+            [
+                ("count".into(), Value::from(4)),
+                ("sum".into(), Value::from(NotNan::new(-1.5).unwrap())),
+                ("schema".into(), Value::from(-1)),
+                (
+                    "zero_threshold".into(),
+                    Value::from(NotNan::new(0.1).unwrap()),
+                ),
+                ("zero_count".into(), Value::from(1)),
+                ("negative_spans".into(), Value::Array(vec![span(-1, 1)])),
+                ("negative_deltas".into(), Value::Array(vec![Value::from(2)])),
+                ("positive_spans".into(), Value::Array(vec![span(2, 1)])),
+                ("positive_deltas".into(), Value::Array(vec![Value::from(1)])),
+                ("reset_hint".into(), Value::from("no")),
+            ]
+            .into_iter()
+            .collect(),
+        );
+        let mut metric = Metric::new(
+            "latency".to_owned(),
+            MetricKind::Absolute,
+            MetricValue::AggregatedHistogram {
+                buckets: vector_lib::buckets![-0.5 => 2, 0.1 => 1, 4.0 => 1],
+                count: 4,
+                sum: -1.5,
+            },
+        )
+        .with_timestamp(Some(timestamp()));
+        metric.metadata_mut().value_mut().insert(
+            path!("prometheus_remote_write", "native_histogram"),
+            histogram,
+        );
+
+        let request = encode_native(&metric);
+        assert_eq!(request.timeseries.len(), 1);
+        assert!(request.timeseries[0].samples.is_empty());
+        assert_eq!(
+            request.timeseries[0].histograms,
+            vec![proto::Histogram {
+                count: Some(proto::histogram::Count::CountInt(4)),
+                sum: -1.5,
+                schema: -1,
+                zero_threshold: 0.1,
+                zero_count: Some(proto::histogram::ZeroCount::ZeroCountInt(1)),
+                negative_spans: vec![proto::BucketSpan {
+                    offset: -1,
+                    length: 1
+                }],
+                negative_deltas: vec![2],
+                negative_counts: vec![],
+                positive_spans: vec![proto::BucketSpan {
+                    offset: 2,
+                    length: 1
+                }],
+                positive_deltas: vec![1],
+                positive_counts: vec![],
+                reset_hint: proto::histogram::ResetHint::No as i32,
+                timestamp: 1612325106789,
+            }]
+        );
+
+        // Without native histograms, the buckets are sent as classic series.
+        let request = encode_one::<TimeSeries>(None, &[], &[], &metric);
+        assert!(request
+            .timeseries
+            .iter()
+            .all(|series| series.histograms.is_empty()));
+        assert_eq!(request.timeseries.len(), 6);
+    }
+
+    #[test]
+    fn encodes_metadata_per_family() {
+        let metric = |namespace: &str| {
+            Metric::new(
+                "requests".to_owned(),
+                MetricKind::Absolute,
+                MetricValue::Counter { value: 1.0 },
+            )
+            .with_namespace(Some(namespace))
+        };
+
+        let mut time_series = TimeSeries::new();
+        time_series.encode_metric(None, &[], &[], &metric("api"));
+        time_series.encode_metric(None, &[], &[], &metric("web"));
+        let request = time_series.finish();
+        assert_eq!(
+            request
+                .metadata
+                .iter()
+                .map(|metadata| metadata.metric_family_name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["api_requests", "web_requests"]
+        );
+    }
+
     fn timestamp() -> DateTime<Utc> {
         Utc.with_ymd_and_hms(2021, 2, 3, 4, 5, 6)
             .single()
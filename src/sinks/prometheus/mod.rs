@@ -2,7 +2,7 @@
 use vector_lib::event::Metric;
 use vector_lib::sensitive_string::SensitiveString;
 
-mod collector;
+pub(crate) mod collector;
 pub mod exporter;
 pub mod remote_write;
 
@@ -36,13 +36,13 @@ pub enum PrometheusRemoteWriteAuth {
     Aws(crate::aws::AwsAuthentication),
 }
 
-fn default_histogram_buckets() -> Vec<f64> {
+pub(crate) fn default_histogram_buckets() -> Vec<f64> {
     vec![
         0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
     ]
 }
 
-fn default_summary_quantiles() -> Vec<f64> {
+pub(crate) fn default_summary_quantiles() -> Vec<f64> {
     vec![0.5, 0.75, 0.9, 0.95, 0.99]
 }
 
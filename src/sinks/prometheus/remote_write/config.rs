@@ -75,6 +75,19 @@ pub struct RemoteWriteConfig {
     #[configurable(metadata(docs::advanced))]
     pub quantiles: Vec<f64>,
 
+    /// Whether or not to send distributions and sketches as [native histograms][native_histograms].
+    ///
+    /// Native histograms keep the full resolution of distributions and sketches, rather than
+    /// aggregating them with `buckets` or `quantiles`, but the endpoint must support them, as
+    /// Prometheus does with the `native-histograms` feature enabled. Histograms with explicit
+    /// buckets are still sent as classic histograms, unless they were received as native
+    /// histograms by the `prometheus_remote_write` source, which are sent as they were received.
+    ///
+    /// [native_histograms]: https://prometheus.io/docs/concepts/metric_types/#histogram
+    #[serde(default)]
+    #[configurable(metadata(docs::advanced))]
+    pub native_histograms: bool,
+
     #[configurable(derived)]
     #[serde(default)]
     pub batch: RemoteWriteBatchConfig,
@@ -201,6 +214,7 @@ impl SinkConfig for RemoteWriteConfig {
                 .into_batcher_settings()?,
             buckets,
             quantiles,
+            native_histograms: self.native_histograms,
             default_namespace,
             service,
         };
@@ -15,6 +15,7 @@ pub(crate) struct RemoteWriteEncoder {
     pub(super) default_namespace: Option<String>,
     pub(super) buckets: Vec<f64>,
     pub(super) quantiles: Vec<f64>,
+    pub(super) native_histograms: bool,
 }
 
 impl encoding::Encoder<Vec<Metric>> for RemoteWriteEncoder {
@@ -25,7 +26,8 @@ impl encoding::Encoder<Vec<Metric>> for RemoteWriteEncoder {
     ) -> io::Result<(usize, GroupedCountByteSize)> {
         let mut byte_size = telemetry().create_request_count_byte_size();
 
-        let mut time_series = collector::TimeSeries::new();
+        let mut time_series =
+            collector::TimeSeries::new().with_native_histograms(self.native_histograms);
         let len = input.len();
         for metric in input {
             byte_size.add_event(&metric, metric.estimated_json_encoded_size_of());
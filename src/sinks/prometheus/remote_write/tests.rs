@@ -9,7 +9,7 @@ use vector_lib::prometheus::parser::proto;
 use super::*;
 use crate::{
     config::SinkContext,
-    event::{MetricKind, MetricValue},
+    event::{MetricKind, MetricValue, StatisticKind},
     sinks::{prometheus::remote_write::config::RemoteWriteConfig, util::test::build_test_server},
     test_util::{
         self,
@@ -229,6 +229,32 @@ async fn doesnt_aggregate_batches() {
     assert_eq!(req.timeseries[0].samples[0].value, 27.0);
 }
 
+#[tokio::test]
+async fn sends_native_histograms() {
+    let distribution = Metric::new(
+        "latency",
+        MetricKind::Absolute,
+        MetricValue::Distribution {
+            samples: vector_lib::samples![1.0 => 2, 2.0 => 1],
+            statistic: StatisticKind::Histogram,
+        },
+    )
+    .with_timestamp(Some(chrono::Utc::now()));
+    let outputs = send_request("native_histograms = true", vec![distribution.into()]).await;
+
+    assert_eq!(outputs.len(), 1);
+    let (_, req) = &outputs[0];
+    assert_eq!(req.timeseries.len(), 1);
+    assert_eq!(req.timeseries[0].labels, labels!("__name__" => "latency"));
+    assert!(req.timeseries[0].samples.is_empty());
+    assert_eq!(req.timeseries[0].histograms.len(), 1);
+    assert_eq!(
+        req.timeseries[0].histograms[0].count,
+        Some(proto::histogram::Count::CountInt(3))
+    );
+    assert_eq!(req.metadata[0].r#type, proto::MetricType::Histogram as i32);
+}
+
 async fn send_request(config: &str, events: Vec<Event>) -> Vec<(HeaderMap, proto::WriteRequest)> {
     assert_sink_compliance(&HTTP_SINK_TAGS, async {
         let addr = test_util::next_addr();
@@ -2,7 +2,7 @@ use crate::{
     schema,
     sinks::{
         prelude::*,
-        pulsar::sink::{healthcheck, PulsarSink},
+        pulsar::sink::{healthcheck, BuildError, PulsarSink},
     },
 };
 use futures_util::FutureExt;
@@ -15,12 +15,19 @@ use pulsar::{
 };
 use pulsar::{error::AuthenticationError, OperationRetryOptions};
 use snafu::ResultExt;
+use std::{num::NonZeroUsize, time::Duration};
 use vector_lib::codecs::{encoding::SerializerConfig, TextSerializerConfig};
 use vector_lib::config::DataType;
 use vector_lib::lookup::lookup_v2::OptionalTargetPath;
 use vector_lib::sensitive_string::SensitiveString;
 use vrl::value::Kind;
 
+// The defaults of key-based batching, which match the defaults of the batches of Pulsar producers
+// where they exist.
+const KEY_BATCH_MAX_EVENTS: usize = 1000;
+const KEY_BATCH_MAX_BYTES: usize = 128 * 1024;
+const KEY_BATCH_TIMEOUT_SECS: f64 = 1.0;
+
 /// Configuration for the `pulsar` sink.
 #[configurable_component(sink("pulsar", "Publish observability events to Apache Pulsar topics."))]
 #[derive(Clone, Debug)]
@@ -60,6 +67,20 @@ pub struct PulsarSinkConfig {
     #[serde(default)]
     pub(crate) batch: PulsarBatchConfig,
 
+    /// Whether to send the events of each request in a Pulsar transaction.
+    ///
+    /// Not supported yet: the Pulsar client doesn't implement the transaction coordinator
+    /// protocol, so enabling it is a configuration error.
+    #[serde(default)]
+    pub(crate) transactional: bool,
+
+    /// Whether to split messages larger than the maximum message size of the broker into chunks.
+    ///
+    /// Not supported yet: the Pulsar client can't split messages into chunks, so enabling it is a
+    /// configuration error. Oversized messages are rejected by the broker.
+    #[serde(default)]
+    pub(crate) chunking: bool,
+
     #[configurable(derived)]
     #[serde(default)]
     pub compression: PulsarCompression,
@@ -94,6 +115,52 @@ pub(crate) struct PulsarBatchConfig {
     /// The maximum size of a batch before it is flushed.
     #[configurable(metadata(docs::type_unit = "bytes"))]
     pub max_bytes: Option<usize>,
+
+    /// The maximum age of a batch before it is flushed.
+    ///
+    /// Only used with key-based batching, as the batches of the producer are flushed once full.
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    #[configurable(metadata(docs::human_name = "Timeout"))]
+    pub timeout_secs: Option<f64>,
+
+    /// Whether to batch events by topic and partition key.
+    ///
+    /// The batches of the producer mix the events of all keys, and a `Key_Shared` subscription
+    /// dispatches each batch as a whole to a single consumer, so consumers receive events of keys
+    /// they aren't assigned to. With key-based batching, the events of each batch share their
+    /// partition key, and the batches of a key are sent in order.
+    ///
+    /// The Pulsar client doesn't build key-based batch messages, so the events of these batches
+    /// are sent as individual messages, pipelined over the connection of the producer.
+    #[serde(default)]
+    pub key_based: bool,
+}
+
+impl PulsarBatchConfig {
+    /// Returns the settings of the batcher of key-based batching, if enabled.
+    pub(crate) fn key_based_settings(&self) -> crate::Result<Option<BatcherSettings>> {
+        if !self.key_based {
+            return Ok(None);
+        }
+
+        let timeout_secs = self.timeout_secs.unwrap_or(KEY_BATCH_TIMEOUT_SECS);
+        if !(timeout_secs.is_finite() && timeout_secs > 0.0) {
+            return Err("`batch.timeout_secs` must be greater than zero.".into());
+        }
+        let max_bytes = NonZeroUsize::new(self.max_bytes.unwrap_or(KEY_BATCH_MAX_BYTES))
+            .ok_or("`batch.max_bytes` must be greater than zero.")?;
+        let max_events = NonZeroUsize::new(
+            self.max_events
+                .map_or(KEY_BATCH_MAX_EVENTS, |max_events| max_events as usize),
+        )
+        .ok_or("`batch.max_events` must be greater than zero.")?;
+
+        Ok(Some(BatcherSettings::new(
+            Duration::from_secs_f64(timeout_secs),
+            max_bytes,
+            max_events,
+        )))
+    }
 }
 
 /// Authentication configuration.
@@ -181,6 +248,8 @@ impl Default for PulsarSinkConfig {
             properties_key: None,
             partition_key_field: None,
             batch: Default::default(),
+            transactional: false,
+            chunking: false,
             compression: Default::default(),
             encoding: TextSerializerConfig::default().into(),
             auth: None,
@@ -190,6 +259,17 @@ impl Default for PulsarSinkConfig {
 }
 
 impl PulsarSinkConfig {
+    /// Rejects the options that the Pulsar client doesn't support.
+    pub(crate) fn check_supported(&self) -> Result<(), BuildError> {
+        if self.transactional {
+            return Err(BuildError::TransactionsUnsupported);
+        }
+        if self.chunking {
+            return Err(BuildError::ChunkingUnsupported);
+        }
+        Ok(())
+    }
+
     pub(crate) async fn create_pulsar_client(&self) -> Result<Pulsar<TokioExecutor>, PulsarError> {
         let mut builder = Pulsar::builder(&self.endpoint, TokioExecutor);
         if let Some(auth) = &self.auth {
@@ -229,13 +309,20 @@ impl PulsarSinkConfig {
     }
 
     pub(crate) fn build_producer_options(&self) -> ProducerOptions {
+        // Key-based batches are built by the sink, as the batches of the producer mix keys.
+        let (batch_size, batch_byte_size) = if self.batch.key_based {
+            (None, None)
+        } else {
+            (self.batch.max_events, self.batch.max_bytes)
+        };
+
         let mut opts = ProducerOptions {
             encrypted: None,
             access_mode: Some(0),
             metadata: Default::default(),
             schema: None,
-            batch_size: self.batch.max_events,
-            batch_byte_size: self.batch.max_bytes,
+            batch_size,
+            batch_byte_size,
             compression: None,
         };
 
@@ -284,6 +371,8 @@ impl GenerateConfig for PulsarSinkConfig {
 #[typetag::serde(name = "pulsar")]
 impl SinkConfig for PulsarSinkConfig {
     async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        self.check_supported()?;
+
         let client = self
             .create_pulsar_client()
             .await
@@ -1,4 +1,7 @@
-use crate::sinks::pulsar::{config::PulsarSinkConfig, sink::PulsarSink};
+use crate::sinks::pulsar::{
+    config::{PulsarBatchConfig, PulsarSinkConfig},
+    sink::PulsarSink,
+};
 use futures::StreamExt;
 use pulsar::SubType;
 
@@ -10,6 +13,7 @@ use crate::test_util::{
     random_lines_with_stream, random_string, trace_init,
 };
 use bytes::Bytes;
+use vector_lib::lookup::lookup_v2::OptionalTargetPath;
 
 fn pulsar_address() -> String {
     std::env::var("PULSAR_ADDRESS").unwrap_or_else(|_| "pulsar://127.0.0.1:6650".into())
@@ -87,3 +91,20 @@ async fn pulsar_happy() {
 
     pulsar_happy_reuse(cnf).await
 }
+
+#[tokio::test]
+async fn pulsar_happy_key_based_batching() {
+    let cnf = PulsarSinkConfig {
+        endpoint: pulsar_address(),
+        partition_key_field: Some(OptionalTargetPath::event("message")),
+        batch: PulsarBatchConfig {
+            max_events: Some(10),
+            key_based: true,
+            ..Default::default()
+        },
+        // overriden by test
+        ..Default::default()
+    };
+
+    pulsar_happy_reuse(cnf).await
+}
@@ -5,7 +5,7 @@ use std::task::{Context, Poll};
 use bytes::Bytes;
 use pulsar::producer::Message;
 use pulsar::{Error as PulsarError, Executor, MultiTopicProducer, ProducerOptions, Pulsar};
-use tokio::sync::Mutex;
+use tokio::sync::{Mutex, OwnedMutexGuard};
 
 use crate::internal_events::PulsarSendingError;
 use crate::sinks::{prelude::*, pulsar::request_builder::PulsarMetadata};
@@ -36,6 +36,42 @@ impl DriverResponse for PulsarResponse {
     }
 }
 
+impl PulsarRequest {
+    fn message(&self) -> Message {
+        let properties = self
+            .metadata
+            .properties
+            .iter()
+            .flatten()
+            .map(|(key, value)| (key.to_string(), String::from_utf8_lossy(value).to_string()))
+            .collect::<HashMap<_, _>>();
+
+        let partition_key = self
+            .metadata
+            .key
+            .as_ref()
+            .map(|key| String::from_utf8_lossy(key).to_string());
+
+        Message {
+            payload: self.body.to_vec(),
+            properties,
+            partition_key,
+            event_time: self.metadata.timestamp_millis.map(|t| t as u64),
+            ..Default::default()
+        }
+    }
+}
+
+impl ByteSizeOf for PulsarRequest {
+    fn size_of(&self) -> usize {
+        self.body.len()
+    }
+
+    fn allocated_bytes(&self) -> usize {
+        0
+    }
+}
+
 impl Finalizable for PulsarRequest {
     fn take_finalizers(&mut self) -> EventFinalizers {
         std::mem::take(&mut self.metadata.finalizers)
@@ -52,10 +88,62 @@ impl MetaDescriptive for PulsarRequest {
     }
 }
 
+/// A batch of requests to the same topic with the same partition key, built by key-based batching.
+pub(super) struct PulsarBatchRequest {
+    pub topic: String,
+    pub requests: Vec<PulsarRequest>,
+    pub finalizers: EventFinalizers,
+    pub request_metadata: RequestMetadata,
+}
+
+impl From<Vec<PulsarRequest>> for PulsarBatchRequest {
+    fn from(mut requests: Vec<PulsarRequest>) -> Self {
+        let finalizers =
+            requests
+                .iter_mut()
+                .fold(EventFinalizers::default(), |mut finalizers, request| {
+                    finalizers.merge(request.take_finalizers());
+                    finalizers
+                });
+        let request_metadata =
+            RequestMetadata::from_batch(requests.iter().map(|r| r.get_metadata().clone()));
+        let topic = requests
+            .first()
+            .map(|request| request.metadata.topic.clone())
+            .unwrap_or_default();
+
+        Self {
+            topic,
+            requests,
+            finalizers,
+            request_metadata,
+        }
+    }
+}
+
+impl Finalizable for PulsarBatchRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        std::mem::take(&mut self.finalizers)
+    }
+}
+
+impl MetaDescriptive for PulsarBatchRequest {
+    fn get_metadata(&self) -> &RequestMetadata {
+        &self.request_metadata
+    }
+
+    fn metadata_mut(&mut self) -> &mut RequestMetadata {
+        &mut self.request_metadata
+    }
+}
+
 pub struct PulsarService<Exe: Executor> {
     // NOTE: the reason for the Mutex here is because the `Producer` from the pulsar crate
     // needs to be `mut`, and the `Service::call()` returns a Future.
     producer: Arc<Mutex<MultiTopicProducer<Exe>>>,
+    // The producer is locked in `poll_ready()` and handed to the next call, so that the messages
+    // of requests are enqueued in the order of the requests.
+    reserved: Option<OwnedMutexGuard<MultiTopicProducer<Exe>>>,
 }
 
 impl<Exe: Executor> PulsarService<Exe> {
@@ -74,8 +162,62 @@ impl<Exe: Executor> PulsarService<Exe> {
 
         PulsarService {
             producer: Arc::new(Mutex::new(producer)),
+            reserved: None,
+        }
+    }
+
+    fn poll_reserve(&mut self) -> Poll<Result<(), PulsarError>> {
+        if self.reserved.is_none() {
+            match Arc::clone(&self.producer).try_lock_owned() {
+                Ok(producer) => self.reserved = Some(producer),
+                Err(_) => return Poll::Pending,
+            }
+        }
+        Poll::Ready(Ok(()))
+    }
+
+    fn take_reserved(&mut self) -> OwnedMutexGuard<MultiTopicProducer<Exe>> {
+        self.reserved
+            .take()
+            .expect("`poll_ready()` must be called before `call()`")
+    }
+}
+
+/// Enqueues the messages to the topic in order, then waits for all of them to be persisted.
+///
+/// The producer is released once the messages are enqueued, so that the messages of the next
+/// requests can be enqueued while these are sent.
+async fn send<Exe: Executor>(
+    mut producer: OwnedMutexGuard<MultiTopicProducer<Exe>>,
+    topic: String,
+    messages: Vec<Message>,
+) -> Result<(), PulsarError> {
+    let count = messages.len();
+    let mut receipts = Vec::with_capacity(count);
+    for message in messages {
+        match producer.send(topic.clone(), message).await {
+            Ok(receipt) => receipts.push(receipt),
+            Err(error) => {
+                emit!(PulsarSendingError {
+                    error: Box::new(PulsarError::Custom("failed to send".to_string())),
+                    count,
+                });
+                return Err(error);
+            }
+        }
+    }
+    drop(producer);
+
+    for receipt in receipts {
+        if let Err(error) = receipt.await {
+            emit!(PulsarSendingError {
+                error: Box::new(PulsarError::Custom("failed to send".to_string())),
+                count,
+            });
+            return Err(error);
         }
     }
+    Ok(())
 }
 
 impl<Exe: Executor> Service<PulsarRequest> for PulsarService<Exe> {
@@ -84,77 +226,57 @@ impl<Exe: Executor> Service<PulsarRequest> for PulsarService<Exe> {
     type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
 
     fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
-        match self.producer.try_lock() {
-            Ok(_) => Poll::Ready(Ok(())),
-            Err(_) => Poll::Pending,
-        }
+        self.poll_reserve()
     }
 
     fn call(&mut self, request: PulsarRequest) -> Self::Future {
-        let producer = Arc::clone(&self.producer);
+        // The locking of this mutex is not normal in `Service::call()` implementations, but we
+        // at least can limit the scope of the lock to the enqueueing of the messages, and reduce
+        // the possibility of performance impact by acquiring it with `try_lock()` in
+        // `poll_ready()`. This sink is already limited to sequential request handling due to the
+        // pulsar API, so this shouldn't impact performance from a concurrent requests standpoint.
+        let producer = self.take_reserved();
         let topic = request.metadata.topic.clone();
-        let event_time = request
-            .metadata
-            .timestamp_millis
-            .to_owned()
-            .map(|t| t as u64);
+        let message = request.message();
 
         Box::pin(async move {
-            let body = request.body.clone();
-            let byte_size = request.body.len();
-
-            let mut properties = HashMap::new();
-            if let Some(props) = request.metadata.properties {
-                for (key, value) in props {
-                    properties.insert(key.into(), String::from_utf8_lossy(&value).to_string());
-                }
-            }
+            send(producer, topic, vec![message]).await?;
+            Ok(PulsarResponse {
+                byte_size: request.body.len(),
+                event_byte_size: request
+                    .request_metadata
+                    .into_events_estimated_json_encoded_byte_size(),
+            })
+        })
+    }
+}
 
-            let partition_key = request
-                .metadata
-                .key
-                .map(|key| String::from_utf8_lossy(&key).to_string());
-
-            let message = Message {
-                payload: body.as_ref().to_vec(),
-                properties,
-                partition_key,
-                event_time,
-                ..Default::default()
-            };
-
-            // The locking if this mutex is not normal in `Service::call()` implementations, but we
-            // at least can limit the scope of the lock by placing it here, and reduce the
-            // possibility of performance impact by checking the `try_lock()` result in
-            // `poll_ready()`. This sink is already limited to sequential request handling due to
-            // the pulsar API, so this shouldn't impact performance from a concurrent requests
-            // standpoint.
-            let fut = producer.lock().await.send(topic, message).await;
-
-            match fut {
-                Ok(resp) => match resp.await {
-                    Ok(_) => Ok(PulsarResponse {
-                        byte_size,
-                        event_byte_size: request
-                            .request_metadata
-                            .into_events_estimated_json_encoded_byte_size(),
-                    }),
-                    Err(e) => {
-                        emit!(PulsarSendingError {
-                            error: Box::new(PulsarError::Custom("failed to send".to_string())),
-                            count: 1
-                        });
-                        Err(e)
-                    }
-                },
-                Err(e) => {
-                    emit!(PulsarSendingError {
-                        error: Box::new(PulsarError::Custom("failed to send".to_string())),
-                        count: 1,
-                    });
-                    Err(e)
-                }
-            }
+impl<Exe: Executor> Service<PulsarBatchRequest> for PulsarService<Exe> {
+    type Response = PulsarResponse;
+    type Error = PulsarError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.poll_reserve()
+    }
+
+    fn call(&mut self, request: PulsarBatchRequest) -> Self::Future {
+        let producer = self.take_reserved();
+        let messages = request
+            .requests
+            .iter()
+            .map(PulsarRequest::message)
+            .collect();
+        let byte_size = request.requests.iter().map(|r| r.body.len()).sum();
+
+        Box::pin(async move {
+            send(producer, request.topic, messages).await?;
+            Ok(PulsarResponse {
+                byte_size,
+                event_byte_size: request
+                    .request_metadata
+                    .into_events_estimated_json_encoded_byte_size(),
+            })
         })
     }
 }
@@ -7,8 +7,11 @@ use std::collections::HashMap;
 use vrl::value::KeyString;
 
 use super::{
-    config::PulsarSinkConfig, encoder::PulsarEncoder, request_builder::PulsarRequestBuilder,
-    service::PulsarService, util,
+    config::PulsarSinkConfig,
+    encoder::PulsarEncoder,
+    request_builder::PulsarRequestBuilder,
+    service::{PulsarBatchRequest, PulsarRequest, PulsarService},
+    util,
 };
 use crate::sinks::prelude::*;
 
@@ -17,6 +20,14 @@ use crate::sinks::prelude::*;
 pub(crate) enum BuildError {
     #[snafu(display("creating pulsar producer failed: {}", source))]
     CreatePulsarSink { source: PulsarError },
+    #[snafu(display(
+        "`transactional` is not supported, as the Pulsar client doesn't implement the transaction coordinator protocol"
+    ))]
+    TransactionsUnsupported,
+    #[snafu(display(
+        "`chunking` is not supported, as the Pulsar client can't split messages into chunks"
+    ))]
+    ChunkingUnsupported,
 }
 
 pub(crate) struct PulsarSink {
@@ -25,6 +36,19 @@ pub(crate) struct PulsarSink {
     service: PulsarService<TokioExecutor>,
     config: PulsarSinkConfig,
     topic_template: Template,
+    key_batch_settings: Option<BatcherSettings>,
+}
+
+/// Partitions requests by their topic and partition key, for key-based batching.
+struct KeyPartitioner;
+
+impl Partitioner for KeyPartitioner {
+    type Item = PulsarRequest;
+    type Key = (String, Option<Bytes>);
+
+    fn partition(&self, item: &Self::Item) -> Self::Key {
+        (item.metadata.topic.clone(), item.metadata.key.clone())
+    }
 }
 
 /// Stores the event together with the extracted keys, topics, etc.
@@ -86,6 +110,7 @@ impl PulsarSink {
         let encoder = Encoder::<()>::new(serializer);
         let service = PulsarService::new(client, producer_opts, config.producer_name.clone());
         let topic_template = config.topic.clone();
+        let key_batch_settings = config.batch.key_based_settings()?;
 
         Ok(PulsarSink {
             config,
@@ -93,6 +118,7 @@ impl PulsarSink {
             encoder,
             service,
             topic_template,
+            key_batch_settings,
         })
     }
 
@@ -104,7 +130,7 @@ impl PulsarSink {
                 encoder: self.encoder.clone(),
             },
         };
-        let sink = input
+        let requests = input
             .filter_map(|event| {
                 std::future::ready(util::make_pulsar_event(
                     &self.topic_template,
@@ -117,11 +143,20 @@ impl PulsarSink {
                 request
                     .map_err(|e| error!("Failed to build Pulsar request: {:?}.", e))
                     .ok()
-            })
-            .into_driver(service)
-            .protocol("tcp");
+            });
 
-        sink.run().await
+        match self.key_batch_settings {
+            None => requests.into_driver(service).protocol("tcp").run().await,
+            Some(batch_settings) => {
+                requests
+                    .batched_partitioned(KeyPartitioner, || batch_settings.as_byte_size_config())
+                    .map(|(_, requests)| PulsarBatchRequest::from(requests))
+                    .into_driver(service)
+                    .protocol("tcp")
+                    .run()
+                    .await
+            }
+        }
     }
 }
 
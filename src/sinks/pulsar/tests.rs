@@ -3,8 +3,9 @@ use vector_lib::configurable::component::GenerateConfig;
 use vector_lib::lookup::lookup_v2::OptionalTargetPath;
 use vrl::value::{ObjectMap, Value};
 
+use crate::config::{SinkConfig, SinkContext};
 use crate::event::{Event, LogEvent};
-use crate::sinks::pulsar::config::PulsarSinkConfig;
+use crate::sinks::pulsar::config::{PulsarBatchConfig, PulsarSinkConfig};
 
 #[test]
 fn generate_config() {
@@ -28,3 +29,67 @@ fn pulsar_get_headers() {
     assert_eq!(properties.get("a-key").unwrap(), "a-value".as_bytes());
     assert_eq!(properties.get("b-key").unwrap(), "b-value".as_bytes());
 }
+
+#[test]
+fn pulsar_key_based_batching() {
+    let config = PulsarSinkConfig {
+        batch: PulsarBatchConfig {
+            max_events: Some(100),
+            key_based: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    // The batches are built by the sink, so that the producer doesn't mix keys.
+    let options = config.build_producer_options();
+    assert_eq!(options.batch_size, None);
+    assert_eq!(options.batch_byte_size, None);
+
+    let settings = config.batch.key_based_settings().unwrap().unwrap();
+    assert_eq!(settings.item_limit, 100);
+    assert_eq!(settings.size_limit, 128 * 1024);
+
+    let config = PulsarSinkConfig {
+        batch: PulsarBatchConfig {
+            timeout_secs: Some(0.0),
+            key_based: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    assert!(config.batch.key_based_settings().is_err());
+    assert!(PulsarBatchConfig::default()
+        .key_based_settings()
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test]
+async fn pulsar_rejects_transactions_and_chunking() {
+    for (config, option) in [
+        (
+            PulsarSinkConfig {
+                transactional: true,
+                ..Default::default()
+            },
+            "`transactional`",
+        ),
+        (
+            PulsarSinkConfig {
+                chunking: true,
+                ..Default::default()
+            },
+            "`chunking`",
+        ),
+    ] {
+        // The options are rejected before connecting to the broker.
+        let error = config
+            .build(SinkContext::default())
+            .await
+            .err()
+            .unwrap()
+            .to_string();
+        assert!(error.starts_with(option), "{}", error);
+    }
+}
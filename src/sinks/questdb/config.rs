@@ -0,0 +1,274 @@
+use std::collections::HashSet;
+
+use openssl::{
+    bn::{BigNum, BigNumContext},
+    ec::{EcGroup, EcKey, EcPoint},
+    nid::Nid,
+    pkey::{PKey, Private},
+};
+use vector_lib::{
+    event::KeyString,
+    sensitive_string::SensitiveString,
+    tls::{MaybeTlsSettings, TlsEnableableConfig},
+};
+
+use super::{
+    encoder::QuestDbEncoder,
+    service::{Connection, ConnectionOptions, QuestDbRetryLogic, QuestDbService},
+    sink::QuestDbSink,
+};
+use crate::sinks::{influxdb::metrics::default_summary_quantiles, prelude::*};
+
+/// The default port of the InfluxDB line protocol over TCP.
+const DEFAULT_PORT: u16 = 9009;
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct QuestDbDefaultBatchSettings;
+
+impl SinkBatchSettings for QuestDbDefaultBatchSettings {
+    const MAX_EVENTS: Option<usize> = None;
+    const MAX_BYTES: Option<usize> = Some(1_000_000);
+    const TIMEOUT_SECS: f64 = 1.0;
+}
+
+/// Configuration for the `questdb` sink.
+#[configurable_component(sink(
+    "questdb",
+    "Deliver log and metric event data to QuestDB over the InfluxDB line protocol."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct QuestDbConfig {
+    /// The address of the InfluxDB line protocol endpoint of QuestDB.
+    ///
+    /// Both IP address and hostname are accepted formats. The port defaults to `9009`.
+    #[configurable(metadata(docs::examples = "localhost:9009"))]
+    #[configurable(metadata(docs::examples = "questdb.example.com:9009"))]
+    pub address: String,
+
+    /// The table that log events are written to.
+    ///
+    /// Metric events are written to the table named after the metric and its namespace, such as
+    /// `service_requests_total`.
+    #[configurable(metadata(docs::examples = "logs"))]
+    #[configurable(metadata(docs::examples = "{{ application }}_logs"))]
+    #[serde(default = "default_table")]
+    pub table: Template,
+
+    /// The names of the fields of log events that are written as symbols.
+    ///
+    /// Symbols are the indexed, repetitive string columns of QuestDB, such as hostnames or log
+    /// levels. The other fields of log events are written as columns of the type of their values,
+    /// and the timestamp of the events as the designated timestamp of the row.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "host"))]
+    #[configurable(metadata(docs::examples = "parent.child_field"))]
+    pub symbols: Vec<KeyString>,
+
+    /// The namespace of the tables of metrics that don't have one.
+    #[configurable(metadata(docs::examples = "service"))]
+    pub default_namespace: Option<String>,
+
+    /// The quantiles to write for distributions.
+    #[serde(default = "default_summary_quantiles")]
+    pub quantiles: Vec<f64>,
+
+    #[configurable(derived)]
+    pub auth: Option<QuestDbAuth>,
+
+    #[configurable(derived)]
+    #[serde(skip_serializing_if = "crate::serde::is_default", default)]
+    pub encoding: Transformer,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch: BatchConfig<QuestDbDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsEnableableConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::is_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+/// Authentication of the InfluxDB line protocol.
+///
+/// The sink proves the ownership of the key by signing a challenge sent by QuestDB with the
+/// private key, which is an elliptic curve key of the P-256 curve.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct QuestDbAuth {
+    /// The ID of the key, the `kid` member of its JSON Web Key.
+    #[configurable(metadata(docs::examples = "admin"))]
+    pub key_id: String,
+
+    /// The private key, the `d` member of its JSON Web Key, encoded in URL-safe Base64.
+    #[configurable(metadata(docs::examples = "${QUESTDB_PRIVATE_KEY}"))]
+    #[configurable(metadata(docs::examples = "5UjEMuA0Pj5pjK8a-fa24dyIf-Es5mYny3oE_Wmus48"))]
+    pub private_key: SensitiveString,
+}
+
+impl QuestDbAuth {
+    pub(super) fn signing_key(&self) -> crate::Result<PKey<Private>> {
+        let scalar = decode_base64_url(self.private_key.inner())
+            .ok_or("The private key of the authentication is not valid URL-safe Base64.")?;
+
+        let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1)?;
+        let private = BigNum::from_slice(&scalar)?;
+        let context = BigNumContext::new()?;
+        let mut public = EcPoint::new(&group)?;
+        public.mul_generator(&group, &private, &context)?;
+        let key = EcKey::from_private_components(&group, &private, &public)?;
+        key.check_key()?;
+
+        Ok(PKey::from_ec_key(key)?)
+    }
+}
+
+fn default_table() -> Template {
+    Template::try_from("logs").expect("the default table is a valid template")
+}
+
+impl GenerateConfig for QuestDbConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"address = "localhost:9009"
+            table = "logs""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "questdb")]
+impl SinkConfig for QuestDbConfig {
+    async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let options = self.connection_options()?;
+
+        let service = ServiceBuilder::new()
+            .settings(self.request.into_settings(), QuestDbRetryLogic)
+            .service(QuestDbService::new(options.clone()));
+
+        let encoder = QuestDbEncoder {
+            table: self.table.clone(),
+            symbols: self.symbols.iter().cloned().collect::<HashSet<_>>(),
+            default_namespace: self.default_namespace.clone(),
+            quantiles: self.quantiles.clone(),
+            transformer: self.encoding.clone(),
+        };
+
+        let sink = QuestDbSink {
+            batch_settings: self.batch.into_batcher_settings()?,
+            service,
+            encoder,
+        };
+
+        let healthcheck = Box::pin(healthcheck(options));
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::new(DataType::Metric | DataType::Log)
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+impl QuestDbConfig {
+    pub(super) fn connection_options(&self) -> crate::Result<ConnectionOptions> {
+        let uri = self.address.parse::<http::Uri>()?;
+        let host = uri
+            .host()
+            .ok_or("The address of the sink must have a host.")?
+            .to_string();
+        let port = uri.port_u16().unwrap_or(DEFAULT_PORT);
+        let tls = MaybeTlsSettings::from_config(&self.tls, false)?;
+
+        let auth = self
+            .auth
+            .as_ref()
+            .map(|auth| auth.signing_key().map(|key| (auth.key_id.clone(), key)))
+            .transpose()?;
+
+        Ok(ConnectionOptions {
+            host,
+            port,
+            tls,
+            auth,
+        })
+    }
+}
+
+async fn healthcheck(options: ConnectionOptions) -> crate::Result<()> {
+    Connection::connect(&options).await?;
+    Ok(())
+}
+
+/// Decodes URL-safe Base64, with or without padding, as used by JSON Web Keys.
+fn decode_base64_url(input: &str) -> Option<Vec<u8>> {
+    let mut standard = input.trim().replace('-', "+").replace('_', "/");
+    while standard.len() % 4 != 0 {
+        standard.push('=');
+    }
+    openssl::base64::decode_block(&standard).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<QuestDbConfig>();
+    }
+
+    #[test]
+    fn parses_address() {
+        let config: QuestDbConfig = toml::from_str(r#"address = "questdb""#).unwrap();
+        let options = config.connection_options().unwrap();
+        assert_eq!(options.host, "questdb");
+        assert_eq!(options.port, DEFAULT_PORT);
+        assert!(options.tls.is_raw());
+        assert!(options.auth.is_none());
+
+        let config: QuestDbConfig = toml::from_str(r#"address = "questdb:9010""#).unwrap();
+        assert_eq!(config.connection_options().unwrap().port, 9010);
+    }
+
+    #[test]
+    fn parses_auth() {
+        let config: QuestDbConfig = toml::from_str(
+            r#"
+            address = "questdb:9009"
+            auth.key_id = "admin"
+            auth.private_key = "5UjEMuA0Pj5pjK8a-fa24dyIf-Es5mYny3oE_Wmus48"
+            "#,
+        )
+        .unwrap();
+        let (key_id, key) = config.connection_options().unwrap().auth.unwrap();
+        assert_eq!(key_id, "admin");
+        assert!(key.ec_key().is_ok());
+
+        let config: QuestDbConfig = toml::from_str(
+            r#"
+            address = "questdb:9009"
+            auth.key_id = "admin"
+            auth.private_key = "not a key!"
+            "#,
+        )
+        .unwrap();
+        assert!(config.connection_options().is_err());
+    }
+}
@@ -0,0 +1,241 @@
+//! Encoding of events into lines of the InfluxDB line protocol, as written into QuestDB.
+
+use std::collections::{HashMap, HashSet};
+
+use bytes::BytesMut;
+use vector_lib::event::{KeyString, Metric, MetricTags};
+
+use crate::{
+    internal_events::InfluxdbEncodingError,
+    sinks::{
+        influxdb::{
+            encode_timestamp, influx_line_protocol, logs::to_field, metrics::get_type_and_fields,
+            Field, ProtocolVersion,
+        },
+        prelude::*,
+        util::encode_namespace,
+    },
+};
+
+/// The characters that QuestDB doesn't allow in the names of tables and columns.
+const RESERVED_CHARACTERS: &[char] = &[
+    '.', '?', ',', '\'', '"', '\\', '/', ':', '(', ')', '+', '-', '*', '%', '~', '\0', '\n', '\r',
+];
+
+/// Encodes log events into rows of the configured table, and metric events into rows of the
+/// tables named after them.
+#[derive(Clone)]
+pub(super) struct QuestDbEncoder {
+    pub(super) table: Template,
+    pub(super) symbols: HashSet<KeyString>,
+    pub(super) default_namespace: Option<String>,
+    pub(super) quantiles: Vec<f64>,
+    pub(super) transformer: Transformer,
+}
+
+impl QuestDbEncoder {
+    /// Appends the line of the event to the output, unless the event can't be encoded.
+    pub(super) fn encode_event(&self, event: Event, output: &mut BytesMut) {
+        match event {
+            Event::Log(log) => self.encode_log(log, output),
+            Event::Metric(metric) => self.encode_metric(&metric, output),
+            // The sink doesn't accept traces.
+            Event::Trace(_) => {}
+        }
+    }
+
+    fn encode_log(&self, mut log: LogEvent, output: &mut BytesMut) {
+        let Ok(table) = self.table.render_string(&log).map_err(|error| {
+            emit!(TemplateRenderingError {
+                error,
+                field: Some("table"),
+                drop_event: true,
+            })
+        }) else {
+            return;
+        };
+
+        let timestamp = encode_timestamp(match log.remove_timestamp() {
+            Some(Value::Timestamp(ts)) => Some(ts),
+            _ => None,
+        });
+
+        let log = {
+            let mut event = Event::from(log);
+            self.transformer.transform(&mut event);
+            event.into_log()
+        };
+
+        let mut symbols = MetricTags::default();
+        let mut columns = HashMap::new();
+        log.convert_to_fields().for_each(|(key, value)| {
+            if self.symbols.contains(&key[..]) {
+                symbols.replace(sanitize_name(&key), value.to_string_lossy().into_owned());
+            } else {
+                columns.insert(sanitize_name(&key).into(), to_field(value));
+            }
+        });
+
+        write_line(&table, symbols, Some(columns), timestamp, output);
+    }
+
+    fn encode_metric(&self, metric: &Metric, output: &mut BytesMut) {
+        let table = sanitize_name(&encode_namespace(
+            metric.namespace().or(self.default_namespace.as_deref()),
+            '_',
+            metric.name(),
+        ));
+
+        let symbols = metric
+            .tags()
+            .map(|tags| {
+                tags.iter_single()
+                    .map(|(key, value)| (sanitize_name(key), value.to_string()))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let (_, columns) = get_type_and_fields(metric.value(), &self.quantiles);
+        let columns = columns.map(|columns| {
+            columns
+                .into_iter()
+                .map(|(key, value)| (sanitize_name(&key).into(), value))
+                .collect()
+        });
+
+        write_line(
+            &table,
+            symbols,
+            columns,
+            encode_timestamp(metric.timestamp()),
+            output,
+        );
+    }
+}
+
+fn write_line(
+    table: &str,
+    symbols: MetricTags,
+    columns: Option<HashMap<KeyString, Field>>,
+    timestamp: i64,
+    output: &mut BytesMut,
+) {
+    if let Err(error_message) = influx_line_protocol(
+        ProtocolVersion::V1,
+        table,
+        Some(symbols),
+        columns,
+        timestamp,
+        output,
+    ) {
+        emit!(InfluxdbEncodingError {
+            error_message,
+            count: 1
+        });
+    }
+}
+
+/// Replaces the characters QuestDB doesn't allow in names, such as the dots of the paths of
+/// nested fields, by underscores.
+fn sanitize_name(name: &str) -> String {
+    name.replace(RESERVED_CHARACTERS, "_")
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_lib::event::{MetricKind, MetricValue, StatisticKind};
+    use vector_lib::metric_tags;
+    use vrl::btreemap;
+
+    use super::*;
+    use crate::sinks::influxdb::test_util::{assert_fields, split_line_protocol, ts};
+
+    fn encoder(symbols: &[&str]) -> QuestDbEncoder {
+        QuestDbEncoder {
+            table: Template::try_from("{{ application }}_logs").unwrap(),
+            symbols: symbols.iter().map(|&symbol| symbol.into()).collect(),
+            default_namespace: Some("vector".to_string()),
+            quantiles: vec![0.5],
+            transformer: Transformer::default(),
+        }
+    }
+
+    fn encode(encoder: &QuestDbEncoder, event: impl Into<Event>) -> String {
+        let mut output = BytesMut::new();
+        encoder.encode_event(event.into(), &mut output);
+        String::from_utf8(output.to_vec()).unwrap()
+    }
+
+    #[test]
+    fn encodes_logs() {
+        let mut log = LogEvent::from(btreemap! {
+            "application" => "shop",
+            "host" => "web-1",
+            "status" => 200,
+            "request" => btreemap! {
+                "path" => "/cart",
+            },
+        });
+        log.insert("timestamp", ts());
+
+        let line = encode(&encoder(&["application", "host"]), log);
+        let (table, symbols, columns, timestamp) = split_line_protocol(line.trim_end());
+        assert_eq!(table, "shop_logs");
+        assert_eq!(symbols, "application=shop,host=web-1");
+        assert_fields(columns, vec!["request_path=\"/cart\"", "status=200i"]);
+        assert_eq!(timestamp, "1542182950000000011");
+    }
+
+    #[test]
+    fn drops_logs_without_table() {
+        let log = LogEvent::from(btreemap! {
+            "message" => "hello",
+        });
+
+        assert_eq!(encode(&encoder(&[]), log), "");
+    }
+
+    #[test]
+    fn encodes_metrics() {
+        let counter = Metric::new(
+            "requests.total",
+            MetricKind::Absolute,
+            MetricValue::Counter { value: 42.0 },
+        )
+        .with_namespace(Some("shop"))
+        .with_tags(Some(metric_tags!("region" => "eu-west")))
+        .with_timestamp(Some(ts()));
+
+        assert_eq!(
+            encode(&encoder(&[]), counter),
+            "shop_requests_total,region=eu-west value=42 1542182950000000011\n"
+        );
+
+        let distribution = Metric::new(
+            "latency",
+            MetricKind::Incremental,
+            MetricValue::Distribution {
+                samples: vector_lib::samples![1.0 => 1, 3.0 => 1],
+                statistic: StatisticKind::Summary,
+            },
+        )
+        .with_timestamp(Some(ts()));
+
+        let line = encode(&encoder(&[]), distribution);
+        let (table, symbols, columns, _) = split_line_protocol(line.trim_end());
+        assert_eq!(table, "vector_latency");
+        assert_eq!(symbols, "");
+        assert_fields(
+            columns,
+            vec![
+                "avg=2",
+                "count=2",
+                "max=3",
+                "median=1",
+                "min=1",
+                "quantile_0_50=1",
+                "sum=4",
+            ],
+        );
+    }
+}
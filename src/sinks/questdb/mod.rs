@@ -0,0 +1,107 @@
+//! The `questdb` sink.
+//!
+//! This sink writes events to [QuestDB][questdb] over the [InfluxDB line protocol][ilp] on TCP,
+//! optionally authenticated by signing a challenge of the server with the private key of the
+//! configured key ID.
+//!
+//! Events are mapped to rows using the following rules:
+//!
+//! - Log events are written to the configured table. The fields listed in `symbols` are written
+//!   as symbols, the other fields as columns, and the timestamp of the event as the designated
+//!   timestamp.
+//! - Metric events are written to the table named `{namespace}_{name}`. The tags of the metrics
+//!   are written as symbols, and their values as columns, such as `value` for counters and gauges.
+//!
+//! The characters QuestDB doesn't allow in the names of tables and columns, such as the dots of
+//! the paths of nested fields, are replaced by underscores.
+//!
+//! [questdb]: https://questdb.io/
+//! [ilp]: https://questdb.io/docs/reference/api/ilp/overview/
+
+mod config;
+mod encoder;
+mod service;
+mod sink;
+
+pub use self::config::QuestDbConfig;
+
+#[cfg(test)]
+mod tests {
+    use futures::stream;
+    use openssl::{hash::MessageDigest, sign::Verifier};
+    use tokio::{
+        io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+        net::TcpListener,
+    };
+    use vector_lib::event::{LogEvent, Metric, MetricKind, MetricValue};
+
+    use super::*;
+    use crate::{
+        config::{SinkConfig, SinkContext},
+        event::Event,
+        test_util::{
+            components::{run_and_assert_sink_compliance, SINK_TAGS},
+            next_addr, trace_init,
+        },
+    };
+
+    #[tokio::test]
+    async fn writes_lines_with_authentication() {
+        trace_init();
+
+        let address = next_addr();
+        let config: QuestDbConfig = toml::from_str(&format!(
+            r#"
+            address = "{}"
+            table = "logs"
+            auth.key_id = "admin"
+            auth.private_key = "5UjEMuA0Pj5pjK8a-fa24dyIf-Es5mYny3oE_Wmus48"
+            "#,
+            address
+        ))
+        .unwrap();
+        let key = config.auth.as_ref().unwrap().signing_key().unwrap();
+
+        let listener = TcpListener::bind(address).await.unwrap();
+        let server = tokio::spawn(async move {
+            let (socket, _) = listener.accept().await.unwrap();
+            let mut socket = BufReader::new(socket);
+
+            let mut key_id = String::new();
+            socket.read_line(&mut key_id).await.unwrap();
+            assert_eq!(key_id, "admin\n");
+            socket.write_all(b"challenge\n").await.unwrap();
+
+            let mut signature = String::new();
+            socket.read_line(&mut signature).await.unwrap();
+            let signature = openssl::base64::decode_block(signature.trim_end()).unwrap();
+            let mut verifier = Verifier::new(MessageDigest::sha256(), &key).unwrap();
+            verifier.update(b"challenge").unwrap();
+            assert!(verifier.verify(&signature).unwrap());
+
+            let mut lines = Vec::new();
+            let mut line = String::new();
+            while socket.read_line(&mut line).await.unwrap() > 0 {
+                lines.push(std::mem::take(&mut line));
+            }
+            lines
+        });
+
+        let (sink, _healthcheck) = config.build(SinkContext::default()).await.unwrap();
+        let events = vec![
+            Event::Log(LogEvent::from("hello")),
+            Event::Metric(Metric::new(
+                "uptime",
+                MetricKind::Absolute,
+                MetricValue::Gauge { value: 12.0 },
+            )),
+        ];
+        run_and_assert_sink_compliance(sink, stream::iter(events), &SINK_TAGS).await;
+
+        let lines = server.await.unwrap();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("logs "));
+        assert!(lines[0].contains("message=\"hello\""));
+        assert!(lines[1].starts_with("uptime value=12 "));
+    }
+}
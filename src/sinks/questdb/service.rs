@@ -0,0 +1,307 @@
+//! Service implementation for the `questdb` sink.
+
+use std::{
+    io,
+    net::SocketAddr,
+    num::NonZeroUsize,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
+
+use bytes::{Bytes, BytesMut};
+use openssl::{
+    error::ErrorStack,
+    hash::MessageDigest,
+    pkey::{PKey, Private},
+    sign::Signer,
+};
+use snafu::{OptionExt, ResultExt, Snafu};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufStream},
+    net::TcpStream,
+};
+use vector_lib::tls::{MaybeTlsSettings, MaybeTlsStream, TlsError};
+
+use super::encoder::QuestDbEncoder;
+use crate::{
+    dns::{DnsError, Resolver},
+    sinks::prelude::*,
+};
+
+/// The maximum size of the authentication challenge accepted from the server.
+const MAX_CHALLENGE_SIZE: usize = 1024;
+
+#[derive(Debug, Snafu)]
+pub(super) enum QuestDbError {
+    #[snafu(display("Failed to resolve {}: {}", host, source))]
+    Resolve { host: String, source: DnsError },
+    #[snafu(display("No addresses found for {}", host))]
+    NoAddresses { host: String },
+    #[snafu(display("Failed to connect to {}: {}", address, source))]
+    Connect {
+        address: SocketAddr,
+        source: TlsError,
+    },
+    #[snafu(display("Connection error: {}", source))]
+    Io { source: io::Error },
+    #[snafu(display("Invalid authentication challenge from the server"))]
+    InvalidChallenge,
+    #[snafu(display("Failed to sign the authentication challenge: {}", source))]
+    Sign { source: ErrorStack },
+}
+
+impl From<io::Error> for QuestDbError {
+    fn from(source: io::Error) -> Self {
+        Self::Io { source }
+    }
+}
+
+/// The options of the connections to a server.
+#[derive(Clone)]
+pub(super) struct ConnectionOptions {
+    pub(super) host: String,
+    pub(super) port: u16,
+    pub(super) tls: MaybeTlsSettings,
+    /// The ID of the key and the private key of the authentication, if enabled.
+    pub(super) auth: Option<(String, PKey<Private>)>,
+}
+
+/// A connection to a server, after the authentication.
+pub(super) struct Connection {
+    stream: BufStream<MaybeTlsStream<TcpStream>>,
+}
+
+impl Connection {
+    pub(super) async fn connect(options: &ConnectionOptions) -> Result<Self, QuestDbError> {
+        let host = &options.host;
+        let ip = Resolver
+            .lookup_ip(host.clone())
+            .await
+            .context(ResolveSnafu { host })?
+            .next()
+            .context(NoAddressesSnafu { host })?;
+        let address = SocketAddr::new(ip, options.port);
+        let stream = options
+            .tls
+            .connect(host, &address)
+            .await
+            .context(ConnectSnafu { address })?;
+
+        let mut connection = Self {
+            stream: BufStream::new(stream),
+        };
+        if let Some((key_id, key)) = &options.auth {
+            connection.authenticate(key_id, key).await?;
+        }
+        Ok(connection)
+    }
+
+    /// Authenticates with the challenge-response handshake of the line protocol: the server
+    /// responds to the ID of the key with a challenge, which is signed with the private key.
+    ///
+    /// The server doesn't acknowledge the signature, but closes the connection if it is invalid.
+    async fn authenticate(
+        &mut self,
+        key_id: &str,
+        key: &PKey<Private>,
+    ) -> Result<(), QuestDbError> {
+        self.send(format!("{}\n", key_id).as_bytes()).await?;
+
+        let mut challenge = Vec::new();
+        (&mut self.stream)
+            .take(MAX_CHALLENGE_SIZE as u64)
+            .read_until(b'\n', &mut challenge)
+            .await?;
+        if challenge.pop() != Some(b'\n') {
+            return InvalidChallengeSnafu.fail();
+        }
+
+        let signature = sign_challenge(key, &challenge).context(SignSnafu)?;
+        self.send(format!("{}\n", openssl::base64::encode_block(&signature)).as_bytes())
+            .await
+    }
+
+    /// Whether the server closed the connection, which it does after lines it fails to parse or
+    /// an invalid authentication, as the line protocol has no responses.
+    fn is_closed(&mut self) -> bool {
+        let mut buf = [0; 1];
+        match self.stream.read(&mut buf).now_or_never() {
+            // The read is pending as long as the connection is open.
+            None => false,
+            Some(_) => true,
+        }
+    }
+
+    async fn send(&mut self, data: &[u8]) -> Result<(), QuestDbError> {
+        self.stream.write_all(data).await?;
+        self.stream.flush().await?;
+        Ok(())
+    }
+}
+
+/// Signs the challenge with ECDSA over SHA-256, into a DER-encoded signature.
+fn sign_challenge(key: &PKey<Private>, challenge: &[u8]) -> Result<Vec<u8>, ErrorStack> {
+    let mut signer = Signer::new(MessageDigest::sha256(), key)?;
+    signer.update(challenge)?;
+    signer.sign_to_vec()
+}
+
+#[derive(Clone)]
+pub(super) struct QuestDbRequest {
+    payload: Bytes,
+    finalizers: EventFinalizers,
+    metadata: RequestMetadata,
+}
+
+impl QuestDbRequest {
+    pub(super) fn new(mut events: Vec<Event>, encoder: &QuestDbEncoder) -> Self {
+        let finalizers = events.take_finalizers();
+        let builder = RequestMetadataBuilder::from_events(&events);
+
+        let mut payload = BytesMut::new();
+        for event in events {
+            encoder.encode_event(event, &mut payload);
+        }
+        let metadata = builder
+            .with_request_size(NonZeroUsize::new(payload.len()).unwrap_or(NonZeroUsize::MIN));
+
+        Self {
+            payload: payload.freeze(),
+            finalizers,
+            metadata,
+        }
+    }
+}
+
+impl Finalizable for QuestDbRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        std::mem::take(&mut self.finalizers)
+    }
+}
+
+impl MetaDescriptive for QuestDbRequest {
+    fn get_metadata(&self) -> &RequestMetadata {
+        &self.metadata
+    }
+
+    fn metadata_mut(&mut self) -> &mut RequestMetadata {
+        &mut self.metadata
+    }
+}
+
+#[derive(Debug)]
+pub(super) struct QuestDbResponse {
+    events_byte_size: GroupedCountByteSize,
+    bytes_sent: usize,
+}
+
+impl DriverResponse for QuestDbResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> &GroupedCountByteSize {
+        &self.events_byte_size
+    }
+
+    fn bytes_sent(&self) -> Option<usize> {
+        Some(self.bytes_sent)
+    }
+}
+
+#[derive(Clone, Default)]
+pub(super) struct QuestDbRetryLogic;
+
+impl RetryLogic for QuestDbRetryLogic {
+    type Error = QuestDbError;
+    type Response = QuestDbResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        !matches!(error, QuestDbError::Sign { .. })
+    }
+}
+
+/// Writes the lines of requests over a pool of connections, opened as needed and kept open
+/// between requests.
+#[derive(Clone)]
+pub(super) struct QuestDbService {
+    options: Arc<ConnectionOptions>,
+    connections: Arc<Mutex<Vec<Connection>>>,
+}
+
+impl QuestDbService {
+    pub(super) fn new(options: ConnectionOptions) -> Self {
+        Self {
+            options: Arc::new(options),
+            connections: Arc::default(),
+        }
+    }
+}
+
+impl Service<QuestDbRequest> for QuestDbService {
+    type Response = QuestDbResponse;
+    type Error = QuestDbError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: QuestDbRequest) -> Self::Future {
+        let service = self.clone();
+        let events_byte_size = request
+            .metadata
+            .events_estimated_json_encoded_byte_size()
+            .clone();
+
+        Box::pin(async move {
+            let mut pooled = service.connections.lock().expect("poisoned lock").pop();
+            // Writing to a connection closed by the server would succeed, losing the lines.
+            if pooled.as_mut().is_some_and(Connection::is_closed) {
+                pooled = None;
+            }
+            let mut connection = match pooled {
+                Some(connection) => connection,
+                None => Connection::connect(&service.options).await?,
+            };
+
+            connection.send(&request.payload).await?;
+
+            service
+                .connections
+                .lock()
+                .expect("poisoned lock")
+                .push(connection);
+
+            Ok(QuestDbResponse {
+                events_byte_size,
+                bytes_sent: request.payload.len(),
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use openssl::sign::Verifier;
+
+    use super::*;
+    use crate::sinks::questdb::config::QuestDbAuth;
+
+    #[test]
+    fn signs_challenges() {
+        let auth = QuestDbAuth {
+            key_id: "admin".to_string(),
+            private_key: "5UjEMuA0Pj5pjK8a-fa24dyIf-Es5mYny3oE_Wmus48"
+                .to_string()
+                .into(),
+        };
+        let key = auth.signing_key().unwrap();
+
+        let signature = sign_challenge(&key, b"challenge").unwrap();
+
+        let mut verifier = Verifier::new(MessageDigest::sha256(), &key).unwrap();
+        verifier.update(b"challenge").unwrap();
+        assert!(verifier.verify(&signature).unwrap());
+    }
+}
@@ -0,0 +1,44 @@
+//! Implementation of the `questdb` sink.
+
+use super::{
+    encoder::QuestDbEncoder,
+    service::{QuestDbRequest, QuestDbRetryLogic, QuestDbService},
+};
+use crate::sinks::{
+    influxdb::metrics::InfluxMetricNormalize, prelude::*, util::buffer::metrics::MetricNormalizer,
+};
+
+pub(super) struct QuestDbSink {
+    pub(super) batch_settings: BatcherSettings,
+    pub(super) service: Svc<QuestDbService, QuestDbRetryLogic>,
+    pub(super) encoder: QuestDbEncoder,
+}
+
+impl QuestDbSink {
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let batch_settings = self.batch_settings;
+        let encoder = self.encoder;
+        let mut normalizer = MetricNormalizer::<InfluxMetricNormalize>::default();
+
+        input
+            .filter_map(move |event| {
+                future::ready(match event {
+                    Event::Metric(metric) => normalizer.normalize(metric).map(Event::Metric),
+                    event => Some(event),
+                })
+            })
+            .batched(batch_settings.as_byte_size_config())
+            .map(|events| QuestDbRequest::new(events, &encoder))
+            .into_driver(self.service)
+            .protocol("tcp")
+            .run()
+            .await
+    }
+}
+
+#[async_trait::async_trait]
+impl StreamSink<Event> for QuestDbSink {
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
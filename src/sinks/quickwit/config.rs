@@ -0,0 +1,192 @@
+//! Configuration for the `quickwit` sink.
+
+use http::{Request, StatusCode, Uri};
+use hyper::Body;
+use vector_lib::codecs::{encoding::Framer, JsonSerializerConfig, NewlineDelimitedEncoderConfig};
+
+use super::{
+    request_builder::QuickwitRequestBuilder,
+    service::{QuickwitRetryLogic, QuickwitServiceRequestBuilder},
+    sink::QuickwitSink,
+};
+use crate::{
+    http::{Auth, HttpClient, MaybeAuth},
+    sinks::{
+        prelude::*,
+        util::{http::HttpService, UriSerde},
+    },
+};
+
+/// When the documents ingested by a request are committed, and become searchable.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum CommitPolicy {
+    /// Documents are committed with the rest of the index, every `commit_timeout_secs` of its
+    /// indexing settings, and requests are answered as soon as their documents are persisted.
+    #[default]
+    Auto,
+
+    /// Requests are answered once their documents are committed with the rest of the index.
+    WaitFor,
+
+    /// Documents are committed right away, and requests are answered once they are.
+    ///
+    /// This creates a split for each request, so it should only be used with low volumes of
+    /// events.
+    Force,
+}
+
+impl CommitPolicy {
+    pub(super) const fn as_str(self) -> &'static str {
+        match self {
+            Self::Auto => "auto",
+            Self::WaitFor => "wait_for",
+            Self::Force => "force",
+        }
+    }
+}
+
+/// The default batch settings of the `quickwit` sink.
+///
+/// Quickwit refuses requests whose body exceeds 10 MiB by default, so batches are kept below it.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct QuickwitDefaultBatchSettings;
+
+impl SinkBatchSettings for QuickwitDefaultBatchSettings {
+    const MAX_EVENTS: Option<usize> = None;
+    const MAX_BYTES: Option<usize> = Some(10_000_000);
+    const TIMEOUT_SECS: f64 = 1.0;
+}
+
+/// Configuration for the `quickwit` sink.
+#[configurable_component(sink(
+    "quickwit",
+    "Ingest log events into Quickwit indexes with the ingest API."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct QuickwitConfig {
+    /// The HTTP endpoint of the Quickwit cluster.
+    #[configurable(metadata(docs::examples = "http://localhost:7280"))]
+    pub endpoint: UriSerde,
+
+    /// The ID of the index that events are ingested into.
+    ///
+    /// The index must exist, as indexes aren't created by Vector.
+    #[configurable(metadata(docs::examples = "app-logs"))]
+    #[configurable(metadata(docs::examples = "{{ service }}-logs"))]
+    pub index: Template,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub commit: CommitPolicy,
+
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "crate::serde::is_default")]
+    pub encoding: Transformer,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch: BatchConfig<QuickwitDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    pub auth: Option<Auth>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::is_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+impl GenerateConfig for QuickwitConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"endpoint = "http://localhost:7280"
+            index = "app-logs""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "quickwit")]
+impl SinkConfig for QuickwitConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let endpoint = self.endpoint.with_default_parts().uri;
+        let auth = self.auth.choose_one(&self.endpoint.auth)?;
+
+        let tls_settings = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new(tls_settings, &cx.proxy)?;
+
+        let service_request_builder = QuickwitServiceRequestBuilder {
+            endpoint: endpoint.clone(),
+            commit: self.commit,
+            auth: auth.clone(),
+        };
+
+        let service: HttpService<QuickwitServiceRequestBuilder, String> =
+            HttpService::new(client.clone(), service_request_builder);
+
+        let service = ServiceBuilder::new()
+            .settings(self.request.into_settings(), QuickwitRetryLogic::default())
+            .service(service);
+
+        let sink = QuickwitSink {
+            service,
+            batch_settings: self.batch.validate()?.into_batcher_settings()?,
+            index: self.index.clone(),
+            request_builder: QuickwitRequestBuilder {
+                encoder: (
+                    self.encoding.clone(),
+                    Encoder::<Framer>::new(
+                        NewlineDelimitedEncoderConfig.build().into(),
+                        JsonSerializerConfig::default().build().into(),
+                    ),
+                ),
+            },
+        };
+
+        let healthcheck = healthcheck(client, endpoint, auth).boxed();
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+/// Checks that the cluster is ready to serve requests with its `/health/readyz` endpoint.
+async fn healthcheck(client: HttpClient, endpoint: Uri, auth: Option<Auth>) -> crate::Result<()> {
+    let mut uri = endpoint.to_string();
+    if !uri.ends_with('/') {
+        uri.push('/');
+    }
+    uri.push_str("health/readyz");
+
+    let mut request = Request::get(uri).body(Body::empty())?;
+    if let Some(auth) = auth {
+        auth.apply(&mut request);
+    }
+
+    let response = client.send(request).await?;
+    match response.status() {
+        StatusCode::OK => Ok(()),
+        status => Err(HealthcheckError::UnexpectedStatus { status }.into()),
+    }
+}
@@ -0,0 +1,48 @@
+//! `RequestBuilder` implementation for the `quickwit` sink.
+
+use bytes::Bytes;
+use std::io;
+use vector_lib::codecs::encoding::Framer;
+
+use crate::sinks::{prelude::*, util::http::HttpRequest};
+
+pub(super) struct QuickwitRequestBuilder {
+    pub(super) encoder: (Transformer, Encoder<Framer>),
+}
+
+impl RequestBuilder<(String, Vec<Event>)> for QuickwitRequestBuilder {
+    type Metadata = (String, EventFinalizers);
+    type Events = Vec<Event>;
+    type Encoder = (Transformer, Encoder<Framer>);
+    type Payload = Bytes;
+    type Request = HttpRequest<String>;
+    type Error = io::Error;
+
+    fn compression(&self) -> Compression {
+        Compression::None
+    }
+
+    fn encoder(&self) -> &Self::Encoder {
+        &self.encoder
+    }
+
+    fn split_input(
+        &self,
+        input: (String, Vec<Event>),
+    ) -> (Self::Metadata, RequestMetadataBuilder, Self::Events) {
+        let (index, mut events) = input;
+        let finalizers = events.take_finalizers();
+        let builder = RequestMetadataBuilder::from_events(&events);
+        ((index, finalizers), builder, events)
+    }
+
+    fn build_request(
+        &self,
+        metadata: Self::Metadata,
+        request_metadata: RequestMetadata,
+        payload: EncodeResult<Self::Payload>,
+    ) -> Self::Request {
+        let (index, finalizers) = metadata;
+        HttpRequest::new(payload.into_payload(), finalizers, request_metadata, index)
+    }
+}
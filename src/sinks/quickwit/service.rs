@@ -0,0 +1,135 @@
+//! Service implementation for the `quickwit` sink.
+
+use bytes::Bytes;
+use http::{
+    header::{CONTENT_LENGTH, CONTENT_TYPE},
+    Request, StatusCode, Uri,
+};
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+use snafu::ResultExt;
+
+use super::config::CommitPolicy;
+use crate::{
+    http::{Auth, HttpError},
+    sinks::{
+        prelude::*,
+        util::{
+            http::{HttpRequest, HttpResponse, HttpRetryLogic, HttpServiceRequestBuilder},
+            retries::RetryAction,
+        },
+        HTTPRequestBuilderSnafu, UriParseSnafu,
+    },
+};
+
+#[derive(Debug, Default, Clone)]
+pub(super) struct QuickwitRetryLogic {
+    inner: HttpRetryLogic,
+}
+
+impl RetryLogic for QuickwitRetryLogic {
+    type Error = HttpError;
+    type Response = HttpResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        self.inner.is_retriable_error(error)
+    }
+
+    fn should_retry_response(&self, response: &Self::Response) -> RetryAction {
+        match response.http_response.status() {
+            // Quickwit refuses the requests whose body exceeds its `content_length_limit`, which
+            // fails the same way on every attempt.
+            StatusCode::PAYLOAD_TOO_LARGE => RetryAction::DontRetry(
+                "request exceeds the content length limit of Quickwit, lower `batch.max_bytes`"
+                    .into(),
+            ),
+            _ => self.inner.should_retry_response(&response.http_response),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct QuickwitServiceRequestBuilder {
+    pub(super) endpoint: Uri,
+    pub(super) commit: CommitPolicy,
+    pub(super) auth: Option<Auth>,
+}
+
+impl HttpServiceRequestBuilder<String> for QuickwitServiceRequestBuilder {
+    fn build(&self, mut request: HttpRequest<String>) -> Result<Request<Bytes>, crate::Error> {
+        let uri = ingest_uri(
+            &self.endpoint,
+            request.get_additional_metadata(),
+            self.commit,
+        )?;
+        let payload = request.take_payload();
+
+        let mut builder = Request::post(&uri)
+            .header(CONTENT_TYPE, "application/x-ndjson")
+            .header(CONTENT_LENGTH, payload.len());
+        if let Some(auth) = &self.auth {
+            builder = auth.apply_builder(builder);
+        }
+
+        builder
+            .body(payload)
+            .context(HTTPRequestBuilderSnafu)
+            .map_err(Into::into)
+    }
+}
+
+/// The URI of the ingest API of an index.
+fn ingest_uri(endpoint: &Uri, index: &str, commit: CommitPolicy) -> crate::Result<Uri> {
+    let mut uri = endpoint.to_string();
+    if !uri.ends_with('/') {
+        uri.push('/');
+    }
+    uri.push_str(&format!(
+        "api/v1/{}/ingest?commit={}",
+        utf8_percent_encode(index, NON_ALPHANUMERIC),
+        commit.as_str()
+    ));
+
+    uri.parse::<Uri>()
+        .context(UriParseSnafu)
+        .map_err(Into::into)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ingest_uris() {
+        let endpoint = "http://localhost:7280".parse().unwrap();
+
+        assert_eq!(
+            ingest_uri(&endpoint, "app-logs", CommitPolicy::Auto).unwrap(),
+            "http://localhost:7280/api/v1/app%2Dlogs/ingest?commit=auto"
+        );
+        assert_eq!(
+            ingest_uri(
+                &"http://quickwit:7280/prefix/".parse().unwrap(),
+                "logs",
+                CommitPolicy::WaitFor
+            )
+            .unwrap(),
+            "http://quickwit:7280/prefix/api/v1/logs/ingest?commit=wait_for"
+        );
+    }
+
+    #[test]
+    fn payload_too_large_is_not_retried() {
+        let response = HttpResponse {
+            http_response: http::Response::builder()
+                .status(StatusCode::PAYLOAD_TOO_LARGE)
+                .body(Bytes::new())
+                .unwrap(),
+            events_byte_size: Default::default(),
+            raw_byte_size: 0,
+        };
+
+        assert!(QuickwitRetryLogic::default()
+            .should_retry_response(&response)
+            .is_not_retryable());
+    }
+}
@@ -0,0 +1,91 @@
+//! Implementation of the `quickwit` sink.
+
+use super::request_builder::QuickwitRequestBuilder;
+use crate::sinks::{
+    prelude::*,
+    util::http::{HttpJsonBatchSizer, HttpRequest},
+};
+
+/// Partitions events by the index they are ingested into.
+struct IndexPartitioner {
+    index: Template,
+}
+
+impl Partitioner for IndexPartitioner {
+    type Item = Event;
+    type Key = Option<String>;
+
+    fn partition(&self, item: &Self::Item) -> Self::Key {
+        self.index
+            .render_string(item)
+            .map_err(|error| {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some("index"),
+                    drop_event: true,
+                });
+            })
+            .ok()
+    }
+}
+
+pub(super) struct QuickwitSink<S> {
+    pub(super) service: S,
+    pub(super) batch_settings: BatcherSettings,
+    pub(super) index: Template,
+    pub(super) request_builder: QuickwitRequestBuilder,
+}
+
+impl<S> QuickwitSink<S>
+where
+    S: Service<HttpRequest<String>> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: std::fmt::Debug + Into<crate::Error> + Send,
+{
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let batch_settings = self.batch_settings;
+
+        input
+            // Batch the input stream by index, with size calculation based on the estimated
+            // encoded json size, so that batches stay within the content length limit of Quickwit.
+            .batched_partitioned(IndexPartitioner { index: self.index }, || {
+                batch_settings.as_item_size_config(HttpJsonBatchSizer)
+            })
+            // Drop the events the index couldn't be rendered for.
+            .filter_map(|(index, batch)| async move { index.map(move |index| (index, batch)) })
+            // Build requests with default concurrency limit.
+            .request_builder(
+                default_request_builder_concurrency_limit(),
+                self.request_builder,
+            )
+            // Filter out any errors that occurred in the request building.
+            .filter_map(|request| async move {
+                match request {
+                    Err(error) => {
+                        emit!(SinkRequestBuildError { error });
+                        None
+                    }
+                    Ok(req) => Some(req),
+                }
+            })
+            // Generate the driver that will send requests and handle retries,
+            // event finalization, and logging/internal metric reporting.
+            .into_driver(self.service)
+            .run()
+            .await
+    }
+}
+
+#[async_trait::async_trait]
+impl<S> StreamSink<Event> for QuickwitSink<S>
+where
+    S: Service<HttpRequest<String>> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: std::fmt::Debug + Into<crate::Error> + Send,
+{
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
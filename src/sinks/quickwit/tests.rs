@@ -0,0 +1,131 @@
+//! Unit tests for the `quickwit` sink.
+
+use std::net::SocketAddr;
+
+use futures::{channel::mpsc, stream, StreamExt};
+use http::{Response, StatusCode};
+use hyper::Body;
+use indoc::indoc;
+use serde_json::json;
+use stream_cancel::Trigger;
+use vector_lib::event::{BatchNotifier, BatchStatus};
+
+use super::config::QuickwitConfig;
+use crate::{
+    sinks::{prelude::*, util::test::build_test_server_generic},
+    test_util::{
+        components::{
+            run_and_assert_sink_compliance, run_and_assert_sink_error, COMPONENT_ERROR_TAGS,
+            HTTP_SINK_TAGS,
+        },
+        next_addr, random_events_with_stream,
+    },
+};
+
+/// Runs a Quickwit node that answers ingest requests with the given status.
+fn server(
+    status: StatusCode,
+) -> (
+    SocketAddr,
+    mpsc::Receiver<(http::request::Parts, bytes::Bytes)>,
+    Trigger,
+) {
+    let addr = next_addr();
+    let (rx, trigger, server) = build_test_server_generic(addr, move || {
+        Response::builder()
+            .status(status)
+            .body(Body::from(r#"{"num_docs_for_processing": 1}"#))
+            .unwrap()
+    });
+    tokio::spawn(server);
+    (addr, rx, trigger)
+}
+
+fn event(service: &str, message: &str) -> Event {
+    let mut log = LogEvent::from(message);
+    log.insert("service", service);
+    log.into()
+}
+
+#[test]
+fn generate_config() {
+    crate::test_util::test_generate_config::<QuickwitConfig>();
+}
+
+#[tokio::test]
+async fn ingests_events_into_their_index() {
+    let (addr, rx, _trigger) = server(StatusCode::OK);
+
+    let config: QuickwitConfig = toml::from_str(&format!(
+        indoc! {r#"
+            endpoint = "http://{}"
+            index = "{{{{ service }}}}-logs"
+            commit = "wait_for"
+            encoding.only_fields = ["message"]
+        "#},
+        addr
+    ))
+    .unwrap();
+    let (sink, _healthcheck) = config.build(SinkContext::default()).await.unwrap();
+
+    let events = vec![
+        event("web", "disk full"),
+        event("api", "out of memory"),
+        event("web", "disk still full"),
+    ];
+    run_and_assert_sink_compliance(sink, stream::iter(events), &HTTP_SINK_TAGS).await;
+
+    let mut requests = rx
+        .take(2)
+        .map(|(parts, body)| {
+            assert_eq!(parts.method, "POST");
+            assert_eq!(parts.uri.query(), Some("commit=wait_for"));
+            assert_eq!(parts.headers["content-type"], "application/x-ndjson");
+            let documents = String::from_utf8(body.to_vec())
+                .unwrap()
+                .lines()
+                .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+                .collect::<Vec<_>>();
+            (parts.uri.path().to_string(), documents)
+        })
+        .collect::<Vec<_>>()
+        .await;
+    requests.sort_by(|a, b| a.0.cmp(&b.0));
+
+    assert_eq!(
+        requests,
+        [
+            (
+                "/api/v1/api%2Dlogs/ingest".to_string(),
+                vec![json!({"message": "out of memory"})]
+            ),
+            (
+                "/api/v1/web%2Dlogs/ingest".to_string(),
+                vec![
+                    json!({"message": "disk full"}),
+                    json!({"message": "disk still full"})
+                ]
+            ),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn rejects_events_of_missing_indexes() {
+    let (addr, _rx, _trigger) = server(StatusCode::NOT_FOUND);
+
+    let config: QuickwitConfig = toml::from_str(&format!(
+        indoc! {r#"
+            endpoint = "http://{}"
+            index = "missing"
+        "#},
+        addr
+    ))
+    .unwrap();
+    let (sink, _healthcheck) = config.build(SinkContext::default()).await.unwrap();
+
+    let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+    let (_input, events) = random_events_with_stream(100, 10, Some(batch));
+    run_and_assert_sink_error(sink, events, &COMPONENT_ERROR_TAGS).await;
+    assert_eq!(receiver.try_recv(), Ok(BatchStatus::Rejected));
+}
@@ -1,7 +1,13 @@
-use vector_lib::{event::Event, partition::Partitioner};
+use std::fmt::Write;
+
+use vector_lib::{event::Event, lookup::lookup_v2::OwnedValuePath, partition::Partitioner};
+use vrl::path::PathPrefix;
 
 use crate::{internal_events::TemplateRenderingError, template::Template};
 
+/// The partition of the events without a value for a partition field, as named by Hive.
+const HIVE_DEFAULT_PARTITION: &str = "__HIVE_DEFAULT_PARTITION__";
+
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub struct S3PartitionKey {
     pub key_prefix: String,
@@ -9,14 +15,73 @@ pub struct S3PartitionKey {
 }
 
 /// Partitions items based on the generated key for the given event.
-pub struct S3KeyPartitioner(Template, Option<Template>);
+///
+/// The key prefix is followed by the Hive-style partitions of the partition fields, if any.
+pub struct S3KeyPartitioner(Template, Option<Template>, Vec<OwnedValuePath>);
 
 impl S3KeyPartitioner {
     pub const fn new(
         key_prefix_template: Template,
         ssekms_key_id_template: Option<Template>,
+        partition_fields: Vec<OwnedValuePath>,
     ) -> Self {
-        Self(key_prefix_template, ssekms_key_id_template)
+        Self(
+            key_prefix_template,
+            ssekms_key_id_template,
+            partition_fields,
+        )
+    }
+
+    /// Appends the `field=value/` partitions of the partition fields of the event to the prefix.
+    fn append_partitions(&self, key_prefix: &mut String, item: &Event) {
+        if self.2.is_empty() {
+            return;
+        }
+        if !key_prefix.is_empty() && !key_prefix.ends_with('/') {
+            key_prefix.push('/');
+        }
+
+        for field in &self.2 {
+            let value = item
+                .maybe_as_log()
+                .and_then(|log| log.get((PathPrefix::Event, field)))
+                .filter(|value| !value.is_null())
+                .map(|value| value.to_string_lossy())
+                .filter(|value| !value.is_empty());
+            let value = value.as_deref().unwrap_or(HIVE_DEFAULT_PARTITION);
+
+            escape_hive_path(key_prefix, &field.to_string());
+            key_prefix.push('=');
+            escape_hive_path(key_prefix, value);
+            key_prefix.push('/');
+        }
+    }
+}
+
+/// Escapes the characters of partition names and values which Hive escapes in paths.
+fn escape_hive_path(path: &mut String, value: &str) {
+    for c in value.chars() {
+        match c {
+            '\x00'..='\x1F'
+            | '"'
+            | '#'
+            | '%'
+            | '\''
+            | '*'
+            | '/'
+            | ':'
+            | '='
+            | '?'
+            | '\\'
+            | '\x7F'
+            | '{'
+            | '['
+            | ']'
+            | '^' => {
+                write!(path, "%{:02X}", c as u32).expect("writing to a string");
+            }
+            c => path.push(c),
+        }
     }
 }
 
@@ -25,7 +90,7 @@ impl Partitioner for S3KeyPartitioner {
     type Key = Option<S3PartitionKey>;
 
     fn partition(&self, item: &Self::Item) -> Self::Key {
-        let key_prefix = self
+        let mut key_prefix = self
             .0
             .render_string(item)
             .map_err(|error| {
@@ -36,6 +101,7 @@ impl Partitioner for S3KeyPartitioner {
                 });
             })
             .ok()?;
+        self.append_partitions(&mut key_prefix, item);
         let ssekms_key_id = self
             .1
             .as_ref()
@@ -56,3 +122,34 @@ impl Partitioner for S3KeyPartitioner {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use vector_lib::{event::LogEvent, lookup::owned_value_path};
+
+    use super::*;
+
+    #[test]
+    fn appends_hive_partitions() {
+        let partitioner = S3KeyPartitioner::new(
+            Template::try_from("logs").unwrap(),
+            None,
+            vec![owned_value_path!("region"), owned_value_path!("service")],
+        );
+
+        let mut log = LogEvent::from("hello");
+        log.insert("region", "us-east-1");
+        log.insert("service", "api/v2=beta");
+        assert_eq!(
+            partitioner.partition(&log.into()).unwrap().key_prefix,
+            "logs/region=us-east-1/service=api%2Fv2%3Dbeta/"
+        );
+
+        let mut log = LogEvent::from("hello");
+        log.insert("service", "");
+        assert_eq!(
+            partitioner.partition(&log.into()).unwrap().key_prefix,
+            "logs/region=__HIVE_DEFAULT_PARTITION__/service=__HIVE_DEFAULT_PARTITION__/"
+        );
+    }
+}
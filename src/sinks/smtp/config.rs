@@ -0,0 +1,318 @@
+//! Configuration for the `smtp` sink.
+
+use std::time::Duration;
+
+use lettre::{
+    message::Mailbox,
+    transport::smtp::{
+        authentication::Credentials,
+        client::{Certificate, Tls, TlsParameters},
+    },
+    AsyncSmtpTransport, Tokio1Executor,
+};
+use snafu::ResultExt;
+use vector_lib::sensitive_string::SensitiveString;
+
+use super::{
+    request_builder::{SmtpEncoder, SmtpRequestBuilder},
+    service::{SmtpRetryLogic, SmtpService},
+    sink::SmtpSink,
+    InvalidMailboxSnafu, NoRecipientsSnafu, ReadCaFileSnafu, SmtpBuildError, TlsSettingsSnafu,
+    UnsupportedTlsOptionSnafu,
+};
+use crate::sinks::{prelude::*, util::service::TowerRequestConfigDefaults};
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SmtpDefaultBatchSettings;
+
+impl SinkBatchSettings for SmtpDefaultBatchSettings {
+    const MAX_EVENTS: Option<usize> = Some(100);
+    const MAX_BYTES: Option<usize> = None;
+    const TIMEOUT_SECS: f64 = 10.0;
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct SmtpTowerRequestConfigDefaults;
+
+impl TowerRequestConfigDefaults for SmtpTowerRequestConfigDefaults {
+    const CONCURRENCY: Concurrency = Concurrency::None;
+    const RATE_LIMIT_DURATION_SECS: u64 = 60;
+    const RATE_LIMIT_NUM: u64 = 10;
+}
+
+/// Configuration for the `smtp` sink.
+#[configurable_component(sink(
+    "smtp",
+    "Send log events as email messages through an SMTP server."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SmtpSinkConfig {
+    /// The host of the SMTP server to send messages through.
+    #[configurable(metadata(docs::examples = "smtp.example.com"))]
+    pub host: String,
+
+    /// The port of the SMTP server.
+    ///
+    /// If not set, the port of the submission protocol of the TLS mode is used, which is `25`
+    /// without TLS, `587` with STARTTLS, and `465` with implicit TLS.
+    #[configurable(metadata(docs::examples = 2525))]
+    pub port: Option<u16>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub tls_mode: SmtpTlsMode,
+
+    /// TLS configuration.
+    ///
+    /// Only the `ca_file`, `verify_certificate`, and `verify_hostname` options are supported.
+    pub tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    pub auth: Option<SmtpAuth>,
+
+    /// The mailbox that messages are sent from.
+    #[configurable(metadata(docs::examples = "Vector <vector@example.com>"))]
+    #[configurable(metadata(docs::examples = "alerts@example.com"))]
+    pub from: String,
+
+    /// The mailboxes that messages are sent to.
+    #[configurable(metadata(docs::examples = "oncall@example.com"))]
+    pub to: Vec<String>,
+
+    /// The subject of the messages.
+    ///
+    /// Events are batched by the subject rendered for them, so that each message only holds the
+    /// events that share its subject.
+    #[configurable(metadata(docs::examples = "Critical events on {{ host }}"))]
+    pub subject: Template,
+
+    /// The body rendered for each event.
+    ///
+    /// The body of the messages holds the body rendered for each of their events, one per line.
+    #[serde(default = "default_body")]
+    #[configurable(metadata(docs::examples = "{{ timestamp }} {{ host }}: {{ message }}"))]
+    pub body: Template,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch: BatchConfig<SmtpDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: TowerRequestConfig<SmtpTowerRequestConfigDefaults>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::is_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+/// The TLS mode to connect to the SMTP server with.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SmtpTlsMode {
+    /// Connect without TLS.
+    ///
+    /// Credentials are sent in plain text, so this should only be used with local SMTP servers.
+    None,
+
+    /// Connect without TLS, and upgrade the connection with the `STARTTLS` command.
+    ///
+    /// The server must support `STARTTLS`.
+    #[default]
+    Starttls,
+
+    /// Connect with implicit TLS.
+    Tls,
+}
+
+impl SmtpTlsMode {
+    const fn default_port(self) -> u16 {
+        match self {
+            Self::None => 25,
+            Self::Starttls => 587,
+            Self::Tls => 465,
+        }
+    }
+}
+
+/// The credentials to authenticate to the SMTP server with.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SmtpAuth {
+    /// The username to authenticate with.
+    #[configurable(metadata(docs::examples = "${SMTP_USERNAME}"))]
+    pub username: String,
+
+    /// The password to authenticate with.
+    #[configurable(metadata(docs::examples = "${SMTP_PASSWORD}"))]
+    pub password: SensitiveString,
+}
+
+fn default_body() -> Template {
+    Template::try_from("{{ message }}").unwrap()
+}
+
+impl GenerateConfig for SmtpSinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"host = "smtp.example.com"
+            from = "Vector <vector@example.com>"
+            to = ["oncall@example.com"]
+            subject = "Critical events on {{ host }}""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "smtp")]
+impl SinkConfig for SmtpSinkConfig {
+    async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let from = parse_mailbox("from", &self.from)?;
+        if self.to.is_empty() {
+            return Err(NoRecipientsSnafu.build().into());
+        }
+        let to = self
+            .to
+            .iter()
+            .map(|mailbox| parse_mailbox("to", mailbox))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let request_settings = self.request.into_settings();
+        let transport = self.build_transport(request_settings.timeout)?;
+
+        let healthcheck = healthcheck(transport.clone()).boxed();
+
+        let service = ServiceBuilder::new()
+            .settings(request_settings, SmtpRetryLogic)
+            .service(SmtpService {
+                transport,
+                from,
+                to,
+            });
+
+        let sink = SmtpSink {
+            batch_settings: self.batch.validate()?.into_batcher_settings()?,
+            service,
+            subject: self.subject.clone(),
+            request_builder: SmtpRequestBuilder {
+                encoder: SmtpEncoder {
+                    body: self.body.clone(),
+                },
+            },
+        };
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+impl SmtpSinkConfig {
+    fn build_transport(
+        &self,
+        timeout: Duration,
+    ) -> Result<AsyncSmtpTransport<Tokio1Executor>, SmtpBuildError> {
+        let mut builder = AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&self.host)
+            .port(self.port.unwrap_or_else(|| self.tls_mode.default_port()))
+            .timeout(Some(timeout));
+
+        match self.tls_mode {
+            SmtpTlsMode::None => {}
+            SmtpTlsMode::Starttls => builder = builder.tls(Tls::Required(self.tls_parameters()?)),
+            SmtpTlsMode::Tls => builder = builder.tls(Tls::Wrapper(self.tls_parameters()?)),
+        }
+
+        if let Some(auth) = &self.auth {
+            builder = builder.credentials(Credentials::new(
+                auth.username.clone(),
+                auth.password.inner().to_string(),
+            ));
+        }
+
+        Ok(builder.build())
+    }
+
+    fn tls_parameters(&self) -> Result<TlsParameters, SmtpBuildError> {
+        let mut builder = TlsParameters::builder(self.host.clone());
+        if let Some(tls) = &self.tls {
+            if tls.crt_file.is_some() {
+                return Err(UnsupportedTlsOptionSnafu { option: "crt_file" }.build());
+            }
+            if tls.key_file.is_some() {
+                return Err(UnsupportedTlsOptionSnafu { option: "key_file" }.build());
+            }
+            if tls.alpn_protocols.is_some() {
+                return Err(UnsupportedTlsOptionSnafu {
+                    option: "alpn_protocols",
+                }
+                .build());
+            }
+
+            if let Some(ca_file) = &tls.ca_file {
+                let ca = std::fs::read(ca_file).context(ReadCaFileSnafu)?;
+                let certificate = Certificate::from_pem(&ca)
+                    .or_else(|_| Certificate::from_der(ca))
+                    .context(TlsSettingsSnafu)?;
+                builder = builder.add_root_certificate(certificate);
+            }
+            builder = builder
+                .dangerous_accept_invalid_certs(!tls.verify_certificate.unwrap_or(true))
+                .dangerous_accept_invalid_hostnames(!tls.verify_hostname.unwrap_or(true));
+        }
+        builder.build().context(TlsSettingsSnafu)
+    }
+}
+
+fn parse_mailbox(field: &'static str, mailbox: &str) -> Result<Mailbox, SmtpBuildError> {
+    mailbox.parse().context(InvalidMailboxSnafu {
+        field,
+        mailbox: mailbox.to_string(),
+    })
+}
+
+async fn healthcheck(transport: AsyncSmtpTransport<Tokio1Executor>) -> crate::Result<()> {
+    use lettre::AsyncTransport;
+
+    match transport.test_connection().await? {
+        true => Ok(()),
+        false => Err("The SMTP server didn't respond to the `NOOP` command".into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<SmtpSinkConfig>();
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_mailboxes() {
+        let config: SmtpSinkConfig = toml::from_str(
+            r#"host = "smtp.example.com"
+            from = "Vector <vector@example.com>"
+            to = ["not a mailbox"]
+            subject = "Alert""#,
+        )
+        .unwrap();
+
+        let error = config.build(SinkContext::default()).await.err().unwrap();
+        assert!(error.to_string().starts_with("Invalid `to` mailbox"));
+    }
+}
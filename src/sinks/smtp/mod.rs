@@ -0,0 +1,42 @@
+//! The `smtp` sink.
+//!
+//! This sink sends batches of log events as email messages through an SMTP server, so that
+//! critical events can be sent to people without running a separate alerting stack.
+//!
+//! Events are batched by the subject rendered for them, and each batch is sent as a single plain
+//! text message, whose body holds the body rendered for each of its events, one per line. The
+//! number of messages sent is limited with the rate limit of the request settings, which is low by
+//! default so that bursts of events don't flood the inboxes of the recipients.
+
+use lettre::address::AddressError;
+use snafu::Snafu;
+
+mod config;
+mod request_builder;
+mod service;
+mod sink;
+
+#[cfg(test)]
+mod tests;
+
+pub use self::config::SmtpSinkConfig;
+
+#[derive(Debug, Snafu)]
+enum SmtpBuildError {
+    #[snafu(display("Invalid `{}` mailbox {:?}: {}", field, mailbox, source))]
+    InvalidMailbox {
+        field: &'static str,
+        mailbox: String,
+        source: AddressError,
+    },
+    #[snafu(display("At least one recipient must be set in `to`"))]
+    NoRecipients,
+    #[snafu(display("The `tls.{}` option is not supported by the `smtp` sink", option))]
+    UnsupportedTlsOption { option: &'static str },
+    #[snafu(display("Failed to read the CA certificate file: {}", source))]
+    ReadCaFile { source: std::io::Error },
+    #[snafu(display("Invalid TLS settings: {}", source))]
+    TlsSettings {
+        source: lettre::transport::smtp::Error,
+    },
+}
@@ -0,0 +1,146 @@
+//! `RequestBuilder` implementation for the `smtp` sink.
+
+use std::io;
+
+use bytes::Bytes;
+
+use crate::sinks::prelude::*;
+
+/// Renders the body of each event of a batch, one per line.
+#[derive(Clone)]
+pub(super) struct SmtpEncoder {
+    pub(super) body: Template,
+}
+
+impl encoding::Encoder<Vec<Event>> for SmtpEncoder {
+    fn encode_input(
+        &self,
+        input: Vec<Event>,
+        writer: &mut dyn io::Write,
+    ) -> io::Result<(usize, GroupedCountByteSize)> {
+        let mut byte_size = telemetry().create_request_count_byte_size();
+        let mut body = String::new();
+        for event in &input {
+            byte_size.add_event(event, event.estimated_json_encoded_size_of());
+
+            match self.body.render_string(event) {
+                Ok(line) => {
+                    body.push_str(&line);
+                    body.push('\n');
+                }
+                Err(error) => emit!(TemplateRenderingError {
+                    error,
+                    field: Some("body"),
+                    drop_event: false,
+                }),
+            }
+        }
+
+        write_all(writer, input.len(), body.as_bytes())?;
+
+        Ok((body.len(), byte_size))
+    }
+}
+
+/// A message to send, with the body of its events.
+#[derive(Clone)]
+pub(super) struct SmtpRequest {
+    pub(super) subject: String,
+    pub(super) body: Bytes,
+    pub(super) finalizers: EventFinalizers,
+    pub(super) metadata: RequestMetadata,
+}
+
+impl Finalizable for SmtpRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        std::mem::take(&mut self.finalizers)
+    }
+}
+
+impl MetaDescriptive for SmtpRequest {
+    fn get_metadata(&self) -> &RequestMetadata {
+        &self.metadata
+    }
+
+    fn metadata_mut(&mut self) -> &mut RequestMetadata {
+        &mut self.metadata
+    }
+}
+
+pub(super) struct SmtpRequestBuilder {
+    pub(super) encoder: SmtpEncoder,
+}
+
+impl RequestBuilder<(String, Vec<Event>)> for SmtpRequestBuilder {
+    type Metadata = (String, EventFinalizers);
+    type Events = Vec<Event>;
+    type Encoder = SmtpEncoder;
+    type Payload = Bytes;
+    type Request = SmtpRequest;
+    type Error = io::Error;
+
+    fn compression(&self) -> Compression {
+        Compression::None
+    }
+
+    fn encoder(&self) -> &Self::Encoder {
+        &self.encoder
+    }
+
+    fn split_input(
+        &self,
+        input: (String, Vec<Event>),
+    ) -> (Self::Metadata, RequestMetadataBuilder, Self::Events) {
+        let (subject, mut events) = input;
+
+        let finalizers = events.take_finalizers();
+        let builder = RequestMetadataBuilder::from_events(&events);
+        ((subject, finalizers), builder, events)
+    }
+
+    fn build_request(
+        &self,
+        metadata: Self::Metadata,
+        request_metadata: RequestMetadata,
+        payload: EncodeResult<Self::Payload>,
+    ) -> Self::Request {
+        let (subject, finalizers) = metadata;
+        SmtpRequest {
+            subject,
+            body: payload.into_payload(),
+            finalizers,
+            metadata: request_metadata,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_body_per_event() {
+        let encoder = SmtpEncoder {
+            body: Template::try_from("{{ host }}: {{ message }}").unwrap(),
+        };
+
+        let mut first = LogEvent::from("disk full");
+        first.insert("host", "db-1");
+        let mut second = LogEvent::from("out of memory");
+        second.insert("host", "db-2");
+        // The body of events it can't be rendered for is left out of the message.
+        let third = LogEvent::from("no host");
+
+        let mut output = Vec::new();
+        let (len, _) = encoding::Encoder::encode_input(
+            &encoder,
+            vec![first.into(), second.into(), third.into()],
+            &mut output,
+        )
+        .unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(output, "db-1: disk full\ndb-2: out of memory\n");
+        assert_eq!(len, output.len());
+    }
+}
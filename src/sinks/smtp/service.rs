@@ -0,0 +1,109 @@
+//! Service implementation for the `smtp` sink.
+
+use std::task::{Context, Poll};
+
+use lettre::{
+    message::{header::ContentType, Mailbox, Message},
+    AsyncSmtpTransport, AsyncTransport, Tokio1Executor,
+};
+use snafu::Snafu;
+
+use super::request_builder::SmtpRequest;
+use crate::sinks::prelude::*;
+
+#[derive(Debug, Snafu)]
+pub(super) enum SmtpServiceError {
+    #[snafu(display("Failed to build the message: {}", source))]
+    BuildMessage { source: lettre::error::Error },
+    #[snafu(display("Failed to send the message: {}", source))]
+    SendMessage {
+        source: lettre::transport::smtp::Error,
+    },
+}
+
+#[derive(Clone)]
+pub(super) struct SmtpService {
+    pub(super) transport: AsyncSmtpTransport<Tokio1Executor>,
+    pub(super) from: Mailbox,
+    pub(super) to: Vec<Mailbox>,
+}
+
+impl SmtpService {
+    fn build_message(&self, request: &SmtpRequest) -> Result<Message, lettre::error::Error> {
+        let mut builder = Message::builder()
+            .from(self.from.clone())
+            .subject(request.subject.clone())
+            .header(ContentType::TEXT_PLAIN);
+        for to in &self.to {
+            builder = builder.to(to.clone());
+        }
+        builder.body(String::from_utf8_lossy(&request.body).into_owned())
+    }
+}
+
+pub(super) struct SmtpResponse {
+    events_byte_size: GroupedCountByteSize,
+    byte_size: usize,
+}
+
+impl DriverResponse for SmtpResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> &GroupedCountByteSize {
+        &self.events_byte_size
+    }
+
+    fn bytes_sent(&self) -> Option<usize> {
+        Some(self.byte_size)
+    }
+}
+
+impl Service<SmtpRequest> for SmtpService {
+    type Response = SmtpResponse;
+    type Error = SmtpServiceError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, mut request: SmtpRequest) -> Self::Future {
+        let message = self.build_message(&request);
+        let transport = self.transport.clone();
+        let metadata = std::mem::take(request.metadata_mut());
+
+        Box::pin(async move {
+            let message = message.map_err(|source| SmtpServiceError::BuildMessage { source })?;
+            let byte_size = message.formatted().len();
+
+            transport
+                .send(message)
+                .await
+                .map_err(|source| SmtpServiceError::SendMessage { source })?;
+
+            Ok(SmtpResponse {
+                events_byte_size: metadata.into_events_estimated_json_encoded_byte_size(),
+                byte_size,
+            })
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(super) struct SmtpRetryLogic;
+
+impl RetryLogic for SmtpRetryLogic {
+    type Error = SmtpServiceError;
+    type Response = SmtpResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        match error {
+            SmtpServiceError::BuildMessage { .. } => false,
+            // Permanent errors are the replies of the server with a `5xx` code, such as when the
+            // messages or the credentials are refused.
+            SmtpServiceError::SendMessage { source } => !source.is_permanent(),
+        }
+    }
+}
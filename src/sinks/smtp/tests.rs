@@ -0,0 +1,109 @@
+use std::net::SocketAddr;
+
+use futures::{channel::mpsc, stream, SinkExt, StreamExt};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+    net::TcpListener,
+};
+
+use super::SmtpSinkConfig;
+use crate::{
+    config::{SinkConfig, SinkContext},
+    event::{Event, LogEvent},
+    test_util::{
+        components::{run_and_assert_sink_compliance, SINK_TAGS},
+        next_addr, trace_init,
+    },
+};
+
+/// Runs an SMTP server that accepts all messages, and sends the data of each of them.
+async fn smtp_server(addr: SocketAddr) -> mpsc::Receiver<String> {
+    let listener = TcpListener::bind(addr).await.unwrap();
+    let (tx, rx) = mpsc::channel(10);
+
+    tokio::spawn(async move {
+        loop {
+            let (socket, _) = listener.accept().await.unwrap();
+            let mut tx = tx.clone();
+            tokio::spawn(async move {
+                let (reader, mut writer) = socket.into_split();
+                let mut lines = BufReader::new(reader).lines();
+                writer.write_all(b"220 localhost ESMTP\r\n").await.unwrap();
+
+                while let Some(line) = lines.next_line().await.unwrap() {
+                    let command = line.to_ascii_uppercase();
+                    let reply: &[u8] = if command.starts_with("DATA") {
+                        writer.write_all(b"354 Go ahead\r\n").await.unwrap();
+                        let mut data = String::new();
+                        while let Some(line) = lines.next_line().await.unwrap() {
+                            if line == "." {
+                                break;
+                            }
+                            data.push_str(&line);
+                            data.push('\n');
+                        }
+                        tx.send(data).await.unwrap();
+                        b"250 Accepted\r\n"
+                    } else if command.starts_with("QUIT") {
+                        writer.write_all(b"221 Bye\r\n").await.unwrap();
+                        break;
+                    } else {
+                        b"250 OK\r\n"
+                    };
+                    writer.write_all(reply).await.unwrap();
+                }
+            });
+        }
+    });
+
+    rx
+}
+
+#[tokio::test]
+async fn sends_messages_by_subject() {
+    trace_init();
+
+    let addr = next_addr();
+    let mut rx = smtp_server(addr).await;
+
+    let config: SmtpSinkConfig = toml::from_str(&format!(
+        r#"
+            host = "{}"
+            port = {}
+            tls_mode = "none"
+            from = "Vector <vector@example.com>"
+            to = ["oncall@example.com"]
+            subject = "Critical events on {{{{ host }}}}"
+            body = "{{{{ host }}}}: {{{{ message }}}}"
+        "#,
+        addr.ip(),
+        addr.port()
+    ))
+    .unwrap();
+
+    let (sink, healthcheck) = config.build(SinkContext::default()).await.unwrap();
+    healthcheck.await.expect("Healthcheck failed");
+
+    let events = [
+        ("db-1", "disk full"),
+        ("db-2", "out of memory"),
+        ("db-1", "disk still full"),
+    ]
+    .into_iter()
+    .map(|(host, message)| {
+        let mut log = LogEvent::from(message);
+        log.insert("host", host);
+        Event::Log(log)
+    })
+    .collect::<Vec<_>>();
+    run_and_assert_sink_compliance(sink, stream::iter(events), &SINK_TAGS).await;
+
+    let mut messages = vec![rx.next().await.unwrap(), rx.next().await.unwrap()];
+    messages.sort_by_key(|message| message.contains("db-2"));
+
+    assert!(messages[0].contains("Subject: Critical events on db-1\n"));
+    assert!(messages[0].contains("To: oncall@example.com\n"));
+    assert!(messages[0].contains("\n\ndb-1: disk full\ndb-1: disk still full\n"));
+    assert!(messages[1].contains("Subject: Critical events on db-2\n"));
+    assert!(messages[1].contains("\n\ndb-2: out of memory\n"));
+}
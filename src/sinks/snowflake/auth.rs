@@ -0,0 +1,128 @@
+//! Key-pair authentication, with JSON Web Tokens signed by the private key of the user.
+
+use chrono::Utc;
+use openssl::{
+    hash::{hash, MessageDigest},
+    pkey::{PKey, Private},
+    sign::Signer,
+};
+
+/// The lifetime of the tokens, which Snowflake limits to an hour.
+const TOKEN_LIFETIME_SECS: i64 = 59 * 60;
+
+pub(super) struct KeyPairAuth {
+    /// The qualified name of the user, `{ACCOUNT}.{USER}`.
+    subject: String,
+    /// The fingerprint of the public key, `SHA256:{Base64 digest}`.
+    fingerprint: String,
+    key: PKey<Private>,
+}
+
+impl KeyPairAuth {
+    pub(super) fn new(
+        account: &str,
+        user: &str,
+        private_key: &str,
+        passphrase: Option<&str>,
+    ) -> crate::Result<Self> {
+        let key = match passphrase {
+            Some(passphrase) => {
+                PKey::private_key_from_pem_passphrase(private_key.as_bytes(), passphrase.as_bytes())
+            }
+            None => PKey::private_key_from_pem(private_key.as_bytes()),
+        }
+        .map_err(|error| format!("Invalid private key: {}", error))?;
+        if key.rsa().is_err() {
+            return Err("The private key must be an RSA key.".into());
+        }
+
+        let digest = hash(MessageDigest::sha256(), &key.public_key_to_der()?)?;
+        let fingerprint = format!("SHA256:{}", openssl::base64::encode_block(&digest));
+
+        // The account identifier doesn't include the region or cloud of the account.
+        let account = account.split('.').next().unwrap_or(account).to_uppercase();
+
+        Ok(Self {
+            subject: format!("{}.{}", account, user.to_uppercase()),
+            fingerprint,
+            key,
+        })
+    }
+
+    /// Issues a new token, signed with RS256.
+    pub(super) fn token(&self) -> crate::Result<String> {
+        let now = Utc::now().timestamp();
+        let header = serde_json::json!({ "alg": "RS256", "typ": "JWT" });
+        let claims = serde_json::json!({
+            "iss": format!("{}.{}", self.subject, self.fingerprint),
+            "sub": self.subject,
+            "iat": now,
+            "exp": now + TOKEN_LIFETIME_SECS,
+        });
+
+        let message = format!(
+            "{}.{}",
+            base64_url(header.to_string().as_bytes()),
+            base64_url(claims.to_string().as_bytes())
+        );
+        let mut signer = Signer::new(MessageDigest::sha256(), &self.key)?;
+        signer.update(message.as_bytes())?;
+        let signature = signer.sign_to_vec()?;
+
+        Ok(format!("{}.{}", message, base64_url(&signature)))
+    }
+}
+
+/// Encodes in URL-safe Base64 without padding, as used by JSON Web Tokens.
+fn base64_url(input: &[u8]) -> String {
+    openssl::base64::encode_block(input)
+        .trim_end_matches('=')
+        .replace('+', "-")
+        .replace('/', "_")
+}
+
+#[cfg(test)]
+mod tests {
+    use openssl::{rsa::Rsa, sign::Verifier};
+
+    use super::*;
+
+    fn decode_base64_url(input: &str) -> Vec<u8> {
+        let mut standard = input.replace('-', "+").replace('_', "/");
+        while standard.len() % 4 != 0 {
+            standard.push('=');
+        }
+        openssl::base64::decode_block(&standard).unwrap()
+    }
+
+    #[test]
+    fn issues_signed_tokens() {
+        let key = PKey::from_rsa(Rsa::generate(2048).unwrap()).unwrap();
+        let pem = String::from_utf8(key.private_key_to_pem_pkcs8().unwrap()).unwrap();
+
+        let auth = KeyPairAuth::new("myorg-myaccount.us-east-1", "vector", &pem, None).unwrap();
+        let token = auth.token().unwrap();
+
+        let parts = token.split('.').collect::<Vec<_>>();
+        assert_eq!(parts.len(), 3);
+
+        let claims: serde_json::Value =
+            serde_json::from_slice(&decode_base64_url(parts[1])).unwrap();
+        assert_eq!(claims["sub"], "MYORG-MYACCOUNT.VECTOR");
+        assert!(claims["iss"]
+            .as_str()
+            .unwrap()
+            .starts_with("MYORG-MYACCOUNT.VECTOR.SHA256:"));
+
+        let mut verifier = Verifier::new(MessageDigest::sha256(), &key).unwrap();
+        verifier
+            .update(format!("{}.{}", parts[0], parts[1]).as_bytes())
+            .unwrap();
+        assert!(verifier.verify(&decode_base64_url(parts[2])).unwrap());
+    }
+
+    #[test]
+    fn rejects_invalid_keys() {
+        assert!(KeyPairAuth::new("account", "vector", "not a key", None).is_err());
+    }
+}
@@ -0,0 +1,468 @@
+//! The client of the Snowpipe Streaming REST API, managing the session and the channel that rows
+//! are appended to.
+
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use bytes::Bytes;
+use http::{
+    header::{AUTHORIZATION, CONTENT_TYPE},
+    Request, StatusCode, Uri,
+};
+use hyper::Body;
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+use serde::Deserialize;
+use snafu::{ResultExt, Snafu};
+use tokio::sync::Mutex;
+
+use super::auth::KeyPairAuth;
+use crate::http::{HttpClient, HttpError};
+
+/// The duration that the ingest host and its scoped token are used for, the tokens expiring
+/// after an hour.
+const SESSION_TTL: Duration = Duration::from_secs(50 * 60);
+
+/// The interval between the checks of the committed offset of the channel.
+const COMMIT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Snafu)]
+pub(super) enum SnowflakeError {
+    #[snafu(display("{}", source))]
+    Http { source: HttpError },
+    #[snafu(display("Failed to sign the key-pair token: {}", source))]
+    Token { source: crate::Error },
+    #[snafu(display("Failed to build the request: {}", source))]
+    Build { source: http::Error },
+    #[snafu(display("{} failed with status {}: {}", operation, status, body))]
+    Status {
+        operation: &'static str,
+        status: StatusCode,
+        body: String,
+    },
+    #[snafu(display("Invalid response from Snowflake: {}", source))]
+    Response { source: serde_json::Error },
+    #[snafu(display("Rows at offset {} weren't committed in time", offset))]
+    CommitTimeout { offset: u64 },
+    #[snafu(display(
+        "The channel was reopened before rows at offset {} were committed",
+        offset
+    ))]
+    ChannelReopened { offset: u64 },
+}
+
+impl SnowflakeError {
+    pub(super) fn is_retriable(&self) -> bool {
+        match self {
+            Self::Http { source } => source.is_retriable(),
+            Self::Token { .. } | Self::Build { .. } => false,
+            Self::Status { status, .. } => {
+                status.is_server_error()
+                    || *status == StatusCode::TOO_MANY_REQUESTS
+                    || *status == StatusCode::REQUEST_TIMEOUT
+                    // The scoped token expired, or the continuation token of the channel is
+                    // stale; both are renewed before the retry.
+                    || *status == StatusCode::UNAUTHORIZED
+                    || *status == StatusCode::CONFLICT
+            }
+            Self::Response { .. } | Self::CommitTimeout { .. } | Self::ChannelReopened { .. } => {
+                true
+            }
+        }
+    }
+}
+
+/// The ingest host of the account, and the token scoped to it.
+struct Session {
+    host: String,
+    token: String,
+    created: Instant,
+}
+
+/// The state of the channel.
+#[derive(Default)]
+struct ChannelState {
+    /// The continuation token of the open channel, `None` until the channel is opened.
+    continuation_token: Option<String>,
+    /// The offset token of the next rows appended.
+    ///
+    /// Offset tokens are increasing integers, which aren't reused when the channel is reopened.
+    next_offset: u64,
+}
+
+#[derive(Deserialize)]
+struct OpenChannelResponse {
+    next_continuation_token: String,
+    #[serde(default)]
+    channel_status: ChannelStatus,
+}
+
+#[derive(Default, Deserialize)]
+struct ChannelStatus {
+    #[serde(default)]
+    last_committed_offset_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct AppendRowsResponse {
+    next_continuation_token: String,
+}
+
+#[derive(Deserialize)]
+struct BulkChannelStatusResponse {
+    #[serde(default)]
+    channel_statuses: std::collections::HashMap<String, ChannelStatus>,
+}
+
+impl ChannelStatus {
+    /// Parses the committed offset token, which is absent until rows are committed.
+    ///
+    /// Tokens that aren't integers were committed by other clients, and are ignored.
+    fn committed_offset(&self) -> Option<u64> {
+        self.last_committed_offset_token
+            .as_deref()
+            .and_then(|token| token.parse().ok())
+    }
+}
+
+#[derive(Clone)]
+pub(super) struct SnowpipeClient {
+    client: HttpClient,
+    auth: Arc<KeyPairAuth>,
+    account_url: Uri,
+    /// The path of the pipe, `databases/{database}/schemas/{schema}/pipes/{pipe}`.
+    pipe_path: String,
+    channel: String,
+    session: Arc<Mutex<Option<Session>>>,
+    state: Arc<Mutex<ChannelState>>,
+    /// The number of times that the channel was reopened after failures.
+    ///
+    /// The rows which weren't committed when a channel is reopened are discarded.
+    generation: Arc<AtomicU64>,
+}
+
+/// The rows appended to the channel.
+#[derive(Clone, Copy, Debug)]
+pub(super) struct Appended {
+    generation: u64,
+    offset: u64,
+}
+
+impl SnowpipeClient {
+    pub(super) fn new(
+        client: HttpClient,
+        auth: KeyPairAuth,
+        account_url: Uri,
+        pipe_path: String,
+        channel: String,
+    ) -> Self {
+        Self {
+            client,
+            auth: Arc::new(auth),
+            account_url,
+            pipe_path,
+            channel,
+            session: Arc::default(),
+            state: Arc::default(),
+            generation: Arc::default(),
+        }
+    }
+
+    async fn send(
+        &self,
+        operation: &'static str,
+        request: Request<Body>,
+    ) -> Result<Bytes, SnowflakeError> {
+        let response = self.client.send(request).await.context(HttpSnafu)?;
+        let status = response.status();
+        let body = hyper::body::to_bytes(response.into_body())
+            .await
+            .map_err(|source| SnowflakeError::Http {
+                source: HttpError::CallRequest { source },
+            })?;
+
+        if status.is_success() {
+            Ok(body)
+        } else {
+            StatusSnafu {
+                operation,
+                status,
+                body: String::from_utf8_lossy(&body).into_owned(),
+            }
+            .fail()
+        }
+    }
+
+    fn key_pair_token(&self) -> Result<String, SnowflakeError> {
+        self.auth.token().context(TokenSnafu)
+    }
+
+    /// Gets the ingest host of the account and a token scoped to it, which are cached.
+    async fn session(&self) -> Result<(String, String), SnowflakeError> {
+        let mut session = self.session.lock().await;
+        if let Some(session) = session.as_ref() {
+            if session.created.elapsed() < SESSION_TTL {
+                return Ok((session.host.clone(), session.token.clone()));
+            }
+        }
+
+        let account_url = self.account_url.to_string();
+        let account_url = account_url.trim_end_matches('/');
+        let token = self.key_pair_token()?;
+
+        let request = Request::get(format!("{}/v2/streaming/hostname", account_url))
+            .header(AUTHORIZATION, format!("Bearer {}", token))
+            .header("X-Snowflake-Authorization-Token-Type", "KEYPAIR_JWT")
+            .body(Body::empty())
+            .context(BuildSnafu)?;
+        let host = self.send("Getting the ingest host", request).await?;
+        let host = String::from_utf8_lossy(&host).trim().to_string();
+
+        let form = format!(
+            "grant_type={}&scope={}",
+            utf8_percent_encode(
+                "urn:ietf:params:oauth:grant-type:jwt-bearer",
+                NON_ALPHANUMERIC
+            ),
+            utf8_percent_encode(&host, NON_ALPHANUMERIC)
+        );
+        let request = Request::post(format!("{}/oauth/token", account_url))
+            .header(AUTHORIZATION, format!("Bearer {}", token))
+            .header(CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .body(Body::from(form))
+            .context(BuildSnafu)?;
+        let scoped_token = self.send("Getting a scoped token", request).await?;
+        let scoped_token = String::from_utf8_lossy(&scoped_token).trim().to_string();
+
+        *session = Some(Session {
+            host: host.clone(),
+            token: scoped_token.clone(),
+            created: Instant::now(),
+        });
+        Ok((host, scoped_token))
+    }
+
+    /// Sends a request to the ingest host, under the path of the pipe.
+    async fn send_to_pipe(
+        &self,
+        operation: &'static str,
+        builder: impl FnOnce(String) -> http::request::Builder,
+        path: &str,
+        body: Body,
+    ) -> Result<Bytes, SnowflakeError> {
+        let (host, token) = self.session().await?;
+        let request = builder(format!("https://{}/v2/streaming/{}", host, path))
+            .header(AUTHORIZATION, format!("Bearer {}", token))
+            .header("X-Snowflake-Authorization-Token-Type", "OAUTH")
+            .body(body)
+            .context(BuildSnafu)?;
+
+        let result = self.send(operation, request).await;
+        if let Err(SnowflakeError::Status { status, .. }) = &result {
+            // The scoped token expired, and is renewed by the next request.
+            if *status == StatusCode::UNAUTHORIZED {
+                *self.session.lock().await = None;
+            }
+        }
+        result
+    }
+
+    /// Opens the channel, returning its continuation token and the last offset committed to it.
+    async fn open_channel(&self) -> Result<(String, Option<u64>), SnowflakeError> {
+        let path = format!("{}/channels/{}", self.pipe_path, encode(&self.channel));
+        let body = self
+            .send_to_pipe(
+                "Opening the channel",
+                |uri| Request::put(uri).header(CONTENT_TYPE, "application/json"),
+                &path,
+                Body::from("{}"),
+            )
+            .await?;
+        let response: OpenChannelResponse = serde_json::from_slice(&body).context(ResponseSnafu)?;
+
+        Ok((
+            response.next_continuation_token,
+            response.channel_status.committed_offset(),
+        ))
+    }
+
+    /// Appends the rows to the channel, returning the offset token they were appended at.
+    ///
+    /// The rows are appended in the order that this is called, one batch at a time. The channel
+    /// is reopened for the next batch after failures, as its continuation token may be stale.
+    pub(super) async fn append_rows(&self, rows: Bytes) -> Result<Appended, SnowflakeError> {
+        let mut state = self.state.lock().await;
+        let continuation_token = match state.continuation_token.take() {
+            Some(token) => token,
+            None => {
+                let (token, committed) = self.open_channel().await?;
+                state.next_offset = state
+                    .next_offset
+                    .max(committed.map_or(0, |offset| offset + 1));
+                token
+            }
+        };
+        let offset = state.next_offset;
+
+        let path = format!(
+            "data/{}/channels/{}/rows?continuationToken={}&offsetToken={}",
+            self.pipe_path,
+            encode(&self.channel),
+            encode(&continuation_token),
+            offset
+        );
+        let result = self
+            .send_to_pipe(
+                "Appending rows",
+                |uri| Request::post(uri).header(CONTENT_TYPE, "application/x-ndjson"),
+                &path,
+                Body::from(rows),
+            )
+            .await
+            .and_then(|body| {
+                serde_json::from_slice::<AppendRowsResponse>(&body).context(ResponseSnafu)
+            });
+
+        match result {
+            Ok(response) => {
+                state.continuation_token = Some(response.next_continuation_token);
+                state.next_offset += 1;
+                Ok(Appended {
+                    generation: self.generation.load(Ordering::Acquire),
+                    offset,
+                })
+            }
+            Err(error) => {
+                // The channel is reopened by the next append, which discards the rows that
+                // weren't committed yet.
+                self.generation.fetch_add(1, Ordering::AcqRel);
+                Err(error)
+            }
+        }
+    }
+
+    /// Gets the last offset token committed to the channel.
+    pub(super) async fn committed_offset(&self) -> Result<Option<u64>, SnowflakeError> {
+        let path = format!("{}:bulk-channel-status", self.pipe_path);
+        let body = serde_json::json!({ "channel_names": [self.channel] }).to_string();
+        let body = self
+            .send_to_pipe(
+                "Getting the status of the channel",
+                |uri| Request::post(uri).header(CONTENT_TYPE, "application/json"),
+                &path,
+                Body::from(body),
+            )
+            .await?;
+        let response: BulkChannelStatusResponse =
+            serde_json::from_slice(&body).context(ResponseSnafu)?;
+
+        Ok(response
+            .channel_statuses
+            .get(&self.channel)
+            .and_then(ChannelStatus::committed_offset))
+    }
+
+    /// Waits until the appended rows are committed to the table.
+    ///
+    /// Fails if the channel is reopened first, as the rows may have been discarded.
+    pub(super) async fn wait_for_commit(
+        &self,
+        appended: Appended,
+        timeout: Duration,
+    ) -> Result<(), SnowflakeError> {
+        let Appended { generation, offset } = appended;
+        let deadline = Instant::now() + timeout;
+        loop {
+            let committed = self.committed_offset().await?;
+            // The generation is checked after the committed offset is read, as the offsets of
+            // the rows appended after the channel is reopened are greater.
+            if self.generation.load(Ordering::Acquire) != generation {
+                return ChannelReopenedSnafu { offset }.fail();
+            }
+            if committed.is_some_and(|committed| committed >= offset) {
+                return Ok(());
+            }
+            if Instant::now() >= deadline {
+                return CommitTimeoutSnafu { offset }.fail();
+            }
+            tokio::time::sleep(COMMIT_POLL_INTERVAL).await;
+        }
+    }
+
+    /// Checks that the account can be authenticated with, and that the pipe exists.
+    pub(super) async fn healthcheck(&self) -> Result<(), SnowflakeError> {
+        self.committed_offset().await.map(|_| ())
+    }
+}
+
+fn encode(value: &str) -> percent_encoding::PercentEncode<'_> {
+    utf8_percent_encode(value, NON_ALPHANUMERIC)
+}
+
+/// Builds the path of the pipe, `databases/{database}/schemas/{schema}/pipes/{pipe}`.
+pub(super) fn pipe_path(database: &str, schema: &str, pipe: &str) -> String {
+    format!(
+        "databases/{}/schemas/{}/pipes/{}",
+        encode(database),
+        encode(schema),
+        encode(pipe)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_pipe_paths() {
+        assert_eq!(
+            pipe_path("LOGS", "PUBLIC", "EVENTS-STREAMING"),
+            "databases/LOGS/schemas/PUBLIC/pipes/EVENTS%2DSTREAMING"
+        );
+    }
+
+    #[test]
+    fn parses_committed_offsets() {
+        let response: OpenChannelResponse = serde_json::from_str(
+            r#"{
+                "next_continuation_token": "token",
+                "channel_status": {
+                    "channel_status_code": "SUCCESS",
+                    "last_committed_offset_token": "41"
+                }
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(response.next_continuation_token, "token");
+        assert_eq!(response.channel_status.committed_offset(), Some(41));
+
+        let response: BulkChannelStatusResponse = serde_json::from_str(
+            r#"{
+                "channel_statuses": {
+                    "vector": { "last_committed_offset_token": null },
+                    "other": { "last_committed_offset_token": "not-an-integer" }
+                }
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(response.channel_statuses["vector"].committed_offset(), None);
+        assert_eq!(response.channel_statuses["other"].committed_offset(), None);
+    }
+
+    #[test]
+    fn retries_transient_errors() {
+        let status = |status| SnowflakeError::Status {
+            operation: "Appending rows",
+            status,
+            body: String::new(),
+        };
+        assert!(status(StatusCode::SERVICE_UNAVAILABLE).is_retriable());
+        assert!(status(StatusCode::UNAUTHORIZED).is_retriable());
+        assert!(!status(StatusCode::BAD_REQUEST).is_retriable());
+        assert!(SnowflakeError::CommitTimeout { offset: 1 }.is_retriable());
+    }
+}
@@ -0,0 +1,266 @@
+use std::time::Duration;
+
+use http::Uri;
+use serde_with::serde_as;
+use vector_lib::{
+    codecs::{encoding::Framer, JsonSerializerConfig, NewlineDelimitedEncoderConfig},
+    sensitive_string::SensitiveString,
+};
+
+use super::{
+    auth::KeyPairAuth,
+    client::{pipe_path, SnowpipeClient},
+    request_builder::SnowflakeRequestBuilder,
+    service::{SnowflakeRetryLogic, SnowflakeService},
+    sink::SnowflakeSink,
+};
+use crate::{http::HttpClient, sinks::prelude::*};
+
+/// The maximum size of the rows appended by a request, before compression.
+const MAX_APPEND_BYTES: usize = 16 * 1024 * 1024;
+
+const fn default_commit_timeout_secs() -> Duration {
+    Duration::from_secs(60)
+}
+
+fn default_channel() -> String {
+    "vector".to_string()
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SnowflakeDefaultBatchSettings;
+
+impl SinkBatchSettings for SnowflakeDefaultBatchSettings {
+    const MAX_EVENTS: Option<usize> = None;
+    const MAX_BYTES: Option<usize> = Some(4_000_000);
+    const TIMEOUT_SECS: f64 = 1.0;
+}
+
+/// Configuration for the `snowflake` sink.
+#[serde_as]
+#[configurable_component(sink(
+    "snowflake",
+    "Stream log events into Snowflake tables with Snowpipe Streaming."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SnowflakeConfig {
+    /// The [identifier][account_identifier] of the Snowflake account.
+    ///
+    /// [account_identifier]: https://docs.snowflake.com/en/user-guide/admin-account-identifier
+    #[configurable(metadata(docs::examples = "myorg-myaccount"))]
+    pub account: String,
+
+    /// The URL of the Snowflake account.
+    ///
+    /// Defaults to `https://{account}.snowflakecomputing.com`.
+    #[configurable(metadata(docs::examples = "https://myorg-myaccount.snowflakecomputing.com"))]
+    pub account_url: Option<String>,
+
+    /// The name of the user that the sink authenticates as.
+    ///
+    /// A public key of the user must be the public key of `private_key`.
+    #[configurable(metadata(docs::examples = "VECTOR"))]
+    pub user: String,
+
+    /// The private key of the user, in PEM format, used for [key-pair authentication][key_pair].
+    ///
+    /// Only RSA keys are supported.
+    ///
+    /// [key_pair]: https://docs.snowflake.com/en/user-guide/key-pair-auth
+    #[configurable(metadata(docs::examples = "${SNOWFLAKE_PRIVATE_KEY}"))]
+    pub private_key: SensitiveString,
+
+    /// The passphrase of the private key, if it is encrypted.
+    #[configurable(metadata(docs::examples = "${SNOWFLAKE_PRIVATE_KEY_PASSPHRASE}"))]
+    pub private_key_passphrase: Option<SensitiveString>,
+
+    /// The database of the table.
+    #[configurable(metadata(docs::examples = "LOGS"))]
+    pub database: String,
+
+    /// The schema of the table.
+    #[configurable(metadata(docs::examples = "PUBLIC"))]
+    pub schema: String,
+
+    /// The table that events are streamed into.
+    #[configurable(metadata(docs::examples = "EVENTS"))]
+    pub table: String,
+
+    /// The pipe that events are streamed through.
+    ///
+    /// Defaults to the default pipe of the table, `{table}-STREAMING`, which maps the fields of
+    /// the events to the columns of the same names.
+    #[configurable(metadata(docs::examples = "EVENTS_PIPE"))]
+    pub pipe: Option<String>,
+
+    /// The name of the channel that events are streamed through.
+    ///
+    /// Rows are streamed through a channel in order, and a channel can only be opened by a single
+    /// client at once, so each Vector instance streaming into the same pipe must use a different
+    /// channel.
+    #[configurable(metadata(docs::examples = "vector-${HOSTNAME}"))]
+    #[serde(default = "default_channel")]
+    pub channel: String,
+
+    /// The maximum time to wait for appended rows to be committed to the table.
+    ///
+    /// Events are only acknowledged once the rows they were appended as are committed, and are
+    /// retried if they aren't committed in time.
+    #[serde(default = "default_commit_timeout_secs")]
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    #[configurable(metadata(docs::human_name = "Commit Timeout"))]
+    pub commit_timeout_secs: Duration,
+
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "crate::serde::is_default")]
+    pub encoding: Transformer,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch: BatchConfig<SnowflakeDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::is_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+impl GenerateConfig for SnowflakeConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"account = "myorg-myaccount"
+            user = "VECTOR"
+            private_key = "${SNOWFLAKE_PRIVATE_KEY}"
+            database = "LOGS"
+            schema = "PUBLIC"
+            table = "EVENTS""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "snowflake")]
+impl SinkConfig for SnowflakeConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let batch_settings = self
+            .batch
+            .validate()?
+            .limit_max_bytes(MAX_APPEND_BYTES)?
+            .into_batcher_settings()?;
+
+        let auth = KeyPairAuth::new(
+            &self.account,
+            &self.user,
+            self.private_key.inner(),
+            self.private_key_passphrase
+                .as_ref()
+                .map(|passphrase| passphrase.inner()),
+        )?;
+
+        let tls_settings = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new(tls_settings, &cx.proxy)?;
+        let snowpipe = SnowpipeClient::new(
+            client,
+            auth,
+            self.account_url()?,
+            pipe_path(&self.database, &self.schema, &self.pipe()),
+            self.channel.clone(),
+        );
+
+        let healthcheck = {
+            let snowpipe = snowpipe.clone();
+            Box::pin(async move { snowpipe.healthcheck().await.map_err(Into::into) })
+        };
+
+        // Rows are appended to the channel one request at a time, to keep their offset tokens in
+        // order, while the commits of several requests are awaited at once.
+        let service = ServiceBuilder::new()
+            .settings(self.request.into_settings(), SnowflakeRetryLogic)
+            .service(SnowflakeService::new(snowpipe, self.commit_timeout_secs));
+
+        let request_builder = SnowflakeRequestBuilder {
+            encoder: (
+                self.encoding.clone(),
+                Encoder::<Framer>::new(
+                    NewlineDelimitedEncoderConfig.build().into(),
+                    JsonSerializerConfig::default().build().into(),
+                ),
+            ),
+        };
+
+        let sink = SnowflakeSink {
+            batch_settings,
+            service,
+            request_builder,
+        };
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+impl SnowflakeConfig {
+    fn account_url(&self) -> crate::Result<Uri> {
+        match &self.account_url {
+            Some(url) => Ok(url.parse()?),
+            None => Ok(format!("https://{}.snowflakecomputing.com", self.account).parse()?),
+        }
+    }
+
+    fn pipe(&self) -> String {
+        self.pipe
+            .clone()
+            .unwrap_or_else(|| format!("{}-STREAMING", self.table))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<SnowflakeConfig>();
+    }
+
+    #[test]
+    fn defaults_to_the_default_pipe() {
+        let config: SnowflakeConfig = toml::from_str(
+            r#"
+            account = "myorg-myaccount"
+            user = "VECTOR"
+            private_key = "key"
+            database = "LOGS"
+            schema = "PUBLIC"
+            table = "EVENTS"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.pipe(), "EVENTS-STREAMING");
+        assert_eq!(config.channel, "vector");
+        assert_eq!(config.commit_timeout_secs, Duration::from_secs(60));
+        assert_eq!(
+            config.account_url().unwrap().to_string(),
+            "https://myorg-myaccount.snowflakecomputing.com/"
+        );
+    }
+}
@@ -0,0 +1,22 @@
+//! The `snowflake` sink.
+//!
+//! This sink streams log events into [Snowflake][snowflake] tables with the REST API of
+//! [Snowpipe Streaming][snowpipe_streaming], authenticating with key-pair authentication.
+//!
+//! Batches of events are appended as rows to a channel of the pipe of the table, each batch with
+//! the next of a sequence of offset tokens. The events of a batch are acknowledged once the
+//! committed offset token of the channel reaches the offset token of the batch. The channel is
+//! reopened after failures, which discards the rows that weren't committed yet, so the batches
+//! still waiting for their commit are retried.
+//!
+//! [snowflake]: https://www.snowflake.com/
+//! [snowpipe_streaming]: https://docs.snowflake.com/en/user-guide/snowpipe-streaming/snowpipe-streaming-high-performance-overview
+
+mod auth;
+mod client;
+mod config;
+mod request_builder;
+mod service;
+mod sink;
+
+pub use self::config::SnowflakeConfig;
@@ -0,0 +1,50 @@
+//! `RequestBuilder` implementation for the `snowflake` sink.
+
+use bytes::Bytes;
+use vector_lib::codecs::encoding::Framer;
+
+use super::service::SnowflakeRequest;
+use crate::sinks::prelude::*;
+
+pub(super) struct SnowflakeRequestBuilder {
+    pub(super) encoder: (Transformer, Encoder<Framer>),
+}
+
+impl RequestBuilder<Vec<Event>> for SnowflakeRequestBuilder {
+    type Metadata = EventFinalizers;
+    type Events = Vec<Event>;
+    type Encoder = (Transformer, Encoder<Framer>);
+    type Payload = Bytes;
+    type Request = SnowflakeRequest;
+    type Error = std::io::Error;
+
+    fn compression(&self) -> Compression {
+        Compression::None
+    }
+
+    fn encoder(&self) -> &Self::Encoder {
+        &self.encoder
+    }
+
+    fn split_input(
+        &self,
+        mut events: Vec<Event>,
+    ) -> (Self::Metadata, RequestMetadataBuilder, Self::Events) {
+        let finalizers = events.take_finalizers();
+        let builder = RequestMetadataBuilder::from_events(&events);
+        (finalizers, builder, events)
+    }
+
+    fn build_request(
+        &self,
+        finalizers: Self::Metadata,
+        request_metadata: RequestMetadata,
+        payload: EncodeResult<Self::Payload>,
+    ) -> Self::Request {
+        SnowflakeRequest {
+            body: payload.into_payload(),
+            finalizers,
+            metadata: request_metadata,
+        }
+    }
+}
@@ -0,0 +1,116 @@
+//! Service implementation for the `snowflake` sink.
+
+use std::{
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use bytes::Bytes;
+
+use super::client::{SnowflakeError, SnowpipeClient};
+use crate::sinks::prelude::*;
+
+#[derive(Clone)]
+pub(super) struct SnowflakeRequest {
+    /// The rows, as newline-delimited JSON objects.
+    pub(super) body: Bytes,
+    pub(super) finalizers: EventFinalizers,
+    pub(super) metadata: RequestMetadata,
+}
+
+impl Finalizable for SnowflakeRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        std::mem::take(&mut self.finalizers)
+    }
+}
+
+impl MetaDescriptive for SnowflakeRequest {
+    fn get_metadata(&self) -> &RequestMetadata {
+        &self.metadata
+    }
+
+    fn metadata_mut(&mut self) -> &mut RequestMetadata {
+        &mut self.metadata
+    }
+}
+
+#[derive(Debug)]
+pub(super) struct SnowflakeResponse {
+    events_byte_size: GroupedCountByteSize,
+    byte_size: usize,
+}
+
+impl DriverResponse for SnowflakeResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> &GroupedCountByteSize {
+        &self.events_byte_size
+    }
+
+    fn bytes_sent(&self) -> Option<usize> {
+        Some(self.byte_size)
+    }
+}
+
+#[derive(Clone, Default)]
+pub(super) struct SnowflakeRetryLogic;
+
+impl RetryLogic for SnowflakeRetryLogic {
+    type Error = SnowflakeError;
+    type Response = SnowflakeResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        error.is_retriable()
+    }
+}
+
+/// Appends the rows of requests to the channel.
+///
+/// Requests are completed once their rows are committed to the table, so that the events are
+/// only acknowledged once they are durably stored.
+#[derive(Clone)]
+pub(super) struct SnowflakeService {
+    snowpipe: SnowpipeClient,
+    commit_timeout: Duration,
+}
+
+impl SnowflakeService {
+    pub(super) const fn new(snowpipe: SnowpipeClient, commit_timeout: Duration) -> Self {
+        Self {
+            snowpipe,
+            commit_timeout,
+        }
+    }
+}
+
+impl Service<SnowflakeRequest> for SnowflakeService {
+    type Response = SnowflakeResponse;
+    type Error = SnowflakeError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: SnowflakeRequest) -> Self::Future {
+        let service = self.clone();
+
+        Box::pin(async move {
+            let byte_size = request.body.len();
+            let appended = service.snowpipe.append_rows(request.body).await?;
+            service
+                .snowpipe
+                .wait_for_commit(appended, service.commit_timeout)
+                .await?;
+
+            Ok(SnowflakeResponse {
+                events_byte_size: request
+                    .metadata
+                    .into_events_estimated_json_encoded_byte_size(),
+                byte_size,
+            })
+        })
+    }
+}
@@ -0,0 +1,44 @@
+//! Implementation of the `snowflake` sink.
+
+use super::{
+    request_builder::SnowflakeRequestBuilder,
+    service::{SnowflakeRetryLogic, SnowflakeService},
+};
+use crate::sinks::prelude::*;
+
+pub(super) struct SnowflakeSink {
+    pub(super) batch_settings: BatcherSettings,
+    pub(super) service: Svc<SnowflakeService, SnowflakeRetryLogic>,
+    pub(super) request_builder: SnowflakeRequestBuilder,
+}
+
+impl SnowflakeSink {
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        input
+            .batched(self.batch_settings.as_byte_size_config())
+            .request_builder(
+                default_request_builder_concurrency_limit(),
+                self.request_builder,
+            )
+            .filter_map(|request| async {
+                match request {
+                    Err(error) => {
+                        emit!(SinkRequestBuildError { error });
+                        None
+                    }
+                    Ok(request) => Some(request),
+                }
+            })
+            .into_driver(self.service)
+            .protocol("https")
+            .run()
+            .await
+    }
+}
+
+#[async_trait::async_trait]
+impl StreamSink<Event> for SnowflakeSink {
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
@@ -1,3 +1,5 @@
+#[cfg(unix)]
+use vector_lib::codecs::BytesEncoder;
 use vector_lib::codecs::{
     encoding::{Framer, FramingConfig},
     TextSerializerConfig,
@@ -5,7 +7,7 @@ use vector_lib::codecs::{
 use vector_lib::configurable::configurable_component;
 
 #[cfg(unix)]
-use crate::sinks::util::unix::UnixSinkConfig;
+use crate::sinks::util::{service::net::UnixMode as UnixSocketMode, unix::UnixSinkConfig};
 use crate::{
     codecs::{Encoder, EncodingConfig, EncodingConfigWithFraming, SinkType},
     config::{AcknowledgementsConfig, DataType, GenerateConfig, Input, SinkConfig, SinkContext},
@@ -136,6 +138,11 @@ impl SinkConfig for SocketSinkConfig {
             Mode::Unix(UnixMode { config, encoding }) => {
                 let transformer = encoding.transformer();
                 let (framer, serializer) = encoding.build(SinkType::StreamBased)?;
+                let framer = match (config.unix_mode, encoding.config().0) {
+                    // Each event is sent in its own datagram, so it needs no framing by default.
+                    (UnixSocketMode::Datagram, None) => BytesEncoder::new().into(),
+                    _ => framer,
+                };
                 let encoder = Encoder::<Framer>::new(framer, serializer);
                 config.build(transformer, encoder)
             }
@@ -181,7 +188,8 @@ mod test {
         event::{Event, LogEvent},
         test_util::{
             components::{assert_sink_compliance, run_and_assert_sink_compliance, SINK_TAGS},
-            next_addr, next_addr_v6, random_lines_with_stream, trace_init, CountReceiver,
+            next_addr, next_addr_v6, random_lines_with_stream, random_string, trace_init,
+            CountReceiver,
         },
     };
 
@@ -238,6 +246,66 @@ mod test {
         test_udp(next_addr_v6()).await;
     }
 
+    #[tokio::test]
+    async fn udp_gelf_chunking() {
+        use tokio_util::codec::Decoder as _;
+        use vector_lib::codecs::decoding::ChunkedGelfDecoderConfig;
+
+        trace_init();
+
+        let addr = next_addr();
+        let receiver = UdpSocket::bind(addr).unwrap();
+
+        let config = toml::from_str::<SocketSinkConfig>(&format!(
+            r#"
+            mode = "udp"
+            address = "{}"
+            encoding.codec = "gelf"
+            chunking.method = "gelf"
+            chunking.gelf.max_chunk_size = 64
+            chunking.gelf.compression = "gzip"
+            "#,
+            addr
+        ))
+        .unwrap();
+
+        let mut event = LogEvent::default();
+        event.insert("host", "example.org");
+        event.insert("short_message", "a".repeat(200));
+        event.insert("_random", random_string(100));
+
+        let context = SinkContext::default();
+        assert_sink_compliance(&SINK_TAGS, async move {
+            let (sink, _healthcheck) = config.build(context).await.unwrap();
+            sink.run(stream::once(ready(Event::Log(event).into())))
+                .await
+        })
+        .await
+        .expect("Running sink failed");
+
+        let mut decoder = ChunkedGelfDecoderConfig::default().build();
+        let mut chunks = 0;
+        let message = loop {
+            let mut buf = [0; 256];
+            let (size, _src_addr) = receiver
+                .recv_from(&mut buf)
+                .expect("Did not receive message");
+            assert!(size <= 64);
+            chunks += 1;
+            if let Some(message) = decoder
+                .decode_eof(&mut bytes::BytesMut::from(&buf[..size]))
+                .unwrap()
+            {
+                break message;
+            }
+        };
+        assert!(chunks > 1);
+
+        let data = serde_json::from_slice::<Value>(&message).expect("Invalid JSON received");
+        assert_eq!(data["short_message"], Value::String("a".repeat(200)));
+        assert_eq!(data["host"], Value::String("example.org".into()));
+    }
+
     #[tokio::test]
     async fn tcp_stream() {
         trace_init();
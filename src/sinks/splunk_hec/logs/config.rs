@@ -210,6 +210,10 @@ impl SinkConfig for HecLogsSinkConfig {
     fn acknowledgements(&self) -> &AcknowledgementsConfig {
         &self.acknowledgements.inner
     }
+
+    fn supports_request_limits(&self) -> bool {
+        true
+    }
 }
 
 impl HecLogsSinkConfig {
@@ -237,7 +241,10 @@ impl HecLogsSinkConfig {
             compression: self.compression,
         };
 
-        let request_settings = self.request.into_settings();
+        let request_settings = self
+            .request
+            .into_settings()
+            .with_request_limits(cx.request_limits.clone());
         let http_request_builder = Arc::new(HttpRequestBuilder::new(
             self.endpoint.clone(),
             self.endpoint_target,
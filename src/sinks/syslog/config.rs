@@ -0,0 +1,214 @@
+//! Configuration for the `syslog` sink.
+
+use vector_lib::codecs::{encoding::SyslogRfc, TextSerializerConfig};
+use vector_lib::configurable::configurable_component;
+use vector_lib::lookup::lookup_v2::ConfigValuePath;
+use vrl::value::Kind;
+
+use super::encoder::SyslogEncoder;
+#[cfg(unix)]
+use crate::sinks::util::{service::net::UnixMode, unix::UnixSinkConfig};
+use crate::{
+    codecs::{Encoder, EncodingConfig, Transformer},
+    config::{AcknowledgementsConfig, DataType, GenerateConfig, Input, SinkConfig, SinkContext},
+    schema,
+    sinks::{
+        util::{tcp::TcpSinkConfig, udp::UdpSinkConfig},
+        Healthcheck, VectorSink,
+    },
+    template::Template,
+};
+
+/// Configuration for the `syslog` sink.
+#[configurable_component(sink("syslog", "Deliver log events to a syslog server."))]
+#[derive(Clone, Debug)]
+pub struct SyslogSinkConfig {
+    #[serde(flatten)]
+    pub mode: Mode,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub rfc: SyslogRfc,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub framing: SyslogFraming,
+
+    /// The facility of the messages.
+    ///
+    /// This can be the name of a facility, such as `local0`, or its numerical code, such as `16`.
+    /// If the facility of an event can't be rendered or isn't valid, `user` is used instead.
+    #[serde(default = "default_facility")]
+    #[configurable(metadata(docs::examples = "local0"))]
+    #[configurable(metadata(docs::examples = "{{ facility }}"))]
+    pub facility: Template,
+
+    /// The severity of the messages.
+    ///
+    /// This can be the name of a severity, such as `err` or `error`, or its numerical code, such
+    /// as `3`. If the severity of an event can't be rendered or isn't valid, `info` is used
+    /// instead.
+    #[serde(default = "default_severity")]
+    #[configurable(metadata(docs::examples = "warning"))]
+    #[configurable(metadata(docs::examples = "{{ severity }}"))]
+    pub severity: Template,
+
+    /// The hostname of the messages.
+    ///
+    /// If not set, the host of the events is used, or the hostname of the machine that Vector runs
+    /// on for the events that have no host.
+    #[configurable(metadata(docs::examples = "{{ hostname }}"))]
+    pub hostname: Option<Template>,
+
+    /// The name of the application that the messages come from.
+    ///
+    /// This is written as the `APP-NAME` of RFC 5424 messages, and as the `TAG` of RFC 3164
+    /// messages.
+    #[serde(default = "default_app_name")]
+    #[configurable(metadata(docs::examples = "{{ appname }}"))]
+    pub app_name: Template,
+
+    /// The ID of the process that the messages come from.
+    #[configurable(metadata(docs::examples = "{{ procid }}"))]
+    pub proc_id: Option<Template>,
+
+    /// The type of the messages.
+    ///
+    /// This is only written to RFC 5424 messages.
+    #[configurable(metadata(docs::examples = "{{ msgid }}"))]
+    #[configurable(metadata(docs::examples = "ID47"))]
+    pub msg_id: Option<Template>,
+
+    /// The field of the log events that holds the structured data of the messages.
+    ///
+    /// The field must be an object of structured data elements, keyed by their ID, that are each
+    /// an object of parameters. Arrays of values are written as repeated parameters. The field is
+    /// removed from the events, and is only written to RFC 5424 messages.
+    #[configurable(metadata(docs::examples = "structured_data"))]
+    pub structured_data_key: Option<ConfigValuePath>,
+
+    /// The encoding of the body of the messages.
+    ///
+    /// By default, the message of the events is written as the body of the messages.
+    #[serde(default = "default_encoding")]
+    pub encoding: EncodingConfig,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::is_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+/// Socket mode.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+#[configurable(metadata(docs::enum_tag_description = "The type of socket to use."))]
+pub enum Mode {
+    /// Send over TCP.
+    Tcp(TcpSinkConfig),
+
+    /// Send over UDP.
+    Udp(UdpSinkConfig),
+
+    /// Send over a Unix domain socket (UDS).
+    #[cfg(unix)]
+    Unix(UnixSinkConfig),
+}
+
+/// The framing of the messages sent over stream sockets.
+///
+/// Messages sent over UDP and Unix datagram sockets are sent in their own datagram, and aren't
+/// framed.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SyslogFraming {
+    /// Each message is prefixed with its length in bytes and a space, as described in
+    /// [RFC 6587][rfc6587].
+    ///
+    /// [rfc6587]: https://datatracker.ietf.org/doc/html/rfc6587#section-3.4.1
+    #[default]
+    OctetCounting,
+
+    /// Each message is followed by a newline, also known as non-transparent framing.
+    NewlineDelimited,
+}
+
+fn default_facility() -> Template {
+    Template::try_from("user").unwrap()
+}
+
+fn default_severity() -> Template {
+    Template::try_from("info").unwrap()
+}
+
+fn default_app_name() -> Template {
+    Template::try_from("vector").unwrap()
+}
+
+fn default_encoding() -> EncodingConfig {
+    TextSerializerConfig::default().into()
+}
+
+impl GenerateConfig for SyslogSinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"address = "127.0.0.1:6514"
+            mode = "tcp""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "syslog")]
+impl SinkConfig for SyslogSinkConfig {
+    async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let mut encoder = SyslogEncoder {
+            rfc: self.rfc,
+            framing: Some(self.framing),
+            facility: self.facility.clone(),
+            severity: self.severity.clone(),
+            hostname: self.hostname.clone(),
+            app_name: self.app_name.clone(),
+            proc_id: self.proc_id.clone(),
+            msg_id: self.msg_id.clone(),
+            structured_data_key: self.structured_data_key.clone(),
+            local_hostname: crate::get_hostname().ok(),
+            transformer: self.encoding.transformer(),
+            encoder: Encoder::<()>::new(self.encoding.build()?),
+        };
+
+        match &self.mode {
+            Mode::Tcp(config) => config.build(Transformer::default(), encoder),
+            Mode::Udp(config) => {
+                encoder.framing = None;
+                config.build(Transformer::default(), encoder)
+            }
+            #[cfg(unix)]
+            Mode::Unix(config) => {
+                if let UnixMode::Datagram = config.unix_mode {
+                    encoder.framing = None;
+                }
+                config.build(Transformer::default(), encoder)
+            }
+        }
+    }
+
+    fn input(&self) -> Input {
+        let requirement = schema::Requirement::empty()
+            .optional_meaning("host", Kind::bytes())
+            .optional_meaning("timestamp", Kind::timestamp());
+
+        Input::new(self.encoding.config().input_type() & DataType::Log)
+            .with_schema_requirement(requirement)
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
@@ -0,0 +1,309 @@
+//! Encoding of log events into syslog messages for the `syslog` sink.
+
+use std::fmt::Write as _;
+
+use bytes::{BufMut, BytesMut};
+use chrono::Utc;
+use tokio_util::codec::Encoder as _;
+use vector_lib::codecs::encoding::format::{
+    parse_facility, parse_severity, SyslogHeader, DEFAULT_FACILITY, DEFAULT_SEVERITY,
+};
+use vector_lib::codecs::encoding::SyslogRfc;
+use vector_lib::lookup::{lookup_v2::ConfigValuePath, PathPrefix};
+use vrl::value::Value;
+
+use super::config::SyslogFraming;
+use crate::{
+    codecs::{Encoder, Transformer},
+    event::Event,
+    internal_events::{SyslogInvalidFieldValue, TemplateRenderingError},
+    template::Template,
+};
+
+/// Encodes log events into syslog messages, framed for the transport they are sent over.
+#[derive(Clone, Debug)]
+pub(super) struct SyslogEncoder {
+    pub(super) rfc: SyslogRfc,
+    pub(super) framing: Option<SyslogFraming>,
+    pub(super) facility: Template,
+    pub(super) severity: Template,
+    pub(super) hostname: Option<Template>,
+    pub(super) app_name: Template,
+    pub(super) proc_id: Option<Template>,
+    pub(super) msg_id: Option<Template>,
+    pub(super) structured_data_key: Option<ConfigValuePath>,
+    pub(super) local_hostname: Option<String>,
+    pub(super) transformer: Transformer,
+    pub(super) encoder: Encoder<()>,
+}
+
+impl SyslogEncoder {
+    fn render(&self, template: &Template, field: &'static str, event: &Event) -> Option<String> {
+        template
+            .render_string(event)
+            .map_err(|error| {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some(field),
+                    drop_event: false,
+                })
+            })
+            .ok()
+    }
+
+    fn render_code(
+        &self,
+        template: &Template,
+        field: &'static str,
+        event: &Event,
+        parse: fn(&str) -> Option<u8>,
+    ) -> Option<u8> {
+        let value = self.render(template, field, event)?;
+        let code = parse(&value);
+        if code.is_none() {
+            emit!(SyslogInvalidFieldValue {
+                field,
+                value: &value,
+            });
+        }
+        code
+    }
+
+    /// Writes the header of the message, which comes before its body.
+    fn write_header(&self, event: &Event, structured_data: Option<&Value>) -> String {
+        let facility = self
+            .render_code(&self.facility, "facility", event, parse_facility)
+            .unwrap_or(DEFAULT_FACILITY);
+        let severity = self
+            .render_code(&self.severity, "severity", event, parse_severity)
+            .unwrap_or(DEFAULT_SEVERITY);
+
+        let log = event.as_log();
+        let timestamp = log
+            .get_timestamp()
+            .and_then(Value::as_timestamp)
+            .copied()
+            .unwrap_or_else(Utc::now);
+        let hostname = match &self.hostname {
+            Some(hostname) => self.render(hostname, "hostname", event),
+            None => log
+                .get_host()
+                .map(|host| host.to_string_lossy().into_owned()),
+        }
+        .or_else(|| self.local_hostname.clone());
+        let app_name = self.render(&self.app_name, "app_name", event);
+        let proc_id = self
+            .proc_id
+            .as_ref()
+            .and_then(|proc_id| self.render(proc_id, "proc_id", event));
+
+        let msg_id = match self.rfc {
+            SyslogRfc::Rfc5424 => self
+                .msg_id
+                .as_ref()
+                .and_then(|msg_id| self.render(msg_id, "msg_id", event)),
+            SyslogRfc::Rfc3164 => None,
+        };
+
+        SyslogHeader {
+            facility,
+            severity,
+            timestamp,
+            hostname: hostname.as_deref(),
+            app_name: app_name.as_deref(),
+            proc_id: proc_id.as_deref(),
+            msg_id: msg_id.as_deref(),
+            structured_data,
+        }
+        .write(self.rfc)
+    }
+}
+
+impl tokio_util::codec::Encoder<Event> for SyslogEncoder {
+    type Error = vector_lib::codecs::encoding::Error;
+
+    fn encode(&mut self, mut event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
+        let structured_data = self
+            .structured_data_key
+            .as_ref()
+            .and_then(|key| event.as_mut_log().remove((PathPrefix::Event, &key.0)));
+
+        let header = self.write_header(&event, structured_data.as_ref());
+
+        self.transformer.transform(&mut event);
+
+        let mut body = BytesMut::new();
+        self.encoder.encode(event, &mut body)?;
+
+        let mut message = BytesMut::with_capacity(header.len() + body.len() + 1);
+        message.extend_from_slice(header.as_bytes());
+        if !body.is_empty() {
+            message.put_u8(b' ');
+            message.extend_from_slice(&body);
+        }
+
+        match self.framing {
+            Some(SyslogFraming::OctetCounting) => {
+                _ = write!(buffer, "{} ", message.len());
+                buffer.extend_from_slice(&message);
+            }
+            Some(SyslogFraming::NewlineDelimited) => {
+                buffer.extend_from_slice(&message);
+                buffer.put_u8(b'\n');
+            }
+            None => buffer.extend_from_slice(&message),
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Timelike};
+    use vector_lib::codecs::{JsonSerializerConfig, TextSerializerConfig};
+    use vector_lib::event::LogEvent;
+
+    use super::*;
+
+    fn encoder(rfc: SyslogRfc, framing: Option<SyslogFraming>) -> SyslogEncoder {
+        SyslogEncoder {
+            rfc,
+            framing,
+            facility: Template::try_from("{{ facility }}").unwrap(),
+            severity: Template::try_from("{{ severity }}").unwrap(),
+            hostname: None,
+            app_name: Template::try_from("{{ app }}").unwrap(),
+            proc_id: Some(Template::try_from("{{ pid }}").unwrap()),
+            msg_id: Some(Template::try_from("ID47").unwrap()),
+            structured_data_key: Some(ConfigValuePath::try_from("sd".to_string()).unwrap()),
+            local_hostname: Some("vector-host".to_string()),
+            transformer: Default::default(),
+            encoder: Encoder::<()>::new(TextSerializerConfig::default().build().into()),
+        }
+    }
+
+    fn event() -> Event {
+        let mut log = LogEvent::from("'su root' failed for lonvick on /dev/pts/8");
+        log.insert("facility", "auth");
+        log.insert("severity", "crit");
+        log.insert("app", "su");
+        log.insert("pid", 42);
+        log.insert("host", "mymachine.example.com");
+        log.insert(
+            "timestamp",
+            Utc.with_ymd_and_hms(2003, 10, 11, 22, 14, 15)
+                .unwrap()
+                .with_nanosecond(3_000_000)
+                .unwrap(),
+        );
+        log.into()
+    }
+
+    fn encode(mut encoder: SyslogEncoder, event: Event) -> String {
+        let mut buffer = BytesMut::new();
+        encoder.encode(event, &mut buffer).unwrap();
+        String::from_utf8(buffer.to_vec()).unwrap()
+    }
+
+    #[test]
+    fn encodes_rfc5424() {
+        let mut event = event();
+        event.as_mut_log().insert(
+            "sd",
+            vrl::value!({
+                "exampleSDID@32473": {
+                    "eventSource": "Application",
+                    "iut": 3,
+                    "note": "a \"quoted\" [value]",
+                },
+                "examplePriority@32473": {
+                    "class": ["high", "low"],
+                },
+            }),
+        );
+
+        assert_eq!(
+            encode(encoder(SyslogRfc::Rfc5424, None), event),
+            "<34>1 2003-10-11T22:14:15.003000Z mymachine.example.com su 42 ID47 \
+             [examplePriority@32473 class=\"high\" class=\"low\"]\
+             [exampleSDID@32473 eventSource=\"Application\" iut=\"3\" note=\"a \\\"quoted\\\" [value\\]\"] \
+             'su root' failed for lonvick on /dev/pts/8"
+        );
+    }
+
+    #[test]
+    fn encodes_rfc5424_nil_values() {
+        let mut encoder = encoder(SyslogRfc::Rfc5424, None);
+        encoder.proc_id = None;
+        encoder.msg_id = None;
+
+        let mut event = event();
+        event.as_mut_log().insert("app", "my app\n");
+        event.as_mut_log().remove("host");
+
+        assert_eq!(
+            encode(encoder, event),
+            "<34>1 2003-10-11T22:14:15.003000Z vector-host my_app_ - - - \
+             'su root' failed for lonvick on /dev/pts/8"
+        );
+    }
+
+    #[test]
+    fn encodes_rfc3164() {
+        assert_eq!(
+            encode(encoder(SyslogRfc::Rfc3164, None), event()),
+            "<34>Oct 11 22:14:15 mymachine.example.com su[42]: \
+             'su root' failed for lonvick on /dev/pts/8"
+        );
+    }
+
+    #[test]
+    fn frames_messages() {
+        let message = "<34>Oct 11 22:14:15 mymachine.example.com su[42]: \
+                       'su root' failed for lonvick on /dev/pts/8";
+
+        assert_eq!(
+            encode(
+                encoder(SyslogRfc::Rfc3164, Some(SyslogFraming::OctetCounting)),
+                event()
+            ),
+            format!("{} {}", message.len(), message)
+        );
+        assert_eq!(
+            encode(
+                encoder(SyslogRfc::Rfc3164, Some(SyslogFraming::NewlineDelimited)),
+                event()
+            ),
+            format!("{}\n", message)
+        );
+    }
+
+    #[test]
+    fn falls_back_to_default_priority() {
+        let mut event = event();
+        event.as_mut_log().insert("facility", "not-a-facility");
+        event.as_mut_log().insert("severity", 8);
+
+        assert!(encode(encoder(SyslogRfc::Rfc3164, None), event).starts_with("<14>"));
+    }
+
+    #[test]
+    fn encodes_body_with_codec() {
+        let mut encoder = encoder(SyslogRfc::Rfc5424, None);
+        encoder.encoder = Encoder::<()>::new(JsonSerializerConfig::default().build().into());
+        encoder.transformer = Transformer::new(Some(vec!["message".into()]), None, None).unwrap();
+
+        let mut event = event();
+        event
+            .as_mut_log()
+            .insert("sd", vrl::value!({ "origin": { "ip": "192.0.2.1" } }));
+
+        assert_eq!(
+            encode(encoder, event),
+            "<34>1 2003-10-11T22:14:15.003000Z mymachine.example.com su 42 ID47 \
+             [origin ip=\"192.0.2.1\"] \
+             {\"message\":\"'su root' failed for lonvick on /dev/pts/8\"}"
+        );
+    }
+}
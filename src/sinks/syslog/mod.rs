@@ -0,0 +1,26 @@
+//! The `syslog` sink.
+//!
+//! This sink sends log events to syslog servers, as messages of the format of [RFC 5424][rfc5424]
+//! or of the legacy format of [RFC 3164][rfc3164], over TCP, optionally with TLS, UDP, or Unix
+//! domain sockets.
+//!
+//! The priority and the header fields of the messages are rendered from templates, so that they
+//! can be mapped from the fields of the events, and the structured data of RFC 5424 messages is
+//! taken from an object field of the events. The body of the messages is encoded with the
+//! configured codec.
+//!
+//! Messages sent over stream sockets are framed with octet counting, or with newlines, as
+//! described in [RFC 6587][rfc6587], and messages sent over datagram sockets are sent in their
+//! own datagram.
+//!
+//! [rfc5424]: https://datatracker.ietf.org/doc/html/rfc5424
+//! [rfc3164]: https://datatracker.ietf.org/doc/html/rfc3164
+//! [rfc6587]: https://datatracker.ietf.org/doc/html/rfc6587
+
+mod config;
+mod encoder;
+
+#[cfg(test)]
+mod tests;
+
+pub use self::config::SyslogSinkConfig;
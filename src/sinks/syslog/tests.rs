@@ -0,0 +1,159 @@
+use std::{future::ready, net::UdpSocket};
+
+use futures::stream;
+use tokio::{io::AsyncReadExt, net::TcpListener};
+
+use super::SyslogSinkConfig;
+use crate::{
+    config::{SinkConfig, SinkContext},
+    event::{Event, LogEvent},
+    test_util::{
+        components::{assert_sink_compliance, SINK_TAGS},
+        next_addr, random_lines_with_stream, trace_init, CountReceiver,
+    },
+};
+
+fn config(extra: &str) -> SyslogSinkConfig {
+    toml::from_str(extra).unwrap()
+}
+
+/// Splits octet-counted frames into the messages they hold.
+fn split_octet_counted(mut frames: &str) -> Vec<&str> {
+    let mut messages = Vec::new();
+    while !frames.is_empty() {
+        let (len, rest) = frames.split_once(' ').expect("Missing frame length");
+        let len = len.parse::<usize>().expect("Invalid frame length");
+        messages.push(&rest[..len]);
+        frames = &rest[len..];
+    }
+    messages
+}
+
+#[test]
+fn generate_config() {
+    crate::test_util::test_generate_config::<SyslogSinkConfig>();
+}
+
+#[tokio::test]
+async fn tcp_octet_counting() {
+    trace_init();
+
+    let addr = next_addr();
+    let listener = TcpListener::bind(addr).await.unwrap();
+    let receiver = tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut output = String::new();
+        socket.read_to_string(&mut output).await.unwrap();
+        output
+    });
+
+    let config = config(&format!(
+        r#"
+            mode = "tcp"
+            address = "{addr}"
+            facility = "local0"
+            severity = "{{{{ level }}}}"
+            app_name = "my-app"
+            msg_id = "{{{{ kind }}}}"
+            structured_data_key = "sd"
+        "#
+    ));
+
+    let mut log = LogEvent::from("raw log line");
+    log.insert("level", "error");
+    log.insert("kind", "audit");
+    log.insert("host", "example.com");
+    log.insert("sd.origin.ip", "192.0.2.1");
+    let event = Event::Log(log);
+
+    assert_sink_compliance(&SINK_TAGS, async move {
+        let (sink, _healthcheck) = config.build(SinkContext::default()).await.unwrap();
+        sink.run(stream::once(ready(event.into()))).await
+    })
+    .await
+    .expect("Running sink failed");
+
+    let output = receiver.await.unwrap();
+    let messages = split_octet_counted(&output);
+    assert_eq!(messages.len(), 1);
+
+    let (priority, rest) = messages[0].split_once(' ').unwrap();
+    assert_eq!(priority, "<131>1");
+    let (_timestamp, rest) = rest.split_once(' ').unwrap();
+    assert_eq!(
+        rest,
+        "example.com my-app - audit [origin ip=\"192.0.2.1\"] raw log line"
+    );
+}
+
+#[tokio::test]
+async fn tcp_newline_delimited_rfc3164() {
+    trace_init();
+
+    let addr = next_addr();
+    let config = config(&format!(
+        r#"
+            mode = "tcp"
+            address = "{addr}"
+            rfc = "rfc3164"
+            framing = "newline_delimited"
+            hostname = "example.com"
+        "#
+    ));
+
+    let mut receiver = CountReceiver::receive_lines(addr);
+
+    let (lines, events) = random_lines_with_stream(10, 100, None);
+
+    assert_sink_compliance(&SINK_TAGS, async move {
+        let (sink, _healthcheck) = config.build(SinkContext::default()).await.unwrap();
+        sink.run(events).await
+    })
+    .await
+    .expect("Running sink failed");
+
+    // Wait for output to connect
+    receiver.connected().await;
+
+    let output = receiver.await;
+    assert_eq!(lines.len(), output.len());
+    for (source, received) in lines.iter().zip(output) {
+        assert!(received.starts_with("<14>"));
+        assert!(received.ends_with(&format!(" example.com vector: {}", source)));
+    }
+}
+
+#[tokio::test]
+async fn udp() {
+    trace_init();
+
+    let addr = next_addr();
+    let receiver = UdpSocket::bind(addr).unwrap();
+
+    let config = config(&format!(
+        r#"
+            mode = "udp"
+            address = "{addr}"
+            hostname = "example.com"
+            proc_id = "42"
+        "#
+    ));
+
+    assert_sink_compliance(&SINK_TAGS, async move {
+        let (sink, _healthcheck) = config.build(SinkContext::default()).await.unwrap();
+        let event = Event::Log(LogEvent::from("raw log line"));
+        sink.run(stream::once(ready(event.into()))).await
+    })
+    .await
+    .expect("Running sink failed");
+
+    let mut buf = [0; 256];
+    let (size, _src_addr) = receiver
+        .recv_from(&mut buf)
+        .expect("Did not receive message");
+
+    // Datagrams aren't framed.
+    let packet = String::from_utf8(buf[..size].to_vec()).expect("Invalid data received");
+    assert!(packet.starts_with("<14>1 "));
+    assert!(packet.ends_with(" example.com vector 42 - - raw log line"));
+}
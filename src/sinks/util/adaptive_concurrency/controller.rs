@@ -59,12 +59,20 @@ pub(super) struct ControllerStatistics {
     pub(super) averaged_rtt: TimeWeightedSum,
 }
 
-impl<L> Controller<L> {
-    pub(super) fn new(
-        concurrency: Option<usize>,
-        settings: AdaptiveConcurrencySettings,
-        logic: L,
-    ) -> Self {
+/// The concurrency limit of a controller, which can be shared by the controllers of several
+/// services so that their requests are limited together.
+#[derive(Clone, Debug)]
+pub(crate) struct ControllerState {
+    semaphore: Arc<ShrinkableSemaphore>,
+    pub(super) concurrency: Option<usize>,
+    pub(super) settings: AdaptiveConcurrencySettings,
+    inner: Arc<Mutex<Inner>>,
+    #[cfg(test)]
+    stats: Arc<Mutex<ControllerStatistics>>,
+}
+
+impl ControllerState {
+    pub(crate) fn new(concurrency: Option<usize>, settings: AdaptiveConcurrencySettings) -> Self {
         // If a `concurrency` is specified, it becomes both the
         // current limit and the maximum, effectively bypassing all the
         // mechanisms. Otherwise, the current limit is set to 1 and the
@@ -74,7 +82,6 @@ impl<L> Controller<L> {
             semaphore: Arc::new(ShrinkableSemaphore::new(current_limit)),
             concurrency,
             settings,
-            logic,
             inner: Arc::new(Mutex::new(Inner {
                 current_limit,
                 in_flight: 0,
@@ -86,6 +93,28 @@ impl<L> Controller<L> {
             })),
             #[cfg(test)]
             stats: Arc::new(Mutex::new(ControllerStatistics::default())),
+        }
+    }
+}
+
+impl<L> Controller<L> {
+    pub(super) fn new(
+        concurrency: Option<usize>,
+        settings: AdaptiveConcurrencySettings,
+        logic: L,
+    ) -> Self {
+        Self::with_state(ControllerState::new(concurrency, settings), logic)
+    }
+
+    pub(super) fn with_state(state: ControllerState, logic: L) -> Self {
+        Self {
+            semaphore: state.semaphore,
+            concurrency: state.concurrency,
+            settings: state.settings,
+            logic,
+            inner: state.inner,
+            #[cfg(test)]
+            stats: state.stats,
             limit: register!(AdaptiveConcurrencyLimit),
             in_flight: register!(AdaptiveConcurrencyInFlight),
             observed_rtt: register!(AdaptiveConcurrencyObservedRtt),
@@ -1,6 +1,6 @@
 use tower::Layer;
 
-use super::{AdaptiveConcurrencyLimit, AdaptiveConcurrencySettings};
+use super::{controller::ControllerState, AdaptiveConcurrencyLimit, AdaptiveConcurrencySettings};
 use crate::sinks::util::retries::RetryLogic;
 
 /// Enforces a limit on the concurrent number of requests the underlying
@@ -10,6 +10,7 @@ pub struct AdaptiveConcurrencyLimitLayer<L> {
     concurrency: Option<usize>,
     options: AdaptiveConcurrencySettings,
     logic: L,
+    state: Option<ControllerState>,
 }
 
 impl<L> AdaptiveConcurrencyLimitLayer<L> {
@@ -23,6 +24,18 @@ impl<L> AdaptiveConcurrencyLimitLayer<L> {
             concurrency,
             options,
             logic,
+            state: None,
+        }
+    }
+
+    /// Create a new concurrency limit layer, whose services share the limit of the given state
+    /// instead of their own.
+    pub(crate) fn with_state(state: ControllerState, logic: L) -> Self {
+        AdaptiveConcurrencyLimitLayer {
+            concurrency: state.concurrency,
+            options: state.settings,
+            logic,
+            state: Some(state),
         }
     }
 }
@@ -31,6 +44,16 @@ impl<S, L: RetryLogic> Layer<S> for AdaptiveConcurrencyLimitLayer<L> {
     type Service = AdaptiveConcurrencyLimit<S, L>;
 
     fn layer(&self, service: S) -> Self::Service {
-        AdaptiveConcurrencyLimit::new(service, self.logic.clone(), self.concurrency, self.options)
+        match &self.state {
+            Some(state) => {
+                AdaptiveConcurrencyLimit::with_state(service, self.logic.clone(), state.clone())
+            }
+            None => AdaptiveConcurrencyLimit::new(
+                service,
+                self.logic.clone(),
+                self.concurrency,
+                self.options,
+            ),
+        }
     }
 }
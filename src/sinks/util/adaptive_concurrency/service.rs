@@ -10,7 +10,11 @@ use futures::future::BoxFuture;
 use tokio::sync::OwnedSemaphorePermit;
 use tower::{load::Load, Service};
 
-use super::{controller::Controller, future::ResponseFuture, AdaptiveConcurrencySettings};
+use super::{
+    controller::{Controller, ControllerState},
+    future::ResponseFuture,
+    AdaptiveConcurrencySettings,
+};
 use crate::sinks::util::retries::RetryLogic;
 
 /// Enforces a limit on the concurrent number of requests the underlying
@@ -42,6 +46,16 @@ impl<S, L> AdaptiveConcurrencyLimit<S, L> {
             state: State::Empty,
         }
     }
+
+    /// Create a new concurrency limiter, that shares its limit with the other limiters created
+    /// with the same state.
+    pub(crate) fn with_state(inner: S, logic: L, state: ControllerState) -> Self {
+        AdaptiveConcurrencyLimit {
+            inner,
+            controller: Arc::new(Controller::with_state(state, logic)),
+            state: State::Empty,
+        }
+    }
 }
 
 impl<S, L, Request> Service<Request> for AdaptiveConcurrencyLimit<S, L>
@@ -71,6 +71,35 @@ impl Encoder<Vec<Event>> for (Transformer, crate::codecs::Encoder<Framer>) {
     }
 }
 
+#[cfg(feature = "codecs-parquet")]
+impl Encoder<Vec<Event>> for (Transformer, crate::codecs::EncoderKind) {
+    fn encode_input(
+        &self,
+        mut events: Vec<Event>,
+        writer: &mut dyn io::Write,
+    ) -> io::Result<(usize, GroupedCountByteSize)> {
+        let serializer = match &self.1 {
+            crate::codecs::EncoderKind::Framed(encoder) => {
+                return (self.0.clone(), encoder.clone()).encode_input(events, writer)
+            }
+            crate::codecs::EncoderKind::Batch(serializer) => serializer,
+        };
+
+        let mut byte_size = telemetry().create_request_count_byte_size();
+        for event in &mut events {
+            self.0.transform(event);
+            byte_size.add_event(event, event.estimated_json_encoded_size_of());
+        }
+
+        let bytes = serializer
+            .encode(&events)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        write_all(writer, events.len(), &bytes)?;
+
+        Ok((bytes.len(), byte_size))
+    }
+}
+
 impl Encoder<Event> for (Transformer, crate::codecs::Encoder<()>) {
     fn encode_input(
         &self,
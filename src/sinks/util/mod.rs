@@ -23,7 +23,14 @@ pub mod tcp;
 #[cfg(any(test, feature = "test-utils"))]
 pub mod test;
 pub mod udp;
-#[cfg(all(any(feature = "sinks-socket", feature = "sinks-statsd"), unix))]
+#[cfg(all(
+    any(
+        feature = "sinks-socket",
+        feature = "sinks-statsd",
+        feature = "sinks-syslog"
+    ),
+    unix
+))]
 pub mod unix;
 pub mod uri;
 pub mod zstd;
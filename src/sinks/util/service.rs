@@ -18,6 +18,9 @@ pub use crate::sinks::util::service::{
     concurrency::Concurrency,
     health::{HealthConfig, HealthLogic, HealthService},
     map::Map,
+    request_limits::{
+        GroupRateLimit, RequestLimitsConfig, RequestLimitsGroup, RequestLimitsGroups,
+    },
 };
 use crate::{
     internal_events::OpenGauge,
@@ -36,15 +39,19 @@ mod concurrency;
 mod health;
 mod map;
 pub mod net;
+mod request_limits;
 
-pub type Svc<S, L> =
-    RateLimit<AdaptiveConcurrencyLimit<Retry<FibonacciRetryPolicy<L>, Timeout<S>>, L>>;
+pub type Svc<S, L> = RateLimit<
+    GroupRateLimit<AdaptiveConcurrencyLimit<Retry<FibonacciRetryPolicy<L>, Timeout<S>>, L>>,
+>;
 pub type TowerBatchedSink<S, B, RL> = BatchSink<Svc<S, RL>, B>;
 pub type TowerPartitionSink<S, B, RL, K> = PartitionBatchSink<Svc<S, RL>, B, K>;
 
 // Distributed service types
 pub type DistributedService<S, RL, HL, K, Req> = RateLimit<
-    Retry<FibonacciRetryPolicy<RL>, Buffer<Balance<DiscoveryService<S, RL, HL, K>, Req>, Req>>,
+    GroupRateLimit<
+        Retry<FibonacciRetryPolicy<RL>, Buffer<Balance<DiscoveryService<S, RL, HL, K>, Req>, Req>>,
+    >,
 >;
 pub type DiscoveryService<S, RL, HL, K> =
     BoxStream<'static, Result<Change<K, SingleDistributedService<S, RL, HL>>, crate::Error>>;
@@ -229,6 +236,7 @@ impl<D: TowerRequestConfigDefaults> TowerRequestConfig<D> {
             retry_initial_backoff: Duration::from_secs(self.retry_initial_backoff_secs),
             adaptive_concurrency: self.adaptive_concurrency,
             retry_jitter_mode: self.retry_jitter_mode,
+            request_limits: None,
         }
     }
 }
@@ -244,9 +252,16 @@ pub struct TowerRequestSettings {
     pub retry_initial_backoff: Duration,
     pub adaptive_concurrency: AdaptiveConcurrencySettings,
     pub retry_jitter_mode: JitterMode,
+    pub request_limits: Option<RequestLimitsGroup>,
 }
 
 impl TowerRequestSettings {
+    /// Limits the requests with the request limits group of the sink, if it references one.
+    pub fn with_request_limits(mut self, group: Option<RequestLimitsGroup>) -> Self {
+        self.request_limits = group;
+        self
+    }
+
     pub fn retry_policy<L: RetryLogic>(&self, logic: L) -> FibonacciRetryPolicy<L> {
         FibonacciRetryPolicy::new(
             self.retry_attempts,
@@ -257,6 +272,21 @@ impl TowerRequestSettings {
         )
     }
 
+    /// Limits the concurrency of the requests with the group of the sink if it has one, or with
+    /// its own settings otherwise.
+    fn concurrency_limit_layer<L>(&self, retry_logic: L) -> AdaptiveConcurrencyLimitLayer<L> {
+        match &self.request_limits {
+            Some(group) => {
+                AdaptiveConcurrencyLimitLayer::with_state(group.controller_state(), retry_logic)
+            }
+            None => AdaptiveConcurrencyLimitLayer::new(
+                self.concurrency,
+                self.adaptive_concurrency,
+                retry_logic,
+            ),
+        }
+    }
+
     /// Note: This has been deprecated, please do not use when creating new Sinks.
     pub fn partition_sink<B, RL, S, K>(
         &self,
@@ -335,11 +365,7 @@ impl TowerRequestSettings {
             .map(|(endpoint, inner)| {
                 // Build individual service
                 ServiceBuilder::new()
-                    .layer(AdaptiveConcurrencyLimitLayer::new(
-                        self.concurrency,
-                        self.adaptive_concurrency,
-                        retry_logic.clone(),
-                    ))
+                    .layer(self.concurrency_limit_layer(retry_logic.clone()))
                     .service(
                         health_config.build(
                             health_logic.clone(),
@@ -358,6 +384,7 @@ impl TowerRequestSettings {
         // Build sink service
         ServiceBuilder::new()
             .rate_limit(self.rate_limit_num, self.rate_limit_duration)
+            .layer_fn(|inner| GroupRateLimit::new(inner, self.request_limits.as_ref()))
             .retry(policy)
             // [Balance] must be wrapped with a [BufferLayer] so that the overall service implements Clone.
             .layer(BufferLayer::new(buffer_bound))
@@ -390,11 +417,11 @@ where
                 self.settings.rate_limit_num,
                 self.settings.rate_limit_duration,
             )
-            .layer(AdaptiveConcurrencyLimitLayer::new(
-                self.settings.concurrency,
-                self.settings.adaptive_concurrency,
-                self.retry_logic.clone(),
-            ))
+            .layer_fn(|inner| GroupRateLimit::new(inner, self.settings.request_limits.as_ref()))
+            .layer(
+                self.settings
+                    .concurrency_limit_layer(self.retry_logic.clone()),
+            )
             .retry(policy)
             .timeout(self.settings.timeout)
             .service(inner)
@@ -62,6 +62,12 @@ impl UnixConnectorConfig {
         }
     }
 
+    /// Sets the Unix socket mode to use.
+    pub const fn with_unix_mode(mut self, unix_mode: UnixMode) -> Self {
+        self.unix_mode = unix_mode;
+        self
+    }
+
     /// Creates a [`NetworkConnector`] from this Unix Domain Socket connector configuration.
     pub fn as_connector(&self) -> NetworkConnector {
         NetworkConnector {
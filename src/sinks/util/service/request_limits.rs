@@ -0,0 +1,357 @@
+//! Request limits shared by groups of sinks.
+//!
+//! The requests of all of the sinks that reference the same group count against the concurrency
+//! and rate limits of that group, so that sinks sending to the same destination don't collectively
+//! overload it.
+
+use std::{
+    collections::HashMap,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex, MutexGuard},
+    task::{ready, Context, Poll},
+    time::Duration,
+};
+
+use indexmap::IndexMap;
+use tokio::time::{sleep_until, Instant, Sleep};
+use tower::Service;
+use vector_lib::configurable::configurable_component;
+
+use super::Concurrency;
+use crate::sinks::util::adaptive_concurrency::{AdaptiveConcurrencySettings, ControllerState};
+
+const fn default_rate_limit_duration_secs() -> u64 {
+    1
+}
+
+const fn default_rate_limit_num() -> u64 {
+    i64::MAX as u64 // i64 avoids TOML deserialize issue
+}
+
+/// Request limits shared by a group of sinks.
+///
+/// Sinks join a group by referencing its name in their `request_limits` option. The requests of all
+/// of the sinks of a group count against the concurrency and rate limits of the group. The
+/// concurrency of the requests of these sinks is limited by the group instead of their own
+/// `request.concurrency` option, while their own rate limit still applies on top of the rate limit
+/// of the group.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct RequestLimitsConfig {
+    #[configurable(derived)]
+    #[serde(default)]
+    pub concurrency: Concurrency,
+
+    /// The time window used for the `rate_limit_num` option.
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    #[configurable(metadata(docs::human_name = "Rate Limit Duration"))]
+    #[serde(default = "default_rate_limit_duration_secs")]
+    pub rate_limit_duration_secs: u64,
+
+    /// The maximum number of requests of all of the sinks of the group allowed within the
+    /// `rate_limit_duration_secs` time window.
+    #[configurable(metadata(docs::type_unit = "requests"))]
+    #[configurable(metadata(docs::human_name = "Rate Limit Number"))]
+    #[serde(default = "default_rate_limit_num")]
+    pub rate_limit_num: u64,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub adaptive_concurrency: AdaptiveConcurrencySettings,
+}
+
+impl Default for RequestLimitsConfig {
+    fn default() -> Self {
+        Self {
+            concurrency: Concurrency::default(),
+            rate_limit_duration_secs: default_rate_limit_duration_secs(),
+            rate_limit_num: default_rate_limit_num(),
+            adaptive_concurrency: AdaptiveConcurrencySettings::default(),
+        }
+    }
+}
+
+/// The shared state of the limits of a group.
+#[derive(Clone, Debug)]
+pub struct RequestLimitsGroup {
+    controller: ControllerState,
+    bucket: RateLimitBucket,
+}
+
+impl RequestLimitsGroup {
+    fn new(config: &RequestLimitsConfig) -> Self {
+        Self {
+            controller: ControllerState::new(
+                config.concurrency.parse_concurrency(),
+                config.adaptive_concurrency,
+            ),
+            bucket: RateLimitBucket::new(
+                config.rate_limit_num,
+                Duration::from_secs(config.rate_limit_duration_secs),
+            ),
+        }
+    }
+
+    pub(crate) fn controller_state(&self) -> ControllerState {
+        self.controller.clone()
+    }
+}
+
+/// The request limits groups of a topology, by name.
+#[derive(Clone, Debug, Default)]
+pub struct RequestLimitsGroups {
+    groups: HashMap<String, (RequestLimitsConfig, RequestLimitsGroup)>,
+}
+
+impl RequestLimitsGroups {
+    /// Builds the groups of a configuration.
+    ///
+    /// The groups whose configuration didn't change are taken from the previous groups of the
+    /// topology, so that the sinks that are rebuilt by a reload share the limits of their group
+    /// with the sinks that are kept running. The groups that are no longer configured are dropped.
+    pub fn build(
+        configs: &IndexMap<String, RequestLimitsConfig>,
+        previous: &RequestLimitsGroups,
+    ) -> Self {
+        let groups = configs
+            .iter()
+            .map(|(name, config)| {
+                let group = match previous.groups.get(name) {
+                    Some((built_config, group)) if built_config == config => group.clone(),
+                    _ => RequestLimitsGroup::new(config),
+                };
+                (name.clone(), (*config, group))
+            })
+            .collect();
+        Self { groups }
+    }
+
+    /// Gets the group of the given name.
+    pub fn get(&self, name: &str) -> Option<RequestLimitsGroup> {
+        self.groups.get(name).map(|(_, group)| group.clone())
+    }
+}
+
+/// A token bucket, which is refilled at the start of each time window.
+///
+/// Tokens are taken by the requests themselves, after the services of the group have waited for
+/// the bucket to have some. When more requests are sent than there were tokens left, the excess is
+/// taken from the next time window.
+#[derive(Clone, Debug)]
+struct RateLimitBucket {
+    num: i64,
+    per: Duration,
+    state: Arc<Mutex<BucketState>>,
+}
+
+#[derive(Debug)]
+struct BucketState {
+    until: Instant,
+    remaining: i64,
+}
+
+impl RateLimitBucket {
+    fn new(num: u64, per: Duration) -> Self {
+        let num = i64::try_from(num).unwrap_or(i64::MAX);
+        Self {
+            num,
+            per,
+            state: Arc::new(Mutex::new(BucketState {
+                until: Instant::now(),
+                remaining: num,
+            })),
+        }
+    }
+
+    fn state(&self) -> MutexGuard<'_, BucketState> {
+        let mut state = self.state.lock().expect("Rate limit mutex is poisoned");
+        let now = Instant::now();
+        if now >= state.until {
+            state.until = now + self.per;
+            state.remaining = self.num.saturating_add(state.remaining.min(0));
+        }
+        state
+    }
+
+    /// Checks that the bucket has tokens left, or returns the time the bucket is next refilled at
+    /// if it is empty.
+    fn check(&self) -> Result<(), Instant> {
+        let state = self.state();
+        if state.remaining > 0 {
+            Ok(())
+        } else {
+            Err(state.until)
+        }
+    }
+
+    /// Takes a token from the bucket for a request.
+    fn acquire(&self) {
+        self.state().remaining -= 1;
+    }
+}
+
+/// Enforces the rate limit of a group on the requests of the underlying service.
+///
+/// Services that aren't limited by a group are passed through as is. The token of a request is
+/// taken when it is called rather than when the service is ready, so that no token is lost or
+/// skipped when the service is cloned in between.
+pub struct GroupRateLimit<S> {
+    inner: S,
+    bucket: Option<RateLimitBucket>,
+    sleep: Option<Pin<Box<Sleep>>>,
+}
+
+impl<S> GroupRateLimit<S> {
+    pub(crate) fn new(inner: S, group: Option<&RequestLimitsGroup>) -> Self {
+        Self {
+            inner,
+            bucket: group.map(|group| group.bucket.clone()),
+            sleep: None,
+        }
+    }
+}
+
+impl<S, Request> Service<Request> for GroupRateLimit<S>
+where
+    S: Service<Request>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        if let Some(bucket) = &self.bucket {
+            loop {
+                if let Some(sleep) = &mut self.sleep {
+                    ready!(sleep.as_mut().poll(cx));
+                    self.sleep = None;
+                }
+                match bucket.check() {
+                    Ok(()) => break,
+                    Err(until) => self.sleep = Some(Box::pin(sleep_until(until))),
+                }
+            }
+        }
+
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        if let Some(bucket) = &self.bucket {
+            bucket.acquire();
+        }
+
+        self.inner.call(request)
+    }
+}
+
+impl<S: Clone> Clone for GroupRateLimit<S> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            bucket: self.bucket.clone(),
+            sleep: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::future;
+    use tokio_test::{assert_pending, assert_ready_ok};
+    use tower::service_fn;
+
+    use super::*;
+
+    fn service(group: &RequestLimitsGroup) -> impl Service<(), Error = crate::Error> + Clone {
+        GroupRateLimit::new(
+            service_fn(|()| future::ok::<_, crate::Error>(())),
+            Some(group),
+        )
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn services_share_the_rate_limit_of_their_group() {
+        let group = RequestLimitsGroup::new(&RequestLimitsConfig {
+            rate_limit_num: 2,
+            ..Default::default()
+        });
+        let mut first = service(&group);
+        let mut second = service(&group);
+        let cx = &mut Context::from_waker(futures::task::noop_waker_ref());
+
+        assert_ready_ok!(first.poll_ready(cx));
+        first.call(()).await.unwrap();
+        assert_ready_ok!(second.poll_ready(cx));
+        second.call(()).await.unwrap();
+        assert_pending!(first.poll_ready(cx));
+        assert_pending!(second.poll_ready(cx));
+
+        tokio::time::advance(Duration::from_secs(1)).await;
+        assert_ready_ok!(first.poll_ready(cx));
+        assert_ready_ok!(second.poll_ready(cx));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn clones_count_against_the_rate_limit_of_their_group() {
+        let group = RequestLimitsGroup::new(&RequestLimitsConfig {
+            rate_limit_num: 1,
+            ..Default::default()
+        });
+        let mut service = service(&group);
+        let cx = &mut Context::from_waker(futures::task::noop_waker_ref());
+
+        // The token isn't lost when the service is cloned between `poll_ready` and `call`.
+        assert_ready_ok!(service.poll_ready(cx));
+        let mut clone = service.clone();
+        clone.call(()).await.unwrap();
+        assert_pending!(service.poll_ready(cx));
+        assert_pending!(clone.poll_ready(cx));
+
+        // Both services being ready, the second request is taken from the next time window.
+        tokio::time::advance(Duration::from_secs(1)).await;
+        assert_ready_ok!(service.poll_ready(cx));
+        assert_ready_ok!(clone.poll_ready(cx));
+        service.call(()).await.unwrap();
+        clone.call(()).await.unwrap();
+        tokio::time::advance(Duration::from_secs(1)).await;
+        assert_pending!(service.poll_ready(cx));
+
+        tokio::time::advance(Duration::from_secs(1)).await;
+        assert_ready_ok!(service.poll_ready(cx));
+    }
+
+    #[test]
+    fn groups_are_rebuilt_when_their_config_changes() {
+        let same_state = |a: &RequestLimitsGroups, b: &RequestLimitsGroups, name| {
+            Arc::ptr_eq(
+                &a.get(name).unwrap().bucket.state,
+                &b.get(name).unwrap().bucket.state,
+            )
+        };
+        let configs = IndexMap::from([
+            ("kept".to_owned(), RequestLimitsConfig::default()),
+            ("changed".to_owned(), RequestLimitsConfig::default()),
+            ("removed".to_owned(), RequestLimitsConfig::default()),
+        ]);
+        let groups = RequestLimitsGroups::build(&configs, &RequestLimitsGroups::default());
+
+        let configs = IndexMap::from([
+            ("kept".to_owned(), RequestLimitsConfig::default()),
+            (
+                "changed".to_owned(),
+                RequestLimitsConfig {
+                    rate_limit_num: 10,
+                    ..Default::default()
+                },
+            ),
+        ]);
+        let reloaded = RequestLimitsGroups::build(&configs, &groups);
+
+        assert!(same_state(&groups, &reloaded, "kept"));
+        assert!(!same_state(&groups, &reloaded, "changed"));
+        assert!(reloaded.get("removed").is_none());
+    }
+}
@@ -1,5 +1,5 @@
 use std::{
-    io::ErrorKind,
+    io::{self, ErrorKind, Write},
     net::SocketAddr,
     pin::Pin,
     task::{Context, Poll},
@@ -7,12 +7,13 @@ use std::{
 };
 
 use async_trait::async_trait;
-use bytes::{Bytes, BytesMut};
-use futures::{stream::BoxStream, task::noop_waker_ref, SinkExt, StreamExt};
+use bytes::{buf::Writer, Buf, BufMut, Bytes, BytesMut};
+use flate2::write::GzEncoder;
+use futures::{ready, stream::BoxStream, task::noop_waker_ref, SinkExt, StreamExt};
 use futures_util::{future::ready, stream};
 use snafu::{ResultExt, Snafu};
 use tokio::{
-    io::{AsyncRead, ReadBuf},
+    io::{AsyncRead, AsyncWrite, ReadBuf},
     net::TcpStream,
     time::sleep,
 };
@@ -78,6 +79,33 @@ pub struct TcpSinkConfig {
     #[configurable(metadata(docs::type_unit = "bytes"))]
     #[configurable(metadata(docs::examples = 65536))]
     send_buffer_bytes: Option<usize>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    compression: TcpCompression,
+}
+
+/// The compression applied to the data sent over each connection.
+///
+/// The data of a connection is compressed as a single stream, which is flushed whenever the sink
+/// flushes the connection, so that the receiver can decompress the events as they arrive.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TcpCompression {
+    /// Data is not compressed.
+    #[default]
+    None,
+
+    /// Data is compressed with [gzip][gzip].
+    ///
+    /// [gzip]: https://www.gzip.org/
+    Gzip,
+
+    /// Data is compressed with [zstd][zstd].
+    ///
+    /// [zstd]: https://facebook.github.io/zstd/
+    Zstd,
 }
 
 impl TcpSinkConfig {
@@ -92,6 +120,7 @@ impl TcpSinkConfig {
             keepalive,
             tls,
             send_buffer_bytes,
+            compression: TcpCompression::None,
         }
     }
 
@@ -101,9 +130,15 @@ impl TcpSinkConfig {
             keepalive: None,
             tls: None,
             send_buffer_bytes: None,
+            compression: TcpCompression::None,
         }
     }
 
+    pub const fn set_compression(mut self, compression: TcpCompression) -> Self {
+        self.compression = compression;
+        self
+    }
+
     pub fn build(
         &self,
         transformer: Transformer,
@@ -118,7 +153,7 @@ impl TcpSinkConfig {
         let port = uri.port_u16().ok_or(SinkBuildError::MissingPort)?;
         let tls = MaybeTlsSettings::from_config(&self.tls, false)?;
         let connector = TcpConnector::new(host, port, self.keepalive, tls, self.send_buffer_bytes);
-        let sink = TcpSink::new(connector.clone(), transformer, encoder);
+        let sink = TcpSink::new(connector.clone(), transformer, encoder, self.compression);
 
         Ok((
             VectorSink::from_event_streamsink(sink),
@@ -225,26 +260,34 @@ where
     connector: TcpConnector,
     transformer: Transformer,
     encoder: E,
+    compression: TcpCompression,
 }
 
 impl<E> TcpSink<E>
 where
     E: Encoder<Event, Error = vector_lib::codecs::encoding::Error> + Clone + Send + Sync + 'static,
 {
-    const fn new(connector: TcpConnector, transformer: Transformer, encoder: E) -> Self {
+    const fn new(
+        connector: TcpConnector,
+        transformer: Transformer,
+        encoder: E,
+        compression: TcpCompression,
+    ) -> Self {
         Self {
             connector,
             transformer,
             encoder,
+            compression,
         }
     }
 
-    async fn connect(&self) -> BytesSink<MaybeTlsStream<TcpStream>> {
+    async fn connect(&self) -> BytesSink<CompressedStream<MaybeTlsStream<TcpStream>>> {
         let stream = self.connector.connect_backoff().await;
+        let stream = CompressedStream::new(stream, self.compression);
         BytesSink::new(stream, Self::shutdown_check, SocketMode::Tcp)
     }
 
-    fn shutdown_check(stream: &mut MaybeTlsStream<TcpStream>) -> ShutdownCheck {
+    fn shutdown_check(stream: &mut CompressedStream<MaybeTlsStream<TcpStream>>) -> ShutdownCheck {
         // Test if the remote has issued a disconnect by calling read(2)
         // with a 1 sized buffer.
         //
@@ -256,7 +299,7 @@ where
         let mut cx = Context::from_waker(noop_waker_ref());
         let mut buf = [0u8; 1];
         let mut buf = ReadBuf::new(&mut buf);
-        match Pin::new(stream).poll_read(&mut cx, &mut buf) {
+        match Pin::new(stream.get_mut()).poll_read(&mut cx, &mut buf) {
             Poll::Ready(Err(error)) => ShutdownCheck::Error(error),
             Poll::Ready(Ok(())) if buf.filled().is_empty() => {
                 // Maybe this is only a sign to close the channel,
@@ -335,6 +378,155 @@ where
     }
 }
 
+/// A compressor writing the compressed data into a buffer.
+enum Compressor {
+    Gzip(GzEncoder<Writer<BytesMut>>),
+    Zstd(zstd::stream::write::Encoder<'static, Writer<BytesMut>>),
+}
+
+impl Compressor {
+    fn new(compression: TcpCompression) -> Option<Self> {
+        let buffer = BytesMut::new().writer();
+        match compression {
+            TcpCompression::None => None,
+            TcpCompression::Gzip => Some(Self::Gzip(GzEncoder::new(
+                buffer,
+                flate2::Compression::default(),
+            ))),
+            TcpCompression::Zstd => {
+                let encoder =
+                    zstd::stream::write::Encoder::new(buffer, zstd::DEFAULT_COMPRESSION_LEVEL)
+                        .expect("Zstd encoder should not fail on init.");
+                Some(Self::Zstd(encoder))
+            }
+        }
+    }
+
+    fn write(&mut self, data: &[u8]) -> io::Result<()> {
+        match self {
+            Self::Gzip(encoder) => encoder.write_all(data),
+            Self::Zstd(encoder) => encoder.write_all(data),
+        }
+    }
+
+    /// Makes all the data written so far decompressible by the receiver.
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Self::Gzip(encoder) => encoder.flush(),
+            Self::Zstd(encoder) => encoder.flush(),
+        }
+    }
+
+    /// Ends the compressed stream.
+    fn finish(&mut self) -> io::Result<()> {
+        match self {
+            Self::Gzip(encoder) => encoder.try_finish(),
+            Self::Zstd(encoder) => encoder.do_finish(),
+        }
+    }
+
+    /// The compressed data that hasn't been sent yet.
+    fn output(&mut self) -> &mut BytesMut {
+        match self {
+            Self::Gzip(encoder) => encoder.get_mut().get_mut(),
+            Self::Zstd(encoder) => encoder.get_mut().get_mut(),
+        }
+    }
+}
+
+/// A stream compressing the data written to it, as configured by `TcpCompression`.
+///
+/// Reads are passed through to the underlying stream.
+struct CompressedStream<S> {
+    inner: S,
+    compressor: Option<Compressor>,
+    needs_flush: bool,
+    finished: bool,
+}
+
+impl<S> CompressedStream<S>
+where
+    S: AsyncWrite + Unpin,
+{
+    fn new(inner: S, compression: TcpCompression) -> Self {
+        Self {
+            inner,
+            compressor: Compressor::new(compression),
+            needs_flush: false,
+            finished: false,
+        }
+    }
+
+    fn get_mut(&mut self) -> &mut S {
+        &mut self.inner
+    }
+
+    /// Writes the pending compressed data to the underlying stream.
+    fn poll_write_output(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        if let Some(compressor) = &mut self.compressor {
+            let output = compressor.output();
+            while !output.is_empty() {
+                let written = ready!(Pin::new(&mut self.inner).poll_write(cx, output))?;
+                if written == 0 {
+                    return Poll::Ready(Err(ErrorKind::WriteZero.into()));
+                }
+                output.advance(written);
+            }
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<S> AsyncWrite for CompressedStream<S>
+where
+    S: AsyncWrite + Unpin,
+{
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        if this.compressor.is_none() {
+            return Pin::new(&mut this.inner).poll_write(cx, buf);
+        }
+
+        ready!(this.poll_write_output(cx))?;
+        if let Some(compressor) = &mut this.compressor {
+            compressor.write(buf)?;
+            this.needs_flush = true;
+        }
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        if this.needs_flush {
+            if let Some(compressor) = &mut this.compressor {
+                compressor.flush()?;
+            }
+            this.needs_flush = false;
+        }
+
+        ready!(this.poll_write_output(cx))?;
+        Pin::new(&mut this.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        if !this.finished {
+            if let Some(compressor) = &mut this.compressor {
+                compressor.finish()?;
+            }
+            this.needs_flush = false;
+            this.finished = true;
+        }
+
+        ready!(this.poll_write_output(cx))?;
+        Pin::new(&mut this.inner).poll_shutdown(cx)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use tokio::net::TcpListener;
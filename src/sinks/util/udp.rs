@@ -5,11 +5,12 @@ use std::{
 };
 
 use async_trait::async_trait;
-use bytes::BytesMut;
+use bytes::{Bytes, BytesMut};
 use futures::{stream::BoxStream, FutureExt, StreamExt};
 use snafu::{ResultExt, Snafu};
 use tokio::{net::UdpSocket, time::sleep};
 use tokio_util::codec::Encoder;
+use vector_lib::codecs::encoding::{Chunker, ChunkingConfig};
 use vector_lib::configurable::configurable_component;
 use vector_lib::internal_event::{ByteSize, BytesSent, InternalEventHandle, Protocol, Registered};
 use vector_lib::EstimatedJsonEncodedSizeOf;
@@ -20,8 +21,8 @@ use crate::{
     dns,
     event::{Event, EventStatus, Finalizable},
     internal_events::{
-        SocketEventsSent, SocketMode, SocketSendError, UdpSendIncompleteError,
-        UdpSocketConnectionEstablished, UdpSocketOutgoingConnectionError,
+        EncoderSerializeError, SocketEventsSent, SocketMode, SocketSendError,
+        UdpSendIncompleteError, UdpSocketConnectionEstablished, UdpSocketOutgoingConnectionError,
     },
     net,
     sinks::{
@@ -61,6 +62,14 @@ pub struct UdpSinkConfig {
     #[configurable(metadata(docs::type_unit = "bytes"))]
     #[configurable(metadata(docs::examples = 65536))]
     send_buffer_bytes: Option<usize>,
+
+    /// Configures splitting the encoded events into chunks sent in separate datagrams, for
+    /// receivers that reassemble them.
+    ///
+    /// By default, each event is sent in a single datagram.
+    #[configurable(derived)]
+    #[serde(default)]
+    chunking: Option<ChunkingConfig>,
 }
 
 impl UdpSinkConfig {
@@ -68,6 +77,7 @@ impl UdpSinkConfig {
         Self {
             address,
             send_buffer_bytes: None,
+            chunking: None,
         }
     }
 
@@ -88,7 +98,12 @@ impl UdpSinkConfig {
             + 'static,
     ) -> crate::Result<(VectorSink, Healthcheck)> {
         let connector = self.build_connector()?;
-        let sink = UdpSink::new(connector.clone(), transformer, encoder);
+        let chunker = self
+            .chunking
+            .as_ref()
+            .map(ChunkingConfig::build)
+            .transpose()?;
+        let sink = UdpSink::new(connector.clone(), transformer, encoder, chunker);
         Ok((
             VectorSink::from_event_streamsink(sink),
             async move { connector.healthcheck().await }.boxed(),
@@ -171,6 +186,7 @@ where
     connector: UdpConnector,
     transformer: Transformer,
     encoder: E,
+    chunker: Option<Chunker>,
     bytes_sent: Registered<BytesSent>,
 }
 
@@ -178,11 +194,17 @@ impl<E> UdpSink<E>
 where
     E: Encoder<Event, Error = vector_lib::codecs::encoding::Error> + Clone + Send + Sync,
 {
-    fn new(connector: UdpConnector, transformer: Transformer, encoder: E) -> Self {
+    fn new(
+        connector: UdpConnector,
+        transformer: Transformer,
+        encoder: E,
+        chunker: Option<Chunker>,
+    ) -> Self {
         Self {
             connector,
             transformer,
             encoder,
+            chunker,
             bytes_sent: register!(BytesSent::from(Protocol::UDP)),
         }
     }
@@ -212,7 +234,20 @@ where
                     continue;
                 }
 
-                match udp_send(&mut socket, &bytes).await {
+                let datagrams = match &self.chunker {
+                    Some(chunker) => match chunker.chunk(bytes.freeze()) {
+                        Ok(chunks) => chunks,
+                        Err(error) => {
+                            emit!(EncoderSerializeError { error: &error });
+                            finalizers.update_status(EventStatus::Errored);
+                            continue;
+                        }
+                    },
+                    None => vec![bytes.freeze()],
+                };
+                let byte_size_sent = datagrams.iter().map(|datagram| datagram.len()).sum();
+
+                match udp_send_all(&mut socket, &datagrams).await {
                     Ok(()) => {
                         emit!(SocketEventsSent {
                             mode: SocketMode::Udp,
@@ -220,7 +255,7 @@ where
                             byte_size,
                         });
 
-                        self.bytes_sent.emit(ByteSize(bytes.len()));
+                        self.bytes_sent.emit(ByteSize(byte_size_sent));
                         finalizers.update_status(EventStatus::Delivered);
                     }
                     Err(error) => {
@@ -239,6 +274,13 @@ where
     }
 }
 
+async fn udp_send_all(socket: &mut UdpSocket, datagrams: &[Bytes]) -> tokio::io::Result<()> {
+    for datagram in datagrams {
+        udp_send(socket, datagram).await?;
+    }
+    Ok(())
+}
+
 async fn udp_send(socket: &mut UdpSocket, buf: &[u8]) -> tokio::io::Result<()> {
     let sent = socket.send(buf).await?;
     if sent != buf.len() {
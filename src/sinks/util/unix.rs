@@ -6,21 +6,23 @@ use futures::{stream::BoxStream, SinkExt, StreamExt};
 use snafu::{ResultExt, Snafu};
 use tokio::{net::UnixStream, time::sleep};
 use tokio_util::codec::Encoder;
+use tower::ServiceExt;
 use vector_lib::configurable::configurable_component;
 use vector_lib::json_size::JsonSize;
 use vector_lib::{ByteSizeOf, EstimatedJsonEncodedSizeOf};
 
 use crate::{
     codecs::Transformer,
-    event::{Event, Finalizable},
+    event::{Event, EventStatus, Finalizable},
     internal_events::{
-        ConnectionOpen, OpenGauge, SocketMode, UnixSocketConnectionEstablished,
-        UnixSocketOutgoingConnectionError, UnixSocketSendError,
+        ConnectionOpen, OpenGauge, SocketBytesSent, SocketEventsSent, SocketMode,
+        UnixSocketConnectionEstablished, UnixSocketOutgoingConnectionError, UnixSocketSendError,
     },
     sink_ext::VecSinkExt,
     sinks::{
         util::{
             retries::ExponentialBackoff,
+            service::net::{NetworkService, UnixConnectorConfig, UnixMode},
             socket_bytes_sink::{BytesSink, ShutdownCheck},
             EncodedEvent, StreamSink,
         },
@@ -46,11 +48,24 @@ pub struct UnixSinkConfig {
     /// This should be an absolute path.
     #[configurable(metadata(docs::examples = "/path/to/socket"))]
     pub path: PathBuf,
+
+    /// The Unix socket mode to use.
+    ///
+    /// In datagram mode, each event is sent in its own datagram.
+    #[serde(default = "default_unix_mode")]
+    pub unix_mode: UnixMode,
+}
+
+const fn default_unix_mode() -> UnixMode {
+    UnixMode::Stream
 }
 
 impl UnixSinkConfig {
     pub const fn new(path: PathBuf) -> Self {
-        Self { path }
+        Self {
+            path,
+            unix_mode: UnixMode::Stream,
+        }
     }
 
     pub fn build(
@@ -62,12 +77,31 @@ impl UnixSinkConfig {
             + Sync
             + 'static,
     ) -> crate::Result<(VectorSink, Healthcheck)> {
-        let connector = UnixConnector::new(self.path.clone());
-        let sink = UnixSink::new(connector.clone(), transformer, encoder);
-        Ok((
-            VectorSink::from_event_streamsink(sink),
-            Box::pin(async move { connector.healthcheck().await }),
-        ))
+        match self.unix_mode {
+            UnixMode::Stream => {
+                let connector = UnixConnector::new(self.path.clone());
+                let sink = UnixSink::new(connector.clone(), transformer, encoder);
+                Ok((
+                    VectorSink::from_event_streamsink(sink),
+                    Box::pin(async move { connector.healthcheck().await }),
+                ))
+            }
+            UnixMode::Datagram => {
+                let connector = UnixConnectorConfig::from_path(&self.path)
+                    .with_unix_mode(UnixMode::Datagram)
+                    .as_connector();
+                let sink = UnixDatagramSink {
+                    path: self.path.clone(),
+                    service: connector.service(),
+                    transformer,
+                    encoder,
+                };
+                Ok((
+                    VectorSink::from_event_streamsink(sink),
+                    connector.healthcheck(),
+                ))
+            }
+        }
     }
 }
 
@@ -154,28 +188,7 @@ where
         let mut encoder = self.encoder.clone();
         let transformer = self.transformer.clone();
         let mut input = input
-            .map(|mut event| {
-                let byte_size = event.size_of();
-                let json_byte_size = event.estimated_json_encoded_size_of();
-
-                transformer.transform(&mut event);
-
-                let finalizers = event.take_finalizers();
-                let mut bytes = BytesMut::new();
-
-                // Errors are handled by `Encoder`.
-                if encoder.encode(event, &mut bytes).is_ok() {
-                    let item = bytes.freeze();
-                    EncodedEvent {
-                        item,
-                        finalizers,
-                        byte_size,
-                        json_byte_size,
-                    }
-                } else {
-                    EncodedEvent::new(Bytes::new(), 0, JsonSize::zero())
-                }
-            })
+            .map(|event| encode_event(event, &transformer, &mut encoder))
             .peekable();
 
         while Pin::new(&mut input).peek().await.is_some() {
@@ -199,10 +212,100 @@ where
     }
 }
 
+/// A sink sending each event in its own Unix datagram.
+///
+/// The datagrams are sent with the `NetworkService` of the Unix connector, which reconnects to the
+/// socket after a failed send.
+struct UnixDatagramSink<E> {
+    path: PathBuf,
+    service: NetworkService,
+    transformer: Transformer,
+    encoder: E,
+}
+
+#[async_trait]
+impl<E> StreamSink<Event> for UnixDatagramSink<E>
+where
+    E: Encoder<Event, Error = vector_lib::codecs::encoding::Error> + Clone + Send + Sync,
+{
+    async fn run(mut self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let mut encoder = self.encoder.clone();
+        let transformer = self.transformer.clone();
+        let mut input = input.map(|event| encode_event(event, &transformer, &mut encoder));
+
+        while let Some(event) = input.next().await {
+            // Events that failed to encode are left empty, and there is nothing to send for them.
+            if event.item.is_empty() {
+                continue;
+            }
+
+            let result = match self.service.ready().await {
+                Ok(service) => service.call(event.item.to_vec()).await,
+                Err(error) => Err(error),
+            };
+            match result {
+                Ok(sent) => {
+                    event.finalizers.update_status(EventStatus::Delivered);
+                    emit!(SocketEventsSent {
+                        mode: SocketMode::Unix,
+                        count: 1,
+                        byte_size: event.json_byte_size,
+                    });
+                    emit!(SocketBytesSent {
+                        mode: SocketMode::Unix,
+                        byte_size: sent,
+                    });
+                }
+                Err(error) => {
+                    event.finalizers.update_status(EventStatus::Errored);
+                    emit!(UnixSocketSendError {
+                        error: &error,
+                        path: &self.path
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn encode_event<E>(
+    mut event: Event,
+    transformer: &Transformer,
+    encoder: &mut E,
+) -> EncodedEvent<Bytes>
+where
+    E: Encoder<Event, Error = vector_lib::codecs::encoding::Error>,
+{
+    let byte_size = event.size_of();
+    let json_byte_size = event.estimated_json_encoded_size_of();
+
+    transformer.transform(&mut event);
+
+    let finalizers = event.take_finalizers();
+    let mut bytes = BytesMut::new();
+
+    // Errors are handled by `Encoder`.
+    if encoder.encode(event, &mut bytes).is_ok() {
+        let item = bytes.freeze();
+        EncodedEvent {
+            item,
+            finalizers,
+            byte_size,
+            json_byte_size,
+        }
+    } else {
+        EncodedEvent::new(Bytes::new(), 0, JsonSize::zero())
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use tokio::net::UnixListener;
-    use vector_lib::codecs::{encoding::Framer, NewlineDelimitedEncoder, TextSerializerConfig};
+    use tokio::net::{UnixDatagram, UnixListener};
+    use vector_lib::codecs::{
+        encoding::Framer, BytesEncoder, NewlineDelimitedEncoder, TextSerializerConfig,
+    };
 
     use super::*;
     use crate::{
@@ -276,4 +379,48 @@ mod tests {
         // Receive the data sent by the Sink to the receiver
         assert_eq!(input_lines, receiver.await);
     }
+
+    #[tokio::test]
+    async fn basic_unix_datagram_sink() {
+        let num_lines = 1000;
+        let out_path = temp_uds_path("unix_datagram_test");
+
+        // Set up server to receive events from the Sink.
+        let receiver = UnixDatagram::bind(&out_path).unwrap();
+        let receiver = tokio::spawn(async move {
+            let mut buf = [0; 1024];
+            let mut lines = Vec::with_capacity(num_lines);
+            while lines.len() < num_lines {
+                let len = receiver.recv(&mut buf).await.unwrap();
+                lines.push(String::from_utf8(buf[..len].to_vec()).unwrap());
+            }
+            lines
+        });
+
+        // Set up Sink
+        let config = UnixSinkConfig {
+            path: out_path,
+            unix_mode: UnixMode::Datagram,
+        };
+        let (sink, healthcheck) = config
+            .build(
+                Default::default(),
+                Encoder::<Framer>::new(
+                    BytesEncoder::new().into(),
+                    TextSerializerConfig::default().build().into(),
+                ),
+            )
+            .unwrap();
+        healthcheck.await.expect("Healthcheck failed");
+
+        // Send the test data
+        let (input_lines, events) = random_lines_with_stream(100, num_lines, None);
+
+        assert_sink_compliance(&SINK_TAGS, async move { sink.run(events).await })
+            .await
+            .expect("Running sink failed");
+
+        // Each event is received in its own datagram, without framing.
+        assert_eq!(input_lines, receiver.await.unwrap());
+    }
 }
@@ -4,7 +4,7 @@ use hyper_openssl::HttpsConnector;
 use hyper_proxy::ProxyConnector;
 use tonic::body::BoxBody;
 use tower::ServiceBuilder;
-use vector_lib::configurable::configurable_component;
+use vector_lib::{configurable::configurable_component, event::proto::NATIVE_VERSION};
 
 use super::{
     service::{VectorResponse, VectorService},
@@ -160,8 +160,15 @@ async fn healthcheck(
     }
 
     let request = service.client.health_check(proto::HealthCheckRequest {});
-    match request.await {
-        Ok(response) => match proto::ServingStatus::try_from(response.into_inner().status) {
+    match request.await.map(tonic::Response::into_inner) {
+        // Sources that predate the versioning of the native encoding report version `0` for both.
+        Ok(response) if !(response.min_version..=response.version).contains(&NATIVE_VERSION) => {
+            Err(Box::new(VectorSinkError::IncompatibleVersion {
+                min_version: response.min_version,
+                version: response.version,
+            }))
+        }
+        Ok(response) => match proto::ServingStatus::try_from(response.status) {
             Ok(proto::ServingStatus::Serving) => Ok(()),
             Ok(status) => Err(Box::new(VectorSinkError::Health {
                 status: Some(status.as_str_name()),
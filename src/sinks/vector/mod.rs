@@ -26,6 +26,16 @@ pub enum VectorSinkError {
     #[snafu(display("Vector source unhealthy: {:?}", status))]
     Health { status: Option<&'static str> },
 
+    #[snafu(display(
+        "Vector source decodes versions {} to {} of the native event encoding, which don't include \
+         the version {} encoded by this Vector. Upgrade the older of the two to the release of the \
+         other one.",
+        min_version,
+        version,
+        vector_lib::event::proto::NATIVE_VERSION
+    ))]
+    IncompatibleVersion { min_version: u32, version: u32 },
+
     #[snafu(display("URL has no host."))]
     NoHost,
 }
@@ -47,7 +57,7 @@ mod tests {
     use prost::Message;
     use vector_lib::{
         config::{init_telemetry, Tags, Telemetry},
-        event::{BatchNotifier, BatchStatus},
+        event::{proto::NATIVE_VERSION, BatchNotifier, BatchStatus},
     };
 
     use super::config::with_default_scheme;
@@ -181,6 +191,35 @@ mod tests {
         assert_eq!(receiver.try_recv(), Ok(BatchStatus::Rejected));
     }
 
+    #[tokio::test]
+    async fn healthcheck_rejects_incompatible_versions() {
+        let in_addr = next_addr();
+
+        let config = format!(r#"address = "http://{}/""#, in_addr);
+        let config: VectorConfig = toml::from_str(&config).unwrap();
+
+        let (_, healthcheck) = config.build(SinkContext::default()).await.unwrap();
+        let (_rx, _trigger, server) = build_test_server_generic(in_addr, move || {
+            hyper::Response::builder()
+                .header("grpc-status", "0") // OK
+                .header("content-type", "application/grpc")
+                .body(hyper::Body::from(encode_body(proto::HealthCheckResponse {
+                    status: proto::ServingStatus::Serving.into(),
+                    min_version: NATIVE_VERSION + 1,
+                    version: NATIVE_VERSION + 1,
+                })))
+                .unwrap()
+        });
+
+        tokio::spawn(server);
+
+        let error = healthcheck.await.unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<VectorSinkError>(),
+            Some(VectorSinkError::IncompatibleVersion { .. })
+        ));
+    }
+
     #[test]
     fn test_with_default_scheme() {
         assert_eq!(
@@ -10,7 +10,10 @@ use vector_lib::{config::telemetry, ByteSizeOf, EstimatedJsonEncodedSizeOf};
 
 use super::service::VectorRequest;
 use crate::{
-    event::{proto::EventWrapper, Event, EventFinalizers, Finalizable},
+    event::{
+        proto::{EventWrapper, NATIVE_VERSION},
+        Event, EventFinalizers, Finalizable,
+    },
     proto::vector as proto_vector,
     sinks::util::{metadata::RequestMetadataBuilder, SinkBuilderExt, StreamSink},
 };
@@ -86,6 +89,7 @@ where
 
                 let encoded_events = proto_vector::PushEventsRequest {
                     events: event_collection.events,
+                    version: NATIVE_VERSION,
                 };
 
                 let byte_size = encoded_events.encoded_len();
@@ -0,0 +1,296 @@
+//! Configuration for the `victoriametrics` sink.
+
+use std::collections::BTreeMap;
+
+use http::{Request, StatusCode, Uri};
+use hyper::Body;
+
+use super::{
+    encoder::VictoriaMetricsEncoder,
+    request_builder::VictoriaMetricsRequestBuilder,
+    service::VictoriaMetricsServiceRequestBuilder,
+    sink::{PartitionKey, VictoriaMetricsSink},
+};
+use crate::{
+    http::{Auth, HttpClient, MaybeAuth},
+    sinks::{
+        prelude::*,
+        prometheus::{default_histogram_buckets, default_summary_quantiles},
+        util::{
+            http::{http_response_retry_logic, HttpService},
+            UriSerde,
+        },
+    },
+};
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct VictoriaMetricsDefaultBatchSettings;
+
+impl SinkBatchSettings for VictoriaMetricsDefaultBatchSettings {
+    const MAX_EVENTS: Option<usize> = Some(1_000);
+    const MAX_BYTES: Option<usize> = None;
+    const TIMEOUT_SECS: f64 = 1.0;
+}
+
+/// The format that metrics are written in.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum VictoriaMetricsFormat {
+    /// The [JSON line format][jsonl] of the `/api/v1/import` endpoint.
+    ///
+    /// Metrics are converted to series as for Prometheus, such as the `_bucket`, `_sum`, and
+    /// `_count` series of histograms, and the samples of the same series are written on the
+    /// same line.
+    ///
+    /// [jsonl]: https://docs.victoriametrics.com/#how-to-import-data-in-json-line-format
+    #[default]
+    Jsonl,
+
+    /// The [InfluxDB line protocol][influx] of the `/write` endpoint.
+    ///
+    /// VictoriaMetrics names the series after the measurement and the fields of the lines, such
+    /// as `requests_total_value` for a counter named `requests_total`.
+    ///
+    /// [influx]: https://docs.victoriametrics.com/#how-to-send-data-from-influxdb-compatible-agents-such-as-telegraf
+    Influx,
+}
+
+impl VictoriaMetricsFormat {
+    /// The path of the endpoint of the format, either on single-node VictoriaMetrics or for a
+    /// tenant on `vminsert`.
+    pub(super) fn path(self, tenant_id: Option<&str>) -> String {
+        match (self, tenant_id) {
+            (Self::Jsonl, None) => "api/v1/import".to_string(),
+            (Self::Influx, None) => "write".to_string(),
+            (Self::Jsonl, Some(tenant_id)) => {
+                format!("insert/{tenant_id}/prometheus/api/v1/import")
+            }
+            (Self::Influx, Some(tenant_id)) => format!("insert/{tenant_id}/influx/write"),
+        }
+    }
+
+    pub(super) const fn content_type(self) -> &'static str {
+        match self {
+            Self::Jsonl => "application/x-ndjson",
+            Self::Influx => "text/plain",
+        }
+    }
+}
+
+/// Configuration for the `victoriametrics` sink.
+#[configurable_component(sink(
+    "victoriametrics",
+    "Deliver metric event data to VictoriaMetrics through its native import endpoints."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct VictoriaMetricsConfig {
+    /// The endpoint of VictoriaMetrics.
+    ///
+    /// This is the address of single-node VictoriaMetrics, or of `vminsert` for the cluster
+    /// version, in which case `tenant_id` must be set.
+    #[configurable(metadata(docs::examples = "http://localhost:8428"))]
+    #[configurable(metadata(docs::examples = "http://vminsert:8480"))]
+    pub endpoint: UriSerde,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub format: VictoriaMetricsFormat,
+
+    /// The tenant that metrics are written to, on the cluster version of VictoriaMetrics.
+    ///
+    /// This is the account ID of the tenant, optionally followed by its project ID, as in
+    /// `accountID:projectID`. Metrics are written to the endpoints of the tenant on `vminsert`,
+    /// batched by tenant. If not set, metrics are written to single-node VictoriaMetrics.
+    #[configurable(metadata(docs::examples = "42"))]
+    #[configurable(metadata(docs::examples = "42:7"))]
+    #[configurable(metadata(docs::examples = "{{ tags.account_id }}"))]
+    pub tenant_id: Option<Template>,
+
+    /// Labels that VictoriaMetrics adds to all the series written by the sink.
+    ///
+    /// The labels are sent as `extra_label` query parameters, and override the labels of the same
+    /// name of the metrics.
+    #[serde(default)]
+    #[configurable(metadata(docs::additional_props_description = "An extra label."))]
+    #[configurable(metadata(docs::examples = "extra_labels_examples()"))]
+    pub extra_labels: BTreeMap<String, String>,
+
+    /// The default namespace for any metrics sent.
+    ///
+    /// This namespace is only used if a metric has no existing namespace. When a namespace is
+    /// present, it is used as a prefix to the metric name, and separated with an underscore (`_`).
+    #[configurable(metadata(docs::examples = "service"))]
+    pub default_namespace: Option<String>,
+
+    /// Default buckets to use for aggregating [distribution][dist_metric_docs] metrics into histograms.
+    ///
+    /// [dist_metric_docs]: https://vector.dev/docs/about/under-the-hood/architecture/data-model/metric/#distribution
+    #[serde(default = "default_histogram_buckets")]
+    #[configurable(metadata(docs::advanced))]
+    pub buckets: Vec<f64>,
+
+    /// Quantiles to use for aggregating [distribution][dist_metric_docs] metrics into a summary.
+    ///
+    /// [dist_metric_docs]: https://vector.dev/docs/about/under-the-hood/architecture/data-model/metric/#distribution
+    #[serde(default = "default_summary_quantiles")]
+    #[configurable(metadata(docs::advanced))]
+    pub quantiles: Vec<f64>,
+
+    #[configurable(derived)]
+    #[serde(default = "Compression::gzip_default")]
+    pub compression: Compression,
+
+    #[configurable(derived)]
+    pub auth: Option<Auth>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch: BatchConfig<VictoriaMetricsDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::is_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+fn extra_labels_examples() -> BTreeMap<String, String> {
+    BTreeMap::from([
+        ("cluster".to_string(), "eu-west-1".to_string()),
+        ("source".to_string(), "vector".to_string()),
+    ])
+}
+
+impl GenerateConfig for VictoriaMetricsConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(r#"endpoint = "http://localhost:8428""#).unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "victoriametrics")]
+impl SinkConfig for VictoriaMetricsConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let endpoint = self.endpoint.with_default_parts().uri;
+        let auth = self.auth.choose_one(&self.endpoint.auth)?;
+
+        let tls_settings = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new(tls_settings, &cx.proxy)?;
+
+        let service_request_builder = VictoriaMetricsServiceRequestBuilder {
+            endpoint: endpoint.clone(),
+            format: self.format,
+            extra_labels: self.extra_labels.clone(),
+            auth: auth.clone(),
+            compression: self.compression,
+        };
+
+        let service: HttpService<VictoriaMetricsServiceRequestBuilder, PartitionKey> =
+            HttpService::new(client.clone(), service_request_builder);
+
+        let service = ServiceBuilder::new()
+            .settings(self.request.into_settings(), http_response_retry_logic())
+            .service(service);
+
+        let request_builder = VictoriaMetricsRequestBuilder {
+            compression: self.compression,
+            encoder: VictoriaMetricsEncoder {
+                format: self.format,
+                default_namespace: self.default_namespace.clone(),
+                buckets: self.buckets.clone(),
+                quantiles: self.quantiles.clone(),
+            },
+        };
+
+        let sink = VictoriaMetricsSink {
+            batch_settings: self.batch.validate()?.into_batcher_settings()?,
+            service,
+            tenant_id: self.tenant_id.clone(),
+            request_builder,
+        };
+
+        let healthcheck = Box::pin(healthcheck(client, endpoint, auth));
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::metric()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+/// Joins the path of an endpoint of VictoriaMetrics to the endpoint of the sink.
+pub(super) fn endpoint_uri(endpoint: &Uri, path: &str) -> String {
+    let mut uri = endpoint.to_string();
+    if !uri.ends_with('/') {
+        uri.push('/');
+    }
+    uri.push_str(path);
+    uri
+}
+
+/// Checks that VictoriaMetrics, or `vminsert`, reports itself as healthy.
+async fn healthcheck(client: HttpClient, endpoint: Uri, auth: Option<Auth>) -> crate::Result<()> {
+    let mut request = Request::get(endpoint_uri(&endpoint, "health"))
+        .body(Body::empty())
+        .unwrap();
+
+    if let Some(auth) = auth {
+        auth.apply(&mut request);
+    }
+
+    let response = client.send(request).await?;
+
+    match response.status() {
+        StatusCode::OK => Ok(()),
+        status => Err(HealthcheckError::UnexpectedStatus { status }.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<VictoriaMetricsConfig>();
+    }
+
+    #[test]
+    fn builds_endpoint_paths() {
+        let endpoint: Uri = "http://localhost:8428".parse().unwrap();
+        assert_eq!(
+            endpoint_uri(&endpoint, &VictoriaMetricsFormat::Jsonl.path(None)),
+            "http://localhost:8428/api/v1/import"
+        );
+        assert_eq!(
+            endpoint_uri(&endpoint, &VictoriaMetricsFormat::Influx.path(None)),
+            "http://localhost:8428/write"
+        );
+
+        let endpoint: Uri = "http://vminsert:8480/".parse().unwrap();
+        assert_eq!(
+            endpoint_uri(&endpoint, &VictoriaMetricsFormat::Jsonl.path(Some("42:7"))),
+            "http://vminsert:8480/insert/42:7/prometheus/api/v1/import"
+        );
+        assert_eq!(
+            endpoint_uri(&endpoint, &VictoriaMetricsFormat::Influx.path(Some("42"))),
+            "http://vminsert:8480/insert/42/influx/write"
+        );
+    }
+}
@@ -0,0 +1,268 @@
+//! Encoding of metrics into the import formats of VictoriaMetrics.
+
+use std::{collections::BTreeMap, io};
+
+use bytes::{BufMut, BytesMut};
+use chrono::Utc;
+use indexmap::IndexMap;
+use serde::Serialize;
+use vector_lib::{
+    event::{Metric, MetricTags},
+    prometheus::parser::{proto, METRIC_NAME_LABEL},
+};
+
+use super::config::VictoriaMetricsFormat;
+use crate::{
+    internal_events::InfluxdbEncodingError,
+    sinks::{
+        influxdb::{
+            encode_timestamp, influx_line_protocol, metrics::get_type_and_fields, ProtocolVersion,
+        },
+        prelude::*,
+        prometheus::collector::MetricCollector,
+        util::encode_namespace,
+    },
+};
+
+/// Encodes batches of metrics in the configured format.
+#[derive(Clone, Debug)]
+pub(super) struct VictoriaMetricsEncoder {
+    pub(super) format: VictoriaMetricsFormat,
+    pub(super) default_namespace: Option<String>,
+    pub(super) buckets: Vec<f64>,
+    pub(super) quantiles: Vec<f64>,
+}
+
+impl encoding::Encoder<Vec<Metric>> for VictoriaMetricsEncoder {
+    fn encode_input(
+        &self,
+        input: Vec<Metric>,
+        writer: &mut dyn io::Write,
+    ) -> io::Result<(usize, GroupedCountByteSize)> {
+        let mut byte_size = telemetry().create_request_count_byte_size();
+        for metric in &input {
+            byte_size.add_event(metric, metric.estimated_json_encoded_size_of());
+        }
+
+        let len = input.len();
+        let body = match self.format {
+            VictoriaMetricsFormat::Jsonl => self.encode_jsonl(&input),
+            VictoriaMetricsFormat::Influx => self.encode_influx(&input),
+        };
+
+        write_all(writer, len, body.as_ref())?;
+
+        Ok((body.len(), byte_size))
+    }
+}
+
+impl VictoriaMetricsEncoder {
+    fn encode_jsonl(&self, metrics: &[Metric]) -> BytesMut {
+        let mut collector = ImportCollector::new();
+        for metric in metrics {
+            collector.encode_metric(
+                self.default_namespace.as_deref(),
+                &self.buckets,
+                &self.quantiles,
+                metric,
+            );
+        }
+        collector.finish()
+    }
+
+    fn encode_influx(&self, metrics: &[Metric]) -> BytesMut {
+        let mut output = BytesMut::new();
+        for metric in metrics {
+            let measurement = encode_namespace(
+                metric.namespace().or(self.default_namespace.as_deref()),
+                '_',
+                metric.name(),
+            );
+            let (_, fields) = get_type_and_fields(metric.value(), &self.quantiles);
+
+            if let Err(error_message) = influx_line_protocol(
+                ProtocolVersion::V1,
+                &measurement,
+                metric.tags().cloned(),
+                fields,
+                encode_timestamp(metric.timestamp()),
+                &mut output,
+            ) {
+                emit!(InfluxdbEncodingError {
+                    error_message,
+                    count: 1
+                });
+            }
+        }
+        output
+    }
+}
+
+/// A line of the JSON line format, with the samples of a series.
+#[derive(Serialize)]
+struct ImportLine<'a> {
+    metric: &'a BTreeMap<String, String>,
+    values: &'a [f64],
+    timestamps: &'a [i64],
+}
+
+#[derive(Default)]
+struct Samples {
+    values: Vec<f64>,
+    timestamps: Vec<i64>,
+}
+
+/// Collects the samples of the series of metrics, grouped by series in the order they first
+/// appear in the batch.
+struct ImportCollector {
+    buffer: IndexMap<BTreeMap<String, String>, Samples>,
+    timestamp: Option<i64>,
+}
+
+impl ImportCollector {
+    fn default_timestamp(&mut self) -> i64 {
+        *self
+            .timestamp
+            .get_or_insert_with(|| Utc::now().timestamp_millis())
+    }
+}
+
+impl MetricCollector for ImportCollector {
+    type Output = BytesMut;
+
+    fn new() -> Self {
+        Self {
+            buffer: IndexMap::new(),
+            timestamp: None,
+        }
+    }
+
+    // The import formats don't carry metadata.
+    fn emit_metadata(&mut self, _name: &str, _fullname: &str, _type: proto::MetricType) {}
+
+    fn emit_value(
+        &mut self,
+        timestamp_millis: Option<i64>,
+        name: &str,
+        suffix: &str,
+        value: f64,
+        tags: Option<&MetricTags>,
+        extra: Option<(&str, String)>,
+    ) {
+        let mut labels = tags
+            .map(|tags| {
+                tags.iter_single()
+                    .map(|(name, value)| (name.to_string(), value.to_string()))
+                    .collect::<BTreeMap<_, _>>()
+            })
+            .unwrap_or_default();
+        labels.insert(METRIC_NAME_LABEL.to_string(), [name, suffix].join(""));
+        if let Some((name, value)) = extra {
+            labels.insert(name.to_string(), value);
+        }
+
+        let timestamp = timestamp_millis.unwrap_or_else(|| self.default_timestamp());
+        let samples = self.buffer.entry(labels).or_default();
+        samples.values.push(value);
+        samples.timestamps.push(timestamp);
+    }
+
+    fn finish(self) -> BytesMut {
+        let mut output = BytesMut::new().writer();
+        for (metric, samples) in &self.buffer {
+            let line = ImportLine {
+                metric,
+                values: &samples.values,
+                timestamps: &samples.timestamps,
+            };
+            serde_json::to_writer(&mut output, &line).expect("Serializing a line can't fail");
+            output.get_mut().put_u8(b'\n');
+        }
+        output.into_inner()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_lib::event::{MetricKind, MetricValue, StatisticKind};
+    use vector_lib::metric_tags;
+
+    use super::*;
+    use crate::sinks::influxdb::test_util::ts;
+
+    fn encoder(format: VictoriaMetricsFormat) -> VictoriaMetricsEncoder {
+        VictoriaMetricsEncoder {
+            format,
+            default_namespace: Some("vector".to_string()),
+            buckets: vec![1.0, 2.0],
+            quantiles: vec![0.5],
+        }
+    }
+
+    fn encode(encoder: &VictoriaMetricsEncoder, metrics: Vec<Metric>) -> String {
+        let mut output = Vec::new();
+        encoding::Encoder::encode_input(encoder, metrics, &mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    fn counter(value: f64, seconds: i64) -> Metric {
+        Metric::new(
+            "requests_total",
+            MetricKind::Absolute,
+            MetricValue::Counter { value },
+        )
+        .with_namespace(Some("shop"))
+        .with_tags(Some(metric_tags!("region" => "eu-west")))
+        .with_timestamp(Some(ts() + chrono::Duration::seconds(seconds)))
+    }
+
+    #[test]
+    fn encodes_jsonl_series() {
+        let histogram = Metric::new(
+            "latency",
+            MetricKind::Absolute,
+            MetricValue::Distribution {
+                samples: vector_lib::samples![1.0 => 1, 3.0 => 2],
+                statistic: StatisticKind::Histogram,
+            },
+        )
+        .with_timestamp(Some(ts()));
+
+        let output = encode(
+            &encoder(VictoriaMetricsFormat::Jsonl),
+            vec![counter(1.0, 0), histogram, counter(3.0, 10)],
+        );
+        let lines = output.lines().collect::<Vec<_>>();
+        assert_eq!(
+            lines,
+            [
+                r#"{"metric":{"__name__":"shop_requests_total","region":"eu-west"},"values":[1.0,3.0],"timestamps":[1542182950000,1542182960000]}"#,
+                r#"{"metric":{"__name__":"vector_latency_bucket","le":"1"},"values":[1.0],"timestamps":[1542182950000]}"#,
+                r#"{"metric":{"__name__":"vector_latency_bucket","le":"2"},"values":[1.0],"timestamps":[1542182950000]}"#,
+                r#"{"metric":{"__name__":"vector_latency_bucket","le":"+Inf"},"values":[3.0],"timestamps":[1542182950000]}"#,
+                r#"{"metric":{"__name__":"vector_latency_sum"},"values":[7.0],"timestamps":[1542182950000]}"#,
+                r#"{"metric":{"__name__":"vector_latency_count"},"values":[3.0],"timestamps":[1542182950000]}"#,
+            ]
+        );
+    }
+
+    #[test]
+    fn encodes_influx_lines() {
+        let gauge = Metric::new(
+            "uptime",
+            MetricKind::Absolute,
+            MetricValue::Gauge { value: 12.0 },
+        )
+        .with_timestamp(Some(ts()));
+
+        let output = encode(
+            &encoder(VictoriaMetricsFormat::Influx),
+            vec![counter(1.0, 0), gauge],
+        );
+        assert_eq!(
+            output,
+            "shop_requests_total,region=eu-west value=1 1542182950000000011\n\
+             vector_uptime value=12 1542182950000000011\n"
+        );
+    }
+}
@@ -0,0 +1,32 @@
+//! The `victoriametrics` sink.
+//!
+//! This sink writes metrics to [VictoriaMetrics][victoriametrics] through its native import
+//! endpoints, rather than through the Prometheus remote write protocol, whose requests are
+//! Snappy-compressed Protocol Buffers.
+//!
+//! Metrics are written in one of the following formats:
+//!
+//! - The [JSON line format][jsonl] of the `/api/v1/import` endpoint, with the metrics converted
+//!   to series the same way as by the `prometheus_remote_write` sink, and the samples of the same
+//!   series in a batch written on the same line.
+//! - The [InfluxDB line protocol][influx] of the `/write` endpoint, with a line per metric named
+//!   after its namespace and name, and fields such as `value` for counters and gauges.
+//!
+//! The configured extra labels are added by VictoriaMetrics to all the series of the requests,
+//! from the `extra_label` query parameters. With a tenant ID, requests are sent to the endpoints
+//! of the tenant on `vminsert`, the ingestion component of the cluster version.
+//!
+//! [victoriametrics]: https://victoriametrics.com/
+//! [jsonl]: https://docs.victoriametrics.com/#how-to-import-data-in-json-line-format
+//! [influx]: https://docs.victoriametrics.com/#how-to-send-data-from-influxdb-compatible-agents-such-as-telegraf
+
+mod config;
+mod encoder;
+mod request_builder;
+mod service;
+mod sink;
+
+#[cfg(test)]
+mod tests;
+
+pub use self::config::VictoriaMetricsConfig;
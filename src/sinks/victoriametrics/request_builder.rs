@@ -0,0 +1,52 @@
+//! `RequestBuilder` implementation for the `victoriametrics` sink.
+
+use std::io;
+
+use bytes::Bytes;
+use vector_lib::event::Metric;
+
+use super::{encoder::VictoriaMetricsEncoder, sink::PartitionKey};
+use crate::sinks::{prelude::*, util::http::HttpRequest};
+
+pub(super) struct VictoriaMetricsRequestBuilder {
+    pub(super) compression: Compression,
+    pub(super) encoder: VictoriaMetricsEncoder,
+}
+
+impl RequestBuilder<(PartitionKey, Vec<Metric>)> for VictoriaMetricsRequestBuilder {
+    type Metadata = (PartitionKey, EventFinalizers);
+    type Events = Vec<Metric>;
+    type Encoder = VictoriaMetricsEncoder;
+    type Payload = Bytes;
+    type Request = HttpRequest<PartitionKey>;
+    type Error = io::Error;
+
+    fn compression(&self) -> Compression {
+        self.compression
+    }
+
+    fn encoder(&self) -> &Self::Encoder {
+        &self.encoder
+    }
+
+    fn split_input(
+        &self,
+        input: (PartitionKey, Vec<Metric>),
+    ) -> (Self::Metadata, RequestMetadataBuilder, Self::Events) {
+        let (key, mut metrics) = input;
+
+        let finalizers = metrics.take_finalizers();
+        let builder = RequestMetadataBuilder::from_events(&metrics);
+        ((key, finalizers), builder, metrics)
+    }
+
+    fn build_request(
+        &self,
+        metadata: Self::Metadata,
+        request_metadata: RequestMetadata,
+        payload: EncodeResult<Self::Payload>,
+    ) -> Self::Request {
+        let (key, finalizers) = metadata;
+        HttpRequest::new(payload.into_payload(), finalizers, request_metadata, key)
+    }
+}
@@ -0,0 +1,98 @@
+//! Service implementation for the `victoriametrics` sink.
+
+use std::collections::BTreeMap;
+
+use bytes::Bytes;
+use http::{
+    header::{CONTENT_ENCODING, CONTENT_LENGTH, CONTENT_TYPE},
+    Request, Uri,
+};
+use snafu::ResultExt;
+
+use super::{
+    config::{endpoint_uri, VictoriaMetricsFormat},
+    sink::PartitionKey,
+};
+use crate::{
+    http::Auth,
+    sinks::{
+        prelude::*,
+        util::http::{HttpRequest, HttpServiceRequestBuilder},
+        HTTPRequestBuilderSnafu,
+    },
+};
+
+#[derive(Debug, Clone)]
+pub(super) struct VictoriaMetricsServiceRequestBuilder {
+    pub(super) endpoint: Uri,
+    pub(super) format: VictoriaMetricsFormat,
+    pub(super) extra_labels: BTreeMap<String, String>,
+    pub(super) auth: Option<Auth>,
+    pub(super) compression: Compression,
+}
+
+impl VictoriaMetricsServiceRequestBuilder {
+    /// The URI of the import endpoint of the tenant, with the extra labels as query parameters.
+    fn uri(&self, tenant_id: Option<&str>) -> String {
+        let mut uri = endpoint_uri(&self.endpoint, &self.format.path(tenant_id));
+        if !self.extra_labels.is_empty() {
+            let mut query = url::form_urlencoded::Serializer::new(String::new());
+            for (name, value) in &self.extra_labels {
+                query.append_pair("extra_label", &format!("{name}={value}"));
+            }
+            uri.push('?');
+            uri.push_str(&query.finish());
+        }
+        uri
+    }
+}
+
+impl HttpServiceRequestBuilder<PartitionKey> for VictoriaMetricsServiceRequestBuilder {
+    fn build(
+        &self,
+        mut request: HttpRequest<PartitionKey>,
+    ) -> Result<Request<Bytes>, crate::Error> {
+        let uri = self.uri(request.get_additional_metadata().tenant_id.as_deref());
+        let payload = request.take_payload();
+
+        let mut builder = Request::post(uri)
+            .header(CONTENT_TYPE, self.format.content_type())
+            .header(CONTENT_LENGTH, payload.len());
+        if let Some(ce) = self.compression.content_encoding() {
+            builder = builder.header(CONTENT_ENCODING, ce);
+        }
+        if let Some(auth) = &self.auth {
+            builder = auth.apply_builder(builder);
+        }
+
+        builder
+            .body(payload)
+            .context(HTTPRequestBuilderSnafu)
+            .map_err(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adds_extra_labels() {
+        let builder = VictoriaMetricsServiceRequestBuilder {
+            endpoint: "http://vminsert:8480".parse().unwrap(),
+            format: VictoriaMetricsFormat::Jsonl,
+            extra_labels: BTreeMap::from([
+                ("cluster".to_string(), "eu-west-1".to_string()),
+                ("source".to_string(), "vector".to_string()),
+            ]),
+            auth: None,
+            compression: Compression::None,
+        };
+
+        assert_eq!(
+            builder.uri(Some("42")),
+            "http://vminsert:8480/insert/42/prometheus/api/v1/import\
+             ?extra_label=cluster%3Deu-west-1&extra_label=source%3Dvector"
+        );
+    }
+}
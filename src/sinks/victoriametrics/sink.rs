@@ -0,0 +1,101 @@
+//! Implementation of the `victoriametrics` sink.
+
+use vector_lib::event::Metric;
+
+use super::request_builder::VictoriaMetricsRequestBuilder;
+use crate::sinks::{
+    prelude::*, prometheus::remote_write::PrometheusMetricNormalize, util::http::HttpRequest,
+};
+
+/// The tenant that metrics are written to.
+#[derive(Hash, Eq, PartialEq, Clone, Debug)]
+pub(super) struct PartitionKey {
+    pub(super) tenant_id: Option<String>,
+}
+
+/// Partitions metrics by the tenant they are written to.
+struct TenantPartitioner {
+    tenant_id: Option<Template>,
+}
+
+impl Partitioner for TenantPartitioner {
+    type Item = Metric;
+    type Key = Option<PartitionKey>;
+
+    fn partition(&self, item: &Self::Item) -> Self::Key {
+        let tenant_id = match &self.tenant_id {
+            Some(template) => Some(
+                template
+                    .render_string(item)
+                    .map_err(|error| {
+                        emit!(TemplateRenderingError {
+                            error,
+                            field: Some("tenant_id"),
+                            drop_event: true,
+                        });
+                    })
+                    .ok()?,
+            ),
+            None => None,
+        };
+        Some(PartitionKey { tenant_id })
+    }
+}
+
+pub(super) struct VictoriaMetricsSink<S> {
+    pub(super) batch_settings: BatcherSettings,
+    pub(super) service: S,
+    pub(super) tenant_id: Option<Template>,
+    pub(super) request_builder: VictoriaMetricsRequestBuilder,
+}
+
+impl<S> VictoriaMetricsSink<S>
+where
+    S: Service<HttpRequest<PartitionKey>> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: std::fmt::Debug + Into<crate::Error> + Send,
+{
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let batch_settings = self.batch_settings;
+        let partitioner = TenantPartitioner {
+            tenant_id: self.tenant_id,
+        };
+
+        input
+            .filter_map(|event| future::ready(event.try_into_metric()))
+            // Both import formats expect the values of counters to be cumulative.
+            .normalized_with_default::<PrometheusMetricNormalize>()
+            .batched_partitioned(partitioner, || batch_settings.as_byte_size_config())
+            .filter_map(|(key, batch)| async move { key.map(move |key| (key, batch)) })
+            .request_builder(
+                default_request_builder_concurrency_limit(),
+                self.request_builder,
+            )
+            .filter_map(|request| async {
+                match request {
+                    Err(error) => {
+                        emit!(SinkRequestBuildError { error });
+                        None
+                    }
+                    Ok(req) => Some(req),
+                }
+            })
+            .into_driver(self.service)
+            .run()
+            .await
+    }
+}
+
+#[async_trait::async_trait]
+impl<S> StreamSink<Event> for VictoriaMetricsSink<S>
+where
+    S: Service<HttpRequest<PartitionKey>> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: std::fmt::Debug + Into<crate::Error> + Send,
+{
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
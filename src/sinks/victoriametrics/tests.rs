@@ -0,0 +1,79 @@
+use futures::StreamExt;
+use indoc::indoc;
+use vector_lib::metric_tags;
+
+use super::VictoriaMetricsConfig;
+use crate::{
+    config::SinkConfig,
+    event::{Event, Metric, MetricKind, MetricValue},
+    sinks::{influxdb::test_util::ts, util::test::build_test_server},
+    test_util::{
+        self,
+        components::{run_and_assert_sink_compliance, HTTP_SINK_TAGS},
+    },
+};
+
+fn gauge(account_id: &str, value: f64) -> Event {
+    Metric::new("uptime", MetricKind::Absolute, MetricValue::Gauge { value })
+        .with_tags(Some(metric_tags!("account_id" => account_id)))
+        .with_timestamp(Some(ts()))
+        .into()
+}
+
+#[tokio::test]
+async fn writes_metrics_by_tenant() {
+    let addr = test_util::next_addr();
+    let (rx, trigger, server) = build_test_server(addr);
+    tokio::spawn(server);
+
+    let config: VictoriaMetricsConfig = toml::from_str(&format!(
+        indoc! {r#"
+            endpoint = "http://{}"
+            tenant_id = "{{{{ tags.account_id }}}}"
+            extra_labels.source = "vector"
+            compression = "none"
+        "#},
+        addr
+    ))
+    .unwrap();
+
+    let (sink, _healthcheck) = config
+        .build(crate::config::SinkContext::default())
+        .await
+        .unwrap();
+    let events = vec![gauge("1", 10.0), gauge("2", 20.0), gauge("1", 30.0)];
+    run_and_assert_sink_compliance(sink, futures::stream::iter(events), &HTTP_SINK_TAGS).await;
+
+    drop(trigger);
+
+    let mut requests = rx
+        .map(|(parts, body)| {
+            assert_eq!(parts.method, "POST");
+            assert_eq!(parts.headers["content-type"], "application/x-ndjson");
+            assert_eq!(parts.uri.query(), Some("extra_label=source%3Dvector"));
+            (
+                parts.uri.path().to_string(),
+                String::from_utf8(body.to_vec())
+                    .unwrap()
+                    .trim_end()
+                    .to_string(),
+            )
+        })
+        .collect::<Vec<_>>()
+        .await;
+    requests.sort();
+
+    assert_eq!(
+        requests,
+        [
+            (
+                "/insert/1/prometheus/api/v1/import".to_string(),
+                r#"{"metric":{"__name__":"uptime","account_id":"1"},"values":[10.0,30.0],"timestamps":[1542182950000,1542182950000]}"#.to_string(),
+            ),
+            (
+                "/insert/2/prometheus/api/v1/import".to_string(),
+                r#"{"metric":{"__name__":"uptime","account_id":"2"},"values":[20.0],"timestamps":[1542182950000]}"#.to_string(),
+            ),
+        ]
+    );
+}
@@ -0,0 +1,114 @@
+use std::{
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    num::NonZeroUsize,
+};
+
+use futures::FutureExt;
+use vector_lib::codecs::JsonSerializerConfig;
+use vector_lib::configurable::configurable_component;
+
+use crate::{
+    codecs::EncodingConfig,
+    config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
+    http::Auth,
+    sinks::{websocket_server::sink::WebSocketServerSink, Healthcheck, VectorSink},
+    tls::TlsEnableableConfig,
+};
+
+/// Configuration for the `websocket_server` sink.
+#[configurable_component(sink(
+    "websocket_server",
+    "Stream observability event data to WebSocket clients."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct WebSocketServerSinkConfig {
+    /// The socket address to listen for connections on.
+    ///
+    /// It _must_ include a port.
+    #[serde(default = "default_address")]
+    #[configurable(metadata(docs::examples = "0.0.0.0:8080"))]
+    pub address: SocketAddr,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsEnableableConfig>,
+
+    #[configurable(derived)]
+    pub encoding: EncodingConfig,
+
+    /// The credentials that clients must present in the `Authorization` header of the request
+    /// they connect with.
+    ///
+    /// If not set, any client can connect.
+    pub auth: Option<Auth>,
+
+    /// The number of messages buffered for each client.
+    ///
+    /// When the buffer of a client is full, because the client doesn't read its messages as fast
+    /// as events are received, the messages for that client are dropped until it catches up. The
+    /// other clients aren't affected.
+    #[serde(default = "default_subscriber_capacity")]
+    #[configurable(metadata(docs::type_unit = "messages"))]
+    pub subscriber_capacity: NonZeroUsize,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::is_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+const fn default_address() -> SocketAddr {
+    SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), 8080)
+}
+
+fn default_subscriber_capacity() -> NonZeroUsize {
+    NonZeroUsize::new(100).unwrap()
+}
+
+impl GenerateConfig for WebSocketServerSinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            address: default_address(),
+            tls: None,
+            encoding: JsonSerializerConfig::default().into(),
+            auth: None,
+            subscriber_capacity: default_subscriber_capacity(),
+            acknowledgements: Default::default(),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "websocket_server")]
+impl SinkConfig for WebSocketServerSinkConfig {
+    async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let ws_sink = WebSocketServerSink::new(self)?;
+
+        Ok((
+            VectorSink::from_event_streamsink(ws_sink),
+            futures::future::ok(()).boxed(),
+        ))
+    }
+
+    fn input(&self) -> Input {
+        Input::new(self.encoding.config().input_type())
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<WebSocketServerSinkConfig>();
+    }
+}
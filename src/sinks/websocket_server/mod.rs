@@ -0,0 +1,15 @@
+//! The `websocket_server` sink.
+//!
+//! This sink listens for WebSocket connections, and streams every event it receives to all the
+//! connected clients, as a message per event. Clients can pass a [VRL][vrl] boolean expression in
+//! the `filter` query parameter of the request they connect with, in which case they are only sent
+//! the events that match it.
+//!
+//! Events received while no client is connected, or that no client subscribes to, are dropped.
+//!
+//! [vrl]: https://vector.dev/docs/reference/vrl
+
+mod config;
+mod sink;
+
+pub use config::WebSocketServerSinkConfig;
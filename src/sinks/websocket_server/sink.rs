@@ -0,0 +1,476 @@
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+use bytes::BytesMut;
+use futures::{sink::SinkExt, stream::BoxStream, StreamExt};
+use http::{header::AUTHORIZATION, HeaderMap, StatusCode, Uri};
+use tokio::{
+    net::TcpStream,
+    sync::mpsc::{self, error::TrySendError},
+};
+use tokio_tungstenite::{
+    accept_hdr_async,
+    tungstenite::{
+        handshake::server::{ErrorResponse, Request, Response},
+        protocol::Message,
+    },
+};
+use tokio_util::codec::Encoder as _;
+use vector_lib::{
+    emit,
+    internal_event::{
+        ByteSize, BytesSent, CountByteSize, EventsSent, InternalEventHandle as _, Output, Protocol,
+    },
+    EstimatedJsonEncodedSizeOf,
+};
+
+use crate::{
+    codecs::{Encoder, Transformer},
+    conditions::{Condition, ConditionConfig, VrlConfig},
+    event::{Event, EventStatus, Finalizable},
+    http::Auth,
+    internal_events::{
+        ConnectionOpen, OpenGauge, WsListenerConnectionError, WsListenerConnectionEstablished,
+        WsListenerConnectionShutdown, WsListenerMessageDropped,
+    },
+    sinks::util::StreamSink,
+    sinks::websocket_server::config::WebSocketServerSinkConfig,
+    tls::{MaybeTlsIncomingStream, MaybeTlsListener, MaybeTlsSettings},
+};
+
+/// The query parameter that clients pass their filter in.
+const FILTER_PARAMETER: &str = "filter";
+
+/// A connected client, with the channel its messages are sent to.
+struct Client {
+    sender: mpsc::Sender<Message>,
+    filter: Option<Condition>,
+}
+
+type Clients = Arc<Mutex<HashMap<SocketAddr, Client>>>;
+
+pub struct WebSocketServerSink {
+    address: SocketAddr,
+    tls: MaybeTlsSettings,
+    transformer: Transformer,
+    encoder: Encoder<()>,
+    auth: Option<Auth>,
+    subscriber_capacity: usize,
+}
+
+impl WebSocketServerSink {
+    pub fn new(config: &WebSocketServerSinkConfig) -> crate::Result<Self> {
+        let transformer = config.encoding.transformer();
+        let serializer = config.encoding.build()?;
+        let encoder = Encoder::<()>::new(serializer);
+        let tls = MaybeTlsSettings::from_config(&config.tls, true)?;
+
+        Ok(Self {
+            address: config.address,
+            tls,
+            transformer,
+            encoder,
+            auth: config.auth.clone(),
+            subscriber_capacity: config.subscriber_capacity.get(),
+        })
+    }
+
+    const fn should_encode_as_binary(&self) -> bool {
+        use vector_lib::codecs::encoding::Serializer::{
+            Avro, Csv, Gelf, Json, Logfmt, Native, NativeJson, Protobuf, RawMessage, Text,
+        };
+
+        match self.encoder.serializer() {
+            RawMessage(_) | Avro(_) | Native(_) | Protobuf(_) => true,
+            Csv(_) | Logfmt(_) | Gelf(_) | Json(_) | Text(_) | NativeJson(_) => false,
+        }
+    }
+
+    /// The clients that subscribe to the event, along with the channels to send it to them.
+    fn subscribers(clients: &Clients, event: &Event) -> Vec<(SocketAddr, mpsc::Sender<Message>)> {
+        clients
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(_, client)| match &client.filter {
+                // Conditions take ownership of the events they check, so each filter gets a copy.
+                Some(filter) => filter.check(event.clone()).0,
+                None => true,
+            })
+            .map(|(address, client)| (*address, client.sender.clone()))
+            .collect()
+    }
+}
+
+#[async_trait]
+impl StreamSink<Event> for WebSocketServerSink {
+    async fn run(mut self: Box<Self>, mut input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let listener = self.tls.bind(&self.address).await.map_err(|error| {
+            error!(
+                "Failed to start WebSocket server on {}: {}.",
+                self.address, error
+            )
+        })?;
+        info!(message = "Listening for WebSocket connections.", address = %self.address);
+
+        let clients = Clients::default();
+        let accept = tokio::spawn(accept_clients(
+            listener,
+            Arc::clone(&clients),
+            self.auth.clone(),
+            self.subscriber_capacity,
+        ));
+
+        let bytes_sent = register!(BytesSent::from(Protocol("websocket".into())));
+        let events_sent = register!(EventsSent::from(Output(None)));
+        let encode_as_binary = self.should_encode_as_binary();
+
+        while let Some(mut event) = input.next().await {
+            let finalizers = event.take_finalizers();
+
+            // Events are only encoded when at least one client subscribes to them, and are
+            // otherwise dropped.
+            let subscribers = Self::subscribers(&clients, &event);
+            if subscribers.is_empty() {
+                finalizers.update_status(EventStatus::Delivered);
+                continue;
+            }
+
+            self.transformer.transform(&mut event);
+
+            let event_byte_size = event.estimated_json_encoded_size_of();
+
+            let mut bytes = BytesMut::new();
+            if self.encoder.encode(event, &mut bytes).is_err() {
+                // Error is handled by `Encoder`.
+                finalizers.update_status(EventStatus::Errored);
+                continue;
+            }
+
+            let message = if encode_as_binary {
+                Message::binary(bytes)
+            } else {
+                Message::text(String::from_utf8_lossy(&bytes))
+            };
+            let message_len = message.len();
+
+            for (client, sender) in subscribers {
+                match sender.try_send(message.clone()) {
+                    Ok(()) => bytes_sent.emit(ByteSize(message_len)),
+                    Err(TrySendError::Full(_)) => emit!(WsListenerMessageDropped { client }),
+                    // The client disconnected since it was looked up.
+                    Err(TrySendError::Closed(_)) => {}
+                }
+            }
+
+            events_sent.emit(CountByteSize(1, event_byte_size));
+            finalizers.update_status(EventStatus::Delivered);
+        }
+
+        // Dropping the channels of the clients closes their connections once they have been sent
+        // their remaining messages.
+        accept.abort();
+        clients.lock().unwrap().clear();
+
+        Ok(())
+    }
+}
+
+async fn accept_clients(
+    mut listener: MaybeTlsListener,
+    clients: Clients,
+    auth: Option<Auth>,
+    subscriber_capacity: usize,
+) {
+    let open_gauge = OpenGauge::new();
+
+    loop {
+        match listener.accept().await {
+            Ok(stream) => {
+                tokio::spawn(handle_client(
+                    stream,
+                    Arc::clone(&clients),
+                    auth.clone(),
+                    subscriber_capacity,
+                    open_gauge.clone(),
+                ));
+            }
+            Err(error) => emit!(WsListenerConnectionError {
+                error: Box::new(error)
+            }),
+        }
+    }
+}
+
+async fn handle_client(
+    stream: MaybeTlsIncomingStream<TcpStream>,
+    clients: Clients,
+    auth: Option<Auth>,
+    subscriber_capacity: usize,
+    open_gauge: OpenGauge,
+) {
+    let client = stream.peer_addr();
+
+    let mut filter = None;
+    let callback = |request: &Request, response: Response| {
+        if !authorized(request.headers(), &auth) {
+            return Err(error_response(
+                StatusCode::UNAUTHORIZED,
+                "Invalid credentials.".to_owned(),
+            ));
+        }
+        filter = client_filter(request.uri())
+            .map_err(|error| error_response(StatusCode::BAD_REQUEST, error))?;
+        Ok(response)
+    };
+
+    let ws_stream = match accept_hdr_async(stream, callback).await {
+        Ok(ws_stream) => ws_stream,
+        Err(error) => {
+            emit!(WsListenerConnectionError {
+                error: Box::new(error)
+            });
+            return;
+        }
+    };
+
+    let (mut ws_sink, mut ws_stream) = ws_stream.split();
+    let (sender, mut receiver) = mpsc::channel(subscriber_capacity);
+
+    clients
+        .lock()
+        .unwrap()
+        .insert(client, Client { sender, filter });
+    let _open_token = open_gauge.open(|count| emit!(ConnectionOpen { count }));
+    emit!(WsListenerConnectionEstablished { client });
+
+    loop {
+        tokio::select! {
+            message = receiver.recv() => match message {
+                Some(message) => {
+                    if let Err(error) = ws_sink.send(message).await {
+                        emit!(WsListenerConnectionError { error: Box::new(error) });
+                        break;
+                    }
+                }
+                // The sink is shutting down.
+                None => break,
+            },
+
+            // Pings are answered by tungstenite while reading from the stream, and any other
+            // message from the client is ignored.
+            message = ws_stream.next() => match message {
+                Some(Ok(Message::Close(_))) | None => break,
+                Some(Ok(_)) => {}
+                Some(Err(error)) => {
+                    emit!(WsListenerConnectionError { error: Box::new(error) });
+                    break;
+                }
+            },
+        }
+    }
+
+    clients.lock().unwrap().remove(&client);
+    _ = ws_sink.close().await;
+    emit!(WsListenerConnectionShutdown { client });
+}
+
+fn authorized(headers: &HeaderMap, auth: &Option<Auth>) -> bool {
+    match auth {
+        Some(auth) => {
+            let mut expected = HeaderMap::new();
+            auth.apply_headers_map(&mut expected);
+            headers.get(AUTHORIZATION) == expected.get(AUTHORIZATION)
+        }
+        None => true,
+    }
+}
+
+/// Compiles the filter that the client passed in the query of its request, if any.
+fn client_filter(uri: &Uri) -> Result<Option<Condition>, String> {
+    let source = uri.query().and_then(|query| {
+        url::form_urlencoded::parse(query.as_bytes())
+            .find(|(key, _)| key == FILTER_PARAMETER)
+            .map(|(_, value)| value.into_owned())
+    });
+
+    source
+        .map(|source| {
+            ConditionConfig::Vrl(VrlConfig {
+                source,
+                runtime: Default::default(),
+            })
+            .build(&Default::default())
+            .map_err(|error| format!("Invalid filter: {error}"))
+        })
+        .transpose()
+}
+
+fn error_response(status: StatusCode, message: String) -> ErrorResponse {
+    let mut response = ErrorResponse::new(Some(message));
+    *response.status_mut() = status;
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use futures::{future, stream};
+    use tokio::{sync::oneshot, time};
+    use tokio_tungstenite::{
+        connect_async,
+        tungstenite::{
+            client::IntoClientRequest, handshake::client::Request as ClientRequest,
+            Error as WsError,
+        },
+        MaybeTlsStream, WebSocketStream,
+    };
+
+    use super::*;
+    use crate::{
+        config::{SinkConfig, SinkContext},
+        event::LogEvent,
+        test_util::{
+            components::{run_and_assert_sink_compliance, SINK_TAGS},
+            next_addr, trace_init,
+        },
+    };
+
+    fn config(address: SocketAddr, auth: &str) -> WebSocketServerSinkConfig {
+        toml::from_str(&format!(
+            r#"
+                address = "{address}"
+                encoding.codec = "text"
+                {auth}
+            "#
+        ))
+        .unwrap()
+    }
+
+    fn uri(address: SocketAddr, filter: Option<&str>) -> String {
+        match filter {
+            Some(filter) => format!(
+                "ws://{address}/?{}",
+                url::form_urlencoded::Serializer::new(String::new())
+                    .append_pair(FILTER_PARAMETER, filter)
+                    .finish()
+            ),
+            None => format!("ws://{address}/"),
+        }
+    }
+
+    /// Connects to the sink, retrying until it listens for connections.
+    async fn connect(
+        address: SocketAddr,
+        filter: Option<&str>,
+    ) -> WebSocketStream<MaybeTlsStream<TcpStream>> {
+        loop {
+            match connect_async(uri(address, filter)).await {
+                Ok((ws_stream, _)) => return ws_stream,
+                Err(WsError::Io(_)) => time::sleep(Duration::from_millis(10)).await,
+                Err(error) => panic!("Failed to connect: {error}"),
+            }
+        }
+    }
+
+    async fn receive(ws_stream: WebSocketStream<MaybeTlsStream<TcpStream>>) -> Vec<String> {
+        ws_stream
+            .take_while(|message| future::ready(message.is_ok()))
+            .filter_map(|message| {
+                future::ready(match message {
+                    Ok(Message::Text(text)) => Some(text),
+                    _ => None,
+                })
+            })
+            .collect()
+            .await
+    }
+
+    #[tokio::test]
+    async fn streams_events_to_clients() {
+        trace_init();
+
+        let address = next_addr();
+        let (sink, _healthcheck) = config(address, "")
+            .build(SinkContext::default())
+            .await
+            .unwrap();
+
+        let (connected_tx, connected_rx) = oneshot::channel();
+        let clients = tokio::spawn(async move {
+            let all = connect(address, None).await;
+            let filtered = connect(address, Some(r#".message == "b""#)).await;
+            // Give the sink time to register the clients after the handshakes.
+            time::sleep(Duration::from_millis(100)).await;
+            connected_tx.send(()).unwrap();
+            (all, filtered)
+        });
+
+        let events = stream::once(connected_rx).flat_map(|_| {
+            stream::iter(["a", "b", "c"].map(|message| Event::Log(LogEvent::from(message))))
+        });
+        run_and_assert_sink_compliance(sink, events, &SINK_TAGS).await;
+
+        let (all, filtered) = clients.await.unwrap();
+        assert_eq!(receive(all).await, ["a", "b", "c"]);
+        assert_eq!(receive(filtered).await, ["b"]);
+    }
+
+    #[tokio::test]
+    async fn rejects_clients() {
+        trace_init();
+
+        let address = next_addr();
+        let auth = r#"auth = { strategy = "bearer", token = "s3cr3t" }"#;
+        let (sink, _healthcheck) = config(address, auth)
+            .build(SinkContext::default())
+            .await
+            .unwrap();
+        tokio::spawn(sink.run(stream::pending().boxed()));
+
+        let request = |filter, token: Option<&str>| {
+            let mut request = uri(address, filter).into_client_request().unwrap();
+            if let Some(token) = token {
+                request
+                    .headers_mut()
+                    .insert(AUTHORIZATION, format!("Bearer {token}").parse().unwrap());
+            }
+            request
+        };
+        let status = |request: ClientRequest| async move {
+            match connect_async(request).await {
+                Ok(_) => Ok(StatusCode::SWITCHING_PROTOCOLS),
+                Err(WsError::Http(response)) => Ok(response.status()),
+                Err(error) => Err(error),
+            }
+        };
+
+        // Wait for the sink to listen for connections.
+        let mut accepted = status(request(Some(r#".message == "a""#), Some("s3cr3t"))).await;
+        while let Err(WsError::Io(_)) = accepted {
+            time::sleep(Duration::from_millis(10)).await;
+            accepted = status(request(Some(r#".message == "a""#), Some("s3cr3t"))).await;
+        }
+        assert_eq!(accepted.unwrap(), StatusCode::SWITCHING_PROTOCOLS);
+
+        assert_eq!(
+            status(request(None, None)).await.unwrap(),
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            status(request(None, Some("wrong"))).await.unwrap(),
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            status(request(Some(".message =="), Some("s3cr3t")))
+                .await
+                .unwrap(),
+            StatusCode::BAD_REQUEST
+        );
+    }
+}
@@ -0,0 +1,490 @@
+use std::{
+    io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use aws_sdk_s3::operation::list_objects_v2::{ListObjectsV2Error, ListObjectsV2Output};
+use aws_sdk_s3::types::Object;
+use aws_smithy_runtime_api::client::orchestrator::HttpResponse;
+use aws_smithy_runtime_api::client::result::SdkError;
+use chrono::{DateTime, TimeZone, Utc};
+use futures::FutureExt;
+use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+use tokio::{pin, select};
+use vector_lib::configurable::configurable_component;
+use vector_lib::internal_event::{BytesReceived, Protocol, Registered};
+
+use super::{sqs::ProcessingError, ObjectReader};
+use crate::{
+    config::{SourceAcknowledgementsConfig, SourceContext},
+    event::{BatchStatus, BatchStatusReceiver},
+    internal_events::{
+        EventsReceived, S3ListCheckpointWriteError, S3ListObjectsError, S3ObjectProcessingError,
+    },
+    shutdown::ShutdownSignal,
+    SourceSender,
+};
+
+const CHECKPOINT_FILENAME: &str = "list_checkpoint.json";
+const LIST_RETRY_DELAY: Duration = Duration::from_secs(5);
+
+/// Bucket listing configuration options.
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub(super) struct Config {
+    /// The name of the bucket to list objects from.
+    #[configurable(metadata(docs::examples = "my-bucket"))]
+    pub(super) bucket: String,
+
+    /// Only read objects whose keys start with this prefix.
+    #[configurable(metadata(docs::examples = "logs/2024/"))]
+    pub(super) prefix: Option<String>,
+
+    /// Only read objects last modified at or after this time.
+    #[configurable(metadata(docs::examples = "2024-01-01T00:00:00Z"))]
+    pub(super) start_time: Option<DateTime<Utc>>,
+
+    /// Only read objects last modified before this time.
+    #[configurable(metadata(docs::examples = "2024-02-01T00:00:00Z"))]
+    pub(super) end_time: Option<DateTime<Utc>>,
+
+    /// The directory used to persist the listing checkpoint.
+    ///
+    /// By default, the [global `data_dir` option][global_data_dir] is used.
+    /// Make sure the running user has write permissions to this directory.
+    ///
+    /// If this directory is specified, then Vector will attempt to create it.
+    ///
+    /// [global_data_dir]: https://vector.dev/docs/reference/configuration/global-options/#data_dir
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "/var/lib/vector"))]
+    #[configurable(metadata(docs::human_name = "Data Directory"))]
+    pub(super) data_dir: Option<PathBuf>,
+}
+
+#[derive(Debug, Snafu)]
+pub(super) enum IngestorNewError {
+    #[snafu(display("`start_time` {} is not before `end_time` {}", start_time, end_time))]
+    InvalidTimeRange {
+        start_time: DateTime<Utc>,
+        end_time: DateTime<Utc>,
+    },
+    #[snafu(display("Could not read checkpoint file {}: {}", path.display(), source))]
+    ReadCheckpoint { path: PathBuf, source: io::Error },
+}
+
+/// The position of the listing, persisted after each page of objects has been processed.
+///
+/// The listing only resumes from the checkpoint when it was written for the same bucket and prefix.
+#[derive(Debug, Default, Deserialize, PartialEq, Serialize)]
+struct Checkpoint {
+    bucket: String,
+    prefix: Option<String>,
+    continuation_token: Option<String>,
+    complete: bool,
+}
+
+impl Checkpoint {
+    fn new(config: &Config) -> Self {
+        Self {
+            bucket: config.bucket.clone(),
+            prefix: config.prefix.clone(),
+            ..Default::default()
+        }
+    }
+
+    /// Loads the checkpoint for the configured listing, if one was written.
+    fn load(path: &Path, config: &Config) -> Result<Self, IngestorNewError> {
+        let contents = match std::fs::read(path) {
+            Ok(contents) => contents,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Self::new(config)),
+            Err(source) => {
+                return Err(IngestorNewError::ReadCheckpoint {
+                    path: path.to_owned(),
+                    source,
+                })
+            }
+        };
+
+        match serde_json::from_slice::<Self>(&contents) {
+            Ok(checkpoint)
+                if checkpoint.bucket == config.bucket && checkpoint.prefix == config.prefix =>
+            {
+                Ok(checkpoint)
+            }
+            Ok(_) => {
+                info!(
+                    message = "Ignoring listing checkpoint for another bucket or prefix.",
+                    path = ?path
+                );
+                Ok(Self::new(config))
+            }
+            Err(error) => {
+                warn!(message = "Ignoring invalid listing checkpoint.", path = ?path, %error);
+                Ok(Self::new(config))
+            }
+        }
+    }
+
+    /// Writes the checkpoint, replacing the previous one atomically.
+    async fn save(&self, path: &Path) -> io::Result<()> {
+        let temp_path = path.with_extension("json.tmp");
+        let contents = serde_json::to_vec(self).map_err(io::Error::from)?;
+        tokio::fs::write(&temp_path, contents).await?;
+        tokio::fs::rename(&temp_path, path).await
+    }
+}
+
+pub(super) struct Ingestor {
+    reader: ObjectReader,
+    config: Config,
+    checkpoint: Checkpoint,
+    checkpoint_path: PathBuf,
+}
+
+impl Ingestor {
+    pub(super) fn new(
+        reader: ObjectReader,
+        config: Config,
+        data_dir: PathBuf,
+    ) -> Result<Ingestor, IngestorNewError> {
+        if let (Some(start_time), Some(end_time)) = (config.start_time, config.end_time) {
+            if start_time >= end_time {
+                return Err(IngestorNewError::InvalidTimeRange {
+                    start_time,
+                    end_time,
+                });
+            }
+        }
+
+        let checkpoint_path = data_dir.join(CHECKPOINT_FILENAME);
+        let checkpoint = Checkpoint::load(&checkpoint_path, &config)?;
+
+        Ok(Ingestor {
+            reader,
+            config,
+            checkpoint,
+            checkpoint_path,
+        })
+    }
+
+    pub(super) async fn run(
+        self,
+        cx: SourceContext,
+        acknowledgements: SourceAcknowledgementsConfig,
+    ) -> Result<(), ()> {
+        let acknowledgements = cx.do_acknowledgements(acknowledgements);
+        let process = IngestorProcess {
+            reader: self.reader,
+            config: self.config,
+            checkpoint: self.checkpoint,
+            checkpoint_path: self.checkpoint_path,
+            out: cx.out,
+            acknowledgements,
+            bytes_received: register!(BytesReceived::from(Protocol::HTTP)),
+            events_received: register!(EventsReceived),
+        };
+        process.run(cx.shutdown).await
+    }
+}
+
+struct IngestorProcess {
+    reader: ObjectReader,
+    config: Config,
+    checkpoint: Checkpoint,
+    checkpoint_path: PathBuf,
+    out: SourceSender,
+    acknowledgements: bool,
+    bytes_received: Registered<BytesReceived>,
+    events_received: Registered<EventsReceived>,
+}
+
+impl IngestorProcess {
+    async fn run(mut self, shutdown: ShutdownSignal) -> Result<(), ()> {
+        let shutdown = shutdown.fuse();
+        pin!(shutdown);
+
+        if self.checkpoint.complete {
+            info!(
+                message = "Bucket listing was already completed.",
+                bucket = %self.config.bucket,
+                path = ?self.checkpoint_path
+            );
+            return Ok(());
+        }
+
+        while !self.checkpoint.complete {
+            let page = select! {
+                _ = &mut shutdown => break,
+                page = self.list_objects() => page,
+            };
+            let page = match page {
+                Ok(page) => page,
+                Err(error) => {
+                    emit!(S3ListObjectsError {
+                        bucket: &self.config.bucket,
+                        error: &error,
+                    });
+                    select! {
+                        _ = &mut shutdown => break,
+                        _ = tokio::time::sleep(LIST_RETRY_DELAY) => continue,
+                    }
+                }
+            };
+
+            // The page is only checkpointed once all of its objects have been read, so a
+            // shutdown part way through a page reads the whole page again on restart.
+            let objects = page.contents.unwrap_or_default();
+            let delivered = select! {
+                _ = &mut shutdown => break,
+                result = self.read_objects(objects) => result?,
+            };
+
+            // The events of an object of the page were not delivered, so the page is read again,
+            // rather than skipped by the checkpoint.
+            if !delivered {
+                select! {
+                    _ = &mut shutdown => break,
+                    _ = tokio::time::sleep(LIST_RETRY_DELAY) => continue,
+                }
+            }
+
+            self.checkpoint.continuation_token = page.next_continuation_token;
+            self.checkpoint.complete = self.checkpoint.continuation_token.is_none();
+            if let Err(error) = self.checkpoint.save(&self.checkpoint_path).await {
+                emit!(S3ListCheckpointWriteError {
+                    path: &self.checkpoint_path,
+                    error,
+                });
+            }
+        }
+
+        if self.checkpoint.complete {
+            info!(message = "Completed bucket listing.", bucket = %self.config.bucket);
+        }
+        Ok(())
+    }
+
+    async fn list_objects(
+        &self,
+    ) -> Result<ListObjectsV2Output, SdkError<ListObjectsV2Error, HttpResponse>> {
+        self.reader
+            .s3_client
+            .list_objects_v2()
+            .bucket(self.config.bucket.clone())
+            .set_prefix(self.config.prefix.clone())
+            .set_continuation_token(self.checkpoint.continuation_token.clone())
+            .send()
+            .await
+    }
+
+    /// Reads the objects of a page that were modified within the configured time range, returning
+    /// whether the events of all of them were delivered.
+    ///
+    /// Objects that cannot be read are reported and skipped. Only a closed pipeline stops the
+    /// listing.
+    async fn read_objects(&mut self, objects: Vec<Object>) -> Result<bool, ()> {
+        let mut receivers = Vec::new();
+        for object in objects {
+            let Some(key) = object.key.as_deref() else {
+                continue;
+            };
+            if !self.in_time_range(&object) {
+                continue;
+            }
+
+            let result = self
+                .reader
+                .read_object(
+                    &self.config.bucket,
+                    key,
+                    &mut self.out,
+                    self.acknowledgements,
+                    &self.bytes_received,
+                    &self.events_received,
+                )
+                .await;
+            match result {
+                Ok(Some(receiver)) => receivers.push((key.to_owned(), receiver)),
+                Ok(None) => {}
+                Err(error) => {
+                    emit!(S3ObjectProcessingError {
+                        bucket: &self.config.bucket,
+                        key,
+                        error: &error,
+                    });
+                    if matches!(error, ProcessingError::PipelineSend { .. }) {
+                        return Err(());
+                    }
+                }
+            }
+        }
+
+        Ok(wait_for_acknowledgements(&self.config.bucket, receivers).await)
+    }
+
+    fn in_time_range(&self, object: &Object) -> bool {
+        if self.config.start_time.is_none() && self.config.end_time.is_none() {
+            return true;
+        }
+        let Some(last_modified) = object
+            .last_modified
+            .as_ref()
+            .and_then(|ts| Utc.timestamp_opt(ts.secs(), ts.subsec_nanos()).single())
+        else {
+            return false;
+        };
+        in_time_range(last_modified, self.config.start_time, self.config.end_time)
+    }
+}
+
+/// Waits for the acknowledgements of the objects read, returning whether the events of all of them
+/// were delivered.
+async fn wait_for_acknowledgements(
+    bucket: &str,
+    receivers: Vec<(String, BatchStatusReceiver)>,
+) -> bool {
+    let mut delivered = true;
+    for (key, receiver) in receivers {
+        if receiver.await != BatchStatus::Delivered {
+            emit!(S3ObjectProcessingError {
+                bucket,
+                key: &key,
+                error: &ProcessingError::ErrorAcknowledgement,
+            });
+            delivered = false;
+        }
+    }
+    delivered
+}
+
+fn in_time_range(
+    last_modified: DateTime<Utc>,
+    start_time: Option<DateTime<Utc>>,
+    end_time: Option<DateTime<Utc>>,
+) -> bool {
+    start_time.map_or(true, |start_time| last_modified >= start_time)
+        && end_time.map_or(true, |end_time| last_modified < end_time)
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::tempdir;
+
+    use super::*;
+    use crate::event::{BatchNotifier, EventFinalizer, EventStatus};
+
+    fn config() -> Config {
+        Config {
+            bucket: "logs".to_owned(),
+            prefix: Some("2024/".to_owned()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn filters_time_range() {
+        let time = |day| Utc.with_ymd_and_hms(2024, 1, day, 0, 0, 0).unwrap();
+
+        assert!(in_time_range(time(1), None, None));
+        assert!(in_time_range(time(2), Some(time(2)), Some(time(3))));
+        assert!(!in_time_range(time(1), Some(time(2)), Some(time(3))));
+        assert!(!in_time_range(time(3), Some(time(2)), Some(time(3))));
+        assert!(in_time_range(time(3), Some(time(2)), None));
+        assert!(in_time_range(time(1), None, Some(time(2))));
+    }
+
+    fn acknowledged(key: &str, status: EventStatus) -> (String, BatchStatusReceiver) {
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        EventFinalizer::new(batch).update_status(status);
+        (key.to_owned(), receiver)
+    }
+
+    #[tokio::test]
+    async fn waits_for_delivered_acknowledgements() {
+        assert!(wait_for_acknowledgements("logs", Vec::new()).await);
+        assert!(
+            wait_for_acknowledgements(
+                "logs",
+                vec![
+                    acknowledged("2024/a.log", EventStatus::Delivered),
+                    acknowledged("2024/b.log", EventStatus::Delivered),
+                ]
+            )
+            .await
+        );
+    }
+
+    #[tokio::test]
+    async fn rejected_acknowledgements_are_not_delivered() {
+        assert!(
+            !wait_for_acknowledgements(
+                "logs",
+                vec![
+                    acknowledged("2024/a.log", EventStatus::Delivered),
+                    acknowledged("2024/b.log", EventStatus::Rejected),
+                ]
+            )
+            .await
+        );
+        assert!(
+            !wait_for_acknowledgements(
+                "logs",
+                vec![acknowledged("2024/c.log", EventStatus::Errored)]
+            )
+            .await
+        );
+    }
+
+    #[tokio::test]
+    async fn checkpoint_round_trips() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join(CHECKPOINT_FILENAME);
+        let config = config();
+
+        assert_eq!(
+            Checkpoint::load(&path, &config).unwrap(),
+            Checkpoint::new(&config)
+        );
+
+        let checkpoint = Checkpoint {
+            continuation_token: Some("token".to_owned()),
+            ..Checkpoint::new(&config)
+        };
+        checkpoint.save(&path).await.unwrap();
+        assert_eq!(Checkpoint::load(&path, &config).unwrap(), checkpoint);
+    }
+
+    #[tokio::test]
+    async fn checkpoint_is_ignored_for_other_listings() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join(CHECKPOINT_FILENAME);
+        let config = config();
+
+        Checkpoint {
+            continuation_token: Some("token".to_owned()),
+            ..Checkpoint::new(&config)
+        }
+        .save(&path)
+        .await
+        .unwrap();
+
+        let other = Config {
+            prefix: Some("2023/".to_owned()),
+            ..config
+        };
+        assert_eq!(
+            Checkpoint::load(&path, &other).unwrap(),
+            Checkpoint::new(&other)
+        );
+
+        std::fs::write(&path, "not json").unwrap();
+        assert_eq!(
+            Checkpoint::load(&path, &other).unwrap(),
+            Checkpoint::new(&other)
+        );
+    }
+}
@@ -1,32 +1,46 @@
-use std::{convert::TryInto, io::ErrorKind};
+use std::{collections::HashMap, convert::TryInto, future::ready, io::ErrorKind};
 
 use async_compression::tokio::bufread;
+use aws_sdk_s3::Client as S3Client;
 use aws_smithy_types::byte_stream::ByteStream;
-use futures::{stream, stream::StreamExt, TryStreamExt};
-use snafu::Snafu;
+use aws_types::region::Region;
+use bytes::Bytes;
+use chrono::{DateTime, TimeZone, Utc};
+use futures::{stream, stream::StreamExt, Stream, TryStreamExt};
+use smallvec::SmallVec;
+use snafu::{ResultExt, Snafu};
+use tokio_util::codec::FramedRead;
 use tokio_util::io::StreamReader;
 use vector_lib::codecs::decoding::{
     DeserializerConfig, FramingConfig, NewlineDelimitedDecoderOptions,
 };
 use vector_lib::codecs::NewlineDelimitedDecoderConfig;
-use vector_lib::config::{LegacyKey, LogNamespace};
+use vector_lib::config::{log_schema, LegacyKey, LogNamespace};
 use vector_lib::configurable::configurable_component;
-use vector_lib::lookup::owned_value_path;
+use vector_lib::event::MaybeAsLogMut;
+use vector_lib::internal_event::{
+    ByteSize, BytesReceived, CountByteSize, InternalEventHandle as _, Registered,
+};
+use vector_lib::lookup::{metadata_path, owned_value_path, path, PathPrefix};
 use vrl::value::{kind::Collection, Kind};
 
 use super::util::MultilineConfig;
-use crate::codecs::DecodingConfig;
+use crate::codecs::{Decoder, DecodingConfig};
 use crate::{
     aws::{auth::AwsAuthentication, create_client, create_client_and_region, RegionOrEndpoint},
     common::{s3::S3ClientBuilder, sqs::SqsClientBuilder},
     config::{
         ProxyConfig, SourceAcknowledgementsConfig, SourceConfig, SourceContext, SourceOutput,
     },
-    line_agg,
+    event::{BatchNotifier, BatchStatusReceiver, EstimatedJsonEncodedSizeOf, Event, LogEvent},
+    internal_events::{EventsReceived, StreamClosedError},
+    line_agg::{self, LineAgg},
     serde::{bool_or_struct, default_decoding},
     tls::TlsConfig,
+    SourceSender,
 };
 
+mod list;
 pub mod sqs;
 
 /// Compression scheme for objects retrieved from S3.
@@ -66,6 +80,12 @@ enum Strategy {
     /// [aws_sqs]: https://aws.amazon.com/sqs/
     #[derivative(Default)]
     Sqs,
+
+    /// Consumes the objects already stored in a bucket by listing them.
+    ///
+    /// This is meant for backfilling, to replay historical objects through a pipeline. Once every
+    /// object has been read, the source stops.
+    List,
 }
 
 /// Configuration for the `aws_s3` source.
@@ -86,12 +106,14 @@ pub struct AwsS3Config {
     compression: Compression,
 
     /// The strategy to use to consume objects from S3.
-    #[configurable(metadata(docs::hidden))]
     strategy: Strategy,
 
     /// Configuration options for SQS.
     sqs: Option<sqs::Config>,
 
+    /// Configuration options for listing a bucket.
+    list: Option<list::Config>,
+
     /// The ARN of an [IAM role][iam_role] to assume at startup.
     ///
     /// [iam_role]: https://docs.aws.amazon.com/IAM/latest/UserGuide/id_roles.html
@@ -157,7 +179,12 @@ impl SourceConfig for AwsS3Config {
             Strategy::Sqs => Ok(Box::pin(
                 self.create_sqs_ingestor(multiline_config, &cx.proxy, log_namespace)
                     .await?
-                    .run(cx, self.acknowledgements, log_namespace),
+                    .run(cx, self.acknowledgements),
+            )),
+            Strategy::List => Ok(Box::pin(
+                self.create_list_ingestor(multiline_config, &cx, log_namespace)
+                    .await?
+                    .run(cx, self.acknowledgements),
             )),
         }
     }
@@ -255,22 +282,59 @@ impl AwsS3Config {
                 )
                 .await?;
 
-                let ingestor = sqs::Ingestor::new(
+                let reader = ObjectReader {
                     region,
-                    sqs_client,
                     s3_client,
-                    sqs.clone(),
-                    self.compression,
+                    compression: self.compression,
                     multiline,
                     decoder,
-                )
-                .await?;
+                    log_namespace,
+                };
+                let ingestor = sqs::Ingestor::new(sqs_client, reader, sqs.clone()).await?;
 
                 Ok(ingestor)
             }
             None => Err(CreateSqsIngestorError::ConfigMissing {}.into()),
         }
     }
+
+    async fn create_list_ingestor(
+        &self,
+        multiline: Option<line_agg::Config>,
+        cx: &SourceContext,
+        log_namespace: LogNamespace,
+    ) -> crate::Result<list::Ingestor> {
+        let Some(ref list) = self.list else {
+            return Err(CreateListIngestorError::ConfigMissing {}.into());
+        };
+
+        let (s3_client, region) = create_client_and_region::<S3ClientBuilder>(
+            &self.auth,
+            self.region.region(),
+            self.region.endpoint(),
+            &cx.proxy,
+            &self.tls_options,
+        )
+        .await?;
+
+        let decoder =
+            DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace)
+                .build()?;
+
+        let data_dir = cx
+            .globals
+            .resolve_and_make_data_subdir(list.data_dir.as_ref(), cx.key.id())?;
+
+        let reader = ObjectReader {
+            region,
+            s3_client,
+            compression: self.compression,
+            multiline,
+            decoder,
+            log_namespace,
+        };
+        Ok(list::Ingestor::new(reader, list.clone(), data_dir)?)
+    }
 }
 
 #[derive(Debug, Snafu)]
@@ -287,6 +351,247 @@ enum CreateSqsIngestorError {
     InvalidEndpoint,
 }
 
+#[derive(Debug, Snafu)]
+enum CreateListIngestorError {
+    #[snafu(display("Configuration for `list` required when strategy=list"))]
+    ConfigMissing,
+}
+
+/// Reads objects from S3 into events.
+///
+/// This is shared by the strategies for consuming objects, which differ only in how they find the
+/// objects to read.
+pub(super) struct ObjectReader {
+    region: Region,
+    s3_client: S3Client,
+    compression: Compression,
+    multiline: Option<line_agg::Config>,
+    decoder: Decoder,
+    log_namespace: LogNamespace,
+}
+
+impl ObjectReader {
+    /// Fetches an object and sends its events.
+    ///
+    /// If acknowledgements are enabled, the returned receiver resolves to the status of the
+    /// object's events once they have all been delivered.
+    pub(super) async fn read_object(
+        &self,
+        bucket: &str,
+        key: &str,
+        out: &mut SourceSender,
+        acknowledgements: bool,
+        bytes_received: &Registered<BytesReceived>,
+        events_received: &Registered<EventsReceived>,
+    ) -> Result<Option<BatchStatusReceiver>, sqs::ProcessingError> {
+        let object_result = self
+            .s3_client
+            .get_object()
+            .bucket(bucket)
+            .key(key)
+            .send()
+            .await
+            .context(sqs::GetObjectSnafu { bucket, key });
+
+        let object = object_result?;
+
+        let metadata = object.metadata;
+
+        let timestamp = object.last_modified.map(|ts| {
+            Utc.timestamp_opt(ts.secs(), ts.subsec_nanos())
+                .single()
+                .expect("invalid timestamp")
+        });
+
+        let (batch, receiver) = BatchNotifier::maybe_new_with_receiver(acknowledgements);
+        let object_reader = s3_object_decoder(
+            self.compression,
+            key,
+            object.content_encoding.as_deref(),
+            object.content_type.as_deref(),
+            object.body,
+        )
+        .await;
+
+        // Record the read error seen to propagate up later so we avoid ack'ing the object
+        //
+        // String is used as we cannot clone std::io::Error to take ownership in closure
+        //
+        // FramedRead likely stops when it gets an i/o error but I found it more clear to
+        // show that we `take_while` there hasn't been an error
+        //
+        // This can result in objects being partially processed before an error, but we
+        // prefer duplicate lines over message loss. Future work could include recording
+        // the offset of the object that has been read, but this would only be relevant in
+        // the case that the same vector instance processes the same object.
+        let mut read_error = None;
+        let lines: Box<dyn Stream<Item = Bytes> + Send + Unpin> = Box::new(
+            FramedRead::new(object_reader, self.decoder.framer.clone())
+                .map(|res| {
+                    res.map(|bytes| {
+                        bytes_received.emit(ByteSize(bytes.len()));
+                        bytes
+                    })
+                    .map_err(|err| {
+                        read_error = Some(err);
+                    })
+                    .ok()
+                })
+                .take_while(|res| ready(res.is_some()))
+                .map(|r| r.expect("validated by take_while")),
+        );
+
+        let lines: Box<dyn Stream<Item = Bytes> + Send + Unpin> = match &self.multiline {
+            Some(config) => Box::new(
+                LineAgg::new(
+                    lines.map(|line| ((), line, ())),
+                    line_agg::Logic::new(config.clone()),
+                )
+                .map(|(_src, line, _context, _lastline_context)| line),
+            ),
+            None => lines,
+        };
+
+        let mut stream = lines.flat_map(|line| {
+            let events = match self.decoder.deserializer_parse(line) {
+                Ok((events, _events_size)) => events,
+                Err(_error) => {
+                    // Error is handled by `codecs::Decoder`, no further handling
+                    // is needed here.
+                    SmallVec::new()
+                }
+            };
+
+            let events = events
+                .into_iter()
+                .map(|mut event: Event| {
+                    event = event.with_batch_notifier_option(&batch);
+                    if let Some(log_event) = event.maybe_as_log_mut() {
+                        handle_single_log(
+                            log_event,
+                            self.log_namespace,
+                            bucket,
+                            key,
+                            self.region.as_ref(),
+                            &metadata,
+                            timestamp,
+                        );
+                    }
+                    events_received.emit(CountByteSize(1, event.estimated_json_encoded_size_of()));
+                    event
+                })
+                .collect::<Vec<Event>>();
+            futures::stream::iter(events)
+        });
+
+        let send_error = match out.send_event_stream(&mut stream).await {
+            Ok(_) => None,
+            Err(_) => {
+                let (count, _) = stream.size_hint();
+                emit!(StreamClosedError { count });
+                Some(crate::source_sender::ClosedError)
+            }
+        };
+
+        // Up above, `lines` captures `read_error`, and eventually is captured by `stream`,
+        // so we explicitly drop it so that we can again utilize `read_error` below.
+        drop(stream);
+
+        // The BatchNotifier is cloned for each LogEvent in the batch stream, but the last
+        // reference must be dropped before the status of the batch is sent to the channel.
+        drop(batch);
+
+        if let Some(error) = read_error {
+            Err(sqs::ProcessingError::ReadObject {
+                source: error,
+                bucket: bucket.to_owned(),
+                key: key.to_owned(),
+            })
+        } else if let Some(error) = send_error {
+            Err(sqs::ProcessingError::PipelineSend {
+                source: error,
+                bucket: bucket.to_owned(),
+                key: key.to_owned(),
+            })
+        } else {
+            Ok(receiver)
+        }
+    }
+}
+
+fn handle_single_log(
+    log: &mut LogEvent,
+    log_namespace: LogNamespace,
+    bucket: &str,
+    key: &str,
+    region: &str,
+    metadata: &Option<HashMap<String, String>>,
+    timestamp: Option<DateTime<Utc>>,
+) {
+    log_namespace.insert_source_metadata(
+        AwsS3Config::NAME,
+        log,
+        Some(LegacyKey::Overwrite(path!("bucket"))),
+        path!("bucket"),
+        Bytes::from(bucket.as_bytes().to_vec()),
+    );
+
+    log_namespace.insert_source_metadata(
+        AwsS3Config::NAME,
+        log,
+        Some(LegacyKey::Overwrite(path!("object"))),
+        path!("object"),
+        Bytes::from(key.as_bytes().to_vec()),
+    );
+    log_namespace.insert_source_metadata(
+        AwsS3Config::NAME,
+        log,
+        Some(LegacyKey::Overwrite(path!("region"))),
+        path!("region"),
+        Bytes::from(region.as_bytes().to_vec()),
+    );
+
+    if let Some(metadata) = metadata {
+        for (key, value) in metadata {
+            log_namespace.insert_source_metadata(
+                AwsS3Config::NAME,
+                log,
+                Some(LegacyKey::Overwrite(path!(key))),
+                path!("metadata", key.as_str()),
+                value.clone(),
+            );
+        }
+    }
+
+    log_namespace.insert_vector_metadata(
+        log,
+        log_schema().source_type_key(),
+        path!("source_type"),
+        Bytes::from_static(AwsS3Config::NAME.as_bytes()),
+    );
+
+    // This handles the transition from the original timestamp logic. Originally the
+    // `timestamp_key` was populated by the `last_modified` time on the object, falling
+    // back to calling `now()`.
+    match log_namespace {
+        LogNamespace::Vector => {
+            if let Some(timestamp) = timestamp {
+                log.insert(metadata_path!(AwsS3Config::NAME, "timestamp"), timestamp);
+            }
+
+            log.insert(metadata_path!("vector", "ingest_timestamp"), Utc::now());
+        }
+        LogNamespace::Legacy => {
+            if let Some(timestamp_key) = log_schema().timestamp_key() {
+                log.try_insert(
+                    (PathPrefix::Event, timestamp_key),
+                    timestamp.unwrap_or_else(Utc::now),
+                );
+            }
+        }
+    };
+}
+
 /// None if body is empty
 async fn s3_object_decoder(
     compression: Compression,
@@ -465,7 +770,7 @@ mod integration_tests {
             util::MultilineConfig,
         },
         test_util::{
-            collect_n,
+            collect_n, collect_ready,
             components::{assert_source_compliance, SOURCE_TAGS},
             lines_from_gzip_file, random_lines, trace_init,
         },
@@ -782,6 +1087,71 @@ mod integration_tests {
         .await;
     }
 
+    #[tokio::test]
+    async fn s3_list_bucket() {
+        trace_init();
+
+        assert_source_compliance(&SOURCE_TAGS, async move {
+            let s3 = s3_client().await;
+            let bucket = create_bucket(&s3).await;
+
+            let logs: Vec<String> = random_lines(100).take(10).collect();
+            for (key, lines) in [
+                ("2024/a.log", &logs[..5]),
+                ("2024/b.log", &logs[5..]),
+                ("2023/c.log", &logs[..1]),
+            ] {
+                s3.put_object()
+                    .bucket(bucket.clone())
+                    .key(key)
+                    .body(ByteStream::from(lines.join("\n").into_bytes()))
+                    .send()
+                    .await
+                    .expect("Could not put object");
+            }
+
+            let data_dir = tempfile::tempdir().unwrap();
+            let config = AwsS3Config {
+                region: RegionOrEndpoint::with_both("us-east-1", s3_address()),
+                strategy: Strategy::List,
+                list: Some(list::Config {
+                    bucket: bucket.clone(),
+                    prefix: Some("2024/".to_owned()),
+                    data_dir: Some(data_dir.path().to_owned()),
+                    ..Default::default()
+                }),
+                acknowledgements: true.into(),
+                ..Default::default()
+            };
+
+            let (tx, rx) = SourceSender::new_test_finalize(Delivered);
+            let source = config
+                .build(SourceContext::new_test(tx, None))
+                .await
+                .unwrap();
+            let source = tokio::spawn(source);
+
+            let events = collect_n(rx, logs.len()).await;
+            let messages = events
+                .iter()
+                .map(|event| event.as_log()["message"].to_string_lossy().into_owned())
+                .collect::<Vec<_>>();
+            assert_eq!(messages, logs);
+
+            // The source stops once the listing is complete, and does not list the bucket again.
+            source.await.unwrap().unwrap();
+
+            let (tx, rx) = SourceSender::new_test_finalize(Delivered);
+            let source = config
+                .build(SourceContext::new_test(tx, None))
+                .await
+                .unwrap();
+            source.await.unwrap();
+            assert!(collect_ready(rx).await.is_empty());
+        })
+        .await;
+    }
+
     fn s3_address() -> String {
         std::env::var("S3_ADDRESS").unwrap_or_else(|_| "http://localhost:4566".into())
     }
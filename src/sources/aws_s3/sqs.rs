@@ -1,8 +1,6 @@
-use std::collections::HashMap;
-use std::{future::ready, num::NonZeroUsize, panic, sync::Arc};
+use std::{num::NonZeroUsize, panic, sync::Arc};
 
 use aws_sdk_s3::operation::get_object::GetObjectError;
-use aws_sdk_s3::Client as S3Client;
 use aws_sdk_sqs::operation::delete_message_batch::{
     DeleteMessageBatchError, DeleteMessageBatchOutput,
 };
@@ -11,44 +9,30 @@ use aws_sdk_sqs::types::{DeleteMessageBatchRequestEntry, Message};
 use aws_sdk_sqs::Client as SqsClient;
 use aws_smithy_runtime_api::client::orchestrator::HttpResponse;
 use aws_smithy_runtime_api::client::result::SdkError;
-use aws_types::region::Region;
-use bytes::Bytes;
-use chrono::{DateTime, TimeZone, Utc};
-use futures::{FutureExt, Stream, StreamExt, TryFutureExt};
+use futures::{FutureExt, TryFutureExt};
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_with::serde_as;
-use smallvec::SmallVec;
 use snafu::{ResultExt, Snafu};
 use tokio::{pin, select};
-use tokio_util::codec::FramedRead;
 use tracing::Instrument;
 use vector_lib::codecs::decoding::FramingError;
 use vector_lib::configurable::configurable_component;
-use vector_lib::internal_event::{
-    ByteSize, BytesReceived, CountByteSize, InternalEventHandle as _, Protocol, Registered,
-};
+use vector_lib::internal_event::{BytesReceived, Protocol, Registered};
 
-use crate::codecs::Decoder;
-use crate::event::{Event, LogEvent};
+use super::ObjectReader;
 use crate::{
     config::{SourceAcknowledgementsConfig, SourceContext},
-    event::{BatchNotifier, BatchStatus, EstimatedJsonEncodedSizeOf},
+    event::BatchStatus,
     internal_events::{
         EventsReceived, SqsMessageDeleteBatchError, SqsMessageDeletePartialError,
         SqsMessageDeleteSucceeded, SqsMessageProcessingError, SqsMessageProcessingSucceeded,
         SqsMessageReceiveError, SqsMessageReceiveSucceeded, SqsS3EventRecordInvalidEventIgnored,
-        StreamClosedError,
     },
-    line_agg::{self, LineAgg},
     shutdown::ShutdownSignal,
-    sources::aws_s3::AwsS3Config,
     tls::TlsConfig,
     SourceSender,
 };
-use vector_lib::config::{log_schema, LegacyKey, LogNamespace};
-use vector_lib::event::MaybeAsLogMut;
-use vector_lib::lookup::{metadata_path, path, PathPrefix};
 
 static SUPPORTED_S3_EVENT_VERSION: Lazy<semver::VersionReq> =
     Lazy::new(|| semver::VersionReq::parse("~2").unwrap());
@@ -170,6 +154,7 @@ pub(super) enum IngestorNewError {
 
 #[allow(clippy::large_enum_variant)]
 #[derive(Debug, Snafu)]
+#[snafu(visibility(pub(super)))]
 pub enum ProcessingError {
     #[snafu(display(
         "Could not parse SQS message with id {} as S3 notification: {}",
@@ -216,13 +201,8 @@ pub enum ProcessingError {
 }
 
 pub struct State {
-    region: Region,
-
-    s3_client: S3Client,
     sqs_client: SqsClient,
-
-    multiline: Option<line_agg::Config>,
-    compression: super::Compression,
+    reader: ObjectReader,
 
     queue_url: String,
     poll_secs: i32,
@@ -231,7 +211,6 @@ pub struct State {
     visibility_timeout_secs: i32,
     delete_message: bool,
     delete_failed_message: bool,
-    decoder: Decoder,
 }
 
 pub(super) struct Ingestor {
@@ -240,13 +219,9 @@ pub(super) struct Ingestor {
 
 impl Ingestor {
     pub(super) async fn new(
-        region: Region,
         sqs_client: SqsClient,
-        s3_client: S3Client,
+        reader: ObjectReader,
         config: Config,
-        compression: super::Compression,
-        multiline: Option<line_agg::Config>,
-        decoder: Decoder,
     ) -> Result<Ingestor, IngestorNewError> {
         if config.max_number_of_messages < 1 || config.max_number_of_messages > 10 {
             return Err(IngestorNewError::InvalidNumberOfMessages {
@@ -254,13 +229,8 @@ impl Ingestor {
             });
         }
         let state = Arc::new(State {
-            region,
-
-            s3_client,
             sqs_client,
-
-            compression,
-            multiline,
+            reader,
 
             queue_url: config.queue_url,
             poll_secs: config.poll_secs as i32,
@@ -272,7 +242,6 @@ impl Ingestor {
             visibility_timeout_secs: config.visibility_timeout_secs as i32,
             delete_message: config.delete_message,
             delete_failed_message: config.delete_failed_message,
-            decoder,
         });
 
         Ok(Ingestor { state })
@@ -282,7 +251,6 @@ impl Ingestor {
         self,
         cx: SourceContext,
         acknowledgements: SourceAcknowledgementsConfig,
-        log_namespace: LogNamespace,
     ) -> Result<(), ()> {
         let acknowledgements = cx.do_acknowledgements(acknowledgements);
         let mut handles = Vec::new();
@@ -291,7 +259,6 @@ impl Ingestor {
                 Arc::clone(&self.state),
                 cx.out.clone(),
                 cx.shutdown.clone(),
-                log_namespace,
                 acknowledgements,
             );
             let fut = process.run();
@@ -318,7 +285,6 @@ pub struct IngestorProcess {
     out: SourceSender,
     shutdown: ShutdownSignal,
     acknowledgements: bool,
-    log_namespace: LogNamespace,
     bytes_received: Registered<BytesReceived>,
     events_received: Registered<EventsReceived>,
 }
@@ -328,7 +294,6 @@ impl IngestorProcess {
         state: Arc<State>,
         out: SourceSender,
         shutdown: ShutdownSignal,
-        log_namespace: LogNamespace,
         acknowledgements: bool,
     ) -> Self {
         Self {
@@ -336,7 +301,6 @@ impl IngestorProcess {
             out,
             shutdown,
             acknowledgements,
-            log_namespace,
             bytes_received: register!(BytesReceived::from(Protocol::HTTP)),
             events_received: register!(EventsReceived),
         }
@@ -463,8 +427,7 @@ impl IngestorProcess {
 
     async fn handle_s3_event(&mut self, s3_event: S3Event) -> Result<(), ProcessingError> {
         for record in s3_event.records {
-            self.handle_s3_event_record(record, self.log_namespace)
-                .await?
+            self.handle_s3_event_record(record).await?
         }
         Ok(())
     }
@@ -472,7 +435,6 @@ impl IngestorProcess {
     async fn handle_s3_event_record(
         &mut self,
         s3_event: S3EventRecord,
-        log_namespace: LogNamespace,
     ) -> Result<(), ProcessingError> {
         let event_version: semver::Version = s3_event.event_version.clone().into();
         if !SUPPORTED_S3_EVENT_VERSION.matches(&event_version) {
@@ -493,7 +455,7 @@ impl IngestorProcess {
 
         // S3 has to send notifications to a queue in the same region so I don't think this will
         // actually ever be hit unless messages are being forwarded from one queue to another
-        if self.state.region.as_ref() != s3_event.aws_region.as_str() {
+        if self.state.reader.region.as_ref() != s3_event.aws_region.as_str() {
             return Err(ProcessingError::WrongRegion {
                 bucket: s3_event.s3.bucket.name.clone(),
                 key: s3_event.s3.object.key.clone(),
@@ -501,154 +463,31 @@ impl IngestorProcess {
             });
         }
 
-        let object_result = self
+        let receiver = self
             .state
-            .s3_client
-            .get_object()
-            .bucket(s3_event.s3.bucket.name.clone())
-            .key(s3_event.s3.object.key.clone())
-            .send()
-            .await
-            .context(GetObjectSnafu {
-                bucket: s3_event.s3.bucket.name.clone(),
-                key: s3_event.s3.object.key.clone(),
-            });
-
-        let object = object_result?;
-
-        let metadata = object.metadata;
-
-        let timestamp = object.last_modified.map(|ts| {
-            Utc.timestamp_opt(ts.secs(), ts.subsec_nanos())
-                .single()
-                .expect("invalid timestamp")
-        });
-
-        let (batch, receiver) = BatchNotifier::maybe_new_with_receiver(self.acknowledgements);
-        let object_reader = super::s3_object_decoder(
-            self.state.compression,
-            &s3_event.s3.object.key,
-            object.content_encoding.as_deref(),
-            object.content_type.as_deref(),
-            object.body,
-        )
-        .await;
-
-        // Record the read error seen to propagate up later so we avoid ack'ing the SQS
-        // message
-        //
-        // String is used as we cannot clone std::io::Error to take ownership in closure
-        //
-        // FramedRead likely stops when it gets an i/o error but I found it more clear to
-        // show that we `take_while` there hasn't been an error
-        //
-        // This can result in objects being partially processed before an error, but we
-        // prefer duplicate lines over message loss. Future work could include recording
-        // the offset of the object that has been read, but this would only be relevant in
-        // the case that the same vector instance processes the same message.
-        let mut read_error = None;
-        let bytes_received = self.bytes_received.clone();
-        let events_received = self.events_received.clone();
-        let lines: Box<dyn Stream<Item = Bytes> + Send + Unpin> = Box::new(
-            FramedRead::new(object_reader, self.state.decoder.framer.clone())
-                .map(|res| {
-                    res.map(|bytes| {
-                        bytes_received.emit(ByteSize(bytes.len()));
-                        bytes
-                    })
-                    .map_err(|err| {
-                        read_error = Some(err);
-                    })
-                    .ok()
-                })
-                .take_while(|res| ready(res.is_some()))
-                .map(|r| r.expect("validated by take_while")),
-        );
-
-        let lines: Box<dyn Stream<Item = Bytes> + Send + Unpin> = match &self.state.multiline {
-            Some(config) => Box::new(
-                LineAgg::new(
-                    lines.map(|line| ((), line, ())),
-                    line_agg::Logic::new(config.clone()),
-                )
-                .map(|(_src, line, _context, _lastline_context)| line),
-            ),
-            None => lines,
-        };
-
-        let mut stream = lines.flat_map(|line| {
-            let events = match self.state.decoder.deserializer_parse(line) {
-                Ok((events, _events_size)) => events,
-                Err(_error) => {
-                    // Error is handled by `codecs::Decoder`, no further handling
-                    // is needed here.
-                    SmallVec::new()
-                }
-            };
-
-            let events = events
-                .into_iter()
-                .map(|mut event: Event| {
-                    event = event.with_batch_notifier_option(&batch);
-                    if let Some(log_event) = event.maybe_as_log_mut() {
-                        handle_single_log(
-                            log_event,
-                            log_namespace,
-                            &s3_event,
-                            &metadata,
-                            timestamp,
-                        );
-                    }
-                    events_received.emit(CountByteSize(1, event.estimated_json_encoded_size_of()));
-                    event
-                })
-                .collect::<Vec<Event>>();
-            futures::stream::iter(events)
-        });
-
-        let send_error = match self.out.send_event_stream(&mut stream).await {
-            Ok(_) => None,
-            Err(_) => {
-                let (count, _) = stream.size_hint();
-                emit!(StreamClosedError { count });
-                Some(crate::source_sender::ClosedError)
-            }
-        };
-
-        // Up above, `lines` captures `read_error`, and eventually is captured by `stream`,
-        // so we explicitly drop it so that we can again utilize `read_error` below.
-        drop(stream);
-
-        // The BatchNotifier is cloned for each LogEvent in the batch stream, but the last
-        // reference must be dropped before the status of the batch is sent to the channel.
-        drop(batch);
-
-        if let Some(error) = read_error {
-            Err(ProcessingError::ReadObject {
-                source: error,
-                bucket: s3_event.s3.bucket.name.clone(),
-                key: s3_event.s3.object.key.clone(),
-            })
-        } else if let Some(error) = send_error {
-            Err(ProcessingError::PipelineSend {
-                source: error,
-                bucket: s3_event.s3.bucket.name.clone(),
-                key: s3_event.s3.object.key.clone(),
-            })
-        } else {
-            match receiver {
-                None => Ok(()),
-                Some(receiver) => {
-                    let result = receiver.await;
-                    match result {
-                        BatchStatus::Delivered => Ok(()),
-                        BatchStatus::Errored => Err(ProcessingError::ErrorAcknowledgement),
-                        BatchStatus::Rejected => {
-                            if self.state.delete_failed_message {
-                                Ok(())
-                            } else {
-                                Err(ProcessingError::ErrorAcknowledgement)
-                            }
+            .reader
+            .read_object(
+                &s3_event.s3.bucket.name,
+                &s3_event.s3.object.key,
+                &mut self.out,
+                self.acknowledgements,
+                &self.bytes_received,
+                &self.events_received,
+            )
+            .await?;
+
+        match receiver {
+            None => Ok(()),
+            Some(receiver) => {
+                let result = receiver.await;
+                match result {
+                    BatchStatus::Delivered => Ok(()),
+                    BatchStatus::Errored => Err(ProcessingError::ErrorAcknowledgement),
+                    BatchStatus::Rejected => {
+                        if self.state.delete_failed_message {
+                            Ok(())
+                        } else {
+                            Err(ProcessingError::ErrorAcknowledgement)
                         }
                     }
                 }
@@ -685,77 +524,6 @@ impl IngestorProcess {
     }
 }
 
-fn handle_single_log(
-    log: &mut LogEvent,
-    log_namespace: LogNamespace,
-    s3_event: &S3EventRecord,
-    metadata: &Option<HashMap<String, String>>,
-    timestamp: Option<DateTime<Utc>>,
-) {
-    log_namespace.insert_source_metadata(
-        AwsS3Config::NAME,
-        log,
-        Some(LegacyKey::Overwrite(path!("bucket"))),
-        path!("bucket"),
-        Bytes::from(s3_event.s3.bucket.name.as_bytes().to_vec()),
-    );
-
-    log_namespace.insert_source_metadata(
-        AwsS3Config::NAME,
-        log,
-        Some(LegacyKey::Overwrite(path!("object"))),
-        path!("object"),
-        Bytes::from(s3_event.s3.object.key.as_bytes().to_vec()),
-    );
-    log_namespace.insert_source_metadata(
-        AwsS3Config::NAME,
-        log,
-        Some(LegacyKey::Overwrite(path!("region"))),
-        path!("region"),
-        Bytes::from(s3_event.aws_region.as_bytes().to_vec()),
-    );
-
-    if let Some(metadata) = metadata {
-        for (key, value) in metadata {
-            log_namespace.insert_source_metadata(
-                AwsS3Config::NAME,
-                log,
-                Some(LegacyKey::Overwrite(path!(key))),
-                path!("metadata", key.as_str()),
-                value.clone(),
-            );
-        }
-    }
-
-    log_namespace.insert_vector_metadata(
-        log,
-        log_schema().source_type_key(),
-        path!("source_type"),
-        Bytes::from_static(AwsS3Config::NAME.as_bytes()),
-    );
-
-    // This handles the transition from the original timestamp logic. Originally the
-    // `timestamp_key` was populated by the `last_modified` time on the object, falling
-    // back to calling `now()`.
-    match log_namespace {
-        LogNamespace::Vector => {
-            if let Some(timestamp) = timestamp {
-                log.insert(metadata_path!(AwsS3Config::NAME, "timestamp"), timestamp);
-            }
-
-            log.insert(metadata_path!("vector", "ingest_timestamp"), Utc::now());
-        }
-        LogNamespace::Legacy => {
-            if let Some(timestamp_key) = log_schema().timestamp_key() {
-                log.try_insert(
-                    (PathPrefix::Event, timestamp_key),
-                    timestamp.unwrap_or_else(Utc::now),
-                );
-            }
-        }
-    };
-}
-
 // https://docs.aws.amazon.com/sns/latest/dg/sns-sqs-as-subscriber.html
 #[derive(Clone, Debug, Deserialize)]
 #[serde(rename_all = "PascalCase")]
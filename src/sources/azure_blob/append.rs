@@ -0,0 +1,240 @@
+use std::{sync::Arc, time::Duration};
+
+use azure_storage_queues::prelude::*;
+use futures::FutureExt;
+use tokio::{
+    pin, select,
+    sync::mpsc,
+    time::{self, Instant},
+};
+use vector_lib::internal_event::{BytesReceived, Protocol, Registered};
+
+use super::{queue::State, AppendBlobsConfig, BlobLocation};
+use crate::{
+    event::BatchStatus,
+    internal_events::{
+        AzureAppendBlobReadError, AzureQueueMessageDeleteError, AzureQueueMessageUpdateError,
+        EventsReceived,
+    },
+    shutdown::ShutdownSignal,
+    SourceSender,
+};
+
+/// A queue message naming append blobs, handed over to the tracker by the ingestor processes.
+pub(super) struct TrackedMessage {
+    pub(super) message_id: String,
+    pub(super) pop_receipt: String,
+    pub(super) blobs: Vec<BlobLocation>,
+}
+
+struct TrackedBlob {
+    location: BlobLocation,
+    offset: u64,
+}
+
+struct Tracked {
+    message_id: String,
+    pop_receipt: String,
+    blobs: Vec<TrackedBlob>,
+    last_growth: Instant,
+}
+
+/// Follows append blobs as they grow.
+///
+/// Blob created events are only sent when an append blob is created, not when blocks are appended
+/// to it, so the blobs are polled for new data instead. The queue message that announced a blob is
+/// kept invisible for as long as the blob is followed, and only deleted once the blob has stopped
+/// growing for the idle timeout. If Vector stops before then, the message becomes visible again
+/// and the blob is read again from its start.
+pub(super) struct AppendBlobTracker {
+    state: Arc<State>,
+    poll_interval: Duration,
+    idle_timeout: Duration,
+    receiver: mpsc::Receiver<TrackedMessage>,
+    tracked: Vec<Tracked>,
+    out: SourceSender,
+    shutdown: ShutdownSignal,
+    acknowledgements: bool,
+    bytes_received: Registered<BytesReceived>,
+    events_received: Registered<EventsReceived>,
+}
+
+impl AppendBlobTracker {
+    pub(super) fn new(
+        state: Arc<State>,
+        config: &AppendBlobsConfig,
+        receiver: mpsc::Receiver<TrackedMessage>,
+        out: SourceSender,
+        shutdown: ShutdownSignal,
+        acknowledgements: bool,
+    ) -> Self {
+        Self {
+            state,
+            poll_interval: Duration::from_secs(config.poll_secs),
+            idle_timeout: Duration::from_secs(config.idle_timeout_secs),
+            receiver,
+            tracked: Vec::new(),
+            out,
+            shutdown,
+            acknowledgements,
+            bytes_received: register!(BytesReceived::from(Protocol::HTTP)),
+            events_received: register!(EventsReceived),
+        }
+    }
+
+    pub(super) async fn run(mut self) {
+        let shutdown = self.shutdown.clone().fuse();
+        pin!(shutdown);
+        let mut interval = time::interval(self.poll_interval);
+
+        loop {
+            select! {
+                _ = &mut shutdown => break,
+                message = self.receiver.recv() => match message {
+                    Some(message) => {
+                        self.tracked.push(Tracked {
+                            message_id: message.message_id,
+                            pop_receipt: message.pop_receipt,
+                            blobs: message
+                                .blobs
+                                .into_iter()
+                                .map(|location| TrackedBlob { location, offset: 0 })
+                                .collect(),
+                            last_growth: Instant::now(),
+                        });
+                        // Read what the new blobs already hold right away, rather than waiting
+                        // for the next poll.
+                        let index = self.tracked.len() - 1;
+                        self.poll(index).await;
+                    }
+                    None => break,
+                },
+                _ = interval.tick() => {
+                    let mut index = 0;
+                    while index < self.tracked.len() {
+                        if self.poll(index).await {
+                            index += 1;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Reads the data appended to the blobs of a tracked message since the last poll.
+    ///
+    /// Returns `false` if the message has stopped being tracked.
+    async fn poll(&mut self, index: usize) -> bool {
+        let mut grew = false;
+        let tracked = &mut self.tracked[index];
+        for blob in &mut tracked.blobs {
+            match read_appended(
+                &self.state,
+                blob,
+                &mut self.out,
+                self.acknowledgements,
+                &self.bytes_received,
+                &self.events_received,
+            )
+            .await
+            {
+                Ok(blob_grew) => grew |= blob_grew,
+                Err(error) => emit!(AzureAppendBlobReadError {
+                    container: &blob.location.container,
+                    blob: &blob.location.blob,
+                    error: &error,
+                }),
+            }
+        }
+
+        if grew {
+            tracked.last_growth = Instant::now();
+        } else if tracked.last_growth.elapsed() >= self.idle_timeout {
+            let tracked = self.tracked.swap_remove(index);
+            let pop_receipt = PopReceipt::new(tracked.message_id.clone(), tracked.pop_receipt);
+            if let Err(error) = self
+                .state
+                .queue_client
+                .pop_receipt_client(pop_receipt)
+                .delete()
+                .into_future()
+                .await
+            {
+                emit!(AzureQueueMessageDeleteError {
+                    message_id: &tracked.message_id,
+                    error: &error,
+                });
+            }
+            return false;
+        }
+
+        // Keep the message invisible until the next poll.
+        let pop_receipt = PopReceipt::new(tracked.message_id.clone(), tracked.pop_receipt.clone());
+        match self
+            .state
+            .queue_client
+            .pop_receipt_client(pop_receipt)
+            .update("", self.state.visibility_timeout)
+            .into_future()
+            .await
+        {
+            Ok(response) => tracked.pop_receipt = response.pop_receipt,
+            Err(error) => {
+                // Without a valid pop receipt the message can no longer be updated or deleted, so
+                // it is left to become visible again and be picked up anew.
+                emit!(AzureQueueMessageUpdateError {
+                    message_id: &tracked.message_id,
+                    error: &error,
+                });
+                self.tracked.swap_remove(index);
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+/// Reads the data appended to a blob since its offset, returning whether there was any.
+///
+/// The offset only advances once the events read have been delivered, so data that fails to be
+/// delivered is read again on the next poll.
+async fn read_appended(
+    state: &State,
+    blob: &mut TrackedBlob,
+    out: &mut SourceSender,
+    acknowledgements: bool,
+    bytes_received: &Registered<BytesReceived>,
+    events_received: &Registered<EventsReceived>,
+) -> Result<bool, super::queue::ProcessingError> {
+    let length = state.reader.blob_length(&blob.location).await?;
+    if length <= blob.offset {
+        return Ok(false);
+    }
+
+    let receiver = state
+        .reader
+        .read_blob(
+            &blob.location,
+            Some(blob.offset..length),
+            out,
+            acknowledgements,
+            bytes_received,
+            events_received,
+        )
+        .await?;
+
+    let delivered = match receiver {
+        None => true,
+        Some(receiver) => matches!(
+            receiver.await,
+            BatchStatus::Delivered | BatchStatus::Rejected
+        ),
+    };
+    if delivered {
+        blob.offset = length;
+        Ok(true)
+    } else {
+        Err(super::queue::ProcessingError::ErrorAcknowledgement)
+    }
+}
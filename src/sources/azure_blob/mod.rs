@@ -0,0 +1,765 @@
+use std::{
+    collections::HashMap, convert::TryInto, future::ready, io::ErrorKind, ops::Range, sync::Arc,
+};
+
+use async_compression::tokio::bufread;
+use azure_identity::{AutoRefreshingTokenCredential, DefaultAzureCredential};
+use azure_storage::{prelude::*, CloudLocation, ConnectionString};
+use azure_storage_blobs::prelude::*;
+use azure_storage_queues::prelude::*;
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use futures::{stream, stream::StreamExt, Stream, TryStreamExt};
+use smallvec::SmallVec;
+use snafu::{ResultExt, Snafu};
+use tokio_util::codec::FramedRead;
+use tokio_util::io::StreamReader;
+use vector_lib::codecs::decoding::{
+    DeserializerConfig, FramingConfig, NewlineDelimitedDecoderOptions,
+};
+use vector_lib::codecs::NewlineDelimitedDecoderConfig;
+use vector_lib::config::{log_schema, LegacyKey, LogNamespace};
+use vector_lib::configurable::configurable_component;
+use vector_lib::event::MaybeAsLogMut;
+use vector_lib::internal_event::{
+    ByteSize, BytesReceived, CountByteSize, InternalEventHandle as _, Registered,
+};
+use vector_lib::lookup::{metadata_path, owned_value_path, path, PathPrefix};
+use vector_lib::sensitive_string::SensitiveString;
+use vrl::value::{kind::Collection, Kind};
+
+use super::util::MultilineConfig;
+use crate::codecs::{Decoder, DecodingConfig};
+use crate::{
+    config::{SourceAcknowledgementsConfig, SourceConfig, SourceContext, SourceOutput},
+    event::{BatchNotifier, BatchStatusReceiver, EstimatedJsonEncodedSizeOf, Event, LogEvent},
+    internal_events::{EventsReceived, StreamClosedError},
+    line_agg::{self, LineAgg},
+    serde::{bool_or_struct, default_decoding},
+    SourceSender,
+};
+
+mod append;
+mod queue;
+
+/// Compression scheme for blobs retrieved from Azure Blob Storage.
+#[configurable_component]
+#[configurable(metadata(docs::advanced))]
+#[derive(Clone, Copy, Debug, Derivative, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+#[derivative(Default)]
+pub enum Compression {
+    /// Automatically attempt to determine the compression scheme.
+    ///
+    /// The compression scheme of the blob is determined from its `Content-Encoding` and
+    /// `Content-Type` properties, as well as the blob name suffix (for example, `.gz`).
+    ///
+    /// It is set to `none` if the compression scheme cannot be determined.
+    #[derivative(Default)]
+    Auto,
+
+    /// Uncompressed.
+    None,
+
+    /// GZIP.
+    Gzip,
+
+    /// ZSTD.
+    Zstd,
+}
+
+/// Append blob options.
+#[configurable_component]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub(super) struct AppendBlobsConfig {
+    /// How often to poll followed append blobs for appended data, in seconds.
+    ///
+    /// This must be lower than `queue.visibility_timeout_secs`, as the queue message of a blob is
+    /// kept invisible for as long as the blob is followed.
+    #[serde(default = "default_append_poll_secs")]
+    #[derivative(Default(value = "default_append_poll_secs()"))]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    pub(super) poll_secs: u64,
+
+    /// How long an append blob is followed after data was last appended to it, in seconds.
+    ///
+    /// Azure diagnostic logs are written to a new append blob every hour, so the default follows a
+    /// blob for an hour after its last write.
+    #[serde(default = "default_append_idle_timeout_secs")]
+    #[derivative(Default(value = "default_append_idle_timeout_secs()"))]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    pub(super) idle_timeout_secs: u64,
+}
+
+const fn default_append_poll_secs() -> u64 {
+    60
+}
+
+const fn default_append_idle_timeout_secs() -> u64 {
+    3600
+}
+
+/// Configuration for the `azure_blob` source.
+#[configurable_component(source("azure_blob", "Collect logs from Azure Blob Storage."))]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub struct AzureBlobConfig {
+    /// The Azure Blob Storage Account connection string.
+    ///
+    /// Authentication with access key is the only supported authentication method.
+    ///
+    /// Either `storage_account`, or this field, must be specified.
+    #[configurable(metadata(
+        docs::examples = "DefaultEndpointsProtocol=https;AccountName=mylogstorage;AccountKey=storageaccountkeybase64encoded;EndpointSuffix=core.windows.net"
+    ))]
+    connection_string: Option<SensitiveString>,
+
+    /// The Azure Blob Storage Account name.
+    ///
+    /// Attempts to load credentials for the account in the following ways, in order:
+    ///
+    /// - read from environment variables ([more information][env_cred_docs])
+    /// - looks for a [Managed Identity][managed_ident_docs]
+    /// - uses the `az` CLI tool to get an access token ([more information][az_cli_docs])
+    ///
+    /// Either `connection_string`, or this field, must be specified.
+    ///
+    /// [env_cred_docs]: https://docs.rs/azure_identity/latest/azure_identity/struct.EnvironmentCredential.html
+    /// [managed_ident_docs]: https://docs.microsoft.com/en-us/azure/active-directory/managed-identities-azure-resources/overview
+    /// [az_cli_docs]: https://docs.microsoft.com/en-us/cli/azure/account?view=azure-cli-latest#az-account-get-access-token
+    #[configurable(metadata(docs::examples = "mylogstorage"))]
+    storage_account: Option<String>,
+
+    /// The Azure Blob Storage Endpoint URL.
+    ///
+    /// This is used to override the default blob storage endpoint URL when authenticating with
+    /// `storage_account`. With `connection_string`, the blob endpoint of the connection string is
+    /// used instead.
+    #[configurable(metadata(docs::examples = "https://test.blob.core.usgovcloudapi.net/"))]
+    #[configurable(metadata(docs::examples = "https://test.blob.core.windows.net/"))]
+    endpoint: Option<String>,
+
+    /// Configuration options for the storage queue receiving blob events.
+    queue: queue::Config,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    append_blobs: AppendBlobsConfig,
+
+    /// The compression scheme used for decompressing blobs retrieved from Azure Blob Storage.
+    #[serde(default)]
+    compression: Compression,
+
+    /// Multiline aggregation configuration.
+    ///
+    /// If not specified, multiline aggregation is disabled.
+    #[configurable(derived)]
+    multiline: Option<MultilineConfig>,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: SourceAcknowledgementsConfig,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[configurable(metadata(docs::hidden))]
+    #[serde(default)]
+    log_namespace: Option<bool>,
+
+    #[configurable(derived)]
+    #[serde(default = "default_framing")]
+    #[derivative(Default(value = "default_framing()"))]
+    pub framing: FramingConfig,
+
+    #[configurable(derived)]
+    #[serde(default = "default_decoding")]
+    #[derivative(Default(value = "default_decoding()"))]
+    pub decoding: DeserializerConfig,
+}
+
+const fn default_framing() -> FramingConfig {
+    FramingConfig::NewlineDelimited(NewlineDelimitedDecoderConfig {
+        newline_delimited: NewlineDelimitedDecoderOptions { max_length: None },
+    })
+}
+
+impl_generate_config_from_default!(AzureBlobConfig);
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "azure_blob")]
+impl SourceConfig for AzureBlobConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let log_namespace = cx.log_namespace(self.log_namespace);
+
+        let multiline: Option<line_agg::Config> = self
+            .multiline
+            .as_ref()
+            .map(|config| config.try_into())
+            .transpose()?;
+
+        let decoder =
+            DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace)
+                .build()?;
+
+        let (blob_client, queue_client) = self.build_clients()?;
+        let reader = BlobReader {
+            client: blob_client,
+            compression: self.compression,
+            multiline,
+            decoder,
+            log_namespace,
+        };
+        let ingestor = queue::Ingestor::new(
+            queue_client,
+            reader,
+            self.queue.clone(),
+            self.append_blobs.clone(),
+        )?;
+
+        Ok(Box::pin(ingestor.run(cx, self.acknowledgements)))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
+        let log_namespace = global_log_namespace.merge(self.log_namespace);
+        let mut schema_definition = self
+            .decoding
+            .schema_definition(log_namespace)
+            .with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::Overwrite(owned_value_path!("container"))),
+                &owned_value_path!("container"),
+                Kind::bytes(),
+                None,
+            )
+            .with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::Overwrite(owned_value_path!("blob"))),
+                &owned_value_path!("blob"),
+                Kind::bytes(),
+                None,
+            )
+            .with_source_metadata(
+                Self::NAME,
+                None,
+                &owned_value_path!("timestamp"),
+                Kind::timestamp(),
+                Some("timestamp"),
+            )
+            .with_standard_vector_source_metadata()
+            // for metadata that is added to the events dynamically from the metadata
+            .with_source_metadata(
+                Self::NAME,
+                None,
+                &owned_value_path!("metadata"),
+                Kind::object(Collection::empty().with_unknown(Kind::bytes())).or_undefined(),
+                None,
+            );
+
+        // for metadata that is added to the events dynamically from the metadata
+        if log_namespace == LogNamespace::Legacy {
+            schema_definition = schema_definition.unknown_fields(Kind::bytes());
+        }
+
+        vec![SourceOutput::new_logs(
+            self.decoding.output_type(),
+            schema_definition,
+        )]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+#[derive(Debug, Snafu)]
+enum BuildClientsError {
+    #[snafu(display("Either `connection_string` or `storage_account` has to be provided"))]
+    MissingCredentials,
+    #[snafu(display(
+        "`connection_string` and `storage_account` can't be provided at the same time"
+    ))]
+    ConflictingCredentials,
+    #[snafu(display("Account name missing in connection string"))]
+    MissingAccountName,
+}
+
+impl AzureBlobConfig {
+    fn build_clients(&self) -> crate::Result<(BlobServiceClient, QueueClient)> {
+        let (blob_builder, queue_builder) = match (&self.connection_string, &self.storage_account) {
+            (Some(connection_string), None) => {
+                let connection_string = ConnectionString::new(connection_string.inner())?;
+                let credentials = connection_string.storage_credentials()?;
+                let account = connection_string.account_name;
+
+                // Endpoints given in the connection string take precedence, otherwise the
+                // endpoints of the account in Azure Commercial are used.
+                let blob_builder = match connection_string.blob_endpoint {
+                    Some(uri) => ClientBuilder::with_location(
+                        CloudLocation::Custom {
+                            uri: uri.to_string(),
+                        },
+                        credentials.clone(),
+                    ),
+                    None => ClientBuilder::new(
+                        account.ok_or(BuildClientsError::MissingAccountName)?,
+                        credentials.clone(),
+                    ),
+                };
+                let queue_builder = match connection_string.queue_endpoint {
+                    Some(uri) => QueueServiceClientBuilder::with_location(
+                        CloudLocation::Custom {
+                            uri: uri.to_string(),
+                        },
+                        credentials,
+                    ),
+                    None => QueueServiceClientBuilder::new(
+                        account.ok_or(BuildClientsError::MissingAccountName)?,
+                        credentials,
+                    ),
+                };
+                (blob_builder, queue_builder)
+            }
+            (None, Some(storage_account)) => {
+                let creds = Arc::new(DefaultAzureCredential::default());
+                let auto_creds = Arc::new(AutoRefreshingTokenCredential::new(creds));
+                let credentials = StorageCredentials::token_credential(auto_creds);
+
+                let blob_builder = match &self.endpoint {
+                    Some(endpoint) => ClientBuilder::with_location(
+                        CloudLocation::Custom {
+                            uri: endpoint.clone(),
+                        },
+                        credentials.clone(),
+                    ),
+                    None => ClientBuilder::new(storage_account, credentials.clone()),
+                };
+                let queue_builder = match &self.queue.endpoint {
+                    Some(endpoint) => QueueServiceClientBuilder::with_location(
+                        CloudLocation::Custom {
+                            uri: endpoint.clone(),
+                        },
+                        credentials,
+                    ),
+                    None => QueueServiceClientBuilder::new(storage_account, credentials),
+                };
+                (blob_builder, queue_builder)
+            }
+            (None, None) => return Err(BuildClientsError::MissingCredentials.into()),
+            (Some(_), Some(_)) => return Err(BuildClientsError::ConflictingCredentials.into()),
+        };
+
+        Ok((
+            blob_builder.blob_service_client(),
+            queue_builder
+                .build()
+                .queue_client(self.queue.queue_name.clone()),
+        ))
+    }
+}
+
+/// The location of a blob within a storage account.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) struct BlobLocation {
+    pub(super) container: String,
+    pub(super) blob: String,
+}
+
+/// Reads blobs into events.
+pub(super) struct BlobReader {
+    client: BlobServiceClient,
+    compression: Compression,
+    multiline: Option<line_agg::Config>,
+    decoder: Decoder,
+    log_namespace: LogNamespace,
+}
+
+impl BlobReader {
+    fn blob_client(&self, location: &BlobLocation) -> BlobClient {
+        self.client
+            .container_client(location.container.clone())
+            .blob_client(location.blob.clone())
+    }
+
+    /// Fetches the current length of a blob.
+    pub(super) async fn blob_length(
+        &self,
+        location: &BlobLocation,
+    ) -> Result<u64, queue::ProcessingError> {
+        self.blob_client(location)
+            .get_properties()
+            .into_future()
+            .await
+            .map(|response| response.blob.properties.content_length)
+            .context(queue::GetBlobSnafu {
+                container: &location.container,
+                blob: &location.blob,
+            })
+    }
+
+    /// Fetches a blob, or a range of bytes of it, and sends its events.
+    ///
+    /// A range is read as is, without decompression, as a compressed stream cannot be decoded
+    /// from its middle.
+    ///
+    /// If acknowledgements are enabled, the returned receiver resolves to the status of the
+    /// blob's events once they have all been delivered.
+    pub(super) async fn read_blob(
+        &self,
+        location: &BlobLocation,
+        range: Option<Range<u64>>,
+        out: &mut SourceSender,
+        acknowledgements: bool,
+        bytes_received: &Registered<BytesReceived>,
+        events_received: &Registered<EventsReceived>,
+    ) -> Result<Option<BatchStatusReceiver>, queue::ProcessingError> {
+        let BlobLocation { container, blob } = location;
+
+        let compression = if range.is_some() {
+            Compression::None
+        } else {
+            self.compression
+        };
+        let mut get = self.blob_client(location).get();
+        if let Some(range) = range {
+            get = get.range(range);
+        }
+
+        // Blobs are fetched in chunks, each response carrying the properties of the blob.
+        let mut responses = get.into_stream();
+        let first = match responses.next().await {
+            Some(response) => response.context(queue::GetBlobSnafu { container, blob })?,
+            None => return Ok(None),
+        };
+
+        let properties = &first.blob.properties;
+        let content_encoding = properties.content_encoding.clone();
+        let content_type = properties.content_type.clone();
+        let timestamp = DateTime::from_timestamp(
+            properties.last_modified.unix_timestamp(),
+            properties.last_modified.nanosecond(),
+        );
+        let metadata = first.blob.metadata.clone().unwrap_or_default();
+
+        let body = stream::once(ready(Ok(first)))
+            .chain(responses)
+            .map_ok(|response| response.data)
+            .try_flatten()
+            .map_err(|e| std::io::Error::new(ErrorKind::Other, e));
+
+        let (batch, receiver) = BatchNotifier::maybe_new_with_receiver(acknowledgements);
+        let blob_reader = blob_decoder(
+            compression,
+            blob,
+            content_encoding.as_deref(),
+            Some(content_type.as_str()),
+            body,
+        )
+        .await;
+
+        // Record the read error seen to propagate up later so we avoid deleting the message.
+        //
+        // This can result in blobs being partially processed before an error, but we
+        // prefer duplicate lines over message loss.
+        let mut read_error = None;
+        let lines: Box<dyn Stream<Item = Bytes> + Send + Unpin> = Box::new(
+            FramedRead::new(blob_reader, self.decoder.framer.clone())
+                .map(|res| {
+                    res.map(|bytes| {
+                        bytes_received.emit(ByteSize(bytes.len()));
+                        bytes
+                    })
+                    .map_err(|err| {
+                        read_error = Some(err);
+                    })
+                    .ok()
+                })
+                .take_while(|res| ready(res.is_some()))
+                .map(|r| r.expect("validated by take_while")),
+        );
+
+        let lines: Box<dyn Stream<Item = Bytes> + Send + Unpin> = match &self.multiline {
+            Some(config) => Box::new(
+                LineAgg::new(
+                    lines.map(|line| ((), line, ())),
+                    line_agg::Logic::new(config.clone()),
+                )
+                .map(|(_src, line, _context, _lastline_context)| line),
+            ),
+            None => lines,
+        };
+
+        let mut stream = lines.flat_map(|line| {
+            let events = match self.decoder.deserializer_parse(line) {
+                Ok((events, _events_size)) => events,
+                Err(_error) => {
+                    // Error is handled by `codecs::Decoder`, no further handling
+                    // is needed here.
+                    SmallVec::new()
+                }
+            };
+
+            let events = events
+                .into_iter()
+                .map(|mut event: Event| {
+                    event = event.with_batch_notifier_option(&batch);
+                    if let Some(log_event) = event.maybe_as_log_mut() {
+                        handle_single_log(
+                            log_event,
+                            self.log_namespace,
+                            location,
+                            &metadata,
+                            timestamp,
+                        );
+                    }
+                    events_received.emit(CountByteSize(1, event.estimated_json_encoded_size_of()));
+                    event
+                })
+                .collect::<Vec<Event>>();
+            futures::stream::iter(events)
+        });
+
+        let send_error = match out.send_event_stream(&mut stream).await {
+            Ok(_) => None,
+            Err(_) => {
+                let (count, _) = stream.size_hint();
+                emit!(StreamClosedError { count });
+                Some(crate::source_sender::ClosedError)
+            }
+        };
+
+        // Up above, `lines` captures `read_error`, and eventually is captured by `stream`,
+        // so we explicitly drop it so that we can again utilize `read_error` below.
+        drop(stream);
+
+        // The BatchNotifier is cloned for each LogEvent in the batch stream, but the last
+        // reference must be dropped before the status of the batch is sent to the channel.
+        drop(batch);
+
+        if let Some(error) = read_error {
+            Err(queue::ProcessingError::ReadBlob {
+                source: error,
+                container: container.to_owned(),
+                blob: blob.to_owned(),
+            })
+        } else if let Some(error) = send_error {
+            Err(queue::ProcessingError::PipelineSend {
+                source: error,
+                container: container.to_owned(),
+                blob: blob.to_owned(),
+            })
+        } else {
+            Ok(receiver)
+        }
+    }
+}
+
+fn handle_single_log(
+    log: &mut LogEvent,
+    log_namespace: LogNamespace,
+    location: &BlobLocation,
+    metadata: &HashMap<String, String>,
+    timestamp: Option<DateTime<Utc>>,
+) {
+    log_namespace.insert_source_metadata(
+        AzureBlobConfig::NAME,
+        log,
+        Some(LegacyKey::Overwrite(path!("container"))),
+        path!("container"),
+        Bytes::from(location.container.as_bytes().to_vec()),
+    );
+
+    log_namespace.insert_source_metadata(
+        AzureBlobConfig::NAME,
+        log,
+        Some(LegacyKey::Overwrite(path!("blob"))),
+        path!("blob"),
+        Bytes::from(location.blob.as_bytes().to_vec()),
+    );
+
+    for (key, value) in metadata {
+        log_namespace.insert_source_metadata(
+            AzureBlobConfig::NAME,
+            log,
+            Some(LegacyKey::Overwrite(path!(key))),
+            path!("metadata", key.as_str()),
+            value.clone(),
+        );
+    }
+
+    log_namespace.insert_vector_metadata(
+        log,
+        log_schema().source_type_key(),
+        path!("source_type"),
+        Bytes::from_static(AzureBlobConfig::NAME.as_bytes()),
+    );
+
+    match log_namespace {
+        LogNamespace::Vector => {
+            if let Some(timestamp) = timestamp {
+                log.insert(
+                    metadata_path!(AzureBlobConfig::NAME, "timestamp"),
+                    timestamp,
+                );
+            }
+
+            log.insert(metadata_path!("vector", "ingest_timestamp"), Utc::now());
+        }
+        LogNamespace::Legacy => {
+            if let Some(timestamp_key) = log_schema().timestamp_key() {
+                log.try_insert(
+                    (PathPrefix::Event, timestamp_key),
+                    timestamp.unwrap_or_else(Utc::now),
+                );
+            }
+        }
+    };
+}
+
+/// None if body is empty
+async fn blob_decoder(
+    compression: Compression,
+    blob: &str,
+    content_encoding: Option<&str>,
+    content_type: Option<&str>,
+    body: impl Stream<Item = std::io::Result<Bytes>> + Send + Unpin + 'static,
+) -> Box<dyn tokio::io::AsyncRead + Send + Unpin> {
+    let mut body = body.peekable();
+    if std::pin::Pin::new(&mut body).peek().await.is_none() {
+        return Box::new(tokio::io::empty());
+    }
+
+    let r = tokio::io::BufReader::new(StreamReader::new(body));
+
+    let compression = match compression {
+        Auto => determine_compression(content_encoding, content_type, blob).unwrap_or(None),
+        _ => compression,
+    };
+
+    use Compression::*;
+    match compression {
+        Auto => unreachable!(), // is mapped above
+        None => Box::new(r),
+        Gzip => Box::new({
+            let mut decoder = bufread::GzipDecoder::new(r);
+            decoder.multiple_members(true);
+            decoder
+        }),
+        Zstd => Box::new({
+            let mut decoder = bufread::ZstdDecoder::new(r);
+            decoder.multiple_members(true);
+            decoder
+        }),
+    }
+}
+
+// try to determine the compression given the:
+// * content-encoding
+// * content-type
+// * blob name (for file extension)
+//
+// It will use this information in this order
+fn determine_compression(
+    content_encoding: Option<&str>,
+    content_type: Option<&str>,
+    blob: &str,
+) -> Option<Compression> {
+    content_encoding
+        .and_then(content_encoding_to_compression)
+        .or_else(|| content_type.and_then(content_type_to_compression))
+        .or_else(|| blob_name_to_compression(blob))
+}
+
+fn content_encoding_to_compression(content_encoding: &str) -> Option<Compression> {
+    match content_encoding {
+        "gzip" => Some(Compression::Gzip),
+        "zstd" => Some(Compression::Zstd),
+        _ => None,
+    }
+}
+
+fn content_type_to_compression(content_type: &str) -> Option<Compression> {
+    match content_type {
+        "application/gzip" | "application/x-gzip" => Some(Compression::Gzip),
+        "application/zstd" => Some(Compression::Zstd),
+        _ => None,
+    }
+}
+
+fn blob_name_to_compression(blob: &str) -> Option<Compression> {
+    let extension = std::path::Path::new(blob)
+        .extension()
+        .and_then(std::ffi::OsStr::to_str);
+
+    use Compression::*;
+    extension.and_then(|extension| match extension {
+        "gz" => Some(Gzip),
+        "zst" => Some(Zstd),
+        _ => Option::None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::io::AsyncReadExt;
+
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<AzureBlobConfig>();
+    }
+
+    #[test]
+    fn determine_compression() {
+        use super::Compression;
+
+        let cases = vec![
+            ("out.log", Some("gzip"), None, Some(Compression::Gzip)),
+            (
+                "out.log",
+                None,
+                Some("application/zstd"),
+                Some(Compression::Zstd),
+            ),
+            ("out.log.gz", None, None, Some(Compression::Gzip)),
+            ("PT1H.json", None, Some("application/json"), None),
+        ];
+        for case in cases {
+            let (blob, content_encoding, content_type, expected) = case;
+            assert_eq!(
+                super::determine_compression(content_encoding, content_type, blob),
+                expected,
+                "blob={:?} content_encoding={:?} content_type={:?}",
+                blob,
+                content_encoding,
+                content_type,
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn decode_empty_blob_gzip() {
+        let mut data = Vec::new();
+        blob_decoder(
+            Compression::Auto,
+            "out.log.gz",
+            None,
+            None,
+            stream::empty::<std::io::Result<Bytes>>(),
+        )
+        .await
+        .read_to_end(&mut data)
+        .await
+        .unwrap();
+
+        assert!(data.is_empty());
+    }
+
+    #[test]
+    fn requires_credentials() {
+        let config = AzureBlobConfig::default();
+        assert!(config.build_clients().is_err());
+    }
+}
@@ -0,0 +1,608 @@
+use std::{num::NonZeroUsize, panic, sync::Arc, time::Duration};
+
+use azure_storage_queues::{operations::Message, prelude::*};
+use base64::prelude::{Engine as _, BASE64_STANDARD};
+use futures::FutureExt;
+use serde::Deserialize;
+use snafu::{ResultExt, Snafu};
+use tokio::{pin, select, sync::mpsc};
+use tracing::Instrument;
+use vector_lib::codecs::decoding::FramingError;
+use vector_lib::configurable::configurable_component;
+use vector_lib::internal_event::{BytesReceived, Protocol, Registered};
+
+use super::{
+    append::{AppendBlobTracker, TrackedMessage},
+    BlobLocation, BlobReader,
+};
+use crate::{
+    config::{SourceAcknowledgementsConfig, SourceContext},
+    event::BatchStatus,
+    internal_events::{
+        AzureBlobEventIgnored, AzureQueueMessageDeleteError, AzureQueueMessageProcessingError,
+        AzureQueueMessageReceiveError, EventsReceived,
+    },
+    shutdown::ShutdownSignal,
+    SourceSender,
+};
+
+const BLOB_CREATED: &str = "Microsoft.Storage.BlobCreated";
+const APPEND_BLOB: &str = "AppendBlob";
+
+// The Queue service returns at most 32 messages per request.
+const MAX_NUMBER_OF_MESSAGES: u32 = 32;
+
+/// Storage Queue configuration options.
+#[configurable_component]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub(super) struct Config {
+    /// The name of the storage queue to poll for blob notifications.
+    ///
+    /// The queue must be in the same storage account as the blobs, and receive the
+    /// `Microsoft.Storage.BlobCreated` events of an [Event Grid subscription][event_grid].
+    ///
+    /// [event_grid]: https://learn.microsoft.com/en-us/azure/storage/blobs/storage-blob-event-overview
+    #[configurable(metadata(docs::examples = "blob-created"))]
+    pub(super) queue_name: String,
+
+    /// The Azure Queue Storage endpoint URL.
+    ///
+    /// This is used to override the default queue endpoint URL when authenticating with
+    /// `storage_account`. With `connection_string`, the queue endpoint of the connection string is
+    /// used instead.
+    #[configurable(metadata(docs::examples = "https://mylogstorage.queue.core.windows.net/"))]
+    pub(super) endpoint: Option<String>,
+
+    /// How long to wait before polling the queue again after it was found empty, in seconds.
+    #[serde(default = "default_poll_secs")]
+    #[derivative(Default(value = "default_poll_secs()"))]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    pub(super) poll_secs: u32,
+
+    /// Maximum number of messages to fetch from the queue in a batch.
+    ///
+    /// Valid values are 1 - 32.
+    #[serde(default = "default_max_number_of_messages")]
+    #[derivative(Default(value = "default_max_number_of_messages()"))]
+    #[configurable(metadata(docs::examples = 1))]
+    pub(super) max_number_of_messages: u32,
+
+    /// The visibility timeout to use for messages, in seconds.
+    ///
+    /// This controls how long a message is left unavailable after it is received. If a message is
+    /// received, and takes longer than `visibility_timeout_secs` to process and delete the message
+    /// from the queue, it is made available again for another consumer.
+    ///
+    /// This can happen if there is an issue between consuming a message and deleting it.
+    #[serde(default = "default_visibility_timeout_secs")]
+    #[derivative(Default(value = "default_visibility_timeout_secs()"))]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    #[configurable(metadata(docs::human_name = "Visibility Timeout"))]
+    pub(super) visibility_timeout_secs: u32,
+
+    /// Number of concurrent tasks to create for polling the queue for messages.
+    ///
+    /// Defaults to the number of available CPUs on the system.
+    ///
+    /// Should not typically need to be changed, but it can sometimes be beneficial to raise this
+    /// value when there is a high rate of messages being pushed into the queue and the blobs
+    /// being fetched are small. In these cases, system resources may not be fully utilized without
+    /// fetching more messages per second, as the message consumption rate affects the blob
+    /// retrieval rate.
+    #[configurable(metadata(docs::type_unit = "tasks"))]
+    #[configurable(metadata(docs::examples = 5))]
+    pub(super) client_concurrency: Option<NonZeroUsize>,
+
+    /// Whether to delete non-retryable messages.
+    ///
+    /// If a message is rejected by the sink and not retryable, it is deleted from the queue.
+    #[serde(default = "default_true")]
+    #[derivative(Default(value = "default_true()"))]
+    pub(super) delete_failed_message: bool,
+}
+
+const fn default_poll_secs() -> u32 {
+    15
+}
+
+const fn default_max_number_of_messages() -> u32 {
+    10
+}
+
+const fn default_visibility_timeout_secs() -> u32 {
+    300
+}
+
+const fn default_true() -> bool {
+    true
+}
+
+#[derive(Debug, Snafu)]
+pub(super) enum IngestorNewError {
+    #[snafu(display("Invalid value for max_number_of_messages {}", messages))]
+    InvalidNumberOfMessages { messages: u32 },
+    #[snafu(display(
+        "`append_blobs.poll_secs` ({}) must be lower than `queue.visibility_timeout_secs` ({})",
+        poll_secs,
+        visibility_timeout_secs
+    ))]
+    InvalidAppendBlobPoll {
+        poll_secs: u64,
+        visibility_timeout_secs: u32,
+    },
+}
+
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Snafu)]
+#[snafu(visibility(pub(super)))]
+pub enum ProcessingError {
+    #[snafu(display(
+        "Could not parse queue message with id {} as a blob event: {}",
+        message_id,
+        source
+    ))]
+    InvalidQueueMessage {
+        source: serde_json::Error,
+        message_id: String,
+    },
+    #[snafu(display("Blob event subject {:?} does not name a blob", subject))]
+    InvalidSubject { subject: String },
+    #[snafu(display("Failed to fetch {}/{}: {}", container, blob, source))]
+    GetBlob {
+        source: azure_core::Error,
+        container: String,
+        blob: String,
+    },
+    #[snafu(display("Failed to read all of {}/{}: {}", container, blob, source))]
+    ReadBlob {
+        source: Box<dyn FramingError>,
+        container: String,
+        blob: String,
+    },
+    #[snafu(display("Failed to flush all of {}/{}: {}", container, blob, source))]
+    PipelineSend {
+        source: crate::source_sender::ClosedError,
+        container: String,
+        blob: String,
+    },
+    #[snafu(display("Sink reported an error sending events"))]
+    ErrorAcknowledgement,
+}
+
+/// A blob storage event, in either the [Event Grid][event_grid_schema] or the
+/// [CloudEvents][cloud_events_schema] schema.
+///
+/// [event_grid_schema]: https://learn.microsoft.com/en-us/azure/event-grid/event-schema-blob-storage
+/// [cloud_events_schema]: https://learn.microsoft.com/en-us/azure/event-grid/cloud-event-schema
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct BlobEvent {
+    #[serde(alias = "type")]
+    event_type: String,
+    subject: String,
+    #[serde(default)]
+    data: BlobEventData,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BlobEventData {
+    blob_type: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(untagged)]
+enum BlobEvents {
+    One(BlobEvent),
+    Many(Vec<BlobEvent>),
+}
+
+impl BlobEvent {
+    /// Parses the events carried by a queue message.
+    ///
+    /// Event Grid delivers events to storage queues as JSON, but queue messages are often
+    /// base64-encoded by their producers, so both forms are accepted.
+    fn parse_message(message_id: &str, text: &str) -> Result<Vec<Self>, ProcessingError> {
+        let decoded = BASE64_STANDARD.decode(text.trim()).ok();
+        let json = decoded.as_deref().unwrap_or(text.as_bytes());
+        let events = serde_json::from_slice::<BlobEvents>(json)
+            .or_else(|_| serde_json::from_str::<BlobEvents>(text))
+            .context(InvalidQueueMessageSnafu { message_id })?;
+        Ok(match events {
+            BlobEvents::One(event) => vec![event],
+            BlobEvents::Many(events) => events,
+        })
+    }
+
+    /// Extracts the container and blob names from the event subject, which has the form
+    /// `/blobServices/default/containers/<container>/blobs/<blob>`.
+    fn location(&self) -> Result<BlobLocation, ProcessingError> {
+        self.subject
+            .strip_prefix("/blobServices/default/containers/")
+            .and_then(|path| path.split_once("/blobs/"))
+            .filter(|(container, blob)| !container.is_empty() && !blob.is_empty())
+            .map(|(container, blob)| BlobLocation {
+                container: container.to_owned(),
+                blob: blob.to_owned(),
+            })
+            .ok_or_else(|| ProcessingError::InvalidSubject {
+                subject: self.subject.clone(),
+            })
+    }
+
+    fn is_append_blob(&self) -> bool {
+        self.data.blob_type.as_deref() == Some(APPEND_BLOB)
+    }
+}
+
+/// The outcome of processing a queue message.
+enum Processed {
+    /// The message is done with and can be deleted.
+    Complete,
+    /// The message names append blobs, which are now followed by the append blob tracker.
+    Tracked(Vec<BlobLocation>),
+}
+
+pub struct State {
+    pub(super) queue_client: QueueClient,
+    pub(super) reader: BlobReader,
+
+    poll_secs: u64,
+    max_number_of_messages: u8,
+    client_concurrency: usize,
+    pub(super) visibility_timeout: Duration,
+    delete_failed_message: bool,
+}
+
+pub(super) struct Ingestor {
+    state: Arc<State>,
+    append_blobs: super::AppendBlobsConfig,
+}
+
+impl Ingestor {
+    pub(super) fn new(
+        queue_client: QueueClient,
+        reader: BlobReader,
+        config: Config,
+        append_blobs: super::AppendBlobsConfig,
+    ) -> Result<Ingestor, IngestorNewError> {
+        if config.max_number_of_messages < 1
+            || config.max_number_of_messages > MAX_NUMBER_OF_MESSAGES
+        {
+            return Err(IngestorNewError::InvalidNumberOfMessages {
+                messages: config.max_number_of_messages,
+            });
+        }
+        // Tracked messages are kept invisible by renewing their visibility timeout on every poll
+        // of their append blobs.
+        if append_blobs.poll_secs >= u64::from(config.visibility_timeout_secs) {
+            return Err(IngestorNewError::InvalidAppendBlobPoll {
+                poll_secs: append_blobs.poll_secs,
+                visibility_timeout_secs: config.visibility_timeout_secs,
+            });
+        }
+
+        let state = Arc::new(State {
+            queue_client,
+            reader,
+
+            poll_secs: config.poll_secs.into(),
+            max_number_of_messages: config.max_number_of_messages as u8,
+            client_concurrency: config
+                .client_concurrency
+                .map(|n| n.get())
+                .unwrap_or_else(crate::num_threads),
+            visibility_timeout: Duration::from_secs(config.visibility_timeout_secs.into()),
+            delete_failed_message: config.delete_failed_message,
+        });
+
+        Ok(Ingestor {
+            state,
+            append_blobs,
+        })
+    }
+
+    pub(super) async fn run(
+        self,
+        cx: SourceContext,
+        acknowledgements: SourceAcknowledgementsConfig,
+    ) -> Result<(), ()> {
+        let acknowledgements = cx.do_acknowledgements(acknowledgements);
+        let (tracked_sender, tracked_receiver) = mpsc::channel(self.state.client_concurrency);
+
+        let mut handles = Vec::new();
+        let tracker = AppendBlobTracker::new(
+            Arc::clone(&self.state),
+            &self.append_blobs,
+            tracked_receiver,
+            cx.out.clone(),
+            cx.shutdown.clone(),
+            acknowledgements,
+        );
+        handles.push(tokio::spawn(tracker.run().in_current_span()));
+
+        for _ in 0..self.state.client_concurrency {
+            let process = IngestorProcess::new(
+                Arc::clone(&self.state),
+                tracked_sender.clone(),
+                cx.out.clone(),
+                cx.shutdown.clone(),
+                acknowledgements,
+            );
+            let fut = process.run();
+            let handle = tokio::spawn(fut.in_current_span());
+            handles.push(handle);
+        }
+
+        // Wait for all of the processes to finish.  If any one of them panics, we resume
+        // that panic here to properly shutdown Vector.
+        for handle in handles.drain(..) {
+            if let Err(e) = handle.await {
+                if e.is_panic() {
+                    panic::resume_unwind(e.into_panic());
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub struct IngestorProcess {
+    state: Arc<State>,
+    tracked: mpsc::Sender<TrackedMessage>,
+    out: SourceSender,
+    shutdown: ShutdownSignal,
+    acknowledgements: bool,
+    bytes_received: Registered<BytesReceived>,
+    events_received: Registered<EventsReceived>,
+}
+
+impl IngestorProcess {
+    pub fn new(
+        state: Arc<State>,
+        tracked: mpsc::Sender<TrackedMessage>,
+        out: SourceSender,
+        shutdown: ShutdownSignal,
+        acknowledgements: bool,
+    ) -> Self {
+        Self {
+            state,
+            tracked,
+            out,
+            shutdown,
+            acknowledgements,
+            bytes_received: register!(BytesReceived::from(Protocol::HTTP)),
+            events_received: register!(EventsReceived),
+        }
+    }
+
+    async fn run(mut self) {
+        let shutdown = self.shutdown.clone().fuse();
+        pin!(shutdown);
+
+        loop {
+            select! {
+                _ = &mut shutdown => break,
+                _ = self.run_once() => {},
+            }
+        }
+    }
+
+    async fn run_once(&mut self) {
+        let messages = match self.receive_messages().await {
+            Ok(messages) => messages,
+            Err(error) => {
+                emit!(AzureQueueMessageReceiveError { error: &error });
+                Vec::new()
+            }
+        };
+
+        // Unlike SQS, the Queue service has no long polling, so an empty queue is polled again
+        // after a delay.
+        if messages.is_empty() {
+            tokio::time::sleep(Duration::from_secs(self.state.poll_secs)).await;
+            return;
+        }
+
+        for message in messages {
+            match self.handle_message(&message).await {
+                Ok(Processed::Complete) => {
+                    let pop_receipt =
+                        PopReceipt::new(message.message_id.clone(), message.pop_receipt.clone());
+                    if let Err(error) = self
+                        .state
+                        .queue_client
+                        .pop_receipt_client(pop_receipt)
+                        .delete()
+                        .into_future()
+                        .await
+                    {
+                        emit!(AzureQueueMessageDeleteError {
+                            message_id: &message.message_id,
+                            error: &error,
+                        });
+                    }
+                }
+                Ok(Processed::Tracked(blobs)) => {
+                    let tracked = TrackedMessage {
+                        message_id: message.message_id,
+                        pop_receipt: message.pop_receipt,
+                        blobs,
+                    };
+                    if self.tracked.send(tracked).await.is_err() {
+                        // The tracker only stops on shutdown, so the message is left to become
+                        // visible again.
+                        break;
+                    }
+                }
+                Err(error) => {
+                    emit!(AzureQueueMessageProcessingError {
+                        message_id: &message.message_id,
+                        error: &error,
+                    });
+                }
+            }
+        }
+    }
+
+    async fn handle_message(&mut self, message: &Message) -> Result<Processed, ProcessingError> {
+        let events = BlobEvent::parse_message(&message.message_id, &message.message_text)?;
+
+        let mut append_blobs = Vec::new();
+        for event in events {
+            if event.event_type != BLOB_CREATED {
+                emit!(AzureBlobEventIgnored {
+                    subject: &event.subject,
+                    event_type: &event.event_type,
+                });
+                continue;
+            }
+
+            let location = event.location()?;
+            if event.is_append_blob() {
+                append_blobs.push(location);
+            } else {
+                self.read_blob(&location).await?;
+            }
+        }
+
+        if append_blobs.is_empty() {
+            Ok(Processed::Complete)
+        } else {
+            Ok(Processed::Tracked(append_blobs))
+        }
+    }
+
+    async fn read_blob(&mut self, location: &BlobLocation) -> Result<(), ProcessingError> {
+        let receiver = self
+            .state
+            .reader
+            .read_blob(
+                location,
+                None,
+                &mut self.out,
+                self.acknowledgements,
+                &self.bytes_received,
+                &self.events_received,
+            )
+            .await?;
+
+        match receiver {
+            None => Ok(()),
+            Some(receiver) => match receiver.await {
+                BatchStatus::Delivered => Ok(()),
+                BatchStatus::Errored => Err(ProcessingError::ErrorAcknowledgement),
+                BatchStatus::Rejected => {
+                    if self.state.delete_failed_message {
+                        Ok(())
+                    } else {
+                        Err(ProcessingError::ErrorAcknowledgement)
+                    }
+                }
+            },
+        }
+    }
+
+    async fn receive_messages(&mut self) -> azure_core::Result<Vec<Message>> {
+        self.state
+            .queue_client
+            .get_messages()
+            .number_of_messages(self.state.max_number_of_messages)
+            .visibility_timeout(self.state.visibility_timeout)
+            .into_future()
+            .await
+            .map(|response| response.messages)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EVENT_GRID_EVENT: &str = r#"{
+        "topic": "/subscriptions/00000000-0000-0000-0000-000000000000/resourceGroups/logs/providers/Microsoft.Storage/storageAccounts/mylogstorage",
+        "subject": "/blobServices/default/containers/insights-logs/blobs/resourceId=/SUBSCRIPTIONS/y=2024/m=01/d=01/h=00/m=00/PT1H.json",
+        "eventType": "Microsoft.Storage.BlobCreated",
+        "id": "831e1650-001e-001b-66ab-eeb76e069631",
+        "data": {
+            "api": "PutBlob",
+            "contentType": "application/json",
+            "contentLength": 0,
+            "blobType": "AppendBlob",
+            "url": "https://mylogstorage.blob.core.windows.net/insights-logs/resourceId=/SUBSCRIPTIONS/y=2024/m=01/d=01/h=00/m=00/PT1H.json"
+        },
+        "dataVersion": "",
+        "metadataVersion": "1",
+        "eventTime": "2024-01-01T00:00:00.0000000Z"
+    }"#;
+
+    const CLOUD_EVENT: &str = r#"{
+        "source": "/subscriptions/00000000-0000-0000-0000-000000000000/resourceGroups/logs/providers/Microsoft.Storage/storageAccounts/mylogstorage",
+        "subject": "/blobServices/default/containers/logs/blobs/app.log.gz",
+        "type": "Microsoft.Storage.BlobCreated",
+        "id": "9aeb0fdf-c01e-0131-0922-9eb54906e209",
+        "data": {
+            "api": "PutBlockList",
+            "blobType": "BlockBlob"
+        },
+        "specversion": "1.0"
+    }"#;
+
+    #[test]
+    fn parses_event_grid_events() {
+        let events = BlobEvent::parse_message("1", EVENT_GRID_EVENT).unwrap();
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event_type, BLOB_CREATED);
+        assert!(events[0].is_append_blob());
+        assert_eq!(
+            events[0].location().unwrap(),
+            BlobLocation {
+                container: "insights-logs".into(),
+                blob: "resourceId=/SUBSCRIPTIONS/y=2024/m=01/d=01/h=00/m=00/PT1H.json".into(),
+            }
+        );
+    }
+
+    #[test]
+    fn parses_base64_encoded_cloud_events() {
+        let text = BASE64_STANDARD.encode(format!("[{}]", CLOUD_EVENT));
+        let events = BlobEvent::parse_message("1", &text).unwrap();
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event_type, BLOB_CREATED);
+        assert!(!events[0].is_append_blob());
+        assert_eq!(
+            events[0].location().unwrap(),
+            BlobLocation {
+                container: "logs".into(),
+                blob: "app.log.gz".into(),
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_messages() {
+        assert!(matches!(
+            BlobEvent::parse_message("1", "not an event"),
+            Err(ProcessingError::InvalidQueueMessage { .. })
+        ));
+    }
+
+    #[test]
+    fn rejects_subjects_without_blobs() {
+        let event = BlobEvent {
+            event_type: BLOB_CREATED.into(),
+            subject: "/blobServices/default/containers/logs".into(),
+            data: Default::default(),
+        };
+
+        assert!(matches!(
+            event.location(),
+            Err(ProcessingError::InvalidSubject { .. })
+        ));
+    }
+}
@@ -0,0 +1,575 @@
+use std::{
+    collections::HashMap,
+    net::{Ipv4Addr, SocketAddr, SocketAddrV4},
+    num::NonZeroU32,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use bytes::Bytes;
+use futures::{StreamExt, TryFutureExt};
+use listenfd::ListenFd;
+use smallvec::SmallVec;
+use snafu::{ResultExt, Snafu};
+use tokio_util::udp::UdpFramed;
+use vector_lib::codecs::{
+    decoding::{self, Deserializer, Framer},
+    BytesDecoder,
+};
+use vector_lib::config::LogNamespace;
+use vector_lib::configurable::configurable_component;
+use vector_lib::internal_event::{CountByteSize, InternalEventHandle as _, Registered};
+use vector_lib::EstimatedJsonEncodedSizeOf;
+
+use self::protocol::{SecurityLevel, Value, ValueList};
+use super::util::net::{try_bind_udp_socket, SocketListenAddr};
+use crate::{
+    codecs::Decoder,
+    config::{GenerateConfig, Resource, SourceConfig, SourceContext, SourceOutput},
+    event::{
+        metric::{Metric, MetricKind, MetricTags, MetricValue},
+        Event,
+    },
+    internal_events::{
+        EventsReceived, SocketBindError, SocketBytesReceived, SocketMode, SocketReceiveError,
+        StreamClosedError,
+    },
+    net,
+    shutdown::ShutdownSignal,
+    SourceSender,
+};
+
+mod protocol;
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Could not read file {}: {}", path.display(), source))]
+    ReadFile {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[snafu(display("An auth file is required with security level {:?}", security_level))]
+    MissingAuthFile { security_level: SecurityLevelConfig },
+    #[snafu(display("Invalid line {} in auth file {}", line, path.display()))]
+    InvalidAuthFile { path: PathBuf, line: usize },
+}
+
+/// Configuration for the `collectd` source.
+#[configurable_component(source("collectd", "Collect metrics sent by collectd's network plugin."))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct CollectdConfig {
+    #[configurable(derived)]
+    address: SocketListenAddr,
+
+    /// The size of the receive buffer used for the listening socket.
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    receive_buffer_bytes: Option<usize>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    security_level: SecurityLevelConfig,
+
+    /// The path to a file with the passwords of the users that sign and encrypt packets.
+    ///
+    /// The file uses the format of collectd's `AuthFile`, with one `<user>: <password>` pair per
+    /// line. Required unless `security_level` is `none`, in which case it is used to verify the
+    /// signatures and decrypt the packets of known users.
+    #[configurable(metadata(docs::examples = "/etc/collectd/passwd"))]
+    auth_file: Option<PathBuf>,
+
+    /// Paths to `types.db` files describing the data sources of each collectd type.
+    ///
+    /// Value lists with several values emit one metric per value, named after the value's data
+    /// source. Without a description of its type, the values are named after their position
+    /// instead.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "/usr/share/collectd/types.db"))]
+    types_db: Vec<PathBuf>,
+
+    /// The namespace of the metrics.
+    ///
+    /// Disabled if empty.
+    #[serde(default = "default_namespace")]
+    namespace: String,
+}
+
+/// The minimum protection of accepted values.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SecurityLevelConfig {
+    /// Accept all values.
+    #[default]
+    None,
+
+    /// Only accept signed or encrypted values.
+    Sign,
+
+    /// Only accept encrypted values.
+    Encrypt,
+}
+
+impl From<SecurityLevelConfig> for SecurityLevel {
+    fn from(level: SecurityLevelConfig) -> Self {
+        match level {
+            SecurityLevelConfig::None => SecurityLevel::None,
+            SecurityLevelConfig::Sign => SecurityLevel::Signed,
+            SecurityLevelConfig::Encrypt => SecurityLevel::Encrypted,
+        }
+    }
+}
+
+pub fn default_namespace() -> String {
+    "collectd".to_string()
+}
+
+impl CollectdConfig {
+    pub fn from_address(address: SocketListenAddr) -> Self {
+        Self {
+            address,
+            receive_buffer_bytes: None,
+            security_level: SecurityLevelConfig::default(),
+            auth_file: None,
+            types_db: Vec::new(),
+            namespace: default_namespace(),
+        }
+    }
+
+    fn deserializer(&self) -> crate::Result<CollectdDeserializer> {
+        let users = match &self.auth_file {
+            Some(path) => read_auth_file(path)?,
+            None if self.security_level == SecurityLevelConfig::None => HashMap::new(),
+            None => {
+                return Err(BuildError::MissingAuthFile {
+                    security_level: self.security_level,
+                }
+                .into())
+            }
+        };
+
+        let mut types = HashMap::new();
+        for path in &self.types_db {
+            types.extend(parse_types_db(&read_file(path)?));
+        }
+
+        Ok(CollectdDeserializer {
+            users: Arc::new(users),
+            types: Arc::new(types),
+            security_level: self.security_level.into(),
+            namespace: Some(self.namespace.clone()).filter(|namespace| !namespace.is_empty()),
+            events_received: register!(EventsReceived),
+        })
+    }
+}
+
+impl GenerateConfig for CollectdConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self::from_address(SocketListenAddr::SocketAddr(
+            SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, 25826)),
+        )))
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "collectd")]
+impl SourceConfig for CollectdConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let deserializer = self.deserializer()?;
+        Ok(Box::pin(collectd_udp(
+            self.clone(),
+            deserializer,
+            cx.shutdown,
+            cx.out,
+        )))
+    }
+
+    fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
+        vec![SourceOutput::new_metrics()]
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        vec![self.address.as_udp_resource()]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+fn read_file(path: &Path) -> crate::Result<String> {
+    Ok(std::fs::read_to_string(path).context(ReadFileSnafu { path })?)
+}
+
+/// Reads the users and passwords of an auth file in collectd's format.
+fn read_auth_file(path: &Path) -> crate::Result<HashMap<String, String>> {
+    let mut users = HashMap::new();
+    for (index, line) in read_file(path)?.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (user, password) = line
+            .split_once(':')
+            .ok_or_else(|| BuildError::InvalidAuthFile {
+                path: path.to_owned(),
+                line: index + 1,
+            })?;
+        users.insert(user.trim().to_owned(), password.trim().to_owned());
+    }
+    Ok(users)
+}
+
+/// Parses the data source names of each type in a `types.db` file.
+///
+/// Each line describes one type, as its name followed by a comma separated list of
+/// `<name>:<type>:<min>:<max>` data sources.
+fn parse_types_db(types_db: &str) -> HashMap<String, Vec<String>> {
+    types_db
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let (name, data_sources) = line.split_once(char::is_whitespace)?;
+            let names = data_sources
+                .split(',')
+                .filter_map(|data_source| data_source.trim().split(':').next())
+                .map(ToOwned::to_owned)
+                .collect();
+            Some((name.to_owned(), names))
+        })
+        .collect()
+}
+
+#[derive(Clone)]
+struct CollectdDeserializer {
+    users: Arc<HashMap<String, String>>,
+    types: Arc<HashMap<String, Vec<String>>>,
+    security_level: SecurityLevel,
+    namespace: Option<String>,
+    events_received: Registered<EventsReceived>,
+}
+
+impl CollectdDeserializer {
+    /// Converts each value of a value list to a metric.
+    ///
+    /// Metrics are named `<plugin>_<type>_<data source>`, leaving out the type when it repeats
+    /// the plugin and the data source when it is the only one, and tagged with the host and the
+    /// plugin and type instances.
+    fn metrics(&self, value_list: ValueList) -> impl Iterator<Item = Metric> + '_ {
+        let mut name = value_list.plugin.clone();
+        if value_list.type_ != value_list.plugin {
+            name.push('_');
+            name.push_str(&value_list.type_);
+        }
+
+        let mut tags = MetricTags::default();
+        for (key, value) in [
+            ("host", &value_list.host),
+            ("plugin_instance", &value_list.plugin_instance),
+            ("type_instance", &value_list.type_instance),
+        ] {
+            if !value.is_empty() {
+                tags.replace(key.to_owned(), value.clone());
+            }
+        }
+
+        let data_sources = self.types.get(&value_list.type_);
+        let interval_ms = value_list
+            .interval
+            .and_then(|interval| u32::try_from(interval.as_millis()).ok())
+            .and_then(NonZeroU32::new);
+        let single = value_list.values.len() == 1;
+
+        value_list
+            .values
+            .into_iter()
+            .enumerate()
+            .map(move |(index, value)| {
+                let name = match data_sources.and_then(|names| names.get(index)) {
+                    Some(data_source) if data_source != "value" => {
+                        format!("{name}_{data_source}")
+                    }
+                    Some(_) => name.clone(),
+                    None if single => name.clone(),
+                    None => format!("{name}_{index}"),
+                };
+                let (kind, value) = match value {
+                    Value::Gauge(value) => (MetricKind::Absolute, MetricValue::Gauge { value }),
+                    Value::Counter(value) => (
+                        MetricKind::Absolute,
+                        MetricValue::Counter {
+                            value: value as f64,
+                        },
+                    ),
+                    Value::Derive(value) => (
+                        MetricKind::Absolute,
+                        MetricValue::Counter {
+                            value: value as f64,
+                        },
+                    ),
+                    Value::Absolute(value) => (
+                        MetricKind::Incremental,
+                        MetricValue::Counter {
+                            value: value as f64,
+                        },
+                    ),
+                };
+                Metric::new(name, kind, value)
+                    .with_namespace(self.namespace.clone())
+                    .with_tags(Some(tags.clone()))
+                    .with_timestamp(value_list.time)
+                    .with_interval_ms(interval_ms)
+            })
+    }
+}
+
+impl decoding::format::Deserializer for CollectdDeserializer {
+    fn parse(
+        &self,
+        bytes: Bytes,
+        _log_namespace: LogNamespace,
+    ) -> crate::Result<SmallVec<[Event; 1]>> {
+        emit!(SocketBytesReceived {
+            mode: SocketMode::Udp,
+            byte_size: bytes.len(),
+        });
+
+        let events: SmallVec<[Event; 1]> = protocol::Decoder::new(&self.users, self.security_level)
+            .decode(&bytes)?
+            .into_iter()
+            .flat_map(|value_list| self.metrics(value_list))
+            .map(Event::Metric)
+            .collect();
+
+        self.events_received.emit(CountByteSize(
+            events.len(),
+            events.estimated_json_encoded_size_of(),
+        ));
+        Ok(events)
+    }
+}
+
+async fn collectd_udp(
+    config: CollectdConfig,
+    deserializer: CollectdDeserializer,
+    shutdown: ShutdownSignal,
+    mut out: SourceSender,
+) -> Result<(), ()> {
+    let listenfd = ListenFd::from_env();
+    let socket = try_bind_udp_socket(config.address, listenfd)
+        .map_err(|error| {
+            emit!(SocketBindError {
+                mode: SocketMode::Udp,
+                error
+            })
+        })
+        .await?;
+
+    if let Some(receive_buffer_bytes) = config.receive_buffer_bytes {
+        if let Err(error) = net::set_receive_buffer_size(&socket, receive_buffer_bytes) {
+            warn!(message = "Failed configuring receive buffer size on UDP socket.", %error);
+        }
+    }
+
+    info!(
+        message = "Listening.",
+        addr = %config.address,
+        r#type = "udp"
+    );
+
+    // Each datagram is a single packet.
+    let codec = Decoder::new(
+        Framer::Bytes(BytesDecoder::new()),
+        Deserializer::Boxed(Box::new(deserializer)),
+    );
+    let mut stream = UdpFramed::new(socket, codec).take_until(shutdown);
+    while let Some(frame) = stream.next().await {
+        match frame {
+            Ok(((events, _byte_size), _sock)) => {
+                let count = events.len();
+                if (out.send_batch(events).await).is_err() {
+                    emit!(StreamClosedError { count });
+                }
+            }
+            Err(error) => {
+                emit!(SocketReceiveError {
+                    mode: SocketMode::Udp,
+                    error
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::{io::Write, time::Duration};
+
+    use chrono::{TimeZone, Utc};
+    use tokio::net::UdpSocket;
+    use vector_lib::{assert_event_data_eq, metric_tags};
+
+    use super::{protocol::tests as packets, *};
+    use crate::test_util::{
+        collect_ready,
+        components::{assert_source_compliance, SOCKET_PUSH_SOURCE_TAGS},
+        next_addr,
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<CollectdConfig>();
+    }
+
+    #[test]
+    fn parses_types_db() {
+        let types = parse_types_db(
+            "# comment\n\
+             load      shortterm:GAUGE:0:5000, midterm:GAUGE:0:5000, longterm:GAUGE:0:5000\n\
+             \n\
+             if_octets\trx:DERIVE:0:U, tx:DERIVE:0:U\n\
+             requests  value:DERIVE:0:U\n",
+        );
+        assert_eq!(types.len(), 3);
+        assert_eq!(types["load"], vec!["shortterm", "midterm", "longterm"]);
+        assert_eq!(types["if_octets"], vec!["rx", "tx"]);
+        assert_eq!(types["requests"], vec!["value"]);
+    }
+
+    #[tokio::test]
+    async fn requires_auth_file_for_security() {
+        let mut config = CollectdConfig::from_address(next_addr().into());
+        config.security_level = SecurityLevelConfig::Sign;
+        let (tx, _rx) = SourceSender::new_test();
+        assert!(config
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .is_err());
+    }
+
+    fn temp_file(contents: &str) -> tempfile::NamedTempFile {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(contents.as_bytes()).unwrap();
+        file
+    }
+
+    fn load(name: &str, value: f64) -> Metric {
+        Metric::new(name, MetricKind::Absolute, MetricValue::Gauge { value })
+            .with_namespace(Some("collectd"))
+            .with_tags(Some(metric_tags!("host" => "web01")))
+            .with_timestamp(Utc.timestamp_opt(1612325106, 0).single())
+            .with_interval_ms(NonZeroU32::new(10_000))
+    }
+
+    async fn run(config: CollectdConfig, packets: &[&'static [u8]]) -> Vec<Event> {
+        let addr = match config.address {
+            SocketListenAddr::SocketAddr(addr) => addr,
+            SocketListenAddr::SystemdFd(_) => unreachable!(),
+        };
+        let packets = packets.to_vec();
+        assert_source_compliance(&SOCKET_PUSH_SOURCE_TAGS, async move {
+            let (tx, rx) = SourceSender::new_test();
+            let source = config
+                .build(SourceContext::new_test(tx, None))
+                .await
+                .unwrap();
+            tokio::spawn(source);
+            tokio::time::sleep(Duration::from_millis(100)).await;
+
+            let socket = UdpSocket::bind(next_addr()).await.unwrap();
+            for packet in packets {
+                socket.send_to(packet, addr).await.unwrap();
+            }
+            tokio::time::sleep(Duration::from_millis(250)).await;
+            collect_ready(rx).await
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn receives_values() {
+        let types_db = temp_file(
+            "load shortterm:GAUGE:0:5000, midterm:GAUGE:0:5000, longterm:GAUGE:0:5000\n\
+             if_octets rx:DERIVE:0:U, tx:DERIVE:0:U\n",
+        );
+        let mut config = CollectdConfig::from_address(next_addr().into());
+        config.types_db = vec![types_db.path().to_owned()];
+
+        let events = run(config, &[packets::LOAD, packets::INTERFACE_CPU_REQUESTS]).await;
+        let metrics = events
+            .into_iter()
+            .map(Event::into_metric)
+            .collect::<Vec<_>>();
+
+        assert_eq!(metrics.len(), 7);
+        assert_event_data_eq!(metrics[0], load("load_shortterm", 0.5));
+        assert_event_data_eq!(metrics[1], load("load_midterm", 0.25));
+        assert_event_data_eq!(metrics[2], load("load_longterm", 0.125));
+
+        let timestamp = Utc.timestamp_opt(1612325106, 0).single();
+        assert_event_data_eq!(
+            metrics[3],
+            Metric::new(
+                "interface_if_octets_rx",
+                MetricKind::Absolute,
+                MetricValue::Counter { value: 100.0 },
+            )
+            .with_namespace(Some("collectd"))
+            .with_tags(Some(
+                metric_tags!("host" => "web01", "plugin_instance" => "eth0")
+            ))
+            .with_timestamp(timestamp)
+        );
+        assert_eq!(metrics[4].name(), "interface_if_octets_tx");
+        assert_event_data_eq!(
+            metrics[5],
+            Metric::new(
+                "cpu",
+                MetricKind::Absolute,
+                MetricValue::Counter { value: 7.0 },
+            )
+            .with_namespace(Some("collectd"))
+            .with_tags(Some(metric_tags!(
+                "host" => "web01",
+                "plugin_instance" => "0",
+                "type_instance" => "idle",
+            )))
+            .with_timestamp(timestamp)
+        );
+        assert_event_data_eq!(
+            metrics[6],
+            Metric::new(
+                "cpu_requests",
+                MetricKind::Incremental,
+                MetricValue::Counter { value: 3.0 },
+            )
+            .with_namespace(Some("collectd"))
+            .with_tags(Some(
+                metric_tags!("host" => "web01", "plugin_instance" => "0")
+            ))
+            .with_timestamp(timestamp)
+        );
+    }
+
+    #[tokio::test]
+    async fn receives_encrypted_values() {
+        let auth_file = temp_file("# users\nalice: secret\n");
+        let mut config = CollectdConfig::from_address(next_addr().into());
+        config.security_level = SecurityLevelConfig::Encrypt;
+        config.auth_file = Some(auth_file.path().to_owned());
+
+        // Only the encrypted packet is accepted.
+        let events = run(
+            config,
+            &[packets::LOAD, packets::SIGNED_LOAD, packets::ENCRYPTED_LOAD],
+        )
+        .await;
+
+        assert_eq!(events.len(), 3);
+        assert_event_data_eq!(events[0].clone().into_metric(), load("load_0", 0.5));
+    }
+}
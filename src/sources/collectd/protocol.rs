@@ -0,0 +1,455 @@
+//! A decoder for collectd's binary network protocol.
+//!
+//! Packets are a sequence of parts, each a big-endian `u16` type and `u16` length followed by the
+//! part's body. Most parts update the identifier of the values being sent, and each `VALUES` part
+//! then carries the values of one value list. See <https://collectd.org/wiki/index.php/Binary_protocol>.
+
+use std::{collections::HashMap, time::Duration};
+
+use aes::Aes256;
+use chrono::{DateTime, TimeZone, Utc};
+use hmac::{Hmac, Mac};
+use ofb::cipher::{KeyIvInit, StreamCipher};
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+use snafu::Snafu;
+
+const PART_HOST: u16 = 0x0000;
+const PART_TIME: u16 = 0x0001;
+const PART_PLUGIN: u16 = 0x0002;
+const PART_PLUGIN_INSTANCE: u16 = 0x0003;
+const PART_TYPE: u16 = 0x0004;
+const PART_TYPE_INSTANCE: u16 = 0x0005;
+const PART_VALUES: u16 = 0x0006;
+const PART_INTERVAL: u16 = 0x0007;
+const PART_TIME_HR: u16 = 0x0008;
+const PART_INTERVAL_HR: u16 = 0x0009;
+const PART_SIGNATURE: u16 = 0x0200;
+const PART_ENCRYPTION: u16 = 0x0210;
+
+const HEADER_LENGTH: usize = 4;
+const SIGNATURE_LENGTH: usize = 32;
+const IV_LENGTH: usize = 16;
+const CHECKSUM_LENGTH: usize = 20;
+
+type HmacSha256 = Hmac<Sha256>;
+type Aes256Ofb = ofb::Ofb<Aes256>;
+
+/// Errors raised while decoding a packet.
+#[derive(Clone, Debug, PartialEq, Snafu)]
+pub enum ProtocolError {
+    #[snafu(display("Collectd packet error: truncated part"))]
+    Truncated,
+    #[snafu(display("Collectd packet error: invalid {} part", part))]
+    InvalidPart { part: &'static str },
+    #[snafu(display("Collectd packet error: unknown value type {}", value_type))]
+    UnknownValueType { value_type: u8 },
+    #[snafu(display("Collectd packet error: unknown user {:?}", user))]
+    UnknownUser { user: String },
+    #[snafu(display("Collectd packet error: invalid signature for user {:?}", user))]
+    InvalidSignature { user: String },
+    #[snafu(display(
+        "Collectd packet error: unable to decrypt the packet of user {:?}",
+        user
+    ))]
+    DecryptionFailed { user: String },
+    #[snafu(display("Collectd packet error: values are not {}", required.as_str()))]
+    InsufficientSecurity { required: SecurityLevel },
+}
+
+vector_lib::impl_event_data_eq!(ProtocolError);
+
+/// How well the values of a packet are protected.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum SecurityLevel {
+    None,
+    Signed,
+    Encrypted,
+}
+
+impl SecurityLevel {
+    const fn as_str(self) -> &'static str {
+        match self {
+            SecurityLevel::None => "unprotected",
+            SecurityLevel::Signed => "signed",
+            SecurityLevel::Encrypted => "encrypted",
+        }
+    }
+}
+
+/// A single value of a value list.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Value {
+    Counter(u64),
+    Gauge(f64),
+    Derive(i64),
+    Absolute(u64),
+}
+
+/// The values of one data set, as sent by a collectd plugin.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ValueList {
+    pub host: String,
+    pub plugin: String,
+    pub plugin_instance: String,
+    pub type_: String,
+    pub type_instance: String,
+    pub time: Option<DateTime<Utc>>,
+    pub interval: Option<Duration>,
+    pub values: Vec<Value>,
+}
+
+/// Decodes the value lists of packets, checking signatures and decrypting packets with the
+/// passwords of the given users.
+pub struct Decoder<'a> {
+    users: &'a HashMap<String, String>,
+    required: SecurityLevel,
+}
+
+impl<'a> Decoder<'a> {
+    pub const fn new(users: &'a HashMap<String, String>, required: SecurityLevel) -> Self {
+        Self { users, required }
+    }
+
+    pub fn decode(&self, packet: &[u8]) -> Result<Vec<ValueList>, ProtocolError> {
+        let mut value_lists = Vec::new();
+        self.decode_parts(
+            packet,
+            SecurityLevel::None,
+            &mut ValueList::default(),
+            &mut value_lists,
+        )?;
+        Ok(value_lists)
+    }
+
+    fn decode_parts(
+        &self,
+        mut packet: &[u8],
+        mut security: SecurityLevel,
+        state: &mut ValueList,
+        value_lists: &mut Vec<ValueList>,
+    ) -> Result<(), ProtocolError> {
+        while !packet.is_empty() {
+            let (part_type, body, rest) = split_part(packet)?;
+            packet = rest;
+
+            match part_type {
+                PART_HOST => state.host = string(body, "host")?,
+                PART_PLUGIN => state.plugin = string(body, "plugin")?,
+                PART_PLUGIN_INSTANCE => state.plugin_instance = string(body, "plugin instance")?,
+                PART_TYPE => state.type_ = string(body, "type")?,
+                PART_TYPE_INSTANCE => state.type_instance = string(body, "type instance")?,
+                PART_TIME => {
+                    state.time = Utc.timestamp_opt(number(body, "time")? as i64, 0).single();
+                }
+                PART_TIME_HR => state.time = high_resolution_time(number(body, "time")?),
+                PART_INTERVAL => {
+                    state.interval = Some(Duration::from_secs(number(body, "interval")?));
+                }
+                PART_INTERVAL_HR => {
+                    state.interval = Some(high_resolution_duration(number(body, "interval")?));
+                }
+                PART_VALUES => {
+                    if security < self.required {
+                        return Err(ProtocolError::InsufficientSecurity {
+                            required: self.required,
+                        });
+                    }
+                    value_lists.push(ValueList {
+                        values: values(body)?,
+                        ..state.clone()
+                    });
+                }
+                PART_SIGNATURE if security == SecurityLevel::None => {
+                    // The signature covers the user name and every part following it.
+                    if body.len() < SIGNATURE_LENGTH {
+                        return Err(ProtocolError::InvalidPart { part: "signature" });
+                    }
+                    let (signature, user) = body.split_at(SIGNATURE_LENGTH);
+                    let user = String::from_utf8_lossy(user).into_owned();
+                    match self.users.get(&user) {
+                        Some(password) => {
+                            let mut mac = HmacSha256::new_from_slice(password.as_bytes())
+                                .expect("HMAC accepts keys of any length");
+                            mac.update(user.as_bytes());
+                            mac.update(packet);
+                            mac.verify_slice(signature)
+                                .map_err(|_| ProtocolError::InvalidSignature { user })?;
+                            security = SecurityLevel::Signed;
+                        }
+                        // Like collectd, signatures are only required to be valid when signed
+                        // packets are required.
+                        None if self.required == SecurityLevel::None => {}
+                        None => return Err(ProtocolError::UnknownUser { user }),
+                    }
+                }
+                PART_ENCRYPTION if security != SecurityLevel::Encrypted => {
+                    let decrypted = self.decrypt(body)?;
+                    self.decode_parts(&decrypted, SecurityLevel::Encrypted, state, value_lists)?;
+                }
+                // Notifications and unknown parts are skipped.
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    fn decrypt(&self, body: &[u8]) -> Result<Vec<u8>, ProtocolError> {
+        let invalid = ProtocolError::InvalidPart { part: "encryption" };
+
+        let (user_length, body) = split_array::<2>(body).ok_or(invalid.clone())?;
+        let user_length = u16::from_be_bytes(user_length) as usize;
+        if body.len() < user_length + IV_LENGTH + CHECKSUM_LENGTH {
+            return Err(invalid);
+        }
+        let (user, body) = body.split_at(user_length);
+        let (iv, encrypted) = body.split_at(IV_LENGTH);
+
+        let user = String::from_utf8_lossy(user).into_owned();
+        let password = self
+            .users
+            .get(&user)
+            .ok_or_else(|| ProtocolError::UnknownUser { user: user.clone() })?;
+
+        let key = Sha256::digest(password.as_bytes());
+        let mut decrypted = encrypted.to_vec();
+        Aes256Ofb::new(&key, iv.into()).apply_keystream(&mut decrypted);
+
+        let parts = decrypted.split_off(CHECKSUM_LENGTH);
+        if Sha1::digest(&parts).as_slice() != decrypted.as_slice() {
+            return Err(ProtocolError::DecryptionFailed { user });
+        }
+        Ok(parts)
+    }
+}
+
+/// Splits the first part off a packet, returning its type, its body, and the rest of the packet.
+fn split_part(packet: &[u8]) -> Result<(u16, &[u8], &[u8]), ProtocolError> {
+    let (header, _) = split_array::<HEADER_LENGTH>(packet).ok_or(ProtocolError::Truncated)?;
+    let part_type = u16::from_be_bytes([header[0], header[1]]);
+    let length = u16::from_be_bytes([header[2], header[3]]) as usize;
+    if length < HEADER_LENGTH || length > packet.len() {
+        return Err(ProtocolError::Truncated);
+    }
+    let (part, rest) = packet.split_at(length);
+    Ok((part_type, &part[HEADER_LENGTH..], rest))
+}
+
+fn split_array<const N: usize>(bytes: &[u8]) -> Option<([u8; N], &[u8])> {
+    (bytes.len() >= N).then(|| {
+        let (array, rest) = bytes.split_at(N);
+        (array.try_into().expect("length was checked"), rest)
+    })
+}
+
+fn string(body: &[u8], part: &'static str) -> Result<String, ProtocolError> {
+    match body.split_last() {
+        Some((0, string)) => Ok(String::from_utf8_lossy(string).into_owned()),
+        _ => Err(ProtocolError::InvalidPart { part }),
+    }
+}
+
+fn number(body: &[u8], part: &'static str) -> Result<u64, ProtocolError> {
+    body.try_into()
+        .map(u64::from_be_bytes)
+        .map_err(|_| ProtocolError::InvalidPart { part })
+}
+
+/// Converts a high resolution time, in units of 2^-30 seconds, to a timestamp.
+fn high_resolution_time(time: u64) -> Option<DateTime<Utc>> {
+    let duration = high_resolution_duration(time);
+    Utc.timestamp_opt(duration.as_secs() as i64, duration.subsec_nanos())
+        .single()
+}
+
+fn high_resolution_duration(duration: u64) -> Duration {
+    let nanos = ((duration & 0x3fff_ffff) * 1_000_000_000) >> 30;
+    Duration::new(duration >> 30, nanos as u32)
+}
+
+fn values(body: &[u8]) -> Result<Vec<Value>, ProtocolError> {
+    let invalid = ProtocolError::InvalidPart { part: "values" };
+
+    let (count, body) = split_array::<2>(body).ok_or(invalid.clone())?;
+    let count = u16::from_be_bytes(count) as usize;
+    if body.len() != count * 9 {
+        return Err(invalid);
+    }
+    let (types, values) = body.split_at(count);
+
+    types
+        .iter()
+        .zip(values.chunks_exact(8))
+        .map(|(value_type, value)| {
+            let value: [u8; 8] = value.try_into().expect("chunks are eight bytes");
+            match value_type {
+                0 => Ok(Value::Counter(u64::from_be_bytes(value))),
+                // Gauges are the one value sent in the host's byte order, which collectd assumes
+                // is little-endian.
+                1 => Ok(Value::Gauge(f64::from_le_bytes(value))),
+                2 => Ok(Value::Derive(i64::from_be_bytes(value))),
+                3 => Ok(Value::Absolute(u64::from_be_bytes(value))),
+                value_type => Err(ProtocolError::UnknownValueType {
+                    value_type: *value_type,
+                }),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use similar_asserts::assert_eq;
+
+    use super::*;
+
+    /// A packet with the three load averages of host `web01`.
+    pub(in super::super) const LOAD: &[u8] = b"\x00\x00\x00\x0aweb01\x00\x00\x08\x00\x0c\x18\x06\x88<\x80\x00\x00\x00\x00\x09\x00\x0c\x00\x00\x00\x02\x80\x00\x00\x00\x00\x02\x00\x09load\x00\x00\x03\x00\x05\x00\x00\x04\x00\x09load\x00\x00\x05\x00\x05\x00\x00\x06\x00!\x00\x03\x01\x01\x01\x00\x00\x00\x00\x00\x00\xe0?\x00\x00\x00\x00\x00\x00\xd0?\x00\x00\x00\x00\x00\x00\xc0?";
+
+    /// `LOAD`, signed by user `alice` with the password `secret`.
+    pub(in super::super) const SIGNED_LOAD: &[u8] = b"\x02\x00\x00)\x5c\xc3\xcf9\xc5:d\x0b\xe6e\x87<!\xac\xd84s\x99{\xb4\xe7\xe9\x16\x0c\xbf\xfdo,\x18'vAalice\x00\x00\x00\x0aweb01\x00\x00\x08\x00\x0c\x18\x06\x88<\x80\x00\x00\x00\x00\x09\x00\x0c\x00\x00\x00\x02\x80\x00\x00\x00\x00\x02\x00\x09load\x00\x00\x03\x00\x05\x00\x00\x04\x00\x09load\x00\x00\x05\x00\x05\x00\x00\x06\x00!\x00\x03\x01\x01\x01\x00\x00\x00\x00\x00\x00\xe0?\x00\x00\x00\x00\x00\x00\xd0?\x00\x00\x00\x00\x00\x00\xc0?";
+
+    /// `LOAD`, encrypted by user `alice` with the password `secret`.
+    pub(in super::super) const ENCRYPTED_LOAD: &[u8] = b"\x02\x10\x00\x8e\x00\x05alice\x00\x01\x02\x03\x04\x05\x06\x07\x08\x09\x0a\x0b\x0c\x0d\x0e\x0f/\xed\x8eS(@{\x09\x18m\xb1}\xb9\xb5\x00s\x9f\x1b\x1d#\x80Z\xc99\x1cbUg\xc8~\x87/\x90\x02\xfb\xcb$\xf2j\x0a\xf2\xe9\x8b\xae@p\xaa\xe2\xb4\xa4\xfc\x1f\x1a\xc2[\xc1\xcf\xb6F\x1a\xc6\x9d\xd4\x226\xe27\x8f\xa1<\xb2\xfb\xf0\x9d\xcf\x18\x11s\x08\x01$\x8c\xcf\x7f\x8dx\x12b\xa6\x8a\xe5\xf2\xaer\xc0\x88o\xac+\x94\x89g\x06\xc4\xe6\xb76{\x09O\xf3\xc7\xd2l:";
+
+    /// Counter, derive, and absolute values from three plugins, with a low resolution time.
+    pub(in super::super) const INTERFACE_CPU_REQUESTS: &[u8] = b"\x00\x00\x00\x0aweb01\x00\x00\x01\x00\x0c\x00\x00\x00\x00`\x1a \xf2\x00\x02\x00\x0einterface\x00\x00\x03\x00\x09eth0\x00\x00\x04\x00\x0eif_octets\x00\x00\x05\x00\x05\x00\x00\x06\x00\x18\x00\x02\x02\x02\x00\x00\x00\x00\x00\x00\x00d\x00\x00\x00\x00\x00\x00\x00\xc8\x00\x02\x00\x08cpu\x00\x00\x03\x00\x060\x00\x00\x04\x00\x08cpu\x00\x00\x05\x00\x09idle\x00\x00\x06\x00\x0f\x00\x01\x00\x00\x00\x00\x00\x00\x00\x00\x07\x00\x04\x00\x0drequests\x00\x00\x05\x00\x05\x00\x00\x06\x00\x0f\x00\x01\x03\x00\x00\x00\x00\x00\x00\x00\x03";
+
+    pub(in super::super) fn users() -> HashMap<String, String> {
+        HashMap::from([("alice".to_owned(), "secret".to_owned())])
+    }
+
+    fn load() -> ValueList {
+        ValueList {
+            host: "web01".to_owned(),
+            plugin: "load".to_owned(),
+            type_: "load".to_owned(),
+            time: Utc.timestamp_opt(1612325106, 0).single(),
+            interval: Some(Duration::from_secs(10)),
+            values: vec![Value::Gauge(0.5), Value::Gauge(0.25), Value::Gauge(0.125)],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn decodes_values() {
+        let users = HashMap::new();
+        let decoder = Decoder::new(&users, SecurityLevel::None);
+        assert_eq!(decoder.decode(LOAD), Ok(vec![load()]));
+
+        let value_lists = decoder.decode(INTERFACE_CPU_REQUESTS).unwrap();
+        let values = value_lists
+            .iter()
+            .map(|value_list| {
+                (
+                    value_list.plugin.as_str(),
+                    value_list.plugin_instance.as_str(),
+                    value_list.type_.as_str(),
+                    value_list.type_instance.as_str(),
+                    value_list.values.clone(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            values,
+            vec![
+                (
+                    "interface",
+                    "eth0",
+                    "if_octets",
+                    "",
+                    vec![Value::Derive(100), Value::Derive(200)]
+                ),
+                ("cpu", "0", "cpu", "idle", vec![Value::Counter(7)]),
+                ("cpu", "0", "requests", "", vec![Value::Absolute(3)]),
+            ]
+        );
+        assert_eq!(
+            value_lists[0].time,
+            Utc.timestamp_opt(1612325106, 0).single()
+        );
+        assert_eq!(value_lists[0].interval, None);
+    }
+
+    #[test]
+    fn verifies_signatures() {
+        let users = users();
+        let decoder = Decoder::new(&users, SecurityLevel::Signed);
+        assert_eq!(decoder.decode(SIGNED_LOAD), Ok(vec![load()]));
+
+        let mut tampered = SIGNED_LOAD.to_vec();
+        *tampered.last_mut().unwrap() = 0x40;
+        assert_eq!(
+            decoder.decode(&tampered),
+            Err(ProtocolError::InvalidSignature {
+                user: "alice".to_owned()
+            })
+        );
+
+        assert_eq!(
+            decoder.decode(LOAD),
+            Err(ProtocolError::InsufficientSecurity {
+                required: SecurityLevel::Signed
+            })
+        );
+    }
+
+    #[test]
+    fn unknown_signers_are_accepted_without_security() {
+        let users = HashMap::new();
+        assert_eq!(
+            Decoder::new(&users, SecurityLevel::None).decode(SIGNED_LOAD),
+            Ok(vec![load()])
+        );
+        assert_eq!(
+            Decoder::new(&users, SecurityLevel::Signed).decode(SIGNED_LOAD),
+            Err(ProtocolError::UnknownUser {
+                user: "alice".to_owned()
+            })
+        );
+    }
+
+    #[test]
+    fn decrypts_packets() {
+        let users = users();
+        let decoder = Decoder::new(&users, SecurityLevel::Encrypted);
+        assert_eq!(decoder.decode(ENCRYPTED_LOAD), Ok(vec![load()]));
+        assert_eq!(
+            decoder.decode(SIGNED_LOAD),
+            Err(ProtocolError::InsufficientSecurity {
+                required: SecurityLevel::Encrypted
+            })
+        );
+
+        let wrong_password = HashMap::from([("alice".to_owned(), "guess".to_owned())]);
+        assert_eq!(
+            Decoder::new(&wrong_password, SecurityLevel::None).decode(ENCRYPTED_LOAD),
+            Err(ProtocolError::DecryptionFailed {
+                user: "alice".to_owned()
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_packets() {
+        let users = HashMap::new();
+        let decoder = Decoder::new(&users, SecurityLevel::None);
+        assert_eq!(
+            decoder.decode(&LOAD[..LOAD.len() - 1]),
+            Err(ProtocolError::Truncated)
+        );
+        assert_eq!(
+            decoder.decode(b"\x00\x00\x00\x02"),
+            Err(ProtocolError::Truncated)
+        );
+        assert_eq!(
+            decoder.decode(b"\x00\x00\x00\x09web01"),
+            Err(ProtocolError::InvalidPart { part: "host" })
+        );
+        assert_eq!(
+            decoder.decode(b"\x00\x06\x00\x0f\x00\x01\x07\x00\x00\x00\x00\x00\x00\x00\x00"),
+            Err(ProtocolError::UnknownValueType { value_type: 7 })
+        );
+    }
+}
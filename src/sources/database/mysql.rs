@@ -0,0 +1,188 @@
+use bytes::Bytes;
+use chrono::{Datelike, NaiveDate, Timelike};
+use mysql_async::{prelude::Queryable, Conn, Opts, OptsBuilder, Params, Row, SslOpts};
+use ordered_float::NotNan;
+use snafu::ResultExt;
+use vrl::value::{ObjectMap, Value};
+
+use super::{
+    BuildError, DatabaseTlsConfig, InvalidMysqlConnectionStringSnafu, MysqlConnectSnafu,
+    QueryError, TrackingValue, TRACKING_PLACEHOLDER,
+};
+
+pub(super) struct MysqlDatabase {
+    opts: Opts,
+    query: String,
+    placeholders: usize,
+    conn: Option<Conn>,
+}
+
+impl MysqlDatabase {
+    pub(super) fn new(
+        connection_string: &str,
+        query: &str,
+        tls: Option<DatabaseTlsConfig>,
+    ) -> Result<Self, BuildError> {
+        let opts = Opts::from_url(connection_string).context(InvalidMysqlConnectionStringSnafu)?;
+        let mut builder = OptsBuilder::from_opts(opts)
+            // Timestamps are read and bound as UTC, which `TIMESTAMP` columns are otherwise
+            // converted from and to the session time zone for.
+            .init(vec!["SET time_zone = '+00:00'"]);
+        if let Some(tls) = tls {
+            builder = builder
+                .ssl_opts(SslOpts::default().with_root_certs(vec![tls.ca_file.clone().into()]));
+        }
+
+        Ok(Self {
+            opts: builder.into(),
+            query: rewrite_query(query),
+            placeholders: query.matches(TRACKING_PLACEHOLDER).count(),
+            conn: None,
+        })
+    }
+
+    pub(super) async fn query(
+        &mut self,
+        tracking_value: Option<TrackingValue>,
+    ) -> Result<Vec<ObjectMap>, QueryError> {
+        let mut conn = match self.conn.take() {
+            Some(conn) => conn,
+            None => Conn::new(self.opts.clone())
+                .await
+                .context(MysqlConnectSnafu)?,
+        };
+
+        // Each placeholder is a separate positional parameter.
+        let params = match tracking_value {
+            Some(value) => Params::Positional(vec![tracking_param(value); self.placeholders]),
+            None => Params::Empty,
+        };
+
+        match conn.exec::<Row, _, _>(self.query.as_str(), params).await {
+            Ok(rows) => {
+                self.conn = Some(conn);
+                Ok(rows.into_iter().map(row_to_object).collect())
+            }
+            Err(error) => {
+                // Errors returned by the server leave the connection usable.
+                if matches!(error, mysql_async::Error::Server(_)) {
+                    self.conn = Some(conn);
+                }
+                Err(QueryError::MysqlQuery { source: error })
+            }
+        }
+    }
+}
+
+/// Replaces the tracking value placeholders with the positional parameters binding them.
+fn rewrite_query(query: &str) -> String {
+    query.replace(TRACKING_PLACEHOLDER, "?")
+}
+
+fn tracking_param(value: TrackingValue) -> mysql_async::Value {
+    match value {
+        TrackingValue::Numeric(value) => mysql_async::Value::Int(value),
+        TrackingValue::Timestamp(value) => mysql_async::Value::Date(
+            value.year() as u16,
+            value.month() as u8,
+            value.day() as u8,
+            value.hour() as u8,
+            value.minute() as u8,
+            value.second() as u8,
+            value.timestamp_subsec_micros(),
+        ),
+    }
+}
+
+fn row_to_object(row: Row) -> ObjectMap {
+    let columns = row.columns();
+    columns
+        .iter()
+        .zip(row.unwrap())
+        .map(|(column, value)| (column.name_str().as_ref().into(), convert_value(value)))
+        .collect()
+}
+
+/// Converts a value of the binary protocol, in which every column type not mapped to a number,
+/// date, or time is sent as bytes.
+fn convert_value(value: mysql_async::Value) -> Value {
+    fn float(value: f64) -> Value {
+        NotNan::new(value).map_or(Value::Null, Value::Float)
+    }
+
+    match value {
+        mysql_async::Value::NULL => Value::Null,
+        mysql_async::Value::Bytes(bytes) => Value::Bytes(Bytes::from(bytes)),
+        mysql_async::Value::Int(value) => Value::Integer(value),
+        mysql_async::Value::UInt(value) => match i64::try_from(value) {
+            Ok(value) => Value::Integer(value),
+            Err(_) => float(value as f64),
+        },
+        mysql_async::Value::Float(value) => float(value.into()),
+        mysql_async::Value::Double(value) => float(value),
+        // Zero dates, such as `0000-00-00`, are not valid timestamps and are read as null.
+        mysql_async::Value::Date(year, month, day, hour, minute, second, micros) => {
+            NaiveDate::from_ymd_opt(year.into(), month.into(), day.into())
+                .and_then(|date| {
+                    date.and_hms_micro_opt(hour.into(), minute.into(), second.into(), micros)
+                })
+                .map_or(Value::Null, |timestamp| {
+                    Value::Timestamp(timestamp.and_utc())
+                })
+        }
+        mysql_async::Value::Time(negative, days, hours, minutes, seconds, micros) => {
+            Value::from(format!(
+                "{}{:02}:{:02}:{:02}.{:06}",
+                if negative { "-" } else { "" },
+                days * 24 + u32::from(hours),
+                minutes,
+                seconds,
+                micros
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+
+    #[test]
+    fn rewrites_tracking_placeholders() {
+        let query = "SELECT * FROM t WHERE id > :tracking_value OR id = :tracking_value";
+        assert_eq!(
+            rewrite_query(query),
+            "SELECT * FROM t WHERE id > ? OR id = ?"
+        );
+    }
+
+    #[test]
+    fn converts_dates_and_times() {
+        let timestamp = Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap();
+        let param = tracking_param(TrackingValue::Timestamp(timestamp));
+        assert_eq!(convert_value(param), Value::Timestamp(timestamp));
+
+        assert_eq!(
+            convert_value(mysql_async::Value::Date(0, 0, 0, 0, 0, 0, 0)),
+            Value::Null
+        );
+        assert_eq!(
+            convert_value(mysql_async::Value::Time(true, 1, 2, 3, 4, 5)),
+            Value::from("-26:03:04.000005")
+        );
+    }
+
+    #[test]
+    fn converts_unsigned_integers() {
+        assert_eq!(
+            convert_value(mysql_async::Value::UInt(42)),
+            Value::Integer(42)
+        );
+        assert!(matches!(
+            convert_value(mysql_async::Value::UInt(u64::MAX)),
+            Value::Float(_)
+        ));
+    }
+}
@@ -0,0 +1,229 @@
+use bytes::Bytes;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use openssl::ssl::{SslConnector, SslMethod};
+use ordered_float::NotNan;
+use postgres_openssl::MakeTlsConnector;
+use snafu::ResultExt;
+use tokio_postgres::{
+    types::{FromSql, ToSql, Type},
+    Client, Config, NoTls, Row,
+};
+use vrl::value::{ObjectMap, Value};
+
+use super::{
+    BuildError, DatabaseTlsConfig, InvalidPostgresConnectionStringSnafu, PostgresConnectSnafu,
+    PostgresQuerySnafu, PostgresTlsSnafu, PostgresValueSnafu, QueryError, TrackingValue,
+    TRACKING_PLACEHOLDER,
+};
+
+pub(super) struct PostgresDatabase {
+    config: Config,
+    tls: Option<DatabaseTlsConfig>,
+    query: String,
+    client: Option<Client>,
+}
+
+impl PostgresDatabase {
+    pub(super) fn new(
+        connection_string: &str,
+        query: &str,
+        tls: Option<DatabaseTlsConfig>,
+    ) -> Result<Self, BuildError> {
+        let config = connection_string
+            .parse::<Config>()
+            .context(InvalidPostgresConnectionStringSnafu)?;
+        Ok(Self {
+            config,
+            tls,
+            query: rewrite_query(query),
+            client: None,
+        })
+    }
+
+    async fn connect(&self) -> Result<Client, QueryError> {
+        let client = match &self.tls {
+            Some(tls) => {
+                let mut builder =
+                    SslConnector::builder(SslMethod::tls_client()).context(PostgresTlsSnafu)?;
+                builder
+                    .set_ca_file(tls.ca_file.clone())
+                    .context(PostgresTlsSnafu)?;
+                let connector = MakeTlsConnector::new(builder.build());
+
+                let (client, connection) = self
+                    .config
+                    .connect(connector)
+                    .await
+                    .context(PostgresConnectSnafu)?;
+                tokio::spawn(connection);
+                client
+            }
+            None => {
+                let (client, connection) = self
+                    .config
+                    .connect(NoTls)
+                    .await
+                    .context(PostgresConnectSnafu)?;
+                tokio::spawn(connection);
+                client
+            }
+        };
+        Ok(client)
+    }
+
+    pub(super) async fn query(
+        &mut self,
+        tracking_value: Option<TrackingValue>,
+    ) -> Result<Vec<ObjectMap>, QueryError> {
+        let client = match self.client.take() {
+            Some(client) if !client.is_closed() => client,
+            _ => self.connect().await?,
+        };
+
+        let result = query(&client, &self.query, tracking_value).await;
+
+        // Query errors leave the connection usable, so it is only dropped once it has been closed.
+        if !client.is_closed() {
+            self.client = Some(client);
+        }
+        result
+    }
+}
+
+/// Replaces the tracking value placeholder with the positional parameter binding it.
+fn rewrite_query(query: &str) -> String {
+    query.replace(TRACKING_PLACEHOLDER, "$1")
+}
+
+async fn query(
+    client: &Client,
+    query: &str,
+    tracking_value: Option<TrackingValue>,
+) -> Result<Vec<ObjectMap>, QueryError> {
+    let statement = client.prepare(query).await.context(PostgresQuerySnafu)?;
+    let rows = match (tracking_value, statement.params().first()) {
+        (Some(value), Some(ty)) => {
+            let param = tracking_param(value, ty)?;
+            let param: &(dyn ToSql + Sync) = &*param;
+            client.query(&statement, &[param]).await
+        }
+        _ => client.query(&statement, &[]).await,
+    }
+    .context(PostgresQuerySnafu)?;
+
+    rows.iter().map(row_to_object).collect()
+}
+
+/// Converts the tracking value to the type PostgreSQL inferred for its parameter.
+fn tracking_param(
+    value: TrackingValue,
+    ty: &Type,
+) -> Result<Box<dyn ToSql + Sync + Send>, QueryError> {
+    let out_of_range = |value| QueryError::TrackingValueOutOfRange {
+        value,
+        type_name: ty.to_string(),
+    };
+    let param: Box<dyn ToSql + Sync + Send> = match (value, ty) {
+        (TrackingValue::Numeric(value), &Type::INT2) => {
+            Box::new(i16::try_from(value).map_err(|_| out_of_range(value))?)
+        }
+        (TrackingValue::Numeric(value), &Type::INT4) => {
+            Box::new(i32::try_from(value).map_err(|_| out_of_range(value))?)
+        }
+        (TrackingValue::Numeric(value), &Type::INT8) => Box::new(value),
+        (TrackingValue::Timestamp(value), &Type::TIMESTAMPTZ) => Box::new(value),
+        (TrackingValue::Timestamp(value), &Type::TIMESTAMP) => Box::new(value.naive_utc()),
+        _ => {
+            return Err(QueryError::UnsupportedParameterType {
+                type_name: ty.to_string(),
+            })
+        }
+    };
+    Ok(param)
+}
+
+fn row_to_object(row: &Row) -> Result<ObjectMap, QueryError> {
+    row.columns()
+        .iter()
+        .enumerate()
+        .map(|(index, column)| {
+            let value = column_value(row, index, column.name(), column.type_())?;
+            Ok((column.name().into(), value))
+        })
+        .collect()
+}
+
+fn column_value(row: &Row, index: usize, name: &str, ty: &Type) -> Result<Value, QueryError> {
+    fn get<'a, T: FromSql<'a>>(
+        row: &'a Row,
+        index: usize,
+        name: &str,
+        convert: impl FnOnce(T) -> Value,
+    ) -> Result<Value, QueryError> {
+        row.try_get::<_, Option<T>>(index)
+            .map(|value| value.map_or(Value::Null, convert))
+            .context(PostgresValueSnafu { column: name })
+    }
+
+    fn float(value: f64) -> Value {
+        NotNan::new(value).map_or(Value::Null, Value::Float)
+    }
+
+    match *ty {
+        Type::BOOL => get(row, index, name, Value::Boolean),
+        Type::INT2 => get(row, index, name, |value: i16| Value::Integer(value.into())),
+        Type::INT4 => get(row, index, name, |value: i32| Value::Integer(value.into())),
+        Type::INT8 => get(row, index, name, Value::Integer),
+        Type::OID => get(row, index, name, |value: u32| Value::Integer(value.into())),
+        Type::FLOAT4 => get(row, index, name, |value: f32| float(value.into())),
+        Type::FLOAT8 => get(row, index, name, float),
+        Type::TEXT | Type::VARCHAR | Type::BPCHAR | Type::NAME | Type::UNKNOWN => {
+            get(row, index, name, |value: String| Value::from(value))
+        }
+        Type::BYTEA => get(row, index, name, |value: Vec<u8>| {
+            Value::Bytes(Bytes::from(value))
+        }),
+        Type::TIMESTAMPTZ => get(row, index, name, |value: DateTime<Utc>| {
+            Value::Timestamp(value)
+        }),
+        Type::TIMESTAMP => get(row, index, name, |value: NaiveDateTime| {
+            Value::Timestamp(value.and_utc())
+        }),
+        Type::DATE => get(row, index, name, |value: NaiveDate| {
+            Value::Timestamp(value.and_time(Default::default()).and_utc())
+        }),
+        Type::JSON | Type::JSONB => get(row, index, name, |value: serde_json::Value| {
+            Value::from(value)
+        }),
+        _ => Err(QueryError::UnsupportedColumnType {
+            column: name.to_owned(),
+            type_name: ty.to_string(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rewrites_tracking_placeholder() {
+        assert_eq!(
+            rewrite_query("SELECT * FROM t WHERE id > :tracking_value OR id = :tracking_value"),
+            "SELECT * FROM t WHERE id > $1 OR id = $1"
+        );
+    }
+
+    #[test]
+    fn converts_tracking_value_to_parameter_type() {
+        assert!(tracking_param(TrackingValue::Numeric(1), &Type::INT4).is_ok());
+        assert!(matches!(
+            tracking_param(TrackingValue::Numeric(i64::MAX), &Type::INT4),
+            Err(QueryError::TrackingValueOutOfRange { .. })
+        ));
+        assert!(matches!(
+            tracking_param(TrackingValue::Numeric(1), &Type::TEXT),
+            Err(QueryError::UnsupportedParameterType { .. })
+        ));
+    }
+}
@@ -0,0 +1,392 @@
+//! The metadata of the containers of containerd, listed through its gRPC API.
+
+use std::{
+    collections::HashMap,
+    convert::TryFrom,
+    path::{Path, PathBuf},
+    sync::{Arc, RwLock, RwLockReadGuard},
+    time::Duration,
+};
+
+use chrono::{DateTime, TimeZone, Utc};
+use tokio::net::UnixStream;
+use tonic::{
+    metadata::MetadataValue,
+    transport::{Channel, Endpoint, Uri},
+};
+use tower::service_fn;
+
+use super::ContainerMetadata;
+use crate::internal_events::DockerLogsContainerdListError;
+
+// prost emits some generated code that includes clones on `Arc`
+// objects, which causes a clippy ding on this block. We don't
+// directly control the generated code, so allow this lint here.
+#[allow(clippy::clone_on_ref_ptr)]
+// https://github.com/hyperium/tonic/issues/1350
+#[allow(clippy::missing_const_for_fn)]
+#[allow(warnings)]
+pub(super) mod proto {
+    pub(super) mod containers {
+        include!(concat!(
+            env!("OUT_DIR"),
+            "/containerd.services.containers.v1.rs"
+        ));
+    }
+
+    pub(super) mod namespaces {
+        include!(concat!(
+            env!("OUT_DIR"),
+            "/containerd.services.namespaces.v1.rs"
+        ));
+    }
+}
+
+/// The socket of the gRPC API of containerd.
+pub(super) const DEFAULT_CONTAINERD_SOCKET: &str = "/run/containerd/containerd.sock";
+
+/// The interval between the lookups of the containers.
+pub(super) const REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The header selecting the namespace of the requests.
+const NAMESPACE_HEADER: &str = "containerd-namespace";
+
+/// The label of the name of the containers created by the CRI plugin.
+const CRI_CONTAINER_NAME_LABEL: &str = "io.kubernetes.container.name";
+
+/// The label of the name of the containers created by nerdctl.
+const NERDCTL_NAME_LABEL: &str = "nerdctl/name";
+
+/// The label of the state directory of the containers created by nerdctl, where their log file is
+/// written.
+const NERDCTL_STATE_DIR_LABEL: &str = "nerdctl/state-dir";
+
+/// A container of containerd.
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct ContainerdContainer {
+    pub(super) id: String,
+    /// The name given by the CRI plugin or nerdctl, or the ID of the container.
+    pub(super) name: String,
+    pub(super) image: Option<String>,
+    pub(super) labels: HashMap<String, String>,
+    pub(super) created_at: Option<DateTime<Utc>>,
+    /// The log file written by nerdctl, in the format of the `json-file` logging driver of Docker.
+    ///
+    /// The log files of the containers of the CRI plugin are found in `cri_log_directory`.
+    pub(super) json_log_file: Option<PathBuf>,
+}
+
+impl ContainerdContainer {
+    fn from_proto(container: proto::containers::Container) -> Self {
+        let labels = container.labels.into_iter().collect::<HashMap<_, _>>();
+        let name = labels
+            .get(CRI_CONTAINER_NAME_LABEL)
+            .or_else(|| labels.get(NERDCTL_NAME_LABEL))
+            .cloned()
+            .unwrap_or_else(|| container.id.clone());
+        let json_log_file = labels
+            .get(NERDCTL_STATE_DIR_LABEL)
+            .map(|state_dir| Path::new(state_dir).join(format!("{}-json.log", container.id)));
+        let created_at = container.created_at.and_then(|created_at| {
+            let nanos = u32::try_from(created_at.nanos).ok()?;
+            Utc.timestamp_opt(created_at.seconds, nanos).single()
+        });
+
+        Self {
+            id: container.id,
+            name,
+            image: Some(container.image).filter(|image| !image.is_empty()),
+            labels,
+            created_at,
+            json_log_file,
+        }
+    }
+
+    pub(super) fn metadata(&self) -> ContainerMetadata {
+        ContainerMetadata {
+            labels: self.labels.clone(),
+            name: self.name.clone().into(),
+            name_str: self.name.clone(),
+            image: self.image.clone().map(Into::into),
+            created_at: self.created_at,
+        }
+    }
+}
+
+/// A client of the gRPC API of containerd.
+#[derive(Clone)]
+pub(super) struct ContainerdClient {
+    channel: Channel,
+}
+
+impl ContainerdClient {
+    /// Creates a client connecting to `socket` when it is first used.
+    pub(super) fn new(socket: PathBuf) -> Self {
+        // The URI is only used for the headers of the requests, which are sent to the socket.
+        let channel = Endpoint::from_static("http://localhost").connect_with_connector_lazy(
+            service_fn(move |_: Uri| UnixStream::connect(socket.clone())),
+        );
+        Self { channel }
+    }
+
+    /// Lists the containers of all the namespaces.
+    pub(super) async fn list_containers(&self) -> Result<Vec<ContainerdContainer>, tonic::Status> {
+        let namespaces =
+            proto::namespaces::namespaces_client::NamespacesClient::new(self.channel.clone())
+                .list(proto::namespaces::ListNamespacesRequest::default())
+                .await?
+                .into_inner()
+                .namespaces;
+
+        let mut client =
+            proto::containers::containers_client::ContainersClient::new(self.channel.clone());
+        let mut containers = Vec::new();
+        for namespace in namespaces {
+            // The names of the namespaces are restricted to ASCII letters, digits, `-`, `_` and `.`.
+            let Ok(namespace) = MetadataValue::try_from(namespace.name) else {
+                continue;
+            };
+            let mut request =
+                tonic::Request::new(proto::containers::ListContainersRequest::default());
+            request.metadata_mut().insert(NAMESPACE_HEADER, namespace);
+
+            let response = client.list(request).await?.into_inner();
+            containers.extend(
+                response
+                    .containers
+                    .into_iter()
+                    .map(ContainerdContainer::from_proto),
+            );
+        }
+        Ok(containers)
+    }
+}
+
+/// The containers of containerd, by their IDs.
+#[derive(Clone, Default)]
+pub(super) struct ContainerdContainers {
+    containers: Arc<RwLock<HashMap<String, ContainerdContainer>>>,
+}
+
+impl ContainerdContainers {
+    /// Replaces the containers with the ones currently listed by containerd.
+    pub(super) async fn refresh(&self, client: &ContainerdClient) {
+        match client.list_containers().await {
+            Ok(containers) => {
+                *self.containers.write().expect("poisoned lock") = containers
+                    .into_iter()
+                    .map(|container| (container.id.clone(), container))
+                    .collect();
+            }
+            Err(error) => emit!(DockerLogsContainerdListError { error }),
+        }
+    }
+
+    pub(super) fn get(&self, id: &str) -> Option<ContainerdContainer> {
+        self.read().get(id).cloned()
+    }
+
+    pub(super) fn read(&self) -> RwLockReadGuard<'_, HashMap<String, ContainerdContainer>> {
+        self.containers.read().expect("poisoned lock")
+    }
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use std::collections::BTreeMap;
+
+    use tokio::net::UnixListener;
+    use tokio_stream::wrappers::UnixListenerStream;
+    use tonic::{transport::Server, Request, Response, Status};
+
+    use super::{
+        proto::{containers, namespaces},
+        *,
+    };
+    use crate::test_util::{temp_dir, temp_file};
+
+    pub(in crate::sources::docker_logs) const ID: &str =
+        "4f2c8e124a6f0c57e1c2cbd9b1f0a9e3d0e6a2b7f1c4d5e6a7b8c9d0e1f2a3b4";
+
+    /// Returns a container of namespace `k8s.io` created by the CRI plugin.
+    pub(in crate::sources::docker_logs) fn cri_container(
+        id: &str,
+        name: &str,
+    ) -> containers::Container {
+        containers::Container {
+            id: id.to_owned(),
+            labels: BTreeMap::from([
+                (CRI_CONTAINER_NAME_LABEL.to_owned(), name.to_owned()),
+                (
+                    "io.kubernetes.pod.namespace".to_owned(),
+                    "default".to_owned(),
+                ),
+            ]),
+            image: "docker.io/library/nginx:1.25".to_owned(),
+            created_at: Some(prost_types::Timestamp {
+                seconds: 1_714_557_600,
+                nanos: 500,
+            }),
+            ..Default::default()
+        }
+    }
+
+    /// Returns a container of namespace `default` created by nerdctl.
+    pub(in crate::sources::docker_logs) fn nerdctl_container(
+        id: &str,
+        name: &str,
+        state_dir: &Path,
+    ) -> containers::Container {
+        containers::Container {
+            id: id.to_owned(),
+            labels: BTreeMap::from([
+                (NERDCTL_NAME_LABEL.to_owned(), name.to_owned()),
+                (
+                    NERDCTL_STATE_DIR_LABEL.to_owned(),
+                    state_dir.to_str().unwrap().to_owned(),
+                ),
+            ]),
+            image: "docker.io/library/redis:7".to_owned(),
+            ..Default::default()
+        }
+    }
+
+    /// A containerd listing its containers by namespace.
+    struct Containerd {
+        containers: BTreeMap<String, Vec<containers::Container>>,
+    }
+
+    #[tonic::async_trait]
+    impl namespaces::namespaces_server::Namespaces for Containerd {
+        async fn list(
+            &self,
+            _: Request<namespaces::ListNamespacesRequest>,
+        ) -> Result<Response<namespaces::ListNamespacesResponse>, Status> {
+            let namespaces = self
+                .containers
+                .keys()
+                .map(|name| namespaces::Namespace {
+                    name: name.clone(),
+                    ..Default::default()
+                })
+                .collect();
+            Ok(Response::new(namespaces::ListNamespacesResponse {
+                namespaces,
+            }))
+        }
+    }
+
+    #[tonic::async_trait]
+    impl containers::containers_server::Containers for Containerd {
+        async fn list(
+            &self,
+            request: Request<containers::ListContainersRequest>,
+        ) -> Result<Response<containers::ListContainersResponse>, Status> {
+            let namespace = request
+                .metadata()
+                .get(NAMESPACE_HEADER)
+                .and_then(|namespace| namespace.to_str().ok())
+                .ok_or_else(|| Status::failed_precondition("namespace is required"))?;
+            let containers = self.containers.get(namespace).cloned().unwrap_or_default();
+            Ok(Response::new(containers::ListContainersResponse {
+                containers,
+            }))
+        }
+    }
+
+    /// Serves the containers by namespace on a new socket, returning the path of the socket.
+    pub(in crate::sources::docker_logs) fn serve_containerd(
+        containers: impl IntoIterator<Item = (&'static str, containers::Container)>,
+    ) -> PathBuf {
+        let mut by_namespace = BTreeMap::<String, Vec<_>>::new();
+        for (namespace, container) in containers {
+            by_namespace
+                .entry(namespace.to_owned())
+                .or_default()
+                .push(container);
+        }
+        let containerd = Arc::new(Containerd {
+            containers: by_namespace,
+        });
+
+        let socket = temp_file();
+        let listener = UnixListener::bind(&socket).unwrap();
+        tokio::spawn(
+            Server::builder()
+                .add_service(namespaces::namespaces_server::NamespacesServer::from_arc(
+                    Arc::clone(&containerd),
+                ))
+                .add_service(containers::containers_server::ContainersServer::from_arc(
+                    containerd,
+                ))
+                .serve_with_incoming(UnixListenerStream::new(listener)),
+        );
+        socket
+    }
+
+    #[test]
+    fn converts_containers() {
+        let container = ContainerdContainer::from_proto(cri_container(ID, "nginx"));
+        assert_eq!(container.id, ID);
+        assert_eq!(container.name, "nginx");
+        assert_eq!(
+            container.image.as_deref(),
+            Some("docker.io/library/nginx:1.25")
+        );
+        assert_eq!(container.labels["io.kubernetes.pod.namespace"], "default");
+        assert_eq!(
+            container.created_at,
+            Some(Utc.timestamp_opt(1_714_557_600, 500).unwrap())
+        );
+        assert_eq!(container.json_log_file, None);
+
+        let state_dir = temp_dir();
+        let container = ContainerdContainer::from_proto(nerdctl_container(ID, "redis", &state_dir));
+        assert_eq!(container.name, "redis");
+        assert_eq!(
+            container.json_log_file,
+            Some(state_dir.join(format!("{ID}-json.log")))
+        );
+        assert_eq!(container.created_at, None);
+
+        // The containers created by other clients are named by their IDs.
+        let container = ContainerdContainer::from_proto(containers::Container {
+            id: ID.to_owned(),
+            ..Default::default()
+        });
+        assert_eq!(container.name, ID);
+        assert_eq!(container.image, None);
+    }
+
+    #[tokio::test]
+    async fn lists_containers_of_all_namespaces() {
+        let state_dir = temp_dir();
+        let socket = serve_containerd([
+            ("k8s.io", cri_container(ID, "nginx")),
+            (
+                "default",
+                nerdctl_container("redis-id", "redis", &state_dir),
+            ),
+        ]);
+
+        let client = ContainerdClient::new(socket);
+        let containers = ContainerdContainers::default();
+        containers.refresh(&client).await;
+
+        assert_eq!(containers.read().len(), 2);
+        assert_eq!(containers.get(ID).unwrap().name, "nginx");
+        assert_eq!(containers.get("redis-id").unwrap().name, "redis");
+    }
+
+    #[tokio::test]
+    async fn keeps_containers_when_containerd_is_unavailable() {
+        let socket = serve_containerd([("k8s.io", cri_container(ID, "nginx"))]);
+        let containers = ContainerdContainers::default();
+        containers.refresh(&ContainerdClient::new(socket)).await;
+
+        containers
+            .refresh(&ContainerdClient::new(temp_file()))
+            .await;
+        assert_eq!(containers.get(ID).unwrap().name, "nginx");
+    }
+}
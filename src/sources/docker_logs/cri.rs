@@ -0,0 +1,763 @@
+//! Reading the logs of the containers of containerd from the log files written by its CRI plugin
+//! and by nerdctl.
+
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    convert::TryFrom,
+    future::ready,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use futures::{Stream, StreamExt};
+use serde::Deserialize;
+use tokio::task::spawn_blocking;
+use tracing_futures::Instrument;
+use vector_lib::config::LogNamespace;
+use vector_lib::file_source::{
+    paths_provider::{
+        glob::{Glob, MatchOptions},
+        PathsProvider,
+    },
+    Checkpointer, FileServer, FileServerShutdown, FingerprintStrategy, Fingerprinter, Line,
+    ReadFrom,
+};
+use vector_lib::internal_event::{ByteSize, BytesReceived, InternalEventHandle as _, Protocol};
+
+use super::{
+    add_hostname,
+    containerd::{ContainerdClient, ContainerdContainers, REFRESH_INTERVAL},
+    line_agg_adapter, merge_partial_event, ContainerId, ContainerMetadata, DockerLogsConfig,
+    STDERR, STDOUT,
+};
+use crate::{
+    config::SourceContext,
+    event::{merge_state::LogEventMergeState, EstimatedJsonEncodedSizeOf, LogEvent},
+    internal_events::{
+        DockerLogsEventsReceived, DockerLogsTimestampParseError, FileSourceInternalEventsEmitter,
+        ParserMatchError, StreamClosedError,
+    },
+    line_agg,
+};
+
+/// The directory where the kubelet links the log files of the containers.
+pub(super) const DEFAULT_CRI_LOG_DIRECTORY: &str = "/var/log/containers";
+
+/// The maximum size of the lines of the log files.
+///
+/// containerd splits the lines of the containers longer than 16KiB into partial lines, so this
+/// leaves room for the prefix of the lines.
+const MAX_LINE_BYTES: usize = 32 * 1024;
+
+/// The maximum number of bytes read from a file before reading the next one.
+const MAX_READ_BYTES: usize = 2048;
+
+/// The interval between the lookups of new log files.
+const GLOB_MINIMUM_COOLDOWN: Duration = Duration::from_secs(1);
+
+/// Builds the source reading the log files of the containers of containerd.
+pub(super) fn build(
+    config: DockerLogsConfig,
+    cx: SourceContext,
+    log_namespace: LogNamespace,
+) -> crate::Result<crate::sources::Source> {
+    let data_dir = cx
+        .globals
+        .resolve_and_make_data_subdir(config.data_dir.as_ref(), cx.key.id())?;
+    let line_agg_config = config
+        .multiline
+        .as_ref()
+        .map(line_agg::Config::try_from)
+        .transpose()?;
+
+    let emitter = FileSourceInternalEventsEmitter {
+        include_file_metric_tag: false,
+    };
+    let glob = Glob::new(
+        &[config.cri_log_directory.join("*.log")],
+        &[],
+        MatchOptions::default(),
+        emitter.clone(),
+    )
+    .ok_or("The `cri_log_directory` option must be a valid UTF-8 path.")?;
+
+    let client = ContainerdClient::new(config.containerd_socket.clone());
+    let containerd_containers = ContainerdContainers::default();
+    let file_server = FileServer {
+        paths_provider: CriPathsProvider {
+            glob,
+            config: config.clone(),
+            containers: containerd_containers.clone(),
+        },
+        max_read_bytes: MAX_READ_BYTES,
+        ignore_checkpoints: false,
+        // Like with the Docker-compatible API, only the logs written from now on are read from the
+        // files of the running containers, the first time the source starts.
+        read_from: ReadFrom::End,
+        ignore_before: None,
+        max_line_bytes: MAX_LINE_BYTES,
+        line_delimiter: Bytes::from("\n"),
+        data_dir,
+        glob_minimum_cooldown: GLOB_MINIMUM_COOLDOWN,
+        fingerprinter: Fingerprinter {
+            strategy: FingerprintStrategy::FirstLinesChecksum {
+                ignored_header_bytes: 0,
+                lines: 1,
+            },
+            max_line_length: MAX_LINE_BYTES,
+            ignore_not_found: true,
+        },
+        oldest_first: false,
+        // The log files are removed by containerd and nerdctl.
+        remove_after: None,
+        emitter,
+        handle: tokio::runtime::Handle::current(),
+        rotate_wait: Duration::from_secs(u64::MAX / 2),
+    };
+
+    let host_key = config.host_key.clone().path;
+    let hostname = crate::get_hostname().ok();
+    let mut out = cx.out;
+    let shutdown = cx.shutdown;
+
+    Ok(Box::pin(async move {
+        info!(
+            message = "Reading the log files of the containers of containerd.",
+            socket = %config.containerd_socket.display(),
+            directory = %config.cri_log_directory.display(),
+        );
+
+        // The files of the containers running when the source starts are only looked up once
+        // they are listed.
+        containerd_containers.refresh(&client).await;
+        let refresher = tokio::spawn({
+            let containers = containerd_containers.clone();
+            async move {
+                let mut interval = tokio::time::interval(REFRESH_INTERVAL);
+                interval.tick().await;
+                loop {
+                    interval.tick().await;
+                    containers.refresh(&client).await;
+                }
+            }
+            .in_current_span()
+        });
+
+        let checkpointer = Checkpointer::new(&file_server.data_dir);
+        let checkpoints = checkpointer.view();
+        let (tx, rx) = futures::channel::mpsc::channel::<Vec<Line>>(2);
+
+        let bytes_received = register!(BytesReceived::from(Protocol::from("file")));
+        let mut containers = CriContainers::new(containerd_containers);
+        let events = rx.flat_map(futures::stream::iter).filter_map(move |line| {
+            bytes_received.emit(ByteSize(line.text.len()));
+            checkpoints.update(line.file_id, line.end_offset);
+
+            let event = containers
+                .get(&line.filename)
+                .and_then(|container| container.new_event(line.text, &config, log_namespace));
+            ready(event)
+        });
+
+        let events: Box<dyn Stream<Item = LogEvent> + Unpin + Send> =
+            if let Some(line_agg_config) = line_agg_config {
+                Box::new(line_agg_adapter(
+                    events,
+                    line_agg::Logic::new(line_agg_config),
+                    log_namespace,
+                ))
+            } else {
+                Box::new(events)
+            };
+
+        let mut events =
+            events.map(move |event| add_hostname(event, &host_key, &hostname, log_namespace));
+        // Once the file server stops, this sends the remaining lines.
+        let sender = tokio::spawn(
+            async move {
+                if out.send_event_stream(&mut events).await.is_err() {
+                    let (count, _) = events.size_hint();
+                    emit!(StreamClosedError { count });
+                }
+            }
+            .in_current_span(),
+        );
+
+        let span = info_span!("file_server");
+        match spawn_blocking(move || {
+            let _enter = span.enter();
+            file_server.run(tx, shutdown.clone(), shutdown, checkpointer)
+        })
+        .await
+        {
+            Ok(Ok(FileServerShutdown)) => debug!(message = "File server completed gracefully."),
+            Ok(Err(error)) => error!(message = "File server failed to send lines.", %error),
+            Err(error) => error!(message = "File server unexpectedly stopped.", %error),
+        }
+
+        refresher.abort();
+        _ = sender.await;
+        Ok(())
+    }))
+}
+
+/// Provides the log files of the included containers listed by containerd.
+struct CriPathsProvider {
+    glob: Glob<FileSourceInternalEventsEmitter>,
+    config: DockerLogsConfig,
+    containers: ContainerdContainers,
+}
+
+impl PathsProvider for CriPathsProvider {
+    type IntoIter = Vec<PathBuf>;
+
+    fn paths(&self) -> Self::IntoIter {
+        let containers = self.containers.read();
+        let included = |id: &str| {
+            containers.get(id).filter(|container| {
+                self.config
+                    .container_name_or_id_included(&container.id, [container.name.as_str()])
+            })
+        };
+
+        let cri_files = self.glob.paths().into_iter().filter(|path| {
+            parse_file_name(path)
+                .is_some_and(|(id, format)| format == LogFormat::Cri && included(id).is_some())
+        });
+        let json_files = containers
+            .keys()
+            .filter_map(|id| included(id)?.json_log_file.clone());
+        cri_files.chain(json_files).collect()
+    }
+}
+
+/// The format of a log file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum LogFormat {
+    /// The [CRI log format][cri_log_format] of the files written by the CRI plugin.
+    ///
+    /// [cri_log_format]: https://github.com/kubernetes/design-proposals-archive/blob/main/node/kubelet-cri-logging.md
+    Cri,
+    /// The format of the `json-file` logging driver of Docker, of the files written by nerdctl.
+    JsonFile,
+}
+
+/// Parses the name of a log file into the ID of its container and the format of the file.
+///
+/// The CRI log files are named `<pod>_<namespace>_<container>-<container ID>.log`, and the files
+/// of nerdctl `<container ID>-json.log`.
+fn parse_file_name(path: &Path) -> Option<(&str, LogFormat)> {
+    let is_id = |id: &str| id.len() == 64 && id.bytes().all(|byte| byte.is_ascii_hexdigit());
+    let stem = path.file_name()?.to_str()?.strip_suffix(".log")?;
+    let (prefix, suffix) = stem.rsplit_once('-')?;
+    if suffix == "json" && is_id(prefix) {
+        return Some((prefix, LogFormat::JsonFile));
+    }
+
+    let mut parts = prefix.splitn(3, '_');
+    let (pod, namespace, name) = (parts.next()?, parts.next()?, parts.next()?);
+    let valid = !pod.is_empty() && !namespace.is_empty() && !name.is_empty() && is_id(suffix);
+    valid.then_some((suffix, LogFormat::Cri))
+}
+
+/// The containers of the log files being read, by the paths of their files.
+struct CriContainers {
+    containers: HashMap<String, CriContainer>,
+    containerd_containers: ContainerdContainers,
+}
+
+impl CriContainers {
+    fn new(containerd_containers: ContainerdContainers) -> Self {
+        Self {
+            containers: HashMap::new(),
+            containerd_containers,
+        }
+    }
+
+    fn get(&mut self, filename: &str) -> Option<&mut CriContainer> {
+        if !self.containers.contains_key(filename) {
+            let (id, format) = parse_file_name(Path::new(filename))?;
+            let metadata = self.containerd_containers.get(id)?.metadata();
+            // The containers whose files were removed since are forgotten when a new file is read.
+            self.containers.retain(|path, _| Path::new(path).exists());
+            self.containers.insert(
+                filename.to_owned(),
+                CriContainer::new(ContainerId::new(id.to_owned()), format, metadata),
+            );
+        }
+        self.containers.get_mut(filename)
+    }
+}
+
+/// A container, with the partial lines of each stream of its log file being merged.
+struct CriContainer {
+    id: ContainerId,
+    format: LogFormat,
+    metadata: ContainerMetadata,
+    partial_event_merge_states: HashMap<Bytes, Option<LogEventMergeState>>,
+}
+
+impl CriContainer {
+    fn new(id: ContainerId, format: LogFormat, metadata: ContainerMetadata) -> Self {
+        Self {
+            id,
+            format,
+            metadata,
+            partial_event_merge_states: HashMap::new(),
+        }
+    }
+
+    fn new_event(
+        &mut self,
+        line: Bytes,
+        config: &DockerLogsConfig,
+        log_namespace: LogNamespace,
+    ) -> Option<LogEvent> {
+        let log_line = match self.format {
+            LogFormat::Cri => parse_line(&line),
+            LogFormat::JsonFile => parse_json_line(&line),
+        };
+        let Some(log_line) = log_line else {
+            emit!(ParserMatchError { value: &line });
+            return None;
+        };
+
+        let timestamp = match DateTime::parse_from_rfc3339(&log_line.timestamp) {
+            Ok(timestamp) => Some(timestamp.with_timezone(&Utc)),
+            Err(error) => {
+                emit!(DockerLogsTimestampParseError {
+                    error,
+                    container_id: self.id.as_str()
+                });
+                None
+            }
+        };
+
+        // The partial lines of stdout and stderr are interleaved in the files.
+        let partial_event_merge_state = self
+            .partial_event_merge_states
+            .entry(log_line.stream.clone())
+            .or_default();
+        let log = self.metadata.new_log(
+            &self.id,
+            log_line.message,
+            log_line.stream,
+            timestamp,
+            log_namespace,
+        );
+        let log = merge_partial_event(
+            log,
+            log_line.partial,
+            config.partial_event_marker_field.clone(),
+            config.auto_partial_merge,
+            partial_event_merge_state,
+            log_namespace,
+        )?;
+
+        emit!(DockerLogsEventsReceived {
+            byte_size: log.estimated_json_encoded_size_of(),
+            container_id: self.id.as_str(),
+            container_name: &self.metadata.name_str
+        });
+
+        Some(log)
+    }
+}
+
+/// A line of a log file.
+#[derive(Debug, PartialEq)]
+struct LogLine<'a> {
+    timestamp: Cow<'a, str>,
+    stream: Bytes,
+    partial: bool,
+    message: Bytes,
+}
+
+/// Parses a line in the CRI log format, `<timestamp> <stream> <P|F> <message>`, where the `P` tag
+/// marks the partial lines, which are continued by the next line of the stream.
+fn parse_line(line: &Bytes) -> Option<LogLine<'_>> {
+    let mut parts = line.splitn(4, |&byte| byte == b' ');
+    let timestamp = std::str::from_utf8(parts.next()?).ok()?;
+    let stream = parse_stream(parts.next()?)?;
+    let partial = match parts.next()? {
+        b"P" => true,
+        b"F" => false,
+        _ => return None,
+    };
+    let message = parts
+        .next()
+        .filter(|message| !message.is_empty())
+        .map(|message| line.slice_ref(message))
+        .unwrap_or_default();
+
+    Some(LogLine {
+        timestamp: timestamp.into(),
+        stream,
+        partial,
+        message,
+    })
+}
+
+/// A line of a log file of the `json-file` logging driver of Docker.
+#[derive(Deserialize)]
+struct JsonLine<'a> {
+    #[serde(borrow)]
+    log: Cow<'a, str>,
+    #[serde(borrow)]
+    stream: Cow<'a, str>,
+    #[serde(borrow)]
+    time: Cow<'a, str>,
+}
+
+/// Parses a line in the format of the `json-file` logging driver of Docker,
+/// `{"log":"<message>\n","stream":"<stream>","time":"<timestamp>"}`, where the messages without a
+/// trailing newline are partial, and are continued by the next line of the stream.
+fn parse_json_line(line: &Bytes) -> Option<LogLine<'_>> {
+    let json_line = serde_json::from_slice::<JsonLine>(line).ok()?;
+    let stream = parse_stream(json_line.stream.as_bytes())?;
+    let (message, partial) = match json_line.log.strip_suffix('\n') {
+        Some(message) => (message, false),
+        None => (json_line.log.as_ref(), true),
+    };
+
+    Some(LogLine {
+        timestamp: json_line.time,
+        stream,
+        partial,
+        message: Bytes::copy_from_slice(message.as_bytes()),
+    })
+}
+
+fn parse_stream(stream: &[u8]) -> Option<Bytes> {
+    match stream {
+        b"stdout" => Some(STDOUT.clone()),
+        b"stderr" => Some(STDERR.clone()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, io::Write};
+
+    use tokio::time::{timeout, Instant};
+    use vector_lib::lookup::path;
+
+    use super::*;
+    use crate::{
+        config::log_schema,
+        event::Value,
+        sources::docker_logs::{
+            containerd::tests::{cri_container, nerdctl_container, serve_containerd, ID},
+            ContainerRuntime, CONTAINER, CREATED_AT, IMAGE, NAME, STREAM,
+        },
+        test_util::{temp_dir, trace_init},
+        SourceSender,
+    };
+
+    const REDIS_ID: &str = "9a8b7c6d5e4f3a2b1c0d9e8f7a6b5c4d3e2f1a0b9c8d7e6f5a4b3c2d1e0f9a8b";
+    const SIDECAR_ID: &str = "0e1f2a3b4c5d6e7f8a9b0c1d2e3f4a5b6c7d8e9f0a1b2c3d4e5f6a7b8c9d0e1f";
+
+    fn container(format: LogFormat) -> CriContainer {
+        CriContainer::new(
+            ContainerId::new(ID.to_owned()),
+            format,
+            ContainerMetadata {
+                labels: HashMap::new(),
+                name: "nginx".into(),
+                name_str: "nginx".to_owned(),
+                image: Some("nginx:1.25".into()),
+                created_at: None,
+            },
+        )
+    }
+
+    fn message(log: &LogEvent) -> &Value {
+        &log[log_schema().message_key().unwrap().to_string()]
+    }
+
+    #[test]
+    fn parses_file_names() {
+        let path = PathBuf::from(format!(
+            "/var/log/containers/web-7d4b9_default_nginx-{ID}.log"
+        ));
+        assert_eq!(parse_file_name(&path), Some((ID, LogFormat::Cri)));
+        let path = PathBuf::from(format!(
+            "/var/lib/nerdctl/1935db59/containers/default/{ID}/{ID}-json.log"
+        ));
+        assert_eq!(parse_file_name(&path), Some((ID, LogFormat::JsonFile)));
+
+        for name in [
+            format!("web-7d4b9_default_nginx-{ID}.txt"),
+            format!("web-7d4b9_nginx-{ID}.log"),
+            "web-7d4b9_default_nginx-4f2c8e124a6f.log".to_owned(),
+            format!("_default_nginx-{ID}.log"),
+            format!("{ID}-json.log.1"),
+            "4f2c8e124a6f-json.log".to_owned(),
+        ] {
+            assert_eq!(parse_file_name(Path::new(&name)), None, "{name}");
+        }
+    }
+
+    #[test]
+    fn parses_lines() {
+        let line = Bytes::from("2024-05-01T10:00:00.123456789Z stdout F hello world");
+        assert_eq!(
+            parse_line(&line),
+            Some(LogLine {
+                timestamp: "2024-05-01T10:00:00.123456789Z".into(),
+                stream: STDOUT.clone(),
+                partial: false,
+                message: Bytes::from("hello world"),
+            })
+        );
+
+        let line = Bytes::from("2024-05-01T10:00:00Z stderr P ");
+        assert_eq!(
+            parse_line(&line),
+            Some(LogLine {
+                timestamp: "2024-05-01T10:00:00Z".into(),
+                stream: STDERR.clone(),
+                partial: true,
+                message: Bytes::new(),
+            })
+        );
+
+        for line in [
+            "2024-05-01T10:00:00Z stdin F hello",
+            "2024-05-01T10:00:00Z stdout X hello",
+            "hello",
+        ] {
+            assert_eq!(parse_line(&Bytes::from(line)), None, "{line}");
+        }
+    }
+
+    #[test]
+    fn parses_json_lines() {
+        let line = Bytes::from(
+            r#"{"log":"hello \"world\"\n","stream":"stdout","time":"2024-05-01T10:00:00.123456789Z"}"#,
+        );
+        assert_eq!(
+            parse_json_line(&line),
+            Some(LogLine {
+                timestamp: "2024-05-01T10:00:00.123456789Z".into(),
+                stream: STDOUT.clone(),
+                partial: false,
+                message: Bytes::from(r#"hello "world""#),
+            })
+        );
+
+        let line =
+            Bytes::from(r#"{"log":"hello","stream":"stderr","time":"2024-05-01T10:00:00Z"}"#);
+        assert_eq!(
+            parse_json_line(&line),
+            Some(LogLine {
+                timestamp: "2024-05-01T10:00:00Z".into(),
+                stream: STDERR.clone(),
+                partial: true,
+                message: Bytes::from("hello"),
+            })
+        );
+
+        for line in [
+            r#"{"log":"hello\n","stream":"stdin","time":"2024-05-01T10:00:00Z"}"#,
+            r#"{"log":"hello\n","time":"2024-05-01T10:00:00Z"}"#,
+            "2024-05-01T10:00:00Z stdout F hello",
+        ] {
+            assert_eq!(parse_json_line(&Bytes::from(line)), None, "{line}");
+        }
+    }
+
+    #[test]
+    fn merges_partial_lines() {
+        let config = DockerLogsConfig::default();
+        let mut container = container(LogFormat::Cri);
+        let mut new_event = |line: &'static str| {
+            container.new_event(Bytes::from(line), &config, LogNamespace::Legacy)
+        };
+
+        assert_eq!(new_event("2024-05-01T10:00:00Z stdout P hello "), None);
+        assert_eq!(new_event("2024-05-01T10:00:01Z stdout P big "), None);
+        let log = new_event("2024-05-01T10:00:02Z stdout F world").unwrap();
+
+        assert_eq!(message(&log), &"hello big world".into());
+        assert_eq!(log[CONTAINER], ID.into());
+        assert_eq!(log[NAME], "nginx".into());
+        assert_eq!(log[IMAGE], "nginx:1.25".into());
+        assert_eq!(log[STREAM], "stdout".into());
+    }
+
+    #[test]
+    fn merges_partial_lines_by_stream() {
+        let config = DockerLogsConfig::default();
+        let mut container = container(LogFormat::JsonFile);
+        let mut new_event = |line: &str| {
+            container.new_event(
+                Bytes::copy_from_slice(line.as_bytes()),
+                &config,
+                LogNamespace::Legacy,
+            )
+        };
+
+        let time = r#""time":"2024-05-01T10:00:00Z""#;
+        let lines = [
+            format!(r#"{{"log":"hello ","stream":"stdout",{time}}}"#),
+            format!(r#"{{"log":"failed ","stream":"stderr",{time}}}"#),
+            format!(r#"{{"log":"world\n","stream":"stdout",{time}}}"#),
+            format!(r#"{{"log":"again\n","stream":"stderr",{time}}}"#),
+        ];
+        let events = lines
+            .iter()
+            .map(|line| new_event(line))
+            .map(|log| log.map(|log| (message(&log).clone(), log[STREAM].clone())))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            events,
+            vec![
+                None,
+                None,
+                Some(("hello world".into(), "stdout".into())),
+                Some(("failed again".into(), "stderr".into())),
+            ]
+        );
+    }
+
+    #[test]
+    fn marks_partial_lines() {
+        let config = DockerLogsConfig {
+            auto_partial_merge: false,
+            ..DockerLogsConfig::default()
+        };
+        let mut container = container(LogFormat::Cri);
+
+        let log = container
+            .new_event(
+                Bytes::from("2024-05-01T10:00:00Z stdout P hello"),
+                &config,
+                LogNamespace::Vector,
+            )
+            .unwrap();
+        assert_eq!(log.value(), &"hello".into());
+        assert_eq!(
+            log.metadata()
+                .value()
+                .get(path!(DockerLogsConfig::NAME, "_partial")),
+            Some(&true.into())
+        );
+    }
+
+    #[tokio::test]
+    async fn reads_log_files() {
+        trace_init();
+
+        let directory = temp_dir();
+        let state_dir = temp_dir();
+        let data_dir = temp_dir();
+        for dir in [&directory, &state_dir, &data_dir] {
+            fs::create_dir_all(dir).unwrap();
+        }
+        let socket = serve_containerd([
+            ("k8s.io", cri_container(ID, "nginx")),
+            ("k8s.io", cri_container(SIDECAR_ID, "sidecar")),
+            ("default", nerdctl_container(REDIS_ID, "redis", &state_dir)),
+        ]);
+        let config = DockerLogsConfig {
+            runtime: ContainerRuntime::Containerd,
+            containerd_socket: socket,
+            cri_log_directory: directory.clone(),
+            data_dir: Some(data_dir),
+            exclude_containers: Some(vec!["sidecar".to_owned()]),
+            ..DockerLogsConfig::default()
+        };
+
+        let (tx, mut rx) = SourceSender::new_test();
+        let source = build(
+            config,
+            SourceContext::new_test(tx, None),
+            LogNamespace::Legacy,
+        )
+        .unwrap();
+        tokio::spawn(source);
+
+        let mut cri_file =
+            fs::File::create(directory.join(format!("web_default_nginx-{ID}.log"))).unwrap();
+        let mut excluded_file =
+            fs::File::create(directory.join(format!("web_default_sidecar-{SIDECAR_ID}.log")))
+                .unwrap();
+        let mut json_file =
+            fs::File::create(state_dir.join(format!("{REDIS_ID}-json.log"))).unwrap();
+
+        // The files found when the source starts are read from their end, so lines are written
+        // until they are read.
+        let deadline = Instant::now() + Duration::from_secs(30);
+        let mut events = Vec::new();
+        for (file, line, name) in [
+            (
+                &mut cri_file,
+                "2024-05-01T10:00:00Z stdout F ready",
+                "nginx",
+            ),
+            (
+                &mut json_file,
+                r#"{"log":"ready\n","stream":"stdout","time":"2024-05-01T10:00:00Z"}"#,
+                "redis",
+            ),
+        ] {
+            loop {
+                writeln!(file, "{line}").unwrap();
+                let event = timeout(Duration::from_millis(100), rx.next()).await;
+                match event {
+                    Ok(Some(event)) if event.as_log()[NAME] == Value::from(name) => {
+                        events.push(event);
+                        break;
+                    }
+                    _ => assert!(Instant::now() < deadline, "The log files were not read."),
+                }
+            }
+        }
+
+        writeln!(excluded_file, "2024-05-01T10:00:00Z stdout F excluded").unwrap();
+        writeln!(cri_file, "2024-05-01T10:00:00Z stdout P hello ").unwrap();
+        writeln!(cri_file, "2024-05-01T10:00:01Z stdout F world").unwrap();
+        writeln!(
+            json_file,
+            r#"{{"log":"failed\n","stream":"stderr","time":"2024-05-01T10:00:02Z"}}"#
+        )
+        .unwrap();
+
+        let mut messages = Vec::new();
+        while messages.len() < 2 {
+            let event = timeout(deadline - Instant::now(), rx.next())
+                .await
+                .expect("The lines were not read.")
+                .unwrap();
+            let log = event.as_log();
+            if message(log) != &Value::from("ready") {
+                messages.push((message(log).clone(), log[NAME].clone(), log[STREAM].clone()));
+            }
+        }
+        messages.sort_by_key(|(_, name, _)| name.to_string_lossy().into_owned());
+        assert_eq!(
+            messages,
+            vec![
+                ("hello world".into(), "nginx".into(), "stdout".into()),
+                ("failed".into(), "redis".into(), "stderr".into()),
+            ]
+        );
+
+        let log = events[0].as_log();
+        assert_eq!(log[IMAGE], "docker.io/library/nginx:1.25".into());
+        assert_eq!(
+            log.get(path!("label", "io.kubernetes.pod.namespace")),
+            Some(&"default".into())
+        );
+        assert!(log.get(CREATED_AT).is_some());
+        assert_eq!(
+            events[1].as_log()[IMAGE],
+            "docker.io/library/redis:7".into()
+        );
+    }
+}
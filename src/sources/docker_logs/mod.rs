@@ -1,5 +1,12 @@
 use std::{
-    collections::HashMap, convert::TryFrom, future::ready, pin::Pin, sync::Arc, time::Duration,
+    collections::HashMap,
+    convert::TryFrom,
+    env,
+    future::ready,
+    path::{Path, PathBuf},
+    pin::Pin,
+    sync::Arc,
+    time::Duration,
 };
 
 use bollard::{
@@ -44,6 +51,8 @@ use crate::{
     SourceSender,
 };
 
+mod containerd;
+mod cri;
 #[cfg(test)]
 mod tests;
 
@@ -59,6 +68,58 @@ static STDERR: Lazy<Bytes> = Lazy::new(|| "stderr".into());
 static STDOUT: Lazy<Bytes> = Lazy::new(|| "stdout".into());
 static CONSOLE: Lazy<Bytes> = Lazy::new(|| "console".into());
 
+/// The rootful Podman API socket.
+const PODMAN_ROOTFUL_SOCKET: &str = "/run/podman/podman.sock";
+
+/// The container engine to collect logs from.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ContainerRuntime {
+    /// [Docker Engine][docker].
+    ///
+    /// [docker]: https://docs.docker.com/engine/
+    #[default]
+    Docker,
+
+    /// [Podman][podman], through its Docker-compatible API service.
+    ///
+    /// If `docker_host` is not set, the `CONTAINER_HOST` environment variable is used. If
+    /// `CONTAINER_HOST` is also absent, the rootless socket at `$XDG_RUNTIME_DIR/podman/podman.sock`
+    /// is used if it exists, falling back to the rootful socket at `/run/podman/podman.sock`.
+    ///
+    /// The Podman API service must be running, for example with `systemctl enable --now podman.socket`.
+    ///
+    /// [podman]: https://podman.io/
+    Podman,
+
+    /// [containerd][containerd], by reading the log files written by its CRI plugin and by
+    /// [nerdctl][nerdctl].
+    ///
+    /// The containers of all the namespaces, with their name, image, labels, and creation
+    /// timestamp, are listed through the gRPC API of containerd at `containerd_socket`.
+    ///
+    /// The log files of the CRI plugin are read from `cri_log_directory`, and are named
+    /// `<pod>_<namespace>_<container>-<container ID>.log`, such as the links kept in
+    /// `/var/log/containers` by the kubelet. Their lines are read in the
+    /// [CRI log format][cri_log_format]. The log files of nerdctl are read from the state
+    /// directory of their container, in the format of the `json-file` logging driver of Docker.
+    /// The containers without a log file, such as the ones started by `ctr run`, are not read.
+    /// Partial lines are merged by stream if `auto_partial_merge` is enabled.
+    ///
+    /// containerd has no Docker-compatible API, so the `docker_host`, `tls`, `include_labels`,
+    /// and `include_images` options are rejected.
+    ///
+    /// The files are read from their end when the source starts for the first time, and from
+    /// their checkpoint in `data_dir` afterwards. Files created while the source runs are read
+    /// from their beginning.
+    ///
+    /// [containerd]: https://containerd.io/
+    /// [nerdctl]: https://github.com/containerd/nerdctl
+    /// [cri_log_format]: https://github.com/kubernetes/design-proposals-archive/blob/main/node/kubelet-cri-logging.md
+    Containerd,
+}
+
 /// Configuration for the `docker_logs` source.
 #[serde_as]
 #[configurable_component(source("docker_logs", "Collect container logs from a Docker Daemon."))]
@@ -88,6 +149,33 @@ pub struct DockerLogsConfig {
     #[configurable(metadata(docs::examples = "//./pipe/docker_engine"))]
     docker_host: Option<String>,
 
+    #[configurable(derived)]
+    runtime: ContainerRuntime,
+
+    /// The socket of the gRPC API of containerd, when the `containerd` runtime is used.
+    #[serde(default = "default_containerd_socket")]
+    #[configurable(metadata(docs::examples = "/run/containerd/containerd.sock"))]
+    #[configurable(metadata(docs::examples = "/run/k3s/containerd/containerd.sock"))]
+    containerd_socket: PathBuf,
+
+    /// The directory of the CRI log files, when the `containerd` runtime is used.
+    #[serde(default = "default_cri_log_directory")]
+    #[configurable(metadata(docs::examples = "/var/log/containers"))]
+    cri_log_directory: PathBuf,
+
+    /// The directory used to persist the checkpoints of the CRI log files, when the `containerd`
+    /// runtime is used.
+    ///
+    /// By default, the [global `data_dir` option][global_data_dir] is used.
+    /// Make sure the running user has write permissions to this directory.
+    ///
+    /// If this directory is specified, then Vector will attempt to create it.
+    ///
+    /// [global_data_dir]: https://vector.dev/docs/reference/configuration/global-options/#data_dir
+    #[configurable(metadata(docs::examples = "/var/local/lib/vector/"))]
+    #[configurable(metadata(docs::human_name = "Data Directory"))]
+    data_dir: Option<PathBuf>,
+
     /// A list of container IDs or names of containers to exclude from log collection.
     ///
     /// Matching is prefix first, so specifying a value of `foo` would match any container named `foo` as well as any
@@ -173,6 +261,10 @@ impl Default for DockerLogsConfig {
         Self {
             host_key: default_host_key(),
             docker_host: None,
+            runtime: ContainerRuntime::default(),
+            containerd_socket: default_containerd_socket(),
+            cri_log_directory: default_cri_log_directory(),
+            data_dir: None,
             tls: None,
             exclude_containers: None,
             include_containers: None,
@@ -191,6 +283,14 @@ fn default_host_key() -> OptionalValuePath {
     log_schema().host_key().cloned().into()
 }
 
+fn default_containerd_socket() -> PathBuf {
+    PathBuf::from(containerd::DEFAULT_CONTAINERD_SOCKET)
+}
+
+fn default_cri_log_directory() -> PathBuf {
+    PathBuf::from(cri::DEFAULT_CRI_LOG_DIRECTORY)
+}
+
 fn default_partial_event_marker_field() -> Option<String> {
     Some(event::PARTIAL.to_string())
 }
@@ -225,6 +325,45 @@ impl DockerLogsConfig {
                 .any(|name| items.iter().any(|item| name.starts_with(item)))
     }
 
+    /// Resolves the host to connect to, taking the container runtime defaults into account.
+    ///
+    /// Returning `None` lets the Docker client apply its own defaults.
+    fn effective_host(&self) -> Option<String> {
+        match self.runtime {
+            ContainerRuntime::Docker | ContainerRuntime::Containerd => self.docker_host.clone(),
+            ContainerRuntime::Podman => self.docker_host.clone().or_else(|| {
+                podman_default_host(
+                    env::var("CONTAINER_HOST").ok(),
+                    env::var_os("XDG_RUNTIME_DIR").map(PathBuf::from),
+                    Path::exists,
+                )
+            }),
+        }
+    }
+
+    /// Rejects the options of the Docker-compatible API, which containerd doesn't have.
+    fn check_containerd_options(&self) -> crate::Result<()> {
+        let unsupported = [
+            ("docker_host", self.docker_host.is_some()),
+            ("tls", self.tls.is_some()),
+            ("include_labels", self.include_labels.is_some()),
+            ("include_images", self.include_images.is_some()),
+        ]
+        .into_iter()
+        .filter_map(|(option, set)| set.then_some(format!("`{option}`")))
+        .collect::<Vec<_>>();
+
+        if unsupported.is_empty() {
+            Ok(())
+        } else {
+            Err(format!(
+                "The {} option(s) are not supported by the `containerd` runtime.",
+                unsupported.join(", ")
+            )
+            .into())
+        }
+    }
+
     fn with_empty_partial_event_marker_field_as_none(mut self) -> Self {
         if let Some(val) = &self.partial_event_marker_field {
             if val.is_empty() {
@@ -235,6 +374,24 @@ impl DockerLogsConfig {
     }
 }
 
+/// Picks the Podman API endpoint, mirroring the lookup order of the `podman --remote` client.
+fn podman_default_host(
+    container_host: Option<String>,
+    runtime_dir: Option<PathBuf>,
+    exists: impl Fn(&Path) -> bool,
+) -> Option<String> {
+    if let Some(host) = container_host.filter(|host| !host.is_empty()) {
+        return Some(host);
+    }
+
+    let socket = runtime_dir
+        .map(|dir| dir.join("podman").join("podman.sock"))
+        .filter(|socket| exists(socket))
+        .unwrap_or_else(|| PathBuf::from(PODMAN_ROOTFUL_SOCKET));
+
+    Some(format!("unix://{}", socket.display()))
+}
+
 impl_generate_config_from_default!(DockerLogsConfig);
 
 #[async_trait::async_trait]
@@ -242,6 +399,15 @@ impl_generate_config_from_default!(DockerLogsConfig);
 impl SourceConfig for DockerLogsConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
         let log_namespace = cx.log_namespace(self.log_namespace);
+        if self.runtime == ContainerRuntime::Containerd {
+            self.check_containerd_options()?;
+            return cri::build(
+                self.clone().with_empty_partial_event_marker_field_as_none(),
+                cx,
+                log_namespace,
+            );
+        }
+
         let source = DockerLogsSource::new(
             self.clone().with_empty_partial_event_marker_field_as_none(),
             cx.out,
@@ -274,6 +440,14 @@ impl SourceConfig for DockerLogsConfig {
 
     fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
         let host_key = self.host_key.clone().path.map(LegacyKey::Overwrite);
+        // containerd doesn't require the containers to have an image and a creation timestamp.
+        let api_metadata = |kind: Kind| {
+            if self.runtime == ContainerRuntime::Containerd {
+                kind.or_undefined()
+            } else {
+                kind
+            }
+        };
 
         let schema_definition = BytesDeserializerConfig
             .schema_definition(global_log_namespace.merge(self.log_namespace))
@@ -295,7 +469,7 @@ impl SourceConfig for DockerLogsConfig {
                 Self::NAME,
                 Some(LegacyKey::Overwrite(owned_value_path!(IMAGE))),
                 &owned_value_path!(IMAGE),
-                Kind::bytes(),
+                api_metadata(Kind::bytes()),
                 None,
             )
             .with_source_metadata(
@@ -309,7 +483,7 @@ impl SourceConfig for DockerLogsConfig {
                 Self::NAME,
                 Some(LegacyKey::Overwrite(owned_value_path!(CREATED_AT))),
                 &owned_value_path!(CREATED_AT),
-                Kind::timestamp(),
+                api_metadata(Kind::timestamp()),
                 None,
             )
             .with_source_metadata(
@@ -369,7 +543,7 @@ impl DockerLogsSourceCore {
     fn new(config: DockerLogsConfig) -> crate::Result<Self> {
         // ?NOTE: Constructs a new Docker instance for a docker host listening at url specified by an env var DOCKER_HOST.
         // ?      Otherwise connects to unix socket which requires sudo privileges, or docker group membership.
-        let docker = docker(config.docker_host.clone(), config.tls.clone())?;
+        let docker = docker(config.effective_host(), config.tls.clone())?;
 
         // Only log events created at-or-after this moment are logged.
         let now = Local::now();
@@ -1071,9 +1245,71 @@ impl ContainerLogInfo {
             true
         };
 
-        // Build the log.
+        let log = self
+            .metadata
+            .new_log(&self.id, bytes_message, stream, timestamp, log_namespace);
+        let log = merge_partial_event(
+            log,
+            is_partial,
+            partial_event_marker_field,
+            auto_partial_merge,
+            partial_event_merge_state,
+            log_namespace,
+        )?;
+
+        // Partial or not partial - we return the event we got here, because all
+        // other cases were handled earlier.
+        emit!(DockerLogsEventsReceived {
+            byte_size: log.estimated_json_encoded_size_of(),
+            container_id: self.id.as_str(),
+            container_name: &self.metadata.name_str
+        });
+
+        Some(log)
+    }
+}
+
+struct ContainerMetadata {
+    /// label.key -> String
+    labels: HashMap<String, String>,
+    /// name -> String
+    name: Value,
+    /// name
+    name_str: String,
+    /// image -> String
+    image: Option<Value>,
+    /// created_at
+    created_at: Option<DateTime<Utc>>,
+}
+
+impl ContainerMetadata {
+    fn from_details(details: ContainerInspectResponse) -> Result<Self, ParseError> {
+        let config = details.config.unwrap();
+        let name = details.name.unwrap();
+        let created = details.created.unwrap();
+
+        let labels = config.labels.unwrap_or_default();
+
+        Ok(ContainerMetadata {
+            labels,
+            name: name.as_str().trim_start_matches('/').to_owned().into(),
+            name_str: name,
+            image: Some(config.image.unwrap().into()),
+            created_at: Some(DateTime::parse_from_rfc3339(created.as_str())?.with_timezone(&Utc)),
+        })
+    }
+
+    /// Builds the event of a log message of the container.
+    fn new_log(
+        &self,
+        id: &ContainerId,
+        message: Bytes,
+        stream: Bytes,
+        timestamp: Option<DateTime<Utc>>,
+        log_namespace: LogNamespace,
+    ) -> LogEvent {
         let deserializer = BytesDeserializer;
-        let mut log = deserializer.parse_single(bytes_message, log_namespace);
+        let mut log = deserializer.parse_single(message, log_namespace);
 
         // Container ID
         log_namespace.insert_source_metadata(
@@ -1081,35 +1317,39 @@ impl ContainerLogInfo {
             &mut log,
             Some(LegacyKey::Overwrite(path!(CONTAINER))),
             path!(CONTAINER),
-            self.id.0.clone(),
+            id.0.clone(),
         );
         // Container image
-        log_namespace.insert_source_metadata(
-            DockerLogsConfig::NAME,
-            &mut log,
-            Some(LegacyKey::Overwrite(path!(IMAGE))),
-            path!(IMAGE),
-            self.metadata.image.clone(),
-        );
+        if let Some(image) = &self.image {
+            log_namespace.insert_source_metadata(
+                DockerLogsConfig::NAME,
+                &mut log,
+                Some(LegacyKey::Overwrite(path!(IMAGE))),
+                path!(IMAGE),
+                image.clone(),
+            );
+        }
         // Container name
         log_namespace.insert_source_metadata(
             DockerLogsConfig::NAME,
             &mut log,
             Some(LegacyKey::Overwrite(path!(NAME))),
             path!(NAME),
-            self.metadata.name.clone(),
+            self.name.clone(),
         );
         // Created at timestamp
-        log_namespace.insert_source_metadata(
-            DockerLogsConfig::NAME,
-            &mut log,
-            Some(LegacyKey::Overwrite(path!(CREATED_AT))),
-            path!(CREATED_AT),
-            self.metadata.created_at,
-        );
+        if let Some(created_at) = self.created_at {
+            log_namespace.insert_source_metadata(
+                DockerLogsConfig::NAME,
+                &mut log,
+                Some(LegacyKey::Overwrite(path!(CREATED_AT))),
+                path!(CREATED_AT),
+                created_at,
+            );
+        }
         // Labels
-        if !self.metadata.labels.is_empty() {
-            for (key, value) in self.metadata.labels.iter() {
+        if !self.labels.is_empty() {
+            for (key, value) in self.labels.iter() {
                 log_namespace.insert_source_metadata(
                     DockerLogsConfig::NAME,
                     &mut log,
@@ -1156,129 +1396,100 @@ impl ContainerLogInfo {
             }
         };
 
-        // If automatic partial event merging is requested - perform the
-        // merging.
-        // Otherwise mark partial events and return all the events with no
-        // merging.
-        let log = if auto_partial_merge {
-            // Partial event events merging logic.
-
-            // If event is partial, stash it and return `None`.
-            if is_partial {
-                // If we already have a partial event merge state, the current
-                // message has to be merged into that existing state.
-                // Otherwise, create a new partial event merge state with the
-                // current message being the initial one.
-                if let Some(partial_event_merge_state) = partial_event_merge_state {
-                    // Depending on the log namespace the actual contents of the log "message" will be
-                    // found in either the root of the event ("."), or at the globally configured "message_key".
-                    match log_namespace {
-                        LogNamespace::Vector => {
-                            partial_event_merge_state.merge_in_next_event(log, &["."]);
-                        }
-                        LogNamespace::Legacy => {
-                            partial_event_merge_state.merge_in_next_event(
-                                log,
-                                &[log_schema()
-                                    .message_key()
-                                    .expect("global log_schema.message_key to be valid path")
-                                    .to_string()],
-                            );
-                        }
-                    }
-                } else {
-                    *partial_event_merge_state = Some(LogEventMergeState::new(log));
-                };
-                return None;
-            };
+        log
+    }
+}
 
-            // This is not a partial event. If we have a partial event merge
-            // state from before, the current event must be a final event, that
-            // would give us a merged event we can return.
-            // Otherwise it's just a regular event that we return as-is.
-            match partial_event_merge_state.take() {
+/// Merges or marks a partial event, returning the event to send, if any.
+fn merge_partial_event(
+    mut log: LogEvent,
+    is_partial: bool,
+    partial_event_marker_field: Option<String>,
+    auto_partial_merge: bool,
+    partial_event_merge_state: &mut Option<LogEventMergeState>,
+    log_namespace: LogNamespace,
+) -> Option<LogEvent> {
+    // If automatic partial event merging is requested - perform the
+    // merging.
+    // Otherwise mark partial events and return all the events with no
+    // merging.
+    let log = if auto_partial_merge {
+        // Partial event events merging logic.
+
+        // If event is partial, stash it and return `None`.
+        if is_partial {
+            // If we already have a partial event merge state, the current
+            // message has to be merged into that existing state.
+            // Otherwise, create a new partial event merge state with the
+            // current message being the initial one.
+            if let Some(partial_event_merge_state) = partial_event_merge_state {
                 // Depending on the log namespace the actual contents of the log "message" will be
                 // found in either the root of the event ("."), or at the globally configured "message_key".
-                Some(partial_event_merge_state) => match log_namespace {
+                match log_namespace {
                     LogNamespace::Vector => {
-                        partial_event_merge_state.merge_in_final_event(log, &["."])
+                        partial_event_merge_state.merge_in_next_event(log, &["."]);
+                    }
+                    LogNamespace::Legacy => {
+                        partial_event_merge_state.merge_in_next_event(
+                            log,
+                            &[log_schema()
+                                .message_key()
+                                .expect("global log_schema.message_key to be valid path")
+                                .to_string()],
+                        );
                     }
-                    LogNamespace::Legacy => partial_event_merge_state.merge_in_final_event(
-                        log,
-                        &[log_schema()
-                            .message_key()
-                            .expect("global log_schema.message_key to be valid path")
-                            .to_string()],
-                    ),
-                },
-                None => log,
-            }
-        } else {
-            // If the event is partial, just set the partial event marker field.
-            if is_partial {
-                // Only add partial event marker field if it's requested.
-                if let Some(partial_event_marker_field) = partial_event_marker_field {
-                    log_namespace.insert_source_metadata(
-                        DockerLogsConfig::NAME,
-                        &mut log,
-                        Some(LegacyKey::Overwrite(path!(
-                            partial_event_marker_field.as_str()
-                        ))),
-                        path!(event::PARTIAL),
-                        true,
-                    );
                 }
-            }
-            // Return the log event as is, partial or not. No merging here.
-            log
+            } else {
+                *partial_event_merge_state = Some(LogEventMergeState::new(log));
+            };
+            return None;
         };
 
-        // Partial or not partial - we return the event we got here, because all
-        // other cases were handled earlier.
-        emit!(DockerLogsEventsReceived {
-            byte_size: log.estimated_json_encoded_size_of(),
-            container_id: self.id.as_str(),
-            container_name: &self.metadata.name_str
-        });
-
-        Some(log)
-    }
-}
-
-struct ContainerMetadata {
-    /// label.key -> String
-    labels: HashMap<String, String>,
-    /// name -> String
-    name: Value,
-    /// name
-    name_str: String,
-    /// image -> String
-    image: Value,
-    /// created_at
-    created_at: DateTime<Utc>,
-}
-
-impl ContainerMetadata {
-    fn from_details(details: ContainerInspectResponse) -> Result<Self, ParseError> {
-        let config = details.config.unwrap();
-        let name = details.name.unwrap();
-        let created = details.created.unwrap();
-
-        let labels = config.labels.unwrap_or_default();
+        // This is not a partial event. If we have a partial event merge
+        // state from before, the current event must be a final event, that
+        // would give us a merged event we can return.
+        // Otherwise it's just a regular event that we return as-is.
+        match partial_event_merge_state.take() {
+            // Depending on the log namespace the actual contents of the log "message" will be
+            // found in either the root of the event ("."), or at the globally configured "message_key".
+            Some(partial_event_merge_state) => match log_namespace {
+                LogNamespace::Vector => partial_event_merge_state.merge_in_final_event(log, &["."]),
+                LogNamespace::Legacy => partial_event_merge_state.merge_in_final_event(
+                    log,
+                    &[log_schema()
+                        .message_key()
+                        .expect("global log_schema.message_key to be valid path")
+                        .to_string()],
+                ),
+            },
+            None => log,
+        }
+    } else {
+        // If the event is partial, just set the partial event marker field.
+        if is_partial {
+            // Only add partial event marker field if it's requested.
+            if let Some(partial_event_marker_field) = partial_event_marker_field {
+                log_namespace.insert_source_metadata(
+                    DockerLogsConfig::NAME,
+                    &mut log,
+                    Some(LegacyKey::Overwrite(path!(
+                        partial_event_marker_field.as_str()
+                    ))),
+                    path!(event::PARTIAL),
+                    true,
+                );
+            }
+        }
+        // Return the log event as is, partial or not. No merging here.
+        log
+    };
 
-        Ok(ContainerMetadata {
-            labels,
-            name: name.as_str().trim_start_matches('/').to_owned().into(),
-            name_str: name,
-            image: config.image.unwrap().into(),
-            created_at: DateTime::parse_from_rfc3339(created.as_str())?.with_timezone(&Utc),
-        })
-    }
+    Some(log)
 }
 
 fn line_agg_adapter(
     inner: impl Stream<Item = LogEvent> + Unpin,
-    logic: line_agg::Logic<Bytes, LogEvent>,
+    logic: line_agg::Logic<(Bytes, Bytes), LogEvent>,
     log_namespace: LogNamespace,
 ) -> impl Stream<Item = LogEvent> {
     let line_agg_in = inner.map(move |mut log| {
@@ -1294,6 +1505,16 @@ fn line_agg_adapter(
                 )
                 .expect("`message` must exist in the event"),
         };
+        // The lines are aggregated per stream of each container, as the logs of all the containers
+        // are read through a single stream with the `containerd` runtime.
+        let container_value = match log_namespace {
+            LogNamespace::Vector => log
+                .get(metadata_path!(DockerLogsConfig::NAME, CONTAINER))
+                .expect("`docker_logs.container_id` must exist in the metadata"),
+            LogNamespace::Legacy => log
+                .get(event_path!(CONTAINER))
+                .expect("container_id must exist in the event"),
+        };
         let stream_value = match log_namespace {
             LogNamespace::Vector => log
                 .get(metadata_path!(DockerLogsConfig::NAME, STREAM))
@@ -1303,11 +1524,14 @@ fn line_agg_adapter(
                 .expect("stream must exist in the event"),
         };
 
-        let stream = stream_value.coerce_to_bytes();
+        let key = (
+            container_value.coerce_to_bytes(),
+            stream_value.coerce_to_bytes(),
+        );
         let message = message_value.coerce_to_bytes();
-        (stream, message, log)
+        (key, message, log)
     });
-    let line_agg_out = LineAgg::<_, Bytes, LogEvent>::new(line_agg_in, logic);
+    let line_agg_out = LineAgg::<_, (Bytes, Bytes), LogEvent>::new(line_agg_in, logic);
     line_agg_out.map(move |(_, message, mut log, _)| {
         match log_namespace {
             LogNamespace::Vector => log.insert(event_path!(), message),
@@ -23,6 +23,72 @@ fn exclude_self() {
     assert!(!source.exclude_self("a29d569bd46c"));
 }
 
+#[test]
+fn podman_host_resolution() {
+    let runtime_dir = Some(PathBuf::from("/run/user/1000"));
+
+    // `CONTAINER_HOST` takes precedence.
+    assert_eq!(
+        podman_default_host(
+            Some("unix:///tmp/podman.sock".to_owned()),
+            runtime_dir.clone(),
+            |_| true
+        ),
+        Some("unix:///tmp/podman.sock".to_owned())
+    );
+
+    // The rootless socket is used when present.
+    assert_eq!(
+        podman_default_host(None, runtime_dir.clone(), |_| true),
+        Some("unix:///run/user/1000/podman/podman.sock".to_owned())
+    );
+
+    // Otherwise fall back to the rootful socket.
+    assert_eq!(
+        podman_default_host(Some(String::new()), runtime_dir, |_| false),
+        Some("unix:///run/podman/podman.sock".to_owned())
+    );
+    assert_eq!(
+        podman_default_host(None, None, |_| true),
+        Some("unix:///run/podman/podman.sock".to_owned())
+    );
+}
+
+#[test]
+fn explicit_host_overrides_runtime_defaults() {
+    let config = DockerLogsConfig {
+        docker_host: Some("unix:///custom/podman.sock".to_owned()),
+        runtime: ContainerRuntime::Podman,
+        ..DockerLogsConfig::default()
+    };
+    assert_eq!(
+        config.effective_host(),
+        Some("unix:///custom/podman.sock".to_owned())
+    );
+
+    // Docker keeps deferring to the client defaults.
+    assert_eq!(DockerLogsConfig::default().effective_host(), None);
+}
+
+#[test]
+fn containerd_rejects_docker_api_options() {
+    let config = DockerLogsConfig {
+        runtime: ContainerRuntime::Containerd,
+        ..DockerLogsConfig::default()
+    };
+    assert!(config.check_containerd_options().is_ok());
+
+    let config = DockerLogsConfig {
+        docker_host: Some("unix:///var/run/docker.sock".to_owned()),
+        include_labels: Some(vec!["app=web".to_owned()]),
+        ..config
+    };
+    assert_eq!(
+        config.check_containerd_options().unwrap_err().to_string(),
+        "The `docker_host`, `include_labels` option(s) are not supported by the `containerd` runtime."
+    );
+}
+
 #[cfg(all(test, feature = "docker-logs-integration-tests"))]
 mod integration_tests {
     use crate::sources::docker_logs::*;
@@ -0,0 +1,330 @@
+// SPDX-License-Identifier: (Apache-2.0 OR GPL-2.0-only)
+//
+// eBPF programs of the `ebpf` source.
+//
+// The programs are compiled once with BTF relocations (CO-RE), so only the kernel structures and
+// fields declared below are needed, and are relocated against the BTF of the running kernel
+// when loaded.
+//
+// The layout of `struct event` must be kept in sync with `src/sources/ebpf/event.rs`.
+
+#include <linux/types.h>
+#include <linux/bpf.h>
+#include <linux/ptrace.h>
+
+#include <bpf/bpf_helpers.h>
+#include <bpf/bpf_core_read.h>
+#include <bpf/bpf_tracing.h>
+
+char LICENSE[] SEC("license") = "Dual Apache/GPL";
+
+#define TASK_COMM_LEN 16
+#define FILENAME_LEN 256
+#define ARGS_LEN 512
+
+#define AF_INET 2
+#define AF_INET6 10
+#define IPPROTO_TCP 6
+
+#define TCP_ESTABLISHED 1
+#define TCP_SYN_SENT 2
+#define TCP_CLOSE 7
+
+enum event_kind {
+	EVENT_PROCESS_EXEC = 1,
+	EVENT_PROCESS_EXIT = 2,
+	EVENT_TCP_CONNECT = 3,
+	EVENT_TCP_ACCEPT = 4,
+	EVENT_TCP_CLOSE = 5,
+};
+
+struct process {
+	__u32 pid;
+	__u32 ppid;
+	__u32 uid;
+	__u32 gid;
+	__u64 cgroup_id;
+	char comm[TASK_COMM_LEN];
+};
+
+struct event {
+	__u32 kind;
+	__u32 pid;
+	__u32 ppid;
+	__u32 uid;
+	__u32 gid;
+	__s32 exit_code;
+	__u64 cgroup_id;
+	char comm[TASK_COMM_LEN];
+	__u16 family;
+	__u16 local_port;
+	__u16 remote_port;
+	__u16 args_size;
+	__u8 local_address[16];
+	__u8 remote_address[16];
+	char filename[FILENAME_LEN];
+	char args[ARGS_LEN];
+};
+
+// The process owning a tracked socket, and whether its connection was established.
+struct socket {
+	struct process owner;
+	__u8 established;
+};
+
+// Minimal definitions of the kernel structures read by the programs, relocated against the BTF of
+// the running kernel.
+struct mm_struct {
+	unsigned long arg_start;
+	unsigned long arg_end;
+} __attribute__((preserve_access_index));
+
+struct task_struct {
+	int exit_code;
+	int tgid;
+	struct task_struct *real_parent;
+	struct mm_struct *mm;
+} __attribute__((preserve_access_index));
+
+struct sock_common {
+	__be32 skc_daddr;
+	__be32 skc_rcv_saddr;
+	__be16 skc_dport;
+	__u16 skc_num;
+	unsigned short skc_family;
+	struct {
+		__u8 u6_addr8[16];
+	} skc_v6_daddr;
+	struct {
+		__u8 u6_addr8[16];
+	} skc_v6_rcv_saddr;
+} __attribute__((preserve_access_index));
+
+struct sock {
+	struct sock_common __sk_common;
+} __attribute__((preserve_access_index));
+
+// Contexts of the tracepoints, as described in `/sys/kernel/tracing/events/*/*/format`.
+struct sched_process_exec_args {
+	__u64 common;
+	__u32 filename_loc;
+	int pid;
+	int old_pid;
+};
+
+struct inet_sock_set_state_args {
+	__u64 common;
+	const void *skaddr;
+	int oldstate;
+	int newstate;
+	__u16 sport;
+	__u16 dport;
+	__u16 family;
+	__u16 protocol;
+	__u8 saddr[4];
+	__u8 daddr[4];
+	__u8 saddr_v6[16];
+	__u8 daddr_v6[16];
+};
+
+struct {
+	__uint(type, BPF_MAP_TYPE_RINGBUF);
+	// Overridden with the `ring_buffer_bytes` option when loaded.
+	__uint(max_entries, 256 * 1024);
+} EVENTS SEC(".maps");
+
+// The number of events lost because the ring buffer was full.
+struct {
+	__uint(type, BPF_MAP_TYPE_PERCPU_ARRAY);
+	__uint(max_entries, 1);
+	__type(key, __u32);
+	__type(value, __u64);
+} DROPPED SEC(".maps");
+
+// The sockets of the connections opened or accepted by processes, by address of their `struct sock`.
+struct {
+	__uint(type, BPF_MAP_TYPE_LRU_HASH);
+	__uint(max_entries, 65536);
+	__type(key, __u64);
+	__type(value, struct socket);
+} SOCKETS SEC(".maps");
+
+static __always_inline void current_process(struct process *process)
+{
+	struct task_struct *task = (struct task_struct *)bpf_get_current_task();
+	__u64 uid_gid = bpf_get_current_uid_gid();
+
+	process->pid = bpf_get_current_pid_tgid() >> 32;
+	process->ppid = BPF_CORE_READ(task, real_parent, tgid);
+	process->uid = uid_gid;
+	process->gid = uid_gid >> 32;
+	process->cgroup_id = bpf_get_current_cgroup_id();
+	bpf_get_current_comm(&process->comm, sizeof(process->comm));
+}
+
+static __always_inline struct event *reserve(__u32 kind, const struct process *process)
+{
+	struct event *event = bpf_ringbuf_reserve(&EVENTS, sizeof(*event), 0);
+	if (!event) {
+		__u32 key = 0;
+		__u64 *dropped = bpf_map_lookup_elem(&DROPPED, &key);
+		if (dropped)
+			*dropped += 1;
+		return NULL;
+	}
+
+	__builtin_memset(event, 0, sizeof(*event));
+	event->kind = kind;
+	event->pid = process->pid;
+	event->ppid = process->ppid;
+	event->uid = process->uid;
+	event->gid = process->gid;
+	event->cgroup_id = process->cgroup_id;
+	__builtin_memcpy(event->comm, process->comm, sizeof(event->comm));
+	return event;
+}
+
+SEC("tracepoint/sched/sched_process_exec")
+int handle_exec(struct sched_process_exec_args *ctx)
+{
+	struct process process = {};
+	current_process(&process);
+
+	struct event *event = reserve(EVENT_PROCESS_EXEC, &process);
+	if (!event)
+		return 0;
+
+	bpf_probe_read_kernel_str(event->filename, sizeof(event->filename),
+				  (void *)ctx + (ctx->filename_loc & 0xFFFF));
+
+	struct task_struct *task = (struct task_struct *)bpf_get_current_task();
+	unsigned long arg_start = BPF_CORE_READ(task, mm, arg_start);
+	unsigned long arg_end = BPF_CORE_READ(task, mm, arg_end);
+	unsigned long size = arg_end - arg_start;
+	if (size > ARGS_LEN - 1)
+		size = ARGS_LEN - 1;
+	// The mask bounds the size for the verifier.
+	if (size > 0 && bpf_probe_read_user(event->args, size & (ARGS_LEN - 1), (void *)arg_start) == 0)
+		event->args_size = size;
+
+	bpf_ringbuf_submit(event, 0);
+	return 0;
+}
+
+SEC("tracepoint/sched/sched_process_exit")
+int handle_exit(void *ctx)
+{
+	__u64 pid_tgid = bpf_get_current_pid_tgid();
+	// Only report the exit of processes, not of each of their threads.
+	if ((__u32)pid_tgid != pid_tgid >> 32)
+		return 0;
+
+	struct process process = {};
+	current_process(&process);
+
+	struct event *event = reserve(EVENT_PROCESS_EXIT, &process);
+	if (!event)
+		return 0;
+
+	struct task_struct *task = (struct task_struct *)bpf_get_current_task();
+	event->exit_code = BPF_CORE_READ(task, exit_code) >> 8;
+
+	bpf_ringbuf_submit(event, 0);
+	return 0;
+}
+
+static __always_inline void submit_connection(__u32 kind, const struct process *owner,
+					      __u16 family, __u16 local_port, __u16 remote_port,
+					      const void *local_address, const void *remote_address)
+{
+	struct event *event = reserve(kind, owner);
+	if (!event)
+		return;
+
+	event->family = family;
+	event->local_port = local_port;
+	event->remote_port = remote_port;
+	if (family == AF_INET) {
+		__builtin_memcpy(event->local_address, local_address, 4);
+		__builtin_memcpy(event->remote_address, remote_address, 4);
+	} else {
+		__builtin_memcpy(event->local_address, local_address, 16);
+		__builtin_memcpy(event->remote_address, remote_address, 16);
+	}
+
+	bpf_ringbuf_submit(event, 0);
+}
+
+SEC("tracepoint/sock/inet_sock_set_state")
+int handle_set_state(struct inet_sock_set_state_args *ctx)
+{
+	if (ctx->protocol != IPPROTO_TCP)
+		return 0;
+	if (ctx->family != AF_INET && ctx->family != AF_INET6)
+		return 0;
+
+	__u64 key = (__u64)ctx->skaddr;
+
+	// Outgoing connections are opened in the context of the process calling `connect`, but are
+	// established in the context of whichever task receives the reply, so their owner is
+	// tracked until then.
+	if (ctx->newstate == TCP_SYN_SENT) {
+		struct socket socket = {};
+		current_process(&socket.owner);
+		bpf_map_update_elem(&SOCKETS, &key, &socket, BPF_ANY);
+		return 0;
+	}
+
+	struct socket *socket = bpf_map_lookup_elem(&SOCKETS, &key);
+	if (!socket)
+		return 0;
+
+	const void *local_address = ctx->family == AF_INET ? (void *)ctx->saddr : (void *)ctx->saddr_v6;
+	const void *remote_address = ctx->family == AF_INET ? (void *)ctx->daddr : (void *)ctx->daddr_v6;
+
+	if (ctx->oldstate == TCP_SYN_SENT && ctx->newstate == TCP_ESTABLISHED) {
+		socket->established = 1;
+		submit_connection(EVENT_TCP_CONNECT, &socket->owner, ctx->family, ctx->sport,
+				  ctx->dport, local_address, remote_address);
+	} else if (ctx->newstate == TCP_CLOSE) {
+		if (socket->established)
+			submit_connection(EVENT_TCP_CLOSE, &socket->owner, ctx->family, ctx->sport,
+					  ctx->dport, local_address, remote_address);
+		bpf_map_delete_elem(&SOCKETS, &key);
+	}
+
+	return 0;
+}
+
+SEC("kretprobe/inet_csk_accept")
+int BPF_KRETPROBE(handle_accept, struct sock *sk)
+{
+	if (!sk)
+		return 0;
+
+	__u16 family = BPF_CORE_READ(sk, __sk_common.skc_family);
+	if (family != AF_INET && family != AF_INET6)
+		return 0;
+
+	struct socket socket = { .established = 1 };
+	current_process(&socket.owner);
+
+	__u16 local_port = BPF_CORE_READ(sk, __sk_common.skc_num);
+	__u16 remote_port = bpf_ntohs(BPF_CORE_READ(sk, __sk_common.skc_dport));
+	__u8 local_address[16] = {};
+	__u8 remote_address[16] = {};
+	if (family == AF_INET) {
+		bpf_core_read(local_address, 4, &sk->__sk_common.skc_rcv_saddr);
+		bpf_core_read(remote_address, 4, &sk->__sk_common.skc_daddr);
+	} else {
+		bpf_core_read(local_address, 16, &sk->__sk_common.skc_v6_rcv_saddr);
+		bpf_core_read(remote_address, 16, &sk->__sk_common.skc_v6_daddr);
+	}
+
+	__u64 key = (__u64)sk;
+	bpf_map_update_elem(&SOCKETS, &key, &socket, BPF_ANY);
+
+	submit_connection(EVENT_TCP_ACCEPT, &socket.owner, family, local_port, remote_port,
+			  local_address, remote_address);
+	return 0;
+}
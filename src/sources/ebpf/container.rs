@@ -0,0 +1,159 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// The maximum number of cgroups whose container is cached.
+const CACHE_CAPACITY: usize = 4096;
+
+static CONTAINER_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?:^|/)(?:(docker|cri-containerd|crio|libpod)-)?([0-9a-f]{64})(?:\.scope)?$")
+        .unwrap()
+});
+
+static POD_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"pod([0-9a-f]{8}[-_][0-9a-f]{4}[-_][0-9a-f]{4}[-_][0-9a-f]{4}[-_][0-9a-f]{12})")
+        .unwrap()
+});
+
+/// The container a process runs in.
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct Container {
+    pub(super) id: String,
+    /// The container runtime, if it can be told from the cgroup.
+    pub(super) runtime: Option<&'static str>,
+    /// The UID of the Kubernetes pod of the container.
+    pub(super) pod_uid: Option<String>,
+}
+
+/// Attributes processes to containers from their cgroup, caching containers by cgroup ID.
+#[derive(Debug)]
+pub(super) struct ContainerResolver {
+    procfs_root: PathBuf,
+    cache: HashMap<u64, Option<Container>>,
+}
+
+impl ContainerResolver {
+    pub(super) fn new() -> Self {
+        let procfs_root = std::env::var_os("PROCFS_ROOT")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from("/proc"));
+        Self {
+            procfs_root,
+            cache: HashMap::new(),
+        }
+    }
+
+    /// Returns the container of a process, reading its cgroup if no other process of the same
+    /// cgroup was seen before.
+    ///
+    /// Processes that already exited when their first event is handled cannot be attributed.
+    pub(super) fn resolve(&mut self, cgroup_id: u64, pid: u32) -> Option<&Container> {
+        if !self.cache.contains_key(&cgroup_id) {
+            let path = self.procfs_root.join(pid.to_string()).join("cgroup");
+            let cgroups = std::fs::read_to_string(path).ok()?;
+            if self.cache.len() >= CACHE_CAPACITY {
+                self.cache.clear();
+            }
+            self.cache.insert(cgroup_id, parse_cgroups(&cgroups));
+        }
+        self.cache.get(&cgroup_id)?.as_ref()
+    }
+}
+
+/// Finds the container in the content of a `/proc/<pid>/cgroup` file.
+fn parse_cgroups(cgroups: &str) -> Option<Container> {
+    cgroups
+        .lines()
+        .filter_map(|line| line.splitn(3, ':').nth(2))
+        .find_map(parse_cgroup_path)
+}
+
+/// Finds the container in a cgroup path, as laid out by Docker, containerd, CRI-O, Podman, and
+/// the kubelet, with either the `cgroupfs` or the `systemd` cgroup driver.
+fn parse_cgroup_path(path: &str) -> Option<Container> {
+    let captures = CONTAINER_REGEX.captures(path)?;
+    let runtime = match captures.get(1).map(|prefix| prefix.as_str()) {
+        Some("docker") => Some("docker"),
+        Some("cri-containerd") => Some("containerd"),
+        Some("crio") => Some("cri-o"),
+        Some("libpod") => Some("podman"),
+        _ if path.starts_with("/docker/") => Some("docker"),
+        _ => None,
+    };
+    let pod_uid = POD_REGEX
+        .captures(path)
+        .map(|captures| captures[1].replace('_', "-"));
+
+    Some(Container {
+        id: captures[2].to_string(),
+        runtime,
+        pod_uid,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ID: &str = "3f4d0a9e8b7c6d5e4f3a2b1c0d9e8f7a6b5c4d3e2f1a0b9c8d7e6f5a4b3c2d1e";
+
+    #[test]
+    fn parses_docker_cgroups() {
+        let expected = Some(Container {
+            id: ID.to_string(),
+            runtime: Some("docker"),
+            pod_uid: None,
+        });
+        assert_eq!(
+            parse_cgroup_path(&format!("/system.slice/docker-{}.scope", ID)),
+            expected
+        );
+        assert_eq!(parse_cgroup_path(&format!("/docker/{}", ID)), expected);
+    }
+
+    #[test]
+    fn parses_kubernetes_cgroups() {
+        let systemd = format!(
+            "/kubepods.slice/kubepods-burstable.slice/kubepods-burstable-pod6a1c4d2e_9b8f_4c7d_a6e5_f4d3c2b1a0e9.slice/cri-containerd-{}.scope",
+            ID
+        );
+        assert_eq!(
+            parse_cgroup_path(&systemd),
+            Some(Container {
+                id: ID.to_string(),
+                runtime: Some("containerd"),
+                pod_uid: Some("6a1c4d2e-9b8f-4c7d-a6e5-f4d3c2b1a0e9".to_string()),
+            })
+        );
+
+        let cgroupfs = format!(
+            "/kubepods/besteffort/pod6a1c4d2e-9b8f-4c7d-a6e5-f4d3c2b1a0e9/{}",
+            ID
+        );
+        assert_eq!(
+            parse_cgroup_path(&cgroupfs),
+            Some(Container {
+                id: ID.to_string(),
+                runtime: None,
+                pod_uid: Some("6a1c4d2e-9b8f-4c7d-a6e5-f4d3c2b1a0e9".to_string()),
+            })
+        );
+    }
+
+    #[test]
+    fn finds_container_in_any_hierarchy() {
+        let cgroups = format!(
+            "12:cpuset:/\n11:memory:/machine.slice/libpod-{}.scope\n0::/user.slice\n",
+            ID
+        );
+        assert_eq!(
+            parse_cgroups(&cgroups).map(|container| container.runtime),
+            Some(Some("podman"))
+        );
+        assert_eq!(
+            parse_cgroups("0::/user.slice/user-1000.slice/session-2.scope\n"),
+            None
+        );
+    }
+}
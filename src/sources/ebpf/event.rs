@@ -0,0 +1,266 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use vector_lib::configurable::configurable_component;
+
+/// The size of the events submitted by the eBPF programs.
+///
+/// Must be kept in sync with `struct event` in `bpf/telemetry.bpf.c`.
+pub(super) const EVENT_SIZE: usize = 856;
+
+const COMM_LEN: usize = 16;
+const FILENAME_LEN: usize = 256;
+const ARGS_LEN: usize = 512;
+
+const AF_INET: u16 = 2;
+const AF_INET6: u16 = 10;
+
+/// A kind of event collected by the source.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum EventKind {
+    /// A process executed a new program.
+    ProcessExec,
+
+    /// A process exited.
+    ProcessExit,
+
+    /// A process opened an outgoing TCP connection.
+    TcpConnect,
+
+    /// A process accepted an incoming TCP connection.
+    TcpAccept,
+
+    /// A TCP connection opened or accepted by a process was closed.
+    TcpClose,
+}
+
+impl EventKind {
+    pub(super) const fn as_str(self) -> &'static str {
+        match self {
+            Self::ProcessExec => "process_exec",
+            Self::ProcessExit => "process_exit",
+            Self::TcpConnect => "tcp_connect",
+            Self::TcpAccept => "tcp_accept",
+            Self::TcpClose => "tcp_close",
+        }
+    }
+
+    pub(super) const fn is_connection(self) -> bool {
+        matches!(self, Self::TcpConnect | Self::TcpAccept | Self::TcpClose)
+    }
+
+    const fn from_raw(kind: u32) -> Option<Self> {
+        match kind {
+            1 => Some(Self::ProcessExec),
+            2 => Some(Self::ProcessExit),
+            3 => Some(Self::TcpConnect),
+            4 => Some(Self::TcpAccept),
+            5 => Some(Self::TcpClose),
+            _ => None,
+        }
+    }
+}
+
+/// The process an event originates from.
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct Process {
+    pub(super) pid: u32,
+    pub(super) ppid: u32,
+    pub(super) uid: u32,
+    pub(super) gid: u32,
+    pub(super) comm: String,
+}
+
+/// The endpoints of a TCP connection.
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct Connection {
+    pub(super) local_address: IpAddr,
+    pub(super) local_port: u16,
+    pub(super) remote_address: IpAddr,
+    pub(super) remote_port: u16,
+}
+
+/// An event submitted by the eBPF programs.
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct RawEvent {
+    pub(super) kind: EventKind,
+    pub(super) process: Process,
+    pub(super) cgroup_id: u64,
+    /// The exit code of the process, for `process_exit` events.
+    pub(super) exit_code: Option<i32>,
+    /// The path of the executed program, for `process_exec` events.
+    pub(super) filename: Option<String>,
+    /// The arguments of the executed program, for `process_exec` events.
+    pub(super) args: Vec<String>,
+    /// The endpoints of the connection, for TCP events.
+    pub(super) connection: Option<Connection>,
+}
+
+impl RawEvent {
+    /// Parses an event submitted by the eBPF programs, returning `None` if it is malformed.
+    pub(super) fn parse(data: &[u8]) -> Option<Self> {
+        if data.len() < EVENT_SIZE {
+            return None;
+        }
+        let u16_at = |offset: usize| u16::from_ne_bytes([data[offset], data[offset + 1]]);
+        let u32_at = |offset: usize| {
+            u32::from_ne_bytes(data[offset..offset + 4].try_into().expect("4 bytes"))
+        };
+
+        let kind = EventKind::from_raw(u32_at(0))?;
+        let process = Process {
+            pid: u32_at(4),
+            ppid: u32_at(8),
+            uid: u32_at(12),
+            gid: u32_at(16),
+            comm: c_string(&data[32..32 + COMM_LEN]),
+        };
+        let cgroup_id = u64::from_ne_bytes(data[24..32].try_into().expect("8 bytes"));
+
+        let mut event = Self {
+            kind,
+            process,
+            cgroup_id,
+            exit_code: None,
+            filename: None,
+            args: Vec::new(),
+            connection: None,
+        };
+        match kind {
+            EventKind::ProcessExec => {
+                event.filename = Some(c_string(&data[88..88 + FILENAME_LEN]));
+                let args_size = usize::from(u16_at(54)).min(ARGS_LEN);
+                event.args = data[344..344 + args_size]
+                    .split(|byte| *byte == 0)
+                    .filter(|arg| !arg.is_empty())
+                    .map(|arg| String::from_utf8_lossy(arg).into_owned())
+                    .collect();
+            }
+            EventKind::ProcessExit => event.exit_code = Some(u32_at(20) as i32),
+            EventKind::TcpConnect | EventKind::TcpAccept | EventKind::TcpClose => {
+                let address = |offset: usize| -> Option<IpAddr> {
+                    match u16_at(48) {
+                        AF_INET => {
+                            let octets: [u8; 4] = data[offset..offset + 4].try_into().ok()?;
+                            Some(Ipv4Addr::from(octets).into())
+                        }
+                        AF_INET6 => {
+                            let octets: [u8; 16] = data[offset..offset + 16].try_into().ok()?;
+                            Some(Ipv6Addr::from(octets).into())
+                        }
+                        _ => None,
+                    }
+                };
+                event.connection = Some(Connection {
+                    local_address: address(56)?,
+                    local_port: u16_at(50),
+                    remote_address: address(72)?,
+                    remote_port: u16_at(52),
+                });
+            }
+        }
+        Some(event)
+    }
+}
+
+/// Reads a NUL terminated string.
+fn c_string(bytes: &[u8]) -> String {
+    let end = bytes
+        .iter()
+        .position(|byte| *byte == 0)
+        .unwrap_or(bytes.len());
+    String::from_utf8_lossy(&bytes[..end]).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds an event the way the eBPF programs lay it out.
+    fn raw_event(kind: u32) -> Vec<u8> {
+        let mut data = vec![0; EVENT_SIZE];
+        data[0..4].copy_from_slice(&kind.to_ne_bytes());
+        data[4..8].copy_from_slice(&4242u32.to_ne_bytes());
+        data[8..12].copy_from_slice(&1u32.to_ne_bytes());
+        data[12..16].copy_from_slice(&1000u32.to_ne_bytes());
+        data[16..20].copy_from_slice(&100u32.to_ne_bytes());
+        data[24..32].copy_from_slice(&7331u64.to_ne_bytes());
+        data[32..36].copy_from_slice(b"curl");
+        data
+    }
+
+    #[test]
+    fn parses_process_exec() {
+        let mut data = raw_event(1);
+        data[88..101].copy_from_slice(b"/usr/bin/curl");
+        let args = b"curl\0-s\0example.com\0";
+        data[54..56].copy_from_slice(&(args.len() as u16).to_ne_bytes());
+        data[344..344 + args.len()].copy_from_slice(args);
+
+        let event = RawEvent::parse(&data).unwrap();
+        assert_eq!(event.kind, EventKind::ProcessExec);
+        assert_eq!(
+            event.process,
+            Process {
+                pid: 4242,
+                ppid: 1,
+                uid: 1000,
+                gid: 100,
+                comm: "curl".to_string(),
+            }
+        );
+        assert_eq!(event.cgroup_id, 7331);
+        assert_eq!(event.filename.as_deref(), Some("/usr/bin/curl"));
+        assert_eq!(event.args, vec!["curl", "-s", "example.com"]);
+        assert_eq!(event.connection, None);
+    }
+
+    #[test]
+    fn parses_process_exit() {
+        let mut data = raw_event(2);
+        data[20..24].copy_from_slice(&3i32.to_ne_bytes());
+
+        let event = RawEvent::parse(&data).unwrap();
+        assert_eq!(event.kind, EventKind::ProcessExit);
+        assert_eq!(event.exit_code, Some(3));
+    }
+
+    #[test]
+    fn parses_tcp_connections() {
+        let mut data = raw_event(3);
+        data[48..50].copy_from_slice(&AF_INET.to_ne_bytes());
+        data[50..52].copy_from_slice(&40000u16.to_ne_bytes());
+        data[52..54].copy_from_slice(&443u16.to_ne_bytes());
+        data[56..60].copy_from_slice(&[10, 0, 0, 1]);
+        data[72..76].copy_from_slice(&[93, 184, 216, 34]);
+
+        let event = RawEvent::parse(&data).unwrap();
+        assert_eq!(event.kind, EventKind::TcpConnect);
+        assert_eq!(
+            event.connection,
+            Some(Connection {
+                local_address: Ipv4Addr::new(10, 0, 0, 1).into(),
+                local_port: 40000,
+                remote_address: Ipv4Addr::new(93, 184, 216, 34).into(),
+                remote_port: 443,
+            })
+        );
+
+        let mut data = raw_event(4);
+        data[48..50].copy_from_slice(&AF_INET6.to_ne_bytes());
+        data[71] = 1;
+        data[87] = 1;
+        let connection = RawEvent::parse(&data).unwrap().connection.unwrap();
+        assert_eq!(connection.local_address, IpAddr::from(Ipv6Addr::LOCALHOST));
+        assert_eq!(connection.remote_address, IpAddr::from(Ipv6Addr::LOCALHOST));
+    }
+
+    #[test]
+    fn rejects_malformed_events() {
+        assert_eq!(RawEvent::parse(&[0; 16]), None);
+        assert_eq!(RawEvent::parse(&raw_event(42)), None);
+        // Unknown address family.
+        assert_eq!(RawEvent::parse(&raw_event(5)), None);
+    }
+}
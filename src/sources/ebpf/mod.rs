@@ -0,0 +1,503 @@
+use bytes::Bytes;
+use chrono::Utc;
+use snafu::Snafu;
+use vector_lib::configurable::configurable_component;
+use vector_lib::lookup::{event_path, metadata_path, owned_value_path, path};
+use vector_lib::{
+    config::{LegacyKey, LogNamespace},
+    schema::Definition,
+};
+use vrl::value::{Kind, Value};
+
+use crate::{
+    config::{log_schema, DataType, SourceConfig, SourceContext, SourceOutput},
+    event::LogEvent,
+};
+
+mod container;
+mod event;
+#[cfg(target_os = "linux")]
+mod programs;
+
+use self::container::Container;
+pub use self::event::EventKind;
+use self::event::RawEvent;
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("`events` must not be empty"))]
+    NoEvents,
+    #[snafu(display(
+        "`ring_buffer_bytes` must be a power of two and a multiple of the page size, got {}",
+        size
+    ))]
+    InvalidRingBufferSize { size: u32 },
+    #[cfg(not(target_os = "linux"))]
+    #[snafu(display("The `ebpf` source is only supported on Linux"))]
+    UnsupportedPlatform,
+}
+
+/// Configuration for the `ebpf` source.
+#[configurable_component(source(
+    "ebpf",
+    "Collect process and network events from the Linux kernel with eBPF."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct EbpfConfig {
+    /// The kinds of events to collect.
+    ///
+    /// Only the eBPF programs needed to collect these events are attached.
+    #[serde(default = "default_events")]
+    #[configurable(metadata(docs::examples = "process_exec"))]
+    #[configurable(metadata(docs::examples = "tcp_connect"))]
+    events: Vec<EventKind>,
+
+    /// Whether to attribute events to the container the process runs in.
+    ///
+    /// The container is found from the cgroup of the process, read from `/proc`, or from the
+    /// directory set in the `PROCFS_ROOT` environment variable. Events of processes that exited
+    /// before the first event of their cgroup was handled are not attributed.
+    #[serde(default = "crate::serde::default_true")]
+    container_attribution: bool,
+
+    /// The size of the ring buffer the kernel submits events through, in bytes.
+    ///
+    /// Must be a power of two and a multiple of the page size. Events submitted while the ring
+    /// buffer is full are lost, and reported with the `component_discarded_events_total` metric.
+    #[serde(default = "default_ring_buffer_bytes")]
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    ring_buffer_bytes: u32,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[configurable(metadata(docs::hidden))]
+    #[serde(default)]
+    log_namespace: Option<bool>,
+}
+
+fn default_events() -> Vec<EventKind> {
+    vec![
+        EventKind::ProcessExec,
+        EventKind::ProcessExit,
+        EventKind::TcpConnect,
+        EventKind::TcpAccept,
+        EventKind::TcpClose,
+    ]
+}
+
+const fn default_ring_buffer_bytes() -> u32 {
+    256 * 1024
+}
+
+impl Default for EbpfConfig {
+    fn default() -> Self {
+        Self {
+            events: default_events(),
+            container_attribution: true,
+            ring_buffer_bytes: default_ring_buffer_bytes(),
+            log_namespace: None,
+        }
+    }
+}
+
+impl_generate_config_from_default!(EbpfConfig);
+
+impl EbpfConfig {
+    fn validate(&self) -> crate::Result<()> {
+        if self.events.is_empty() {
+            return Err(BuildError::NoEvents.into());
+        }
+        // Page sizes are powers of two of at least 4 KiB.
+        let size = self.ring_buffer_bytes;
+        if !size.is_power_of_two() || size < 4096 {
+            return Err(BuildError::InvalidRingBufferSize { size }.into());
+        }
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "ebpf")]
+impl SourceConfig for EbpfConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        self.validate()?;
+
+        #[cfg(target_os = "linux")]
+        {
+            linux::build(self, cx)
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            let _ = cx;
+            Err(BuildError::UnsupportedPlatform.into())
+        }
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
+        let log_namespace = global_log_namespace.merge(self.log_namespace);
+
+        let schema_definition = match log_namespace {
+            LogNamespace::Legacy => {
+                Definition::empty_legacy_namespace().unknown_fields(Kind::any())
+            }
+            LogNamespace::Vector => {
+                Definition::new_with_default_metadata(Kind::any_object(), [log_namespace])
+            }
+        }
+        .with_source_metadata(
+            Self::NAME,
+            log_schema()
+                .host_key()
+                .cloned()
+                .map(LegacyKey::InsertIfEmpty),
+            &owned_value_path!("host"),
+            Kind::bytes(),
+            Some("host"),
+        )
+        .with_source_metadata(
+            Self::NAME,
+            None,
+            &owned_value_path!("timestamp"),
+            Kind::timestamp(),
+            Some("timestamp"),
+        )
+        .with_standard_vector_source_metadata();
+
+        vec![SourceOutput::new_logs(DataType::Log, schema_definition)]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use std::{collections::HashSet, time::Duration};
+
+    use tokio::{io::unix::AsyncFd, time};
+    use vector_lib::{
+        internal_event::{CountByteSize, InternalEventHandle as _},
+        EstimatedJsonEncodedSizeOf,
+    };
+
+    use super::{container::ContainerResolver, programs::Programs, *};
+    use crate::{
+        internal_events::{
+            EbpfEventsLost, EbpfMalformedEvent, EbpfReadError, EventsReceived, StreamClosedError,
+        },
+        sources::Source,
+    };
+
+    /// The interval at which the number of lost events is read.
+    const LOST_EVENTS_INTERVAL: Duration = Duration::from_secs(10);
+
+    pub(super) fn build(config: &EbpfConfig, cx: SourceContext) -> crate::Result<Source> {
+        let kinds = config.events.iter().copied().collect::<HashSet<_>>();
+        let (programs, events) = Programs::load(&kinds, config.ring_buffer_bytes)?;
+        let hostname = crate::get_hostname().ok();
+        let mut containers = config.container_attribution.then(ContainerResolver::new);
+        let log_namespace = cx.log_namespace(config.log_namespace);
+        let mut out = cx.out;
+        let mut shutdown = cx.shutdown;
+
+        Ok(Box::pin(async move {
+            let events_received = register!(EventsReceived);
+            let mut lost_events_interval = time::interval(LOST_EVENTS_INTERVAL);
+            let mut lost = 0;
+            let mut ring_buffer = match AsyncFd::new(events) {
+                Ok(ring_buffer) => ring_buffer,
+                Err(error) => {
+                    emit!(EbpfReadError {
+                        error,
+                        map: "EVENTS"
+                    });
+                    return Err(());
+                }
+            };
+
+            loop {
+                tokio::select! {
+                    _ = &mut shutdown => break,
+                    guard = ring_buffer.readable_mut() => {
+                        let mut guard = match guard {
+                            Ok(guard) => guard,
+                            Err(error) => {
+                                emit!(EbpfReadError { error, map: "EVENTS" });
+                                return Err(());
+                            }
+                        };
+                        let mut logs = Vec::new();
+                        while let Some(item) = guard.get_inner_mut().next() {
+                            let Some(event) = RawEvent::parse(&item) else {
+                                emit!(EbpfMalformedEvent { size: item.len() });
+                                continue;
+                            };
+                            if !kinds.contains(&event.kind) {
+                                continue;
+                            }
+                            let container = containers
+                                .as_mut()
+                                .and_then(|containers| {
+                                    containers.resolve(event.cgroup_id, event.process.pid)
+                                })
+                                .cloned();
+                            logs.push(to_log(
+                                event,
+                                container.as_ref(),
+                                hostname.as_deref(),
+                                log_namespace,
+                            ));
+                        }
+                        guard.clear_ready();
+                        drop(guard);
+
+                        if logs.is_empty() {
+                            continue;
+                        }
+                        let count = logs.len();
+                        events_received.emit(CountByteSize(
+                            count,
+                            logs.estimated_json_encoded_size_of(),
+                        ));
+                        if out.send_batch(logs).await.is_err() {
+                            emit!(StreamClosedError { count });
+                            return Err(());
+                        }
+                    }
+                    _ = lost_events_interval.tick() => {
+                        match programs.dropped() {
+                            Ok(total) => {
+                                if total > lost {
+                                    emit!(EbpfEventsLost { count: total - lost });
+                                }
+                                lost = total;
+                            }
+                            Err(error) => emit!(EbpfReadError { error, map: "DROPPED" }),
+                        }
+                    }
+                }
+            }
+
+            Ok(())
+        }))
+    }
+}
+
+/// Converts an event of the eBPF programs into a log event.
+fn to_log(
+    event: RawEvent,
+    container: Option<&Container>,
+    hostname: Option<&str>,
+    log_namespace: LogNamespace,
+) -> LogEvent {
+    let mut log = LogEvent::default();
+    log.insert(event_path!("event"), event.kind.as_str());
+
+    let process = event.process;
+    log.insert(event_path!("process", "pid"), process.pid);
+    log.insert(event_path!("process", "ppid"), process.ppid);
+    log.insert(event_path!("process", "uid"), process.uid);
+    log.insert(event_path!("process", "gid"), process.gid);
+    log.insert(event_path!("process", "name"), process.comm);
+    if let Some(filename) = event.filename {
+        log.insert(event_path!("process", "executable"), filename);
+    }
+    if event.kind == EventKind::ProcessExec {
+        let args = event.args.into_iter().map(Value::from).collect::<Vec<_>>();
+        log.insert(event_path!("process", "args"), args);
+    }
+    if let Some(exit_code) = event.exit_code {
+        log.insert(event_path!("process", "exit_code"), exit_code);
+    }
+
+    if let Some(connection) = event.connection {
+        log.insert(
+            event_path!("connection", "local_address"),
+            connection.local_address.to_string(),
+        );
+        log.insert(
+            event_path!("connection", "local_port"),
+            connection.local_port,
+        );
+        log.insert(
+            event_path!("connection", "remote_address"),
+            connection.remote_address.to_string(),
+        );
+        log.insert(
+            event_path!("connection", "remote_port"),
+            connection.remote_port,
+        );
+    }
+
+    log.insert(event_path!("cgroup_id"), event.cgroup_id);
+    if let Some(container) = container {
+        log.insert(event_path!("container", "id"), container.id.clone());
+        if let Some(runtime) = container.runtime {
+            log.insert(event_path!("container", "runtime"), runtime);
+        }
+        if let Some(pod_uid) = &container.pod_uid {
+            log.insert(event_path!("kubernetes", "pod_uid"), pod_uid.clone());
+        }
+    }
+
+    log_namespace.insert_vector_metadata(
+        &mut log,
+        log_schema().source_type_key(),
+        path!("source_type"),
+        Bytes::from_static(EbpfConfig::NAME.as_bytes()),
+    );
+    if let Some(hostname) = hostname {
+        log_namespace.insert_source_metadata(
+            EbpfConfig::NAME,
+            &mut log,
+            log_schema().host_key().map(LegacyKey::InsertIfEmpty),
+            path!("host"),
+            hostname.to_owned(),
+        );
+    }
+    match log_namespace {
+        LogNamespace::Vector => {
+            log.insert(metadata_path!(EbpfConfig::NAME, "timestamp"), Utc::now());
+            log.insert(metadata_path!("vector", "ingest_timestamp"), Utc::now());
+        }
+        LogNamespace::Legacy => {
+            log_namespace.insert_source_metadata(
+                EbpfConfig::NAME,
+                &mut log,
+                log_schema().timestamp_key().map(LegacyKey::Overwrite),
+                path!("timestamp"),
+                Utc::now(),
+            );
+        }
+    }
+    log
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+
+    use vrl::value;
+
+    use super::{
+        event::{Connection, Process},
+        *,
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<EbpfConfig>();
+    }
+
+    #[test]
+    fn validates_config() {
+        let config = |events, ring_buffer_bytes| EbpfConfig {
+            events,
+            ring_buffer_bytes,
+            ..Default::default()
+        };
+        assert!(config(default_events(), 256 * 1024).validate().is_ok());
+        assert!(config(Vec::new(), 256 * 1024).validate().is_err());
+        assert!(config(default_events(), 100_000).validate().is_err());
+        assert!(config(default_events(), 1024).validate().is_err());
+    }
+
+    fn raw_event(kind: EventKind) -> RawEvent {
+        RawEvent {
+            kind,
+            process: Process {
+                pid: 4242,
+                ppid: 1,
+                uid: 1000,
+                gid: 100,
+                comm: "curl".to_string(),
+            },
+            cgroup_id: 7331,
+            exit_code: None,
+            filename: None,
+            args: Vec::new(),
+            connection: None,
+        }
+    }
+
+    #[test]
+    fn converts_process_events() {
+        let mut event = raw_event(EventKind::ProcessExec);
+        event.filename = Some("/usr/bin/curl".to_string());
+        event.args = vec!["curl".to_string(), "example.com".to_string()];
+        let container = Container {
+            id: "abc".to_string(),
+            runtime: Some("containerd"),
+            pod_uid: Some("6a1c4d2e-9b8f-4c7d-a6e5-f4d3c2b1a0e9".to_string()),
+        };
+
+        let log = to_log(
+            event,
+            Some(&container),
+            Some("node-1"),
+            LogNamespace::Vector,
+        );
+        assert_eq!(
+            *log.value(),
+            value!({
+                event: "process_exec",
+                process: {
+                    pid: 4242,
+                    ppid: 1,
+                    uid: 1000,
+                    gid: 100,
+                    name: "curl",
+                    executable: "/usr/bin/curl",
+                    args: ["curl", "example.com"],
+                },
+                cgroup_id: 7331,
+                container: {
+                    id: "abc",
+                    runtime: "containerd",
+                },
+                kubernetes: {
+                    pod_uid: "6a1c4d2e-9b8f-4c7d-a6e5-f4d3c2b1a0e9",
+                },
+            })
+        );
+        assert_eq!(
+            log.get(metadata_path!(EbpfConfig::NAME, "host")),
+            Some(&value!("node-1"))
+        );
+
+        let mut event = raw_event(EventKind::ProcessExit);
+        event.exit_code = Some(1);
+        let log = to_log(event, None, None, LogNamespace::Legacy);
+        assert_eq!(log.get("event"), Some(&value!("process_exit")));
+        assert_eq!(log.get("process.exit_code"), Some(&value!(1)));
+        assert!(log.get("process.args").is_none());
+        assert!(log.get("container").is_none());
+        assert!(log
+            .get(log_schema().timestamp_key_target_path().unwrap())
+            .is_some());
+    }
+
+    #[test]
+    fn converts_connection_events() {
+        let mut event = raw_event(EventKind::TcpAccept);
+        event.connection = Some(Connection {
+            local_address: Ipv4Addr::new(10, 0, 0, 1).into(),
+            local_port: 443,
+            remote_address: Ipv4Addr::new(10, 0, 0, 2).into(),
+            remote_port: 40000,
+        });
+
+        let log = to_log(event, None, None, LogNamespace::Legacy);
+        assert_eq!(log.get("event"), Some(&value!("tcp_accept")));
+        assert_eq!(
+            log.get("connection"),
+            Some(&value!({
+                local_address: "10.0.0.1",
+                local_port: 443,
+                remote_address: "10.0.0.2",
+                remote_port: 40000,
+            }))
+        );
+    }
+}
@@ -0,0 +1,113 @@
+use std::collections::HashSet;
+
+use aya::{
+    include_bytes_aligned,
+    maps::{Map, MapData, MapError, PerCpuArray, RingBuf},
+    programs::{KProbe, ProgramError, TracePoint},
+    Bpf, BpfError, BpfLoader,
+};
+use snafu::{ResultExt, Snafu};
+
+use super::event::EventKind;
+
+/// The eBPF programs, compiled by the build script from `bpf/telemetry.bpf.c`.
+static OBJECT: &[u8] = include_bytes_aligned!(concat!(env!("OUT_DIR"), "/telemetry.bpf.o"));
+
+#[derive(Debug, Snafu)]
+pub(super) enum LoadError {
+    #[snafu(display("Failed to load the eBPF programs: {}", source))]
+    Load { source: BpfError },
+    #[snafu(display("Failed to attach the `{}` eBPF program: {}", program, source))]
+    Attach {
+        program: &'static str,
+        source: ProgramError,
+    },
+    #[snafu(display("Failed to open the `{}` eBPF map: {}", map, source))]
+    OpenMap { map: &'static str, source: MapError },
+}
+
+/// The loaded eBPF programs, which stay attached until dropped.
+pub(super) struct Programs {
+    dropped: PerCpuArray<MapData, u64>,
+    // Owns the attached programs.
+    _bpf: Bpf,
+}
+
+impl Programs {
+    /// Loads the eBPF programs, and attaches those needed to collect the given kinds of events.
+    ///
+    /// Returns the programs along with the ring buffer they submit events through.
+    ///
+    /// This requires the `CAP_BPF` and `CAP_PERFMON` capabilities, or `CAP_SYS_ADMIN` on kernels
+    /// older than 5.8.
+    pub(super) fn load(
+        kinds: &HashSet<EventKind>,
+        ring_buffer_bytes: u32,
+    ) -> Result<(Self, RingBuf<MapData>), LoadError> {
+        let mut bpf = BpfLoader::new()
+            .set_max_entries("EVENTS", ring_buffer_bytes)
+            .load(OBJECT)
+            .context(LoadSnafu)?;
+
+        if kinds.contains(&EventKind::ProcessExec) {
+            attach_tracepoint(&mut bpf, "handle_exec", "sched", "sched_process_exec")?;
+        }
+        if kinds.contains(&EventKind::ProcessExit) {
+            attach_tracepoint(&mut bpf, "handle_exit", "sched", "sched_process_exit")?;
+        }
+        if kinds.iter().any(|kind| kind.is_connection()) {
+            // Also needed for accepted connections, to report when they are closed.
+            attach_tracepoint(&mut bpf, "handle_set_state", "sock", "inet_sock_set_state")?;
+        }
+        if kinds.contains(&EventKind::TcpAccept) || kinds.contains(&EventKind::TcpClose) {
+            attach_kretprobe(&mut bpf, "handle_accept", "inet_csk_accept")?;
+        }
+
+        let events = RingBuf::try_from(take_map(&mut bpf, "EVENTS"))
+            .context(OpenMapSnafu { map: "EVENTS" })?;
+        let dropped = PerCpuArray::try_from(take_map(&mut bpf, "DROPPED"))
+            .context(OpenMapSnafu { map: "DROPPED" })?;
+
+        Ok((Self { dropped, _bpf: bpf }, events))
+    }
+
+    /// The total number of events lost because the ring buffer was full.
+    pub(super) fn dropped(&self) -> Result<u64, MapError> {
+        Ok(self.dropped.get(&0, 0)?.iter().sum())
+    }
+}
+
+fn attach_tracepoint(
+    bpf: &mut Bpf,
+    program: &'static str,
+    category: &str,
+    name: &str,
+) -> Result<(), LoadError> {
+    let tracepoint: &mut TracePoint = bpf
+        .program_mut(program)
+        .expect("the object contains the program")
+        .try_into()
+        .context(AttachSnafu { program })?;
+    tracepoint.load().context(AttachSnafu { program })?;
+    tracepoint
+        .attach(category, name)
+        .context(AttachSnafu { program })?;
+    Ok(())
+}
+
+fn attach_kretprobe(bpf: &mut Bpf, program: &'static str, function: &str) -> Result<(), LoadError> {
+    let kretprobe: &mut KProbe = bpf
+        .program_mut(program)
+        .expect("the object contains the program")
+        .try_into()
+        .context(AttachSnafu { program })?;
+    kretprobe.load().context(AttachSnafu { program })?;
+    kretprobe
+        .attach(function, 0)
+        .context(AttachSnafu { program })?;
+    Ok(())
+}
+
+fn take_map(bpf: &mut Bpf, map: &str) -> Map {
+    bpf.take_map(map).expect("the object contains the map")
+}
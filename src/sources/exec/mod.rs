@@ -30,13 +30,15 @@ use vrl::value::Kind;
 use crate::{
     codecs::{Decoder, DecodingConfig},
     config::{SourceConfig, SourceContext, SourceOutput},
-    event::Event,
+    event::{Event, LogEvent},
     internal_events::{
         ExecChannelClosedError, ExecCommandExecuted, ExecEventsReceived, ExecFailedError,
-        ExecFailedToSignalChild, ExecFailedToSignalChildError, ExecTimeoutError, StreamClosedError,
+        ExecFailedToSignalChild, ExecFailedToSignalChildError, ExecOutputLimitExceededError,
+        ExecRuntimeLimitExceededError, ExecTimeoutError, StreamClosedError, TemplateRenderingError,
     },
     serde::default_decoding,
     shutdown::ShutdownSignal,
+    template::Template,
     SourceSender,
 };
 use vector_lib::config::{log_schema, LogNamespace};
@@ -70,6 +72,21 @@ pub struct ExecConfig {
     #[configurable(metadata(docs::examples = "environment_examples()"))]
     pub environment: Option<HashMap<String, String>>,
 
+    /// Custom environment variables to set or update when running the command, rendered from
+    /// templates each time the command is started.
+    ///
+    /// [`strftime` specifiers][strftime] are replaced with the time the command is started at, and
+    /// the `{{ host }}` field with the hostname. These variables take precedence over the ones set
+    /// in `environment`.
+    ///
+    /// [strftime]: https://docs.rs/chrono/latest/chrono/format/strftime/index.html#specifiers
+    #[serde(default)]
+    #[configurable(metadata(
+        docs::additional_props_description = "An environment variable template."
+    ))]
+    #[configurable(metadata(docs::examples = "environment_template_examples()"))]
+    pub environment_templates: Option<HashMap<String, Template>>,
+
     /// Whether or not to clear the environment before setting custom environment variables.
     #[serde(default = "default_clear_environment")]
     pub clear_environment: bool,
@@ -81,10 +98,31 @@ pub struct ExecConfig {
     #[serde(default = "default_include_stderr")]
     pub include_stderr: bool,
 
+    /// Whether or not to send the events generated from stderr to the separate `stderr` output.
+    ///
+    /// If disabled, they are sent to the default output, along with the events generated from
+    /// stdout. Only applies if `include_stderr` is enabled.
+    #[serde(default)]
+    pub separate_stderr_output: bool,
+
     /// The maximum buffer size allowed before a log event is generated.
     #[serde(default = "default_maximum_buffer_size")]
     pub maximum_buffer_size_bytes: usize,
 
+    /// The maximum amount of time, in seconds, a run of the command is allowed to take.
+    ///
+    /// If the command is still running after this amount of time, it is killed.
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    #[configurable(metadata(docs::human_name = "Maximum Runtime"))]
+    pub maximum_runtime_secs: Option<u64>,
+
+    /// The maximum amount of output, in bytes, read from a single run of the command.
+    ///
+    /// Once this amount of output has been read, the command is killed and the rest of its output
+    /// is discarded.
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    pub maximum_output_size_bytes: Option<usize>,
+
     #[configurable(derived)]
     framing: Option<FramingConfig>,
 
@@ -135,6 +173,34 @@ pub struct StreamingConfig {
     #[serde(default = "default_respawn_interval_secs")]
     #[configurable(metadata(docs::human_name = "Respawn Interval"))]
     respawn_interval_secs: u64,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    respawn_policy: RespawnPolicy,
+
+    /// The maximum amount of time, in seconds, before rerunning a streaming command that keeps
+    /// exiting.
+    ///
+    /// If set, the time before rerunning the command doubles each time the command exits, starting
+    /// from `respawn_interval_secs`, up to `maximum_respawn_interval_secs`. It is reset to
+    /// `respawn_interval_secs` once a run of the command lasts longer than
+    /// `maximum_respawn_interval_secs`.
+    #[configurable(metadata(docs::human_name = "Maximum Respawn Interval"))]
+    maximum_respawn_interval_secs: Option<u64>,
+}
+
+/// Policy for rerunning a streaming command that exited.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RespawnPolicy {
+    /// The command is rerun whenever it exits.
+    #[default]
+    Always,
+
+    /// The command is only rerun if it fails, by exiting with a non-zero exit code, being killed
+    /// by a signal, or failing to start.
+    OnFailure,
 }
 
 #[derive(Debug, PartialEq, Eq, Snafu)]
@@ -143,6 +209,14 @@ pub enum ExecConfigError {
     CommandEmpty,
     #[snafu(display("The maximum buffer size must be greater than zero"))]
     ZeroBuffer,
+    #[snafu(display("The maximum runtime must be greater than zero"))]
+    ZeroRuntime,
+    #[snafu(display("The maximum output size must be greater than zero"))]
+    ZeroOutputSize,
+    #[snafu(display(
+        "The maximum respawn interval must be greater than or equal to the respawn interval"
+    ))]
+    InvalidMaximumRespawnInterval,
 }
 
 impl Default for ExecConfig {
@@ -155,10 +229,14 @@ impl Default for ExecConfig {
             streaming: None,
             command: vec!["echo".to_owned(), "Hello World!".to_owned()],
             environment: None,
+            environment_templates: None,
             clear_environment: default_clear_environment(),
             working_directory: None,
             include_stderr: default_include_stderr(),
+            separate_stderr_output: false,
             maximum_buffer_size_bytes: default_maximum_buffer_size(),
+            maximum_runtime_secs: None,
+            maximum_output_size_bytes: None,
             framing: None,
             decoding: default_decoding(),
             log_namespace: None,
@@ -199,6 +277,13 @@ fn environment_examples() -> HashMap<String, String> {
     ])
 }
 
+fn environment_template_examples() -> HashMap<String, String> {
+    HashMap::<_, _>::from_iter([
+        ("RUN_DATE".to_owned(), "%Y-%m-%d".to_owned()),
+        ("RUN_ID".to_owned(), "{{ host }}-%s".to_owned()),
+    ])
+}
+
 fn get_hostname() -> Option<String> {
     crate::get_hostname().ok()
 }
@@ -217,6 +302,16 @@ impl ExecConfig {
             Err(ExecConfigError::CommandEmpty)
         } else if self.maximum_buffer_size_bytes == 0 {
             Err(ExecConfigError::ZeroBuffer)
+        } else if self.maximum_runtime_secs == Some(0) {
+            Err(ExecConfigError::ZeroRuntime)
+        } else if self.maximum_output_size_bytes == Some(0) {
+            Err(ExecConfigError::ZeroOutputSize)
+        } else if self.streaming.as_ref().is_some_and(|config| {
+            config
+                .maximum_respawn_interval_secs
+                .is_some_and(|maximum| maximum < config.respawn_interval_secs)
+        }) {
+            Err(ExecConfigError::InvalidMaximumRespawnInterval)
         } else {
             Ok(())
         }
@@ -240,11 +335,62 @@ impl ExecConfig {
         }
     }
 
-    const fn respawn_interval_secs_or_default(&self) -> u64 {
+    fn respawn(&self) -> Respawn {
         match &self.streaming {
-            None => default_respawn_interval_secs(),
-            Some(config) => config.respawn_interval_secs,
+            None => Respawn::new(
+                RespawnPolicy::default(),
+                default_respawn_interval_secs(),
+                None,
+            ),
+            Some(config) => Respawn::new(
+                config.respawn_policy,
+                config.respawn_interval_secs,
+                config.maximum_respawn_interval_secs,
+            ),
+        }
+    }
+}
+
+/// Schedules the reruns of a streaming command.
+#[derive(Debug)]
+struct Respawn {
+    policy: RespawnPolicy,
+    interval: Duration,
+    maximum_interval: Option<Duration>,
+    next_interval: Duration,
+}
+
+impl Respawn {
+    fn new(policy: RespawnPolicy, interval_secs: u64, maximum_interval_secs: Option<u64>) -> Self {
+        let interval = Duration::from_secs(interval_secs);
+        Self {
+            policy,
+            interval,
+            maximum_interval: maximum_interval_secs.map(Duration::from_secs),
+            next_interval: interval,
+        }
+    }
+
+    /// Returns whether the command should be rerun after a run ending with `exit_status`.
+    fn should_respawn(&self, exit_status: Option<ExitStatus>) -> bool {
+        match self.policy {
+            RespawnPolicy::Always => true,
+            RespawnPolicy::OnFailure => !exit_status.is_some_and(|status| status.success()),
+        }
+    }
+
+    /// Returns the time to wait before rerunning the command after a run that lasted
+    /// `run_duration`.
+    fn next_interval(&mut self, run_duration: Duration) -> Duration {
+        let Some(maximum_interval) = self.maximum_interval else {
+            return self.interval;
+        };
+        if run_duration >= maximum_interval {
+            self.next_interval = self.interval;
         }
+        let interval = self.next_interval;
+        self.next_interval = (interval * 2).min(maximum_interval);
+        interval
     }
 }
 
@@ -279,13 +425,13 @@ impl SourceConfig for ExecConfig {
             }
             Mode::Streaming => {
                 let respawn_on_exit = self.respawn_on_exit_or_default();
-                let respawn_interval_secs = self.respawn_interval_secs_or_default();
+                let respawn = self.respawn();
 
                 Ok(Box::pin(run_streaming(
                     self.clone(),
                     hostname,
                     respawn_on_exit,
-                    respawn_interval_secs,
+                    respawn,
                     decoder,
                     cx.shutdown,
                     cx.out,
@@ -335,10 +481,17 @@ impl SourceConfig for ExecConfig {
                 None,
             );
 
-        vec![SourceOutput::new_logs(
+        let mut outputs = vec![SourceOutput::new_logs(
             self.decoding.output_type(),
-            schema_definition,
-        )]
+            schema_definition.clone(),
+        )];
+        if self.include_stderr && self.separate_stderr_output {
+            outputs.push(
+                SourceOutput::new_logs(self.decoding.output_type(), schema_definition)
+                    .with_port(STDERR),
+            );
+        }
+        outputs
     }
 
     fn can_acknowledge(&self) -> bool {
@@ -403,17 +556,16 @@ async fn run_streaming(
     config: ExecConfig,
     hostname: Option<String>,
     respawn_on_exit: bool,
-    respawn_interval_secs: u64,
+    mut respawn: Respawn,
     decoder: Decoder,
     mut shutdown: ShutdownSignal,
     out: SourceSender,
     log_namespace: LogNamespace,
 ) -> Result<(), ()> {
     if respawn_on_exit {
-        let duration = Duration::from_secs(respawn_interval_secs);
-
         // Continue to loop while not shutdown
         loop {
+            let start = Instant::now();
             let output = run_command(
                 config.clone(),
                 hostname.clone(),
@@ -425,12 +577,22 @@ async fn run_streaming(
             .await;
 
             // handle command finished
-            if let Err(command_error) = output {
-                emit!(ExecFailedError {
-                    command: config.command_line().as_str(),
-                    error: command_error,
-                });
+            let exit_status = match output {
+                Ok(exit_status) => exit_status,
+                Err(command_error) => {
+                    emit!(ExecFailedError {
+                        command: config.command_line().as_str(),
+                        error: command_error,
+                    });
+                    None
+                }
+            };
+
+            if !respawn.should_respawn(exit_status) {
+                debug!("Streaming process succeeded, not restarting it.");
+                break;
             }
+            let duration = respawn.next_interval(start.elapsed());
 
             tokio::select! {
                 _ = &mut shutdown => break, // will break early if a shutdown is started
@@ -469,6 +631,7 @@ async fn run_command(
 ) -> Result<Option<ExitStatus>, Error> {
     debug!("Starting command run.");
     let mut command = build_command(&config);
+    render_environment(&config, &hostname, &mut command);
 
     // Mark the start time just before spawning the process as
     // this seems to be the best approximation of exec duration
@@ -505,6 +668,16 @@ async fn run_command(
 
     let bytes_received = register!(BytesReceived::from(Protocol::NONE));
 
+    let runtime_limit = config.maximum_runtime_secs;
+    let runtime_limit_reached = async move {
+        match runtime_limit {
+            Some(secs) => sleep(Duration::from_secs(secs)).await,
+            None => std::future::pending().await,
+        }
+    };
+    tokio::pin!(runtime_limit_reached);
+    let mut output_size = 0;
+
     'outer: loop {
         tokio::select! {
             _ = &mut shutdown => {
@@ -512,6 +685,14 @@ async fn run_command(
                         break 'outer; // couldn't signal, exit early
                 }
             }
+            _ = &mut runtime_limit_reached => {
+                emit!(ExecRuntimeLimitExceededError {
+                    command: config.command_line().as_str(),
+                    maximum_runtime_secs: runtime_limit.unwrap_or_default(),
+                });
+                kill_child(&mut child).await;
+                break 'outer;
+            }
             v = receiver.recv() => {
                 match v {
                     None => break 'outer,
@@ -528,10 +709,27 @@ async fn run_command(
                         for event in &mut events {
                             handle_event(&config, &hostname, &Some(stream.to_string()), pid, event, log_namespace);
                         }
-                        if (out.send_batch(events).await).is_err() {
+                        let sent = if stream == STDERR && config.separate_stderr_output {
+                            out.send_batch_named(STDERR, events).await
+                        } else {
+                            out.send_batch(events).await
+                        };
+                        if sent.is_err() {
                             emit!(StreamClosedError { count });
                             break;
                         }
+
+                        output_size += byte_size;
+                        if let Some(maximum_output_size_bytes) = config.maximum_output_size_bytes {
+                            if output_size >= maximum_output_size_bytes {
+                                emit!(ExecOutputLimitExceededError {
+                                    command: config.command_line().as_str(),
+                                    maximum_output_size_bytes,
+                                });
+                                kill_child(&mut child).await;
+                                break 'outer;
+                            }
+                        }
                     },
                 }
             }
@@ -608,6 +806,13 @@ async fn shutdown_child(
     }
 }
 
+/// Kills a command that exceeded one of its limits, and waits for it to exit.
+async fn kill_child(child: &mut tokio::process::Child) {
+    if let Err(error) = child.kill().await {
+        error!(message = "Unable to kill command.", %error);
+    }
+}
+
 #[cfg(windows)]
 async fn shutdown_child(
     child: &mut tokio::process::Child,
@@ -668,6 +873,36 @@ fn build_command(config: &ExecConfig) -> Command {
     command
 }
 
+/// Sets the environment variables rendered from `environment_templates`.
+fn render_environment(config: &ExecConfig, hostname: &Option<String>, command: &mut Command) {
+    let Some(templates) = &config.environment_templates else {
+        return;
+    };
+
+    let mut log = LogEvent::default();
+    if let Some(timestamp_key) = log_schema().timestamp_key_target_path() {
+        log.insert(timestamp_key, Utc::now());
+    }
+    if let Some(hostname) = hostname {
+        log.insert("host", hostname.clone());
+    }
+
+    for (name, template) in templates {
+        match template.render_string(&log) {
+            Ok(value) => {
+                command.env(name, value);
+            }
+            Err(error) => {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some(name.as_str()),
+                    drop_event: false,
+                });
+            }
+        }
+    }
+}
+
 fn handle_event(
     config: &ExecConfig,
     hostname: &Option<String>,
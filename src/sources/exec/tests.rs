@@ -1,5 +1,8 @@
 use crate::sources::exec::*;
-use crate::{event::LogEvent, test_util::trace_init};
+use crate::{
+    event::{EventStatus, LogEvent},
+    test_util::trace_init,
+};
 use bytes::Bytes;
 use std::ffi::OsStr;
 use std::io::Cursor;
@@ -174,13 +177,19 @@ fn test_build_command() {
         streaming: Some(StreamingConfig {
             respawn_on_exit: default_respawn_on_exit(),
             respawn_interval_secs: default_respawn_interval_secs(),
+            respawn_policy: RespawnPolicy::default(),
+            maximum_respawn_interval_secs: None,
         }),
         command: vec!["./runner".to_owned(), "arg1".to_owned(), "arg2".to_owned()],
         environment: None,
+        environment_templates: None,
         clear_environment: default_clear_environment(),
         working_directory: Some(PathBuf::from("/tmp")),
         include_stderr: default_include_stderr(),
+        separate_stderr_output: false,
         maximum_buffer_size_bytes: default_maximum_buffer_size(),
+        maximum_runtime_secs: None,
+        maximum_output_size_bytes: None,
         framing: None,
         decoding: default_decoding(),
         log_namespace: None,
@@ -208,13 +217,19 @@ fn test_build_command_custom_environment() {
         streaming: Some(StreamingConfig {
             respawn_on_exit: default_respawn_on_exit(),
             respawn_interval_secs: default_respawn_interval_secs(),
+            respawn_policy: RespawnPolicy::default(),
+            maximum_respawn_interval_secs: None,
         }),
         command: vec!["./runner".to_owned(), "arg1".to_owned(), "arg2".to_owned()],
         environment: Some(HashMap::from([("FOO".to_owned(), "foo".to_owned())])),
+        environment_templates: None,
         clear_environment: default_clear_environment(),
         working_directory: Some(PathBuf::from("/tmp")),
         include_stderr: default_include_stderr(),
+        separate_stderr_output: false,
         maximum_buffer_size_bytes: default_maximum_buffer_size(),
+        maximum_runtime_secs: None,
+        maximum_output_size_bytes: None,
         framing: None,
         decoding: default_decoding(),
         log_namespace: None,
@@ -238,13 +253,19 @@ fn test_build_command_clear_environment() {
         streaming: Some(StreamingConfig {
             respawn_on_exit: default_respawn_on_exit(),
             respawn_interval_secs: default_respawn_interval_secs(),
+            respawn_policy: RespawnPolicy::default(),
+            maximum_respawn_interval_secs: None,
         }),
         command: vec!["./runner".to_owned(), "arg1".to_owned(), "arg2".to_owned()],
         environment: Some(HashMap::from([("FOO".to_owned(), "foo".to_owned())])),
+        environment_templates: None,
         clear_environment: true,
         working_directory: Some(PathBuf::from("/tmp")),
         include_stderr: default_include_stderr(),
+        separate_stderr_output: false,
         maximum_buffer_size_bytes: default_maximum_buffer_size(),
+        maximum_runtime_secs: None,
+        maximum_output_size_bytes: None,
         framing: None,
         decoding: default_decoding(),
         log_namespace: None,
@@ -258,6 +279,84 @@ fn test_build_command_clear_environment() {
     assert_eq!(envs.len(), 1);
 }
 
+#[test]
+fn test_render_environment() {
+    let mut config = standard_scheduled_test_config();
+    config.environment = Some(HashMap::from([("HOST".to_owned(), "static".to_owned())]));
+    config.environment_templates = Some(HashMap::from([
+        ("HOST".to_owned(), Template::try_from("{{ host }}").unwrap()),
+        (
+            "MISSING".to_owned(),
+            Template::try_from("{{ missing }}").unwrap(),
+        ),
+    ]));
+    let hostname = Some("Some.Machine".to_string());
+
+    let mut command = build_command(&config);
+    render_environment(&config, &hostname, &mut command);
+    let envs: Vec<_> = command.as_std().get_envs().collect();
+
+    assert!(envs.contains(&(OsStr::new("HOST"), Some(OsStr::new("Some.Machine")))));
+    assert!(!envs.iter().any(|(name, _)| *name == OsStr::new("MISSING")));
+}
+
+#[test]
+fn test_validate_limits() {
+    let mut config = standard_scheduled_test_config();
+    config.maximum_runtime_secs = Some(0);
+    assert_eq!(config.validate(), Err(ExecConfigError::ZeroRuntime));
+
+    let mut config = standard_streaming_test_config();
+    config.streaming = Some(StreamingConfig {
+        respawn_on_exit: true,
+        respawn_interval_secs: 10,
+        respawn_policy: RespawnPolicy::Always,
+        maximum_respawn_interval_secs: Some(5),
+    });
+    assert_eq!(
+        config.validate(),
+        Err(ExecConfigError::InvalidMaximumRespawnInterval)
+    );
+}
+
+#[test]
+fn test_respawn_backoff() {
+    let mut respawn = Respawn::new(RespawnPolicy::Always, 1, Some(5));
+    let short = Duration::from_millis(100);
+
+    assert_eq!(respawn.next_interval(short), Duration::from_secs(1));
+    assert_eq!(respawn.next_interval(short), Duration::from_secs(2));
+    assert_eq!(respawn.next_interval(short), Duration::from_secs(4));
+    assert_eq!(respawn.next_interval(short), Duration::from_secs(5));
+    assert_eq!(respawn.next_interval(short), Duration::from_secs(5));
+    // A long enough run resets the backoff.
+    assert_eq!(
+        respawn.next_interval(Duration::from_secs(5)),
+        Duration::from_secs(1)
+    );
+
+    let mut respawn = Respawn::new(RespawnPolicy::Always, 1, None);
+    assert_eq!(respawn.next_interval(short), Duration::from_secs(1));
+    assert_eq!(respawn.next_interval(short), Duration::from_secs(1));
+}
+
+#[test]
+#[cfg(unix)]
+fn test_respawn_policy() {
+    use std::os::unix::process::ExitStatusExt;
+
+    let success = ExitStatus::from_raw(0);
+    let failure = ExitStatus::from_raw(1 << 8);
+
+    let respawn = Respawn::new(RespawnPolicy::OnFailure, 1, None);
+    assert!(!respawn.should_respawn(Some(success)));
+    assert!(respawn.should_respawn(Some(failure)));
+    assert!(respawn.should_respawn(None));
+
+    let respawn = Respawn::new(RespawnPolicy::Always, 1, None);
+    assert!(respawn.should_respawn(Some(success)));
+}
+
 #[tokio::test]
 async fn test_spawn_reader_thread() {
     trace_init();
@@ -435,6 +534,105 @@ async fn test_graceful_shutdown() {
     }
 }
 
+#[tokio::test]
+#[cfg(unix)]
+async fn test_separate_stderr_output() {
+    use vector_lib::event::EventContainer;
+
+    let mut config = standard_scheduled_test_config();
+    config.command = vec![
+        String::from("bash"),
+        String::from("-c"),
+        String::from("echo out; echo err >&2"),
+    ];
+    config.separate_stderr_output = true;
+    let (mut tx, rx) = SourceSender::new_test();
+    let stderr_rx = tx.add_outputs(EventStatus::Delivered, STDERR.to_owned());
+
+    tokio::time::timeout(
+        time::Duration::from_secs(5),
+        run_command(
+            config,
+            None,
+            Default::default(),
+            ShutdownSignal::noop(),
+            tx,
+            LogNamespace::Legacy,
+        ),
+    )
+    .await
+    .expect("command timed out")
+    .expect("command error");
+
+    let events = rx.collect::<Vec<_>>().await;
+    assert_eq!(events.len(), 1);
+    assert_eq!(*events[0].as_log().get_message().unwrap(), "out".into());
+
+    let events = stderr_rx
+        .flat_map(|item| futures::stream::iter(item.events.into_events()))
+        .collect::<Vec<_>>()
+        .await;
+    assert_eq!(events.len(), 1);
+    assert_eq!(*events[0].as_log().get_message().unwrap(), "err".into());
+    assert_eq!(events[0].as_log()[STREAM_KEY], STDERR.into());
+}
+
+#[tokio::test]
+#[cfg(unix)]
+async fn test_maximum_output_size() {
+    let mut config = standard_scheduled_test_config();
+    config.command = vec!["yes".to_owned()];
+    config.maximum_output_size_bytes = Some(50);
+    let (tx, rx) = SourceSender::new_test();
+
+    let exit_status = tokio::time::timeout(
+        time::Duration::from_secs(5),
+        run_command(
+            config,
+            None,
+            Default::default(),
+            ShutdownSignal::noop(),
+            tx,
+            LogNamespace::Legacy,
+        ),
+    )
+    .await
+    .expect("command timed out")
+    .expect("command error");
+
+    // The command is killed once enough output has been read.
+    assert!(!exit_status.expect("missing exit status").success());
+    let events = rx.collect::<Vec<_>>().await;
+    assert!(!events.is_empty());
+    assert!(events.len() <= 50);
+}
+
+#[tokio::test]
+#[cfg(unix)]
+async fn test_maximum_runtime() {
+    let mut config = standard_scheduled_test_config();
+    config.command = vec!["sleep".to_owned(), "30".to_owned()];
+    config.maximum_runtime_secs = Some(1);
+    let (tx, _rx) = SourceSender::new_test();
+
+    let exit_status = tokio::time::timeout(
+        time::Duration::from_secs(5),
+        run_command(
+            config,
+            None,
+            Default::default(),
+            ShutdownSignal::noop(),
+            tx,
+            LogNamespace::Legacy,
+        ),
+    )
+    .await
+    .expect("command timed out")
+    .expect("command error");
+
+    assert!(!exit_status.expect("missing exit status").success());
+}
+
 fn standard_scheduled_test_config() -> ExecConfig {
     Default::default()
 }
@@ -446,13 +644,19 @@ fn standard_streaming_test_config() -> ExecConfig {
         streaming: Some(StreamingConfig {
             respawn_on_exit: default_respawn_on_exit(),
             respawn_interval_secs: default_respawn_interval_secs(),
+            respawn_policy: RespawnPolicy::default(),
+            maximum_respawn_interval_secs: None,
         }),
         command: vec!["yes".to_owned()],
         environment: None,
+        environment_templates: None,
         clear_environment: default_clear_environment(),
         working_directory: None,
         include_stderr: default_include_stderr(),
+        separate_stderr_output: false,
         maximum_buffer_size_bytes: default_maximum_buffer_size(),
+        maximum_runtime_secs: None,
+        maximum_output_size_bytes: None,
         framing: None,
         decoding: default_decoding(),
         log_namespace: None,
@@ -0,0 +1,350 @@
+use std::{collections::HashMap, sync::Arc};
+
+use bytes::Bytes;
+use rmpv::Value;
+use sha2::{Digest, Sha512};
+use vector_lib::configurable::configurable_component;
+use vector_lib::sensitive_string::SensitiveString;
+
+/// Authentication settings of the forward protocol handshake.
+///
+/// When set, clients must authenticate with the shared key, and with one of `users` if any,
+/// before sending any message.
+///
+/// See the [forward protocol specification][spec] for more information.
+///
+/// [spec]: https://github.com/fluent/fluentd/wiki/Forward-Protocol-Specification-v1#handshake-messages
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct FluentSecurityConfig {
+    /// The hostname of Vector sent to the clients during the handshake.
+    ///
+    /// By default, the hostname of the host Vector is running on.
+    #[configurable(metadata(docs::examples = "vector-aggregator-0"))]
+    self_hostname: Option<String>,
+
+    /// The key shared with the clients, set as `shared_key` in their `<security>` section.
+    #[configurable(metadata(docs::examples = "${FLUENT_SHARED_KEY}"))]
+    shared_key: SensitiveString,
+
+    /// The users the clients authenticate as, in addition to the shared key.
+    ///
+    /// If empty, clients are not required to authenticate as a user.
+    #[serde(default)]
+    users: Vec<FluentUserConfig>,
+}
+
+/// A user of the forward protocol handshake.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct FluentUserConfig {
+    /// The name of the user.
+    #[configurable(metadata(docs::examples = "td-agent"))]
+    username: String,
+
+    /// The password of the user.
+    #[configurable(metadata(docs::examples = "${FLUENT_PASSWORD}"))]
+    password: SensitiveString,
+}
+
+impl FluentSecurityConfig {
+    pub(super) fn build(&self) -> crate::Result<Arc<Security>> {
+        let hostname = match &self.self_hostname {
+            Some(hostname) => hostname.clone(),
+            None => crate::get_hostname()?,
+        };
+        Ok(Arc::new(Security {
+            hostname,
+            shared_key: self.shared_key.inner().to_owned(),
+            users: self
+                .users
+                .iter()
+                .map(|user| (user.username.clone(), user.password.inner().to_owned()))
+                .collect(),
+        }))
+    }
+}
+
+#[derive(Debug)]
+pub(super) struct Security {
+    hostname: String,
+    shared_key: String,
+    users: HashMap<String, String>,
+}
+
+/// The state of the handshake of a single connection.
+#[derive(Debug)]
+pub(super) struct Handshake {
+    security: Arc<Security>,
+    nonce: [u8; 16],
+    auth_salt: [u8; 16],
+    authenticated: bool,
+}
+
+impl Handshake {
+    pub(super) fn new(security: Arc<Security>) -> Self {
+        Self {
+            security,
+            nonce: rand::random(),
+            auth_salt: rand::random(),
+            authenticated: false,
+        }
+    }
+
+    pub(super) const fn is_authenticated(&self) -> bool {
+        self.authenticated
+    }
+
+    /// The `HELO` message, sent to the client when it connects.
+    pub(super) fn helo(&self) -> Bytes {
+        let auth = if self.security.users.is_empty() {
+            Value::from("")
+        } else {
+            Value::Binary(self.auth_salt.to_vec())
+        };
+        encode(Value::Array(vec![
+            Value::from("HELO"),
+            Value::Map(vec![
+                (Value::from("nonce"), Value::Binary(self.nonce.to_vec())),
+                (Value::from("auth"), auth),
+                (Value::from("keepalive"), Value::Boolean(true)),
+            ]),
+        ]))
+    }
+
+    /// Whether a message is the `PING` message of the client.
+    pub(super) fn is_ping(message: &Value) -> bool {
+        message
+            .as_array()
+            .and_then(|message| message.first())
+            .and_then(Value::as_str)
+            .is_some_and(|kind| kind == "PING")
+    }
+
+    /// Verifies the `PING` message of the client, and returns the `PONG` message to reply with.
+    ///
+    /// If the client failed to authenticate, the reason is returned along with the reply, after
+    /// which the connection is to be closed.
+    pub(super) fn pong(&mut self, ping: &Value) -> (Bytes, Result<(), &'static str>) {
+        match self.verify(ping) {
+            Ok(shared_key_salt) => {
+                self.authenticated = true;
+                let digest = digest(&[
+                    &shared_key_salt,
+                    self.security.hostname.as_bytes(),
+                    &self.nonce,
+                    self.security.shared_key.as_bytes(),
+                ]);
+                let pong = encode(Value::Array(vec![
+                    Value::from("PONG"),
+                    Value::Boolean(true),
+                    Value::from(""),
+                    Value::from(self.security.hostname.as_str()),
+                    Value::from(digest),
+                ]));
+                (pong, Ok(()))
+            }
+            Err(reason) => {
+                let pong = encode(Value::Array(vec![
+                    Value::from("PONG"),
+                    Value::Boolean(false),
+                    Value::from(reason),
+                    Value::from(""),
+                    Value::from(""),
+                ]));
+                (pong, Err(reason))
+            }
+        }
+    }
+
+    /// Verifies the digests of the `PING` message, returning the shared key salt of the client.
+    fn verify(&self, ping: &Value) -> Result<Vec<u8>, &'static str> {
+        let fields = ping
+            .as_array()
+            .filter(|fields| fields.len() == 6)
+            .and_then(|fields| fields.iter().skip(1).map(bytes).collect::<Option<Vec<_>>>())
+            .ok_or("invalid ping message")?;
+        let [hostname, shared_key_salt, shared_key_digest, username, password_digest] =
+            <[&[u8]; 5]>::try_from(fields).map_err(|_| "invalid ping message")?;
+
+        let expected = digest(&[
+            shared_key_salt,
+            hostname,
+            &self.nonce,
+            self.security.shared_key.as_bytes(),
+        ]);
+        if !digests_eq(shared_key_digest, &expected) {
+            return Err("shared_key mismatch");
+        }
+
+        if !self.security.users.is_empty() {
+            let password = std::str::from_utf8(username)
+                .ok()
+                .and_then(|username| self.security.users.get(username));
+            let authenticated = password.is_some_and(|password| {
+                let expected = digest(&[&self.auth_salt, username, password.as_bytes()]);
+                digests_eq(password_digest, &expected)
+            });
+            if !authenticated {
+                return Err("username/password mismatch");
+            }
+        }
+
+        Ok(shared_key_salt.to_vec())
+    }
+}
+
+/// The content of a string or binary message field.
+fn bytes(value: &Value) -> Option<&[u8]> {
+    match value {
+        Value::String(value) => Some(value.as_bytes()),
+        Value::Binary(value) => Some(value),
+        _ => None,
+    }
+}
+
+/// The hex encoded SHA-512 digest of the concatenation of `parts`.
+fn digest(parts: &[&[u8]]) -> String {
+    let mut hasher = Sha512::new();
+    for part in parts {
+        hasher.update(part);
+    }
+    hex::encode(hasher.finalize())
+}
+
+/// Compares digests in constant time.
+fn digests_eq(digest: &[u8], expected: &str) -> bool {
+    digest.len() == expected.len() && openssl::memcmp::eq(digest, expected.as_bytes())
+}
+
+fn encode(value: Value) -> Bytes {
+    let mut buf = Vec::new();
+    rmpv::encode::write_value(&mut buf, &value).expect("writing to a vector cannot fail");
+    buf.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn security(users: Vec<FluentUserConfig>) -> Arc<Security> {
+        FluentSecurityConfig {
+            self_hostname: Some("vector".to_owned()),
+            shared_key: "secret".to_owned().into(),
+            users,
+        }
+        .build()
+        .unwrap()
+    }
+
+    /// Builds the `PING` message a client sends in reply to the given handshake.
+    fn ping(handshake: &Handshake, shared_key: &str, user: Option<(&str, &str)>) -> Value {
+        let salt = b"salt";
+        let (username, password_digest) = match user {
+            Some((username, password)) => (
+                username,
+                digest(&[
+                    &handshake.auth_salt,
+                    username.as_bytes(),
+                    password.as_bytes(),
+                ]),
+            ),
+            None => ("", String::new()),
+        };
+        Value::Array(vec![
+            Value::from("PING"),
+            Value::from("client"),
+            Value::Binary(salt.to_vec()),
+            Value::from(digest(&[
+                salt,
+                b"client",
+                &handshake.nonce,
+                shared_key.as_bytes(),
+            ])),
+            Value::from(username),
+            Value::from(password_digest),
+        ])
+    }
+
+    fn decode(bytes: Bytes) -> Value {
+        rmpv::decode::read_value(&mut &bytes[..]).unwrap()
+    }
+
+    #[test]
+    fn sends_helo() {
+        let handshake = Handshake::new(security(Vec::new()));
+        let helo = decode(handshake.helo());
+        let helo = helo.as_array().unwrap();
+        assert_eq!(helo[0].as_str(), Some("HELO"));
+
+        let options = helo[1].as_map().unwrap();
+        assert_eq!(
+            options[0],
+            (
+                Value::from("nonce"),
+                Value::Binary(handshake.nonce.to_vec())
+            )
+        );
+        assert_eq!(options[1], (Value::from("auth"), Value::from("")));
+    }
+
+    #[test]
+    fn authenticates_with_shared_key() {
+        let mut handshake = Handshake::new(security(Vec::new()));
+        let ping = ping(&handshake, "secret", None);
+        assert!(Handshake::is_ping(&ping));
+
+        let (pong, result) = handshake.pong(&ping);
+        assert_eq!(result, Ok(()));
+        assert!(handshake.is_authenticated());
+
+        let pong = decode(pong);
+        let pong = pong.as_array().unwrap();
+        assert_eq!(pong[1], Value::Boolean(true));
+        assert_eq!(pong[3].as_str(), Some("vector"));
+        let expected = digest(&[b"salt", b"vector", &handshake.nonce, b"secret"]);
+        assert_eq!(pong[4].as_str(), Some(expected.as_str()));
+    }
+
+    #[test]
+    fn rejects_invalid_shared_key() {
+        let mut handshake = Handshake::new(security(Vec::new()));
+        let ping = ping(&handshake, "guess", None);
+
+        let (pong, result) = handshake.pong(&ping);
+        assert_eq!(result, Err("shared_key mismatch"));
+        assert!(!handshake.is_authenticated());
+        assert_eq!(decode(pong).as_array().unwrap()[1], Value::Boolean(false));
+
+        let (_, result) = handshake.pong(&Value::Array(vec![Value::from("PING")]));
+        assert_eq!(result, Err("invalid ping message"));
+    }
+
+    #[test]
+    fn authenticates_users() {
+        let users = vec![FluentUserConfig {
+            username: "td-agent".to_owned(),
+            password: "password".to_owned().into(),
+        }];
+        let mut handshake = Handshake::new(security(users));
+        assert_eq!(
+            decode(handshake.helo()).as_array().unwrap()[1]
+                .as_map()
+                .unwrap()[1],
+            (
+                Value::from("auth"),
+                Value::Binary(handshake.auth_salt.to_vec())
+            )
+        );
+
+        let (_, result) = handshake.pong(&ping(&handshake, "secret", None));
+        assert_eq!(result, Err("username/password mismatch"));
+        let (_, result) = handshake.pong(&ping(&handshake, "secret", Some(("td-agent", "guess"))));
+        assert_eq!(result, Err("username/password mismatch"));
+        let (_, result) =
+            handshake.pong(&ping(&handshake, "secret", Some(("td-agent", "password"))));
+        assert_eq!(result, Ok(()));
+    }
+}
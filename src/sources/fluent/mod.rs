@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 use std::io::{self, Read};
 use std::net::SocketAddr;
+use std::sync::Arc;
 use std::time::Duration;
 
 use base64::prelude::{Engine as _, BASE64_STANDARD};
@@ -28,13 +29,16 @@ use crate::{
         SourceContext, SourceOutput,
     },
     event::{Event, LogEvent},
-    internal_events::{FluentMessageDecodeError, FluentMessageReceived},
+    internal_events::{FluentHandshakeError, FluentMessageDecodeError, FluentMessageReceived},
     serde::bool_or_struct,
     tcp::TcpKeepaliveConfig,
     tls::{MaybeTlsSettings, TlsSourceConfig},
 };
 
+mod handshake;
 mod message;
+pub use self::handshake::{FluentSecurityConfig, FluentUserConfig};
+use self::handshake::{Handshake, Security};
 use self::message::{FluentEntry, FluentMessage, FluentRecord, FluentTag, FluentTimestamp};
 
 /// Configuration for the `fluent` source.
@@ -64,6 +68,9 @@ pub struct FluentConfig {
     #[configurable(derived)]
     tls: Option<TlsSourceConfig>,
 
+    #[configurable(derived)]
+    security: Option<FluentSecurityConfig>,
+
     #[configurable(derived)]
     #[serde(default, deserialize_with = "bool_or_struct")]
     acknowledgements: SourceAcknowledgementsConfig,
@@ -81,6 +88,7 @@ impl GenerateConfig for FluentConfig {
             keepalive: None,
             permit_origin: None,
             tls: None,
+            security: None,
             receive_buffer_bytes: None,
             acknowledgements: Default::default(),
             connection_limit: Some(2),
@@ -95,7 +103,12 @@ impl GenerateConfig for FluentConfig {
 impl SourceConfig for FluentConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
         let log_namespace = cx.log_namespace(self.log_namespace);
-        let source = FluentSource::new(log_namespace);
+        let security = self
+            .security
+            .as_ref()
+            .map(FluentSecurityConfig::build)
+            .transpose()?;
+        let source = FluentSource::new(log_namespace, security);
         let shutdown_secs = Duration::from_secs(30);
         let tls_config = self.tls.as_ref().map(|tls| tls.tls_config.clone());
         let tls_client_metadata_key = self
@@ -210,13 +223,15 @@ impl FluentConfig {
 struct FluentSource {
     log_namespace: LogNamespace,
     legacy_host_key_path: Option<OwnedValuePath>,
+    security: Option<Arc<Security>>,
 }
 
 impl FluentSource {
-    fn new(log_namespace: LogNamespace) -> Self {
+    fn new(log_namespace: LogNamespace, security: Option<Arc<Security>>) -> Self {
         Self {
             log_namespace,
             legacy_host_key_path: log_schema().host_key().cloned(),
+            security,
         }
     }
 }
@@ -228,7 +243,14 @@ impl TcpSource for FluentSource {
     type Acker = FluentAcker;
 
     fn decoder(&self) -> Self::Decoder {
-        FluentDecoder::new(self.log_namespace)
+        FluentDecoder::new(
+            self.log_namespace,
+            self.security.clone().map(Handshake::new),
+        )
+    }
+
+    fn greeting(&self, decoder: &Self::Decoder) -> Option<Bytes> {
+        decoder.handshake.as_ref().map(Handshake::helo)
     }
 
     fn handle_events(&self, events: &mut [Event], host: SocketAddr) {
@@ -261,6 +283,7 @@ pub enum DecodeError {
     Decode(decode::Error),
     UnknownCompression(String),
     UnexpectedValue(rmpv::Value),
+    Unauthenticated(&'static str),
 }
 
 impl std::fmt::Display for DecodeError {
@@ -274,6 +297,9 @@ impl std::fmt::Display for DecodeError {
             DecodeError::UnexpectedValue(value) => {
                 write!(f, "unexpected msgpack value, ignoring: {}", value)
             }
+            DecodeError::Unauthenticated(reason) => {
+                write!(f, "client failed to authenticate: {}", reason)
+            }
         }
     }
 }
@@ -285,6 +311,7 @@ impl StreamDecodingError for DecodeError {
             DecodeError::Decode(_) => true,
             DecodeError::UnknownCompression(_) => true,
             DecodeError::UnexpectedValue(_) => true,
+            DecodeError::Unauthenticated(_) => false,
         }
     }
 }
@@ -304,11 +331,56 @@ impl From<decode::Error> for DecodeError {
 #[derive(Debug)]
 struct FluentDecoder {
     log_namespace: LogNamespace,
+    handshake: Option<Handshake>,
+    /// Why the client failed to authenticate, once the reply telling it so has been decoded.
+    rejection: Option<&'static str>,
 }
 
 impl FluentDecoder {
-    const fn new(log_namespace: LogNamespace) -> Self {
-        Self { log_namespace }
+    const fn new(log_namespace: LogNamespace, handshake: Option<Handshake>) -> Self {
+        Self {
+            log_namespace,
+            handshake,
+            rejection: None,
+        }
+    }
+
+    /// Handles a message, or the handshake if the client has not authenticated yet.
+    ///
+    /// The `PING` message of the client is answered with a `PONG` message, sent as the reply of
+    /// an empty frame. Any message other than heartbeats is rejected.
+    fn handle_handshake(
+        &mut self,
+        message: Result<FluentMessage, DecodeError>,
+        byte_size: usize,
+    ) -> Result<Option<(FluentFrame, usize)>, DecodeError> {
+        let Some(handshake) = self.handshake.as_mut() else {
+            return self.handle_message(message, byte_size);
+        };
+        if handshake.is_authenticated() {
+            return self.handle_message(message, byte_size);
+        }
+
+        match message {
+            Ok(FluentMessage::Heartbeat(ping)) if Handshake::is_ping(&ping) => {
+                let (pong, result) = handshake.pong(&ping);
+                if let Err(reason) = result {
+                    emit!(FluentHandshakeError { reason });
+                    self.rejection = Some(reason);
+                }
+                let frame = FluentFrame {
+                    events: smallvec![],
+                    chunk: None,
+                    reply: Some(pong),
+                };
+                Ok(Some((frame, byte_size)))
+            }
+            Ok(FluentMessage::Heartbeat(rmpv::Value::Nil)) => Ok(None),
+            Ok(_) => Err(DecodeError::Unauthenticated(
+                "message received before the handshake",
+            )),
+            Err(error) => Err(error),
+        }
     }
 
     fn handle_message(
@@ -329,6 +401,7 @@ impl FluentDecoder {
                 let frame = FluentFrame {
                     events: smallvec![event],
                     chunk: None,
+                    reply: None,
                 };
                 Ok(Some((frame, byte_size)))
             }
@@ -342,6 +415,7 @@ impl FluentDecoder {
                 let frame = FluentFrame {
                     events: smallvec![event],
                     chunk: options.chunk,
+                    reply: None,
                 };
                 Ok(Some((frame, byte_size)))
             }
@@ -360,6 +434,7 @@ impl FluentDecoder {
                 let frame = FluentFrame {
                     events,
                     chunk: None,
+                    reply: None,
                 };
                 Ok(Some((frame, byte_size)))
             }
@@ -378,6 +453,7 @@ impl FluentDecoder {
                 let frame = FluentFrame {
                     events,
                     chunk: options.chunk,
+                    reply: None,
                 };
                 Ok(Some((frame, byte_size)))
             }
@@ -398,6 +474,7 @@ impl FluentDecoder {
                 let frame = FluentFrame {
                     events,
                     chunk: None,
+                    reply: None,
                 };
                 Ok(Some((frame, byte_size)))
             }
@@ -430,6 +507,7 @@ impl FluentDecoder {
                 let frame = FluentFrame {
                     events,
                     chunk: options.chunk,
+                    reply: None,
                 };
                 Ok(Some((frame, byte_size)))
             }
@@ -445,6 +523,9 @@ impl Decoder for FluentDecoder {
 
     fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
         loop {
+            if let Some(reason) = self.rejection {
+                return Err(DecodeError::Unauthenticated(reason));
+            }
             if src.is_empty() {
                 return Ok(None);
             }
@@ -470,7 +551,7 @@ impl Decoder for FluentDecoder {
 
             src.advance(byte_size);
 
-            let maybe_item = self.handle_message(res, byte_size).map_err(|error| {
+            let maybe_item = self.handle_handshake(res, byte_size).map_err(|error| {
                 let base64_encoded_message = BASE64_STANDARD.encode(&src);
                 emit!(FluentMessageDecodeError {
                     error: &error,
@@ -524,23 +605,25 @@ impl Decoder for FluentEntryStreamDecoder {
 
 struct FluentAcker {
     chunks: Vec<String>,
+    replies: Vec<Bytes>,
 }
 
 impl FluentAcker {
     fn new(frames: &[FluentFrame]) -> Self {
         Self {
             chunks: frames.iter().filter_map(|f| f.chunk.clone()).collect(),
+            replies: frames.iter().filter_map(|f| f.reply.clone()).collect(),
         }
     }
 }
 
 impl TcpSourceAcker for FluentAcker {
     fn build_ack(self, ack: TcpSourceAck) -> Option<Bytes> {
-        if self.chunks.is_empty() {
+        if self.chunks.is_empty() && self.replies.is_empty() {
             return None;
         }
 
-        let mut buf = Vec::new();
+        let mut buf = self.replies.concat();
         let mut ser = Serializer::new(&mut buf);
         let mut ack_map = HashMap::new();
 
@@ -573,6 +656,8 @@ impl From<FluentEvent<'_>> for Event {
 struct FluentFrame {
     events: SmallVec<[Event; 1]>,
     chunk: Option<String>,
+    /// A handshake message to send back to the client.
+    reply: Option<Bytes>,
 }
 
 impl From<FluentFrame> for SmallVec<[Event; 1]> {
@@ -643,6 +728,7 @@ mod tests {
     };
     use tokio_util::codec::Decoder;
     use vector_lib::assert_event_data_eq;
+    use vector_lib::codecs::StreamDecodingError;
     use vector_lib::lookup::OwnedTargetPath;
     use vector_lib::schema::Definition;
     use vrl::value::{kind::Collection, ObjectMap, Value};
@@ -852,12 +938,60 @@ mod tests {
     fn decode_all(message: Vec<u8>) -> Result<(SmallVec<[Event; 1]>, usize), DecodeError> {
         let mut buf = BytesMut::from(&message[..]);
 
-        let mut decoder = FluentDecoder::new(LogNamespace::default());
+        let mut decoder = FluentDecoder::new(LogNamespace::default(), None);
 
         let (frame, byte_size) = decoder.decode(&mut buf)?.unwrap();
         Ok((frame.into(), byte_size))
     }
 
+    fn handshake_decoder() -> FluentDecoder {
+        let security: FluentSecurityConfig = toml::from_str(
+            r#"
+            self_hostname = "vector"
+            shared_key = "secret"
+            "#,
+        )
+        .unwrap();
+        let handshake = Handshake::new(security.build().unwrap());
+        FluentDecoder::new(LogNamespace::default(), Some(handshake))
+    }
+
+    #[test]
+    fn rejects_messages_before_handshake() {
+        let mut decoder = handshake_decoder();
+        let mut buf = BytesMut::from(&build_req("tag", &[("field", "value")], false)[..]);
+
+        let error = decoder.decode(&mut buf).err().unwrap();
+        assert!(matches!(error, DecodeError::Unauthenticated(_)));
+        assert!(!error.can_continue());
+    }
+
+    #[test]
+    fn rejects_invalid_ping() {
+        let mut decoder = handshake_decoder();
+        let ping = rmpv::Value::Array(
+            ["PING", "client", "salt", "digest", "", ""]
+                .into_iter()
+                .map(rmpv::Value::from)
+                .collect(),
+        );
+        let mut buf = Vec::new();
+        rmpv::encode::write_value(&mut buf, &ping).unwrap();
+        let mut buf = BytesMut::from(&buf[..]);
+
+        // The client is told why it failed to authenticate before the connection is closed.
+        let (frame, _) = decoder.decode(&mut buf).unwrap().unwrap();
+        assert!(frame.events.is_empty());
+        let pong = rmpv::decode::read_value(&mut &frame.reply.unwrap()[..]).unwrap();
+        assert_eq!(pong.as_array().unwrap()[1], rmpv::Value::Boolean(false));
+
+        let error = decoder.decode(&mut buf).err().unwrap();
+        assert!(matches!(
+            error,
+            DecodeError::Unauthenticated("shared_key mismatch")
+        ));
+    }
+
     #[tokio::test]
     async fn ack_delivered_without_chunk() {
         let (result, output) = check_acknowledgements(EventStatus::Delivered, false).await;
@@ -899,6 +1033,7 @@ mod tests {
         let source = FluentConfig {
             address: address.into(),
             tls: None,
+            security: None,
             keepalive: None,
             permit_origin: None,
             receive_buffer_bytes: None,
@@ -964,6 +1099,7 @@ mod tests {
         let config = FluentConfig {
             address: SocketListenAddr::SocketAddr("0.0.0.0:24224".parse().unwrap()),
             tls: None,
+            security: None,
             keepalive: None,
             permit_origin: None,
             receive_buffer_bytes: None,
@@ -1020,6 +1156,7 @@ mod tests {
         let config = FluentConfig {
             address: SocketListenAddr::SocketAddr("0.0.0.0:24224".parse().unwrap()),
             tls: None,
+            security: None,
             keepalive: None,
             permit_origin: None,
             receive_buffer_bytes: None,
@@ -1242,6 +1379,7 @@ mod integration_tests {
             FluentConfig {
                 address: address.into(),
                 tls: None,
+                security: None,
                 keepalive: None,
                 permit_origin: None,
                 receive_buffer_bytes: None,
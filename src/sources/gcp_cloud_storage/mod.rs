@@ -0,0 +1,809 @@
+use std::{collections::HashMap, convert::TryInto, future::ready, io::ErrorKind};
+
+use async_compression::tokio::bufread;
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use futures::{stream, stream::StreamExt, Stream, TryStreamExt};
+use http::{
+    header::{ACCEPT_ENCODING, CONTENT_ENCODING, CONTENT_TYPE, LAST_MODIFIED},
+    HeaderMap, Request,
+};
+use hyper::Body;
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use smallvec::SmallVec;
+use snafu::ResultExt;
+use tokio_util::codec::FramedRead;
+use tokio_util::io::StreamReader;
+use vector_lib::codecs::decoding::{
+    DeserializerConfig, FramingConfig, NewlineDelimitedDecoderOptions,
+};
+use vector_lib::codecs::NewlineDelimitedDecoderConfig;
+use vector_lib::config::{log_schema, LegacyKey, LogNamespace};
+use vector_lib::configurable::configurable_component;
+use vector_lib::event::MaybeAsLogMut;
+use vector_lib::internal_event::{
+    ByteSize, BytesReceived, CountByteSize, InternalEventHandle as _, Registered,
+};
+use vector_lib::lookup::{metadata_path, owned_value_path, path, PathPrefix};
+use vrl::value::{kind::Collection, Kind};
+
+use super::util::MultilineConfig;
+use crate::codecs::{Decoder, DecodingConfig};
+use crate::{
+    config::{SourceAcknowledgementsConfig, SourceConfig, SourceContext, SourceOutput},
+    event::{BatchNotifier, BatchStatusReceiver, EstimatedJsonEncodedSizeOf, Event, LogEvent},
+    gcp::{GcpAuthConfig, GcpAuthenticator, Scope},
+    http::HttpClient,
+    internal_events::{EventsReceived, StreamClosedError},
+    line_agg::{self, LineAgg},
+    serde::{bool_or_struct, default_decoding},
+    tls::{TlsConfig, TlsSettings},
+    SourceSender,
+};
+
+mod pubsub;
+
+const DEFAULT_ENDPOINT: &str = "https://storage.googleapis.com";
+
+// Object names may contain any UTF-8 character, so everything but the unreserved characters and
+// the `/` separators is escaped when the name is used as a path.
+const OBJECT_NAME: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~')
+    .remove(b'/');
+
+const METADATA_HEADER_PREFIX: &str = "x-goog-meta-";
+
+/// Compression scheme for objects retrieved from GCS.
+#[configurable_component]
+#[configurable(metadata(docs::advanced))]
+#[derive(Clone, Copy, Debug, Derivative, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+#[derivative(Default)]
+pub enum Compression {
+    /// Automatically attempt to determine the compression scheme.
+    ///
+    /// The compression scheme of the object is determined from its `Content-Encoding` and
+    /// `Content-Type` metadata, as well as the object name suffix (for example, `.gz`).
+    ///
+    /// It is set to `none` if the compression scheme cannot be determined.
+    #[derivative(Default)]
+    Auto,
+
+    /// Uncompressed.
+    None,
+
+    /// GZIP.
+    Gzip,
+
+    /// ZSTD.
+    Zstd,
+}
+
+/// Configuration for the `gcp_cloud_storage` source.
+#[configurable_component(source("gcp_cloud_storage", "Collect logs from GCP Cloud Storage."))]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub struct GcsSourceConfig {
+    /// The endpoint from which to fetch objects.
+    #[configurable(metadata(docs::examples = "https://storage.googleapis.com"))]
+    #[serde(default = "default_endpoint")]
+    #[derivative(Default(value = "default_endpoint()"))]
+    endpoint: String,
+
+    /// The compression scheme used for decompressing objects retrieved from GCS.
+    #[serde(default)]
+    compression: Compression,
+
+    /// Configuration options for Pub/Sub.
+    pubsub: pubsub::Config,
+
+    #[serde(default, flatten)]
+    auth: GcpAuthConfig,
+
+    /// Multiline aggregation configuration.
+    ///
+    /// If not specified, multiline aggregation is disabled.
+    #[configurable(derived)]
+    multiline: Option<MultilineConfig>,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: SourceAcknowledgementsConfig,
+
+    #[configurable(derived)]
+    tls: Option<TlsConfig>,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[configurable(metadata(docs::hidden))]
+    #[serde(default)]
+    log_namespace: Option<bool>,
+
+    #[configurable(derived)]
+    #[serde(default = "default_framing")]
+    #[derivative(Default(value = "default_framing()"))]
+    pub framing: FramingConfig,
+
+    #[configurable(derived)]
+    #[serde(default = "default_decoding")]
+    #[derivative(Default(value = "default_decoding()"))]
+    pub decoding: DeserializerConfig,
+}
+
+fn default_endpoint() -> String {
+    DEFAULT_ENDPOINT.to_string()
+}
+
+const fn default_framing() -> FramingConfig {
+    FramingConfig::NewlineDelimited(NewlineDelimitedDecoderConfig {
+        newline_delimited: NewlineDelimitedDecoderOptions { max_length: None },
+    })
+}
+
+impl_generate_config_from_default!(GcsSourceConfig);
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "gcp_cloud_storage")]
+impl SourceConfig for GcsSourceConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let log_namespace = cx.log_namespace(self.log_namespace);
+
+        let multiline: Option<line_agg::Config> = self
+            .multiline
+            .as_ref()
+            .map(|config| config.try_into())
+            .transpose()?;
+
+        let decoder =
+            DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace)
+                .build()?;
+
+        let tls = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new(tls, &cx.proxy)?;
+
+        // Reading objects and pulling notifications need different scopes, so each API gets its
+        // own authenticator.
+        let storage_auth = self.auth.build(Scope::DevStorageReadOnly).await?;
+        storage_auth.spawn_regenerate_token();
+        let pubsub_auth = self.auth.build(Scope::PubSub).await?;
+        pubsub_auth.spawn_regenerate_token();
+
+        let reader = ObjectReader {
+            client: client.clone(),
+            auth: storage_auth,
+            endpoint: self.endpoint.trim_end_matches('/').to_string(),
+            compression: self.compression,
+            multiline,
+            decoder,
+            log_namespace,
+        };
+        let ingestor = pubsub::Ingestor::new(client, pubsub_auth, reader, self.pubsub.clone())?;
+
+        Ok(Box::pin(ingestor.run(cx, self.acknowledgements)))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
+        let log_namespace = global_log_namespace.merge(self.log_namespace);
+        let mut schema_definition = self
+            .decoding
+            .schema_definition(log_namespace)
+            .with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::Overwrite(owned_value_path!("bucket"))),
+                &owned_value_path!("bucket"),
+                Kind::bytes(),
+                None,
+            )
+            .with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::Overwrite(owned_value_path!("object"))),
+                &owned_value_path!("object"),
+                Kind::bytes(),
+                None,
+            )
+            .with_source_metadata(
+                Self::NAME,
+                None,
+                &owned_value_path!("timestamp"),
+                Kind::timestamp(),
+                Some("timestamp"),
+            )
+            .with_standard_vector_source_metadata()
+            // for metadata that is added to the events dynamically from the metadata
+            .with_source_metadata(
+                Self::NAME,
+                None,
+                &owned_value_path!("metadata"),
+                Kind::object(Collection::empty().with_unknown(Kind::bytes())).or_undefined(),
+                None,
+            );
+
+        // for metadata that is added to the events dynamically from the metadata
+        if log_namespace == LogNamespace::Legacy {
+            schema_definition = schema_definition.unknown_fields(Kind::bytes());
+        }
+
+        vec![SourceOutput::new_logs(
+            self.decoding.output_type(),
+            schema_definition,
+        )]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+/// Reads objects from GCS into events.
+pub(super) struct ObjectReader {
+    client: HttpClient,
+    auth: GcpAuthenticator,
+    endpoint: String,
+    compression: Compression,
+    multiline: Option<line_agg::Config>,
+    decoder: Decoder,
+    log_namespace: LogNamespace,
+}
+
+impl ObjectReader {
+    /// Fetches an object and sends its events.
+    ///
+    /// If acknowledgements are enabled, the returned receiver resolves to the status of the
+    /// object's events once they have all been delivered.
+    pub(super) async fn read_object(
+        &self,
+        bucket: &str,
+        object: &str,
+        out: &mut SourceSender,
+        acknowledgements: bool,
+        bytes_received: &Registered<BytesReceived>,
+        events_received: &Registered<EventsReceived>,
+    ) -> Result<Option<BatchStatusReceiver>, pubsub::ProcessingError> {
+        let uri = object_uri(&self.endpoint, bucket, object);
+        // Objects stored with `Content-Encoding: gzip` are transparently decompressed by GCS
+        // unless the client accepts gzip, which would leave the metadata disagreeing with the
+        // body. Accepting it means the object is always served exactly as it was stored.
+        let mut request = Request::get(uri)
+            .header(ACCEPT_ENCODING, "gzip")
+            .body(Body::empty())
+            .expect("object requests are always valid");
+        self.auth.apply(&mut request);
+
+        let response = self
+            .client
+            .send(request)
+            .await
+            .context(pubsub::GetObjectSnafu { bucket, object })?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(pubsub::ProcessingError::GetObjectStatus {
+                status,
+                bucket: bucket.to_owned(),
+                object: object.to_owned(),
+            });
+        }
+
+        let (parts, body) = response.into_parts();
+        let metadata = object_metadata(&parts.headers);
+        let timestamp = header_str(&parts.headers, LAST_MODIFIED)
+            .and_then(|value| DateTime::parse_from_rfc2822(value).ok())
+            .map(|timestamp| timestamp.with_timezone(&Utc));
+
+        let (batch, receiver) = BatchNotifier::maybe_new_with_receiver(acknowledgements);
+        let object_reader = gcs_object_decoder(
+            self.compression,
+            object,
+            header_str(&parts.headers, CONTENT_ENCODING),
+            header_str(&parts.headers, CONTENT_TYPE),
+            body,
+        )
+        .await;
+
+        // Record the read error seen to propagate up later so we avoid ack'ing the object.
+        //
+        // This can result in objects being partially processed before an error, but we
+        // prefer duplicate lines over message loss.
+        let mut read_error = None;
+        let lines: Box<dyn Stream<Item = Bytes> + Send + Unpin> = Box::new(
+            FramedRead::new(object_reader, self.decoder.framer.clone())
+                .map(|res| {
+                    res.map(|bytes| {
+                        bytes_received.emit(ByteSize(bytes.len()));
+                        bytes
+                    })
+                    .map_err(|err| {
+                        read_error = Some(err);
+                    })
+                    .ok()
+                })
+                .take_while(|res| ready(res.is_some()))
+                .map(|r| r.expect("validated by take_while")),
+        );
+
+        let lines: Box<dyn Stream<Item = Bytes> + Send + Unpin> = match &self.multiline {
+            Some(config) => Box::new(
+                LineAgg::new(
+                    lines.map(|line| ((), line, ())),
+                    line_agg::Logic::new(config.clone()),
+                )
+                .map(|(_src, line, _context, _lastline_context)| line),
+            ),
+            None => lines,
+        };
+
+        let mut stream = lines.flat_map(|line| {
+            let events = match self.decoder.deserializer_parse(line) {
+                Ok((events, _events_size)) => events,
+                Err(_error) => {
+                    // Error is handled by `codecs::Decoder`, no further handling
+                    // is needed here.
+                    SmallVec::new()
+                }
+            };
+
+            let events = events
+                .into_iter()
+                .map(|mut event: Event| {
+                    event = event.with_batch_notifier_option(&batch);
+                    if let Some(log_event) = event.maybe_as_log_mut() {
+                        handle_single_log(
+                            log_event,
+                            self.log_namespace,
+                            bucket,
+                            object,
+                            &metadata,
+                            timestamp,
+                        );
+                    }
+                    events_received.emit(CountByteSize(1, event.estimated_json_encoded_size_of()));
+                    event
+                })
+                .collect::<Vec<Event>>();
+            futures::stream::iter(events)
+        });
+
+        let send_error = match out.send_event_stream(&mut stream).await {
+            Ok(_) => None,
+            Err(_) => {
+                let (count, _) = stream.size_hint();
+                emit!(StreamClosedError { count });
+                Some(crate::source_sender::ClosedError)
+            }
+        };
+
+        // Up above, `lines` captures `read_error`, and eventually is captured by `stream`,
+        // so we explicitly drop it so that we can again utilize `read_error` below.
+        drop(stream);
+
+        // The BatchNotifier is cloned for each LogEvent in the batch stream, but the last
+        // reference must be dropped before the status of the batch is sent to the channel.
+        drop(batch);
+
+        if let Some(error) = read_error {
+            Err(pubsub::ProcessingError::ReadObject {
+                source: error,
+                bucket: bucket.to_owned(),
+                object: object.to_owned(),
+            })
+        } else if let Some(error) = send_error {
+            Err(pubsub::ProcessingError::PipelineSend {
+                source: error,
+                bucket: bucket.to_owned(),
+                object: object.to_owned(),
+            })
+        } else {
+            Ok(receiver)
+        }
+    }
+}
+
+fn object_uri(endpoint: &str, bucket: &str, object: &str) -> String {
+    format!(
+        "{}/{}/{}",
+        endpoint,
+        utf8_percent_encode(bucket, OBJECT_NAME),
+        utf8_percent_encode(object, OBJECT_NAME)
+    )
+}
+
+fn header_str(headers: &HeaderMap, name: http::header::HeaderName) -> Option<&str> {
+    headers.get(name).and_then(|value| value.to_str().ok())
+}
+
+/// Collects the custom metadata of an object, which is returned as `x-goog-meta-` headers.
+fn object_metadata(headers: &HeaderMap) -> HashMap<String, String> {
+    headers
+        .iter()
+        .filter_map(|(name, value)| {
+            let key = name.as_str().strip_prefix(METADATA_HEADER_PREFIX)?;
+            let value = value.to_str().ok()?;
+            Some((key.to_owned(), value.to_owned()))
+        })
+        .collect()
+}
+
+fn handle_single_log(
+    log: &mut LogEvent,
+    log_namespace: LogNamespace,
+    bucket: &str,
+    object: &str,
+    metadata: &HashMap<String, String>,
+    timestamp: Option<DateTime<Utc>>,
+) {
+    log_namespace.insert_source_metadata(
+        GcsSourceConfig::NAME,
+        log,
+        Some(LegacyKey::Overwrite(path!("bucket"))),
+        path!("bucket"),
+        Bytes::from(bucket.as_bytes().to_vec()),
+    );
+
+    log_namespace.insert_source_metadata(
+        GcsSourceConfig::NAME,
+        log,
+        Some(LegacyKey::Overwrite(path!("object"))),
+        path!("object"),
+        Bytes::from(object.as_bytes().to_vec()),
+    );
+
+    for (key, value) in metadata {
+        log_namespace.insert_source_metadata(
+            GcsSourceConfig::NAME,
+            log,
+            Some(LegacyKey::Overwrite(path!(key))),
+            path!("metadata", key.as_str()),
+            value.clone(),
+        );
+    }
+
+    log_namespace.insert_vector_metadata(
+        log,
+        log_schema().source_type_key(),
+        path!("source_type"),
+        Bytes::from_static(GcsSourceConfig::NAME.as_bytes()),
+    );
+
+    match log_namespace {
+        LogNamespace::Vector => {
+            if let Some(timestamp) = timestamp {
+                log.insert(
+                    metadata_path!(GcsSourceConfig::NAME, "timestamp"),
+                    timestamp,
+                );
+            }
+
+            log.insert(metadata_path!("vector", "ingest_timestamp"), Utc::now());
+        }
+        LogNamespace::Legacy => {
+            if let Some(timestamp_key) = log_schema().timestamp_key() {
+                log.try_insert(
+                    (PathPrefix::Event, timestamp_key),
+                    timestamp.unwrap_or_else(Utc::now),
+                );
+            }
+        }
+    };
+}
+
+/// None if body is empty
+async fn gcs_object_decoder(
+    compression: Compression,
+    object: &str,
+    content_encoding: Option<&str>,
+    content_type: Option<&str>,
+    mut body: Body,
+) -> Box<dyn tokio::io::AsyncRead + Send + Unpin> {
+    let first = if let Some(first) = body.next().await {
+        first
+    } else {
+        return Box::new(tokio::io::empty());
+    };
+
+    let r = tokio::io::BufReader::new(StreamReader::new(
+        stream::iter(Some(first))
+            .chain(body)
+            .map_err(|e| std::io::Error::new(ErrorKind::Other, e)),
+    ));
+
+    let compression = match compression {
+        Auto => determine_compression(content_encoding, content_type, object).unwrap_or(None),
+        _ => compression,
+    };
+
+    use Compression::*;
+    match compression {
+        Auto => unreachable!(), // is mapped above
+        None => Box::new(r),
+        Gzip => Box::new({
+            let mut decoder = bufread::GzipDecoder::new(r);
+            decoder.multiple_members(true);
+            decoder
+        }),
+        Zstd => Box::new({
+            let mut decoder = bufread::ZstdDecoder::new(r);
+            decoder.multiple_members(true);
+            decoder
+        }),
+    }
+}
+
+// try to determine the compression given the:
+// * content-encoding
+// * content-type
+// * object name (for file extension)
+//
+// It will use this information in this order
+fn determine_compression(
+    content_encoding: Option<&str>,
+    content_type: Option<&str>,
+    object: &str,
+) -> Option<Compression> {
+    content_encoding
+        .and_then(content_encoding_to_compression)
+        .or_else(|| content_type.and_then(content_type_to_compression))
+        .or_else(|| object_name_to_compression(object))
+}
+
+fn content_encoding_to_compression(content_encoding: &str) -> Option<Compression> {
+    match content_encoding {
+        "gzip" => Some(Compression::Gzip),
+        "zstd" => Some(Compression::Zstd),
+        _ => None,
+    }
+}
+
+fn content_type_to_compression(content_type: &str) -> Option<Compression> {
+    match content_type {
+        "application/gzip" | "application/x-gzip" => Some(Compression::Gzip),
+        "application/zstd" => Some(Compression::Zstd),
+        _ => None,
+    }
+}
+
+fn object_name_to_compression(object: &str) -> Option<Compression> {
+    let extension = std::path::Path::new(object)
+        .extension()
+        .and_then(std::ffi::OsStr::to_str);
+
+    use Compression::*;
+    extension.and_then(|extension| match extension {
+        "gz" => Some(Gzip),
+        "zst" => Some(Zstd),
+        _ => Option::None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::Write,
+        net::SocketAddr,
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    use flate2::{write::GzEncoder, Compression as GzCompression};
+    use serde_json::json;
+    use tokio::io::AsyncReadExt;
+    use warp::Filter;
+
+    use super::*;
+    use crate::{
+        event::EventStatus,
+        test_util::{
+            collect_n,
+            components::{assert_source_compliance, SOURCE_TAGS},
+            next_addr, wait_for_tcp,
+        },
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<GcsSourceConfig>();
+    }
+
+    #[test]
+    fn determine_compression() {
+        use super::Compression;
+
+        let cases = vec![
+            ("out.log", Some("gzip"), None, Some(Compression::Gzip)),
+            (
+                "out.log",
+                None,
+                Some("application/gzip"),
+                Some(Compression::Gzip),
+            ),
+            ("out.log.zst", None, None, Some(Compression::Zstd)),
+            ("out.txt", None, None, None),
+        ];
+        for case in cases {
+            let (object, content_encoding, content_type, expected) = case;
+            assert_eq!(
+                super::determine_compression(content_encoding, content_type, object),
+                expected,
+                "object={:?} content_encoding={:?} content_type={:?}",
+                object,
+                content_encoding,
+                content_type,
+            );
+        }
+    }
+
+    #[test]
+    fn object_uri_escapes_names() {
+        assert_eq!(
+            object_uri(DEFAULT_ENDPOINT, "logs", "2024/01/app log#1.gz"),
+            "https://storage.googleapis.com/logs/2024/01/app%20log%231.gz"
+        );
+    }
+
+    #[test]
+    fn collects_object_metadata() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-goog-meta-team", "storage".parse().unwrap());
+        headers.insert("x-goog-generation", "1".parse().unwrap());
+
+        assert_eq!(
+            object_metadata(&headers),
+            HashMap::from([("team".to_owned(), "storage".to_owned())])
+        );
+    }
+
+    #[tokio::test]
+    async fn decode_empty_message_gzip() {
+        let mut data = Vec::new();
+        gcs_object_decoder(
+            Compression::Auto,
+            "out.log",
+            Some("gzip"),
+            None,
+            Body::empty(),
+        )
+        .await
+        .read_to_end(&mut data)
+        .await
+        .unwrap();
+
+        assert!(data.is_empty());
+    }
+
+    /// Serves a single gzipped object notification from a fake Pub/Sub API, recording the
+    /// acknowledged IDs, and the object itself from a fake GCS API.
+    async fn spawn_fake_apis(lines: &[&str]) -> (SocketAddr, Arc<Mutex<Vec<String>>>) {
+        let mut encoder = GzEncoder::new(Vec::new(), GzCompression::fast());
+        encoder.write_all(lines.join("\n").as_bytes()).unwrap();
+        let object = Bytes::from(encoder.finish().unwrap());
+
+        let pulled = Arc::new(Mutex::new(false));
+        let acked = Arc::new(Mutex::new(Vec::new()));
+
+        let pull = warp::path!("v1" / "projects" / "my-project" / "subscriptions" / "logs:pull")
+            .and(warp::post())
+            .then(move || {
+                let pulled = Arc::clone(&pulled);
+                async move {
+                    let already_pulled = std::mem::replace(&mut *pulled.lock().unwrap(), true);
+                    if already_pulled {
+                        tokio::time::sleep(Duration::from_millis(100)).await;
+                        return warp::reply::json(&json!({}));
+                    }
+                    warp::reply::json(&json!({
+                        "receivedMessages": [{
+                            "ackId": "ack-1",
+                            "message": {
+                                "attributes": {
+                                    "eventType": "OBJECT_FINALIZE",
+                                    "bucketId": "bucket",
+                                    "objectId": "path/to/app.log",
+                                },
+                                "messageId": "1",
+                            },
+                        }],
+                    }))
+                }
+            });
+        let acknowledge =
+            warp::path!("v1" / "projects" / "my-project" / "subscriptions" / "logs:acknowledge")
+                .and(warp::post())
+                .and(warp::body::json())
+                .map({
+                    let acked = Arc::clone(&acked);
+                    move |body: serde_json::Value| {
+                        let ack_ids = body["ackIds"].as_array().unwrap();
+                        acked.lock().unwrap().extend(
+                            ack_ids
+                                .iter()
+                                .map(|ack_id| ack_id.as_str().unwrap().to_owned()),
+                        );
+                        warp::reply::json(&json!({}))
+                    }
+                });
+        let get_object = warp::path!("bucket" / "path" / "to" / "app.log")
+            .and(warp::get())
+            .map(move || {
+                warp::http::Response::builder()
+                    .header("Content-Encoding", "gzip")
+                    .header("Last-Modified", "Mon, 01 Jan 2024 00:00:00 GMT")
+                    .header("x-goog-meta-team", "storage")
+                    .body(object.clone())
+                    .unwrap()
+            });
+
+        let addr = next_addr();
+        tokio::spawn(warp::serve(pull.or(acknowledge).or(get_object)).run(addr));
+        wait_for_tcp(addr).await;
+
+        (addr, acked)
+    }
+
+    fn config(addr: SocketAddr) -> GcsSourceConfig {
+        GcsSourceConfig {
+            endpoint: format!("http://{}", addr),
+            pubsub: pubsub::Config {
+                project: "my-project".into(),
+                subscription: "logs".into(),
+                endpoint: format!("http://{}", addr),
+                client_concurrency: std::num::NonZeroUsize::new(1),
+                ..Default::default()
+            },
+            auth: GcpAuthConfig {
+                skip_authentication: true,
+                ..Default::default()
+            },
+            acknowledgements: true.into(),
+            ..Default::default()
+        }
+    }
+
+    async fn receive_object(status: EventStatus) -> (Vec<Event>, Vec<String>) {
+        let lines = ["first line", "second line"];
+        let (addr, acked) = spawn_fake_apis(&lines).await;
+
+        let events = assert_source_compliance(&SOURCE_TAGS, async move {
+            let (tx, rx) = SourceSender::new_test_finalize(status);
+            let cx = SourceContext::new_test(tx, None);
+            let source = config(addr).build(cx).await.unwrap();
+            tokio::spawn(async move { source.await.unwrap() });
+
+            collect_n(rx, lines.len()).await
+        })
+        .await;
+
+        // Give the source time to acknowledge the notification.
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        let acked = acked.lock().unwrap().clone();
+        (events, acked)
+    }
+
+    #[tokio::test]
+    async fn reads_notified_objects() {
+        let (events, acked) = receive_object(EventStatus::Delivered).await;
+
+        assert_eq!(events.len(), 2);
+        let log = events[0].as_log();
+        assert_eq!(
+            log[log_schema().message_key().unwrap().to_string()],
+            "first line".into()
+        );
+        assert_eq!(log["bucket"], "bucket".into());
+        assert_eq!(log["object"], "path/to/app.log".into());
+        assert_eq!(log["team"], "storage".into());
+        assert_eq!(
+            events[1].as_log()[log_schema().message_key().unwrap().to_string()],
+            "second line".into()
+        );
+        assert_eq!(acked, vec!["ack-1".to_owned()]);
+    }
+
+    #[tokio::test]
+    async fn does_not_acknowledge_errored_objects() {
+        let (events, acked) = receive_object(EventStatus::Errored).await;
+
+        assert_eq!(events.len(), 2);
+        assert!(acked.is_empty());
+    }
+}
@@ -0,0 +1,640 @@
+use std::{
+    collections::HashMap, future::Future, num::NonZeroUsize, panic, sync::Arc, time::Duration,
+};
+
+use bytes::Buf;
+use futures::FutureExt;
+use http::{header::CONTENT_TYPE, Request, StatusCode};
+use hyper::Body;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_with::serde_as;
+use snafu::{OptionExt, ResultExt, Snafu};
+use tokio::{pin, select, time::Instant};
+use tracing::Instrument;
+use vector_lib::codecs::decoding::FramingError;
+use vector_lib::configurable::configurable_component;
+use vector_lib::internal_event::{BytesReceived, Protocol, Registered};
+
+use super::ObjectReader;
+use crate::{
+    config::{SourceAcknowledgementsConfig, SourceContext},
+    event::BatchStatus,
+    gcp::{GcpAuthenticator, PUBSUB_URL},
+    http::{HttpClient, HttpError},
+    internal_events::{
+        EventsReceived, GcsNotificationAcknowledgeError, GcsNotificationIgnored,
+        GcsNotificationModifyAckDeadlineError, GcsNotificationProcessingError,
+        GcsNotificationReceiveError,
+    },
+    shutdown::ShutdownSignal,
+    SourceSender,
+};
+
+const MIN_ACK_DEADLINE_SECS: u64 = 10;
+const MAX_ACK_DEADLINE_SECS: u64 = 600;
+
+// The Pub/Sub API returns at most 1000 messages per pull request.
+const MAX_MESSAGES: u32 = 1000;
+
+const OBJECT_FINALIZE: &str = "OBJECT_FINALIZE";
+
+/// Pub/Sub configuration options.
+#[serde_as]
+#[configurable_component]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub(super) struct Config {
+    /// The project containing the subscription.
+    #[configurable(metadata(docs::examples = "my-log-source-project"))]
+    pub(super) project: String,
+
+    /// The subscription receiving the bucket's [object change notifications][gcs_notifications].
+    ///
+    /// [gcs_notifications]: https://cloud.google.com/storage/docs/pubsub-notifications
+    #[configurable(metadata(docs::examples = "my-vector-source-subscription"))]
+    pub(super) subscription: String,
+
+    /// The endpoint from which to pull notifications.
+    #[configurable(metadata(docs::examples = "https://us-central1-pubsub.googleapis.com"))]
+    #[serde(default = "default_endpoint")]
+    #[derivative(Default(value = "default_endpoint()"))]
+    pub(super) endpoint: String,
+
+    /// The acknowledgement deadline, in seconds, to request for each notification.
+    ///
+    /// The deadline is extended for as long as the notification's object is being read and its
+    /// events are being delivered. If Vector stops while an object is being processed, its
+    /// notification is redelivered once the deadline expires.
+    #[serde(default = "default_ack_deadline")]
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    #[derivative(Default(value = "default_ack_deadline()"))]
+    #[configurable(metadata(docs::human_name = "Acknowledgement Deadline"))]
+    pub(super) ack_deadline_secs: Duration,
+
+    /// The maximum number of notifications to pull in a single request.
+    #[serde(default = "default_max_messages")]
+    #[derivative(Default(value = "default_max_messages()"))]
+    #[configurable(metadata(docs::type_unit = "messages"))]
+    pub(super) max_messages: u32,
+
+    /// Number of concurrent tasks to create for pulling notifications.
+    ///
+    /// Defaults to the number of available CPUs on the system.
+    ///
+    /// Should not typically need to be changed, but it can sometimes be beneficial to raise this
+    /// value when there is a high rate of notifications being pushed into the subscription and the
+    /// objects being fetched are small. In these cases, system resources may not be fully utilized
+    /// without fetching more notifications per second, as the fetch spends more time waiting on
+    /// the network than processing objects.
+    #[configurable(metadata(docs::type_unit = "tasks"))]
+    #[configurable(metadata(docs::examples = 5))]
+    pub(super) client_concurrency: Option<NonZeroUsize>,
+
+    /// The amount of time, in seconds, to wait between retry attempts after an error.
+    #[serde(default = "default_retry_delay")]
+    #[serde_as(as = "serde_with::DurationSecondsWithFrac<f64>")]
+    #[derivative(Default(value = "default_retry_delay()"))]
+    #[configurable(metadata(docs::human_name = "Retry Delay"))]
+    pub(super) retry_delay_secs: Duration,
+
+    /// Whether to acknowledge the notification for an object if its events were rejected by a sink.
+    ///
+    /// If this is `false`, the notification is redelivered once its deadline expires and the
+    /// object is read again.
+    #[serde(default = "default_true")]
+    #[derivative(Default(value = "default_true()"))]
+    pub(super) ack_failed_message: bool,
+}
+
+fn default_endpoint() -> String {
+    PUBSUB_URL.to_string()
+}
+
+const fn default_ack_deadline() -> Duration {
+    Duration::from_secs(600)
+}
+
+const fn default_max_messages() -> u32 {
+    10
+}
+
+const fn default_retry_delay() -> Duration {
+    Duration::from_secs(1)
+}
+
+const fn default_true() -> bool {
+    true
+}
+
+#[derive(Debug, Snafu)]
+pub(super) enum IngestorNewError {
+    #[snafu(display(
+        "`ack_deadline_secs` is outside the valid range of {} to {}",
+        MIN_ACK_DEADLINE_SECS,
+        MAX_ACK_DEADLINE_SECS
+    ))]
+    InvalidAckDeadline,
+    #[snafu(display("Invalid value for max_messages {}", messages))]
+    InvalidNumberOfMessages { messages: u32 },
+}
+
+/// Errors from calls to the Pub/Sub API.
+#[derive(Debug, Snafu)]
+pub enum PubsubApiError {
+    #[snafu(display("Failed to build request: {}", source))]
+    BuildRequest { source: http::Error },
+    #[snafu(display("Failed to send request: {}", source))]
+    SendRequest { source: HttpError },
+    #[snafu(display("Request failed with status {}", status))]
+    UnexpectedStatus { status: StatusCode },
+    #[snafu(display("Failed to read response: {}", source))]
+    ReadResponse { source: hyper::Error },
+    #[snafu(display("Failed to parse response: {}", source))]
+    ParseResponse { source: serde_json::Error },
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(visibility(pub(super)))]
+pub enum ProcessingError {
+    #[snafu(display(
+        "Pub/Sub message with id {} is not an object notification, missing attribute {}",
+        message_id,
+        attribute
+    ))]
+    InvalidNotification {
+        message_id: String,
+        attribute: &'static str,
+    },
+    #[snafu(display("Failed to fetch gs://{}/{}: {}", bucket, object, source))]
+    GetObject {
+        source: HttpError,
+        bucket: String,
+        object: String,
+    },
+    #[snafu(display("Failed to fetch gs://{}/{}: status {}", bucket, object, status))]
+    GetObjectStatus {
+        status: StatusCode,
+        bucket: String,
+        object: String,
+    },
+    #[snafu(display("Failed to read all of gs://{}/{}: {}", bucket, object, source))]
+    ReadObject {
+        source: Box<dyn FramingError>,
+        bucket: String,
+        object: String,
+    },
+    #[snafu(display("Failed to flush all of gs://{}/{}: {}", bucket, object, source))]
+    PipelineSend {
+        source: crate::source_sender::ClosedError,
+        bucket: String,
+        object: String,
+    },
+    #[snafu(display("Sink reported an error sending events"))]
+    ErrorAcknowledgement,
+}
+
+// https://cloud.google.com/pubsub/docs/reference/rest/v1/projects.subscriptions/pull
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PullRequest {
+    max_messages: u32,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PullResponse {
+    #[serde(default)]
+    received_messages: Vec<ReceivedMessage>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ReceivedMessage {
+    ack_id: String,
+    message: PubsubMessage,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PubsubMessage {
+    #[serde(default)]
+    attributes: HashMap<String, String>,
+    message_id: String,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AcknowledgeRequest<'a> {
+    ack_ids: &'a [String],
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ModifyAckDeadlineRequest<'a> {
+    ack_ids: &'a [String],
+    ack_deadline_seconds: u64,
+}
+
+/// An object change notification, as described by the attributes of its Pub/Sub message.
+///
+/// The attributes are set regardless of the notification's payload format, so the payload itself
+/// is never read.
+// https://cloud.google.com/storage/docs/pubsub-notifications#attributes
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Notification<'a> {
+    event_type: &'a str,
+    bucket: &'a str,
+    object: &'a str,
+}
+
+impl<'a> Notification<'a> {
+    fn from_message(message: &'a PubsubMessage) -> Result<Self, ProcessingError> {
+        let attribute = |attribute: &'static str| {
+            message
+                .attributes
+                .get(attribute)
+                .map(String::as_str)
+                .context(InvalidNotificationSnafu {
+                    message_id: &message.message_id,
+                    attribute,
+                })
+        };
+        Ok(Self {
+            event_type: attribute("eventType")?,
+            bucket: attribute("bucketId")?,
+            object: attribute("objectId")?,
+        })
+    }
+}
+
+pub struct State {
+    client: HttpClient,
+    auth: GcpAuthenticator,
+    reader: ObjectReader,
+
+    subscription_url: String,
+    ack_deadline: Duration,
+    max_messages: u32,
+    client_concurrency: usize,
+    retry_delay: Duration,
+    ack_failed_message: bool,
+}
+
+impl State {
+    async fn pull(&self) -> Result<Vec<ReceivedMessage>, PubsubApiError> {
+        self.request::<_, PullResponse>(
+            "pull",
+            &PullRequest {
+                max_messages: self.max_messages,
+            },
+        )
+        .await
+        .map(|response| response.received_messages)
+    }
+
+    async fn acknowledge(&self, ack_ids: &[String]) -> Result<(), PubsubApiError> {
+        self.request::<_, serde::de::IgnoredAny>("acknowledge", &AcknowledgeRequest { ack_ids })
+            .await
+            .map(|_| ())
+    }
+
+    async fn modify_ack_deadline(&self, ack_ids: &[String]) -> Result<(), PubsubApiError> {
+        self.request::<_, serde::de::IgnoredAny>(
+            "modifyAckDeadline",
+            &ModifyAckDeadlineRequest {
+                ack_ids,
+                ack_deadline_seconds: self.ack_deadline.as_secs(),
+            },
+        )
+        .await
+        .map(|_| ())
+    }
+
+    async fn request<T: Serialize, R: DeserializeOwned>(
+        &self,
+        method: &str,
+        body: &T,
+    ) -> Result<R, PubsubApiError> {
+        let body = serde_json::to_vec(body).expect("Pub/Sub requests are always serializable");
+        let mut request = Request::post(format!("{}:{}", self.subscription_url, method))
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(body))
+            .context(BuildRequestSnafu)?;
+        self.auth.apply(&mut request);
+
+        let response = self.client.send(request).await.context(SendRequestSnafu)?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(PubsubApiError::UnexpectedStatus { status });
+        }
+
+        let body = hyper::body::aggregate(response.into_body())
+            .await
+            .context(ReadResponseSnafu)?;
+        serde_json::from_reader(body.reader()).context(ParseResponseSnafu)
+    }
+}
+
+pub(super) struct Ingestor {
+    state: Arc<State>,
+}
+
+impl Ingestor {
+    pub(super) fn new(
+        client: HttpClient,
+        auth: GcpAuthenticator,
+        reader: ObjectReader,
+        config: Config,
+    ) -> Result<Ingestor, IngestorNewError> {
+        if !(MIN_ACK_DEADLINE_SECS..=MAX_ACK_DEADLINE_SECS)
+            .contains(&config.ack_deadline_secs.as_secs())
+        {
+            return Err(IngestorNewError::InvalidAckDeadline);
+        }
+        if config.max_messages < 1 || config.max_messages > MAX_MESSAGES {
+            return Err(IngestorNewError::InvalidNumberOfMessages {
+                messages: config.max_messages,
+            });
+        }
+
+        let state = Arc::new(State {
+            client,
+            auth,
+            reader,
+
+            subscription_url: format!(
+                "{}/v1/projects/{}/subscriptions/{}",
+                config.endpoint.trim_end_matches('/'),
+                config.project,
+                config.subscription
+            ),
+            ack_deadline: config.ack_deadline_secs,
+            max_messages: config.max_messages,
+            client_concurrency: config
+                .client_concurrency
+                .map(|n| n.get())
+                .unwrap_or_else(crate::num_threads),
+            retry_delay: config.retry_delay_secs,
+            ack_failed_message: config.ack_failed_message,
+        });
+
+        Ok(Ingestor { state })
+    }
+
+    pub(super) async fn run(
+        self,
+        cx: SourceContext,
+        acknowledgements: SourceAcknowledgementsConfig,
+    ) -> Result<(), ()> {
+        let acknowledgements = cx.do_acknowledgements(acknowledgements);
+        let mut handles = Vec::new();
+        for _ in 0..self.state.client_concurrency {
+            let process = IngestorProcess::new(
+                Arc::clone(&self.state),
+                cx.out.clone(),
+                cx.shutdown.clone(),
+                acknowledgements,
+            );
+            let fut = process.run();
+            let handle = tokio::spawn(fut.in_current_span());
+            handles.push(handle);
+        }
+
+        // Wait for all of the processes to finish.  If any one of them panics, we resume
+        // that panic here to properly shutdown Vector.
+        for handle in handles.drain(..) {
+            if let Err(e) = handle.await {
+                if e.is_panic() {
+                    panic::resume_unwind(e.into_panic());
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub struct IngestorProcess {
+    state: Arc<State>,
+    out: SourceSender,
+    shutdown: ShutdownSignal,
+    acknowledgements: bool,
+    bytes_received: Registered<BytesReceived>,
+    events_received: Registered<EventsReceived>,
+}
+
+impl IngestorProcess {
+    pub fn new(
+        state: Arc<State>,
+        out: SourceSender,
+        shutdown: ShutdownSignal,
+        acknowledgements: bool,
+    ) -> Self {
+        Self {
+            state,
+            out,
+            shutdown,
+            acknowledgements,
+            bytes_received: register!(BytesReceived::from(Protocol::HTTP)),
+            events_received: register!(EventsReceived),
+        }
+    }
+
+    async fn run(mut self) {
+        let shutdown = self.shutdown.clone().fuse();
+        pin!(shutdown);
+
+        loop {
+            select! {
+                _ = &mut shutdown => break,
+                _ = self.run_once() => {},
+            }
+        }
+    }
+
+    async fn run_once(&mut self) {
+        let messages = match self.state.pull().await {
+            Ok(messages) => messages,
+            Err(error) => {
+                emit!(GcsNotificationReceiveError { error: &error });
+                tokio::time::sleep(self.state.retry_delay).await;
+                return;
+            }
+        };
+        if messages.is_empty() {
+            return;
+        }
+
+        // The deadlines of every notification in the batch are extended until the whole batch
+        // has been processed, as the notifications are only acknowledged at the end.
+        let state = Arc::clone(&self.state);
+        let ack_ids = messages
+            .iter()
+            .map(|received| received.ack_id.clone())
+            .collect::<Vec<_>>();
+        let processed =
+            extend_ack_deadlines(&state, &ack_ids, self.handle_messages(messages)).await;
+
+        if !processed.is_empty() {
+            if let Err(error) = state.acknowledge(&processed).await {
+                emit!(GcsNotificationAcknowledgeError {
+                    count: processed.len(),
+                    error: &error,
+                });
+            }
+        }
+    }
+
+    /// Processes a batch of notifications, returning the acknowledgement IDs of those that were
+    /// processed successfully.
+    async fn handle_messages(&mut self, messages: Vec<ReceivedMessage>) -> Vec<String> {
+        let mut processed = Vec::with_capacity(messages.len());
+        for received in messages {
+            match self.handle_message(&received.message).await {
+                Ok(()) => processed.push(received.ack_id),
+                Err(error) => {
+                    emit!(GcsNotificationProcessingError {
+                        message_id: &received.message.message_id,
+                        error: &error,
+                    });
+                }
+            }
+        }
+        processed
+    }
+
+    async fn handle_message(&mut self, message: &PubsubMessage) -> Result<(), ProcessingError> {
+        let notification = Notification::from_message(message)?;
+
+        if notification.event_type != OBJECT_FINALIZE {
+            emit!(GcsNotificationIgnored {
+                bucket: notification.bucket,
+                object: notification.object,
+                event_type: notification.event_type,
+            });
+            return Ok(());
+        }
+
+        let receiver = self
+            .state
+            .reader
+            .read_object(
+                notification.bucket,
+                notification.object,
+                &mut self.out,
+                self.acknowledgements,
+                &self.bytes_received,
+                &self.events_received,
+            )
+            .await?;
+
+        match receiver {
+            None => Ok(()),
+            Some(receiver) => match receiver.await {
+                BatchStatus::Delivered => Ok(()),
+                BatchStatus::Errored => Err(ProcessingError::ErrorAcknowledgement),
+                BatchStatus::Rejected => {
+                    if self.state.ack_failed_message {
+                        Ok(())
+                    } else {
+                        Err(ProcessingError::ErrorAcknowledgement)
+                    }
+                }
+            },
+        }
+    }
+}
+
+/// Drives `future` to completion, extending the deadlines of `ack_ids` at half the configured
+/// deadline so that the notifications are not redelivered while they are still being processed.
+async fn extend_ack_deadlines<F: Future>(
+    state: &State,
+    ack_ids: &[String],
+    future: F,
+) -> F::Output {
+    pin!(future);
+    let period = state.ack_deadline / 2;
+    let mut interval = tokio::time::interval_at(Instant::now() + period, period);
+
+    loop {
+        select! {
+            output = &mut future => return output,
+            _ = interval.tick() => {
+                if let Err(error) = state.modify_ack_deadline(ack_ids).await {
+                    emit!(GcsNotificationModifyAckDeadlineError {
+                        count: ack_ids.len(),
+                        error: &error,
+                    });
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_pull_response() {
+        let response: PullResponse = serde_json::from_str(
+            r#"{
+                "receivedMessages": [
+                    {
+                        "ackId": "ack-1",
+                        "message": {
+                            "data": "e30=",
+                            "attributes": {
+                                "notificationConfig": "projects/_/buckets/logs/notificationConfigs/1",
+                                "eventType": "OBJECT_FINALIZE",
+                                "payloadFormat": "JSON_API_V1",
+                                "bucketId": "logs",
+                                "objectId": "2024/01/01/app.log.gz",
+                                "objectGeneration": "1704067200000000"
+                            },
+                            "messageId": "1234",
+                            "publishTime": "2024-01-01T00:00:00.000Z"
+                        }
+                    }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(response.received_messages.len(), 1);
+        let received = &response.received_messages[0];
+        assert_eq!(received.ack_id, "ack-1");
+        assert_eq!(
+            Notification::from_message(&received.message).unwrap(),
+            Notification {
+                event_type: "OBJECT_FINALIZE",
+                bucket: "logs",
+                object: "2024/01/01/app.log.gz",
+            }
+        );
+    }
+
+    #[test]
+    fn parses_empty_pull_response() {
+        let response: PullResponse = serde_json::from_str("{}").unwrap();
+        assert!(response.received_messages.is_empty());
+    }
+
+    #[test]
+    fn rejects_messages_without_notification_attributes() {
+        let message = PubsubMessage {
+            attributes: HashMap::from([("eventType".into(), OBJECT_FINALIZE.into())]),
+            message_id: "1234".into(),
+        };
+
+        let error = Notification::from_message(&message).unwrap_err();
+        assert!(matches!(
+            error,
+            ProcessingError::InvalidNotification {
+                attribute: "bucketId",
+                ..
+            }
+        ));
+    }
+}
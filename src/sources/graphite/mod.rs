@@ -0,0 +1,593 @@
+use std::{
+    net::{Ipv4Addr, SocketAddr, SocketAddrV4},
+    sync::Arc,
+    time::Duration,
+};
+
+use bytes::Bytes;
+use futures::{StreamExt, TryFutureExt};
+use listenfd::ListenFd;
+use serde_with::serde_as;
+use smallvec::SmallVec;
+use tokio_util::udp::UdpFramed;
+use vector_lib::codecs::{
+    decoding::{self, Deserializer, Framer},
+    LengthDelimitedDecoder, NewlineDelimitedDecoder,
+};
+use vector_lib::config::LogNamespace;
+use vector_lib::configurable::configurable_component;
+use vector_lib::internal_event::{CountByteSize, InternalEventHandle as _, Registered};
+use vector_lib::ipallowlist::IpAllowlistConfig;
+use vector_lib::EstimatedJsonEncodedSizeOf;
+
+use self::parser::Parser;
+use super::util::net::{try_bind_udp_socket, SocketListenAddr, TcpNullAcker, TcpSource};
+use crate::{
+    codecs::Decoder,
+    config::{GenerateConfig, Resource, SourceConfig, SourceContext, SourceOutput},
+    event::Event,
+    internal_events::{
+        EventsReceived, SocketBindError, SocketBytesReceived, SocketMode, SocketReceiveError,
+        StreamClosedError,
+    },
+    net,
+    shutdown::ShutdownSignal,
+    tcp::TcpKeepaliveConfig,
+    tls::{MaybeTlsSettings, TlsSourceConfig},
+    SourceSender,
+};
+
+pub mod parser;
+mod pickle;
+
+/// Configuration for the `graphite` source.
+#[configurable_component(source(
+    "graphite",
+    "Collect metrics sent with the Graphite plaintext and pickle protocols."
+))]
+#[derive(Clone, Debug)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+#[configurable(metadata(docs::enum_tag_description = "The type of socket to use."))]
+#[allow(clippy::large_enum_variant)] // just used for configuration
+pub enum GraphiteConfig {
+    /// Listen on TCP.
+    Tcp(TcpConfig),
+
+    /// Listen on UDP.
+    Udp(UdpConfig),
+}
+
+/// The protocol metrics are sent with.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum GraphiteProtocol {
+    /// One metric per line, as `<path> <value> <timestamp>`.
+    #[default]
+    Plaintext,
+
+    /// Pickled lists of `(path, (timestamp, value))` tuples, each message prefixed by its length
+    /// as a four-byte big-endian integer.
+    Pickle,
+}
+
+/// UDP configuration for the `graphite` source.
+///
+/// Only the plaintext protocol is supported over UDP.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct UdpConfig {
+    #[configurable(derived)]
+    address: SocketListenAddr,
+
+    /// The size of the receive buffer used for each connection.
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    receive_buffer_bytes: Option<usize>,
+
+    #[configurable(derived)]
+    #[serde(flatten)]
+    parsing: ParsingConfig,
+}
+
+impl UdpConfig {
+    pub fn from_address(address: SocketListenAddr) -> Self {
+        Self {
+            address,
+            receive_buffer_bytes: None,
+            parsing: ParsingConfig::default(),
+        }
+    }
+}
+
+/// TCP configuration for the `graphite` source.
+#[serde_as]
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct TcpConfig {
+    #[configurable(derived)]
+    address: SocketListenAddr,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    protocol: GraphiteProtocol,
+
+    #[configurable(derived)]
+    keepalive: Option<TcpKeepaliveConfig>,
+
+    #[configurable(derived)]
+    pub permit_origin: Option<IpAllowlistConfig>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    tls: Option<TlsSourceConfig>,
+
+    /// The timeout before a connection is forcefully closed during shutdown.
+    #[serde(default = "default_shutdown_timeout_secs")]
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    #[configurable(metadata(docs::human_name = "Shutdown Timeout"))]
+    shutdown_timeout_secs: Duration,
+
+    /// The size of the receive buffer used for each connection.
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    receive_buffer_bytes: Option<usize>,
+
+    /// The maximum number of TCP connections that are allowed at any given time.
+    #[configurable(metadata(docs::type_unit = "connections"))]
+    connection_limit: Option<u32>,
+
+    #[configurable(derived)]
+    #[serde(flatten)]
+    parsing: ParsingConfig,
+}
+
+impl TcpConfig {
+    pub fn from_address(address: SocketListenAddr) -> Self {
+        Self {
+            address,
+            protocol: GraphiteProtocol::default(),
+            keepalive: None,
+            permit_origin: None,
+            tls: None,
+            shutdown_timeout_secs: default_shutdown_timeout_secs(),
+            receive_buffer_bytes: None,
+            connection_limit: None,
+            parsing: ParsingConfig::default(),
+        }
+    }
+}
+
+/// How metric paths are turned into metric names and tags.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct ParsingConfig {
+    /// Templates extracting the name and tags of metrics from their dotted paths.
+    ///
+    /// Each template has the form `[filter] template [tag=value,...]`. The template assigns a
+    /// meaning to each segment of the path: `measurement` segments make up the metric name,
+    /// `measurement*` takes all remaining segments into the name, other names set the tag of that
+    /// name to the segment, and empty parts skip the segment. The optional filter restricts the
+    /// template to paths starting with the given segments, `*` matching any segment, and the
+    /// optional tags are added to every metric the template applies to.
+    ///
+    /// The first template whose filter matches the path is used. Metrics matching no template are
+    /// named after their full path.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "servers.* .host.measurement*"))]
+    #[configurable(metadata(
+        docs::examples = "stats.* .measurement.measurement.region datacenter=us-east"
+    ))]
+    #[configurable(metadata(docs::examples = "measurement*"))]
+    templates: Vec<String>,
+
+    /// The separator used to join the `measurement` segments of a path into the metric name.
+    #[serde(default = "default_separator")]
+    #[configurable(metadata(docs::examples = "_"))]
+    separator: String,
+}
+
+impl Default for ParsingConfig {
+    fn default() -> Self {
+        Self {
+            templates: Vec::new(),
+            separator: default_separator(),
+        }
+    }
+}
+
+impl ParsingConfig {
+    fn build(&self) -> crate::Result<Arc<Parser>> {
+        Ok(Arc::new(Parser::new(&self.templates, &self.separator)?))
+    }
+}
+
+fn default_separator() -> String {
+    ".".to_owned()
+}
+
+const fn default_shutdown_timeout_secs() -> Duration {
+    Duration::from_secs(30)
+}
+
+impl GenerateConfig for GraphiteConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self::Tcp(TcpConfig::from_address(
+            SocketListenAddr::SocketAddr(SocketAddr::V4(SocketAddrV4::new(
+                Ipv4Addr::UNSPECIFIED,
+                2003,
+            ))),
+        )))
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "graphite")]
+impl SourceConfig for GraphiteConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        match self {
+            GraphiteConfig::Udp(config) => {
+                let parser = config.parsing.build()?;
+                Ok(Box::pin(graphite_udp(
+                    config.clone(),
+                    parser,
+                    cx.shutdown,
+                    cx.out,
+                )))
+            }
+            GraphiteConfig::Tcp(config) => {
+                let tls_config = config.tls.as_ref().map(|tls| tls.tls_config.clone());
+                let tls_client_metadata_key = config
+                    .tls
+                    .as_ref()
+                    .and_then(|tls| tls.client_metadata_key.clone())
+                    .and_then(|k| k.path);
+                let tls = MaybeTlsSettings::from_config(&tls_config, true)?;
+                let source = GraphiteTcpSource {
+                    parser: config.parsing.build()?,
+                    protocol: config.protocol,
+                };
+                source.run(
+                    config.address,
+                    config.keepalive,
+                    config.shutdown_timeout_secs,
+                    tls,
+                    tls_client_metadata_key,
+                    config.receive_buffer_bytes,
+                    None,
+                    cx,
+                    false.into(),
+                    config.connection_limit,
+                    config.permit_origin.clone().map(Into::into),
+                    GraphiteConfig::NAME,
+                    LogNamespace::Legacy,
+                )
+            }
+        }
+    }
+
+    fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
+        vec![SourceOutput::new_metrics()]
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        match self {
+            Self::Tcp(tcp) => vec![tcp.address.as_tcp_resource()],
+            Self::Udp(udp) => vec![udp.address.as_udp_resource()],
+        }
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+#[derive(Clone)]
+struct GraphiteDeserializer {
+    parser: Arc<Parser>,
+    protocol: GraphiteProtocol,
+    socket_mode: Option<SocketMode>,
+    events_received: Option<Registered<EventsReceived>>,
+}
+
+impl GraphiteDeserializer {
+    fn udp(parser: Arc<Parser>) -> Self {
+        Self {
+            parser,
+            protocol: GraphiteProtocol::Plaintext,
+            socket_mode: Some(SocketMode::Udp),
+            // TCP connections emit their own `EventsReceived`.
+            events_received: Some(register!(EventsReceived)),
+        }
+    }
+
+    const fn tcp(parser: Arc<Parser>, protocol: GraphiteProtocol) -> Self {
+        Self {
+            parser,
+            protocol,
+            socket_mode: None,
+            events_received: None,
+        }
+    }
+}
+
+impl decoding::format::Deserializer for GraphiteDeserializer {
+    fn parse(
+        &self,
+        bytes: Bytes,
+        _log_namespace: LogNamespace,
+    ) -> crate::Result<SmallVec<[Event; 1]>> {
+        if let Some(mode) = self.socket_mode {
+            emit!(SocketBytesReceived {
+                mode,
+                byte_size: bytes.len(),
+            });
+        }
+
+        let events: SmallVec<[Event; 1]> = match self.protocol {
+            GraphiteProtocol::Plaintext => {
+                let line = std::str::from_utf8(&bytes)?;
+                self.parser
+                    .parse_line(line)?
+                    .map(Event::Metric)
+                    .into_iter()
+                    .collect()
+            }
+            GraphiteProtocol::Pickle => pickle::decode(&bytes)?
+                .into_iter()
+                .map(|datapoint| {
+                    self.parser
+                        .metric(&datapoint.path, datapoint.value, Some(datapoint.timestamp))
+                        .map(Event::Metric)
+                })
+                .collect::<Result<_, _>>()?,
+        };
+
+        if let Some(events_received) = &self.events_received {
+            events_received.emit(CountByteSize(
+                events.len(),
+                events.estimated_json_encoded_size_of(),
+            ));
+        }
+        Ok(events)
+    }
+}
+
+async fn graphite_udp(
+    config: UdpConfig,
+    parser: Arc<Parser>,
+    shutdown: ShutdownSignal,
+    mut out: SourceSender,
+) -> Result<(), ()> {
+    let listenfd = ListenFd::from_env();
+    let socket = try_bind_udp_socket(config.address, listenfd)
+        .map_err(|error| {
+            emit!(SocketBindError {
+                mode: SocketMode::Udp,
+                error
+            })
+        })
+        .await?;
+
+    if let Some(receive_buffer_bytes) = config.receive_buffer_bytes {
+        if let Err(error) = net::set_receive_buffer_size(&socket, receive_buffer_bytes) {
+            warn!(message = "Failed configuring receive buffer size on UDP socket.", %error);
+        }
+    }
+
+    info!(
+        message = "Listening.",
+        addr = %config.address,
+        r#type = "udp"
+    );
+
+    let codec = Decoder::new(
+        Framer::NewlineDelimited(NewlineDelimitedDecoder::new()),
+        Deserializer::Boxed(Box::new(GraphiteDeserializer::udp(parser))),
+    );
+    let mut stream = UdpFramed::new(socket, codec).take_until(shutdown);
+    while let Some(frame) = stream.next().await {
+        match frame {
+            Ok(((events, _byte_size), _sock)) => {
+                let count = events.len();
+                if (out.send_batch(events).await).is_err() {
+                    emit!(StreamClosedError { count });
+                }
+            }
+            Err(error) => {
+                emit!(SocketReceiveError {
+                    mode: SocketMode::Udp,
+                    error
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[derive(Clone)]
+struct GraphiteTcpSource {
+    parser: Arc<Parser>,
+    protocol: GraphiteProtocol,
+}
+
+impl TcpSource for GraphiteTcpSource {
+    type Error = vector_lib::codecs::decoding::Error;
+    type Item = SmallVec<[Event; 1]>;
+    type Decoder = Decoder;
+    type Acker = TcpNullAcker;
+
+    fn decoder(&self) -> Self::Decoder {
+        let framer = match self.protocol {
+            GraphiteProtocol::Plaintext => Framer::NewlineDelimited(NewlineDelimitedDecoder::new()),
+            GraphiteProtocol::Pickle => Framer::LengthDelimited(LengthDelimitedDecoder::default()),
+        };
+        Decoder::new(
+            framer,
+            Deserializer::Boxed(Box::new(GraphiteDeserializer::tcp(
+                Arc::clone(&self.parser),
+                self.protocol,
+            ))),
+        )
+    }
+
+    fn build_acker(&self, _: &[Self::Item]) -> Self::Acker {
+        TcpNullAcker
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::{TimeZone, Utc};
+    use tokio::{io::AsyncWriteExt, net::UdpSocket};
+    use vector_lib::{assert_event_data_eq, metric_tags};
+
+    use super::*;
+    use crate::{
+        event::metric::{Metric, MetricKind, MetricValue},
+        test_util::{
+            collect_ready,
+            components::{assert_source_compliance, SOCKET_PUSH_SOURCE_TAGS},
+            next_addr, wait_for_tcp,
+        },
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<GraphiteConfig>();
+    }
+
+    #[test]
+    fn parses_config() {
+        let config: GraphiteConfig = toml::from_str(
+            r#"
+            mode = "tcp"
+            address = "0.0.0.0:2004"
+            protocol = "pickle"
+            templates = ["servers.* .host.measurement*"]
+            separator = "_"
+            "#,
+        )
+        .unwrap();
+        let GraphiteConfig::Tcp(config) = config else {
+            panic!("expected a TCP config");
+        };
+        assert_eq!(config.protocol, GraphiteProtocol::Pickle);
+        assert_eq!(
+            config.parsing.templates,
+            vec!["servers.* .host.measurement*"]
+        );
+        assert_eq!(config.parsing.separator, "_");
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_templates() {
+        let mut config = UdpConfig::from_address(next_addr().into());
+        config.parsing.templates = vec!["host.region".to_owned()];
+        let (tx, _rx) = SourceSender::new_test();
+        assert!(GraphiteConfig::Udp(config)
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .is_err());
+    }
+
+    fn expected_load() -> Metric {
+        Metric::new(
+            "cpu.load",
+            MetricKind::Absolute,
+            MetricValue::Gauge { value: 1.5 },
+        )
+        .with_tags(Some(metric_tags!("host" => "web01")))
+        .with_timestamp(Utc.timestamp_opt(1612325106, 0).single())
+    }
+
+    async fn run(
+        config: GraphiteConfig,
+        send: impl std::future::Future<Output = ()>,
+    ) -> Vec<Event> {
+        assert_source_compliance(&SOCKET_PUSH_SOURCE_TAGS, async move {
+            let (tx, rx) = SourceSender::new_test();
+            let source = config
+                .build(SourceContext::new_test(tx, None))
+                .await
+                .unwrap();
+            tokio::spawn(source);
+            send.await;
+            tokio::time::sleep(Duration::from_millis(250)).await;
+            collect_ready(rx).await
+        })
+        .await
+    }
+
+    fn parsing() -> ParsingConfig {
+        ParsingConfig {
+            templates: vec!["servers.* .host.measurement*".to_owned()],
+            separator: default_separator(),
+        }
+    }
+
+    #[tokio::test]
+    async fn receives_plaintext_over_tcp() {
+        let addr = next_addr();
+        let mut config = TcpConfig::from_address(addr.into());
+        config.parsing = parsing();
+
+        let events = run(GraphiteConfig::Tcp(config), async move {
+            wait_for_tcp(addr).await;
+            let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+            stream
+                .write_all(b"servers.web01.cpu.load 1.5 1612325106\nrequests 2 -1\n")
+                .await
+                .unwrap();
+        })
+        .await;
+
+        assert_eq!(events.len(), 2);
+        assert_event_data_eq!(events[0].clone().into_metric(), expected_load());
+        assert_eq!(events[1].as_metric().name(), "requests");
+        assert_eq!(events[1].as_metric().timestamp(), None);
+    }
+
+    #[tokio::test]
+    async fn receives_pickle_over_tcp() {
+        let addr = next_addr();
+        let mut config = TcpConfig::from_address(addr.into());
+        config.protocol = GraphiteProtocol::Pickle;
+        config.parsing = parsing();
+
+        let events = run(GraphiteConfig::Tcp(config), async move {
+            wait_for_tcp(addr).await;
+            // pickle.dumps([("servers.web01.cpu.load", (1612325106, 1.5))], protocol=2)
+            let message = b"\x80\x02]q\x00X\x16\x00\x00\x00servers.web01.cpu.loadq\x01J\xf2 \x1a`G?\xf8\x00\x00\x00\x00\x00\x00\x86q\x02\x86q\x03a.";
+            let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+            stream
+                .write_all(&(message.len() as u32).to_be_bytes())
+                .await
+                .unwrap();
+            stream.write_all(message).await.unwrap();
+        })
+        .await;
+
+        assert_eq!(events.len(), 1);
+        assert_event_data_eq!(events[0].clone().into_metric(), expected_load());
+    }
+
+    #[tokio::test]
+    async fn receives_plaintext_over_udp() {
+        let addr = next_addr();
+        let mut config = UdpConfig::from_address(addr.into());
+        config.parsing = parsing();
+
+        let events = run(GraphiteConfig::Udp(config), async move {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            let socket = UdpSocket::bind(next_addr()).await.unwrap();
+            socket
+                .send_to(b"servers.web01.cpu.load 1.5 1612325106\n", addr)
+                .await
+                .unwrap();
+        })
+        .await;
+
+        assert_eq!(events.len(), 1);
+    }
+}
@@ -0,0 +1,404 @@
+use chrono::{DateTime, TimeZone, Utc};
+use snafu::Snafu;
+
+use crate::event::metric::{Metric, MetricKind, MetricTags, MetricValue};
+
+/// Errors raised while compiling the configured templates.
+#[derive(Debug, Snafu)]
+pub enum TemplateError {
+    #[snafu(display("Invalid template {:?}: {}", template, reason))]
+    InvalidTemplate {
+        template: String,
+        reason: &'static str,
+    },
+}
+
+/// Errors raised while parsing a metric.
+#[derive(Debug, PartialEq, Snafu)]
+pub enum ParseError {
+    #[snafu(display("Graphite parse error: {}", reason))]
+    Malformed { reason: &'static str },
+    #[snafu(display("Graphite parse error: invalid value {:?}", value))]
+    InvalidValue { value: String },
+    #[snafu(display("Graphite parse error: invalid timestamp {:?}", timestamp))]
+    InvalidTimestamp { timestamp: String },
+}
+
+vector_lib::impl_event_data_eq!(ParseError);
+
+/// A part of a template, matched against the segment of the metric path at the same position.
+#[derive(Clone, Debug, PartialEq)]
+enum Part {
+    /// The segment is dropped.
+    Skip,
+    /// The segment is part of the metric name.
+    Measurement,
+    /// This and all following segments are part of the metric name.
+    MeasurementRest,
+    /// The segment becomes the value of the named tag.
+    Tag(String),
+}
+
+/// A template turning the dotted path of a metric into a name and tags, in the format used by
+/// the InfluxDB and Telegraf Graphite parsers: `[filter] template [tag=value,...]`.
+#[derive(Clone, Debug)]
+struct Template {
+    filter: Option<Vec<String>>,
+    parts: Vec<Part>,
+    tags: Vec<(String, String)>,
+}
+
+impl Template {
+    fn parse(template: &str) -> Result<Self, TemplateError> {
+        let invalid = |reason| TemplateError::InvalidTemplate {
+            template: template.to_owned(),
+            reason,
+        };
+
+        let fields = template.split_whitespace().collect::<Vec<_>>();
+        let (filter, parts, tags) = match fields.as_slice() {
+            [parts] => (None, *parts, None),
+            [parts, tags] if tags.contains('=') => (None, *parts, Some(*tags)),
+            [filter, parts] => (Some(*filter), *parts, None),
+            [filter, parts, tags] => (Some(*filter), *parts, Some(*tags)),
+            _ => return Err(invalid("expected `[filter] template [tags]`")),
+        };
+
+        let parts = parts
+            .split('.')
+            .map(|part| match part {
+                "" => Ok(Part::Skip),
+                "measurement" => Ok(Part::Measurement),
+                "measurement*" => Ok(Part::MeasurementRest),
+                part if part.contains('*') => Err(invalid("only `measurement*` may be greedy")),
+                tag => Ok(Part::Tag(tag.to_owned())),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if !parts
+            .iter()
+            .any(|part| matches!(part, Part::Measurement | Part::MeasurementRest))
+        {
+            return Err(invalid("a template must contain `measurement`"));
+        }
+
+        let tags = tags
+            .into_iter()
+            .flat_map(|tags| tags.split(','))
+            .map(|tag| {
+                tag.split_once('=')
+                    .filter(|(name, _)| !name.is_empty())
+                    .map(|(name, value)| (name.to_owned(), value.to_owned()))
+                    .ok_or_else(|| invalid("tags must be of the form `name=value`"))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            filter: filter.map(|filter| filter.split('.').map(str::to_owned).collect()),
+            parts,
+            tags,
+        })
+    }
+
+    /// Returns `true` if every segment of the filter matches the start of the path. A `*` segment
+    /// matches any single segment.
+    fn matches(&self, segments: &[&str]) -> bool {
+        self.filter.as_ref().map_or(true, |filter| {
+            filter.len() <= segments.len()
+                && filter
+                    .iter()
+                    .zip(segments)
+                    .all(|(filter, segment)| filter == "*" || filter == segment)
+        })
+    }
+
+    /// Builds the name and tags of a metric from its path, returning `None` if the path is too
+    /// short to contain any part of the name.
+    fn apply(&self, segments: &[&str], separator: &str) -> Option<(String, MetricTags)> {
+        let mut tags = MetricTags::default();
+        for (name, value) in &self.tags {
+            tags.replace(name.clone(), value.clone());
+        }
+
+        let mut name = Vec::new();
+        for (index, (part, segment)) in self.parts.iter().zip(segments).enumerate() {
+            match part {
+                Part::Skip => {}
+                Part::Measurement => name.push(*segment),
+                Part::MeasurementRest => {
+                    name.extend_from_slice(&segments[index..]);
+                    break;
+                }
+                Part::Tag(tag) => {
+                    tags.replace(tag.clone(), segment.to_string());
+                }
+            }
+        }
+
+        (!name.is_empty()).then(|| (name.join(separator), tags))
+    }
+}
+
+/// Turns Graphite metric paths into Vector metrics using the configured templates.
+#[derive(Clone, Debug)]
+pub struct Parser {
+    templates: Vec<Template>,
+    separator: String,
+}
+
+impl Parser {
+    pub fn new(templates: &[String], separator: &str) -> Result<Self, TemplateError> {
+        Ok(Self {
+            templates: templates
+                .iter()
+                .map(|template| Template::parse(template))
+                .collect::<Result<_, _>>()?,
+            separator: separator.to_owned(),
+        })
+    }
+
+    /// Parses a line of the plaintext protocol, `<path> <value> [<timestamp>]`, returning `None`
+    /// for blank lines.
+    pub fn parse_line(&self, line: &str) -> Result<Option<Metric>, ParseError> {
+        let mut fields = line.split_whitespace();
+        let Some(path) = fields.next() else {
+            return Ok(None);
+        };
+        let value = fields.next().ok_or(ParseError::Malformed {
+            reason: "missing value",
+        })?;
+        let timestamp = fields.next();
+        if fields.next().is_some() {
+            return Err(ParseError::Malformed {
+                reason: "too many fields",
+            });
+        }
+
+        let value = value.parse::<f64>().map_err(|_| ParseError::InvalidValue {
+            value: value.to_owned(),
+        })?;
+        let timestamp = timestamp
+            .map(|timestamp| {
+                timestamp
+                    .parse::<f64>()
+                    .map_err(|_| ParseError::InvalidTimestamp {
+                        timestamp: timestamp.to_owned(),
+                    })
+            })
+            .transpose()?;
+
+        self.metric(path, value, timestamp).map(Some)
+    }
+
+    /// Builds the metric for a path, value, and timestamp in seconds. Negative timestamps stand for
+    /// the current time, as they do in Graphite.
+    pub fn metric(
+        &self,
+        path: &str,
+        value: f64,
+        timestamp: Option<f64>,
+    ) -> Result<Metric, ParseError> {
+        // Tagged series carry their tags after the name: `name;tag1=value1;tag2=value2`.
+        let mut path_tags = path.split(';');
+        let path = path_tags.next().unwrap_or_default();
+        if path.is_empty() {
+            return Err(ParseError::Malformed {
+                reason: "empty metric path",
+            });
+        }
+
+        let segments = path.split('.').collect::<Vec<_>>();
+        let (name, mut tags) = self
+            .templates
+            .iter()
+            .find(|template| template.matches(&segments))
+            .and_then(|template| template.apply(&segments, &self.separator))
+            .unwrap_or_else(|| (path.to_owned(), MetricTags::default()));
+
+        for tag in path_tags {
+            match tag.split_once('=') {
+                Some((name, value)) if !name.is_empty() => {
+                    tags.replace(name.to_owned(), value.to_owned());
+                }
+                _ => {
+                    return Err(ParseError::Malformed {
+                        reason: "tags must be of the form `name=value`",
+                    })
+                }
+            }
+        }
+
+        let timestamp = timestamp
+            .filter(|timestamp| *timestamp >= 0.0)
+            .map(|timestamp| {
+                to_datetime(timestamp).ok_or_else(|| ParseError::InvalidTimestamp {
+                    timestamp: timestamp.to_string(),
+                })
+            })
+            .transpose()?;
+
+        Ok(
+            Metric::new(name, MetricKind::Absolute, MetricValue::Gauge { value })
+                .with_tags((!tags.is_empty()).then_some(tags))
+                .with_timestamp(timestamp),
+        )
+    }
+}
+
+fn to_datetime(timestamp: f64) -> Option<DateTime<Utc>> {
+    if !timestamp.is_finite() || timestamp > i64::MAX as f64 {
+        return None;
+    }
+    let seconds = timestamp.trunc();
+    let nanos = ((timestamp - seconds) * 1e9).round() as u32;
+    Utc.timestamp_opt(seconds as i64, nanos.min(999_999_999))
+        .single()
+}
+
+#[cfg(test)]
+mod tests {
+    use similar_asserts::assert_eq;
+    use vector_lib::assert_event_data_eq;
+    use vector_lib::metric_tags;
+
+    use super::*;
+
+    fn parser(templates: &[&str]) -> Parser {
+        let templates = templates
+            .iter()
+            .map(|template| template.to_string())
+            .collect::<Vec<_>>();
+        Parser::new(&templates, ".").unwrap()
+    }
+
+    fn gauge(name: &str, value: f64) -> Metric {
+        Metric::new(name, MetricKind::Absolute, MetricValue::Gauge { value })
+    }
+
+    #[test]
+    fn plaintext_without_templates() {
+        assert_event_data_eq!(
+            parser(&[]).parse_line("servers.web01.cpu.load 1.5 1612325106\n"),
+            Ok(Some(
+                gauge("servers.web01.cpu.load", 1.5)
+                    .with_timestamp(Utc.timestamp_opt(1612325106, 0).single())
+            ))
+        );
+    }
+
+    #[test]
+    fn plaintext_without_timestamp() {
+        let parser = parser(&[]);
+        assert_event_data_eq!(
+            parser.parse_line("requests 3"),
+            Ok(Some(gauge("requests", 3.0)))
+        );
+        assert_event_data_eq!(
+            parser.parse_line("requests 3 -1"),
+            Ok(Some(gauge("requests", 3.0)))
+        );
+        assert_eq!(parser.parse_line("  \r").unwrap(), None);
+    }
+
+    #[test]
+    fn plaintext_errors() {
+        let parser = parser(&[]);
+        assert_eq!(
+            parser.parse_line("requests").unwrap_err(),
+            ParseError::Malformed {
+                reason: "missing value"
+            }
+        );
+        assert_eq!(
+            parser.parse_line("requests many").unwrap_err(),
+            ParseError::InvalidValue {
+                value: "many".to_owned()
+            }
+        );
+        assert_eq!(
+            parser.parse_line("requests 1 now").unwrap_err(),
+            ParseError::InvalidTimestamp {
+                timestamp: "now".to_owned()
+            }
+        );
+        assert!(parser.parse_line("requests 1 2 3").is_err());
+    }
+
+    #[test]
+    fn tagged_series() {
+        assert_event_data_eq!(
+            parser(&[]).parse_line("disk.used;datacenter=dc1;rack=a1 42 -1"),
+            Ok(Some(gauge("disk.used", 42.0).with_tags(Some(
+                metric_tags!(
+                    "datacenter" => "dc1",
+                    "rack" => "a1",
+                )
+            ))))
+        );
+        assert!(parser(&[]).parse_line("disk.used;dc1 42").is_err());
+    }
+
+    #[test]
+    fn templates_extract_tags() {
+        let parser = parser(&[
+            "servers.* .host.measurement.measurement region=us-west",
+            "stats.* .measurement*",
+            "region.measurement*",
+        ]);
+
+        assert_event_data_eq!(
+            parser.parse_line("servers.web01.cpu.load 1"),
+            Ok(Some(gauge("cpu.load", 1.0).with_tags(Some(metric_tags!(
+                "host" => "web01",
+                "region" => "us-west",
+            )))))
+        );
+        assert_event_data_eq!(
+            parser.parse_line("stats.api.requests.count 2"),
+            Ok(Some(gauge("api.requests.count", 2.0)))
+        );
+        assert_event_data_eq!(
+            parser.parse_line("eu.memory.free 3"),
+            Ok(Some(gauge("memory.free", 3.0).with_tags(Some(
+                metric_tags!(
+                    "region" => "eu",
+                )
+            ))))
+        );
+    }
+
+    #[test]
+    fn templates_join_with_separator() {
+        let parser = Parser::new(&["host.measurement.measurement".to_owned()], "_").unwrap();
+        assert_event_data_eq!(
+            parser.parse_line("web01.cpu.load 1"),
+            Ok(Some(gauge("cpu_load", 1.0).with_tags(Some(metric_tags!(
+                "host" => "web01",
+            )))))
+        );
+    }
+
+    #[test]
+    fn series_tags_override_template_tags() {
+        assert_event_data_eq!(
+            parser(&["host.measurement*"]).parse_line("web01.cpu;host=web02 1"),
+            Ok(Some(gauge("cpu", 1.0).with_tags(Some(metric_tags!(
+                "host" => "web02",
+            )))))
+        );
+    }
+
+    #[test]
+    fn invalid_templates() {
+        for template in [
+            "host.region",
+            "host.measurement =us-west",
+            "host.tag*.measurement",
+            "a b c d",
+        ] {
+            assert!(
+                Parser::new(&[template.to_owned()], ".").is_err(),
+                "{template:?} should be rejected"
+            );
+        }
+    }
+}
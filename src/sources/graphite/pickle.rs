@@ -0,0 +1,601 @@
+//! A decoder for the messages of Graphite's pickle protocol.
+//!
+//! Each message is a pickled list of `(path, (timestamp, value))` tuples. Only the opcodes needed to
+//! build lists, tuples, strings, and numbers are supported: anything that would construct an
+//! arbitrary object, such as `GLOBAL` or `REDUCE`, is rejected rather than interpreted.
+
+use std::collections::HashMap;
+
+use snafu::Snafu;
+
+/// The deepest nesting of lists and tuples accepted. Messages only need three levels, and limiting
+/// the nesting keeps the recursion over decoded values bounded.
+const MAX_DEPTH: usize = 8;
+
+/// Errors raised while decoding a pickled message.
+#[derive(Debug, PartialEq, Snafu)]
+pub enum PickleError {
+    #[snafu(display("Graphite pickle error: unexpected end of message"))]
+    UnexpectedEnd,
+    #[snafu(display("Graphite pickle error: unsupported opcode {:#04x}", opcode))]
+    UnsupportedOpcode { opcode: u8 },
+    #[snafu(display("Graphite pickle error: {}", reason))]
+    Invalid { reason: &'static str },
+}
+
+vector_lib::impl_event_data_eq!(PickleError);
+
+/// A single data point of a pickled message.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Datapoint {
+    pub path: String,
+    pub timestamp: f64,
+    pub value: f64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Value {
+    None,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    String(String),
+    List(Vec<Value>),
+    Tuple(Vec<Value>),
+}
+
+impl Value {
+    fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Bool(value) => Some(f64::from(u8::from(*value))),
+            Value::Int(value) => Some(*value as f64),
+            Value::Float(value) => Some(*value),
+            Value::String(value) => value.trim().parse().ok(),
+            _ => None,
+        }
+    }
+
+    /// The number of nested lists and tuples making up this value.
+    fn depth(&self) -> usize {
+        match self {
+            Value::List(items) | Value::Tuple(items) => {
+                1 + items.iter().map(Value::depth).max().unwrap_or_default()
+            }
+            _ => 0,
+        }
+    }
+
+    /// The number of values making up this value.
+    fn size(&self) -> usize {
+        match self {
+            Value::List(items) | Value::Tuple(items) => {
+                1 + items.iter().map(Value::size).sum::<usize>()
+            }
+            _ => 1,
+        }
+    }
+
+    fn into_items(self) -> Option<Vec<Value>> {
+        match self {
+            Value::List(items) | Value::Tuple(items) => Some(items),
+            _ => None,
+        }
+    }
+}
+
+/// Decodes the data points of a pickled message.
+pub fn decode(message: &[u8]) -> Result<Vec<Datapoint>, PickleError> {
+    let invalid = |reason| PickleError::Invalid { reason };
+
+    Unpickler::new(message)
+        .load()?
+        .into_items()
+        .ok_or_else(|| invalid("message is not a list"))?
+        .into_iter()
+        .map(|item| {
+            let Some([path, datapoint]) = item.into_items().and_then(|items| items.try_into().ok())
+            else {
+                return Err(invalid("expected a `(path, (timestamp, value))` tuple"));
+            };
+            let Some([timestamp, value]) = datapoint
+                .into_items()
+                .and_then(|items| items.try_into().ok())
+            else {
+                return Err(invalid("expected a `(timestamp, value)` tuple"));
+            };
+            let Value::String(path) = path else {
+                return Err(invalid("metric path is not a string"));
+            };
+
+            Ok(Datapoint {
+                path,
+                timestamp: timestamp
+                    .as_f64()
+                    .ok_or_else(|| invalid("timestamp is not a number"))?,
+                value: value
+                    .as_f64()
+                    .ok_or_else(|| invalid("value is not a number"))?,
+            })
+        })
+        .collect()
+}
+
+struct Unpickler<'a> {
+    input: &'a [u8],
+    stack: Vec<Value>,
+    marks: Vec<usize>,
+    memo: HashMap<u32, Value>,
+    /// The number of values that may still be copied by `DUP` and `GET`. Copying values that
+    /// contain themselves would otherwise grow the message exponentially.
+    copy_budget: usize,
+}
+
+impl<'a> Unpickler<'a> {
+    fn new(input: &'a [u8]) -> Self {
+        Self {
+            input,
+            stack: Vec::new(),
+            marks: Vec::new(),
+            memo: HashMap::new(),
+            copy_budget: input.len(),
+        }
+    }
+
+    fn load(mut self) -> Result<Value, PickleError> {
+        loop {
+            let opcode = self.byte()?;
+            match opcode {
+                // PROTO
+                0x80 => {
+                    self.byte()?;
+                }
+                // FRAME
+                0x95 => {
+                    self.take(8)?;
+                }
+                // STOP
+                b'.' => return self.pop(),
+
+                // MARK, POP, POP_MARK, DUP
+                b'(' => self.marks.push(self.stack.len()),
+                b'0' => {
+                    self.pop()?;
+                }
+                b'1' => {
+                    self.pop_mark()?;
+                }
+                b'2' => {
+                    let top = self.top()?.clone();
+                    self.copy(top)?;
+                }
+
+                // NONE, NEWTRUE, NEWFALSE
+                b'N' => self.stack.push(Value::None),
+                0x88 => self.stack.push(Value::Bool(true)),
+                0x89 => self.stack.push(Value::Bool(false)),
+
+                // INT, LONG, BININT, BININT1, BININT2, LONG1, LONG4
+                b'I' => {
+                    let value = match self.line()? {
+                        "00" => Value::Bool(false),
+                        "01" => Value::Bool(true),
+                        line => Value::Int(parse_number(line)?),
+                    };
+                    self.stack.push(value);
+                }
+                b'L' => {
+                    let value = parse_number(self.line()?.trim_end_matches('L'))?;
+                    self.stack.push(Value::Int(value));
+                }
+                b'J' => {
+                    let value = i32::from_le_bytes(self.array()?);
+                    self.stack.push(Value::Int(value.into()));
+                }
+                b'K' => {
+                    let value = self.byte()?;
+                    self.stack.push(Value::Int(value.into()));
+                }
+                b'M' => {
+                    let value = u16::from_le_bytes(self.array()?);
+                    self.stack.push(Value::Int(value.into()));
+                }
+                0x8a => {
+                    let length = self.byte()?.into();
+                    let value = self.long(length)?;
+                    self.stack.push(Value::Int(value));
+                }
+                0x8b => {
+                    let length = u32::from_le_bytes(self.array()?) as usize;
+                    let value = self.long(length)?;
+                    self.stack.push(Value::Int(value));
+                }
+
+                // FLOAT, BINFLOAT
+                b'F' => {
+                    let value = parse_number(self.line()?)?;
+                    self.stack.push(Value::Float(value));
+                }
+                b'G' => {
+                    let value = f64::from_be_bytes(self.array()?);
+                    self.stack.push(Value::Float(value));
+                }
+
+                // STRING, UNICODE
+                b'S' => {
+                    let line = self.line()?;
+                    let value = line
+                        .strip_prefix('\'')
+                        .and_then(|line| line.strip_suffix('\''))
+                        .or_else(|| {
+                            line.strip_prefix('"')
+                                .and_then(|line| line.strip_suffix('"'))
+                        })
+                        .ok_or(PickleError::Invalid {
+                            reason: "unquoted string",
+                        })
+                        .and_then(unescape)?;
+                    self.stack.push(Value::String(value));
+                }
+                b'V' => {
+                    let value = unescape(self.line()?)?;
+                    self.stack.push(Value::String(value));
+                }
+                // BINSTRING, BINUNICODE, BINBYTES, SHORT_BINSTRING, SHORT_BINBYTES,
+                // SHORT_BINUNICODE, BINUNICODE8, BINBYTES8
+                b'T' | b'X' | b'B' => {
+                    let length = u32::from_le_bytes(self.array()?) as usize;
+                    self.string(length)?;
+                }
+                b'U' | b'C' | 0x8c => {
+                    let length = self.byte()?.into();
+                    self.string(length)?;
+                }
+                0x8d | 0x8e => {
+                    let length = usize::try_from(u64::from_le_bytes(self.array()?))
+                        .map_err(|_| PickleError::UnexpectedEnd)?;
+                    self.string(length)?;
+                }
+
+                // EMPTY_LIST, LIST, APPEND, APPENDS
+                b']' => self.stack.push(Value::List(Vec::new())),
+                b'l' => {
+                    let items = self.pop_mark().and_then(nestable)?;
+                    self.stack.push(Value::List(items));
+                }
+                b'a' => {
+                    let item = self.pop().map(|item| vec![item]).and_then(nestable)?;
+                    self.list()?.extend(item);
+                }
+                b'e' => {
+                    let items = self.pop_mark().and_then(nestable)?;
+                    self.list()?.extend(items);
+                }
+
+                // EMPTY_TUPLE, TUPLE, TUPLE1, TUPLE2, TUPLE3
+                b')' => self.stack.push(Value::Tuple(Vec::new())),
+                b't' => {
+                    let items = self.pop_mark().and_then(nestable)?;
+                    self.stack.push(Value::Tuple(items));
+                }
+                0x85..=0x87 => {
+                    let length = usize::from(opcode - 0x84);
+                    let start =
+                        self.stack
+                            .len()
+                            .checked_sub(length)
+                            .ok_or(PickleError::Invalid {
+                                reason: "stack underflow",
+                            })?;
+                    let items = nestable(self.stack.split_off(start))?;
+                    self.stack.push(Value::Tuple(items));
+                }
+
+                // PUT, BINPUT, LONG_BINPUT, MEMOIZE, GET, BINGET, LONG_BINGET
+                b'p' => {
+                    let index = parse_number(self.line()?)?;
+                    self.put(index)?;
+                }
+                b'q' => {
+                    let index = self.byte()?.into();
+                    self.put(index)?;
+                }
+                b'r' => {
+                    let index = u32::from_le_bytes(self.array()?);
+                    self.put(index)?;
+                }
+                0x94 => {
+                    let index = self.memo.len() as u32;
+                    self.put(index)?;
+                }
+                b'g' => {
+                    let index = parse_number(self.line()?)?;
+                    self.get(index)?;
+                }
+                b'h' => {
+                    let index = self.byte()?.into();
+                    self.get(index)?;
+                }
+                b'j' => {
+                    let index = u32::from_le_bytes(self.array()?);
+                    self.get(index)?;
+                }
+
+                opcode => return Err(PickleError::UnsupportedOpcode { opcode }),
+            }
+        }
+    }
+
+    fn take(&mut self, length: usize) -> Result<&'a [u8], PickleError> {
+        if self.input.len() < length {
+            return Err(PickleError::UnexpectedEnd);
+        }
+        let (bytes, rest) = self.input.split_at(length);
+        self.input = rest;
+        Ok(bytes)
+    }
+
+    fn byte(&mut self) -> Result<u8, PickleError> {
+        self.take(1).map(|bytes| bytes[0])
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], PickleError> {
+        self.take(N)
+            .map(|bytes| bytes.try_into().expect("length was checked"))
+    }
+
+    fn line(&mut self) -> Result<&'a str, PickleError> {
+        let end = self
+            .input
+            .iter()
+            .position(|byte| *byte == b'\n')
+            .ok_or(PickleError::UnexpectedEnd)?;
+        let line = self.take(end + 1)?;
+        std::str::from_utf8(&line[..end]).map_err(|_| PickleError::Invalid {
+            reason: "invalid UTF-8",
+        })
+    }
+
+    fn long(&mut self, length: usize) -> Result<i64, PickleError> {
+        let bytes = self.take(length)?;
+        if length > 8 {
+            return Err(PickleError::Invalid {
+                reason: "integer out of range",
+            });
+        }
+        // Little-endian two's complement, sign extended to eight bytes.
+        let fill = match bytes.last() {
+            Some(last) if last & 0x80 != 0 => 0xff,
+            _ => 0,
+        };
+        let mut value = [fill; 8];
+        value[..length].copy_from_slice(bytes);
+        Ok(i64::from_le_bytes(value))
+    }
+
+    fn string(&mut self, length: usize) -> Result<(), PickleError> {
+        let bytes = self.take(length)?;
+        let value = String::from_utf8_lossy(bytes).into_owned();
+        self.stack.push(Value::String(value));
+        Ok(())
+    }
+
+    fn top(&mut self) -> Result<&mut Value, PickleError> {
+        self.stack.last_mut().ok_or(PickleError::Invalid {
+            reason: "stack underflow",
+        })
+    }
+
+    fn pop(&mut self) -> Result<Value, PickleError> {
+        self.stack.pop().ok_or(PickleError::Invalid {
+            reason: "stack underflow",
+        })
+    }
+
+    fn pop_mark(&mut self) -> Result<Vec<Value>, PickleError> {
+        let mark = self.marks.pop().ok_or(PickleError::Invalid {
+            reason: "missing mark",
+        })?;
+        if mark > self.stack.len() {
+            return Err(PickleError::Invalid {
+                reason: "stack underflow",
+            });
+        }
+        Ok(self.stack.split_off(mark))
+    }
+
+    fn list(&mut self) -> Result<&mut Vec<Value>, PickleError> {
+        match self.top()? {
+            Value::List(items) => Ok(items),
+            _ => Err(PickleError::Invalid {
+                reason: "appending to a value that is not a list",
+            }),
+        }
+    }
+
+    fn put(&mut self, index: u32) -> Result<(), PickleError> {
+        let value = self.top()?.clone();
+        self.memo.insert(index, value);
+        Ok(())
+    }
+
+    fn get(&mut self, index: u32) -> Result<(), PickleError> {
+        let value = self.memo.get(&index).cloned().ok_or(PickleError::Invalid {
+            reason: "unknown memo entry",
+        })?;
+        self.copy(value)
+    }
+
+    fn copy(&mut self, value: Value) -> Result<(), PickleError> {
+        self.copy_budget =
+            self.copy_budget
+                .checked_sub(value.size())
+                .ok_or(PickleError::Invalid {
+                    reason: "too many copied values",
+                })?;
+        self.stack.push(value);
+        Ok(())
+    }
+}
+
+/// Checks that the items can be added to a list or tuple without exceeding `MAX_DEPTH`.
+fn nestable(items: Vec<Value>) -> Result<Vec<Value>, PickleError> {
+    if items.iter().any(|item| item.depth() >= MAX_DEPTH) {
+        return Err(PickleError::Invalid {
+            reason: "too deeply nested",
+        });
+    }
+    Ok(items)
+}
+
+fn parse_number<T: std::str::FromStr>(line: &str) -> Result<T, PickleError> {
+    line.trim().parse().map_err(|_| PickleError::Invalid {
+        reason: "invalid number",
+    })
+}
+
+/// Resolves the backslash escapes of the text opcodes.
+fn unescape(text: &str) -> Result<String, PickleError> {
+    let invalid = || PickleError::Invalid {
+        reason: "invalid escape sequence",
+    };
+
+    let mut unescaped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        let c = match chars.next().ok_or_else(invalid)? {
+            'n' => '\n',
+            'r' => '\r',
+            't' => '\t',
+            'x' => hex(&mut chars, 2).ok_or_else(invalid)?,
+            'u' => hex(&mut chars, 4).ok_or_else(invalid)?,
+            'U' => hex(&mut chars, 8).ok_or_else(invalid)?,
+            c => c,
+        };
+        unescaped.push(c);
+    }
+    Ok(unescaped)
+}
+
+fn hex(chars: &mut std::str::Chars<'_>, digits: usize) -> Option<char> {
+    let digits = chars.take(digits).collect::<String>();
+    u32::from_str_radix(&digits, 16)
+        .ok()
+        .and_then(char::from_u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use similar_asserts::assert_eq;
+
+    use super::*;
+
+    fn datapoint(path: &str, timestamp: f64, value: f64) -> Datapoint {
+        Datapoint {
+            path: path.to_owned(),
+            timestamp,
+            value,
+        }
+    }
+
+    #[test]
+    fn decodes_protocol_0() {
+        // pickle.dumps([("servers.web01.load", (1612325106, 1.5))], protocol=0), as written by
+        // Python 3 and Python 2.
+        for message in [
+            &b"(lp0\n(Vservers.web01.load\np1\n(I1612325106\nF1.5\ntp2\ntp3\na."[..],
+            &b"(lp0\n(S'servers.web01.load'\np1\n(I1612325106\nF1.5\ntp2\ntp3\na."[..],
+        ] {
+            assert_eq!(
+                decode(message),
+                Ok(vec![datapoint("servers.web01.load", 1612325106.0, 1.5)])
+            );
+        }
+    }
+
+    #[test]
+    fn decodes_protocol_2() {
+        // pickle.dumps([("a.b", (1612325106, 2)), ("a.c", (1612325106.5, -3.25))], protocol=2)
+        let message = b"\x80\x02]q\x00(X\x03\x00\x00\x00a.bq\x01J\xf2 \x1a`K\x02\x86q\x02\x86q\x03X\x03\x00\x00\x00a.cq\x04GA\xd8\x06\x88<\xa0\x00\x00G\xc0\n\x00\x00\x00\x00\x00\x00\x86q\x05\x86q\x06e.";
+        assert_eq!(
+            decode(message),
+            Ok(vec![
+                datapoint("a.b", 1612325106.0, 2.0),
+                datapoint("a.c", 1612325106.5, -3.25),
+            ])
+        );
+    }
+
+    #[test]
+    fn decodes_protocol_4_with_memo() {
+        // pickle.dumps([(path, (1, 2)), (path, (3, 4))], protocol=4), the path being memoized.
+        let message = b"\x80\x04\x95\x1d\x00\x00\x00\x00\x00\x00\x00]\x94(\x8c\x03a.b\x94K\x01K\x02\x86\x94\x86\x94h\x01K\x03K\x04\x86\x94\x86\x94e.";
+        assert_eq!(
+            decode(message),
+            Ok(vec![datapoint("a.b", 1.0, 2.0), datapoint("a.b", 3.0, 4.0)])
+        );
+    }
+
+    #[test]
+    fn decodes_long_integers() {
+        assert_eq!(
+            Unpickler::new(b"\x8a\x02\x00\xff.").load(),
+            Ok(Value::Int(-256))
+        );
+        assert_eq!(
+            Unpickler::new(b"\x80\x02\x8a\x05\x00\x00\x00\x00\x01.").load(),
+            Ok(Value::Int(1 << 32))
+        );
+    }
+
+    #[test]
+    fn rejects_recursive_copies() {
+        // Each `DUP` and `APPENDS` doubles the size of the list.
+        let mut message = b"]".to_vec();
+        for _ in 0..64 {
+            message.extend_from_slice(b"(2e");
+        }
+        message.push(b'.');
+        assert_eq!(
+            decode(&message),
+            Err(PickleError::Invalid {
+                reason: "too many copied values"
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_deep_nesting() {
+        let message = [
+            &b"]".repeat(MAX_DEPTH + 1)[..],
+            &b"a".repeat(MAX_DEPTH),
+            b".",
+        ]
+        .concat();
+        assert_eq!(
+            decode(&message),
+            Err(PickleError::Invalid {
+                reason: "too deeply nested"
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_objects() {
+        // Payloads such as pickle.dumps(os.system) rely on `GLOBAL` to load arbitrary objects.
+        assert_eq!(
+            decode(b"cposix\nsystem\np0\n."),
+            Err(PickleError::UnsupportedOpcode { opcode: b'c' })
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_messages() {
+        assert_eq!(decode(b"\x80\x02]q\x00("), Err(PickleError::UnexpectedEnd));
+        assert!(decode(b"\x80\x02K\x01.").is_err());
+        assert!(decode(b"\x80\x02]q\x00K\x01a.").is_err());
+    }
+}
@@ -0,0 +1,292 @@
+//! The `grpc` source. See [GrpcConfig].
+use std::{convert::Infallible, net::SocketAddr, path::PathBuf};
+
+use futures::FutureExt;
+use hyper::{service::make_service_fn, Server};
+use prost_reflect::{DescriptorPool, MethodDescriptor};
+use snafu::{ResultExt, Snafu};
+use tower::ServiceBuilder;
+use tracing::Span;
+use vector_lib::configurable::configurable_component;
+use vector_lib::lookup::owned_value_path;
+use vector_lib::{config::LegacyKey, config::LogNamespace, schema::Definition};
+use vrl::value::Kind;
+
+use crate::{
+    config::{
+        DataType, GenerateConfig, Resource, SourceAcknowledgementsConfig, SourceConfig,
+        SourceContext, SourceOutput,
+    },
+    serde::bool_or_struct,
+    shutdown::ShutdownSignalToken,
+    sources::{
+        util::grpc::{build_grpc_trace_layer, DecompressionAndMetricsLayer},
+        Source,
+    },
+    tls::{MaybeTlsSettings, TlsEnableableConfig},
+};
+
+mod service;
+
+use self::service::{request_path, DynamicService};
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Could not read descriptor set file {}: {}", path.display(), source))]
+    ReadDescriptorSet {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[snafu(display("Could not decode descriptor set file {}: {}", path.display(), source))]
+    DecodeDescriptorSet {
+        path: PathBuf,
+        source: prost_reflect::DescriptorError,
+    },
+    #[snafu(display("Service {} was not found in the descriptor set", service))]
+    ServiceNotFound { service: String },
+    #[snafu(display("The descriptor set does not define any services"))]
+    NoServices,
+}
+
+/// Configuration for the `grpc` source.
+#[configurable_component(source(
+    "grpc",
+    "Receive structured events from applications over gRPC, using your own protobuf service definition."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct GrpcConfig {
+    /// The socket address to listen for connections on.
+    ///
+    /// It _must_ include a port.
+    #[configurable(metadata(docs::examples = "0.0.0.0:50051"))]
+    pub address: SocketAddr,
+
+    /// The path to the protobuf descriptor set file describing the services to serve.
+    ///
+    /// The file must contain a `FileDescriptorSet`, such as the one written by
+    /// `protoc --include_imports --descriptor_set_out=<path>`.
+    #[configurable(metadata(docs::examples = "/etc/vector/ingest.desc"))]
+    pub desc_file: PathBuf,
+
+    /// The fully qualified names of the services to serve.
+    ///
+    /// If empty, every service defined in the descriptor set is served.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "mycompany.telemetry.v1.IngestService"))]
+    pub services: Vec<String>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    tls: Option<TlsEnableableConfig>,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: SourceAcknowledgementsConfig,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[serde(default)]
+    #[configurable(metadata(docs::hidden))]
+    pub log_namespace: Option<bool>,
+}
+
+impl GenerateConfig for GrpcConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"address = "0.0.0.0:50051"
+            desc_file = "/etc/vector/ingest.desc""#,
+        )
+        .unwrap()
+    }
+}
+
+impl GrpcConfig {
+    /// Loads the methods of the configured services from the descriptor set.
+    fn methods(&self) -> crate::Result<Vec<MethodDescriptor>> {
+        let bytes = std::fs::read(&self.desc_file).context(ReadDescriptorSetSnafu {
+            path: self.desc_file.clone(),
+        })?;
+        let pool = DescriptorPool::decode(bytes.as_slice()).context(DecodeDescriptorSetSnafu {
+            path: self.desc_file.clone(),
+        })?;
+
+        let services = if self.services.is_empty() {
+            pool.services().collect::<Vec<_>>()
+        } else {
+            self.services
+                .iter()
+                .map(|name| {
+                    pool.get_service_by_name(name)
+                        .ok_or_else(|| BuildError::ServiceNotFound {
+                            service: name.clone(),
+                        })
+                })
+                .collect::<Result<Vec<_>, _>>()?
+        };
+
+        if services.is_empty() {
+            return Err(BuildError::NoServices.into());
+        }
+
+        Ok(services
+            .into_iter()
+            .flat_map(|service| service.methods().collect::<Vec<_>>())
+            .collect())
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "grpc")]
+impl SourceConfig for GrpcConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<Source> {
+        let methods = self.methods()?;
+        for method in &methods {
+            debug!(message = "Serving gRPC method.", path = %request_path(method));
+        }
+
+        let tls_settings = MaybeTlsSettings::from_config(&self.tls, true)?;
+        let acknowledgements = cx.do_acknowledgements(self.acknowledgements);
+        let log_namespace = cx.log_namespace(self.log_namespace);
+
+        let service = DynamicService::new(methods, cx.out, acknowledgements, log_namespace);
+        let listener = tls_settings.bind(&self.address).await?;
+        let address = self.address;
+        let shutdown = cx.shutdown;
+
+        Ok(Box::pin(async move {
+            let span = Span::current();
+            let (tx, rx) = tokio::sync::oneshot::channel::<ShutdownSignalToken>();
+
+            let make_svc = make_service_fn(move |_conn| {
+                let service = ServiceBuilder::new()
+                    .layer(build_grpc_trace_layer(span.clone()))
+                    .layer(DecompressionAndMetricsLayer)
+                    .service(service.clone());
+                futures::future::ok::<_, Infallible>(service)
+            });
+
+            info!(%address, "Building gRPC server.");
+
+            Server::builder(hyper::server::accept::from_stream(listener.accept_stream()))
+                .http2_only(true)
+                .serve(make_svc)
+                .with_graceful_shutdown(shutdown.map(|token| tx.send(token).unwrap()))
+                .await
+                .map_err(|error| error!(message = "Source future failed.", %error))?;
+
+            drop(rx.await);
+
+            Ok(())
+        }))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
+        let log_namespace = global_log_namespace.merge(self.log_namespace);
+
+        let schema_definition = match log_namespace {
+            LogNamespace::Legacy => {
+                Definition::empty_legacy_namespace().unknown_fields(Kind::any())
+            }
+            LogNamespace::Vector => {
+                Definition::new_with_default_metadata(Kind::any_object(), [log_namespace])
+            }
+        }
+        .with_source_metadata(
+            Self::NAME,
+            Some(LegacyKey::InsertIfEmpty(owned_value_path!("grpc_method"))),
+            &owned_value_path!("method"),
+            Kind::bytes(),
+            None,
+        )
+        .with_standard_vector_source_metadata();
+
+        vec![SourceOutput::new_logs(DataType::Log, schema_definition)]
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        vec![Resource::tcp(self.address)]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, path::PathBuf};
+
+    use super::*;
+
+    fn test_desc_file() -> PathBuf {
+        PathBuf::from(env::var_os("CARGO_MANIFEST_DIR").unwrap())
+            .join("tests/data/protobuf/test_grpc_ingest.desc")
+    }
+
+    fn config(services: &[&str]) -> GrpcConfig {
+        let mut config: GrpcConfig = toml::from_str(&format!(
+            r#"address = "127.0.0.1:50051"
+            desc_file = "{}""#,
+            test_desc_file().display()
+        ))
+        .unwrap();
+        config.services = services.iter().map(|s| s.to_string()).collect();
+        config
+    }
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<GrpcConfig>();
+    }
+
+    #[test]
+    fn serves_all_methods_by_default() {
+        let paths = config(&[])
+            .methods()
+            .unwrap()
+            .iter()
+            .map(request_path)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            paths,
+            [
+                "/test_grpc_ingest.Ingest/Push",
+                "/test_grpc_ingest.Ingest/PushStream"
+            ]
+        );
+    }
+
+    #[test]
+    fn unknown_service_is_rejected() {
+        let error = config(&["test_grpc_ingest.Missing"]).methods().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Service test_grpc_ingest.Missing was not found in the descriptor set"
+        );
+    }
+
+    #[test]
+    fn output_schema_definition_vector_namespace() {
+        let definition = config(&[])
+            .outputs(LogNamespace::Vector)
+            .remove(0)
+            .schema_definition(true);
+
+        let expected_definition =
+            Definition::new_with_default_metadata(Kind::any_object(), [LogNamespace::Vector])
+                .with_metadata_field(&owned_value_path!("grpc", "method"), Kind::bytes(), None)
+                .with_metadata_field(
+                    &owned_value_path!("vector", "source_type"),
+                    Kind::bytes(),
+                    None,
+                )
+                .with_metadata_field(
+                    &owned_value_path!("vector", "ingest_timestamp"),
+                    Kind::timestamp(),
+                    None,
+                );
+
+        assert_eq!(definition, Some(expected_definition));
+    }
+}
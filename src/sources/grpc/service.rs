@@ -0,0 +1,290 @@
+use std::{
+    collections::HashMap,
+    convert::Infallible,
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use chrono::Utc;
+use futures::{stream::BoxStream, StreamExt, TryFutureExt};
+use http::{Request, Response};
+use hyper::Body;
+use prost::Message as _;
+use prost_reflect::{DynamicMessage, MessageDescriptor, MethodDescriptor};
+use tonic::{
+    body::BoxBody,
+    codec::{Codec, CompressionEncoding, DecodeBuf, Decoder, EncodeBuf, Encoder, Streaming},
+    server::{ClientStreamingService, Grpc, StreamingService},
+    Status,
+};
+use vector_lib::internal_event::{CountByteSize, InternalEventHandle as _, Registered};
+use vector_lib::lookup::path;
+use vector_lib::{
+    config::{LegacyKey, LogNamespace},
+    event::{BatchNotifier, BatchStatus, BatchStatusReceiver, Event, LogEvent},
+    EstimatedJsonEncodedSizeOf,
+};
+
+use super::GrpcConfig;
+use crate::{
+    config::SourceConfig,
+    internal_events::{EventsReceived, StreamClosedError},
+    SourceSender,
+};
+
+/// A `tonic` codec for messages whose type is only known at runtime.
+#[derive(Clone, Debug)]
+struct DynamicCodec {
+    input: MessageDescriptor,
+}
+
+impl Codec for DynamicCodec {
+    type Encode = DynamicMessage;
+    type Decode = DynamicMessage;
+    type Encoder = DynamicEncoder;
+    type Decoder = DynamicDecoder;
+
+    fn encoder(&mut self) -> Self::Encoder {
+        DynamicEncoder
+    }
+
+    fn decoder(&mut self) -> Self::Decoder {
+        DynamicDecoder(self.input.clone())
+    }
+}
+
+#[derive(Debug)]
+struct DynamicEncoder;
+
+impl Encoder for DynamicEncoder {
+    type Item = DynamicMessage;
+    type Error = Status;
+
+    fn encode(&mut self, item: Self::Item, dst: &mut EncodeBuf<'_>) -> Result<(), Self::Error> {
+        item.encode(dst)
+            .map_err(|error| Status::internal(format!("Failed to encode response: {}", error)))
+    }
+}
+
+#[derive(Debug)]
+struct DynamicDecoder(MessageDescriptor);
+
+impl Decoder for DynamicDecoder {
+    type Item = DynamicMessage;
+    type Error = Status;
+
+    fn decode(&mut self, src: &mut DecodeBuf<'_>) -> Result<Option<Self::Item>, Self::Error> {
+        DynamicMessage::decode(self.0.clone(), src)
+            .map(Some)
+            .map_err(|error| Status::invalid_argument(format!("Error parsing protobuf: {}", error)))
+    }
+}
+
+#[derive(Clone)]
+struct Pipeline {
+    out: SourceSender,
+    acknowledgements: bool,
+    log_namespace: LogNamespace,
+    events_received: Registered<EventsReceived>,
+}
+
+impl Pipeline {
+    /// Converts each request message received on the call into an event and forwards it, returning
+    /// once the client has finished sending and every event has been delivered.
+    async fn ingest(
+        mut self,
+        method: Arc<str>,
+        mut messages: Streaming<DynamicMessage>,
+    ) -> Result<(), Status> {
+        while let Some(message) = messages.message().await? {
+            let mut events = vec![self.to_event(&method, message)?];
+
+            let count = events.len();
+            let byte_size = events.estimated_json_encoded_size_of();
+            self.events_received.emit(CountByteSize(count, byte_size));
+
+            let receiver = BatchNotifier::maybe_apply_to(self.acknowledgements, &mut events);
+
+            self.out
+                .send_batch(events)
+                .map_err(|error| {
+                    let message = error.to_string();
+                    emit!(StreamClosedError { count });
+                    Status::unavailable(message)
+                })
+                .and_then(|_| handle_batch_status(receiver))
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    fn to_event(&self, method: &str, message: DynamicMessage) -> Result<Event, Status> {
+        let value = vrl::protobuf::proto_to_value(&prost_reflect::Value::Message(message), None)
+            .map_err(|error| Status::invalid_argument(error.to_string()))?;
+
+        let mut log = LogEvent::from(value);
+
+        self.log_namespace.insert_source_metadata(
+            GrpcConfig::NAME,
+            &mut log,
+            Some(LegacyKey::InsertIfEmpty(path!("grpc_method"))),
+            path!("method"),
+            method,
+        );
+        self.log_namespace.insert_standard_vector_source_metadata(
+            &mut log,
+            GrpcConfig::NAME,
+            Utc::now(),
+        );
+
+        Ok(Event::Log(log))
+    }
+}
+
+async fn handle_batch_status(receiver: Option<BatchStatusReceiver>) -> Result<(), Status> {
+    let status = match receiver {
+        Some(receiver) => receiver.await,
+        None => BatchStatus::Delivered,
+    };
+
+    match status {
+        BatchStatus::Errored => Err(Status::internal("Delivery error")),
+        BatchStatus::Rejected => Err(Status::data_loss("Delivery failed")),
+        BatchStatus::Delivered => Ok(()),
+    }
+}
+
+/// Handles a single RPC whose response is one message, i.e. unary and client streaming methods.
+///
+/// Unary requests are framed identically to a client stream carrying one message, so both are
+/// served through the client streaming handler.
+struct SingleResponse {
+    pipeline: Pipeline,
+    method: Arc<str>,
+    output: MessageDescriptor,
+}
+
+impl ClientStreamingService<DynamicMessage> for SingleResponse {
+    type Response = DynamicMessage;
+    type Future =
+        Pin<Box<dyn Future<Output = Result<tonic::Response<Self::Response>, Status>> + Send>>;
+
+    fn call(&mut self, request: tonic::Request<Streaming<DynamicMessage>>) -> Self::Future {
+        let pipeline = self.pipeline.clone();
+        let method = Arc::clone(&self.method);
+        let output = self.output.clone();
+        Box::pin(async move {
+            pipeline.ingest(method, request.into_inner()).await?;
+            Ok(tonic::Response::new(DynamicMessage::new(output)))
+        })
+    }
+}
+
+/// Handles a single RPC whose response is a stream, i.e. server and bidirectional streaming methods.
+///
+/// The source has nothing to send back, so the response stream ends as soon as the client has
+/// finished sending.
+struct StreamingResponse {
+    pipeline: Pipeline,
+    method: Arc<str>,
+}
+
+impl StreamingService<DynamicMessage> for StreamingResponse {
+    type Response = DynamicMessage;
+    type ResponseStream = BoxStream<'static, Result<DynamicMessage, Status>>;
+    type Future =
+        Pin<Box<dyn Future<Output = Result<tonic::Response<Self::ResponseStream>, Status>> + Send>>;
+
+    fn call(&mut self, request: tonic::Request<Streaming<DynamicMessage>>) -> Self::Future {
+        let pipeline = self.pipeline.clone();
+        let method = Arc::clone(&self.method);
+        Box::pin(async move {
+            pipeline.ingest(method, request.into_inner()).await?;
+            Ok(tonic::Response::new(futures::stream::empty().boxed()))
+        })
+    }
+}
+
+/// A gRPC service that accepts every method of the configured protobuf services.
+#[derive(Clone)]
+pub(super) struct DynamicService {
+    methods: Arc<HashMap<String, MethodDescriptor>>,
+    pipeline: Pipeline,
+}
+
+impl DynamicService {
+    pub(super) fn new(
+        methods: impl IntoIterator<Item = MethodDescriptor>,
+        out: SourceSender,
+        acknowledgements: bool,
+        log_namespace: LogNamespace,
+    ) -> Self {
+        let methods = methods
+            .into_iter()
+            .map(|method| (request_path(&method), method))
+            .collect();
+
+        Self {
+            methods: Arc::new(methods),
+            pipeline: Pipeline {
+                out,
+                acknowledgements,
+                log_namespace,
+                events_received: register!(EventsReceived),
+            },
+        }
+    }
+}
+
+/// The HTTP/2 path a method is served on, defined as "/" {service name} "/" {method name}.
+pub(super) fn request_path(method: &MethodDescriptor) -> String {
+    format!("/{}/{}", method.parent_service().full_name(), method.name())
+}
+
+impl tower::Service<Request<Body>> for DynamicService {
+    type Response = Response<BoxBody>;
+    type Error = Infallible;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: Request<Body>) -> Self::Future {
+        let Some(descriptor) = self.methods.get(request.uri().path()).cloned() else {
+            let status = Status::unimplemented(format!(
+                "Method {} is not part of the served services",
+                request.uri().path()
+            ));
+            return Box::pin(async move { Ok(status.to_http()) });
+        };
+
+        let pipeline = self.pipeline.clone();
+        let method: Arc<str> = descriptor.full_name().into();
+        Box::pin(async move {
+            let mut grpc = Grpc::new(DynamicCodec {
+                input: descriptor.input(),
+            })
+            .accept_compressed(CompressionEncoding::Gzip)
+            // Matches the `vector` source, which lifts the 4MB default introduced in tonic 0.9.
+            .max_decoding_message_size(usize::MAX);
+
+            let response = if descriptor.is_server_streaming() {
+                grpc.streaming(StreamingResponse { pipeline, method }, request)
+                    .await
+            } else {
+                let service = SingleResponse {
+                    pipeline,
+                    method,
+                    output: descriptor.output(),
+                };
+                grpc.client_streaming(service, request).await
+            };
+
+            Ok(response)
+        })
+    }
+}
@@ -0,0 +1,421 @@
+#[cfg(target_os = "linux")]
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+#[cfg(target_os = "linux")]
+use nvml_wrapper::{
+    enum_wrappers::device::TemperatureSensor, enums::device::UsedGpuMemory, error::NvmlError,
+    Device, Nvml,
+};
+use vector_lib::configurable::configurable_component;
+#[cfg(target_os = "linux")]
+use vector_lib::event::MetricTags;
+
+#[cfg(target_os = "linux")]
+use super::{filter_result_sync, HostMetrics, MetricsBuffer};
+
+/// Options for the GPU metrics collector.
+///
+/// This collector is only available on Linux.
+#[configurable_component]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+pub struct GpuConfig {
+    /// The vendors of the GPUs to report metrics for.
+    ///
+    /// NVIDIA GPUs are read with the NVIDIA Management Library (NVML), loaded from the driver
+    /// installed on the host. AMD GPUs are read from the sysfs interface of the `amdgpu` driver,
+    /// which is also the source of the ROCm System Management Interface.
+    #[derivative(Default(value = "default_vendors()"))]
+    #[serde(default = "default_vendors")]
+    #[configurable(metadata(docs::examples = "nvidia"))]
+    #[configurable(metadata(docs::examples = "amd"))]
+    vendors: Vec<GpuVendor>,
+
+    /// Whether to report the GPU memory and utilization of each process using a GPU.
+    ///
+    /// Only available for NVIDIA GPUs.
+    #[derivative(Default(value = "true"))]
+    #[serde(default = "crate::serde::default_true")]
+    processes: bool,
+}
+
+/// A GPU vendor.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum GpuVendor {
+    /// NVIDIA GPUs.
+    Nvidia,
+
+    /// AMD GPUs.
+    Amd,
+}
+
+fn default_vendors() -> Vec<GpuVendor> {
+    vec![GpuVendor::Nvidia]
+}
+
+/// The GPUs of the host, and the state needed to read their metrics.
+#[cfg(target_os = "linux")]
+pub(super) struct Gpus {
+    nvml: Option<Nvml>,
+    amd: bool,
+    processes: bool,
+    sysfs_root: PathBuf,
+    procfs_root: PathBuf,
+    /// The timestamp of the last process utilization sample read from each NVIDIA GPU.
+    last_samples: Mutex<HashMap<u32, u64>>,
+}
+
+#[cfg(target_os = "linux")]
+impl Gpus {
+    pub(super) fn new(config: &GpuConfig) -> Self {
+        let nvml = config
+            .vendors
+            .contains(&GpuVendor::Nvidia)
+            .then(Nvml::init)
+            .and_then(|result| {
+                filter_result_sync(result, "Failed to load the NVIDIA Management Library.")
+            });
+        let root = |var, default| {
+            std::env::var_os(var)
+                .map(PathBuf::from)
+                .unwrap_or_else(|| PathBuf::from(default))
+        };
+        Self {
+            nvml,
+            amd: config.vendors.contains(&GpuVendor::Amd),
+            processes: config.processes,
+            sysfs_root: root("SYSFS_ROOT", "/sys"),
+            procfs_root: root("PROCFS_ROOT", "/proc"),
+            last_samples: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn nvidia_metrics(&self, nvml: &Nvml, output: &mut MetricsBuffer) {
+        let Some(count) = filter_result_sync(nvml.device_count(), "Failed to count NVIDIA GPUs.")
+        else {
+            return;
+        };
+        for index in 0..count {
+            let Some(device) =
+                filter_result_sync(nvml.device_by_index(index), "Failed to load NVIDIA GPU.")
+            else {
+                continue;
+            };
+
+            let mut tags = MetricTags::default();
+            tags.replace("device".into(), index.to_string());
+            tags.replace("vendor".into(), "nvidia".to_string());
+            if let Some(model) = supported(device.name(), "Failed to load NVIDIA GPU name.") {
+                tags.replace("model".into(), model);
+            }
+            if let Some(uuid) = supported(device.uuid(), "Failed to load NVIDIA GPU UUID.") {
+                tags.replace("uuid".into(), uuid);
+            }
+
+            if let Some(utilization) = supported(
+                device.utilization_rates(),
+                "Failed to load NVIDIA GPU utilization.",
+            ) {
+                output.gauge(
+                    "gpu_utilization_ratio",
+                    f64::from(utilization.gpu) / 100.0,
+                    tags.clone(),
+                );
+                output.gauge(
+                    "gpu_memory_utilization_ratio",
+                    f64::from(utilization.memory) / 100.0,
+                    tags.clone(),
+                );
+            }
+            if let Some(memory) = supported(
+                device.memory_info(),
+                "Failed to load NVIDIA GPU memory info.",
+            ) {
+                output.gauge("gpu_memory_total_bytes", memory.total as f64, tags.clone());
+                output.gauge("gpu_memory_used_bytes", memory.used as f64, tags.clone());
+                output.gauge("gpu_memory_free_bytes", memory.free as f64, tags.clone());
+            }
+            if let Some(temperature) = supported(
+                device.temperature(TemperatureSensor::Gpu),
+                "Failed to load NVIDIA GPU temperature.",
+            ) {
+                output.gauge(
+                    "gpu_temperature_celsius",
+                    f64::from(temperature),
+                    tags.clone(),
+                );
+            }
+            if let Some(power) = supported(
+                device.power_usage(),
+                "Failed to load NVIDIA GPU power usage.",
+            ) {
+                // NVML reports the power usage in milliwatts.
+                output.gauge("gpu_power_watts", f64::from(power) / 1000.0, tags.clone());
+            }
+
+            if self.processes {
+                self.nvidia_process_metrics(index, &device, output, &tags);
+            }
+        }
+    }
+
+    fn nvidia_process_metrics(
+        &self,
+        index: u32,
+        device: &Device,
+        output: &mut MetricsBuffer,
+        tags: &MetricTags,
+    ) {
+        // Processes using the GPU for both compute and graphics are listed twice, with the same
+        // memory usage.
+        let mut memory = HashMap::<u32, Option<u64>>::new();
+        let compute = supported(
+            device.running_compute_processes(),
+            "Failed to load NVIDIA GPU compute processes.",
+        );
+        let graphics = supported(
+            device.running_graphics_processes(),
+            "Failed to load NVIDIA GPU graphics processes.",
+        );
+        for process in compute.into_iter().chain(graphics).flatten() {
+            let used = match process.used_gpu_memory {
+                UsedGpuMemory::Used(bytes) => Some(bytes),
+                UsedGpuMemory::Unavailable => None,
+            };
+            let entry = memory.entry(process.pid).or_default();
+            *entry = (*entry).max(used);
+        }
+
+        let mut utilization = HashMap::new();
+        let mut last_samples = self.last_samples.lock().expect("poisoned lock");
+        let last_sample = last_samples.get(&index).copied();
+        let samples = match device.process_utilization_stats(last_sample) {
+            // No process used the GPU since the last sample.
+            Err(NvmlError::NotFound) => Vec::new(),
+            result => supported(result, "Failed to load NVIDIA GPU process utilization.")
+                .unwrap_or_default(),
+        };
+        if let Some(latest) = samples.iter().map(|sample| sample.timestamp).max() {
+            last_samples.insert(index, latest);
+        }
+        drop(last_samples);
+        // Samples are returned from the oldest to the most recent.
+        for sample in samples {
+            utilization.insert(sample.pid, sample.sm_util);
+        }
+
+        for (pid, used) in memory {
+            let mut tags = tags.clone();
+            tags.replace("pid".into(), pid.to_string());
+            if let Some(name) = process_name(&self.procfs_root, pid) {
+                tags.replace("process_name".into(), name);
+            }
+            if let Some(used) = used {
+                output.gauge("gpu_process_memory_used_bytes", used as f64, tags.clone());
+            }
+            if let Some(sm_util) = utilization.get(&pid) {
+                output.gauge(
+                    "gpu_process_utilization_ratio",
+                    f64::from(*sm_util) / 100.0,
+                    tags,
+                );
+            }
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl HostMetrics {
+    pub(super) async fn gpu_metrics(&self, output: &mut MetricsBuffer) {
+        if let Some(gpus) = &self.gpus {
+            output.name = "gpu";
+            if let Some(nvml) = &gpus.nvml {
+                gpus.nvidia_metrics(nvml, output);
+            }
+            if gpus.amd {
+                amd_metrics(&gpus.sysfs_root, output);
+            }
+        }
+    }
+}
+
+/// Reports the metrics of the GPUs of the `amdgpu` driver, from its sysfs interface.
+#[cfg(target_os = "linux")]
+fn amd_metrics(sysfs_root: &Path, output: &mut MetricsBuffer) {
+    let drm = sysfs_root.join("class/drm");
+    let Some(entries) = filter_result_sync(std::fs::read_dir(&drm), "Failed to list DRM devices.")
+    else {
+        return;
+    };
+    // Only the cards themselves, not their connectors such as `card0-DP-1`.
+    let mut cards = entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter_map(|name| {
+            name.strip_prefix("card")
+                .and_then(|index| index.parse::<u32>().ok())
+        })
+        .collect::<Vec<_>>();
+    cards.sort_unstable();
+
+    for index in cards {
+        let device = drm.join(format!("card{}", index)).join("device");
+        // The PCI vendor ID of AMD.
+        if read_string(&device.join("vendor")).as_deref() != Some("0x1002") {
+            continue;
+        }
+
+        let mut tags = MetricTags::default();
+        tags.replace("device".into(), index.to_string());
+        tags.replace("vendor".into(), "amd".to_string());
+        if let Some(model) = read_string(&device.join("product_name")) {
+            tags.replace("model".into(), model);
+        }
+        if let Some(uuid) = read_string(&device.join("unique_id")) {
+            tags.replace("uuid".into(), uuid);
+        }
+
+        if let Some(busy) = read_number(&device.join("gpu_busy_percent")) {
+            output.gauge("gpu_utilization_ratio", busy / 100.0, tags.clone());
+        }
+        if let Some(busy) = read_number(&device.join("mem_busy_percent")) {
+            output.gauge("gpu_memory_utilization_ratio", busy / 100.0, tags.clone());
+        }
+        let total = read_number(&device.join("mem_info_vram_total"));
+        let used = read_number(&device.join("mem_info_vram_used"));
+        if let Some(total) = total {
+            output.gauge("gpu_memory_total_bytes", total, tags.clone());
+        }
+        if let Some(used) = used {
+            output.gauge("gpu_memory_used_bytes", used, tags.clone());
+        }
+        if let (Some(total), Some(used)) = (total, used) {
+            output.gauge("gpu_memory_free_bytes", total - used, tags.clone());
+        }
+
+        if let Some(hwmon) = hwmon(&device) {
+            // The temperature is reported in millidegrees Celsius.
+            if let Some(temperature) = read_number(&hwmon.join("temp1_input")) {
+                output.gauge(
+                    "gpu_temperature_celsius",
+                    temperature / 1000.0,
+                    tags.clone(),
+                );
+            }
+            // The power is reported in microwatts, averaged by older drivers.
+            let power = read_number(&hwmon.join("power1_input"))
+                .or_else(|| read_number(&hwmon.join("power1_average")));
+            if let Some(power) = power {
+                output.gauge("gpu_power_watts", power / 1_000_000.0, tags.clone());
+            }
+        }
+    }
+}
+
+/// Returns the hardware monitoring directory of a device.
+#[cfg(target_os = "linux")]
+fn hwmon(device: &Path) -> Option<PathBuf> {
+    std::fs::read_dir(device.join("hwmon"))
+        .ok()?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .find(|path| path.is_dir())
+}
+
+#[cfg(target_os = "linux")]
+fn process_name(procfs_root: &Path, pid: u32) -> Option<String> {
+    read_string(&procfs_root.join(pid.to_string()).join("comm"))
+}
+
+#[cfg(target_os = "linux")]
+fn read_string(path: &Path) -> Option<String> {
+    let value = std::fs::read_to_string(path).ok()?;
+    let value = value.trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+#[cfg(target_os = "linux")]
+fn read_number(path: &Path) -> Option<f64> {
+    read_string(path)?.parse().ok()
+}
+
+/// Discards the metrics a GPU does not support, and reports the other errors.
+#[cfg(target_os = "linux")]
+fn supported<T>(result: Result<T, NvmlError>, message: &'static str) -> Option<T> {
+    match result {
+        Err(NvmlError::NotSupported) => None,
+        result => filter_result_sync(result, message),
+    }
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use std::fs;
+
+    use similar_asserts::assert_eq;
+
+    use super::{
+        super::{tests::count_tag, MetricsBuffer},
+        *,
+    };
+
+    fn write(path: &Path, content: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn generates_amd_metrics() {
+        let sysfs = tempfile::tempdir().unwrap();
+        let card = sysfs.path().join("class/drm/card1/device");
+        write(&card.join("vendor"), "0x1002\n");
+        write(&card.join("product_name"), "Instinct MI210\n");
+        write(&card.join("gpu_busy_percent"), "42\n");
+        write(&card.join("mem_busy_percent"), "5\n");
+        write(&card.join("mem_info_vram_total"), "1000\n");
+        write(&card.join("mem_info_vram_used"), "250\n");
+        write(&card.join("hwmon/hwmon3/temp1_input"), "51000\n");
+        write(&card.join("hwmon/hwmon3/power1_average"), "95000000\n");
+        // Connectors and the cards of other vendors are ignored.
+        fs::create_dir_all(sysfs.path().join("class/drm/card1-DP-1")).unwrap();
+        write(
+            &sysfs.path().join("class/drm/card0/device/vendor"),
+            "0x8086\n",
+        );
+
+        let mut buffer = MetricsBuffer::new(None);
+        amd_metrics(sysfs.path(), &mut buffer);
+        let metrics = buffer.metrics;
+
+        let gauges = metrics
+            .iter()
+            .map(|metric| (metric.name(), metric.value().clone()))
+            .collect::<Vec<_>>();
+        let gauge = |value| crate::event::MetricValue::Gauge { value };
+        assert_eq!(
+            gauges,
+            vec![
+                ("gpu_utilization_ratio", gauge(0.42)),
+                ("gpu_memory_utilization_ratio", gauge(0.05)),
+                ("gpu_memory_total_bytes", gauge(1000.0)),
+                ("gpu_memory_used_bytes", gauge(250.0)),
+                ("gpu_memory_free_bytes", gauge(750.0)),
+                ("gpu_temperature_celsius", gauge(51.0)),
+                ("gpu_power_watts", gauge(95.0)),
+            ]
+        );
+        assert_eq!(count_tag(&metrics, "uuid"), 0);
+        for metric in &metrics {
+            let tags = metric.tags().unwrap();
+            assert_eq!(tags.get("device"), Some("1"));
+            assert_eq!(tags.get("vendor"), Some("amd"));
+            assert_eq!(tags.get("model"), Some("Instinct MI210"));
+        }
+    }
+}
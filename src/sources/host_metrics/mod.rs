@@ -32,6 +32,7 @@ mod cgroups;
 mod cpu;
 mod disk;
 mod filesystem;
+mod gpu;
 mod memory;
 mod network;
 
@@ -55,6 +56,11 @@ pub enum Collector {
     /// Metrics related to filesystem space utilization.
     Filesystem,
 
+    /// Metrics related to GPU utilization.
+    ///
+    /// Only available on Linux. Not collected by default.
+    Gpu,
+
     /// Metrics related to the system load average.
     Load,
 
@@ -122,6 +128,10 @@ pub struct HostMetricsConfig {
     #[serde(default)]
     pub filesystem: filesystem::FilesystemConfig,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    pub gpu: gpu::GpuConfig,
+
     #[configurable(derived)]
     #[serde(default)]
     pub network: network::NetworkConfig,
@@ -170,12 +180,13 @@ pub fn default_namespace() -> Option<String> {
     Some(String::from("host"))
 }
 
-const fn example_collectors() -> [&'static str; 8] {
+const fn example_collectors() -> [&'static str; 9] {
     [
         "cgroups",
         "cpu",
         "disk",
         "filesystem",
+        "gpu",
         "load",
         "host",
         "memory",
@@ -261,6 +272,9 @@ impl SourceConfig for HostMetricsConfig {
             if self.cgroups.is_some() || self.has_collector(Collector::CGroups) {
                 return Err("CGroups collector is only available on Linux systems".into());
             }
+            if self.has_collector(Collector::Gpu) {
+                return Err("GPU collector is only available on Linux systems".into());
+            }
         }
 
         let mut config = self.clone();
@@ -317,6 +331,8 @@ pub struct HostMetrics {
     config: HostMetricsConfig,
     #[cfg(target_os = "linux")]
     root_cgroup: Option<cgroups::CGroupRoot>,
+    #[cfg(target_os = "linux")]
+    gpus: Option<gpu::Gpus>,
     events_received: Registered<EventsReceived>,
 }
 
@@ -333,9 +349,13 @@ impl HostMetrics {
     pub fn new(config: HostMetricsConfig) -> Self {
         let cgroups = config.cgroups.clone().unwrap_or_default();
         let root_cgroup = cgroups::CGroupRoot::new(&cgroups);
+        let gpus = config
+            .has_collector(Collector::Gpu)
+            .then(|| gpu::Gpus::new(&config.gpu));
         Self {
             config,
             root_cgroup,
+            gpus,
             events_received: register!(EventsReceived),
         }
     }
@@ -360,6 +380,10 @@ impl HostMetrics {
         if self.config.has_collector(Collector::Filesystem) {
             self.filesystem_metrics(&mut buffer).await;
         }
+        #[cfg(target_os = "linux")]
+        if self.config.has_collector(Collector::Gpu) {
+            self.gpu_metrics(&mut buffer).await;
+        }
         if self.config.has_collector(Collector::Load) {
             self.loadavg_metrics(&mut buffer).await;
         }
@@ -7,21 +7,23 @@ use futures_util::FutureExt;
 use http::{response::Parts, Uri};
 use serde_with::serde_as;
 use snafu::ResultExt;
-use std::{collections::HashMap, time::Duration};
+use std::{collections::HashMap, path::PathBuf, sync::Arc, time::Duration};
 use tokio_util::codec::Decoder as _;
 
+use super::pagination::{IncrementalConfig, Pagination, PaginationConfig, Watermark};
 use crate::sources::util::http_client;
 use crate::{
     codecs::{Decoder, DecodingConfig},
     config::{SourceConfig, SourceContext},
     http::Auth,
+    internal_events::HttpClientPaginationError,
     serde::{default_decoding, default_framing_message_based},
     sources,
     sources::util::{
         http::HttpMethod,
         http_client::{
-            build_url, call, default_interval, default_timeout, warn_if_interval_too_low,
-            GenericHttpClientInputs, HttpClientBuilder,
+            build_url, call, default_interval, default_timeout, static_urls,
+            warn_if_interval_too_low, GenericHttpClientInputs, HttpClientBuilder,
         },
     },
     tls::{TlsConfig, TlsSettings},
@@ -112,12 +114,33 @@ pub struct HttpClientConfig {
     #[configurable(derived)]
     pub auth: Option<Auth>,
 
+    #[configurable(derived)]
+    pub pagination: Option<PaginationConfig>,
+
+    #[configurable(derived)]
+    pub incremental: Option<IncrementalConfig>,
+
+    /// The directory used to persist the watermark of incremental scrapes.
+    ///
+    /// By default, the [global `data_dir` option][global_data_dir] is used.
+    /// Make sure the running user has write permissions to this directory.
+    ///
+    /// If this directory is specified, then Vector attempts to create it.
+    ///
+    /// [global_data_dir]: https://vector.dev/docs/reference/configuration/global-options/#data_dir
+    #[configurable(metadata(docs::examples = "/var/lib/vector"))]
+    #[configurable(metadata(docs::human_name = "Data Directory"))]
+    pub data_dir: Option<PathBuf>,
+
     /// The namespace to use for logs. This overrides the global setting.
     #[configurable(metadata(docs::hidden))]
     #[serde(default)]
     pub log_namespace: Option<bool>,
 }
 
+/// The name of the file holding the watermark of incremental scrapes in the data directory.
+const WATERMARK_FILENAME: &str = "watermark";
+
 const fn default_http_method() -> HttpMethod {
     HttpMethod::Get
 }
@@ -163,6 +186,9 @@ impl Default for HttpClientConfig {
             method: default_http_method(),
             tls: None,
             auth: None,
+            pagination: None,
+            incremental: None,
+            data_dir: None,
             log_namespace: None,
         }
     }
@@ -184,6 +210,24 @@ impl SourceConfig for HttpClientConfig {
 
         let tls = TlsSettings::from_options(&self.tls)?;
 
+        let pagination = self
+            .pagination
+            .as_ref()
+            .map(PaginationConfig::build)
+            .transpose()?;
+
+        let (urls, watermark) = match &self.incremental {
+            Some(incremental) => {
+                let mut checkpoint_path = cx
+                    .globals
+                    .resolve_and_make_data_subdir(self.data_dir.as_ref(), cx.key.id())?;
+                checkpoint_path.push(WATERMARK_FILENAME);
+                let watermark = Watermark::new(incremental, urls[0].clone(), checkpoint_path)?;
+                (watermark.urls(), Some(Arc::new(watermark)))
+            }
+            None => (static_urls(urls), None),
+        };
+
         let log_namespace = cx.log_namespace(self.log_namespace);
 
         // build the decoder
@@ -195,6 +239,9 @@ impl SourceConfig for HttpClientConfig {
         let context = HttpClientContext {
             decoder,
             log_namespace,
+            pagination,
+            watermark,
+            scrape: Scrape::default(),
         };
 
         warn_if_interval_too_low(self.timeout, self.interval);
@@ -251,6 +298,17 @@ impl HttpClientConfig {
 pub struct HttpClientContext {
     pub decoder: Decoder,
     pub log_namespace: LogNamespace,
+    pagination: Option<Pagination>,
+    watermark: Option<Arc<Watermark>>,
+    scrape: Scrape,
+}
+
+/// The state of a scrape, across the pages of its response.
+#[derive(Clone, Default)]
+struct Scrape {
+    first_url: Option<Uri>,
+    pages: usize,
+    watermark: Option<String>,
 }
 
 impl HttpClientContext {
@@ -280,9 +338,14 @@ impl HttpClientContext {
 impl HttpClientBuilder for HttpClientContext {
     type Context = HttpClientContext;
 
-    /// No additional context from request data is needed from this particular client.
-    fn build(&self, _uri: &Uri) -> Self::Context {
-        self.clone()
+    /// Starts a new scrape of `uri`.
+    fn build(&self, uri: &Uri) -> Self::Context {
+        let mut context = self.clone();
+        context.scrape = Scrape {
+            first_url: Some(uri.clone()),
+            ..Default::default()
+        };
+        context
     }
 }
 
@@ -295,9 +358,44 @@ impl http_client::HttpClientContext for HttpClientContext {
 
         let events = self.decode_events(&mut buf);
 
+        if let Some(watermark) = &self.watermark {
+            self.scrape.watermark = watermark.highest(self.scrape.watermark.take(), &events);
+        }
+
         Some(events)
     }
 
+    /// Finds the next page of the response per the pagination configured, and advances the
+    /// watermark once the last page has been received.
+    fn next_page(&mut self, url: &Uri, header: &Parts, body: &Bytes) -> Option<Uri> {
+        self.scrape.pages += 1;
+        let next_page = match &self.pagination {
+            Some(pagination) if self.scrape.pages < pagination.max_pages() => {
+                let first_url = self.scrape.first_url.as_ref().unwrap_or(url);
+                match pagination.next_page(first_url, url, header, body) {
+                    Ok(next_page) => next_page,
+                    Err(error) => {
+                        // The remaining pages are unknown, so the watermark cannot advance.
+                        emit!(HttpClientPaginationError {
+                            error,
+                            url: url.to_string(),
+                        });
+                        return None;
+                    }
+                }
+            }
+            _ => None,
+        };
+
+        if next_page.is_none() {
+            if let (Some(watermark), Some(value)) = (&self.watermark, self.scrape.watermark.take())
+            {
+                watermark.advance(value);
+            }
+        }
+        next_page
+    }
+
     /// Enriches events with source_type, timestamp
     fn enrich_events(&mut self, events: &mut Vec<Event>) {
         let now = Utc::now();
@@ -61,6 +61,9 @@ async fn invalid_endpoint() {
         method: HttpMethod::Get,
         auth: None,
         tls: None,
+        pagination: None,
+        incremental: None,
+        data_dir: None,
         log_namespace: None,
     })
     .await;
@@ -80,6 +83,9 @@ async fn collected_logs_bytes() {
         method: HttpMethod::Get,
         auth: None,
         tls: None,
+        pagination: None,
+        incremental: None,
+        data_dir: None,
         log_namespace: None,
     })
     .await;
@@ -105,6 +111,9 @@ async fn collected_logs_json() {
         method: HttpMethod::Get,
         auth: None,
         tls: None,
+        pagination: None,
+        incremental: None,
+        data_dir: None,
         log_namespace: None,
     })
     .await;
@@ -130,6 +139,9 @@ async fn collected_metrics_native_json() {
         method: HttpMethod::Get,
         auth: None,
         tls: None,
+        pagination: None,
+        incremental: None,
+        data_dir: None,
         log_namespace: None,
     })
     .await;
@@ -160,6 +172,9 @@ async fn collected_trace_native_json() {
         method: HttpMethod::Get,
         auth: None,
         tls: None,
+        pagination: None,
+        incremental: None,
+        data_dir: None,
         log_namespace: None,
     })
     .await;
@@ -185,6 +200,9 @@ async fn unauthorized_no_auth() {
         method: HttpMethod::Get,
         auth: None,
         tls: None,
+        pagination: None,
+        incremental: None,
+        data_dir: None,
         log_namespace: None,
     })
     .await;
@@ -207,6 +225,9 @@ async fn unauthorized_wrong_auth() {
             user: "white_rabbit".to_string(),
             password: "morpheus".to_string().into(),
         }),
+        pagination: None,
+        incremental: None,
+        data_dir: None,
         log_namespace: None,
     })
     .await;
@@ -229,6 +250,9 @@ async fn authorized() {
             user: "user".to_string(),
             password: "pass".to_string().into(),
         }),
+        pagination: None,
+        incremental: None,
+        data_dir: None,
         log_namespace: None,
     })
     .await;
@@ -251,6 +275,9 @@ async fn tls_invalid_ca() {
             ..Default::default()
         }),
         auth: None,
+        pagination: None,
+        incremental: None,
+        data_dir: None,
         log_namespace: None,
     })
     .await;
@@ -273,6 +300,9 @@ async fn tls_valid() {
             ..Default::default()
         }),
         auth: None,
+        pagination: None,
+        incremental: None,
+        data_dir: None,
         log_namespace: None,
     })
     .await;
@@ -293,6 +323,9 @@ async fn shutdown() {
         method: HttpMethod::Get,
         tls: None,
         auth: None,
+        pagination: None,
+        incremental: None,
+        data_dir: None,
         log_namespace: None,
     };
 
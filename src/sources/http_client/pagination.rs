@@ -0,0 +1,566 @@
+//! Pagination and incremental scrapes of the `http_client` source.
+
+use std::{
+    cmp::Ordering,
+    collections::HashMap,
+    fs, io,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+
+use bytes::Bytes;
+use chrono::{DateTime, SecondsFormat};
+use http::{header::LINK, response::Parts, Uri};
+use tokio::sync::watch;
+use vector_lib::configurable::configurable_component;
+use vector_lib::{compile_vrl, lookup::lookup_v2::ConfigTargetPath, TimeZone};
+use vrl::compiler::{runtime::Runtime, CompilationResult, CompileConfig, Program, TypeState};
+use vrl::{
+    compiler::TargetValue,
+    diagnostic::Formatter,
+    value::{Secrets, Value},
+};
+
+use crate::{
+    event::Event, internal_events::HttpClientWatermarkSetError,
+    sources::util::http_client::build_url,
+};
+
+/// Configuration of the pagination of the HTTP responses.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct PaginationConfig {
+    #[serde(flatten)]
+    pub strategy: PaginationStrategy,
+
+    /// The maximum number of pages requested on each scrape.
+    ///
+    /// Once the limit is reached, the scrape ends as if the last page had been received.
+    #[serde(default = "default_max_pages")]
+    pub max_pages: usize,
+}
+
+const fn default_max_pages() -> usize {
+    100
+}
+
+/// The strategy used to find the next page of a response.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(tag = "strategy", rename_all = "snake_case")]
+#[configurable(metadata(
+    docs::enum_tag_description = "The strategy used to find the next page of a response."
+))]
+pub enum PaginationStrategy {
+    /// Follows the URL of the `next` relation of the `Link` response header, as sent by the
+    /// GitHub and Okta APIs.
+    LinkHeader,
+
+    /// Extracts the cursor of the next page from the response body with a VRL expression.
+    Cursor {
+        /// The VRL expression extracting the cursor of the next page.
+        ///
+        /// The expression is evaluated against the response body, parsed as JSON when possible.
+        /// It must return the cursor as a string or an integer, or `null` once the last page has
+        /// been received.
+        #[configurable(metadata(docs::examples = ".next_cursor"))]
+        #[configurable(metadata(docs::examples = ".nextRecordsUrl"))]
+        expression: String,
+
+        /// The query string parameter in which the cursor is sent.
+        ///
+        /// If not set, the cursor is requested as the URL of the next page, which can be relative
+        /// to the URL of the current page.
+        #[configurable(metadata(docs::examples = "cursor"))]
+        query_parameter: Option<String>,
+    },
+}
+
+/// Configuration of incremental scrapes.
+///
+/// The highest value of `field` in the events of a scrape is sent in the `query_parameter` of the
+/// following scrapes, so that only new events are requested.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct IncrementalConfig {
+    /// The field of the events holding the watermark.
+    ///
+    /// Once all the pages of a scrape have been received, the highest value of this field becomes
+    /// the watermark, and is persisted in the data directory. Values are compared as numbers, or
+    /// as RFC 3339 timestamps, when both are, and as strings otherwise.
+    #[configurable(metadata(docs::examples = "published"))]
+    #[configurable(metadata(docs::examples = "updated_at"))]
+    pub field: ConfigTargetPath,
+
+    /// The query string parameter in which the watermark is sent.
+    #[configurable(metadata(docs::examples = "since"))]
+    #[configurable(metadata(docs::examples = "updated_since"))]
+    pub query_parameter: String,
+
+    /// The watermark sent until one has been collected.
+    ///
+    /// If not set, the query string parameter is only sent once a watermark has been collected.
+    #[configurable(metadata(docs::examples = "2024-01-01T00:00:00Z"))]
+    pub initial_value: Option<String>,
+}
+
+impl PaginationConfig {
+    pub(super) fn build(&self) -> crate::Result<Pagination> {
+        let strategy = match &self.strategy {
+            PaginationStrategy::LinkHeader => Strategy::LinkHeader,
+            PaginationStrategy::Cursor {
+                expression,
+                query_parameter,
+            } => {
+                let functions = vrl::stdlib::all()
+                    .into_iter()
+                    .chain(vector_vrl_functions::all())
+                    .collect::<Vec<_>>();
+
+                let mut config = CompileConfig::default();
+                config.set_read_only();
+
+                let CompilationResult {
+                    program,
+                    warnings,
+                    config: _,
+                } = compile_vrl(expression, &functions, &TypeState::default(), config).map_err(
+                    |diagnostics| {
+                        Formatter::new(expression, diagnostics)
+                            .colored()
+                            .to_string()
+                    },
+                )?;
+                if !warnings.is_empty() {
+                    let warnings = Formatter::new(expression, warnings).colored().to_string();
+                    warn!(message = "VRL compilation warning.", %warnings);
+                }
+
+                Strategy::Cursor {
+                    program: Arc::new(program),
+                    query_parameter: query_parameter.clone(),
+                }
+            }
+        };
+
+        Ok(Pagination {
+            strategy,
+            max_pages: self.max_pages,
+        })
+    }
+}
+
+/// The pagination of the HTTP responses, built from a `PaginationConfig`.
+#[derive(Clone, Debug)]
+pub(super) struct Pagination {
+    strategy: Strategy,
+    max_pages: usize,
+}
+
+#[derive(Clone, Debug)]
+enum Strategy {
+    LinkHeader,
+    Cursor {
+        program: Arc<Program>,
+        query_parameter: Option<String>,
+    },
+}
+
+impl Pagination {
+    pub(super) const fn max_pages(&self) -> usize {
+        self.max_pages
+    }
+
+    /// Returns the URL of the page following the page received from `url`, if any.
+    ///
+    /// `first_url` is the URL of the first page of the scrape, to which the cursor is added when
+    /// it is sent as a query string parameter.
+    pub(super) fn next_page(
+        &self,
+        first_url: &Uri,
+        url: &Uri,
+        header: &Parts,
+        body: &Bytes,
+    ) -> Result<Option<Uri>, String> {
+        match &self.strategy {
+            Strategy::LinkHeader => next_link(header)
+                .map(|link| resolve_url(url, link))
+                .transpose(),
+            Strategy::Cursor {
+                program,
+                query_parameter,
+            } => match cursor(program, body)? {
+                None => Ok(None),
+                Some(cursor) => match query_parameter {
+                    Some(parameter) => Ok(Some(build_url(
+                        first_url,
+                        &HashMap::from([(parameter.clone(), vec![cursor])]),
+                    ))),
+                    None => resolve_url(url, &cursor).map(Some),
+                },
+            },
+        }
+    }
+}
+
+/// Returns the target of the `next` relation of the `Link` headers.
+fn next_link(header: &Parts) -> Option<&str> {
+    header
+        .headers
+        .get_all(LINK)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .find_map(|link| {
+            let (target, params) = link.trim().strip_prefix('<')?.split_once('>')?;
+            params
+                .split(';')
+                .filter_map(|param| param.trim().strip_prefix("rel="))
+                .any(|rel| {
+                    rel.trim_matches('"')
+                        .split_whitespace()
+                        .any(|rel| rel.eq_ignore_ascii_case("next"))
+                })
+                .then_some(target)
+        })
+}
+
+/// Evaluates the cursor expression against the response body.
+fn cursor(program: &Program, body: &Bytes) -> Result<Option<String>, String> {
+    let value = serde_json::from_slice::<Value>(body).unwrap_or_else(|_| body.clone().into());
+    let mut target = TargetValue {
+        value,
+        metadata: Value::Object(Default::default()),
+        secrets: Secrets::default(),
+    };
+
+    match Runtime::default()
+        .resolve(&mut target, program, &TimeZone::default())
+        .map_err(|error| error.to_string())?
+    {
+        Value::Null => Ok(None),
+        Value::Bytes(bytes) if bytes.is_empty() => Ok(None),
+        Value::Bytes(bytes) => Ok(Some(String::from_utf8_lossy(&bytes).into_owned())),
+        Value::Integer(integer) => Ok(Some(integer.to_string())),
+        value => Err(format!(
+            "the cursor expression returned {}, expected a string, an integer or null",
+            value.kind_str()
+        )),
+    }
+}
+
+fn resolve_url(base: &Uri, reference: &str) -> Result<Uri, String> {
+    let url = url::Url::parse(&base.to_string())
+        .and_then(|base| base.join(reference))
+        .map_err(|error| format!("invalid URL of the next page {:?}: {}", reference, error))?;
+    url.as_str()
+        .parse()
+        .map_err(|error| format!("invalid URL of the next page {:?}: {}", reference, error))
+}
+
+/// The watermark of incremental scrapes, shared by all the scrapes of the source.
+#[derive(Debug)]
+pub(super) struct Watermark {
+    field: ConfigTargetPath,
+    query_parameter: String,
+    endpoint: Uri,
+    checkpoint_path: PathBuf,
+    value: Mutex<Option<String>>,
+    urls: watch::Sender<Vec<Uri>>,
+}
+
+impl Watermark {
+    /// Loads the watermark persisted at `checkpoint_path`, falling back to the initial value.
+    pub(super) fn new(
+        config: &IncrementalConfig,
+        endpoint: Uri,
+        checkpoint_path: PathBuf,
+    ) -> io::Result<Self> {
+        let value = match fs::read_to_string(&checkpoint_path) {
+            Ok(value) => Some(value.trim().to_owned()).filter(|value| !value.is_empty()),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => None,
+            Err(error) => return Err(error),
+        }
+        .or_else(|| config.initial_value.clone());
+
+        let watermark = Self {
+            field: config.field.clone(),
+            query_parameter: config.query_parameter.clone(),
+            endpoint,
+            checkpoint_path,
+            value: Mutex::new(None),
+            urls: watch::channel(Vec::new()).0,
+        };
+        watermark.set(value);
+        Ok(watermark)
+    }
+
+    /// Returns the URLs to scrape, updated as the watermark advances.
+    pub(super) fn urls(&self) -> watch::Receiver<Vec<Uri>> {
+        self.urls.subscribe()
+    }
+
+    /// Returns the highest of `current` and of the watermarks of `events`.
+    pub(super) fn highest(&self, current: Option<String>, events: &[Event]) -> Option<String> {
+        events
+            .iter()
+            .filter_map(|event| event.maybe_as_log()?.get(&self.field.0))
+            .filter_map(watermark_value)
+            .fold(current, |highest, value| match highest {
+                Some(highest) if compare(&value, &highest) != Ordering::Greater => Some(highest),
+                _ => Some(value),
+            })
+    }
+
+    /// Advances the watermark to `value`, if it is higher than the current watermark.
+    pub(super) fn advance(&self, value: String) {
+        let current = self.value.lock().expect("poisoned lock").clone();
+        if current.is_some_and(|current| compare(&value, &current) != Ordering::Greater) {
+            return;
+        }
+
+        let temporary_path = self.checkpoint_path.with_extension("tmp");
+        if let Err(error) = fs::write(&temporary_path, &value)
+            .and_then(|()| fs::rename(&temporary_path, &self.checkpoint_path))
+        {
+            emit!(HttpClientWatermarkSetError {
+                error,
+                filename: self.checkpoint_path.to_string_lossy().into_owned(),
+            });
+        }
+        self.set(Some(value));
+    }
+
+    fn set(&self, value: Option<String>) {
+        let url = match &value {
+            Some(value) => build_url(
+                &self.endpoint,
+                &HashMap::from([(self.query_parameter.clone(), vec![value.clone()])]),
+            ),
+            None => self.endpoint.clone(),
+        };
+        *self.value.lock().expect("poisoned lock") = value;
+        self.urls.send_replace(vec![url]);
+    }
+}
+
+fn watermark_value(value: &Value) -> Option<String> {
+    match value {
+        Value::Bytes(bytes) => Some(String::from_utf8_lossy(bytes).into_owned()),
+        Value::Integer(integer) => Some(integer.to_string()),
+        Value::Float(float) => Some(float.to_string()),
+        Value::Timestamp(timestamp) => Some(timestamp.to_rfc3339_opts(SecondsFormat::AutoSi, true)),
+        _ => None,
+    }
+}
+
+/// Compares watermarks as numbers or timestamps when both are, and as strings otherwise.
+fn compare(a: &str, b: &str) -> Ordering {
+    if let (Ok(a), Ok(b)) = (a.parse::<f64>(), b.parse::<f64>()) {
+        return a.partial_cmp(&b).unwrap_or(Ordering::Equal);
+    }
+    if let (Ok(a), Ok(b)) = (
+        DateTime::parse_from_rfc3339(a),
+        DateTime::parse_from_rfc3339(b),
+    ) {
+        return a.cmp(&b);
+    }
+    a.cmp(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use http::Response;
+    use vector_lib::event::LogEvent;
+
+    use super::*;
+
+    fn parts(link: Option<&str>) -> Parts {
+        let mut response = Response::builder();
+        if let Some(link) = link {
+            response = response.header(LINK, link);
+        }
+        response.body(()).unwrap().into_parts().0
+    }
+
+    fn cursor_pagination(expression: &str, query_parameter: Option<&str>) -> Pagination {
+        PaginationConfig {
+            strategy: PaginationStrategy::Cursor {
+                expression: expression.to_owned(),
+                query_parameter: query_parameter.map(ToOwned::to_owned),
+            },
+            max_pages: default_max_pages(),
+        }
+        .build()
+        .unwrap()
+    }
+
+    #[test]
+    fn follows_link_headers() {
+        let pagination = PaginationConfig {
+            strategy: PaginationStrategy::LinkHeader,
+            max_pages: default_max_pages(),
+        }
+        .build()
+        .unwrap();
+        let url = Uri::from_static("https://api.github.com/repos/vectordotdev/vector/events");
+        let body = Bytes::new();
+
+        let header = parts(Some(
+            r#"<https://api.github.com/repositories/1/events?page=2>; rel="next", <https://api.github.com/repositories/1/events?page=5>; rel="last""#,
+        ));
+        assert_eq!(
+            pagination.next_page(&url, &url, &header, &body).unwrap(),
+            Some(Uri::from_static(
+                "https://api.github.com/repositories/1/events?page=2"
+            ))
+        );
+
+        let header = parts(Some(r#"</api/v1/logs?after=abc>; rel="next""#));
+        assert_eq!(
+            pagination.next_page(&url, &url, &header, &body).unwrap(),
+            Some(Uri::from_static(
+                "https://api.github.com/api/v1/logs?after=abc"
+            ))
+        );
+
+        let header = parts(Some(
+            r#"<https://api.github.com/events?page=1>; rel="prev""#,
+        ));
+        assert_eq!(
+            pagination.next_page(&url, &url, &header, &body).unwrap(),
+            None
+        );
+        assert_eq!(
+            pagination
+                .next_page(&url, &url, &parts(None), &body)
+                .unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn extracts_cursors() {
+        let first_url = Uri::from_static("http://localhost/logs?limit=10");
+        let url = Uri::from_static("http://localhost/logs?limit=10&cursor=a");
+        let header = parts(None);
+
+        let pagination = cursor_pagination(".meta.next", Some("cursor"));
+        assert_eq!(
+            pagination
+                .next_page(
+                    &first_url,
+                    &url,
+                    &header,
+                    &Bytes::from(r#"{"meta":{"next":"b"}}"#)
+                )
+                .unwrap(),
+            Some(Uri::from_static("http://localhost/logs?limit=10&cursor=b"))
+        );
+        assert_eq!(
+            pagination
+                .next_page(&first_url, &url, &header, &Bytes::from(r#"{"meta":{}}"#))
+                .unwrap(),
+            None
+        );
+        assert!(pagination
+            .next_page(
+                &first_url,
+                &url,
+                &header,
+                &Bytes::from(r#"{"meta":{"next":[]}}"#)
+            )
+            .is_err());
+
+        let pagination = cursor_pagination(".nextRecordsUrl", None);
+        assert_eq!(
+            pagination
+                .next_page(
+                    &first_url,
+                    &url,
+                    &header,
+                    &Bytes::from(r#"{"nextRecordsUrl":"/query/01gD-2000"}"#)
+                )
+                .unwrap(),
+            Some(Uri::from_static("http://localhost/query/01gD-2000"))
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_cursor_expressions() {
+        assert!(PaginationConfig {
+            strategy: PaginationStrategy::Cursor {
+                expression: ".next +".to_owned(),
+                query_parameter: None,
+            },
+            max_pages: default_max_pages(),
+        }
+        .build()
+        .is_err());
+    }
+
+    #[test]
+    fn compares_watermarks() {
+        assert_eq!(compare("9", "10"), Ordering::Less);
+        assert_eq!(
+            compare("2024-01-01T10:00:00+02:00", "2024-01-01T09:00:00Z"),
+            Ordering::Less
+        );
+        assert_eq!(compare("b", "a"), Ordering::Greater);
+    }
+
+    #[test]
+    fn persists_watermarks() {
+        let directory = tempfile::tempdir().unwrap();
+        let checkpoint_path = directory.path().join("watermark");
+        let config = IncrementalConfig {
+            field: ConfigTargetPath::try_from("updated_at".to_owned()).unwrap(),
+            query_parameter: "updated_since".to_owned(),
+            initial_value: Some("2024-01-01T00:00:00Z".to_owned()),
+        };
+        let endpoint = Uri::from_static("http://localhost/logs?limit=10");
+
+        let watermark = Watermark::new(&config, endpoint.clone(), checkpoint_path.clone()).unwrap();
+        let urls = watermark.urls();
+        assert_eq!(
+            urls.borrow().clone(),
+            vec![Uri::from_static(
+                "http://localhost/logs?limit=10&updated_since=2024-01-01T00%3A00%3A00Z"
+            )]
+        );
+
+        let events = [
+            "2024-01-02T00:00:00Z",
+            "2024-01-03T00:00:00Z",
+            "2024-01-01T12:00:00Z",
+        ]
+        .into_iter()
+        .map(|updated_at| {
+            let mut log = LogEvent::default();
+            log.insert("updated_at", updated_at);
+            Event::from(log)
+        })
+        .collect::<Vec<_>>();
+        let highest = watermark.highest(None, &events).unwrap();
+        assert_eq!(highest, "2024-01-03T00:00:00Z");
+
+        watermark.advance(highest);
+        watermark.advance("2024-01-02T00:00:00Z".to_owned());
+        assert_eq!(
+            fs::read_to_string(&checkpoint_path).unwrap(),
+            "2024-01-03T00:00:00Z"
+        );
+        assert_eq!(
+            urls.borrow().clone(),
+            vec![Uri::from_static(
+                "http://localhost/logs?limit=10&updated_since=2024-01-03T00%3A00%3A00Z"
+            )]
+        );
+
+        let watermark = Watermark::new(&config, endpoint, checkpoint_path).unwrap();
+        assert_eq!(
+            watermark.value.lock().unwrap().as_deref(),
+            Some("2024-01-03T00:00:00Z")
+        );
+    }
+}
@@ -13,6 +13,7 @@ use vector_lib::codecs::decoding::{
 use vector_lib::codecs::CharacterDelimitedDecoderConfig;
 use vector_lib::event::Event;
 
+use super::pagination::{IncrementalConfig, PaginationConfig, PaginationStrategy};
 use super::HttpClientConfig;
 use crate::test_util::{
     components::{run_and_assert_source_compliance, HTTP_PULL_SOURCE_TAGS},
@@ -95,6 +96,9 @@ async fn bytes_decoding() {
         method: HttpMethod::Get,
         tls: None,
         auth: None,
+        pagination: None,
+        incremental: None,
+        data_dir: None,
         log_namespace: None,
     })
     .await;
@@ -124,6 +128,9 @@ async fn json_decoding_newline_delimited() {
         method: HttpMethod::Get,
         tls: None,
         auth: None,
+        pagination: None,
+        incremental: None,
+        data_dir: None,
         log_namespace: None,
     })
     .await;
@@ -158,6 +165,9 @@ async fn json_decoding_character_delimited() {
         method: HttpMethod::Get,
         tls: None,
         auth: None,
+        pagination: None,
+        incremental: None,
+        data_dir: None,
         log_namespace: None,
     })
     .await;
@@ -192,6 +202,9 @@ async fn request_query_applied() {
         method: HttpMethod::Get,
         tls: None,
         auth: None,
+        pagination: None,
+        incremental: None,
+        data_dir: None,
         log_namespace: None,
     })
     .await;
@@ -256,6 +269,9 @@ async fn headers_applied() {
         method: HttpMethod::Get,
         auth: None,
         tls: None,
+        pagination: None,
+        incremental: None,
+        data_dir: None,
         log_namespace: None,
     })
     .await;
@@ -285,7 +301,146 @@ async fn accept_header_override() {
         method: HttpMethod::Get,
         auth: None,
         tls: None,
+        pagination: None,
+        incremental: None,
+        data_dir: None,
         log_namespace: None,
     })
     .await;
 }
+
+/// The pages following the first page of a response should be requested.
+#[tokio::test]
+async fn pagination_link_header() {
+    let in_addr = next_addr();
+
+    let dummy_endpoint = warp::path!("endpoint")
+        .and(warp::query::<HashMap<String, String>>())
+        .map(
+            move |query: HashMap<String, String>| match query.get("page") {
+                None => warp::reply::with_header(
+                    r#"{"data" : "first"}"#,
+                    "Link",
+                    format!(r#"<http://{}/endpoint?page=2>; rel="next""#, in_addr),
+                ),
+                Some(_) => warp::reply::with_header(r#"{"data" : "second"}"#, "X-Page", "2".into()),
+            },
+        );
+
+    tokio::spawn(warp::serve(dummy_endpoint).run(in_addr));
+    wait_for_tcp(in_addr).await;
+
+    let events = run_compliance(HttpClientConfig {
+        endpoint: format!("http://{}/endpoint", in_addr),
+        interval: INTERVAL,
+        timeout: TIMEOUT,
+        decoding: DeserializerConfig::Json(Default::default()),
+        pagination: Some(PaginationConfig {
+            strategy: PaginationStrategy::LinkHeader,
+            max_pages: 10,
+        }),
+        ..Default::default()
+    })
+    .await;
+
+    let data = events
+        .into_iter()
+        .map(|event| event.into_log()["data"].to_string_lossy().into_owned())
+        .collect::<Vec<_>>();
+    assert!(data.contains(&"first".to_owned()));
+    assert!(data.contains(&"second".to_owned()));
+}
+
+/// Pages should be requested with the cursor extracted from the previous page.
+#[tokio::test]
+async fn pagination_cursor() {
+    let in_addr = next_addr();
+
+    let dummy_endpoint = warp::path!("endpoint")
+        .and(warp::query::<HashMap<String, String>>())
+        .map(
+            |query: HashMap<String, String>| match query.get("cursor").map(String::as_str) {
+                None => r#"{"data" : "first", "next" : "b"}"#,
+                Some("b") => r#"{"data" : "second", "next" : null}"#,
+                Some(_) => r#"{"data" : "unexpected"}"#,
+            },
+        );
+
+    tokio::spawn(warp::serve(dummy_endpoint).run(in_addr));
+    wait_for_tcp(in_addr).await;
+
+    let events = run_compliance(HttpClientConfig {
+        endpoint: format!("http://{}/endpoint", in_addr),
+        interval: INTERVAL,
+        timeout: TIMEOUT,
+        decoding: DeserializerConfig::Json(Default::default()),
+        pagination: Some(PaginationConfig {
+            strategy: PaginationStrategy::Cursor {
+                expression: ".next".to_owned(),
+                query_parameter: Some("cursor".to_owned()),
+            },
+            max_pages: 10,
+        }),
+        ..Default::default()
+    })
+    .await;
+
+    let data = events
+        .into_iter()
+        .map(|event| event.into_log()["data"].to_string_lossy().into_owned())
+        .collect::<Vec<_>>();
+    assert!(data.contains(&"first".to_owned()));
+    assert!(data.contains(&"second".to_owned()));
+    assert!(!data.contains(&"unexpected".to_owned()));
+}
+
+/// The watermark of the events should be sent in the following scrapes, and persisted.
+#[tokio::test]
+async fn incremental_watermark() {
+    let in_addr = next_addr();
+    let data_dir = tempfile::tempdir().unwrap();
+
+    let dummy_endpoint = warp::path!("endpoint")
+        .and(warp::query::<HashMap<String, String>>())
+        .map(|query: HashMap<String, String>| {
+            let since = query.get("updated_since").cloned().unwrap_or_default();
+            format!(
+                r#"{{"data" : "{}", "updated_at" : "2024-01-02T00:00:00Z"}}"#,
+                since
+            )
+        });
+
+    tokio::spawn(warp::serve(dummy_endpoint).run(in_addr));
+    wait_for_tcp(in_addr).await;
+
+    let events = run_compliance(HttpClientConfig {
+        endpoint: format!("http://{}/endpoint", in_addr),
+        interval: INTERVAL,
+        timeout: TIMEOUT,
+        decoding: DeserializerConfig::Json(Default::default()),
+        incremental: Some(IncrementalConfig {
+            field: "updated_at".to_owned().try_into().unwrap(),
+            query_parameter: "updated_since".to_owned(),
+            initial_value: Some("2024-01-01T00:00:00Z".to_owned()),
+        }),
+        data_dir: Some(data_dir.path().to_path_buf()),
+        ..Default::default()
+    })
+    .await;
+
+    let data = events
+        .into_iter()
+        .map(|event| event.into_log()["data"].to_string_lossy().into_owned())
+        .collect::<Vec<_>>();
+    assert_eq!(data[0], "2024-01-01T00:00:00Z");
+    assert!(data.contains(&"2024-01-02T00:00:00Z".to_owned()));
+
+    let subdir = std::fs::read_dir(data_dir.path())
+        .unwrap()
+        .next()
+        .unwrap()
+        .unwrap()
+        .path();
+    let watermark = std::fs::read_to_string(subdir.join("watermark")).unwrap();
+    assert_eq!(watermark, "2024-01-02T00:00:00Z");
+}
@@ -0,0 +1,481 @@
+use std::{collections::HashMap, path::PathBuf, pin::Pin};
+
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use futures::{
+    future::ready,
+    stream::{self, Stream, StreamExt},
+};
+use k8s_openapi::api::core::v1::Event as KubeEvent;
+use kube::{
+    api::Api,
+    config::{self, KubeConfigOptions},
+    runtime::{watcher, WatchStreamExt},
+    Client, Config as ClientConfig,
+};
+use vector_lib::configurable::configurable_component;
+use vector_lib::lookup::{metadata_path, owned_value_path, path};
+use vector_lib::{
+    config::{LegacyKey, LogNamespace},
+    internal_event::{CountByteSize, InternalEventHandle as _},
+    schema::Definition,
+    EstimatedJsonEncodedSizeOf,
+};
+use vrl::value::{Kind, Value};
+
+use crate::{
+    config::{log_schema, DataType, SourceConfig, SourceContext, SourceOutput},
+    event::LogEvent,
+    internal_events::{EventsReceived, KubernetesEventsWatchError, StreamClosedError},
+    shutdown::ShutdownSignal,
+    SourceSender,
+};
+
+/// Configuration for the `kubernetes_events` source.
+#[configurable_component(source(
+    "kubernetes_events",
+    "Collect events from the Kubernetes API server."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct KubernetesEventsConfig {
+    /// The namespaces to watch the events of.
+    ///
+    /// If empty, the events of all namespaces are collected.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "default", docs::examples = "kube-system"))]
+    namespaces: Vec<String>,
+
+    /// A field selector restricting the collected events.
+    ///
+    /// See [Kubernetes field selectors](https://kubernetes.io/docs/concepts/overview/working-with-objects/field-selectors/)
+    /// for the syntax.
+    #[configurable(metadata(
+        docs::examples = "type=Warning",
+        docs::examples = "involvedObject.kind=Pod"
+    ))]
+    field_selector: Option<String>,
+
+    /// Whether to collect the events already stored by the API server when the source starts.
+    ///
+    /// By default, only the events occurring after the source started are collected.
+    #[serde(default)]
+    include_existing: bool,
+
+    /// Whether to collect an event again each time its `count` increases.
+    ///
+    /// Kubernetes records the repeated occurrences of an event by increasing the `count` of the
+    /// existing event rather than creating a new one. When disabled, each event is only collected
+    /// the first time it is seen.
+    #[serde(default = "crate::serde::default_true")]
+    emit_count_updates: bool,
+
+    /// Optional path to a readable [kubeconfig][kubeconfig] file.
+    ///
+    /// If not set, a connection to Kubernetes is made using the in-cluster configuration.
+    ///
+    /// [kubeconfig]: https://kubernetes.io/docs/concepts/configuration/organize-cluster-access-kubeconfig/
+    #[configurable(metadata(docs::examples = "/path/to/.kube/config"))]
+    kube_config_file: Option<PathBuf>,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[configurable(metadata(docs::hidden))]
+    #[serde(default)]
+    log_namespace: Option<bool>,
+}
+
+impl Default for KubernetesEventsConfig {
+    fn default() -> Self {
+        Self {
+            namespaces: Vec::new(),
+            field_selector: None,
+            include_existing: false,
+            emit_count_updates: true,
+            kube_config_file: None,
+            log_namespace: None,
+        }
+    }
+}
+
+impl_generate_config_from_default!(KubernetesEventsConfig);
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "kubernetes_events")]
+impl SourceConfig for KubernetesEventsConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        // If the user passed a custom Kubeconfig use it, otherwise
+        // we attempt to load the local kubeconfig, followed by the
+        // in-cluster environment variables
+        let client_config = match &self.kube_config_file {
+            Some(kc) => {
+                ClientConfig::from_custom_kubeconfig(
+                    config::Kubeconfig::read_from(kc)?,
+                    &KubeConfigOptions::default(),
+                )
+                .await?
+            }
+            None => ClientConfig::infer().await?,
+        };
+        let client = Client::try_from(client_config)?;
+
+        let apis = if self.namespaces.is_empty() {
+            vec![Api::<KubeEvent>::all(client)]
+        } else {
+            self.namespaces
+                .iter()
+                .map(|namespace| Api::<KubeEvent>::namespaced(client.clone(), namespace))
+                .collect()
+        };
+
+        Ok(Box::pin(run(
+            apis,
+            self.clone(),
+            cx.log_namespace(self.log_namespace),
+            cx.out,
+            cx.shutdown,
+        )))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
+        let log_namespace = global_log_namespace.merge(self.log_namespace);
+
+        let schema_definition = match log_namespace {
+            LogNamespace::Legacy => {
+                Definition::empty_legacy_namespace().unknown_fields(Kind::any())
+            }
+            LogNamespace::Vector => {
+                Definition::new_with_default_metadata(Kind::any_object(), [log_namespace])
+            }
+        }
+        .with_source_metadata(
+            Self::NAME,
+            None,
+            &owned_value_path!("timestamp"),
+            Kind::timestamp(),
+            Some("timestamp"),
+        )
+        .with_standard_vector_source_metadata();
+
+        vec![SourceOutput::new_logs(DataType::Log, schema_definition)]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+type EventStream = Pin<Box<dyn Stream<Item = Vec<KubeEvent>> + Send>>;
+
+async fn run(
+    apis: Vec<Api<KubeEvent>>,
+    config: KubernetesEventsConfig,
+    log_namespace: LogNamespace,
+    mut out: SourceSender,
+    shutdown: ShutdownSignal,
+) -> Result<(), ()> {
+    let events_received = register!(EventsReceived);
+
+    // The watcher resumes from the last resource version it has seen, as updated by bookmarks,
+    // when its connection is lost, and lists the events again when that version has expired.
+    // Each namespace has its own watcher, and so its own deduplicator.
+    let streams = apis.into_iter().map(|api| {
+        let mut deduplicator =
+            Deduplicator::new(config.include_existing, config.emit_count_updates);
+        let stream = watcher(
+            api,
+            watcher::Config {
+                field_selector: config.field_selector.clone(),
+                ..Default::default()
+            },
+        )
+        .backoff(watcher::default_backoff())
+        .filter_map(move |result| {
+            ready(match result {
+                Ok(event) => Some(deduplicator.handle(event)),
+                Err(error) => {
+                    emit!(KubernetesEventsWatchError { error });
+                    None
+                }
+            })
+        });
+        Box::pin(stream) as EventStream
+    });
+    let mut stream = stream::select_all(streams).take_until(shutdown);
+
+    while let Some(events) = stream.next().await {
+        if events.is_empty() {
+            continue;
+        }
+
+        let events = events
+            .into_iter()
+            .filter_map(|event| event_to_log(event, log_namespace))
+            .collect::<Vec<_>>();
+        let count = events.len();
+        let byte_size = events.estimated_json_encoded_size_of();
+        events_received.emit(CountByteSize(count, byte_size));
+
+        if out.send_batch(events).await.is_err() {
+            emit!(StreamClosedError { count });
+            return Err(());
+        }
+    }
+
+    Ok(())
+}
+
+/// Tracks the events seen by a watcher, so that each occurrence is only collected once.
+///
+/// The API server sends an event again when it is listed after a restart of the watcher, or
+/// when any of its fields change, and Kubernetes counts the repeated occurrences of an event in
+/// the existing event object. Events are only collected when first seen, or when their count
+/// increases.
+struct Deduplicator {
+    counts: HashMap<String, i32>,
+    /// Whether no listing has been received yet, to skip the events existing on startup.
+    initial: bool,
+    include_existing: bool,
+    emit_count_updates: bool,
+}
+
+impl Deduplicator {
+    fn new(include_existing: bool, emit_count_updates: bool) -> Self {
+        Self {
+            counts: HashMap::new(),
+            initial: true,
+            include_existing,
+            emit_count_updates,
+        }
+    }
+
+    /// Returns the events of a watcher event to collect.
+    fn handle(&mut self, event: watcher::Event<KubeEvent>) -> Vec<KubeEvent> {
+        match event {
+            watcher::Event::Applied(event) => {
+                let collect = Self::update(&mut self.counts, &event, self.emit_count_updates);
+                collect.then_some(event).into_iter().collect()
+            }
+            watcher::Event::Deleted(event) => {
+                self.counts.remove(&event_key(&event));
+                Vec::new()
+            }
+            watcher::Event::Restarted(events) => {
+                let skip = self.initial && !self.include_existing;
+                self.initial = false;
+
+                // Only the listed events are kept, as the others have been deleted in between.
+                let mut previous = std::mem::take(&mut self.counts);
+                events
+                    .into_iter()
+                    .filter(|event| {
+                        let key = event_key(event);
+                        if let Some(count) = previous.remove(&key) {
+                            self.counts.insert(key, count);
+                        }
+                        Self::update(&mut self.counts, event, self.emit_count_updates) && !skip
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    /// Records the count of an event, returning whether it is to be collected.
+    fn update(
+        counts: &mut HashMap<String, i32>,
+        event: &KubeEvent,
+        emit_count_updates: bool,
+    ) -> bool {
+        let key = event_key(event);
+        let count = event_count(event);
+        match counts.get(&key) {
+            // The counts of an event never decrease, so the event has not changed.
+            Some(&previous) if previous >= count => false,
+            previous => {
+                let seen = previous.is_some();
+                counts.insert(key, count);
+                !seen || emit_count_updates
+            }
+        }
+    }
+}
+
+fn event_key(event: &KubeEvent) -> String {
+    match &event.metadata.uid {
+        Some(uid) => uid.clone(),
+        None => format!(
+            "{}/{}",
+            event.metadata.namespace.as_deref().unwrap_or_default(),
+            event.metadata.name.as_deref().unwrap_or_default()
+        ),
+    }
+}
+
+/// The number of occurrences of an event, which newer components report in its series.
+fn event_count(event: &KubeEvent) -> i32 {
+    event
+        .series
+        .as_ref()
+        .and_then(|series| series.count)
+        .or(event.count)
+        .unwrap_or(1)
+}
+
+/// The time of the last occurrence of an event.
+fn event_timestamp(event: &KubeEvent) -> Option<DateTime<Utc>> {
+    event
+        .series
+        .as_ref()
+        .and_then(|series| series.last_observed_time.as_ref())
+        .map(|time| time.0)
+        .or_else(|| event.last_timestamp.as_ref().map(|time| time.0))
+        .or_else(|| event.event_time.as_ref().map(|time| time.0))
+        .or_else(|| event.first_timestamp.as_ref().map(|time| time.0))
+        .or_else(|| {
+            event
+                .metadata
+                .creation_timestamp
+                .as_ref()
+                .map(|time| time.0)
+        })
+}
+
+/// Converts a Kubernetes event into a log event with the fields of the event object, using the
+/// time of its last occurrence as the timestamp.
+fn event_to_log(event: KubeEvent, log_namespace: LogNamespace) -> Option<LogEvent> {
+    let timestamp = event_timestamp(&event);
+    let Value::Object(fields) = serde_json::to_value(event).ok()?.into() else {
+        return None;
+    };
+
+    let mut log = LogEvent::from_map(fields, Default::default());
+    log_namespace.insert_vector_metadata(
+        &mut log,
+        log_schema().source_type_key(),
+        path!("source_type"),
+        Bytes::from_static(KubernetesEventsConfig::NAME.as_bytes()),
+    );
+    match log_namespace {
+        LogNamespace::Vector => {
+            if let Some(timestamp) = timestamp {
+                log.insert(
+                    metadata_path!(KubernetesEventsConfig::NAME, "timestamp"),
+                    timestamp,
+                );
+            }
+            log.insert(metadata_path!("vector", "ingest_timestamp"), Utc::now());
+        }
+        LogNamespace::Legacy => {
+            log_namespace.insert_source_metadata(
+                KubernetesEventsConfig::NAME,
+                &mut log,
+                log_schema().timestamp_key().map(LegacyKey::Overwrite),
+                path!("timestamp"),
+                timestamp.unwrap_or_else(Utc::now),
+            );
+        }
+    }
+    Some(log)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use k8s_openapi::apimachinery::pkg::apis::meta::v1::{ObjectMeta, Time};
+    use vrl::value;
+
+    use super::*;
+
+    fn kube_event(uid: &str, count: i32) -> KubeEvent {
+        KubeEvent {
+            metadata: ObjectMeta {
+                name: Some(format!("{}.17a", uid)),
+                namespace: Some("default".to_owned()),
+                uid: Some(uid.to_owned()),
+                ..Default::default()
+            },
+            count: Some(count),
+            reason: Some("BackOff".to_owned()),
+            message: Some("Back-off restarting failed container".to_owned()),
+            type_: Some("Warning".to_owned()),
+            last_timestamp: Some(Time(Utc.with_ymd_and_hms(2023, 5, 1, 12, 0, 0).unwrap())),
+            ..Default::default()
+        }
+    }
+
+    fn uids(events: Vec<KubeEvent>) -> Vec<String> {
+        events.iter().map(event_key).collect()
+    }
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<KubernetesEventsConfig>();
+    }
+
+    #[test]
+    fn skips_existing_events() {
+        let mut deduplicator = Deduplicator::new(false, true);
+        let listed = deduplicator.handle(watcher::Event::Restarted(vec![kube_event("a", 1)]));
+        assert!(listed.is_empty());
+
+        let applied = deduplicator.handle(watcher::Event::Applied(kube_event("b", 1)));
+        assert_eq!(uids(applied), vec!["b"]);
+
+        let mut deduplicator = Deduplicator::new(true, true);
+        let listed = deduplicator.handle(watcher::Event::Restarted(vec![kube_event("a", 1)]));
+        assert_eq!(uids(listed), vec!["a"]);
+    }
+
+    #[test]
+    fn deduplicates_events() {
+        let mut deduplicator = Deduplicator::new(false, true);
+        deduplicator.handle(watcher::Event::Restarted(vec![kube_event("a", 1)]));
+
+        // An update not increasing the count is not a new occurrence.
+        let applied = deduplicator.handle(watcher::Event::Applied(kube_event("a", 1)));
+        assert!(applied.is_empty());
+        let applied = deduplicator.handle(watcher::Event::Applied(kube_event("a", 2)));
+        assert_eq!(uids(applied), vec!["a"]);
+
+        // Events listed again after a restart of the watcher are only collected if they changed
+        // in between.
+        let listed = deduplicator.handle(watcher::Event::Restarted(vec![
+            kube_event("a", 2),
+            kube_event("b", 1),
+        ]));
+        assert_eq!(uids(listed), vec!["b"]);
+
+        let deleted = deduplicator.handle(watcher::Event::Deleted(kube_event("a", 2)));
+        assert!(deleted.is_empty());
+        assert!(!deduplicator.counts.contains_key("a"));
+    }
+
+    #[test]
+    fn skips_count_updates() {
+        let mut deduplicator = Deduplicator::new(false, false);
+        let applied = deduplicator.handle(watcher::Event::Applied(kube_event("a", 1)));
+        assert_eq!(uids(applied), vec!["a"]);
+        let applied = deduplicator.handle(watcher::Event::Applied(kube_event("a", 2)));
+        assert!(applied.is_empty());
+    }
+
+    #[test]
+    fn converts_events_to_logs() {
+        let log = event_to_log(kube_event("a", 3), LogNamespace::Legacy).unwrap();
+        assert_eq!(
+            log.get("message"),
+            Some(&value!("Back-off restarting failed container"))
+        );
+        assert_eq!(log.get("count"), Some(&value!(3)));
+        assert_eq!(log.get("metadata.uid"), Some(&value!("a")));
+        assert_eq!(
+            log.get(log_schema().timestamp_key_target_path().unwrap()),
+            Some(&Value::Timestamp(
+                Utc.with_ymd_and_hms(2023, 5, 1, 12, 0, 0).unwrap()
+            ))
+        );
+        assert_eq!(
+            log.get(log_schema().source_type_key_target_path().unwrap()),
+            Some(&value!("kubernetes_events"))
+        );
+    }
+}
@@ -8,7 +8,7 @@ use k8s_openapi::api::core::v1::{Namespace, Pod};
 use kube::runtime::reflector::{store::Store, ObjectRef};
 use vector_lib::file_source::paths_provider::PathsProvider;
 
-use super::path_helpers::build_pod_logs_directory;
+use super::{path_helpers::build_pod_logs_directory, pod_log_controls::is_pod_excluded};
 use crate::kubernetes::pod_manager_logic::extract_static_pod_config_hashsum;
 
 /// A paths provider implementation that uses the state obtained from the
@@ -57,6 +57,9 @@ impl PathsProvider for K8sPathsProvider {
                     false
                 }
             })
+            // filter out pods that opted out of collection via annotations, as
+            // exclusion by annotation cannot be expressed as a label selector
+            .filter(|pod| !is_pod_excluded(pod))
             .flat_map(|pod| {
                 trace!(message = "Providing log paths for pod.", pod = ?pod.metadata.name);
                 let paths_iter = list_pod_log_paths(real_glob, pod.as_ref());
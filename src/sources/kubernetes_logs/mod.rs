@@ -61,6 +61,7 @@ mod node_metadata_annotator;
 mod parser;
 mod partial_events_merger;
 mod path_helpers;
+mod pod_log_controls;
 mod pod_metadata_annotator;
 mod transform_utils;
 mod util;
@@ -68,6 +69,7 @@ mod util;
 use self::namespace_metadata_annotator::NamespaceMetadataAnnotator;
 use self::node_metadata_annotator::NodeMetadataAnnotator;
 use self::parser::Parser;
+use self::pod_log_controls::{apply_pod_log_controls, PodLogControlsCache};
 use self::pod_metadata_annotator::PodMetadataAnnotator;
 
 /// The `self_node_name` value env var key.
@@ -518,6 +520,18 @@ impl SourceConfig for Config {
             )
             .with_standard_vector_source_metadata();
 
+        // Owner references are opt-in, so they are only part of the schema when enabled.
+        let schema_definition = match &self.pod_annotation_fields.pod_owner_references.path {
+            Some(key) => schema_definition.with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::Overwrite(key.path.clone())),
+                &owned_value_path!("pod_owner_references"),
+                Kind::array(Collection::empty().with_unknown(Kind::any_object())).or_undefined(),
+                None,
+            ),
+            None => schema_definition,
+        };
+
         vec![SourceOutput::new_logs(DataType::Log, schema_definition)]
     }
 
@@ -696,12 +710,15 @@ impl Source {
         let pod_store_w = reflector::store::Writer::default();
         let pod_state = pod_store_w.as_reader();
         let pod_cacher = MetaCache::new();
+        let pod_log_controls = PodLogControlsCache::new(pod_state.clone());
+        let invalidated_pod_log_controls = pod_log_controls.clone();
 
         reflectors.push(tokio::spawn(custom_reflector(
             pod_store_w,
             pod_cacher,
             pod_watcher,
             delay_deletion,
+            move |event| invalidated_pod_log_controls.invalidate(event),
         )));
 
         // -----------------------------------------------------------------
@@ -725,6 +742,7 @@ impl Source {
             ns_cacher,
             ns_watcher,
             delay_deletion,
+            |_| {},
         )));
 
         // -----------------------------------------------------------------
@@ -748,6 +766,7 @@ impl Source {
             node_cacher,
             node_watcher,
             delay_deletion,
+            |_| {},
         )));
 
         let paths_provider = K8sPathsProvider::new(
@@ -880,12 +899,20 @@ impl Source {
 
         let (events_count, _) = events.size_hint();
 
-        let mut stream = if auto_partial_merge {
+        let events = if auto_partial_merge {
             merge_partial_events(events, log_namespace).left_stream()
         } else {
             events.right_stream()
         };
 
+        // Per-Pod multiline and parser controls operate on complete messages, so
+        // they have to be applied after partial events are merged.
+        let mut stream = apply_pod_log_controls(
+            events,
+            move |file| pod_log_controls.get(file),
+            log_namespace,
+        );
+
         let event_processing_loop = out.send_event_stream(&mut stream);
 
         let mut lifecycle = Lifecycle::new();
@@ -0,0 +1,647 @@
+//! Per-Pod log collection controls driven by Pod annotations.
+//!
+//! Pod owners can tune how their logs are collected without touching the
+//! Vector configuration:
+//!
+//! - `vector.dev/exclude: "true"` skips all of the Pod's log files, so they are
+//!   never read in the first place.
+//! - `vector.dev/multiline-start-pattern: "<regex>"` merges every line that does
+//!   not match the pattern into the preceding line that did.
+//! - `vector.dev/parser: "json"` parses each (merged) message as a JSON object.
+
+#![deny(missing_docs)]
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+use bytes::BytesMut;
+use futures::{Stream, StreamExt};
+use k8s_openapi::api::core::v1::Pod;
+use kube::runtime::{
+    reflector::{store::Store, ObjectRef},
+    watcher,
+};
+use regex::bytes::Regex;
+use serde_json::Value as JsonValue;
+use vector_lib::config::LogNamespace;
+use vector_lib::lookup::{event_path, OwnedTargetPath};
+use vector_lib::stream::expiration_map::{map_with_expiration, Emitter};
+use vrl::owned_value_path;
+
+use crate::{
+    event::{Event, LogEvent, Value},
+    internal_events::{KubernetesLogsInvalidPodAnnotation, KubernetesLogsParserHintError},
+    sources::kubernetes_logs::{
+        path_helpers::parse_log_file_path, transform_utils::get_message_path,
+    },
+};
+
+/// Annotation that excludes all of a Pod's containers from collection.
+pub const POD_EXCLUSION_ANNOTATION_KEY: &str = "vector.dev/exclude";
+
+/// Annotation holding a regular expression that matches the first line of a message.
+pub const MULTILINE_START_PATTERN_ANNOTATION_KEY: &str = "vector.dev/multiline-start-pattern";
+
+/// Annotation hinting how the messages of a Pod should be parsed.
+pub const PARSER_ANNOTATION_KEY: &str = "vector.dev/parser";
+
+/// The key we use for `file` field.
+const FILE_KEY: &str = "file";
+
+/// How long to wait for a continuation line before flushing a multiline message.
+const MULTILINE_EXPIRATION_TIME: Duration = Duration::from_secs(1);
+
+/// How often buffered multiline messages are checked for expiration.
+const MULTILINE_EXPIRATION_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Returns `true` if the Pod opted out of log collection via its annotations.
+pub fn is_pod_excluded(pod: &Pod) -> bool {
+    pod.metadata
+        .annotations
+        .as_ref()
+        .and_then(|annotations| annotations.get(POD_EXCLUSION_ANNOTATION_KEY))
+        .map_or(false, |value| value.trim().eq_ignore_ascii_case("true"))
+}
+
+/// A parser requested by a Pod through the `vector.dev/parser` annotation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParserHint {
+    /// Messages are JSON objects whose fields are merged into the event.
+    Json,
+}
+
+impl ParserHint {
+    const fn as_str(self) -> &'static str {
+        match self {
+            ParserHint::Json => "json",
+        }
+    }
+}
+
+/// Log collection controls requested by a single Pod.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PodLogControls {
+    /// The raw multiline start pattern, as written in the annotation.
+    pub multiline_start_pattern: Option<String>,
+
+    /// The parser to apply to the Pod's messages.
+    pub parser: Option<ParserHint>,
+}
+
+impl PodLogControls {
+    /// Extracts the log controls from the Pod annotations.
+    ///
+    /// Returns `None` if the Pod does not request any control, so callers can
+    /// skip the extra processing entirely.
+    pub fn from_pod(pod: &Pod) -> Option<Self> {
+        let annotations = pod.metadata.annotations.as_ref()?;
+
+        let multiline_start_pattern = annotations
+            .get(MULTILINE_START_PATTERN_ANNOTATION_KEY)
+            .filter(|pattern| !pattern.is_empty())
+            .cloned();
+
+        let parser = annotations
+            .get(PARSER_ANNOTATION_KEY)
+            .and_then(|value| match value.trim() {
+                "json" => Some(ParserHint::Json),
+                _ => {
+                    debug!(
+                        message = "Ignoring unsupported parser hint.",
+                        parser = %value,
+                        internal_log_rate_limit = true,
+                    );
+                    None
+                }
+            });
+
+        (multiline_start_pattern.is_some() || parser.is_some()).then_some(Self {
+            multiline_start_pattern,
+            parser,
+        })
+    }
+}
+
+/// The log controls of the Pods, parsed once per Pod from its annotations.
+///
+/// The controls are keyed by the UID of the Pods, and the Pod reflector invalidates them whenever
+/// it applies a change, so the annotations are only parsed again when they may have changed.
+#[derive(Clone)]
+pub struct PodLogControlsCache {
+    pods: Store<Pod>,
+    controls: Arc<RwLock<HashMap<String, Option<Arc<PodLogControls>>>>>,
+}
+
+impl PodLogControlsCache {
+    /// Creates a cache of the controls of the Pods of the store.
+    pub fn new(pods: Store<Pod>) -> Self {
+        Self {
+            pods,
+            controls: Arc::default(),
+        }
+    }
+
+    /// Returns the controls of the Pod owning a log file.
+    pub fn get(&self, file: &str) -> Option<Arc<PodLogControls>> {
+        let file_info = parse_log_file_path(file)?;
+        if let Some(controls) = self
+            .controls
+            .read()
+            .expect("poisoned lock")
+            .get(file_info.pod_uid)
+        {
+            return controls.clone();
+        }
+
+        // The store is read under the write lock, so that an invalidation can't happen between
+        // the read of the Pod and the insertion of its controls.
+        let mut controls = self.controls.write().expect("poisoned lock");
+        if let Some(controls) = controls.get(file_info.pod_uid) {
+            return controls.clone();
+        }
+        let obj = ObjectRef::<Pod>::new(file_info.pod_name).within(file_info.pod_namespace);
+        // The files of the Pods missing from the store aren't read anymore, and the files of a
+        // previous Pod with the same name don't have its controls, so neither is cached.
+        let pod = self
+            .pods
+            .get(&obj)
+            .filter(|pod| pod.metadata.uid.as_deref() == Some(file_info.pod_uid))?;
+        let pod_controls = PodLogControls::from_pod(&pod).map(Arc::new);
+        controls.insert(file_info.pod_uid.to_owned(), pod_controls.clone());
+        pod_controls
+    }
+
+    /// Invalidates the controls of the Pods changed by an event applied to the store.
+    pub fn invalidate(&self, event: &watcher::Event<Pod>) {
+        let mut controls = self.controls.write().expect("poisoned lock");
+        match event {
+            watcher::Event::Applied(pod) | watcher::Event::Deleted(pod) => {
+                if let Some(uid) = &pod.metadata.uid {
+                    controls.remove(uid);
+                }
+            }
+            watcher::Event::Restarted(_) => controls.clear(),
+        }
+    }
+}
+
+struct Bucket {
+    event: LogEvent,
+    parser: Option<ParserHint>,
+    expiration: Instant,
+}
+
+struct PodLogControlsState<R> {
+    resolve: R,
+    buckets: HashMap<String, Bucket>,
+    /// Compiled multiline patterns, `None` if the pattern failed to compile.
+    patterns: HashMap<String, Option<Regex>>,
+    message_path: OwnedTargetPath,
+    log_namespace: LogNamespace,
+}
+
+impl<R> PodLogControlsState<R>
+where
+    R: FnMut(&str) -> Option<Arc<PodLogControls>>,
+{
+    fn pattern(&mut self, pattern: &str) -> Option<&Regex> {
+        if !self.patterns.contains_key(pattern) {
+            let regex = match Regex::new(pattern) {
+                Ok(regex) => Some(regex),
+                Err(error) => {
+                    emit!(KubernetesLogsInvalidPodAnnotation {
+                        annotation: MULTILINE_START_PATTERN_ANNOTATION_KEY,
+                        value: pattern,
+                        error: &error,
+                    });
+                    None
+                }
+            };
+            self.patterns.insert(pattern.to_owned(), regex);
+        }
+        self.patterns.get(pattern).and_then(Option::as_ref)
+    }
+
+    fn add_event(&mut self, event: LogEvent, file: String, emitter: &mut Emitter<LogEvent>) {
+        let Some(controls) = (self.resolve)(&file) else {
+            self.flush_file(&file, emitter);
+            emitter.emit(event);
+            return;
+        };
+
+        let message = event
+            .get(&self.message_path)
+            .and_then(Value::as_bytes)
+            .cloned();
+        let starts_message = match (controls.multiline_start_pattern.as_deref(), message) {
+            (Some(pattern), Some(message)) => self
+                .pattern(pattern)
+                .map(|regex| regex.is_match(message.as_ref())),
+            _ => None,
+        };
+
+        match starts_message {
+            // A new message begins, flush the previous one.
+            Some(true) => {
+                self.flush_file(&file, emitter);
+                self.buckets.insert(
+                    file,
+                    Bucket {
+                        event,
+                        parser: controls.parser,
+                        expiration: Instant::now() + MULTILINE_EXPIRATION_TIME,
+                    },
+                );
+            }
+            // A continuation line, append it to the buffered message if any.
+            Some(false) => match self.buckets.get_mut(&file) {
+                Some(bucket) => {
+                    if let (Some(Value::Bytes(prev_value)), Some(Value::Bytes(new_value))) = (
+                        bucket.event.get_mut(&self.message_path),
+                        event.get(&self.message_path),
+                    ) {
+                        let mut bytes_mut =
+                            BytesMut::with_capacity(prev_value.len() + 1 + new_value.len());
+                        bytes_mut.extend_from_slice(prev_value);
+                        bytes_mut.extend_from_slice(b"\n");
+                        bytes_mut.extend_from_slice(new_value);
+                        *prev_value = bytes_mut.freeze();
+                    }
+                    bucket.expiration = Instant::now() + MULTILINE_EXPIRATION_TIME;
+                }
+                None => emitter.emit(self.finalize(event, controls.parser)),
+            },
+            // No usable multiline pattern for this event.
+            None => {
+                self.flush_file(&file, emitter);
+                emitter.emit(self.finalize(event, controls.parser));
+            }
+        }
+    }
+
+    fn flush_file(&mut self, file: &str, emitter: &mut Emitter<LogEvent>) {
+        if let Some(bucket) = self.buckets.remove(file) {
+            emitter.emit(self.finalize(bucket.event, bucket.parser));
+        }
+    }
+
+    fn emit_expired_events(&mut self, emitter: &mut Emitter<LogEvent>) {
+        let now = Instant::now();
+        let expired = self
+            .buckets
+            .iter()
+            .filter(|(_, bucket)| now >= bucket.expiration)
+            .map(|(file, _)| file.clone())
+            .collect::<Vec<_>>();
+        for file in expired {
+            self.flush_file(&file, emitter);
+        }
+    }
+
+    fn flush_events(&mut self, emitter: &mut Emitter<LogEvent>) {
+        let buckets = std::mem::take(&mut self.buckets);
+        for (_, bucket) in buckets {
+            emitter.emit(self.finalize(bucket.event, bucket.parser));
+        }
+    }
+
+    fn finalize(&self, mut event: LogEvent, parser: Option<ParserHint>) -> LogEvent {
+        if let Some(parser) = parser {
+            apply_parser_hint(&mut event, parser, &self.message_path, self.log_namespace);
+        }
+        event
+    }
+}
+
+/// Parses the message according to the parser hint.
+///
+/// On failure the event is left untouched, so the message is never lost.
+fn apply_parser_hint(
+    log: &mut LogEvent,
+    parser: ParserHint,
+    message_path: &OwnedTargetPath,
+    log_namespace: LogNamespace,
+) {
+    let Some(Value::Bytes(message)) = log.get(message_path) else {
+        return;
+    };
+
+    match parser {
+        ParserHint::Json => match serde_json::from_slice::<JsonValue>(message) {
+            Ok(JsonValue::Object(object)) => match log_namespace {
+                LogNamespace::Vector => {
+                    *log.value_mut() = Value::from(JsonValue::Object(object));
+                }
+                LogNamespace::Legacy => {
+                    log.remove(message_path);
+                    // The fields set by the source, such as `kubernetes`, `file` and the
+                    // timestamp, take precedence over the fields of the message.
+                    for (key, value) in object {
+                        if !log.contains(event_path!(key.as_str())) {
+                            log.insert(event_path!(key.as_str()), value);
+                        }
+                    }
+                }
+            },
+            Ok(_) => emit!(KubernetesLogsParserHintError {
+                parser: parser.as_str(),
+                error: &NotAnObjectError,
+            }),
+            Err(error) => emit!(KubernetesLogsParserHintError {
+                parser: parser.as_str(),
+                error: &error,
+            }),
+        },
+    }
+}
+
+#[derive(Debug)]
+struct NotAnObjectError;
+
+impl std::fmt::Display for NotAnObjectError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("message is not a JSON object")
+    }
+}
+
+impl std::error::Error for NotAnObjectError {}
+
+/// Applies the per-Pod log controls to a stream of events.
+///
+/// `resolve` maps a log file path to the controls of the Pod owning it.
+pub fn apply_pod_log_controls<R>(
+    stream: impl Stream<Item = Event> + 'static,
+    resolve: R,
+    log_namespace: LogNamespace,
+) -> impl Stream<Item = Event>
+where
+    R: FnMut(&str) -> Option<Arc<PodLogControls>> + 'static,
+{
+    let file_path = match log_namespace {
+        LogNamespace::Vector => {
+            OwnedTargetPath::metadata(owned_value_path!(super::Config::NAME, FILE_KEY))
+        }
+        LogNamespace::Legacy => OwnedTargetPath::event(owned_value_path!(FILE_KEY)),
+    };
+
+    let state = PodLogControlsState {
+        resolve,
+        buckets: HashMap::new(),
+        patterns: HashMap::new(),
+        message_path: get_message_path(log_namespace),
+        log_namespace,
+    };
+
+    map_with_expiration(
+        state,
+        stream.map(|e| e.into_log()),
+        MULTILINE_EXPIRATION_INTERVAL,
+        move |state: &mut PodLogControlsState<R>,
+              event: LogEvent,
+              emitter: &mut Emitter<LogEvent>| {
+            let file = event
+                .get(&file_path)
+                .and_then(|x| x.as_str())
+                .map(|x| x.to_string())
+                .unwrap_or_default();
+
+            state.add_event(event, file, emitter);
+        },
+        |state: &mut PodLogControlsState<R>, emitter: &mut Emitter<LogEvent>| {
+            state.emit_expired_events(emitter)
+        },
+        |state: &mut PodLogControlsState<R>, emitter: &mut Emitter<LogEvent>| {
+            state.flush_events(emitter);
+        },
+    )
+    .map(|e| e.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+    use vrl::value;
+
+    use super::*;
+
+    fn pod_with_annotations(annotations: &[(&str, &str)]) -> Pod {
+        Pod {
+            metadata: ObjectMeta {
+                annotations: Some(
+                    annotations
+                        .iter()
+                        .map(|(k, v)| (k.to_string(), v.to_string()))
+                        .collect::<BTreeMap<_, _>>(),
+                ),
+                ..ObjectMeta::default()
+            },
+            ..Pod::default()
+        }
+    }
+
+    fn legacy_event(message: &str, file: &str) -> Event {
+        let mut log = LogEvent::from(message);
+        log.insert(FILE_KEY, file);
+        log.into()
+    }
+
+    fn multiline_controls(_file: &str) -> Option<Arc<PodLogControls>> {
+        Some(Arc::new(PodLogControls {
+            multiline_start_pattern: Some(r"^\S".to_owned()),
+            parser: None,
+        }))
+    }
+
+    fn json_controls(_file: &str) -> Option<Arc<PodLogControls>> {
+        Some(Arc::new(PodLogControls {
+            multiline_start_pattern: None,
+            parser: Some(ParserHint::Json),
+        }))
+    }
+
+    #[test]
+    fn pod_exclusion_annotation() {
+        assert!(is_pod_excluded(&pod_with_annotations(&[(
+            POD_EXCLUSION_ANNOTATION_KEY,
+            "true"
+        )])));
+        assert!(is_pod_excluded(&pod_with_annotations(&[(
+            POD_EXCLUSION_ANNOTATION_KEY,
+            " True "
+        )])));
+        assert!(!is_pod_excluded(&pod_with_annotations(&[(
+            POD_EXCLUSION_ANNOTATION_KEY,
+            "false"
+        )])));
+        assert!(!is_pod_excluded(&Pod::default()));
+    }
+
+    #[test]
+    fn controls_from_annotations() {
+        assert_eq!(PodLogControls::from_pod(&Pod::default()), None);
+        assert_eq!(
+            PodLogControls::from_pod(&pod_with_annotations(&[(PARSER_ANNOTATION_KEY, "xml")])),
+            None
+        );
+        assert_eq!(
+            PodLogControls::from_pod(&pod_with_annotations(&[
+                (MULTILINE_START_PATTERN_ANNOTATION_KEY, r"^\d{4}-"),
+                (PARSER_ANNOTATION_KEY, "json"),
+            ])),
+            Some(PodLogControls {
+                multiline_start_pattern: Some(r"^\d{4}-".to_owned()),
+                parser: Some(ParserHint::Json),
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn passthrough_without_controls() {
+        let input = futures::stream::iter([legacy_event("a", "f"), legacy_event(" b", "f")]);
+        let output: Vec<Event> = apply_pod_log_controls(input, |_| None, LogNamespace::Legacy)
+            .collect()
+            .await;
+
+        assert_eq!(output.len(), 2);
+        assert_eq!(output[1].as_log().get(".message"), Some(&value!(" b")));
+    }
+
+    #[tokio::test]
+    async fn merges_multiline_messages_per_file() {
+        let input = futures::stream::iter([
+            legacy_event("Exception in thread main", "f1"),
+            legacy_event("first line", "f2"),
+            legacy_event("  at com.example.Foo", "f1"),
+            legacy_event("  at com.example.Bar", "f1"),
+            legacy_event("next message", "f1"),
+        ]);
+        let output: Vec<Event> =
+            apply_pod_log_controls(input, multiline_controls, LogNamespace::Legacy)
+                .collect()
+                .await;
+
+        let mut messages = output
+            .iter()
+            .map(|e| {
+                e.as_log()
+                    .get(".message")
+                    .unwrap()
+                    .to_string_lossy()
+                    .into_owned()
+            })
+            .collect::<Vec<_>>();
+        messages.sort();
+        assert_eq!(
+            messages,
+            vec![
+                "Exception in thread main\n  at com.example.Foo\n  at com.example.Bar",
+                "first line",
+                "next message",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn multiline_messages_expire() {
+        let input = futures::stream::iter([
+            legacy_event("start", "f1"),
+            legacy_event("  continued", "f1"),
+        ])
+        .chain(futures::stream::pending());
+        let output: Vec<Event> =
+            apply_pod_log_controls(input, multiline_controls, LogNamespace::Legacy)
+                .take(1)
+                .collect()
+                .await;
+
+        assert_eq!(
+            output[0].as_log().get(".message"),
+            Some(&value!("start\n  continued"))
+        );
+    }
+
+    #[tokio::test]
+    async fn json_parser_hint_legacy() {
+        let input = futures::stream::iter([
+            legacy_event(r#"{"level":"info","msg":"hello","file":"other"}"#, "f"),
+            legacy_event("not json", "f"),
+        ]);
+        let output: Vec<Event> = apply_pod_log_controls(input, json_controls, LogNamespace::Legacy)
+            .collect()
+            .await;
+
+        let parsed = output[0].as_log();
+        assert_eq!(parsed.get(".message"), None);
+        assert_eq!(parsed.get(".level"), Some(&value!("info")));
+        assert_eq!(parsed.get(".msg"), Some(&value!("hello")));
+        // The fields of the message don't overwrite the fields set by the source.
+        assert_eq!(parsed.get(".file"), Some(&value!("f")));
+        assert_eq!(
+            output[1].as_log().get(".message"),
+            Some(&value!("not json"))
+        );
+    }
+
+    #[tokio::test]
+    async fn json_parser_hint_vector_namespace() {
+        let mut log = LogEvent::from(value!(r#"{"level":"warn"}"#));
+        log.insert(
+            vrl::metadata_path!(super::super::Config::NAME, FILE_KEY),
+            "f",
+        );
+        let input = futures::stream::iter([Event::from(log)]);
+        let output: Vec<Event> = apply_pod_log_controls(input, json_controls, LogNamespace::Vector)
+            .collect()
+            .await;
+
+        assert_eq!(
+            output[0].as_log().get("."),
+            Some(&value!({"level": "warn"}))
+        );
+        assert_eq!(
+            output[0].as_log().get("%kubernetes_logs.file"),
+            Some(&value!("f"))
+        );
+    }
+
+    #[test]
+    fn caches_controls_until_invalidated() {
+        fn pod(uid: &str, annotations: &[(&str, &str)]) -> Pod {
+            let mut pod = pod_with_annotations(annotations);
+            pod.metadata.name = Some("app".to_owned());
+            pod.metadata.namespace = Some("default".to_owned());
+            pod.metadata.uid = Some(uid.to_owned());
+            pod
+        }
+
+        let file = "/var/log/pods/default_app_uid1/app/0.log";
+        let mut store_w = kube::runtime::reflector::store::Writer::default();
+        let cache = PodLogControlsCache::new(store_w.as_reader());
+        assert_eq!(cache.get(file), None);
+
+        let applied = watcher::Event::Applied(pod("uid1", &[(PARSER_ANNOTATION_KEY, "json")]));
+        store_w.apply_watcher_event(&applied);
+        cache.invalidate(&applied);
+        assert_eq!(cache.get(file).unwrap().parser, Some(ParserHint::Json));
+
+        // The controls are cached until the Pod changes.
+        let applied = watcher::Event::Applied(pod("uid1", &[]));
+        store_w.apply_watcher_event(&applied);
+        assert!(cache.get(file).is_some());
+        cache.invalidate(&applied);
+        assert_eq!(cache.get(file), None);
+
+        // A new Pod with the same name doesn't apply its controls to the files of the previous one.
+        let restarted =
+            watcher::Event::Restarted(vec![pod("uid2", &[(PARSER_ANNOTATION_KEY, "json")])]);
+        store_w.apply_watcher_event(&restarted);
+        cache.invalidate(&restarted);
+        assert_eq!(cache.get(file), None);
+        assert!(cache
+            .get("/var/log/pods/default_app_uid2/app/0.log")
+            .is_some());
+    }
+}
@@ -18,7 +18,7 @@ use super::{
     path_helpers::{parse_log_file_path, LogFileInfo},
     Config,
 };
-use crate::event::{Event, LogEvent};
+use crate::event::{Event, LogEvent, ObjectMap, Value};
 
 /// Configuration for how the events are enriched with Pod metadata.
 #[configurable_component]
@@ -97,6 +97,16 @@ pub struct FieldsSpec {
     #[configurable(metadata(docs::examples = ""))]
     pub pod_owner: OptionalTargetPath,
 
+    /// Event field for all of the Pod's owner references.
+    ///
+    /// Each reference is an object with the `api_version`, `kind`, `name`, `uid` and `controller`
+    /// fields of the owner. Unlike `pod_owner`, this includes every owner, not just the first one.
+    ///
+    /// Disabled by default. Set to a path to enable this key.
+    #[configurable(metadata(docs::examples = ".k8s.pod_owner_references"))]
+    #[configurable(metadata(docs::examples = "k8s.pod_owner_references"))]
+    pub pod_owner_references: OptionalTargetPath,
+
     /// Event field for the Container's name.
     ///
     /// Set to `""` to suppress this key.
@@ -149,6 +159,7 @@ impl Default for FieldsSpec {
             pod_node_name: OwnedTargetPath::event(owned_value_path!("kubernetes", "pod_node_name"))
                 .into(),
             pod_owner: OwnedTargetPath::event(owned_value_path!("kubernetes", "pod_owner")).into(),
+            pod_owner_references: OptionalTargetPath::none(),
             container_name: OwnedTargetPath::event(owned_value_path!(
                 "kubernetes",
                 "container_name"
@@ -310,6 +321,35 @@ fn annotate_from_metadata(
         )
     }
 
+    if let (Some(owner_references), Some(key)) = (
+        &metadata.owner_references,
+        fields_spec.pod_owner_references.path.as_ref(),
+    ) {
+        let value = owner_references
+            .iter()
+            .map(|owner| {
+                let mut object = ObjectMap::new();
+                object.insert("api_version".into(), owner.api_version.clone().into());
+                object.insert("kind".into(), owner.kind.clone().into());
+                object.insert("name".into(), owner.name.clone().into());
+                object.insert("uid".into(), owner.uid.clone().into());
+                object.insert(
+                    "controller".into(),
+                    owner.controller.unwrap_or_default().into(),
+                );
+                Value::Object(object)
+            })
+            .collect::<Vec<_>>();
+
+        log_namespace.insert_source_metadata(
+            Config::NAME,
+            log,
+            Some(LegacyKey::Overwrite(&key.path)),
+            path!("pod_owner_references"),
+            value,
+        )
+    }
+
     if let Some(labels) = &metadata.labels {
         let legacy_key_prefix = fields_spec.pod_labels.path.as_ref().map(|k| &k.path);
 
@@ -478,7 +518,7 @@ fn annotate_from_container(
 
 #[cfg(test)]
 mod tests {
-    use k8s_openapi::api::core::v1::PodIP;
+    use k8s_openapi::{api::core::v1::PodIP, apimachinery::pkg::apis::meta::v1::OwnerReference};
     use similar_asserts::assert_eq;
     use vector_lib::lookup::{event_path, metadata_path};
 
@@ -741,6 +781,47 @@ mod tests {
                 },
                 LogNamespace::Legacy,
             ),
+            // Ensure all owner references are exposed when enabled.
+            (
+                FieldsSpec {
+                    pod_owner_references: OwnedTargetPath::event(owned_value_path!(
+                        "kubernetes",
+                        "pod_owner_references"
+                    ))
+                    .into(),
+                    ..Default::default()
+                },
+                ObjectMeta {
+                    owner_references: Some(vec![OwnerReference {
+                        api_version: "apps/v1".to_owned(),
+                        kind: "ReplicaSet".to_owned(),
+                        name: "sandbox0-rs".to_owned(),
+                        uid: "sandbox0-rs-uid".to_owned(),
+                        controller: Some(true),
+                        ..OwnerReference::default()
+                    }]),
+                    ..ObjectMeta::default()
+                },
+                {
+                    let mut log = LogEvent::default();
+                    log.insert(
+                        event_path!("kubernetes", "pod_owner"),
+                        "ReplicaSet/sandbox0-rs",
+                    );
+                    log.insert(
+                        event_path!("kubernetes", "pod_owner_references"),
+                        vrl::value!([{
+                            "api_version": "apps/v1",
+                            "kind": "ReplicaSet",
+                            "name": "sandbox0-rs",
+                            "uid": "sandbox0-rs-uid",
+                            "controller": true,
+                        }]),
+                    );
+                    log
+                },
+                LogNamespace::Legacy,
+            ),
         ];
 
         for (fields_spec, metadata, expected, log_namespace) in cases.into_iter() {
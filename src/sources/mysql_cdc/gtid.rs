@@ -0,0 +1,173 @@
+use std::{collections::BTreeMap, fmt, ops::Range, str::FromStr};
+
+use mysql_async::{GnoInterval, Sid};
+use snafu::Snafu;
+
+#[derive(Debug, PartialEq, Eq, Snafu)]
+pub(super) enum GtidSetParseError {
+    #[snafu(display("invalid source UUID {:?}", uuid))]
+    InvalidUuid { uuid: String },
+    #[snafu(display("invalid transaction interval {:?}", interval))]
+    InvalidInterval { interval: String },
+}
+
+/// A set of global transaction identifiers, in the `uuid:1-5:7,uuid:1-3` format used by the
+/// `gtid_executed` variable.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(super) struct GtidSet {
+    /// The sorted and disjoint intervals of transaction numbers of each source server. The ends
+    /// of the intervals are exclusive, as sent in the binlog dump request.
+    sources: BTreeMap<[u8; 16], Vec<Range<u64>>>,
+}
+
+impl GtidSet {
+    /// Adds the transaction `gno` of the source server `sid` to the set.
+    pub(super) fn add(&mut self, sid: [u8; 16], gno: u64) {
+        let intervals = self.sources.entry(sid).or_default();
+        let index = intervals.partition_point(|interval| interval.end < gno);
+        match intervals.get_mut(index) {
+            Some(interval) if interval.contains(&gno) => {}
+            Some(interval) if interval.end == gno => {
+                interval.end = gno + 1;
+                // The interval may now be adjacent to the next one.
+                if intervals
+                    .get(index + 1)
+                    .is_some_and(|next| next.start == gno + 1)
+                {
+                    let next = intervals.remove(index + 1);
+                    intervals[index].end = next.end;
+                }
+            }
+            Some(interval) if interval.start == gno + 1 => interval.start = gno,
+            _ => intervals.insert(index, gno..gno + 1),
+        }
+    }
+
+    /// Returns the set in the form sent in the binlog dump request.
+    pub(super) fn to_sids(&self) -> Vec<Sid<'static>> {
+        self.sources
+            .iter()
+            .map(|(uuid, intervals)| {
+                intervals.iter().fold(Sid::new(*uuid), |sid, interval| {
+                    sid.with_interval(GnoInterval::new(interval.start, interval.end))
+                })
+            })
+            .collect()
+    }
+}
+
+impl FromStr for GtidSet {
+    type Err = GtidSetParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut set = Self::default();
+        for source in s.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+            let mut parts = source.split(':');
+            let uuid = parts.next().unwrap_or_default();
+            let sid = parse_uuid(uuid).ok_or_else(|| GtidSetParseError::InvalidUuid {
+                uuid: uuid.to_owned(),
+            })?;
+
+            let intervals = set.sources.entry(sid).or_default();
+            for part in parts {
+                let interval =
+                    parse_interval(part).ok_or_else(|| GtidSetParseError::InvalidInterval {
+                        interval: part.to_owned(),
+                    })?;
+                intervals.push(interval);
+            }
+            intervals.sort_by_key(|interval| interval.start);
+        }
+        Ok(set)
+    }
+}
+
+fn parse_uuid(uuid: &str) -> Option<[u8; 16]> {
+    let hex = uuid.replace('-', "");
+    if hex.len() != 32 || !hex.is_ascii() {
+        return None;
+    }
+    let mut sid = [0; 16];
+    for (index, byte) in sid.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[index * 2..index * 2 + 2], 16).ok()?;
+    }
+    Some(sid)
+}
+
+fn parse_interval(interval: &str) -> Option<Range<u64>> {
+    let (start, end) = match interval.split_once('-') {
+        Some((start, end)) => (start.parse().ok()?, end.parse::<u64>().ok()?),
+        None => {
+            let gno = interval.parse().ok()?;
+            (gno, gno)
+        }
+    };
+    (start > 0 && start <= end).then_some(start..end + 1)
+}
+
+impl fmt::Display for GtidSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, (sid, intervals)) in self.sources.iter().enumerate() {
+            if index > 0 {
+                f.write_str(",")?;
+            }
+            for (index, byte) in sid.iter().enumerate() {
+                if matches!(index, 4 | 6 | 8 | 10) {
+                    f.write_str("-")?;
+                }
+                write!(f, "{:02x}", byte)?;
+            }
+            for interval in intervals {
+                match interval.end - interval.start {
+                    1 => write!(f, ":{}", interval.start)?,
+                    _ => write!(f, ":{}-{}", interval.start, interval.end - 1)?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const UUID: &str = "3e11fa47-71ca-11e1-9e33-c80aa9429562";
+
+    #[test]
+    fn parses_and_formats_sets() {
+        let set = format!("{}:1-5:7,\n4a6b2e10-71ca-11e1-9e33-c80aa9429562:1-3", UUID);
+        assert_eq!(
+            set.parse::<GtidSet>().unwrap().to_string(),
+            format!("{}:1-5:7,4a6b2e10-71ca-11e1-9e33-c80aa9429562:1-3", UUID)
+        );
+        assert_eq!("".parse::<GtidSet>().unwrap(), GtidSet::default());
+    }
+
+    #[test]
+    fn rejects_invalid_sets() {
+        assert!(matches!(
+            "3e11fa47:1-5".parse::<GtidSet>(),
+            Err(GtidSetParseError::InvalidUuid { .. })
+        ));
+        assert!(matches!(
+            format!("{}:5-1", UUID).parse::<GtidSet>(),
+            Err(GtidSetParseError::InvalidInterval { .. })
+        ));
+    }
+
+    #[test]
+    fn adds_transactions() {
+        let mut set = format!("{}:1-3:5", UUID).parse::<GtidSet>().unwrap();
+        let sid = parse_uuid(UUID).unwrap();
+
+        set.add(sid, 2);
+        assert_eq!(set.to_string(), format!("{}:1-3:5", UUID));
+        set.add(sid, 7);
+        assert_eq!(set.to_string(), format!("{}:1-3:5:7", UUID));
+        set.add(sid, 4);
+        assert_eq!(set.to_string(), format!("{}:1-5:7", UUID));
+        set.add(sid, 6);
+        assert_eq!(set.to_string(), format!("{}:1-7", UUID));
+    }
+}
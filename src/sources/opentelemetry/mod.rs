@@ -11,6 +11,7 @@ mod status;
 use std::net::SocketAddr;
 
 use futures::{future::join, FutureExt, TryFutureExt};
+use tonic::transport::server::Routes;
 use vector_lib::lookup::{owned_value_path, OwnedTargetPath};
 use vector_lib::opentelemetry::convert::{
     ATTRIBUTES_KEY, DROPPED_ATTRIBUTES_COUNT_KEY, FLAGS_KEY, OBSERVED_TIMESTAMP_KEY, RESOURCE_KEY,
@@ -19,7 +20,11 @@ use vector_lib::opentelemetry::convert::{
 
 use vector_lib::configurable::configurable_component;
 use vector_lib::internal_event::{BytesReceived, EventsReceived, Protocol};
-use vector_lib::opentelemetry::proto::collector::logs::v1::logs_service_server::LogsServiceServer;
+use vector_lib::opentelemetry::proto::collector::{
+    logs::v1::logs_service_server::LogsServiceServer,
+    metrics::v1::metrics_service_server::MetricsServiceServer,
+    trace::v1::trace_service_server::TraceServiceServer,
+};
 use vector_lib::{
     config::{log_schema, LegacyKey, LogNamespace},
     schema::Definition,
@@ -37,14 +42,19 @@ use crate::{
     },
     http::KeepaliveConfig,
     serde::bool_or_struct,
-    sources::{util::grpc::run_grpc_server, Source},
+    sources::{util::grpc::run_grpc_server_with_routes, Source},
     tls::{MaybeTlsSettings, TlsEnableableConfig},
 };
 
 pub const LOGS: &str = "logs";
+pub const METRICS: &str = "metrics";
+pub const TRACES: &str = "traces";
 
 /// Configuration for the `opentelemetry` source.
-#[configurable_component(source("opentelemetry", "Receive OTLP data through gRPC or HTTP."))]
+#[configurable_component(source(
+    "opentelemetry",
+    "Receive OTLP logs, metrics, and traces through gRPC or HTTP."
+))]
 #[derive(Clone, Debug)]
 #[serde(deny_unknown_fields)]
 pub struct OpentelemetryConfig {
@@ -138,20 +148,33 @@ impl SourceConfig for OpentelemetryConfig {
         let log_namespace = cx.log_namespace(self.log_namespace);
 
         let grpc_tls_settings = MaybeTlsSettings::from_config(&self.grpc.tls, true)?;
-        let grpc_service = LogsServiceServer::new(Service {
+        let grpc_service = Service {
             pipeline: cx.out.clone(),
             acknowledgements,
             log_namespace,
             events_received: events_received.clone(),
-        })
-        .accept_compressed(tonic::codec::CompressionEncoding::Gzip)
-        // Tonic added a default of 4MB in 0.9. This replaces the old behavior.
-        .max_decoding_message_size(usize::MAX);
+        };
+        let grpc_routes = Routes::new(
+            LogsServiceServer::new(grpc_service.clone())
+                .accept_compressed(tonic::codec::CompressionEncoding::Gzip)
+                // Tonic added a default of 4MB in 0.9. This replaces the old behavior.
+                .max_decoding_message_size(usize::MAX),
+        )
+        .add_service(
+            MetricsServiceServer::new(grpc_service.clone())
+                .accept_compressed(tonic::codec::CompressionEncoding::Gzip)
+                .max_decoding_message_size(usize::MAX),
+        )
+        .add_service(
+            TraceServiceServer::new(grpc_service)
+                .accept_compressed(tonic::codec::CompressionEncoding::Gzip)
+                .max_decoding_message_size(usize::MAX),
+        );
 
-        let grpc_source = run_grpc_server(
+        let grpc_source = run_grpc_server_with_routes(
             self.grpc.address,
             grpc_tls_settings,
-            grpc_service,
+            grpc_routes,
             cx.shutdown.clone(),
         )
         .map_err(|error| {
@@ -269,7 +292,11 @@ impl SourceConfig for OpentelemetryConfig {
             }
         };
 
-        vec![SourceOutput::new_logs(DataType::Log, schema_definition).with_port(LOGS)]
+        vec![
+            SourceOutput::new_logs(DataType::Log, schema_definition).with_port(LOGS),
+            SourceOutput::new_metrics().with_port(METRICS),
+            SourceOutput::new_traces().with_port(TRACES),
+        ]
     }
 
     fn resources(&self) -> Vec<Resource> {
@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{net::SocketAddr, sync::Arc};
 
 use chrono::{TimeZone, Utc};
 use futures::Stream;
@@ -6,20 +6,31 @@ use futures_util::StreamExt;
 use similar_asserts::assert_eq;
 use tonic::Request;
 use vector_lib::config::LogNamespace;
-use vector_lib::lookup::path;
+use vector_lib::lookup::{event_path, path};
 use vector_lib::opentelemetry::proto::{
-    collector::logs::v1::{logs_service_client::LogsServiceClient, ExportLogsServiceRequest},
+    collector::{
+        logs::v1::{logs_service_client::LogsServiceClient, ExportLogsServiceRequest},
+        metrics::v1::ExportMetricsServiceRequest,
+        trace::v1::{trace_service_client::TraceServiceClient, ExportTraceServiceRequest},
+    },
     common::v1::{any_value, AnyValue, KeyValue},
     logs::v1::{LogRecord, ResourceLogs, ScopeLogs},
+    metrics::v1::{
+        metric, number_data_point, AggregationTemporality, Metric as OtelMetric, NumberDataPoint,
+        ResourceMetrics, ScopeMetrics, Sum,
+    },
     resource::v1::Resource as OtelResource,
+    trace::v1::{ResourceSpans, ScopeSpans, Span},
 };
 use vrl::value;
 
 use crate::config::OutputId;
 use crate::{
     config::{SourceConfig, SourceContext},
-    event::{into_event_stream, Event, EventStatus, LogEvent, ObjectMap, Value},
-    sources::opentelemetry::{GrpcConfig, HttpConfig, OpentelemetryConfig, LOGS},
+    event::{
+        into_event_stream, Event, EventStatus, LogEvent, MetricKind, MetricValue, ObjectMap, Value,
+    },
+    sources::opentelemetry::{GrpcConfig, HttpConfig, OpentelemetryConfig, LOGS, METRICS, TRACES},
     test_util::{
         self,
         components::{assert_source_compliance, SOURCE_TAGS},
@@ -283,6 +294,152 @@ async fn receive_grpc_logs_legacy_namespace() {
     .await;
 }
 
+#[tokio::test]
+async fn receive_grpc_traces() {
+    assert_source_compliance(&SOURCE_TAGS, async {
+        let grpc_addr = next_addr();
+        let source = test_config(grpc_addr, next_addr());
+
+        let (mut sender, _) = SourceSender::new_test_finalize(EventStatus::Delivered);
+        let traces_output = sender
+            .add_outputs(EventStatus::Delivered, TRACES.to_string())
+            .flat_map(into_event_stream);
+        let server = source
+            .build(SourceContext::new_test(sender, None))
+            .await
+            .unwrap();
+        tokio::spawn(server);
+        test_util::wait_for_tcp(grpc_addr).await;
+
+        let mut client = TraceServiceClient::connect(format!("http://{}", grpc_addr))
+            .await
+            .unwrap();
+        let req = Request::new(ExportTraceServiceRequest {
+            resource_spans: vec![ResourceSpans {
+                resource: Some(OtelResource {
+                    attributes: vec![KeyValue {
+                        key: "res_key".into(),
+                        value: Some(AnyValue {
+                            value: Some(any_value::Value::StringValue("res_val".into())),
+                        }),
+                    }],
+                    dropped_attributes_count: 0,
+                }),
+                scope_spans: vec![ScopeSpans {
+                    scope: None,
+                    spans: vec![Span {
+                        trace_id: str_into_hex_bytes("4ac52aadf321c2e531db005df08792f5"),
+                        span_id: str_into_hex_bytes("0b9e4bda2a55530d"),
+                        name: "checkout".into(),
+                        start_time_unix_nano: 1,
+                        end_time_unix_nano: 2,
+                        ..Default::default()
+                    }],
+                    schema_url: "v1".into(),
+                }],
+                schema_url: "v1".into(),
+            }],
+        });
+        _ = client.export(req).await;
+
+        let mut output = test_util::collect_ready(traces_output).await;
+        assert_eq!(output.len(), 1);
+        let trace = output.pop().unwrap().into_trace();
+        assert_eq!(
+            trace.get(event_path!("trace_id")),
+            Some(&Value::from("4ac52aadf321c2e531db005df08792f5"))
+        );
+        assert_eq!(
+            trace.get(event_path!("name")),
+            Some(&Value::from("checkout"))
+        );
+        assert_eq!(
+            trace.get(event_path!("start_time_unix_nano")),
+            Some(&Value::from(Utc.timestamp_nanos(1)))
+        );
+        assert_eq!(
+            trace.get(event_path!("resources", "res_key")),
+            Some(&Value::from("res_val"))
+        );
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn receive_http_metrics() {
+    assert_source_compliance(&SOURCE_TAGS, async {
+        let http_addr = next_addr();
+        let source = test_config(next_addr(), http_addr);
+
+        let (mut sender, _) = SourceSender::new_test_finalize(EventStatus::Delivered);
+        let metrics_output = sender
+            .add_outputs(EventStatus::Delivered, METRICS.to_string())
+            .flat_map(into_event_stream);
+        let server = source
+            .build(SourceContext::new_test(sender, None))
+            .await
+            .unwrap();
+        tokio::spawn(server);
+        test_util::wait_for_tcp(http_addr).await;
+
+        let req = ExportMetricsServiceRequest {
+            resource_metrics: vec![ResourceMetrics {
+                resource: None,
+                scope_metrics: vec![ScopeMetrics {
+                    scope: None,
+                    metrics: vec![OtelMetric {
+                        name: "requests_total".into(),
+                        description: String::new(),
+                        unit: String::new(),
+                        data: Some(metric::Data::Sum(Sum {
+                            data_points: vec![NumberDataPoint {
+                                value: Some(number_data_point::Value::AsInt(5)),
+                                ..Default::default()
+                            }],
+                            aggregation_temporality: AggregationTemporality::Delta as i32,
+                            is_monotonic: true,
+                        })),
+                    }],
+                    schema_url: String::new(),
+                }],
+                schema_url: String::new(),
+            }],
+        };
+        let res = reqwest::Client::new()
+            .post(format!("http://{}/v1/metrics", http_addr))
+            .header("Content-Type", "application/x-protobuf")
+            .body(prost::Message::encode_to_vec(&req))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), 200);
+
+        let mut output = test_util::collect_ready(metrics_output).await;
+        assert_eq!(output.len(), 1);
+        let metric = output.pop().unwrap().into_metric();
+        assert_eq!(metric.name(), "requests_total");
+        assert_eq!(metric.kind(), MetricKind::Incremental);
+        assert_eq!(metric.value(), &MetricValue::Counter { value: 5.0 });
+    })
+    .await;
+}
+
+fn test_config(grpc_addr: SocketAddr, http_addr: SocketAddr) -> OpentelemetryConfig {
+    OpentelemetryConfig {
+        grpc: GrpcConfig {
+            address: grpc_addr,
+            tls: Default::default(),
+        },
+        http: HttpConfig {
+            address: http_addr,
+            tls: Default::default(),
+            keepalive: Default::default(),
+        },
+        acknowledgements: Default::default(),
+        log_namespace: None,
+    }
+}
+
 pub(super) fn new_source(
     status: EventStatus,
 ) -> (
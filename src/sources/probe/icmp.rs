@@ -0,0 +1,212 @@
+use std::{
+    io,
+    net::{IpAddr, SocketAddr},
+    time::{Duration, Instant},
+};
+
+use socket2::{Domain, Protocol, Socket, Type};
+use tokio::{net::UdpSocket, time::timeout};
+
+const ECHO_REQUEST_V4: u8 = 8;
+const ECHO_REPLY_V4: u8 = 0;
+const ECHO_REQUEST_V6: u8 = 128;
+const ECHO_REPLY_V6: u8 = 129;
+const HEADER_LEN: usize = 8;
+const PAYLOAD: &[u8] = b"vector probe\0\0\0\0";
+
+/// The outcome of a series of echo requests.
+#[derive(Debug, Default, PartialEq)]
+pub(super) struct PingResult {
+    pub(super) sent: u16,
+    pub(super) round_trip_times: Vec<Duration>,
+}
+
+impl PingResult {
+    pub(super) fn received(&self) -> u16 {
+        self.round_trip_times.len() as u16
+    }
+
+    pub(super) fn mean_round_trip_time(&self) -> Option<Duration> {
+        let count = self.round_trip_times.len() as u32;
+        (count > 0).then(|| self.round_trip_times.iter().sum::<Duration>() / count)
+    }
+}
+
+/// A socket sending ICMP echo requests to a single address.
+struct IcmpSocket {
+    socket: UdpSocket,
+    /// Raw sockets receive every ICMP message of the host rather than the replies to their own
+    /// requests, so replies are matched by identifier.
+    raw: bool,
+    identifier: u16,
+    v6: bool,
+}
+
+impl IcmpSocket {
+    /// Opens an unprivileged datagram ICMP socket, which Linux allows for the groups listed in
+    /// `net.ipv4.ping_group_range`, falling back to a raw socket otherwise.
+    async fn connect(address: IpAddr) -> io::Result<Self> {
+        let (domain, protocol) = match address {
+            IpAddr::V4(_) => (Domain::IPV4, Protocol::ICMPV4),
+            IpAddr::V6(_) => (Domain::IPV6, Protocol::ICMPV6),
+        };
+        let (socket, raw) = match Socket::new(domain, Type::DGRAM, Some(protocol)) {
+            Ok(socket) => (socket, false),
+            Err(_) => (Socket::new(domain, Type::RAW, Some(protocol))?, true),
+        };
+        socket.set_nonblocking(true)?;
+
+        let socket = UdpSocket::from_std(socket.into())?;
+        socket.connect(SocketAddr::new(address, 0)).await?;
+        Ok(Self {
+            socket,
+            raw,
+            identifier: rand::random(),
+            v6: address.is_ipv6(),
+        })
+    }
+
+    /// Sends an echo request, and waits up to `timeout_duration` for its reply.
+    async fn ping(
+        &self,
+        sequence: u16,
+        timeout_duration: Duration,
+    ) -> io::Result<Option<Duration>> {
+        let request = echo_request(self.v6, self.identifier, sequence);
+        let start = Instant::now();
+        self.socket.send(&request).await?;
+
+        let mut buf = [0; 1024];
+        let receive = async {
+            loop {
+                let len = self.socket.recv(&mut buf).await?;
+                let identifier = self.raw.then_some(self.identifier);
+                if is_echo_reply(&buf[..len], self.v6, identifier, sequence) {
+                    return Ok::<_, io::Error>(start.elapsed());
+                }
+            }
+        };
+        match timeout(timeout_duration, receive).await {
+            Ok(result) => result.map(Some),
+            Err(_) => Ok(None),
+        }
+    }
+}
+
+/// Sends `count` echo requests to `address`, one after the other.
+pub(super) async fn ping(
+    address: IpAddr,
+    count: u16,
+    timeout_duration: Duration,
+) -> io::Result<PingResult> {
+    let socket = IcmpSocket::connect(address).await?;
+    let mut result = PingResult::default();
+    for sequence in 0..count {
+        result.sent += 1;
+        if let Some(round_trip_time) = socket.ping(sequence, timeout_duration).await? {
+            result.round_trip_times.push(round_trip_time);
+        }
+    }
+    Ok(result)
+}
+
+fn echo_request(v6: bool, identifier: u16, sequence: u16) -> Vec<u8> {
+    let mut packet = Vec::with_capacity(HEADER_LEN + PAYLOAD.len());
+    packet.push(if v6 { ECHO_REQUEST_V6 } else { ECHO_REQUEST_V4 });
+    packet.push(0);
+    packet.extend_from_slice(&[0, 0]);
+    packet.extend_from_slice(&identifier.to_be_bytes());
+    packet.extend_from_slice(&sequence.to_be_bytes());
+    packet.extend_from_slice(PAYLOAD);
+
+    // The checksum of ICMPv6 messages covers a pseudo-header, and is computed by the kernel.
+    if !v6 {
+        let checksum = checksum(&packet);
+        packet[2..4].copy_from_slice(&checksum.to_be_bytes());
+    }
+    packet
+}
+
+fn is_echo_reply(mut packet: &[u8], v6: bool, identifier: Option<u16>, sequence: u16) -> bool {
+    // Raw IPv4 sockets, and datagram sockets on some platforms, receive the IP header along with
+    // the ICMP message.
+    if !v6 && packet.first().is_some_and(|byte| byte >> 4 == 4) {
+        let header_len = usize::from(packet[0] & 0x0f) * 4;
+        packet = packet.get(header_len..).unwrap_or_default();
+    }
+    if packet.len() < HEADER_LEN {
+        return false;
+    }
+
+    let reply_type = if v6 { ECHO_REPLY_V6 } else { ECHO_REPLY_V4 };
+    packet[0] == reply_type
+        && identifier.map_or(true, |identifier| {
+            u16::from_be_bytes([packet[4], packet[5]]) == identifier
+        })
+        && u16::from_be_bytes([packet[6], packet[7]]) == sequence
+}
+
+/// The Internet checksum of RFC 1071.
+fn checksum(data: &[u8]) -> u16 {
+    let mut sum = data
+        .chunks(2)
+        .map(|chunk| match chunk {
+            [high, low] => u32::from(u16::from_be_bytes([*high, *low])),
+            [high] => u32::from(*high) << 8,
+            _ => 0,
+        })
+        .sum::<u32>();
+    while sum >> 16 != 0 {
+        sum = (sum & 0xffff) + (sum >> 16);
+    }
+    !(sum as u16)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_echo_requests() {
+        let request = echo_request(false, 0x1234, 7);
+        assert_eq!(&request[..2], &[ECHO_REQUEST_V4, 0]);
+        assert_eq!(&request[4..8], &[0x12, 0x34, 0, 7]);
+        // A valid checksum makes the checksum of the whole message zero.
+        assert_eq!(checksum(&request), 0);
+
+        let request = echo_request(true, 0x1234, 7);
+        assert_eq!(&request[..4], &[ECHO_REQUEST_V6, 0, 0, 0]);
+    }
+
+    #[test]
+    fn matches_echo_replies() {
+        let mut reply = echo_request(false, 0x1234, 7);
+        reply[0] = ECHO_REPLY_V4;
+        assert!(is_echo_reply(&reply, false, None, 7));
+        assert!(is_echo_reply(&reply, false, Some(0x1234), 7));
+        assert!(!is_echo_reply(&reply, false, Some(0x4321), 7));
+        assert!(!is_echo_reply(&reply, false, None, 8));
+
+        let mut with_ip_header = vec![0x45];
+        with_ip_header.extend_from_slice(&[0; 19]);
+        with_ip_header.extend_from_slice(&reply);
+        assert!(is_echo_reply(&with_ip_header, false, Some(0x1234), 7));
+
+        let request = echo_request(false, 0x1234, 7);
+        assert!(!is_echo_reply(&request, false, None, 7));
+    }
+
+    #[test]
+    fn computes_mean_round_trip_time() {
+        let result = PingResult {
+            sent: 3,
+            round_trip_times: vec![Duration::from_millis(10), Duration::from_millis(20)],
+        };
+        assert_eq!(result.received(), 2);
+        assert_eq!(
+            result.mean_round_trip_time(),
+            Some(Duration::from_millis(15))
+        );
+        assert_eq!(PingResult::default().mean_round_trip_time(), None);
+    }
+}
@@ -0,0 +1,590 @@
+use std::{
+    net::SocketAddr,
+    time::{Duration, Instant},
+};
+
+use bytes::Bytes;
+use chrono::Utc;
+use futures::{future::join_all, StreamExt};
+use serde_with::serde_as;
+use snafu::Snafu;
+use tokio::{
+    net::{lookup_host, TcpStream, ToSocketAddrs},
+    time,
+};
+use tokio_stream::wrappers::IntervalStream;
+use vector_lib::configurable::configurable_component;
+use vector_lib::lookup::{metadata_path, owned_value_path, path};
+use vector_lib::{
+    config::{LegacyKey, LogNamespace},
+    internal_event::{CountByteSize, InternalEventHandle as _},
+    metric_tags,
+    schema::Definition,
+    EstimatedJsonEncodedSizeOf,
+};
+use vrl::value::{Kind, ObjectMap};
+
+use crate::{
+    config::{log_schema, DataType, SourceConfig, SourceContext, SourceOutput},
+    event::{
+        metric::{Metric, MetricKind, MetricTags, MetricValue},
+        LogEvent,
+    },
+    internal_events::{
+        CollectionCompleted, EventsReceived, ProbeIcmpSocketError, StreamClosedError,
+    },
+};
+
+mod icmp;
+
+use icmp::PingResult;
+
+/// The name of the output receiving failed probes and lost packets.
+const FAILURES: &str = "failures";
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("`timeout_secs` must be greater than zero"))]
+    ZeroTimeout,
+    #[snafu(display("`packets` of ICMP target {:?} must be greater than zero", host))]
+    ZeroPackets { host: String },
+}
+
+/// Configuration for the `probe` source.
+#[serde_as]
+#[configurable_component(source(
+    "probe",
+    "Probe the availability and latency of hosts with ICMP echo requests or TCP connections."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ProbeConfig {
+    /// A list of targets to probe.
+    targets: Vec<ProbeTarget>,
+
+    /// The interval between probes.
+    #[serde(default = "default_interval_secs")]
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    #[configurable(metadata(docs::human_name = "Probe Interval"))]
+    interval_secs: Duration,
+
+    /// The maximum time to wait for a TCP connection to be established, or for the reply to an
+    /// ICMP echo request.
+    ///
+    /// A probe that does not complete in time is considered failed, and an echo request whose
+    /// reply does not arrive in time is considered lost.
+    #[serde(default = "default_timeout_secs")]
+    #[serde_as(as = "serde_with::DurationSecondsWithFrac<f64>")]
+    #[configurable(metadata(docs::human_name = "Timeout"))]
+    timeout_secs: Duration,
+
+    /// Overrides the default namespace for the metrics emitted by the source.
+    ///
+    /// If set to an empty string, no namespace is added to the metrics.
+    ///
+    /// By default, `probe` is used.
+    #[serde(default = "default_namespace")]
+    namespace: String,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[configurable(metadata(docs::hidden))]
+    #[serde(default)]
+    log_namespace: Option<bool>,
+}
+
+/// A target to probe.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(tag = "protocol", rename_all = "snake_case")]
+#[configurable(metadata(docs::enum_tag_description = "The protocol used to probe the target."))]
+pub enum ProbeTarget {
+    /// Sends ICMP echo requests to the target.
+    ///
+    /// Unprivileged ICMP sockets are used where the operating system allows them, which on Linux
+    /// requires the group of Vector to be included in the `net.ipv4.ping_group_range` sysctl.
+    /// Otherwise, raw sockets are used, which require the `CAP_NET_RAW` capability.
+    Icmp(IcmpTargetConfig),
+
+    /// Opens TCP connections to the target.
+    Tcp(TcpTargetConfig),
+}
+
+/// Configuration of an ICMP target.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct IcmpTargetConfig {
+    /// The host name or IP address of the target.
+    #[configurable(metadata(docs::examples = "192.168.1.1"))]
+    #[configurable(metadata(docs::examples = "example.com"))]
+    host: String,
+
+    /// The number of echo requests sent to the target on each probe.
+    #[serde(default = "default_packets")]
+    packets: u16,
+}
+
+/// Configuration of a TCP target.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct TcpTargetConfig {
+    /// The address of the target, in the form of `host:port`.
+    #[configurable(metadata(docs::examples = "example.com:443"))]
+    #[configurable(metadata(docs::examples = "10.0.0.5:5432"))]
+    address: String,
+}
+
+const fn default_interval_secs() -> Duration {
+    Duration::from_secs(15)
+}
+
+const fn default_timeout_secs() -> Duration {
+    Duration::from_secs(5)
+}
+
+const fn default_packets() -> u16 {
+    3
+}
+
+fn default_namespace() -> String {
+    "probe".to_string()
+}
+
+impl Default for ProbeConfig {
+    fn default() -> Self {
+        Self {
+            targets: Vec::new(),
+            interval_secs: default_interval_secs(),
+            timeout_secs: default_timeout_secs(),
+            namespace: default_namespace(),
+            log_namespace: None,
+        }
+    }
+}
+
+impl_generate_config_from_default!(ProbeConfig);
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "probe")]
+impl SourceConfig for ProbeConfig {
+    async fn build(&self, mut cx: SourceContext) -> crate::Result<super::Source> {
+        if self.timeout_secs.is_zero() {
+            return Err(BuildError::ZeroTimeout.into());
+        }
+        for target in &self.targets {
+            if let ProbeTarget::Icmp(icmp) = target {
+                if icmp.packets == 0 {
+                    return Err(BuildError::ZeroPackets {
+                        host: icmp.host.clone(),
+                    }
+                    .into());
+                }
+            }
+        }
+
+        let namespace = Some(self.namespace.clone()).filter(|namespace| !namespace.is_empty());
+        let log_namespace = cx.log_namespace(self.log_namespace);
+        let probers = self
+            .targets
+            .iter()
+            .map(|target| Prober {
+                target: target.clone(),
+                timeout: self.timeout_secs,
+                namespace: namespace.clone(),
+                log_namespace,
+            })
+            .collect::<Vec<_>>();
+
+        let duration = self.interval_secs;
+        let shutdown = cx.shutdown;
+        Ok(Box::pin(async move {
+            let events_received = register!(EventsReceived);
+            let mut interval = IntervalStream::new(time::interval(duration)).take_until(shutdown);
+            while interval.next().await.is_some() {
+                let start = Instant::now();
+                let outcomes = join_all(probers.iter().map(|prober| prober.probe())).await;
+                emit!(CollectionCompleted {
+                    start,
+                    end: Instant::now()
+                });
+
+                let mut metrics = Vec::new();
+                let mut failures = Vec::new();
+                for (prober, outcome) in probers.iter().zip(outcomes) {
+                    if let Some(outcome) = outcome {
+                        metrics.extend(prober.metrics(&outcome));
+                        failures.extend(prober.failure(&outcome));
+                    }
+                }
+
+                let count = metrics.len();
+                events_received.emit(CountByteSize(
+                    count,
+                    metrics.estimated_json_encoded_size_of(),
+                ));
+                if cx.out.send_batch(metrics).await.is_err() {
+                    emit!(StreamClosedError { count });
+                    return Err(());
+                }
+
+                if !failures.is_empty() {
+                    let count = failures.len();
+                    events_received.emit(CountByteSize(
+                        count,
+                        failures.estimated_json_encoded_size_of(),
+                    ));
+                    if cx.out.send_batch_named(FAILURES, failures).await.is_err() {
+                        emit!(StreamClosedError { count });
+                        return Err(());
+                    }
+                }
+            }
+
+            Ok(())
+        }))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
+        let log_namespace = global_log_namespace.merge(self.log_namespace);
+
+        let schema_definition = match log_namespace {
+            LogNamespace::Legacy => {
+                Definition::empty_legacy_namespace().unknown_fields(Kind::any())
+            }
+            LogNamespace::Vector => {
+                Definition::new_with_default_metadata(Kind::any_object(), [log_namespace])
+            }
+        }
+        .with_source_metadata(
+            Self::NAME,
+            None,
+            &owned_value_path!("timestamp"),
+            Kind::timestamp(),
+            Some("timestamp"),
+        )
+        .with_standard_vector_source_metadata();
+
+        vec![
+            SourceOutput::new_metrics(),
+            SourceOutput::new_logs(DataType::Log, schema_definition).with_port(FAILURES),
+        ]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+/// The outcome of probing a target.
+#[derive(Debug)]
+enum Outcome {
+    Icmp(Result<PingResult, String>),
+    Tcp(Result<Duration, String>),
+}
+
+#[derive(Debug)]
+struct Prober {
+    target: ProbeTarget,
+    timeout: Duration,
+    namespace: Option<String>,
+    log_namespace: LogNamespace,
+}
+
+impl Prober {
+    const fn protocol(&self) -> &'static str {
+        match self.target {
+            ProbeTarget::Icmp(_) => "icmp",
+            ProbeTarget::Tcp(_) => "tcp",
+        }
+    }
+
+    fn target(&self) -> &str {
+        match &self.target {
+            ProbeTarget::Icmp(icmp) => &icmp.host,
+            ProbeTarget::Tcp(tcp) => &tcp.address,
+        }
+    }
+
+    /// Probes the target, returning `None` if the probe itself could not be run.
+    async fn probe(&self) -> Option<Outcome> {
+        match &self.target {
+            ProbeTarget::Icmp(icmp) => {
+                let address = match resolve((icmp.host.as_str(), 0)).await {
+                    Ok(address) => address,
+                    Err(error) => return Some(Outcome::Icmp(Err(error))),
+                };
+                match icmp::ping(address.ip(), icmp.packets, self.timeout).await {
+                    Ok(result) => Some(Outcome::Icmp(Ok(result))),
+                    Err(error) => {
+                        emit!(ProbeIcmpSocketError {
+                            error,
+                            host: &icmp.host,
+                        });
+                        None
+                    }
+                }
+            }
+            ProbeTarget::Tcp(tcp) => Some(Outcome::Tcp(self.connect(&tcp.address).await)),
+        }
+    }
+
+    async fn connect(&self, address: &str) -> Result<Duration, String> {
+        let address = resolve(address).await?;
+
+        let start = Instant::now();
+        match time::timeout(self.timeout, TcpStream::connect(address)).await {
+            Ok(Ok(_)) => Ok(start.elapsed()),
+            Ok(Err(error)) => Err(error.to_string()),
+            Err(_) => Err("connection timed out".to_string()),
+        }
+    }
+
+    fn metrics(&self, outcome: &Outcome) -> Vec<Metric> {
+        match outcome {
+            Outcome::Icmp(Ok(result)) => {
+                let lost = result.sent - result.received();
+                let mut metrics = vec![
+                    self.create_metric(
+                        "up",
+                        MetricKind::Absolute,
+                        gauge(f64::from(u8::from(lost < result.sent))),
+                    ),
+                    self.create_metric(
+                        "packets_sent_total",
+                        MetricKind::Incremental,
+                        counter(result.sent.into()),
+                    ),
+                    self.create_metric(
+                        "packets_lost_total",
+                        MetricKind::Incremental,
+                        counter(lost.into()),
+                    ),
+                    self.create_metric(
+                        "packet_loss_ratio",
+                        MetricKind::Absolute,
+                        gauge(f64::from(lost) / f64::from(result.sent)),
+                    ),
+                ];
+                if let Some(round_trip_time) = result.mean_round_trip_time() {
+                    metrics.push(self.create_metric(
+                        "duration_seconds",
+                        MetricKind::Absolute,
+                        gauge(round_trip_time.as_secs_f64()),
+                    ));
+                }
+                metrics
+            }
+            Outcome::Tcp(Ok(duration)) => vec![
+                self.create_metric("up", MetricKind::Absolute, gauge(1.0)),
+                self.create_metric(
+                    "duration_seconds",
+                    MetricKind::Absolute,
+                    gauge(duration.as_secs_f64()),
+                ),
+            ],
+            Outcome::Icmp(Err(_)) | Outcome::Tcp(Err(_)) => {
+                vec![self.create_metric("up", MetricKind::Absolute, gauge(0.0))]
+            }
+        }
+    }
+
+    /// Returns a log describing the failure of the probe, or the loss of some of its packets.
+    fn failure(&self, outcome: &Outcome) -> Option<LogEvent> {
+        let mut fields = ObjectMap::new();
+        match outcome {
+            Outcome::Icmp(Ok(result)) => {
+                let lost = result.sent - result.received();
+                if lost == 0 {
+                    return None;
+                }
+                fields.insert(
+                    "message".into(),
+                    format!("Lost {} of {} packets.", lost, result.sent).into(),
+                );
+                fields.insert("packets_sent".into(), i64::from(result.sent).into());
+                fields.insert("packets_lost".into(), i64::from(lost).into());
+            }
+            Outcome::Tcp(Ok(_)) => return None,
+            Outcome::Icmp(Err(error)) | Outcome::Tcp(Err(error)) => {
+                fields.insert("message".into(), "Probe failed.".into());
+                fields.insert("error".into(), error.as_str().into());
+            }
+        }
+        fields.insert("target".into(), self.target().into());
+        fields.insert("protocol".into(), self.protocol().into());
+
+        let mut log = LogEvent::from_map(fields, Default::default());
+        let now = Utc::now();
+        self.log_namespace.insert_vector_metadata(
+            &mut log,
+            log_schema().source_type_key(),
+            path!("source_type"),
+            Bytes::from_static(ProbeConfig::NAME.as_bytes()),
+        );
+        match self.log_namespace {
+            LogNamespace::Vector => {
+                log.insert(metadata_path!(ProbeConfig::NAME, "timestamp"), now);
+                log.insert(metadata_path!("vector", "ingest_timestamp"), now);
+            }
+            LogNamespace::Legacy => {
+                self.log_namespace.insert_source_metadata(
+                    ProbeConfig::NAME,
+                    &mut log,
+                    log_schema().timestamp_key().map(LegacyKey::Overwrite),
+                    path!("timestamp"),
+                    now,
+                );
+            }
+        }
+        Some(log)
+    }
+
+    fn tags(&self) -> MetricTags {
+        metric_tags!(
+            "target" => self.target(),
+            "protocol" => self.protocol(),
+        )
+    }
+
+    fn create_metric(&self, name: &str, kind: MetricKind, value: MetricValue) -> Metric {
+        Metric::new(name, kind, value)
+            .with_namespace(self.namespace.clone())
+            .with_tags(Some(self.tags()))
+            .with_timestamp(Some(Utc::now()))
+    }
+}
+
+const fn gauge(value: f64) -> MetricValue {
+    MetricValue::Gauge { value }
+}
+
+const fn counter(value: f64) -> MetricValue {
+    MetricValue::Counter { value }
+}
+
+async fn resolve(address: impl ToSocketAddrs) -> Result<SocketAddr, String> {
+    lookup_host(address)
+        .await
+        .map_err(|error| error.to_string())?
+        .next()
+        .ok_or_else(|| "no addresses found".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::net::TcpListener;
+    use vrl::value::Value;
+
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<ProbeConfig>();
+    }
+
+    #[test]
+    fn parses_targets() {
+        let config: ProbeConfig = toml::from_str(
+            r#"
+            targets = [
+                { protocol = "icmp", host = "192.168.1.1" },
+                { protocol = "tcp", address = "example.com:443" },
+            ]
+            "#,
+        )
+        .unwrap();
+        assert!(matches!(
+            &config.targets[0],
+            ProbeTarget::Icmp(IcmpTargetConfig { host, packets: 3 }) if host == "192.168.1.1"
+        ));
+        assert!(matches!(
+            &config.targets[1],
+            ProbeTarget::Tcp(TcpTargetConfig { address }) if address == "example.com:443"
+        ));
+    }
+
+    fn prober(target: ProbeTarget) -> Prober {
+        Prober {
+            target,
+            timeout: Duration::from_secs(1),
+            namespace: Some("probe".to_owned()),
+            log_namespace: LogNamespace::Legacy,
+        }
+    }
+
+    fn tcp_prober(address: String) -> Prober {
+        prober(ProbeTarget::Tcp(TcpTargetConfig { address }))
+    }
+
+    fn gauge_value(metrics: &[Metric], name: &str) -> Option<f64> {
+        metrics
+            .iter()
+            .find(|metric| metric.name() == name)
+            .map(|metric| match metric.value() {
+                MetricValue::Gauge { value } | MetricValue::Counter { value } => *value,
+                value => panic!("unexpected metric value {:?}", value),
+            })
+    }
+
+    #[tokio::test]
+    async fn probes_tcp_targets() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        let prober = tcp_prober(address.clone());
+
+        let outcome = prober.probe().await.unwrap();
+        assert!(matches!(outcome, Outcome::Tcp(Ok(_))));
+        let metrics = prober.metrics(&outcome);
+        assert_eq!(gauge_value(&metrics, "up"), Some(1.0));
+        assert!(gauge_value(&metrics, "duration_seconds").is_some());
+        assert!(metrics.iter().all(|metric| {
+            metric.tag_value("target").as_deref() == Some(address.as_str())
+                && metric.tag_value("protocol").as_deref() == Some("tcp")
+        }));
+        assert!(prober.failure(&outcome).is_none());
+
+        drop(listener);
+        let outcome = prober.probe().await.unwrap();
+        assert!(matches!(outcome, Outcome::Tcp(Err(_))));
+        let metrics = prober.metrics(&outcome);
+        assert_eq!(metrics.len(), 1);
+        assert_eq!(gauge_value(&metrics, "up"), Some(0.0));
+
+        let log = prober.failure(&outcome).unwrap();
+        assert_eq!(log["message"], "Probe failed.".into());
+        assert_eq!(log["target"], address.into());
+        assert_eq!(log["protocol"], "tcp".into());
+        assert!(log.get("error").is_some());
+    }
+
+    #[test]
+    fn reports_lost_packets() {
+        let prober = prober(ProbeTarget::Icmp(IcmpTargetConfig {
+            host: "192.168.1.1".to_owned(),
+            packets: 4,
+        }));
+        let outcome = Outcome::Icmp(Ok(PingResult {
+            sent: 4,
+            round_trip_times: vec![Duration::from_millis(10)],
+        }));
+
+        let metrics = prober.metrics(&outcome);
+        assert_eq!(gauge_value(&metrics, "up"), Some(1.0));
+        assert_eq!(gauge_value(&metrics, "packets_sent_total"), Some(4.0));
+        assert_eq!(gauge_value(&metrics, "packets_lost_total"), Some(3.0));
+        assert_eq!(gauge_value(&metrics, "packet_loss_ratio"), Some(0.75));
+        assert_eq!(gauge_value(&metrics, "duration_seconds"), Some(0.01));
+
+        let log = prober.failure(&outcome).unwrap();
+        assert_eq!(log["message"], "Lost 3 of 4 packets.".into());
+        assert_eq!(log["packets_lost"], Value::from(3));
+        assert_eq!(log["protocol"], "icmp".into());
+
+        let outcome = Outcome::Icmp(Ok(PingResult {
+            sent: 4,
+            round_trip_times: Vec::new(),
+        }));
+        let metrics = prober.metrics(&outcome);
+        assert_eq!(gauge_value(&metrics, "up"), Some(0.0));
+        assert_eq!(gauge_value(&metrics, "duration_seconds"), None);
+    }
+}
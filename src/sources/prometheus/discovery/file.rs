@@ -0,0 +1,194 @@
+use std::{
+    ffi::OsStr,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use serde_with::serde_as;
+use snafu::{ResultExt, Snafu};
+use vector_lib::configurable::configurable_component;
+
+use super::TargetGroup;
+use crate::internal_events::PrometheusServiceDiscoveryError;
+
+const FILEPATH_LABEL: &str = "__meta_filepath";
+
+#[derive(Debug, Snafu)]
+enum FileSdError {
+    #[snafu(display("Invalid file pattern {:?}: {}", pattern, source))]
+    InvalidPattern {
+        pattern: String,
+        source: glob::PatternError,
+    },
+    #[snafu(display("Unable to list files: {}", source))]
+    Glob { source: glob::GlobError },
+    #[snafu(display("Unable to read {:?}: {}", path, source))]
+    Read {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[snafu(display("Unable to parse {:?}: {}", path, source))]
+    ParseJson {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+    #[snafu(display("Unable to parse {:?}: {}", path, source))]
+    ParseYaml {
+        path: PathBuf,
+        source: serde_yaml::Error,
+    },
+}
+
+/// Discovers targets listed in files, in the format of Prometheus' `file_sd_configs`.
+///
+/// Each file contains a list of target groups, each with a list of `targets` addresses and optional
+/// `labels`. Files ending in `.json` are parsed as JSON, all others as YAML.
+#[serde_as]
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct FileSdConfig {
+    /// The files to read targets from.
+    ///
+    /// Glob patterns are supported.
+    #[configurable(metadata(docs::examples = "/etc/vector/targets/*.json"))]
+    files: Vec<String>,
+
+    /// The interval between reads of the files.
+    #[serde(default = "default_refresh_interval")]
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    #[serde(rename = "refresh_interval_secs")]
+    #[configurable(metadata(docs::human_name = "Refresh Interval"))]
+    refresh_interval: Duration,
+}
+
+const fn default_refresh_interval() -> Duration {
+    Duration::from_secs(60)
+}
+
+pub(super) struct FileProvider {
+    config: FileSdConfig,
+    groups: Vec<TargetGroup>,
+    last_refresh: Option<Instant>,
+}
+
+impl FileProvider {
+    pub(super) fn new(config: FileSdConfig) -> crate::Result<Self> {
+        for pattern in &config.files {
+            glob::Pattern::new(pattern).context(InvalidPatternSnafu { pattern })?;
+        }
+
+        Ok(Self {
+            config,
+            groups: Vec::new(),
+            last_refresh: None,
+        })
+    }
+
+    pub(super) fn groups(&mut self) -> Vec<TargetGroup> {
+        let refresh_due = self
+            .last_refresh
+            .map_or(true, |last| last.elapsed() >= self.config.refresh_interval);
+        if refresh_due {
+            self.last_refresh = Some(Instant::now());
+            // A file being rewritten can fail to parse, so the previous targets are kept until the
+            // files can be read again.
+            match read_all(&self.config.files) {
+                Ok(groups) => self.groups = groups,
+                Err(error) => emit!(PrometheusServiceDiscoveryError {
+                    mechanism: "file",
+                    error: &error,
+                }),
+            }
+        }
+
+        self.groups.clone()
+    }
+}
+
+fn read_all(patterns: &[String]) -> Result<Vec<TargetGroup>, FileSdError> {
+    let mut groups = Vec::new();
+    for pattern in patterns {
+        for path in glob::glob(pattern).context(InvalidPatternSnafu { pattern })? {
+            groups.extend(read_groups(&path.context(GlobSnafu)?)?);
+        }
+    }
+    Ok(groups)
+}
+
+fn read_groups(path: &Path) -> Result<Vec<TargetGroup>, FileSdError> {
+    let contents = std::fs::read(path).context(ReadSnafu { path })?;
+    let mut groups: Vec<TargetGroup> = match path.extension().and_then(OsStr::to_str) {
+        Some("json") => serde_json::from_slice(&contents).context(ParseJsonSnafu { path })?,
+        _ => serde_yaml::from_slice(&contents).context(ParseYamlSnafu { path })?,
+    };
+
+    for group in &mut groups {
+        group
+            .labels
+            .insert(FILEPATH_LABEL.to_owned(), path.display().to_string());
+    }
+    Ok(groups)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn provider(files: Vec<String>) -> FileProvider {
+        FileProvider::new(FileSdConfig {
+            files,
+            refresh_interval: Duration::ZERO,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn reads_json_and_yaml() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("web.json"),
+            r#"[{"targets": ["10.0.0.1:9100", "10.0.0.2:9100"], "labels": {"job": "web"}}]"#,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("db.yml"),
+            "- targets: ['10.0.1.1:9187']\n  labels:\n    job: db\n",
+        )
+        .unwrap();
+
+        let pattern = dir.path().join("*").display().to_string();
+        let mut groups = provider(vec![pattern]).groups();
+        groups.sort_by(|a, b| a.labels["job"].cmp(&b.labels["job"]));
+
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].targets, vec!["10.0.1.1:9187"]);
+        assert_eq!(groups[1].targets, vec!["10.0.0.1:9100", "10.0.0.2:9100"]);
+        assert_eq!(
+            groups[1].labels[FILEPATH_LABEL],
+            dir.path().join("web.json").display().to_string()
+        );
+    }
+
+    #[test]
+    fn keeps_targets_on_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("targets.json");
+        std::fs::write(&path, r#"[{"targets": ["10.0.0.1:9100"]}]"#).unwrap();
+
+        let mut provider = provider(vec![path.display().to_string()]);
+        assert_eq!(provider.groups().len(), 1);
+
+        std::fs::write(&path, r#"[{"targets": "#).unwrap();
+        assert_eq!(provider.groups().len(), 1);
+    }
+
+    #[test]
+    fn rejects_invalid_pattern() {
+        assert!(FileProvider::new(FileSdConfig {
+            files: vec!["/etc/vector/[".to_string()],
+            refresh_interval: default_refresh_interval(),
+        })
+        .is_err());
+    }
+}
@@ -0,0 +1,530 @@
+use std::{fmt::Debug, future::ready, hash::Hash, path::PathBuf};
+
+use futures::{future::BoxFuture, FutureExt, StreamExt};
+use k8s_openapi::{
+    api::core::v1::{Endpoints, Pod},
+    apimachinery::pkg::apis::meta::v1::ObjectMeta,
+    NamespaceResourceScope,
+};
+use kube::{
+    config::{self, KubeConfigOptions},
+    runtime::{
+        reflector::{self, Store},
+        watcher, WatchStreamExt,
+    },
+    Api, Client, Config as ClientConfig, Resource,
+};
+use serde::de::DeserializeOwned;
+use tokio::task::JoinHandle;
+use vector_lib::configurable::configurable_component;
+
+use super::{Labels, TargetGroup};
+use crate::internal_events::PrometheusServiceDiscoveryError;
+
+const META_PREFIX: &str = "__meta_kubernetes_";
+
+/// The kind of Kubernetes object targets are discovered from.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum KubernetesRole {
+    /// Discovers a target for each port declared by the containers of every pod.
+    ///
+    /// Containers that don't declare any ports are discovered as a single target on the pod IP,
+    /// with the port expected to be added by relabeling.
+    Pod,
+
+    /// Discovers a target for each address and port of every endpoints object.
+    ///
+    /// Endpoints share the name of the service they belong to.
+    Endpoints,
+}
+
+/// Discovers targets from the Kubernetes API, in the manner of Prometheus' `kubernetes_sd_configs`.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct KubernetesSdConfig {
+    #[configurable(derived)]
+    role: KubernetesRole,
+
+    /// The namespaces to discover targets in.
+    ///
+    /// If empty, targets are discovered in all namespaces.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "monitoring"))]
+    namespaces: Vec<String>,
+
+    /// Only discovers targets from objects matching this [label selector][label_selector].
+    ///
+    /// [label_selector]: https://kubernetes.io/docs/concepts/overview/working-with-objects/labels/#label-selectors
+    #[configurable(metadata(docs::examples = "app.kubernetes.io/part-of=shop"))]
+    label_selector: Option<String>,
+
+    /// Only discovers targets from objects matching this [field selector][field_selector].
+    ///
+    /// [field_selector]: https://kubernetes.io/docs/concepts/overview/working-with-objects/field-selectors/
+    #[configurable(metadata(docs::examples = "status.phase=Running"))]
+    field_selector: Option<String>,
+
+    /// Optional path to a readable [kubeconfig][kubeconfig] file.
+    ///
+    /// If not set, a connection to Kubernetes is made using the in-cluster configuration.
+    ///
+    /// [kubeconfig]: https://kubernetes.io/docs/concepts/configuration/organize-cluster-access-kubeconfig/
+    #[configurable(metadata(docs::examples = "/path/to/.kube/config"))]
+    kube_config_file: Option<PathBuf>,
+}
+
+enum Stores {
+    Pod(Vec<Store<Pod>>),
+    Endpoints(Vec<Store<Endpoints>>),
+}
+
+pub(super) struct KubernetesProvider {
+    stores: Stores,
+    reflectors: Vec<BoxFuture<'static, ()>>,
+    tasks: Vec<JoinHandle<()>>,
+}
+
+impl KubernetesProvider {
+    pub(super) async fn new(config: &KubernetesSdConfig) -> crate::Result<Self> {
+        let client_config = match &config.kube_config_file {
+            Some(kc) => {
+                ClientConfig::from_custom_kubeconfig(
+                    config::Kubeconfig::read_from(kc)?,
+                    &KubeConfigOptions::default(),
+                )
+                .await?
+            }
+            None => ClientConfig::infer().await?,
+        };
+        let client = Client::try_from(client_config)?;
+
+        let (stores, reflectors) = match config.role {
+            KubernetesRole::Pod => {
+                let (stores, reflectors) = reflect::<Pod>(&client, config);
+                (Stores::Pod(stores), reflectors)
+            }
+            KubernetesRole::Endpoints => {
+                let (stores, reflectors) = reflect::<Endpoints>(&client, config);
+                (Stores::Endpoints(stores), reflectors)
+            }
+        };
+
+        Ok(Self {
+            stores,
+            reflectors,
+            tasks: Vec::new(),
+        })
+    }
+
+    /// Starts watching the Kubernetes API.
+    pub(super) fn start(&mut self) {
+        self.tasks
+            .extend(self.reflectors.drain(..).map(tokio::spawn));
+    }
+
+    pub(super) fn groups(&self) -> Vec<TargetGroup> {
+        match &self.stores {
+            Stores::Pod(stores) => stores
+                .iter()
+                .flat_map(Store::state)
+                .flat_map(|pod| pod_groups(&pod))
+                .collect(),
+            Stores::Endpoints(stores) => stores
+                .iter()
+                .flat_map(Store::state)
+                .flat_map(|endpoints| endpoints_groups(&endpoints))
+                .collect(),
+        }
+    }
+}
+
+impl Drop for KubernetesProvider {
+    fn drop(&mut self) {
+        // Stop the reflectors to avoid their leak on vector reload.
+        for task in &self.tasks {
+            task.abort();
+        }
+    }
+}
+
+/// Builds a reflector for each configured namespace, returning the stores they keep up to date.
+fn reflect<K>(
+    client: &Client,
+    config: &KubernetesSdConfig,
+) -> (Vec<Store<K>>, Vec<BoxFuture<'static, ()>>)
+where
+    K: Resource<Scope = NamespaceResourceScope> + Clone + DeserializeOwned + Debug + Send + Sync,
+    K: 'static,
+    K::DynamicType: Clone + Default + Eq + Hash,
+{
+    let apis = if config.namespaces.is_empty() {
+        vec![Api::<K>::all(client.clone())]
+    } else {
+        config
+            .namespaces
+            .iter()
+            .map(|namespace| Api::<K>::namespaced(client.clone(), namespace))
+            .collect()
+    };
+
+    apis.into_iter()
+        .map(|api| {
+            let stream = watcher(
+                api,
+                watcher::Config {
+                    label_selector: config.label_selector.clone(),
+                    field_selector: config.field_selector.clone(),
+                    ..Default::default()
+                },
+            )
+            .backoff(watcher::default_backoff());
+            let writer = reflector::store::Writer::default();
+            let store = writer.as_reader();
+
+            let reflector = reflector::reflector(writer, stream)
+                .for_each(|event| {
+                    if let Err(error) = event {
+                        emit!(PrometheusServiceDiscoveryError {
+                            mechanism: "kubernetes",
+                            error: &error,
+                        });
+                    }
+                    ready(())
+                })
+                .boxed();
+
+            (store, reflector)
+        })
+        .unzip()
+}
+
+fn pod_groups(pod: &Pod) -> Vec<TargetGroup> {
+    let Some(ip) = pod
+        .status
+        .as_ref()
+        .and_then(|status| status.pod_ip.as_deref())
+    else {
+        // Pods that haven't been scheduled yet have no address to scrape.
+        return Vec::new();
+    };
+
+    let mut labels = Labels::new();
+    insert_object_labels(&mut labels, "pod", &pod.metadata);
+    insert(&mut labels, "pod_name", pod.metadata.name.as_deref());
+    insert(&mut labels, "pod_uid", pod.metadata.uid.as_deref());
+    insert(&mut labels, "pod_ip", Some(ip));
+    if let Some(owner) = pod
+        .metadata
+        .owner_references
+        .iter()
+        .flatten()
+        .find(|owner| owner.controller == Some(true))
+    {
+        insert(
+            &mut labels,
+            "pod_controller_kind",
+            Some(owner.kind.as_str()),
+        );
+        insert(
+            &mut labels,
+            "pod_controller_name",
+            Some(owner.name.as_str()),
+        );
+    }
+    if let Some(spec) = &pod.spec {
+        insert(&mut labels, "pod_node_name", spec.node_name.as_deref());
+    }
+    if let Some(status) = &pod.status {
+        insert(&mut labels, "pod_host_ip", status.host_ip.as_deref());
+        insert(&mut labels, "pod_phase", status.phase.as_deref());
+        let ready = status
+            .conditions
+            .iter()
+            .flatten()
+            .find(|condition| condition.type_ == "Ready")
+            .map(|condition| condition.status.to_lowercase());
+        insert(&mut labels, "pod_ready", ready.as_deref());
+    }
+
+    let mut groups = Vec::new();
+    for container in pod.spec.iter().flat_map(|spec| &spec.containers) {
+        let mut labels = labels.clone();
+        insert(
+            &mut labels,
+            "pod_container_name",
+            Some(container.name.as_str()),
+        );
+        insert(
+            &mut labels,
+            "pod_container_image",
+            container.image.as_deref(),
+        );
+
+        let ports = container.ports.as_deref().unwrap_or_default();
+        if ports.is_empty() {
+            groups.push(TargetGroup {
+                targets: vec![join_host_port(ip, None)],
+                labels,
+            });
+            continue;
+        }
+
+        for port in ports {
+            let mut labels = labels.clone();
+            let number = port.container_port.to_string();
+            insert(&mut labels, "pod_container_port_name", port.name.as_deref());
+            insert(
+                &mut labels,
+                "pod_container_port_number",
+                Some(number.as_str()),
+            );
+            insert(
+                &mut labels,
+                "pod_container_port_protocol",
+                port.protocol.as_deref(),
+            );
+            groups.push(TargetGroup {
+                targets: vec![join_host_port(ip, Some(port.container_port))],
+                labels,
+            });
+        }
+    }
+    groups
+}
+
+fn endpoints_groups(endpoints: &Endpoints) -> Vec<TargetGroup> {
+    let mut labels = Labels::new();
+    insert_object_labels(&mut labels, "endpoints", &endpoints.metadata);
+    insert(
+        &mut labels,
+        "endpoints_name",
+        endpoints.metadata.name.as_deref(),
+    );
+    insert(
+        &mut labels,
+        "service_name",
+        endpoints.metadata.name.as_deref(),
+    );
+
+    let mut groups = Vec::new();
+    for subset in endpoints.subsets.iter().flatten() {
+        let ready = subset
+            .addresses
+            .iter()
+            .flatten()
+            .map(|address| (address, "true"));
+        let not_ready = subset
+            .not_ready_addresses
+            .iter()
+            .flatten()
+            .map(|address| (address, "false"));
+
+        for (address, ready) in ready.chain(not_ready) {
+            let mut labels = labels.clone();
+            insert(&mut labels, "endpoint_ready", Some(ready));
+            insert(
+                &mut labels,
+                "endpoint_hostname",
+                address.hostname.as_deref(),
+            );
+            insert(
+                &mut labels,
+                "endpoint_node_name",
+                address.node_name.as_deref(),
+            );
+            if let Some(target) = &address.target_ref {
+                insert(
+                    &mut labels,
+                    "endpoint_address_target_kind",
+                    target.kind.as_deref(),
+                );
+                insert(
+                    &mut labels,
+                    "endpoint_address_target_name",
+                    target.name.as_deref(),
+                );
+            }
+
+            for port in subset.ports.iter().flatten() {
+                let mut labels = labels.clone();
+                insert(&mut labels, "endpoint_port_name", port.name.as_deref());
+                insert(
+                    &mut labels,
+                    "endpoint_port_protocol",
+                    port.protocol.as_deref(),
+                );
+                groups.push(TargetGroup {
+                    targets: vec![join_host_port(&address.ip, Some(port.port))],
+                    labels,
+                });
+            }
+        }
+    }
+    groups
+}
+
+/// Inserts the namespace, labels, and annotations of an object.
+fn insert_object_labels(labels: &mut Labels, kind: &str, metadata: &ObjectMeta) {
+    insert(labels, "namespace", metadata.namespace.as_deref());
+    for (name, value) in metadata.labels.iter().flatten() {
+        let name = sanitize(name);
+        insert(
+            labels,
+            &format!("{}_label_{}", kind, name),
+            Some(value.as_str()),
+        );
+        insert(
+            labels,
+            &format!("{}_labelpresent_{}", kind, name),
+            Some("true"),
+        );
+    }
+    for (name, value) in metadata.annotations.iter().flatten() {
+        let name = sanitize(name);
+        insert(
+            labels,
+            &format!("{}_annotation_{}", kind, name),
+            Some(value.as_str()),
+        );
+        insert(
+            labels,
+            &format!("{}_annotationpresent_{}", kind, name),
+            Some("true"),
+        );
+    }
+}
+
+fn insert(labels: &mut Labels, name: &str, value: Option<&str>) {
+    if let Some(value) = value {
+        labels.insert(format!("{}{}", META_PREFIX, name), value.to_owned());
+    }
+}
+
+/// Replaces the characters that aren't valid in Prometheus label names.
+fn sanitize(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+fn join_host_port(ip: &str, port: Option<i32>) -> String {
+    let host = if ip.contains(':') {
+        format!("[{}]", ip)
+    } else {
+        ip.to_owned()
+    };
+    match port {
+        Some(port) => format!("{}:{}", host, port),
+        None => host,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn label<'a>(group: &'a TargetGroup, name: &str) -> Option<&'a str> {
+        group
+            .labels
+            .get(&format!("{}{}", META_PREFIX, name))
+            .map(String::as_str)
+    }
+
+    #[test]
+    fn discovers_pod_ports() {
+        let pod: Pod = serde_json::from_value(serde_json::json!({
+            "metadata": {
+                "name": "web-0",
+                "namespace": "shop",
+                "labels": {"app.kubernetes.io/name": "web"},
+                "annotations": {"prometheus.io/scrape": "true"},
+            },
+            "spec": {
+                "nodeName": "node-1",
+                "containers": [
+                    {
+                        "name": "web",
+                        "ports": [
+                            {"name": "http", "containerPort": 8080, "protocol": "TCP"},
+                            {"name": "metrics", "containerPort": 9090, "protocol": "TCP"},
+                        ],
+                    },
+                    {"name": "sidecar"},
+                ],
+            },
+            "status": {
+                "podIP": "10.0.0.5",
+                "phase": "Running",
+                "conditions": [{"type": "Ready", "status": "True"}],
+            },
+        }))
+        .unwrap();
+
+        let groups = pod_groups(&pod);
+        let targets = groups
+            .iter()
+            .flat_map(|group| group.targets.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(targets, vec!["10.0.0.5:8080", "10.0.0.5:9090", "10.0.0.5"]);
+
+        let metrics = &groups[1];
+        assert_eq!(label(metrics, "namespace"), Some("shop"));
+        assert_eq!(label(metrics, "pod_name"), Some("web-0"));
+        assert_eq!(label(metrics, "pod_node_name"), Some("node-1"));
+        assert_eq!(label(metrics, "pod_ready"), Some("true"));
+        assert_eq!(label(metrics, "pod_container_port_name"), Some("metrics"));
+        assert_eq!(
+            label(metrics, "pod_label_app_kubernetes_io_name"),
+            Some("web")
+        );
+        assert_eq!(
+            label(metrics, "pod_annotation_prometheus_io_scrape"),
+            Some("true")
+        );
+        assert_eq!(label(&groups[2], "pod_container_name"), Some("sidecar"));
+        assert_eq!(label(&groups[2], "pod_container_port_number"), None);
+    }
+
+    #[test]
+    fn skips_pods_without_ip() {
+        let pod: Pod = serde_json::from_value(serde_json::json!({
+            "metadata": {"name": "pending"},
+            "spec": {"containers": [{"name": "web"}]},
+            "status": {"phase": "Pending"},
+        }))
+        .unwrap();
+
+        assert!(pod_groups(&pod).is_empty());
+    }
+
+    #[test]
+    fn discovers_endpoints() {
+        let endpoints: Endpoints = serde_json::from_value(serde_json::json!({
+            "metadata": {"name": "web", "namespace": "shop"},
+            "subsets": [{
+                "addresses": [{
+                    "ip": "10.0.0.5",
+                    "targetRef": {"kind": "Pod", "name": "web-0"},
+                }],
+                "notReadyAddresses": [{"ip": "fd00::6"}],
+                "ports": [{"name": "metrics", "port": 9090, "protocol": "TCP"}],
+            }],
+        }))
+        .unwrap();
+
+        let groups = endpoints_groups(&endpoints);
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].targets, vec!["10.0.0.5:9090"]);
+        assert_eq!(label(&groups[0], "service_name"), Some("web"));
+        assert_eq!(label(&groups[0], "endpoint_ready"), Some("true"));
+        assert_eq!(
+            label(&groups[0], "endpoint_address_target_name"),
+            Some("web-0")
+        );
+        assert_eq!(groups[1].targets, vec!["[fd00::6]:9090"]);
+        assert_eq!(label(&groups[1], "endpoint_ready"), Some("false"));
+    }
+}
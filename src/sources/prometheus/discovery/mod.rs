@@ -0,0 +1,345 @@
+//! Service discovery for the `prometheus_scrape` source.
+//!
+//! This mirrors Prometheus' own service discovery: providers produce groups of target addresses
+//! annotated with `__meta_*` labels, relabeling rules rewrite or filter the targets, and the
+//! targets that survive are turned into the URLs the source scrapes. Labels that don't start with
+//! `__` once relabeling is complete are added as tags to every metric scraped from the target.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    future::Future,
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+
+use http::Uri;
+use serde::Deserialize;
+use tokio::sync::watch;
+
+use crate::sources::util::http_client::build_url;
+
+mod file;
+mod kubernetes;
+mod relabel;
+
+pub use file::FileSdConfig;
+pub use kubernetes::{KubernetesRole, KubernetesSdConfig};
+pub use relabel::{RelabelAction, RelabelConfig};
+
+use relabel::Relabeler;
+
+pub(super) type Labels = BTreeMap<String, String>;
+
+/// The labels to add to the metrics scraped from each discovered target.
+pub(super) type TargetLabels = Arc<RwLock<HashMap<Uri, Vec<(String, String)>>>>;
+
+const ADDRESS_LABEL: &str = "__address__";
+const SCHEME_LABEL: &str = "__scheme__";
+const METRICS_PATH_LABEL: &str = "__metrics_path__";
+const PARAM_LABEL_PREFIX: &str = "__param_";
+const RESERVED_LABEL_PREFIX: &str = "__";
+
+const DEFAULT_SCHEME: &str = "http";
+const DEFAULT_METRICS_PATH: &str = "/metrics";
+
+/// How often providers are checked for changed targets. File providers additionally only re-read
+/// their files once their own refresh interval has elapsed.
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// A group of targets sharing a set of labels, in the format used by Prometheus' `file_sd_configs`.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+pub(super) struct TargetGroup {
+    #[serde(default)]
+    targets: Vec<String>,
+    #[serde(default)]
+    labels: Labels,
+}
+
+enum Provider {
+    File(file::FileProvider),
+    Kubernetes(kubernetes::KubernetesProvider),
+}
+
+impl Provider {
+    fn groups(&mut self) -> Vec<TargetGroup> {
+        match self {
+            Provider::File(provider) => provider.groups(),
+            Provider::Kubernetes(provider) => provider.groups(),
+        }
+    }
+}
+
+/// Keeps the list of URLs to scrape up to date with the targets found by the providers.
+pub(super) struct Discovery {
+    static_urls: Vec<Uri>,
+    providers: Vec<Provider>,
+    relabelers: Vec<Relabeler>,
+    query: HashMap<String, Vec<String>>,
+}
+
+impl Discovery {
+    /// Builds the discovery providers, returning `None` if none are configured.
+    ///
+    /// The static URLs are always scraped, and are not subject to relabeling.
+    pub(super) async fn new(
+        static_urls: Vec<Uri>,
+        file_sd_configs: &[FileSdConfig],
+        kubernetes_sd_configs: &[KubernetesSdConfig],
+        relabel_configs: &[RelabelConfig],
+        query: &HashMap<String, Vec<String>>,
+    ) -> crate::Result<Option<Self>> {
+        let mut providers = Vec::new();
+        for config in file_sd_configs {
+            providers.push(Provider::File(file::FileProvider::new(config.clone())?));
+        }
+        for config in kubernetes_sd_configs {
+            providers.push(Provider::Kubernetes(
+                kubernetes::KubernetesProvider::new(config).await?,
+            ));
+        }
+        if providers.is_empty() {
+            return Ok(None);
+        }
+
+        let relabelers = relabel_configs
+            .iter()
+            .cloned()
+            .map(Relabeler::new)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Some(Self {
+            static_urls,
+            providers,
+            relabelers,
+            query: query.clone(),
+        }))
+    }
+
+    /// Resolves the current targets, returning the channel updated URLs are published on, the
+    /// labels of each target, and the task keeping both up to date.
+    pub(super) fn start(
+        mut self,
+    ) -> (
+        watch::Receiver<Vec<Uri>>,
+        TargetLabels,
+        impl Future<Output = ()>,
+    ) {
+        let (urls, target_labels) = self.targets();
+        let (sender, receiver) = watch::channel(urls);
+        let labels = TargetLabels::new(RwLock::new(target_labels));
+
+        let task = {
+            let labels = Arc::clone(&labels);
+            async move {
+                for provider in &mut self.providers {
+                    if let Provider::Kubernetes(provider) = provider {
+                        provider.start();
+                    }
+                }
+
+                let mut interval = tokio::time::interval(POLL_INTERVAL);
+                loop {
+                    interval.tick().await;
+
+                    let (urls, target_labels) = self.targets();
+                    if *sender.borrow() != urls {
+                        debug!(message = "Discovered targets changed.", count = urls.len());
+                        // The labels are updated first so they are in place by the time the new
+                        // targets are scraped.
+                        *labels.write().expect("poisoned lock") = target_labels;
+                        sender.send_replace(urls);
+                    }
+                }
+            }
+        };
+
+        (receiver, labels, task)
+    }
+
+    fn targets(&mut self) -> (Vec<Uri>, HashMap<Uri, Vec<(String, String)>>) {
+        let mut urls = self.static_urls.clone();
+        let mut target_labels = HashMap::new();
+
+        for provider in &mut self.providers {
+            for group in provider.groups() {
+                for address in &group.targets {
+                    let mut labels = group.labels.clone();
+                    labels.insert(ADDRESS_LABEL.to_owned(), address.clone());
+
+                    if let Some((url, labels)) = build_target(&self.relabelers, &self.query, labels)
+                    {
+                        if !urls.contains(&url) {
+                            urls.push(url.clone());
+                            target_labels.insert(url, labels);
+                        }
+                    }
+                }
+            }
+        }
+
+        (urls, target_labels)
+    }
+}
+
+/// Relabels a discovered target and builds the URL it is scraped at, or returns `None` if the
+/// target was dropped.
+fn build_target(
+    relabelers: &[Relabeler],
+    query: &HashMap<String, Vec<String>>,
+    mut labels: Labels,
+) -> Option<(Uri, Vec<(String, String)>)> {
+    labels
+        .entry(SCHEME_LABEL.to_owned())
+        .or_insert_with(|| DEFAULT_SCHEME.to_owned());
+    labels
+        .entry(METRICS_PATH_LABEL.to_owned())
+        .or_insert_with(|| DEFAULT_METRICS_PATH.to_owned());
+
+    if !relabelers
+        .iter()
+        .all(|relabeler| relabeler.apply(&mut labels))
+    {
+        return None;
+    }
+
+    let address = labels
+        .get(ADDRESS_LABEL)
+        .filter(|address| !address.is_empty())?;
+    let scheme = labels
+        .get(SCHEME_LABEL)
+        .map_or(DEFAULT_SCHEME, String::as_str);
+    let path = labels.get(METRICS_PATH_LABEL).map_or("", String::as_str);
+    let separator = if path.starts_with('/') { "" } else { "/" };
+
+    let url = match format!("{}://{}{}{}", scheme, address, separator, path).parse::<Uri>() {
+        Ok(url) => url,
+        Err(error) => {
+            warn!(
+                message = "Skipping discovered target with an invalid URL.",
+                address = %address,
+                %error,
+                internal_log_rate_limit = true,
+            );
+            return None;
+        }
+    };
+
+    let mut query = query.clone();
+    for (name, value) in &labels {
+        if let Some(param) = name.strip_prefix(PARAM_LABEL_PREFIX) {
+            query.insert(param.to_owned(), vec![value.clone()]);
+        }
+    }
+
+    let labels = labels
+        .into_iter()
+        .filter(|(name, _)| !name.starts_with(RESERVED_LABEL_PREFIX))
+        .collect();
+
+    Some((build_url(&url, &query), labels))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(pairs: &[(&str, &str)]) -> Labels {
+        pairs
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    fn relabelers(toml: &str) -> Vec<Relabeler> {
+        #[derive(Deserialize)]
+        struct Rules {
+            rules: Vec<RelabelConfig>,
+        }
+
+        toml::from_str::<Rules>(toml)
+            .unwrap()
+            .rules
+            .into_iter()
+            .map(|config| Relabeler::new(config).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn builds_default_url() {
+        let (url, labels) = build_target(
+            &[],
+            &HashMap::new(),
+            labels(&[("__address__", "10.0.0.1:9100"), ("job", "node")]),
+        )
+        .unwrap();
+
+        assert_eq!(url, "http://10.0.0.1:9100/metrics");
+        assert_eq!(labels, vec![("job".to_string(), "node".to_string())]);
+    }
+
+    #[test]
+    fn relabels_url() {
+        let rules = relabelers(
+            r#"
+            [[rules]]
+            source_labels = ["__meta_kubernetes_pod_annotation_prometheus_io_path"]
+            regex = "(.+)"
+            target_label = "__metrics_path__"
+
+            [[rules]]
+            source_labels = ["__meta_kubernetes_pod_annotation_prometheus_io_scheme"]
+            regex = "(https?)"
+            target_label = "__scheme__"
+
+            [[rules]]
+            regex = "__meta_kubernetes_pod_label_(.+)"
+            action = "labelmap"
+            "#,
+        );
+
+        let (url, labels) = build_target(
+            &rules,
+            &HashMap::from([("match[]".to_string(), vec!["up".to_string()])]),
+            labels(&[
+                ("__address__", "10.0.0.1:8443"),
+                (
+                    "__meta_kubernetes_pod_annotation_prometheus_io_path",
+                    "/stats",
+                ),
+                (
+                    "__meta_kubernetes_pod_annotation_prometheus_io_scheme",
+                    "https",
+                ),
+                ("__meta_kubernetes_pod_label_app", "web"),
+                ("__param_format", "text"),
+            ]),
+        )
+        .unwrap();
+
+        let query = url.query().unwrap();
+        assert!(query.contains("format=text"));
+        assert!(query.contains("match%5B%5D=up"));
+        assert_eq!(url.scheme_str(), Some("https"));
+        assert_eq!(url.path(), "/stats");
+        assert_eq!(labels, vec![("app".to_string(), "web".to_string())]);
+    }
+
+    #[test]
+    fn drops_target() {
+        let rules = relabelers(
+            r#"
+            [[rules]]
+            source_labels = ["__meta_kubernetes_pod_annotation_prometheus_io_scrape"]
+            regex = "true"
+            action = "keep"
+            "#,
+        );
+
+        assert!(build_target(
+            &rules,
+            &HashMap::new(),
+            labels(&[("__address__", "10.0.0.1:9100")])
+        )
+        .is_none());
+    }
+}
@@ -0,0 +1,334 @@
+use md5::{Digest, Md5};
+use regex::Regex;
+use snafu::{ResultExt, Snafu};
+use vector_lib::configurable::configurable_component;
+
+use super::Labels;
+
+#[derive(Debug, Snafu)]
+pub enum RelabelBuildError {
+    #[snafu(display("Invalid relabeling regex {:?}: {}", regex, source))]
+    InvalidRegex { regex: String, source: regex::Error },
+    #[snafu(display("The `{}` relabeling action requires `target_label` to be set", action))]
+    MissingTargetLabel { action: &'static str },
+    #[snafu(display("The `hashmod` relabeling action requires a non-zero `modulus`"))]
+    MissingModulus,
+}
+
+/// The action performed by a relabeling rule.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum RelabelAction {
+    /// Sets `target_label` to `replacement` if `regex` matches the source value.
+    ///
+    /// Capture groups in `replacement` are expanded with the matched values.
+    #[default]
+    Replace,
+
+    /// Drops the target if `regex` does not match the source value.
+    Keep,
+
+    /// Drops the target if `regex` matches the source value.
+    Drop,
+
+    /// Sets `target_label` to the hash of the source value, modulo `modulus`.
+    ///
+    /// This is typically combined with a `keep` rule to shard targets across several instances.
+    Hashmod,
+
+    /// Copies every label whose name matches `regex` to the label named by `replacement`.
+    Labelmap,
+
+    /// Removes every label whose name matches `regex`.
+    Labeldrop,
+
+    /// Removes every label whose name does not match `regex`.
+    Labelkeep,
+}
+
+impl RelabelAction {
+    const fn as_str(self) -> &'static str {
+        match self {
+            RelabelAction::Replace => "replace",
+            RelabelAction::Keep => "keep",
+            RelabelAction::Drop => "drop",
+            RelabelAction::Hashmod => "hashmod",
+            RelabelAction::Labelmap => "labelmap",
+            RelabelAction::Labeldrop => "labeldrop",
+            RelabelAction::Labelkeep => "labelkeep",
+        }
+    }
+}
+
+/// A rule rewriting the labels of discovered targets, as Prometheus' `relabel_configs`.
+///
+/// The source value of a rule is the value of each of its `source_labels`, joined by `separator`.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct RelabelConfig {
+    /// The labels whose values make up the source value.
+    #[serde(default)]
+    #[configurable(metadata(
+        docs::examples = "__meta_kubernetes_pod_annotation_prometheus_io_scrape"
+    ))]
+    source_labels: Vec<String>,
+
+    /// The separator placed between the values of the source labels.
+    #[serde(default = "default_separator")]
+    separator: String,
+
+    /// The regular expression matched against the source value, or against label names for the
+    /// `labelmap`, `labeldrop`, and `labelkeep` actions.
+    ///
+    /// The expression is anchored at both ends.
+    #[serde(default = "default_regex")]
+    #[configurable(metadata(docs::examples = "true"))]
+    #[configurable(metadata(docs::examples = "__meta_kubernetes_pod_label_(.+)"))]
+    regex: String,
+
+    /// The label written by the `replace` and `hashmod` actions.
+    #[configurable(metadata(docs::examples = "__metrics_path__"))]
+    target_label: Option<String>,
+
+    /// The value written by the `replace` and `labelmap` actions.
+    ///
+    /// Capture groups of `regex` are referenced as `$1`, or `${name}` for named groups.
+    #[serde(default = "default_replacement")]
+    replacement: String,
+
+    /// The modulus used by the `hashmod` action.
+    modulus: Option<u64>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    action: RelabelAction,
+}
+
+fn default_separator() -> String {
+    ";".to_owned()
+}
+
+fn default_regex() -> String {
+    "(.*)".to_owned()
+}
+
+fn default_replacement() -> String {
+    "$1".to_owned()
+}
+
+/// A compiled relabeling rule.
+#[derive(Clone, Debug)]
+pub(super) struct Relabeler {
+    config: RelabelConfig,
+    regex: Regex,
+}
+
+impl Relabeler {
+    pub(super) fn new(config: RelabelConfig) -> Result<Self, RelabelBuildError> {
+        let regex = Regex::new(&format!("^(?:{})$", config.regex)).context(InvalidRegexSnafu {
+            regex: config.regex.clone(),
+        })?;
+
+        match config.action {
+            RelabelAction::Replace | RelabelAction::Hashmod if config.target_label.is_none() => {
+                return Err(RelabelBuildError::MissingTargetLabel {
+                    action: config.action.as_str(),
+                });
+            }
+            RelabelAction::Hashmod if config.modulus.unwrap_or(0) == 0 => {
+                return Err(RelabelBuildError::MissingModulus);
+            }
+            _ => {}
+        }
+
+        Ok(Self { config, regex })
+    }
+
+    /// Applies the rule to the labels of a target, returning `false` if the target is dropped.
+    pub(super) fn apply(&self, labels: &mut Labels) -> bool {
+        let config = &self.config;
+        let source = || {
+            config
+                .source_labels
+                .iter()
+                .map(|name| labels.get(name).map(String::as_str).unwrap_or_default())
+                .collect::<Vec<_>>()
+                .join(&config.separator)
+        };
+
+        match config.action {
+            RelabelAction::Replace => {
+                let source = source();
+                if let Some(captures) = self.regex.captures(&source) {
+                    let mut value = String::new();
+                    captures.expand(&config.replacement, &mut value);
+                    let target = config.target_label.as_deref().unwrap_or_default();
+                    if value.is_empty() {
+                        labels.remove(target);
+                    } else {
+                        labels.insert(target.to_owned(), value);
+                    }
+                }
+            }
+            RelabelAction::Keep => return self.regex.is_match(&source()),
+            RelabelAction::Drop => return !self.regex.is_match(&source()),
+            RelabelAction::Hashmod => {
+                let digest = Md5::digest(source().as_bytes());
+                let hash = u64::from_be_bytes(digest[8..].try_into().expect("md5 is 16 bytes"));
+                let modulus = config.modulus.unwrap_or(1);
+                let target = config.target_label.clone().unwrap_or_default();
+                labels.insert(target, (hash % modulus).to_string());
+            }
+            RelabelAction::Labelmap => {
+                let mapped = labels
+                    .iter()
+                    .filter_map(|(name, value)| {
+                        self.regex.captures(name).map(|captures| {
+                            let mut target = String::new();
+                            captures.expand(&config.replacement, &mut target);
+                            (target, value.clone())
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                labels.extend(mapped);
+            }
+            RelabelAction::Labeldrop => labels.retain(|name, _| !self.regex.is_match(name)),
+            RelabelAction::Labelkeep => labels.retain(|name, _| self.regex.is_match(name)),
+        }
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn relabeler(toml: &str) -> Relabeler {
+        Relabeler::new(toml::from_str(toml).unwrap()).unwrap()
+    }
+
+    fn labels(pairs: &[(&str, &str)]) -> Labels {
+        pairs
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn replace_with_capture_groups() {
+        let rule = relabeler(
+            r#"
+            source_labels = ["__address__", "__meta_port"]
+            regex = "([^:]+)(?::\\d+)?;(\\d+)"
+            replacement = "$1:$2"
+            target_label = "__address__"
+            "#,
+        );
+
+        let mut target = labels(&[("__address__", "10.0.0.1:80"), ("__meta_port", "9090")]);
+        assert!(rule.apply(&mut target));
+        assert_eq!(target["__address__"], "10.0.0.1:9090");
+    }
+
+    #[test]
+    fn replace_requires_full_match() {
+        let rule = relabeler(
+            r#"
+            source_labels = ["app"]
+            regex = "web"
+            replacement = "frontend"
+            target_label = "tier"
+            "#,
+        );
+
+        let mut target = labels(&[("app", "webserver")]);
+        assert!(rule.apply(&mut target));
+        assert!(!target.contains_key("tier"));
+    }
+
+    #[test]
+    fn keep_and_drop() {
+        let keep = relabeler(
+            r#"
+            source_labels = ["__meta_kubernetes_pod_annotation_prometheus_io_scrape"]
+            regex = "true"
+            action = "keep"
+            "#,
+        );
+        let drop = relabeler(
+            r#"
+            source_labels = ["__meta_kubernetes_namespace"]
+            regex = "kube-.*"
+            action = "drop"
+            "#,
+        );
+
+        let mut annotated = labels(&[
+            (
+                "__meta_kubernetes_pod_annotation_prometheus_io_scrape",
+                "true",
+            ),
+            ("__meta_kubernetes_namespace", "kube-system"),
+        ]);
+        assert!(keep.apply(&mut annotated));
+        assert!(!drop.apply(&mut annotated));
+        assert!(!keep.apply(&mut labels(&[])));
+    }
+
+    #[test]
+    fn hashmod_is_stable() {
+        let rule = relabeler(
+            r#"
+            source_labels = ["__address__"]
+            modulus = 4
+            target_label = "__tmp_hash"
+            action = "hashmod"
+            "#,
+        );
+
+        let mut first = labels(&[("__address__", "10.0.0.1:9090")]);
+        let mut second = first.clone();
+        rule.apply(&mut first);
+        rule.apply(&mut second);
+        let shard: u64 = first["__tmp_hash"].parse().unwrap();
+        assert!(shard < 4);
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn labelmap_and_labeldrop() {
+        let map = relabeler(
+            r#"
+            regex = "__meta_kubernetes_pod_label_(.+)"
+            action = "labelmap"
+            "#,
+        );
+        let drop = relabeler(
+            r#"
+            regex = "pod_template_hash"
+            action = "labeldrop"
+            "#,
+        );
+
+        let mut target = labels(&[
+            ("__meta_kubernetes_pod_label_app", "web"),
+            ("__meta_kubernetes_pod_label_pod_template_hash", "abc"),
+        ]);
+        map.apply(&mut target);
+        drop.apply(&mut target);
+        assert_eq!(target["app"], "web");
+        assert!(!target.contains_key("pod_template_hash"));
+    }
+
+    #[test]
+    fn replace_without_target_label_is_rejected() {
+        let config = toml::from_str::<RelabelConfig>(r#"source_labels = ["app"]"#).unwrap();
+        assert!(matches!(
+            Relabeler::new(config),
+            Err(RelabelBuildError::MissingTargetLabel { .. })
+        ));
+    }
+}
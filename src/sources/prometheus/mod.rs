@@ -1,3 +1,5 @@
+#[cfg(feature = "sources-prometheus-scrape")]
+mod discovery;
 pub(crate) mod parser;
 
 #[cfg(feature = "sources-prometheus-pushgateway")]
@@ -1,9 +1,12 @@
 use std::cmp::Ordering;
 
 use chrono::{DateTime, TimeZone, Utc};
-#[cfg(feature = "sources-prometheus-remote-write")]
-use vector_lib::prometheus::parser::proto;
-use vector_lib::prometheus::parser::{GroupKind, MetricGroup, ParserError};
+use ordered_float::NotNan;
+use vector_lib::prometheus::parser::{proto, Exemplar, GroupKind, MetricGroup, ParserError};
+use vector_lib::{
+    event::{ObjectMap, Value},
+    lookup::path,
+};
 
 use crate::event::{
     metric::{Bucket, Metric, MetricKind, MetricTags, MetricValue, Quantile},
@@ -71,7 +74,7 @@ fn reparse_groups(
                     .with_timestamp(Some(utc_timestamp(key.timestamp, start)))
                     .with_tags(tags.as_option());
 
-                    result.push(counter.into());
+                    result.push(with_exemplars(counter, metric.exemplars, start).into());
                 }
             }
             GroupKind::Gauge(metrics) | GroupKind::Untyped(metrics) => {
@@ -89,7 +92,7 @@ fn reparse_groups(
                     .with_timestamp(Some(utc_timestamp(key.timestamp, start)))
                     .with_tags(tags.as_option());
 
-                    result.push(gauge.into());
+                    result.push(with_exemplars(gauge, metric.exemplars, start).into());
                 }
             }
             GroupKind::Histogram(metrics) => {
@@ -108,26 +111,31 @@ fn reparse_groups(
                         buckets.pop();
                     }
 
-                    result.push(
-                        Metric::new(
-                            group.name.clone(),
-                            metric_kind,
-                            MetricValue::AggregatedHistogram {
-                                buckets: buckets
-                                    .into_iter()
-                                    .map(|b| Bucket {
-                                        upper_limit: b.bucket,
-                                        count: b.count,
-                                    })
-                                    .collect(),
-                                count: metric.count,
-                                sum: metric.sum,
-                            },
-                        )
-                        .with_timestamp(Some(utc_timestamp(key.timestamp, start)))
-                        .with_tags(tags.as_option())
-                        .into(),
+                    let metric = with_native_histogram(
+                        with_exemplars(
+                            Metric::new(
+                                group.name.clone(),
+                                metric_kind,
+                                MetricValue::AggregatedHistogram {
+                                    buckets: buckets
+                                        .into_iter()
+                                        .map(|b| Bucket {
+                                            upper_limit: b.bucket,
+                                            count: b.count,
+                                        })
+                                        .collect(),
+                                    count: metric.count,
+                                    sum: metric.sum,
+                                },
+                            )
+                            .with_timestamp(Some(utc_timestamp(key.timestamp, start)))
+                            .with_tags(tags.as_option()),
+                            metric.exemplars,
+                            start,
+                        ),
+                        metric.native,
                     );
+                    result.push(metric.into());
                 }
             }
             GroupKind::Summary(metrics) => {
@@ -135,25 +143,29 @@ fn reparse_groups(
                     let tags = combine_tags(key.labels, tag_overrides.clone());
 
                     result.push(
-                        Metric::new(
-                            group.name.clone(),
-                            // Summaries are always absolute: aggregating them makes no sense
-                            MetricKind::Absolute,
-                            MetricValue::AggregatedSummary {
-                                quantiles: metric
-                                    .quantiles
-                                    .into_iter()
-                                    .map(|q| Quantile {
-                                        quantile: q.quantile,
-                                        value: q.value,
-                                    })
-                                    .collect(),
-                                count: metric.count,
-                                sum: metric.sum,
-                            },
+                        with_exemplars(
+                            Metric::new(
+                                group.name.clone(),
+                                // Summaries are always absolute: aggregating them makes no sense
+                                MetricKind::Absolute,
+                                MetricValue::AggregatedSummary {
+                                    quantiles: metric
+                                        .quantiles
+                                        .into_iter()
+                                        .map(|q| Quantile {
+                                            quantile: q.quantile,
+                                            value: q.value,
+                                        })
+                                        .collect(),
+                                    count: metric.count,
+                                    sum: metric.sum,
+                                },
+                            )
+                            .with_timestamp(Some(utc_timestamp(key.timestamp, start)))
+                            .with_tags(tags.as_option()),
+                            metric.exemplars,
+                            start,
                         )
-                        .with_timestamp(Some(utc_timestamp(key.timestamp, start)))
-                        .with_tags(tags.as_option())
                         .into(),
                     );
                 }
@@ -164,6 +176,108 @@ fn reparse_groups(
     result
 }
 
+/// Stores the exemplars of a remote write time series in the metadata of its metric, where the
+/// `prometheus_remote_write` sink picks them up again.
+fn with_exemplars(mut metric: Metric, exemplars: Vec<Exemplar>, default: DateTime<Utc>) -> Metric {
+    if exemplars.is_empty() {
+        return metric;
+    }
+
+    let exemplars = exemplars
+        .into_iter()
+        .map(|exemplar| {
+            let labels = exemplar
+                .labels
+                .into_iter()
+                .map(|(name, value)| (name.into(), Value::from(value)))
+                .collect::<ObjectMap>();
+            let mut object = ObjectMap::new();
+            object.insert("labels".into(), Value::Object(labels));
+            object.insert(
+                "value".into(),
+                NotNan::new(exemplar.value).map_or(Value::Null, Value::Float),
+            );
+            object.insert(
+                "timestamp".into(),
+                Value::Timestamp(utc_timestamp(Some(exemplar.timestamp), default)),
+            );
+            Value::Object(object)
+        })
+        .collect::<Vec<_>>();
+
+    metric.metadata_mut().value_mut().insert(
+        path!("prometheus_remote_write", "exemplars"),
+        Value::Array(exemplars),
+    );
+    metric
+}
+
+/// Stores the native histogram of a remote write time series in the metadata of its metric, where
+/// the `prometheus_remote_write` sink picks it up again, as the buckets of the metric lose its
+/// resolution.
+fn with_native_histogram(mut metric: Metric, histogram: Option<proto::Histogram>) -> Metric {
+    use proto::histogram::{Count, ResetHint, ZeroCount};
+
+    let Some(histogram) = histogram else {
+        return metric;
+    };
+
+    let float = |value: f64| NotNan::new(value).map_or(Value::Null, Value::Float);
+    let spans = |spans: Vec<proto::BucketSpan>| {
+        let spans = spans.into_iter().map(|span| {
+            let mut object = ObjectMap::new();
+            object.insert("offset".into(), Value::from(span.offset));
+            object.insert("length".into(), Value::from(span.length));
+            Value::Object(object)
+        });
+        Value::Array(spans.collect())
+    };
+    let deltas = |deltas: Vec<i64>| Value::Array(deltas.into_iter().map(Value::from).collect());
+    let counts = |counts: Vec<f64>| Value::Array(counts.into_iter().map(float).collect());
+
+    // Integer histograms have integer counts and deltas, float histograms float counts.
+    let mut object = ObjectMap::new();
+    object.insert(
+        "count".into(),
+        match histogram.count {
+            Some(Count::CountInt(count)) => Value::from(count),
+            Some(Count::CountFloat(count)) => float(count),
+            None => Value::Null,
+        },
+    );
+    object.insert("sum".into(), float(histogram.sum));
+    object.insert("schema".into(), Value::from(histogram.schema));
+    object.insert("zero_threshold".into(), float(histogram.zero_threshold));
+    object.insert(
+        "zero_count".into(),
+        match histogram.zero_count {
+            Some(ZeroCount::ZeroCountInt(count)) => Value::from(count),
+            Some(ZeroCount::ZeroCountFloat(count)) => float(count),
+            None => Value::Null,
+        },
+    );
+    object.insert("negative_spans".into(), spans(histogram.negative_spans));
+    object.insert("negative_deltas".into(), deltas(histogram.negative_deltas));
+    object.insert("negative_counts".into(), counts(histogram.negative_counts));
+    object.insert("positive_spans".into(), spans(histogram.positive_spans));
+    object.insert("positive_deltas".into(), deltas(histogram.positive_deltas));
+    object.insert("positive_counts".into(), counts(histogram.positive_counts));
+    object.insert(
+        "reset_hint".into(),
+        ResetHint::try_from(histogram.reset_hint)
+            .unwrap_or(ResetHint::Unknown)
+            .as_str_name()
+            .to_lowercase()
+            .into(),
+    );
+
+    metric.metadata_mut().value_mut().insert(
+        path!("prometheus_remote_write", "native_histogram"),
+        Value::Object(object),
+    );
+    metric
+}
+
 fn combine_tags(
     base_tags: impl Into<MetricTags>,
     tag_overrides: impl IntoIterator<Item = (String, String)>,
@@ -1276,4 +1390,47 @@ mod test {
             ]),
         );
     }
+
+    #[cfg(feature = "sources-prometheus-remote-write")]
+    #[test]
+    fn test_request_exemplars_in_metadata() {
+        let label = |name: &str, value: &str| proto::Label {
+            name: name.into(),
+            value: value.into(),
+        };
+        let request = proto::WriteRequest {
+            timeseries: vec![proto::TimeSeries {
+                labels: vec![label("__name__", "requests_total")],
+                samples: vec![proto::Sample {
+                    value: 3.0,
+                    timestamp: 1612411506789,
+                }],
+                exemplars: vec![proto::Exemplar {
+                    labels: vec![label("trace_id", "abc")],
+                    value: 1.0,
+                    timestamp: 1612411506789,
+                }],
+                histograms: vec![],
+            }],
+            metadata: vec![],
+        };
+
+        let events = parse_request(request).unwrap();
+        let metric = events[0].as_metric();
+        let exemplars = metric
+            .metadata()
+            .value()
+            .get(path!("prometheus_remote_write", "exemplars"))
+            .and_then(Value::as_array)
+            .unwrap();
+        assert_eq!(exemplars.len(), 1);
+        assert_eq!(
+            exemplars[0].get(path!("labels", "trace_id")),
+            Some(&Value::from("abc"))
+        );
+        assert_eq!(
+            exemplars[0].get(path!("timestamp")),
+            Some(&Value::Timestamp(*TIMESTAMP))
+        );
+    }
 }
@@ -152,6 +152,7 @@ mod test {
     use chrono::{SubsecRound as _, Utc};
     use vector_lib::{
         event::{EventStatus, Metric, MetricKind, MetricValue},
+        lookup::path,
         metric_tags,
     };
 
@@ -270,6 +271,98 @@ mod test {
         ]
     }
 
+    #[tokio::test]
+    async fn receives_native_histograms() {
+        let address = test_util::next_addr();
+        let (tx, rx) = SourceSender::new_test_finalize(EventStatus::Delivered);
+
+        let source = PrometheusRemoteWriteConfig {
+            address,
+            auth: None,
+            tls: None,
+            acknowledgements: SourceAcknowledgementsConfig::default(),
+            keepalive: KeepaliveConfig::default(),
+        };
+        let source = source
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .unwrap();
+        tokio::spawn(source);
+        wait_for_tcp(address).await;
+
+        let sink = RemoteWriteConfig {
+            endpoint: format!("http://localhost:{}/", address.port()),
+            native_histograms: true,
+            ..Default::default()
+        };
+        let (sink, _) = sink
+            .build(SinkContext::default())
+            .await
+            .expect("Error building config.");
+
+        // A native histogram with negative, zero and positive buckets, as received by a source.
+        let request = proto::WriteRequest {
+            timeseries: vec![proto::TimeSeries {
+                labels: vec![proto::Label {
+                    name: "__name__".into(),
+                    value: "latency".into(),
+                }],
+                samples: vec![],
+                exemplars: vec![],
+                histograms: vec![proto::Histogram {
+                    count: Some(proto::histogram::Count::CountInt(9)),
+                    sum: 12.5,
+                    schema: 1,
+                    zero_threshold: 0.01,
+                    zero_count: Some(proto::histogram::ZeroCount::ZeroCountInt(2)),
+                    negative_spans: vec![proto::BucketSpan {
+                        offset: 1,
+                        length: 2,
+                    }],
+                    negative_deltas: vec![1, 0],
+                    negative_counts: vec![],
+                    positive_spans: vec![
+                        proto::BucketSpan {
+                            offset: -1,
+                            length: 1,
+                        },
+                        proto::BucketSpan {
+                            offset: 2,
+                            length: 2,
+                        },
+                    ],
+                    positive_deltas: vec![2, -1, 0],
+                    positive_counts: vec![],
+                    reset_hint: proto::histogram::ResetHint::No as i32,
+                    timestamp: Utc::now().trunc_subsecs(3).timestamp_millis(),
+                }],
+            }],
+            metadata: vec![],
+        };
+        let events = parser::parse_request(request).unwrap();
+
+        let events_copy = events.clone();
+        let output = test_util::spawn_collect_ready(
+            async move {
+                sink.run_events(events_copy).await.unwrap();
+            },
+            rx,
+            1,
+        )
+        .await;
+
+        vector_lib::assert_event_data_eq!(events, output);
+        let native_histogram = |event: &Event| {
+            event
+                .metadata()
+                .value()
+                .get(path!("prometheus_remote_write", "native_histogram"))
+                .cloned()
+        };
+        assert!(native_histogram(&events[0]).is_some());
+        assert_eq!(native_histogram(&output[0]), native_histogram(&events[0]));
+    }
+
     /// According to the [spec](https://github.com/OpenObservability/OpenMetrics/blob/main/specification/OpenMetrics.md?plain=1#L115)
     /// > Label names MUST be unique within a LabelSet.
     /// Prometheus itself will reject the metric with an error. Largely to remain backward compatible with older versions of Vector,
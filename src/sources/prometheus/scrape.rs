@@ -9,6 +9,7 @@ use snafu::{ResultExt, Snafu};
 use vector_lib::configurable::configurable_component;
 use vector_lib::{config::LogNamespace, event::Event};
 
+use super::discovery::{Discovery, FileSdConfig, KubernetesSdConfig, RelabelConfig, TargetLabels};
 use super::parser;
 use crate::sources::util::http::HttpMethod;
 use crate::sources::util::http_client::{default_timeout, warn_if_interval_too_low};
@@ -19,8 +20,8 @@ use crate::{
     sources::{
         self,
         util::http_client::{
-            build_url, call, default_interval, GenericHttpClientInputs, HttpClientBuilder,
-            HttpClientContext,
+            build_url, call, default_interval, static_urls, GenericHttpClientInputs,
+            HttpClientBuilder, HttpClientContext,
         },
     },
     tls::{TlsConfig, TlsSettings},
@@ -39,6 +40,10 @@ static NOT_FOUND_NO_PATH: &str = "No path is set on the endpoint and we got a 40
 enum ConfigError {
     #[snafu(display("Cannot set both `endpoints` and `hosts`"))]
     BothEndpointsAndHosts,
+    #[snafu(display(
+        "At least one of `endpoints`, `file_sd_configs`, or `kubernetes_sd_configs` must be set"
+    ))]
+    NoTargets,
 }
 
 /// Configuration for the `prometheus_scrape` source.
@@ -50,10 +55,36 @@ enum ConfigError {
 #[derive(Clone, Debug)]
 pub struct PrometheusScrapeConfig {
     /// Endpoints to scrape metrics from.
+    ///
+    /// These are scraped in addition to any targets found through service discovery.
     #[configurable(metadata(docs::examples = "http://localhost:9090/metrics"))]
-    #[serde(alias = "hosts")]
+    #[serde(alias = "hosts", default)]
     endpoints: Vec<String>,
 
+    /// Discovers targets to scrape from files.
+    #[serde(default)]
+    #[configurable(metadata(docs::advanced))]
+    file_sd_configs: Vec<FileSdConfig>,
+
+    /// Discovers targets to scrape from the Kubernetes API.
+    #[serde(default)]
+    #[configurable(metadata(docs::advanced))]
+    kubernetes_sd_configs: Vec<KubernetesSdConfig>,
+
+    /// Rules rewriting or filtering the targets found through service discovery, applied in order.
+    ///
+    /// Each target starts with the `__address__` label set to its discovered address, the
+    /// `__scheme__` and `__metrics_path__` labels set to `http` and `/metrics`, and the `__meta_*`
+    /// labels of its discovery mechanism. Once all rules have been applied, the target is scraped
+    /// at `<__scheme__>://<__address__><__metrics_path__>`, with each `__param_<name>` label added to
+    /// the query string, and its remaining labels that don't start with `__` are added as tags to
+    /// the scraped metrics.
+    ///
+    /// `endpoints` are not subject to relabeling.
+    #[serde(default)]
+    #[configurable(metadata(docs::advanced))]
+    relabel_configs: Vec<RelabelConfig>,
+
     /// The interval between scrapes. Requests are run concurrently so if a scrape takes longer
     /// than the interval a new scrape will be started. This can take extra resources, set the timeout
     /// to a value lower than the scrape interval to prevent this from happening.
@@ -123,6 +154,9 @@ impl GenerateConfig for PrometheusScrapeConfig {
     fn generate_config() -> toml::Value {
         toml::Value::try_from(Self {
             endpoints: vec!["http://localhost:9090/metrics".to_string()],
+            file_sd_configs: Vec::new(),
+            kubernetes_sd_configs: Vec::new(),
+            relabel_configs: Vec::new(),
             interval: default_interval(),
             timeout: default_timeout(),
             instance_tag: Some("instance".to_string()),
@@ -148,10 +182,31 @@ impl SourceConfig for PrometheusScrapeConfig {
             .collect::<std::result::Result<Vec<Uri>, sources::BuildError>>()?;
         let tls = TlsSettings::from_options(&self.tls)?;
 
+        let discovery = Discovery::new(
+            urls.clone(),
+            &self.file_sd_configs,
+            &self.kubernetes_sd_configs,
+            &self.relabel_configs,
+            &self.query,
+        )
+        .await?;
+        if urls.is_empty() && discovery.is_none() {
+            return Err(ConfigError::NoTargets.into());
+        }
+
+        let (urls, target_labels, discovery) = match discovery {
+            Some(discovery) => {
+                let (urls, target_labels, task) = discovery.start();
+                (urls, Some(target_labels), Some(task))
+            }
+            None => (static_urls(urls), None, None),
+        };
+
         let builder = PrometheusScrapeBuilder {
             honor_labels: self.honor_labels,
             instance_tag: self.instance_tag.clone(),
             endpoint_tag: self.endpoint_tag.clone(),
+            target_labels,
         };
 
         warn_if_interval_too_low(self.timeout, self.interval);
@@ -168,7 +223,15 @@ impl SourceConfig for PrometheusScrapeConfig {
             shutdown: cx.shutdown,
         };
 
-        Ok(call(inputs, builder, cx.out, HttpMethod::Get).boxed())
+        Ok(async move {
+            let discovery = discovery.map(tokio::spawn);
+            let result = call(inputs, builder, cx.out, HttpMethod::Get).await;
+            if let Some(discovery) = discovery {
+                discovery.abort();
+            }
+            result
+        }
+        .boxed())
     }
 
     fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
@@ -206,6 +269,7 @@ struct PrometheusScrapeBuilder {
     honor_labels: bool,
     instance_tag: Option<String>,
     endpoint_tag: Option<String>,
+    target_labels: Option<TargetLabels>,
 }
 
 impl HttpClientBuilder for PrometheusScrapeBuilder {
@@ -234,9 +298,16 @@ impl HttpClientBuilder for PrometheusScrapeBuilder {
             endpoint: url.to_string(),
             honor_label: self.honor_labels,
         });
+        let target_labels = self
+            .target_labels
+            .as_ref()
+            .and_then(|labels| labels.read().expect("poisoned lock").get(url).cloned())
+            .unwrap_or_default();
         PrometheusScrapeContext {
             instance_info,
             endpoint_info,
+            target_labels,
+            honor_labels: self.honor_labels,
         }
     }
 }
@@ -245,6 +316,8 @@ impl HttpClientBuilder for PrometheusScrapeBuilder {
 struct PrometheusScrapeContext {
     instance_info: Option<InstanceInfo>,
     endpoint_info: Option<EndpointInfo>,
+    target_labels: Vec<(String, String)>,
+    honor_labels: bool,
 }
 
 impl HttpClientContext for PrometheusScrapeContext {
@@ -285,6 +358,18 @@ impl HttpClientContext for PrometheusScrapeContext {
                     }
                 }
             }
+            for (tag, value) in &self.target_labels {
+                match (self.honor_labels, metric.tag_value(tag)) {
+                    (false, Some(old_value)) => {
+                        metric.replace_tag(format!("exported_{}", tag), old_value);
+                        metric.replace_tag(tag.clone(), value.clone());
+                    }
+                    (true, Some(_)) => {}
+                    (_, None) => {
+                        metric.replace_tag(tag.clone(), value.clone());
+                    }
+                }
+            }
         }
     }
 
@@ -364,6 +449,9 @@ mod test {
 
         let config = PrometheusScrapeConfig {
             endpoints: vec![format!("http://{}/metrics", in_addr)],
+            file_sd_configs: Vec::new(),
+            kubernetes_sd_configs: Vec::new(),
+            relabel_configs: Vec::new(),
             interval: Duration::from_secs(1),
             timeout: default_timeout(),
             instance_tag: Some("instance".to_string()),
@@ -398,6 +486,9 @@ mod test {
 
         let config = PrometheusScrapeConfig {
             endpoints: vec![format!("http://{}/metrics", in_addr)],
+            file_sd_configs: Vec::new(),
+            kubernetes_sd_configs: Vec::new(),
+            relabel_configs: Vec::new(),
             interval: Duration::from_secs(1),
             timeout: default_timeout(),
             instance_tag: Some("instance".to_string()),
@@ -450,6 +541,9 @@ mod test {
 
         let config = PrometheusScrapeConfig {
             endpoints: vec![format!("http://{}/metrics", in_addr)],
+            file_sd_configs: Vec::new(),
+            kubernetes_sd_configs: Vec::new(),
+            relabel_configs: Vec::new(),
             interval: Duration::from_secs(1),
             timeout: default_timeout(),
             instance_tag: Some("instance".to_string()),
@@ -501,6 +595,63 @@ mod test {
     /// > Label names MUST be unique within a LabelSet.
     /// Prometheus itself will reject the metric with an error. Largely to remain backward compatible with older versions of Vector,
     /// we accept the metric, but take the last label in the list.
+    #[tokio::test]
+    async fn test_prometheus_file_sd() {
+        let in_addr = next_addr();
+
+        let dummy_endpoint = warp::path!("stats").map(|| {
+            r#"
+                    promhttp_metric_handler_requests_total{code="200",job="exporter"} 100 1612411516789
+            "#
+        });
+
+        tokio::spawn(warp::serve(dummy_endpoint).run(in_addr));
+        wait_for_tcp(in_addr).await;
+
+        let dir = tempfile::tempdir().unwrap();
+        let targets = dir.path().join("targets.json");
+        std::fs::write(
+            &targets,
+            format!(
+                r#"[{{"targets": ["{}"], "labels": {{"job": "web", "__metrics_path__": "/stats"}}}}]"#,
+                in_addr
+            ),
+        )
+        .unwrap();
+
+        let config: PrometheusScrapeConfig = toml::from_str(&format!(
+            r#"
+            scrape_interval_secs = 1
+            relabel_configs = [
+                {{ source_labels = ["__meta_filepath"], regex = ".*/(.*)", target_label = "file" }},
+            ]
+
+            [[file_sd_configs]]
+            files = ["{}"]
+            "#,
+            dir.path().join("*.json").display()
+        ))
+        .unwrap();
+
+        let events = run_and_assert_source_compliance(
+            config,
+            Duration::from_secs(3),
+            &HTTP_PULL_SOURCE_TAGS,
+        )
+        .await;
+        assert!(!events.is_empty());
+
+        for event in events {
+            let metric = event.into_metric();
+            assert_eq!(metric.tag_value("job"), Some(String::from("web")));
+            assert_eq!(
+                metric.tag_value("exported_job"),
+                Some(String::from("exporter"))
+            );
+            assert_eq!(metric.tag_value("file"), Some(String::from("targets.json")));
+        }
+    }
+
     #[tokio::test]
     async fn test_prometheus_duplicate_tags() {
         let in_addr = next_addr();
@@ -516,6 +667,9 @@ mod test {
 
         let config = PrometheusScrapeConfig {
             endpoints: vec![format!("http://{}/metrics", in_addr)],
+            file_sd_configs: Vec::new(),
+            kubernetes_sd_configs: Vec::new(),
+            relabel_configs: Vec::new(),
             interval: Duration::from_secs(1),
             timeout: default_timeout(),
             instance_tag: Some("instance".to_string()),
@@ -572,6 +726,9 @@ mod test {
 
         let config = PrometheusScrapeConfig {
             endpoints: vec![format!("http://{}/metrics?key1=val1", in_addr)],
+            file_sd_configs: Vec::new(),
+            kubernetes_sd_configs: Vec::new(),
+            relabel_configs: Vec::new(),
             interval: Duration::from_secs(1),
             timeout: default_timeout(),
             instance_tag: Some("instance".to_string()),
@@ -680,6 +837,9 @@ mod test {
             "in",
             PrometheusScrapeConfig {
                 endpoints: vec![format!("http://{}", in_addr)],
+                file_sd_configs: Vec::new(),
+                kubernetes_sd_configs: Vec::new(),
+                relabel_configs: Vec::new(),
                 instance_tag: None,
                 endpoint_tag: None,
                 honor_labels: false,
@@ -770,6 +930,9 @@ mod integration_tests {
     async fn scrapes_metrics() {
         let config = PrometheusScrapeConfig {
             endpoints: vec!["http://prometheus:9090/metrics".into()],
+            file_sd_configs: Vec::new(),
+            kubernetes_sd_configs: Vec::new(),
+            relabel_configs: Vec::new(),
             interval: Duration::from_secs(1),
             timeout: Duration::from_secs(1),
             instance_tag: Some("instance".to_string()),
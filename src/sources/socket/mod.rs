@@ -340,11 +340,14 @@ mod test {
         task::JoinHandle,
         time::{timeout, Duration, Instant},
     };
-    use vector_lib::codecs::NewlineDelimitedDecoderConfig;
     #[cfg(unix)]
     use vector_lib::codecs::{
         decoding::CharacterDelimitedDecoderOptions, CharacterDelimitedDecoderConfig,
     };
+    use vector_lib::codecs::{
+        encoding::Framer, NewlineDelimitedDecoderConfig, NewlineDelimitedEncoder,
+        TextSerializerConfig,
+    };
     use vector_lib::event::EventContainer;
     use vector_lib::lookup::{lookup_v2::OptionalValuePath, owned_value_path, path};
     use vrl::value::ObjectMap;
@@ -369,11 +372,12 @@ mod test {
 
     use super::{tcp::TcpConfig, udp::UdpConfig, SocketConfig};
     use crate::{
+        codecs::Encoder,
         config::{log_schema, ComponentKey, GlobalOptions, SourceConfig, SourceContext},
         event::{Event, LogEvent},
         shutdown::{ShutdownSignal, SourceShutdownCoordinator},
-        sinks::util::tcp::TcpSinkConfig,
-        sources::util::net::SocketListenAddr,
+        sinks::util::tcp::{TcpCompression, TcpSinkConfig},
+        sources::util::net::{SocketListenAddr, TcpDecompression},
         test_util::{
             collect_n, collect_n_limited,
             components::{assert_source_compliance, SOCKET_PUSH_SOURCE_TAGS},
@@ -814,6 +818,49 @@ mod test {
         assert_eq!(source_result, Ok(()));
     }
 
+    #[tokio::test]
+    async fn tcp_decompresses_compressed_streams() {
+        for compression in [TcpCompression::Gzip, TcpCompression::Zstd] {
+            let (tx, rx) = SourceSender::new_test();
+            let addr = next_addr();
+
+            let mut source_config = TcpConfig::from_address(addr.into());
+            source_config.set_decompression(TcpDecompression::Auto);
+            let server = SocketConfig::from(source_config)
+                .build(SourceContext::new_test(tx, None))
+                .await
+                .unwrap();
+            tokio::spawn(server);
+            wait_for_tcp(addr).await;
+
+            let sink_config = TcpSinkConfig::from_address(format!("localhost:{}", addr.port()))
+                .set_compression(compression);
+            let encoder = Encoder::<Framer>::new(
+                NewlineDelimitedEncoder::new().into(),
+                TextSerializerConfig::default().build().into(),
+            );
+            let (sink, _healthcheck) = sink_config.build(Default::default(), encoder).unwrap();
+
+            let messages = (0..3)
+                .map(|index| format!("message {} with {:?}", index, compression))
+                .collect::<Vec<_>>();
+            let input = messages
+                .iter()
+                .map(|message| LogEvent::from(message.as_str()).into())
+                .collect::<Vec<Event>>();
+            sink.run(stream::iter(input).boxed()).await.unwrap();
+
+            let events = collect_n(rx, 3).await;
+            let message_key = log_schema().message_key().unwrap().to_string();
+            for (event, message) in events.iter().zip(&messages) {
+                assert_eq!(
+                    event.as_log()[message_key.as_str()],
+                    message.as_str().into()
+                );
+            }
+        }
+    }
+
     #[tokio::test]
     async fn tcp_connection_close_after_max_duration() {
         let (tx, _) = SourceSender::new_test();
@@ -13,7 +13,9 @@ use crate::{
     codecs::Decoder,
     event::Event,
     serde::default_decoding,
-    sources::util::net::{SocketListenAddr, TcpNullAcker, TcpSource},
+    sources::util::net::{
+        DecompressingDecoder, SocketListenAddr, TcpDecompression, TcpNullAcker, TcpSource,
+    },
     tcp::TcpKeepaliveConfig,
     tls::TlsSourceConfig,
 };
@@ -79,6 +81,10 @@ pub struct TcpConfig {
     #[configurable(metadata(docs::type_unit = "connections"))]
     pub connection_limit: Option<u32>,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    decompression: TcpDecompression,
+
     #[configurable(derived)]
     pub(super) framing: Option<FramingConfig>,
 
@@ -115,6 +121,7 @@ impl TcpConfig {
             framing: None,
             decoding: default_decoding(),
             connection_limit: None,
+            decompression: TcpDecompression::None,
             log_namespace: None,
         }
     }
@@ -174,6 +181,11 @@ impl TcpConfig {
         self
     }
 
+    pub fn set_decompression(&mut self, val: TcpDecompression) -> &mut Self {
+        self.decompression = val;
+        self
+    }
+
     pub fn set_framing(&mut self, val: Option<FramingConfig>) -> &mut Self {
         self.framing = val;
         self
@@ -210,11 +222,11 @@ impl RawTcpSource {
 impl TcpSource for RawTcpSource {
     type Error = vector_lib::codecs::decoding::Error;
     type Item = SmallVec<[Event; 1]>;
-    type Decoder = Decoder;
+    type Decoder = DecompressingDecoder<Decoder>;
     type Acker = TcpNullAcker;
 
     fn decoder(&self) -> Self::Decoder {
-        self.decoder.clone()
+        DecompressingDecoder::new(self.decoder.clone(), self.config.decompression)
     }
 
     fn handle_events(&self, events: &mut [Event], host: std::net::SocketAddr) {
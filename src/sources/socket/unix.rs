@@ -28,8 +28,11 @@ use super::{default_host_key, SocketConfig};
 pub struct UnixConfig {
     /// The Unix socket path.
     ///
-    /// This should be an absolute path.
+    /// This should be an absolute path, or `systemd{#N}` to use the Nth socket passed by
+    /// systemd socket activation. The socket file of a systemd-provided socket is managed by
+    /// systemd, so `socket_file_mode` has no effect on it.
     #[configurable(metadata(docs::examples = "/path/to/socket"))]
+    #[configurable(metadata(docs::examples = "systemd"))]
     pub path: PathBuf,
 
     /// Unix file mode bits to be applied to the unix socket file as its designated file permissions.
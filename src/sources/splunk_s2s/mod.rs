@@ -0,0 +1,651 @@
+//! The `splunk_s2s` source. See [SplunkS2sConfig].
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use bytes::{Bytes, BytesMut};
+use chrono::{TimeZone, Utc};
+use smallvec::{smallvec, SmallVec};
+use tokio_util::codec::Decoder;
+use vector_lib::codecs::{BytesDeserializerConfig, StreamDecodingError};
+use vector_lib::config::{LegacyKey, LogNamespace};
+use vector_lib::configurable::configurable_component;
+use vector_lib::ipallowlist::IpAllowlistConfig;
+use vector_lib::lookup::{metadata_path, owned_value_path, path};
+use vector_lib::schema::{meaning, Definition};
+use vrl::value::kind::Collection;
+use vrl::value::{Kind, Value};
+
+use super::util::net::{SocketListenAddr, TcpSource, TcpSourceAck, TcpSourceAcker};
+use crate::{
+    config::{
+        log_schema, DataType, GenerateConfig, Resource, SourceAcknowledgementsConfig, SourceConfig,
+        SourceContext, SourceOutput,
+    },
+    event::{Event, LogEvent},
+    internal_events::SplunkS2sMessageDecodeError,
+    serde::bool_or_struct,
+    tcp::TcpKeepaliveConfig,
+    tls::{MaybeTlsSettings, TlsSourceConfig},
+};
+
+mod protocol;
+pub use self::protocol::DecodeError;
+use self::protocol::{S2sMessage, S2sMessageDecoder};
+
+// The same keys as used by the `splunk_hec` source, so events can be routed on to a `splunk_hec`
+// sink using the same templates.
+const INDEX: &str = "splunk_index";
+const SOURCE: &str = "splunk_source";
+const SOURCETYPE: &str = "splunk_sourcetype";
+
+const CAPABILITIES_KEY: &str = "__s2s_capabilities";
+const CONTROL_MESSAGE_KEY: &str = "__s2s_control_msg";
+
+/// The answer to a forwarder's capability request.
+///
+/// Forwarder acknowledgements, compression, and the version 4 framing are all declined, which makes
+/// the forwarder fall back to plain version 3 messages.
+const CAPABILITIES_RESPONSE: &str =
+    "cap_response=success;cap_flush_key=false;idx_can_send_hb=false;idx_can_recv_token=false;v4=false";
+
+/// Configuration for the `splunk_s2s` source.
+#[configurable_component(source(
+    "splunk_s2s",
+    "Receive logs from Splunk forwarders over the Splunk-to-Splunk protocol."
+))]
+#[derive(Clone, Debug)]
+pub struct SplunkS2sConfig {
+    #[configurable(derived)]
+    address: SocketListenAddr,
+
+    /// The maximum number of TCP connections that are allowed at any given time.
+    #[configurable(metadata(docs::type_unit = "connections"))]
+    connection_limit: Option<u32>,
+
+    #[configurable(derived)]
+    keepalive: Option<TcpKeepaliveConfig>,
+
+    #[configurable(derived)]
+    pub permit_origin: Option<IpAllowlistConfig>,
+
+    /// The size of the receive buffer used for each connection.
+    ///
+    /// This generally should not need to be changed.
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    #[configurable(metadata(docs::examples = 65536))]
+    receive_buffer_bytes: Option<usize>,
+
+    #[configurable(derived)]
+    tls: Option<TlsSourceConfig>,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: SourceAcknowledgementsConfig,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[configurable(metadata(docs::hidden))]
+    #[serde(default)]
+    log_namespace: Option<bool>,
+}
+
+impl GenerateConfig for SplunkS2sConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            address: SocketListenAddr::SocketAddr("0.0.0.0:9997".parse().unwrap()),
+            keepalive: None,
+            permit_origin: None,
+            tls: None,
+            receive_buffer_bytes: None,
+            acknowledgements: Default::default(),
+            connection_limit: None,
+            log_namespace: None,
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "splunk_s2s")]
+impl SourceConfig for SplunkS2sConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let log_namespace = cx.log_namespace(self.log_namespace);
+        let source = SplunkS2sSource { log_namespace };
+        let shutdown_secs = Duration::from_secs(30);
+        let tls_config = self.tls.as_ref().map(|tls| tls.tls_config.clone());
+        let tls_client_metadata_key = self
+            .tls
+            .as_ref()
+            .and_then(|tls| tls.client_metadata_key.clone())
+            .and_then(|k| k.path);
+        let tls = MaybeTlsSettings::from_config(&tls_config, true)?;
+        source.run(
+            self.address,
+            self.keepalive,
+            shutdown_secs,
+            tls,
+            tls_client_metadata_key,
+            self.receive_buffer_bytes,
+            None,
+            cx,
+            self.acknowledgements,
+            self.connection_limit,
+            self.permit_origin.clone().map(Into::into),
+            SplunkS2sConfig::NAME,
+            log_namespace,
+        )
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
+        let log_namespace = global_log_namespace.merge(self.log_namespace);
+        let schema_definition = self.schema_definition(log_namespace);
+
+        vec![SourceOutput::new_logs(DataType::Log, schema_definition)]
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        vec![self.address.as_tcp_resource()]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+impl SplunkS2sConfig {
+    /// Builds the `schema::Definition` for this source using the provided `LogNamespace`.
+    fn schema_definition(&self, log_namespace: LogNamespace) -> Definition {
+        // `host_key` is only inserted if not present already.
+        let host_key = log_schema()
+            .host_key()
+            .cloned()
+            .map(LegacyKey::InsertIfEmpty);
+
+        let tls_client_metadata_path = self
+            .tls
+            .as_ref()
+            .and_then(|tls| tls.client_metadata_key.as_ref())
+            .and_then(|k| k.path.clone())
+            .map(LegacyKey::Overwrite);
+
+        let mut schema_definition = BytesDeserializerConfig
+            .schema_definition(log_namespace)
+            .with_standard_vector_source_metadata()
+            .with_source_metadata(
+                Self::NAME,
+                host_key,
+                &owned_value_path!("host"),
+                Kind::bytes(),
+                Some(meaning::HOST),
+            )
+            .with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::Overwrite(owned_value_path!(INDEX))),
+                &owned_value_path!("index"),
+                Kind::bytes().or_undefined(),
+                None,
+            )
+            .with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::Overwrite(owned_value_path!(SOURCE))),
+                &owned_value_path!("source"),
+                Kind::bytes().or_undefined(),
+                Some(meaning::SERVICE),
+            )
+            .with_source_metadata(
+                Self::NAME,
+                Some(LegacyKey::Overwrite(owned_value_path!(SOURCETYPE))),
+                &owned_value_path!("sourcetype"),
+                Kind::bytes().or_undefined(),
+                None,
+            )
+            .with_source_metadata(
+                Self::NAME,
+                None,
+                &owned_value_path!("timestamp"),
+                Kind::timestamp().or_undefined(),
+                Some(meaning::TIMESTAMP),
+            )
+            // for the indexed fields that are added to the events dynamically
+            .with_source_metadata(
+                Self::NAME,
+                None,
+                &owned_value_path!("fields"),
+                Kind::object(Collection::empty().with_unknown(Kind::bytes())).or_undefined(),
+                None,
+            )
+            .with_source_metadata(
+                Self::NAME,
+                tls_client_metadata_path,
+                &owned_value_path!("tls_client_metadata"),
+                Kind::object(Collection::empty().with_unknown(Kind::bytes())).or_undefined(),
+                None,
+            );
+
+        // for indexed fields that are added to the events dynamically
+        if log_namespace == LogNamespace::Legacy {
+            schema_definition = schema_definition.unknown_fields(Kind::bytes());
+        }
+
+        schema_definition
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SplunkS2sSource {
+    log_namespace: LogNamespace,
+}
+
+impl TcpSource for SplunkS2sSource {
+    type Error = DecodeError;
+    type Item = S2sFrame;
+    type Decoder = S2sDecoder;
+    type Acker = S2sAcker;
+
+    fn decoder(&self) -> Self::Decoder {
+        S2sDecoder {
+            inner: S2sMessageDecoder::default(),
+            log_namespace: self.log_namespace,
+        }
+    }
+
+    fn handle_events(&self, events: &mut [Event], host: SocketAddr) {
+        for event in events {
+            let log = event.as_mut_log();
+
+            // The host reported by the forwarder takes precedence over the peer address. The legacy
+            // key is only inserted if empty, so only the metadata needs to be checked here.
+            let has_host = self.log_namespace == LogNamespace::Vector
+                && log.contains(metadata_path!(SplunkS2sConfig::NAME, "host"));
+            if !has_host {
+                self.log_namespace.insert_source_metadata(
+                    SplunkS2sConfig::NAME,
+                    log,
+                    log_schema().host_key().map(LegacyKey::InsertIfEmpty),
+                    path!("host"),
+                    host.ip().to_string(),
+                );
+            }
+        }
+    }
+
+    fn build_acker(&self, frames: &[Self::Item]) -> Self::Acker {
+        S2sAcker {
+            replies: frames.iter().filter_map(|f| f.reply.clone()).collect(),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct S2sDecoder {
+    inner: S2sMessageDecoder,
+    log_namespace: LogNamespace,
+}
+
+impl S2sDecoder {
+    fn handle_message(&self, message: S2sMessage) -> Option<S2sFrame> {
+        if message.get(CAPABILITIES_KEY).is_some() {
+            let reply = S2sMessage {
+                fields: vec![(
+                    CONTROL_MESSAGE_KEY.to_owned(),
+                    Bytes::from_static(CAPABILITIES_RESPONSE.as_bytes()),
+                )],
+            };
+            return Some(S2sFrame {
+                events: smallvec![],
+                reply: Some(reply.encode()),
+            });
+        }
+
+        // Control messages, such as the `_done` markers closing a stream, carry no data.
+        build_event(message, self.log_namespace).map(|event| S2sFrame {
+            events: smallvec![event],
+            reply: None,
+        })
+    }
+}
+
+impl Decoder for S2sDecoder {
+    type Item = (S2sFrame, usize);
+    type Error = DecodeError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        loop {
+            let (message, byte_size) = match self.inner.decode(src) {
+                Ok(Some(item)) => item,
+                Ok(None) => return Ok(None),
+                Err(error) => {
+                    // Unrecoverable errors are reported when the connection is closed.
+                    if error.can_continue() {
+                        emit!(SplunkS2sMessageDecodeError { error: &error });
+                    }
+                    return Err(error);
+                }
+            };
+
+            if let Some(frame) = self.handle_message(message) {
+                return Ok(Some((frame, byte_size)));
+            }
+        }
+    }
+}
+
+/// Converts a message carrying event data into a log event.
+fn build_event(message: S2sMessage, log_namespace: LogNamespace) -> Option<Event> {
+    let raw = message.get("_raw")?.clone();
+
+    let mut log = match log_namespace {
+        LogNamespace::Vector => LogEvent::from(Value::from(raw)),
+        LogNamespace::Legacy => {
+            let mut log = LogEvent::default();
+            log.maybe_insert(log_schema().message_key_target_path(), raw);
+            log
+        }
+    };
+
+    let mut seconds = None;
+    let mut subseconds = None;
+
+    for (key, value) in message.fields {
+        match key.as_str() {
+            "_raw" => {}
+            "_time" => {
+                seconds = std::str::from_utf8(&value)
+                    .ok()
+                    .and_then(|s| s.parse::<i64>().ok())
+            }
+            "_subsecond" => {
+                subseconds = std::str::from_utf8(&value)
+                    .ok()
+                    .and_then(|s| s.parse::<f64>().ok())
+            }
+            "MetaData:Host" => log_namespace.insert_source_metadata(
+                SplunkS2sConfig::NAME,
+                &mut log,
+                log_schema().host_key().map(LegacyKey::InsertIfEmpty),
+                path!("host"),
+                strip_metadata_prefix(value, "host::"),
+            ),
+            "MetaData:Source" => log_namespace.insert_source_metadata(
+                SplunkS2sConfig::NAME,
+                &mut log,
+                Some(LegacyKey::Overwrite(path!(SOURCE))),
+                path!("source"),
+                strip_metadata_prefix(value, "source::"),
+            ),
+            "MetaData:Sourcetype" => log_namespace.insert_source_metadata(
+                SplunkS2sConfig::NAME,
+                &mut log,
+                Some(LegacyKey::Overwrite(path!(SOURCETYPE))),
+                path!("sourcetype"),
+                strip_metadata_prefix(value, "sourcetype::"),
+            ),
+            "_MetaData:Index" => log_namespace.insert_source_metadata(
+                SplunkS2sConfig::NAME,
+                &mut log,
+                Some(LegacyKey::Overwrite(path!(INDEX))),
+                path!("index"),
+                value,
+            ),
+            // Everything else starting with an underscore is internal to the forwarder, such as
+            // the path of the monitored file or the line breaking state.
+            key if key.starts_with('_') => {}
+            // The remaining keys are indexed fields extracted on the forwarder.
+            key => log_namespace.insert_source_metadata(
+                SplunkS2sConfig::NAME,
+                &mut log,
+                Some(LegacyKey::InsertIfEmpty(path!(key))),
+                path!("fields", key),
+                value,
+            ),
+        }
+    }
+
+    let timestamp = seconds.and_then(|seconds| {
+        let nanos = subseconds.map_or(0, |fraction| (fraction.fract() * 1e9) as u32);
+        Utc.timestamp_opt(seconds, nanos).single()
+    });
+    if let Some(timestamp) = timestamp {
+        match log_namespace {
+            LogNamespace::Vector => {
+                log.insert(
+                    metadata_path!(SplunkS2sConfig::NAME, "timestamp"),
+                    timestamp,
+                );
+            }
+            LogNamespace::Legacy => {
+                log.maybe_insert(log_schema().timestamp_key_target_path(), timestamp);
+            }
+        }
+    }
+
+    log_namespace.insert_standard_vector_source_metadata(
+        &mut log,
+        SplunkS2sConfig::NAME,
+        Utc::now(),
+    );
+
+    Some(log.into())
+}
+
+/// Forwarders prefix the indexed metadata fields with their name, as in `host::web01`.
+fn strip_metadata_prefix(value: Bytes, prefix: &str) -> Bytes {
+    if value.starts_with(prefix.as_bytes()) {
+        value.slice(prefix.len()..)
+    } else {
+        value
+    }
+}
+
+struct S2sAcker {
+    replies: Vec<Bytes>,
+}
+
+impl TcpSourceAcker for S2sAcker {
+    // The replies are part of the protocol handshake rather than delivery acknowledgements, so they
+    // are sent regardless of the outcome.
+    fn build_ack(self, _ack: TcpSourceAck) -> Option<Bytes> {
+        if self.replies.is_empty() {
+            return None;
+        }
+        Some(self.replies.concat().into())
+    }
+}
+
+struct S2sFrame {
+    events: SmallVec<[Event; 1]>,
+    reply: Option<Bytes>,
+}
+
+impl From<S2sFrame> for SmallVec<[Event; 1]> {
+    fn from(frame: S2sFrame) -> Self {
+        frame.events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        time::{timeout, Duration},
+    };
+    use vector_lib::lookup::OwnedTargetPath;
+
+    use super::{protocol::signature_block, *};
+    use crate::{
+        event::EventStatus,
+        test_util::{self, next_addr, trace_init, wait_for_tcp},
+        SourceSender,
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<SplunkS2sConfig>();
+    }
+
+    fn message(fields: &[(&str, &str)]) -> S2sMessage {
+        S2sMessage {
+            fields: fields
+                .iter()
+                .map(|(key, value)| (key.to_string(), Bytes::from(value.to_string())))
+                .collect(),
+        }
+    }
+
+    fn forwarder_event(raw: &str) -> S2sMessage {
+        message(&[
+            ("_raw", raw),
+            ("_path", "/var/log/app.log"),
+            ("_time", "1700000000"),
+            ("_subsecond", ".25"),
+            ("MetaData:Host", "host::web01"),
+            ("MetaData:Source", "source::/var/log/app.log"),
+            ("MetaData:Sourcetype", "sourcetype::app"),
+            ("_MetaData:Index", "main"),
+            ("env", "prod"),
+        ])
+    }
+
+    #[test]
+    fn builds_legacy_event() {
+        let event = build_event(forwarder_event("hello"), LogNamespace::Legacy).unwrap();
+        let log = event.as_log();
+
+        assert_eq!(log["message"], "hello".into());
+        assert_eq!(log["host"], "web01".into());
+        assert_eq!(log[SOURCE], "/var/log/app.log".into());
+        assert_eq!(log[SOURCETYPE], "app".into());
+        assert_eq!(log[INDEX], "main".into());
+        assert_eq!(log["env"], "prod".into());
+        assert_eq!(log["source_type"], SplunkS2sConfig::NAME.into());
+        assert_eq!(
+            log["timestamp"],
+            Utc.timestamp_opt(1_700_000_000, 250_000_000)
+                .unwrap()
+                .into()
+        );
+        assert!(!log.contains("_path"));
+    }
+
+    #[test]
+    fn builds_vector_event() {
+        let event = build_event(forwarder_event("hello"), LogNamespace::Vector).unwrap();
+        let log = event.as_log();
+
+        assert_eq!(log.value(), &Value::from("hello"));
+        assert_eq!(
+            log.get(metadata_path!("splunk_s2s", "host")),
+            Some(&"web01".into())
+        );
+        assert_eq!(
+            log.get(metadata_path!("splunk_s2s", "source")),
+            Some(&"/var/log/app.log".into())
+        );
+        assert_eq!(
+            log.get(metadata_path!("splunk_s2s", "sourcetype")),
+            Some(&"app".into())
+        );
+        assert_eq!(
+            log.get(metadata_path!("splunk_s2s", "index")),
+            Some(&"main".into())
+        );
+        assert_eq!(
+            log.get(metadata_path!("splunk_s2s", "fields", "env")),
+            Some(&"prod".into())
+        );
+    }
+
+    #[test]
+    fn skips_control_messages() {
+        assert!(build_event(message(&[("_done", "_done")]), LogNamespace::Legacy).is_none());
+    }
+
+    #[tokio::test]
+    async fn receives_events_and_answers_capabilities() {
+        trace_init();
+
+        let (sender, recv) = SourceSender::new_test_finalize(EventStatus::Delivered);
+        let address = next_addr();
+        let source = SplunkS2sConfig {
+            address: address.into(),
+            tls: None,
+            keepalive: None,
+            permit_origin: None,
+            receive_buffer_bytes: None,
+            acknowledgements: true.into(),
+            connection_limit: None,
+            log_namespace: None,
+        }
+        .build(SourceContext::new_test(sender, None))
+        .await
+        .unwrap();
+        tokio::spawn(source);
+        wait_for_tcp(address).await;
+
+        let mut req = signature_block("uf01", "8089");
+        req.extend_from_slice(&message(&[(CAPABILITIES_KEY, "ack=0;compression=0")]).encode());
+        req.extend_from_slice(&forwarder_event("first").encode());
+        req.extend_from_slice(&message(&[("_raw", "second")]).encode());
+
+        let sender = tokio::spawn(async move {
+            let mut socket = tokio::net::TcpStream::connect(address).await.unwrap();
+            socket.write_all(&req).await.unwrap();
+
+            let mut output = BytesMut::new();
+            let _ = timeout(Duration::from_millis(250), socket.read_buf(&mut output)).await;
+            output
+        });
+        let events = test_util::collect_n(recv, 2).await;
+        let output = sender.await.unwrap();
+
+        assert_eq!(events[0].as_log()["message"], "first".into());
+        assert_eq!(events[0].as_log()["host"], "web01".into());
+        assert_eq!(events[1].as_log()["message"], "second".into());
+        assert_eq!(events[1].as_log()["host"], "127.0.0.1".into());
+
+        let (reply, _) = S2sMessageDecoder::default()
+            .decode(&mut BytesMut::from(
+                &[&signature_block("", "")[..], &output[..]].concat()[..],
+            ))
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            reply.get(CONTROL_MESSAGE_KEY).unwrap(),
+            CAPABILITIES_RESPONSE
+        );
+    }
+
+    #[test]
+    fn output_schema_definition_vector_namespace() {
+        let config = SplunkS2sConfig {
+            address: SocketListenAddr::SocketAddr("0.0.0.0:9997".parse().unwrap()),
+            tls: None,
+            keepalive: None,
+            permit_origin: None,
+            receive_buffer_bytes: None,
+            acknowledgements: false.into(),
+            connection_limit: None,
+            log_namespace: Some(true),
+        };
+
+        let definition = config
+            .outputs(LogNamespace::Vector)
+            .remove(0)
+            .schema_definition(true)
+            .unwrap();
+
+        assert_eq!(
+            definition.meaning_path("host"),
+            Some(&OwnedTargetPath::metadata(owned_value_path!(
+                "splunk_s2s",
+                "host"
+            )))
+        );
+        assert_eq!(
+            definition.meaning_path("timestamp"),
+            Some(&OwnedTargetPath::metadata(owned_value_path!(
+                "splunk_s2s",
+                "timestamp"
+            )))
+        );
+    }
+}
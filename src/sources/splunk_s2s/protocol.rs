@@ -0,0 +1,298 @@
+//! The Splunk-to-Splunk (S2S) "cooked" wire protocol spoken by Splunk forwarders.
+//!
+//! A connection starts with a fixed size signature block, after which the forwarder sends a
+//! stream of messages. Each message is a length prefixed list of key/value pairs, with every
+//! string itself being length prefixed and NUL terminated:
+//!
+//! ```text
+//! message = u32 size, u32 count, count * (string key, string value), trailer
+//! string  = u32 length (including the NUL terminator), bytes, NUL
+//! ```
+//!
+//! All integers are big endian. The trailer repeats the `_raw` key and carries no information
+//! that isn't already part of the key/value pairs, so it is skipped.
+
+use std::io;
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use tokio_util::codec::Decoder;
+use vector_lib::codecs::StreamDecodingError;
+
+/// The signature sent by forwarders speaking cooked mode version 3.
+///
+/// Version 4 is negotiated on top of a version 3 connection through the capability exchange, so it
+/// shares this signature.
+pub(super) const SIGNATURE: &[u8] = b"--splunk-cooked-mode-v3--";
+
+/// Size of the signature block: a 128 byte signature, a 256 byte server name and a 16 byte
+/// management port, all NUL padded.
+const SIGNATURE_BLOCK_LEN: usize = 400;
+const SERVER_NAME_RANGE: std::ops::Range<usize> = 128..384;
+const MGMT_PORT_RANGE: std::ops::Range<usize> = 384..400;
+
+/// Messages larger than this are assumed to be garbage rather than buffered.
+const MAX_MESSAGE_LEN: usize = 64 * 1024 * 1024;
+
+const RAW_KEY: &str = "_raw";
+
+#[derive(Debug)]
+pub enum DecodeError {
+    IO(io::Error),
+    InvalidSignature,
+    MessageTooLarge(usize),
+    Truncated,
+}
+
+impl std::fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DecodeError::IO(err) => write!(f, "{}", err),
+            DecodeError::InvalidSignature => {
+                write!(
+                    f,
+                    "connection did not start with a cooked mode v3 signature"
+                )
+            }
+            DecodeError::MessageTooLarge(len) => {
+                write!(
+                    f,
+                    "message of {} bytes exceeds the maximum of {} bytes",
+                    len, MAX_MESSAGE_LEN
+                )
+            }
+            DecodeError::Truncated => write!(f, "message ended before all of its fields"),
+        }
+    }
+}
+
+impl StreamDecodingError for DecodeError {
+    fn can_continue(&self) -> bool {
+        match self {
+            DecodeError::IO(_) => false,
+            DecodeError::InvalidSignature => false,
+            DecodeError::MessageTooLarge(_) => false,
+            // The message size was intact, so the next message starts at a known offset.
+            DecodeError::Truncated => true,
+        }
+    }
+}
+
+impl From<io::Error> for DecodeError {
+    fn from(e: io::Error) -> Self {
+        DecodeError::IO(e)
+    }
+}
+
+/// A decoded S2S message.
+#[derive(Debug, Default, PartialEq)]
+pub(super) struct S2sMessage {
+    pub fields: Vec<(String, Bytes)>,
+}
+
+impl S2sMessage {
+    pub(super) fn get(&self, key: &str) -> Option<&Bytes> {
+        self.fields
+            .iter()
+            .find_map(|(k, value)| (k == key).then_some(value))
+    }
+
+    /// Encodes the message, including its size prefix and trailer.
+    pub(super) fn encode(&self) -> Bytes {
+        let mut body = BytesMut::new();
+        body.put_u32(self.fields.len() as u32);
+        for (key, value) in &self.fields {
+            put_string(&mut body, key.as_bytes());
+            put_string(&mut body, value);
+        }
+        body.put_u32(0);
+        put_string(&mut body, RAW_KEY.as_bytes());
+
+        let mut message = BytesMut::with_capacity(body.len() + 4);
+        message.put_u32(body.len() as u32);
+        message.extend_from_slice(&body);
+        message.freeze()
+    }
+}
+
+fn put_string(buf: &mut BytesMut, string: &[u8]) {
+    buf.put_u32(string.len() as u32 + 1);
+    buf.extend_from_slice(string);
+    buf.put_u8(0);
+}
+
+/// Decodes the signature block followed by a stream of messages.
+#[derive(Debug, Default)]
+pub(super) struct S2sMessageDecoder {
+    signature_received: bool,
+}
+
+impl Decoder for S2sMessageDecoder {
+    type Item = (S2sMessage, usize);
+    type Error = DecodeError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        if !self.signature_received {
+            if src.len() < SIGNATURE_BLOCK_LEN {
+                return Ok(None);
+            }
+
+            let block = src.split_to(SIGNATURE_BLOCK_LEN);
+            if !block.starts_with(SIGNATURE) {
+                return Err(DecodeError::InvalidSignature);
+            }
+            self.signature_received = true;
+
+            debug!(
+                message = "Received Splunk forwarder signature.",
+                server_name = %trim_nul(&block[SERVER_NAME_RANGE]),
+                mgmt_port = %trim_nul(&block[MGMT_PORT_RANGE]),
+            );
+        }
+
+        if src.len() < 4 {
+            return Ok(None);
+        }
+
+        let len = u32::from_be_bytes([src[0], src[1], src[2], src[3]]) as usize;
+        if len > MAX_MESSAGE_LEN {
+            return Err(DecodeError::MessageTooLarge(len));
+        }
+        if src.len() < len + 4 {
+            src.reserve(len + 4 - src.len());
+            return Ok(None);
+        }
+
+        src.advance(4);
+        let mut body = src.split_to(len).freeze();
+        parse_message(&mut body).map(|message| Some((message, len + 4)))
+    }
+}
+
+fn parse_message(body: &mut Bytes) -> Result<S2sMessage, DecodeError> {
+    let count = read_u32(body)? as usize;
+
+    // Don't trust the count for the allocation, a corrupt message would otherwise be able to
+    // request an arbitrary amount of memory.
+    let mut fields = Vec::with_capacity(count.min(32));
+    for _ in 0..count {
+        let key = read_string(body)?;
+        let value = read_string(body)?;
+        fields.push((String::from_utf8_lossy(&key).into_owned(), value));
+    }
+
+    Ok(S2sMessage { fields })
+}
+
+fn read_u32(body: &mut Bytes) -> Result<u32, DecodeError> {
+    if body.remaining() < 4 {
+        return Err(DecodeError::Truncated);
+    }
+    Ok(body.get_u32())
+}
+
+fn read_string(body: &mut Bytes) -> Result<Bytes, DecodeError> {
+    let len = read_u32(body)? as usize;
+    if body.remaining() < len {
+        return Err(DecodeError::Truncated);
+    }
+
+    let mut string = body.split_to(len);
+    if string.last() == Some(&0) {
+        string.truncate(len - 1);
+    }
+    Ok(string)
+}
+
+fn trim_nul(field: &[u8]) -> String {
+    let end = field.iter().position(|&b| b == 0).unwrap_or(field.len());
+    String::from_utf8_lossy(&field[..end]).into_owned()
+}
+
+/// Builds the signature block a forwarder sends when it connects.
+#[cfg(test)]
+pub(super) fn signature_block(server_name: &str, mgmt_port: &str) -> Vec<u8> {
+    let mut block = vec![0; SIGNATURE_BLOCK_LEN];
+    block[..SIGNATURE.len()].copy_from_slice(SIGNATURE);
+    block[SERVER_NAME_RANGE.start..][..server_name.len()].copy_from_slice(server_name.as_bytes());
+    block[MGMT_PORT_RANGE.start..][..mgmt_port.len()].copy_from_slice(mgmt_port.as_bytes());
+    block
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(fields: &[(&str, &str)]) -> S2sMessage {
+        S2sMessage {
+            fields: fields
+                .iter()
+                .map(|(key, value)| (key.to_string(), Bytes::from(value.to_string())))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn decodes_messages_after_signature() {
+        let first = message(&[("_raw", "hello"), ("MetaData:Host", "host::web01")]);
+        let second = message(&[("_done", "_done")]);
+
+        let mut src = BytesMut::from(&signature_block("uf01", "8089")[..]);
+        src.extend_from_slice(&first.encode());
+        src.extend_from_slice(&second.encode());
+
+        let mut decoder = S2sMessageDecoder::default();
+        let (decoded, size) = decoder.decode(&mut src).unwrap().unwrap();
+        assert_eq!(decoded, first);
+        assert_eq!(size, first.encode().len());
+        assert_eq!(decoded.get("_raw").unwrap(), "hello");
+
+        let (decoded, _) = decoder.decode(&mut src).unwrap().unwrap();
+        assert_eq!(decoded, second);
+        assert!(decoder.decode(&mut src).unwrap().is_none());
+    }
+
+    #[test]
+    fn waits_for_complete_message() {
+        let encoded = message(&[("_raw", "hello world")]).encode();
+
+        let mut src = BytesMut::from(&signature_block("uf01", "8089")[..]);
+        src.extend_from_slice(&encoded[..encoded.len() - 3]);
+
+        let mut decoder = S2sMessageDecoder::default();
+        assert!(decoder.decode(&mut src).unwrap().is_none());
+
+        src.extend_from_slice(&encoded[encoded.len() - 3..]);
+        let (decoded, _) = decoder.decode(&mut src).unwrap().unwrap();
+        assert_eq!(decoded.get("_raw").unwrap(), "hello world");
+    }
+
+    #[test]
+    fn rejects_invalid_signature() {
+        let mut src = BytesMut::from(&[b'x'; SIGNATURE_BLOCK_LEN][..]);
+
+        let error = S2sMessageDecoder::default().decode(&mut src).unwrap_err();
+        assert!(matches!(error, DecodeError::InvalidSignature));
+        assert!(!error.can_continue());
+    }
+
+    #[test]
+    fn skips_truncated_message() {
+        let mut truncated = BytesMut::new();
+        truncated.put_u32(8);
+        truncated.put_u32(1);
+        truncated.put_u32(100);
+        let valid = message(&[("_raw", "after")]).encode();
+
+        let mut src = BytesMut::from(&signature_block("uf01", "8089")[..]);
+        src.extend_from_slice(&truncated);
+        src.extend_from_slice(&valid);
+
+        let mut decoder = S2sMessageDecoder::default();
+        let error = decoder.decode(&mut src).unwrap_err();
+        assert!(matches!(error, DecodeError::Truncated));
+        assert!(error.can_continue());
+
+        let (decoded, _) = decoder.decode(&mut src).unwrap().unwrap();
+        assert_eq!(decoded.get("_raw").unwrap(), "after");
+    }
+}
@@ -41,7 +41,7 @@ mod unix;
 
 use parser::parse;
 #[cfg(unix)]
-use unix::{statsd_unix, UnixConfig};
+use unix::{statsd_unix, statsd_unix_datagram, UnixConfig};
 use vector_lib::config::LogNamespace;
 
 /// Configuration for the `statsd` source.
@@ -57,9 +57,13 @@ pub enum StatsdConfig {
     /// Listen on UDP.
     Udp(UdpConfig),
 
-    /// Listen on a Unix domain Socket (UDS).
+    /// Listen on a Unix domain stream socket (UDS).
     #[cfg(unix)]
     Unix(UnixConfig),
+
+    /// Listen on a Unix domain datagram socket, as used by DogStatsD clients.
+    #[cfg(unix)]
+    UnixDatagram(UnixConfig),
 }
 
 /// UDP configuration for the `statsd` source.
@@ -180,6 +184,10 @@ impl SourceConfig for StatsdConfig {
             }
             #[cfg(unix)]
             StatsdConfig::Unix(config) => statsd_unix(config.clone(), cx.shutdown, cx.out),
+            #[cfg(unix)]
+            StatsdConfig::UnixDatagram(config) => {
+                statsd_unix_datagram(config.clone(), cx.shutdown, cx.out)
+            }
         }
     }
 
@@ -192,7 +200,7 @@ impl SourceConfig for StatsdConfig {
             Self::Tcp(tcp) => vec![tcp.address.as_tcp_resource()],
             Self::Udp(udp) => vec![udp.address.as_udp_resource()],
             #[cfg(unix)]
-            Self::Unix(_) => vec![],
+            Self::Unix(_) | Self::UnixDatagram(_) => vec![],
         }
     }
 
@@ -454,6 +462,29 @@ mod test {
         .await;
     }
 
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_statsd_unix_datagram() {
+        assert_source_compliance(&SOCKET_PUSH_SOURCE_TAGS, async move {
+            let in_path = tempfile::tempdir()
+                .unwrap()
+                .into_path()
+                .join("unix_datagram_test");
+            let config = StatsdConfig::UnixDatagram(UnixConfig {
+                path: in_path.clone(),
+            });
+            let (sender, mut receiver) = mpsc::channel(200);
+            tokio::spawn(async move {
+                let socket = tokio::net::UnixDatagram::unbound().unwrap();
+                while let Some(bytes) = receiver.next().await {
+                    socket.send_to(bytes, &in_path).await.unwrap();
+                }
+            });
+            test_statsd(config, sender).await;
+        })
+        .await;
+    }
+
     async fn test_statsd(statsd_config: StatsdConfig, mut sender: mpsc::Sender<&'static [u8]>) {
         // Build our statsd source and then spawn it.  We use a big pipeline buffer because each
         // packet we send has a lot of metrics per packet.  We could technically count them all up
@@ -8,7 +8,7 @@ use once_cell::sync::Lazy;
 use regex::Regex;
 
 use crate::{
-    event::metric::{Metric, MetricKind, MetricTags, MetricValue, StatisticKind},
+    event::metric::{Metric, MetricKind, MetricTags, MetricValue, Sample, StatisticKind},
     sources::util::extract_tag_key_and_value,
 };
 
@@ -35,74 +35,91 @@ pub fn parse(packet: &str) -> Result<Metric, ParseError> {
     let name = sanitize_key(key);
     let metric_type = parts[1];
 
-    // sampling part is optional and comes after metric type part
-    let sampling = parts.get(2).filter(|s| s.starts_with('@'));
-    let sample_rate = if let Some(s) = sampling {
-        1.0 / sanitize_sampling(parse_sampling(s)?)
-    } else {
-        1.0
-    };
+    // The extensions following the metric type, such as sampling and tags, may come in any order.
+    let mut sample_rate = 1.0;
+    let mut tags = None;
+    for part in &parts[2..] {
+        if part.starts_with('@') {
+            sample_rate = 1.0 / sanitize_sampling(parse_sampling(part)?);
+        } else if part.starts_with('#') {
+            tags = Some(parse_tags(part)?);
+        }
+    }
 
-    // tags are optional and could be found either after sampling of after metric type part
-    let tags = if sampling.is_none() {
-        parts.get(2)
-    } else {
-        parts.get(3)
-    };
-    let tags = tags.filter(|s| s.starts_with('#'));
-    let tags = tags.map(parse_tags).transpose()?;
+    // DogStatsD allows packing multiple values of the same metric into one line, as in
+    // `foo:1:2:3|d`.
+    let values = parts[0].split(':');
 
     let metric = match metric_type {
         "c" => {
-            let val: f64 = parts[0].parse()?;
+            let value = values
+                .map(|value| value.parse::<f64>())
+                .sum::<Result<f64, _>>()?;
             Metric::new(
                 name,
                 MetricKind::Incremental,
                 MetricValue::Counter {
-                    value: val * sample_rate,
+                    value: value * sample_rate,
                 },
             )
             .with_tags(tags)
         }
         unit @ "h" | unit @ "ms" | unit @ "d" => {
-            let val: f64 = parts[0].parse()?;
+            // Sample counts are integers, so the rate is rounded to the nearest whole sample.
+            let rate = (sample_rate.round() as u32).max(1);
+            let samples = values
+                .map(|value| {
+                    value.parse().map(|value| Sample {
+                        value: convert_to_base_units(unit, value),
+                        rate,
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
             Metric::new(
-                name, MetricKind::Incremental, MetricValue::Distribution {
-                    samples: vector_lib::samples![convert_to_base_units(unit, val) => sample_rate as u32],
+                name,
+                MetricKind::Incremental,
+                MetricValue::Distribution {
+                    samples,
                     statistic: convert_to_statistic(unit),
                 },
-            ).with_tags(tags)
+            )
+            .with_tags(tags)
         }
         "g" => {
-            let value = if parts[0]
-                .chars()
-                .next()
-                .map(|c| c.is_ascii_digit())
-                .ok_or(ParseError::Malformed("empty first body component"))?
-            {
-                parts[0].parse()?
-            } else {
-                parts[0][1..].parse()?
-            };
-
-            match parse_direction(parts[0])? {
-                None => Metric::new(name, MetricKind::Absolute, MetricValue::Gauge { value })
-                    .with_tags(tags),
-                Some(sign) => Metric::new(
-                    name,
-                    MetricKind::Incremental,
-                    MetricValue::Gauge {
-                        value: value * sign,
-                    },
-                )
-                .with_tags(tags),
+            // Each value replaces the previous one, so sampling does not apply to gauges.
+            let mut absolute = None;
+            let mut delta = None;
+            for value in values {
+                match parse_direction(value)? {
+                    None => absolute = Some(value.parse::<f64>()?),
+                    Some(sign) => {
+                        *delta.get_or_insert(0.0) += sign * value[1..].parse::<f64>()?;
+                    }
+                }
+            }
+
+            match (absolute, delta) {
+                (Some(value), None) => {
+                    Metric::new(name, MetricKind::Absolute, MetricValue::Gauge { value })
+                        .with_tags(tags)
+                }
+                (None, Some(value)) => {
+                    Metric::new(name, MetricKind::Incremental, MetricValue::Gauge { value })
+                        .with_tags(tags)
+                }
+                _ => {
+                    return Err(ParseError::Malformed(
+                        "gauge values should either all be absolute or all be relative",
+                    ))
+                }
             }
         }
+        // Sets count unique values rather than occurrences, so sampling does not apply to them.
         "s" => Metric::new(
             name,
             MetricKind::Incremental,
             MetricValue::Set {
-                values: vec![parts[0].into()].into_iter().collect(),
+                values: values.map(Into::into).collect(),
             },
         )
         .with_tags(tags),
@@ -394,6 +411,130 @@ mod test {
         );
     }
 
+    #[test]
+    fn sampling_after_tags() {
+        assert_event_data_eq!(
+            parse("bar:2|c|#region:us-west1|@0.5"),
+            Ok(Metric::new(
+                "bar",
+                MetricKind::Incremental,
+                MetricValue::Counter { value: 4.0 },
+            )
+            .with_tags(Some(metric_tags!("region" => "us-west1")))),
+        );
+    }
+
+    #[test]
+    fn fractional_sample_rate_is_rounded() {
+        assert_event_data_eq!(
+            parse("glork:320|h|@0.3"),
+            Ok(Metric::new(
+                "glork",
+                MetricKind::Incremental,
+                MetricValue::Distribution {
+                    samples: vector_lib::samples![320.0 => 3],
+                    statistic: StatisticKind::Histogram
+                },
+            )),
+        );
+        assert_event_data_eq!(
+            parse("glork:320|h|@0.9"),
+            Ok(Metric::new(
+                "glork",
+                MetricKind::Incremental,
+                MetricValue::Distribution {
+                    samples: vector_lib::samples![320.0 => 1],
+                    statistic: StatisticKind::Histogram
+                },
+            )),
+        );
+    }
+
+    #[test]
+    fn sampled_gauge_and_set_are_not_scaled() {
+        assert_event_data_eq!(
+            parse("gaugor:333|g|@0.1"),
+            Ok(Metric::new(
+                "gaugor",
+                MetricKind::Absolute,
+                MetricValue::Gauge { value: 333.0 },
+            )),
+        );
+        assert_event_data_eq!(
+            parse("uniques:765|s|@0.1"),
+            Ok(Metric::new(
+                "uniques",
+                MetricKind::Incremental,
+                MetricValue::Set {
+                    values: vec!["765".into()].into_iter().collect()
+                },
+            )),
+        );
+    }
+
+    #[test]
+    fn packed_counter() {
+        assert_event_data_eq!(
+            parse("foo:1:2:3|c|@0.5"),
+            Ok(Metric::new(
+                "foo",
+                MetricKind::Incremental,
+                MetricValue::Counter { value: 12.0 },
+            )),
+        );
+    }
+
+    #[test]
+    fn packed_distribution() {
+        assert_event_data_eq!(
+            parse("glork:320:100|ms|@0.1|#production"),
+            Ok(Metric::new(
+                "glork",
+                MetricKind::Incremental,
+                MetricValue::Distribution {
+                    samples: vector_lib::samples![0.320 => 10, 0.1 => 10],
+                    statistic: StatisticKind::Histogram
+                },
+            )
+            .with_tags(Some(metric_tags!("production" => TagValue::Bare)))),
+        );
+    }
+
+    #[test]
+    fn packed_gauges() {
+        assert_event_data_eq!(
+            parse("gaugor:1:333|g"),
+            Ok(Metric::new(
+                "gaugor",
+                MetricKind::Absolute,
+                MetricValue::Gauge { value: 333.0 },
+            )),
+        );
+        assert_event_data_eq!(
+            parse("gaugor:+10:-4|g"),
+            Ok(Metric::new(
+                "gaugor",
+                MetricKind::Incremental,
+                MetricValue::Gauge { value: 6.0 },
+            )),
+        );
+        assert!(parse("gaugor:10:-4|g").is_err());
+    }
+
+    #[test]
+    fn packed_set() {
+        assert_event_data_eq!(
+            parse("uniques:765:766|s"),
+            Ok(Metric::new(
+                "uniques",
+                MetricKind::Incremental,
+                MetricValue::Set {
+                    values: vec!["765".into(), "766".into()].into_iter().collect()
+                },
+            )),
+        );
+    }
+
     #[test]
     fn sanitizing_keys() {
         assert_eq!("foo-bar-baz", sanitize_key("foo/bar/baz"));
@@ -10,7 +10,10 @@ use super::StatsdDeserializer;
 use crate::{
     codecs::Decoder,
     shutdown::ShutdownSignal,
-    sources::{util::build_unix_stream_source, Source},
+    sources::{
+        util::{build_unix_datagram_source, build_unix_stream_source},
+        Source,
+    },
     SourceSender,
 };
 
@@ -44,3 +47,25 @@ pub fn statsd_unix(
         out,
     )
 }
+
+pub fn statsd_unix_datagram(
+    config: UnixConfig,
+    shutdown: ShutdownSignal,
+    out: SourceSender,
+) -> crate::Result<Source> {
+    // Each datagram may carry several newline separated metrics.
+    let decoder = Decoder::new(
+        Framer::NewlineDelimited(NewlineDelimitedDecoder::new()),
+        Deserializer::Boxed(Box::new(StatsdDeserializer::unix())),
+    );
+
+    build_unix_datagram_source(
+        config.path,
+        None,
+        crate::serde::default_max_length(),
+        decoder,
+        |_events, _host| {},
+        shutdown,
+        out,
+    )
+}
@@ -3,15 +3,16 @@ use std::path::PathBuf;
 use std::{net::SocketAddr, time::Duration};
 use vector_lib::ipallowlist::IpAllowlistConfig;
 
-use bytes::Bytes;
+use bytes::{Bytes, BytesMut};
 use chrono::Utc;
 use futures::StreamExt;
 use listenfd::ListenFd;
 use smallvec::SmallVec;
+use snafu::Snafu;
 use tokio_util::udp::UdpFramed;
 use vector_lib::codecs::{
-    decoding::{Deserializer, Framer},
-    BytesDecoder, OctetCountingDecoder, SyslogDeserializerConfig,
+    decoding::{BoxedFramingError, Deserializer, Framer},
+    BytesDecoder, NewlineDelimitedDecoder, OctetCountingDecoder, SyslogDeserializerConfig,
 };
 use vector_lib::config::{LegacyKey, LogNamespace};
 use vector_lib::configurable::configurable_component;
@@ -32,10 +33,18 @@ use crate::{
     shutdown::ShutdownSignal,
     sources::util::net::{try_bind_udp_socket, SocketListenAddr, TcpNullAcker, TcpSource},
     tcp::TcpKeepaliveConfig,
-    tls::{MaybeTlsSettings, TlsSourceConfig},
+    tls::{CertificateMetadata, MaybeTlsSettings, TlsSourceConfig},
     SourceSender,
 };
 
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display(
+        "`permitted_peers` requires `tls.enabled` and `tls.verify_certificate` to be set"
+    ))]
+    PermittedPeersWithoutVerification,
+}
+
 /// Configuration for the `syslog` source.
 #[configurable_component(source("syslog", "Collect logs sent via Syslog."))]
 #[derive(Clone, Debug)]
@@ -87,6 +96,18 @@ pub enum Mode {
         #[configurable(derived)]
         tls: Option<TlsSourceConfig>,
 
+        /// The common names of the client certificates permitted to connect.
+        ///
+        /// Connections presenting a certificate with any other common name are closed before any
+        /// message is read. Requires `tls.verify_certificate` to be enabled, so that the presented
+        /// certificates are known to be issued by a trusted CA.
+        #[configurable(metadata(docs::examples = "relay-1.example.com"))]
+        permitted_peers: Option<Vec<String>>,
+
+        #[configurable(derived)]
+        #[serde(default)]
+        framing: SyslogFraming,
+
         /// The size of the receive buffer used for each connection.
         ///
         /// This should not typically needed to be changed.
@@ -116,8 +137,11 @@ pub enum Mode {
     Unix {
         /// The Unix socket path.
         ///
-        /// This should be an absolute path.
+        /// This should be an absolute path, or `systemd{#N}` to use the Nth socket passed by
+        /// systemd socket activation. The socket file of a systemd-provided socket is managed by
+        /// systemd, so `socket_file_mode` has no effect on it.
         #[configurable(metadata(docs::examples = "/path/to/socket"))]
+        #[configurable(metadata(docs::examples = "systemd"))]
         path: PathBuf,
 
         /// Unix file mode bits to be applied to the unix socket file as its designated file permissions.
@@ -128,6 +152,91 @@ pub enum Mode {
     },
 }
 
+/// The framing of messages received over TCP, as described in [RFC 6587][rfc6587].
+///
+/// [rfc6587]: https://datatracker.ietf.org/doc/html/rfc6587
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SyslogFraming {
+    /// Detects the framing of each connection from its first message.
+    ///
+    /// Connections whose first message starts with a non-zero digit are read as octet counted,
+    /// and all others as newline delimited. This allows relays using either framing to send to
+    /// the same port.
+    #[default]
+    Auto,
+
+    /// Messages are prefixed with their length in bytes.
+    ///
+    /// Frames that do not start with a length are read up to the next newline.
+    OctetCounting,
+
+    /// Messages are terminated by a newline.
+    NewlineDelimited,
+}
+
+impl SyslogFraming {
+    fn framer(self, max_length: usize) -> Framer {
+        let framer = match self {
+            Self::Auto => SyslogFramer::Detecting { max_length },
+            Self::OctetCounting => {
+                SyslogFramer::OctetCounting(OctetCountingDecoder::new_with_max_length(max_length))
+            }
+            Self::NewlineDelimited => SyslogFramer::NewlineDelimited(
+                NewlineDelimitedDecoder::new_with_max_length(max_length),
+            ),
+        };
+        Framer::Boxed(Box::new(framer))
+    }
+}
+
+/// A framer for a single connection, detecting its framing when configured to.
+#[derive(Clone, Debug)]
+enum SyslogFramer {
+    /// No data has been received on the connection yet.
+    Detecting {
+        max_length: usize,
+    },
+    OctetCounting(OctetCountingDecoder),
+    NewlineDelimited(NewlineDelimitedDecoder),
+}
+
+impl SyslogFramer {
+    fn detect(&mut self, src: &BytesMut) {
+        if let (Self::Detecting { max_length }, Some(first_byte)) = (&*self, src.first()) {
+            *self = if (b'1'..=b'9').contains(first_byte) {
+                Self::OctetCounting(OctetCountingDecoder::new_with_max_length(*max_length))
+            } else {
+                Self::NewlineDelimited(NewlineDelimitedDecoder::new_with_max_length(*max_length))
+            };
+        }
+    }
+}
+
+impl tokio_util::codec::Decoder for SyslogFramer {
+    type Item = Bytes;
+    type Error = BoxedFramingError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        self.detect(src);
+        match self {
+            Self::Detecting { .. } => Ok(None),
+            Self::OctetCounting(framer) => framer.decode(src),
+            Self::NewlineDelimited(framer) => framer.decode(src),
+        }
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        self.detect(src);
+        match self {
+            Self::Detecting { .. } => Ok(None),
+            Self::OctetCounting(framer) => framer.decode_eof(src),
+            Self::NewlineDelimited(framer) => framer.decode_eof(src),
+        }
+    }
+}
+
 impl SyslogConfig {
     #[cfg(test)]
     pub fn from_mode(mode: Mode) -> Self {
@@ -148,6 +257,8 @@ impl Default for SyslogConfig {
                 keepalive: None,
                 permit_origin: None,
                 tls: None,
+                permitted_peers: None,
+                framing: SyslogFraming::Auto,
                 receive_buffer_bytes: None,
                 connection_limit: None,
             },
@@ -181,16 +292,27 @@ impl SourceConfig for SyslogConfig {
                 keepalive,
                 permit_origin,
                 tls,
+                permitted_peers,
+                framing,
                 receive_buffer_bytes,
                 connection_limit,
             } => {
+                let tls_config = tls.as_ref().map(|tls| tls.tls_config.clone());
+                let verifies_certificates = tls_config.as_ref().is_some_and(|tls| {
+                    tls.enabled == Some(true) && tls.options.verify_certificate == Some(true)
+                });
+                if permitted_peers.is_some() && !verifies_certificates {
+                    return Err(BuildError::PermittedPeersWithoutVerification.into());
+                }
+
                 let source = SyslogTcpSource {
                     max_length: self.max_length,
                     host_key,
+                    framing,
+                    permitted_peers,
                     log_namespace,
                 };
                 let shutdown_secs = Duration::from_secs(30);
-                let tls_config = tls.as_ref().map(|tls| tls.tls_config.clone());
                 let tls_client_metadata_key = tls
                     .as_ref()
                     .and_then(|tls| tls.client_metadata_key.clone())
@@ -277,6 +399,8 @@ impl SourceConfig for SyslogConfig {
 struct SyslogTcpSource {
     max_length: usize,
     host_key: Option<OwnedValuePath>,
+    framing: SyslogFraming,
+    permitted_peers: Option<Vec<String>>,
     log_namespace: LogNamespace,
 }
 
@@ -288,11 +412,19 @@ impl TcpSource for SyslogTcpSource {
 
     fn decoder(&self) -> Self::Decoder {
         Decoder::new(
-            Framer::OctetCounting(OctetCountingDecoder::new_with_max_length(self.max_length)),
+            self.framing.framer(self.max_length),
             Deserializer::Syslog(SyslogDeserializerConfig::from_source(SyslogConfig::NAME).build()),
         )
     }
 
+    fn permits_peer(&self, certificate: Option<&CertificateMetadata>) -> bool {
+        self.permitted_peers.as_ref().map_or(true, |peers| {
+            certificate
+                .and_then(|certificate| certificate.common_name.as_ref())
+                .is_some_and(|common_name| peers.contains(common_name))
+        })
+    }
+
     fn handle_events(&self, events: &mut [Event], host: SocketAddr) {
         handle_events(
             events,
@@ -720,6 +852,99 @@ mod test {
         assert_eq!(keepalive.time_secs, Some(7200));
     }
 
+    #[test]
+    fn config_tcp_framing() {
+        let config: SyslogConfig = toml::from_str(
+            r#"
+            mode = "tcp"
+            address = "127.0.0.1:1235"
+            framing = "newline_delimited"
+          "#,
+        )
+        .unwrap();
+
+        let framing = match config.mode {
+            Mode::Tcp { framing, .. } => framing,
+            _ => panic!("expected Mode::Tcp"),
+        };
+
+        assert_eq!(framing, SyslogFraming::NewlineDelimited);
+    }
+
+    #[tokio::test]
+    async fn permitted_peers_require_certificate_verification() {
+        let config: SyslogConfig = toml::from_str(
+            r#"
+            mode = "tcp"
+            address = "127.0.0.1:1235"
+            permitted_peers = ["relay-1.example.com"]
+          "#,
+        )
+        .unwrap();
+
+        assert!(config
+            .build(SourceContext::new_test(SourceSender::new_test().0, None))
+            .await
+            .is_err());
+    }
+
+    #[test]
+    fn permits_peers_by_common_name() {
+        let source = SyslogTcpSource {
+            max_length: crate::serde::default_max_length(),
+            host_key: None,
+            framing: SyslogFraming::Auto,
+            permitted_peers: Some(vec!["relay-1.example.com".to_owned()]),
+            log_namespace: LogNamespace::Legacy,
+        };
+        let certificate = |common_name: &str| CertificateMetadata {
+            country_name: None,
+            state_or_province_name: None,
+            locality_name: None,
+            organization_name: None,
+            organizational_unit_name: None,
+            common_name: Some(common_name.to_owned()),
+        };
+
+        assert!(source.permits_peer(Some(&certificate("relay-1.example.com"))));
+        assert!(!source.permits_peer(Some(&certificate("relay-2.example.com"))));
+        assert!(!source.permits_peer(None));
+    }
+
+    fn decode_frames(framing: SyslogFraming, input: &str) -> Vec<Bytes> {
+        use tokio_util::codec::Decoder as _;
+
+        let mut framer = framing.framer(crate::serde::default_max_length());
+        let mut buffer = BytesMut::from(input);
+        let mut frames = Vec::new();
+        while let Some(frame) = framer.decode_eof(&mut buffer).unwrap() {
+            frames.push(frame);
+        }
+        frames
+    }
+
+    #[test]
+    fn detects_framing_per_connection() {
+        assert_eq!(
+            decode_frames(SyslogFraming::Auto, "5 <13>a6 <13>bc"),
+            vec![Bytes::from("<13>a"), Bytes::from("<13>bc")]
+        );
+        // Once a connection is detected as newline delimited, lines starting with a digit are not
+        // read as octet counted.
+        assert_eq!(
+            decode_frames(SyslogFraming::Auto, "<13>a\n5 <13>b\n"),
+            vec![Bytes::from("<13>a"), Bytes::from("5 <13>b")]
+        );
+        assert_eq!(
+            decode_frames(SyslogFraming::OctetCounting, "<13>a\n5 <13>b"),
+            vec![Bytes::from("<13>a"), Bytes::from("<13>b")]
+        );
+        assert_eq!(
+            decode_frames(SyslogFraming::NewlineDelimited, "5 <13>a\n"),
+            vec![Bytes::from("5 <13>a")]
+        );
+    }
+
     #[test]
     fn config_udp() {
         let config: SyslogConfig = toml::from_str(
@@ -1125,6 +1350,8 @@ mod test {
                 permit_origin: None,
                 keepalive: None,
                 tls: None,
+                permitted_peers: None,
+                framing: SyslogFraming::Auto,
                 receive_buffer_bytes: None,
                 connection_limit: None,
             });
@@ -1269,6 +1496,8 @@ mod test {
                 permit_origin: None,
                 keepalive: None,
                 tls: None,
+                permitted_peers: None,
+                framing: SyslogFraming::Auto,
                 receive_buffer_bytes: None,
                 connection_limit: None,
             });
@@ -9,7 +9,7 @@ use hyper::Body;
 use std::{convert::Infallible, net::SocketAddr, time::Duration};
 use tonic::{
     body::BoxBody,
-    transport::server::{NamedService, Server},
+    transport::server::{NamedService, Routes, Server},
 };
 use tower::Service;
 use tower_http::{
@@ -63,6 +63,33 @@ where
     Ok(())
 }
 
+/// Runs a gRPC server serving several services on the same address.
+pub async fn run_grpc_server_with_routes(
+    address: SocketAddr,
+    tls_settings: MaybeTlsSettings,
+    routes: Routes,
+    shutdown: ShutdownSignal,
+) -> crate::Result<()> {
+    let span = Span::current();
+    let (tx, rx) = tokio::sync::oneshot::channel::<ShutdownSignalToken>();
+    let listener = tls_settings.bind(&address).await?;
+    let stream = listener.accept_stream();
+
+    info!(%address, "Building gRPC server.");
+
+    Server::builder()
+        .layer(build_grpc_trace_layer(span.clone()))
+        // See `run_grpc_server` for why decompression is handled by a layer of its own.
+        .layer(DecompressionAndMetricsLayer)
+        .add_routes(routes)
+        .serve_with_incoming_shutdown(stream, shutdown.map(|token| tx.send(token).unwrap()))
+        .await?;
+
+    drop(rx.await);
+
+    Ok(())
+}
+
 /// Builds a [TraceLayer] configured for a gRPC server.
 ///
 /// This layer emits gPRC specific telemetry for messages received/sent and handler duration.
@@ -9,11 +9,12 @@
 //!     context.
 
 use bytes::Bytes;
-use futures_util::{stream, FutureExt, StreamExt, TryFutureExt};
+use futures_util::{stream, StreamExt};
 use http::{response::Parts, Uri};
 use hyper::{Body, Request};
 use std::time::Duration;
-use std::{collections::HashMap, future::ready};
+use std::{collections::HashMap, sync::Arc};
+use tokio::sync::watch;
 use tokio_stream::wrappers::IntervalStream;
 use vector_lib::json_size::JsonSize;
 
@@ -32,8 +33,8 @@ use vector_lib::{config::proxy::ProxyConfig, event::Event, EstimatedJsonEncodedS
 
 /// Contains the inputs generic to any http client.
 pub(crate) struct GenericHttpClientInputs {
-    /// Array of URLs to call.
-    pub urls: Vec<Uri>,
+    /// URLs to call, re-read before every round of calls.
+    pub urls: watch::Receiver<Vec<Uri>>,
     /// Interval between calls.
     pub interval: Duration,
     /// Timeout for the HTTP request.
@@ -48,6 +49,11 @@ pub(crate) struct GenericHttpClientInputs {
     pub shutdown: ShutdownSignal,
 }
 
+/// Wraps a fixed list of URLs for `GenericHttpClientInputs`.
+pub(crate) fn static_urls(urls: Vec<Uri>) -> watch::Receiver<Vec<Uri>> {
+    watch::channel(urls).1
+}
+
 /// The default interval to call the HTTP endpoint if none is configured.
 pub(crate) const fn default_interval() -> Duration {
     Duration::from_secs(15)
@@ -75,6 +81,12 @@ pub(crate) trait HttpClientContext {
     /// (Optional) Called if the HTTP response is not 200 ('OK').
     fn on_http_response_error(&self, _uri: &Uri, _header: &Parts) {}
 
+    /// (Optional) Called after `on_response` returns events, to find the URL of the next page of
+    /// the response. The next page is requested with the same context, until `None` is returned.
+    fn next_page(&mut self, _url: &Uri, _header: &Parts, _body: &Bytes) -> Option<Uri> {
+        None
+    }
+
     // This function can be defined to enrich events with additional HTTP
     // metadata. This function should be used rather than internal enrichment so
     // that accurate byte count metrics can be emitted.
@@ -119,9 +131,82 @@ pub(crate) fn warn_if_interval_too_low(timeout: Duration, interval: Duration) {
     }
 }
 
+/// The options of the HTTP requests, shared by all the calls.
+struct RequestOptions {
+    http_method: HttpMethod,
+    headers: HashMap<String, Vec<String>>,
+    content_type: String,
+    auth: Option<Auth>,
+    timeout: Duration,
+}
+
+impl RequestOptions {
+    fn build(&self, url: &Uri) -> Request<Body> {
+        let mut builder = match self.http_method {
+            HttpMethod::Head => Request::head(url),
+            HttpMethod::Get => Request::get(url),
+            HttpMethod::Post => Request::post(url),
+            HttpMethod::Put => Request::put(url),
+            HttpMethod::Patch => Request::patch(url),
+            HttpMethod::Delete => Request::delete(url),
+        };
+
+        // add user specified headers
+        for (header, values) in &self.headers {
+            for value in values {
+                builder = builder.header(header, value);
+            }
+        }
+
+        // set ACCEPT header if not user specified
+        if !self.headers.contains_key(http::header::ACCEPT.as_str()) {
+            builder = builder.header(http::header::ACCEPT, &self.content_type);
+        }
+
+        // building an empty request should be infallible
+        let mut request = builder.body(Body::empty()).expect("error creating request");
+
+        if let Some(auth) = &self.auth {
+            auth.apply(&mut request);
+        }
+
+        request
+    }
+}
+
+/// Calls `url`, returning the headers and the body of the response.
+async fn fetch(
+    client: &HttpClient,
+    options: &RequestOptions,
+    url: &Uri,
+) -> crate::Result<(Parts, Bytes)> {
+    let response =
+        match tokio::time::timeout(options.timeout, client.send(options.build(url))).await {
+            Ok(Ok(response)) => response,
+            Ok(Err(error)) => return Err(error.into()),
+            Err(_) => {
+                return Err(format!(
+                    "Timeout error: request exceeded {}s",
+                    options.timeout.as_secs_f64()
+                )
+                .into())
+            }
+        };
+
+    let (header, body) = response.into_parts();
+    let body = hyper::body::to_bytes(body).await?;
+    emit!(EndpointBytesReceived {
+        byte_size: body.len(),
+        protocol: "http",
+        endpoint: url.to_string().as_str(),
+    });
+    Ok((header, body))
+}
+
 /// Calls one or more urls at an interval.
 ///   - The HTTP request is built per the options in provided generic inputs.
 ///   - The HTTP response is decoded/parsed into events by the specific context.
+///   - The next pages of the response, if any, are requested one after the other.
 ///   - The events are then sent to the output stream.
 pub(crate) async fn call<
     B: HttpClientBuilder<Context = C> + Send + Clone,
@@ -136,72 +221,33 @@ pub(crate) async fn call<
     // proxy and tls settings.
     let client =
         HttpClient::new(inputs.tls.clone(), &inputs.proxy).expect("Building HTTP client failed");
+    let options = Arc::new(RequestOptions {
+        http_method,
+        headers: inputs.headers,
+        content_type: inputs.content_type,
+        auth: inputs.auth,
+        timeout: inputs.timeout,
+    });
+    let urls = inputs.urls;
     let mut stream = IntervalStream::new(tokio::time::interval(inputs.interval))
         .take_until(inputs.shutdown)
-        .map(move |_| stream::iter(inputs.urls.clone()))
+        .map(move |_| stream::iter(urls.borrow().clone()))
         .flatten()
         .map(move |url| {
             let client = client.clone();
-            let endpoint = url.to_string();
-
-            let context_builder = context_builder.clone();
-            let mut context = context_builder.build(&url);
-
-            let mut builder = match http_method {
-                HttpMethod::Head => Request::head(&url),
-                HttpMethod::Get => Request::get(&url),
-                HttpMethod::Post => Request::post(&url),
-                HttpMethod::Put => Request::put(&url),
-                HttpMethod::Patch => Request::patch(&url),
-                HttpMethod::Delete => Request::delete(&url),
-            };
-
-            // add user specified headers
-            for (header, values) in &inputs.headers {
-                for value in values {
-                    builder = builder.header(header, value);
-                }
-            }
-
-            // set ACCEPT header if not user specified
-            if !inputs.headers.contains_key(http::header::ACCEPT.as_str()) {
-                builder = builder.header(http::header::ACCEPT, &inputs.content_type);
-            }
-
-            // building an empty request should be infallible
-            let mut request = builder.body(Body::empty()).expect("error creating request");
+            let options = Arc::clone(&options);
+            let context = context_builder.build(&url);
 
-            if let Some(auth) = &inputs.auth {
-                auth.apply(&mut request);
-            }
-
-            tokio::time::timeout(inputs.timeout, client.send(request))
-                .then(move |result| async move {
-                    match result {
-                        Ok(Ok(response)) => Ok(response),
-                        Ok(Err(error)) => Err(error.into()),
-                        Err(_) => Err(format!(
-                            "Timeout error: request exceeded {}s",
-                            inputs.timeout.as_secs_f64()
-                        )
-                        .into()),
-                    }
-                })
-                .and_then(|response| async move {
-                    let (header, body) = response.into_parts();
-                    let body = hyper::body::to_bytes(body).await?;
-                    emit!(EndpointBytesReceived {
-                        byte_size: body.len(),
-                        protocol: "http",
-                        endpoint: endpoint.as_str(),
-                    });
-                    Ok((header, body))
-                })
-                .into_stream()
-                .filter_map(move |response| {
-                    ready(match response {
-                        Ok((header, body)) if header.status == hyper::StatusCode::OK => {
-                            context.on_response(&url, &header, &body).map(|mut events| {
+            stream::unfold((Some(url), context), move |(url, mut context)| {
+                let client = client.clone();
+                let options = Arc::clone(&options);
+                async move {
+                    let url = url?;
+                    let mut next_url = None;
+                    let events = match fetch(&client, &options, &url).await {
+                        Ok((header, body)) if header.status == hyper::StatusCode::OK => context
+                            .on_response(&url, &header, &body)
+                            .map(|mut events| {
                                 let byte_size = if events.is_empty() {
                                     // We need to explicitly set the byte size
                                     // to 0 since
@@ -226,28 +272,31 @@ pub(crate) async fn call<
                                 // that the byte sizes are accurate.
                                 context.enrich_events(&mut events);
 
-                                stream::iter(events)
+                                next_url = context.next_page(&url, &header, &body);
+                                events
                             })
-                        }
+                            .unwrap_or_default(),
                         Ok((header, _)) => {
                             context.on_http_response_error(&url, &header);
                             emit!(HttpClientHttpResponseError {
                                 code: header.status,
                                 url: url.to_string(),
                             });
-                            None
+                            Vec::new()
                         }
                         Err(error) => {
                             emit!(HttpClientHttpError {
                                 error,
                                 url: url.to_string()
                             });
-                            None
+                            Vec::new()
                         }
-                    })
-                })
-                .flatten()
-                .boxed()
+                    };
+                    Some((stream::iter(events), (next_url, context)))
+                }
+            })
+            .flatten()
+            .boxed()
         })
         .flatten_unordered(None)
         .boxed();
@@ -0,0 +1,275 @@
+use std::io::{self, Write};
+
+use bytes::{buf::Writer, BufMut, BytesMut};
+use flate2::write::GzDecoder;
+use tokio_util::codec::Decoder;
+use vector_lib::configurable::configurable_component;
+
+const GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+const ZSTD_MAGIC: &[u8] = &[0x28, 0xb5, 0x2f, 0xfd];
+
+/// The decompression applied to the data received over each connection.
+///
+/// The data of a connection is decompressed as a single stream, as it arrives.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TcpDecompression {
+    /// Data is not decompressed.
+    #[default]
+    None,
+
+    /// The compression of each connection is detected from the first bytes it receives.
+    ///
+    /// Connections whose data doesn't start with a gzip or zstd header are read as-is, so that
+    /// compressing and non-compressing senders can share the same listener.
+    Auto,
+
+    /// Data is decompressed with [gzip][gzip].
+    ///
+    /// [gzip]: https://www.gzip.org/
+    Gzip,
+
+    /// Data is decompressed with [zstd][zstd].
+    ///
+    /// [zstd]: https://facebook.github.io/zstd/
+    Zstd,
+}
+
+#[derive(Clone, Copy)]
+enum Algorithm {
+    Gzip,
+    Zstd,
+}
+
+/// A decompressor writing the decompressed data into a buffer.
+enum Decompressor {
+    Gzip(GzDecoder<Writer<BytesMut>>),
+    Zstd(zstd::stream::write::Decoder<'static, Writer<BytesMut>>),
+}
+
+impl Decompressor {
+    fn new(algorithm: Algorithm) -> io::Result<Self> {
+        let buffer = BytesMut::new().writer();
+        Ok(match algorithm {
+            Algorithm::Gzip => Self::Gzip(GzDecoder::new(buffer)),
+            Algorithm::Zstd => Self::Zstd(zstd::stream::write::Decoder::new(buffer)?),
+        })
+    }
+
+    fn write(&mut self, data: &[u8]) -> io::Result<()> {
+        // Flushing makes the decompressors hand over all the data they decompressed so far.
+        match self {
+            Self::Gzip(decoder) => decoder.write_all(data).and_then(|()| decoder.flush()),
+            Self::Zstd(decoder) => decoder.write_all(data).and_then(|()| decoder.flush()),
+        }
+    }
+
+    /// The decompressed data that hasn't been decoded yet.
+    fn output(&mut self) -> &mut BytesMut {
+        match self {
+            Self::Gzip(decoder) => decoder.get_mut().get_mut(),
+            Self::Zstd(decoder) => decoder.get_mut().get_mut(),
+        }
+    }
+}
+
+enum State {
+    /// Waiting for enough data to detect the compression of the stream.
+    Detect,
+    Uncompressed,
+    Compressed(Algorithm),
+    Decompressing(Decompressor),
+}
+
+impl State {
+    const fn new(decompression: TcpDecompression) -> Self {
+        match decompression {
+            TcpDecompression::None => Self::Uncompressed,
+            TcpDecompression::Auto => Self::Detect,
+            TcpDecompression::Gzip => Self::Compressed(Algorithm::Gzip),
+            TcpDecompression::Zstd => Self::Compressed(Algorithm::Zstd),
+        }
+    }
+
+    /// Detects the compression of a stream starting with `data`, or returns `None` if more data is
+    /// needed to tell.
+    fn detect(data: &[u8]) -> Option<Self> {
+        if data.starts_with(GZIP_MAGIC) {
+            Some(Self::Compressed(Algorithm::Gzip))
+        } else if data.starts_with(ZSTD_MAGIC) {
+            Some(Self::Compressed(Algorithm::Zstd))
+        } else if GZIP_MAGIC.starts_with(data) || ZSTD_MAGIC.starts_with(data) {
+            None
+        } else {
+            Some(Self::Uncompressed)
+        }
+    }
+}
+
+/// A decoder decompressing the stream of a connection before handing it to the inner decoder.
+///
+/// The decompressor is created for the first bytes of the stream, so a new decoder must be used for
+/// each connection.
+pub struct DecompressingDecoder<D> {
+    inner: D,
+    state: State,
+}
+
+impl<D> DecompressingDecoder<D>
+where
+    D: Decoder,
+    D::Error: From<io::Error>,
+{
+    pub const fn new(inner: D, decompression: TcpDecompression) -> Self {
+        Self {
+            inner,
+            state: State::new(decompression),
+        }
+    }
+
+    fn decode_stream(
+        &mut self,
+        src: &mut BytesMut,
+        eof: bool,
+    ) -> Result<Option<D::Item>, D::Error> {
+        loop {
+            match &mut self.state {
+                State::Detect => match State::detect(src) {
+                    Some(state) => self.state = state,
+                    None if eof => self.state = State::Uncompressed,
+                    None => return Ok(None),
+                },
+                State::Uncompressed => {
+                    return if eof {
+                        self.inner.decode_eof(src)
+                    } else {
+                        self.inner.decode(src)
+                    };
+                }
+                State::Compressed(algorithm) => {
+                    let decompressor = Decompressor::new(*algorithm)?;
+                    self.state = State::Decompressing(decompressor);
+                }
+                State::Decompressing(decompressor) => {
+                    if !src.is_empty() {
+                        decompressor.write(src)?;
+                        src.clear();
+                    }
+
+                    let output = decompressor.output();
+                    return if eof {
+                        self.inner.decode_eof(output)
+                    } else {
+                        self.inner.decode(output)
+                    };
+                }
+            }
+        }
+    }
+}
+
+impl<D> Decoder for DecompressingDecoder<D>
+where
+    D: Decoder,
+    D::Error: From<io::Error>,
+{
+    type Item = D::Item;
+    type Error = D::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        self.decode_stream(src, false)
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        self.decode_stream(src, true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use flate2::write::GzEncoder;
+    use vector_lib::codecs::NewlineDelimitedDecoder;
+
+    use super::*;
+
+    fn decode_all(decompression: TcpDecompression, data: &[u8]) -> Vec<String> {
+        let mut decoder = DecompressingDecoder::new(NewlineDelimitedDecoder::new(), decompression);
+        let mut frames = Vec::new();
+
+        // Feed the data in small pieces, the way it arrives from a socket.
+        let mut src = BytesMut::new();
+        for chunk in data.chunks(3) {
+            src.extend_from_slice(chunk);
+            while let Some(frame) = decoder.decode(&mut src).unwrap() {
+                frames.push(String::from_utf8(frame.to_vec()).unwrap());
+            }
+        }
+        while let Some(frame) = decoder.decode_eof(&mut src).unwrap() {
+            frames.push(String::from_utf8(frame.to_vec()).unwrap());
+        }
+        frames
+    }
+
+    fn gzip(data: &[u8]) -> Vec<u8> {
+        let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(data).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    fn zstd(data: &[u8]) -> Vec<u8> {
+        zstd::encode_all(data, zstd::DEFAULT_COMPRESSION_LEVEL).unwrap()
+    }
+
+    #[test]
+    fn decodes_uncompressed_streams() {
+        let data = b"foo\nbar\n";
+
+        assert_eq!(decode_all(TcpDecompression::None, data), ["foo", "bar"]);
+        assert_eq!(decode_all(TcpDecompression::Auto, data), ["foo", "bar"]);
+        assert_eq!(decode_all(TcpDecompression::Auto, b"\x1f"), ["\x1f"]);
+    }
+
+    #[test]
+    fn decodes_compressed_streams() {
+        let data = b"foo\nbar\n";
+
+        assert_eq!(
+            decode_all(TcpDecompression::Gzip, &gzip(data)),
+            ["foo", "bar"]
+        );
+        assert_eq!(
+            decode_all(TcpDecompression::Zstd, &zstd(data)),
+            ["foo", "bar"]
+        );
+        assert_eq!(
+            decode_all(TcpDecompression::Auto, &gzip(data)),
+            ["foo", "bar"]
+        );
+        assert_eq!(
+            decode_all(TcpDecompression::Auto, &zstd(data)),
+            ["foo", "bar"]
+        );
+    }
+
+    #[test]
+    fn decodes_unfinished_compressed_streams() {
+        let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(b"foo\nbar\n").unwrap();
+        encoder.flush().unwrap();
+
+        assert_eq!(
+            decode_all(TcpDecompression::Gzip, encoder.get_ref()),
+            ["foo", "bar"]
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_compressed_streams() {
+        let mut decoder =
+            DecompressingDecoder::new(NewlineDelimitedDecoder::new(), TcpDecompression::Gzip);
+
+        let mut src = BytesMut::from("foo\nbar\n");
+        assert!(decoder.decode(&mut src).is_err());
+    }
+}
@@ -1,3 +1,4 @@
+pub mod decompression;
 pub mod request_limiter;
 
 use std::{io, mem::drop, net::SocketAddr, time::Duration};
@@ -34,7 +35,7 @@ use crate::{
     internal_events::{
         ConnectionOpen, DecoderFramingError, OpenGauge, SocketBindError, SocketEventsReceived,
         SocketMode, SocketReceiveError, StreamClosedError, TcpBytesReceived, TcpSendAckError,
-        TcpSocketTlsConnectionError,
+        TcpSocketError, TcpSocketPeerRejected, TcpSocketTlsConnectionError,
     },
     shutdown::ShutdownSignal,
     sources::util::AfterReadExt,
@@ -106,6 +107,16 @@ where
 
     fn handle_events(&self, _events: &mut [Event], _host: std::net::SocketAddr) {}
 
+    /// Whether a connection presenting the given client certificate is accepted.
+    fn permits_peer(&self, _certificate: Option<&CertificateMetadata>) -> bool {
+        true
+    }
+
+    /// The bytes sent to a connection before anything is read from it, by the given decoder.
+    fn greeting(&self, _decoder: &Self::Decoder) -> Option<Bytes> {
+        None
+    }
+
     fn build_acker(&self, item: &[Self::Item]) -> Self::Acker;
 
     #[allow(clippy::too_many_arguments)]
@@ -278,7 +289,7 @@ async fn handle_stream<T>(
         }
     }
 
-    let socket = socket.after_read(move |byte_size| {
+    let mut socket = socket.after_read(move |byte_size| {
         emit!(TcpBytesReceived {
             byte_size,
             peer_addr
@@ -291,7 +302,28 @@ async fn handle_stream<T>(
         .and_then(|stream| stream.ssl().peer_certificate())
         .map(CertificateMetadata::from);
 
-    let reader = FramedRead::new(socket, source.decoder());
+    if !source.permits_peer(certificate_metadata.as_ref()) {
+        emit!(TcpSocketPeerRejected {
+            peer_addr,
+            subject: certificate_metadata
+                .as_ref()
+                .map(CertificateMetadata::subject),
+        });
+        return;
+    }
+
+    let decoder = source.decoder();
+    if let Some(greeting) = source.greeting(&decoder) {
+        if let Err(error) = socket.write_all(&greeting).await {
+            emit!(TcpSocketError {
+                error: &error,
+                peer_addr
+            });
+            return;
+        }
+    }
+
+    let reader = FramedRead::new(socket, decoder);
     let mut reader = ReadyFrames::new(reader);
 
     let connection_close_timeout = OptionFuture::from(
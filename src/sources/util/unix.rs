@@ -1,10 +1,16 @@
 use std::os::unix::fs::PermissionsExt;
-use std::{fs, fs::remove_file, path::Path};
+use std::{fs, fs::remove_file, io, path::Path, sync::Mutex};
+
+use listenfd::ListenFd;
+use once_cell::sync::Lazy;
 
 use crate::internal_events::UnixSocketFileDeleteError;
 
 pub const UNNAMED_SOCKET_HOST: &str = "(unnamed)";
 
+/// Socket path prefix selecting a socket passed by systemd socket activation.
+const SYSTEMD_SOCKET_PREFIX: &str = "systemd";
+
 pub fn change_socket_permissions(path: &Path, perms: Option<u32>) -> crate::Result<()> {
     if let Some(mode) = perms {
         match fs::set_permissions(path, fs::Permissions::from_mode(mode)) {
@@ -19,3 +25,206 @@ pub fn change_socket_permissions(path: &Path, perms: Option<u32>) -> crate::Resu
     }
     Ok(())
 }
+
+/// Returns the zero-based offset of the systemd-provided socket to use, if the Unix socket path is
+/// `systemd` or `systemd#N`.
+///
+/// Like `SocketListenAddr`, the index in `systemd#N` is one-based, so `systemd` and `systemd#1`
+/// both refer to the first socket passed through `LISTEN_FDS`.
+pub fn systemd_socket_offset(path: &Path) -> Option<usize> {
+    let path = path.to_str()?;
+    match path.strip_prefix(SYSTEMD_SOCKET_PREFIX)? {
+        "" => Some(0),
+        rest => rest
+            .strip_prefix('#')?
+            .parse::<usize>()
+            .ok()?
+            .checked_sub(1),
+    }
+}
+
+/// The sockets passed by systemd.
+///
+/// `ListenFd::from_env` clears `LISTEN_FDS`, so the sockets are taken from the environment once
+/// for the whole process, and each of them is handed out to a single source.
+static SYSTEMD_SOCKETS: Lazy<Mutex<ListenFd>> = Lazy::new(|| Mutex::new(ListenFd::from_env()));
+
+/// Takes the Unix stream listener passed by systemd at the given offset.
+pub fn take_systemd_unix_listener(offset: usize) -> io::Result<std::os::unix::net::UnixListener> {
+    let listener = take_systemd_socket(offset, "Unix stream", ListenFd::take_unix_listener)?;
+    listener.set_nonblocking(true)?;
+    Ok(listener)
+}
+
+/// Takes the Unix datagram socket passed by systemd at the given offset.
+pub fn take_systemd_unix_datagram(offset: usize) -> io::Result<std::os::unix::net::UnixDatagram> {
+    let socket = take_systemd_socket(offset, "Unix datagram", ListenFd::take_unix_datagram)?;
+    socket.set_nonblocking(true)?;
+    Ok(socket)
+}
+
+/// Takes the socket passed by systemd at the given offset, once its address family and type are
+/// checked by `take`.
+fn take_systemd_socket<T>(
+    offset: usize,
+    kind: &str,
+    take: impl FnOnce(&mut ListenFd, usize) -> io::Result<Option<T>>,
+) -> io::Result<T> {
+    let mut sockets = SYSTEMD_SOCKETS.lock().expect("poisoned lock");
+    match take(&mut sockets, offset) {
+        Ok(Some(socket)) => Ok(socket),
+        Ok(None) if offset < sockets.len() => Err(io::Error::new(
+            io::ErrorKind::AddrInUse,
+            format!(
+                "systemd socket #{} is already used by another source",
+                offset + 1
+            ),
+        )),
+        Ok(None) => Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!(
+                "systemd socket #{} was not passed, as only {} sockets were passed by systemd",
+                offset + 1,
+                sockets.len()
+            ),
+        )),
+        Err(error) => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "systemd socket #{} is not a {kind} socket: {error}",
+                offset + 1
+            ),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        env,
+        io::{Read, Write},
+        os::unix::{
+            io::AsRawFd,
+            net::{UnixDatagram, UnixListener, UnixStream},
+            process::CommandExt,
+        },
+        path::PathBuf,
+        process::Command,
+        time::Duration,
+    };
+
+    use super::*;
+    use crate::test_util::temp_file;
+
+    /// Set in the child process of `takes_systemd_sockets_once`.
+    const CHILD_ENV: &str = "VECTOR_TEST_SYSTEMD_SOCKETS";
+
+    #[test]
+    fn parse_systemd_socket_path() {
+        let cases = [
+            ("systemd", Some(0)),
+            ("systemd#1", Some(0)),
+            ("systemd#3", Some(2)),
+            ("systemd#0", None),
+            ("systemd#x", None),
+            ("systemd3", None),
+            ("/run/systemd/journal/syslog", None),
+            ("/path/to/socket", None),
+        ];
+
+        for (path, expected) in cases {
+            assert_eq!(
+                systemd_socket_offset(&PathBuf::from(path)),
+                expected,
+                "path: {path}"
+            );
+        }
+    }
+
+    /// Passes a Unix datagram socket and a Unix stream listener to a child process running this
+    /// test, as systemd does, which takes them.
+    #[test]
+    fn takes_systemd_sockets_once() {
+        if env::var_os(CHILD_ENV).is_some() {
+            return take_passed_sockets();
+        }
+
+        let (datagram, peer) = UnixDatagram::pair().unwrap();
+        let path = temp_file();
+        let listener = UnixListener::bind(&path).unwrap();
+        let fds = [datagram.as_raw_fd(), listener.as_raw_fd()];
+
+        let mut command = Command::new(env::current_exe().unwrap());
+        command
+            .args([
+                "--exact",
+                "sources::util::unix::tests::takes_systemd_sockets_once",
+                "--nocapture",
+            ])
+            .env(CHILD_ENV, "1")
+            .env("LISTEN_FDS", "2")
+            .env_remove("LISTEN_PID")
+            .env_remove("LISTEN_FDS_FIRST_FD");
+        // SAFETY: Only async-signal-safe functions are called between `fork` and `exec`.
+        unsafe {
+            command.pre_exec(move || {
+                // The descriptors are moved out of the way first, as they may already be 3 or 4.
+                for (offset, fd) in fds.into_iter().enumerate() {
+                    if libc::dup2(fd, 100 + offset as libc::c_int) < 0 {
+                        return Err(io::Error::last_os_error());
+                    }
+                }
+                for offset in 0..fds.len() as libc::c_int {
+                    if libc::dup2(100 + offset, 3 + offset) < 0 {
+                        return Err(io::Error::last_os_error());
+                    }
+                }
+                Ok(())
+            });
+        }
+        let output = command.output().unwrap();
+        assert!(
+            output.status.success(),
+            "{}{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        );
+
+        // The child sent through the taken sockets.
+        peer.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+        let mut buf = [0; 16];
+        let len = peer.recv(&mut buf).unwrap();
+        assert_eq!(&buf[..len], b"datagram");
+
+        listener.set_nonblocking(false).unwrap();
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut message = String::new();
+        stream.read_to_string(&mut message).unwrap();
+        assert_eq!(message, "stream");
+        _ = remove_file(&path);
+    }
+
+    /// Takes the sockets passed by `takes_systemd_sockets_once`.
+    fn take_passed_sockets() {
+        // The sockets are checked before being taken, and are left untouched when of another type.
+        let error = take_systemd_unix_datagram(1).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput, "{error}");
+        let error = take_systemd_unix_listener(0).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput, "{error}");
+
+        let datagram = take_systemd_unix_datagram(0).unwrap();
+        datagram.send(b"datagram").unwrap();
+        let listener = take_systemd_unix_listener(1).unwrap();
+        let path = listener.local_addr().unwrap();
+        let mut stream = UnixStream::connect(path.as_pathname().unwrap()).unwrap();
+        stream.write_all(b"stream").unwrap();
+
+        // Each socket is only handed out once.
+        let error = take_systemd_unix_datagram(0).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::AddrInUse, "{error}");
+        let error = take_systemd_unix_listener(1).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::AddrInUse, "{error}");
+        let error = take_systemd_unix_listener(2).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::NotFound, "{error}");
+    }
+}
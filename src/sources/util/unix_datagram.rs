@@ -18,7 +18,7 @@ use crate::{
     },
     shutdown::ShutdownSignal,
     sources::util::change_socket_permissions,
-    sources::util::unix::UNNAMED_SOCKET_HOST,
+    sources::util::unix::{systemd_socket_offset, take_systemd_unix_datagram, UNNAMED_SOCKET_HOST},
     sources::Source,
     SourceSender,
 };
@@ -37,20 +37,31 @@ pub fn build_unix_datagram_source(
     out: SourceSender,
 ) -> crate::Result<Source> {
     Ok(Box::pin(async move {
-        let socket = UnixDatagram::bind(&listen_path).expect("Failed to bind to datagram socket");
+        let systemd_offset = systemd_socket_offset(&listen_path);
+        let socket = match systemd_offset {
+            Some(offset) => take_systemd_unix_datagram(offset)
+                .and_then(UnixDatagram::from_std)
+                .expect("Failed to take systemd datagram socket"),
+            None => UnixDatagram::bind(&listen_path).expect("Failed to bind to datagram socket"),
+        };
         info!(message = "Listening.", path = ?listen_path, r#type = "unix_datagram");
 
-        change_socket_permissions(&listen_path, socket_file_mode)
-            .expect("Failed to set socket permissions");
+        // The socket file of a systemd-provided socket is owned by systemd.
+        if systemd_offset.is_none() {
+            change_socket_permissions(&listen_path, socket_file_mode)
+                .expect("Failed to set socket permissions");
+        }
 
         let result = listen(socket, max_length, decoder, shutdown, handle_events, out).await;
 
         // Delete socket file.
-        if let Err(error) = remove_file(&listen_path) {
-            emit!(UnixSocketFileDeleteError {
-                path: &listen_path,
-                error
-            });
+        if systemd_offset.is_none() {
+            if let Err(error) = remove_file(&listen_path) {
+                emit!(UnixSocketFileDeleteError {
+                    path: &listen_path,
+                    error
+                });
+            }
         }
 
         result
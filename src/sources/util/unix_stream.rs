@@ -25,7 +25,7 @@ use crate::{
     },
     shutdown::ShutdownSignal,
     sources::util::change_socket_permissions,
-    sources::util::unix::UNNAMED_SOCKET_HOST,
+    sources::util::unix::{systemd_socket_offset, take_systemd_unix_listener, UNNAMED_SOCKET_HOST},
     sources::Source,
     SourceSender,
 };
@@ -43,17 +43,32 @@ pub fn build_unix_stream_source(
     out: SourceSender,
 ) -> crate::Result<Source> {
     Ok(Box::pin(async move {
-        let listener = UnixListener::bind(&listen_path).unwrap_or_else(|e| {
-            panic!(
-                "Failed to bind to listener socket at path: {}. Err: {}",
-                listen_path.to_string_lossy(),
-                e
-            )
-        });
+        let systemd_offset = systemd_socket_offset(&listen_path);
+        let listener = match systemd_offset {
+            Some(offset) => take_systemd_unix_listener(offset)
+                .and_then(UnixListener::from_std)
+                .unwrap_or_else(|e| {
+                    panic!(
+                        "Failed to take systemd listener socket {}. Err: {}",
+                        listen_path.to_string_lossy(),
+                        e
+                    )
+                }),
+            None => UnixListener::bind(&listen_path).unwrap_or_else(|e| {
+                panic!(
+                    "Failed to bind to listener socket at path: {}. Err: {}",
+                    listen_path.to_string_lossy(),
+                    e
+                )
+            }),
+        };
         info!(message = "Listening.", path = ?listen_path, r#type = "unix");
 
-        change_socket_permissions(&listen_path, socket_file_mode)
-            .expect("Failed to set socket permissions");
+        // The socket file of a systemd-provided socket is owned by systemd.
+        if systemd_offset.is_none() {
+            change_socket_permissions(&listen_path, socket_file_mode)
+                .expect("Failed to set socket permissions");
+        }
 
         let bytes_received = register!(BytesReceived::from(Protocol::UNIX));
 
@@ -152,11 +167,13 @@ pub fn build_unix_stream_source(
         }
 
         // Delete socket file
-        if let Err(error) = remove_file(&listen_path) {
-            emit!(UnixSocketFileDeleteError {
-                path: &listen_path,
-                error
-            });
+        if systemd_offset.is_none() {
+            if let Err(error) = remove_file(&listen_path) {
+                emit!(UnixSocketFileDeleteError {
+                    path: &listen_path,
+                    error
+                });
+            }
         }
 
         Ok(())
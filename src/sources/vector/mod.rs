@@ -9,7 +9,10 @@ use vector_lib::configurable::configurable_component;
 use vector_lib::internal_event::{CountByteSize, InternalEventHandle as _};
 use vector_lib::{
     config::LogNamespace,
-    event::{BatchNotifier, BatchStatus, BatchStatusReceiver, Event},
+    event::{
+        proto::{check_native_version, MIN_NATIVE_VERSION, NATIVE_VERSION},
+        BatchNotifier, BatchStatus, BatchStatusReceiver, Event,
+    },
     EstimatedJsonEncodedSizeOf,
 };
 
@@ -48,12 +51,13 @@ impl proto::Service for Service {
         &self,
         request: Request<proto::PushEventsRequest>,
     ) -> Result<Response<proto::PushEventsResponse>, Status> {
-        let mut events: Vec<Event> = request
-            .into_inner()
-            .events
-            .into_iter()
-            .map(Event::from)
-            .collect();
+        let request = request.into_inner();
+        // Events encoded with a version that can't be decoded would lose data, so they are
+        // rejected in a way the `vector` sink doesn't retry.
+        check_native_version(request.version)
+            .map_err(|error| Status::invalid_argument(error.to_string()))?;
+
+        let mut events: Vec<Event> = request.events.into_iter().map(Event::from).collect();
 
         let now = Utc::now();
         for event in &mut events {
@@ -94,6 +98,8 @@ impl proto::Service for Service {
     ) -> Result<Response<proto::HealthCheckResponse>, Status> {
         let message = proto::HealthCheckResponse {
             status: proto::ServingStatus::Serving.into(),
+            min_version: MIN_NATIVE_VERSION,
+            version: NATIVE_VERSION,
         };
 
         Ok(Response::new(message))
@@ -283,6 +289,7 @@ mod tests {
     };
     use vector_lib::assert_event_data_eq;
     use vector_lib::config::log_schema;
+    use vector_lib::event::LogEvent;
 
     async fn run_test(vector_source_config_str: &str, addr: SocketAddr) {
         let config = format!(r#"address = "{}""#, addr);
@@ -336,4 +343,43 @@ mod tests {
         );
         run_test(&config, addr).await;
     }
+
+    #[tokio::test]
+    async fn rejects_unsupported_native_versions() {
+        let addr = test_util::next_addr();
+
+        let config = format!(r#"address = "{}""#, addr);
+        let source: VectorConfig = toml::from_str(&config).unwrap();
+
+        let (tx, _rx) = SourceSender::new_test();
+        let server = source
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .unwrap();
+        tokio::spawn(server);
+        test_util::wait_for_tcp(addr).await;
+
+        let channel = tonic::transport::Endpoint::try_from(format!("http://{}", addr))
+            .unwrap()
+            .connect_lazy();
+        let mut client = proto::Client::new(channel);
+
+        let health = client
+            .health_check(proto::HealthCheckRequest {})
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(health.min_version, MIN_NATIVE_VERSION);
+        assert_eq!(health.version, NATIVE_VERSION);
+
+        let status = client
+            .push_events(proto::PushEventsRequest {
+                events: vec![Event::from(LogEvent::from("foo")).into()],
+                version: NATIVE_VERSION + 1,
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+        assert!(status.message().contains("upgrade this Vector"));
+    }
 }
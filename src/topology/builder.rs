@@ -48,6 +48,7 @@ use crate::{
     extra_context::ExtraContext,
     internal_events::EventsReceived,
     shutdown::SourceShutdownCoordinator,
+    sinks::util::service::RequestLimitsGroups,
     source_sender::{SourceSenderItem, CHUNK_SIZE},
     spawn_named,
     topology::task::TaskError,
@@ -85,6 +86,7 @@ struct Builder<'a> {
     inputs: HashMap<ComponentKey, (BufferSender<EventArray>, Inputs<OutputId>)>,
     healthchecks: HashMap<ComponentKey, Task>,
     detach_triggers: HashMap<ComponentKey, Trigger>,
+    request_limits: RequestLimitsGroups,
     extra_context: ExtraContext,
 }
 
@@ -93,12 +95,14 @@ impl<'a> Builder<'a> {
         config: &'a super::Config,
         diff: &'a ConfigDiff,
         buffers: HashMap<ComponentKey, BuiltBuffer>,
+        request_limits: &RequestLimitsGroups,
         extra_context: ExtraContext,
     ) -> Self {
         Self {
             config,
             diff,
             buffers,
+            request_limits: RequestLimitsGroups::build(&config.request_limits, request_limits),
             shutdown_coordinator: SourceShutdownCoordinator::default(),
             errors: vec![],
             outputs: HashMap::new(),
@@ -130,6 +134,7 @@ impl<'a> Builder<'a> {
                 healthchecks: self.healthchecks,
                 shutdown_coordinator: self.shutdown_coordinator,
                 detach_triggers: self.detach_triggers,
+                request_limits: self.request_limits,
             })
         } else {
             Err(self.errors)
@@ -575,6 +580,9 @@ impl<'a> Builder<'a> {
                 app_name: crate::get_app_name().to_string(),
                 app_name_slug: crate::get_slugified_app_name(),
                 extra_context: self.extra_context.clone(),
+                request_limits: sink
+                    .request_limits()
+                    .and_then(|name| self.request_limits.get(name)),
             };
 
             let (sink, healthcheck) = match sink.inner.build(cx).await {
@@ -684,6 +692,7 @@ pub struct TopologyPieces {
     pub(super) healthchecks: HashMap<ComponentKey, Task>,
     pub(crate) shutdown_coordinator: SourceShutdownCoordinator,
     pub(crate) detach_triggers: HashMap<ComponentKey, Trigger>,
+    pub(super) request_limits: RequestLimitsGroups,
 }
 
 impl TopologyPieces {
@@ -691,9 +700,10 @@ impl TopologyPieces {
         config: &Config,
         diff: &ConfigDiff,
         buffers: HashMap<ComponentKey, BuiltBuffer>,
+        request_limits: &RequestLimitsGroups,
         extra_context: ExtraContext,
     ) -> Option<Self> {
-        match TopologyPieces::build(config, diff, buffers, extra_context).await {
+        match TopologyPieces::build(config, diff, buffers, request_limits, extra_context).await {
             Err(errors) => {
                 for error in errors {
                     error!(message = "Configuration error.", %error);
@@ -705,13 +715,18 @@ impl TopologyPieces {
     }
 
     /// Builds only the new pieces, and doesn't check their topology.
+    ///
+    /// The request limits groups of `request_limits`, owned by the running topology, are kept when
+    /// their configuration is unchanged, so that the sinks which aren't rebuilt keep sharing them
+    /// with the new sinks.
     pub async fn build(
         config: &super::Config,
         diff: &ConfigDiff,
         buffers: HashMap<ComponentKey, BuiltBuffer>,
+        request_limits: &RequestLimitsGroups,
         extra_context: ExtraContext,
     ) -> Result<Self, Vec<String>> {
-        Builder::new(config, diff, buffers, extra_context)
+        Builder::new(config, diff, buffers, request_limits, extra_context)
             .build()
             .await
     }
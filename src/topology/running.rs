@@ -29,6 +29,7 @@ use crate::{
     extra_context::ExtraContext,
     shutdown::SourceShutdownCoordinator,
     signal::ShutdownError,
+    sinks::util::service::RequestLimitsGroups,
     spawn_named,
 };
 
@@ -44,6 +45,7 @@ pub struct RunningTopology {
     tasks: HashMap<ComponentKey, TaskHandle>,
     shutdown_coordinator: SourceShutdownCoordinator,
     detach_triggers: HashMap<ComponentKey, DisabledTrigger>,
+    request_limits: RequestLimitsGroups,
     pub(crate) config: Config,
     pub(crate) abort_tx: mpsc::UnboundedSender<ShutdownError>,
     watch: (WatchTx, WatchRx),
@@ -60,6 +62,7 @@ impl RunningTopology {
             outputs_tap_metadata: HashMap::new(),
             shutdown_coordinator: SourceShutdownCoordinator::default(),
             detach_triggers: HashMap::new(),
+            request_limits: RequestLimitsGroups::default(),
             source_tasks: HashMap::new(),
             tasks: HashMap::new(),
             abort_tx,
@@ -258,6 +261,7 @@ impl RunningTopology {
             &new_config,
             &diff,
             buffers.clone(),
+            &self.request_limits,
             extra_context.clone(),
         )
         .await
@@ -285,9 +289,14 @@ impl RunningTopology {
         warn!("Failed to completely load new configuration. Restoring old configuration.");
 
         let diff = diff.flip();
-        if let Some(mut new_pieces) =
-            TopologyPieces::build_or_log_errors(&self.config, &diff, buffers, extra_context.clone())
-                .await
+        if let Some(mut new_pieces) = TopologyPieces::build_or_log_errors(
+            &self.config,
+            &diff,
+            buffers,
+            &self.request_limits,
+            extra_context.clone(),
+        )
+        .await
         {
             if self
                 .run_healthchecks(&diff, &mut new_pieces, self.config.healthchecks)
@@ -818,6 +827,10 @@ impl RunningTopology {
 
     /// Starts any new or changed components in the given configuration diff.
     pub(crate) fn spawn_diff(&mut self, diff: &ConfigDiff, mut new_pieces: TopologyPieces) {
+        // The groups of the new pieces are the ones of the new configuration, which is the one
+        // running from now on.
+        self.request_limits = std::mem::take(&mut new_pieces.request_limits);
+
         for key in &diff.sources.to_change {
             debug!(message = "Spawning changed source.", key = %key);
             self.spawn_source(key, &mut new_pieces);
@@ -993,9 +1006,14 @@ impl RunningTopology {
         extra_context: ExtraContext,
     ) -> Option<(Self, ShutdownErrorReceiver)> {
         let diff = ConfigDiff::initial(&config);
-        let pieces =
-            TopologyPieces::build_or_log_errors(&config, &diff, HashMap::new(), extra_context)
-                .await?;
+        let pieces = TopologyPieces::build_or_log_errors(
+            &config,
+            &diff,
+            HashMap::new(),
+            &RequestLimitsGroups::default(),
+            extra_context,
+        )
+        .await?;
         Self::start_validated(config, diff, pieces).await
     }
 
@@ -919,11 +919,18 @@ async fn topology_transform_error_definition() {
 
     let config = config.build().unwrap();
     let diff = ConfigDiff::initial(&config);
-    let errors =
-        match TopologyPieces::build(&config, &diff, HashMap::new(), Default::default()).await {
-            Ok(_) => panic!("build pieces should not succeed"),
-            Err(err) => err,
-        };
+    let errors = match TopologyPieces::build(
+        &config,
+        &diff,
+        HashMap::new(),
+        &Default::default(),
+        Default::default(),
+    )
+    .await
+    {
+        Ok(_) => panic!("build pieces should not succeed"),
+        Err(err) => err,
+    };
 
     assert_eq!(
         r#"Transform "transform": It all went horribly wrong"#,
@@ -22,6 +22,7 @@ async fn sources_finished() {
         ConsoleSinkConfig {
             target: Target::Stdout,
             encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
+            display: None,
             acknowledgements: Default::default(),
         },
     );
@@ -14,6 +14,7 @@ use crate::{
     event::{metric, Event, EventMetadata},
     internal_events::{AggregateEventRecorded, AggregateFlushed, AggregateUpdateFailed},
     schema,
+    sinks::util::buffer::metrics::MetricSet,
     transforms::{TaskTransform, Transform},
 };
 
@@ -28,6 +29,44 @@ pub struct AggregateConfig {
     #[serde(default = "default_interval_ms")]
     #[configurable(metadata(docs::human_name = "Flush Interval"))]
     pub interval_ms: u64,
+
+    /// How counters are aggregated.
+    #[serde(default)]
+    pub counters: CounterMode,
+
+    /// Whether or not distributions are converted into sketches.
+    ///
+    /// The samples of a distribution are stored in the sketch, and the sketches of the same series
+    /// are merged together, so that the size of the aggregated metric doesn't depend on the number
+    /// of samples. The sketches are [DDSketches][ddsketch], with the parameters of the Datadog
+    /// Agent.
+    ///
+    /// [ddsketch]: https://www.vldb.org/pvldb/vol12/p2195-masson.pdf
+    #[serde(default)]
+    pub convert_distributions_to_sketches: bool,
+}
+
+/// How counters are aggregated.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum CounterMode {
+    /// Incremental counters are summed, and the last value of absolute counters is kept.
+    #[default]
+    Sum,
+
+    /// Counters are converted into the increase of their value over the interval, as incremental
+    /// counters.
+    ///
+    /// Absolute counters are converted from the difference with their previous value, and are
+    /// only emitted once they have been seen twice. A counter going backwards is considered reset.
+    Delta,
+
+    /// Counters are converted into their rate of increase per second over the interval, as
+    /// absolute gauges.
+    ///
+    /// The rates are computed from the deltas of the counters, as with `delta`.
+    Rate,
 }
 
 const fn default_interval_ms() -> u64 {
@@ -62,19 +101,48 @@ type MetricEntry = (metric::MetricData, EventMetadata);
 #[derive(Debug)]
 pub struct Aggregate {
     interval: Duration,
+    counters: CounterMode,
+    convert_distributions_to_sketches: bool,
     map: HashMap<metric::MetricSeries, MetricEntry>,
+    /// The last values of the absolute counters, to convert them into deltas.
+    absolute_counters: MetricSet,
 }
 
 impl Aggregate {
     pub fn new(config: &AggregateConfig) -> crate::Result<Self> {
         Ok(Self {
             interval: Duration::from_millis(config.interval_ms),
+            counters: config.counters,
+            convert_distributions_to_sketches: config.convert_distributions_to_sketches,
             map: Default::default(),
+            absolute_counters: Default::default(),
         })
     }
 
     fn record(&mut self, event: Event) {
-        let (series, data, metadata) = event.into_metric().into_parts();
+        let mut metric = event.into_metric();
+
+        if self.convert_distributions_to_sketches {
+            if let Some(sketch) = metric.value().distribution_to_sketch() {
+                metric = metric.with_value(sketch);
+            }
+        }
+
+        if self.counters != CounterMode::Sum
+            && matches!(metric.value(), metric::MetricValue::Counter { .. })
+        {
+            match self.absolute_counters.make_incremental(metric) {
+                Some(incremental) => metric = incremental,
+                None => {
+                    // The first value of an absolute counter is only stored, to compute the
+                    // delta of the next one.
+                    emit!(AggregateEventRecorded);
+                    return;
+                }
+            }
+        }
+
+        let (series, data, metadata) = metric.into_parts();
 
         match data.kind {
             metric::MetricKind::Incremental => match self.map.entry(series) {
@@ -104,7 +172,15 @@ impl Aggregate {
     fn flush_into(&mut self, output: &mut Vec<Event>) {
         let map = std::mem::take(&mut self.map);
         for (series, entry) in map.into_iter() {
-            let metric = metric::Metric::from_parts(series, entry.0, entry.1);
+            let mut metric = metric::Metric::from_parts(series, entry.0, entry.1);
+            if self.counters == CounterMode::Rate {
+                if let metric::MetricValue::Counter { value } = metric.value() {
+                    let rate = value / self.interval.as_secs_f64();
+                    metric = metric
+                        .with_value(metric::MetricValue::Gauge { value: rate })
+                        .into_absolute();
+                }
+            }
             output.push(Event::Metric(metric));
         }
 
@@ -192,6 +268,7 @@ mod tests {
     fn incremental() {
         let mut agg = Aggregate::new(&AggregateConfig {
             interval_ms: 1000_u64,
+            ..Default::default()
         })
         .unwrap();
 
@@ -262,6 +339,7 @@ mod tests {
     fn absolute() {
         let mut agg = Aggregate::new(&AggregateConfig {
             interval_ms: 1000_u64,
+            ..Default::default()
         })
         .unwrap();
 
@@ -327,6 +405,7 @@ mod tests {
     fn conflicting_value_type() {
         let mut agg = Aggregate::new(&AggregateConfig {
             interval_ms: 1000_u64,
+            ..Default::default()
         })
         .unwrap();
 
@@ -384,6 +463,7 @@ mod tests {
     fn conflicting_kinds() {
         let mut agg = Aggregate::new(&AggregateConfig {
             interval_ms: 1000_u64,
+            ..Default::default()
         })
         .unwrap();
 
@@ -434,6 +514,134 @@ mod tests {
         assert_eq!(&summed, &out[0]);
     }
 
+    #[test]
+    fn counter_deltas() {
+        let mut agg = Aggregate::new(&AggregateConfig {
+            interval_ms: 1000_u64,
+            counters: CounterMode::Delta,
+            ..Default::default()
+        })
+        .unwrap();
+
+        let absolute = |value| {
+            make_metric(
+                "counter_a",
+                metric::MetricKind::Absolute,
+                metric::MetricValue::Counter { value },
+            )
+        };
+        let incremental = |value| {
+            make_metric(
+                "counter_a",
+                metric::MetricKind::Incremental,
+                metric::MetricValue::Counter { value },
+            )
+        };
+
+        // The first value is only stored as the reference of the next ones
+        agg.record(absolute(10.0));
+        let mut out = vec![];
+        agg.flush_into(&mut out);
+        assert_eq!(0, out.len());
+
+        // The deltas between the values are summed over the interval
+        agg.record(absolute(15.0));
+        agg.record(absolute(22.0));
+        agg.flush_into(&mut out);
+        assert_eq!(1, out.len());
+        assert_eq!(&incremental(12.0), &out[0]);
+
+        // A counter going backwards is reset
+        out.clear();
+        agg.record(absolute(3.0));
+        agg.record(absolute(5.0));
+        agg.flush_into(&mut out);
+        assert_eq!(1, out.len());
+        assert_eq!(&incremental(2.0), &out[0]);
+    }
+
+    #[test]
+    fn counter_rates() {
+        let mut agg = Aggregate::new(&AggregateConfig {
+            interval_ms: 2000_u64,
+            counters: CounterMode::Rate,
+            ..Default::default()
+        })
+        .unwrap();
+
+        let counter =
+            |kind, value| make_metric("counter_a", kind, metric::MetricValue::Counter { value });
+
+        agg.record(counter(metric::MetricKind::Incremental, 3.0));
+        agg.record(counter(metric::MetricKind::Incremental, 5.0));
+        let mut out = vec![];
+        agg.flush_into(&mut out);
+        assert_eq!(1, out.len());
+        assert_eq!(
+            &make_metric(
+                "counter_a",
+                metric::MetricKind::Absolute,
+                metric::MetricValue::Gauge { value: 4.0 },
+            ),
+            &out[0]
+        );
+
+        // Gauges are aggregated as usual
+        let gauge = make_metric(
+            "gauge_a",
+            metric::MetricKind::Absolute,
+            metric::MetricValue::Gauge { value: 42.0 },
+        );
+        out.clear();
+        agg.record(gauge.clone());
+        agg.flush_into(&mut out);
+        assert_eq!(1, out.len());
+        assert_eq!(&gauge, &out[0]);
+    }
+
+    #[test]
+    fn distributions_to_sketches() {
+        let mut agg = Aggregate::new(&AggregateConfig {
+            interval_ms: 1000_u64,
+            convert_distributions_to_sketches: true,
+            ..Default::default()
+        })
+        .unwrap();
+
+        let distribution = |values: &[f64]| {
+            make_metric(
+                "distribution_a",
+                metric::MetricKind::Incremental,
+                metric::MetricValue::Distribution {
+                    samples: values
+                        .iter()
+                        .map(|&value| metric::Sample { value, rate: 1 })
+                        .collect(),
+                    statistic: metric::StatisticKind::Histogram,
+                },
+            )
+        };
+
+        agg.record(distribution(&[1.0, 2.0]));
+        agg.record(distribution(&[3.0]));
+        let mut out = vec![];
+        agg.flush_into(&mut out);
+        assert_eq!(1, out.len());
+
+        // The samples of both distributions are merged into a single sketch
+        match out[0].as_metric().value() {
+            metric::MetricValue::Sketch {
+                sketch: metric::MetricSketch::AgentDDSketch(sketch),
+            } => {
+                assert_eq!(3, sketch.count());
+                assert_eq!(Some(1.0), sketch.min());
+                assert_eq!(Some(3.0), sketch.max());
+                assert_eq!(Some(6.0), sketch.sum());
+            }
+            value => panic!("Unexpected metric value in aggregate output: {:?}", value),
+        }
+    }
+
     #[tokio::test]
     async fn transform_shutdown() {
         let agg = toml::from_str::<AggregateConfig>(
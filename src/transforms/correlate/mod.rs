@@ -0,0 +1,743 @@
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    num::NonZeroUsize,
+    path::PathBuf,
+    pin::Pin,
+    time::{Duration, Instant},
+};
+
+use async_stream::stream;
+use futures::{Stream, StreamExt};
+use serde_with::serde_as;
+use vector_lib::config::LogNamespace;
+use vector_lib::configurable::configurable_component;
+use vector_lib::lookup::lookup_v2::ConfigValuePath;
+use vrl::path::PathPrefix;
+
+use crate::{
+    config::{
+        ComponentKey, DataType, GenerateConfig, Input, OutputId, TransformConfig, TransformContext,
+        TransformOutput,
+    },
+    event::{discriminant::Discriminant, Event, EventMetadata, LogEvent, Value},
+    internal_events::{
+        CorrelateSpillReadError, CorrelateSpillWriteError, CorrelateUnmatchedEventDropped,
+    },
+    schema::{self, Definition},
+    transforms::{TaskTransform, Transform},
+};
+
+mod spill;
+
+use spill::Spill;
+
+/// Configuration for the `correlate` transform.
+#[serde_as]
+#[configurable_component(transform(
+    "correlate",
+    "Join the events of two sets of inputs on a key, within a time window."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct CorrelateConfig {
+    /// The events joined with the events of `right`.
+    #[configurable(metadata(docs::human_name = "Left Events"))]
+    pub left: CorrelateInputConfig,
+
+    /// The events joined with the events of `left`.
+    #[configurable(metadata(docs::human_name = "Right Events"))]
+    pub right: CorrelateInputConfig,
+
+    /// How long each event waits for the event it's joined with, in seconds.
+    #[serde(default = "default_window")]
+    #[serde_as(as = "serde_with::DurationSecondsWithFrac<f64>")]
+    #[configurable(metadata(docs::human_name = "Join Window"))]
+    pub window_secs: Duration,
+
+    /// Whether the events that aren't joined within `window_secs` are sent downstream on their own.
+    ///
+    /// When enabled, each unmatched event is nested in the field of its side, and the field of the
+    /// other side is missing. When disabled, unmatched events are dropped.
+    #[serde(default = "crate::serde::default_true")]
+    pub emit_unmatched: bool,
+
+    /// The maximum number of events waiting in memory to be joined.
+    ///
+    /// Once this many events are waiting, the next events are spilled to disk if `spill` is set.
+    /// Otherwise, or once the spilled events are also at their limit, the oldest waiting event is
+    /// handled as unmatched to make room for the next one.
+    #[serde(default = "default_max_events")]
+    pub max_events: NonZeroUsize,
+
+    #[configurable(derived)]
+    pub spill: Option<SpillConfig>,
+}
+
+/// The events on one side of the join.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct CorrelateInputConfig {
+    /// The IDs of the inputs whose events are on this side of the join.
+    ///
+    /// Each of them must also be one of the `inputs` of the transform. Events from the inputs of
+    /// neither side are sent downstream unmodified.
+    #[configurable(metadata(docs::examples = "dns_queries"))]
+    pub inputs: Vec<String>,
+
+    /// The fields whose values form the key that events are joined on.
+    ///
+    /// Both sides must have the same number of fields, and their values are compared in order.
+    /// Events that are missing any of these fields are handled as unmatched.
+    #[configurable(metadata(docs::examples = "query_id"))]
+    pub key: Vec<String>,
+
+    /// The field of the joined events in which the events of this side are nested.
+    ///
+    /// Defaults to `left` for the left events, and to `right` for the right events.
+    #[configurable(metadata(docs::examples = "query"))]
+    pub field: Option<ConfigValuePath>,
+}
+
+/// Spills the events waiting to be joined to disk, once `max_events` are waiting in memory.
+///
+/// Only the data of the events is spilled, and their metadata is kept in memory. The spilled
+/// events don't survive restarts of Vector.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SpillConfig {
+    /// The directory used to spill the events.
+    ///
+    /// By default, the [global `data_dir` option][global_data_dir] is used.
+    /// Make sure the running user has write permissions to this directory.
+    ///
+    /// If this directory is specified, then Vector will attempt to create it.
+    ///
+    /// [global_data_dir]: https://vector.dev/docs/reference/configuration/global-options/#data_dir
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "/var/local/lib/vector/"))]
+    #[configurable(metadata(docs::human_name = "Data Directory"))]
+    pub data_dir: Option<PathBuf>,
+
+    /// The maximum number of events spilled to disk.
+    #[serde(default = "default_max_spilled_events")]
+    pub max_events: NonZeroUsize,
+
+    /// The maximum size of the page cache of the database in memory, in bytes.
+    #[serde(default = "default_max_memory_bytes")]
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    pub max_memory_bytes: u64,
+}
+
+const fn default_window() -> Duration {
+    Duration::from_secs(10)
+}
+
+fn default_max_events() -> NonZeroUsize {
+    NonZeroUsize::new(10_000).unwrap()
+}
+
+fn default_max_spilled_events() -> NonZeroUsize {
+    NonZeroUsize::new(1_000_000).unwrap()
+}
+
+const fn default_max_memory_bytes() -> u64 {
+    64 * 1024 * 1024
+}
+
+impl GenerateConfig for CorrelateConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            left: CorrelateInputConfig {
+                inputs: vec!["requests".to_string()],
+                key: vec!["request_id".to_string()],
+                field: None,
+            },
+            right: CorrelateInputConfig {
+                inputs: vec!["responses".to_string()],
+                key: vec!["request_id".to_string()],
+                field: None,
+            },
+            window_secs: default_window(),
+            emit_unmatched: true,
+            max_events: default_max_events(),
+            spill: None,
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "correlate")]
+impl TransformConfig for CorrelateConfig {
+    async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
+        let spill = match &self.spill {
+            Some(config) => {
+                let id = context.key.as_ref().map_or("correlate", ComponentKey::id);
+                let path = context
+                    .globals
+                    .resolve_and_make_data_subdir(config.data_dir.as_ref(), id)?;
+                let spill = Spill::open(&path, config.max_memory_bytes).map_err(|error| {
+                    format!("Unable to open the spilled events in {:?}: {}", path, error)
+                })?;
+                Some((spill, config.max_events.get()))
+            }
+            None => None,
+        };
+
+        Correlate::new(self, spill).map(Transform::event_task)
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn outputs(
+        &self,
+        _: vector_lib::enrichment::TableRegistry,
+        input_definitions: &[(OutputId, schema::Definition)],
+        _: LogNamespace,
+    ) -> Vec<TransformOutput> {
+        // The joined events are new events, whose fields depend on the events of both sides.
+        let namespaces = input_definitions
+            .iter()
+            .flat_map(|(_output, definition)| definition.log_namespaces().clone())
+            .collect();
+
+        let definitions = input_definitions
+            .iter()
+            .map(|(output, _definition)| {
+                (
+                    output.clone(),
+                    Definition::default_for_namespace(&namespaces),
+                )
+            })
+            .collect();
+
+        vec![TransformOutput::new(DataType::Log, definitions)]
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Side {
+    Left = 0,
+    Right = 1,
+}
+
+impl Side {
+    const fn other(self) -> Self {
+        match self {
+            Self::Left => Self::Right,
+            Self::Right => Self::Left,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct JoinSide {
+    inputs: Vec<String>,
+    key: Vec<String>,
+    field: ConfigValuePath,
+}
+
+impl JoinSide {
+    fn new(config: &CorrelateInputConfig, default_field: &str) -> Self {
+        Self {
+            inputs: config.inputs.clone(),
+            key: config.key.clone(),
+            field: config
+                .field
+                .clone()
+                .unwrap_or_else(|| ConfigValuePath::from(default_field)),
+        }
+    }
+
+    fn key(&self, log: &LogEvent) -> Option<Discriminant> {
+        let complete = self.key.iter().all(|field| {
+            log.parse_path_and_get_value(field.as_str())
+                .ok()
+                .flatten()
+                .is_some()
+        });
+        complete.then(|| Discriminant::from_log_event(log, &self.key))
+    }
+}
+
+/// Where the data of a waiting event is kept.
+#[derive(Debug)]
+enum Stored {
+    Memory(LogEvent),
+    Spilled(EventMetadata),
+}
+
+#[derive(Debug)]
+struct Waiting {
+    side: Side,
+    key: Discriminant,
+    deadline: Instant,
+    event: Stored,
+}
+
+pub struct Correlate {
+    sides: [JoinSide; 2],
+    window: Duration,
+    emit_unmatched: bool,
+    max_events: usize,
+    spill: Option<(Spill, usize)>,
+    /// The waiting events, by sequence number, which is also the order of their deadlines.
+    waiting: BTreeMap<u64, Waiting>,
+    /// The sequence numbers of the waiting events of each side, by key.
+    keys: HashMap<Discriminant, [VecDeque<u64>; 2]>,
+    in_memory: usize,
+    next_seq: u64,
+}
+
+impl Correlate {
+    fn new(config: &CorrelateConfig, spill: Option<(Spill, usize)>) -> crate::Result<Self> {
+        let (left, right) = (&config.left, &config.right);
+        if left.key.is_empty() || left.key.len() != right.key.len() {
+            return Err(
+                "`left.key` and `right.key` must have the same, non-zero, number of fields".into(),
+            );
+        }
+        if left.inputs.is_empty() || right.inputs.is_empty() {
+            return Err("`left.inputs` and `right.inputs` must not be empty".into());
+        }
+        if let Some(input) = left
+            .inputs
+            .iter()
+            .find(|input| right.inputs.contains(input))
+        {
+            return Err(format!("`{}` can't be an input of both sides", input).into());
+        }
+        if config.window_secs.is_zero() {
+            return Err("`window_secs` must be greater than zero".into());
+        }
+
+        Ok(Self {
+            sides: [JoinSide::new(left, "left"), JoinSide::new(right, "right")],
+            window: config.window_secs,
+            emit_unmatched: config.emit_unmatched,
+            max_events: config.max_events.get(),
+            spill,
+            waiting: BTreeMap::new(),
+            keys: HashMap::new(),
+            in_memory: 0,
+            next_seq: 0,
+        })
+    }
+
+    fn side(&self, event: &Event) -> Option<Side> {
+        let upstream_id = event.metadata().upstream_id()?.to_string();
+        [Side::Left, Side::Right]
+            .into_iter()
+            .find(|side| self.sides[*side as usize].inputs.contains(&upstream_id))
+    }
+
+    fn record(&mut self, output: &mut Vec<Event>, event: Event, now: Instant) {
+        let Some(side) = self.side(&event) else {
+            output.push(event);
+            return;
+        };
+        let log = event.into_log();
+        let Some(key) = self.sides[side as usize].key(&log) else {
+            self.unmatched(output, side, log);
+            return;
+        };
+
+        let matched = self
+            .keys
+            .get_mut(&key)
+            .and_then(|keys| keys[side.other() as usize].pop_front());
+        match matched {
+            Some(seq) => {
+                self.remove_key_if_empty(&key);
+                let waiting = self.waiting.remove(&seq).expect("waiting event must exist");
+                match self.load(seq, waiting.event) {
+                    Some(other) => output.push(self.join(side, log, Some(other))),
+                    None => self.unmatched(output, side, log),
+                }
+            }
+            None => self.wait(output, side, key, log, now),
+        }
+    }
+
+    fn wait(
+        &mut self,
+        output: &mut Vec<Event>,
+        side: Side,
+        key: Discriminant,
+        log: LogEvent,
+        now: Instant,
+    ) {
+        while self.is_full() && self.expire_oldest(output) {}
+
+        let seq = self.next_seq;
+        let event = if self.in_memory < self.max_events {
+            self.in_memory += 1;
+            Stored::Memory(log)
+        } else {
+            let (spill, _) = self
+                .spill
+                .as_mut()
+                .expect("spill must be set when memory is full");
+            match spill.write(seq, log.into()) {
+                Ok(metadata) => Stored::Spilled(metadata),
+                Err((error, event)) => {
+                    // The event is kept in memory rather than handled as unmatched early.
+                    emit!(CorrelateSpillWriteError { error });
+                    self.in_memory += 1;
+                    Stored::Memory(event.into_log())
+                }
+            }
+        };
+
+        self.next_seq += 1;
+        self.keys.entry(key.clone()).or_default()[side as usize].push_back(seq);
+        self.waiting.insert(
+            seq,
+            Waiting {
+                side,
+                key,
+                deadline: now + self.window,
+                event,
+            },
+        );
+    }
+
+    fn is_full(&self) -> bool {
+        self.in_memory >= self.max_events
+            && self
+                .spill
+                .as_ref()
+                .map_or(true, |(spill, max_events)| spill.len() >= *max_events)
+    }
+
+    /// Handles the oldest waiting event as unmatched, and returns whether there was one.
+    fn expire_oldest(&mut self, output: &mut Vec<Event>) -> bool {
+        let Some((seq, waiting)) = self.waiting.pop_first() else {
+            return false;
+        };
+
+        // The oldest event is at the front of the events waiting with its key.
+        if let Some(keys) = self.keys.get_mut(&waiting.key) {
+            keys[waiting.side as usize].pop_front();
+        }
+        self.remove_key_if_empty(&waiting.key);
+
+        if let Some(log) = self.load(seq, waiting.event) {
+            self.unmatched(output, waiting.side, log);
+        }
+        true
+    }
+
+    /// Handles the events that waited for the whole window as unmatched.
+    fn flush_into(&mut self, output: &mut Vec<Event>, now: Instant) {
+        while self
+            .waiting
+            .first_key_value()
+            .is_some_and(|(_, waiting)| waiting.deadline <= now)
+        {
+            self.expire_oldest(output);
+        }
+    }
+
+    fn flush_all_into(&mut self, output: &mut Vec<Event>) {
+        while self.expire_oldest(output) {}
+    }
+
+    fn remove_key_if_empty(&mut self, key: &Discriminant) {
+        if self
+            .keys
+            .get(key)
+            .is_some_and(|keys| keys.iter().all(VecDeque::is_empty))
+        {
+            self.keys.remove(key);
+        }
+    }
+
+    fn load(&mut self, seq: u64, event: Stored) -> Option<LogEvent> {
+        match event {
+            Stored::Memory(log) => {
+                self.in_memory -= 1;
+                Some(log)
+            }
+            Stored::Spilled(metadata) => {
+                let (spill, _) = self.spill.as_mut()?;
+                match spill.read(seq, metadata) {
+                    Ok(event) => Some(event.into_log()),
+                    Err(error) => {
+                        emit!(CorrelateSpillReadError { error });
+                        None
+                    }
+                }
+            }
+        }
+    }
+
+    fn unmatched(&self, output: &mut Vec<Event>, side: Side, log: LogEvent) {
+        if self.emit_unmatched {
+            output.push(self.join(side, log, None));
+        } else {
+            emit!(CorrelateUnmatchedEventDropped);
+        }
+    }
+
+    /// Nests an event, and the event it's joined with if any, in the fields of their sides.
+    fn join(&self, side: Side, log: LogEvent, other: Option<LogEvent>) -> Event {
+        let (value, mut metadata) = log.into_parts();
+        let mut fields = vec![(side, value)];
+        if let Some(other) = other {
+            let (other_value, other_metadata) = other.into_parts();
+            metadata.merge(other_metadata);
+            fields.push((side.other(), other_value));
+        }
+
+        let mut joined = LogEvent::from_parts(Value::Object(Default::default()), metadata);
+        for (side, value) in fields {
+            joined.insert((PathPrefix::Event, &self.sides[side as usize].field), value);
+        }
+        joined.into()
+    }
+}
+
+impl TaskTransform<Event> for Correlate {
+    fn transform(
+        mut self: Box<Self>,
+        mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
+    where
+        Self: 'static,
+    {
+        let mut flush_stream = tokio::time::interval(self.window.min(Duration::from_secs(1)));
+
+        Box::pin(stream! {
+            let mut output = Vec::new();
+            let mut done = false;
+            while !done {
+                tokio::select! {
+                    _ = flush_stream.tick() => {
+                        self.flush_into(&mut output, Instant::now());
+                    },
+                    maybe_event = input_rx.next() => {
+                        match maybe_event {
+                            None => {
+                                self.flush_all_into(&mut output);
+                                done = true;
+                            }
+                            Some(event) => self.record(&mut output, event, Instant::now()),
+                        }
+                    }
+                };
+                for event in output.drain(..) {
+                    yield event;
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use tokio::sync::mpsc;
+    use tokio_stream::wrappers::ReceiverStream;
+
+    use super::*;
+    use crate::{
+        test_util::components::assert_transform_compliance, transforms::test::create_topology,
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<CorrelateConfig>();
+    }
+
+    fn correlate(options: &str, spill: Option<(Spill, usize)>) -> Correlate {
+        let config = toml::from_str::<CorrelateConfig>(&format!(
+            r#"
+            window_secs = 10
+            {}
+
+            [left]
+            inputs = ["queries"]
+            key = ["id"]
+            field = "query"
+
+            [right]
+            inputs = ["responses"]
+            key = ["query_id"]
+            "#,
+            options
+        ))
+        .unwrap();
+        Correlate::new(&config, spill).unwrap()
+    }
+
+    fn event(input: &str, key: &str, id: i64) -> Event {
+        let mut log = LogEvent::from(input);
+        log.insert(key, id);
+        let mut event = Event::from(log);
+        event.set_upstream_id(Arc::new(OutputId::from(input)));
+        event
+    }
+
+    fn field<'a>(event: &'a Event, path: &str) -> Option<&'a Value> {
+        event.as_log().get(path)
+    }
+
+    fn ids(output: &[Event]) -> Vec<(Option<&Value>, Option<&Value>)> {
+        output
+            .iter()
+            .map(|event| (field(event, "query.id"), field(event, "right.query_id")))
+            .collect()
+    }
+
+    #[test]
+    fn joins_events_on_their_keys() {
+        let mut correlate = correlate("", None);
+        let now = Instant::now();
+        let mut output = Vec::new();
+
+        correlate.record(&mut output, event("queries", "id", 1), now);
+        correlate.record(&mut output, event("queries", "id", 2), now);
+        correlate.record(&mut output, event("responses", "query_id", 2), now);
+        correlate.record(&mut output, event("responses", "query_id", 1), now);
+        correlate.record(&mut output, event("other", "id", 1), now);
+
+        let (one, two) = (Value::from(1), Value::from(2));
+        assert_eq!(
+            ids(&output[..2]),
+            [(Some(&two), Some(&two)), (Some(&one), Some(&one))]
+        );
+        assert_eq!(
+            field(&output[0], "query.message"),
+            Some(&Value::from("queries"))
+        );
+        assert_eq!(field(&output[2], "message"), Some(&Value::from("other")));
+        assert!(correlate.waiting.is_empty() && correlate.keys.is_empty());
+    }
+
+    #[test]
+    fn emits_unmatched_events_after_the_window() {
+        let mut correlate = correlate("", None);
+        let now = Instant::now();
+        let mut output = Vec::new();
+
+        correlate.record(&mut output, event("queries", "id", 1), now);
+        correlate.record(&mut output, event("responses", "id", 2), now);
+        correlate.flush_into(&mut output, now + Duration::from_secs(9));
+        assert_eq!(output.len(), 1);
+        assert_eq!(field(&output[0], "right.id"), Some(&Value::from(2)));
+
+        correlate.flush_into(&mut output, now + Duration::from_secs(10));
+        assert_eq!(ids(&output[1..]), [(Some(&Value::from(1)), None)]);
+
+        correlate.record(&mut output, event("responses", "query_id", 1), now);
+        assert_eq!(correlate.waiting.len(), 1);
+    }
+
+    #[test]
+    fn drops_unmatched_events() {
+        let mut correlate = correlate("emit_unmatched = false", None);
+        let now = Instant::now();
+        let mut output = Vec::new();
+
+        correlate.record(&mut output, event("queries", "id", 1), now);
+        correlate.flush_all_into(&mut output);
+        assert!(output.is_empty());
+    }
+
+    #[test]
+    fn expires_the_oldest_events_when_full() {
+        let mut correlate = correlate("max_events = 2", None);
+        let now = Instant::now();
+        let mut output = Vec::new();
+
+        for id in 1..=3 {
+            correlate.record(&mut output, event("queries", "id", id), now);
+        }
+        assert_eq!(ids(&output), [(Some(&Value::from(1)), None)]);
+
+        correlate.record(&mut output, event("responses", "query_id", 3), now);
+        assert_eq!(
+            ids(&output[1..]),
+            [(Some(&Value::from(3)), Some(&Value::from(3)))]
+        );
+    }
+
+    #[test]
+    fn spills_events_to_disk() {
+        let dir = tempfile::tempdir().unwrap();
+        let spill = Spill::open(dir.path(), 1024 * 1024).unwrap();
+        let mut correlate = correlate("max_events = 1", Some((spill, 1)));
+        let now = Instant::now();
+        let mut output = Vec::new();
+
+        for id in 1..=3 {
+            correlate.record(&mut output, event("queries", "id", id), now);
+        }
+        assert_eq!(ids(&output), [(Some(&Value::from(1)), None)]);
+        assert_eq!(correlate.in_memory, 1);
+
+        // The second event was spilled, since the first one was still waiting in memory.
+        correlate.record(&mut output, event("responses", "query_id", 2), now);
+        assert_eq!(
+            ids(&output[1..]),
+            [(Some(&Value::from(2)), Some(&Value::from(2)))]
+        );
+        assert_eq!(correlate.spill.as_ref().unwrap().0.len(), 0);
+    }
+
+    #[test]
+    fn rejects_invalid_configs() {
+        for config in [
+            r#"
+            left = { inputs = ["a"], key = ["id"] }
+            right = { inputs = ["b"], key = ["id", "host"] }
+            "#,
+            r#"
+            left = { inputs = ["a"], key = ["id"] }
+            right = { inputs = ["a"], key = ["id"] }
+            "#,
+            r#"
+            left = { inputs = [], key = ["id"] }
+            right = { inputs = ["b"], key = ["id"] }
+            "#,
+        ] {
+            let config = toml::from_str::<CorrelateConfig>(config).unwrap();
+            assert!(Correlate::new(&config, None).is_err());
+        }
+    }
+
+    #[tokio::test]
+    async fn emits_unmatched_events_at_shutdown() {
+        let config = toml::from_str::<CorrelateConfig>(
+            r#"
+            left = { inputs = ["in"], key = ["id"] }
+            right = { inputs = ["other"], key = ["id"] }
+            "#,
+        )
+        .unwrap();
+
+        assert_transform_compliance(async move {
+            let (tx, rx) = mpsc::channel(1);
+            let (topology, mut out) = create_topology(ReceiverStream::new(rx), config).await;
+
+            let mut log = LogEvent::from("request");
+            log.insert("id", 1);
+            tx.send(log.into()).await.unwrap();
+            drop(tx);
+
+            let unmatched = out.recv().await.unwrap();
+            assert_eq!(field(&unmatched, "left.id"), Some(&Value::from(1)));
+            assert_eq!(field(&unmatched, "right"), None);
+
+            topology.stop().await;
+            assert_eq!(out.recv().await, None);
+        })
+        .await
+    }
+}
@@ -0,0 +1,122 @@
+//! The events waiting to be joined by the `correlate` transform, spilled to disk.
+
+use std::path::Path;
+
+use prost::Message;
+use snafu::{OptionExt, ResultExt, Snafu};
+
+use crate::event::{proto::EventWrapper, Event, EventMetadata, WithMetadata};
+
+#[derive(Debug, Snafu)]
+pub enum SpillError {
+    #[snafu(display("Unable to access the spilled events: {}", source))]
+    Database { source: sled::Error },
+    #[snafu(display("Unable to decode a spilled event: {}", source))]
+    Decode { source: prost::DecodeError },
+    #[snafu(display("The spilled event {} is missing", seq))]
+    Missing { seq: u64 },
+}
+
+/// The events spilled to a [sled] database, by sequence number.
+///
+/// Only the data of the events is written to disk. Their metadata, which includes the finalizers
+/// that acknowledge them, is kept in memory by the caller, so the events spilled by a previous run
+/// are discarded when the database is opened.
+pub struct Spill {
+    tree: sled::Tree,
+    // The database must outlive its tree.
+    _db: sled::Db,
+    len: usize,
+}
+
+impl Spill {
+    pub fn open(path: &Path, max_memory_bytes: u64) -> sled::Result<Self> {
+        let db = sled::Config::new()
+            .path(path)
+            .cache_capacity(max_memory_bytes)
+            .open()?;
+        let tree = db.open_tree("correlate")?;
+        tree.clear()?;
+
+        Ok(Self {
+            tree,
+            _db: db,
+            len: 0,
+        })
+    }
+
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Writes the data of an event, and returns its metadata.
+    ///
+    /// The event is returned as-is if it can't be written.
+    pub fn write(&mut self, seq: u64, event: Event) -> Result<EventMetadata, (SpillError, Event)> {
+        let WithMetadata { data, metadata } = WithMetadata::<EventWrapper>::from(event);
+
+        match self.tree.insert(seq.to_be_bytes(), data.encode_to_vec()) {
+            Ok(_) => {
+                self.len += 1;
+                Ok(metadata)
+            }
+            Err(source) => Err((SpillError::Database { source }, restore(data, metadata))),
+        }
+    }
+
+    /// Reads back and removes a spilled event, with the metadata returned when it was written.
+    pub fn read(&mut self, seq: u64, metadata: EventMetadata) -> Result<Event, SpillError> {
+        let bytes = self
+            .tree
+            .remove(seq.to_be_bytes())
+            .context(DatabaseSnafu)?
+            .context(MissingSnafu { seq })?;
+        self.len -= 1;
+
+        let data = EventWrapper::decode(&bytes[..]).context(DecodeSnafu)?;
+        Ok(restore(data, metadata))
+    }
+}
+
+fn restore(data: EventWrapper, metadata: EventMetadata) -> Event {
+    let mut event = Event::from(data);
+    *event.metadata_mut() = metadata;
+    event
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::LogEvent;
+
+    #[test]
+    fn reads_back_spilled_events() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut spill = Spill::open(dir.path(), 1024 * 1024).unwrap();
+
+        let mut log = LogEvent::from("query");
+        log.insert("id", 42);
+        let event = Event::from(log);
+
+        let metadata = spill.write(7, event.clone()).unwrap();
+        assert_eq!(spill.len(), 1);
+        assert_eq!(spill.read(7, metadata).unwrap(), event);
+        assert_eq!(spill.len(), 0);
+        assert!(spill.read(7, EventMetadata::default()).is_err());
+    }
+
+    #[test]
+    fn discards_the_events_of_previous_runs() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut spill = Spill::open(dir.path(), 1024 * 1024).unwrap();
+        spill
+            .write(0, LogEvent::from("stale").into())
+            .map_err(|(error, _)| error)
+            .unwrap();
+        drop(spill);
+
+        let mut spill = Spill::open(dir.path(), 1024 * 1024).unwrap();
+        assert_eq!(spill.len(), 0);
+        assert!(spill.read(0, EventMetadata::default()).is_err());
+    }
+}
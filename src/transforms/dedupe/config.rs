@@ -1,5 +1,7 @@
+use std::time::Duration;
+
 use vector_lib::{
-    config::{clone_input_definitions, LogNamespace},
+    config::{clone_input_definitions, ComponentKey, LogNamespace},
     configurable::configurable_component,
 };
 
@@ -14,6 +16,7 @@ use crate::{
 
 use super::{
     common::{default_cache_config, fill_default_fields_match, CacheConfig, FieldMatchConfig},
+    disk::DiskCache,
     transform::Dedupe,
 };
 
@@ -44,11 +47,30 @@ impl GenerateConfig for DedupeConfig {
 #[async_trait::async_trait]
 #[typetag::serde(name = "dedupe")]
 impl TransformConfig for DedupeConfig {
-    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
-        Ok(Transform::event_task(Dedupe::new(
-            self.cache.num_events,
-            fill_default_fields_match(self.fields.as_ref()),
-        )))
+    async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
+        let fields = fill_default_fields_match(self.fields.as_ref());
+        let ttl = self
+            .cache
+            .ttl_secs
+            .map(|ttl_secs| Duration::from_secs(ttl_secs.get()));
+
+        let dedupe = match &self.cache.disk {
+            Some(disk) => {
+                let id = context.key.as_ref().map_or("dedupe", ComponentKey::id);
+                let path = context
+                    .globals
+                    .resolve_and_make_data_subdir(disk.data_dir.as_ref(), id)?;
+                let cache =
+                    DiskCache::open(&path, self.cache.num_events, ttl, disk.max_memory_bytes)
+                        .map_err(|error| {
+                            format!("Unable to open the disk cache in {:?}: {}", path, error)
+                        })?;
+                Dedupe::with_disk_cache(cache, fields)
+            }
+            None => Dedupe::new(self.cache.num_events, ttl, fields),
+        };
+
+        Ok(Transform::event_task(dedupe))
     }
 
     fn input(&self) -> Input {
@@ -83,7 +105,10 @@ mod tests {
         event::{Event, LogEvent, ObjectMap, Value},
         test_util::components::assert_transform_compliance,
         transforms::{
-            dedupe::config::{CacheConfig, DedupeConfig, FieldMatchConfig},
+            dedupe::{
+                common::DiskCacheConfig,
+                config::{CacheConfig, DedupeConfig, FieldMatchConfig},
+            },
             test::create_topology,
         },
     };
@@ -100,6 +125,8 @@ mod tests {
         DedupeConfig {
             cache: CacheConfig {
                 num_events: std::num::NonZeroUsize::new(num_events).expect("non-zero num_events"),
+                ttl_secs: None,
+                disk: None,
             },
             fields: Some(FieldMatchConfig::MatchFields(fields)),
         }
@@ -116,6 +143,8 @@ mod tests {
         DedupeConfig {
             cache: CacheConfig {
                 num_events: std::num::NonZeroUsize::new(num_events).expect("non-zero num_events"),
+                ttl_secs: None,
+                disk: None,
             },
             fields: Some(FieldMatchConfig::IgnoreFields(fields)),
         }
@@ -133,6 +162,28 @@ mod tests {
         basic(transform_config, "matched", "unmatched").await;
     }
 
+    #[tokio::test]
+    async fn dedupe_match_disk_basic() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let mut transform_config = make_match_transform_config(5, vec!["matched".into()]);
+        transform_config.cache.disk = Some(DiskCacheConfig {
+            data_dir: Some(data_dir.path().to_path_buf()),
+            max_memory_bytes: 1024 * 1024,
+        });
+        basic(transform_config, "matched", "unmatched").await;
+    }
+
+    #[tokio::test]
+    async fn dedupe_ignore_disk_basic() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let mut transform_config = make_ignore_transform_config(5, vec!["unmatched".into()]);
+        transform_config.cache.disk = Some(DiskCacheConfig {
+            data_dir: Some(data_dir.path().to_path_buf()),
+            max_memory_bytes: 1024 * 1024,
+        });
+        basic(transform_config, "matched", "unmatched").await;
+    }
+
     #[tokio::test]
     async fn dedupe_ignore_with_metadata_field() {
         let transform_config = make_ignore_transform_config(5, vec!["%ignored".into()]);
@@ -0,0 +1,187 @@
+//! A cache of the events seen by the `dedupe` transform, persisted on disk.
+
+use std::{num::NonZeroUsize, path::Path, time::Duration};
+
+/// The prefix of the keys of the entries, which map the digest of an entry to its sequence number
+/// and the time it was first seen.
+const ENTRY_PREFIX: u8 = b'e';
+
+/// The prefix of the keys of the insertion order, which map the sequence number of an entry to its
+/// digest and the time it was first seen.
+const ORDER_PREFIX: u8 = b'o';
+
+/// A bounded cache of digests of entries, persisted in a [sled] database.
+///
+/// Both the entries and their insertion order are kept in a single tree, so that each insertion and
+/// the evictions it causes are applied atomically. Entries are evicted in the order they were first
+/// seen, once the cache is full or once they have expired.
+pub struct DiskCache {
+    tree: sled::Tree,
+    // The database must outlive its tree, and is flushed when dropped.
+    _db: sled::Db,
+    num_events: usize,
+    ttl: Option<Duration>,
+    len: usize,
+    next_seq: u64,
+}
+
+impl DiskCache {
+    /// Opens the cache in the given directory, with the entries persisted by a previous run.
+    pub fn open(
+        path: &Path,
+        num_events: NonZeroUsize,
+        ttl: Option<Duration>,
+        max_memory_bytes: u64,
+    ) -> sled::Result<Self> {
+        let db = sled::Config::new()
+            .path(path)
+            .cache_capacity(max_memory_bytes)
+            .open()?;
+        let tree = db.open_tree("dedupe")?;
+
+        let len = tree.scan_prefix([ENTRY_PREFIX]).count();
+        let next_seq = match tree.scan_prefix([ORDER_PREFIX]).next_back().transpose()? {
+            Some((key, _)) => decode_u64(&key[1..]) + 1,
+            None => 0,
+        };
+
+        Ok(Self {
+            tree,
+            _db: db,
+            num_events: num_events.get(),
+            ttl,
+            len,
+            next_seq,
+        })
+    }
+
+    /// Inserts the digest of an entry seen at `now`, in milliseconds since the Unix epoch.
+    ///
+    /// Returns `true` if the entry was already in the cache and hasn't expired, in which case the
+    /// time it was first seen is kept.
+    pub fn insert(&mut self, digest: &[u8], now: u64) -> sled::Result<bool> {
+        let entry_key = key(ENTRY_PREFIX, digest);
+        let mut batch = sled::Batch::default();
+        let mut len = self.len;
+
+        let replaced = match self.tree.get(&entry_key)? {
+            Some(value) => {
+                let seen = decode_u64(&value[8..]);
+                if !self.is_expired(seen, now) {
+                    return Ok(true);
+                }
+                let replaced = key(ORDER_PREFIX, &value[..8]);
+                batch.remove(replaced.clone());
+                Some(replaced)
+            }
+            None => {
+                len += 1;
+                None
+            }
+        };
+
+        let seq = self.next_seq.to_be_bytes();
+        batch.insert(entry_key, [seq, now.to_be_bytes()].concat());
+        batch.insert(
+            key(ORDER_PREFIX, &seq),
+            [digest, &now.to_be_bytes()].concat(),
+        );
+
+        for item in self.tree.scan_prefix([ORDER_PREFIX]) {
+            let (order_key, value) = item?;
+            if replaced.as_deref() == Some(&order_key[..]) {
+                continue;
+            }
+            let (evicted, seen) = value.split_at(value.len() - 8);
+            if len <= self.num_events && !self.is_expired(decode_u64(seen), now) {
+                break;
+            }
+            batch.remove(order_key);
+            batch.remove(key(ENTRY_PREFIX, evicted));
+            len -= 1;
+        }
+
+        self.tree.apply_batch(batch)?;
+        self.len = len;
+        self.next_seq += 1;
+        Ok(false)
+    }
+
+    fn is_expired(&self, seen: u64, now: u64) -> bool {
+        self.ttl.map_or(false, |ttl| {
+            now.saturating_sub(seen) >= ttl.as_millis() as u64
+        })
+    }
+}
+
+fn key(prefix: u8, suffix: &[u8]) -> Vec<u8> {
+    let mut key = Vec::with_capacity(suffix.len() + 1);
+    key.push(prefix);
+    key.extend_from_slice(suffix);
+    key
+}
+
+fn decode_u64(bytes: &[u8]) -> u64 {
+    let mut buf = [0; 8];
+    buf.copy_from_slice(&bytes[..8]);
+    u64::from_be_bytes(buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn open(path: &Path, num_events: usize, ttl: Option<Duration>) -> DiskCache {
+        DiskCache::open(
+            path,
+            NonZeroUsize::new(num_events).unwrap(),
+            ttl,
+            1024 * 1024,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn persists_entries_across_restarts() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let mut cache = open(dir.path(), 10, None);
+        assert!(!cache.insert(b"first", 0).unwrap());
+        assert!(cache.insert(b"first", 1).unwrap());
+        drop(cache);
+
+        let mut cache = open(dir.path(), 10, None);
+        assert!(cache.insert(b"first", 2).unwrap());
+        assert!(!cache.insert(b"second", 3).unwrap());
+    }
+
+    #[test]
+    fn evicts_the_oldest_entries() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let mut cache = open(dir.path(), 2, None);
+        assert!(!cache.insert(b"first", 0).unwrap());
+        assert!(!cache.insert(b"second", 1).unwrap());
+        assert!(!cache.insert(b"third", 2).unwrap());
+        assert!(cache.insert(b"second", 3).unwrap());
+        assert!(!cache.insert(b"first", 4).unwrap());
+        drop(cache);
+
+        // The bound is enforced on the entries persisted with a larger one.
+        let mut cache = open(dir.path(), 1, None);
+        assert!(!cache.insert(b"fourth", 5).unwrap());
+        assert!(!cache.insert(b"first", 6).unwrap());
+    }
+
+    #[test]
+    fn expires_entries() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let mut cache = open(dir.path(), 10, Some(Duration::from_millis(100)));
+        assert!(!cache.insert(b"first", 0).unwrap());
+        assert!(cache.insert(b"first", 99).unwrap());
+        assert!(!cache.insert(b"first", 100).unwrap());
+        assert!(cache.insert(b"first", 150).unwrap());
+        assert_eq!(cache.len, 1);
+    }
+}
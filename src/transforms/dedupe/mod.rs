@@ -1,12 +1,18 @@
 #[cfg(feature = "transforms-dedupe")]
 pub mod config;
 
+#[cfg(feature = "transforms-impl-dedupe")]
+pub mod disk;
+
 #[cfg(feature = "transforms-impl-dedupe")]
 pub mod transform;
 
 #[cfg(feature = "transforms-impl-dedupe")]
 pub mod common {
-    use std::num::NonZeroUsize;
+    use std::{
+        num::{NonZeroU64, NonZeroUsize},
+        path::PathBuf,
+    };
 
     use vector_lib::{configurable::configurable_component, lookup::lookup_v2::ConfigTargetPath};
 
@@ -19,14 +25,65 @@ pub mod common {
     pub struct CacheConfig {
         /// Number of events to cache and use for comparing incoming events to previously seen events.
         pub num_events: NonZeroUsize,
+
+        /// The maximum age of the events in the cache, in seconds.
+        ///
+        /// Incoming events are only compared to the previously seen events that are younger, and
+        /// older events are evicted from the cache. An event whose match expired is passed through,
+        /// and starts a new deduplication window. By default, events are only evicted once the cache
+        /// is full.
+        #[serde(default)]
+        #[configurable(metadata(docs::examples = 86400))]
+        #[configurable(metadata(docs::human_name = "Time To Live"))]
+        #[configurable(metadata(docs::type_unit = "seconds"))]
+        pub ttl_secs: Option<NonZeroU64>,
+
+        #[configurable(derived)]
+        #[serde(default)]
+        pub disk: Option<DiskCacheConfig>,
     }
 
     pub fn default_cache_config() -> CacheConfig {
         CacheConfig {
             num_events: NonZeroUsize::new(5000).expect("static non-zero number"),
+            ttl_secs: None,
+            disk: None,
         }
     }
 
+    const fn default_max_memory_bytes() -> u64 {
+        64 * 1024 * 1024
+    }
+
+    /// Persists the cache on disk, instead of keeping it in memory.
+    ///
+    /// The cache then survives restarts of Vector, and can hold many more events than fit in
+    /// memory, which allows deduplicating events over windows of hours or days. Only a digest of
+    /// the matched fields is stored for each event. The oldest events are evicted first once the
+    /// cache is full.
+    #[configurable_component]
+    #[derive(Clone, Debug)]
+    #[serde(deny_unknown_fields)]
+    pub struct DiskCacheConfig {
+        /// The directory used to persist the cache.
+        ///
+        /// By default, the [global `data_dir` option][global_data_dir] is used.
+        /// Make sure the running user has write permissions to this directory.
+        ///
+        /// If this directory is specified, then Vector will attempt to create it.
+        ///
+        /// [global_data_dir]: https://vector.dev/docs/reference/configuration/global-options/#data_dir
+        #[serde(default)]
+        #[configurable(metadata(docs::examples = "/var/local/lib/vector/"))]
+        #[configurable(metadata(docs::human_name = "Data Directory"))]
+        pub data_dir: Option<PathBuf>,
+
+        /// The maximum size of the page cache of the database in memory, in bytes.
+        #[serde(default = "default_max_memory_bytes")]
+        #[configurable(metadata(docs::type_unit = "bytes"))]
+        pub max_memory_bytes: u64,
+    }
+
     /// Options to control what fields to match against.
     ///
     /// When no field matching configuration is specified, events are matched using the `timestamp`,
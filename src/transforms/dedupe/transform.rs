@@ -1,23 +1,37 @@
-use std::{future::ready, num::NonZeroUsize, pin::Pin};
+use std::{
+    future::ready,
+    num::NonZeroUsize,
+    pin::Pin,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
 
 use bytes::Bytes;
 use futures::{Stream, StreamExt};
 use lru::LruCache;
+use sha2::{Digest, Sha256};
 use vector_lib::lookup::lookup_v2::ConfigTargetPath;
 use vrl::path::OwnedTargetPath;
 
 use crate::{
     event::{Event, Value},
-    internal_events::DedupeEventsDropped,
+    internal_events::{DedupeCacheError, DedupeEventsDropped},
     transforms::TaskTransform,
 };
 
-use super::common::FieldMatchConfig;
+use super::{common::FieldMatchConfig, disk::DiskCache};
 
-#[derive(Clone)]
 pub struct Dedupe {
     fields: FieldMatchConfig,
-    cache: LruCache<CacheEntry, bool>,
+    cache: Cache,
+}
+
+enum Cache {
+    /// The entries, with the time they were first seen.
+    Memory {
+        entries: LruCache<CacheEntry, Instant>,
+        ttl: Option<Duration>,
+    },
+    Disk(DiskCache),
 }
 
 type TypeId = u8;
@@ -66,17 +80,91 @@ const fn type_id_for_value(val: &Value) -> TypeId {
     }
 }
 
+impl CacheEntry {
+    /// A digest of the entry, which identifies it in the disk cache.
+    fn digest(&self) -> [u8; 32] {
+        fn update_value(hasher: &mut Sha256, type_id: TypeId, bytes: &Bytes) {
+            hasher.update([type_id]);
+            hasher.update((bytes.len() as u64).to_be_bytes());
+            hasher.update(bytes);
+        }
+
+        let mut hasher = Sha256::new();
+        match self {
+            Self::Match(fields) => {
+                hasher.update([0]);
+                for field in fields {
+                    match field {
+                        Some((type_id, bytes)) => {
+                            hasher.update([1]);
+                            update_value(&mut hasher, *type_id, bytes);
+                        }
+                        None => hasher.update([0]),
+                    }
+                }
+            }
+            Self::Ignore(fields) => {
+                hasher.update([1]);
+                for (path, type_id, bytes) in fields {
+                    let path = path.to_string();
+                    hasher.update((path.len() as u64).to_be_bytes());
+                    hasher.update(path);
+                    update_value(&mut hasher, *type_id, bytes);
+                }
+            }
+        }
+        hasher.finalize().into()
+    }
+}
+
 impl Dedupe {
-    pub fn new(num_entries: NonZeroUsize, fields: FieldMatchConfig) -> Self {
+    pub fn new(num_entries: NonZeroUsize, ttl: Option<Duration>, fields: FieldMatchConfig) -> Self {
+        Self {
+            fields,
+            cache: Cache::Memory {
+                entries: LruCache::new(num_entries),
+                ttl,
+            },
+        }
+    }
+
+    pub fn with_disk_cache(cache: DiskCache, fields: FieldMatchConfig) -> Self {
         Self {
             fields,
-            cache: LruCache::new(num_entries),
+            cache: Cache::Disk(cache),
         }
     }
 
     pub fn transform_one(&mut self, event: Event) -> Option<Event> {
         let cache_entry = build_cache_entry(&event, &self.fields);
-        if self.cache.put(cache_entry, true).is_some() {
+        let duplicate = match &mut self.cache {
+            Cache::Memory { entries, ttl } => {
+                let now = Instant::now();
+                match entries.get(&cache_entry) {
+                    Some(seen) if ttl.map_or(true, |ttl| now.duration_since(*seen) < ttl) => true,
+                    _ => {
+                        entries.put(cache_entry, now);
+                        false
+                    }
+                }
+            }
+            Cache::Disk(cache) => {
+                let now = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_millis() as u64;
+                // Events are passed through when the cache can't be used, as they can't be told
+                // apart from new events.
+                cache
+                    .insert(&cache_entry.digest(), now)
+                    .unwrap_or_else(|error| {
+                        emit!(DedupeCacheError { error });
+                        false
+                    })
+            }
+        };
+
+        if duplicate {
             emit!(DedupeEventsDropped { count: 1 });
             None
         } else {
@@ -0,0 +1,586 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    num::NonZeroUsize,
+    path::PathBuf,
+    pin::Pin,
+    process::Stdio,
+    time::Duration,
+};
+
+use async_stream::stream;
+use bytes::Bytes;
+use futures::{stream::SelectAll, Stream, StreamExt};
+use serde_with::serde_as;
+use tokio::{
+    io::AsyncWriteExt,
+    process::{Child, ChildStdin, Command},
+    sync::mpsc,
+    time::Instant,
+};
+use tokio_util::codec::{FramedRead, LinesCodec, LinesCodecError};
+use vector_lib::config::LogNamespace;
+use vector_lib::configurable::configurable_component;
+use vector_lib::schema::Definition;
+
+use crate::{
+    config::{
+        DataType, GenerateConfig, Input, OutputId, TransformConfig, TransformContext,
+        TransformOutput,
+    },
+    event::{Event, EventMetadata, LogEvent, Value},
+    internal_events::{
+        ExecTransformExitedError, ExecTransformInvalidOutputError, ExecTransformSpawnError,
+        ExecTransformTimeoutError,
+    },
+    schema,
+    transforms::{TaskTransform, Transform},
+};
+
+/// The maximum number of events written to a process before their output is read.
+const MAX_PENDING_EVENTS: usize = 1000;
+
+/// The maximum length of the lines read from a process. Longer lines are discarded.
+const MAX_LINE_BYTES: usize = 16 * 1024 * 1024;
+
+/// The period of the checks for the timed out events, and for the processes to restart.
+const CHECK_PERIOD: Duration = Duration::from_millis(100);
+
+const fn default_concurrency() -> NonZeroUsize {
+    NonZeroUsize::MIN
+}
+
+const fn default_timeout() -> Duration {
+    Duration::from_secs(30)
+}
+
+const fn default_restart_delay() -> Duration {
+    Duration::from_secs(1)
+}
+
+/// Configuration for the `exec` transform.
+#[serde_as]
+#[configurable_component(transform(
+    "exec",
+    "Pipe events through a long-running process, for logic that cannot be expressed in VRL."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ExecConfig {
+    /// The command to run, plus any arguments required.
+    ///
+    /// Each event is written to the standard input of the command as a line of JSON, and the
+    /// command must write exactly one line of JSON to its standard output for each event, in the
+    /// same order: an object for the transformed event, an array of objects to replace the event
+    /// with several events, or `null` to drop the event.
+    #[configurable(metadata(docs::examples = "python3", docs::examples = "./transform.py"))]
+    pub command: Vec<String>,
+
+    /// Custom environment variables to set or update when running the command.
+    /// If a variable name already exists in the environment, its value is replaced.
+    #[serde(default)]
+    #[configurable(metadata(docs::additional_props_description = "An environment variable."))]
+    #[configurable(metadata(docs::examples = "environment_examples()"))]
+    pub environment: Option<HashMap<String, String>>,
+
+    /// The directory in which to run the command.
+    pub working_directory: Option<PathBuf>,
+
+    /// The number of processes of the command to run, between which the events are distributed.
+    #[serde(default = "default_concurrency")]
+    pub concurrency: NonZeroUsize,
+
+    /// The maximum amount of time, in seconds, to wait for the output of an event.
+    ///
+    /// If the command does not write the output of an event in time, it is killed and restarted,
+    /// and the events it has not written the output of yet are dropped.
+    #[serde(default = "default_timeout")]
+    #[serde_as(as = "serde_with::DurationSecondsWithFrac<f64>")]
+    #[configurable(metadata(docs::human_name = "Timeout"))]
+    pub timeout_secs: Duration,
+
+    /// The amount of time, in seconds, to wait before restarting the command, after it exits or
+    /// fails to start.
+    ///
+    /// The events the command has not written the output of yet when it exits are dropped.
+    #[serde(default = "default_restart_delay")]
+    #[serde_as(as = "serde_with::DurationSecondsWithFrac<f64>")]
+    #[configurable(metadata(docs::human_name = "Restart Delay"))]
+    pub restart_delay_secs: Duration,
+}
+
+fn environment_examples() -> HashMap<String, String> {
+    HashMap::<_, _>::from_iter([
+        ("LANG".to_owned(), "es_ES.UTF-8".to_owned()),
+        ("TZ".to_owned(), "Etc/UTC".to_owned()),
+    ])
+}
+
+impl GenerateConfig for ExecConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            command: vec!["./transform.py".to_owned()],
+            environment: None,
+            working_directory: None,
+            concurrency: default_concurrency(),
+            timeout_secs: default_timeout(),
+            restart_delay_secs: default_restart_delay(),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "exec")]
+impl TransformConfig for ExecConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        if self.command.is_empty() {
+            return Err("`command` must not be empty".into());
+        }
+        Ok(Transform::event_task(Exec::new(self)))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn outputs(
+        &self,
+        _: vector_lib::enrichment::TableRegistry,
+        input_definitions: &[(OutputId, schema::Definition)],
+        _: LogNamespace,
+    ) -> Vec<TransformOutput> {
+        // The fields of the events are set by the command, and only known at runtime.
+        let definitions = input_definitions
+            .iter()
+            .map(|(output, definition)| {
+                (
+                    output.clone(),
+                    Definition::default_for_namespace(definition.log_namespaces()),
+                )
+            })
+            .collect();
+        vec![TransformOutput::new(DataType::Log, definitions)]
+    }
+}
+
+/// An event written to a process, waiting for its output.
+struct Pending {
+    metadata: EventMetadata,
+    deadline: Instant,
+}
+
+struct Process {
+    _child: Child,
+    stdin: mpsc::UnboundedSender<Bytes>,
+    pending: VecDeque<Pending>,
+}
+
+enum Slot {
+    Running(Process),
+    Restarting(Instant),
+}
+
+/// A line written by a process, or `None` once its standard output is closed.
+struct Output {
+    slot: usize,
+    generation: u64,
+    line: Option<Result<String, LinesCodecError>>,
+}
+
+type Outputs = Pin<Box<dyn Stream<Item = Output> + Send>>;
+
+pub struct Exec {
+    command: Vec<String>,
+    command_line: String,
+    environment: Option<HashMap<String, String>>,
+    working_directory: Option<PathBuf>,
+    concurrency: usize,
+    timeout: Duration,
+    restart_delay: Duration,
+}
+
+impl Exec {
+    pub fn new(config: &ExecConfig) -> Self {
+        Self {
+            command: config.command.clone(),
+            command_line: config.command.join(" "),
+            environment: config.environment.clone(),
+            working_directory: config.working_directory.clone(),
+            concurrency: config.concurrency.get(),
+            timeout: config.timeout_secs,
+            restart_delay: config.restart_delay_secs,
+        }
+    }
+
+    fn spawn(&self, slot: usize, generation: u64) -> std::io::Result<(Process, Outputs)> {
+        let mut command = Command::new(&self.command[0]);
+        command
+            .args(&self.command[1..])
+            .kill_on_drop(true)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            // The diagnostics of the command are written along with the ones of Vector.
+            .stderr(Stdio::inherit());
+        if let Some(environment) = &self.environment {
+            command.envs(environment);
+        }
+        if let Some(working_directory) = &self.working_directory {
+            command.current_dir(working_directory);
+        }
+
+        let mut child = command.spawn()?;
+        let stdin = child.stdin.take().expect("stdin is piped");
+        let stdout = child.stdout.take().expect("stdout is piped");
+
+        let (stdin_tx, stdin_rx) = mpsc::unbounded_channel();
+        tokio::spawn(write_lines(stdin, stdin_rx));
+
+        let outputs = FramedRead::new(stdout, LinesCodec::new_with_max_length(MAX_LINE_BYTES))
+            .map(Some)
+            .chain(futures::stream::once(async { None }))
+            .map(move |line| Output {
+                slot,
+                generation,
+                line,
+            });
+
+        let process = Process {
+            _child: child,
+            stdin: stdin_tx,
+            pending: VecDeque::new(),
+        };
+        Ok((process, Box::pin(outputs)))
+    }
+
+    /// Starts the processes, in the slots whose restart delay has elapsed.
+    fn start(
+        &self,
+        slots: &mut [Slot],
+        generations: &mut [u64],
+        outputs: &mut SelectAll<Outputs>,
+        now: Instant,
+    ) {
+        for (index, slot) in slots.iter_mut().enumerate() {
+            if !matches!(slot, Slot::Restarting(at) if *at <= now) {
+                continue;
+            }
+
+            generations[index] += 1;
+            match self.spawn(index, generations[index]) {
+                Ok((process, output)) => {
+                    outputs.push(output);
+                    *slot = Slot::Running(process);
+                }
+                Err(error) => {
+                    emit!(ExecTransformSpawnError {
+                        command: &self.command_line,
+                        error,
+                    });
+                    *slot = Slot::Restarting(now + self.restart_delay);
+                }
+            }
+        }
+    }
+
+    /// Writes an event to the running process with the fewest pending events.
+    fn dispatch(&self, slots: &mut [Slot], event: Event, now: Instant) {
+        let process = slots
+            .iter_mut()
+            .filter_map(|slot| match slot {
+                Slot::Running(process) => Some(process),
+                Slot::Restarting(_) => None,
+            })
+            .min_by_key(|process| process.pending.len())
+            .expect("events are only read while a process is running");
+
+        let (value, metadata) = event.into_log().into_parts();
+        let mut line = serde_json::to_vec(&value).expect("values are serializable");
+        line.push(b'\n');
+
+        process.pending.push_back(Pending {
+            metadata,
+            deadline: now + self.timeout,
+        });
+        // If the process exited, the event is dropped once its standard output is closed.
+        _ = process.stdin.send(line.into());
+    }
+
+    /// Handles the output of an event, or the exit of a process.
+    fn handle_output(
+        &self,
+        slots: &mut [Slot],
+        generations: &[u64],
+        output: Output,
+        events: &mut Vec<Event>,
+        now: Instant,
+    ) {
+        // Ignore the outputs of the processes which were already replaced.
+        if generations[output.slot] != output.generation {
+            return;
+        }
+        let Slot::Running(process) = &mut slots[output.slot] else {
+            return;
+        };
+
+        match output.line {
+            Some(line) => match process.pending.pop_front() {
+                Some(pending) => self.parse_output(line, pending.metadata, events),
+                None => emit!(ExecTransformInvalidOutputError {
+                    command: &self.command_line,
+                    error: "the output does not match any event".to_owned(),
+                }),
+            },
+            None => {
+                emit!(ExecTransformExitedError {
+                    command: &self.command_line,
+                    count: process.pending.len(),
+                });
+                slots[output.slot] = Slot::Restarting(now + self.restart_delay);
+            }
+        }
+    }
+
+    fn parse_output(
+        &self,
+        line: Result<String, LinesCodecError>,
+        metadata: EventMetadata,
+        events: &mut Vec<Event>,
+    ) {
+        let parsed = line
+            .map_err(|error| error.to_string())
+            .and_then(|line| serde_json::from_str(&line).map_err(|error| error.to_string()));
+        let values = match parsed {
+            Ok(serde_json::Value::Null) => Vec::new(),
+            Ok(serde_json::Value::Array(values)) => values,
+            Ok(value) => vec![value],
+            Err(error) => {
+                emit!(ExecTransformInvalidOutputError {
+                    command: &self.command_line,
+                    error,
+                });
+                return;
+            }
+        };
+
+        if let Some(value) = values.iter().find(|value| !value.is_object()) {
+            emit!(ExecTransformInvalidOutputError {
+                command: &self.command_line,
+                error: format!("expected an object, found `{value}`"),
+            });
+            return;
+        }
+        events.extend(
+            values.into_iter().map(|value| {
+                Event::from(LogEvent::from_parts(Value::from(value), metadata.clone()))
+            }),
+        );
+    }
+
+    /// Kills the processes which did not write the output of an event in time.
+    fn expire(&self, slots: &mut [Slot], now: Instant) {
+        for slot in slots.iter_mut() {
+            let Slot::Running(process) = slot else {
+                continue;
+            };
+            if process
+                .pending
+                .front()
+                .map_or(false, |pending| pending.deadline <= now)
+            {
+                emit!(ExecTransformTimeoutError {
+                    command: &self.command_line,
+                    timeout: self.timeout,
+                    count: process.pending.len(),
+                });
+                // Replacing the process drops the child, which kills it.
+                *slot = Slot::Restarting(now + self.restart_delay);
+            }
+        }
+    }
+}
+
+async fn write_lines(mut stdin: ChildStdin, mut lines: mpsc::UnboundedReceiver<Bytes>) {
+    while let Some(line) = lines.recv().await {
+        if stdin.write_all(&line).await.is_err() {
+            // The process exited, which is handled once its standard output is closed.
+            break;
+        }
+    }
+    // Dropping the standard input closes it, signaling the end of the events to the process.
+}
+
+impl TaskTransform<Event> for Exec {
+    fn transform(
+        self: Box<Self>,
+        mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
+    where
+        Self: 'static,
+    {
+        Box::pin(stream! {
+            let mut check = tokio::time::interval(CHECK_PERIOD);
+            let now = Instant::now();
+            let mut slots = (0..self.concurrency)
+                .map(|_| Slot::Restarting(now))
+                .collect::<Vec<_>>();
+            let mut generations = vec![0; self.concurrency];
+            let mut outputs = SelectAll::new();
+            let mut input_done = false;
+            let mut events = Vec::new();
+
+            loop {
+                let pending = slots
+                    .iter()
+                    .map(|slot| match slot {
+                        Slot::Running(process) => process.pending.len(),
+                        Slot::Restarting(_) => 0,
+                    })
+                    .sum::<usize>();
+                if input_done && pending == 0 {
+                    break;
+                }
+
+                self.start(&mut slots, &mut generations, &mut outputs, Instant::now());
+                let accepting = !input_done
+                    && slots.iter().any(|slot| {
+                        matches!(slot, Slot::Running(process) if process.pending.len() < MAX_PENDING_EVENTS)
+                    });
+
+                // The outputs are handled before the next events, so that the processes that exited
+                // are restarted before the next events are written to them.
+                tokio::select! {
+                    biased;
+
+                    _ = check.tick() => self.expire(&mut slots, Instant::now()),
+                    Some(output) = outputs.next(), if !outputs.is_empty() => {
+                        self.handle_output(&mut slots, &generations, output, &mut events, Instant::now());
+                    }
+                    maybe_event = input_rx.next(), if accepting => match maybe_event {
+                        None => input_done = true,
+                        Some(event) => self.dispatch(&mut slots, event, Instant::now()),
+                    },
+                }
+
+                for event in events.drain(..) {
+                    yield event;
+                }
+            }
+        })
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use futures::stream;
+    use tokio::sync::mpsc;
+    use tokio_stream::wrappers::ReceiverStream;
+
+    use super::*;
+
+    fn config(script: &str) -> ExecConfig {
+        ExecConfig {
+            command: vec!["sh".to_owned(), "-c".to_owned(), script.to_owned()],
+            environment: None,
+            working_directory: None,
+            concurrency: default_concurrency(),
+            timeout_secs: Duration::from_secs(5),
+            restart_delay_secs: Duration::from_millis(100),
+        }
+    }
+
+    fn log(value: serde_json::Value) -> Event {
+        Event::from_json_value(value, LogNamespace::Legacy).unwrap()
+    }
+
+    async fn run(config: ExecConfig, events: Vec<Event>) -> Vec<serde_json::Value> {
+        let transform = Box::new(Exec::new(&config));
+        transform
+            .transform(Box::pin(stream::iter(events)))
+            .map(|event| serde_json::to_value(event.as_log()).unwrap())
+            .collect()
+            .await
+    }
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<ExecConfig>();
+    }
+
+    #[tokio::test]
+    async fn pipes_events_through_the_command() {
+        let events = (0..100)
+            .map(|id| log(serde_json::json!({ "id": id })))
+            .collect();
+        let mut config = config("cat");
+        config.concurrency = NonZeroUsize::new(3).unwrap();
+
+        let mut outputs = run(config, events).await;
+        outputs.sort_by_key(|output| output["id"].as_i64());
+        assert_eq!(
+            outputs,
+            (0..100)
+                .map(|id| serde_json::json!({ "id": id }))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[tokio::test]
+    async fn expands_and_drops_events() {
+        let script = r#"
+            while IFS= read -r line; do
+                case "$line" in
+                    *drop*) echo null ;;
+                    *split*) echo '[{"part": 1}, {"part": 2}]' ;;
+                    *) echo 'not json' ;;
+                esac
+            done
+        "#;
+        let events = vec![
+            log(serde_json::json!({ "action": "split" })),
+            log(serde_json::json!({ "action": "drop" })),
+            log(serde_json::json!({ "action": "fail" })),
+            log(serde_json::json!({ "action": "split" })),
+        ];
+
+        let outputs = run(config(script), events).await;
+        assert_eq!(
+            outputs,
+            vec![
+                serde_json::json!({ "part": 1 }),
+                serde_json::json!({ "part": 2 }),
+                serde_json::json!({ "part": 1 }),
+                serde_json::json!({ "part": 2 }),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn drops_timed_out_events() {
+        let mut config = config("sleep 10");
+        config.timeout_secs = Duration::from_millis(200);
+
+        let started = Instant::now();
+        let outputs = run(config, vec![log(serde_json::json!({ "id": 1 }))]).await;
+        assert!(outputs.is_empty());
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn restarts_the_command() {
+        // Each process transforms a single event, and then exits.
+        let (tx, rx) = mpsc::channel(1);
+        let transform = Box::new(Exec::new(&config(r#"read -r line; echo "$line""#)));
+        let mut output = transform.transform(Box::pin(ReceiverStream::new(rx)));
+
+        for id in 0..3 {
+            let event = log(serde_json::json!({ "id": id }));
+            tx.send(event.clone()).await.unwrap();
+            assert_eq!(output.next().await.unwrap(), event);
+            // Leave the time for the process to exit, and to be restarted.
+            tokio::time::sleep(Duration::from_millis(300)).await;
+        }
+
+        drop(tx);
+        assert!(output.next().await.is_none());
+    }
+}
@@ -0,0 +1,282 @@
+use vector_lib::config::LogNamespace;
+use vector_lib::configurable::configurable_component;
+use vector_lib::lookup::lookup_v2::ConfigTargetPath;
+use vrl::value::{kind::Collection, Kind};
+
+use crate::{
+    config::{
+        DataType, GenerateConfig, Input, OutputId, TransformConfig, TransformContext,
+        TransformOutput,
+    },
+    event::{Event, LogEvent, Value},
+    internal_events::ExplodeEmptyArray,
+    schema,
+    transforms::{FunctionTransform, OutputBuffer, Transform},
+};
+
+/// Configuration for the `explode` transform.
+#[configurable_component(transform(
+    "explode",
+    "Split the elements of an array field into individual events."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ExplodeConfig {
+    /// The field containing the array to split into events.
+    ///
+    /// Events without the field, or in which the field is not an array, are forwarded
+    /// unmodified. Events in which the field is an empty array are dropped.
+    #[configurable(metadata(docs::examples = "records"))]
+    #[configurable(metadata(docs::examples = "body.items"))]
+    pub field: ConfigTargetPath,
+
+    /// Whether the fields of the original event are kept in the events of its elements.
+    ///
+    /// When enabled, the fields of each element that is an object are merged into a copy of the
+    /// original event without the array, replacing the fields of the same name. When disabled,
+    /// each element that is an object becomes the event on its own.
+    ///
+    /// Elements that are not objects are stored in `field`, in a copy of the original event or
+    /// in an otherwise empty event.
+    #[serde(default = "crate::serde::default_true")]
+    pub merge_parent_fields: bool,
+}
+
+impl GenerateConfig for ExplodeConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(r#"field = "records""#).unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "explode")]
+impl TransformConfig for ExplodeConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        Ok(Transform::function(Explode::new(self)))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn outputs(
+        &self,
+        _: vector_lib::enrichment::TableRegistry,
+        input_definitions: &[(OutputId, schema::Definition)],
+        _: LogNamespace,
+    ) -> Vec<TransformOutput> {
+        // The fields of the elements are not known until the events are processed.
+        let definitions = input_definitions
+            .iter()
+            .map(|(output, definition)| {
+                let mut definition = definition.clone();
+                *definition.event_kind_mut() = Kind::object(Collection::any());
+                (output.clone(), definition)
+            })
+            .collect();
+
+        vec![TransformOutput::new(DataType::Log, definitions)]
+    }
+
+    fn enable_concurrency(&self) -> bool {
+        true
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Explode {
+    field: ConfigTargetPath,
+    merge_parent_fields: bool,
+}
+
+impl Explode {
+    pub fn new(config: &ExplodeConfig) -> Self {
+        Self {
+            field: config.field.clone(),
+            merge_parent_fields: config.merge_parent_fields,
+        }
+    }
+
+    fn element_event(&self, parent: &LogEvent, element: Value) -> LogEvent {
+        let mut log = if self.merge_parent_fields {
+            parent.clone()
+        } else {
+            LogEvent::from_parts(Value::Object(Default::default()), parent.metadata().clone())
+        };
+
+        match element {
+            Value::Object(fields) if !self.merge_parent_fields => {
+                *log.value_mut() = Value::Object(fields);
+            }
+            Value::Object(fields) => match log.as_map_mut() {
+                Some(root) => root.extend(fields),
+                None => {
+                    log.insert(&self.field.0, Value::Object(fields));
+                }
+            },
+            element => {
+                log.insert(&self.field.0, element);
+            }
+        }
+        log
+    }
+}
+
+impl FunctionTransform for Explode {
+    fn transform(&mut self, output: &mut OutputBuffer, event: Event) {
+        let mut log = event.into_log();
+        let elements = match log.remove(&self.field.0) {
+            Some(Value::Array(elements)) => elements,
+            Some(value) => {
+                log.insert(&self.field.0, value);
+                output.push(log.into());
+                return;
+            }
+            None => {
+                output.push(log.into());
+                return;
+            }
+        };
+
+        if elements.is_empty() {
+            emit!(ExplodeEmptyArray);
+            return;
+        }
+
+        for element in elements {
+            output.push(self.element_event(&log, element).into());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::mpsc;
+    use tokio_stream::wrappers::ReceiverStream;
+    use vrl::value;
+
+    use super::*;
+    use crate::{
+        test_util::components::assert_transform_compliance, transforms::test::create_topology,
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<ExplodeConfig>();
+    }
+
+    fn explode(config: &str, log: LogEvent) -> Vec<LogEvent> {
+        let config = toml::from_str::<ExplodeConfig>(config).unwrap();
+        let mut output = OutputBuffer::default();
+        Explode::new(&config).transform(&mut output, log.into());
+        output.into_events().map(Event::into_log).collect()
+    }
+
+    fn log(value: Value) -> LogEvent {
+        LogEvent::from(value)
+    }
+
+    #[test]
+    fn merges_parent_fields() {
+        let events = explode(
+            r#"field = "records""#,
+            log(value!({
+                "source": "webhook",
+                "id": "batch",
+                "records": [{"id": 1, "name": "first"}, {"id": 2}, "text"],
+            })),
+        );
+
+        let events = events
+            .into_iter()
+            .map(|log| log.into_parts().0)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            events,
+            vec![
+                value!({"source": "webhook", "id": 1, "name": "first"}),
+                value!({"source": "webhook", "id": 2}),
+                value!({"source": "webhook", "id": "batch", "records": "text"}),
+            ]
+        );
+    }
+
+    #[test]
+    fn replaces_events_with_elements() {
+        let events = explode(
+            r#"
+            field = "body.items"
+            merge_parent_fields = false
+            "#,
+            log(value!({
+                "source": "webhook",
+                "body": {"items": [{"id": 1}, 2]},
+            })),
+        );
+
+        let events = events
+            .into_iter()
+            .map(|log| log.into_parts().0)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            events,
+            vec![value!({"id": 1}), value!({"body": {"items": 2}})]
+        );
+    }
+
+    #[test]
+    fn shares_metadata() {
+        let mut parent = log(value!({"records": [1, 2]}));
+        parent.metadata_mut().set_source_type("http_server");
+
+        for event in explode(
+            r#"
+            field = "records"
+            merge_parent_fields = false
+            "#,
+            parent,
+        ) {
+            assert_eq!(event.metadata().source_type(), Some("http_server"));
+        }
+    }
+
+    #[test]
+    fn forwards_events_without_arrays() {
+        let missing = log(value!({"message": "text"}));
+        assert_eq!(
+            explode(r#"field = "records""#, missing.clone()),
+            vec![missing]
+        );
+
+        let scalar = log(value!({"records": "text"}));
+        assert_eq!(
+            explode(r#"field = "records""#, scalar.clone()),
+            vec![scalar]
+        );
+
+        let empty = log(value!({"records": []}));
+        assert!(explode(r#"field = "records""#, empty).is_empty());
+    }
+
+    #[tokio::test]
+    async fn emits_events_of_elements() {
+        assert_transform_compliance(async {
+            let config = toml::from_str::<ExplodeConfig>(r#"field = "records""#).unwrap();
+            let (tx, rx) = mpsc::channel(1);
+            let (topology, mut out) = create_topology(ReceiverStream::new(rx), config).await;
+
+            tx.send(log(value!({"records": [{"n": 1}, {"n": 2}]})).into())
+                .await
+                .unwrap();
+            for n in 1..=2 {
+                let event = out.recv().await.unwrap().into_log();
+                assert_eq!(event["n"], n.into());
+            }
+
+            drop(tx);
+            topology.stop().await;
+            assert_eq!(out.recv().await, None);
+        })
+        .await;
+    }
+}
@@ -0,0 +1,325 @@
+use std::collections::HashMap;
+
+/// The token of a template standing for any token.
+pub const WILDCARD: &str = "<*>";
+
+/// The parameters of the [Drain][drain] algorithm.
+///
+/// [drain]: https://jiemingzhu.github.io/pub/pjhe_icws2017.pdf
+#[derive(Clone, Copy, Debug)]
+pub struct Parameters {
+    /// The number of tokens of the messages used to find their clusters in the prefix tree.
+    pub depth: usize,
+    /// The minimum ratio of the tokens of a message matching a template, for the message to be
+    /// part of its cluster.
+    pub similarity_threshold: f64,
+    /// The maximum number of children of a node of the prefix tree.
+    pub max_children: usize,
+    /// The maximum number of clusters, above which the least recently matched ones are evicted.
+    pub max_clusters: usize,
+}
+
+/// A cluster of messages, and the template matching all of them.
+#[derive(Debug)]
+pub struct Cluster {
+    tokens: Vec<String>,
+    /// The keys of the nodes of the prefix tree leading to the cluster.
+    path: Vec<String>,
+    last_matched: u64,
+}
+
+impl Cluster {
+    pub fn template(&self) -> String {
+        self.tokens.join(" ")
+    }
+
+    /// Returns the number of tokens of a message matching the template, or `None` if the message
+    /// doesn't have as many tokens as the template.
+    fn similarity(&self, tokens: &[&str]) -> Option<usize> {
+        (self.tokens.len() == tokens.len()).then(|| {
+            self.tokens
+                .iter()
+                .zip(tokens)
+                .filter(|(template, token)| template == token)
+                .count()
+        })
+    }
+
+    /// Replaces the tokens of the template which don't match the message by a wildcard, returning
+    /// whether the template changed.
+    fn update(&mut self, tokens: &[&str]) -> bool {
+        let mut changed = false;
+        for (template, token) in self.tokens.iter_mut().zip(tokens) {
+            if template != token && template != WILDCARD {
+                *template = WILDCARD.to_owned();
+                changed = true;
+            }
+        }
+        changed
+    }
+}
+
+#[derive(Debug, Default)]
+struct Node {
+    children: HashMap<String, Node>,
+    clusters: Vec<u64>,
+}
+
+/// The result of adding a message to the clusters.
+#[derive(Debug, Eq, PartialEq)]
+pub enum Match {
+    /// The message created a new cluster.
+    New(u64),
+    /// The message matched an existing cluster, whose template may have changed.
+    Existing { id: u64, changed: bool },
+}
+
+impl Match {
+    pub const fn id(&self) -> u64 {
+        match self {
+            Self::New(id) | Self::Existing { id, .. } => *id,
+        }
+    }
+}
+
+/// Messages clustered with the [Drain][drain] algorithm.
+///
+/// The messages are split into tokens on whitespace, and their clusters are looked up in a prefix
+/// tree by their number of tokens and their first tokens, then chosen by the similarity of their
+/// template with the message. Tokens containing digits are likely to be variable, and are matched
+/// in the prefix tree as wildcards.
+///
+/// [drain]: https://jiemingzhu.github.io/pub/pjhe_icws2017.pdf
+#[derive(Debug)]
+pub struct Drain {
+    parameters: Parameters,
+    root: Node,
+    clusters: HashMap<u64, Cluster>,
+    next_id: u64,
+    matches: u64,
+}
+
+impl Drain {
+    pub fn new(parameters: Parameters) -> Self {
+        Self {
+            parameters,
+            root: Node::default(),
+            clusters: HashMap::new(),
+            next_id: 1,
+            matches: 0,
+        }
+    }
+
+    pub fn cluster(&self, id: u64) -> Option<&Cluster> {
+        self.clusters.get(&id)
+    }
+
+    /// Adds a message to its cluster, creating a new cluster if none matches it.
+    pub fn add(&mut self, message: &str) -> Match {
+        let tokens = message.split_whitespace().collect::<Vec<_>>();
+        self.matches += 1;
+
+        let path = self.path(&tokens);
+        let node = path
+            .iter()
+            .fold(&self.root, |node, key| &node.children[key]);
+
+        let threshold = self.parameters.similarity_threshold * tokens.len() as f64;
+        let best = node
+            .clusters
+            .iter()
+            .filter_map(|id| {
+                let similarity = self.clusters[id].similarity(&tokens)?;
+                (similarity as f64 >= threshold).then_some((similarity, *id))
+            })
+            // The oldest cluster is chosen between the ones with the same similarity.
+            .max_by_key(|&(similarity, id)| (similarity, std::cmp::Reverse(id)));
+
+        if let Some((_, id)) = best {
+            let cluster = self.clusters.get_mut(&id).expect("cluster of a node");
+            cluster.last_matched = self.matches;
+            let changed = cluster.update(&tokens);
+            return Match::Existing { id, changed };
+        }
+
+        if self.clusters.len() >= self.parameters.max_clusters {
+            self.evict();
+        }
+
+        let id = self.next_id;
+        self.next_id += 1;
+        let node = path.iter().fold(&mut self.root, |node, key| {
+            node.children.entry(key.clone()).or_default()
+        });
+        node.clusters.push(id);
+        self.clusters.insert(
+            id,
+            Cluster {
+                tokens: tokens.into_iter().map(str::to_owned).collect(),
+                path,
+                last_matched: self.matches,
+            },
+        );
+        Match::New(id)
+    }
+
+    /// Returns the keys of the nodes of the prefix tree leading to the clusters of a message,
+    /// creating the nodes missing from the tree.
+    fn path(&mut self, tokens: &[&str]) -> Vec<String> {
+        let mut path = vec![tokens.len().to_string()];
+        let mut node = self.root.children.entry(path[0].clone()).or_default();
+
+        // The first level of the tree is the number of tokens, and the last one holds the clusters.
+        for token in tokens.iter().take(self.parameters.depth.saturating_sub(2)) {
+            let key = if token.chars().any(|c| c.is_ascii_digit()) {
+                WILDCARD
+            } else {
+                *token
+            };
+            let key = if node.children.contains_key(key)
+                || node.children.len() < self.parameters.max_children
+            {
+                key
+            } else {
+                WILDCARD
+            };
+            node = node.children.entry(key.to_owned()).or_default();
+            path.push(key.to_owned());
+        }
+        path
+    }
+
+    /// Removes the least recently matched cluster.
+    fn evict(&mut self) {
+        let Some(id) = self
+            .clusters
+            .iter()
+            .min_by_key(|(_, cluster)| cluster.last_matched)
+            .map(|(id, _)| *id)
+        else {
+            return;
+        };
+
+        let cluster = self.clusters.remove(&id).expect("evicted cluster");
+        let mut node = &mut self.root;
+        for key in &cluster.path {
+            node = node.children.get_mut(key).expect("node of a cluster");
+        }
+        node.clusters.retain(|cluster| *cluster != id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PARAMETERS: Parameters = Parameters {
+        depth: 4,
+        similarity_threshold: 0.4,
+        max_children: 100,
+        max_clusters: 1000,
+    };
+
+    fn template(drain: &Drain, found: Match) -> String {
+        drain.cluster(found.id()).unwrap().template()
+    }
+
+    #[test]
+    fn clusters_similar_messages() {
+        let mut drain = Drain::new(PARAMETERS);
+
+        assert_eq!(drain.add("connected to 10.0.0.1 in 5ms"), Match::New(1));
+        assert_eq!(
+            drain.add("connected to 10.0.0.2 in 7ms"),
+            Match::Existing {
+                id: 1,
+                changed: true
+            }
+        );
+        assert_eq!(
+            drain.add("connected to 10.0.0.3 in 7ms"),
+            Match::Existing {
+                id: 1,
+                changed: false
+            }
+        );
+        assert_eq!(
+            drain.cluster(1).unwrap().template(),
+            "connected to <*> in <*>"
+        );
+
+        // Messages with a different number of tokens, or different first tokens, are never part of
+        // the same cluster.
+        let found = drain.add("connected to 10.0.0.1");
+        assert_eq!(found, Match::New(2));
+        assert_eq!(template(&drain, found), "connected to 10.0.0.1");
+        assert_eq!(
+            drain.add("disconnected from 10.0.0.1 in 5ms"),
+            Match::New(3)
+        );
+    }
+
+    #[test]
+    fn requires_similar_messages() {
+        let mut drain = Drain::new(PARAMETERS);
+
+        assert_eq!(
+            drain.add("request completed with status ok today"),
+            Match::New(1)
+        );
+        // Only the first two tokens match, below the similarity threshold.
+        assert_eq!(
+            drain.add("request completed but the connection reset"),
+            Match::New(2)
+        );
+        assert_eq!(
+            drain.add("request completed with status error today"),
+            Match::Existing {
+                id: 1,
+                changed: true
+            }
+        );
+        assert_eq!(
+            drain.cluster(1).unwrap().template(),
+            "request completed with status <*> today"
+        );
+    }
+
+    #[test]
+    fn limits_the_children_of_the_nodes() {
+        let mut drain = Drain::new(Parameters {
+            max_children: 2,
+            ..PARAMETERS
+        });
+
+        assert_eq!(drain.add("alpha started"), Match::New(1));
+        assert_eq!(drain.add("beta started"), Match::New(2));
+        // The tokens beyond the limit share a wildcard node.
+        assert_eq!(drain.add("gamma started"), Match::New(3));
+        assert_eq!(
+            drain.add("delta started"),
+            Match::Existing {
+                id: 3,
+                changed: true
+            }
+        );
+        assert_eq!(drain.cluster(3).unwrap().template(), "<*> started");
+    }
+
+    #[test]
+    fn evicts_the_least_recently_matched_clusters() {
+        let mut drain = Drain::new(Parameters {
+            max_clusters: 2,
+            ..PARAMETERS
+        });
+
+        assert_eq!(drain.add("first message"), Match::New(1));
+        assert_eq!(drain.add("second message here"), Match::New(2));
+        drain.add("first message");
+        assert_eq!(drain.add("third message is here"), Match::New(3));
+
+        assert!(drain.cluster(1).is_some());
+        assert!(drain.cluster(2).is_none());
+        assert_eq!(drain.add("second message here"), Match::New(4));
+    }
+}
@@ -0,0 +1,474 @@
+use std::{
+    collections::HashMap,
+    num::{NonZeroU64, NonZeroUsize},
+    pin::Pin,
+    time::Duration,
+};
+
+use async_stream::stream;
+use chrono::Utc;
+use futures::{Stream, StreamExt};
+use tokio_stream::wrappers::IntervalStream;
+use vector_lib::config::LogNamespace;
+use vector_lib::configurable::configurable_component;
+use vector_lib::lookup::lookup_v2::ConfigTargetPath;
+use vrl::value::Kind;
+
+use crate::{
+    config::{DataType, Input, OutputId, TransformConfig, TransformContext, TransformOutput},
+    event::{
+        metric::{Metric, MetricKind, MetricTags, MetricValue},
+        Event,
+    },
+    schema,
+    transforms::{TaskTransform, Transform},
+};
+
+mod drain;
+
+use drain::{Drain, Match, Parameters};
+
+/// Configuration for the `log_cluster` transform.
+#[configurable_component(transform(
+    "log_cluster",
+    "Cluster log messages into patterns with the Drain algorithm.",
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct LogClusterConfig {
+    /// The field containing the messages to cluster.
+    ///
+    /// By default, the message of the events is used. Events without the field are forwarded
+    /// unmodified.
+    #[configurable(metadata(docs::examples = "message"))]
+    pub field: Option<ConfigTargetPath>,
+
+    /// The field in which the identifier of the pattern of each event is stored.
+    ///
+    /// The identifiers are assigned to the patterns in the order in which they are found, and are
+    /// not preserved across restarts of Vector.
+    #[serde(default = "default_pattern_id_field")]
+    pub pattern_id_field: ConfigTargetPath,
+
+    /// The field in which the template of the pattern of each event is stored.
+    ///
+    /// The template is the message, with the tokens varying between the messages of the pattern
+    /// replaced by `<*>`.
+    #[serde(default = "default_template_field")]
+    pub template_field: ConfigTargetPath,
+
+    /// The field in which is stored whether each event is the first one of its pattern.
+    ///
+    /// This can be used to detect new kinds of messages.
+    #[configurable(metadata(docs::examples = "new_pattern"))]
+    pub new_pattern_field: Option<ConfigTargetPath>,
+
+    /// The number of levels of the prefix tree used to find the pattern of each message.
+    ///
+    /// The first level is the number of tokens of the messages, the last one holds the patterns,
+    /// and each level in between is one of the first tokens of the messages. Messages with a
+    /// different number of tokens, or different first tokens, are never part of the same pattern.
+    #[serde(default = "default_depth")]
+    pub depth: usize,
+
+    /// The minimum ratio of the tokens of a message matching a pattern for the message to be part
+    /// of it, between `0` and `1`.
+    ///
+    /// Lower values result in fewer, more general patterns.
+    #[serde(default = "default_similarity_threshold")]
+    pub similarity_threshold: f64,
+
+    /// The maximum number of children of each node of the prefix tree.
+    ///
+    /// Tokens beyond this limit are treated as variable.
+    #[serde(default = "default_max_children")]
+    pub max_children: NonZeroUsize,
+
+    /// The maximum number of patterns.
+    ///
+    /// When the limit is reached, the least recently matched pattern is forgotten to make room for
+    /// a new one.
+    #[serde(default = "default_max_patterns")]
+    pub max_patterns: NonZeroUsize,
+
+    #[configurable(derived)]
+    pub metrics: Option<MetricsConfig>,
+}
+
+/// Configuration for the metrics counting the events of each pattern.
+///
+/// The metrics are incremental counters emitted alongside the log events, tagged with the
+/// `pattern_id` and the `pattern` template of their pattern.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct MetricsConfig {
+    /// The name of the metrics.
+    #[serde(default = "default_metric_name")]
+    pub name: String,
+
+    /// The interval between the emissions of the metrics, in seconds.
+    ///
+    /// Only the patterns matched during the interval are counted.
+    #[serde(default = "default_metrics_interval_secs")]
+    #[configurable(metadata(docs::human_name = "Metrics Interval"))]
+    pub interval_secs: NonZeroU64,
+}
+
+fn default_pattern_id_field() -> ConfigTargetPath {
+    ConfigTargetPath::from("pattern_id")
+}
+
+fn default_template_field() -> ConfigTargetPath {
+    ConfigTargetPath::from("pattern")
+}
+
+const fn default_depth() -> usize {
+    4
+}
+
+const fn default_similarity_threshold() -> f64 {
+    0.4
+}
+
+fn default_max_children() -> NonZeroUsize {
+    NonZeroUsize::new(100).unwrap()
+}
+
+fn default_max_patterns() -> NonZeroUsize {
+    NonZeroUsize::new(1000).unwrap()
+}
+
+fn default_metric_name() -> String {
+    "log_cluster_events".to_string()
+}
+
+fn default_metrics_interval_secs() -> NonZeroU64 {
+    NonZeroU64::new(60).unwrap()
+}
+
+impl Default for LogClusterConfig {
+    fn default() -> Self {
+        Self {
+            field: None,
+            pattern_id_field: default_pattern_id_field(),
+            template_field: default_template_field(),
+            new_pattern_field: None,
+            depth: default_depth(),
+            similarity_threshold: default_similarity_threshold(),
+            max_children: default_max_children(),
+            max_patterns: default_max_patterns(),
+            metrics: None,
+        }
+    }
+}
+
+impl_generate_config_from_default!(LogClusterConfig);
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "log_cluster")]
+impl TransformConfig for LogClusterConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        LogCluster::new(self).map(Transform::event_task)
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn outputs(
+        &self,
+        _: vector_lib::enrichment::TableRegistry,
+        input_definitions: &[(OutputId, schema::Definition)],
+        _: LogNamespace,
+    ) -> Vec<TransformOutput> {
+        let definitions = input_definitions
+            .iter()
+            .map(|(output, definition)| {
+                // The fields are only set on the events containing a message.
+                let mut definition = definition
+                    .clone()
+                    .with_field(
+                        &self.pattern_id_field.0,
+                        Kind::integer().or_undefined(),
+                        None,
+                    )
+                    .with_field(&self.template_field.0, Kind::bytes().or_undefined(), None);
+                if let Some(field) = &self.new_pattern_field {
+                    definition =
+                        definition.with_field(&field.0, Kind::boolean().or_undefined(), None);
+                }
+                (output.clone(), definition)
+            })
+            .collect();
+
+        let data_type = if self.metrics.is_some() {
+            DataType::Log | DataType::Metric
+        } else {
+            DataType::Log
+        };
+        vec![TransformOutput::new(data_type, definitions)]
+    }
+}
+
+#[derive(Debug)]
+pub struct LogCluster {
+    field: Option<ConfigTargetPath>,
+    pattern_id_field: ConfigTargetPath,
+    template_field: ConfigTargetPath,
+    new_pattern_field: Option<ConfigTargetPath>,
+    drain: Drain,
+    metrics: Option<MetricsConfig>,
+    /// The number of events of each pattern since the metrics were last emitted.
+    counts: HashMap<u64, u64>,
+}
+
+impl LogCluster {
+    pub fn new(config: &LogClusterConfig) -> crate::Result<Self> {
+        if !(0.0..=1.0).contains(&config.similarity_threshold) {
+            return Err("the `similarity_threshold` must be between 0 and 1".into());
+        }
+        if config.depth < 2 {
+            return Err("the `depth` must be at least 2".into());
+        }
+
+        Ok(Self {
+            field: config.field.clone(),
+            pattern_id_field: config.pattern_id_field.clone(),
+            template_field: config.template_field.clone(),
+            new_pattern_field: config.new_pattern_field.clone(),
+            drain: Drain::new(Parameters {
+                depth: config.depth,
+                similarity_threshold: config.similarity_threshold,
+                max_children: config.max_children.get(),
+                max_clusters: config.max_patterns.get(),
+            }),
+            metrics: config.metrics.clone(),
+            counts: HashMap::new(),
+        })
+    }
+
+    fn record(&mut self, event: Event) -> Event {
+        let mut log = event.into_log();
+        let message = match &self.field {
+            Some(field) => log.get(&field.0),
+            None => log.get_message(),
+        };
+        let Some(message) = message.map(|message| message.to_string_lossy().into_owned()) else {
+            return log.into();
+        };
+
+        let found = self.drain.add(&message);
+        let id = found.id();
+        let template = self
+            .drain
+            .cluster(id)
+            .expect("cluster of a message")
+            .template();
+
+        log.insert(&self.pattern_id_field.0, id as i64);
+        log.insert(&self.template_field.0, template);
+        if let Some(field) = &self.new_pattern_field {
+            log.insert(&field.0, matches!(found, Match::New(_)));
+        }
+        if self.metrics.is_some() {
+            *self.counts.entry(id).or_default() += 1;
+        }
+
+        log.into()
+    }
+
+    fn flush_into(&mut self, output: &mut Vec<Event>) {
+        let Some(metrics) = &self.metrics else {
+            return;
+        };
+
+        let timestamp = Utc::now();
+        for (id, count) in self.counts.drain() {
+            let mut tags = MetricTags::default();
+            tags.replace("pattern_id".to_string(), id.to_string());
+            // The pattern may have been forgotten since its events were counted.
+            if let Some(cluster) = self.drain.cluster(id) {
+                tags.replace("pattern".to_string(), cluster.template());
+            }
+
+            let metric = Metric::new(
+                metrics.name.clone(),
+                MetricKind::Incremental,
+                MetricValue::Counter {
+                    value: count as f64,
+                },
+            )
+            .with_tags(Some(tags))
+            .with_timestamp(Some(timestamp));
+            output.push(metric.into());
+        }
+    }
+}
+
+impl TaskTransform<Event> for LogCluster {
+    fn transform(
+        mut self: Box<Self>,
+        mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
+    where
+        Self: 'static,
+    {
+        let mut flush_stream = match &self.metrics {
+            Some(metrics) => {
+                let interval = Duration::from_secs(metrics.interval_secs.get());
+                IntervalStream::new(tokio::time::interval_at(
+                    tokio::time::Instant::now() + interval,
+                    interval,
+                ))
+                .boxed()
+            }
+            None => futures::stream::pending().boxed(),
+        };
+
+        Box::pin(stream! {
+            let mut output = Vec::new();
+            let mut done = false;
+            while !done {
+                tokio::select! {
+                    _ = flush_stream.next() => {
+                        self.flush_into(&mut output);
+                    },
+                    maybe_event = input_rx.next() => {
+                        match maybe_event {
+                            None => {
+                                self.flush_into(&mut output);
+                                done = true;
+                            }
+                            Some(event) => output.push(self.record(event)),
+                        }
+                    }
+                };
+                for event in output.drain(..) {
+                    yield event;
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::mpsc;
+    use tokio_stream::wrappers::ReceiverStream;
+
+    use super::*;
+    use crate::{
+        event::LogEvent, test_util::components::assert_transform_compliance,
+        transforms::test::create_topology,
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<LogClusterConfig>();
+    }
+
+    fn log_cluster(config: &str) -> LogCluster {
+        let config = toml::from_str::<LogClusterConfig>(config).unwrap();
+        LogCluster::new(&config).unwrap()
+    }
+
+    #[test]
+    fn assigns_patterns() {
+        let mut cluster = log_cluster(r#"new_pattern_field = "new_pattern""#);
+
+        let first = cluster
+            .record(LogEvent::from("connected to 10.0.0.1 in 5ms").into())
+            .into_log();
+        assert_eq!(first["pattern_id"], 1.into());
+        assert_eq!(first["pattern"], "connected to 10.0.0.1 in 5ms".into());
+        assert_eq!(first["new_pattern"], true.into());
+
+        let second = cluster
+            .record(LogEvent::from("connected to 10.0.0.2 in 7ms").into())
+            .into_log();
+        assert_eq!(second["pattern_id"], 1.into());
+        assert_eq!(second["pattern"], "connected to <*> in <*>".into());
+        assert_eq!(second["new_pattern"], false.into());
+
+        let other = cluster
+            .record(LogEvent::from("disk full").into())
+            .into_log();
+        assert_eq!(other["pattern_id"], 2.into());
+        assert_eq!(other["new_pattern"], true.into());
+    }
+
+    #[test]
+    fn forwards_events_without_messages() {
+        let mut cluster = log_cluster(r#"field = "text""#);
+
+        let mut log = LogEvent::from("connected to 10.0.0.1 in 5ms");
+        log.insert("other", "value");
+        let event = cluster.record(log.clone().into());
+        assert_eq!(event.into_log(), log);
+    }
+
+    #[test]
+    fn counts_patterns() {
+        let mut cluster = log_cluster(
+            r#"
+            [metrics]
+            name = "patterns"
+            "#,
+        );
+
+        cluster.record(LogEvent::from("user alice logged in").into());
+        cluster.record(LogEvent::from("user bob logged in").into());
+        cluster.record(LogEvent::from("disk full").into());
+
+        let mut output = Vec::new();
+        cluster.flush_into(&mut output);
+        let mut metrics = output
+            .into_iter()
+            .map(Event::into_metric)
+            .collect::<Vec<_>>();
+        metrics.sort_by_key(|metric| metric.tag_value("pattern_id"));
+
+        assert_eq!(metrics.len(), 2);
+        assert_eq!(metrics[0].name(), "patterns");
+        assert_eq!(
+            metrics[0].tag_value("pattern").as_deref(),
+            Some("user <*> logged in")
+        );
+        assert_eq!(metrics[0].value(), &MetricValue::Counter { value: 2.0 });
+        assert_eq!(
+            metrics[1].tag_value("pattern").as_deref(),
+            Some("disk full")
+        );
+        assert_eq!(metrics[1].value(), &MetricValue::Counter { value: 1.0 });
+
+        // The counts are reset after each emission.
+        let mut output = Vec::new();
+        cluster.flush_into(&mut output);
+        assert!(output.is_empty());
+    }
+
+    #[test]
+    fn validates_the_parameters() {
+        let config = toml::from_str::<LogClusterConfig>("similarity_threshold = 1.5").unwrap();
+        assert!(LogCluster::new(&config).is_err());
+    }
+
+    #[tokio::test]
+    async fn emits_internal_events() {
+        assert_transform_compliance(async {
+            let config = LogClusterConfig::default();
+            let (tx, rx) = mpsc::channel(1);
+            let (topology, mut out) = create_topology(ReceiverStream::new(rx), config).await;
+
+            tx.send(LogEvent::from("disk full").into()).await.unwrap();
+            let event = out.recv().await.unwrap();
+            assert_eq!(event.as_log()["pattern"], "disk full".into());
+
+            drop(tx);
+            topology.stop().await;
+            assert_eq!(out.recv().await, None);
+        })
+        .await;
+    }
+}
@@ -88,6 +88,28 @@ pub struct CounterConfig {
     pub kind: MetricKind,
 }
 
+/// Specification of a histogram derived from a log event.
+///
+/// By default, each value is emitted as a distribution, whose samples are aggregated downstream.
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+pub struct HistogramConfig {
+    /// The upper limits of the buckets of the histogram, in ascending order.
+    ///
+    /// When set, the values are counted in the buckets whose limits they don't exceed, and
+    /// aggregated histograms are emitted instead of distributions.
+    #[serde(default)]
+    pub buckets: Vec<f64>,
+
+    /// Emits the values as [DDSketches][ddsketch], instead of distributions.
+    ///
+    /// This can't be combined with `buckets`.
+    ///
+    /// [ddsketch]: https://www.vldb.org/pvldb/vol12/p2195-masson.pdf
+    #[serde(default)]
+    pub sketch: bool,
+}
+
 /// Specification of a metric derived from a log event.
 // TODO: While we're resolving the schema for this enum somewhat reasonably (in
 // `generate-components-docs.rb`), we have a problem where an overlapping field (overlap between two
@@ -141,7 +163,7 @@ pub enum MetricTypeConfig {
     Counter(CounterConfig),
 
     /// A histogram.
-    Histogram,
+    Histogram(HistogramConfig),
 
     /// A gauge.
     Gauge,
@@ -159,6 +181,38 @@ impl MetricConfig {
     }
 }
 
+impl HistogramConfig {
+    fn validate(&self) -> crate::Result<()> {
+        if self.sketch && !self.buckets.is_empty() {
+            return Err("`buckets` and `sketch` can't both be set for a histogram".into());
+        }
+        if self.buckets.iter().any(|limit| !limit.is_finite())
+            || self.buckets.windows(2).any(|limits| limits[0] >= limits[1])
+        {
+            return Err(
+                "the `buckets` of a histogram must be finite and in ascending order".into(),
+            );
+        }
+        Ok(())
+    }
+
+    fn to_value(&self, value: f64) -> MetricValue {
+        let distribution = MetricValue::Distribution {
+            samples: vector_lib::samples![value => 1],
+            statistic: StatisticKind::Histogram,
+        };
+
+        if self.sketch {
+            distribution.distribution_to_sketch()
+        } else if !self.buckets.is_empty() {
+            distribution.distribution_to_agg_histogram(&self.buckets)
+        } else {
+            None
+        }
+        .unwrap_or(distribution)
+    }
+}
+
 const fn default_increment_by_value() -> bool {
     false
 }
@@ -195,6 +249,12 @@ impl GenerateConfig for LogToMetricConfig {
 #[typetag::serde(name = "log_to_metric")]
 impl TransformConfig for LogToMetricConfig {
     async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        for metric in &self.metrics {
+            if let MetricTypeConfig::Histogram(histogram) = &metric.metric {
+                histogram.validate()?;
+            }
+        }
+
         Ok(Transform::function(LogToMetric::new(self.clone())))
     }
 
@@ -379,7 +439,7 @@ fn to_metric_with_config(config: &MetricConfig, event: &Event) -> Result<Metric,
 
             (counter.kind, MetricValue::Counter { value })
         }
-        MetricTypeConfig::Histogram => {
+        MetricTypeConfig::Histogram(histogram) => {
             let value = value.to_string_lossy().parse().map_err(|error| {
                 TransformError::ParseFloatError {
                     path: field.to_string(),
@@ -387,13 +447,7 @@ fn to_metric_with_config(config: &MetricConfig, event: &Event) -> Result<Metric,
                 }
             })?;
 
-            (
-                MetricKind::Incremental,
-                MetricValue::Distribution {
-                    samples: vector_lib::samples![value => 1],
-                    statistic: StatisticKind::Histogram,
-                },
-            )
+            (MetricKind::Incremental, histogram.to_value(value))
         }
         MetricTypeConfig::Summary => {
             let value = value.to_string_lossy().parse().map_err(|error| {
@@ -897,7 +951,7 @@ mod tests {
     use crate::{
         config::log_schema,
         event::{
-            metric::{Metric, MetricKind, MetricValue, StatisticKind},
+            metric::{Metric, MetricKind, MetricSketch, MetricValue, StatisticKind},
             Event, LogEvent,
         },
     };
@@ -1564,6 +1618,76 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn response_time_histogram_with_buckets() {
+        let config = parse_config(
+            r#"
+            [[metrics]]
+            type = "histogram"
+            field = "response_time"
+            buckets = [0.5, 1.0, 5.0]
+            "#,
+        );
+
+        let event = create_event("response_time", "2.5");
+        let metric = do_transform(config, event).await.unwrap().into_metric();
+
+        assert_eq!(metric.kind(), MetricKind::Incremental);
+        assert_eq!(
+            metric.value(),
+            &MetricValue::AggregatedHistogram {
+                buckets: vector_lib::buckets![0.5 => 0, 1.0 => 0, 5.0 => 1],
+                count: 1,
+                sum: 2.5,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn response_time_histogram_with_sketch() {
+        let config = parse_config(
+            r#"
+            [[metrics]]
+            type = "histogram"
+            field = "response_time"
+            sketch = true
+            "#,
+        );
+
+        let event = create_event("response_time", "2.5");
+        let metric = do_transform(config, event).await.unwrap().into_metric();
+
+        match metric.value() {
+            MetricValue::Sketch {
+                sketch: MetricSketch::AgentDDSketch(sketch),
+            } => {
+                assert_eq!(sketch.count(), 1);
+                assert_eq!(sketch.sum(), Some(2.5));
+            }
+            value => panic!("unexpected metric value: {:?}", value),
+        }
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_histograms() {
+        for histogram in [
+            "buckets = [1.0, 0.5]",
+            "buckets = [0.5, 0.5]",
+            "buckets = [0.5, 1.0]\nsketch = true",
+        ] {
+            let config = parse_config(&format!(
+                "[[metrics]]\ntype = \"histogram\"\nfield = \"response_time\"\n{}",
+                histogram
+            ));
+
+            assert!(
+                config.build(&TransformContext::default()).await.is_err(),
+                "{}",
+                histogram
+            );
+        }
+    }
+
     #[tokio::test]
     async fn response_time_summary() {
         let config = parse_config(
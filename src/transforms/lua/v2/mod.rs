@@ -19,6 +19,7 @@ use crate::{
 };
 
 #[derive(Debug, Snafu)]
+#[snafu(visibility(pub(super)))]
 pub enum BuildError {
     #[snafu(display("Invalid \"search_dirs\": {}", source))]
     InvalidSearchDirs { source: mlua::Error },
@@ -88,7 +89,7 @@ pub struct LuaConfig {
     metric_tag_values: MetricTagValues,
 }
 
-fn default_config_paths() -> Vec<PathBuf> {
+pub(super) fn default_config_paths() -> Vec<PathBuf> {
     match CONFIG_PATHS.lock().ok() {
         Some(config_paths) => config_paths
             .clone()
@@ -111,7 +112,7 @@ fn default_config_paths() -> Vec<PathBuf> {
 #[configurable_component]
 #[derive(Clone, Debug)]
 #[serde(deny_unknown_fields)]
-struct HooksConfig {
+pub(super) struct HooksConfig {
     /// The function called when the first event comes in, before `hooks.process` is called.
     ///
     /// It can produce new events using the `emit` function.
@@ -122,7 +123,7 @@ struct HooksConfig {
         docs::examples = "function (emit)\n\t-- Custom Lua code here\nend",
         docs::examples = "init",
     ))]
-    init: Option<String>,
+    pub(super) init: Option<String>,
 
     /// The function called for each incoming event.
     ///
@@ -135,7 +136,7 @@ struct HooksConfig {
         docs::examples = "function (event, emit)\n\tevent.log.field = \"value\" -- set value of a field\n\tevent.log.another_field = nil -- remove field\n\tevent.log.first, event.log.second = nil, event.log.first -- rename field\n\t-- Very important! Emit the processed event.\n\temit(event)\nend",
         docs::examples = "process",
     ))]
-    process: String,
+    pub(super) process: String,
 
     /// The function called when the transform is stopped.
     ///
@@ -147,18 +148,18 @@ struct HooksConfig {
         docs::examples = "function (emit)\n\t-- Custom Lua code here\nend",
         docs::examples = "shutdown",
     ))]
-    shutdown: Option<String>,
+    pub(super) shutdown: Option<String>,
 }
 
 /// A Lua timer.
 #[serde_as]
 #[configurable_component]
 #[derive(Clone, Debug)]
-struct TimerConfig {
+pub(super) struct TimerConfig {
     /// The interval to execute the handler, in seconds.
     #[serde_as(as = "serde_with::DurationSeconds<u64>")]
     #[configurable(metadata(docs::human_name = "Interval"))]
-    interval_seconds: Duration,
+    pub(super) interval_seconds: Duration,
 
     /// The handler function which is called when the timer ticks.
     ///
@@ -168,7 +169,7 @@ struct TimerConfig {
     /// to call. In both cases, the closure/function takes a single parameter, `emit`, which is a
     /// reference to a function for emitting events.
     #[configurable(metadata(docs::examples = "timer_handler"))]
-    handler: String,
+    pub(super) handler: String,
 }
 
 impl LuaConfig {
@@ -213,7 +214,7 @@ impl LuaConfig {
 //
 // This constant is larger than 1 because calling GC is an expensive operation, so doing it
 // after each transform would have significant footprint on the performance.
-const GC_INTERVAL: usize = 16;
+pub(super) const GC_INTERVAL: usize = 16;
 
 pub struct Lua {
     lua: mlua::Lua,
@@ -227,7 +228,10 @@ pub struct Lua {
 }
 
 // Helper to create `RegistryKey` from Lua function code
-fn make_registry_value(lua: &mlua::Lua, source: &str) -> mlua::Result<mlua::RegistryKey> {
+pub(super) fn make_registry_value(
+    lua: &mlua::Lua,
+    source: &str,
+) -> mlua::Result<mlua::RegistryKey> {
     lua.load(source)
         .eval::<mlua::Function>()
         .and_then(|f| lua.create_registry_value(f))
@@ -358,7 +362,7 @@ impl Lua {
 }
 
 // A helper that reduces code duplication.
-fn wrap_emit_fn<'lua, 'scope, F: 'scope>(
+pub(super) fn wrap_emit_fn<'lua, 'scope, F: 'scope>(
     scope: &mlua::Scope<'lua, 'scope>,
     mut emit_fn: F,
     source_id: Arc<ComponentKey>,
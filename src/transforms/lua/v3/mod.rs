@@ -0,0 +1,632 @@
+use std::{path::PathBuf, pin::Pin, sync::Arc, time::Duration};
+
+use futures::{Stream, StreamExt};
+use snafu::ResultExt;
+use tokio::{runtime::Handle, sync::mpsc, task::spawn_blocking, time::Instant};
+use tokio_stream::wrappers::ReceiverStream;
+use vector_lib::codecs::MetricTagValues;
+use vector_lib::configurable::configurable_component;
+
+use super::v2::{
+    default_config_paths, make_registry_value, wrap_emit_fn, HooksConfig, TimerConfig, GC_INTERVAL,
+};
+use super::v2::{
+    InvalidHooksInitSnafu, InvalidHooksProcessSnafu, InvalidHooksShutdownSnafu, InvalidSourceSnafu,
+    InvalidTimerHandlerSnafu, RuntimeErrorGcSnafu, RuntimeErrorHooksInitSnafu,
+    RuntimeErrorHooksProcessSnafu, RuntimeErrorHooksShutdownSnafu, RuntimeErrorTimerHandlerSnafu,
+};
+use crate::config::{ComponentKey, OutputId};
+use crate::event::lua::event::LuaEvent;
+use crate::schema::Definition;
+use crate::{
+    config::{DataType, Input, TransformOutput},
+    event::Event,
+    internal_events::{LuaBuildError, LuaGcTriggered},
+    schema,
+    transforms::{TaskTransform, Transform},
+};
+
+mod state;
+mod timers;
+
+use self::{state::State, timers::Timers};
+
+const fn default_max_entries() -> usize {
+    10_000
+}
+
+const fn default_max_bytes() -> usize {
+    10 * 1024 * 1024
+}
+
+const fn default_emit_buffer_size() -> usize {
+    1000
+}
+
+/// Limits on the size of the shared state table.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+struct StateConfig {
+    /// The maximum number of entries in the `vector.state` table.
+    #[serde(default = "default_max_entries")]
+    max_entries: usize,
+
+    /// The maximum size, in bytes, of the keys and values in the `vector.state` table.
+    #[serde(default = "default_max_bytes")]
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    max_bytes: usize,
+}
+
+impl Default for StateConfig {
+    fn default() -> Self {
+        Self {
+            max_entries: default_max_entries(),
+            max_bytes: default_max_bytes(),
+        }
+    }
+}
+
+/// Configuration for the version three of the `lua` transform.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct LuaConfig {
+    /// The Lua program to initialize the transform with.
+    ///
+    /// The program can be used to import external dependencies, as well as define the functions
+    /// used for the various lifecycle hooks. However, it's not strictly required, as the lifecycle
+    /// hooks can be configured directly with inline Lua source for each respective hook.
+    #[configurable(metadata(
+        docs::examples = "function init()\n\tcount = 0\nend\n\nfunction process()\n\tcount = count + 1\nend\n\nfunction timer_handler(emit)\n\temit(make_counter(counter))\n\tcounter = 0\nend\n\nfunction shutdown(emit)\n\temit(make_counter(counter))\nend\n\nfunction make_counter(value)\n\treturn metric = {\n\t\tname = \"event_counter\",\n\t\tkind = \"incremental\",\n\t\ttimestamp = os.date(\"!*t\"),\n\t\tcounter = {\n\t\t\tvalue = value\n\t\t}\n \t}\nend",
+        docs::examples = "-- external file with hooks and timers defined\nrequire('custom_module')",
+    ))]
+    source: Option<String>,
+
+    /// A list of directories to search when loading a Lua file via the `require` function.
+    ///
+    /// If not specified, the modules are looked up in the configuration directories.
+    #[serde(default = "default_config_paths")]
+    #[configurable(metadata(docs::examples = "/etc/vector/lua"))]
+    #[configurable(metadata(docs::human_name = "Search Directories"))]
+    search_dirs: Vec<PathBuf>,
+
+    #[configurable(derived)]
+    hooks: HooksConfig,
+
+    /// A list of timers which should be configured and executed periodically.
+    #[serde(default)]
+    timers: Vec<TimerConfig>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    state: StateConfig,
+
+    /// The maximum number of events emitted by the Lua code waiting to be sent downstream.
+    ///
+    /// The Lua code runs on its own thread, and when this many events are waiting, the `emit`
+    /// function waits for downstream components to catch up, instead of blocking the other
+    /// components.
+    #[serde(default = "default_emit_buffer_size")]
+    #[configurable(metadata(docs::type_unit = "events"))]
+    emit_buffer_size: usize,
+
+    /// When set to `single`, metric tag values are exposed as single strings, the
+    /// same as they were before this config option. Tags with multiple values show the last assigned value, and null values
+    /// are ignored.
+    ///
+    /// When set to `full`, all metric tags are exposed as arrays of either string or null
+    /// values.
+    #[serde(default)]
+    metric_tag_values: MetricTagValues,
+}
+
+impl LuaConfig {
+    pub fn build(&self, key: ComponentKey) -> crate::Result<Transform> {
+        Lua::new(self, key).map(Transform::event_task)
+    }
+
+    pub fn input(&self) -> Input {
+        Input::new(DataType::Metric | DataType::Log)
+    }
+
+    pub fn outputs(
+        &self,
+        input_definitions: &[(OutputId, schema::Definition)],
+    ) -> Vec<TransformOutput> {
+        // Lua causes the type definition to be reset
+        let namespaces = input_definitions
+            .iter()
+            .flat_map(|(_output, definition)| definition.log_namespaces().clone())
+            .collect();
+
+        let definition = input_definitions
+            .iter()
+            .map(|(output, _definition)| {
+                (
+                    output.clone(),
+                    Definition::default_for_namespace(&namespaces),
+                )
+            })
+            .collect();
+
+        vec![TransformOutput::new(
+            DataType::Metric | DataType::Log,
+            definition,
+        )]
+    }
+}
+
+/// Converts a number of seconds from the Lua code to the delay of a timer.
+fn timer_delay(seconds: f64, allow_zero: bool) -> mlua::Result<Duration> {
+    Duration::try_from_secs_f64(seconds)
+        .ok()
+        .filter(|delay| allow_zero || !delay.is_zero())
+        .ok_or_else(|| {
+            mlua::Error::RuntimeError(format!("Invalid timer delay of {seconds} seconds."))
+        })
+}
+
+/// Creates the `vector` table exposing the state and the timers to the Lua code.
+fn make_vector_table<'lua>(
+    lua: &'lua mlua::Lua,
+    state: State,
+    timers: &Timers,
+) -> mlua::Result<mlua::Table<'lua>> {
+    let table = lua.create_table()?;
+    table.set("state", state)?;
+
+    let interval_timers = timers.clone();
+    table.set(
+        "set_interval",
+        lua.create_function(move |lua, (seconds, handler): (f64, mlua::Function)| {
+            let interval = timer_delay(seconds, false)?;
+            let handler = lua.create_registry_value(handler)?;
+            Ok(interval_timers.add(interval, Some(interval), handler))
+        })?,
+    )?;
+
+    let timeout_timers = timers.clone();
+    table.set(
+        "set_timeout",
+        lua.create_function(move |lua, (seconds, handler): (f64, mlua::Function)| {
+            let delay = timer_delay(seconds, true)?;
+            let handler = lua.create_registry_value(handler)?;
+            Ok(timeout_timers.add(delay, None, handler))
+        })?,
+    )?;
+
+    let cleared_timers = timers.clone();
+    table.set(
+        "clear_timer",
+        lua.create_function(move |_, id: u64| Ok(cleared_timers.remove(id)))?,
+    )?;
+
+    Ok(table)
+}
+
+pub struct Lua {
+    lua: mlua::Lua,
+    invocations_after_gc: usize,
+    hook_init: Option<mlua::RegistryKey>,
+    hook_process: mlua::RegistryKey,
+    hook_shutdown: Option<mlua::RegistryKey>,
+    timers: Timers,
+    emit_buffer_size: usize,
+    multi_value_tags: bool,
+    source_id: Arc<ComponentKey>,
+}
+
+/// Returns an `emit` function sending the events downstream, waiting while the buffer is full.
+fn emit_to(output: &mpsc::Sender<Event>) -> impl FnMut(Event) + '_ {
+    move |event| {
+        // The events are discarded if the downstream components have stopped.
+        _ = output.blocking_send(event);
+    }
+}
+
+impl Lua {
+    pub fn new(config: &LuaConfig, key: ComponentKey) -> crate::Result<Self> {
+        // In order to support loading C modules in Lua, we need to create unsafe instance
+        // without debug library.
+        let lua = unsafe {
+            mlua::Lua::unsafe_new_with(mlua::StdLib::ALL_SAFE, mlua::LuaOptions::default())
+        };
+
+        let additional_paths = config
+            .search_dirs
+            .iter()
+            .map(|d| format!("{}/?.lua", d.to_string_lossy()))
+            .collect::<Vec<_>>()
+            .join(";");
+
+        if !additional_paths.is_empty() {
+            let package = lua.globals().get::<_, mlua::Table<'_>>("package")?;
+            let current_paths = package
+                .get::<_, String>("path")
+                .unwrap_or_else(|_| ";".to_string());
+            let paths = format!("{};{}", additional_paths, current_paths);
+            package.set("path", paths)?;
+        }
+
+        let timers = Timers::default();
+        let state = State::new(config.state.max_entries, config.state.max_bytes);
+        lua.globals()
+            .set("vector", make_vector_table(&lua, state, &timers)?)?;
+
+        if let Some(source) = &config.source {
+            lua.load(source).eval().context(InvalidSourceSnafu)?;
+        }
+
+        let hook_init = config
+            .hooks
+            .init
+            .as_ref()
+            .map(|code| make_registry_value(&lua, code))
+            .transpose()
+            .context(InvalidHooksInitSnafu)?;
+
+        let hook_process =
+            make_registry_value(&lua, &config.hooks.process).context(InvalidHooksProcessSnafu)?;
+
+        let hook_shutdown = config
+            .hooks
+            .shutdown
+            .as_ref()
+            .map(|code| make_registry_value(&lua, code))
+            .transpose()
+            .context(InvalidHooksShutdownSnafu)?;
+
+        for timer in &config.timers {
+            let handler_key =
+                make_registry_value(&lua, &timer.handler).context(InvalidTimerHandlerSnafu)?;
+            timers.add(
+                timer.interval_seconds,
+                Some(timer.interval_seconds),
+                handler_key,
+            );
+        }
+
+        Ok(Self {
+            lua,
+            invocations_after_gc: 0,
+            hook_init,
+            hook_process,
+            hook_shutdown,
+            timers,
+            emit_buffer_size: config.emit_buffer_size.max(1),
+            multi_value_tags: config.metric_tag_values == MetricTagValues::Full,
+            source_id: Arc::new(key),
+        })
+    }
+
+    fn attempt_gc(&mut self) {
+        self.invocations_after_gc += 1;
+        if self.invocations_after_gc % GC_INTERVAL == 0 {
+            emit!(LuaGcTriggered {
+                used_memory: self.lua.used_memory()
+            });
+            _ = self
+                .lua
+                .gc_collect()
+                .context(RuntimeErrorGcSnafu)
+                .map_err(|error| error!(%error, rate_limit = 30));
+            // Releases the handlers of the timers which were cleared or fired.
+            self.lua.expire_registry_values();
+            self.invocations_after_gc = 0;
+        }
+    }
+
+    fn hook_process(&mut self, event: Event, output: &mpsc::Sender<Event>) {
+        let lua = &self.lua;
+        let source_id = Arc::clone(event.source_id().unwrap_or(&self.source_id));
+        _ = lua
+            .scope(|scope| -> mlua::Result<()> {
+                lua.registry_value::<mlua::Function>(&self.hook_process)?
+                    .call((
+                        LuaEvent {
+                            event,
+                            metric_multi_value_tags: self.multi_value_tags,
+                        },
+                        wrap_emit_fn(scope, emit_to(output), source_id)?,
+                    ))
+            })
+            .context(RuntimeErrorHooksProcessSnafu)
+            .map_err(|e| emit!(LuaBuildError { error: e }));
+
+        self.attempt_gc();
+    }
+
+    fn hook_init(&mut self, output: &mpsc::Sender<Event>) {
+        let lua = &self.lua;
+        _ = lua
+            .scope(|scope| -> mlua::Result<()> {
+                match &self.hook_init {
+                    Some(key) => lua
+                        .registry_value::<mlua::Function>(key)?
+                        .call(wrap_emit_fn(
+                            scope,
+                            emit_to(output),
+                            Arc::clone(&self.source_id),
+                        )?),
+                    None => Ok(()),
+                }
+            })
+            .context(RuntimeErrorHooksInitSnafu)
+            .map_err(|error| error!(%error, rate_limit = 30));
+
+        self.attempt_gc();
+    }
+
+    fn hook_shutdown(&mut self, output: &mpsc::Sender<Event>) {
+        let lua = &self.lua;
+        _ = lua
+            .scope(|scope| -> mlua::Result<()> {
+                match &self.hook_shutdown {
+                    Some(key) => lua
+                        .registry_value::<mlua::Function>(key)?
+                        .call(wrap_emit_fn(
+                            scope,
+                            emit_to(output),
+                            Arc::clone(&self.source_id),
+                        )?),
+                    None => Ok(()),
+                }
+            })
+            .context(RuntimeErrorHooksShutdownSnafu)
+            .map_err(|error| error!(%error, rate_limit = 30));
+
+        self.attempt_gc();
+    }
+
+    /// Calls the handlers of the timers which are due.
+    fn fire_timers(&mut self, output: &mpsc::Sender<Event>) {
+        let now = Instant::now();
+        while let Some(handler_key) = self.timers.take_due(now) {
+            let lua = &self.lua;
+            _ = lua
+                .scope(|scope| -> mlua::Result<()> {
+                    lua.registry_value::<mlua::Function>(&handler_key)?
+                        .call(wrap_emit_fn(
+                            scope,
+                            emit_to(output),
+                            Arc::clone(&self.source_id),
+                        )?)
+                })
+                .context(RuntimeErrorTimerHandlerSnafu)
+                .map_err(|error| error!(%error, rate_limit = 30));
+
+            self.attempt_gc();
+        }
+    }
+
+    /// Runs the hooks and the timers, until the input stream ends.
+    ///
+    /// This runs on a blocking thread, so that neither slow Lua code nor a full buffer of emitted
+    /// events block the threads running the other components.
+    fn run(
+        mut self,
+        handle: Handle,
+        mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
+        output: mpsc::Sender<Event>,
+    ) {
+        self.hook_init(&output);
+
+        while !output.is_closed() {
+            let deadline = self.timers.next_deadline();
+            if deadline.map_or(false, |deadline| deadline <= Instant::now()) {
+                // The timers are fired before reading the next event, so that a steady flow of
+                // events does not delay them.
+                self.fire_timers(&output);
+                continue;
+            }
+
+            let next = handle.block_on(async {
+                match deadline {
+                    Some(deadline) => tokio::time::timeout_at(deadline, input_rx.next())
+                        .await
+                        .ok(),
+                    None => Some(input_rx.next().await),
+                }
+            });
+            match next {
+                Some(Some(event)) => self.hook_process(event, &output),
+                Some(None) => break,
+                None => self.fire_timers(&output),
+            }
+        }
+
+        self.hook_shutdown(&output);
+    }
+}
+
+impl TaskTransform<Event> for Lua {
+    fn transform(
+        self: Box<Self>,
+        input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
+    where
+        Self: 'static,
+    {
+        let (output, output_rx) = mpsc::channel(self.emit_buffer_size);
+        let handle = Handle::current();
+        spawn_blocking(move || self.run(handle, input_rx, output));
+
+        Box::pin(ReceiverStream::new(output_rx))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{future::Future, sync::Arc};
+
+    use similar_asserts::assert_eq;
+    use tokio::sync::mpsc::{self, Receiver, Sender};
+    use tokio::sync::Mutex;
+    use tokio_stream::wrappers::ReceiverStream;
+
+    use super::*;
+    use crate::test_util::components::assert_transform_compliance;
+    use crate::transforms::test::create_topology;
+    use crate::{
+        event::{Event, LogEvent, Value},
+        test_util,
+    };
+
+    fn from_config(config: &str) -> Lua {
+        Lua::new(&toml::from_str(config).unwrap(), "transform".into()).unwrap()
+    }
+
+    async fn run_transform<T: Future>(
+        config: &str,
+        func: impl FnOnce(Sender<Event>, Arc<Mutex<Receiver<Event>>>) -> T,
+    ) -> T::Output {
+        test_util::trace_init();
+        assert_transform_compliance(async move {
+            let config = super::super::LuaConfig::V3(toml::from_str(config).unwrap());
+            let (tx, rx) = mpsc::channel(1);
+            let (topology, out) = create_topology(ReceiverStream::new(rx), config).await;
+
+            let out = Arc::new(tokio::sync::Mutex::new(out));
+
+            let result = func(tx, Arc::clone(&out)).await;
+
+            topology.stop().await;
+            assert_eq!(out.lock().await.recv().await, None);
+
+            result
+        })
+        .await
+    }
+
+    async fn next_message(out: &Arc<Mutex<Receiver<Event>>>) -> Value {
+        out.lock()
+            .await
+            .recv()
+            .await
+            .expect("Event was not received")
+            .as_log()["message"]
+            .clone()
+    }
+
+    #[tokio::test]
+    async fn lua_process_and_shutdown_share_state() {
+        run_transform(
+            r#"
+            version = "3"
+            hooks.process = """function (event, emit)
+                vector.state.count = (vector.state.count or 0) + 1
+                emit(event)
+            end
+            """
+            hooks.shutdown = """function (emit)
+                emit({log = {message = "count " .. vector.state.count}})
+            end
+            """
+            "#,
+            |tx, out| async move {
+                for _ in 0..3 {
+                    tx.send(LogEvent::from("event").into()).await.unwrap();
+                    assert_eq!(next_message(&out).await, "event".into());
+                }
+                drop(tx);
+                assert_eq!(next_message(&out).await, "count 3".into());
+            },
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn lua_sets_and_clears_timers() {
+        run_transform(
+            r#"
+            version = "3"
+            hooks.init = """function (emit)
+                local cleared = vector.set_interval(0.01, function (emit)
+                    emit({log = {message = "cleared"}})
+                end)
+                vector.clear_timer(cleared)
+
+                vector.set_timeout(0.05, function (emit)
+                    emit({log = {message = "timeout"}})
+                end)
+                vector.set_interval(0.1, function (emit)
+                    vector.state.ticks = (vector.state.ticks or 0) + 1
+                    emit({log = {message = "tick " .. vector.state.ticks}})
+                end)
+            end
+            """
+            hooks.process = """function (event, emit)
+            end
+            """
+            "#,
+            |tx, out| async move {
+                // The event is dropped by `hooks.process`.
+                tx.send(LogEvent::from("event").into()).await.unwrap();
+
+                assert_eq!(next_message(&out).await, "timeout".into());
+                assert_eq!(next_message(&out).await, "tick 1".into());
+                assert_eq!(next_message(&out).await, "tick 2".into());
+                drop(tx);
+            },
+        )
+        .await;
+    }
+
+    #[test]
+    fn lua_state_limits() {
+        let mut transform = from_config(
+            r#"
+            state.max_entries = 1
+            hooks.process = """function (event, emit)
+                vector.state[event.log.message] = true
+                emit(event)
+            end
+            """
+            "#,
+        );
+        let (tx, mut rx) = mpsc::channel(10);
+
+        transform.hook_process(LogEvent::from("first").into(), &tx);
+        transform.hook_process(LogEvent::from("first").into(), &tx);
+        // The state is full, so the hook fails and the event is dropped.
+        transform.hook_process(LogEvent::from("second").into(), &tx);
+        drop(tx);
+
+        let mut messages = Vec::new();
+        while let Some(event) = rx.blocking_recv() {
+            messages.push(event.as_log()["message"].clone());
+        }
+        assert_eq!(messages, vec![Value::from("first"), Value::from("first")]);
+    }
+
+    #[test]
+    fn lua_rejects_invalid_timer_delays() {
+        let mut transform = from_config(
+            r#"
+            hooks.init = """function (emit)
+                local ok, error = pcall(vector.set_interval, 0, function () end)
+                emit({log = {message = tostring(ok) .. " " .. tostring(error)}})
+            end
+            """
+            hooks.process = """function (event, emit)
+            end
+            """
+            "#,
+        );
+        let (tx, mut rx) = mpsc::channel(10);
+
+        transform.hook_init(&tx);
+        drop(tx);
+
+        let message = rx.blocking_recv().unwrap().as_log()["message"]
+            .to_string_lossy()
+            .into_owned();
+        assert!(message.starts_with("false"), "{}", message);
+        assert!(
+            message.contains("Invalid timer delay of 0 seconds."),
+            "{}",
+            message
+        );
+    }
+}
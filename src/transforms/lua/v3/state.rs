@@ -0,0 +1,158 @@
+use std::collections::BTreeMap;
+
+use mlua::{MetaMethod, UserData, UserDataMethods};
+use vector_lib::ByteSizeOf;
+
+use crate::event::Value;
+
+/// A table of values shared by the hooks and the timers, with limits on its size.
+///
+/// The values are copied in and out of the table, so changing a table read from the state does not
+/// change the state until the table is assigned to it again.
+#[derive(Debug)]
+pub struct State {
+    entries: BTreeMap<String, Value>,
+    bytes: usize,
+    max_entries: usize,
+    max_bytes: usize,
+}
+
+impl State {
+    pub const fn new(max_entries: usize, max_bytes: usize) -> Self {
+        Self {
+            entries: BTreeMap::new(),
+            bytes: 0,
+            max_entries,
+            max_bytes,
+        }
+    }
+
+    fn entry_bytes(key: &str, value: &Value) -> usize {
+        key.len() + value.size_of()
+    }
+
+    fn get(&self, key: &str) -> Option<Value> {
+        self.entries.get(key).cloned()
+    }
+
+    /// Sets the value of a key, or removes the key if the value is `nil`.
+    fn set(&mut self, key: String, value: Option<Value>) -> mlua::Result<()> {
+        let previous_bytes = self
+            .entries
+            .get(&key)
+            .map_or(0, |previous| Self::entry_bytes(&key, previous));
+
+        let Some(value) = value else {
+            self.entries.remove(&key);
+            self.bytes -= previous_bytes;
+            return Ok(());
+        };
+
+        let bytes = self.bytes - previous_bytes + Self::entry_bytes(&key, &value);
+        if bytes > self.max_bytes {
+            return Err(mlua::Error::RuntimeError(format!(
+                "Cannot set {key:?} in the state: the state would exceed its limit of {} bytes.",
+                self.max_bytes
+            )));
+        }
+        if !self.entries.contains_key(&key) && self.entries.len() >= self.max_entries {
+            return Err(mlua::Error::RuntimeError(format!(
+                "Cannot set {key:?} in the state: the state is limited to {} entries.",
+                self.max_entries
+            )));
+        }
+
+        self.entries.insert(key, value);
+        self.bytes = bytes;
+        Ok(())
+    }
+}
+
+impl UserData for State {
+    fn add_methods<'lua, M: UserDataMethods<'lua, Self>>(methods: &mut M) {
+        methods.add_meta_method(MetaMethod::Index, |_, this, key: String| Ok(this.get(&key)));
+        methods.add_meta_method_mut(
+            MetaMethod::NewIndex,
+            |_, this, (key, value): (String, Option<Value>)| this.set(key, value),
+        );
+        methods.add_meta_method(MetaMethod::Len, |_, this, ()| Ok(this.entries.len()));
+        methods.add_meta_method(MetaMethod::Pairs, |lua, this, ()| {
+            // The entries are iterated over a snapshot, so that the state can be changed while
+            // iterating.
+            let mut entries = this.entries.clone().into_iter();
+            lua.create_function_mut(move |_, _: mlua::MultiValue| {
+                Ok(entries
+                    .next()
+                    .map_or((None, None), |(key, value)| (Some(key), Some(value))))
+            })
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lua(state: State) -> mlua::Lua {
+        let lua = mlua::Lua::new();
+        lua.globals().set("state", state).unwrap();
+        lua
+    }
+
+    #[test]
+    fn stores_values() {
+        let lua = lua(State::new(10, 1024));
+        lua.load(
+            r#"
+            state.count = 1
+            state.count = state.count + 1
+            state.user = { name = "jane" }
+            state.missing = nil
+            "#,
+        )
+        .exec()
+        .unwrap();
+
+        let (count, name, len, keys): (i64, String, usize, String) = lua
+            .load(
+                r#"
+                local keys = {}
+                for key, _ in pairs(state) do
+                    table.insert(keys, key)
+                end
+                return state.count, state.user.name, #state, table.concat(keys, ",")
+                "#,
+            )
+            .eval()
+            .unwrap();
+        assert_eq!(
+            (count, name.as_str(), len, keys.as_str()),
+            (2, "jane", 2, "count,user")
+        );
+    }
+
+    #[test]
+    fn limits_entries() {
+        let lua = lua(State::new(2, 1024));
+        lua.load("state.a = 1; state.b = 2; state.a = 3")
+            .exec()
+            .unwrap();
+
+        let error = lua.load("state.c = 4").exec().unwrap_err().to_string();
+        assert!(error.contains("limited to 2 entries"), "{}", error);
+
+        // Removing an entry makes room for another one.
+        lua.load("state.a = nil; state.c = 4").exec().unwrap();
+    }
+
+    #[test]
+    fn limits_bytes() {
+        let lua = lua(State::new(10, 256));
+        let error = lua
+            .load("state.text = string.rep('x', 512)")
+            .exec()
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("limit of 256 bytes"), "{}", error);
+    }
+}
@@ -0,0 +1,134 @@
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use tokio::time::Instant;
+
+struct Timer {
+    deadline: Instant,
+    /// The interval of a repeating timer, or `None` for a timer firing once.
+    interval: Option<Duration>,
+    handler: Arc<mlua::RegistryKey>,
+}
+
+/// The timers of a Lua runtime, including the ones set by the Lua code with `set_interval` and
+/// `set_timeout`.
+///
+/// The timers are shared with the functions exposed to the Lua code, but only used from the thread
+/// running the Lua runtime. The handlers of the removed timers are dropped, and expire from the
+/// registry of the runtime on its next garbage collection.
+#[derive(Clone, Default)]
+pub struct Timers {
+    inner: Arc<Mutex<Inner>>,
+}
+
+#[derive(Default)]
+struct Inner {
+    next_id: u64,
+    timers: BTreeMap<u64, Timer>,
+}
+
+impl Timers {
+    /// Adds a timer, returning its identifier.
+    pub fn add(
+        &self,
+        delay: Duration,
+        interval: Option<Duration>,
+        handler: mlua::RegistryKey,
+    ) -> u64 {
+        let mut inner = self.inner.lock().expect("timers lock poisoned");
+        inner.next_id += 1;
+        let id = inner.next_id;
+        inner.timers.insert(
+            id,
+            Timer {
+                deadline: Instant::now() + delay,
+                interval,
+                handler: Arc::new(handler),
+            },
+        );
+        id
+    }
+
+    /// Removes a timer, returning whether it existed.
+    pub fn remove(&self, id: u64) -> bool {
+        let mut inner = self.inner.lock().expect("timers lock poisoned");
+        inner.timers.remove(&id).is_some()
+    }
+
+    /// Returns the earliest deadline of the timers.
+    pub fn next_deadline(&self) -> Option<Instant> {
+        let inner = self.inner.lock().expect("timers lock poisoned");
+        inner.timers.values().map(|timer| timer.deadline).min()
+    }
+
+    /// Returns the handler of the earliest timer due at `now`, rescheduling the timer if it
+    /// repeats, or removing it otherwise.
+    pub fn take_due(&self, now: Instant) -> Option<Arc<mlua::RegistryKey>> {
+        let mut inner = self.inner.lock().expect("timers lock poisoned");
+        let (&id, timer) = inner
+            .timers
+            .iter_mut()
+            .filter(|(_, timer)| timer.deadline <= now)
+            .min_by_key(|(_, timer)| timer.deadline)?;
+
+        match timer.interval {
+            Some(interval) => {
+                // A timer falling behind is not fired repeatedly to catch up.
+                timer.deadline = (timer.deadline + interval).max(now);
+                Some(Arc::clone(&timer.handler))
+            }
+            None => inner.timers.remove(&id).map(|timer| timer.handler),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test(start_paused = true)]
+    async fn fires_the_due_timers() {
+        let lua = mlua::Lua::new();
+        let handler = |name: &str| {
+            let function = lua
+                .create_function({
+                    let name = name.to_owned();
+                    move |_, ()| Ok(name.clone())
+                })
+                .unwrap();
+            lua.create_registry_value(function).unwrap()
+        };
+        let fire = |timers: &Timers| {
+            timers.take_due(Instant::now()).map(|key| {
+                lua.registry_value::<mlua::Function>(&key)
+                    .unwrap()
+                    .call::<_, String>(())
+                    .unwrap()
+            })
+        };
+
+        let timers = Timers::default();
+        timers.add(Duration::from_secs(2), None, handler("once"));
+        let every = timers.add(
+            Duration::from_secs(1),
+            Some(Duration::from_secs(1)),
+            handler("every"),
+        );
+        assert_eq!(fire(&timers), None);
+
+        tokio::time::advance(Duration::from_secs(1)).await;
+        assert_eq!(fire(&timers).as_deref(), Some("every"));
+        assert_eq!(fire(&timers), None);
+
+        tokio::time::advance(Duration::from_secs(1)).await;
+        assert_eq!(fire(&timers).as_deref(), Some("once"));
+        assert_eq!(fire(&timers).as_deref(), Some("every"));
+        assert_eq!(fire(&timers), None);
+
+        assert!(timers.remove(every));
+        assert_eq!(timers.next_deadline(), None);
+    }
+}
@@ -11,24 +11,48 @@ pub mod sample;
 pub mod aggregate;
 #[cfg(feature = "transforms-aws_ec2_metadata")]
 pub mod aws_ec2_metadata;
+#[cfg(feature = "transforms-correlate")]
+pub mod correlate;
+#[cfg(feature = "transforms-exec")]
+pub mod exec;
+#[cfg(feature = "transforms-explode")]
+pub mod explode;
 #[cfg(feature = "transforms-filter")]
 pub mod filter;
+#[cfg(feature = "transforms-log_cluster")]
+pub mod log_cluster;
 #[cfg(feature = "transforms-log_to_metric")]
 pub mod log_to_metric;
 #[cfg(feature = "transforms-lua")]
 pub mod lua;
 #[cfg(feature = "transforms-metric_to_log")]
 pub mod metric_to_log;
+#[cfg(feature = "transforms-pii_redact")]
+pub mod pii_redact;
+#[cfg(feature = "transforms-quota")]
+pub mod quota;
 #[cfg(feature = "transforms-reduce")]
 pub mod reduce;
 #[cfg(feature = "transforms-remap")]
 pub mod remap;
 #[cfg(feature = "transforms-route")]
 pub mod route;
+#[cfg(feature = "transforms-schema_enforce")]
+pub mod schema_enforce;
+#[cfg(feature = "transforms-shed")]
+pub mod shed;
 #[cfg(feature = "transforms-tag_cardinality_limit")]
 pub mod tag_cardinality_limit;
 #[cfg(feature = "transforms-throttle")]
 pub mod throttle;
+#[cfg(feature = "transforms-tokenize")]
+pub mod tokenize;
+#[cfg(feature = "transforms-trace_sampler")]
+pub mod trace_sampler;
+#[cfg(feature = "transforms-wasm")]
+pub mod wasm;
+#[cfg(feature = "transforms-window_aggregate")]
+pub mod window_aggregate;
 
 pub use vector_lib::transform::{
     FunctionTransform, OutputBuffer, SyncTransform, TaskTransform, Transform, TransformOutputs,
@@ -0,0 +1,339 @@
+use std::collections::BTreeMap;
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use vector_lib::config::{clone_input_definitions, LogNamespace};
+use vector_lib::configurable::configurable_component;
+use vector_lib::lookup::lookup_v2::ConfigValuePath;
+use vector_lib::sensitive_string::SensitiveString;
+
+use crate::{
+    config::{
+        DataType, GenerateConfig, Input, OutputId, TransformConfig, TransformContext,
+        TransformOutput,
+    },
+    event::{Event, Value},
+    internal_events::PiiRedacted,
+    schema,
+    transforms::{FunctionTransform, OutputBuffer, Transform},
+};
+
+mod detectors;
+
+pub use self::detectors::Detector;
+use self::detectors::Detectors;
+
+/// How the detected values are redacted.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum RedactAction {
+    /// Replaces each character of the value with `*`.
+    #[default]
+    Mask,
+
+    /// Replaces the value with the hex-encoded HMAC-SHA256 hash of the value.
+    Hash,
+
+    /// Replaces the value with a token naming the detector, such as `[email:4b6c8a0f2d1e3f5a]`.
+    ///
+    /// The token is derived from the hash of the value, so the occurrences of a value are replaced
+    /// with the same token and can still be correlated.
+    Tokenize,
+}
+
+/// Configuration for the `pii_redact` transform.
+#[configurable_component(transform(
+    "pii_redact",
+    "Detect and redact personally identifiable information in the string fields of events."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct PiiRedactConfig {
+    /// The detectors to run on the string fields.
+    #[serde(default = "default_detectors")]
+    #[configurable(metadata(docs::examples = "email", docs::examples = "credit_card"))]
+    pub detectors: Vec<Detector>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub action: RedactAction,
+
+    /// The key of the HMAC-SHA256 hashes of the `hash` and `tokenize` actions.
+    ///
+    /// Without a key, the values with few possibilities, such as Social Security numbers, can be
+    /// recovered from their hashes by brute force.
+    #[configurable(metadata(docs::examples = "${PII_REDACT_KEY}"))]
+    pub key: Option<SensitiveString>,
+
+    /// The fields to scan.
+    ///
+    /// The fields are scanned recursively, including the strings in nested objects and arrays. By
+    /// default, all of the fields of the events are scanned.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "message", docs::examples = "user.email"))]
+    pub fields: Vec<ConfigValuePath>,
+}
+
+fn default_detectors() -> Vec<Detector> {
+    Detector::ALL.to_vec()
+}
+
+impl Default for PiiRedactConfig {
+    fn default() -> Self {
+        Self {
+            detectors: default_detectors(),
+            action: RedactAction::default(),
+            key: None,
+            fields: Vec::new(),
+        }
+    }
+}
+
+impl GenerateConfig for PiiRedactConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self::default()).unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "pii_redact")]
+impl TransformConfig for PiiRedactConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        Ok(Transform::function(PiiRedact::new(self)))
+    }
+
+    fn input(&self) -> Input {
+        Input::new(DataType::Log | DataType::Trace)
+    }
+
+    fn outputs(
+        &self,
+        _: vector_lib::enrichment::TableRegistry,
+        input_definitions: &[(OutputId, schema::Definition)],
+        _: LogNamespace,
+    ) -> Vec<TransformOutput> {
+        // Only the contents of the strings are replaced.
+        vec![TransformOutput::new(
+            DataType::Log | DataType::Trace,
+            clone_input_definitions(input_definitions),
+        )]
+    }
+
+    fn enable_concurrency(&self) -> bool {
+        true
+    }
+}
+
+#[derive(Clone)]
+pub struct PiiRedact {
+    detectors: Detectors,
+    action: RedactAction,
+    mac: Hmac<Sha256>,
+    fields: Vec<ConfigValuePath>,
+}
+
+impl PiiRedact {
+    pub fn new(config: &PiiRedactConfig) -> Self {
+        let key = config.key.as_ref().map_or("", SensitiveString::inner);
+        Self {
+            detectors: Detectors::new(&config.detectors),
+            action: config.action,
+            mac: Hmac::new_from_slice(key.as_bytes()).expect("HMAC accepts keys of any size"),
+            fields: config.fields.clone(),
+        }
+    }
+
+    /// Redacts the strings of a value, recursively, and counts the redactions by detector.
+    fn redact_value(&self, value: &mut Value, redactions: &mut BTreeMap<Detector, usize>) {
+        match value {
+            Value::Bytes(bytes) => {
+                if let Some(redacted) = std::str::from_utf8(bytes)
+                    .ok()
+                    .and_then(|text| self.redact_str(text, redactions))
+                {
+                    *bytes = redacted.into();
+                }
+            }
+            Value::Object(fields) => fields
+                .values_mut()
+                .for_each(|field| self.redact_value(field, redactions)),
+            Value::Array(items) => items
+                .iter_mut()
+                .for_each(|item| self.redact_value(item, redactions)),
+            _ => {}
+        }
+    }
+
+    /// Returns the redacted string, if any personally identifiable information was detected in it.
+    fn redact_str(&self, text: &str, redactions: &mut BTreeMap<Detector, usize>) -> Option<String> {
+        let detections = self.detectors.detect(text);
+        if detections.is_empty() {
+            return None;
+        }
+
+        let mut redacted = String::with_capacity(text.len());
+        let mut end = 0;
+        for detection in detections {
+            redacted.push_str(&text[end..detection.range.start]);
+            redacted
+                .push_str(&self.replacement(detection.detector, &text[detection.range.clone()]));
+            end = detection.range.end;
+            *redactions.entry(detection.detector).or_default() += 1;
+        }
+        redacted.push_str(&text[end..]);
+        Some(redacted)
+    }
+
+    fn replacement(&self, detector: Detector, value: &str) -> String {
+        match self.action {
+            RedactAction::Mask => "*".repeat(value.chars().count()),
+            RedactAction::Hash => self.hash(value),
+            RedactAction::Tokenize => format!("[{}:{}]", detector.name(), &self.hash(value)[..16]),
+        }
+    }
+
+    fn hash(&self, value: &str) -> String {
+        let mut mac = self.mac.clone();
+        mac.update(value.as_bytes());
+        hex::encode(mac.finalize().into_bytes())
+    }
+}
+
+impl FunctionTransform for PiiRedact {
+    fn transform(&mut self, output: &mut OutputBuffer, mut event: Event) {
+        let mut redactions = BTreeMap::new();
+        let value = match &mut event {
+            Event::Log(log) => log.value_mut(),
+            Event::Trace(trace) => trace.value_mut(),
+            Event::Metric(_) => unreachable!("metrics are not accepted"),
+        };
+
+        if self.fields.is_empty() {
+            self.redact_value(value, &mut redactions);
+        } else {
+            for field in &self.fields {
+                if let Some(value) = value.get_mut(&field.0) {
+                    self.redact_value(value, &mut redactions);
+                }
+            }
+        }
+
+        for (detector, count) in redactions {
+            emit!(PiiRedacted {
+                detector: detector.name(),
+                count,
+            });
+        }
+        output.push(event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::{LogEvent, TraceEvent};
+
+    fn redact(config: PiiRedactConfig, event: Event) -> Event {
+        let mut redact = PiiRedact::new(&config);
+        let mut output = OutputBuffer::default();
+        redact.transform(&mut output, event);
+
+        let mut events = output.into_events().collect::<Vec<_>>();
+        assert_eq!(events.len(), 1);
+        events.remove(0)
+    }
+
+    fn log(value: serde_json::Value) -> Event {
+        Event::from_json_value(value, LogNamespace::Legacy).unwrap()
+    }
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<PiiRedactConfig>();
+    }
+
+    #[test]
+    fn masks_all_string_fields() {
+        let event = log(serde_json::json!({
+            "message": "login from 10.1.2.3 by jane@example.com",
+            "user": {"ssn": "123-45-6789", "cards": ["4111-1111-1111-1111"], "age": 42},
+        }));
+
+        let event = redact(PiiRedactConfig::default(), event);
+        assert_eq!(
+            event.into_log().value(),
+            &Value::from(serde_json::json!({
+                "message": "login from ******** by ****************",
+                "user": {"ssn": "***********", "cards": ["*******************"], "age": 42},
+            }))
+        );
+    }
+
+    #[test]
+    fn hashes_and_tokenizes_consistently() {
+        let key = Some(SensitiveString::from("secret".to_owned()));
+        let message = "jane@example.com, jane@example.com, joe@example.com";
+
+        let config = PiiRedactConfig {
+            action: RedactAction::Hash,
+            key: key.clone(),
+            ..Default::default()
+        };
+        let event = redact(config, log(serde_json::json!({ "message": message })));
+        let hashes = event.as_log()["message"].to_string_lossy().into_owned();
+        let hashes = hashes.split(", ").collect::<Vec<_>>();
+        assert_eq!(hashes[0].len(), 64);
+        assert_eq!(hashes[0], hashes[1]);
+        assert_ne!(hashes[0], hashes[2]);
+
+        let config = PiiRedactConfig {
+            action: RedactAction::Tokenize,
+            key,
+            ..Default::default()
+        };
+        let event = redact(config, log(serde_json::json!({ "message": message })));
+        assert_eq!(
+            event.as_log()["message"],
+            Value::from(format!(
+                "[email:{0}], [email:{0}], [email:{1}]",
+                &hashes[0][..16],
+                &hashes[2][..16]
+            ))
+        );
+    }
+
+    #[test]
+    fn only_scans_the_selected_fields() {
+        let config = PiiRedactConfig {
+            detectors: vec![Detector::IpAddress],
+            fields: vec![ConfigValuePath::try_from("client".to_owned()).unwrap()],
+            ..Default::default()
+        };
+        let event = log(serde_json::json!({
+            "client": {"ip": "10.1.2.3", "email": "jane@example.com"},
+            "server": "10.9.8.7",
+        }));
+
+        let event = redact(config, event);
+        assert_eq!(
+            event.into_log().value(),
+            &Value::from(serde_json::json!({
+                "client": {"ip": "********", "email": "jane@example.com"},
+                "server": "10.9.8.7",
+            }))
+        );
+    }
+
+    #[test]
+    fn redacts_traces() {
+        let mut trace = TraceEvent::from(LogEvent::default());
+        trace.insert("attributes.user", "jane@example.com");
+
+        let event = redact(PiiRedactConfig::default(), trace.into());
+        assert_eq!(
+            event.as_trace().get("attributes.user"),
+            Some(&Value::from("****************"))
+        );
+    }
+}
@@ -0,0 +1,471 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    num::NonZeroU64,
+    time::{Duration, Instant},
+};
+
+use vector_lib::config::{clone_input_definitions, LogNamespace};
+use vector_lib::configurable::configurable_component;
+use vector_lib::lookup::lookup_v2::ConfigTargetPath;
+use vector_lib::transform::{SyncTransform, TransformOutputsBuf};
+use vector_lib::EstimatedJsonEncodedSizeOf;
+use vrl::value::Kind;
+
+use crate::{
+    config::{DataType, Input, OutputId, TransformConfig, TransformContext, TransformOutput},
+    event::Event,
+    internal_events::{QuotaExceeded, QuotaUsage, TemplateRenderingError},
+    schema,
+    template::Template,
+    transforms::Transform,
+};
+
+/// The name of the output of the over-quota events, with the `reroute` action.
+pub(crate) const OVER_QUOTA_OUTPUT: &str = "over_quota";
+
+/// The number of slots of the rolling window, whose usage expires together.
+const SLOTS: u64 = 10;
+
+/// The limits of the usage of a key over the window.
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct QuotaLimits {
+    /// The maximum number of events of a key in the window.
+    #[configurable(metadata(docs::examples = 100000))]
+    pub max_events: Option<NonZeroU64>,
+
+    /// The maximum number of bytes of the events of a key in the window.
+    ///
+    /// The size of an event is estimated as the size of its JSON encoding.
+    #[configurable(metadata(docs::examples = 100000000))]
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    pub max_bytes: Option<NonZeroU64>,
+}
+
+/// What happens to the events received once their key exceeded its quota.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum QuotaAction {
+    /// Drops the events.
+    #[default]
+    Drop,
+
+    /// Forwards the events, with the `tag_field` field set to `true`.
+    Tag,
+
+    /// Sends the events to the `over_quota` output, instead of the default output.
+    ///
+    /// The events can then be consumed by other components with the name
+    /// `<transform_name>.over_quota`.
+    Reroute,
+}
+
+/// Configuration for the `quota` transform.
+#[configurable_component(transform(
+    "quota",
+    "Enforce quotas of events and bytes per key over a rolling window."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct QuotaConfig {
+    /// The key whose usage is limited, such as a tenant or a namespace.
+    ///
+    /// The events without the key, and all of the events when it's unset, share a single quota.
+    #[configurable(metadata(docs::examples = "{{ tenant }}", docs::examples = "{{ namespace }}"))]
+    pub key_field: Option<Template>,
+
+    /// The duration of the rolling window over which the usage of each key is limited, in
+    /// seconds.
+    ///
+    /// The usage expires in tenths of the window, so the quota of a key is restored gradually.
+    #[configurable(metadata(docs::human_name = "Time Window"))]
+    pub window_secs: NonZeroU64,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub limits: QuotaLimits,
+
+    /// The limits of specific keys, replacing the default `limits`.
+    #[configurable(metadata(
+        docs::additional_props_description = "The limits of an individual key."
+    ))]
+    #[serde(default)]
+    pub overrides: HashMap<String, QuotaLimits>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub action: QuotaAction,
+
+    /// The field set to `true` in the events exceeding their quota, with the `tag` action.
+    #[serde(default = "default_tag_field")]
+    pub tag_field: ConfigTargetPath,
+}
+
+fn default_tag_field() -> ConfigTargetPath {
+    ConfigTargetPath::from("quota_exceeded")
+}
+
+impl Default for QuotaConfig {
+    fn default() -> Self {
+        Self {
+            key_field: None,
+            window_secs: NonZeroU64::new(60).unwrap(),
+            limits: QuotaLimits::default(),
+            overrides: HashMap::new(),
+            action: QuotaAction::default(),
+            tag_field: default_tag_field(),
+        }
+    }
+}
+
+impl_generate_config_from_default!(QuotaConfig);
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "quota")]
+impl TransformConfig for QuotaConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        Ok(Transform::synchronous(Quota::new(self, Instant::now())))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn outputs(
+        &self,
+        _: vector_lib::enrichment::TableRegistry,
+        input_definitions: &[(OutputId, schema::Definition)],
+        _: LogNamespace,
+    ) -> Vec<TransformOutput> {
+        match self.action {
+            QuotaAction::Drop => vec![TransformOutput::new(
+                DataType::Log,
+                clone_input_definitions(input_definitions),
+            )],
+            QuotaAction::Tag => {
+                let definitions = input_definitions
+                    .iter()
+                    .map(|(output, definition)| {
+                        let definition = definition.clone().with_field(
+                            &self.tag_field.0,
+                            Kind::boolean().or_undefined(),
+                            None,
+                        );
+                        (output.clone(), definition)
+                    })
+                    .collect();
+                vec![TransformOutput::new(DataType::Log, definitions)]
+            }
+            QuotaAction::Reroute => vec![
+                TransformOutput::new(DataType::Log, clone_input_definitions(input_definitions)),
+                TransformOutput::new(DataType::Log, clone_input_definitions(input_definitions))
+                    .with_port(OVER_QUOTA_OUTPUT),
+            ],
+        }
+    }
+}
+
+/// The usage of a key in the slots of the window.
+#[derive(Clone, Debug, Default)]
+struct Usage {
+    /// The slots with usage, by index, the most recent last.
+    slots: VecDeque<(u64, u64, u64)>,
+    events: u64,
+    bytes: u64,
+}
+
+impl Usage {
+    /// Forgets the usage of the slots before the window ending with the slot `now`.
+    fn expire(&mut self, now: u64) {
+        while let Some(&(slot, events, bytes)) = self.slots.front() {
+            if slot + SLOTS > now {
+                break;
+            }
+            self.slots.pop_front();
+            self.events -= events;
+            self.bytes -= bytes;
+        }
+    }
+
+    fn exceeds(&self, limits: &QuotaLimits, bytes: u64) -> bool {
+        limits
+            .max_events
+            .is_some_and(|max_events| self.events + 1 > max_events.get())
+            || limits
+                .max_bytes
+                .is_some_and(|max_bytes| self.bytes + bytes > max_bytes.get())
+    }
+
+    fn add(&mut self, now: u64, bytes: u64) {
+        match self.slots.back_mut() {
+            Some((slot, slot_events, slot_bytes)) if *slot == now => {
+                *slot_events += 1;
+                *slot_bytes += bytes;
+            }
+            _ => self.slots.push_back((now, 1, bytes)),
+        }
+        self.events += 1;
+        self.bytes += bytes;
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Quota {
+    key_field: Option<Template>,
+    limits: QuotaLimits,
+    overrides: HashMap<String, QuotaLimits>,
+    action: QuotaAction,
+    tag_field: ConfigTargetPath,
+    start: Instant,
+    slot_duration: Duration,
+    usages: HashMap<Option<String>, Usage>,
+    /// The slot at which the keys without usage are next forgotten.
+    next_sweep: u64,
+}
+
+impl Quota {
+    pub fn new(config: &QuotaConfig, start: Instant) -> Self {
+        Self {
+            key_field: config.key_field.clone(),
+            limits: config.limits.clone(),
+            overrides: config.overrides.clone(),
+            action: config.action,
+            tag_field: config.tag_field.clone(),
+            start,
+            slot_duration: Duration::from_secs(config.window_secs.get()) / SLOTS as u32,
+            usages: HashMap::new(),
+            next_sweep: SLOTS,
+        }
+    }
+
+    /// Returns whether the event is within the quota of its key at `now`, and counts it in the
+    /// usage of the key if it is.
+    fn check(&mut self, event: &Event, now: Instant) -> bool {
+        let slot = (now.saturating_duration_since(self.start).as_nanos()
+            / self.slot_duration.as_nanos().max(1)) as u64;
+        if slot >= self.next_sweep {
+            self.sweep(slot);
+        }
+
+        let key = self.key_field.as_ref().and_then(|template| {
+            template
+                .render_string(event)
+                .map_err(|error| {
+                    emit!(TemplateRenderingError {
+                        error,
+                        field: Some("key_field"),
+                        drop_event: false,
+                    })
+                })
+                .ok()
+        });
+        let limits = key
+            .as_ref()
+            .and_then(|key| self.overrides.get(key))
+            .unwrap_or(&self.limits);
+        let bytes = event.estimated_json_encoded_size_of().get() as u64;
+
+        let usage = self.usages.entry(key.clone()).or_default();
+        usage.expire(slot);
+        let exceeded = usage.exceeds(limits, bytes);
+        if !exceeded {
+            usage.add(slot, bytes);
+        }
+
+        emit!(QuotaUsage {
+            key: key.as_deref(),
+            events: usage.events,
+            bytes: usage.bytes,
+        });
+        if exceeded {
+            emit!(QuotaExceeded {
+                key: key.as_deref(),
+                dropped: self.action == QuotaAction::Drop,
+            });
+        }
+        exceeded
+    }
+
+    /// Forgets the keys without usage in the window ending with the slot `now`.
+    fn sweep(&mut self, now: u64) {
+        self.usages.retain(|key, usage| {
+            usage.expire(now);
+            let retained = !usage.slots.is_empty();
+            if !retained {
+                emit!(QuotaUsage {
+                    key: key.as_deref(),
+                    events: 0,
+                    bytes: 0,
+                });
+            }
+            retained
+        });
+        self.next_sweep = now + SLOTS;
+    }
+}
+
+impl SyncTransform for Quota {
+    fn transform(&mut self, event: Event, output: &mut TransformOutputsBuf) {
+        self.transform_at(event, Instant::now(), output);
+    }
+}
+
+impl Quota {
+    fn transform_at(&mut self, event: Event, now: Instant, output: &mut TransformOutputsBuf) {
+        if !self.check(&event, now) {
+            output.push(None, event);
+            return;
+        }
+
+        match self.action {
+            QuotaAction::Drop => {}
+            QuotaAction::Tag => {
+                let mut log = event.into_log();
+                log.insert(&self.tag_field.0, true);
+                output.push(None, log.into());
+            }
+            QuotaAction::Reroute => output.push(Some(OVER_QUOTA_OUTPUT), event),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::LogEvent;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<QuotaConfig>();
+    }
+
+    fn quota(config: &str) -> (Quota, Instant, TransformOutputsBuf) {
+        let config = toml::from_str::<QuotaConfig>(config).unwrap();
+        let start = Instant::now();
+        let outputs = config.outputs(
+            Default::default(),
+            &[(
+                OutputId::dummy(),
+                schema::Definition::default_legacy_namespace(),
+            )],
+            LogNamespace::Legacy,
+        );
+        let outputs = TransformOutputsBuf::new_with_capacity(outputs, 1);
+        (Quota::new(&config, start), start, outputs)
+    }
+
+    fn log(tenant: &str, message: &str) -> Event {
+        let mut log = LogEvent::from(message);
+        log.insert("tenant", tenant);
+        log.into()
+    }
+
+    fn messages(events: impl Iterator<Item = Event>) -> Vec<String> {
+        events
+            .map(|event| event.as_log()["message"].to_string_lossy().into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn limits_events_per_key() {
+        let (mut quota, start, mut outputs) = quota(
+            r#"
+            key_field = "{{ tenant }}"
+            window_secs = 10
+            limits.max_events = 2
+            overrides.big.max_events = 3
+            "#,
+        );
+
+        for (tenant, message) in [("small", "1"), ("small", "2"), ("small", "3")] {
+            quota.transform_at(log(tenant, message), start, &mut outputs);
+        }
+        for message in ["4", "5", "6", "7"] {
+            quota.transform_at(log("big", message), start, &mut outputs);
+        }
+        assert_eq!(messages(outputs.drain()), ["1", "2", "4", "5", "6"]);
+    }
+
+    #[test]
+    fn restores_quotas_over_the_window() {
+        let (mut quota, start, mut outputs) = quota(
+            r#"
+            window_secs = 10
+            limits.max_events = 2
+            "#,
+        );
+
+        quota.transform_at(log("a", "1"), start, &mut outputs);
+        quota.transform_at(log("a", "2"), start + Duration::from_secs(5), &mut outputs);
+        quota.transform_at(log("a", "3"), start + Duration::from_secs(9), &mut outputs);
+        // The first event expired, but not the second one.
+        quota.transform_at(log("a", "4"), start + Duration::from_secs(10), &mut outputs);
+        quota.transform_at(log("a", "5"), start + Duration::from_secs(11), &mut outputs);
+        quota.transform_at(log("a", "6"), start + Duration::from_secs(15), &mut outputs);
+        assert_eq!(messages(outputs.drain()), ["1", "2", "4", "6"]);
+    }
+
+    #[test]
+    fn limits_bytes() {
+        let (mut quota, start, mut outputs) = quota(
+            r#"
+            window_secs = 10
+            limits.max_bytes = 100
+            "#,
+        );
+
+        let size = log("a", "small").estimated_json_encoded_size_of().get();
+        for _ in 0..(100 / size + 1) {
+            quota.transform_at(log("a", "small"), start, &mut outputs);
+        }
+        assert_eq!(outputs.drain().count(), 100 / size);
+    }
+
+    #[test]
+    fn tags_and_reroutes_events() {
+        let (mut quota, start, mut outputs) = quota(
+            r#"
+            window_secs = 10
+            limits.max_events = 1
+            action = "tag"
+            "#,
+        );
+        quota.transform_at(log("a", "1"), start, &mut outputs);
+        quota.transform_at(log("a", "2"), start, &mut outputs);
+        let events = outputs.drain().collect::<Vec<_>>();
+        assert!(!events[0].as_log().contains("quota_exceeded"));
+        assert_eq!(events[1].as_log()["quota_exceeded"], true.into());
+
+        let (mut quota, start, mut outputs) = quota(
+            r#"
+            window_secs = 10
+            limits.max_events = 1
+            action = "reroute"
+            "#,
+        );
+        quota.transform_at(log("a", "1"), start, &mut outputs);
+        quota.transform_at(log("a", "2"), start, &mut outputs);
+        assert_eq!(messages(outputs.drain()), ["1"]);
+        assert_eq!(messages(outputs.drain_named(OVER_QUOTA_OUTPUT)), ["2"]);
+    }
+
+    #[test]
+    fn forgets_keys_without_usage() {
+        let (mut quota, start, mut outputs) = quota(
+            r#"
+            key_field = "{{ tenant }}"
+            window_secs = 10
+            "#,
+        );
+
+        quota.transform_at(log("a", "1"), start, &mut outputs);
+        quota.transform_at(log("b", "2"), start + Duration::from_secs(5), &mut outputs);
+        assert_eq!(quota.usages.len(), 2);
+
+        quota.transform_at(log("b", "3"), start + Duration::from_secs(12), &mut outputs);
+        assert_eq!(quota.usages.len(), 1);
+        assert_eq!(quota.usages[&Some("b".to_owned())].events, 2);
+    }
+}
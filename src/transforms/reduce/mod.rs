@@ -23,10 +23,12 @@ use crate::{
 };
 
 mod merge_strategy;
+mod program;
 
 use crate::config::schema::Definition;
 use crate::event::Value;
 pub use merge_strategy::*;
+use program::ReduceProgram;
 use vector_lib::config::LogNamespace;
 use vector_lib::stream::expiration_map::{map_with_expiration, Emitter};
 use vrl::value::kind::Collection;
@@ -93,6 +95,33 @@ pub struct ReduceConfig {
     ))]
     pub merge_strategies: IndexMap<KeyString, MergeStrategy>,
 
+    /// A [Vector Remap Language][vrl] (VRL) program that folds each event into the reduced event,
+    /// instead of the merge strategies.
+    ///
+    /// The program runs on the reduced event, which has no fields until the first event of its
+    /// group is folded into it, and the folded event is available at `%reduce.event`. The reduced
+    /// event only contains the fields set by the program. If the program fails or aborts, the
+    /// event isn't folded into the reduced event.
+    ///
+    /// [vrl]: https://vector.dev/docs/reference/vrl
+    #[configurable(metadata(
+        docs::examples = ".count = (int(.count) ?? 0) + 1\n.messages = push(array(.messages) ?? [], %reduce.event.message)",
+        docs::syntax_override = "remap_program"
+    ))]
+    pub fold: Option<String>,
+
+    /// A [Vector Remap Language][vrl] (VRL) program run on each reduced event before it's emitted.
+    ///
+    /// The program can modify the reduced event, or drop it with `abort`. If the program fails,
+    /// the reduced event is emitted unmodified.
+    ///
+    /// [vrl]: https://vector.dev/docs/reference/vrl
+    #[configurable(metadata(
+        docs::examples = ".average = .total / .count",
+        docs::syntax_override = "remap_program"
+    ))]
+    pub finalizer: Option<String>,
+
     /// A condition used to distinguish the final event of a transaction.
     ///
     /// If this condition resolves to `true` for an event, the current transaction is immediately
@@ -120,7 +149,7 @@ impl_generate_config_from_default!(ReduceConfig);
 #[typetag::serde(name = "reduce")]
 impl TransformConfig for ReduceConfig {
     async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
-        Reduce::new(self, &context.enrichment_tables).map(Transform::event_task)
+        Reduce::new(self, context).map(Transform::event_task)
     }
 
     fn input(&self) -> Input {
@@ -133,6 +162,24 @@ impl TransformConfig for ReduceConfig {
         input_definitions: &[(OutputId, schema::Definition)],
         _: LogNamespace,
     ) -> Vec<TransformOutput> {
+        if self.fold.is_some() || self.finalizer.is_some() {
+            // The fields of the reduced events are set by the programs, and only known at runtime.
+            let namespaces = input_definitions
+                .iter()
+                .flat_map(|(_output, definition)| definition.log_namespaces().clone())
+                .collect();
+            let definitions = input_definitions
+                .iter()
+                .map(|(output, _definition)| {
+                    (
+                        output.clone(),
+                        Definition::default_for_namespace(&namespaces),
+                    )
+                })
+                .collect();
+            return vec![TransformOutput::new(DataType::Log, definitions)];
+        }
+
         // Events may be combined, so there isn't a true single "source" for events.
         // All of the definitions must be merged.
         let merged_definition: Definition = input_definitions
@@ -238,6 +285,8 @@ impl TransformConfig for ReduceConfig {
 struct ReduceState {
     events: usize,
     fields: HashMap<KeyString, Box<dyn ReduceValueMerger>>,
+    /// The fields set by the `fold` program, if any.
+    folded: Value,
     stale_since: Instant,
     metadata: EventMetadata,
 }
@@ -251,14 +300,27 @@ impl ReduceState {
             events: 0,
             stale_since: Instant::now(),
             fields,
+            folded: Value::Object(BTreeMap::new()),
             metadata,
         }
     }
 
-    fn add_event(&mut self, e: LogEvent, strategies: &IndexMap<KeyString, MergeStrategy>) {
+    fn add_event(
+        &mut self,
+        e: LogEvent,
+        strategies: &IndexMap<KeyString, MergeStrategy>,
+        fold: Option<&ReduceProgram>,
+    ) {
         let (value, metadata) = e.into_parts();
         self.metadata.merge(metadata);
 
+        if let Some(fold) = fold {
+            fold.fold(&mut self.folded, value);
+            self.events += 1;
+            self.stale_since = Instant::now();
+            return;
+        }
+
         let fields = if let Value::Object(fields) = value {
             fields
         } else {
@@ -294,7 +356,7 @@ impl ReduceState {
     }
 
     fn flush(mut self) -> LogEvent {
-        let mut event = LogEvent::new_with_metadata(self.metadata);
+        let mut event = LogEvent::from_parts(self.folded, self.metadata);
         for (k, v) in self.fields.drain() {
             if let Err(error) = v.insert_into(k, &mut event) {
                 warn!(message = "Failed to merge values for field.", %error);
@@ -310,6 +372,8 @@ pub struct Reduce {
     flush_period: Duration,
     group_by: Vec<String>,
     merge_strategies: IndexMap<KeyString, MergeStrategy>,
+    fold: Option<ReduceProgram>,
+    finalizer: Option<ReduceProgram>,
     reduce_merge_states: HashMap<Discriminant, ReduceState>,
     ends_when: Option<Condition>,
     starts_when: Option<Condition>,
@@ -317,14 +381,15 @@ pub struct Reduce {
 }
 
 impl Reduce {
-    pub fn new(
-        config: &ReduceConfig,
-        enrichment_tables: &vector_lib::enrichment::TableRegistry,
-    ) -> crate::Result<Self> {
+    pub fn new(config: &ReduceConfig, context: &TransformContext) -> crate::Result<Self> {
         if config.ends_when.is_some() && config.starts_when.is_some() {
             return Err("only one of `ends_when` and `starts_when` can be provided".into());
         }
+        if config.fold.is_some() && !config.merge_strategies.is_empty() {
+            return Err("only one of `fold` and `merge_strategies` can be provided".into());
+        }
 
+        let enrichment_tables = &context.enrichment_tables;
         let ends_when = config
             .ends_when
             .as_ref()
@@ -337,12 +402,24 @@ impl Reduce {
             .transpose()?;
         let group_by = config.group_by.clone().into_iter().collect();
         let max_events = config.max_events.map(|max| max.into());
+        let fold = config
+            .fold
+            .as_deref()
+            .map(|source| ReduceProgram::compile(source, context))
+            .transpose()?;
+        let finalizer = config
+            .finalizer
+            .as_deref()
+            .map(|source| ReduceProgram::compile(source, context))
+            .transpose()?;
 
         Ok(Reduce {
             expire_after: config.expire_after_ms,
             flush_period: config.flush_period_ms,
             group_by,
             merge_strategies: config.merge_strategies.clone(),
+            fold,
+            finalizer,
             reduce_merge_states: HashMap::new(),
             ends_when,
             starts_when,
@@ -361,26 +438,40 @@ impl Reduce {
         for k in &flush_discriminants {
             if let Some(t) = self.reduce_merge_states.remove(k) {
                 emit!(ReduceStaleEventFlushed);
-                emitter.emit(Event::from(t.flush()));
+                self.emit_reduced(emitter, t.flush());
             }
         }
     }
 
     fn flush_all_into(&mut self, emitter: &mut Emitter<Event>) {
-        self.reduce_merge_states
+        let reduced = self
+            .reduce_merge_states
             .drain()
-            .for_each(|(_, s)| emitter.emit(Event::from(s.flush())));
+            .map(|(_, s)| s.flush())
+            .collect::<Vec<_>>();
+        for log in reduced {
+            self.emit_reduced(emitter, log);
+        }
+    }
+
+    fn emit_reduced(&self, emitter: &mut Emitter<Event>, log: LogEvent) {
+        match &self.finalizer {
+            Some(finalizer) => finalizer.finalize_into(emitter, log),
+            None => emitter.emit(log.into()),
+        }
     }
 
     fn push_or_new_reduce_state(&mut self, event: LogEvent, discriminant: Discriminant) {
         match self.reduce_merge_states.entry(discriminant) {
             hash_map::Entry::Vacant(entry) => {
                 let mut state = ReduceState::new();
-                state.add_event(event, &self.merge_strategies);
+                state.add_event(event, &self.merge_strategies, self.fold.as_ref());
                 entry.insert(state);
             }
             hash_map::Entry::Occupied(mut entry) => {
-                entry.get_mut().add_event(event, &self.merge_strategies);
+                entry
+                    .get_mut()
+                    .add_event(event, &self.merge_strategies, self.fold.as_ref());
             }
         }
     }
@@ -412,22 +503,17 @@ impl Reduce {
 
         if starts_here {
             if let Some(state) = self.reduce_merge_states.remove(&discriminant) {
-                emitter.emit(state.flush().into());
+                self.emit_reduced(emitter, state.flush());
             }
 
             self.push_or_new_reduce_state(event, discriminant)
         } else if ends_here {
-            emitter.emit(match self.reduce_merge_states.remove(&discriminant) {
-                Some(mut state) => {
-                    state.add_event(event, &self.merge_strategies);
-                    state.flush().into()
-                }
-                None => {
-                    let mut state = ReduceState::new();
-                    state.add_event(event, &self.merge_strategies);
-                    state.flush().into()
-                }
-            })
+            let mut state = self
+                .reduce_merge_states
+                .remove(&discriminant)
+                .unwrap_or_else(ReduceState::new);
+            state.add_event(event, &self.merge_strategies, self.fold.as_ref());
+            self.emit_reduced(emitter, state.flush());
         } else {
             self.push_or_new_reduce_state(event, discriminant)
         }
@@ -966,4 +1052,113 @@ merge_strategies.bar = "concat"
         })
         .await;
     }
+
+    #[tokio::test]
+    async fn fold_and_finalize() {
+        let reduce_config = toml::from_str::<ReduceConfig>(
+            r#"
+group_by = [ "request_id" ]
+fold = '''
+.request_id = %reduce.event.request_id
+.count = (int(.count) ?? 0) + 1
+.total = (int(.total) ?? 0) + (int(%reduce.event.counter) ?? 0)
+.messages = push(array(.messages) ?? [], %reduce.event.message)
+'''
+finalizer = '''
+if .count == 1 { abort }
+.finalized = true
+'''
+
+[ends_when]
+  type = "vrl"
+  source = "exists(.test_end)"
+"#,
+        )
+        .unwrap();
+
+        assert_transform_compliance(async move {
+            let (tx, rx) = mpsc::channel(1);
+            let (topology, mut out) = create_topology(ReceiverStream::new(rx), reduce_config).await;
+
+            let mut e_1 = LogEvent::from("test message 1");
+            e_1.insert("counter", 1);
+            e_1.insert("request_id", "1");
+            tx.send(e_1.into()).await.unwrap();
+
+            // The reduced event of this request only has one event, so it's aborted.
+            let mut e_2 = LogEvent::from("test message 2");
+            e_2.insert("counter", 2);
+            e_2.insert("request_id", "2");
+            e_2.insert("test_end", "yep");
+            tx.send(e_2.into()).await.unwrap();
+
+            let mut e_3 = LogEvent::from("test message 3");
+            e_3.insert("counter", 3);
+            e_3.insert("request_id", "1");
+            e_3.insert("test_end", "yep");
+            tx.send(e_3.into()).await.unwrap();
+
+            let output = out.recv().await.unwrap().into_log();
+            assert_eq!(output["request_id"], "1".into());
+            assert_eq!(output["count"], Value::from(2));
+            assert_eq!(output["total"], Value::from(4));
+            assert_eq!(
+                output["messages"],
+                json!(["test message 1", "test message 3"]).into()
+            );
+            assert_eq!(output["finalized"], Value::from(true));
+            assert!(!output.contains("counter"));
+
+            drop(tx);
+            topology.stop().await;
+            assert_eq!(out.recv().await, None);
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn fold_skips_failed_events() {
+        let reduce_config = toml::from_str::<ReduceConfig>(
+            r#"
+fold = ".total = (int(.total) ?? 0) + int!(%reduce.event.counter)"
+
+[ends_when]
+  type = "vrl"
+  source = "exists(.test_end)"
+"#,
+        )
+        .unwrap();
+
+        let (tx, rx) = mpsc::channel(1);
+        let (topology, mut out) = create_topology(ReceiverStream::new(rx), reduce_config).await;
+
+        for counter in [Value::from(1), Value::from("n/a"), Value::from(2)] {
+            let mut event = LogEvent::from("test message");
+            event.insert("counter", counter.clone());
+            if counter == Value::from(2) {
+                event.insert("test_end", "yep");
+            }
+            tx.send(event.into()).await.unwrap();
+        }
+
+        let output = out.recv().await.unwrap().into_log();
+        assert_eq!(output["total"], Value::from(3));
+
+        drop(tx);
+        topology.stop().await;
+        assert_eq!(out.recv().await, None);
+    }
+
+    #[test]
+    fn fold_excludes_merge_strategies() {
+        let reduce_config = toml::from_str::<ReduceConfig>(
+            r#"
+fold = ".count = (int(.count) ?? 0) + 1"
+merge_strategies.message = "concat"
+"#,
+        )
+        .unwrap();
+
+        assert!(Reduce::new(&reduce_config, &TransformContext::default()).is_err());
+    }
 }
@@ -0,0 +1,98 @@
+use vector_lib::config::LogNamespace;
+use vector_lib::stream::expiration_map::Emitter;
+use vector_lib::{compile_vrl, TimeZone};
+use vector_vrl_functions::set_semantic_meaning::MeaningList;
+use vrl::compiler::runtime::{Runtime, Terminate};
+use vrl::compiler::{CompilationResult, CompileConfig, Program, TypeState};
+use vrl::diagnostic::Formatter;
+use vrl::path;
+
+use crate::{
+    config::TransformContext,
+    event::{Event, EventMetadata, LogEvent, TargetEvents, Value, VrlTarget},
+    internal_events::{ReduceFinalizerAbort, ReduceFinalizerError, ReduceFoldError},
+};
+
+/// A VRL program run by the `reduce` transform, either to fold events into the reduced events, or
+/// to finalize the reduced events.
+pub(super) struct ReduceProgram {
+    program: Program,
+    timezone: TimeZone,
+}
+
+impl ReduceProgram {
+    pub(super) fn compile(source: &str, context: &TransformContext) -> crate::Result<Self> {
+        let functions = vrl::stdlib::all()
+            .into_iter()
+            .chain(vector_lib::enrichment::vrl_functions())
+            .chain(vector_vrl_functions::all())
+            .collect::<Vec<_>>();
+
+        let mut config = CompileConfig::default();
+        config.set_custom(context.enrichment_tables.clone());
+        config.set_custom(MeaningList::default());
+
+        let CompilationResult {
+            program,
+            warnings,
+            config: _,
+        } = compile_vrl(source, &functions, &TypeState::default(), config)
+            .map_err(|diagnostics| Formatter::new(source, diagnostics).colored().to_string())?;
+
+        if !warnings.is_empty() {
+            let warnings = Formatter::new(source, warnings).colored().to_string();
+            warn!(message = "VRL compilation warning.", %warnings);
+        }
+
+        Ok(Self {
+            program,
+            timezone: context.globals.timezone(),
+        })
+    }
+
+    /// Folds the fields of an event into the fields of the reduced event.
+    ///
+    /// The program runs on the reduced event, with the event available at `%reduce.event`. The
+    /// reduced event is left unmodified if the program fails or aborts.
+    pub(super) fn fold(&self, reduced: &mut Value, event: Value) {
+        let mut metadata = EventMetadata::default();
+        metadata.value_mut().insert(path!("reduce", "event"), event);
+        let target = LogEvent::from_parts(reduced.clone(), metadata);
+
+        let mut target = VrlTarget::new(target.into(), self.program.info(), false);
+        match Runtime::default().resolve(&mut target, &self.program, &self.timezone) {
+            Ok(_) => match target.into_events(LogNamespace::Legacy) {
+                TargetEvents::One(event) => *reduced = event.into_log().into_parts().0,
+                _ => emit!(ReduceFoldError {
+                    error: "the program must produce a single event".to_string(),
+                }),
+            },
+            Err(Terminate::Abort(_)) => {}
+            Err(Terminate::Error(error)) => emit!(ReduceFoldError {
+                error: error.to_string(),
+            }),
+        }
+    }
+
+    /// Runs the program on a reduced event before it's emitted.
+    ///
+    /// The reduced event is dropped if the program aborts, and emitted unmodified if it fails.
+    pub(super) fn finalize_into(&self, emitter: &mut Emitter<Event>, log: LogEvent) {
+        let original = log.clone();
+        let mut target = VrlTarget::new(log.into(), self.program.info(), false);
+        match Runtime::default().resolve(&mut target, &self.program, &self.timezone) {
+            Ok(_) => match target.into_events(LogNamespace::Legacy) {
+                TargetEvents::One(event) => emitter.emit(event),
+                TargetEvents::Logs(events) => events.for_each(|event| emitter.emit(event)),
+                TargetEvents::Traces(events) => events.for_each(|event| emitter.emit(event)),
+            },
+            Err(Terminate::Abort(_)) => emit!(ReduceFinalizerAbort),
+            Err(Terminate::Error(error)) => {
+                emit!(ReduceFinalizerError {
+                    error: error.to_string(),
+                });
+                emitter.emit(original.into());
+            }
+        }
+    }
+}
@@ -1,7 +1,9 @@
 use indexmap::IndexMap;
 use vector_lib::config::{clone_input_definitions, LogNamespace};
 use vector_lib::configurable::configurable_component;
+use vector_lib::lookup::lookup_v2::ConfigValuePath;
 use vector_lib::transform::SyncTransform;
+use vrl::path::PathPrefix;
 
 use crate::{
     conditions::{AnyCondition, Condition},
@@ -19,6 +21,7 @@ pub(crate) const UNMATCHED_ROUTE: &str = "_unmatched";
 #[derive(Clone)]
 pub struct Route {
     conditions: Vec<(String, Condition)>,
+    split: Option<Split>,
     reroute_unmatched: bool,
 }
 
@@ -31,6 +34,7 @@ impl Route {
         }
         Ok(Self {
             conditions,
+            split: config.split.as_ref().map(Split::new),
             reroute_unmatched: config.reroute_unmatched,
         })
     }
@@ -47,12 +51,61 @@ impl SyncTransform for Route {
                 check_failed += 1;
             }
         }
-        if self.reroute_unmatched && check_failed == self.conditions.len() {
+        if let Some(split) = &self.split {
+            // Every event takes one of the split routes, so none are unmatched.
+            output.push(Some(split.pick(&event)), event);
+        } else if self.reroute_unmatched && check_failed == self.conditions.len() {
             output.push(Some(UNMATCHED_ROUTE), event);
         }
     }
 }
 
+/// The split routes, with the upper bounds of their shares of the hash space.
+#[derive(Clone)]
+struct Split {
+    routes: Vec<(String, u64)>,
+    total_weight: u64,
+    key: Option<ConfigValuePath>,
+}
+
+impl Split {
+    fn new(config: &SplitConfig) -> Self {
+        let mut total_weight = 0;
+        let routes = config
+            .routes
+            .iter()
+            .map(|(output_name, weight)| {
+                total_weight += u64::from(*weight);
+                (output_name.clone(), total_weight)
+            })
+            .collect();
+        Self {
+            routes,
+            total_weight,
+            key: config.key.clone(),
+        }
+    }
+
+    fn pick(&self, event: &Event) -> &str {
+        let value = self.key.as_ref().and_then(|key| match event {
+            Event::Log(log) => log.get((PathPrefix::Event, key)),
+            Event::Trace(trace) => trace.get((PathPrefix::Event, key)),
+            Event::Metric(_) => None,
+        });
+        let hash = match value {
+            Some(value) => seahash::hash(value.to_string_lossy().as_bytes()),
+            None => rand::random(),
+        };
+
+        let point = hash % self.total_weight;
+        self.routes
+            .iter()
+            .find(|(_, upper_bound)| point < *upper_bound)
+            .map(|(output_name, _)| output_name.as_str())
+            .expect("the total weight is the last upper bound")
+    }
+}
+
 /// Configuration for the `route` transform.
 #[configurable_component(transform(
     "route",
@@ -83,7 +136,42 @@ pub struct RouteConfig {
     /// Both `_unmatched`, as well as `_default`, are reserved output names and thus cannot be used
     /// as a route name.
     #[configurable(metadata(docs::additional_props_description = "An individual route."))]
+    #[serde(default)]
     route: IndexMap<String, AnyCondition>,
+
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    split: Option<SplitConfig>,
+}
+
+/// Splits the events between routes, in proportion to their weights.
+///
+/// Each event is sent to exactly one of the split routes, in addition to the routes whose
+/// condition it matches, so none of the events are unmatched and the `_unmatched` output is
+/// disabled.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SplitConfig {
+    /// A table of route identifiers to weights.
+    ///
+    /// Each route receives a share of the events equal to its weight divided by the sum of the
+    /// weights: for example, with the weights `5` and `95`, the first route receives 5% of the
+    /// events. Each route can then be referenced as an input by other components with the name
+    /// `<transform_name>.<route_id>`.
+    #[configurable(metadata(
+        docs::additional_props_description = "The weight of an individual route."
+    ))]
+    routes: IndexMap<String, u32>,
+
+    /// The field whose value is hashed to pick the route of an event.
+    ///
+    /// The events with the same value always take the same route, as long as the routes and their
+    /// weights are unchanged, so that an entity such as a user consistently takes the same path
+    /// during an experiment. The events without the field, and all of the events when it's unset,
+    /// are split at random.
+    #[configurable(metadata(docs::examples = "user_id"))]
+    key: Option<ConfigValuePath>,
 }
 
 impl GenerateConfig for RouteConfig {
@@ -91,6 +179,7 @@ impl GenerateConfig for RouteConfig {
         toml::Value::try_from(Self {
             reroute_unmatched: true,
             route: IndexMap::new(),
+            split: None,
         })
         .unwrap()
     }
@@ -109,12 +198,29 @@ impl TransformConfig for RouteConfig {
     }
 
     fn validate(&self, _: &schema::Definition) -> Result<(), Vec<String>> {
+        let mut errors = Vec::new();
         if self.route.contains_key(UNMATCHED_ROUTE) {
-            Err(vec![format!(
+            errors.push(format!(
                 "cannot have a named output with reserved name: `{UNMATCHED_ROUTE}`"
-            )])
-        } else {
+            ));
+        }
+        if let Some(split) = &self.split {
+            for output_name in split.routes.keys() {
+                if output_name == UNMATCHED_ROUTE || self.route.contains_key(output_name) {
+                    errors.push(format!(
+                        "split route `{output_name}` conflicts with another named output"
+                    ));
+                }
+            }
+            if split.routes.values().all(|weight| *weight == 0) {
+                errors.push("split routes must have a positive total weight".to_owned());
+            }
+        }
+
+        if errors.is_empty() {
             Ok(())
+        } else {
+            Err(errors)
         }
     }
 
@@ -127,12 +233,13 @@ impl TransformConfig for RouteConfig {
         let mut result: Vec<TransformOutput> = self
             .route
             .keys()
+            .chain(self.split.iter().flat_map(|split| split.routes.keys()))
             .map(|output_name| {
                 TransformOutput::new(DataType::all(), clone_input_definitions(input_definitions))
                     .with_port(output_name)
             })
             .collect();
-        if self.reroute_unmatched {
+        if self.reroute_unmatched && self.split.is_none() {
             result.push(
                 TransformOutput::new(DataType::all(), clone_input_definitions(input_definitions))
                     .with_port(UNMATCHED_ROUTE),
@@ -357,6 +464,79 @@ mod test {
         }
     }
 
+    #[test]
+    fn route_split_by_key() {
+        let output_names = vec!["canary", "production", UNMATCHED_ROUTE];
+        let config = toml::from_str::<RouteConfig>(
+            r#"
+            split.key = "user_id"
+            split.routes.canary = 5
+            split.routes.production = 95
+        "#,
+        )
+        .unwrap();
+
+        let mut transform = Route::new(&config, &Default::default()).unwrap();
+        let mut outputs = TransformOutputsBuf::new_with_capacity(
+            output_names
+                .iter()
+                .map(|output_name| {
+                    TransformOutput::new(DataType::all(), HashMap::new())
+                        .with_port(output_name.to_owned())
+                })
+                .collect(),
+            1,
+        );
+
+        let mut routes = HashMap::new();
+        for _ in 0..3 {
+            for user_id in 0..1000 {
+                let event = Event::from_json_value(
+                    serde_json::json!({"user_id": user_id}),
+                    LogNamespace::Legacy,
+                )
+                .unwrap();
+                transform.transform(event, &mut outputs);
+
+                let canary = outputs.drain_named("canary").count();
+                let production = outputs.drain_named("production").count();
+                assert_eq!(canary + production, 1);
+                let route = if canary == 1 { "canary" } else { "production" };
+                assert_eq!(*routes.entry(user_id).or_insert(route), route);
+            }
+        }
+        assert_eq!(outputs.drain_named(UNMATCHED_ROUTE).count(), 0);
+
+        let canaries = routes.values().filter(|route| **route == "canary").count();
+        assert!((20..=80).contains(&canaries), "{canaries} canaries");
+    }
+
+    #[test]
+    fn route_split_validation() {
+        let config = toml::from_str::<RouteConfig>(
+            r#"
+            route.first.type = "is_log"
+            split.routes.first = 1
+            split.routes.second = 0
+        "#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.validate(&schema::Definition::any()),
+            Err(vec![
+                "split route `first` conflicts with another named output".to_owned()
+            ])
+        );
+
+        let config = toml::from_str::<RouteConfig>("split.routes.first = 0").unwrap();
+        assert_eq!(
+            config.validate(&schema::Definition::any()),
+            Err(vec![
+                "split routes must have a positive total weight".to_owned()
+            ])
+        );
+    }
+
     #[tokio::test]
     async fn route_metrics_with_output_tag() {
         init_test();
@@ -13,7 +13,7 @@ use crate::{
     transforms::Transform,
 };
 
-use super::transform::Sample;
+use super::transform::{RateExpression, Sample};
 
 /// Configuration for the `sample` transform.
 #[configurable_component(transform(
@@ -46,6 +46,20 @@ pub struct SampleConfig {
 
     /// A logical condition used to exclude events from sampling.
     pub exclude: Option<AnyCondition>,
+
+    /// A [VRL][vrl] expression computing the rate at which each event is sampled, expressed as `1/N`.
+    ///
+    /// The expression must return a positive integer. If it fails, or returns another value, the
+    /// event is sampled at `rate` instead.
+    ///
+    /// Events sampled at a computed rate are sampled randomly rather than by counting them. When
+    /// `key_field` is set, the events with the same key are still sampled together, as long as
+    /// the expression returns the same rate for them.
+    ///
+    /// [vrl]: https://vector.dev/docs/reference/vrl
+    #[configurable(metadata(docs::examples = "if .level == \"error\" { 1 } else { 100 }"))]
+    #[configurable(metadata(docs::examples = "to_int(.sample_rate) ?? 10"))]
+    pub rate_expression: Option<String>,
 }
 
 impl GenerateConfig for SampleConfig {
@@ -54,6 +68,7 @@ impl GenerateConfig for SampleConfig {
             rate: 10,
             key_field: None,
             exclude: None::<AnyCondition>,
+            rate_expression: None,
         })
         .unwrap()
     }
@@ -63,15 +78,30 @@ impl GenerateConfig for SampleConfig {
 #[typetag::serde(name = "sample")]
 impl TransformConfig for SampleConfig {
     async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
-        Ok(Transform::function(Sample::new(
-            Self::NAME.to_string(),
-            self.rate,
-            self.key_field.clone(),
-            self.exclude
-                .as_ref()
-                .map(|condition| condition.build(&context.enrichment_tables))
-                .transpose()?,
-        )))
+        let rate_expression = self
+            .rate_expression
+            .as_deref()
+            .map(|source| {
+                RateExpression::compile(
+                    source,
+                    &context.enrichment_tables,
+                    context.globals.timezone(),
+                )
+            })
+            .transpose()?;
+
+        Ok(Transform::function(
+            Sample::new(
+                Self::NAME.to_string(),
+                self.rate,
+                self.key_field.clone(),
+                self.exclude
+                    .as_ref()
+                    .map(|condition| condition.build(&context.enrichment_tables))
+                    .transpose()?,
+            )
+            .with_rate_expression(rate_expression),
+        ))
     }
 
     fn input(&self) -> Input {
@@ -127,6 +157,7 @@ mod tests {
                 rate: 1,
                 key_field: None,
                 exclude: None,
+                rate_expression: None,
             };
             let (tx, rx) = mpsc::channel(1);
             let (topology, mut out) = create_topology(ReceiverStream::new(rx), config).await;
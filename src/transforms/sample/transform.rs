@@ -1,19 +1,104 @@
-use vector_lib::config::LegacyKey;
+use vector_lib::config::{LegacyKey, LogNamespace};
+use vector_lib::{compile_vrl, TimeZone};
+use vrl::compiler::runtime::Runtime;
+use vrl::compiler::{CompilationResult, CompileConfig, Program, TypeState};
+use vrl::diagnostic::Formatter;
 use vrl::event_path;
 
 use crate::{
     conditions::Condition,
-    event::Event,
-    internal_events::SampleEventDiscarded,
+    event::{Event, TargetEvents, Value, VrlTarget},
+    internal_events::{SampleEventDiscarded, SampleRateExpressionError},
     transforms::{FunctionTransform, OutputBuffer},
 };
 
+/// A VRL expression computing the rate at which an event is sampled.
+#[derive(Clone, Debug)]
+pub struct RateExpression {
+    program: Program,
+    timezone: TimeZone,
+}
+
+impl RateExpression {
+    // This function is dead code when the feature flag `transforms-impl-sample` is specified but not
+    // `transforms-sample`.
+    #[allow(dead_code)]
+    pub fn compile(
+        source: &str,
+        enrichment_tables: &vector_lib::enrichment::TableRegistry,
+        timezone: TimeZone,
+    ) -> crate::Result<Self> {
+        let functions = vrl::stdlib::all()
+            .into_iter()
+            .chain(vector_lib::enrichment::vrl_functions())
+            .chain(vector_vrl_functions::all())
+            .collect::<Vec<_>>();
+
+        let mut config = CompileConfig::default();
+        config.set_custom(enrichment_tables.clone());
+        config.set_read_only();
+
+        let CompilationResult {
+            program,
+            warnings,
+            config: _,
+        } = compile_vrl(source, &functions, &TypeState::default(), config)
+            .map_err(|diagnostics| Formatter::new(source, diagnostics).colored().to_string())?;
+
+        if !program.final_type_info().result.contains_integer() {
+            return Err("the `rate_expression` expression must return an integer".into());
+        }
+
+        if !warnings.is_empty() {
+            let warnings = Formatter::new(source, warnings).colored().to_string();
+            warn!(message = "VRL compilation warning.", %warnings);
+        }
+
+        Ok(Self { program, timezone })
+    }
+
+    /// Returns the rate of an event, or `None` if the expression doesn't resolve to a positive
+    /// integer.
+    fn rate(&self, event: Event) -> (Option<u64>, Event) {
+        let log_namespace = event
+            .maybe_as_log()
+            .map(|log| log.namespace())
+            .unwrap_or(LogNamespace::Legacy);
+        let mut target = VrlTarget::new(event, self.program.info(), false);
+        let result = Runtime::default().resolve(&mut target, &self.program, &self.timezone);
+        let event = match target.into_events(log_namespace) {
+            TargetEvents::One(event) => event,
+            _ => panic!(
+                "Event was modified in a rate expression. This is an internal compiler error."
+            ),
+        };
+
+        let rate = match result {
+            Ok(Value::Integer(rate)) if rate > 0 => Some(rate as u64),
+            Ok(value) => {
+                emit!(SampleRateExpressionError {
+                    error: &format!("expected a positive integer, found `{value}`"),
+                });
+                None
+            }
+            Err(error) => {
+                emit!(SampleRateExpressionError {
+                    error: error.to_string().as_ref(),
+                });
+                None
+            }
+        };
+        (rate, event)
+    }
+}
+
 #[derive(Clone)]
 pub struct Sample {
     name: String,
     rate: u64,
     key_field: Option<String>,
     exclude: Option<Condition>,
+    rate_expression: Option<RateExpression>,
     count: u64,
 }
 
@@ -32,9 +117,17 @@ impl Sample {
             rate,
             key_field,
             exclude,
+            rate_expression: None,
             count: 0,
         }
     }
+
+    /// Computes the rate of each event with an expression, falling back to the configured rate
+    /// when the expression fails.
+    pub fn with_rate_expression(mut self, rate_expression: Option<RateExpression>) -> Self {
+        self.rate_expression = rate_expression;
+        self
+    }
 }
 
 impl FunctionTransform for Sample {
@@ -53,6 +146,11 @@ impl FunctionTransform for Sample {
             }
         };
 
+        let (rate, mut event) = match &self.rate_expression {
+            Some(expression) => expression.rate(event),
+            None => (None, event),
+        };
+
         let value = self
             .key_field
             .as_ref()
@@ -69,15 +167,20 @@ impl FunctionTransform for Sample {
             })
             .map(|v| v.to_string_lossy());
 
-        let num = if let Some(value) = value {
-            seahash::hash(value.as_bytes())
-        } else {
-            self.count
+        // The events sampled at a rate computed by the expression are sampled randomly, rather
+        // than by counting them, as counting the events of different rates together would bias
+        // the sampling.
+        let (num, rate) = match (value, rate) {
+            (Some(value), rate) => (seahash::hash(value.as_bytes()), rate.unwrap_or(self.rate)),
+            (None, Some(rate)) => (rand::random(), rate),
+            (None, None) => {
+                let count = self.count;
+                self.count = (self.count + 1) % self.rate;
+                (count, self.rate)
+            }
         };
 
-        self.count = (self.count + 1) % self.rate;
-
-        if num % self.rate == 0 {
+        if num % rate == 0 {
             match event {
                 Event::Log(ref mut event) => {
                     event.namespace().insert_source_metadata(
@@ -85,11 +188,11 @@ impl FunctionTransform for Sample {
                         event,
                         Some(LegacyKey::Overwrite(vrl::path!("sample_rate"))),
                         vrl::path!("sample_rate"),
-                        self.rate.to_string(),
+                        rate.to_string(),
                     );
                 }
                 Event::Trace(ref mut event) => {
-                    event.insert(event_path!("sample_rate"), self.rate.to_string());
+                    event.insert(event_path!("sample_rate"), rate.to_string());
                 }
                 Event::Metric(_) => panic!("component can never receive metric events"),
             };
@@ -312,6 +415,86 @@ mod tests {
         assert_eq!(total_passed, 1);
     }
 
+    fn rate_expression(source: &str) -> RateExpression {
+        RateExpression::compile(source, &Default::default(), Default::default())
+            .expect("should not fail to compile the rate expression")
+    }
+
+    #[test]
+    fn rate_expression_must_return_an_integer() {
+        let error = RateExpression::compile(r#""ten""#, &Default::default(), Default::default())
+            .unwrap_err();
+        assert!(error.to_string().contains("must return an integer"));
+    }
+
+    #[test]
+    fn rate_expression_sets_the_rate_of_each_event() {
+        let num_events = 10000;
+
+        let events = random_events(num_events);
+        let mut sampler =
+            Sample::new("sample".to_string(), 1, None, None).with_rate_expression(Some(
+                rate_expression(r#"if contains(string!(.message), "a") { 1 } else { 2 }"#),
+            ));
+        let (always, sometimes): (Vec<_>, Vec<_>) = events
+            .into_iter()
+            .partition(|event| event.as_log()["message"].to_string_lossy().contains('a'));
+
+        let num_always = always.len();
+        let passed = always
+            .into_iter()
+            .filter_map(|event| transform_one(&mut sampler, event))
+            .collect::<Vec<_>>();
+        assert_eq!(passed.len(), num_always);
+        assert!(passed
+            .iter()
+            .all(|event| event.as_log()["sample_rate"] == "1".into()));
+
+        let num_sometimes = sometimes.len();
+        let passed = sometimes
+            .into_iter()
+            .filter_map(|event| transform_one(&mut sampler, event))
+            .collect::<Vec<_>>();
+        assert_relative_eq!(
+            passed.len() as f64 / num_sometimes as f64,
+            0.5,
+            epsilon = 0.05
+        );
+        assert!(passed
+            .iter()
+            .all(|event| event.as_log()["sample_rate"] == "2".into()));
+    }
+
+    #[test]
+    fn rate_expression_falls_back_to_the_configured_rate() {
+        let mut sampler = Sample::new("sample".to_string(), 2, None, None)
+            .with_rate_expression(Some(rate_expression("to_int(.rate) ?? 0")));
+
+        let mut log = LogEvent::from("hello");
+        log.insert("rate", "invalid");
+        let total_passed = (0..10)
+            .filter_map(|_| transform_one(&mut sampler, log.clone().into()))
+            .inspect(|event| assert_eq!(event.as_log()["sample_rate"], "2".into()))
+            .count();
+        assert_eq!(total_passed, 5);
+    }
+
+    #[test]
+    fn rate_expression_samples_the_same_keys_together() {
+        let mut sampler = Sample::new("sample".to_string(), 1, Some("trace_id".to_string()), None)
+            .with_rate_expression(Some(rate_expression("to_int(.rate) ?? 1")));
+
+        for trace_id in 0..100 {
+            let mut log = LogEvent::from("hello");
+            log.insert("trace_id", trace_id);
+            log.insert("rate", 4);
+            let passed = (0..10)
+                .filter_map(|_| transform_one(&mut sampler, log.clone().into()))
+                .count();
+            assert!(passed == 0 || passed == 10);
+        }
+    }
+
     fn random_events(n: usize) -> Vec<Event> {
         random_lines(10)
             .take(n)
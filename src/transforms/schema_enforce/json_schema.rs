@@ -0,0 +1,625 @@
+//! The subset of [JSON Schema][json_schema] that the `schema_enforce` transform validates events
+//! against.
+//!
+//! [json_schema]: https://json-schema.org/
+
+use std::{collections::BTreeMap, fmt};
+
+use ordered_float::NotNan;
+use regex::Regex;
+use serde_json::{Map, Value as JsonValue};
+use snafu::Snafu;
+use vrl::value::{KeyString, ObjectMap, Value};
+
+/// The keywords that only annotate a schema, without constraining the values it matches.
+const ANNOTATIONS: &[&str] = &[
+    "$comment",
+    "$id",
+    "$schema",
+    "deprecated",
+    "description",
+    "examples",
+    "format",
+    "readOnly",
+    "title",
+    "writeOnly",
+];
+
+#[derive(Debug, Snafu)]
+pub enum SchemaError {
+    #[snafu(display("Unsupported keyword `{}` at `{}`", keyword, pointer))]
+    UnsupportedKeyword { keyword: String, pointer: String },
+    #[snafu(display("Invalid value at `{}`: {}", pointer, reason))]
+    InvalidKeyword { pointer: String, reason: String },
+}
+
+/// How values are conformed to a schema while they're validated.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Conform {
+    pub coerce_types: bool,
+    pub drop_unknown_fields: bool,
+    pub fill_defaults: bool,
+}
+
+/// A value that doesn't conform to a schema.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Violation {
+    pub path: String,
+    pub message: String,
+}
+
+impl Violation {
+    fn new(path: &str, message: impl Into<String>) -> Self {
+        Self {
+            path: if path.is_empty() { "." } else { path }.to_owned(),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum InstanceType {
+    Null,
+    Boolean,
+    Integer,
+    Number,
+    String,
+    Array,
+    Object,
+}
+
+impl InstanceType {
+    fn parse(name: &str) -> Option<Self> {
+        Some(match name {
+            "null" => Self::Null,
+            "boolean" => Self::Boolean,
+            "integer" => Self::Integer,
+            "number" => Self::Number,
+            "string" => Self::String,
+            "array" => Self::Array,
+            "object" => Self::Object,
+            _ => return None,
+        })
+    }
+
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Null => "null",
+            Self::Boolean => "boolean",
+            Self::Integer => "integer",
+            Self::Number => "number",
+            Self::String => "string",
+            Self::Array => "array",
+            Self::Object => "object",
+        }
+    }
+
+    fn matches(self, value: &Value) -> bool {
+        match (self, value) {
+            (Self::Null, Value::Null)
+            | (Self::Boolean, Value::Boolean(_))
+            | (Self::Integer, Value::Integer(_))
+            | (Self::Number, Value::Integer(_) | Value::Float(_))
+            // Timestamps are encoded as strings.
+            | (Self::String, Value::Bytes(_) | Value::Timestamp(_))
+            | (Self::Array, Value::Array(_))
+            | (Self::Object, Value::Object(_)) => true,
+            (Self::Integer, Value::Float(float)) => float.fract() == 0.0,
+            _ => false,
+        }
+    }
+
+    /// Converts a value of another type to this type, if it has an unambiguous representation.
+    fn coerce(self, value: &Value) -> Option<Value> {
+        match (self, value) {
+            (Self::Boolean, Value::Bytes(bytes)) => match bytes.as_ref() {
+                b"true" => Some(Value::Boolean(true)),
+                b"false" => Some(Value::Boolean(false)),
+                _ => None,
+            },
+            (Self::Integer, Value::Bytes(bytes)) => std::str::from_utf8(bytes)
+                .ok()?
+                .trim()
+                .parse()
+                .ok()
+                .map(Value::Integer),
+            (Self::Number, Value::Bytes(bytes)) => {
+                let number = std::str::from_utf8(bytes).ok()?.trim();
+                match number.parse() {
+                    Ok(integer) => Some(Value::Integer(integer)),
+                    Err(_) => number
+                        .parse()
+                        .ok()
+                        .and_then(|float: f64| NotNan::new(float).ok())
+                        .map(Value::Float),
+                }
+            }
+            (Self::String, Value::Integer(_) | Value::Float(_) | Value::Boolean(_)) => {
+                Some(value.to_string_lossy().into_owned().into())
+            }
+            _ => None,
+        }
+    }
+}
+
+const fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Bytes(_) => "string",
+        Value::Regex(_) => "regex",
+        Value::Integer(_) => "integer",
+        Value::Float(_) => "number",
+        Value::Boolean(_) => "boolean",
+        Value::Timestamp(_) => "timestamp",
+        Value::Object(_) => "object",
+        Value::Array(_) => "array",
+        Value::Null => "null",
+    }
+}
+
+/// A parsed JSON Schema.
+///
+/// The supported keywords are the ones constraining the type, the fields of objects, the items of
+/// arrays, the length and pattern of strings, the range of numbers, and the allowed values.
+/// Annotations are ignored, and any other keyword, such as `$ref` or `oneOf`, is rejected so that
+/// a schema is never silently enforced partially.
+#[derive(Clone, Debug, Default)]
+pub struct Schema {
+    /// Whether the schema rejects every value, as the `false` schema does.
+    reject: bool,
+    types: Vec<InstanceType>,
+    allowed: Option<Vec<Value>>,
+    constant: Option<Value>,
+    default: Option<Value>,
+    properties: BTreeMap<String, Schema>,
+    required: Vec<String>,
+    additional_properties: Option<Box<Schema>>,
+    items: Option<Box<Schema>>,
+    min_items: Option<usize>,
+    max_items: Option<usize>,
+    min_length: Option<usize>,
+    max_length: Option<usize>,
+    pattern: Option<Regex>,
+    minimum: Option<f64>,
+    maximum: Option<f64>,
+    exclusive_minimum: Option<f64>,
+    exclusive_maximum: Option<f64>,
+}
+
+impl Schema {
+    pub fn parse(json: &JsonValue) -> Result<Self, SchemaError> {
+        Self::parse_at(json, "#")
+    }
+
+    fn parse_at(json: &JsonValue, pointer: &str) -> Result<Self, SchemaError> {
+        let keywords = match json {
+            JsonValue::Bool(true) => return Ok(Self::default()),
+            JsonValue::Bool(false) => {
+                return Ok(Self {
+                    reject: true,
+                    ..Self::default()
+                })
+            }
+            JsonValue::Object(keywords) => keywords,
+            _ => return Err(invalid(pointer, "a schema must be an object or a boolean")),
+        };
+
+        let mut schema = Self::default();
+        for (keyword, value) in keywords {
+            let pointer = format!("{pointer}/{keyword}");
+            match keyword.as_str() {
+                "type" => schema.types = parse_types(value, &pointer)?,
+                "enum" => {
+                    let values = value
+                        .as_array()
+                        .ok_or_else(|| invalid(&pointer, "must be an array"))?;
+                    schema.allowed = Some(values.iter().cloned().map(Value::from).collect());
+                }
+                "const" => schema.constant = Some(value.clone().into()),
+                "default" => schema.default = Some(value.clone().into()),
+                "properties" => {
+                    for (name, property) in as_object(value, &pointer)? {
+                        let property = Self::parse_at(property, &format!("{pointer}/{name}"))?;
+                        schema.properties.insert(name.clone(), property);
+                    }
+                }
+                "required" => {
+                    schema.required = value
+                        .as_array()
+                        .and_then(|names| {
+                            names
+                                .iter()
+                                .map(|name| name.as_str().map(ToOwned::to_owned))
+                                .collect()
+                        })
+                        .ok_or_else(|| invalid(&pointer, "must be an array of strings"))?;
+                }
+                "additionalProperties" => {
+                    schema.additional_properties = Some(Box::new(Self::parse_at(value, &pointer)?))
+                }
+                "items" => schema.items = Some(Box::new(Self::parse_at(value, &pointer)?)),
+                "minItems" => schema.min_items = Some(as_usize(value, &pointer)?),
+                "maxItems" => schema.max_items = Some(as_usize(value, &pointer)?),
+                "minLength" => schema.min_length = Some(as_usize(value, &pointer)?),
+                "maxLength" => schema.max_length = Some(as_usize(value, &pointer)?),
+                "pattern" => {
+                    let pattern = value
+                        .as_str()
+                        .ok_or_else(|| invalid(&pointer, "must be a string"))?;
+                    let pattern = Regex::new(pattern)
+                        .map_err(|error| invalid(&pointer, error.to_string()))?;
+                    schema.pattern = Some(pattern);
+                }
+                "minimum" => schema.minimum = Some(as_f64(value, &pointer)?),
+                "maximum" => schema.maximum = Some(as_f64(value, &pointer)?),
+                "exclusiveMinimum" => schema.exclusive_minimum = Some(as_f64(value, &pointer)?),
+                "exclusiveMaximum" => schema.exclusive_maximum = Some(as_f64(value, &pointer)?),
+                keyword if ANNOTATIONS.contains(&keyword) => {}
+                keyword => {
+                    return Err(SchemaError::UnsupportedKeyword {
+                        keyword: keyword.to_owned(),
+                        pointer,
+                    })
+                }
+            }
+        }
+
+        Ok(schema)
+    }
+
+    /// Conforms a value to the schema, and returns the ways it still doesn't conform to it.
+    pub fn enforce(&self, value: &mut Value, conform: Conform) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        self.enforce_at(value, conform, "", &mut violations);
+        violations
+    }
+
+    fn enforce_at(
+        &self,
+        value: &mut Value,
+        conform: Conform,
+        path: &str,
+        violations: &mut Vec<Violation>,
+    ) {
+        if self.reject {
+            violations.push(Violation::new(path, "is not allowed"));
+            return;
+        }
+
+        if !self.types.is_empty() && !self.types.iter().any(|ty| ty.matches(value)) {
+            let coerced = conform
+                .coerce_types
+                .then(|| self.types.iter().find_map(|ty| ty.coerce(value)))
+                .flatten();
+            match coerced {
+                Some(coerced) => *value = coerced,
+                None => {
+                    let expected = self
+                        .types
+                        .iter()
+                        .map(|ty| ty.name())
+                        .collect::<Vec<_>>()
+                        .join(" or ");
+                    violations.push(Violation::new(
+                        path,
+                        format!("expected {expected}, found {}", type_name(value)),
+                    ));
+                    return;
+                }
+            }
+        }
+
+        if let Some(allowed) = &self.allowed {
+            if !allowed.contains(value) {
+                violations.push(Violation::new(path, "is not one of the allowed values"));
+            }
+        }
+        if let Some(constant) = &self.constant {
+            if constant != value {
+                violations.push(Violation::new(path, "is not the allowed value"));
+            }
+        }
+
+        match value {
+            Value::Object(fields) => self.enforce_fields(fields, conform, path, violations),
+            Value::Array(items) => self.enforce_items(items, conform, path, violations),
+            Value::Bytes(bytes) => {
+                self.check_string(&String::from_utf8_lossy(bytes), path, violations)
+            }
+            Value::Integer(integer) => self.check_number(*integer as f64, path, violations),
+            Value::Float(float) => self.check_number(float.into_inner(), path, violations),
+            _ => {}
+        }
+    }
+
+    fn enforce_fields(
+        &self,
+        fields: &mut ObjectMap,
+        conform: Conform,
+        path: &str,
+        violations: &mut Vec<Violation>,
+    ) {
+        if conform.fill_defaults {
+            for (name, property) in &self.properties {
+                if let Some(default) = &property.default {
+                    fields
+                        .entry(KeyString::from(name.as_str()))
+                        .or_insert_with(|| default.clone());
+                }
+            }
+        }
+        if conform.drop_unknown_fields {
+            fields.retain(|name, _| self.properties.contains_key(name.as_str()));
+        }
+
+        for name in &self.required {
+            if !fields.contains_key(name.as_str()) {
+                violations.push(Violation::new(&field_path(path, name), "is required"));
+            }
+        }
+
+        for (name, field) in fields.iter_mut() {
+            let schema = self
+                .properties
+                .get(name.as_str())
+                .or(self.additional_properties.as_deref());
+            if let Some(schema) = schema {
+                schema.enforce_at(field, conform, &field_path(path, name), violations);
+            }
+        }
+    }
+
+    fn enforce_items(
+        &self,
+        items: &mut [Value],
+        conform: Conform,
+        path: &str,
+        violations: &mut Vec<Violation>,
+    ) {
+        if let Some(min_items) = self.min_items.filter(|min| items.len() < *min) {
+            violations.push(Violation::new(
+                path,
+                format!("must have at least {min_items} items"),
+            ));
+        }
+        if let Some(max_items) = self.max_items.filter(|max| items.len() > *max) {
+            violations.push(Violation::new(
+                path,
+                format!("must have at most {max_items} items"),
+            ));
+        }
+
+        if let Some(schema) = &self.items {
+            for (index, item) in items.iter_mut().enumerate() {
+                schema.enforce_at(item, conform, &format!("{path}[{index}]"), violations);
+            }
+        }
+    }
+
+    fn check_string(&self, string: &str, path: &str, violations: &mut Vec<Violation>) {
+        let length = string.chars().count();
+        if let Some(min_length) = self.min_length.filter(|min| length < *min) {
+            violations.push(Violation::new(
+                path,
+                format!("must be at least {min_length} characters long"),
+            ));
+        }
+        if let Some(max_length) = self.max_length.filter(|max| length > *max) {
+            violations.push(Violation::new(
+                path,
+                format!("must be at most {max_length} characters long"),
+            ));
+        }
+        if let Some(pattern) = self.pattern.as_ref().filter(|re| !re.is_match(string)) {
+            violations.push(Violation::new(
+                path,
+                format!("must match the pattern `{pattern}`"),
+            ));
+        }
+    }
+
+    fn check_number(&self, number: f64, path: &str, violations: &mut Vec<Violation>) {
+        if let Some(minimum) = self.minimum.filter(|min| number < *min) {
+            violations.push(Violation::new(path, format!("must be at least {minimum}")));
+        }
+        if let Some(maximum) = self.maximum.filter(|max| number > *max) {
+            violations.push(Violation::new(path, format!("must be at most {maximum}")));
+        }
+        if let Some(minimum) = self.exclusive_minimum.filter(|min| number <= *min) {
+            violations.push(Violation::new(
+                path,
+                format!("must be greater than {minimum}"),
+            ));
+        }
+        if let Some(maximum) = self.exclusive_maximum.filter(|max| number >= *max) {
+            violations.push(Violation::new(path, format!("must be less than {maximum}")));
+        }
+    }
+}
+
+fn parse_types(value: &JsonValue, pointer: &str) -> Result<Vec<InstanceType>, SchemaError> {
+    let names = match value {
+        JsonValue::String(name) => vec![name.as_str()],
+        JsonValue::Array(names) => names
+            .iter()
+            .map(JsonValue::as_str)
+            .collect::<Option<_>>()
+            .ok_or_else(|| invalid(pointer, "must be a string or an array of strings"))?,
+        _ => return Err(invalid(pointer, "must be a string or an array of strings")),
+    };
+
+    names
+        .into_iter()
+        .map(|name| {
+            InstanceType::parse(name)
+                .ok_or_else(|| invalid(pointer, format!("unknown type `{name}`")))
+        })
+        .collect()
+}
+
+fn as_object<'a>(
+    value: &'a JsonValue,
+    pointer: &str,
+) -> Result<&'a Map<String, JsonValue>, SchemaError> {
+    value
+        .as_object()
+        .ok_or_else(|| invalid(pointer, "must be an object"))
+}
+
+fn as_usize(value: &JsonValue, pointer: &str) -> Result<usize, SchemaError> {
+    value
+        .as_u64()
+        .map(|value| value as usize)
+        .ok_or_else(|| invalid(pointer, "must be a non-negative integer"))
+}
+
+fn as_f64(value: &JsonValue, pointer: &str) -> Result<f64, SchemaError> {
+    value
+        .as_f64()
+        .ok_or_else(|| invalid(pointer, "must be a number"))
+}
+
+fn invalid(pointer: &str, reason: impl Into<String>) -> SchemaError {
+    SchemaError::InvalidKeyword {
+        pointer: pointer.to_owned(),
+        reason: reason.into(),
+    }
+}
+
+/// Appends a field to a path, quoting its name unless it's an identifier.
+fn field_path(path: &str, name: &str) -> String {
+    if !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        format!("{path}.{name}")
+    } else {
+        format!("{path}.{name:?}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn enforce(schema: JsonValue, value: JsonValue, conform: Conform) -> (Value, Vec<String>) {
+        let schema = Schema::parse(&schema).unwrap();
+        let mut value = Value::from(value);
+        let violations = schema.enforce(&mut value, conform);
+        (value, violations.iter().map(ToString::to_string).collect())
+    }
+
+    #[test]
+    fn reports_nested_violations() {
+        let schema = json!({
+            "type": "object",
+            "required": ["service", "status"],
+            "properties": {
+                "service": {"type": "string", "minLength": 1},
+                "status": {"type": "integer", "minimum": 100, "maximum": 599},
+                "tags": {"type": "array", "items": {"enum": ["a", "b"]}},
+                "user agent": {"type": "string", "pattern": "^Mozilla/"}
+            }
+        });
+        let value = json!({
+            "service": "",
+            "tags": ["a", "c"],
+            "user agent": "curl/8.0"
+        });
+
+        let (_, violations) = enforce(schema, value, Conform::default());
+        assert_eq!(
+            violations,
+            vec![
+                ".status: is required",
+                ".service: must be at least 1 characters long",
+                ".tags[1]: is not one of the allowed values",
+                r#"."user agent": must match the pattern `^Mozilla/`"#,
+            ]
+        );
+    }
+
+    #[test]
+    fn conforms_values() {
+        let schema = json!({
+            "type": "object",
+            "additionalProperties": false,
+            "properties": {
+                "status": {"type": "integer"},
+                "ratio": {"type": "number"},
+                "enabled": {"type": "boolean"},
+                "code": {"type": "string"},
+                "level": {"type": "string", "default": "info"}
+            }
+        });
+        let value = json!({
+            "status": "200",
+            "ratio": "0.5",
+            "enabled": "true",
+            "code": 42,
+            "extra": "field"
+        });
+
+        let (_, violations) = enforce(schema.clone(), value.clone(), Conform::default());
+        assert_eq!(
+            violations,
+            vec![
+                ".code: expected string, found integer",
+                ".enabled: expected boolean, found string",
+                ".extra: is not allowed",
+                ".ratio: expected number, found string",
+                ".status: expected integer, found string",
+            ]
+        );
+
+        let conform = Conform {
+            coerce_types: true,
+            drop_unknown_fields: true,
+            fill_defaults: true,
+        };
+        let (value, violations) = enforce(schema, value, conform);
+        assert!(violations.is_empty(), "{violations:?}");
+        assert_eq!(
+            value,
+            Value::from(json!({
+                "status": 200,
+                "ratio": 0.5,
+                "enabled": true,
+                "code": "42",
+                "level": "info"
+            }))
+        );
+    }
+
+    #[test]
+    fn does_not_coerce_ambiguous_values() {
+        let schema = json!({"type": "integer"});
+        let conform = Conform {
+            coerce_types: true,
+            ..Conform::default()
+        };
+
+        let (value, violations) = enforce(schema, json!("2.5"), conform);
+        assert_eq!(value, Value::from("2.5"));
+        assert_eq!(violations, vec![".: expected integer, found string"]);
+    }
+
+    #[test]
+    fn rejects_unsupported_keywords() {
+        let schema = json!({"properties": {"id": {"$ref": "#/definitions/id"}}});
+        assert_eq!(
+            Schema::parse(&schema).unwrap_err().to_string(),
+            "Unsupported keyword `$ref` at `#/properties/id/$ref`"
+        );
+
+        let schema = json!({"type": "timestamp"});
+        assert_eq!(
+            Schema::parse(&schema).unwrap_err().to_string(),
+            "Invalid value at `#/type`: unknown type `timestamp`"
+        );
+    }
+}
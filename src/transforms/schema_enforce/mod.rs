@@ -0,0 +1,437 @@
+use std::{collections::BTreeMap, fs, io, path::PathBuf};
+
+use snafu::{ResultExt, Snafu};
+use vector_lib::config::{clone_input_definitions, LogNamespace};
+use vector_lib::configurable::configurable_component;
+use vector_lib::lookup::{metadata_path, owned_value_path, path, PathPrefix};
+use vector_lib::schema::Definition;
+use vrl::value::Kind;
+
+use crate::{
+    config::{
+        log_schema, ComponentKey, DataType, GenerateConfig, Input, OutputId, TransformConfig,
+        TransformContext, TransformOutput,
+    },
+    event::{Event, LogEvent},
+    internal_events::SchemaEnforceViolation,
+    schema,
+    transforms::{SyncTransform, Transform, TransformOutputsBuf},
+};
+
+mod json_schema;
+
+use self::json_schema::{Conform, Schema, SchemaError, Violation};
+
+const DROPPED: &str = "dropped";
+
+/// Configuration for the `schema_enforce` transform.
+#[configurable_component(transform(
+    "schema_enforce",
+    "Validate events against a JSON Schema, and quarantine the ones that do not conform to it."
+))]
+#[derive(Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct SchemaEnforceConfig {
+    /// The [JSON Schema][json_schema] to validate the events against.
+    ///
+    /// The supported keywords are `type`, `enum`, `const`, `default`, `properties`, `required`,
+    /// `additionalProperties`, `items`, `minItems`, `maxItems`, `minLength`, `maxLength`,
+    /// `pattern`, `minimum`, `maximum`, `exclusiveMinimum`, and `exclusiveMaximum`. Annotations
+    /// such as `title` and `description` are ignored, and any other keyword is rejected.
+    ///
+    /// Required if `schema_file` is missing.
+    ///
+    /// [json_schema]: https://json-schema.org/
+    #[configurable(metadata(
+        docs::examples = r#"{"type": "object", "required": ["message"], "properties": {"message": {"type": "string"}}}"#
+    ))]
+    pub schema: Option<String>,
+
+    /// File path to the [JSON Schema][json_schema] to validate the events against.
+    ///
+    /// If a relative path is provided, its root is the current working directory.
+    ///
+    /// Required if `schema` is missing.
+    ///
+    /// [json_schema]: https://json-schema.org/
+    #[configurable(metadata(docs::examples = "./schemas/access_log.json"))]
+    pub schema_file: Option<PathBuf>,
+
+    /// Converts fields to the type required by the schema, when their value has an unambiguous
+    /// representation in that type.
+    ///
+    /// Strings are converted to integers, numbers, and booleans, and integers, numbers, and booleans
+    /// are converted to strings.
+    #[serde(default)]
+    pub coerce_types: bool,
+
+    /// Removes the fields of objects that are not declared in their `properties`, instead of
+    /// validating them against `additionalProperties`.
+    #[serde(default)]
+    pub drop_unknown_fields: bool,
+
+    /// Inserts the `default` value of the properties of objects that are missing.
+    #[serde(default)]
+    pub fill_defaults: bool,
+
+    /// Reroutes the events that do not conform to the schema to a named output instead of
+    /// dropping them.
+    ///
+    /// When set to `true`, the original event is forwarded to a specially-named output,
+    /// `dropped`, for quarantine. The event is annotated with additional fields describing how it
+    /// violates the schema.
+    #[serde(default = "crate::serde::default_true")]
+    #[configurable(metadata(docs::human_name = "Reroute Dropped Events"))]
+    pub reroute_dropped: bool,
+}
+
+#[derive(Debug, Snafu)]
+pub enum BuildError {
+    #[snafu(display("must provide exactly one of `schema` or `schema_file` configuration"))]
+    SchemaAndOrFile,
+
+    #[snafu(display("Could not read JSON Schema file {:?}: {}", path, source))]
+    FileReadFailed { path: PathBuf, source: io::Error },
+
+    #[snafu(display("Could not parse JSON Schema: {}", source))]
+    InvalidJson { source: serde_json::Error },
+
+    #[snafu(display("Invalid JSON Schema: {}", source))]
+    InvalidSchema { source: SchemaError },
+}
+
+impl SchemaEnforceConfig {
+    fn load_schema(&self) -> Result<Schema, BuildError> {
+        let json = match (&self.schema, &self.schema_file) {
+            (Some(schema), None) => schema.clone(),
+            (None, Some(path)) => {
+                fs::read_to_string(path).with_context(|_| FileReadFailedSnafu { path })?
+            }
+            _ => return Err(BuildError::SchemaAndOrFile),
+        };
+        let json = serde_json::from_str(&json).context(InvalidJsonSnafu)?;
+
+        Schema::parse(&json).context(InvalidSchemaSnafu)
+    }
+
+    const fn conform(&self) -> Conform {
+        Conform {
+            coerce_types: self.coerce_types,
+            drop_unknown_fields: self.drop_unknown_fields,
+            fill_defaults: self.fill_defaults,
+        }
+    }
+}
+
+impl GenerateConfig for SchemaEnforceConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            schema: Some(r#"{"type": "object"}"#.to_owned()),
+            reroute_dropped: true,
+            ..Self::default()
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "schema_enforce")]
+impl TransformConfig for SchemaEnforceConfig {
+    async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
+        let schema_enforce = SchemaEnforce::new(self, context)?;
+        Ok(Transform::synchronous(schema_enforce))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn outputs(
+        &self,
+        _: vector_lib::enrichment::TableRegistry,
+        input_definitions: &[(OutputId, schema::Definition)],
+        _: LogNamespace,
+    ) -> Vec<TransformOutput> {
+        let conform = self.conform();
+        let default_definitions =
+            if conform.coerce_types || conform.drop_unknown_fields || conform.fill_defaults {
+                // The fields of the events are conformed to the schema, which isn't mapped to a
+                // definition.
+                input_definitions
+                    .iter()
+                    .map(|(output, definition)| {
+                        (
+                            output.clone(),
+                            Definition::default_for_namespace(definition.log_namespaces()),
+                        )
+                    })
+                    .collect()
+            } else {
+                clone_input_definitions(input_definitions)
+            };
+        let default_output = TransformOutput::new(DataType::Log, default_definitions);
+
+        if !self.reroute_dropped {
+            return vec![default_output];
+        }
+
+        // The dropped events are the original events, annotated with the violations.
+        let dropped_definitions = input_definitions
+            .iter()
+            .map(|(output, definition)| {
+                let dropped = Kind::object(BTreeMap::from([
+                    ("reason".into(), Kind::bytes()),
+                    ("message".into(), Kind::bytes()),
+                    ("component_id".into(), Kind::bytes()),
+                    ("component_type".into(), Kind::bytes()),
+                    ("component_kind".into(), Kind::bytes()),
+                ]));
+                let definition = Definition::combine_log_namespaces(
+                    definition.log_namespaces(),
+                    definition.clone().with_event_field(
+                        &log_schema()
+                            .metadata_key()
+                            .expect("valid metadata key")
+                            .with_field_appended("dropped"),
+                        dropped.clone(),
+                        None,
+                    ),
+                    definition.clone().with_metadata_field(
+                        &owned_value_path!("vector", "dropped"),
+                        dropped,
+                        None,
+                    ),
+                );
+                (output.clone(), definition)
+            })
+            .collect();
+
+        vec![
+            default_output,
+            TransformOutput::new(DataType::Log, dropped_definitions).with_port(DROPPED),
+        ]
+    }
+
+    fn enable_concurrency(&self) -> bool {
+        true
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SchemaEnforce {
+    schema: Schema,
+    conform: Conform,
+    reroute_dropped: bool,
+    component_key: Option<ComponentKey>,
+}
+
+impl SchemaEnforce {
+    pub fn new(config: &SchemaEnforceConfig, context: &TransformContext) -> crate::Result<Self> {
+        Ok(Self {
+            schema: config.load_schema()?,
+            conform: config.conform(),
+            reroute_dropped: config.reroute_dropped,
+            component_key: context.key.clone(),
+        })
+    }
+
+    fn annotate_dropped(&self, log: &mut LogEvent, violations: &str) {
+        let dropped = serde_json::json!({
+            "reason": "schema_violation",
+            "message": violations,
+            "component_id": self.component_key,
+            "component_type": "schema_enforce",
+            "component_kind": "transform",
+        });
+
+        match log.namespace() {
+            LogNamespace::Legacy => {
+                if let Some(metadata_key) = log_schema().metadata_key() {
+                    log.insert(
+                        (PathPrefix::Event, metadata_key.concat(path!("dropped"))),
+                        dropped,
+                    );
+                }
+            }
+            LogNamespace::Vector => {
+                log.insert(metadata_path!("vector", "dropped"), dropped);
+            }
+        }
+    }
+}
+
+impl SyncTransform for SchemaEnforce {
+    fn transform(&mut self, event: Event, output: &mut TransformOutputsBuf) {
+        let mut log = event.into_log();
+        // The original event is quarantined, rather than the partially conformed one.
+        let original = self.reroute_dropped.then(|| log.clone());
+
+        let violations = self.schema.enforce(log.value_mut(), self.conform);
+        if violations.is_empty() {
+            output.push(None, log.into());
+            return;
+        }
+
+        let violations = violations
+            .iter()
+            .map(Violation::to_string)
+            .collect::<Vec<_>>()
+            .join("; ");
+        match original {
+            Some(mut original) => {
+                self.annotate_dropped(&mut original, &violations);
+                output.push(Some(DROPPED), original.into());
+            }
+            None => emit!(SchemaEnforceViolation {
+                violations: &violations
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use indoc::indoc;
+    use vector_lib::transform::OutputBuffer;
+
+    use super::*;
+    use crate::event::Value;
+
+    const SCHEMA: &str = r#"{
+        "type": "object",
+        "required": ["message", "status"],
+        "properties": {
+            "message": {"type": "string"},
+            "status": {"type": "integer"},
+            "level": {"type": "string", "enum": ["info", "error"], "default": "info"}
+        }
+    }"#;
+
+    fn enforcer(extra: &str) -> SchemaEnforce {
+        let config = format!("schema = '''{SCHEMA}'''\n{extra}");
+        let config = toml::from_str::<SchemaEnforceConfig>(&config).unwrap();
+        let context = TransformContext {
+            key: Some(ComponentKey::from("enforce")),
+            ..Default::default()
+        };
+        SchemaEnforce::new(&config, &context).unwrap()
+    }
+
+    fn transform(
+        enforcer: &mut SchemaEnforce,
+        event: serde_json::Value,
+    ) -> (OutputBuffer, OutputBuffer) {
+        let mut outputs = TransformOutputsBuf::new_with_capacity(
+            vec![
+                TransformOutput::new(DataType::Log, HashMap::new()),
+                TransformOutput::new(DataType::Log, HashMap::new()).with_port(DROPPED),
+            ],
+            1,
+        );
+        let event = Event::from_json_value(event, LogNamespace::Legacy).unwrap();
+        enforcer.transform(event, &mut outputs);
+
+        let mut named = outputs.take_all_named();
+        (outputs.take_primary(), named.remove(DROPPED).unwrap())
+    }
+
+    fn only_log(buffer: OutputBuffer) -> LogEvent {
+        let mut events = buffer.into_events().collect::<Vec<_>>();
+        assert_eq!(events.len(), 1);
+        events.remove(0).into_log()
+    }
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<SchemaEnforceConfig>();
+    }
+
+    #[test]
+    fn forwards_conforming_events() {
+        let mut enforcer = enforcer("");
+        let event = serde_json::json!({"message": "hello", "status": 200, "extra": true});
+
+        let (primary, dropped) = transform(&mut enforcer, event.clone());
+        assert!(dropped.is_empty());
+        assert_eq!(
+            only_log(primary),
+            Event::from_json_value(event, LogNamespace::Legacy)
+                .unwrap()
+                .into_log()
+        );
+    }
+
+    #[test]
+    fn reroutes_violations() {
+        let mut enforcer = enforcer("fill_defaults = true");
+        let (primary, dropped) = transform(
+            &mut enforcer,
+            serde_json::json!({"message": "hello", "status": "200"}),
+        );
+        assert!(primary.is_empty());
+
+        let log = only_log(dropped);
+        assert!(!log.contains("level"), "the original event is quarantined");
+        assert_eq!(log["status"], Value::from("200"));
+        assert_eq!(
+            log["metadata.dropped.message"],
+            Value::from(".status: expected integer, found string")
+        );
+        assert_eq!(
+            log["metadata.dropped.reason"],
+            Value::from("schema_violation")
+        );
+        assert_eq!(log["metadata.dropped.component_id"], Value::from("enforce"));
+    }
+
+    #[test]
+    fn drops_violations() {
+        let mut enforcer = enforcer("reroute_dropped = false");
+        let (primary, dropped) = transform(
+            &mut enforcer,
+            serde_json::json!({"message": "hello", "level": "debug"}),
+        );
+        assert!(primary.is_empty());
+        assert!(dropped.is_empty());
+    }
+
+    #[test]
+    fn conforms_events() {
+        let mut enforcer = enforcer(indoc! {"
+            coerce_types = true
+            drop_unknown_fields = true
+            fill_defaults = true
+        "});
+        let (primary, dropped) = transform(
+            &mut enforcer,
+            serde_json::json!({"message": "hello", "status": "200", "extra": true}),
+        );
+        assert!(dropped.is_empty());
+
+        let log = only_log(primary);
+        assert_eq!(
+            log.value(),
+            &Value::from(serde_json::json!({"message": "hello", "status": 200, "level": "info"}))
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_schemas() {
+        let config = toml::from_str::<SchemaEnforceConfig>(indoc! {r#"
+            schema = '{"oneOf": []}'
+        "#})
+        .unwrap();
+        assert_eq!(
+            config.load_schema().unwrap_err().to_string(),
+            "Invalid JSON Schema: Unsupported keyword `oneOf` at `#/oneOf`"
+        );
+
+        let config = SchemaEnforceConfig::default();
+        assert!(matches!(
+            config.load_schema(),
+            Err(BuildError::SchemaAndOrFile)
+        ));
+    }
+}
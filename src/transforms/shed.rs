@@ -0,0 +1,399 @@
+use std::{
+    collections::{BTreeMap, VecDeque},
+    num::{NonZeroU32, NonZeroUsize},
+    pin::Pin,
+};
+
+use async_stream::stream;
+use futures::{FutureExt, Stream, StreamExt};
+use governor::{Quota, RateLimiter};
+use vector_lib::config::{clone_input_definitions, LogNamespace};
+use vector_lib::configurable::configurable_component;
+use vector_lib::{compile_vrl, TimeZone};
+use vrl::compiler::runtime::Runtime;
+use vrl::compiler::{CompilationResult, CompileConfig, Program, TypeState};
+use vrl::diagnostic::Formatter;
+
+use crate::{
+    config::{
+        DataType, GenerateConfig, Input, OutputId, TransformConfig, TransformContext,
+        TransformOutput,
+    },
+    event::{Event, TargetEvents, Value, VrlTarget},
+    internal_events::{ShedEventDropped, ShedPriorityError},
+    schema,
+    transforms::{TaskTransform, Transform},
+};
+
+/// Configuration for the `shed` transform.
+#[configurable_component(transform(
+    "shed",
+    "Shed the lowest priority events under backpressure or above a rate threshold."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ShedConfig {
+    /// A [Vector Remap Language][vrl] (VRL) expression that computes the priority of each event.
+    ///
+    /// The expression must return an integer, and the events with the lowest priorities are shed
+    /// first. If the expression fails for an event, the event gets a priority of `0`.
+    ///
+    /// The priority tags the `shed_events_total` internal metric, so the expression should only
+    /// return a few distinct priorities.
+    ///
+    /// [vrl]: https://vector.dev/docs/reference/vrl
+    #[configurable(metadata(
+        docs::examples = "if .level == \"error\" { 2 } else if .level == \"warn\" { 1 } else { 0 }",
+        docs::syntax_override = "remap_program"
+    ))]
+    pub priority: String,
+
+    /// The maximum number of events waiting to be sent downstream.
+    ///
+    /// Events wait when downstream components apply backpressure, or when `max_events_per_sec`
+    /// is reached. Once this many events are waiting, the events with the lowest priority are
+    /// shed, starting with the most recent ones.
+    #[serde(default = "default_max_queued_events")]
+    pub max_queued_events: NonZeroUsize,
+
+    /// The maximum number of events sent downstream per second.
+    ///
+    /// When set, the events above this rate wait to be sent, as if a downstream component applied
+    /// backpressure.
+    #[configurable(metadata(docs::examples = 1000))]
+    pub max_events_per_sec: Option<NonZeroU32>,
+}
+
+fn default_max_queued_events() -> NonZeroUsize {
+    NonZeroUsize::new(1000).unwrap()
+}
+
+impl GenerateConfig for ShedConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            priority: "if .level == \"error\" { 1 } else { 0 }".to_string(),
+            max_queued_events: default_max_queued_events(),
+            max_events_per_sec: None,
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "shed")]
+impl TransformConfig for ShedConfig {
+    async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
+        Shed::new(self, context).map(Transform::event_task)
+    }
+
+    fn input(&self) -> Input {
+        Input::all()
+    }
+
+    fn outputs(
+        &self,
+        _: vector_lib::enrichment::TableRegistry,
+        input_definitions: &[(OutputId, schema::Definition)],
+        _: LogNamespace,
+    ) -> Vec<TransformOutput> {
+        // The events are not modified, so the definitions are passed through as-is
+        vec![TransformOutput::new(
+            DataType::all(),
+            clone_input_definitions(input_definitions),
+        )]
+    }
+}
+
+/// The events waiting to be sent downstream, by priority.
+#[derive(Debug, Default)]
+struct ShedQueue {
+    priorities: BTreeMap<i64, VecDeque<(u64, Event)>>,
+    len: usize,
+    next_seq: u64,
+}
+
+impl ShedQueue {
+    const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Queues an event, and sheds the most recent event with the lowest priority if more than
+    /// `max_len` events are waiting.
+    fn push(&mut self, priority: i64, event: Event, max_len: usize) {
+        self.priorities
+            .entry(priority)
+            .or_default()
+            .push_back((self.next_seq, event));
+        self.next_seq += 1;
+        self.len += 1;
+
+        if self.len > max_len {
+            if let Some(mut lowest) = self.priorities.first_entry() {
+                lowest.get_mut().pop_back();
+                emit!(ShedEventDropped {
+                    priority: *lowest.key()
+                });
+                if lowest.get().is_empty() {
+                    lowest.remove();
+                }
+                self.len -= 1;
+            }
+        }
+    }
+
+    /// Pops the oldest event, whatever its priority.
+    fn pop(&mut self) -> Option<Event> {
+        let priority = self
+            .priorities
+            .iter()
+            .filter_map(|(priority, events)| Some((*priority, events.front()?.0)))
+            .min_by_key(|(_, seq)| *seq)?
+            .0;
+
+        let events = self.priorities.get_mut(&priority)?;
+        let (_, event) = events.pop_front()?;
+        if events.is_empty() {
+            self.priorities.remove(&priority);
+        }
+        self.len -= 1;
+        Some(event)
+    }
+}
+
+pub struct Shed {
+    priority: Program,
+    timezone: TimeZone,
+    max_queued_events: usize,
+    quota: Option<Quota>,
+}
+
+impl Shed {
+    pub fn new(config: &ShedConfig, context: &TransformContext) -> crate::Result<Self> {
+        let functions = vrl::stdlib::all()
+            .into_iter()
+            .chain(vector_lib::enrichment::vrl_functions())
+            .chain(vector_vrl_functions::all())
+            .collect::<Vec<_>>();
+
+        let mut compile_config = CompileConfig::default();
+        compile_config.set_custom(context.enrichment_tables.clone());
+        compile_config.set_read_only();
+
+        let source = &config.priority;
+        let CompilationResult {
+            program,
+            warnings,
+            config: _,
+        } = compile_vrl(source, &functions, &TypeState::default(), compile_config)
+            .map_err(|diagnostics| Formatter::new(source, diagnostics).colored().to_string())?;
+
+        if !program.final_type_info().result.is_integer() {
+            return Err("the `priority` expression must return an integer".into());
+        }
+
+        if !warnings.is_empty() {
+            let warnings = Formatter::new(source, warnings).colored().to_string();
+            warn!(message = "VRL compilation warning.", %warnings);
+        }
+
+        Ok(Self {
+            priority: program,
+            timezone: context.globals.timezone(),
+            max_queued_events: config.max_queued_events.get(),
+            quota: config.max_events_per_sec.map(Quota::per_second),
+        })
+    }
+
+    fn prioritize(&self, event: Event) -> (i64, Event) {
+        let log_namespace = event
+            .maybe_as_log()
+            .map(|log| log.namespace())
+            .unwrap_or(LogNamespace::Legacy);
+        let mut target = VrlTarget::new(event, self.priority.info(), false);
+
+        let priority = match Runtime::default().resolve(&mut target, &self.priority, &self.timezone)
+        {
+            Ok(Value::Integer(priority)) => priority,
+            Ok(_) => 0,
+            Err(error) => {
+                emit!(ShedPriorityError {
+                    error: error.to_string().as_ref()
+                });
+                0
+            }
+        };
+
+        match target.into_events(log_namespace) {
+            TargetEvents::One(event) => (priority, event),
+            _ => panic!(
+                "Event was modified in a priority expression. This is an internal compiler error."
+            ),
+        }
+    }
+
+    fn queue(&self, queue: &mut ShedQueue, event: Event) {
+        let (priority, event) = self.prioritize(event);
+        queue.push(priority, event, self.max_queued_events);
+    }
+}
+
+impl TaskTransform<Event> for Shed {
+    fn transform(
+        self: Box<Self>,
+        mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
+    where
+        Self: 'static,
+    {
+        let limiter = self.quota.map(RateLimiter::direct);
+
+        Box::pin(stream! {
+            let mut queue = ShedQueue::default();
+            let mut done = false;
+            loop {
+                if queue.is_empty() {
+                    if done {
+                        break;
+                    }
+                    match input_rx.next().await {
+                        Some(event) => self.queue(&mut queue, event),
+                        None => break,
+                    }
+                }
+
+                if let Some(limiter) = &limiter {
+                    limiter.until_ready().await;
+                }
+
+                // The events that are ready were held back while the previous event was sent
+                // downstream, so they are queued in case a lower priority event must be shed.
+                while !done {
+                    match input_rx.next().now_or_never() {
+                        Some(Some(event)) => self.queue(&mut queue, event),
+                        Some(None) => done = true,
+                        None => break,
+                    }
+                }
+
+                match queue.pop() {
+                    Some(event) => yield event,
+                    None => break,
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::mpsc;
+    use tokio_stream::wrappers::ReceiverStream;
+
+    use super::*;
+    use crate::{
+        event::LogEvent, test_util::components::assert_transform_compliance,
+        transforms::test::create_topology,
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<ShedConfig>();
+    }
+
+    fn shed(max_queued_events: usize) -> Shed {
+        let config = toml::from_str::<ShedConfig>(&format!(
+            r#"
+            priority = 'int!(.priority)'
+            max_queued_events = {}
+            "#,
+            max_queued_events
+        ))
+        .unwrap();
+        Shed::new(&config, &TransformContext::default()).unwrap()
+    }
+
+    fn event(message: &str, priority: i64) -> Event {
+        let mut log = LogEvent::from(message);
+        log.insert("priority", priority);
+        log.into()
+    }
+
+    fn messages(events: Vec<Event>) -> Vec<String> {
+        events
+            .into_iter()
+            .map(|event| {
+                event
+                    .as_log()
+                    .get_message()
+                    .unwrap()
+                    .to_string_lossy()
+                    .into_owned()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn sheds_the_most_recent_lowest_priority_events() {
+        let mut queue = ShedQueue::default();
+        for (message, priority) in [("a", 1), ("b", 0), ("c", 2), ("d", 0), ("e", 1)] {
+            queue.push(priority, event(message, priority), 3);
+        }
+
+        let events = std::iter::from_fn(|| queue.pop()).collect();
+        assert_eq!(messages(events), ["a", "c", "e"]);
+        assert!(queue.is_empty());
+    }
+
+    #[tokio::test]
+    async fn sheds_events_under_backpressure() {
+        let events = vec![
+            event("low 1", 0),
+            event("high 1", 1),
+            event("low 2", 0),
+            event("high 2", 1),
+            event("low 3", 0),
+        ];
+
+        // All the events are ready at once, as if they were held back by downstream components.
+        let output = Box::new(shed(2))
+            .transform(Box::pin(futures::stream::iter(events)))
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(messages(output), ["high 1", "high 2"]);
+    }
+
+    #[test]
+    fn uses_the_default_priority_when_the_expression_fails() {
+        let shed = shed(1);
+        let (priority, _) = shed.prioritize(event("high", 3));
+        assert_eq!(priority, 3);
+
+        let (priority, _) = shed.prioritize(LogEvent::from("unprioritized").into());
+        assert_eq!(priority, 0);
+    }
+
+    #[test]
+    fn requires_an_integer_priority() {
+        let config = toml::from_str::<ShedConfig>(r#"priority = ".level == \"error\"""#).unwrap();
+        assert!(Shed::new(&config, &TransformContext::default()).is_err());
+    }
+
+    #[tokio::test]
+    async fn passes_events_through_without_backpressure() {
+        let config = toml::from_str::<ShedConfig>(r#"priority = "1""#).unwrap();
+
+        assert_transform_compliance(async move {
+            let (tx, rx) = mpsc::channel(1);
+            let (topology, mut out) = create_topology(ReceiverStream::new(rx), config).await;
+
+            tx.send(event("first", 0)).await.unwrap();
+            assert_eq!(messages(vec![out.recv().await.unwrap()]), ["first"]);
+
+            drop(tx);
+            topology.stop().await;
+            assert_eq!(out.recv().await, None);
+        })
+        .await
+    }
+}
@@ -0,0 +1,247 @@
+//! The FF3-1 format-preserving encryption mode, from [NIST SP 800-38G Rev. 1][sp].
+//!
+//! [sp]: https://csrc.nist.gov/pubs/sp/800/38/g/r1/ipd
+
+use aes::{
+    cipher::{BlockEncrypt, KeyInit},
+    Aes128, Aes192, Aes256, Block,
+};
+use snafu::Snafu;
+
+#[derive(Debug, Eq, PartialEq, Snafu)]
+pub enum Ff3Error {
+    #[snafu(display("The key must be 16, 24 or 32 bytes long, not {} bytes", length))]
+    InvalidKey { length: usize },
+    #[snafu(display("The tweak must be 7 bytes long, not {} bytes", length))]
+    InvalidTweak { length: usize },
+    #[snafu(display(
+        "Values must have between {} and {} characters of the alphabet, not {}",
+        min,
+        max,
+        length
+    ))]
+    InvalidLength {
+        length: usize,
+        min: usize,
+        max: usize,
+    },
+}
+
+#[derive(Clone)]
+enum Aes {
+    Aes128(Aes128),
+    Aes192(Aes192),
+    Aes256(Aes256),
+}
+
+impl Aes {
+    fn new(key: &[u8]) -> Result<Self, Ff3Error> {
+        Ok(match key.len() {
+            16 => Self::Aes128(Aes128::new_from_slice(key).expect("key of 16 bytes")),
+            24 => Self::Aes192(Aes192::new_from_slice(key).expect("key of 24 bytes")),
+            32 => Self::Aes256(Aes256::new_from_slice(key).expect("key of 32 bytes")),
+            length => return InvalidKeySnafu { length }.fail(),
+        })
+    }
+
+    fn encrypt_block(&self, block: &mut Block) {
+        match self {
+            Self::Aes128(aes) => aes.encrypt_block(block),
+            Self::Aes192(aes) => aes.encrypt_block(block),
+            Self::Aes256(aes) => aes.encrypt_block(block),
+        }
+    }
+}
+
+/// An FF3-1 cipher, encrypting strings of numerals in a radix into strings of numerals of the same
+/// length.
+#[derive(Clone)]
+pub struct Ff3_1 {
+    aes: Aes,
+    tweak_left: [u8; 4],
+    tweak_right: [u8; 4],
+    radix: u128,
+    min_length: usize,
+    max_length: usize,
+}
+
+impl Ff3_1 {
+    pub fn new(key: &[u8], tweak: &[u8], radix: u32) -> Result<Self, Ff3Error> {
+        debug_assert!((2..=65536).contains(&radix));
+
+        // The cipher uses the key with its bytes reversed.
+        let key = key.iter().rev().copied().collect::<Vec<_>>();
+        let aes = Aes::new(&key)?;
+
+        let tweak = <[u8; 7]>::try_from(tweak).map_err(|_| Ff3Error::InvalidTweak {
+            length: tweak.len(),
+        })?;
+        let tweak_left = [tweak[0], tweak[1], tweak[2], tweak[3] & 0xf0];
+        let tweak_right = [tweak[4], tweak[5], tweak[6], (tweak[3] & 0x0f) << 4];
+
+        // The domain of the values must have at least a million values, and each half of a value
+        // must fit in 96 bits.
+        let radix = u128::from(radix);
+        let min_length = (1..)
+            .find(|&n| radix.pow(n) >= 1_000_000)
+            .expect("minimum length");
+        let half_length = (1..)
+            .find(|&n| radix.pow(n) > 1 << 96)
+            .expect("maximum length")
+            - 1;
+
+        Ok(Self {
+            aes,
+            tweak_left,
+            tweak_right,
+            radix,
+            min_length: min_length as usize,
+            max_length: 2 * half_length as usize,
+        })
+    }
+
+    /// Encrypts a string of numerals.
+    pub fn encrypt(&self, numerals: &[u32]) -> Result<Vec<u32>, Ff3Error> {
+        let (u, v) = self.halves(numerals.len())?;
+        let (mut a, mut b) = (numerals[..u].to_vec(), numerals[u..].to_vec());
+
+        for i in 0..8 {
+            let m = if i % 2 == 0 { u } else { v };
+            let modulus = self.radix.pow(m as u32);
+            let y = self.round(i, &b) % modulus;
+            let c = (self.num(&a) + y) % modulus;
+            a = std::mem::replace(&mut b, self.str(c, m));
+        }
+
+        a.extend(b);
+        Ok(a)
+    }
+
+    /// Decrypts a string of numerals.
+    pub fn decrypt(&self, numerals: &[u32]) -> Result<Vec<u32>, Ff3Error> {
+        let (u, v) = self.halves(numerals.len())?;
+        let (mut a, mut b) = (numerals[..u].to_vec(), numerals[u..].to_vec());
+
+        for i in (0..8).rev() {
+            let m = if i % 2 == 0 { u } else { v };
+            let modulus = self.radix.pow(m as u32);
+            let y = self.round(i, &a) % modulus;
+            let c = (self.num(&b) + modulus - y) % modulus;
+            b = std::mem::replace(&mut a, self.str(c, m));
+        }
+
+        a.extend(b);
+        Ok(a)
+    }
+
+    fn halves(&self, length: usize) -> Result<(usize, usize), Ff3Error> {
+        if !(self.min_length..=self.max_length).contains(&length) {
+            return InvalidLengthSnafu {
+                length,
+                min: self.min_length,
+                max: self.max_length,
+            }
+            .fail();
+        }
+        let u = length.div_ceil(2);
+        Ok((u, length - u))
+    }
+
+    /// Returns the number encrypted from one half of the numerals in the round `i`.
+    fn round(&self, i: u8, half: &[u32]) -> u128 {
+        let tweak = if i % 2 == 0 {
+            self.tweak_right
+        } else {
+            self.tweak_left
+        };
+
+        let mut block = [0; 16];
+        block[..4].copy_from_slice(&tweak);
+        block[3] ^= i;
+        block[4..].copy_from_slice(&self.num(half).to_be_bytes()[4..]);
+
+        // The block is encrypted with its bytes reversed, and the result is reversed back.
+        block.reverse();
+        let mut block = Block::clone_from_slice(&block);
+        self.aes.encrypt_block(&mut block);
+        block.reverse();
+        u128::from_be_bytes(block.as_slice().try_into().expect("block of 16 bytes"))
+    }
+
+    /// Returns the number of numerals, the first one being the least significant.
+    fn num(&self, numerals: &[u32]) -> u128 {
+        numerals.iter().rev().fold(0, |number, &numeral| {
+            number * self.radix + u128::from(numeral)
+        })
+    }
+
+    /// Returns the `length` numerals of a number, the first one being the least significant.
+    fn str(&self, mut number: u128, length: usize) -> Vec<u32> {
+        (0..length)
+            .map(|_| {
+                let numeral = number % self.radix;
+                number /= self.radix;
+                numeral as u32
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn numerals(text: &str) -> Vec<u32> {
+        text.chars().map(|c| c.to_digit(10).unwrap()).collect()
+    }
+
+    #[test]
+    fn encrypts_and_decrypts() {
+        let key = hex::decode("2DE79D232DF5585D68CE47882AE256D6").unwrap();
+        let tweak = hex::decode("CBD09280979564").unwrap();
+        let cipher = Ff3_1::new(&key, &tweak, 10).unwrap();
+
+        let encrypted = cipher.encrypt(&numerals("3992520240")).unwrap();
+        assert_eq!(encrypted, numerals("8901801106"));
+        assert_eq!(cipher.decrypt(&encrypted).unwrap(), numerals("3992520240"));
+    }
+
+    #[test]
+    fn round_trips_in_any_radix() {
+        let key = [7; 32];
+        let tweak = [1, 2, 3, 4, 5, 6, 7];
+        let cipher = Ff3_1::new(&key, &tweak, 62).unwrap();
+
+        for length in [4, 5, 17, 32] {
+            let plaintext = (0..length).map(|n| n * 7 % 62).collect::<Vec<_>>();
+            let encrypted = cipher.encrypt(&plaintext).unwrap();
+            assert_eq!(encrypted.len(), plaintext.len());
+            assert!(encrypted.iter().all(|&numeral| numeral < 62));
+            assert_ne!(encrypted, plaintext);
+            assert_eq!(cipher.decrypt(&encrypted).unwrap(), plaintext);
+        }
+    }
+
+    #[test]
+    fn validates_the_parameters() {
+        assert!(matches!(
+            Ff3_1::new(&[0; 20], &[0; 7], 10),
+            Err(Ff3Error::InvalidKey { length: 20 })
+        ));
+        assert!(matches!(
+            Ff3_1::new(&[0; 16], &[0; 8], 10),
+            Err(Ff3Error::InvalidTweak { length: 8 })
+        ));
+
+        let cipher = Ff3_1::new(&[0; 16], &[0; 7], 10).unwrap();
+        assert_eq!(
+            cipher.encrypt(&numerals("12345")),
+            Err(Ff3Error::InvalidLength {
+                length: 5,
+                min: 6,
+                max: 56
+            })
+        );
+        assert!(cipher.encrypt(&[1; 57]).is_err());
+    }
+}
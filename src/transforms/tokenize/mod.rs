@@ -0,0 +1,390 @@
+use std::path::PathBuf;
+
+use vector_lib::config::{clone_input_definitions, LogNamespace};
+use vector_lib::configurable::configurable_component;
+use vector_lib::lookup::lookup_v2::ConfigTargetPath;
+use vector_lib::sensitive_string::SensitiveString;
+
+use crate::{
+    config::{
+        DataType, GenerateConfig, GlobalOptions, Input, OutputId, TransformConfig,
+        TransformContext, TransformOutput,
+    },
+    event::{Event, Value},
+    internal_events::TokenizeError,
+    schema,
+    transforms::{FunctionTransform, OutputBuffer, Transform},
+};
+
+mod ff3_1;
+mod vault;
+
+use self::{ff3_1::Ff3_1, vault::Vault};
+
+/// Whether values are replaced with their tokens, or tokens with their values.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Operation {
+    /// Replaces the values with their tokens.
+    #[default]
+    Tokenize,
+
+    /// Replaces the tokens with their original values.
+    Detokenize,
+}
+
+/// The characters encrypted by the `fpe` method.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Alphabet {
+    /// The digits, from `0` to `9`.
+    #[default]
+    Numeric,
+
+    /// The digits, and the lowercase and uppercase ASCII letters.
+    Alphanumeric,
+}
+
+impl Alphabet {
+    const fn characters(self) -> &'static str {
+        match self {
+            Self::Numeric => "0123456789",
+            Self::Alphanumeric => "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz",
+        }
+    }
+}
+
+/// How the tokens are created from the values.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+#[configurable(metadata(docs::enum_tag_description = "The method of the tokenization."))]
+pub enum Method {
+    /// Encrypts the values with the FF3-1 format-preserving encryption mode.
+    ///
+    /// The characters of the values in the alphabet are encrypted into other characters of the
+    /// alphabet, and the others are kept, so that tokens have the same length and format as their
+    /// values. The same value is always encrypted into the same token, and tokens can be decrypted
+    /// by any pipeline with the same key and tweak, without any state.
+    Fpe {
+        /// The AES key of the encryption, hex-encoded.
+        ///
+        /// The key must be 16, 24, or 32 bytes long.
+        #[configurable(metadata(docs::examples = "${TOKENIZE_KEY}"))]
+        key: SensitiveString,
+
+        /// The tweak of the encryption, as 7 hex-encoded bytes.
+        ///
+        /// The tweak doesn't need to be secret, but different tweaks result in different tokens
+        /// for the same values. By default, a tweak of zeros is used.
+        #[configurable(metadata(docs::examples = "d8e7920afa330a"))]
+        tweak: Option<String>,
+
+        #[configurable(derived)]
+        #[serde(default)]
+        alphabet: Alphabet,
+    },
+
+    /// Replaces the values with random tokens, stored in a vault on disk.
+    ///
+    /// Digits are replaced with random digits, and letters with random letters of the same case,
+    /// so that tokens keep the format of their values. The tokens are kept in the vault, so that
+    /// each value always gets the same token, and tokens can be replaced with their values by the
+    /// pipelines using the same vault. The vault contains the original values, and must be
+    /// protected like them.
+    Mapping {
+        /// The directory in which the vault is stored, in a `tokenize` subdirectory.
+        ///
+        /// By default, the [global `data_dir` option][global_data_dir] is used. The vault is shared
+        /// by the components using the same directory.
+        ///
+        /// [global_data_dir]: https://vector.dev/docs/reference/configuration/global-options/#data_dir
+        #[configurable(metadata(docs::examples = "/var/lib/vector/tokens/"))]
+        #[configurable(metadata(docs::human_name = "Data Directory"))]
+        data_dir: Option<PathBuf>,
+    },
+}
+
+/// Configuration for the `tokenize` transform.
+#[configurable_component(transform(
+    "tokenize",
+    "Replace sensitive values with format-preserving tokens."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct TokenizeConfig {
+    /// The fields whose values are replaced.
+    ///
+    /// Missing fields are ignored. If a field isn't a string, or its value can't be replaced, the
+    /// event is dropped, so that sensitive values are never forwarded.
+    #[configurable(metadata(docs::examples = "card_number", docs::examples = "user.email"))]
+    pub fields: Vec<ConfigTargetPath>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub operation: Operation,
+
+    #[configurable(derived)]
+    pub method: Method,
+}
+
+impl GenerateConfig for TokenizeConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"fields = ["card_number"]
+            method.type = "fpe"
+            method.key = "00112233445566778899aabbccddeeff""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "tokenize")]
+impl TransformConfig for TokenizeConfig {
+    async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
+        Tokenize::new(self, &context.globals).map(Transform::function)
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn outputs(
+        &self,
+        _: vector_lib::enrichment::TableRegistry,
+        input_definitions: &[(OutputId, schema::Definition)],
+        _: LogNamespace,
+    ) -> Vec<TransformOutput> {
+        // Only the contents of the strings are replaced.
+        vec![TransformOutput::new(
+            DataType::Log,
+            clone_input_definitions(input_definitions),
+        )]
+    }
+
+    fn enable_concurrency(&self) -> bool {
+        true
+    }
+}
+
+#[derive(Clone)]
+enum Tokenizer {
+    Fpe {
+        cipher: Ff3_1,
+        alphabet: &'static str,
+    },
+    Mapping(Vault),
+}
+
+impl Tokenizer {
+    fn apply(&self, operation: Operation, value: &str) -> crate::Result<String> {
+        match self {
+            Self::Fpe { cipher, alphabet } => {
+                let numerals = value
+                    .chars()
+                    .filter_map(|c| alphabet.find(c))
+                    .map(|numeral| numeral as u32)
+                    .collect::<Vec<_>>();
+                let numerals = match operation {
+                    Operation::Tokenize => cipher.encrypt(&numerals)?,
+                    Operation::Detokenize => cipher.decrypt(&numerals)?,
+                };
+
+                // The characters outside of the alphabet are kept in place.
+                let mut numerals = numerals.into_iter();
+                Ok(value
+                    .chars()
+                    .map(|c| match alphabet.find(c) {
+                        Some(_) => {
+                            let numeral = numerals.next().expect("numeral of a character");
+                            char::from(alphabet.as_bytes()[numeral as usize])
+                        }
+                        None => c,
+                    })
+                    .collect())
+            }
+            Self::Mapping(vault) => Ok(match operation {
+                Operation::Tokenize => vault.tokenize(value)?,
+                Operation::Detokenize => vault.detokenize(value)?,
+            }),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Tokenize {
+    fields: Vec<ConfigTargetPath>,
+    operation: Operation,
+    tokenizer: Tokenizer,
+}
+
+impl Tokenize {
+    pub fn new(config: &TokenizeConfig, globals: &GlobalOptions) -> crate::Result<Self> {
+        let tokenizer = match &config.method {
+            Method::Fpe {
+                key,
+                tweak,
+                alphabet,
+            } => {
+                let key = hex::decode(key.inner())
+                    .map_err(|error| format!("The `key` is not hex-encoded: {}", error))?;
+                let tweak = match tweak {
+                    Some(tweak) => hex::decode(tweak)
+                        .map_err(|error| format!("The `tweak` is not hex-encoded: {}", error))?,
+                    None => vec![0; 7],
+                };
+                let radix = alphabet.characters().len() as u32;
+                Tokenizer::Fpe {
+                    cipher: Ff3_1::new(&key, &tweak, radix)?,
+                    alphabet: alphabet.characters(),
+                }
+            }
+            Method::Mapping { data_dir } => {
+                let path = globals.resolve_and_make_data_subdir(data_dir.as_ref(), "tokenize")?;
+                let vault = Vault::open(&path).map_err(|error| {
+                    format!("Unable to open the vault in {:?}: {}", path, error)
+                })?;
+                Tokenizer::Mapping(vault)
+            }
+        };
+
+        Ok(Self {
+            fields: config.fields.clone(),
+            operation: config.operation,
+            tokenizer,
+        })
+    }
+}
+
+impl FunctionTransform for Tokenize {
+    fn transform(&mut self, output: &mut OutputBuffer, event: Event) {
+        let mut log = event.into_log();
+        for field in &self.fields {
+            let result = match log.get(&field.0) {
+                None => continue,
+                Some(Value::Bytes(bytes)) => self
+                    .tokenizer
+                    .apply(self.operation, &String::from_utf8_lossy(bytes)),
+                Some(value) => {
+                    Err(format!("The field is a {}, not a string", value.kind_str()).into())
+                }
+            };
+
+            match result {
+                Ok(replaced) => {
+                    log.insert(&field.0, replaced);
+                }
+                Err(error) => {
+                    emit!(TokenizeError {
+                        field: &field.0.to_string(),
+                        error,
+                    });
+                    return;
+                }
+            }
+        }
+        output.push(log.into());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vrl::value;
+
+    use super::*;
+    use crate::event::LogEvent;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<TokenizeConfig>();
+    }
+
+    fn build(config: &str, data_dir: Option<PathBuf>) -> Tokenize {
+        let config = toml::from_str::<TokenizeConfig>(config).unwrap();
+        let globals = GlobalOptions {
+            data_dir,
+            ..Default::default()
+        };
+        Tokenize::new(&config, &globals).unwrap()
+    }
+
+    fn apply(tokenize: &mut Tokenize, log: LogEvent) -> Option<LogEvent> {
+        let mut output = OutputBuffer::default();
+        tokenize.transform(&mut output, log.into());
+        output.into_events().next().map(Event::into_log)
+    }
+
+    const FPE: &str = r#"
+        fields = ["card", "user.id"]
+        method.type = "fpe"
+        method.key = "2DE79D232DF5585D68CE47882AE256D6"
+        method.tweak = "CBD09280979564"
+    "#;
+
+    #[test]
+    fn encrypts_and_decrypts_values() {
+        let mut tokenize = build(FPE, None);
+        let log = LogEvent::from(value!({
+            "card": "3992-5202-40",
+            "user": {"id": "3992520240", "name": "jane"},
+        }));
+
+        let tokenized = apply(&mut tokenize, log.clone()).unwrap();
+        assert_eq!(
+            tokenized.value(),
+            &value!({
+                "card": "8901-8011-06",
+                "user": {"id": "8901801106", "name": "jane"},
+            })
+        );
+
+        let mut detokenize = build(&format!("{}\noperation = \"detokenize\"", FPE), None);
+        assert_eq!(apply(&mut detokenize, tokenized), Some(log));
+    }
+
+    #[test]
+    fn drops_events_with_invalid_values() {
+        let mut tokenize = build(FPE, None);
+
+        // Missing fields are ignored.
+        let log = LogEvent::from(value!({"user": {"id": "3992520240"}}));
+        assert!(apply(&mut tokenize, log).is_some());
+
+        let log = LogEvent::from(value!({"card": 3992520240_i64}));
+        assert_eq!(apply(&mut tokenize, log), None);
+
+        // The value is too short to be encrypted.
+        let log = LogEvent::from(value!({"card": "12-34"}));
+        assert_eq!(apply(&mut tokenize, log), None);
+    }
+
+    #[test]
+    fn maps_values_to_tokens() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let config = r#"
+            fields = ["email"]
+            method.type = "mapping"
+        "#;
+        let mut tokenize = build(config, Some(data_dir.path().to_owned()));
+
+        let log = LogEvent::from(value!({"email": "jane@example.com"}));
+        let tokenized = apply(&mut tokenize, log.clone()).unwrap();
+        let token = tokenized["email"].to_string_lossy().into_owned();
+        assert_ne!(token, "jane@example.com");
+        assert_eq!(token.len(), "jane@example.com".len());
+        assert_eq!(&token[4..5], "@");
+
+        let mut detokenize = build(
+            &format!("{}\noperation = \"detokenize\"", config),
+            Some(data_dir.path().to_owned()),
+        );
+        assert_eq!(apply(&mut detokenize, tokenized), Some(log));
+
+        // Unknown tokens can't be detokenized.
+        let log = LogEvent::from(value!({"email": "unknown@example.com"}));
+        assert_eq!(apply(&mut detokenize, log), None);
+    }
+}
@@ -0,0 +1,187 @@
+//! The tokens of the `mapping` method, persisted in a [sled] database.
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use once_cell::sync::Lazy;
+use rand::Rng;
+use snafu::{ResultExt, Snafu};
+
+/// The prefix of the keys mapping a value to its token.
+const TOKEN_PREFIX: u8 = b't';
+
+/// The prefix of the keys mapping a token to its value.
+const VALUE_PREFIX: u8 = b'v';
+
+/// The maximum size of the page cache of a database in memory, in bytes.
+const CACHE_CAPACITY: u64 = 64 * 1024 * 1024;
+
+/// The number of random tokens tried for a value before giving up, when they are all taken.
+const MAX_ATTEMPTS: usize = 16;
+
+/// The databases opened by the components, by path.
+///
+/// A database can only be opened once, so the components using the same directory share it, and it
+/// stays open until Vector stops.
+static DATABASES: Lazy<Mutex<HashMap<PathBuf, sled::Db>>> = Lazy::new(Default::default);
+
+#[derive(Debug, Snafu)]
+pub enum VaultError {
+    #[snafu(display("Vault database error: {}", source))]
+    Database { source: sled::Error },
+    #[snafu(display("No value is known for the token"))]
+    UnknownToken,
+    #[snafu(display("No unused token could be generated for the value"))]
+    NoUniqueToken,
+}
+
+/// A vault of the tokens of values, and of the values of tokens.
+///
+/// The tokens are random, but keep the format of their values: each digit is replaced with a digit,
+/// and each letter with a letter of the same case. Each value always gets the same token, and the
+/// values can be recovered from their tokens.
+#[derive(Clone)]
+pub struct Vault {
+    tree: sled::Tree,
+}
+
+impl Vault {
+    /// Opens the vault in the given directory, with the tokens of the previous runs.
+    pub fn open(path: &Path) -> sled::Result<Self> {
+        let mut databases = DATABASES.lock().expect("poisoned lock");
+        let db = match databases.get(path) {
+            Some(db) => db.clone(),
+            None => {
+                let db = sled::Config::new()
+                    .path(path)
+                    .cache_capacity(CACHE_CAPACITY)
+                    .open()?;
+                databases.insert(path.to_owned(), db.clone());
+                db
+            }
+        };
+
+        Ok(Self {
+            tree: db.open_tree("tokens")?,
+        })
+    }
+
+    /// Returns the token of a value, creating it if the value doesn't have one yet.
+    pub fn tokenize(&self, value: &str) -> Result<String, VaultError> {
+        let token_key = key(TOKEN_PREFIX, value);
+        if let Some(token) = self.tree.get(&token_key).context(DatabaseSnafu)? {
+            return Ok(String::from_utf8_lossy(&token).into_owned());
+        }
+
+        for _ in 0..MAX_ATTEMPTS {
+            let token = random_token(value);
+            if token == value {
+                continue;
+            }
+
+            // The token is reserved first, so that it's never given to two values by components
+            // sharing the vault.
+            let value_key = key(VALUE_PREFIX, &token);
+            if self
+                .tree
+                .compare_and_swap(&value_key, None::<&[u8]>, Some(value.as_bytes()))
+                .context(DatabaseSnafu)?
+                .is_err()
+            {
+                continue;
+            }
+
+            return match self
+                .tree
+                .compare_and_swap(&token_key, None::<&[u8]>, Some(token.as_bytes()))
+                .context(DatabaseSnafu)?
+            {
+                Ok(()) => Ok(token),
+                // Another component gave a token to the value in the meantime.
+                Err(error) => {
+                    self.tree.remove(&value_key).context(DatabaseSnafu)?;
+                    let token = error.current.expect("token of the value");
+                    Ok(String::from_utf8_lossy(&token).into_owned())
+                }
+            };
+        }
+
+        Err(VaultError::NoUniqueToken)
+    }
+
+    /// Returns the value of a token.
+    pub fn detokenize(&self, token: &str) -> Result<String, VaultError> {
+        match self
+            .tree
+            .get(key(VALUE_PREFIX, token))
+            .context(DatabaseSnafu)?
+        {
+            Some(value) => Ok(String::from_utf8_lossy(&value).into_owned()),
+            None => Err(VaultError::UnknownToken),
+        }
+    }
+}
+
+fn key(prefix: u8, text: &str) -> Vec<u8> {
+    let mut key = Vec::with_capacity(1 + text.len());
+    key.push(prefix);
+    key.extend_from_slice(text.as_bytes());
+    key
+}
+
+fn random_token(value: &str) -> String {
+    let mut rng = rand::thread_rng();
+    value
+        .chars()
+        .map(|c| match c {
+            '0'..='9' => rng.gen_range('0'..='9'),
+            'a'..='z' => rng.gen_range('a'..='z'),
+            'A'..='Z' => rng.gen_range('A'..='Z'),
+            c => c,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_the_tokens_of_the_values() {
+        let dir = tempfile::tempdir().unwrap();
+        let vault = Vault::open(dir.path()).unwrap();
+
+        let token = vault.tokenize("4111-1111-1111-1111").unwrap();
+        assert_ne!(token, "4111-1111-1111-1111");
+        assert_eq!(token.len(), 19);
+        assert!(token
+            .chars()
+            .zip("4111-1111-1111-1111".chars())
+            .all(|(token, value)| token.is_ascii_digit() == value.is_ascii_digit()));
+
+        assert_eq!(vault.tokenize("4111-1111-1111-1111").unwrap(), token);
+        assert_eq!(vault.detokenize(&token).unwrap(), "4111-1111-1111-1111");
+
+        // The vault is shared with the components opening the same directory.
+        let other = Vault::open(dir.path()).unwrap();
+        assert_eq!(other.tokenize("4111-1111-1111-1111").unwrap(), token);
+    }
+
+    #[test]
+    fn rejects_unknown_tokens() {
+        let dir = tempfile::tempdir().unwrap();
+        let vault = Vault::open(dir.path()).unwrap();
+
+        assert!(matches!(
+            vault.detokenize("not a token"),
+            Err(VaultError::UnknownToken)
+        ));
+        assert!(matches!(
+            vault.tokenize("---"),
+            Err(VaultError::NoUniqueToken)
+        ));
+    }
+}
@@ -0,0 +1,555 @@
+use std::{
+    collections::{hash_map, HashMap, VecDeque},
+    num::NonZeroUsize,
+    pin::Pin,
+    time::{Duration, Instant},
+};
+
+use async_stream::stream;
+use futures::{Stream, StreamExt};
+use serde_with::serde_as;
+use vector_lib::config::{clone_input_definitions, LogNamespace};
+use vector_lib::configurable::configurable_component;
+use vector_lib::lookup::event_path;
+
+use crate::{
+    config::{
+        DataType, GenerateConfig, Input, OutputId, TransformConfig, TransformContext,
+        TransformOutput,
+    },
+    event::{Event, TraceEvent, Value},
+    internal_events::{TraceSamplerSpansDropped, TraceSamplerTraceDecided},
+    schema,
+    transforms::{TaskTransform, Transform},
+};
+
+/// The status code of the spans that failed, as set by the `opentelemetry` source.
+const STATUS_CODE_ERROR: i64 = 2;
+
+/// The longest interval between checks for the traces to decide.
+const MAX_FLUSH_PERIOD: Duration = Duration::from_secs(1);
+
+/// Configuration for the `trace_sampler` transform.
+#[serde_as]
+#[configurable_component(transform(
+    "trace_sampler",
+    "Sample whole traces, once all of their spans are received."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct TraceSamplerConfig {
+    /// How long to wait for the spans of a trace after its first span, in seconds.
+    ///
+    /// The spans of each trace are buffered until then, and the policies are applied to all of
+    /// them at once. The spans received after the decision, within another `decision_wait_secs`,
+    /// follow the same decision.
+    #[serde(default = "default_decision_wait")]
+    #[serde_as(as = "serde_with::DurationSecondsWithFrac<f64>")]
+    #[configurable(metadata(docs::human_name = "Decision Wait"))]
+    pub decision_wait_secs: Duration,
+
+    /// The maximum number of traces buffered while waiting for their spans.
+    ///
+    /// Once this many traces are buffered, the policies are applied to the oldest trace early,
+    /// to make room for the next one.
+    #[serde(default = "default_max_traces")]
+    pub max_traces: NonZeroUsize,
+
+    /// The policies that decide whether to sample each trace.
+    ///
+    /// A trace is sampled when any of the policies samples it, and all of its spans are dropped
+    /// otherwise.
+    pub policies: Vec<SamplingPolicy>,
+}
+
+const fn default_decision_wait() -> Duration {
+    Duration::from_secs(30)
+}
+
+fn default_max_traces() -> NonZeroUsize {
+    NonZeroUsize::new(50_000).unwrap()
+}
+
+/// A policy that decides whether to sample a trace.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+#[configurable(metadata(docs::enum_tag_description = "The type of the policy."))]
+pub enum SamplingPolicy {
+    /// Sample the traces with at least one failed span.
+    ///
+    /// A span failed when its `status.code` field is `2`, which is the OpenTelemetry
+    /// `STATUS_CODE_ERROR` status.
+    Error,
+
+    /// Sample the traces that last at least a threshold, from the start of their first span to the
+    /// end of their last span.
+    ///
+    /// The bounds of the spans are read from their `start_time_unix_nano` and `end_time_unix_nano`
+    /// fields.
+    Latency {
+        /// The minimum duration of the sampled traces, in milliseconds.
+        #[configurable(metadata(docs::examples = 500))]
+        threshold_ms: u64,
+    },
+
+    /// Sample a percentage of the traces.
+    ///
+    /// The decision is based on a hash of the trace ID, so that each Vector instance makes the same
+    /// decision for the spans of a trace.
+    Probabilistic {
+        /// The percentage of the traces to sample, between `0` and `100`.
+        #[configurable(metadata(docs::examples = 10.0))]
+        percentage: f64,
+    },
+}
+
+impl SamplingPolicy {
+    fn validate(&self) -> crate::Result<()> {
+        match self {
+            Self::Probabilistic { percentage } if !(0.0..=100.0).contains(percentage) => {
+                Err("the `percentage` of a probabilistic policy must be between 0 and 100".into())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn samples(&self, trace_id: &str, spans: &[Event]) -> bool {
+        match self {
+            Self::Error => spans.iter().any(|span| {
+                span.as_trace().get(event_path!("status", "code"))
+                    == Some(&Value::Integer(STATUS_CODE_ERROR))
+            }),
+            Self::Latency { threshold_ms } => {
+                let start = spans
+                    .iter()
+                    .filter_map(|span| nanos(span.as_trace(), "start_time_unix_nano"))
+                    .min();
+                let end = spans
+                    .iter()
+                    .filter_map(|span| nanos(span.as_trace(), "end_time_unix_nano"))
+                    .max();
+                match (start, end) {
+                    (Some(start), Some(end)) => {
+                        end.saturating_sub(start)
+                            >= (*threshold_ms as i64).saturating_mul(1_000_000)
+                    }
+                    _ => false,
+                }
+            }
+            Self::Probabilistic { percentage } => {
+                // The hash is bucketed in hundredths of a percent, so that 0 and 100 are exact.
+                seahash::hash(trace_id.as_bytes()) % 10_000 < (percentage * 100.0) as u64
+            }
+        }
+    }
+}
+
+/// The time of a span field, in nanoseconds since the Unix epoch.
+fn nanos(span: &TraceEvent, field: &str) -> Option<i64> {
+    match span.get(field)? {
+        Value::Timestamp(timestamp) => timestamp.timestamp_nanos_opt(),
+        Value::Integer(nanos) => Some(*nanos),
+        _ => None,
+    }
+}
+
+impl GenerateConfig for TraceSamplerConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            decision_wait_secs: default_decision_wait(),
+            max_traces: default_max_traces(),
+            policies: vec![
+                SamplingPolicy::Error,
+                SamplingPolicy::Probabilistic { percentage: 10.0 },
+            ],
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "trace_sampler")]
+impl TransformConfig for TraceSamplerConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        TraceSampler::new(self).map(Transform::event_task)
+    }
+
+    fn input(&self) -> Input {
+        Input::trace()
+    }
+
+    fn outputs(
+        &self,
+        _: vector_lib::enrichment::TableRegistry,
+        input_definitions: &[(OutputId, schema::Definition)],
+        _: LogNamespace,
+    ) -> Vec<TransformOutput> {
+        // The spans are not modified, so the definitions are passed through as-is
+        vec![TransformOutput::new(
+            DataType::Trace,
+            clone_input_definitions(input_definitions),
+        )]
+    }
+}
+
+pub struct TraceSampler {
+    policies: Vec<SamplingPolicy>,
+    decision_wait: Duration,
+    max_traces: usize,
+    /// The spans of the traces waiting for a decision, by trace ID.
+    pending: HashMap<String, Vec<Event>>,
+    /// The IDs of the pending traces, with the time of their decision, in the order of their first
+    /// span.
+    deadlines: VecDeque<(Instant, String)>,
+    /// The recent decisions, followed by the spans received after them.
+    decisions: HashMap<String, bool>,
+    /// The IDs of the traces in `decisions`, with the time at which their decision is forgotten.
+    expirations: VecDeque<(Instant, String)>,
+}
+
+impl TraceSampler {
+    pub fn new(config: &TraceSamplerConfig) -> crate::Result<Self> {
+        if config.decision_wait_secs.is_zero() {
+            return Err("`decision_wait_secs` must be greater than zero".into());
+        }
+        if config.policies.is_empty() {
+            return Err("at least one sampling policy must be set".into());
+        }
+        for policy in &config.policies {
+            policy.validate()?;
+        }
+
+        Ok(Self {
+            policies: config.policies.clone(),
+            decision_wait: config.decision_wait_secs,
+            max_traces: config.max_traces.get(),
+            pending: HashMap::new(),
+            deadlines: VecDeque::new(),
+            decisions: HashMap::new(),
+            expirations: VecDeque::new(),
+        })
+    }
+
+    fn record(&mut self, output: &mut Vec<Event>, event: Event, now: Instant) {
+        let Some(trace_id) = event
+            .as_trace()
+            .get(event_path!("trace_id"))
+            .map(|trace_id| trace_id.to_string_lossy().into_owned())
+        else {
+            // Spans without a trace ID can't be sampled with the rest of their trace.
+            output.push(event);
+            return;
+        };
+
+        if let Some(&sampled) = self.decisions.get(&trace_id) {
+            if sampled {
+                output.push(event);
+            } else {
+                emit!(TraceSamplerSpansDropped { count: 1 });
+            }
+            return;
+        }
+
+        match self.pending.entry(trace_id) {
+            hash_map::Entry::Occupied(mut entry) => entry.get_mut().push(event),
+            hash_map::Entry::Vacant(entry) => {
+                let trace_id = entry.key().clone();
+                entry.insert(vec![event]);
+                self.deadlines
+                    .push_back((now + self.decision_wait, trace_id));
+
+                if self.pending.len() > self.max_traces {
+                    if let Some((_, oldest)) = self.deadlines.pop_front() {
+                        self.decide(output, oldest, now);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Decides the traces whose spans have been waited for, and forgets the expired decisions.
+    fn flush_into(&mut self, output: &mut Vec<Event>, now: Instant) {
+        while self
+            .deadlines
+            .front()
+            .is_some_and(|(deadline, _)| *deadline <= now)
+        {
+            if let Some((_, trace_id)) = self.deadlines.pop_front() {
+                self.decide(output, trace_id, now);
+            }
+        }
+
+        while self
+            .expirations
+            .front()
+            .is_some_and(|(expiration, _)| *expiration <= now)
+        {
+            if let Some((_, trace_id)) = self.expirations.pop_front() {
+                self.decisions.remove(&trace_id);
+            }
+        }
+    }
+
+    fn flush_all_into(&mut self, output: &mut Vec<Event>, now: Instant) {
+        while let Some((_, trace_id)) = self.deadlines.pop_front() {
+            self.decide(output, trace_id, now);
+        }
+    }
+
+    fn decide(&mut self, output: &mut Vec<Event>, trace_id: String, now: Instant) {
+        let Some(spans) = self.pending.remove(&trace_id) else {
+            return;
+        };
+
+        let sampled = self
+            .policies
+            .iter()
+            .any(|policy| policy.samples(&trace_id, &spans));
+        emit!(TraceSamplerTraceDecided { sampled });
+        if sampled {
+            output.extend(spans);
+        } else {
+            emit!(TraceSamplerSpansDropped { count: spans.len() });
+        }
+
+        self.decisions.insert(trace_id.clone(), sampled);
+        self.expirations
+            .push_back((now + self.decision_wait, trace_id));
+    }
+}
+
+impl TaskTransform<Event> for TraceSampler {
+    fn transform(
+        mut self: Box<Self>,
+        mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
+    where
+        Self: 'static,
+    {
+        let mut flush_stream = tokio::time::interval(self.decision_wait.min(MAX_FLUSH_PERIOD));
+
+        Box::pin(stream! {
+            let mut output = Vec::new();
+            let mut done = false;
+            while !done {
+                tokio::select! {
+                    _ = flush_stream.tick() => {
+                        self.flush_into(&mut output, Instant::now());
+                    },
+                    maybe_event = input_rx.next() => {
+                        match maybe_event {
+                            None => {
+                                self.flush_all_into(&mut output, Instant::now());
+                                done = true;
+                            }
+                            Some(event) => self.record(&mut output, event, Instant::now()),
+                        }
+                    }
+                };
+                for event in output.drain(..) {
+                    yield event;
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use tokio::sync::mpsc;
+    use tokio_stream::wrappers::ReceiverStream;
+
+    use super::*;
+    use crate::{
+        event::LogEvent, test_util::components::assert_transform_compliance,
+        transforms::test::create_topology,
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<TraceSamplerConfig>();
+    }
+
+    fn sampler(policies: &str) -> TraceSampler {
+        let config = toml::from_str::<TraceSamplerConfig>(&format!(
+            r#"
+            decision_wait_secs = 10
+            max_traces = 2
+            {}
+            "#,
+            policies
+        ))
+        .unwrap();
+        TraceSampler::new(&config).unwrap()
+    }
+
+    fn span(trace_id: &str, span_id: &str, start_ms: i64, end_ms: i64, status_code: i64) -> Event {
+        let mut span = TraceEvent::from(LogEvent::default());
+        span.insert(event_path!("trace_id"), trace_id);
+        span.insert(event_path!("span_id"), span_id);
+        span.insert(
+            event_path!("start_time_unix_nano"),
+            Utc.timestamp_millis_opt(start_ms).unwrap(),
+        );
+        span.insert(
+            event_path!("end_time_unix_nano"),
+            Utc.timestamp_millis_opt(end_ms).unwrap(),
+        );
+        span.insert(event_path!("status", "code"), status_code);
+        span.into()
+    }
+
+    fn span_ids(spans: &[Event]) -> Vec<String> {
+        spans
+            .iter()
+            .map(|span| {
+                span.as_trace()
+                    .get(event_path!("span_id"))
+                    .unwrap()
+                    .to_string_lossy()
+                    .into_owned()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn samples_traces_with_errors() {
+        let mut sampler = sampler(r#"policies = [{ type = "error" }]"#);
+        let now = Instant::now();
+        let mut output = Vec::new();
+
+        sampler.record(&mut output, span("ok", "a", 0, 10, 1), now);
+        sampler.record(&mut output, span("failed", "b", 0, 10, 0), now);
+        sampler.record(&mut output, span("failed", "c", 0, 10, 2), now);
+        sampler.record(&mut output, span("ok", "d", 0, 10, 0), now);
+
+        sampler.flush_into(&mut output, now + Duration::from_secs(9));
+        assert!(output.is_empty());
+
+        sampler.flush_into(&mut output, now + Duration::from_secs(10));
+        assert_eq!(span_ids(&output), ["b", "c"]);
+    }
+
+    #[test]
+    fn samples_slow_traces() {
+        let mut sampler = sampler(
+            r#"
+            [[policies]]
+            type = "latency"
+            threshold_ms = 500
+            "#,
+        );
+        let now = Instant::now();
+        let mut output = Vec::new();
+
+        sampler.record(&mut output, span("fast", "a", 0, 200, 0), now);
+        sampler.record(&mut output, span("fast", "b", 100, 499, 0), now);
+        sampler.record(&mut output, span("slow", "c", 1000, 1200, 0), now);
+        sampler.record(&mut output, span("slow", "d", 1300, 1500, 0), now);
+        sampler.flush_all_into(&mut output, now);
+
+        assert_eq!(span_ids(&output), ["c", "d"]);
+    }
+
+    #[test]
+    fn samples_a_consistent_percentage_of_traces() {
+        let sample = |percentage: f64| {
+            let policy = SamplingPolicy::Probabilistic { percentage };
+            (0..10_000)
+                .filter(|id| policy.samples(&format!("{:032x}", id), &[]))
+                .count()
+        };
+
+        assert_eq!(sample(0.0), 0);
+        assert_eq!(sample(100.0), 10_000);
+        assert!((900..1100).contains(&sample(10.0)));
+
+        let policy = SamplingPolicy::Probabilistic { percentage: 50.0 };
+        assert_eq!(policy.samples("trace", &[]), policy.samples("trace", &[]));
+    }
+
+    #[test]
+    fn late_spans_follow_the_decision() {
+        let mut sampler = sampler(r#"policies = [{ type = "error" }]"#);
+        let now = Instant::now();
+        let mut output = Vec::new();
+
+        sampler.record(&mut output, span("failed", "a", 0, 10, 2), now);
+        sampler.record(&mut output, span("ok", "b", 0, 10, 0), now);
+        sampler.flush_into(&mut output, now + Duration::from_secs(10));
+        assert_eq!(span_ids(&output), ["a"]);
+        output.clear();
+
+        let late = now + Duration::from_secs(15);
+        sampler.record(&mut output, span("failed", "c", 0, 10, 0), late);
+        sampler.record(&mut output, span("ok", "d", 0, 10, 0), late);
+        assert_eq!(span_ids(&output), ["c"]);
+        output.clear();
+
+        // Once the decision is forgotten, the spans start a new trace.
+        sampler.flush_into(&mut output, now + Duration::from_secs(20));
+        sampler.record(&mut output, span("ok", "e", 0, 10, 2), late);
+        assert!(output.is_empty());
+        sampler.flush_all_into(&mut output, late);
+        assert_eq!(span_ids(&output), ["e"]);
+    }
+
+    #[test]
+    fn decides_the_oldest_trace_when_full() {
+        let mut sampler = sampler(r#"policies = [{ type = "error" }]"#);
+        let now = Instant::now();
+        let mut output = Vec::new();
+
+        sampler.record(&mut output, span("first", "a", 0, 10, 2), now);
+        sampler.record(&mut output, span("second", "b", 0, 10, 2), now);
+        assert!(output.is_empty());
+
+        sampler.record(&mut output, span("third", "c", 0, 10, 2), now);
+        assert_eq!(span_ids(&output), ["a"]);
+        assert_eq!(sampler.pending.len(), 2);
+    }
+
+    #[test]
+    fn rejects_invalid_configs() {
+        for config in [
+            "policies = []",
+            r#"policies = [{ type = "probabilistic", percentage = 101.0 }]"#,
+            r#"
+            decision_wait_secs = 0
+            policies = [{ type = "error" }]
+            "#,
+        ] {
+            let config = toml::from_str::<TraceSamplerConfig>(config).unwrap();
+            assert!(TraceSampler::new(&config).is_err());
+        }
+    }
+
+    #[tokio::test]
+    async fn samples_pending_traces_at_shutdown() {
+        let config = toml::from_str::<TraceSamplerConfig>(
+            r#"
+            [[policies]]
+            type = "error"
+            "#,
+        )
+        .unwrap();
+
+        assert_transform_compliance(async move {
+            let (tx, rx) = mpsc::channel(1);
+            let (topology, mut out) = create_topology(ReceiverStream::new(rx), config).await;
+
+            tx.send(span("failed", "a", 0, 10, 2)).await.unwrap();
+            tx.send(span("ok", "b", 0, 10, 1)).await.unwrap();
+            drop(tx);
+
+            let sampled = out.recv().await.unwrap();
+            assert_eq!(span_ids(&[sampled]), ["a"]);
+
+            topology.stop().await;
+            assert_eq!(out.recv().await, None);
+        })
+        .await
+    }
+}
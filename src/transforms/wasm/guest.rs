@@ -0,0 +1,198 @@
+use snafu::{OptionExt, Snafu};
+use wasmtime::{
+    Engine, Instance, Memory, Module, Store, StoreLimits, StoreLimitsBuilder, TypedFunc,
+};
+
+/// The exports of a module implementing the guest ABI.
+const MEMORY_EXPORT: &str = "memory";
+const ALLOC_EXPORT: &str = "alloc";
+const PROCESS_EXPORT: &str = "process";
+
+#[derive(Debug, Snafu)]
+pub enum GuestError {
+    #[snafu(display("Unable to instantiate the module: {:#}", error))]
+    Instantiate { error: wasmtime::Error },
+    #[snafu(display("The module does not export the {:?} {}", name, kind))]
+    MissingExport {
+        name: &'static str,
+        kind: &'static str,
+    },
+    #[snafu(display("The {:?} function of the module failed: {:#}", name, error))]
+    Call {
+        name: &'static str,
+        error: wasmtime::Error,
+    },
+    #[snafu(display("The module returned a buffer out of the bounds of its memory"))]
+    OutOfBounds,
+}
+
+impl GuestError {
+    pub const fn error_code(&self) -> &'static str {
+        match self {
+            Self::Instantiate { .. } => "instantiate",
+            Self::MissingExport { .. } => "missing_export",
+            Self::Call { .. } => "call",
+            Self::OutOfBounds => "out_of_bounds",
+        }
+    }
+}
+
+/// The limits on the resources used by an instance of a module.
+#[derive(Clone, Copy, Debug)]
+pub struct Limits {
+    pub fuel: Option<u64>,
+    pub max_memory_bytes: usize,
+}
+
+struct State {
+    limits: StoreLimits,
+}
+
+/// An instance of a module implementing the guest ABI.
+///
+/// The module exports its `memory`, an `alloc(len: i32) -> i32` function returning a buffer of
+/// `len` bytes in its memory, and a `process(ptr: i32, len: i32) -> i64` function transforming
+/// the event encoded in the buffer, and returning the buffer of its output, packed as
+/// `ptr << 32 | len`.
+pub struct Guest {
+    store: Store<State>,
+    memory: Memory,
+    alloc: TypedFunc<i32, i32>,
+    process: TypedFunc<(i32, i32), i64>,
+    fuel: Option<u64>,
+}
+
+impl Guest {
+    pub fn new(engine: &Engine, module: &Module, limits: Limits) -> Result<Self, GuestError> {
+        let state = State {
+            limits: StoreLimitsBuilder::new()
+                .memory_size(limits.max_memory_bytes)
+                .build(),
+        };
+        let mut store = Store::new(engine, state);
+        store.limiter(|state| &mut state.limits);
+        if let Some(fuel) = limits.fuel {
+            // The initialization of the module consumes fuel as well.
+            set_fuel(&mut store, fuel).map_err(|error| GuestError::Instantiate { error })?;
+        }
+
+        // No functions are provided to the module, so that it cannot have any side effects.
+        let instance = Instance::new(&mut store, module, &[])
+            .map_err(|error| GuestError::Instantiate { error })?;
+        let memory =
+            instance
+                .get_memory(&mut store, MEMORY_EXPORT)
+                .context(MissingExportSnafu {
+                    name: MEMORY_EXPORT,
+                    kind: "memory",
+                })?;
+        let alloc = instance
+            .get_typed_func(&mut store, ALLOC_EXPORT)
+            .ok()
+            .context(MissingExportSnafu {
+                name: ALLOC_EXPORT,
+                kind: "function",
+            })?;
+        let process = instance
+            .get_typed_func(&mut store, PROCESS_EXPORT)
+            .ok()
+            .context(MissingExportSnafu {
+                name: PROCESS_EXPORT,
+                kind: "function",
+            })?;
+
+        Ok(Self {
+            store,
+            memory,
+            alloc,
+            process,
+            fuel: limits.fuel,
+        })
+    }
+
+    /// Runs the `process` function of the module on an encoded event, returning its output.
+    ///
+    /// After an error, the instance may be left in an inconsistent state, and should be dropped.
+    pub fn process(&mut self, input: &[u8]) -> Result<Vec<u8>, GuestError> {
+        if let Some(fuel) = self.fuel {
+            set_fuel(&mut self.store, fuel).map_err(|error| GuestError::Call {
+                name: PROCESS_EXPORT,
+                error,
+            })?;
+        }
+
+        let len = i32::try_from(input.len()).ok().context(OutOfBoundsSnafu)?;
+        let ptr = self
+            .alloc
+            .call(&mut self.store, len)
+            .map_err(|error| GuestError::Call {
+                name: ALLOC_EXPORT,
+                error,
+            })?;
+        self.memory
+            .write(&mut self.store, ptr as u32 as usize, input)
+            .ok()
+            .context(OutOfBoundsSnafu)?;
+
+        let packed = self
+            .process
+            .call(&mut self.store, (ptr, len))
+            .map_err(|error| GuestError::Call {
+                name: PROCESS_EXPORT,
+                error,
+            })?;
+        let (ptr, len) = ((packed as u64 >> 32) as usize, packed as u32 as usize);
+        self.memory
+            .data(&self.store)
+            .get(ptr..ptr.saturating_add(len))
+            .map(<[u8]>::to_vec)
+            .context(OutOfBoundsSnafu)
+    }
+}
+
+/// Sets the fuel remaining in the store, which `Store::add_fuel` only adds to.
+fn set_fuel<T>(store: &mut Store<T>, fuel: u64) -> Result<(), wasmtime::Error> {
+    let remaining = store.consume_fuel(0)?;
+    if remaining < fuel {
+        store.add_fuel(fuel - remaining)
+    } else {
+        store.consume_fuel(remaining - fuel).map(drop)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIMITS: Limits = Limits {
+        fuel: Some(1_000_000),
+        max_memory_bytes: 1024 * 1024,
+    };
+
+    fn guest(wat: &str, limits: Limits) -> Result<Guest, GuestError> {
+        let engine = Engine::new(wasmtime::Config::new().consume_fuel(true)).unwrap();
+        let module = Module::new(&engine, wat).unwrap();
+        Guest::new(&engine, &module, limits)
+    }
+
+    #[test]
+    fn checks_the_exports() {
+        let error = guest(r#"(module (memory (export "memory") 1))"#, LIMITS)
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.to_string(),
+            r#"The module does not export the "alloc" function"#
+        );
+    }
+
+    #[test]
+    fn limits_the_memory() {
+        // The module requires 32 pages of 64KiB, above the limit of 1MiB.
+        let wat = r#"(module (memory (export "memory") 32))"#;
+        assert!(matches!(
+            guest(wat, LIMITS),
+            Err(GuestError::Instantiate { .. })
+        ));
+    }
+}
@@ -0,0 +1,439 @@
+use std::{
+    path::PathBuf,
+    time::{Duration, Instant, SystemTime},
+};
+
+use snafu::{ResultExt, Snafu};
+use vector_lib::config::LogNamespace;
+use vector_lib::configurable::configurable_component;
+use vector_lib::schema::Definition;
+use wasmtime::{Engine, Module};
+
+use crate::{
+    config::{
+        DataType, GenerateConfig, Input, OutputId, TransformConfig, TransformContext,
+        TransformOutput,
+    },
+    event::{Event, LogEvent, Value},
+    internal_events::{
+        WasmInvalidOutputError, WasmModuleReloadError, WasmModuleReloaded, WasmProcessError,
+    },
+    schema,
+    transforms::{FunctionTransform, OutputBuffer, Transform},
+};
+
+mod guest;
+
+pub use self::guest::GuestError;
+use self::guest::{Guest, Limits};
+
+/// The period of the checks for the changes to the module, when `hot_reload` is enabled.
+const RELOAD_CHECK_PERIOD: Duration = Duration::from_secs(1);
+
+const fn default_max_memory_bytes() -> usize {
+    64 * 1024 * 1024
+}
+
+/// Configuration for the `wasm` transform.
+#[configurable_component(transform(
+    "wasm",
+    "Transform events with a WebAssembly module, for compiled custom logic."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct WasmConfig {
+    /// The path of the WebAssembly module, in the binary or in the text format.
+    ///
+    /// The module must not import any function, and must export its `memory`, an
+    /// `alloc(len: i32) -> i32` function returning the address of a buffer of `len` bytes, and a
+    /// `process(ptr: i32, len: i32) -> i64` function. Each event is written to a buffer as JSON
+    /// and passed to `process`, which returns the address of the buffer of its output in the high
+    /// 32 bits, and its length in the low 32 bits. The output is JSON as well: an object for the
+    /// transformed event, an array of objects to replace the event with several events, or `null`
+    /// to drop the event.
+    #[configurable(metadata(docs::examples = "/etc/vector/transform.wasm"))]
+    pub module: PathBuf,
+
+    /// The maximum amount of fuel the module can consume to process an event.
+    ///
+    /// Fuel is consumed by each WebAssembly instruction, and an event whose processing runs out of
+    /// fuel is dropped, protecting the pipeline from infinite loops. By default, the fuel is not
+    /// limited.
+    #[configurable(metadata(docs::examples = 10000000))]
+    pub fuel: Option<u64>,
+
+    /// The maximum size, in bytes, of the memory of the module.
+    #[serde(default = "default_max_memory_bytes")]
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    pub max_memory_bytes: usize,
+
+    /// Whether or not to reload the module when its file changes.
+    ///
+    /// The file is checked for changes every second. If the new module is not valid, the previous
+    /// one is kept.
+    #[serde(default)]
+    pub hot_reload: bool,
+}
+
+impl GenerateConfig for WasmConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            module: PathBuf::from("/etc/vector/transform.wasm"),
+            fuel: None,
+            max_memory_bytes: default_max_memory_bytes(),
+            hot_reload: false,
+        })
+        .unwrap()
+    }
+}
+
+#[derive(Debug, Snafu)]
+pub enum BuildError {
+    #[snafu(display("Unable to read the module {:?}: {}", path, source))]
+    ReadModule {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[snafu(display("Unable to compile the module {:?}: {:#}", path, error))]
+    CompileModule {
+        path: PathBuf,
+        error: wasmtime::Error,
+    },
+    #[snafu(display("Invalid module {:?}: {}", path, source))]
+    InvalidModule { path: PathBuf, source: GuestError },
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "wasm")]
+impl TransformConfig for WasmConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        Wasm::new(self).map(Transform::function).map_err(Into::into)
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn outputs(
+        &self,
+        _: vector_lib::enrichment::TableRegistry,
+        input_definitions: &[(OutputId, schema::Definition)],
+        _: LogNamespace,
+    ) -> Vec<TransformOutput> {
+        // The fields of the events are set by the module, and only known at runtime.
+        let definitions = input_definitions
+            .iter()
+            .map(|(output, definition)| {
+                (
+                    output.clone(),
+                    Definition::default_for_namespace(definition.log_namespaces()),
+                )
+            })
+            .collect();
+        vec![TransformOutput::new(DataType::Log, definitions)]
+    }
+}
+
+/// The version of the file of a module, to detect its changes.
+type ModuleVersion = (Option<SystemTime>, u64);
+
+pub struct Wasm {
+    path: PathBuf,
+    engine: Engine,
+    module: Module,
+    limits: Limits,
+    hot_reload: bool,
+    version: ModuleVersion,
+    last_check: Instant,
+    /// The instance of the module, created when processing the first event, and after an error.
+    guest: Option<Guest>,
+}
+
+impl Clone for Wasm {
+    fn clone(&self) -> Self {
+        Self {
+            path: self.path.clone(),
+            engine: self.engine.clone(),
+            module: self.module.clone(),
+            limits: self.limits,
+            hot_reload: self.hot_reload,
+            version: self.version,
+            last_check: self.last_check,
+            guest: None,
+        }
+    }
+}
+
+impl Wasm {
+    pub fn new(config: &WasmConfig) -> Result<Self, BuildError> {
+        let limits = Limits {
+            fuel: config.fuel,
+            max_memory_bytes: config.max_memory_bytes,
+        };
+        let engine = Engine::new(wasmtime::Config::new().consume_fuel(limits.fuel.is_some()))
+            .expect("the engine configuration is valid");
+
+        let version = module_version(&config.module).context(ReadModuleSnafu {
+            path: &config.module,
+        })?;
+        let module = compile(&engine, &config.module)?;
+        // Instantiate the module once, to report a module not implementing the guest ABI at startup.
+        let guest = Guest::new(&engine, &module, limits).context(InvalidModuleSnafu {
+            path: &config.module,
+        })?;
+
+        Ok(Self {
+            path: config.module.clone(),
+            engine,
+            module,
+            limits,
+            hot_reload: config.hot_reload,
+            version,
+            last_check: Instant::now(),
+            guest: Some(guest),
+        })
+    }
+
+    /// Reloads the module if its file changed since it was loaded.
+    fn reload(&mut self) {
+        let version = match module_version(&self.path) {
+            Ok(version) if version == self.version => return,
+            Ok(version) => version,
+            Err(source) => {
+                emit!(WasmModuleReloadError {
+                    error: BuildError::ReadModule {
+                        path: self.path.clone(),
+                        source,
+                    },
+                });
+                return;
+            }
+        };
+        // The version is updated even if the new module is not valid, to only report it once.
+        self.version = version;
+
+        let reloaded = compile(&self.engine, &self.path).and_then(|module| {
+            Guest::new(&self.engine, &module, self.limits)
+                .map(|guest| (module, guest))
+                .context(InvalidModuleSnafu { path: &self.path })
+        });
+        match reloaded {
+            Ok((module, guest)) => {
+                self.module = module;
+                self.guest = Some(guest);
+                emit!(WasmModuleReloaded { path: &self.path });
+            }
+            Err(error) => emit!(WasmModuleReloadError { error }),
+        }
+    }
+
+    fn process(&mut self, event: Event) -> Option<Vec<Event>> {
+        let guest = match self.guest.take() {
+            Some(guest) => guest,
+            None => match Guest::new(&self.engine, &self.module, self.limits) {
+                Ok(guest) => guest,
+                Err(error) => {
+                    emit!(WasmProcessError { error });
+                    return None;
+                }
+            },
+        };
+        let guest = self.guest.insert(guest);
+
+        let (value, metadata) = event.into_log().into_parts();
+        let input = serde_json::to_vec(&value).expect("values are serializable");
+        let output = match guest.process(&input) {
+            Ok(output) => output,
+            Err(error) => {
+                // The instance may have been left in an inconsistent state, so it is replaced.
+                self.guest = None;
+                emit!(WasmProcessError { error });
+                return None;
+            }
+        };
+
+        let values = match serde_json::from_slice(&output) {
+            Ok(serde_json::Value::Null) => Vec::new(),
+            Ok(serde_json::Value::Array(values)) => values,
+            Ok(value) => vec![value],
+            Err(error) => {
+                emit!(WasmInvalidOutputError {
+                    error: error.to_string(),
+                });
+                return None;
+            }
+        };
+        if let Some(value) = values.iter().find(|value| !value.is_object()) {
+            emit!(WasmInvalidOutputError {
+                error: format!("expected an object, found `{value}`"),
+            });
+            return None;
+        }
+
+        Some(
+            values
+                .into_iter()
+                .map(|value| {
+                    Event::from(LogEvent::from_parts(Value::from(value), metadata.clone()))
+                })
+                .collect(),
+        )
+    }
+}
+
+fn module_version(path: &PathBuf) -> std::io::Result<ModuleVersion> {
+    let metadata = std::fs::metadata(path)?;
+    Ok((metadata.modified().ok(), metadata.len()))
+}
+
+fn compile(engine: &Engine, path: &PathBuf) -> Result<Module, BuildError> {
+    let bytes = std::fs::read(path).context(ReadModuleSnafu { path })?;
+    Module::new(engine, bytes).map_err(|error| BuildError::CompileModule {
+        path: path.clone(),
+        error,
+    })
+}
+
+impl FunctionTransform for Wasm {
+    fn transform(&mut self, output: &mut OutputBuffer, event: Event) {
+        if self.hot_reload && self.last_check.elapsed() >= RELOAD_CHECK_PERIOD {
+            self.last_check = Instant::now();
+            self.reload();
+        }
+
+        if let Some(events) = self.process(event) {
+            for event in events {
+                output.push(event);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_file;
+
+    /// Returns the input event unmodified.
+    const IDENTITY: &str = r#"
+        (module
+            (memory (export "memory") 1)
+            (func (export "alloc") (param $len i32) (result i32)
+                (i32.const 1024))
+            (func (export "process") (param $ptr i32) (param $len i32) (result i64)
+                (i64.or
+                    (i64.shl (i64.extend_i32_u (local.get $ptr)) (i64.const 32))
+                    (i64.extend_i32_u (local.get $len)))))
+    "#;
+
+    /// Replaces each event with two events.
+    const SPLIT: &str = r#"
+        (module
+            (memory (export "memory") 1)
+            (data (i32.const 0) "[{\"part\":1},{\"part\":2}]")
+            (func (export "alloc") (param $len i32) (result i32)
+                (i32.const 1024))
+            (func (export "process") (param $ptr i32) (param $len i32) (result i64)
+                (i64.const 23)))
+    "#;
+
+    /// Never returns.
+    const LOOP: &str = r#"
+        (module
+            (memory (export "memory") 1)
+            (func (export "alloc") (param $len i32) (result i32)
+                (i32.const 1024))
+            (func (export "process") (param $ptr i32) (param $len i32) (result i64)
+                (loop $forever (br $forever))
+                (i64.const 0)))
+    "#;
+
+    fn config(wat: &str) -> WasmConfig {
+        let path = temp_file();
+        std::fs::write(&path, wat).unwrap();
+        WasmConfig {
+            module: path,
+            fuel: Some(1_000_000),
+            max_memory_bytes: default_max_memory_bytes(),
+            hot_reload: false,
+        }
+    }
+
+    fn log(value: serde_json::Value) -> Event {
+        Event::from_json_value(value, LogNamespace::Legacy).unwrap()
+    }
+
+    fn transform(wasm: &mut Wasm, event: Event) -> Vec<serde_json::Value> {
+        let mut output = OutputBuffer::default();
+        wasm.transform(&mut output, event);
+        output
+            .into_events()
+            .map(|event| serde_json::to_value(event.as_log()).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<WasmConfig>();
+    }
+
+    #[test]
+    fn transforms_events() {
+        let event = serde_json::json!({ "message": "hello", "count": 3 });
+        let mut wasm = Wasm::new(&config(IDENTITY)).unwrap();
+        assert_eq!(transform(&mut wasm, log(event.clone())), vec![event]);
+
+        let mut wasm = Wasm::new(&config(SPLIT)).unwrap();
+        assert_eq!(
+            transform(&mut wasm, log(serde_json::json!({ "message": "hello" }))),
+            vec![
+                serde_json::json!({ "part": 1 }),
+                serde_json::json!({ "part": 2 })
+            ]
+        );
+    }
+
+    #[test]
+    fn drops_events_running_out_of_fuel() {
+        let mut wasm = Wasm::new(&config(LOOP)).unwrap();
+        for _ in 0..2 {
+            assert!(
+                transform(&mut wasm, log(serde_json::json!({ "message": "hello" }))).is_empty()
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_modules() {
+        let error = Wasm::new(&config(r#"(module (memory (export "memory") 1))"#))
+            .err()
+            .unwrap();
+        assert!(matches!(error, BuildError::InvalidModule { .. }));
+    }
+
+    #[test]
+    fn hot_reloads_the_module() {
+        let config = WasmConfig {
+            hot_reload: true,
+            ..config(IDENTITY)
+        };
+        let mut wasm = Wasm::new(&config).unwrap();
+        let event = serde_json::json!({ "message": "hello" });
+        assert_eq!(
+            transform(&mut wasm, log(event.clone())),
+            vec![event.clone()]
+        );
+
+        // An invalid module is ignored.
+        std::fs::write(&config.module, "(module").unwrap();
+        wasm.last_check -= RELOAD_CHECK_PERIOD;
+        assert_eq!(transform(&mut wasm, log(event.clone())), vec![event]);
+
+        std::fs::write(&config.module, SPLIT).unwrap();
+        wasm.last_check -= RELOAD_CHECK_PERIOD;
+        assert_eq!(
+            transform(&mut wasm, log(serde_json::json!({ "message": "hello" }))).len(),
+            2
+        );
+    }
+}
@@ -0,0 +1,204 @@
+use ordered_float::NotNan;
+use vector_lib::configurable::configurable_component;
+use vector_lib::lookup::lookup_v2::ConfigValuePath;
+use vrl::path::PathPrefix;
+
+use crate::event::{LogEvent, Value};
+
+/// An aggregation computed over the events of each window.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(tag = "function", rename_all = "snake_case", deny_unknown_fields)]
+#[configurable(metadata(docs::enum_tag_description = "The function of the aggregation."))]
+pub enum Aggregation {
+    /// The number of events in the window.
+    Count,
+
+    /// The sum of the values of a field.
+    Sum {
+        /// The field to aggregate.
+        ///
+        /// Events whose field is missing, or isn't a number, are ignored by the aggregation.
+        field: ConfigValuePath,
+    },
+
+    /// The minimum of the values of a field.
+    Min {
+        /// The field to aggregate.
+        ///
+        /// Events whose field is missing, or isn't a number, are ignored by the aggregation.
+        field: ConfigValuePath,
+    },
+
+    /// The maximum of the values of a field.
+    Max {
+        /// The field to aggregate.
+        ///
+        /// Events whose field is missing, or isn't a number, are ignored by the aggregation.
+        field: ConfigValuePath,
+    },
+
+    /// A percentile of the values of a field, computed with the nearest-rank method.
+    ///
+    /// All the values of the field in the window are kept until the window is flushed.
+    Percentile {
+        /// The field to aggregate.
+        ///
+        /// Events whose field is missing, or isn't a number, are ignored by the aggregation.
+        field: ConfigValuePath,
+
+        /// The percentile to compute, between `0` and `100`.
+        #[configurable(metadata(docs::examples = 50.0))]
+        #[configurable(metadata(docs::examples = 99.0))]
+        percentile: f64,
+    },
+}
+
+impl Aggregation {
+    pub(super) fn validate(&self) -> crate::Result<()> {
+        match self {
+            Self::Percentile { percentile, .. } if !(0.0..=100.0).contains(percentile) => {
+                Err("the `percentile` of an aggregation must be between 0 and 100".into())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn field(&self) -> Option<&ConfigValuePath> {
+        match self {
+            Self::Count => None,
+            Self::Sum { field }
+            | Self::Min { field }
+            | Self::Max { field }
+            | Self::Percentile { field, .. } => Some(field),
+        }
+    }
+
+    /// Adds the value of the field of the event to the accumulator of the aggregation.
+    pub(super) fn add(&self, accumulator: &mut Accumulator, log: &LogEvent) {
+        let Some(value) = self
+            .field()
+            .and_then(|field| log.get((PathPrefix::Event, field)))
+            .and_then(to_f64)
+        else {
+            return;
+        };
+
+        accumulator.count += 1;
+        accumulator.sum += value;
+        accumulator.min = accumulator.min.min(value);
+        accumulator.max = accumulator.max.max(value);
+        if matches!(self, Self::Percentile { .. }) {
+            accumulator.values.push(value);
+        }
+    }
+
+    /// The result of the aggregation over a window of `events`, if any of them had a value.
+    pub(super) fn result(&self, accumulator: Accumulator, events: usize) -> Option<Value> {
+        let value = match self {
+            Self::Count => return Some(Value::from(events as i64)),
+            Self::Sum { .. } => accumulator.sum,
+            _ if accumulator.count == 0 => return None,
+            Self::Min { .. } => accumulator.min,
+            Self::Max { .. } => accumulator.max,
+            Self::Percentile { percentile, .. } => {
+                let mut values = accumulator.values;
+                values.sort_unstable_by(f64::total_cmp);
+                let rank = (percentile / 100.0 * values.len() as f64).ceil() as usize;
+                values[rank.clamp(1, values.len()) - 1]
+            }
+        };
+        NotNan::new(value).ok().map(Value::Float)
+    }
+}
+
+/// The state of an aggregation over the events of a window, or of a part of it.
+#[derive(Clone, Debug)]
+pub(super) struct Accumulator {
+    count: usize,
+    sum: f64,
+    min: f64,
+    max: f64,
+    values: Vec<f64>,
+}
+
+impl Default for Accumulator {
+    fn default() -> Self {
+        Self {
+            count: 0,
+            sum: 0.0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+            values: Vec::new(),
+        }
+    }
+}
+
+impl Accumulator {
+    pub(super) fn merge(&mut self, other: &Self) {
+        self.count += other.count;
+        self.sum += other.sum;
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+        self.values.extend_from_slice(&other.values);
+    }
+}
+
+fn to_f64(value: &Value) -> Option<f64> {
+    match value {
+        Value::Integer(value) => Some(*value as f64),
+        Value::Float(value) => Some(value.into_inner()),
+        Value::Bytes(bytes) => std::str::from_utf8(bytes)
+            .ok()
+            .and_then(|value| value.trim().parse::<f64>().ok())
+            .filter(|value| !value.is_nan()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn percentile(percentile: f64, values: &[f64]) -> Option<Value> {
+        let aggregation = Aggregation::Percentile {
+            field: ConfigValuePath::from("duration"),
+            percentile,
+        };
+        let mut accumulator = Accumulator::default();
+        for value in values {
+            let mut log = LogEvent::default();
+            log.insert("duration", *value);
+            aggregation.add(&mut accumulator, &log);
+        }
+        aggregation.result(accumulator, values.len())
+    }
+
+    #[test]
+    fn computes_nearest_rank_percentiles() {
+        let values = [15.0, 20.0, 35.0, 40.0, 50.0];
+
+        assert_eq!(percentile(0.0, &values), Some(Value::from(15.0)));
+        assert_eq!(percentile(30.0, &values), Some(Value::from(20.0)));
+        assert_eq!(percentile(40.0, &values), Some(Value::from(20.0)));
+        assert_eq!(percentile(50.0, &values), Some(Value::from(35.0)));
+        assert_eq!(percentile(100.0, &values), Some(Value::from(50.0)));
+        assert_eq!(percentile(50.0, &[]), None);
+    }
+
+    #[test]
+    fn ignores_non_numeric_values() {
+        let aggregation = Aggregation::Sum {
+            field: ConfigValuePath::from("bytes"),
+        };
+        let mut accumulator = Accumulator::default();
+        for value in [Value::from(2), Value::from("3.5"), Value::from("n/a")] {
+            let mut log = LogEvent::default();
+            log.insert("bytes", value);
+            aggregation.add(&mut accumulator, &log);
+        }
+        aggregation.add(&mut accumulator, &LogEvent::default());
+
+        assert_eq!(aggregation.result(accumulator, 4), Some(Value::from(5.5)));
+    }
+}
@@ -0,0 +1,633 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    pin::Pin,
+    time::Duration,
+};
+
+use async_stream::stream;
+use chrono::{DateTime, Utc};
+use futures::{Stream, StreamExt};
+use indexmap::IndexMap;
+use vector_lib::config::LogNamespace;
+use vector_lib::configurable::configurable_component;
+use vector_lib::lookup::{event_path, lookup_v2::parse_target_path, OwnedTargetPath};
+use vector_lib::{compile_vrl, TimeZone};
+use vector_vrl_functions::set_semantic_meaning::MeaningList;
+use vrl::compiler::runtime::{Runtime, Terminate};
+use vrl::compiler::{CompilationResult, CompileConfig, Program, TypeState};
+use vrl::diagnostic::Formatter;
+use vrl::value::KeyString;
+
+use crate::{
+    config::{DataType, Input, OutputId, TransformConfig, TransformContext, TransformOutput},
+    event::{
+        discriminant::Discriminant, Event, EventMetadata, LogEvent, TargetEvents, Value, VrlTarget,
+    },
+    internal_events::{WindowAggregateFinalizerAbort, WindowAggregateFinalizerError},
+    schema::{self, Definition},
+    transforms::{TaskTransform, Transform},
+};
+
+mod aggregation;
+
+use aggregation::Accumulator;
+pub use aggregation::Aggregation;
+
+/// Configuration for the `window_aggregate` transform.
+#[configurable_component(transform(
+    "window_aggregate",
+    "Aggregate log events over tumbling or sliding windows into summary events.",
+))]
+#[derive(Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct WindowAggregateConfig {
+    #[configurable(derived)]
+    #[serde(default)]
+    pub window: WindowConfig,
+
+    /// An ordered list of fields by which to group events.
+    ///
+    /// Each group with matching values for the specified fields is aggregated independently, into
+    /// one summary event per window that contains these fields. When no fields are specified, all
+    /// events are aggregated in a single group.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "service", docs::examples = "status"))]
+    pub group_by: Vec<String>,
+
+    /// A map of the fields of the summary events to the aggregations computed into them.
+    ///
+    /// Besides these fields and the fields in `group_by`, each summary event contains the bounds
+    /// of its window, in the `window_start` and `window_end` fields.
+    #[serde(default)]
+    #[configurable(metadata(docs::additional_props_description = "An aggregation."))]
+    pub aggregations: IndexMap<KeyString, Aggregation>,
+
+    /// A [Vector Remap Language][vrl] (VRL) program run on each summary event before it's emitted.
+    ///
+    /// The program can modify the summary event, or drop it with `abort`. If the program fails,
+    /// the summary event is emitted unmodified.
+    ///
+    /// [vrl]: https://vector.dev/docs/reference/vrl
+    #[configurable(metadata(
+        docs::examples = ".error_rate = .errors / .count",
+        docs::syntax_override = "remap_program"
+    ))]
+    pub finalizer: Option<String>,
+}
+
+/// The windows over which events are aggregated.
+///
+/// Windows are based on the time at which the events are processed, rather than on their
+/// timestamps.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+#[configurable(metadata(docs::enum_tag_description = "The type of the windows."))]
+pub enum WindowConfig {
+    /// Consecutive windows that don't overlap, such that each event is aggregated in one window.
+    Tumbling {
+        /// The duration of the windows, in milliseconds.
+        #[configurable(metadata(docs::human_name = "Window Duration"))]
+        duration_ms: u64,
+    },
+
+    /// Overlapping windows that start at every step, such that each event is aggregated in
+    /// `duration_ms / step_ms` windows.
+    Sliding {
+        /// The duration of the windows, in milliseconds.
+        #[configurable(metadata(docs::human_name = "Window Duration"))]
+        duration_ms: u64,
+
+        /// The interval between the starts of consecutive windows, in milliseconds.
+        ///
+        /// The duration of the windows must be a multiple of it.
+        #[configurable(metadata(docs::human_name = "Window Step"))]
+        step_ms: u64,
+    },
+}
+
+impl Default for WindowConfig {
+    fn default() -> Self {
+        Self::Tumbling {
+            duration_ms: 60 * 1000,
+        }
+    }
+}
+
+impl WindowConfig {
+    /// The interval between flushes, and the number of these intervals in each window.
+    fn steps(&self) -> crate::Result<(Duration, usize)> {
+        let (duration_ms, step_ms) = match *self {
+            Self::Tumbling { duration_ms } => (duration_ms, duration_ms),
+            Self::Sliding {
+                duration_ms,
+                step_ms,
+            } => (duration_ms, step_ms),
+        };
+
+        if duration_ms == 0 || step_ms == 0 || duration_ms % step_ms != 0 {
+            return Err(
+                "the `duration_ms` of the windows must be a non-zero multiple of their `step_ms`"
+                    .into(),
+            );
+        }
+        Ok((
+            Duration::from_millis(step_ms),
+            (duration_ms / step_ms) as usize,
+        ))
+    }
+}
+
+impl_generate_config_from_default!(WindowAggregateConfig);
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "window_aggregate")]
+impl TransformConfig for WindowAggregateConfig {
+    async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
+        WindowAggregate::new(self, context).map(Transform::event_task)
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn outputs(
+        &self,
+        _: vector_lib::enrichment::TableRegistry,
+        input_definitions: &[(OutputId, schema::Definition)],
+        _: LogNamespace,
+    ) -> Vec<TransformOutput> {
+        // The summary events are new events, whose fields are only known at runtime.
+        let namespaces = input_definitions
+            .iter()
+            .flat_map(|(_output, definition)| definition.log_namespaces().clone())
+            .collect();
+
+        let definitions = input_definitions
+            .iter()
+            .map(|(output, _definition)| {
+                (
+                    output.clone(),
+                    Definition::default_for_namespace(&namespaces),
+                )
+            })
+            .collect();
+
+        vec![TransformOutput::new(DataType::Log, definitions)]
+    }
+}
+
+/// The aggregations of the events of a group during a step of the windows.
+#[derive(Debug)]
+struct Pane {
+    events: usize,
+    accumulators: Vec<Accumulator>,
+    metadata: EventMetadata,
+}
+
+impl Pane {
+    fn new(aggregations: usize) -> Self {
+        Self {
+            events: 0,
+            accumulators: vec![Accumulator::default(); aggregations],
+            metadata: EventMetadata::default(),
+        }
+    }
+}
+
+/// The events of a group during the steps of its current window, the last of which is in progress.
+#[derive(Debug)]
+struct Window {
+    group: Vec<(OwnedTargetPath, Value)>,
+    panes: VecDeque<Pane>,
+}
+
+pub struct WindowAggregate {
+    step: Duration,
+    steps_per_window: usize,
+    duration: chrono::Duration,
+    group_by: Vec<String>,
+    group_paths: Vec<OwnedTargetPath>,
+    aggregations: Vec<(OwnedTargetPath, Aggregation)>,
+    finalizer: Option<Program>,
+    timezone: TimeZone,
+    windows: HashMap<Discriminant, Window>,
+}
+
+impl WindowAggregate {
+    pub fn new(config: &WindowAggregateConfig, context: &TransformContext) -> crate::Result<Self> {
+        let (step, steps_per_window) = config.window.steps()?;
+        let duration = chrono::Duration::from_std(step * steps_per_window as u32)?;
+
+        let group_paths = config
+            .group_by
+            .iter()
+            .map(|field| {
+                parse_target_path(field)
+                    .map_err(|error| format!("invalid `group_by` field {:?}: {}", field, error))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let aggregations = config
+            .aggregations
+            .iter()
+            .map(|(field, aggregation)| {
+                aggregation.validate()?;
+                let path = parse_target_path(field)
+                    .map_err(|error| format!("invalid aggregation field {:?}: {}", field, error))?;
+                Ok((path, aggregation.clone()))
+            })
+            .collect::<crate::Result<Vec<_>>>()?;
+
+        let finalizer = config
+            .finalizer
+            .as_deref()
+            .map(|source| compile_finalizer(source, context))
+            .transpose()?;
+
+        Ok(Self {
+            step,
+            steps_per_window,
+            duration,
+            group_by: config.group_by.clone(),
+            group_paths,
+            aggregations,
+            finalizer,
+            timezone: context.globals.timezone(),
+            windows: HashMap::new(),
+        })
+    }
+
+    fn record(&mut self, event: Event) {
+        let log = event.into_log();
+        let discriminant = Discriminant::from_log_event(&log, &self.group_by);
+
+        let aggregations = self.aggregations.len();
+        let group_paths = &self.group_paths;
+        let window = self.windows.entry(discriminant).or_insert_with(|| Window {
+            group: group_paths
+                .iter()
+                .filter_map(|path| Some((path.clone(), log.get(path)?.clone())))
+                .collect(),
+            panes: VecDeque::from([Pane::new(aggregations)]),
+        });
+
+        let pane = window
+            .panes
+            .back_mut()
+            .expect("windows always have a step in progress");
+        for ((_, aggregation), accumulator) in self.aggregations.iter().zip(&mut pane.accumulators)
+        {
+            aggregation.add(accumulator, &log);
+        }
+        pane.events += 1;
+        pane.metadata.merge(log.into_parts().1);
+    }
+
+    /// Flushes the windows ending at `end`, and starts their next step.
+    fn flush_into(&mut self, output: &mut Vec<Event>, end: DateTime<Utc>) {
+        let mut windows = std::mem::take(&mut self.windows);
+        windows.retain(|_, window| {
+            self.summarize_into(output, window, end);
+
+            window.panes.push_back(Pane::new(self.aggregations.len()));
+            if window.panes.len() > self.steps_per_window {
+                window.panes.pop_front();
+            }
+            window.panes.iter().any(|pane| pane.events > 0)
+        });
+        self.windows = windows;
+    }
+
+    /// Flushes the windows in progress, when the input ends.
+    fn flush_all_into(&mut self, output: &mut Vec<Event>, end: DateTime<Utc>) {
+        for (_, window) in std::mem::take(&mut self.windows) {
+            self.summarize_into(output, &window, end);
+        }
+    }
+
+    fn summarize_into(&self, output: &mut Vec<Event>, window: &Window, end: DateTime<Utc>) {
+        let events = window.panes.iter().map(|pane| pane.events).sum();
+        if events == 0 {
+            return;
+        }
+
+        let mut metadata = EventMetadata::default();
+        let mut accumulators = vec![Accumulator::default(); self.aggregations.len()];
+        for pane in &window.panes {
+            metadata.merge(pane.metadata.clone());
+            for (accumulator, other) in accumulators.iter_mut().zip(&pane.accumulators) {
+                accumulator.merge(other);
+            }
+        }
+
+        let mut log = LogEvent::new_with_metadata(metadata);
+        for (path, value) in &window.group {
+            log.insert(path, value.clone());
+        }
+        log.insert(event_path!("window_start"), end - self.duration);
+        log.insert(event_path!("window_end"), end);
+        for ((path, aggregation), accumulator) in self.aggregations.iter().zip(accumulators) {
+            if let Some(value) = aggregation.result(accumulator, events) {
+                log.insert(path, value);
+            }
+        }
+
+        self.finalize_into(output, log);
+    }
+
+    fn finalize_into(&self, output: &mut Vec<Event>, log: LogEvent) {
+        let Some(program) = &self.finalizer else {
+            output.push(log.into());
+            return;
+        };
+
+        let original = log.clone();
+        let mut target = VrlTarget::new(log.into(), program.info(), false);
+        match Runtime::default().resolve(&mut target, program, &self.timezone) {
+            Ok(_) => match target.into_events(LogNamespace::Legacy) {
+                TargetEvents::One(event) => output.push(event),
+                TargetEvents::Logs(events) => output.extend(events),
+                TargetEvents::Traces(events) => output.extend(events),
+            },
+            Err(Terminate::Abort(_)) => emit!(WindowAggregateFinalizerAbort),
+            Err(Terminate::Error(error)) => {
+                emit!(WindowAggregateFinalizerError {
+                    error: error.to_string(),
+                });
+                output.push(original.into());
+            }
+        }
+    }
+}
+
+fn compile_finalizer(source: &str, context: &TransformContext) -> crate::Result<Program> {
+    let functions = vrl::stdlib::all()
+        .into_iter()
+        .chain(vector_lib::enrichment::vrl_functions())
+        .chain(vector_vrl_functions::all())
+        .collect::<Vec<_>>();
+
+    let mut config = CompileConfig::default();
+    config.set_custom(context.enrichment_tables.clone());
+    config.set_custom(MeaningList::default());
+
+    let CompilationResult {
+        program,
+        warnings,
+        config: _,
+    } = compile_vrl(source, &functions, &TypeState::default(), config)
+        .map_err(|diagnostics| Formatter::new(source, diagnostics).colored().to_string())?;
+
+    if !warnings.is_empty() {
+        let warnings = Formatter::new(source, warnings).colored().to_string();
+        warn!(message = "VRL compilation warning.", %warnings);
+    }
+
+    Ok(program)
+}
+
+impl TaskTransform<Event> for WindowAggregate {
+    fn transform(
+        mut self: Box<Self>,
+        mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
+    where
+        Self: 'static,
+    {
+        let mut flush_stream =
+            tokio::time::interval_at(tokio::time::Instant::now() + self.step, self.step);
+
+        Box::pin(stream! {
+            let mut output = Vec::new();
+            let mut done = false;
+            while !done {
+                tokio::select! {
+                    _ = flush_stream.tick() => {
+                        self.flush_into(&mut output, Utc::now());
+                    },
+                    maybe_event = input_rx.next() => {
+                        match maybe_event {
+                            None => {
+                                self.flush_all_into(&mut output, Utc::now());
+                                done = true;
+                            }
+                            Some(event) => self.record(event),
+                        }
+                    }
+                };
+                for event in output.drain(..) {
+                    yield event;
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone as _;
+    use tokio::sync::mpsc;
+    use tokio_stream::wrappers::ReceiverStream;
+    use vrl::value;
+
+    use super::*;
+    use crate::{
+        test_util::components::assert_transform_compliance, transforms::test::create_topology,
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<WindowAggregateConfig>();
+    }
+
+    fn window_aggregate(config: &str) -> WindowAggregate {
+        let config = toml::from_str::<WindowAggregateConfig>(config).unwrap();
+        WindowAggregate::new(&config, &TransformContext::default()).unwrap()
+    }
+
+    fn event(service: &str, duration: i64) -> Event {
+        let mut log = LogEvent::from("request");
+        log.insert("service", service);
+        log.insert("duration", duration);
+        log.into()
+    }
+
+    fn end(seconds: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, seconds).unwrap()
+    }
+
+    fn flush(aggregate: &mut WindowAggregate, end: DateTime<Utc>) -> Vec<LogEvent> {
+        let mut output = Vec::new();
+        aggregate.flush_into(&mut output, end);
+        let mut summaries = output.into_iter().map(Event::into_log).collect::<Vec<_>>();
+        summaries.sort_by_key(|summary| {
+            summary
+                .get("service")
+                .map(|service| service.to_string_lossy().into_owned())
+        });
+        summaries
+    }
+
+    fn values(summaries: Vec<LogEvent>) -> Vec<Value> {
+        summaries
+            .into_iter()
+            .map(|summary| summary.value().clone())
+            .collect()
+    }
+
+    #[test]
+    fn aggregates_tumbling_windows() {
+        let mut aggregate = window_aggregate(
+            r#"
+            window.type = "tumbling"
+            window.duration_ms = 10000
+            group_by = ["service"]
+            aggregations.count.function = "count"
+            aggregations.total.function = "sum"
+            aggregations.total.field = "duration"
+            aggregations.fastest.function = "min"
+            aggregations.fastest.field = "duration"
+            aggregations.slowest.function = "max"
+            aggregations.slowest.field = "duration"
+            aggregations.median.function = "percentile"
+            aggregations.median.field = "duration"
+            aggregations.median.percentile = 50.0
+            "#,
+        );
+
+        for (service, duration) in [("api", 30), ("web", 5), ("api", 10), ("api", 20)] {
+            aggregate.record(event(service, duration));
+        }
+
+        assert_eq!(
+            values(flush(&mut aggregate, end(10))),
+            [
+                value!({
+                    "service": "api",
+                    "window_start": (end(0)),
+                    "window_end": (end(10)),
+                    "count": 3,
+                    "total": 60.0,
+                    "fastest": 10.0,
+                    "slowest": 30.0,
+                    "median": 20.0,
+                }),
+                value!({
+                    "service": "web",
+                    "window_start": (end(0)),
+                    "window_end": (end(10)),
+                    "count": 1,
+                    "total": 5.0,
+                    "fastest": 5.0,
+                    "slowest": 5.0,
+                    "median": 5.0,
+                }),
+            ]
+        );
+        assert!(flush(&mut aggregate, end(20)).is_empty());
+    }
+
+    #[test]
+    fn aggregates_sliding_windows() {
+        let mut aggregate = window_aggregate(
+            r#"
+            window.type = "sliding"
+            window.duration_ms = 2000
+            window.step_ms = 1000
+            aggregations.total.function = "sum"
+            aggregations.total.field = "duration"
+            "#,
+        );
+
+        aggregate.record(event("api", 1));
+        let summaries = flush(&mut aggregate, end(1));
+        assert_eq!(summaries.len(), 1);
+        assert_eq!(summaries[0].get("total"), Some(&Value::from(1.0)));
+
+        aggregate.record(event("api", 2));
+        let summaries = flush(&mut aggregate, end(2));
+        assert_eq!(summaries.len(), 1);
+        assert_eq!(summaries[0].get("window_start"), Some(&Value::from(end(0))));
+        assert_eq!(summaries[0].get("total"), Some(&Value::from(3.0)));
+
+        let summaries = flush(&mut aggregate, end(3));
+        assert_eq!(summaries.len(), 1);
+        assert_eq!(summaries[0].get("total"), Some(&Value::from(2.0)));
+
+        assert!(flush(&mut aggregate, end(4)).is_empty());
+        assert!(aggregate.windows.is_empty());
+    }
+
+    #[test]
+    fn finalizes_summaries() {
+        let mut aggregate = window_aggregate(
+            r#"
+            window.type = "tumbling"
+            window.duration_ms = 1000
+            group_by = ["service"]
+            aggregations.count.function = "count"
+            finalizer = '''
+            if int!(.count) < 2 { abort }
+            .service = upcase!(.service)
+            del(.window_start)
+            '''
+            "#,
+        );
+
+        for service in ["api", "web", "api"] {
+            aggregate.record(event(service, 1));
+        }
+
+        assert_eq!(
+            values(flush(&mut aggregate, end(1))),
+            [value!({"service": "API", "window_end": (end(1)), "count": 2})]
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_configs() {
+        for config in [
+            "window.type = \"sliding\"\nwindow.duration_ms = 1500\nwindow.step_ms = 1000",
+            "window.type = \"tumbling\"\nwindow.duration_ms = 0",
+            "aggregations.p.function = \"percentile\"\naggregations.p.field = \"duration\"\naggregations.p.percentile = 101.0",
+            "finalizer = \".count = \"",
+        ] {
+            let config = toml::from_str::<WindowAggregateConfig>(config).unwrap();
+
+            assert!(
+                config.build(&TransformContext::default()).await.is_err(),
+                "{:?}",
+                config
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn flushes_windows_in_progress_on_shutdown() {
+        let config = toml::from_str::<WindowAggregateConfig>(
+            r#"
+            group_by = ["service"]
+            aggregations.count.function = "count"
+            "#,
+        )
+        .unwrap();
+
+        assert_transform_compliance(async move {
+            let (tx, rx) = mpsc::channel(1);
+            let (topology, mut out) = create_topology(ReceiverStream::new(rx), config).await;
+
+            tx.send(event("api", 1)).await.unwrap();
+            tx.send(event("api", 2)).await.unwrap();
+            drop(tx);
+
+            let summary = out.recv().await.unwrap().into_log();
+            assert_eq!(summary.get("service"), Some(&Value::from("api")));
+            assert_eq!(summary.get("count"), Some(&Value::from(2)));
+
+            topology.stop().await;
+            assert_eq!(out.recv().await, None);
+        })
+        .await
+    }
+}
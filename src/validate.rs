@@ -9,6 +9,7 @@ use exitcode::ExitCode;
 use crate::{
     config::{self, Config, ConfigDiff},
     extra_context::ExtraContext,
+    sinks::util::service::RequestLimitsGroups,
     topology::{self, builder::TopologyPieces},
 };
 
@@ -190,8 +191,14 @@ async fn validate_components(
     diff: &ConfigDiff,
     fmt: &mut Formatter,
 ) -> Option<TopologyPieces> {
-    match topology::TopologyPieces::build(config, diff, HashMap::new(), ExtraContext::default())
-        .await
+    match topology::TopologyPieces::build(
+        config,
+        diff,
+        HashMap::new(),
+        &RequestLimitsGroups::default(),
+        ExtraContext::default(),
+    )
+    .await
     {
         Ok(pieces) => {
             fmt.success("Component configuration");
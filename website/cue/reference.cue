@@ -234,7 +234,7 @@ _values: {
 	}
 }
 
-#Protocol: "http" | "tcp" | "udp" | "unix" | "unix_datagram" | "unix_stream"
+#Protocol: "http" | "icmp" | "tcp" | "udp" | "unix" | "unix_datagram" | "unix_stream"
 
 #Service: {
 	// `description` describes the components with a single paragraph.
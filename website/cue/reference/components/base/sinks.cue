@@ -171,4 +171,17 @@ base: components: sinks: configuration: {
 			}
 		}
 	}
+	request_limits: {
+		description: """
+			The name of the request limits group to limit the requests of this sink with.
+
+			The requests of all of the sinks that reference the same group count against the
+			concurrency and rate limits configured for it in the top-level `request_limits` section.
+
+			Only the `elasticsearch`, `http`, and `splunk_hec_logs` sinks, and the `loki` sink when its
+			`out_of_order_action` is `accept`, support this option.
+			"""
+		required: false
+		type: string: examples: ["elasticsearch_cluster"]
+	}
 }
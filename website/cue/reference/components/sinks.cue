@@ -30,6 +30,10 @@ components: sinks: [Name=string]: {
 			}
 		}
 
+		if Name == "elasticsearch" || Name == "http" || Name == "loki" || Name == "splunk_hec_logs" {
+			request_limits: base.components.sinks.configuration.request_limits
+		}
+
 		if !features.auto_generated {
 			if features.acknowledgements {
 				acknowledgements: {
@@ -84,6 +84,28 @@ components: sinks: aws_kinesis_firehose: components._aws & {
 		traces:  false
 	}
 
+	how_it_works: {
+		dynamic_partitioning: {
+			title: "Dynamic partitioning"
+			body:  """
+				Delivery streams with [dynamic partitioning](\(urls.aws_kinesis_firehose_dynamic_partitioning))
+				enabled organize the objects they deliver to S3 by the partition keys of their records. The
+				`dynamic_partitioning.keys` templates are rendered for each event, and embedded into its
+				record under the `dynamic_partitioning.field` object, so that the delivery stream extracts
+				them with inline parsing rather than with a Lambda function.
+
+				For example, with the keys `tenant = "{{ tenant_id }}"` and `service = "{{ service }}"`, the
+				delivery stream is configured with `{tenant: .partition_keys.tenant, service: .partition_keys.service}`
+				as its JQ expression, and an S3 prefix such as
+				`logs/!{partitionKeyFromQuery:tenant}/!{partitionKeyFromQuery:service}/`.
+
+				Inline parsing requires the records to be uncompressed JSON objects, so dynamic partitioning
+				can only be used with the `json` codec and without compression. Events whose keys fail to
+				render are dropped.
+				"""
+		}
+	}
+
 	permissions: iam: [
 		{
 			platform: "aws"
@@ -174,6 +174,30 @@ components: sinks: aws_s3: components._aws & {
 				"""
 		}
 
+		parquet_files: {
+			title: "Parquet files"
+			body:  """
+				With the [`batch_encoding.codec`](#batch_encoding.codec) option set to `parquet`, Vector
+				writes each batch of events as an [Apache Parquet](https://parquet.apache.org/) file,
+				with one row per event, which can be queried directly by engines such as Athena,
+				Trino, or DuckDB. The objects are named with the `parquet` extension, and aren't
+				compressed with [`compression`](#compression): the columns of the files are
+				compressed instead, as configured with
+				[`batch_encoding.parquet.compression`](#batch_encoding.parquet.compression).
+
+				The schema of the files is set with
+				[`batch_encoding.parquet.schema`](#batch_encoding.parquet.schema), or otherwise
+				inferred from the top-level fields of the events of each batch. The files are rolled
+				once the batch reaches the size or the number of events set with
+				[`batch`](#batch), or times out after [`batch.timeout_secs`](#batch.timeout_secs).
+
+				To write the files into the partitions of a table, list the partition columns in
+				[`partition_fields`](#partition_fields): the objects of each combination of their values
+				are then written under Hive-style `column=value/` prefixes, such as
+				`date=2024-03-01/region=us-east-1/`.
+				"""
+		}
+
 		server_side_encryption: {
 			title: "Server-Side Encryption (SSE)"
 			body:  """
@@ -17,6 +17,13 @@ components: sinks: aws_sqs: components._aws & {
 		auto_generated:   true
 		healthcheck: enabled: true
 		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    262144
+				max_events:   1
+				timeout_secs: 1.0
+			}
 			compression: enabled: false
 			encoding: {
 				enabled: true
@@ -76,6 +83,36 @@ components: sinks: aws_sqs: components._aws & {
 		traces:  false
 	}
 
+	how_it_works: {
+		batching: {
+			title: "Batching"
+			body:  """
+				By default, each event is sent as its own message. Setting `batch.max_events` sends up
+				to 10 messages at once with a single `SendMessageBatch` request, whose messages can't
+				add up to more than 256 KiB, the limits of Amazon SQS.
+
+				The messages of a batch can be accepted while others are refused. The events of
+				messages that are refused because of their content are rejected, while the whole
+				batch is retried when Amazon SQS fails to send some of its messages.
+				"""
+		}
+		fifo_queues: {
+			title: "FIFO queues"
+			body:  """
+				Messages sent to [FIFO queues](\(urls.aws_sqs_fifo)) need a `message_group_id`, the
+				messages of a group being received in the order they are sent. Both the
+				`message_group_id` and the `message_deduplication_id` are templates, and events whose
+				IDs fail to render are dropped. Without a `message_deduplication_id`, the queue must
+				have content-based deduplication enabled.
+
+				Batching preserves the order of the messages within a batch. To keep the batches
+				themselves in order, set `request.concurrency` to `1` so that they are sent one at a
+				time. Since a retried batch is sent with the same deduplication IDs, its messages
+				aren't duplicated within the deduplication interval of the queue.
+				"""
+		}
+	}
+
 	permissions: iam: [
 		{
 			platform:  "aws"
@@ -20,11 +20,15 @@ components: sinks: axiom: {
 			batch: {
 				enabled:      true
 				common:       false
-				max_events:   1000
-				max_bytes:    1_048_576
+				max_bytes:    10_000_000
 				timeout_secs: 1.0
 			}
-			compression: enabled: false
+			compression: {
+				enabled: true
+				default: "zstd"
+				algorithms: ["none", "gzip", "zstd"]
+				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
+			}
 			encoding: {
 				enabled: true
 				codec: enabled: false
@@ -47,8 +51,8 @@ components: sinks: axiom: {
 				interface: {
 					socket: {
 						api: {
-							title: "Axiom API"
-							url:   urls.axiom
+							title: "Axiom ingest API"
+							url:   urls.axiom_ingest_api
 						}
 						direction: "outgoing"
 						protocols: ["http"]
@@ -89,5 +93,25 @@ components: sinks: axiom: {
 				2. Once registered, create a new dataset and create an API token for it
 				"""
 		}
+		ingest: {
+			title: "Ingest API"
+			body: """
+				Events are encoded as newline delimited JSON, compressed with zstd by default, and sent
+				to the [ingest API](\(urls.axiom_ingest_api)) of their dataset, authenticated with the
+				API token as a bearer token. The `dataset` option is a template, and events are
+				batched by the dataset it renders to. Axiom reads the time of each event from its
+				`timestamp` field.
+
+				Requests that are rate limited or fail with a server error are retried. Requests
+				refused because of an invalid token, a missing dataset, or a request too large for
+				Axiom are not retried, and their events are rejected. Axiom ingests the accepted
+				events of a batch even when others fail, such as events with too many fields, in
+				which case the error of the first failure is logged and the batch is not retried.
+
+				The health check ingests an empty batch into the dataset, so that an invalid token
+				or a missing dataset is reported on startup. It is skipped when the dataset is
+				templated.
+				"""
+		}
 	}
 }
@@ -122,5 +122,24 @@ components: sinks: azure_blob: {
 				```
 				"""
 		}
+
+		parquet_files: {
+			title: "Parquet files"
+			body:  """
+				With the [`batch_encoding.codec`](#batch_encoding.codec) option set to `parquet`, Vector
+				writes each batch of events as an [Apache Parquet](https://parquet.apache.org/) file,
+				with one row per event, which can be queried directly by engines such as Athena,
+				Trino, or DuckDB. The blobs are named with the `parquet` extension, and aren't
+				compressed with [`compression`](#compression): the columns of the files are
+				compressed instead, as configured with
+				[`batch_encoding.parquet.compression`](#batch_encoding.parquet.compression).
+
+				The schema of the files is set with
+				[`batch_encoding.parquet.schema`](#batch_encoding.parquet.schema), or otherwise
+				inferred from the top-level fields of the events of each batch. The files are rolled
+				once the batch reaches the size or the number of events set with
+				[`batch`](#batch), or times out after [`batch.timeout_secs`](#batch.timeout_secs).
+				"""
+		}
 	}
 }
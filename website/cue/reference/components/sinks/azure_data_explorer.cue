@@ -0,0 +1,124 @@
+package metadata
+
+components: sinks: azure_data_explorer: {
+	title: "Azure Data Explorer"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: ["Azure"]
+		stateful: false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    4_194_304
+				max_events:   null
+				timeout_secs: 30.0
+			}
+			compression: {
+				enabled: true
+				default: "gzip"
+				algorithms: ["none", "gzip"]
+				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
+			}
+			encoding: {
+				enabled: true
+				codec: {
+					enabled: true
+					enum: ["json", "csv"]
+				}
+			}
+			proxy: enabled: true
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        true
+				enabled_by_scheme:      true
+			}
+			to: {
+				service: services.azure_data_explorer
+
+				interface: {
+					socket: {
+						api: {
+							title: "Azure Data Explorer ingestion"
+							url:   urls.azure_data_explorer_ingestion
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "required"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: [
+			"""
+				The principal that the sink authenticates as must have the `Ingestor` role on the
+				databases that events are ingested into.
+				""",
+		]
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.azure_data_explorer.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		authentication: {
+			title: "Authentication"
+			body:  """
+				The sink authenticates with Azure Active Directory. When `auth` is set, it authenticates
+				as the application with its client secret. Otherwise, the credentials are read from the
+				`AZURE_TENANT_ID`, `AZURE_CLIENT_ID` and `AZURE_CLIENT_SECRET` environment variables,
+				or obtained from the managed identity of the host, or from the Azure CLI, in turn.
+				"""
+		}
+
+		ingestion_modes: {
+			title: "Ingestion Modes"
+			body:  """
+				With `queued` ingestion, each batch is uploaded as a blob to the temporary storage of
+				the cluster, and a message describing the blob is posted to one of its ingestion
+				queues. The cluster aggregates the queued data before ingesting it, so events are
+				acknowledged once they are queued, and become available for queries within minutes.
+
+				With [`streaming`](\(urls.azure_data_explorer_streaming)) ingestion, batches are sent
+				directly to the cluster and are available within seconds, but the streaming ingestion
+				policy must be enabled on the database or the table, and batches are limited to 4 MiB.
+				"""
+		}
+
+		mappings: {
+			title: "Formats and Mappings"
+			body:  """
+				Events are encoded as JSON lines with the `json` codec, or as CSV rows with the `csv`
+				codec. The fields of the events are ingested into the columns of the table according to
+				the [ingestion mapping](\(urls.azure_data_explorer_mappings)) named by
+				`mapping_reference`, which must be of the kind of the codec.
+				"""
+		}
+	}
+}
@@ -0,0 +1,123 @@
+package metadata
+
+components: sinks: azure_event_hubs: {
+	title: "Azure Event Hubs"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: ["Azure"]
+		stateful: false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    1_048_576
+				max_events:   1000
+				timeout_secs: 1.0
+			}
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: {
+					enabled: true
+					enum: ["json", "text"]
+				}
+			}
+			proxy: enabled: true
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        true
+				enabled_by_scheme:      false
+			}
+			to: {
+				service: services.azure_event_hubs
+
+				interface: {
+					socket: {
+						api: {
+							title: "Azure Event Hubs REST API"
+							url:   urls.azure_event_hubs_send_batch
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "required"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: [
+			"""
+				The shared access policy of `auth.key_name` must have the `Send` claim, and the
+				principal that the sink authenticates as with Azure Active Directory must be assigned
+				the `Azure Event Hubs Data Sender` role.
+				""",
+		]
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.azure_event_hubs.configuration
+
+	input: {
+		logs: true
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			set:          true
+			summary:      true
+		}
+		traces: false
+	}
+
+	how_it_works: {
+		authentication: {
+			title: "Authentication"
+			body:  """
+				With the `shared_access_key` strategy, each request is authorized with a
+				[shared access signature](\(urls.azure_event_hubs_sas)) of the event hub, signed
+				with the key and valid for one hour.
+
+				With the `client_secret` strategy, the sink authenticates with Azure Active Directory
+				as the application with its client secret. When `auth` is not set, the credentials are
+				read from the `AZURE_TENANT_ID`, `AZURE_CLIENT_ID` and `AZURE_CLIENT_SECRET`
+				environment variables, or obtained from the managed identity of the host, or from the
+				Azure CLI, in turn.
+				"""
+		}
+
+		batching: {
+			title: "Batching and Partitioning"
+			body:  """
+				Events are batched by event hub and partition key, and each batch is sent with the
+				[batch format](\(urls.azure_event_hubs_send_batch)) of the Event Hubs REST API. The
+				body of each event is its encoded form, which must be valid UTF-8, so binary codecs
+				such as `protobuf` and `avro` are not supported.
+
+				Batches are split into requests no larger than `max_request_size`, which must not
+				exceed the maximum size of the messages of the [tier](\(urls.azure_event_hubs_tiers))
+				of the namespace. Events with the same partition key are stored in the same partition,
+				in the order in which they are sent.
+				"""
+		}
+	}
+}
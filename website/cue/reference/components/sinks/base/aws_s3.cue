@@ -236,6 +236,85 @@ base: components: sinks: aws_s3: configuration: {
 			}
 		}
 	}
+	batch_encoding: {
+		description: """
+			Configures encoding all the events of a batch together into a single object.
+
+			When set, this is used instead of the `codec` and `framing` of `encoding`, and objects are
+			not compressed with `compression`, as the batch codecs compress their own data. Objects are
+			written once the batch is full or times out, as configured with `batch`.
+			"""
+		required: false
+		type: object: options: {
+			codec: {
+				description: "The codec to use for encoding batches of events."
+				required:    true
+				type: string: enum: parquet: """
+					Encodes each batch of events as an [Apache Parquet][apache_parquet] file.
+
+					[apache_parquet]: https://parquet.apache.org/
+					"""
+			}
+			parquet: {
+				description:   "Apache Parquet-specific encoder options."
+				relevant_when: "codec = \"parquet\""
+				required:      false
+				type: object: options: {
+					compression: {
+						description: "The compression codec of the column chunks."
+						required:    false
+						type: string: {
+							default: "snappy"
+							enum: {
+								gzip: """
+									[Gzip][gzip] compression.
+
+									[gzip]: https://www.gzip.org/
+									"""
+								none: "No compression."
+								snappy: """
+									[Snappy][snappy] compression.
+
+									[snappy]: https://github.com/google/snappy/blob/main/docs/README.md
+									"""
+								zstd: """
+									[Zstandard][zstd] compression.
+
+									[zstd]: https://facebook.github.io/zstd/
+									"""
+							}
+						}
+					}
+					row_group_size: {
+						description: """
+							The maximum number of rows of a row group.
+
+							Larger row groups compress better and are read faster, at the cost of more memory when
+							writing and reading them.
+							"""
+						required: false
+						type: uint: default: 100000
+					}
+					schema: {
+						description: """
+							The schema of the Parquet files, in the message type format of Parquet.
+
+							The top-level fields of the schema are the columns of the files, and are read from the
+							fields of the events with the same names. Only `required` and `optional` fields of the
+							`boolean`, `int32`, `int64`, `float`, `double`, and `binary` types are supported, with
+							the `DATE` annotation for `int32` fields and the `TIMESTAMP` annotation for `int64` fields.
+
+							If not set, the schema of each file is inferred from the fields of its events: each
+							top-level field is encoded into an `optional` column of the type of its values. Objects,
+							arrays, and fields with values of different types are encoded as strings.
+							"""
+						required: false
+						type: string: examples: ["message log { required binary message (STRING); optional int64 status; optional int64 timestamp (TIMESTAMP(MICROS, true)); }"]
+					}
+				}
+			}
+		}
+	}
 	bucket: {
 		description: """
 			The S3 bucket name.
@@ -321,6 +400,95 @@ base: components: sinks: aws_s3: configuration: {
 					type: string: examples: ["{ \"type\": \"record\", \"name\": \"log\", \"fields\": [{ \"name\": \"message\", \"type\": \"string\" }] }"]
 				}
 			}
+			cef: {
+				description:   "Options for the CEF serializer."
+				relevant_when: "codec = \"cef\""
+				required:      false
+				type: object: options: {
+					device_event_class_id_key: {
+						description: """
+																The field of the log events that holds the ID of the type of the messages, such as a
+																signature ID.
+
+																If the field isn't set, `0` is used.
+																"""
+						required: false
+						type: string: examples: ["event_id"]
+					}
+					device_product: {
+						description: "The product of the device that sends the messages."
+						required:    false
+						type: string: default: "Vector"
+					}
+					device_vendor: {
+						description: "The vendor of the device that sends the messages."
+						required:    false
+						type: string: default: "Vector"
+					}
+					device_version: {
+						description: """
+																The version of the device that sends the messages.
+
+																If not set, the header field is left empty.
+																"""
+						required: false
+						type: string: examples: ["1.0"]
+					}
+					extensions: {
+						description: """
+																The extensions of the messages, mapping their keys to the fields of the log events that
+																hold their values.
+
+																The keys must only contain ASCII letters and digits, such as the keys of the CEF
+																dictionary, like `src` or `suser`. Timestamps are written as milliseconds since the Unix
+																epoch, and the extensions whose field isn't set are left out.
+																"""
+						required: false
+						type: object: {
+							examples: [{
+								msg:   "message"
+								src:   "client.ip"
+								suser: "user.name"
+							}]
+							options: "*": {
+								description: "The field of the log events that holds the value of the extension."
+								required:    true
+								type: string: {}
+							}
+						}
+					}
+					name_key: {
+						description: """
+																The field of the log events that holds the human-readable description of the messages.
+
+																If not set, the message of the events is used.
+																"""
+						required: false
+						type: string: examples: ["title"]
+					}
+					severity_key: {
+						description: """
+																The field of the log events that holds the severity of the messages.
+
+																The field can hold a number from `0` to `10`, or the name of a severity, such as `Low` or
+																`Very-High`. If the field isn't set, or doesn't hold a valid severity, `5` is used.
+																"""
+						required: false
+						type: string: examples: ["severity"]
+					}
+					version: {
+						description: "The version of the CEF format."
+						required:    false
+						type: string: {
+							default: "v0"
+							enum: {
+								v0: "Version 0 of the format, which all CEF consumers read."
+								v1: "Version 1 of the format, which adds the extensions holding IPv6 addresses."
+							}
+						}
+					}
+				}
+			}
 			codec: {
 				description: "The codec to use for encoding events."
 				required:    true
@@ -330,6 +498,14 @@ base: components: sinks: aws_s3: configuration: {
 
 						[apache_avro]: https://avro.apache.org/
 						"""
+					cef: """
+						Encodes an event as a [Common Event Format (CEF)][cef] message.
+
+						The header fields of the message and its extensions are taken from the fields of the
+						event, for security information and event management (SIEM) systems such as ArcSight.
+
+						[cef]: https://www.microfocus.com/documentation/arcsight/arcsight-smartconnectors-8.4/pdfdoc/cef-implementation-standard/cef-implementation-standard.pdf
+						"""
 					csv: """
 						Encodes an event as a CSV message.
 
@@ -363,6 +539,14 @@ base: components: sinks: aws_s3: configuration: {
 
 						[logfmt]: https://brandur.org/logfmt
 						"""
+					msgpack: """
+						Encodes an event as [MessagePack][msgpack].
+
+						Timestamps are encoded with the timestamp extension type, and bytes that aren't valid UTF-8
+						are encoded as binaries.
+
+						[msgpack]: https://msgpack.org/
+						"""
 					native: """
 						Encodes an event in the [native Protocol Buffers format][vector_native_protobuf].
 
@@ -379,6 +563,15 @@ base: components: sinks: aws_s3: configuration: {
 						[vector_native_json]: https://github.com/vectordotdev/vector/blob/master/lib/codecs/tests/data/native_encoding/schema.cue
 						[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
 						"""
+					otlp: """
+						Encodes an event as an [OTLP][otlp] export request encoded as protobuf.
+
+						Logs are encoded as `ExportLogsServiceRequest` messages and metrics as
+						`ExportMetricsServiceRequest` messages, converted the same way as with the `opentelemetry`
+						sink. Traces and sketches are not supported.
+
+						[otlp]: https://opentelemetry.io/docs/specs/otlp/
+						"""
 					protobuf: """
 						Encodes an event as a [Protobuf][protobuf] message.
 
@@ -393,6 +586,16 @@ base: components: sinks: aws_s3: configuration: {
 						transform) and removing the message field while doing additional parsing on it, as this
 						could lead to the encoding emitting empty strings for the given event.
 						"""
+					syslog: """
+						Encodes an event as a [syslog][syslog] message.
+
+						The message of the event is written as the body of the message, after a header in the
+						format of [RFC 5424][rfc5424] or of [RFC 3164][rfc3164] taken from the fields of the event.
+
+						[syslog]: https://en.wikipedia.org/wiki/Syslog
+						[rfc5424]: https://datatracker.ietf.org/doc/html/rfc5424
+						[rfc3164]: https://datatracker.ietf.org/doc/html/rfc3164
+						"""
 					text: """
 						Plain text encoding.
 
@@ -492,6 +695,86 @@ base: components: sinks: aws_s3: configuration: {
 				required:    false
 				type: array: items: type: string: {}
 			}
+			json: {
+				description:   "Options for the JSON serializer."
+				relevant_when: "codec = \"json\""
+				required:      false
+				type: object: options: {
+					canonical: {
+						description: """
+																Encode the events as canonical JSON, as specified by the
+																[JSON Canonicalization Scheme][jcs].
+
+																The keys of all objects are sorted by their UTF-16 code units, no whitespace is written
+																between the tokens, and floating-point numbers are written as set by `float_format`, so that
+																the same event is always encoded to the same bytes, such as for computing content hashes or
+																signatures over the encoded events.
+
+																[jcs]: https://datatracker.ietf.org/doc/html/rfc8785
+																"""
+						required: false
+						type: bool: default: false
+					}
+					except_keys: {
+						description: """
+																The keys of the encoded events that are removed, when encoding canonical JSON.
+
+																Like `only_keys`, this applies to the keys of the encoded JSON objects of all types of
+																events, after `only_keys`.
+																"""
+						required: false
+						type: array: items: type: string: examples: ["password"]
+					}
+					field_order: {
+						description: """
+																The keys of the encoded events that are written first, in this order, when encoding
+																canonical JSON.
+
+																The other keys are written after them, in the canonical order. The keys of nested objects
+																are always written in the canonical order.
+																"""
+						required: false
+						type: array: {
+							default: []
+							items: type: string: examples: ["timestamp", "message"]
+						}
+					}
+					float_format: {
+						description: """
+																The format of the floating-point numbers.
+
+																This is only used when `canonical` is enabled.
+																"""
+						required: false
+						type: string: {
+							default: "ecmascript"
+							enum: {
+								ecmascript: """
+																			The representation of the ECMAScript `Number.prototype.toString` method, such as `100` or
+																			`1e+21`, as required by the [JSON Canonicalization Scheme][jcs].
+
+																			[jcs]: https://datatracker.ietf.org/doc/html/rfc8785
+																			"""
+								shortest: """
+																			The shortest representation that parses back to the same number, always with a fractional
+																			part or an exponent, such as `100.0` or `1e21`, as written by the non-canonical encoding.
+																			"""
+							}
+						}
+					}
+					only_keys: {
+						description: """
+																The keys of the encoded events that are kept, when encoding canonical JSON.
+
+																Unlike `only_fields`, which selects fields of log events before they are encoded, this
+																applies to the keys of the encoded JSON objects of all types of events, such as the `name`
+																or `tags` of metrics.
+																"""
+						required: false
+						type: array: items: type: string: examples: ["message"]
+					}
+				}
+			}
 			metric_tag_values: {
 				description: """
 					Controls how metric tag values are encoded.
@@ -532,6 +815,32 @@ base: components: sinks: aws_s3: configuration: {
 						required: true
 						type: string: examples: ["/etc/vector/protobuf_descriptor_set.desc"]
 					}
+					field_mappings: {
+						description: """
+																Rules moving fields of the events to other paths before they are serialized.
+
+																The fields of the message are set from the fields of the event with the same name, and
+																the fields of nested messages from the fields of nested objects. Event fields at other
+																paths can be moved to the paths of the message fields they are serialized as. The rules are
+																applied together, so fields can be swapped.
+																"""
+						required: false
+						type: array: {
+							default: []
+							items: type: object: options: {
+								from: {
+									description: "The path of the field in the event."
+									required:    true
+									type: string: examples: ["user.name"]
+								}
+								to: {
+									description: "The path of the field in the message."
+									required:    true
+									type: string: examples: ["username"]
+								}
+							}
+						}
+					}
 					message_type: {
 						description: "The name of the message type to use for serializing."
 						required:    true
@@ -539,6 +848,97 @@ base: components: sinks: aws_s3: configuration: {
 					}
 				}
 			}
+			syslog: {
+				description:   "Options for the syslog serializer."
+				relevant_when: "codec = \"syslog\""
+				required:      false
+				type: object: options: {
+					app_name_key: {
+						description: """
+																The field of the log events that holds the name of the application that the messages
+																come from.
+
+																This is written as the `APP-NAME` of RFC 5424 messages, and as the `TAG` of RFC 3164
+																messages. If the field isn't set, `vector` is used.
+																"""
+						required: false
+						type: string: examples: ["appname"]
+					}
+					facility_key: {
+						description: """
+																The field of the log events that holds the facility of the messages.
+
+																The field can hold the name of a facility, such as `local0`, or its numerical code, such as
+																`16`. If the field isn't set, or doesn't hold a valid facility, `user` is used.
+																"""
+						required: false
+						type: string: examples: ["facility"]
+					}
+					hostname_key: {
+						description: """
+																The field of the log events that holds the hostname of the messages.
+
+																If not set, the host of the events is used.
+																"""
+						required: false
+						type: string: examples: ["hostname"]
+					}
+					msg_id_key: {
+						description: """
+																The field of the log events that holds the type of the messages.
+
+																This is only written to RFC 5424 messages.
+																"""
+						required: false
+						type: string: examples: ["msgid"]
+					}
+					proc_id_key: {
+						description: "The field of the log events that holds the ID of the process that the messages come from."
+						required:    false
+						type: string: examples: ["procid"]
+					}
+					rfc: {
+						description: "The format of the syslog messages."
+						required:    false
+						type: string: {
+							default: "rfc5424"
+							enum: {
+								rfc3164: """
+																			The legacy BSD format of [RFC 3164][rfc3164].
+
+																			[rfc3164]: https://datatracker.ietf.org/doc/html/rfc3164
+																			"""
+								rfc5424: """
+																			The format of [RFC 5424][rfc5424].
+
+																			[rfc5424]: https://datatracker.ietf.org/doc/html/rfc5424
+																			"""
+							}
+						}
+					}
+					severity_key: {
+						description: """
+																The field of the log events that holds the severity of the messages.
+
+																The field can hold the name of a severity, such as `err` or `error`, or its numerical code,
+																such as `3`. If the field isn't set, or doesn't hold a valid severity, `info` is used.
+																"""
+						required: false
+						type: string: examples: ["severity"]
+					}
+					structured_data_key: {
+						description: """
+																The field of the log events that holds the structured data of the messages.
+
+																The field must be an object of structured data elements, keyed by their ID, that are each
+																an object of parameters. Arrays of values are written as repeated parameters. This is only
+																written to RFC 5424 messages.
+																"""
+						required: false
+						type: string: examples: ["structured_data"]
+					}
+				}
+			}
 			timestamp_format: {
 				description: "Format used for timestamp fields."
 				required:    false
@@ -658,6 +1058,27 @@ base: components: sinks: aws_s3: configuration: {
 						The prefix is a 32-bit unsigned integer, little endian.
 						"""
 					newline_delimited: "Event data is delimited by a newline (LF) character."
+					varint_length_delimited: """
+						Event data is prefixed with its length in bytes as an unsigned [varint][varint].
+
+						This is the framing of streams of Protocol Buffers messages, as read by
+						`parseDelimitedFrom` in Java or `ParseDelimitedFromZeroCopyStream` in C++.
+
+						[varint]: https://protobuf.dev/programming-guides/encoding/#varints
+						"""
+				}
+			}
+			varint_length_delimited: {
+				description:   "Options for the varint length delimited encoder."
+				relevant_when: "method = \"varint_length_delimited\""
+				required:      false
+				type: object: options: max_frame_length: {
+					description: "Maximum frame length"
+					required:    false
+					type: uint: {
+						default: 8388608
+						unit:    "bytes"
+					}
 				}
 			}
 		}
@@ -722,6 +1143,25 @@ base: components: sinks: aws_s3: configuration: {
 			syntax: "template"
 		}
 	}
+	partition_fields: {
+		description: """
+			Fields of the events to partition objects by, in the style of Hive.
+
+			For each field, in order, a `field=value/` partition is appended to the key prefix, which is
+			separated from the partitions by a `/` if it doesn't end with one. Values are escaped as Hive
+			escapes partition paths, and events without a value for a field are written to the
+			`__HIVE_DEFAULT_PARTITION__` partition of that field.
+
+			Partitioned tables of query engines such as Athena, Hive, and Spark can then read the
+			objects directly, usually with the partition fields excluded from the objects with the
+			`except_fields` option of `encoding`.
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: string: examples: ["region", "service"]
+		}
+	}
 	region: {
 		description: """
 			The [AWS region][aws_region] of the target service.
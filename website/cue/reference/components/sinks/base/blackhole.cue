@@ -27,6 +27,93 @@ base: components: sinks: blackhole: configuration: {
 			type: bool: {}
 		}
 	}
+	errors: {
+		description: """
+			Errors injected into the acknowledgement of batches.
+
+			This can be used to validate how the sources and the topology upstream handle failed
+			deliveries.
+			"""
+		required: false
+		type: object: options: {
+			rate: {
+				description: "The ratio of batches that fail, between `0` and `1`."
+				required:    true
+				type: float: examples: [0.01]
+			}
+			status: {
+				description: "The status that failed batches are acknowledged with."
+				required:    false
+				type: string: {
+					default: "errored"
+					enum: {
+						errored:  "The batches fail with a transient error, and sources that support it can retry them."
+						rejected: "The batches are rejected, as a permanent failure, and their events are dropped."
+					}
+				}
+			}
+		}
+	}
+	latency: {
+		description: """
+			The artificial latency applied before acknowledging each batch of events.
+
+			The latency is sampled for each batch, and the batches are acknowledged concurrently, as a
+			downstream system would with pipelined requests.
+			"""
+		required: false
+		type: object: options: {
+			distribution: {
+				description: "The distribution that the latency is sampled from."
+				required:    true
+				type: string: enum: {
+					constant: "Every batch is acknowledged after the same latency."
+					exponential: """
+						The latency is sampled from an exponential distribution.
+
+						This models a system whose requests mostly complete quickly, with a long tail of slow
+						requests.
+						"""
+					normal: """
+						The latency is sampled from a normal distribution.
+
+						Negative samples are clamped to no latency.
+						"""
+					uniform: "The latency is sampled uniformly between a minimum and a maximum."
+				}
+			}
+			latency_ms: {
+				description:   "The latency, in milliseconds."
+				relevant_when: "distribution = \"constant\""
+				required:      true
+				type: uint: examples: [50]
+			}
+			max_ms: {
+				description:   "The maximum latency, in milliseconds."
+				relevant_when: "distribution = \"uniform\""
+				required:      true
+				type: uint: examples: [100]
+			}
+			mean_ms: {
+				description:   "The mean latency, in milliseconds."
+				relevant_when: "distribution = \"normal\" or distribution = \"exponential\""
+				required:      true
+				type: float: examples: [50.0]
+			}
+			min_ms: {
+				description:   "The minimum latency, in milliseconds."
+				relevant_when: "distribution = \"uniform\""
+				required:      true
+				type: uint: examples: [10]
+			}
+			std_dev_ms: {
+				description:   "The standard deviation of the latency, in milliseconds."
+				relevant_when: "distribution = \"normal\""
+				required:      true
+				type: float: examples: [10.0]
+			}
+		}
+	}
 	print_interval_secs: {
 		description: """
 			The interval between reporting a summary of activity.
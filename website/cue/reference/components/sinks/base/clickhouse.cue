@@ -27,6 +27,15 @@ base: components: sinks: clickhouse: configuration: {
 			type: bool: {}
 		}
 	}
+	async_insert: {
+		description: """
+			Sets `async_insert`, allowing ClickHouse to buffer inserted rows and write them to the table in larger parts.
+
+			Also sets `wait_for_async_insert`, so that events are only acknowledged once written to the table.
+			"""
+		required: false
+		type: bool: default: false
+	}
 	auth: {
 		description: """
 			Configuration of the authentication strategy for HTTP requests.
@@ -182,9 +191,14 @@ base: components: sinks: clickhouse: configuration: {
 		}
 	}
 	endpoint: {
-		description: "The endpoint of the ClickHouse server."
-		required:    true
-		type: string: examples: ["http://localhost:8123"]
+		description: """
+			The endpoint of the ClickHouse server.
+
+			With the native protocol, this is the address of its TCP port, which defaults to `9000`.
+			The `tls` or `https` scheme connects with TLS, to the default port `9440`.
+			"""
+		required: true
+		type: string: examples: ["http://localhost:8123", "tcp://localhost:9000"]
 	}
 	format: {
 		description: """
@@ -207,6 +221,51 @@ base: components: sinks: clickhouse: configuration: {
 		required:    false
 		type: bool: default: false
 	}
+	native: {
+		description: "Options for the native protocol."
+		required:    false
+		type: object: options: compression: {
+			description: """
+				The compression of the blocks of rows.
+
+				The `compression` option only applies to the HTTP interface.
+				"""
+			required: false
+			type: string: {
+				default: "lz4"
+				enum: {
+					lz4: """
+						[LZ4][lz4] compression.
+
+						[lz4]: https://lz4.org/
+						"""
+					none: "No compression."
+				}
+			}
+		}
+	}
+	protocol: {
+		description: "The protocol used to insert data into ClickHouse."
+		required:    false
+		type: string: {
+			default: "http"
+			enum: {
+				http: """
+					The [HTTP interface][http], with rows encoded in the configured `format`.
+
+					[http]: https://clickhouse.com/docs/en/interfaces/http
+					"""
+				native: """
+					The [native protocol][native] over TCP, with rows encoded into columnar blocks of the types
+					of the columns of the table.
+
+					Requires ClickHouse 20.1 or later.
+
+					[native]: https://clickhouse.com/docs/en/interfaces/tcp
+					"""
+			}
+		}
+	}
 	request: {
 		description: """
 			Middleware settings for outbound requests.
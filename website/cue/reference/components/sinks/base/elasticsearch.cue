@@ -328,6 +328,17 @@ base: components: sinks: elasticsearch: configuration: {
 		description: "Elasticsearch data stream mode configuration."
 		required:    false
 		type: object: options: {
+			auto_create: {
+				description: """
+					Whether or not bulk requests can create the data streams that don't exist yet.
+
+					Elasticsearch creates a data stream when it receives a document for a data stream that
+					doesn't exist and matches an index template with data streams enabled, but otherwise
+					creates a regular index. If disabled, such documents are rejected instead.
+					"""
+				required: false
+				type: bool: default: true
+			}
 			auto_routing: {
 				description: """
 					Automatically routes events by deriving the data stream name using specific event fields.
@@ -550,6 +561,26 @@ base: components: sinks: elasticsearch: configuration: {
 			}
 		}
 	}
+	opensearch_service_type: {
+		description: """
+			The type of Amazon OpenSearch service that the endpoints belong to.
+
+			An OpenSearch Serverless collection requires the `aws` authentication strategy.
+			"""
+		required: false
+		type: string: {
+			default: "managed"
+			enum: {
+				managed: "Elasticsearch or an Amazon OpenSearch Service managed domain."
+				serverless: """
+					An Amazon OpenSearch Serverless collection.
+
+					Requests are signed for the `aoss` service, and the API version is not probed, as
+					OpenSearch Serverless doesn't expose it.
+					"""
+			}
+		}
+	}
 	pipeline: {
 		description: "The name of the pipeline to apply."
 		required:    false
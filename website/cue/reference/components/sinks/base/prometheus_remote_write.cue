@@ -295,6 +295,21 @@ base: components: sinks: prometheus_remote_write: configuration: {
 		required: true
 		type: string: examples: ["https://localhost:8087/api/v1/write"]
 	}
+	native_histograms: {
+		description: """
+			Whether or not to send distributions and sketches as [native histograms][native_histograms].
+
+			Native histograms keep the full resolution of distributions and sketches, rather than
+			aggregating them with `buckets` or `quantiles`, but the endpoint must support them, as
+			Prometheus does with the `native-histograms` feature enabled. Histograms with explicit
+			buckets are still sent as classic histograms, unless they were received as native
+			histograms by the `prometheus_remote_write` source, which are sent as they were received.
+
+			[native_histograms]: https://prometheus.io/docs/concepts/metric_types/#histogram
+			"""
+		required: false
+		type: bool: default: false
+	}
 	quantiles: {
 		description: """
 			Quantiles to use for aggregating [distribution][dist_metric_docs] metrics into a summary.
@@ -32,6 +32,28 @@ components: sinks: blackhole: {
 
 	configuration: base.components.sinks.blackhole.configuration
 
+	how_it_works: {
+		load_testing: {
+			title: "Load testing"
+			body:  """
+				The `blackhole` sink can stand in for a downstream system when benchmarking a topology.
+				With the `latency` option, the acknowledgement of each batch is delayed by a latency
+				sampled from a distribution, and with the `errors` option, a ratio of the batches fail
+				instead of being delivered. Together with end-to-end acknowledgements, this can be used to
+				validate how sources handle slow and failed deliveries, without an external system.
+
+				The sink reports the number of events of each batch it receives, and the time elapsed
+				since the timestamp of each event, as the `blackhole_batch_size_events` and
+				`blackhole_event_latency_seconds` histograms.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		blackhole_batch_size_events:     components.sources.internal_metrics.output.metrics.blackhole_batch_size_events
+		blackhole_event_latency_seconds: components.sources.internal_metrics.output.metrics.blackhole_event_latency_seconds
+	}
+
 	input: {
 		logs: true
 		metrics: {
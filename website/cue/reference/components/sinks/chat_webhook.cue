@@ -0,0 +1,109 @@
+package metadata
+
+components: sinks: chat_webhook: {
+	title: "Chat Webhook"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: ["Discord", "Microsoft", "Slack"]
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		auto_generated:   true
+		healthcheck: enabled: false
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    null
+				max_events:   20
+				timeout_secs: 5.0
+			}
+			compression: enabled: false
+			encoding: enabled:    false
+			proxy: enabled:       true
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+				enabled_by_scheme:      true
+			}
+			to: {
+				service: services.chat_webhook
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "required"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.chat_webhook.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		digests: {
+			title: "Digest messages"
+			body: """
+				Events are batched by the `url` and the `title` rendered for them, and each batch is
+				sent as a single digest message, which holds the `message` line rendered for each of
+				its events. Events that the URL can't be rendered for are dropped, and events that the
+				message can't be rendered for are left out of their digest.
+
+				To only send the events that should be posted to a channel, connect the sink to a
+				`filter` or a `route` transform.
+				"""
+		}
+		payloads: {
+			title: "Payloads"
+			body: """
+				Messages are sent in the payload format of the `platform` of the webhooks:
+
+				* [Slack](\(urls.slack_incoming_webhooks)) messages are sent as text, with the title in
+				  bold. The `&`, `<`, and `>` characters are escaped, so that the lines of events aren't
+				  taken as links or mentions. The text is truncated to 40,000 characters.
+				* [Microsoft Teams](\(urls.teams_incoming_webhooks)) messages are sent as Adaptive
+				  Cards, with the title and each line in their own text block, which is accepted by both
+				  the incoming webhooks of Office 365 connectors and the webhooks of Workflows.
+				* [Discord](\(urls.discord_webhooks)) messages are sent as an embed, whose title and
+				  description are truncated to 256 and 4,096 characters, and whose mentions don't
+				  notify anyone.
+				"""
+		}
+		rate_limiting: {
+			title: "Rate limiting"
+			body: """
+				Chat platforms limit the number of messages accepted by each webhook, so the messages
+				sent to each webhook are delayed until they're within the `channel_rate_limit` of
+				the sink, which allows one message per second by default. The messages that are delayed
+				keep their place in the requests in flight, and the `request` options still apply to
+				all the requests of the sink.
+				"""
+		}
+	}
+}
@@ -80,4 +80,34 @@ components: sinks: clickhouse: {
 		metrics: null
 		traces:  false
 	}
+
+	how_it_works: {
+		native_protocol: {
+			title: "Native protocol"
+			body: """
+				With `protocol` set to `native`, events are inserted over the native TCP protocol of
+				ClickHouse rather than its HTTP interface. ClickHouse responds to each insert with the
+				columns of the table, and the events of a batch are encoded column by column into a
+				block of the types of these columns, compressed with LZ4 unless `native.compression`
+				is `none`. This takes less CPU on both ends, and less bandwidth, than encoding and
+				parsing rows of JSON.
+
+				Columns are filled with the fields of the events named after them, and with the
+				nested fields of the events when they are named like `kubernetes.pod_name`. Fields
+				without a column are discarded, and columns without a field are set to the default
+				value of their type, not to their `DEFAULT` expression. Timestamps are inserted into
+				`DateTime` and `DateTime64` columns, as are RFC 3339 strings and Unix timestamps in
+				seconds. The `format`, `skip_unknown_fields`, and `date_time_best_effort` options only
+				apply to the HTTP interface.
+
+				The columns of the integer, floating point, `Bool`, `String`, `FixedString`, `UUID`,
+				`Date`, `Date32`, `DateTime`, `DateTime64`, `IPv4`, `IPv6`, `Enum8`, and `Enum16` types
+				are supported, along with the `Nullable`, `LowCardinality`, `Array`, and `Map` of these
+				types.
+
+				A batch that cannot be encoded into the types of the columns, or that ClickHouse
+				rejects as incorrect data, is not retried.
+				"""
+		}
+	}
 }
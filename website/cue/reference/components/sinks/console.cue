@@ -43,6 +43,23 @@ components: sinks: console: {
 
 	configuration: base.components.sinks.console.configuration
 
+	how_it_works: {
+		display: {
+			title: "Displaying events"
+			body:  """
+				When reading events in a terminal, the `display` option renders them for humans instead of
+				encoding them. In `pretty` mode, the timestamp, level, and message of each log event are
+				displayed on one line, with the level colored by its severity, and its other fields are
+				displayed below it. In `table` mode, the fields listed in `display.fields` are displayed as
+				fixed-width columns, with a header row at the start of the output.
+
+				By default, the output is colorized when the target is a terminal. It can be colorized
+				when piped, such as into `less -R`, with `display.color` set to `always`. Metric events
+				are displayed in a format similar to the Prometheus text format in both modes.
+				"""
+		}
+	}
+
 	input: {
 		logs: true
 		metrics: {
@@ -0,0 +1,127 @@
+package metadata
+
+components: sinks: doris: {
+	title: "Apache Doris"
+
+	classes: {
+		commonly_used: false
+		delivery:      "exactly_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    10_000_000
+				timeout_secs: 1.0
+			}
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: enabled: true
+			}
+			proxy: enabled: true
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+				enabled_by_scheme:      true
+			}
+			to: {
+				service: services.doris
+
+				interface: {
+					socket: {
+						api: {
+							title: "Stream Load"
+							url:   urls.doris_stream_load
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.doris.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		labels: {
+			title: "Labels and exactly-once loads"
+			body: """
+				Each batch is loaded with a Stream Load labelled with `label_prefix` and a random
+				UUID. The label is kept when the load of the batch is retried, and Doris refuses to
+				load a label twice into the same database, so a batch whose load succeeded without
+				its response reaching Vector is not loaded again. Such a retry is reported by Doris
+				as `Label Already Exists`, which is treated as a success once the previous load has
+				finished, and retried while it is still running.
+
+				Loads that Doris fails, such as those with too many rows that don't match the
+				columns of the table, are not retried, and their events are rejected. The error
+				logged for them includes the URL of the rows that were refused.
+				"""
+		}
+		routing: {
+			title: "Routing events to tables"
+			body: """
+				The `database` and `table` options are templates, and events are batched by the
+				table they are rendered to, so that each batch is loaded into a single table. Events
+				for which either template fails to render are dropped.
+				"""
+		}
+		formats: {
+			title: "Formats"
+			body: """
+				With the `json` codec, the events of a batch are loaded as a JSON array, whose objects
+				are loaded into the columns named after their fields. With the `csv` codec, the
+				events are loaded as rows of the configured fields, in the order of the columns of
+				the table unless the `columns` header is set. The headers that describe the format,
+				such as `format` and `column_separator`, are set by Vector, and can be overridden with
+				the `headers` option like any other Stream Load parameter.
+				"""
+		}
+		redirects: {
+			title: "Frontends and backends"
+			body: """
+				Stream Loads are sent to the frontend of the `endpoint` option, which redirects them
+				to one of the backends. Vector follows these redirects, so the backends must be
+				reachable at the addresses that the frontend advertises for them.
+				"""
+		}
+		starrocks: {
+			title: "StarRocks"
+			body: """
+				[StarRocks](\(urls.starrocks)) implements the same Stream Load API as Doris, and can be
+				loaded into with this sink by setting `endpoint` to the HTTP address of one of its
+				frontends.
+				"""
+		}
+	}
+}
@@ -101,6 +101,11 @@ components: sinks: elasticsearch: {
 				To use [Data streams](\(urls.elasticsearch_data_streams)), set the `mode` to
 				`data_stream`. Use the combination of `data_stream.type`, `data_stream.dataset` and
 				`data_stream.namespace` instead of `index`.
+
+				Elasticsearch creates a data stream that doesn't exist yet if an index template with
+				data streams enabled matches its name, and otherwise creates a regular index. To reject
+				the events of data streams that don't exist instead, set `data_stream.auto_create` to
+				`false`.
 				"""
 		}
 
@@ -119,6 +124,20 @@ components: sinks: elasticsearch: {
 				"""
 		}
 
+		opensearch_serverless: {
+			title: "Amazon OpenSearch Serverless"
+			body:  """
+				To send events to an Amazon OpenSearch Serverless collection, set
+				`opensearch_service_type` to `serverless` and use the `aws` authentication strategy.
+				Requests are then signed for the `aoss` service instead of `es`.
+
+				OpenSearch Serverless doesn't expose its version nor the cluster health API, so Vector
+				uses the Elasticsearch 8.x API without probing the version, and skips the healthcheck.
+				Ingest pipelines aren't supported by OpenSearch Serverless, so the `pipeline` option
+				can't be used with it.
+				"""
+		}
+
 		partial_failures: {
 			title: "Partial Failures"
 			body:  """
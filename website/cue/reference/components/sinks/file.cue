@@ -73,5 +73,20 @@ components: sinks: file: {
 				disk before acknowledging the events.
 				"""
 		}
+
+		rotation: {
+			title: "File Rotation"
+			body: """
+				Vector can rotate the files it writes without coordinating with external tools
+				such as `logrotate`. When a file reaches the size set by `rotation.max_bytes` or
+				the age set by `rotation.max_age_secs`, it is renamed by appending the time of the
+				rotation to its path, such as `/var/log/vector.log.20240101T120000000`, and the
+				events written afterwards go to a new file.
+
+				Rotated files are then compressed with `rotation.compression`, and the oldest
+				rotated files of the path are removed once there are more than
+				`rotation.keep_files` of them, or once they are older than `rotation.keep_days`.
+				"""
+		}
 	}
 }
@@ -0,0 +1,136 @@
+package metadata
+
+components: sinks: gcp_bigquery: {
+	title: "GCP BigQuery"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: ["GCP"]
+		stateful: false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    9_000_000
+				max_events:   null
+				timeout_secs: 1.0
+			}
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: enabled: false
+			}
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        true
+				enabled_by_scheme:      true
+			}
+			to: {
+				service: services.gcp_bigquery
+
+				interface: {
+					socket: {
+						api: {
+							title: "BigQuery Storage Write API"
+							url:   urls.gcp_bigquery_write_api
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "required"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: [
+			"""
+				The table must exist before events are written to it, the sink doesn't create or alter
+				tables.
+				""",
+		]
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.gcp_bigquery.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		schema: {
+			title: "Row Schema"
+			body:  """
+				Each log event is serialized into a row of the Protobuf message type configured with
+				`schema.message_type`, from the descriptor set in `schema.desc_file`. The fields of the
+				message are matched to the columns of the table by name, and the descriptor of the
+				message type is sent along with the rows of each request, so the message type must be
+				self-contained: the messages it refers to must be nested inside of it.
+
+				Events which can't be serialized into the message type are rejected, without failing
+				the other events of their batch.
+				"""
+		}
+
+		write_modes: {
+			title: "Write Modes"
+			body:  """
+				In the `committed` write mode, rows are appended to the default stream of their table,
+				and are available for queries as soon as BigQuery acknowledges them.
+
+				In the `pending` write mode, the rows of each batch are appended to a new pending
+				stream, which is finalized and committed once all of them are appended. The rows of a
+				batch then become available all at once, and none of them are written when the batch
+				fails, at the cost of three more requests per batch.
+				"""
+		}
+
+		partitioning: {
+			title: "Partitioned Tables"
+			body:  """
+				The `table` option is a template, so that events can be routed to the tables of
+				date-sharded tables, such as `logs_{{ %Y%m%d }}`, or to a partition of a partitioned
+				table with a partition decorator, such as `logs${{ %Y%m%d }}`. The healthcheck is only
+				run when the table isn't templated.
+				"""
+		}
+	}
+
+	permissions: iam: [
+		{
+			platform: "gcp"
+			_service: "bigquery"
+
+			policies: [
+				{
+					_action: "tables.get"
+					required_for: ["healthcheck"]
+				},
+				{
+					_action: "tables.updateData"
+					required_for: ["operation"]
+				},
+			]
+		},
+	]
+}
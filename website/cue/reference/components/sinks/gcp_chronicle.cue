@@ -0,0 +1,104 @@
+package metadata
+
+components: sinks: gcp_chronicle: {
+	title: "GCP Chronicle"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: ["GCP"]
+		stateful: false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    10_000_000
+				timeout_secs: 300.0
+			}
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: {
+					enabled: true
+					framing: true
+					enum: ["json", "text"]
+				}
+			}
+			proxy: enabled: true
+			request: {
+				enabled:        true
+				rate_limit_num: 1000
+				headers:        false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        true
+				enabled_by_scheme:      true
+			}
+			to: {
+				service: services.gcp_chronicle
+
+				interface: {
+					socket: {
+						api: {
+							title: "Chronicle Ingestion API"
+							url:   urls.gcp_chronicle_ingestion_api
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "required"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.gcp_chronicle.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		event_types: {
+			title: "Event Types"
+			body:  """
+				With the `unstructured` event type, events are encoded with the configured codec and sent
+				as unstructured log entries of `log_type`, with their timestamps. Chronicle parses the
+				entries into UDM events with the default parser of their log type.
+
+				With the `udm` event type, events are expected to be already normalized to the
+				[Unified Data Model](\(urls.gcp_chronicle_udm)), for instance with a `remap` transform,
+				and are sent as they are encoded by the `json` codec. Events which aren't encoded to
+				JSON objects are dropped.
+				"""
+		}
+
+		regions: {
+			title: "Regions"
+			body:  """
+				Chronicle instances are hosted in a region, whose ingestion endpoint is selected with
+				`region`. Alternatively, `endpoint` sets the URL of the ingestion API directly, for
+				instance to reach a regional endpoint not listed in `region`.
+				"""
+		}
+	}
+}
@@ -83,6 +83,37 @@ components: sinks: gcp_cloud_storage: {
 	}
 
 	how_it_works: {
+		encryption: {
+			title: "Encryption"
+			body:  """
+				Objects are encrypted at rest by Cloud Storage. To encrypt them with a
+				[customer-managed encryption key](\(urls.gcs_cmek)) rather than with the default key of
+				the bucket, set the [`kms_key_name`](#kms_key_name) option to the resource name of a
+				Cloud KMS key. The Cloud Storage service agent of the project must be granted the
+				`roles/cloudkms.cryptoKeyEncrypterDecrypter` role on the key.
+				"""
+		}
+
+		holds_and_retention: {
+			title: "Holds and retention"
+			body:  """
+				For buckets that archive regulated data, Vector can place
+				[holds](\(urls.gcs_object_holds)) and [retention](\(urls.gcs_object_retention)) on
+				the objects it creates, so that they can't be deleted or replaced:
+
+				* [`temporary_hold`](#temporary_hold) and [`event_based_hold`](#event_based_hold)
+				  place holds that last until they are released.
+				* [`retention`](#retention) retains the objects for
+				  [`retention.retain_for_secs`](#retention.retain_for_secs) from their creation, and
+				  requires the bucket to have Object Retention Lock enabled.
+
+				These settings can't be set while uploading the objects, so Vector sets them right
+				after each object is created, which requires the `storage.objects.update` and
+				`storage.objects.setRetention` permissions. If this fails, the upload is retried or
+				its events are rejected according to the response.
+				"""
+		}
+
 		object_access_control_list: {
 			title: "Object access control list (ACL)"
 			body:  """
@@ -141,6 +172,25 @@ components: sinks: gcp_cloud_storage: {
 				"""
 		}
 
+		parquet_files: {
+			title: "Parquet files"
+			body:  """
+				With the [`batch_encoding.codec`](#batch_encoding.codec) option set to `parquet`, Vector
+				writes each batch of events as an [Apache Parquet](https://parquet.apache.org/) file,
+				with one row per event, which can be queried directly by engines such as Athena,
+				Trino, or DuckDB. The objects are named with the `parquet` extension, and aren't
+				compressed with [`compression`](#compression): the columns of the files are
+				compressed instead, as configured with
+				[`batch_encoding.parquet.compression`](#batch_encoding.parquet.compression).
+
+				The schema of the files is set with
+				[`batch_encoding.parquet.schema`](#batch_encoding.parquet.schema), or otherwise
+				inferred from the top-level fields of the events of each batch. The files are rolled
+				once the batch reaches the size or the number of events set with
+				[`batch`](#batch), or times out after [`batch.timeout_secs`](#batch.timeout_secs).
+				"""
+		}
+
 		storage_class: {
 			title: "Storage Class"
 			body:  """
@@ -158,6 +208,10 @@ components: sinks: gcp_cloud_storage: {
 					Vector supports adding [custom metadata](\(urls.gcs_custom_metadata)) to
 					created objects. These metadata items are a way of associating extra
 					data items with the object that are not part of the uploaded data.
+
+					The values of the `metadata` option can be templates, such as
+					`{{ tenant_id }}`, in which case events are batched into separate objects
+					by the values rendered from them.
 					"""
 		}
 	}
@@ -176,6 +230,14 @@ components: sinks: gcp_cloud_storage: {
 					_action: "objects.get"
 					required_for: ["healthcheck"]
 				},
+				{
+					_action:       "objects.update"
+					required_when: "[`temporary_hold`](#temporary_hold) or [`event_based_hold`](#event_based_hold) is set to `true`"
+				},
+				{
+					_action:       "objects.setRetention"
+					required_when: "[`retention`](#retention) is set"
+				},
 			]
 		},
 	]
@@ -91,4 +91,21 @@ components: sinks: http: {
 		}
 		traces: true
 	}
+
+	how_it_works: {
+		templated_requests: {
+			title: "Templated requests"
+			body: """
+				The `uri`, the `method`, and the values of the `request.headers` can reference event
+				fields with the template syntax, such as `https://{{ tenant }}.example.com/logs`. Events
+				are then batched by the URI, method, and headers rendered for them, so that a single
+				sink can send the events of each tenant to its own endpoint. Events that the templates
+				can't be rendered for are dropped, as are the requests whose rendered URI, method, or
+				header values are invalid.
+
+				The `uri` and header values that don't reference event fields are used as is, so any
+				percent-encoded characters in them are kept.
+				"""
+		}
+	}
 }
@@ -0,0 +1,125 @@
+package metadata
+
+components: sinks: influxdb3: {
+	title: "InfluxDB 3.x"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: ["InfluxData"]
+		stateful: false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    1_000_000
+				timeout_secs: 1.0
+			}
+			compression: {
+				enabled: true
+				default: "gzip"
+				algorithms: ["none", "gzip"]
+				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
+			}
+			encoding: {
+				enabled: true
+				codec: enabled: false
+			}
+			proxy: sinks._influxdb.features.send.proxy
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: sinks._influxdb.features.send.tls
+			to: {
+				service: services.influxdb
+
+				interface: {
+					socket: {
+						api: {
+							title: "InfluxDB 3.x write API"
+							url:   urls.influxdb3_write_api
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.influxdb3.configuration
+
+	input: {
+		logs: true
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			set:          true
+			summary:      true
+		}
+		traces: false
+	}
+
+	how_it_works: {
+		write_api: {
+			title: "Write API"
+			body: """
+				InfluxDB 3.x, such as InfluxDB Cloud Dedicated and InfluxDB Clustered, serves queries over
+				Arrow Flight, but only accepts writes over HTTP. This sink writes events in the
+				[line protocol](\(urls.influxdb_line_protocol)) to the `/api/v2/write` endpoint, with the
+				database as the bucket, and authenticates with the database token as a bearer token.
+				"""
+		}
+
+		database_routing: {
+			title: "Routing events to databases"
+			body: """
+				The `database` option is a template, rendered for each event. Events are batched by
+				database, so that, for instance, the events of each tenant can be written to their own
+				database with `database = "{{ tenant }}-metrics"`. Events for which the template can't be
+				rendered are dropped.
+				"""
+		}
+
+		mapping: {
+			title: "Mapping events to line protocol"
+			body: """
+				Log events are written to the configured `measurement`, the same way as by the
+				`influxdb_logs` sink:
+
+				| Field         | Line Protocol     |
+				|---------------|-------------------|
+				| host          | tag               |
+				| message       | field             |
+				| source_type   | tag               |
+				| timestamp     | timestamp         |
+				| [custom-key]  | field             |
+
+				Fields listed in `tags` are written as tags instead, and nested fields are written with
+				their full path as their name, such as `request.path`. Each line also has a
+				`metric_type=logs` tag.
+
+				Metric events are written the same way as by the `influxdb_metrics` sink, to a measurement
+				named after their namespace and name, with their tags and a `metric_type` tag.
+				"""
+		}
+	}
+}
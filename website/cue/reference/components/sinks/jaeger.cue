@@ -0,0 +1,99 @@
+package metadata
+
+components: sinks: jaeger: {
+	title: "Jaeger"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    4_000_000
+				max_events:   null
+				timeout_secs: 1.0
+			}
+			compression: enabled: false
+			encoding: enabled:    false
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+				enabled_by_scheme:      true
+			}
+			to: {
+				service: services.jaeger
+
+				interface: {
+					socket: {
+						api: {
+							title: "Jaeger APIs"
+							url:   urls.jaeger_apis
+						}
+						direction: "outgoing"
+						protocols: ["http", "udp"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.jaeger.configuration
+
+	input: {
+		logs:    false
+		metrics: null
+		traces:  true
+	}
+
+	how_it_works: {
+		spans: {
+			title: "Spans"
+			body:  """
+				Trace events are read as the OTLP spans received by the `opentelemetry` source, and
+				converted into Jaeger spans following the
+				[translation of OpenTelemetry spans to Jaeger](\(urls.jaeger_opentelemetry)). The
+				`service.name` attribute of the resource of the spans names their process, and the
+				other attributes of the resource are set as the tags of the process.
+
+				The kind, instrumentation scope, and status of the spans are set as tags, and their
+				events as logs. Array and map attributes are rendered as JSON strings.
+				"""
+		}
+
+		modes: {
+			title: "Modes"
+			body:  """
+				With the `grpc` mode, the spans of each batch are posted to the collector, in a
+				request per process. With the `udp` mode, the spans are emitted to the agent in
+				`emitBatch` datagrams of the Thrift compact protocol, as Jaeger clients do. The spans
+				of a batch are split into as many datagrams as needed to stay under
+				`max_packet_size`, and the spans too large to fit into a datagram of their own are
+				rejected.
+				"""
+		}
+	}
+}
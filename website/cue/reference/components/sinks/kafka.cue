@@ -67,6 +67,20 @@ components: sinks: kafka: {
 	}
 
 	how_it_works: components._kafka.how_it_works
+	how_it_works: transactions: {
+		title: "Transactions"
+		body:  """
+			With the `transactional_id` option, the sink writes the events of each batch in a Kafka
+			transaction, and the transactions are written one at a time. A transaction is committed once
+			all of its records are delivered, and its events are then acknowledged. If any of its
+			records fails to be delivered, or if it fails to be committed, the transaction is aborted and
+			all of its events are reported as failed.
+
+			Consumers must read with the `read_committed` isolation level to ignore the records of
+			aborted transactions. Combined with sources supporting end-to-end acknowledgements, which
+			send the events of failed batches again, this achieves effectively-once delivery to Kafka.
+			"""
+	}
 
 	telemetry: metrics: {
 		kafka_queue_messages:                components.sources.internal_metrics.output.metrics.kafka_queue_messages
@@ -156,6 +156,21 @@ components: sinks: loki: {
 				on the configured `compression`.
 				"""
 		}
+
+		structured_metadata: {
+			title: "Structured Metadata"
+			body: """
+				Loki 3.0 and newer can store [structured metadata](\(urls.loki_structured_metadata))
+				with each log line: key/value pairs that are not indexed, unlike labels, and so don't
+				create new streams. Fields with many distinct values, such as trace IDs or request IDs,
+				can be mapped to structured metadata with the `structured_metadata` option instead of
+				`labels`, keeping the cardinality of the streams low while remaining queryable.
+
+				The keys of `structured_metadata` are expanded in the same way as the keys of
+				`labels`, and the fields used as structured metadata can be removed from the events
+				with `remove_structured_metadata_fields`.
+				"""
+		}
 	}
 
 	telemetry: metrics: {
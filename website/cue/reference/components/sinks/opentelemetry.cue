@@ -0,0 +1,123 @@
+package metadata
+
+components: sinks: opentelemetry: {
+	title: "OpenTelemetry"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		healthcheck: enabled: false
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    4_000_000
+				max_events:   null
+				timeout_secs: 1.0
+			}
+			compression: {
+				enabled: true
+				default: "none"
+				algorithms: ["none", "gzip"]
+				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
+			}
+			encoding: enabled: false
+			proxy: enabled: true
+			request: {
+				enabled: true
+				headers: true
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+				enabled_by_scheme:      true
+			}
+			to: {
+				service: services.opentelemetry
+
+				interface: {
+					socket: {
+						api: {
+							title: "OpenTelemetry Protocol"
+							url:   urls.opentelemetry_protocol
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.opentelemetry.configuration
+
+	input: {
+		logs: true
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			summary:      true
+			set:          true
+		}
+		traces: true
+	}
+
+	how_it_works: {
+		signals: {
+			title: "Signals"
+			body:  """
+				Log events are exported as OTLP log records, metric events as metrics, and trace events
+				as spans. Events received by the `opentelemetry` source are exported with the resource,
+				instrumentation scope, and attributes they were received with.
+
+				Logs with the `legacy` log namespace are exported with their `message` field as the body
+				of the log record, and their other fields as its attributes. Logs with the `vector` log
+				namespace are exported with the event as the body of the log record.
+				"""
+		}
+
+		metrics: {
+			title: "Metrics"
+			body:  """
+				Counters are exported as monotonic sums, and incremental gauges as non-monotonic sums,
+				whose aggregation temporality is delta for incremental metrics and cumulative for
+				absolute ones. Absolute gauges and sets are exported as gauges, sets as the number of
+				their values. Distributions and aggregated histograms are exported as histograms, and
+				aggregated summaries as summaries. Sketches have no OTLP equivalent and are dropped.
+
+				The tags of metrics prefixed by `resource.` and `scope.` are exported as the attributes
+				of the resource and of the instrumentation scope, and the other tags as the attributes
+				of the data points.
+				"""
+		}
+
+		partial_success: {
+			title: "Partial Success"
+			body:  """
+				When the receiver rejects some of the records of a request, as a partial success, the
+				rejection is logged and the other records are acknowledged. As required by the
+				specification, the rejected records are not retried.
+				"""
+		}
+	}
+}
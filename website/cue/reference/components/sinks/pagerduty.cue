@@ -0,0 +1,118 @@
+package metadata
+
+components: sinks: pagerduty: {
+	title: "PagerDuty"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		service_providers: ["PagerDuty"]
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		auto_generated:   true
+		healthcheck: enabled: false
+		send: {
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: enabled: false
+			}
+			proxy: enabled: true
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+				enabled_by_scheme:      true
+			}
+			to: {
+				service: services.pagerduty
+
+				interface: {
+					socket: {
+						api: {
+							title: "PagerDuty Events API v2"
+							url:   urls.pagerduty_events_api
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "required"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: [
+			"""
+				The PagerDuty service that alerts are sent to must have an Events API v2 integration,
+				whose integration key is the `routing_key` of the sink.
+				""",
+		]
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.pagerduty.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		alerts: {
+			title: "Alerts"
+			body: """
+				Each event is sent to the [Events API v2](\(urls.pagerduty_events_api)) as an event
+				that triggers, acknowledges, or resolves an alert, depending on the `event_action`
+				rendered for it. Events with the same `dedup_key` act on the same alert, so that an
+				alert triggered by an event can be resolved by a later event once the issue is fixed.
+				Events that trigger alerts are sent with the `summary`, `source`, `severity`,
+				`component`, `group`, and `class` rendered for them, and with the event itself as the
+				custom details of the alert.
+
+				Events whose `event_action` isn't valid, and events that acknowledge or resolve alerts
+				without a dedup key, are dropped. To only send the events that should open incidents,
+				connect the sink to a `filter` or a `route` transform.
+				"""
+		}
+		severity_mapping: {
+			title: "Severity mapping"
+			body: """
+				The `severity` rendered for the events can be a severity of PagerDuty, or the name or
+				numerical code of a syslog severity, or the name of a common log level, which are
+				mapped to the severities of PagerDuty as follows:
+
+				| Severity | Values |
+				|:---------|:-------|
+				| `critical` | `critical`, `crit`, `emergency`, `emerg`, `panic`, `alert`, `fatal`, `0`, `1`, `2` |
+				| `error` | `error`, `err`, `3` |
+				| `warning` | `warning`, `warn`, `notice`, `4`, `5` |
+				| `info` | `info`, `informational`, `debug`, `trace`, `6`, `7` |
+
+				Values are matched regardless of their case. The severity of events whose value isn't
+				valid is `error`.
+				"""
+		}
+		rate_limiting: {
+			title: "Rate limiting"
+			body: """
+				The Events API limits the number of events accepted for each integration key, so by
+				default the sink sends at most 120 events per minute. Events that are rate limited by
+				the API are retried.
+				"""
+		}
+	}
+}
@@ -0,0 +1,128 @@
+package metadata
+
+components: sinks: postgres: {
+	title: "PostgreSQL"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    10_000_000
+				max_events:   1000
+				timeout_secs: 1.0
+			}
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: enabled: false
+			}
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+				enabled_by_scheme:      true
+			}
+			to: {
+				service: services.postgresql
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["tcp", "unix"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: [
+			"""
+				The `table` and its columns must exist before events are inserted, the sink doesn't
+				create or alter tables.
+				""",
+		]
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.postgres.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		column_mapping: {
+			title: "Mapping Fields to Columns"
+			body:  """
+				Each log event is inserted as a row of the `table`, with the fields listed in `columns`
+				written to their column. The values of fields are converted to the type of their column,
+				which is read from the table when inserting each batch:
+
+				| Column type                                  | Field values                        |
+				|----------------------------------------------|-------------------------------------|
+				| `boolean`                                    | booleans                            |
+				| `smallint`, `integer`, `bigint`              | integers within the range of the type |
+				| `real`, `double precision`                   | floats and integers                 |
+				| `text`, `varchar`, `char`, `name`            | any, with objects and arrays as JSON |
+				| `bytea`                                      | strings                             |
+				| `json`, `jsonb`                              | any                                 |
+				| `timestamptz`, `timestamp`, `date`           | timestamps and RFC 3339 strings     |
+
+				Missing and null fields are written as `NULL`. When `catch_all_column` is set, the fields
+				of the event which aren't listed in `columns` are written to it as a JSON object, so that
+				no data is lost when events have more fields than the table has columns.
+				"""
+		}
+
+		insert_methods: {
+			title: "Insert Methods"
+			body:  """
+				By default, the rows of a batch are streamed with a single binary
+				[`COPY`](\(urls.postgresql_copy)) statement, which is the fastest way to load data into
+				PostgreSQL. `COPY` doesn't fire the rules of tables, so `method` can be set to `insert` to
+				insert rows with multi-row `INSERT` statements instead, such as for views or for the
+				hypertables of older versions of [TimescaleDB](\(urls.timescaledb)).
+				"""
+		}
+
+		transactions: {
+			title: "Transactions and Retries"
+			body:  """
+				The rows of each batch are inserted in a single transaction, so a batch is either inserted
+				entirely or not at all, and can be retried without duplicating rows. Batches are retried
+				when the connection fails, and on errors of the [SQLSTATE classes](\(urls.postgresql_sqlstate))
+				of connection exceptions (`08`), transaction rollbacks such as serialization failures and
+				deadlocks (`40`), insufficient resources (`53`), and operator interventions (`57`).
+
+				Other errors, such as constraint violations or values which can't be converted to the type
+				of their column, reject the whole batch without retrying it.
+
+				Connections are kept open in a pool between batches, with up to
+				`request.concurrency` connections used at a time.
+				"""
+		}
+	}
+}
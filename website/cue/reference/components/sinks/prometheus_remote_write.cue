@@ -109,5 +109,24 @@ components: sinks: prometheus_remote_write: {
 				Vector also supports using Gzip and Zstd.
 				"""
 		}
+		metadata_and_exemplars: {
+			title: "Metadata and exemplars"
+			body:  """
+				Each request carries the metadata of the metric families of its series, with their
+				type, and the exemplars that the `prometheus_remote_write` source decoded for them.
+				"""
+		}
+		native_histograms: {
+			title: "Native histograms"
+			body:  """
+				With `native_histograms` enabled, distributions and sketches are sent as native
+				histograms with a schema of 5, whose buckets grow by about 2.2%. The samples of
+				distributions are counted in the buckets they fall in, and the bins of sketches in
+				the buckets of their midpoints, so the histograms are as precise as the sketches
+				allow, without being limited to `buckets` or `quantiles`. Metrics which already
+				have explicit buckets, such as aggregated histograms, are sent as classic
+				histograms.
+				"""
+		}
 	}
 }
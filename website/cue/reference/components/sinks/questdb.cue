@@ -0,0 +1,134 @@
+package metadata
+
+components: sinks: questdb: {
+	title: "QuestDB"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: ["QuestDB"]
+		stateful: false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    1_000_000
+				timeout_secs: 1.0
+			}
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: enabled: false
+			}
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+				enabled_by_scheme:      false
+			}
+			to: {
+				service: services.questdb
+
+				interface: {
+					socket: {
+						api: {
+							title: "InfluxDB line protocol"
+							url:   urls.questdb_ilp
+						}
+						direction: "outgoing"
+						protocols: ["tcp"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.questdb.configuration
+
+	input: {
+		logs: true
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			set:          true
+			summary:      true
+		}
+		traces: false
+	}
+
+	how_it_works: {
+		authentication: {
+			title: "Authentication"
+			body:  """
+				When `auth` is configured, the sink [authenticates](\(urls.questdb_authentication)) each
+				connection by sending the `key_id`, and signing the challenge QuestDB responds with using
+				the `private_key`. These are the `kid` and `d` members of the JSON Web Key of the user in
+				the authentication database of QuestDB.
+
+				The line protocol has no responses, QuestDB closes the connection instead when the
+				signature is invalid or when it fails to parse a line. The sink checks that pooled
+				connections are still open before writing to them, and retries failed writes on new
+				connections, so lines can be written more than once.
+				"""
+		}
+
+		log_mapping: {
+			title: "Mapping Log Events"
+			body:  """
+				Log events are written as rows of the `table`, which is created by QuestDB on the first
+				write.
+
+				| Field            | Row                  |
+				|------------------|----------------------|
+				| timestamp        | designated timestamp |
+				| listed `symbols` | symbol               |
+				| [custom-key]     | column               |
+
+				Nested fields are flattened into columns named after their path, with the dots replaced by
+				underscores, such as `request_path` for `request.path`. The characters QuestDB doesn't
+				allow in the names of columns are replaced by underscores as well.
+				"""
+		}
+
+		metric_mapping: {
+			title: "Mapping Metric Events"
+			body:  """
+				Metric events are written as rows of the table named `{namespace}_{name}` after the
+				metric, with the `default_namespace` used for metrics without one. The tags of metrics are
+				written as symbols, and their values as the following columns:
+
+				| Metric type           | Columns                                          |
+				|-----------------------|--------------------------------------------------|
+				| counter, gauge        | `value`                                          |
+				| set                   | `value`, the number of values                    |
+				| distribution          | `min`, `max`, `median`, `avg`, `sum`, `count`, and the `quantiles` |
+				| histogram             | `count`, `sum`, and a `bucket_*` column per bucket |
+				| summary               | `count`, `sum`, and a `quantile_*` column per quantile |
+
+				Counters are written as the increments since the previous write of the metric.
+				"""
+		}
+	}
+}
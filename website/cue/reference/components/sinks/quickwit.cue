@@ -0,0 +1,106 @@
+package metadata
+
+components: sinks: quickwit: {
+	title: "Quickwit"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    10_000_000
+				timeout_secs: 1.0
+			}
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: enabled: false
+			}
+			proxy: enabled: true
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+				enabled_by_scheme:      true
+			}
+			to: {
+				service: services.quickwit
+
+				interface: {
+					socket: {
+						api: {
+							title: "Quickwit ingest API"
+							url:   urls.quickwit_ingest_api
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.quickwit.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		commit: {
+			title: "Commit policies"
+			body: """
+				Each batch is sent to the `/api/v1/<index>/ingest` endpoint of the index, which is
+				served by the ingest v2 pipeline on clusters where it is enabled. With the default
+				`auto` commit policy, Quickwit answers once the documents are persisted, and they
+				become searchable when the index is next committed, every `commit_timeout_secs` of
+				its [indexing settings](\(urls.quickwit_indexing_settings)). With `wait_for` or
+				`force`, requests are only answered once the documents are searchable, so
+				`request.timeout_secs` should exceed the commit timeout of the indexes.
+				"""
+		}
+		batch_sizing: {
+			title: "Batch sizing"
+			body: """
+				Quickwit refuses request bodies over its `content_length_limit`, 10 MiB by default,
+				with a `413 Payload Too Large` response that is not retried. Batches default to 10 MB
+				of events, measured by their estimated size as JSON, so that they stay below this
+				limit, and `batch.max_bytes` should only be raised along with it.
+				"""
+		}
+		routing: {
+			title: "Routing events to indexes"
+			body: """
+				The `index` option is a template, and events are batched by the index ID it renders
+				to. Events for which it fails to render are dropped. Indexes are not created by
+				Vector, and the events of a batch sent to an index that doesn't exist are rejected.
+				"""
+		}
+	}
+}
@@ -0,0 +1,104 @@
+package metadata
+
+components: sinks: smtp: {
+	title: "SMTP"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		auto_generated:   true
+		healthcheck: enabled: true
+		send: {
+			compression: enabled: false
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    null
+				max_events:   100
+				timeout_secs: 10.0
+			}
+			encoding: enabled: false
+			request: {
+				enabled:     true
+				concurrency: 1
+				headers:     false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        true
+				enabled_by_scheme:      false
+			}
+			to: {
+				service: services.smtp
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["tcp"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.smtp.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		messages: {
+			title: "Messages"
+			body: """
+				Events are batched by the `subject` rendered for them, and each batch is sent as a
+				plain text email message with that subject to all of the `to` mailboxes. The body of
+				the message holds the `body` rendered for each of its events, one per line. Events that
+				the subject can't be rendered for are dropped, and events that the body can't be
+				rendered for are left out of the body of their message.
+
+				To only send the events that should be alerted on, or to compute the fields rendered in
+				the subject or the body with VRL, connect the sink to a `filter` or a `remap` transform.
+				"""
+		}
+		rate_limiting: {
+			title: "Rate limiting"
+			body: """
+				To avoid flooding the mailboxes of the recipients, the sink sends one message at a time,
+				and at most 10 messages per minute by default. This can be tuned with the
+				`request.rate_limit_num` and `request.rate_limit_duration_secs` options, and the size
+				and age of the batches with the `batch.max_events` and `batch.timeout_secs` options.
+				Events that arrive while the rate limit is reached are buffered until they can be sent.
+				"""
+		}
+		tls_and_authentication: {
+			title: "TLS and authentication"
+			body: """
+				By default, the sink connects to the port `587` of the SMTP server, and upgrades the
+				connection to TLS with the `STARTTLS` command. Set `tls_mode` to `tls` to connect to
+				servers that use implicit TLS, usually on the port `465`, or to `none` for local SMTP
+				servers that don't support TLS. When `auth` is set, the sink authenticates to the server
+				with its username and password.
+				"""
+		}
+	}
+}
@@ -0,0 +1,114 @@
+package metadata
+
+components: sinks: snowflake: {
+	title: "Snowflake"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: ["Snowflake"]
+		stateful: false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    4_000_000
+				max_events:   null
+				timeout_secs: 1.0
+			}
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: enabled: false
+			}
+			proxy: enabled: true
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        true
+				enabled_by_scheme:      false
+			}
+			to: {
+				service: services.snowflake
+
+				interface: {
+					socket: {
+						api: {
+							title: "Snowpipe Streaming REST API"
+							url:   urls.snowflake_snowpipe_streaming_api
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "required"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: [
+			"""
+				The role of the user that the sink authenticates as must have the `OPERATE` privilege
+				on the pipe, and the `INSERT` privilege on the table.
+				""",
+		]
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.snowflake.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		authentication: {
+			title: "Authentication"
+			body:  """
+				The sink authenticates as `user` with [key-pair authentication](\(urls.snowflake_key_pair_auth)),
+				signing its tokens with `private_key`. The public key of the key pair must be assigned to
+				the user with `ALTER USER ... SET RSA_PUBLIC_KEY`.
+				"""
+		}
+
+		channels: {
+			title: "Channels and Offset Tokens"
+			body:  """
+				Events are streamed as rows through a channel of the pipe of the table, named by
+				`channel`. Each batch is appended with the next of a sequence of integer offset tokens,
+				and its events are only acknowledged once the committed offset token of the channel
+				reaches the offset token of the batch, or are retried after `commit_timeout_secs`.
+
+				A channel can only be opened by a single client at once, so each Vector instance must
+				stream through a different channel. When the channel is reopened after a failure, the
+				rows that weren't committed are discarded, and their batches are retried.
+				"""
+		}
+
+		pipes: {
+			title: "Pipes"
+			body:  """
+				Without `pipe`, rows are streamed through the default pipe of the table, which inserts
+				the top-level fields of the events into the columns of the same names. A pipe created
+				with `CREATE PIPE` can transform the rows before they are inserted.
+				"""
+		}
+	}
+}
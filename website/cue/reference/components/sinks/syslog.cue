@@ -0,0 +1,95 @@
+package metadata
+
+components: sinks: syslog: {
+	title: "Syslog"
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		development:   "beta"
+		egress_method: "stream"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		auto_generated:   true
+		healthcheck: enabled: true
+		send: {
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: {
+					enabled: true
+					framing: false
+					enum: ["json", "text"]
+				}
+			}
+			send_buffer_bytes: {
+				enabled:       true
+				relevant_when: "mode = `tcp` or mode = `udp`"
+			}
+			keepalive: enabled: true
+			request: enabled:   false
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+				enabled_by_scheme:      false
+			}
+			to: {
+				service: services.syslog
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["tcp", "udp", "unix"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.syslog.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		message_format: {
+			title: "Message format"
+			body: """
+				Events are sent as messages of the format of [RFC 5424](\(urls.syslog_5424)), or of the
+				legacy format of [RFC 3164](\(urls.syslog_3164)) when `rfc` is set to `rfc3164`. The
+				priority of the messages is computed from their `facility` and `severity`, and their
+				header fields are rendered from the templates of the sink, so that they can be taken
+				from the fields of the events. Characters that aren't allowed in the header fields are
+				replaced with underscores, and the fields are truncated to their maximum length. The
+				body of the messages is encoded with the configured `encoding`.
+				"""
+		}
+
+		framing: {
+			title: "Framing"
+			body: """
+				Over TCP and Unix stream sockets, messages are either octet counted, prefixed with
+				their length in bytes, or newline delimited, as described in
+				[RFC 6587](\(urls.syslog_6587)). Octet counting is the default, because it allows the
+				body of the messages to contain newlines. Over UDP and Unix datagram sockets, each
+				message is sent in its own datagram.
+				"""
+		}
+	}
+}
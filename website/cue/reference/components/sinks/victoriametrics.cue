@@ -0,0 +1,123 @@
+package metadata
+
+components: sinks: victoriametrics: {
+	title: "VictoriaMetrics"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: ["VictoriaMetrics"]
+		stateful: false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_events:   1000
+				timeout_secs: 1.0
+			}
+			compression: {
+				enabled: true
+				default: "gzip"
+				algorithms: ["none", "gzip"]
+				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
+			}
+			encoding: enabled: false
+			proxy: enabled:    true
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+				enabled_by_scheme:      true
+			}
+			to: {
+				service: services.victoriametrics
+
+				interface: {
+					socket: {
+						api: {
+							title: "VictoriaMetrics import API"
+							url:   urls.victoriametrics_jsonl
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.victoriametrics.configuration
+
+	input: {
+		logs: false
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			set:          true
+			summary:      true
+		}
+		traces: false
+	}
+
+	how_it_works: {
+		formats: {
+			title: "Formats"
+			body:  """
+				With the default `jsonl` format, metrics are written to the `/api/v1/import` endpoint in
+				the [JSON line format](\(urls.victoriametrics_jsonl)). They are converted to series the
+				same way as by the `prometheus_remote_write` sink, such as the `_bucket`, `_sum`, and
+				`_count` series of histograms, and the samples of the same series in a batch are written
+				on the same line.
+
+				With the `influx` format, metrics are written to the `/write` endpoint over the
+				[InfluxDB line protocol](\(urls.victoriametrics_influx)), and VictoriaMetrics names the
+				series after the measurement and the fields of the lines, such as `requests_total_value`
+				for a counter named `requests_total`.
+
+				Incremental metrics are converted to absolute metrics in both formats.
+				"""
+		}
+
+		multitenancy: {
+			title: "Multitenancy"
+			body:  """
+				With the [cluster version](\(urls.victoriametrics_cluster)) of VictoriaMetrics, the
+				`endpoint` is the address of `vminsert`, and `tenant_id` selects the tenant that metrics
+				are written to, as an account ID optionally followed by a project ID, such as `42:7`.
+				It can be templated to route metrics to the tenants named by their tags, in which case
+				metrics are batched by tenant.
+				"""
+		}
+
+		extra_labels: {
+			title: "Extra labels"
+			body:  """
+				The `extra_labels` are sent as `extra_label` query parameters of the requests, so that
+				VictoriaMetrics adds them to all the series written by the sink, without increasing the
+				size of the requests.
+				"""
+		}
+	}
+}
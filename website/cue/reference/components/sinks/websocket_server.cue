@@ -0,0 +1,99 @@
+package metadata
+
+components: sinks: websocket_server: {
+	title: "WebSocket Server"
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		development:   "beta"
+		egress_method: "expose"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		auto_generated:   true
+		healthcheck: enabled: false
+		exposes: {
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				enabled_default:        false
+			}
+
+			for: {
+				service: services.websocket
+
+				interface: {
+					socket: {
+						direction: "incoming"
+						port:      8080
+						protocols: ["tcp"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.websocket_server.configuration
+
+	input: {
+		logs: true
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			summary:      true
+			set:          true
+		}
+		traces: true
+	}
+
+	how_it_works: {
+		streaming: {
+			title: "Streaming to clients"
+			body: """
+				The sink listens for WebSocket connections on the configured `address`, and sends every
+				event it receives to all the connected clients, as a message per event. This makes it
+				possible for live-tail UIs and dashboards to attach directly to a pipeline.
+
+				Events are not buffered for clients that connect later: events received while no client
+				is connected are dropped. Each client has its own buffer of `subscriber_capacity`
+				messages, and messages are dropped for a client whose buffer is full, without slowing
+				down the pipeline or the other clients.
+				"""
+		}
+
+		filters: {
+			title: "Client filters"
+			body: """
+				A client can pass a [VRL](\(urls.vrl_reference)) boolean expression in the `filter` query
+				parameter of the request it connects with, such as
+				`ws://localhost:8080/?filter=.level%20%3D%3D%20%22error%22` for `.level == "error"`. The
+				client is then only sent the events that the expression returns `true` for. The
+				expression is compiled when the client connects, and the connection is rejected with a
+				`400 Bad Request` response if the expression is invalid.
+
+				Filters are compiled with the same functions as conditions. Configure `auth` when the
+				sink is reachable by untrusted clients.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		open_connections:                        components.sources.internal_metrics.output.metrics.open_connections
+		connection_established_total:            components.sources.internal_metrics.output.metrics.connection_established_total
+		connection_shutdown_total:               components.sources.internal_metrics.output.metrics.connection_shutdown_total
+		websocket_server_dropped_messages_total: components.sources.internal_metrics.output.metrics.websocket_server_dropped_messages_total
+	}
+}
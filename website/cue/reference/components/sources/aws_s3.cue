@@ -129,6 +129,21 @@ components: sources: aws_s3: components._aws & {
 				```
 				"""
 		}
+		backfilling: {
+			title: "Backfilling objects"
+			body: """
+				SQS notifications are only sent for objects created after they were set up. To read the
+				objects already stored in a bucket, for example to reprocess historical data through a
+				new pipeline, set `strategy` to `list` and configure the bucket to read in the `list`
+				options. The objects can be restricted to a key `prefix` and to a range of
+				modification times with `start_time` and `end_time`.
+
+				The position of the listing is checkpointed in the data directory after each page of up
+				to 1,000 objects, so a restarted source resumes where it left off, and a completed
+				listing is not repeated. Objects of the page being read when Vector stopped are read
+				again. Once every object has been read, the source stops.
+				"""
+		}
 	}
 
 	permissions: iam: [
@@ -142,6 +157,10 @@ components: sources: aws_s3: components._aws & {
 				{
 					_action: "GetObject"
 				},
+				{
+					_action:       "ListBucket"
+					required_when: "[`strategy`](#strategy) is set to `list`"
+				},
 			]
 		},
 		{
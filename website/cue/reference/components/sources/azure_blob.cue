@@ -0,0 +1,129 @@
+package metadata
+
+components: sources: azure_blob: {
+	title: "Azure Blob Storage"
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		multiline: enabled: true
+		collect: {
+			tls: enabled:        false
+			checkpoint: enabled: false
+			proxy: enabled:      false
+			from: service:       services.azure_blob
+		}
+	}
+
+	classes: {
+		commonly_used: false
+		deployment_roles: ["aggregator"]
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	support: {
+		requirements: [
+			"""
+				The Azure Blob Storage source requires a storage queue receiving the
+				[blob storage events](\(urls.azure_blob_events)) of the desired containers.
+				""",
+		]
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.azure_blob.configuration
+
+	output: logs: object: {
+		description: "A line from an Azure blob."
+		fields: {
+			message: {
+				description: "A line from the blob."
+				required:    true
+				type: string: {
+					examples: ["{\"time\": \"2024-01-01T00:00:00.0000000Z\", \"category\": \"AppServiceHTTPLogs\", \"operationName\": \"Microsoft.Web/sites/log\"}"]
+				}
+			}
+			timestamp: fields._current_timestamp & {
+				description: "The Last-Modified time of the blob. Defaults the current timestamp if this information is missing."
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["azure_blob"]
+				}
+			}
+			container: {
+				description: "The container of the blob the line came from."
+				required:    true
+				type: string: {
+					examples: ["insights-logs-appservicehttplogs"]
+				}
+			}
+			blob: {
+				description: "The blob the line came from."
+				required:    true
+				type: string: {
+					examples: ["resourceId=/SUBSCRIPTIONS/.../y=2024/m=01/d=01/h=00/m=00/PT1H.json"]
+				}
+			}
+		}
+	}
+
+	how_it_works: {
+		events: {
+			title: "Blob storage events"
+			body: """
+				Blobs are discovered through the `Microsoft.Storage.BlobCreated` events that an Event
+				Grid subscription of the storage account delivers to a storage queue. Other events are
+				deleted from the queue and ignored. A subscription can be set up with:
+
+				```shell
+				az eventgrid event-subscription create --name vector --source-resource-id <storage account id> --endpoint-type storagequeue --endpoint <storage account id>/queueservices/default/queues/<queue> --included-event-types Microsoft.Storage.BlobCreated
+				```
+
+				Both the Event Grid and the CloudEvents event schemas are supported.
+				"""
+		}
+		append_blobs: {
+			title: "Append blobs"
+			body: """
+				Azure diagnostic settings write logs to append blobs, to which data keeps being appended
+				after the blob is created, without any further event being sent. Append blobs are
+				therefore followed: they are polled for new data every `append_blobs.poll_secs`, and only
+				the data appended since the last poll is read. The queue message of an append blob is
+				kept invisible while it is followed, and deleted once the blob has not grown for
+				`append_blobs.idle_timeout_secs`. If Vector stops before then, the message becomes
+				visible again and the blob is read again from its start.
+				"""
+		}
+		acknowledgements: {
+			title: "Acknowledgements"
+			body: """
+				A queue message is deleted once its blobs have been read and, if end-to-end
+				acknowledgements are enabled, their events have been delivered. Messages that fail to be
+				processed become visible again after `queue.visibility_timeout_secs` and are retried.
+				"""
+		}
+		compression: {
+			title: "Compression"
+			body: """
+				With `compression` set to `auto`, the compression of a blob is determined from its
+				`Content-Encoding` and `Content-Type` properties, falling back to the `.gz` and `.zst`
+				suffixes of its name. Data appended to append blobs is always read uncompressed.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		azure_blob_event_ignored_total: components.sources.internal_metrics.output.metrics.azure_blob_event_ignored_total
+	}
+}
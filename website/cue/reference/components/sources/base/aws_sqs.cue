@@ -148,6 +148,18 @@ base: components: sources: aws_sqs: configuration: {
 				relevant_when: "codec = \"avro\""
 				required:      true
 				type: object: options: {
+					object_container_file: {
+						description: """
+																Whether each message is an [Avro object container file][container_file].
+
+																The records of the file are decoded with the schema in its header, and each of them
+																becomes an event.
+
+																[container_file]: https://avro.apache.org/docs/1.11.1/specification/#object-container-files
+																"""
+						required: false
+						type: bool: default: false
+					}
 					schema: {
 						description: """
 																The Avro schema definition.
@@ -157,17 +169,60 @@ base: components: sources: aws_sqs: configuration: {
 																* `Duration`
 																* `Fixed`
 																* `TimeMillis`
+
+																Required, unless the schemas of the messages are read from object container files or
+																fetched from a schema registry. In that case, the records are resolved to this schema
+																when it's set.
 																"""
-						required: true
+						required: false
 						type: string: examples: ["{ \"type\": \"record\", \"name\": \"log\", \"fields\": [{ \"name\": \"message\", \"type\": \"string\" }] }"]
 					}
+					schema_registry: {
+						description: """
+																The schema registry to fetch the schemas of the messages from.
+
+																When set, the messages must be in the [Confluent wire format][wire_format], and each of them
+																is decoded with the schema of the ID it's prefixed with. The schemas are fetched once, and
+																reused for the following messages. A failure to fetch a schema is remembered for 30 seconds,
+																during which the messages prefixed with its ID are rejected without querying the registry
+																again.
+
+																[wire_format]: https://docs.confluent.io/platform/current/schema-registry/fundamentals/serdes-develop/index.html#wire-format
+																"""
+						required: false
+						type: object: options: {
+							password: {
+								description: "The password for the basic authentication to the schema registry."
+								required:    false
+								type: string: examples: ["${SCHEMA_REGISTRY_PASSWORD}"]
+							}
+							timeout_secs: {
+								description: "The timeout of the requests to the schema registry, in seconds."
+								required:    false
+								type: uint: {
+									default: 10
+									unit:    "seconds"
+								}
+							}
+							url: {
+								description: "The URL of the schema registry."
+								required:    true
+								type: string: examples: ["http://localhost:8081"]
+							}
+							username: {
+								description: "The username for the basic authentication to the schema registry."
+								required:    false
+								type: string: examples: ["${SCHEMA_REGISTRY_USERNAME}"]
+							}
+						}
+					}
 					strip_schema_id_prefix: {
 						description: """
 																For Avro datum encoded in Kafka messages, the bytes are prefixed with the schema ID.  Set this to true to strip the schema ID prefix.
 																According to [Confluent Kafka's document](https://docs.confluent.io/platform/current/schema-registry/fundamentals/serdes-develop/index.html#wire-format).
 																"""
-						required: true
-						type: bool: {}
+						required: false
+						type: bool: default: false
 					}
 				}
 			}
@@ -183,6 +238,13 @@ base: components: sources: aws_sqs: configuration: {
 															[apache_avro]: https://avro.apache.org/
 															"""
 						bytes: "Uses the raw bytes as-is."
+						csv: """
+															Decodes the raw bytes as [CSV][csv] rows.
+
+															Each row becomes an event, with a field for each of its columns.
+
+															[csv]: https://datatracker.ietf.org/doc/html/rfc4180
+															"""
 						gelf: """
 															Decodes the raw bytes as a [GELF][gelf] message.
 
@@ -206,6 +268,15 @@ base: components: sources: aws_sqs: configuration: {
 
 															[json]: https://www.json.org/
 															"""
+						msgpack: """
+															Decodes the raw bytes as [MessagePack][msgpack].
+
+															Each value of the frame becomes an event, and the values of arrays at its root become
+															separate events. Timestamps, including the `EventTime` values of the Fluentd forward
+															protocol, are decoded as timestamps.
+
+															[msgpack]: https://msgpack.org/
+															"""
 						native: """
 															Decodes the raw bytes as [native Protocol Buffers format][vector_native_protobuf].
 
@@ -222,6 +293,14 @@ base: components: sources: aws_sqs: configuration: {
 															[vector_native_json]: https://github.com/vectordotdev/vector/blob/master/lib/codecs/tests/data/native_encoding/schema.cue
 															[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
 															"""
+						otlp: """
+															Decodes the raw bytes as an [OTLP][otlp] export request encoded as protobuf.
+
+															Each log record or metric data point of the request becomes an event, converted the same
+															way as with the `opentelemetry` source.
+
+															[otlp]: https://opentelemetry.io/docs/specs/otlp/
+															"""
 						protobuf: """
 															Decodes the raw bytes as [protobuf][protobuf].
 
@@ -244,6 +323,84 @@ base: components: sources: aws_sqs: configuration: {
 					}
 				}
 			}
+			csv: {
+				description:   "CSV-specific decoding options."
+				relevant_when: "codec = \"csv\""
+				required:      false
+				type: object: options: {
+					delimiter: {
+						description: "The field delimiter to use when reading CSV."
+						required:    false
+						type: uint: default: 44
+					}
+					header: {
+						description: "The names of the fields of the rows."
+						required:    false
+						type: object: options: {
+							fields: {
+								description:   "The names of the fields, in the order of their columns."
+								relevant_when: "type = \"explicit\""
+								required:      true
+								type: array: items: type: string: examples: ["timestamp", "host", "message"]
+							}
+							type: {
+								description: "How the names of the fields are determined."
+								required:    false
+								type: string: {
+									default: "first_row"
+									enum: {
+										explicit: "The names of the fields are configured, and all the rows hold values."
+										first_row: """
+																The first row holds the names of the fields.
+
+																The first row of each stream, such as a file, an object, a connection, or a request
+																body, is the header of the following rows.
+																"""
+										positional: """
+																The fields are named after the position of their columns, starting with `column0`, and
+																all the rows hold values.
+																"""
+									}
+								}
+							}
+						}
+					}
+					infer_types: {
+						description: """
+																Whether the types of the values are inferred.
+
+																When enabled, the values that are integers, floats, or booleans (`true` or `false`, in any
+																case) are decoded as such, and empty values are decoded as `null`. Otherwise, all the
+																values are decoded as strings.
+																"""
+						required: false
+						type: bool: default: false
+					}
+					malformed_rows: {
+						description: "How the rows that don't have a value for each field are handled."
+						required:    false
+						type: string: {
+							default: "fail"
+							enum: {
+								fail: """
+																The frame of the row fails to decode, and is reported and dropped like other decoding
+																errors.
+																"""
+								raw: """
+																The row is decoded into an event with its text in the `message` field, and the reason it's
+																malformed in the `csv_error` field, so that it can be routed apart.
+																"""
+								skip: "The row is skipped, and the other rows of the frame are decoded."
+							}
+						}
+					}
+					quote: {
+						description: "The quote character to use when reading CSV."
+						required:    false
+						type: uint: default: 34
+					}
+				}
+			}
 			gelf: {
 				description:   "GELF-specific decoding options."
 				relevant_when: "codec = \"gelf\""
@@ -292,6 +449,27 @@ base: components: sources: aws_sqs: configuration: {
 					type: bool: default: true
 				}
 			}
+			otlp: {
+				description:   "OTLP-specific decoding options."
+				relevant_when: "codec = \"otlp\""
+				required:      false
+				type: object: options: signal: {
+					description: """
+						The kind of telemetry held by the payloads.
+
+						Payloads don't identify the kind of request they hold, so all the payloads decoded by a
+						component must hold the same one.
+						"""
+					required: false
+					type: string: {
+						default: "logs"
+						enum: {
+							logs:    "The payloads are `ExportLogsServiceRequest` messages, decoded as log events."
+							metrics: "The payloads are `ExportMetricsServiceRequest` messages, decoded as metric events."
+						}
+					}
+				}
+			}
 			protobuf: {
 				description:   "Protobuf-specific decoding options."
 				relevant_when: "codec = \"protobuf\""
@@ -411,6 +589,71 @@ base: components: sources: aws_sqs: configuration: {
 					}
 				}
 			}
+			chunked_gelf: {
+				description:   "Options for the chunked GELF decoder."
+				relevant_when: "method = \"chunked_gelf\""
+				required:      false
+				type: object: options: {
+					decompression: {
+						description: "The decompression applied to the messages."
+						required:    false
+						type: string: {
+							default: "auto"
+							enum: {
+								auto: """
+																Messages are decompressed according to the magic bytes they start with, if they're
+																compressed with gzip or zlib.
+																"""
+								gzip: """
+																Messages are compressed with [gzip][gzip].
+
+																[gzip]: https://www.gzip.org/
+																"""
+								none: "Messages are not compressed."
+								zlib: """
+																Messages are compressed with [zlib][zlib].
+
+																[zlib]: https://www.zlib.net/
+																"""
+							}
+						}
+					}
+					max_length: {
+						description: """
+																The maximum length of a message, once its chunks are reassembled and it's decompressed.
+
+																Longer messages are discarded. By default, there is no maximum length enforced. If the
+																messages are received from untrusted clients, consider setting the maximum length to a
+																reasonably large value as a safety net, as compressed messages can be much larger than the
+																chunks they're sent in.
+																"""
+						required: false
+						type: uint: {}
+					}
+					pending_messages_limit: {
+						description: """
+																The maximum number of messages that can wait for their chunks at once.
+
+																The chunks of new messages are discarded while the limit is reached. By default, there is no
+																limit enforced.
+																"""
+						required: false
+						type: uint: {}
+					}
+					timeout_secs: {
+						description: """
+																The time to wait for all the chunks of a message, after its first chunk is received.
+
+																The chunks of messages that aren't complete in time are discarded.
+																"""
+						required: false
+						type: uint: {
+							default: 5
+							unit:    "seconds"
+						}
+					}
+				}
+			}
 			length_delimited: {
 				description:   "Options for the length delimited decoder."
 				relevant_when: "method = \"length_delimited\""
@@ -446,13 +689,102 @@ base: components: sources: aws_sqs: configuration: {
 					enum: {
 						bytes:               "Byte frames are passed through as-is according to the underlying I/O boundaries (for example, split between messages or stream segments)."
 						character_delimited: "Byte frames which are delimited by a chosen character."
-						length_delimited:    "Byte frames which are prefixed by an unsigned big-endian 32-bit integer indicating the length."
-						newline_delimited:   "Byte frames which are delimited by a newline character."
+						chunked_gelf: """
+															Byte frames which are [chunked GELF][chunking] messages, reassembled from their chunks and
+															decompressed.
+
+															This is meant for message-based transports such as UDP, which Graylog clients send chunked
+															and compressed messages over.
+
+															[chunking]: https://go2docs.graylog.org/current/getting_in_log_data/gelf.html#GELFviaUDP
+															"""
+						length_delimited: "Byte frames which are prefixed by an unsigned big-endian 32-bit integer indicating the length."
+						multiline: """
+															Byte frames which are lines aggregated into multi-line messages, such as stack traces, the
+															same way the `file` source aggregates them.
+															"""
+						newline_delimited: "Byte frames which are delimited by a newline character."
 						octet_counting: """
 															Byte frames according to the [octet counting][octet_counting] format.
 
 															[octet_counting]: https://tools.ietf.org/html/rfc6587#section-3.4.1
 															"""
+						varint_length_delimited: """
+															Byte frames which are prefixed by an unsigned [varint][varint] indicating the length.
+
+															This is the framing of streams of Protocol Buffers messages, as written by
+															`writeDelimitedTo` in Java or `SerializeDelimitedToOstream` in C++.
+
+															[varint]: https://protobuf.dev/programming-guides/encoding/#varints
+															"""
+					}
+				}
+			}
+			multiline: {
+				description:   "Options for the multiline decoder."
+				relevant_when: "method = \"multiline\""
+				required:      true
+				type: object: options: {
+					condition_pattern: {
+						description: """
+																Regular expression pattern that is used to determine whether or not more lines should be read.
+
+																This setting must be configured in conjunction with `mode`.
+																"""
+						required: true
+						type: string: examples: ["^[\\s]+", "\\\\$", "^(INFO|ERROR) ", ";$"]
+					}
+					mode: {
+						description: """
+																Aggregation mode.
+
+																This setting must be configured in conjunction with `condition_pattern`.
+																"""
+						required: true
+						type: string: enum: {
+							continue_past: """
+																All consecutive lines matching this pattern, plus one additional line, are included in the group.
+
+																This is useful in cases where a log message ends with a continuation marker, such as a backslash, indicating
+																that the following line is part of the same message.
+																"""
+							continue_through: """
+																All consecutive lines matching this pattern are included in the group.
+
+																The first line (the line that matched the start pattern) does not need to match the `ContinueThrough` pattern.
+
+																This is useful in cases such as a Java stack trace, where some indicator in the line (such as a leading
+																whitespace) indicates that it is an extension of the proceeding line.
+																"""
+							halt_before: """
+																All consecutive lines not matching this pattern are included in the group.
+
+																This is useful where a log line contains a marker indicating that it begins a new message.
+																"""
+							halt_with: """
+																All consecutive lines, up to and including the first line matching this pattern, are included in the group.
+
+																This is useful where a log line ends with a termination marker, such as a semicolon.
+																"""
+						}
+					}
+					start_pattern: {
+						description: "Regular expression pattern that is used to match the start of a new message."
+						required:    true
+						type: string: examples: ["^[\\s]+", "\\\\$", "^(INFO|ERROR) ", ";$"]
+					}
+					timeout_ms: {
+						description: """
+																The maximum amount of time to wait for the next additional line, in milliseconds.
+
+																Once this timeout is reached, the buffered message is flushed, even if incomplete, as soon
+																as more data is received or the stream ends.
+																"""
+						required: true
+						type: uint: {
+							examples: [1000, 600000]
+							unit: "milliseconds"
+						}
 					}
 				}
 			}
@@ -488,6 +820,19 @@ base: components: sources: aws_sqs: configuration: {
 					type: uint: {}
 				}
 			}
+			varint_length_delimited: {
+				description:   "Options for the varint length delimited decoder."
+				relevant_when: "method = \"varint_length_delimited\""
+				required:      false
+				type: object: options: max_frame_length: {
+					description: "Maximum frame length"
+					required:    false
+					type: uint: {
+						default: 8388608
+						unit:    "bytes"
+					}
+				}
+			}
 		}
 	}
 	poll_secs: {
@@ -0,0 +1,962 @@
+package metadata
+
+base: components: sources: azure_blob: configuration: {
+	acknowledgements: {
+		deprecated: true
+		description: """
+			Controls how acknowledgements are handled by this source.
+
+			This setting is **deprecated** in favor of enabling `acknowledgements` at the [global][global_acks] or sink level.
+
+			Enabling or disabling acknowledgements at the source level has **no effect** on acknowledgement behavior.
+
+			See [End-to-end Acknowledgements][e2e_acks] for more information on how event acknowledgement is handled.
+
+			[global_acks]: https://vector.dev/docs/reference/configuration/global-options/#acknowledgements
+			[e2e_acks]: https://vector.dev/docs/about/under-the-hood/architecture/end-to-end-acknowledgements/
+			"""
+		required: false
+		type: object: options: enabled: {
+			description: "Whether or not end-to-end acknowledgements are enabled for this source."
+			required:    false
+			type: bool: {}
+		}
+	}
+	append_blobs: {
+		description: "Append blob options."
+		required:    false
+		type: object: options: {
+			idle_timeout_secs: {
+				description: """
+					How long an append blob is followed after data was last appended to it, in seconds.
+
+					Azure diagnostic logs are written to a new append blob every hour, so the default follows a
+					blob for an hour after its last write.
+					"""
+				required: false
+				type: uint: {
+					default: 3600
+					unit:    "seconds"
+				}
+			}
+			poll_secs: {
+				description: """
+					How often to poll followed append blobs for appended data, in seconds.
+
+					This must be lower than `queue.visibility_timeout_secs`, as the queue message of a blob is
+					kept invisible for as long as the blob is followed.
+					"""
+				required: false
+				type: uint: {
+					default: 60
+					unit:    "seconds"
+				}
+			}
+		}
+	}
+	compression: {
+		description: "The compression scheme used for decompressing blobs retrieved from Azure Blob Storage."
+		required:    false
+		type: string: {
+			default: "auto"
+			enum: {
+				auto: """
+					Automatically attempt to determine the compression scheme.
+
+					The compression scheme of the blob is determined from its `Content-Encoding` and
+					`Content-Type` properties, as well as the blob name suffix (for example, `.gz`).
+
+					It is set to `none` if the compression scheme cannot be determined.
+					"""
+				gzip: "GZIP."
+				none: "Uncompressed."
+				zstd: "ZSTD."
+			}
+		}
+	}
+	connection_string: {
+		description: """
+			The Azure Blob Storage Account connection string.
+
+			Authentication with access key is the only supported authentication method.
+
+			Either `storage_account`, or this field, must be specified.
+			"""
+		required: false
+		type: string: examples: ["DefaultEndpointsProtocol=https;AccountName=mylogstorage;AccountKey=storageaccountkeybase64encoded;EndpointSuffix=core.windows.net"]
+	}
+	decoding: {
+		description: "Configures how events are decoded from raw bytes."
+		required:    false
+		type: object: options: {
+			avro: {
+				description:   "Apache Avro-specific encoder options."
+				relevant_when: "codec = \"avro\""
+				required:      true
+				type: object: options: {
+					object_container_file: {
+						description: """
+																Whether each message is an [Avro object container file][container_file].
+
+																The records of the file are decoded with the schema in its header, and each of them
+																becomes an event.
+
+																[container_file]: https://avro.apache.org/docs/1.11.1/specification/#object-container-files
+																"""
+						required: false
+						type: bool: default: false
+					}
+					schema: {
+						description: """
+																The Avro schema definition.
+																Please note that the following [`apache_avro::types::Value`] variants are currently *not* supported:
+																* `Date`
+																* `Decimal`
+																* `Duration`
+																* `Fixed`
+																* `TimeMillis`
+
+																Required, unless the schemas of the messages are read from object container files or
+																fetched from a schema registry. In that case, the records are resolved to this schema
+																when it's set.
+																"""
+						required: false
+						type: string: examples: ["{ \"type\": \"record\", \"name\": \"log\", \"fields\": [{ \"name\": \"message\", \"type\": \"string\" }] }"]
+					}
+					schema_registry: {
+						description: """
+																The schema registry to fetch the schemas of the messages from.
+
+																When set, the messages must be in the [Confluent wire format][wire_format], and each of them
+																is decoded with the schema of the ID it's prefixed with. The schemas are fetched once, and
+																reused for the following messages. A failure to fetch a schema is remembered for 30 seconds,
+																during which the messages prefixed with its ID are rejected without querying the registry
+																again.
+
+																[wire_format]: https://docs.confluent.io/platform/current/schema-registry/fundamentals/serdes-develop/index.html#wire-format
+																"""
+						required: false
+						type: object: options: {
+							password: {
+								description: "The password for the basic authentication to the schema registry."
+								required:    false
+								type: string: examples: ["${SCHEMA_REGISTRY_PASSWORD}"]
+							}
+							timeout_secs: {
+								description: "The timeout of the requests to the schema registry, in seconds."
+								required:    false
+								type: uint: {
+									default: 10
+									unit:    "seconds"
+								}
+							}
+							url: {
+								description: "The URL of the schema registry."
+								required:    true
+								type: string: examples: ["http://localhost:8081"]
+							}
+							username: {
+								description: "The username for the basic authentication to the schema registry."
+								required:    false
+								type: string: examples: ["${SCHEMA_REGISTRY_USERNAME}"]
+							}
+						}
+					}
+					strip_schema_id_prefix: {
+						description: """
+																For Avro datum encoded in Kafka messages, the bytes are prefixed with the schema ID.  Set this to true to strip the schema ID prefix.
+																According to [Confluent Kafka's document](https://docs.confluent.io/platform/current/schema-registry/fundamentals/serdes-develop/index.html#wire-format).
+																"""
+						required: false
+						type: bool: default: false
+					}
+				}
+			}
+			codec: {
+				description: "The codec to use for decoding events."
+				required:    false
+				type: string: {
+					default: "bytes"
+					enum: {
+						avro: """
+															Decodes the raw bytes as as an [Apache Avro][apache_avro] message.
+
+															[apache_avro]: https://avro.apache.org/
+															"""
+						bytes: "Uses the raw bytes as-is."
+						csv: """
+															Decodes the raw bytes as [CSV][csv] rows.
+
+															Each row becomes an event, with a field for each of its columns.
+
+															[csv]: https://datatracker.ietf.org/doc/html/rfc4180
+															"""
+						gelf: """
+															Decodes the raw bytes as a [GELF][gelf] message.
+
+															This codec is experimental for the following reason:
+
+															The GELF specification is more strict than the actual Graylog receiver.
+															Vector's decoder currently adheres more strictly to the GELF spec, with
+															the exception that some characters such as `@`  are allowed in field names.
+
+															Other GELF codecs such as Loki's, use a [Go SDK][implementation] that is maintained
+															by Graylog, and is much more relaxed than the GELF spec.
+
+															Going forward, Vector will use that [Go SDK][implementation] as the reference implementation, which means
+															the codec may continue to relax the enforcement of specification.
+
+															[gelf]: https://docs.graylog.org/docs/gelf
+															[implementation]: https://github.com/Graylog2/go-gelf/blob/v2/gelf/reader.go
+															"""
+						json: """
+															Decodes the raw bytes as [JSON][json].
+
+															[json]: https://www.json.org/
+															"""
+						msgpack: """
+															Decodes the raw bytes as [MessagePack][msgpack].
+
+															Each value of the frame becomes an event, and the values of arrays at its root become
+															separate events. Timestamps, including the `EventTime` values of the Fluentd forward
+															protocol, are decoded as timestamps.
+
+															[msgpack]: https://msgpack.org/
+															"""
+						native: """
+															Decodes the raw bytes as [native Protocol Buffers format][vector_native_protobuf].
+
+															This codec is **[experimental][experimental]**.
+
+															[vector_native_protobuf]: https://github.com/vectordotdev/vector/blob/master/lib/vector-core/proto/event.proto
+															[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
+															"""
+						native_json: """
+															Decodes the raw bytes as [native JSON format][vector_native_json].
+
+															This codec is **[experimental][experimental]**.
+
+															[vector_native_json]: https://github.com/vectordotdev/vector/blob/master/lib/codecs/tests/data/native_encoding/schema.cue
+															[experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
+															"""
+						otlp: """
+															Decodes the raw bytes as an [OTLP][otlp] export request encoded as protobuf.
+
+															Each log record or metric data point of the request becomes an event, converted the same
+															way as with the `opentelemetry` source.
+
+															[otlp]: https://opentelemetry.io/docs/specs/otlp/
+															"""
+						protobuf: """
+															Decodes the raw bytes as [protobuf][protobuf].
+
+															[protobuf]: https://protobuf.dev/
+															"""
+						syslog: """
+															Decodes the raw bytes as a Syslog message.
+
+															Decodes either as the [RFC 3164][rfc3164]-style format ("old" style) or the
+															[RFC 5424][rfc5424]-style format ("new" style, includes structured data).
+
+															[rfc3164]: https://www.ietf.org/rfc/rfc3164.txt
+															[rfc5424]: https://www.ietf.org/rfc/rfc5424.txt
+															"""
+						vrl: """
+															Decodes the raw bytes as a string and passes them as input to a [VRL][vrl] program.
+
+															[vrl]: https://vector.dev/docs/reference/vrl
+															"""
+					}
+				}
+			}
+			csv: {
+				description:   "CSV-specific decoding options."
+				relevant_when: "codec = \"csv\""
+				required:      false
+				type: object: options: {
+					delimiter: {
+						description: "The field delimiter to use when reading CSV."
+						required:    false
+						type: uint: default: 44
+					}
+					header: {
+						description: "The names of the fields of the rows."
+						required:    false
+						type: object: options: {
+							fields: {
+								description:   "The names of the fields, in the order of their columns."
+								relevant_when: "type = \"explicit\""
+								required:      true
+								type: array: items: type: string: examples: ["timestamp", "host", "message"]
+							}
+							type: {
+								description: "How the names of the fields are determined."
+								required:    false
+								type: string: {
+									default: "first_row"
+									enum: {
+										explicit: "The names of the fields are configured, and all the rows hold values."
+										first_row: """
+																The first row holds the names of the fields.
+
+																The first row of each stream, such as a file, an object, a connection, or a request
+																body, is the header of the following rows.
+																"""
+										positional: """
+																The fields are named after the position of their columns, starting with `column0`, and
+																all the rows hold values.
+																"""
+									}
+								}
+							}
+						}
+					}
+					infer_types: {
+						description: """
+																Whether the types of the values are inferred.
+
+																When enabled, the values that are integers, floats, or booleans (`true` or `false`, in any
+																case) are decoded as such, and empty values are decoded as `null`. Otherwise, all the
+																values are decoded as strings.
+																"""
+						required: false
+						type: bool: default: false
+					}
+					malformed_rows: {
+						description: "How the rows that don't have a value for each field are handled."
+						required:    false
+						type: string: {
+							default: "fail"
+							enum: {
+								fail: """
+																The frame of the row fails to decode, and is reported and dropped like other decoding
+																errors.
+																"""
+								raw: """
+																The row is decoded into an event with its text in the `message` field, and the reason it's
+																malformed in the `csv_error` field, so that it can be routed apart.
+																"""
+								skip: "The row is skipped, and the other rows of the frame are decoded."
+							}
+						}
+					}
+					quote: {
+						description: "The quote character to use when reading CSV."
+						required:    false
+						type: uint: default: 34
+					}
+				}
+			}
+			gelf: {
+				description:   "GELF-specific decoding options."
+				relevant_when: "codec = \"gelf\""
+				required:      false
+				type: object: options: lossy: {
+					description: """
+						Determines whether or not to replace invalid UTF-8 sequences instead of failing.
+
+						When true, invalid UTF-8 sequences are replaced with the [`U+FFFD REPLACEMENT CHARACTER`][U+FFFD].
+
+						[U+FFFD]: https://en.wikipedia.org/wiki/Specials_(Unicode_block)#Replacement_character
+						"""
+					required: false
+					type: bool: default: true
+				}
+			}
+			json: {
+				description:   "JSON-specific decoding options."
+				relevant_when: "codec = \"json\""
+				required:      false
+				type: object: options: lossy: {
+					description: """
+						Determines whether or not to replace invalid UTF-8 sequences instead of failing.
+
+						When true, invalid UTF-8 sequences are replaced with the [`U+FFFD REPLACEMENT CHARACTER`][U+FFFD].
+
+						[U+FFFD]: https://en.wikipedia.org/wiki/Specials_(Unicode_block)#Replacement_character
+						"""
+					required: false
+					type: bool: default: true
+				}
+			}
+			native_json: {
+				description:   "Vector's native JSON-specific decoding options."
+				relevant_when: "codec = \"native_json\""
+				required:      false
+				type: object: options: lossy: {
+					description: """
+						Determines whether or not to replace invalid UTF-8 sequences instead of failing.
+
+						When true, invalid UTF-8 sequences are replaced with the [`U+FFFD REPLACEMENT CHARACTER`][U+FFFD].
+
+						[U+FFFD]: https://en.wikipedia.org/wiki/Specials_(Unicode_block)#Replacement_character
+						"""
+					required: false
+					type: bool: default: true
+				}
+			}
+			otlp: {
+				description:   "OTLP-specific decoding options."
+				relevant_when: "codec = \"otlp\""
+				required:      false
+				type: object: options: signal: {
+					description: """
+						The kind of telemetry held by the payloads.
+
+						Payloads don't identify the kind of request they hold, so all the payloads decoded by a
+						component must hold the same one.
+						"""
+					required: false
+					type: string: {
+						default: "logs"
+						enum: {
+							logs:    "The payloads are `ExportLogsServiceRequest` messages, decoded as log events."
+							metrics: "The payloads are `ExportMetricsServiceRequest` messages, decoded as metric events."
+						}
+					}
+				}
+			}
+			protobuf: {
+				description:   "Protobuf-specific decoding options."
+				relevant_when: "codec = \"protobuf\""
+				required:      false
+				type: object: options: {
+					desc_file: {
+						description: "Path to desc file"
+						required:    false
+						type: string: default: ""
+					}
+					message_type: {
+						description: "message type. e.g package.message"
+						required:    false
+						type: string: default: ""
+					}
+				}
+			}
+			syslog: {
+				description:   "Syslog-specific decoding options."
+				relevant_when: "codec = \"syslog\""
+				required:      false
+				type: object: options: lossy: {
+					description: """
+						Determines whether or not to replace invalid UTF-8 sequences instead of failing.
+
+						When true, invalid UTF-8 sequences are replaced with the [`U+FFFD REPLACEMENT CHARACTER`][U+FFFD].
+
+						[U+FFFD]: https://en.wikipedia.org/wiki/Specials_(Unicode_block)#Replacement_character
+						"""
+					required: false
+					type: bool: default: true
+				}
+			}
+			vrl: {
+				description:   "VRL-specific decoding options."
+				relevant_when: "codec = \"vrl\""
+				required:      true
+				type: object: options: {
+					source: {
+						description: """
+																The [Vector Remap Language][vrl] (VRL) program to execute for each event.
+																Note that the final contents of the `.` target will be used as the decoding result.
+																Compilation error or use of 'abort' in a program will result in a decoding error.
+
+																[vrl]: https://vector.dev/docs/reference/vrl
+																"""
+						required: true
+						type: string: {}
+					}
+					timezone: {
+						description: """
+																The name of the timezone to apply to timestamp conversions that do not contain an explicit
+																time zone. The time zone name may be any name in the [TZ database][tz_database], or `local`
+																to indicate system local time.
+
+																If not set, `local` will be used.
+
+																[tz_database]: https://en.wikipedia.org/wiki/List_of_tz_database_time_zones
+																"""
+						required: false
+						type: string: examples: ["local", "America/New_York", "EST5EDT"]
+					}
+				}
+			}
+		}
+	}
+	endpoint: {
+		description: """
+			The Azure Blob Storage Endpoint URL.
+
+			This is used to override the default blob storage endpoint URL when authenticating with
+			`storage_account`. With `connection_string`, the blob endpoint of the connection string is
+			used instead.
+			"""
+		required: false
+		type: string: examples: ["https://test.blob.core.usgovcloudapi.net/", "https://test.blob.core.windows.net/"]
+	}
+	framing: {
+		description: """
+			Framing configuration.
+
+			Framing handles how events are separated when encoded in a raw byte form, where each event is
+			a frame that must be prefixed, or delimited, in a way that marks where an event begins and
+			ends within the byte stream.
+			"""
+		required: false
+		type: object: options: {
+			character_delimited: {
+				description:   "Options for the character delimited decoder."
+				relevant_when: "method = \"character_delimited\""
+				required:      true
+				type: object: options: {
+					delimiter: {
+						description: "The character that delimits byte sequences."
+						required:    true
+						type: uint: {}
+					}
+					max_length: {
+						description: """
+																The maximum length of the byte buffer.
+
+																This length does *not* include the trailing delimiter.
+
+																By default, there is no maximum length enforced. If events are malformed, this can lead to
+																additional resource usage as events continue to be buffered in memory, and can potentially
+																lead to memory exhaustion in extreme cases.
+
+																If there is a risk of processing malformed data, such as logs with user-controlled input,
+																consider setting the maximum length to a reasonably large value as a safety net. This
+																ensures that processing is not actually unbounded.
+																"""
+						required: false
+						type: uint: {}
+					}
+				}
+			}
+			chunked_gelf: {
+				description:   "Options for the chunked GELF decoder."
+				relevant_when: "method = \"chunked_gelf\""
+				required:      false
+				type: object: options: {
+					decompression: {
+						description: "The decompression applied to the messages."
+						required:    false
+						type: string: {
+							default: "auto"
+							enum: {
+								auto: """
+																Messages are decompressed according to the magic bytes they start with, if they're
+																compressed with gzip or zlib.
+																"""
+								gzip: """
+																Messages are compressed with [gzip][gzip].
+
+																[gzip]: https://www.gzip.org/
+																"""
+								none: "Messages are not compressed."
+								zlib: """
+																Messages are compressed with [zlib][zlib].
+
+																[zlib]: https://www.zlib.net/
+																"""
+							}
+						}
+					}
+					max_length: {
+						description: """
+																The maximum length of a message, once its chunks are reassembled and it's decompressed.
+
+																Longer messages are discarded. By default, there is no maximum length enforced. If the
+																messages are received from untrusted clients, consider setting the maximum length to a
+																reasonably large value as a safety net, as compressed messages can be much larger than the
+																chunks they're sent in.
+																"""
+						required: false
+						type: uint: {}
+					}
+					pending_messages_limit: {
+						description: """
+																The maximum number of messages that can wait for their chunks at once.
+
+																The chunks of new messages are discarded while the limit is reached. By default, there is no
+																limit enforced.
+																"""
+						required: false
+						type: uint: {}
+					}
+					timeout_secs: {
+						description: """
+																The time to wait for all the chunks of a message, after its first chunk is received.
+
+																The chunks of messages that aren't complete in time are discarded.
+																"""
+						required: false
+						type: uint: {
+							default: 5
+							unit:    "seconds"
+						}
+					}
+				}
+			}
+			length_delimited: {
+				description:   "Options for the length delimited decoder."
+				relevant_when: "method = \"length_delimited\""
+				required:      true
+				type: object: options: {
+					length_field_is_big_endian: {
+						description: "Length field byte order (little or big endian)"
+						required:    false
+						type: bool: default: true
+					}
+					length_field_length: {
+						description: "Number of bytes representing the field length"
+						required:    false
+						type: uint: default: 4
+					}
+					length_field_offset: {
+						description: "Number of bytes in the header before the length field"
+						required:    false
+						type: uint: default: 0
+					}
+					max_frame_length: {
+						description: "Maximum frame length"
+						required:    false
+						type: uint: default: 8388608
+					}
+				}
+			}
+			method: {
+				description: "The framing method."
+				required:    false
+				type: string: {
+					default: "newline_delimited"
+					enum: {
+						bytes:               "Byte frames are passed through as-is according to the underlying I/O boundaries (for example, split between messages or stream segments)."
+						character_delimited: "Byte frames which are delimited by a chosen character."
+						chunked_gelf: """
+															Byte frames which are [chunked GELF][chunking] messages, reassembled from their chunks and
+															decompressed.
+
+															This is meant for message-based transports such as UDP, which Graylog clients send chunked
+															and compressed messages over.
+
+															[chunking]: https://go2docs.graylog.org/current/getting_in_log_data/gelf.html#GELFviaUDP
+															"""
+						length_delimited: "Byte frames which are prefixed by an unsigned big-endian 32-bit integer indicating the length."
+						multiline: """
+															Byte frames which are lines aggregated into multi-line messages, such as stack traces, the
+															same way the `file` source aggregates them.
+															"""
+						newline_delimited: "Byte frames which are delimited by a newline character."
+						octet_counting: """
+															Byte frames according to the [octet counting][octet_counting] format.
+
+															[octet_counting]: https://tools.ietf.org/html/rfc6587#section-3.4.1
+															"""
+						varint_length_delimited: """
+															Byte frames which are prefixed by an unsigned [varint][varint] indicating the length.
+
+															This is the framing of streams of Protocol Buffers messages, as written by
+															`writeDelimitedTo` in Java or `SerializeDelimitedToOstream` in C++.
+
+															[varint]: https://protobuf.dev/programming-guides/encoding/#varints
+															"""
+					}
+				}
+			}
+			multiline: {
+				description:   "Options for the multiline decoder."
+				relevant_when: "method = \"multiline\""
+				required:      true
+				type: object: options: {
+					condition_pattern: {
+						description: """
+																Regular expression pattern that is used to determine whether or not more lines should be read.
+
+																This setting must be configured in conjunction with `mode`.
+																"""
+						required: true
+						type: string: examples: ["^[\\s]+", "\\\\$", "^(INFO|ERROR) ", ";$"]
+					}
+					mode: {
+						description: """
+																Aggregation mode.
+
+																This setting must be configured in conjunction with `condition_pattern`.
+																"""
+						required: true
+						type: string: enum: {
+							continue_past: """
+																All consecutive lines matching this pattern, plus one additional line, are included in the group.
+
+																This is useful in cases where a log message ends with a continuation marker, such as a backslash, indicating
+																that the following line is part of the same message.
+																"""
+							continue_through: """
+																All consecutive lines matching this pattern are included in the group.
+
+																The first line (the line that matched the start pattern) does not need to match the `ContinueThrough` pattern.
+
+																This is useful in cases such as a Java stack trace, where some indicator in the line (such as a leading
+																whitespace) indicates that it is an extension of the proceeding line.
+																"""
+							halt_before: """
+																All consecutive lines not matching this pattern are included in the group.
+
+																This is useful where a log line contains a marker indicating that it begins a new message.
+																"""
+							halt_with: """
+																All consecutive lines, up to and including the first line matching this pattern, are included in the group.
+
+																This is useful where a log line ends with a termination marker, such as a semicolon.
+																"""
+						}
+					}
+					start_pattern: {
+						description: "Regular expression pattern that is used to match the start of a new message."
+						required:    true
+						type: string: examples: ["^[\\s]+", "\\\\$", "^(INFO|ERROR) ", ";$"]
+					}
+					timeout_ms: {
+						description: """
+																The maximum amount of time to wait for the next additional line, in milliseconds.
+
+																Once this timeout is reached, the buffered message is flushed, even if incomplete, as soon
+																as more data is received or the stream ends.
+																"""
+						required: true
+						type: uint: {
+							examples: [1000, 600000]
+							unit: "milliseconds"
+						}
+					}
+				}
+			}
+			newline_delimited: {
+				description:   "Options for the newline delimited decoder."
+				relevant_when: "method = \"newline_delimited\""
+				required:      false
+				type: object: options: max_length: {
+					description: """
+						The maximum length of the byte buffer.
+
+						This length does *not* include the trailing delimiter.
+
+						By default, there is no maximum length enforced. If events are malformed, this can lead to
+						additional resource usage as events continue to be buffered in memory, and can potentially
+						lead to memory exhaustion in extreme cases.
+
+						If there is a risk of processing malformed data, such as logs with user-controlled input,
+						consider setting the maximum length to a reasonably large value as a safety net. This
+						ensures that processing is not actually unbounded.
+						"""
+					required: false
+					type: uint: {}
+				}
+			}
+			octet_counting: {
+				description:   "Options for the octet counting decoder."
+				relevant_when: "method = \"octet_counting\""
+				required:      false
+				type: object: options: max_length: {
+					description: "The maximum length of the byte buffer."
+					required:    false
+					type: uint: {}
+				}
+			}
+			varint_length_delimited: {
+				description:   "Options for the varint length delimited decoder."
+				relevant_when: "method = \"varint_length_delimited\""
+				required:      false
+				type: object: options: max_frame_length: {
+					description: "Maximum frame length"
+					required:    false
+					type: uint: {
+						default: 8388608
+						unit:    "bytes"
+					}
+				}
+			}
+		}
+	}
+	multiline: {
+		description: """
+			Multiline aggregation configuration.
+
+			If not specified, multiline aggregation is disabled.
+			"""
+		required: false
+		type: object: options: {
+			condition_pattern: {
+				description: """
+					Regular expression pattern that is used to determine whether or not more lines should be read.
+
+					This setting must be configured in conjunction with `mode`.
+					"""
+				required: true
+				type: string: examples: ["^[\\s]+", "\\\\$", "^(INFO|ERROR) ", ";$"]
+			}
+			mode: {
+				description: """
+					Aggregation mode.
+
+					This setting must be configured in conjunction with `condition_pattern`.
+					"""
+				required: true
+				type: string: enum: {
+					continue_past: """
+						All consecutive lines matching this pattern, plus one additional line, are included in the group.
+
+						This is useful in cases where a log message ends with a continuation marker, such as a backslash, indicating
+						that the following line is part of the same message.
+						"""
+					continue_through: """
+						All consecutive lines matching this pattern are included in the group.
+
+						The first line (the line that matched the start pattern) does not need to match the `ContinueThrough` pattern.
+
+						This is useful in cases such as a Java stack trace, where some indicator in the line (such as a leading
+						whitespace) indicates that it is an extension of the proceeding line.
+						"""
+					halt_before: """
+						All consecutive lines not matching this pattern are included in the group.
+
+						This is useful where a log line contains a marker indicating that it begins a new message.
+						"""
+					halt_with: """
+						All consecutive lines, up to and including the first line matching this pattern, are included in the group.
+
+						This is useful where a log line ends with a termination marker, such as a semicolon.
+						"""
+				}
+			}
+			start_pattern: {
+				description: "Regular expression pattern that is used to match the start of a new message."
+				required:    true
+				type: string: examples: ["^[\\s]+", "\\\\$", "^(INFO|ERROR) ", ";$"]
+			}
+			timeout_ms: {
+				description: """
+					The maximum amount of time to wait for the next additional line, in milliseconds.
+
+					Once this timeout is reached, the buffered message is guaranteed to be flushed, even if incomplete.
+					"""
+				required: true
+				type: uint: {
+					examples: [1000, 600000]
+					unit: "milliseconds"
+				}
+			}
+		}
+	}
+	queue: {
+		description: "Configuration options for the storage queue receiving blob events."
+		required:    true
+		type: object: options: {
+			client_concurrency: {
+				description: """
+					Number of concurrent tasks to create for polling the queue for messages.
+
+					Defaults to the number of available CPUs on the system.
+
+					Should not typically need to be changed, but it can sometimes be beneficial to raise this
+					value when there is a high rate of messages being pushed into the queue and the blobs
+					being fetched are small. In these cases, system resources may not be fully utilized without
+					fetching more messages per second, as the message consumption rate affects the blob
+					retrieval rate.
+					"""
+				required: false
+				type: uint: {
+					examples: [5]
+					unit: "tasks"
+				}
+			}
+			delete_failed_message: {
+				description: """
+					Whether to delete non-retryable messages.
+
+					If a message is rejected by the sink and not retryable, it is deleted from the queue.
+					"""
+				required: false
+				type: bool: default: true
+			}
+			endpoint: {
+				description: """
+					The Azure Queue Storage endpoint URL.
+
+					This is used to override the default queue endpoint URL when authenticating with
+					`storage_account`. With `connection_string`, the queue endpoint of the connection string is
+					used instead.
+					"""
+				required: false
+				type: string: examples: ["https://mylogstorage.queue.core.windows.net/"]
+			}
+			max_number_of_messages: {
+				description: """
+					Maximum number of messages to fetch from the queue in a batch.
+
+					Valid values are 1 - 32.
+					"""
+				required: false
+				type: uint: {
+					default: 10
+					examples: [1]
+				}
+			}
+			poll_secs: {
+				description: "How long to wait before polling the queue again after it was found empty, in seconds."
+				required:    false
+				type: uint: {
+					default: 15
+					unit:    "seconds"
+				}
+			}
+			queue_name: {
+				description: """
+					The name of the storage queue to poll for blob notifications.
+
+					The queue must be in the same storage account as the blobs, and receive the
+					`Microsoft.Storage.BlobCreated` events of an [Event Grid subscription][event_grid].
+
+					[event_grid]: https://learn.microsoft.com/en-us/azure/storage/blobs/storage-blob-event-overview
+					"""
+				required: true
+				type: string: examples: ["blob-created"]
+			}
+			visibility_timeout_secs: {
+				description: """
+					The visibility timeout to use for messages, in seconds.
+
+					This controls how long a message is left unavailable after it is received. If a message is
+					received, and takes longer than `visibility_timeout_secs` to process and delete the message
+					from the queue, it is made available again for another consumer.
+
+					This can happen if there is an issue between consuming a message and deleting it.
+					"""
+				required: false
+				type: uint: {
+					default: 300
+					unit:    "seconds"
+				}
+			}
+		}
+	}
+	storage_account: {
+		description: """
+			The Azure Blob Storage Account name.
+
+			Attempts to load credentials for the account in the following ways, in order:
+
+			- read from environment variables ([more information][env_cred_docs])
+			- looks for a [Managed Identity][managed_ident_docs]
+			- uses the `az` CLI tool to get an access token ([more information][az_cli_docs])
+
+			Either `connection_string`, or this field, must be specified.
+
+			[env_cred_docs]: https://docs.rs/azure_identity/latest/azure_identity/struct.EnvironmentCredential.html
+			[managed_ident_docs]: https://docs.microsoft.com/en-us/azure/active-directory/managed-identities-azure-resources/overview
+			[az_cli_docs]: https://docs.microsoft.com/en-us/cli/azure/account?view=azure-cli-latest#az-account-get-access-token
+			"""
+		required: false
+		type: string: examples: ["mylogstorage"]
+	}
+}
@@ -0,0 +1,65 @@
+package metadata
+
+base: components: sources: collectd: configuration: {
+	address: {
+		description: """
+			The socket address to listen for connections on, or `systemd{#N}` to use the Nth socket passed by
+			systemd socket activation.
+
+			If a socket address is used, it _must_ include a port.
+			"""
+		required: true
+		type: string: examples: ["0.0.0.0:9000", "systemd", "systemd#3"]
+	}
+	auth_file: {
+		description: """
+			The path to a file with the passwords of the users that sign and encrypt packets.
+
+			The file uses the format of collectd's `AuthFile`, with one `<user>: <password>` pair per
+			line. Required unless `security_level` is `none`, in which case it is used to verify the
+			signatures and decrypt the packets of known users.
+			"""
+		required: false
+		type: string: examples: ["/etc/collectd/passwd"]
+	}
+	namespace: {
+		description: """
+			The namespace of the metrics.
+
+			Disabled if empty.
+			"""
+		required: false
+		type: string: default: "collectd"
+	}
+	receive_buffer_bytes: {
+		description: "The size of the receive buffer used for the listening socket."
+		required:    false
+		type: uint: unit: "bytes"
+	}
+	security_level: {
+		description: "The minimum protection of accepted values."
+		required:    false
+		type: string: {
+			default: "none"
+			enum: {
+				encrypt: "Only accept encrypted values."
+				none:    "Accept all values."
+				sign:    "Only accept signed or encrypted values."
+			}
+		}
+	}
+	types_db: {
+		description: """
+			Paths to `types.db` files describing the data sources of each collectd type.
+
+			Value lists with several values emit one metric per value, named after the value's data
+			source. Without a description of its type, the values are named after their position
+			instead.
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: string: examples: ["/usr/share/collectd/types.db"]
+		}
+	}
+}
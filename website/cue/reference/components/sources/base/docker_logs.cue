@@ -6,6 +6,37 @@ base: components: sources: docker_logs: configuration: {
 		required:    false
 		type: bool: default: true
 	}
+	containerd_socket: {
+		description: "The socket of the gRPC API of containerd, when the `containerd` runtime is used."
+		required:    false
+		type: string: {
+			default: "/run/containerd/containerd.sock"
+			examples: ["/run/containerd/containerd.sock", "/run/k3s/containerd/containerd.sock"]
+		}
+	}
+	cri_log_directory: {
+		description: "The directory of the CRI log files, when the `containerd` runtime is used."
+		required:    false
+		type: string: {
+			default: "/var/log/containers"
+			examples: ["/var/log/containers"]
+		}
+	}
+	data_dir: {
+		description: """
+			The directory used to persist the checkpoints of the CRI log files, when the `containerd`
+			runtime is used.
+
+			By default, the [global `data_dir` option][global_data_dir] is used.
+			Make sure the running user has write permissions to this directory.
+
+			If this directory is specified, then Vector will attempt to create it.
+
+			[global_data_dir]: https://vector.dev/docs/reference/configuration/global-options/#data_dir
+			"""
+		required: false
+		type: string: examples: ["/var/local/lib/vector/"]
+	}
 	docker_host: {
 		description: """
 			Docker host to connect to.
@@ -168,6 +199,57 @@ base: components: sources: docker_logs: configuration: {
 			unit:    "seconds"
 		}
 	}
+	runtime: {
+		description: "The container engine to collect logs from."
+		required:    false
+		type: string: {
+			default: "docker"
+			enum: {
+				containerd: """
+					[containerd][containerd], by reading the log files written by its CRI plugin and by
+					[nerdctl][nerdctl].
+
+					The containers of all the namespaces, with their name, image, labels, and creation
+					timestamp, are listed through the gRPC API of containerd at `containerd_socket`.
+
+					The log files of the CRI plugin are read from `cri_log_directory`, and are named
+					`<pod>_<namespace>_<container>-<container ID>.log`, such as the links kept in
+					`/var/log/containers` by the kubelet. Their lines are read in the
+					[CRI log format][cri_log_format]. The log files of nerdctl are read from the state
+					directory of their container, in the format of the `json-file` logging driver of Docker.
+					The containers without a log file, such as the ones started by `ctr run`, are not read.
+					Partial lines are merged by stream if `auto_partial_merge` is enabled.
+
+					containerd has no Docker-compatible API, so the `docker_host`, `tls`, `include_labels`,
+					and `include_images` options are rejected.
+
+					The files are read from their end when the source starts for the first time, and from
+					their checkpoint in `data_dir` afterwards. Files created while the source runs are read
+					from their beginning.
+
+					[containerd]: https://containerd.io/
+					[nerdctl]: https://github.com/containerd/nerdctl
+					[cri_log_format]: https://github.com/kubernetes/design-proposals-archive/blob/main/node/kubelet-cri-logging.md
+					"""
+				docker: """
+					[Docker Engine][docker].
+
+					[docker]: https://docs.docker.com/engine/
+					"""
+				podman: """
+					[Podman][podman], through its Docker-compatible API service.
+
+					If `docker_host` is not set, the `CONTAINER_HOST` environment variable is used. If
+					`CONTAINER_HOST` is also absent, the rootless socket at `$XDG_RUNTIME_DIR/podman/podman.sock`
+					is used if it exists, falling back to the rootful socket at `/run/podman/podman.sock`.
+
+					The Podman API service must be running, for example with `systemctl enable --now podman.socket`.
+
+					[podman]: https://podman.io/
+					"""
+			}
+		}
+	}
 	tls: {
 		description: """
 			Configuration of TLS when connecting to the Docker daemon.
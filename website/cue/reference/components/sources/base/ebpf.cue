@@ -0,0 +1,49 @@
+package metadata
+
+base: components: sources: ebpf: configuration: {
+	container_attribution: {
+		description: """
+			Whether to attribute events to the container the process runs in.
+
+			The container is found from the cgroup of the process, read from `/proc`, or from the
+			directory set in the `PROCFS_ROOT` environment variable. Events of processes that exited
+			before the first event of their cgroup was handled are not attributed.
+			"""
+		required: false
+		type: bool: default: true
+	}
+	events: {
+		description: """
+			The kinds of events to collect.
+
+			Only the eBPF programs needed to collect these events are attached.
+			"""
+		required: false
+		type: array: {
+			default: ["process_exec", "process_exit", "tcp_connect", "tcp_accept", "tcp_close"]
+			items: type: string: {
+				enum: {
+					process_exec: "A process executed a new program."
+					process_exit: "A process exited."
+					tcp_accept:   "A process accepted an incoming TCP connection."
+					tcp_close:    "A TCP connection opened or accepted by a process was closed."
+					tcp_connect:  "A process opened an outgoing TCP connection."
+				}
+				examples: ["process_exec", "tcp_connect"]
+			}
+		}
+	}
+	ring_buffer_bytes: {
+		description: """
+			The size of the ring buffer the kernel submits events through, in bytes.
+
+			Must be a power of two and a multiple of the page size. Events submitted while the ring
+			buffer is full are lost, and reported with the `component_discarded_events_total` metric.
+			"""
+		required: false
+		type: uint: {
+			default: 262144
+			unit:    "bytes"
+		}
+	}
+}
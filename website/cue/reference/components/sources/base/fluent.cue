@@ -65,6 +65,58 @@ base: components: sources: fluent: configuration: {
 			unit: "bytes"
 		}
 	}
+	security: {
+		description: """
+			Authentication settings of the forward protocol handshake.
+
+			When set, clients must authenticate with the shared key, and with one of `users` if any,
+			before sending any message.
+
+			See the [forward protocol specification][spec] for more information.
+
+			[spec]: https://github.com/fluent/fluentd/wiki/Forward-Protocol-Specification-v1#handshake-messages
+			"""
+		required: false
+		type: object: options: {
+			self_hostname: {
+				description: """
+					The hostname of Vector sent to the clients during the handshake.
+
+					By default, the hostname of the host Vector is running on.
+					"""
+				required: false
+				type: string: examples: ["vector-aggregator-0"]
+			}
+			shared_key: {
+				description: "The key shared with the clients, set as `shared_key` in their `<security>` section."
+				required:    true
+				type: string: examples: ["${FLUENT_SHARED_KEY}"]
+			}
+			users: {
+				description: """
+					The users the clients authenticate as, in addition to the shared key.
+
+					If empty, clients are not required to authenticate as a user.
+					"""
+				required: false
+				type: array: {
+					default: []
+					items: type: object: options: {
+						password: {
+							description: "The password of the user."
+							required:    true
+							type: string: examples: ["${FLUENT_PASSWORD}"]
+						}
+						username: {
+							description: "The name of the user."
+							required:    true
+							type: string: examples: ["td-agent"]
+						}
+					}
+				}
+			}
+		}
+	}
 	tls: {
 		description: "TlsEnableableConfig for `sources`, adding metadata from the client certificate."
 		required:    false
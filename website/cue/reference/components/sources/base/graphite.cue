@@ -0,0 +1,202 @@
+package metadata
+
+base: components: sources: graphite: configuration: {
+	address: {
+		description: """
+			The socket address to listen for connections on, or `systemd{#N}` to use the Nth socket passed by
+			systemd socket activation.
+
+			If a socket address is used, it _must_ include a port.
+			"""
+		required: true
+		type: string: examples: ["0.0.0.0:9000", "systemd", "systemd#3"]
+	}
+	connection_limit: {
+		description:   "The maximum number of TCP connections that are allowed at any given time."
+		relevant_when: "mode = \"tcp\""
+		required:      false
+		type: uint: unit: "connections"
+	}
+	keepalive: {
+		description:   "TCP keepalive settings for socket-based components."
+		relevant_when: "mode = \"tcp\""
+		required:      false
+		type: object: options: time_secs: {
+			description: "The time to wait before starting to send TCP keepalive probes on an idle connection."
+			required:    false
+			type: uint: unit: "seconds"
+		}
+	}
+	mode: {
+		description: "The type of socket to use."
+		required:    true
+		type: string: enum: {
+			tcp: "Listen on TCP."
+			udp: "Listen on UDP."
+		}
+	}
+	permit_origin: {
+		description:   "List of allowed origin IP networks. IP addresses must be in CIDR notation."
+		relevant_when: "mode = \"tcp\""
+		required:      false
+		type: array: items: type: string: examples: ["192.168.0.0/16", "127.0.0.1/32", "::1/128", "9876:9ca3:99ab::23/128"]
+	}
+	protocol: {
+		description:   "The protocol metrics are sent with."
+		relevant_when: "mode = \"tcp\""
+		required:      false
+		type: string: {
+			default: "plaintext"
+			enum: {
+				pickle: """
+					Pickled lists of `(path, (timestamp, value))` tuples, each message prefixed by its length
+					as a four-byte big-endian integer.
+					"""
+				plaintext: "One metric per line, as `<path> <value> <timestamp>`."
+			}
+		}
+	}
+	receive_buffer_bytes: {
+		description: "The size of the receive buffer used for each connection."
+		required:    false
+		type: uint: unit: "bytes"
+	}
+	separator: {
+		description: "The separator used to join the `measurement` segments of a path into the metric name."
+		required:    false
+		type: string: {
+			default: "."
+			examples: ["_"]
+		}
+	}
+	shutdown_timeout_secs: {
+		description:   "The timeout before a connection is forcefully closed during shutdown."
+		relevant_when: "mode = \"tcp\""
+		required:      false
+		type: uint: {
+			default: 30
+			unit:    "seconds"
+		}
+	}
+	templates: {
+		description: """
+			Templates extracting the name and tags of metrics from their dotted paths.
+
+			Each template has the form `[filter] template [tag=value,...]`. The template assigns a
+			meaning to each segment of the path: `measurement` segments make up the metric name,
+			`measurement*` takes all remaining segments into the name, other names set the tag of that
+			name to the segment, and empty parts skip the segment. The optional filter restricts the
+			template to paths starting with the given segments, `*` matching any segment, and the
+			optional tags are added to every metric the template applies to.
+
+			The first template whose filter matches the path is used. Metrics matching no template are
+			named after their full path.
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: string: examples: ["servers.* .host.measurement*", "stats.* .measurement.measurement.region datacenter=us-east", "measurement*"]
+		}
+	}
+	tls: {
+		description:   "TlsEnableableConfig for `sources`, adding metadata from the client certificate."
+		relevant_when: "mode = \"tcp\""
+		required:      false
+		type: object: options: {
+			alpn_protocols: {
+				description: """
+					Sets the list of supported ALPN protocols.
+
+					Declare the supported ALPN protocols, which are used during negotiation with peer. They are prioritized in the order
+					that they are defined.
+					"""
+				required: false
+				type: array: items: type: string: examples: ["h2"]
+			}
+			ca_file: {
+				description: """
+					Absolute path to an additional CA certificate file.
+
+					The certificate must be in the DER or PEM (X.509) format. Additionally, the certificate can be provided as an inline string in PEM format.
+					"""
+				required: false
+				type: string: examples: ["/path/to/certificate_authority.crt"]
+			}
+			client_metadata_key: {
+				description: "Event field for client certificate metadata."
+				required:    false
+				type: string: {}
+			}
+			crt_file: {
+				description: """
+					Absolute path to a certificate file used to identify this server.
+
+					The certificate must be in DER, PEM (X.509), or PKCS#12 format. Additionally, the certificate can be provided as
+					an inline string in PEM format.
+
+					If this is set, and is not a PKCS#12 archive, `key_file` must also be set.
+					"""
+				required: false
+				type: string: examples: ["/path/to/host_certificate.crt"]
+			}
+			enabled: {
+				description: """
+					Whether or not to require TLS for incoming or outgoing connections.
+
+					When enabled and used for incoming connections, an identity certificate is also required. See `tls.crt_file` for
+					more information.
+					"""
+				required: false
+				type: bool: {}
+			}
+			key_file: {
+				description: """
+					Absolute path to a private key file used to identify this server.
+
+					The key must be in DER or PEM (PKCS#8) format. Additionally, the key can be provided as an inline string in PEM format.
+					"""
+				required: false
+				type: string: examples: ["/path/to/host_certificate.key"]
+			}
+			key_pass: {
+				description: """
+					Passphrase used to unlock the encrypted key file.
+
+					This has no effect unless `key_file` is set.
+					"""
+				required: false
+				type: string: examples: ["${KEY_PASS_ENV_VAR}", "PassWord1"]
+			}
+			verify_certificate: {
+				description: """
+					Enables certificate verification. For components that create a server, this requires that the
+					client connections have a valid client certificate. For components that initiate requests,
+					this validates that the upstream has a valid certificate.
+
+					If enabled, certificates must not be expired and must be issued by a trusted
+					issuer. This verification operates in a hierarchical manner, checking that the leaf certificate (the
+					certificate presented by the client/server) is not only valid, but that the issuer of that certificate is also valid, and
+					so on until the verification process reaches a root certificate.
+
+					Do NOT set this to `false` unless you understand the risks of not verifying the validity of certificates.
+					"""
+				required: false
+				type: bool: {}
+			}
+			verify_hostname: {
+				description: """
+					Enables hostname verification.
+
+					If enabled, the hostname used to connect to the remote host must be present in the TLS certificate presented by
+					the remote host, either as the Common Name or as an entry in the Subject Alternative Name extension.
+
+					Only relevant for outgoing connections.
+
+					Do NOT set this to `false` unless you understand the risks of not verifying the remote hostname.
+					"""
+				required: false
+				type: bool: {}
+			}
+		}
+	}
+}
@@ -83,12 +83,17 @@ base: components: sources: host_metrics: configuration: {
 					cpu:        "Metrics related to CPU utilization."
 					disk:       "Metrics related to disk I/O utilization."
 					filesystem: "Metrics related to filesystem space utilization."
-					host:       "Metrics related to the host."
-					load:       "Metrics related to the system load average."
-					memory:     "Metrics related to memory utilization."
-					network:    "Metrics related to network utilization."
+					gpu: """
+						Metrics related to GPU utilization.
+
+						Only available on Linux. Not collected by default.
+						"""
+					host:    "Metrics related to the host."
+					load:    "Metrics related to the system load average."
+					memory:  "Metrics related to memory utilization."
+					network: "Metrics related to network utilization."
 				}
-				examples: ["cgroups", "cpu", "disk", "filesystem", "load", "host", "memory", "network"]
+				examples: ["cgroups", "cpu", "disk", "filesystem", "gpu", "load", "host", "memory", "network"]
 			}
 		}
 	}
@@ -246,6 +251,45 @@ base: components: sources: host_metrics: configuration: {
 			}
 		}
 	}
+	gpu: {
+		description: """
+			Options for the GPU metrics collector.
+
+			This collector is only available on Linux.
+			"""
+		required: false
+		type: object: options: {
+			processes: {
+				description: """
+					Whether to report the GPU memory and utilization of each process using a GPU.
+
+					Only available for NVIDIA GPUs.
+					"""
+				required: false
+				type: bool: default: true
+			}
+			vendors: {
+				description: """
+					The vendors of the GPUs to report metrics for.
+
+					NVIDIA GPUs are read with the NVIDIA Management Library (NVML), loaded from the driver
+					installed on the host. AMD GPUs are read from the sysfs interface of the `amdgpu` driver,
+					which is also the source of the ROCm System Management Interface.
+					"""
+				required: false
+				type: array: {
+					default: ["nvidia"]
+					items: type: string: {
+						enum: {
+							amd:    "AMD GPUs."
+							nvidia: "NVIDIA GPUs."
+						}
+						examples: ["nvidia", "amd"]
+					}
+				}
+			}
+		}
+	}
 	namespace: {
 		description: "Overrides the default namespace for the metrics emitted by the source."
 		required:    false
@@ -0,0 +1,57 @@
+package metadata
+
+base: components: sources: kubernetes_events: configuration: {
+	emit_count_updates: {
+		description: """
+			Whether to collect an event again each time its `count` increases.
+
+			Kubernetes records the repeated occurrences of an event by increasing the `count` of the
+			existing event rather than creating a new one. When disabled, each event is only collected
+			the first time it is seen.
+			"""
+		required: false
+		type: bool: default: true
+	}
+	field_selector: {
+		description: """
+			A field selector restricting the collected events.
+
+			See [Kubernetes field selectors](https://kubernetes.io/docs/concepts/overview/working-with-objects/field-selectors/)
+			for the syntax.
+			"""
+		required: false
+		type: string: examples: ["type=Warning", "involvedObject.kind=Pod"]
+	}
+	include_existing: {
+		description: """
+			Whether to collect the events already stored by the API server when the source starts.
+
+			By default, only the events occurring after the source started are collected.
+			"""
+		required: false
+		type: bool: default: false
+	}
+	kube_config_file: {
+		description: """
+			Optional path to a readable [kubeconfig][kubeconfig] file.
+
+			If not set, a connection to Kubernetes is made using the in-cluster configuration.
+
+			[kubeconfig]: https://kubernetes.io/docs/concepts/configuration/organize-cluster-access-kubeconfig/
+			"""
+		required: false
+		type: string: examples: ["/path/to/.kube/config"]
+	}
+	namespaces: {
+		description: """
+			The namespaces to watch the events of.
+
+			If empty, the events of all namespaces are collected.
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: string: examples: ["default", "kube-system"]
+		}
+	}
+}
@@ -390,6 +390,21 @@ base: components: sources: kubernetes_logs: configuration: {
 					examples: [".k8s.pod_owner", "k8s.pod_owner", ""]
 				}
 			}
+			pod_owner_references: {
+				description: """
+					Event field for all of the Pod's owner references.
+
+					Each reference is an object with the `api_version`, `kind`, `name`, `uid` and `controller`
+					fields of the owner. Unlike `pod_owner`, this includes every owner, not just the first one.
+
+					Disabled by default. Set to a path to enable this key.
+					"""
+				required: false
+				type: string: {
+					default: ""
+					examples: [".k8s.pod_owner_references", "k8s.pod_owner_references"]
+				}
+			}
 			pod_uid: {
 				description: """
 					Event field for the Pod's UID.
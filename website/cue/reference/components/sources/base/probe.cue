@@ -0,0 +1,75 @@
+package metadata
+
+base: components: sources: probe: configuration: {
+	interval_secs: {
+		description: "The interval between probes."
+		required:    false
+		type: uint: {
+			default: 15
+			unit:    "seconds"
+		}
+	}
+	namespace: {
+		description: """
+			Overrides the default namespace for the metrics emitted by the source.
+
+			If set to an empty string, no namespace is added to the metrics.
+
+			By default, `probe` is used.
+			"""
+		required: false
+		type: string: default: "probe"
+	}
+	targets: {
+		description: "A list of targets to probe."
+		required:    true
+		type: array: items: type: object: options: {
+			address: {
+				description:   "The address of the target, in the form of `host:port`."
+				relevant_when: "protocol = \"tcp\""
+				required:      true
+				type: string: examples: ["example.com:443", "10.0.0.5:5432"]
+			}
+			host: {
+				description:   "The host name or IP address of the target."
+				relevant_when: "protocol = \"icmp\""
+				required:      true
+				type: string: examples: ["192.168.1.1", "example.com"]
+			}
+			packets: {
+				description:   "The number of echo requests sent to the target on each probe."
+				relevant_when: "protocol = \"icmp\""
+				required:      false
+				type: uint: default: 3
+			}
+			protocol: {
+				description: "The protocol used to probe the target."
+				required:    true
+				type: string: enum: {
+					icmp: """
+						Sends ICMP echo requests to the target.
+
+						Unprivileged ICMP sockets are used where the operating system allows them, which on Linux
+						requires the group of Vector to be included in the `net.ipv4.ping_group_range` sysctl.
+						Otherwise, raw sockets are used, which require the `CAP_NET_RAW` capability.
+						"""
+					tcp: "Opens TCP connections to the target."
+				}
+			}
+		}
+	}
+	timeout_secs: {
+		description: """
+			The maximum time to wait for a TCP connection to be established, or for the reply to an
+			ICMP echo request.
+
+			A probe that does not complete in time is considered failed, and an echo request whose
+			reply does not arrive in time is considered lost.
+			"""
+		required: false
+		type: float: {
+			default: 5.0
+			unit:    "seconds"
+		}
+	}
+}
@@ -58,9 +58,42 @@ base: components: sources: prometheus_scrape: configuration: {
 		type: string: {}
 	}
 	endpoints: {
-		description: "Endpoints to scrape metrics from."
-		required:    true
-		type: array: items: type: string: examples: ["http://localhost:9090/metrics"]
+		description: """
+			Endpoints to scrape metrics from.
+
+			These are scraped in addition to any targets found through service discovery.
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: string: examples: ["http://localhost:9090/metrics"]
+		}
+	}
+	file_sd_configs: {
+		description: "Discovers targets to scrape from files."
+		required:    false
+		type: array: {
+			default: []
+			items: type: object: options: {
+				files: {
+					description: """
+						The files to read targets from.
+
+						Glob patterns are supported.
+						"""
+					required: true
+					type: array: items: type: string: examples: ["/etc/vector/targets/*.json"]
+				}
+				refresh_interval_secs: {
+					description: "The interval between reads of the files."
+					required:    false
+					type: uint: {
+						default: 60
+						unit:    "seconds"
+					}
+				}
+			}
+		}
 	}
 	honor_labels: {
 		description: """
@@ -83,6 +116,73 @@ base: components: sources: prometheus_scrape: configuration: {
 		required: false
 		type: string: {}
 	}
+	kubernetes_sd_configs: {
+		description: "Discovers targets to scrape from the Kubernetes API."
+		required:    false
+		type: array: {
+			default: []
+			items: type: object: options: {
+				field_selector: {
+					description: """
+						Only discovers targets from objects matching this [field selector][field_selector].
+
+						[field_selector]: https://kubernetes.io/docs/concepts/overview/working-with-objects/field-selectors/
+						"""
+					required: false
+					type: string: examples: ["status.phase=Running"]
+				}
+				kube_config_file: {
+					description: """
+						Optional path to a readable [kubeconfig][kubeconfig] file.
+
+						If not set, a connection to Kubernetes is made using the in-cluster configuration.
+
+						[kubeconfig]: https://kubernetes.io/docs/concepts/configuration/organize-cluster-access-kubeconfig/
+						"""
+					required: false
+					type: string: examples: ["/path/to/.kube/config"]
+				}
+				label_selector: {
+					description: """
+						Only discovers targets from objects matching this [label selector][label_selector].
+
+						[label_selector]: https://kubernetes.io/docs/concepts/overview/working-with-objects/labels/#label-selectors
+						"""
+					required: false
+					type: string: examples: ["app.kubernetes.io/part-of=shop"]
+				}
+				namespaces: {
+					description: """
+						The namespaces to discover targets in.
+
+						If empty, targets are discovered in all namespaces.
+						"""
+					required: false
+					type: array: {
+						default: []
+						items: type: string: examples: ["monitoring"]
+					}
+				}
+				role: {
+					description: "The kind of Kubernetes object targets are discovered from."
+					required:    true
+					type: string: enum: {
+						endpoints: """
+							Discovers a target for each address and port of every endpoints object.
+
+							Endpoints share the name of the service they belong to.
+							"""
+						pod: """
+							Discovers a target for each port declared by the containers of every pod.
+
+							Containers that don't declare any ports are discovered as a single target on the pod IP,
+							with the port expected to be added by relabeling.
+							"""
+					}
+				}
+			}
+		}
+	}
 	query: {
 		description: """
 			Custom parameters for the scrape request query string.
@@ -103,6 +203,95 @@ base: components: sources: prometheus_scrape: configuration: {
 			}
 		}
 	}
+	relabel_configs: {
+		description: """
+			Rules rewriting or filtering the targets found through service discovery, applied in order.
+
+			Each target starts with the `__address__` label set to its discovered address, the
+			`__scheme__` and `__metrics_path__` labels set to `http` and `/metrics`, and the `__meta_*`
+			labels of its discovery mechanism. Once all rules have been applied, the target is scraped
+			at `<__scheme__>://<__address__><__metrics_path__>`, with each `__param_<name>` label added to
+			the query string, and its remaining labels that don't start with `__` are added as tags to
+			the scraped metrics.
+
+			`endpoints` are not subject to relabeling.
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: object: options: {
+				action: {
+					description: "The action performed by a relabeling rule."
+					required:    false
+					type: string: {
+						default: "replace"
+						enum: {
+							drop:      "Drops the target if `regex` matches the source value."
+							hashmod: """
+								Sets `target_label` to the hash of the source value, modulo `modulus`.
+
+								This is typically combined with a `keep` rule to shard targets across several instances.
+								"""
+							keep:      "Drops the target if `regex` does not match the source value."
+							labeldrop: "Removes every label whose name matches `regex`."
+							labelkeep: "Removes every label whose name does not match `regex`."
+							labelmap:  "Copies every label whose name matches `regex` to the label named by `replacement`."
+							replace: """
+								Sets `target_label` to `replacement` if `regex` matches the source value.
+
+								Capture groups in `replacement` are expanded with the matched values.
+								"""
+						}
+					}
+				}
+				modulus: {
+					description: "The modulus used by the `hashmod` action."
+					required:    false
+					type: uint: {}
+				}
+				regex: {
+					description: """
+						The regular expression matched against the source value, or against label names for the
+						`labelmap`, `labeldrop`, and `labelkeep` actions.
+
+						The expression is anchored at both ends.
+						"""
+					required: false
+					type: string: {
+						default: "(.*)"
+						examples: ["true", "__meta_kubernetes_pod_label_(.+)"]
+					}
+				}
+				replacement: {
+					description: """
+						The value written by the `replace` and `labelmap` actions.
+
+						Capture groups of `regex` are referenced as `$1`, or `${name}` for named groups.
+						"""
+					required: false
+					type: string: default: "$1"
+				}
+				separator: {
+					description: "The separator placed between the values of the source labels."
+					required:    false
+					type: string: default: ";"
+				}
+				source_labels: {
+					description: "The labels whose values make up the source value."
+					required:    false
+					type: array: {
+						default: []
+						items: type: string: examples: ["__meta_kubernetes_pod_annotation_prometheus_io_scrape"]
+					}
+				}
+				target_label: {
+					description: "The label written by the `replace` and `hashmod` actions."
+					required:    false
+					type: string: examples: ["__metrics_path__"]
+				}
+			}
+		}
+	}
 	scrape_interval_secs: {
 		description: """
 			The interval between scrapes. Requests are run concurrently so if a scrape takes longer
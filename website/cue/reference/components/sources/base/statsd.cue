@@ -32,9 +32,10 @@ base: components: sources: statsd: configuration: {
 		description: "The type of socket to use."
 		required:    true
 		type: string: enum: {
-			tcp:  "Listen on TCP."
-			udp:  "Listen on UDP."
-			unix: "Listen on a Unix domain Socket (UDS)."
+			tcp:           "Listen on TCP."
+			udp:           "Listen on UDP."
+			unix:          "Listen on a Unix domain stream socket (UDS)."
+			unix_datagram: "Listen on a Unix domain datagram socket, as used by DogStatsD clients."
 		}
 	}
 	path: {
@@ -43,7 +44,7 @@ base: components: sources: statsd: configuration: {
 
 			This should be an absolute path.
 			"""
-		relevant_when: "mode = \"unix\""
+		relevant_when: "mode = \"unix\" or mode = \"unix_datagram\""
 		required:      true
 		type: string: examples: ["/path/to/socket"]
 	}
@@ -18,6 +18,33 @@ base: components: sources: syslog: configuration: {
 		required:      false
 		type: uint: {}
 	}
+	framing: {
+		description: """
+			The framing of messages received over TCP, as described in [RFC 6587][rfc6587].
+
+			[rfc6587]: https://datatracker.ietf.org/doc/html/rfc6587
+			"""
+		relevant_when: "mode = \"tcp\""
+		required:      false
+		type: string: {
+			default: "auto"
+			enum: {
+				auto: """
+					Detects the framing of each connection from its first message.
+
+					Connections whose first message starts with a non-zero digit are read as octet counted,
+					and all others as newline delimited. This allows relays using either framing to send to
+					the same port.
+					"""
+				newline_delimited: "Messages are terminated by a newline."
+				octet_counting: """
+					Messages are prefixed with their length in bytes.
+
+					Frames that do not start with a length are read up to the next newline.
+					"""
+			}
+		}
+	}
 	host_key: {
 		description: """
 			Overrides the name of the log field used to add the peer host to each event.
@@ -71,11 +98,13 @@ base: components: sources: syslog: configuration: {
 		description: """
 			The Unix socket path.
 
-			This should be an absolute path.
+			This should be an absolute path, or `systemd{#N}` to use the Nth socket passed by
+			systemd socket activation. The socket file of a systemd-provided socket is managed by
+			systemd, so `socket_file_mode` has no effect on it.
 			"""
 		relevant_when: "mode = \"unix\""
 		required:      true
-		type: string: examples: ["/path/to/socket"]
+		type: string: examples: ["/path/to/socket", "systemd"]
 	}
 	permit_origin: {
 		description:   "List of allowed origin IP networks. IP addresses must be in CIDR notation."
@@ -83,6 +112,18 @@ base: components: sources: syslog: configuration: {
 		required:      false
 		type: array: items: type: string: examples: ["192.168.0.0/16", "127.0.0.1/32", "::1/128", "9876:9ca3:99ab::23/128"]
 	}
+	permitted_peers: {
+		description: """
+			The common names of the client certificates permitted to connect.
+
+			Connections presenting a certificate with any other common name are closed before any
+			message is read. Requires `tls.verify_certificate` to be enabled, so that the presented
+			certificates are known to be issued by a trusted CA.
+			"""
+		relevant_when: "mode = \"tcp\""
+		required:      false
+		type: array: items: type: string: examples: ["relay-1.example.com"]
+	}
 	receive_buffer_bytes: {
 		description: """
 			The size of the receive buffer used for each connection.
@@ -0,0 +1,106 @@
+package metadata
+
+components: sources: collectd: {
+	_port: 25826
+
+	title: "collectd"
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: false
+		multiline: enabled: false
+		receive: {
+			from: {
+				service: services.collectd
+				interface: socket: {
+					api: {
+						title: "collectd binary protocol"
+						url:   urls.collectd_binary_protocol
+					}
+					direction: "incoming"
+					port:      _port
+					protocols: ["udp"]
+					ssl: "disabled"
+				}
+			}
+			receive_buffer_bytes: {
+				enabled: true
+			}
+			keepalive: enabled: false
+			tls: enabled:       false
+		}
+		auto_generated: true
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.collectd.configuration
+
+	output: metrics: {
+		counter: output._passthrough_counter
+		gauge:   output._passthrough_gauge
+	}
+
+	how_it_works: {
+		network_plugin: {
+			title: "Network plugin"
+			body: """
+				The source receives the packets that collectd's [network plugin](\(urls.collectd_network_plugin))
+				sends to its servers. Point a `Server` block of the plugin at the address of the source
+				to send values to Vector instead of, or as well as, another collectd instance.
+				Notifications are ignored.
+				"""
+		}
+		security: {
+			title: "Security"
+			body: """
+				Packets signed or encrypted with the `SecurityLevel` options of the network plugin are
+				verified and decrypted with the passwords in `auth_file`. Values below the configured
+				`security_level` are rejected. With the default `security_level` of `none`, packets
+				signed by users that are not in the auth file are accepted without verification,
+				matching the behavior of collectd.
+				"""
+		}
+		metric_names: {
+			title: "Metric names"
+			body: """
+				Each value becomes a metric named `<plugin>_<type>_<data source>`, leaving out the
+				type when it is the same as the plugin and the data source when the type has a single
+				data source named `value`. The names of the data sources are read from the
+				[`types.db`](\(urls.collectd_types_db)) files given in `types_db`; without them, the
+				values of types with several data sources are named after their position.
+
+				The host, plugin instance, and type instance of the values are added as the `host`,
+				`plugin_instance`, and `type_instance` tags when they are not empty.
+				"""
+		}
+		metric_types: {
+			title: "Metric types"
+			body: """
+				`GAUGE` values are emitted as absolute gauges, `COUNTER` and `DERIVE` values as
+				absolute counters, and `ABSOLUTE` values, which collectd resets whenever they are read,
+				as incremental counters.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_received_bytes: components.sources.internal_metrics.output.metrics.component_received_bytes
+	}
+}
@@ -0,0 +1,109 @@
+package metadata
+
+components: sources: database: {
+	title: "Database"
+
+	description: """
+		Collects rows from [PostgreSQL](\(urls.postgresql)) and [MySQL](\(urls.mysql)) databases by
+		periodically running a query, such as a query reading the new rows of an audit or outbox
+		table.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "batch"
+		stateful:      true
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		collect: {
+			checkpoint: enabled: true
+			from: {
+				service: {
+					name:     "SQL database"
+					thing:    "a \(name)"
+					url:      urls.postgresql
+					versions: null
+				}
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["tcp"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.database.configuration
+
+	output: logs: row: {
+		description: "A row returned by the query, with a field for each column."
+		fields: {
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["database"]
+				}
+			}
+			timestamp: fields._current_timestamp
+		}
+	}
+
+	how_it_works: {
+		tracking: {
+			title: "Tracking new rows"
+			body: """
+				To only read new rows, set `tracking_column` to a column that increases with each new
+				row, such as an auto-incremented primary key or an insertion timestamp, and compare
+				it to the `:tracking_value` placeholder in the query:
+
+				```sql
+				SELECT * FROM audit_log WHERE id > :tracking_value ORDER BY id LIMIT 1000
+				```
+
+				After each run, the highest value of the tracking column among the rows read is
+				checkpointed in the data directory, and bound to the placeholder on the next run. The
+				checkpoint only advances once the rows have been delivered, so rows that failed to be
+				delivered are read again on the next run.
+
+				Rows inserted with a tracking value lower than the checkpoint, for example by
+				transactions committed out of order, are not read. A margin can be kept by comparing
+				the tracking column to the placeholder minus an offset, at the cost of reading some rows
+				twice.
+				"""
+		}
+		column_types: {
+			title: "Column types"
+			body: """
+				Columns are converted to the closest Vector type: integers, floats, booleans,
+				timestamps, and strings, as well as objects and arrays for PostgreSQL `json` and
+				`jsonb` columns. PostgreSQL columns of other types, such as `numeric` or `uuid`, have
+				to be cast to a supported type in the query, for example `amount::text`. MySQL
+				`DECIMAL`, `JSON`, and other textual or binary columns are read as strings.
+
+				MySQL connections use the UTC time zone, so that `TIMESTAMP` columns are read and
+				compared to the tracking value in UTC.
+				"""
+		}
+	}
+}
@@ -0,0 +1,166 @@
+package metadata
+
+components: sources: ebpf: {
+	title: "eBPF"
+
+	description: """
+		Collects process and network events from the Linux kernel with [eBPF](\(urls.ebpf))
+		programs: the programs executed and exited by processes, and the TCP connections they
+		open, accept, and close, attributed to the container they run in.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		deployment_roles: ["daemon"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: false
+		collect: {
+			checkpoint: enabled: false
+			from: service:       services.host
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		requirements: [
+			"""
+				Linux 5.8 or later, with [BTF](\(urls.ebpf_core)) enabled (`CONFIG_DEBUG_INFO_BTF`),
+				is required.
+				""",
+			"""
+				Vector needs the `CAP_BPF` and `CAP_PERFMON` [capabilities](\(urls.linux_capability)),
+				or to run as `root`.
+				""",
+		]
+		warnings: []
+		notices: [
+			"""
+				The source is not included in the default builds of Vector. It is built with the
+				`sources-ebpf` feature, which requires `clang` and the `libbpf` and kernel headers.
+				""",
+		]
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.ebpf.configuration
+
+	env_vars: {
+		PROCFS_ROOT: {
+			description: "Sets an arbitrary path to the system's Procfs root, read to attribute events to containers. Can be used to attribute the events of the host from within a container. Unset and uses system `/proc` by default."
+			type: string: {
+				default: null
+				examples: ["/host/proc"]
+			}
+		}
+	}
+
+	output: logs: event: {
+		description: "A process or network event."
+		fields: {
+			event: {
+				description: "The kind of the event."
+				required:    true
+				type: string: {
+					enum: {
+						process_exec: "A process executed a new program."
+						process_exit: "A process exited."
+						tcp_accept:   "A process accepted an incoming TCP connection."
+						tcp_close:    "A TCP connection opened or accepted by a process was closed."
+						tcp_connect:  "A process opened an outgoing TCP connection."
+					}
+				}
+			}
+			process: {
+				description: """
+					The process the event originates from, with its `pid`, `ppid`, `uid`, `gid`, and
+					`name`, and for `process_exec` events the `executable` path and the `args` of the
+					program, or for `process_exit` events its `exit_code`.
+
+					For `tcp_close` events, this is the process that opened or accepted the connection.
+					"""
+				required: true
+				type: object: {}
+			}
+			connection: {
+				description: """
+					The endpoints of the TCP connection, with their `local_address`, `local_port`,
+					`remote_address`, and `remote_port`.
+					"""
+				required: false
+				type: object: {}
+			}
+			cgroup_id: {
+				description: "The ID of the cgroup of the process."
+				required:    true
+				type: uint: {
+					examples: [7331]
+					unit: null
+				}
+			}
+			container: {
+				description: """
+					The container the process runs in, with its `id`, and its `runtime` when it can
+					be told from the cgroup of the process.
+					"""
+				required: false
+				type: object: {}
+			}
+			kubernetes: {
+				description: "The `pod_uid` of the Kubernetes pod of the container."
+				required:    false
+				type: object: {}
+			}
+			host: fields._local_host
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["ebpf"]
+				}
+			}
+			timestamp: fields._current_timestamp
+		}
+	}
+
+	how_it_works: {
+		co_re: {
+			title: "Portability"
+			body: """
+				The eBPF programs are compiled once, and the kernel structures they read are
+				relocated against the BTF of the running kernel when they are loaded ([CO-RE](\(urls.ebpf_core))),
+				so the same build of Vector runs on any supported kernel. Processes are traced with
+				the `sched_process_exec` and `sched_process_exit` tracepoints, and TCP connections
+				with the `inet_sock_set_state` tracepoint and a probe on the return of
+				`inet_csk_accept`. Only the programs needed by the configured `events` are attached.
+				"""
+		}
+		containers: {
+			title: "Container attribution"
+			body: """
+				Events are attributed to containers from the cgroup of their process, as laid out by
+				Docker, containerd, CRI-O, and Podman, and to Kubernetes pods from the cgroups of the
+				kubelet. When Vector runs in a container, mount the `/proc` directory of the host and
+				set the `PROCFS_ROOT` environment variable to its path.
+				"""
+		}
+		lost_events: {
+			title: "Lost events"
+			body: """
+				The kernel submits events through a ring buffer of `ring_buffer_bytes` bytes. When
+				Vector does not read events as fast as they are submitted, the events that don't fit
+				in the ring buffer are lost, and counted in the `component_discarded_events_total`
+				metric.
+				"""
+		}
+	}
+}
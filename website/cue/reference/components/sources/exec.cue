@@ -41,6 +41,18 @@ components: sources: exec: {
 
 	configuration: base.components.sources.exec.configuration
 
+	outputs: [
+		components._default_output,
+		{
+			name: "stderr"
+			description: """
+				If `separate_stderr_output` is enabled, the events generated from stderr are sent to
+				this output instead of the default one. For a source named `foo`, this output can be
+				accessed by specifying `foo.stderr` as the input to another component.
+				"""
+		},
+	]
+
 	output: logs: line: {
 		description: "An individual event from exec."
 		fields: {
@@ -109,6 +121,19 @@ components: sources: exec: {
 				[`maximum_buffer_size_bytes`](#maximum_buffer_size_bytes) is reached.
 				"""
 		}
+		supervision: {
+			title: "Supervising Commands"
+			body: """
+				Streaming commands are rerun when they exit, after `respawn_interval_secs`. With
+				`respawn_policy` set to `on_failure`, a command that exits successfully is not rerun.
+				Setting `maximum_respawn_interval_secs` makes the time before rerunning a command
+				that keeps failing double after each run, up to this maximum.
+
+				Runs of the command can be bounded with `maximum_runtime_secs` and
+				`maximum_output_size_bytes`: once a run exceeds either limit, the command is killed,
+				and the rest of its output is discarded.
+				"""
+		}
 		shutdown: {
 			title: "Shutting Down"
 			body: """
@@ -163,15 +163,16 @@ components: sources: fluent: {
 		secure_mode: {
 			title: "Secure forward mode support"
 			body:  """
-				The `fluent` source currently supports using TLS, but does not support the authentication part of the
-				Fluent protocol including:
-
-				- Shared key
-				- Username and password
-
-				And so these options of the secure forward output plugins for Fluent and Fluent Bit cannot be used.
-
-				If you would find this useful, [please let us know](\(urls.vector_repo)/issues/7532).
+				The `fluent` source supports the secure forward mode of the Fluent protocol. When
+				`security` is set, Vector sends a `HELO` message to each client when it connects, and
+				only accepts messages once the client has authenticated with the shared key, and with
+				one of the configured users if any. Clients failing to authenticate are told why in
+				the `PONG` reply, and disconnected.
+
+				This matches the `<security>` section of the Fluentd `forward` output and the
+				`Shared_Key`, `Username`, and `Password` options of the Fluent Bit `forward` output.
+				To also authenticate the clients with certificates, enable `tls.verify_certificate`
+				and set `tls.ca_file` to the CA issuing their certificates.
 				"""
 		}
 	}
@@ -0,0 +1,153 @@
+package metadata
+
+components: sources: gcp_cloud_storage: {
+	title: "GCP Cloud Storage"
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		multiline: enabled: true
+		collect: {
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        true
+				enabled_by_scheme:      true
+			}
+			checkpoint: enabled: false
+			proxy: enabled:      true
+			from: service:       services.gcp_cloud_storage
+		}
+	}
+
+	classes: {
+		commonly_used: false
+		deployment_roles: ["aggregator"]
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	support: {
+		requirements: [
+			"""
+				The GCP Cloud Storage source requires a Pub/Sub subscription receiving the
+				[object change notifications](\(urls.gcs_pubsub_notifications)) of the desired buckets.
+				""",
+		]
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.gcp_cloud_storage.configuration
+
+	output: logs: object: {
+		description: "A line from a GCS object."
+		fields: {
+			message: {
+				description: "A line from the GCS object."
+				required:    true
+				type: string: {
+					examples: ["53.126.150.246 - - [01/Oct/2020:11:25:58 -0400] \"GET /disintermediate HTTP/2.0\" 401 20308"]
+				}
+			}
+			timestamp: fields._current_timestamp & {
+				description: "The Last-Modified time of the object. Defaults the current timestamp if this information is missing."
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["gcp_cloud_storage"]
+				}
+			}
+			bucket: {
+				description: "The bucket of the object the line came from."
+				required:    true
+				type: string: {
+					examples: ["my-bucket"]
+				}
+			}
+			object: {
+				description: "The object the line came from."
+				required:    true
+				type: string: {
+					examples: ["logs/2024/01/01/app.log.gz"]
+				}
+			}
+		}
+	}
+
+	how_it_works: {
+		notifications: {
+			title: "Object notifications"
+			body: """
+				Objects are discovered through the notifications GCS publishes to a Pub/Sub topic when
+				objects change. Only `OBJECT_FINALIZE` notifications, sent when an object is created or
+				overwritten, are read; other notifications are acknowledged and ignored. Notifications
+				can be set up for a bucket with:
+
+				```shell
+				gcloud storage buckets notifications create gs://my-bucket --topic=my-topic --event-types=OBJECT_FINALIZE
+				```
+
+				The source identifies the object from the attributes of each notification, so both the
+				`JSON_API_V1` and `NONE` payload formats are supported.
+				"""
+		}
+		acknowledgements: {
+			title: "Acknowledgements"
+			body: """
+				A notification is acknowledged once its object has been read and, if
+				end-to-end acknowledgements are enabled, its events
+				have been delivered. While an object is being processed, the acknowledgement deadline of
+				its notification is extended every half of `pubsub.ack_deadline_secs`, so that large
+				objects or slow sinks do not cause the notification to be redelivered. If Vector stops
+				before a notification is acknowledged, the notification is redelivered once its deadline
+				expires and the object is read again.
+				"""
+		}
+		compression: {
+			title: "Compression"
+			body: """
+				Objects are requested exactly as they are stored, without the decompressive transcoding
+				GCS applies to objects stored with `Content-Encoding: gzip`. With `compression` set to
+				`auto`, the compression of an object is determined from its `Content-Encoding` and
+				`Content-Type` metadata, falling back to the `.gz` and `.zst` suffixes of its name.
+				"""
+		}
+	}
+
+	permissions: iam: [
+		{
+			platform: "gcp"
+			_service: "storage"
+
+			policies: [
+				{
+					_action: "objects.get"
+				},
+			]
+		},
+		{
+			platform: "gcp"
+			_service: "pubsub"
+
+			policies: [
+				{
+					_action: "subscriptions.consume"
+				},
+			]
+		},
+	]
+
+	telemetry: metrics: {
+		gcs_notification_ignored_total: components.sources.internal_metrics.output.metrics.gcs_notification_ignored_total
+	}
+}
@@ -0,0 +1,110 @@
+package metadata
+
+components: sources: graphite: {
+	_port: 2003
+
+	title: "Graphite"
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: false
+		multiline: enabled: false
+		receive: {
+			from: {
+				service: services.graphite
+				interface: socket: {
+					api: {
+						title: "Graphite"
+						url:   urls.graphite_feeding_carbon
+					}
+					direction: "incoming"
+					port:      _port
+					protocols: ["tcp", "udp"]
+					ssl: "optional"
+				}
+			}
+			receive_buffer_bytes: {
+				enabled: true
+			}
+			keepalive: enabled: true
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				enabled_default:        false
+			}
+		}
+		auto_generated: true
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.graphite.configuration
+
+	output: metrics: {
+		gauge: output._passthrough_gauge
+	}
+
+	how_it_works: {
+		protocols: {
+			title: "Protocols"
+			body: """
+				The plaintext protocol sends one metric per line, as `<path> <value> <timestamp>`, and is
+				accepted over both TCP and UDP. The pickle protocol, used by Carbon relays and some
+				clients to send metrics in batches, is accepted over TCP when `protocol` is set to
+				`pickle`. Pickle ports are conventionally `2004` rather than `2003`.
+
+				Only pickled lists, tuples, strings, and numbers are decoded. Messages that would
+				construct any other kind of Python object are rejected.
+				"""
+		}
+		metric_types: {
+			title: "Metric types"
+			body: """
+				Graphite does not record the type of a metric, so every metric is emitted as an absolute
+				gauge. Timestamps are given in seconds; a timestamp of `-1`, or no timestamp at all,
+				leaves the timestamp of the metric unset.
+				"""
+		}
+		templates: {
+			title: "Templates"
+			body: """
+				By default a metric is named after its full dotted path. Templates, in the format used
+				by the InfluxDB and Telegraf Graphite parsers, extract tags from the path instead. With
+				the template `servers.* .host.measurement*`, the path `servers.web01.cpu.load` becomes
+				the metric `cpu.load` with the tag `host` set to `web01`.
+
+				Templates are tried in order, and the first template whose filter matches the path is
+				used. Templates without a filter match every path, so they should be listed last.
+				"""
+		}
+		tagged_series: {
+			title: "Tagged series"
+			body: """
+				Tags can also be sent in the path, using the [Graphite tag format](\(urls.graphite_tags)):
+				`disk.used;datacenter=dc1;rack=a1`. Templates are applied to the part of the path before
+				the first `;`, and the tags given in the path take precedence over tags extracted by
+				templates.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_received_bytes: components.sources.internal_metrics.output.metrics.component_received_bytes
+	}
+}
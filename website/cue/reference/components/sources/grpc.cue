@@ -0,0 +1,110 @@
+package metadata
+
+components: sources: grpc: {
+	_port: 50051
+
+	title: "gRPC"
+
+	description: """
+		Receives structured events from applications over gRPC. The services to serve are
+		read from a user-provided protobuf descriptor set, and every request message is
+		converted into a log event.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["aggregator", "sidecar"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		multiline: enabled: false
+		receive: {
+			from: {
+				service: services.grpc_client
+
+				interface: socket: {
+					direction: "incoming"
+					port:      _port
+					protocols: ["http"]
+					ssl: "optional"
+				}
+			}
+			receive_buffer_bytes: enabled: false
+			keepalive: enabled:            true
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				enabled_default:        false
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.grpc.configuration
+
+	output: logs: event: {
+		description: "An individual request message."
+		fields: {
+			grpc_method: {
+				description: "The fully qualified name of the method the message was sent to."
+				required:    true
+				type: string: {
+					examples: ["mycompany.telemetry.v1.IngestService.Push"]
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["grpc"]
+				}
+			}
+			timestamp: fields._current_timestamp
+			"*": {
+				description: "The fields of the request message, decoded with its protobuf definition."
+				required:    true
+				type: "*": {}
+			}
+		}
+	}
+
+	how_it_works: {
+		services: {
+			title: "Serving a service definition"
+			body: """
+				Every method of the configured services is served on its standard gRPC path,
+				`/<package>.<Service>/<Method>`. Calls to any other path are answered with the
+				`UNIMPLEMENTED` status.
+
+				Each request message becomes one event. Client streaming calls produce one event
+				per message and complete once the client has finished sending. Since the source
+				has nothing to send back, the response is the default (empty) instance of the
+				method's output message, or an empty stream for server streaming methods.
+
+				When acknowledgements are enabled, the call only succeeds once its events have
+				been delivered, so clients can safely retry failed calls.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		grpc_server_handler_duration_seconds: components.sources.internal_metrics.output.metrics.grpc_server_handler_duration_seconds
+		grpc_server_messages_received_total:  components.sources.internal_metrics.output.metrics.grpc_server_messages_received_total
+		grpc_server_messages_sent_total:      components.sources.internal_metrics.output.metrics.grpc_server_messages_sent_total
+	}
+}
@@ -128,6 +128,17 @@ components: sources: host_metrics: {
 		filesystem_used_bytes: _host & _filesystem_bytes & {description: "The number of bytes used on the named filesystem."}
 		filesystem_used_ratio: _host & _filesystem_bytes & {description: "The ratio between used and total bytes on the named filesystem."}
 
+		// Host GPU
+		gpu_memory_free_bytes: _host & _gpu_gauge & {description: "The number of bytes of memory free on the GPU."}
+		gpu_memory_total_bytes: _host & _gpu_gauge & {description: "The total number of bytes of memory of the GPU."}
+		gpu_memory_used_bytes: _host & _gpu_gauge & {description: "The number of bytes of memory used on the GPU."}
+		gpu_memory_utilization_ratio: _host & _gpu_gauge & {description: "The ratio of time the memory of the GPU was read or written over the last sample period."}
+		gpu_power_watts: _host & _gpu_gauge & {description: "The power drawn by the GPU, in watts."}
+		gpu_process_memory_used_bytes: _host & _gpu_process_gauge & {description: "The number of bytes of GPU memory used by the process."}
+		gpu_process_utilization_ratio: _host & _gpu_process_gauge & {description: "The ratio of time the GPU was running kernels of the process since the last scrape."}
+		gpu_temperature_celsius: _host & _gpu_gauge & {description: "The temperature of the GPU, in degrees Celsius."}
+		gpu_utilization_ratio: _host & _gpu_gauge & {description: "The ratio of time the GPU was running kernels over the last sample period."}
+
 		// Host load
 		load1: _host & _loadavg & {description: "System load averaged over the last 1 minute."}
 		load5: _host & _loadavg & {description: "System load averaged over the last 5 minutes."}
@@ -216,6 +227,50 @@ components: sources: host_metrics: {
 				}
 			}
 		}
+		_gpu_tags: _host_metrics_tags & {
+			collector: examples: ["gpu"]
+			device: {
+				description: "The index of the GPU, or its DRM card number for AMD GPUs."
+				required:    true
+				examples: ["0", "1"]
+			}
+			model: {
+				description: "The product name of the GPU."
+				required:    false
+				examples: ["NVIDIA A100-SXM4-80GB", "Instinct MI210"]
+			}
+			uuid: {
+				description: "The unique identifier of the GPU."
+				required:    false
+				examples: ["GPU-2f0c4b6e-1c3a-9d8e-7f6a-5b4c3d2e1f0a"]
+			}
+			vendor: {
+				description: "The vendor of the GPU."
+				required:    true
+				examples: ["nvidia", "amd"]
+			}
+		}
+		_gpu_gauge: {
+			type:          "gauge"
+			tags:          _gpu_tags
+			relevant_when: "OS is Linux"
+		}
+		_gpu_process_gauge: {
+			type: "gauge"
+			tags: _gpu_tags & {
+				pid: {
+					description: "The ID of the process."
+					required:    true
+					examples: ["4242"]
+				}
+				process_name: {
+					description: "The name of the process."
+					required:    false
+					examples: ["python3"]
+				}
+			}
+			relevant_when: "OS is Linux and the GPU is an NVIDIA GPU"
+		}
 		_loadavg: {
 			type: "gauge"
 			tags: _host_metrics_tags & {
@@ -20,7 +20,7 @@ components: sources: http_client: {
 			default_framing: "`bytes`"
 		}
 		collect: {
-			checkpoint: enabled: false
+			checkpoint: enabled: true
 			from: {
 				service: services.http_scrape
 
@@ -138,6 +138,31 @@ components: sources: http_client: {
 		}
 	}
 
+	how_it_works: {
+		pagination: {
+			title: "Pagination"
+			body: """
+				When `pagination` is set, the pages following the first page of a response are
+				requested one after the other, until the last page or `pagination.max_pages` pages
+				have been received. The `link_header` strategy follows the `next` relation of the
+				`Link` response header. The `cursor` strategy evaluates a VRL expression against the
+				response body to extract the cursor of the next page, which is either sent in a
+				query string parameter of the first URL, or requested as the URL of the next page.
+				"""
+		}
+		incremental: {
+			title: "Incremental scrapes"
+			body: """
+				When `incremental` is set, the highest value of `incremental.field` in the events of
+				a scrape becomes the watermark once all of its pages have been received. The
+				watermark is sent in the `incremental.query_parameter` of the following scrapes, so
+				that only new events are requested, and is persisted in the data directory so that
+				scrapes resume from it when Vector restarts. The watermark is not advanced by
+				scrapes that fail before their last page.
+				"""
+		}
+	}
+
 	telemetry: metrics: {
 		http_client_responses_total:      components.sources.internal_metrics.output.metrics.http_client_responses_total
 		http_client_response_rtt_seconds: components.sources.internal_metrics.output.metrics.http_client_response_rtt_seconds
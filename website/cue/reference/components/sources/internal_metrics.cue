@@ -147,6 +147,33 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		blackhole_batch_size_events: {
+			description:       "The number of events in each batch received by the `blackhole` sink."
+			type:              "histogram"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		blackhole_event_latency_seconds: {
+			description:       "The time elapsed between the timestamp of an event and its reception by the `blackhole` sink."
+			type:              "histogram"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		azure_blob_event_ignored_total: {
+			description:       "The total number of times an Azure blob storage event was ignored (for an event that was not `Microsoft.Storage.BlobCreated`)."
+			type:              "counter"
+			default_namespace: "vector"
+
+			tags: _component_tags & {
+				ignore_type: {
+					description: "The reason for ignoring the event"
+					required:    true
+					enum: {
+						"invalid_event_type": "The type of invalid event."
+					}
+				}
+			}
+		}
 		checkpoints_total: {
 			description:       "The total number of files checkpointed."
 			type:              "counter"
@@ -623,6 +650,21 @@ components: sources: internal_metrics: {
 				file: _file
 			}
 		}
+		gcs_notification_ignored_total: {
+			description:       "The total number of times a GCS object notification was ignored (for an event that was not `OBJECT_FINALIZE`)."
+			type:              "counter"
+			default_namespace: "vector"
+
+			tags: _component_tags & {
+				ignore_type: {
+					description: "The reason for ignoring the notification"
+					required:    true
+					enum: {
+						"invalid_event_type": "The type of invalid event."
+					}
+				}
+			}
+		}
 		grpc_server_messages_received_total: {
 			description:       "The total number of gRPC messages received."
 			type:              "counter"
@@ -747,18 +789,87 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _internal_metrics_tags
 		}
+		pii_redactions_total: {
+			description:       "The number of values redacted by the `pii_redact` transform."
+			type:              "counter"
+			default_namespace: "vector"
+			tags: _component_tags & {
+				detector: {
+					description: "The detector that detected the values."
+					required:    true
+					enum: {
+						email:       "Email addresses."
+						credit_card: "Credit card numbers."
+						ssn:         "US Social Security numbers."
+						api_key:     "API keys and access tokens."
+						ip_address:  "IPv4 and IPv6 addresses."
+					}
+				}
+			}
+		}
 		protobuf_decode_errors_total: {
 			description:       "The total number of [Protocol Buffers](\(urls.protobuf)) errors thrown during communication between Vector instances."
 			type:              "counter"
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		quota_exceeded_events_total: {
+			description:       "The number of events exceeding the quota of their key in the `quota` transform."
+			type:              "counter"
+			default_namespace: "vector"
+			tags: _component_tags & {
+				key: _quota_key
+			}
+		}
+		quota_usage_bytes: {
+			description:       "The number of bytes of the events of a key in the window of the `quota` transform."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags: _component_tags & {
+				key: _quota_key
+			}
+		}
+		quota_usage_events: {
+			description:       "The number of events of a key in the window of the `quota` transform."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags: _component_tags & {
+				key: _quota_key
+			}
+		}
+		sampling_decisions_total: {
+			description:       "The number of traces sampled or dropped by the `trace_sampler` transform."
+			type:              "counter"
+			default_namespace: "vector"
+			tags: _component_tags & {
+				decision: {
+					description: "Whether the traces were sampled or dropped."
+					required:    true
+					enum: {
+						sampled: "The traces were sampled by at least one policy."
+						dropped: "The traces weren't sampled by any policy."
+					}
+				}
+			}
+		}
 		send_errors_total: {
 			description:       "The total number of errors sending messages."
 			type:              "counter"
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		shed_events_total: {
+			description:       "The number of events shed by the `shed` transform, by priority."
+			type:              "counter"
+			default_namespace: "vector"
+			tags: _component_tags & {
+				priority: {
+					description: "The priority of the shed events."
+					required:    true
+					examples: ["0", "1"]
+				}
+			}
+		}
 		source_lag_time_seconds: {
 			description:       "The difference between the timestamp recorded in each event and the time when it was ingested, expressed as fractional seconds."
 			type:              "histogram"
@@ -905,6 +1016,15 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		websocket_server_dropped_messages_total: {
+			description: """
+				The total number of messages dropped by the `websocket_server` sink for clients that
+				didn't read them fast enough.
+				"""
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 
 		// Windows metrics
 		windows_service_install_total: {
@@ -1068,6 +1188,11 @@ components: sources: internal_metrics: {
 			description: "The path that produced the error."
 			required:    true
 		}
+		_quota_key: {
+			description: "The key of the quota, which is empty for the events without a key."
+			required:    true
+			examples: ["tenant-a"]
+		}
 		_reason: {
 			description: "The type of the error"
 			required:    true
@@ -0,0 +1,147 @@
+package metadata
+
+components: sources: kubernetes_events: {
+	title: "Kubernetes Events"
+
+	description: """
+		Collects the events of a Kubernetes cluster, such as scheduling failures, image pull
+		errors, and container restarts, by watching the [Events API](\(urls.kubernetes_events)).
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: false
+		collect: {
+			checkpoint: enabled: false
+			from: {
+				service: services.kubernetes
+
+				interface: {
+					socket: {
+						api: {
+							title: "Kubernetes Events API"
+							url:   urls.kubernetes_events
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		requirements: [
+			"""
+				[Kubernetes](\(urls.kubernetes)) version `\(services.kubernetes.versions)` is required.
+				""",
+			"""
+				The service account of Vector needs the `list` and `watch` [verbs](\(urls.kubernetes_request_verbs))
+				on the `events` resource, in the watched namespaces or with a `ClusterRole` when watching
+				all namespaces.
+				""",
+		]
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.kubernetes_events.configuration
+
+	output: logs: event: {
+		description: "A Kubernetes event, with the fields of the `v1` `Event` object."
+		fields: {
+			message: {
+				description: "The description of the event."
+				required:    false
+				type: string: {
+					examples: ["Back-off restarting failed container"]
+				}
+			}
+			reason: {
+				description: "The short, machine-readable reason of the event."
+				required:    false
+				type: string: {
+					examples: ["BackOff", "FailedScheduling"]
+				}
+			}
+			type: {
+				description: "The type of the event."
+				required:    false
+				type: string: {
+					examples: ["Normal", "Warning"]
+				}
+			}
+			count: {
+				description: "The number of times the event has occurred."
+				required:    false
+				type: uint: {
+					examples: [1, 12]
+					unit: null
+				}
+			}
+			involvedObject: {
+				description: "The object the event is about, with its `kind`, `namespace`, and `name`."
+				required:    false
+				type: object: {}
+			}
+			metadata: {
+				description: "The metadata of the event object, including its `namespace` and `uid`."
+				required:    true
+				type: object: {}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["kubernetes_events"]
+				}
+			}
+			timestamp: fields._current_timestamp & {
+				description: """
+					The time of the last occurrence of the event, or the time it was read if it is
+					not reported.
+					"""
+			}
+		}
+	}
+
+	how_it_works: {
+		watching: {
+			title: "Watching events"
+			body: """
+				Each watched namespace, or the whole cluster if `namespaces` is empty, is watched with
+				the [watch API](\(urls.kubernetes_watch_api)). When the connection to the API server is
+				lost, the watch is resumed from the last resource version, which the API server keeps
+				up to date with bookmarks. If that version has expired, the events are listed again.
+				"""
+		}
+		deduplication: {
+			title: "Deduplication"
+			body: """
+				Kubernetes records the repeated occurrences of an event by increasing the `count` of
+				the existing event object, and the API server sends events again when they are listed
+				after a watch expired. The source tracks the count of each event it has seen, and
+				only collects an event when it is new or its count increased, or only when it is new
+				if `emit_count_updates` is disabled.
+
+				The tracked counts are not persisted, so with `include_existing` enabled, the events
+				still stored by the API server are collected again when Vector restarts.
+				"""
+		}
+	}
+}
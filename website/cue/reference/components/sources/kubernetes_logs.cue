@@ -321,7 +321,9 @@ components: sources: kubernetes_logs: {
 			title: "Pod exclusion"
 			body:  """
 					By default, the [`kubernetes_logs` source](\(urls.vector_kubernetes_logs_source))
-					will skip logs from the Pods that have a `vector.dev/exclude: "true"` **label**.
+					will skip logs from the Pods that have a `vector.dev/exclude: "true"` **label**
+					or **annotation**. Excluded Pods' log files are never read, so they add no
+					processing cost.
 					You can configure additional exclusion rules via label or field selectors,
 					see [the available options](\(urls.vector_kubernetes_logs_source)#configuration).
 					"""
@@ -346,6 +348,27 @@ components: sources: kubernetes_logs: {
 					"""
 		}
 
+		pod_log_controls: {
+			title: "Per-Pod log controls"
+			body:  """
+					Pods can tune how their logs are collected with the following **annotations**:
+
+					```yaml
+					# Lines not matching this regular expression are appended to the
+					# previous line, for example to assemble stack traces.
+					vector.dev/multiline-start-pattern: "^\\\\S"
+					# Parse each message as a JSON object and merge its fields into the event.
+					vector.dev/parser: "json"
+					```
+
+					These controls are applied within the source, after partial messages are
+					merged and before events are sent to the rest of the topology. A message
+					that fails to parse with the requested parser is forwarded unparsed. With the
+					`legacy` log namespace, the fields of a parsed message don't overwrite the
+					fields set by the source, such as `kubernetes`, `file`, and the timestamp.
+					"""
+		}
+
 		kubernetes_api_communication: {
 			title: "Kubernetes API communication"
 			body:  """
@@ -0,0 +1,152 @@
+package metadata
+
+components: sources: mongodb_changestream: {
+	title: "MongoDB Change Streams"
+
+	description: """
+		Collects the inserted, updated, replaced, and deleted documents of a MongoDB deployment,
+		database, or collection by tailing its [change stream](\(urls.mongodb_change_streams)).
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		collect: {
+			checkpoint: enabled: true
+			from: {
+				service: services.mongodb
+
+				interface: {
+					socket: {
+						api: {
+							title: "MongoDB change streams"
+							url:   urls.mongodb_change_streams
+						}
+						direction: "outgoing"
+						protocols: ["tcp"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		requirements: [
+			"""
+				Change streams are only available on replica sets and sharded clusters. The user
+				connecting to the deployment needs the `changeStream` and `find` privileges on the
+				watched resources.
+				""",
+		]
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.mongodb_changestream.configuration
+
+	output: logs: change: {
+		description: "A change of a document."
+		fields: {
+			operation_type: {
+				description: "The type of the change."
+				required:    true
+				type: string: {
+					examples: ["insert", "update", "replace", "delete"]
+				}
+			}
+			database: {
+				description: "The database of the changed document."
+				required:    false
+				type: string: {
+					examples: ["app"]
+				}
+			}
+			collection: {
+				description: "The collection of the changed document."
+				required:    false
+				type: string: {
+					examples: ["orders"]
+				}
+			}
+			document_key: {
+				description: "The `_id` of the changed document, and its shard key for sharded collections."
+				required:    false
+				type: object: {}
+			}
+			full_document: {
+				description: """
+					The inserted or replaced document, or the updated document depending on the
+					`full_document` option.
+					"""
+				required: false
+				type: object: {}
+			}
+			full_document_before_change: {
+				description: """
+					The document before the change, if the collection has
+					`changeStreamPreAndPostImages` enabled.
+					"""
+				required: false
+				type: object: {}
+			}
+			update_description: {
+				description: """
+					The changes of an update, with the `updated_fields`, the `removed_fields`,
+					and the `truncated_arrays` of the document.
+					"""
+				required: false
+				type: object: {}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["mongodb_changestream"]
+				}
+			}
+			timestamp: fields._current_timestamp & {
+				description: """
+					The time of the change, with a millisecond precision on MongoDB 6.0 and later, or
+					the time the change was read if it is not reported.
+					"""
+			}
+		}
+	}
+
+	how_it_works: {
+		resume_tokens: {
+			title: "Resume tokens"
+			body: """
+				The resume token of the last change read is checkpointed in the data directory,
+				once the changes have been delivered if acknowledgements are enabled. When Vector
+				restarts, or the connection to the deployment is lost, the change stream is resumed
+				after the checkpointed change, as long as it is still in the oplog of the deployment.
+
+				If the watched collection or database is dropped or renamed, the change stream is
+				invalidated and a new one is started after it.
+				"""
+		}
+		bson_types: {
+			title: "BSON types"
+			body: """
+				Documents are converted to the closest Vector type: object IDs and decimals are
+				converted to strings, dates to timestamps, and binary data to bytes.
+				"""
+		}
+	}
+}
@@ -0,0 +1,152 @@
+package metadata
+
+components: sources: mysql_cdc: {
+	title: "MySQL CDC"
+
+	description: """
+		Collects the rows inserted, updated, and deleted in a [MySQL](\(urls.mysql)) server by
+		reading its replication binlog as a replica, tracking the replicated transactions by their
+		[GTIDs](\(urls.mysql_gtids)).
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		collect: {
+			checkpoint: enabled: true
+			from: {
+				service: {
+					name:     "MySQL"
+					thing:    "a \(name) server"
+					url:      urls.mysql
+					versions: ">= 5.7"
+				}
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["tcp"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		requirements: [
+			"""
+				The server must write a row-based binlog with GTIDs, with `binlog_format = ROW`,
+				`binlog_row_image = FULL`, `gtid_mode = ON`, and `enforce_gtid_consistency = ON`.
+				""",
+			"""
+				Column names are only written to the binlog with
+				[`binlog_row_metadata = FULL`](\(urls.mysql_binlog_row_metadata)), available since
+				MySQL 8.0.1. Otherwise, the columns are named after their position in the table, as
+				`column_1`, `column_2`, and so on.
+				""",
+		]
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.mysql_cdc.configuration
+
+	output: logs: change: {
+		description: "A row changed by a replicated transaction."
+		fields: {
+			operation: {
+				description: "The type of the change."
+				required:    true
+				type: string: {
+					enum: {
+						insert: "A row was inserted."
+						update: "A row was updated."
+						delete: "A row was deleted."
+					}
+				}
+			}
+			database: {
+				description: "The database of the changed table."
+				required:    true
+				type: string: {
+					examples: ["app"]
+				}
+			}
+			table: {
+				description: "The changed table."
+				required:    true
+				type: string: {
+					examples: ["orders"]
+				}
+			}
+			before: {
+				description: "The row before the change, for updates and deletes."
+				required:    false
+				type: object: {}
+			}
+			after: {
+				description: "The row after the change, for inserts and updates."
+				required:    false
+				type: object: {}
+			}
+			gtid: {
+				description: "The GTID of the transaction that changed the row."
+				required:    false
+				type: string: {
+					examples: ["3e11fa47-71ca-11e1-9e33-c80aa9429562:23"]
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["mysql_cdc"]
+				}
+			}
+			timestamp: fields._current_timestamp & {
+				description: "The time the transaction was committed at, in seconds."
+			}
+		}
+	}
+
+	how_it_works: {
+		gtid_tracking: {
+			title: "GTID tracking"
+			body: """
+				The set of transactions read is checkpointed in the data directory once the changes
+				of the transactions have been delivered, and sent to the server when the source
+				reconnects so that replication resumes after the last delivered transaction. The
+				changes of a transaction are sent as a single batch once the transaction has been
+				committed.
+
+				When no set has been checkpointed yet, replication starts from the transactions
+				the server has executed when Vector first connects, so only new changes are
+				collected. The binlog files of the checkpointed transactions must not be purged
+				from the server while Vector is stopped.
+				"""
+		}
+		column_types: {
+			title: "Column types"
+			body: """
+				Columns are converted to the closest Vector type: integers, floats, and timestamps
+				for `DATETIME` and `TIMESTAMP` columns, as well as objects and arrays for `JSON`
+				columns. `DECIMAL`, textual, and binary columns are read as strings.
+				"""
+		}
+	}
+}
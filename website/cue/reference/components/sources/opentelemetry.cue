@@ -39,11 +39,7 @@ components: sources: opentelemetry: {
 
 	support: {
 		requirements: []
-		warnings: [
-			"""
-				The `opentelemetry` source only supports log events at this time.
-				""",
-		]
+		warnings: []
 		notices: []
 	}
 
@@ -60,6 +56,18 @@ components: sources: opentelemetry: {
 				Received log events will go to this output stream. Use `<component_id>.logs` as an input to downstream transforms and sinks.
 				"""
 		},
+		{
+			name: "metrics"
+			description: """
+				Received metric events will go to this output stream. Use `<component_id>.metrics` as an input to downstream transforms and sinks.
+				"""
+		},
+		{
+			name: "traces"
+			description: """
+				Received trace events will go to this output stream. Use `<component_id>.traces` as an input to downstream transforms and sinks.
+				"""
+		},
 	]
 
 	output: {
@@ -195,6 +203,33 @@ components: sources: opentelemetry: {
 	}
 
 	how_it_works: {
+		metrics: {
+			title: "Metrics"
+			body: """
+				Each OTLP data point becomes a Vector metric named after the OTLP metric:
+
+				* Gauges become gauges.
+				* Monotonic sums become counters, and non-monotonic sums become gauges.
+				* Histograms and exponential histograms become aggregated histograms.
+				* Summaries become aggregated summaries.
+
+				Points with delta temporality are incremental, while cumulative points are absolute.
+				Data point attributes become tags. Resource attributes are added as tags prefixed with
+				`resource.`, and the instrumentation scope name, version, and attributes as tags prefixed
+				with `scope.`. Since tags are strings, non-string attribute values are converted to their
+				string representation. Exemplars, units, and descriptions are not retained.
+				"""
+		}
+		traces: {
+			title: "Traces"
+			body: """
+				Each OTLP span becomes a trace event holding the span's fields, such as `trace_id`,
+				`span_id`, `parent_span_id`, `name`, `kind`, `start_time_unix_nano`,
+				`end_time_unix_nano`, `attributes`, `events`, `links`, and `status`. Identifiers are
+				hex-encoded. The resource attributes are stored under `resources`, and the
+				instrumentation scope under `scope`.
+				"""
+		}
 		tls: {
 			title: "Transport Layer Security (TLS)"
 			body:  """
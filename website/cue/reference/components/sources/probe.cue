@@ -0,0 +1,205 @@
+package metadata
+
+components: sources: probe: {
+	title: "Probe"
+
+	description: """
+		Probes a list of hosts on an interval, either with ICMP echo requests or by opening TCP
+		connections, and emits their availability and latency as metrics, for basic black-box
+		monitoring.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["daemon", "sidecar"]
+		development:   "beta"
+		egress_method: "batch"
+		stateful:      false
+	}
+
+	features: {
+		auto_generated:   true
+		acknowledgements: false
+		collect: {
+			checkpoint: enabled: false
+			from: {
+				service: {
+					name:     "Network hosts"
+					thing:    "network hosts"
+					url:      urls.icmp
+					versions: null
+				}
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["icmp", "tcp"]
+						ssl: "disabled"
+					}
+				}
+			}
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		requirements: [
+			"""
+				ICMP targets require either unprivileged ICMP sockets, which on Linux are allowed
+				for the groups listed in the `net.ipv4.ping_group_range` sysctl, or the
+				`CAP_NET_RAW` capability to open raw sockets.
+				""",
+		]
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.probe.configuration
+
+	outputs: [
+		components._default_output,
+		{
+			name: "failures"
+			description: """
+				A log event is sent to this output for each failed probe, and for each ICMP probe
+				that lost some of its packets. For a source named `foo`, this output can be accessed
+				by specifying `foo.failures` as the input to another component.
+				"""
+		},
+	]
+
+	output: {
+		logs: failure: {
+			description: "A failed probe, or an ICMP probe that lost some of its packets."
+			fields: {
+				message: {
+					description: "A description of the failure."
+					required:    true
+					type: string: {
+						examples: ["Probe failed.", "Lost 1 of 3 packets."]
+					}
+				}
+				target: {
+					description: "The host or address of the target."
+					required:    true
+					type: string: {
+						examples: ["example.com:443"]
+					}
+				}
+				protocol: {
+					description: "The protocol used to probe the target."
+					required:    true
+					type: string: {
+						enum: {
+							icmp: "ICMP echo requests."
+							tcp:  "TCP connections."
+						}
+					}
+				}
+				error: {
+					description: "The reason the probe failed."
+					required:    false
+					type: string: {
+						examples: ["Connection refused (os error 111)"]
+					}
+				}
+				packets_sent: {
+					description: "The number of echo requests sent, for ICMP probes that lost packets."
+					required:    false
+					type: uint: {
+						examples: [3]
+						unit: null
+					}
+				}
+				packets_lost: {
+					description: "The number of echo requests left unanswered, for ICMP probes that lost packets."
+					required:    false
+					type: uint: {
+						examples: [1]
+						unit: null
+					}
+				}
+				source_type: {
+					description: "The name of the source type."
+					required:    true
+					type: string: {
+						examples: ["probe"]
+					}
+				}
+				timestamp: fields._current_timestamp
+			}
+		}
+
+		metrics: {
+			_probe_tags: {
+				target: {
+					description: "The host or address of the target."
+					required:    true
+					examples: ["example.com:443"]
+				}
+				protocol: {
+					description: "The protocol used to probe the target, either `icmp` or `tcp`."
+					required:    true
+					examples: ["tcp"]
+				}
+			}
+
+			up: {
+				description:       "If the target replied to the probe or not."
+				type:              "gauge"
+				default_namespace: "probe"
+				tags:              _probe_tags
+			}
+			duration_seconds: {
+				description:       "The time taken to establish the TCP connection, or the mean round-trip time of the answered ICMP echo requests."
+				type:              "gauge"
+				default_namespace: "probe"
+				tags:              _probe_tags
+			}
+			packets_sent_total: {
+				description:       "The total number of ICMP echo requests sent."
+				type:              "counter"
+				default_namespace: "probe"
+				tags:              _probe_tags
+			}
+			packets_lost_total: {
+				description:       "The total number of ICMP echo requests left unanswered."
+				type:              "counter"
+				default_namespace: "probe"
+				tags:              _probe_tags
+			}
+			packet_loss_ratio: {
+				description:       "The ratio of ICMP echo requests left unanswered in the last probe."
+				type:              "gauge"
+				default_namespace: "probe"
+				tags:              _probe_tags
+			}
+		}
+	}
+
+	how_it_works: {
+		probes: {
+			title: "Probes"
+			body: """
+				All targets are probed concurrently on each interval. A TCP probe succeeds once the
+				connection is established, and the connection is closed right away. An ICMP probe
+				sends its echo requests one after the other, and succeeds if any of them is
+				answered before the timeout.
+
+				A target whose host name does not resolve, or whose connection is refused or times
+				out, is reported as down. A target for which no ICMP socket can be opened emits no
+				metrics, and the error is logged instead.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		collect_completed_total:  components.sources.internal_metrics.output.metrics.collect_completed_total
+		collect_duration_seconds: components.sources.internal_metrics.output.metrics.collect_duration_seconds
+	}
+}
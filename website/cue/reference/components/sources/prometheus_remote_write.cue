@@ -81,6 +81,43 @@ components: sources: prometheus_remote_write: {
 				but will only take the last value for each tag name specified.
 				"""
 		}
+
+		exemplars: {
+			title: "Exemplars"
+			body: """
+				Exemplars sent alongside a series, such as the trace IDs attached to
+				request latencies, are stored in the event metadata of the metric the
+				series is decoded into, and can be read in VRL at
+				`%prometheus_remote_write.exemplars`. Each exemplar is an object with
+				`labels`, `value`, and `timestamp` fields.
+
+				The `prometheus_remote_write` sink sends these exemplars on with the
+				series they came from. Exemplars on histograms are attached to the
+				`_bucket` series their value falls into.
+				"""
+		}
+
+		native_histograms: {
+			title: "Native histograms"
+			body: """
+				Native (sparse) histograms are converted into aggregated histograms,
+				with one bucket for each populated bucket of the native histogram. The
+				bucket boundaries are computed from the histogram schema, and the zero
+				bucket is given an upper limit of the zero threshold. Histograms
+				with float counts have their counts rounded to the nearest integer.
+
+				This conversion is lossy, as aggregated histogram buckets only have
+				an upper limit: negative buckets are given the bound of their range
+				closest to zero, which is negative, the zero bucket also covers the
+				negative values within the zero threshold, and the schema is lost, so
+				the buckets of histograms with different schemas or spans no longer
+				line up. The native histogram itself is
+				kept in the event metadata at `%prometheus_remote_write.native_histogram`,
+				with its `schema`, `zero_threshold`, `zero_count`, spans, and deltas
+				or counts, and the `prometheus_remote_write` sink sends it on unchanged
+				when its `native_histograms` option is enabled.
+				"""
+		}
 	}
 
 	telemetry: metrics: {
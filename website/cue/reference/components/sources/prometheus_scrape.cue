@@ -66,6 +66,43 @@ components: sources: prometheus_scrape: {
 				but will only take the last value for each tag name specified.
 				"""
 		}
+
+		service_discovery: {
+			title: "Service discovery"
+			body: """
+				Instead of listing every target in `endpoints`, targets can be discovered from files with
+				`file_sd_configs`, or from the pods and endpoints of a Kubernetes cluster with
+				`kubernetes_sd_configs`. Both follow Prometheus' own service discovery, so existing target
+				files and `relabel_configs` can be reused. For example, to scrape the pods annotated with
+				`prometheus.io/scrape: "true"` on the port and path given by their annotations:
+
+				```yaml
+				sources:
+				  pods:
+				    type: prometheus_scrape
+				    kubernetes_sd_configs:
+				      - role: pod
+				    relabel_configs:
+				      - source_labels: [__meta_kubernetes_pod_annotation_prometheus_io_scrape]
+				        regex: "true"
+				        action: keep
+				      - source_labels: [__meta_kubernetes_pod_annotation_prometheus_io_path]
+				        regex: (.+)
+				        target_label: __metrics_path__
+				      - source_labels: [__address__, __meta_kubernetes_pod_annotation_prometheus_io_port]
+				        regex: ([^:]+)(?::\\d+)?;(\\d+)
+				        replacement: $1:$2
+				        target_label: __address__
+				      - source_labels: [__meta_kubernetes_namespace]
+				        target_label: namespace
+				```
+
+				Discovered targets are refreshed every few seconds, and files are re-read every
+				`refresh_interval_secs`. If a file can't be read or parsed, the targets previously read from
+				the files are kept. Watching the Kubernetes API requires permission to list and watch the
+				pods or endpoints in the configured namespaces.
+				"""
+		}
 	}
 
 	output: metrics: {
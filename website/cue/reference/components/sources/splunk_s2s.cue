@@ -0,0 +1,165 @@
+package metadata
+
+components: sources: splunk_s2s: {
+	_port: 9997
+
+	title: "Splunk forwarder"
+
+	description: """
+		Receives logs from Splunk universal and heavy forwarders over the Splunk-to-Splunk (S2S)
+		protocol, the protocol forwarders use to send data to Splunk indexers.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: true
+		auto_generated:   true
+		receive: {
+			from: {
+				service: services.splunk_forwarder
+
+				interface: socket: {
+					api: {
+						title: "Splunk forwarder outputs"
+						url:   urls.splunk_forwarder_outputs
+					}
+					direction: "incoming"
+					port:      _port
+					protocols: ["tcp"]
+					ssl: "optional"
+				}
+			}
+			receive_buffer_bytes: {
+				enabled: true
+			}
+			keepalive: enabled: true
+			tls: sources.socket.features.receive.tls
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		requirements: []
+		warnings: [
+			"""
+				Forwarder acknowledgements (`useACK`) and S2S compression are not supported and must be disabled
+				in the forwarder's `outputs.conf`.
+				""",
+		]
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.splunk_s2s.configuration
+
+	output: logs: event: {
+		description: "A single event sent by a forwarder."
+		fields: {
+			host: {
+				description: """
+					The host reported by the forwarder for the event. If the forwarder did not report a host, the IP
+					address the event was sent from is used instead.
+					"""
+				required: true
+				type: string: {
+					examples: ["web01", "127.0.0.1"]
+				}
+			}
+			message: {
+				description: "The raw event data."
+				required:    true
+				type: string: {
+					examples: ["Hello world"]
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["splunk_s2s"]
+				}
+			}
+			splunk_index: {
+				description: "The index the forwarder routed the event to."
+				required:    false
+				type: string: {
+					examples: ["main"]
+				}
+			}
+			splunk_source: {
+				description: "The source of the event, such as the path of the monitored file."
+				required:    false
+				type: string: {
+					examples: ["/var/log/syslog"]
+				}
+			}
+			splunk_sourcetype: {
+				description: "The sourcetype of the event."
+				required:    false
+				type: string: {
+					examples: ["syslog"]
+				}
+			}
+			timestamp: {
+				description: """
+					The time of the event as reported by the forwarder, or the time the event was received if the
+					forwarder did not report one.
+					"""
+				required: true
+				type: timestamp: {}
+			}
+			client_metadata: fields._client_metadata
+			"*": {
+				description: "Indexed fields extracted by the forwarder are inserted as root level fields."
+				required:    false
+				type: string: {
+					examples: ["prod"]
+				}
+			}
+		}
+	}
+
+	how_it_works: {
+		forwarder_configuration: {
+			title: "Forwarder configuration"
+			body: """
+				Splunk forwarders send data to Vector the same way they send data to an indexer. Add Vector as a
+				target group in the forwarder's `outputs.conf`:
+
+				```text
+				[tcpout]
+				defaultGroup = vector
+
+				[tcpout:vector]
+				server = vector.example.com:9997
+				useACK = false
+				compressed = false
+				```
+
+				Forwarders that offer version 4 of the protocol are asked to fall back to version 3 while the
+				connection is set up, so no other changes are needed on the forwarder.
+				"""
+		}
+
+		event_breaking: {
+			title: "Event breaking"
+			body: """
+				Universal forwarders do not parse data, and by default send monitored files in chunks that may
+				contain several events, or only part of one. Each chunk received becomes one Vector event. Set
+				`EVENT_BREAKER_ENABLE = true` and an `EVENT_BREAKER` for the sourcetype in the forwarder's
+				`props.conf` to have chunks end on event boundaries, or split the chunks in a transform.
+				"""
+		}
+	}
+}
@@ -65,6 +65,26 @@ components: sources: statsd: {
 	}
 
 	how_it_works: {
+		sampling: {
+			title: "Sample rates"
+			body: """
+				Clients sampling their metrics mark each line with the `@<rate>` extension, which
+				may appear anywhere after the metric type. Counters are scaled by the inverse of
+				the rate, and every timing, histogram, and distribution value is recorded as
+				standing for the inverse of the rate in samples, rounded to the nearest whole
+				sample. Gauges and sets are not affected, since resending a value does not change
+				them.
+				"""
+		}
+		packed_values: {
+			title: "Packed values"
+			body: """
+				Several values of the same metric can be sent on one line by separating them with
+				colons, as in `request.duration:12:15:9|ms`. Counter values are summed, timing,
+				histogram, distribution, and set values are all recorded, and gauges take the last
+				value, or the sum of the changes for relative gauges.
+				"""
+		}
 		timings: {
 			title: "StatsD timings"
 			body: """
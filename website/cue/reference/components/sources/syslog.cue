@@ -191,6 +191,29 @@ components: sources: syslog: {
 				"""
 		}
 
+		framing: {
+			title: "Framing"
+			body: """
+				Over TCP, messages are either octet counted, prefixed with their length in bytes, or
+				newline delimited, as described in [RFC 6587](\(urls.syslog_6587)). With the default
+				`framing` of `auto`, the framing of each connection is detected from its first message,
+				so relays using either framing can send to the same port. Once detected, the framing
+				is kept for the rest of the connection, so a newline delimited message starting with
+				a digit is not mistaken for a length.
+				"""
+		}
+
+		permitted_peers: {
+			title: "Permitted peers"
+			body: """
+				When `tls.verify_certificate` is enabled, only clients presenting a certificate issued
+				by a trusted CA are accepted. `permitted_peers` further restricts the accepted clients
+				to those whose certificate has one of the listed common names. Other connections are
+				closed before any message is read, and counted in the `component_errors_total`
+				metric with the `peer_not_permitted` error code.
+				"""
+		}
+
 		parsing: {
 			title: "Parsing"
 			body:  """
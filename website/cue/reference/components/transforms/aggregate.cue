@@ -173,6 +173,22 @@ components: transforms: aggregate: {
 				"""
 		}
 
+		counter_conversion: {
+			title: "Counter Conversion"
+			body: """
+				With `counters` set to `delta`, counters are emitted as the increase of their value over the
+				interval. `absolute` counters are converted into `incremental` ones from the difference with
+				their previous value, so an `absolute` `counter` with values 10, 15, and 22 results in an
+				`incremental` `counter` with a value of 12 once the interval is flushed. With `counters` set
+				to `rate`, the increase is further divided by the interval, and emitted as an `absolute`
+				`gauge` of the rate per second, for downstream systems that cannot compute rates themselves.
+
+				With `convert_distributions_to_sketches` enabled, `distribution` metrics are converted into
+				sketches and merged, so that the aggregated metrics have a bounded size regardless of the
+				number of samples.
+				"""
+		}
+
 		advantages: {
 			title: "Advantages of Use"
 			body: """
@@ -1,11 +1,50 @@
 package metadata
 
-base: components: transforms: aggregate: configuration: interval_ms: {
-	description: """
-		The interval between flushes, in milliseconds.
-
-		During this time frame, metrics (beta) with the same series data (name, namespace, tags, and so on) are aggregated.
-		"""
-	required: false
-	type: uint: default: 10000
+base: components: transforms: aggregate: configuration: {
+	convert_distributions_to_sketches: {
+		description: """
+			Whether or not distributions are converted into sketches.
+
+			The samples of a distribution are stored in the sketch, and the sketches of the same series
+			are merged together, so that the size of the aggregated metric doesn't depend on the number
+			of samples. The sketches are [DDSketches][ddsketch], with the parameters of the Datadog
+			Agent.
+
+			[ddsketch]: https://www.vldb.org/pvldb/vol12/p2195-masson.pdf
+			"""
+		required: false
+		type: bool: default: false
+	}
+	counters: {
+		description: "How counters are aggregated."
+		required:    false
+		type: string: {
+			default: "sum"
+			enum: {
+				delta: """
+					Counters are converted into the increase of their value over the interval, as incremental
+					counters.
+
+					Absolute counters are converted from the difference with their previous value, and are
+					only emitted once they have been seen twice. A counter going backwards is considered reset.
+					"""
+				rate: """
+					Counters are converted into their rate of increase per second over the interval, as
+					absolute gauges.
+
+					The rates are computed from the deltas of the counters, as with `delta`.
+					"""
+				sum: "Incremental counters are summed, and the last value of absolute counters is kept."
+			}
+		}
+	}
+	interval_ms: {
+		description: """
+			The interval between flushes, in milliseconds.
+
+			During this time frame, metrics (beta) with the same series data (name, namespace, tags, and so on) are aggregated.
+			"""
+		required: false
+		type: uint: default: 10000
+	}
 }
@@ -0,0 +1,141 @@
+package metadata
+
+base: components: transforms: correlate: configuration: {
+	emit_unmatched: {
+		description: """
+			Whether the events that aren't joined within `window_secs` are sent downstream on their own.
+
+			When enabled, each unmatched event is nested in the field of its side, and the field of the
+			other side is missing. When disabled, unmatched events are dropped.
+			"""
+		required: false
+		type: bool: default: true
+	}
+	left: {
+		description: "The events joined with the events of `right`."
+		required:    true
+		type: object: options: {
+			field: {
+				description: """
+					The field of the joined events in which the events of this side are nested.
+
+					Defaults to `left` for the left events, and to `right` for the right events.
+					"""
+				required: false
+				type: string: examples: ["query"]
+			}
+			inputs: {
+				description: """
+					The IDs of the inputs whose events are on this side of the join.
+
+					Each of them must also be one of the `inputs` of the transform. Events from the inputs of
+					neither side are sent downstream unmodified.
+					"""
+				required: true
+				type: array: items: type: string: examples: ["dns_queries"]
+			}
+			key: {
+				description: """
+					The fields whose values form the key that events are joined on.
+
+					Both sides must have the same number of fields, and their values are compared in order.
+					Events that are missing any of these fields are handled as unmatched.
+					"""
+				required: true
+				type: array: items: type: string: examples: ["query_id"]
+			}
+		}
+	}
+	max_events: {
+		description: """
+			The maximum number of events waiting in memory to be joined.
+
+			Once this many events are waiting, the next events are spilled to disk if `spill` is set.
+			Otherwise, or once the spilled events are also at their limit, the oldest waiting event is
+			handled as unmatched to make room for the next one.
+			"""
+		required: false
+		type: uint: default: 10000
+	}
+	right: {
+		description: "The events joined with the events of `left`."
+		required:    true
+		type: object: options: {
+			field: {
+				description: """
+					The field of the joined events in which the events of this side are nested.
+
+					Defaults to `left` for the left events, and to `right` for the right events.
+					"""
+				required: false
+				type: string: examples: ["query"]
+			}
+			inputs: {
+				description: """
+					The IDs of the inputs whose events are on this side of the join.
+
+					Each of them must also be one of the `inputs` of the transform. Events from the inputs of
+					neither side are sent downstream unmodified.
+					"""
+				required: true
+				type: array: items: type: string: examples: ["dns_queries"]
+			}
+			key: {
+				description: """
+					The fields whose values form the key that events are joined on.
+
+					Both sides must have the same number of fields, and their values are compared in order.
+					Events that are missing any of these fields are handled as unmatched.
+					"""
+				required: true
+				type: array: items: type: string: examples: ["query_id"]
+			}
+		}
+	}
+	spill: {
+		description: """
+			Spills the events waiting to be joined to disk, once `max_events` are waiting in memory.
+
+			Only the data of the events is spilled, and their metadata is kept in memory. The spilled
+			events don't survive restarts of Vector.
+			"""
+		required: false
+		type: object: options: {
+			data_dir: {
+				description: """
+					The directory used to spill the events.
+
+					By default, the [global `data_dir` option][global_data_dir] is used.
+					Make sure the running user has write permissions to this directory.
+
+					If this directory is specified, then Vector will attempt to create it.
+
+					[global_data_dir]: https://vector.dev/docs/reference/configuration/global-options/#data_dir
+					"""
+				required: false
+				type: string: examples: ["/var/local/lib/vector/"]
+			}
+			max_events: {
+				description: "The maximum number of events spilled to disk."
+				required:    false
+				type: uint: default: 1000000
+			}
+			max_memory_bytes: {
+				description: "The maximum size of the page cache of the database in memory, in bytes."
+				required:    false
+				type: uint: {
+					default: 67108864
+					unit:    "bytes"
+				}
+			}
+		}
+	}
+	window_secs: {
+		description: "How long each event waits for the event it's joined with, in seconds."
+		required:    false
+		type: float: {
+			default: 10.0
+			unit:    "seconds"
+		}
+	}
+}
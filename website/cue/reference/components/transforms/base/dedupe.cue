@@ -4,10 +4,62 @@ base: components: transforms: dedupe: configuration: {
 	cache: {
 		description: "Caching configuration for deduplication."
 		required:    false
-		type: object: options: num_events: {
-			description: "Number of events to cache and use for comparing incoming events to previously seen events."
-			required:    false
-			type: uint: default: 5000
+		type: object: options: {
+			disk: {
+				description: """
+					Persists the cache on disk, instead of keeping it in memory.
+
+					The cache then survives restarts of Vector, and can hold many more events than fit in
+					memory, which allows deduplicating events over windows of hours or days. Only a digest of
+					the matched fields is stored for each event. The oldest events are evicted first once the
+					cache is full.
+					"""
+				required: false
+				type: object: options: {
+					data_dir: {
+						description: """
+							The directory used to persist the cache.
+
+							By default, the [global `data_dir` option][global_data_dir] is used.
+							Make sure the running user has write permissions to this directory.
+
+							If this directory is specified, then Vector will attempt to create it.
+
+							[global_data_dir]: https://vector.dev/docs/reference/configuration/global-options/#data_dir
+							"""
+						required: false
+						type: string: examples: ["/var/local/lib/vector/"]
+					}
+					max_memory_bytes: {
+						description: "The maximum size of the page cache of the database in memory, in bytes."
+						required:    false
+						type: uint: {
+							default: 67108864
+							unit:    "bytes"
+						}
+					}
+				}
+			}
+			num_events: {
+				description: "Number of events to cache and use for comparing incoming events to previously seen events."
+				required:    false
+				type: uint: default: 5000
+			}
+			ttl_secs: {
+				description: """
+					The maximum age of the events in the cache, in seconds.
+
+					Incoming events are only compared to the previously seen events that are younger, and
+					older events are evicted from the cache. An event whose match expired is passed through,
+					and starts a new deduplication window. By default, events are only evicted once the cache
+					is full.
+					"""
+				required: false
+				type: uint: {
+					examples: [86400]
+					unit: "seconds"
+				}
+			}
 		}
 	}
 	fields: {
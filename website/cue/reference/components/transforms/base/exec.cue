@@ -0,0 +1,70 @@
+package metadata
+
+base: components: transforms: exec: configuration: {
+	command: {
+		description: """
+			The command to run, plus any arguments required.
+
+			Each event is written to the standard input of the command as a line of JSON, and the
+			command must write exactly one line of JSON to its standard output for each event, in the
+			same order: an object for the transformed event, an array of objects to replace the event
+			with several events, or `null` to drop the event.
+			"""
+		required: true
+		type: array: items: type: string: examples: ["python3", "./transform.py"]
+	}
+	concurrency: {
+		description: "The number of processes of the command to run, between which the events are distributed."
+		required:    false
+		type: uint: default: 1
+	}
+	environment: {
+		description: """
+			Custom environment variables to set or update when running the command.
+			If a variable name already exists in the environment, its value is replaced.
+			"""
+		required: false
+		type: object: {
+			examples: [{
+				LANG: "es_ES.UTF-8"
+				TZ:   "Etc/UTC"
+			}]
+			options: "*": {
+				description: "An environment variable."
+				required:    true
+				type: string: {}
+			}
+		}
+	}
+	restart_delay_secs: {
+		description: """
+			The amount of time, in seconds, to wait before restarting the command, after it exits or
+			fails to start.
+
+			The events the command has not written the output of yet when it exits are dropped.
+			"""
+		required: false
+		type: float: {
+			default: 1.0
+			unit:    "seconds"
+		}
+	}
+	timeout_secs: {
+		description: """
+			The maximum amount of time, in seconds, to wait for the output of an event.
+
+			If the command does not write the output of an event in time, it is killed and restarted,
+			and the events it has not written the output of yet are dropped.
+			"""
+		required: false
+		type: float: {
+			default: 30.0
+			unit:    "seconds"
+		}
+	}
+	working_directory: {
+		description: "The directory in which to run the command."
+		required:    false
+		type: string: {}
+	}
+}
@@ -0,0 +1,28 @@
+package metadata
+
+base: components: transforms: explode: configuration: {
+	field: {
+		description: """
+			The field containing the array to split into events.
+
+			Events without the field, or in which the field is not an array, are forwarded
+			unmodified. Events in which the field is an empty array are dropped.
+			"""
+		required: true
+		type: string: examples: ["records", "body.items"]
+	}
+	merge_parent_fields: {
+		description: """
+			Whether the fields of the original event are kept in the events of its elements.
+
+			When enabled, the fields of each element that is an object are merged into a copy of the
+			original event without the array, replacing the fields of the same name. When disabled,
+			each element that is an object becomes the event on its own.
+
+			Elements that are not objects are stored in `field`, in a copy of the original event or
+			in an otherwise empty event.
+			"""
+		required: false
+		type: bool: default: true
+	}
+}
@@ -0,0 +1,111 @@
+package metadata
+
+base: components: transforms: log_cluster: configuration: {
+	depth: {
+		description: """
+			The number of levels of the prefix tree used to find the pattern of each message.
+
+			The first level is the number of tokens of the messages, the last one holds the patterns,
+			and each level in between is one of the first tokens of the messages. Messages with a
+			different number of tokens, or different first tokens, are never part of the same pattern.
+			"""
+		required: false
+		type: uint: default: 4
+	}
+	field: {
+		description: """
+			The field containing the messages to cluster.
+
+			By default, the message of the events is used. Events without the field are forwarded
+			unmodified.
+			"""
+		required: false
+		type: string: examples: ["message"]
+	}
+	max_children: {
+		description: """
+			The maximum number of children of each node of the prefix tree.
+
+			Tokens beyond this limit are treated as variable.
+			"""
+		required: false
+		type: uint: default: 100
+	}
+	max_patterns: {
+		description: """
+			The maximum number of patterns.
+
+			When the limit is reached, the least recently matched pattern is forgotten to make room for
+			a new one.
+			"""
+		required: false
+		type: uint: default: 1000
+	}
+	metrics: {
+		description: """
+			Configuration for the metrics counting the events of each pattern.
+
+			The metrics are incremental counters emitted alongside the log events, tagged with the
+			`pattern_id` and the `pattern` template of their pattern.
+			"""
+		required: false
+		type: object: options: {
+			interval_secs: {
+				description: """
+					The interval between the emissions of the metrics, in seconds.
+
+					Only the patterns matched during the interval are counted.
+					"""
+				required: false
+				type: uint: {
+					default: 60
+					unit:    "seconds"
+				}
+			}
+			name: {
+				description: "The name of the metrics."
+				required:    false
+				type: string: default: "log_cluster_events"
+			}
+		}
+	}
+	new_pattern_field: {
+		description: """
+			The field in which is stored whether each event is the first one of its pattern.
+
+			This can be used to detect new kinds of messages.
+			"""
+		required: false
+		type: string: examples: ["new_pattern"]
+	}
+	pattern_id_field: {
+		description: """
+			The field in which the identifier of the pattern of each event is stored.
+
+			The identifiers are assigned to the patterns in the order in which they are found, and are
+			not preserved across restarts of Vector.
+			"""
+		required: false
+		type: string: default: "pattern_id"
+	}
+	similarity_threshold: {
+		description: """
+			The minimum ratio of the tokens of a message matching a pattern for the message to be part
+			of it, between `0` and `1`.
+
+			Lower values result in fewer, more general patterns.
+			"""
+		required: false
+		type: float: default: 0.4
+	}
+	template_field: {
+		description: """
+			The field in which the template of the pattern of each event is stored.
+
+			The template is the message, with the tokens varying between the messages of the pattern
+			replaced by `<*>`.
+			"""
+		required: false
+		type: string: default: "pattern"
+	}
+}
@@ -37,6 +37,20 @@ base: components: transforms: log_to_metric: configuration: {
 		description: "A list of metrics to generate."
 		required:    true
 		type: array: items: type: object: options: {
+			buckets: {
+				description: """
+					The upper limits of the buckets of the histogram, in ascending order.
+
+					When set, the values are counted in the buckets whose limits they don't exceed, and
+					aggregated histograms are emitted instead of distributions.
+					"""
+				relevant_when: "type = \"histogram\""
+				required:      false
+				type: array: {
+					default: []
+					items: type: float: {}
+				}
+			}
 			field: {
 				description: "Name of the field in the event to generate the metric."
 				required:    true
@@ -84,6 +98,18 @@ base: components: transforms: log_to_metric: configuration: {
 				required:    false
 				type: string: syntax: "template"
 			}
+			sketch: {
+				description: """
+					Emits the values as [DDSketches][ddsketch], instead of distributions.
+
+					This can't be combined with `buckets`.
+
+					[ddsketch]: https://www.vldb.org/pvldb/vol12/p2195-masson.pdf
+					"""
+				relevant_when: "type = \"histogram\""
+				required:      false
+				type: bool: default: false
+			}
 			tags: {
 				description: "Tags to apply to the metric."
 				required:    false
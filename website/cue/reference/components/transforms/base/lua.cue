@@ -1,6 +1,20 @@
 package metadata
 
 base: components: transforms: lua: configuration: {
+	emit_buffer_size: {
+		description: """
+			The maximum number of events emitted by the Lua code waiting to be sent downstream.
+
+			The Lua code runs on its own thread, and when this many events are waiting, the `emit`
+			function waits for downstream components to catch up, instead of blocking the other
+			components.
+			"""
+		required: false
+		type: uint: {
+			default: 1000
+			unit:    "events"
+		}
+	}
 	hooks: {
 		description: """
 			Lifecycle hooks.
@@ -140,6 +154,25 @@ base: components: transforms: lua: configuration: {
 			require('custom_module')
 			"""]
 	}
+	state: {
+		description: "Limits on the size of the shared state table."
+		required:    false
+		type: object: options: {
+			max_bytes: {
+				description: "The maximum size, in bytes, of the keys and values in the `vector.state` table."
+				required:    false
+				type: uint: {
+					default: 10485760
+					unit:    "bytes"
+				}
+			}
+			max_entries: {
+				description: "The maximum number of entries in the `vector.state` table."
+				required:    false
+				type: uint: default: 10000
+			}
+		}
+	}
 	timers: {
 		description: "A list of timers which should be configured and executed periodically."
 		required:    false
@@ -181,6 +214,7 @@ base: components: transforms: lua: configuration: {
 				This version is deprecated and will be removed in a future version.
 				"""
 			"2": "Lua transform API version 2."
+			"3": "Lua transform API version 3."
 		}
 	}
 }
@@ -0,0 +1,67 @@
+package metadata
+
+base: components: transforms: pii_redact: configuration: {
+	action: {
+		description: "How the detected values are redacted."
+		required:    false
+		type: string: {
+			default: "mask"
+			enum: {
+				hash: "Replaces the value with the hex-encoded HMAC-SHA256 hash of the value."
+				mask: "Replaces each character of the value with `*`."
+				tokenize: """
+					Replaces the value with a token naming the detector, such as `[email:4b6c8a0f2d1e3f5a]`.
+
+					The token is derived from the hash of the value, so the occurrences of a value are replaced
+					with the same token and can still be correlated.
+					"""
+			}
+		}
+	}
+	detectors: {
+		description: "The detectors to run on the string fields."
+		required:    false
+		type: array: {
+			default: ["email", "credit_card", "ssn", "api_key", "ip_address"]
+			items: type: string: {
+				enum: {
+					api_key: """
+						API keys and access tokens with a well-known prefix: AWS access key IDs, GitHub, Slack, and
+						Stripe tokens, and Google API keys.
+						"""
+					credit_card: """
+						Credit card numbers of 13 to 19 digits, optionally grouped with spaces or dashes, that pass
+						the Luhn check.
+						"""
+					email:      "Email addresses."
+					ip_address: "IPv4 and IPv6 addresses."
+					ssn:        "US Social Security numbers, formatted as `AAA-GG-SSSS`."
+				}
+				examples: ["email", "credit_card"]
+			}
+		}
+	}
+	fields: {
+		description: """
+			The fields to scan.
+
+			The fields are scanned recursively, including the strings in nested objects and arrays. By
+			default, all of the fields of the events are scanned.
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: string: examples: ["message", "user.email"]
+		}
+	}
+	key: {
+		description: """
+			The key of the HMAC-SHA256 hashes of the `hash` and `tokenize` actions.
+
+			Without a key, the values with few possibilities, such as Social Security numbers, can be
+			recovered from their hashes by brute force.
+			"""
+		required: false
+		type: string: examples: ["${PII_REDACT_KEY}"]
+	}
+}
@@ -0,0 +1,98 @@
+package metadata
+
+base: components: transforms: quota: configuration: {
+	action: {
+		description: "What happens to the events received once their key exceeded its quota."
+		required:    false
+		type: string: {
+			default: "drop"
+			enum: {
+				drop: "Drops the events."
+				reroute: """
+					Sends the events to the `over_quota` output, instead of the default output.
+
+					The events can then be consumed by other components with the name
+					`<transform_name>.over_quota`.
+					"""
+				tag: "Forwards the events, with the `tag_field` field set to `true`."
+			}
+		}
+	}
+	key_field: {
+		description: """
+			The key whose usage is limited, such as a tenant or a namespace.
+
+			The events without the key, and all of the events when it's unset, share a single quota.
+			"""
+		required: false
+		type: string: {
+			examples: ["{{ tenant }}", "{{ namespace }}"]
+			syntax: "template"
+		}
+	}
+	limits: {
+		description: "The limits of the usage of a key over the window."
+		required:    false
+		type: object: options: {
+			max_bytes: {
+				description: """
+					The maximum number of bytes of the events of a key in the window.
+
+					The size of an event is estimated as the size of its JSON encoding.
+					"""
+				required: false
+				type: uint: {
+					examples: [100000000]
+					unit: "bytes"
+				}
+			}
+			max_events: {
+				description: "The maximum number of events of a key in the window."
+				required:    false
+				type: uint: examples: [100000]
+			}
+		}
+	}
+	overrides: {
+		description: "The limits of specific keys, replacing the default `limits`."
+		required:    false
+		type: object: options: "*": {
+			description: "The limits of an individual key."
+			required:    true
+			type: object: options: {
+				max_bytes: {
+					description: """
+						The maximum number of bytes of the events of a key in the window.
+
+						The size of an event is estimated as the size of its JSON encoding.
+						"""
+					required: false
+					type: uint: {
+						examples: [100000000]
+						unit: "bytes"
+					}
+				}
+				max_events: {
+					description: "The maximum number of events of a key in the window."
+					required:    false
+					type: uint: examples: [100000]
+				}
+			}
+		}
+	}
+	tag_field: {
+		description: "The field set to `true` in the events exceeding their quota, with the `tag` action."
+		required:    false
+		type: string: default: "quota_exceeded"
+	}
+	window_secs: {
+		description: """
+			The duration of the rolling window over which the usage of each key is limited, in
+			seconds.
+
+			The usage expires in tenths of the window, so the quota of a key is restored gradually.
+			"""
+		required: true
+		type: uint: {}
+	}
+}
@@ -22,6 +22,21 @@ base: components: transforms: reduce: configuration: {
 			unit:    "milliseconds"
 		}
 	}
+	finalizer: {
+		description: """
+			A [Vector Remap Language][vrl] (VRL) program run on each reduced event before it's emitted.
+
+			The program can modify the reduced event, or drop it with `abort`. If the program fails,
+			the reduced event is emitted unmodified.
+
+			[vrl]: https://vector.dev/docs/reference/vrl
+			"""
+		required: false
+		type: string: {
+			examples: [".average = .total / .count"]
+			syntax: "remap_program"
+		}
+	}
 	flush_period_ms: {
 		description: "The interval to check for and flush any expired events, in milliseconds."
 		required:    false
@@ -30,6 +45,27 @@ base: components: transforms: reduce: configuration: {
 			unit:    "milliseconds"
 		}
 	}
+	fold: {
+		description: """
+			A [Vector Remap Language][vrl] (VRL) program that folds each event into the reduced event,
+			instead of the merge strategies.
+
+			The program runs on the reduced event, which has no fields until the first event of its
+			group is folded into it, and the folded event is available at `%reduce.event`. The reduced
+			event only contains the fields set by the program. If the program fails or aborts, the
+			event isn't folded into the reduced event.
+
+			[vrl]: https://vector.dev/docs/reference/vrl
+			"""
+		required: false
+		type: string: {
+			examples: ["""
+				.count = (int(.count) ?? 0) + 1
+				.messages = push(array(.messages) ?? [], %reduce.event.message)
+				"""]
+			syntax: "remap_program"
+		}
+	}
 	group_by: {
 		description: """
 			An ordered list of fields by which to group events.
@@ -34,4 +34,44 @@ base: components: transforms: route: configuration: {
 			type: condition: {}
 		}
 	}
+	split: {
+		description: """
+			Splits the events between routes, in proportion to their weights.
+
+			Each event is sent to exactly one of the split routes, in addition to the routes whose
+			condition it matches, so none of the events are unmatched and the `_unmatched` output is
+			disabled.
+			"""
+		required: false
+		type: object: options: {
+			key: {
+				description: """
+					The field whose value is hashed to pick the route of an event.
+
+					The events with the same value always take the same route, as long as the routes and their
+					weights are unchanged, so that an entity such as a user consistently takes the same path
+					during an experiment. The events without the field, and all of the events when it's unset,
+					are split at random.
+					"""
+				required: false
+				type: string: examples: ["user_id"]
+			}
+			routes: {
+				description: """
+					A table of route identifiers to weights.
+
+					Each route receives a share of the events equal to its weight divided by the sum of the
+					weights: for example, with the weights `5` and `95`, the first route receives 5% of the
+					events. Each route can then be referenced as an input by other components with the name
+					`<transform_name>.<route_id>`.
+					"""
+				required: true
+				type: object: options: "*": {
+					description: "The weight of an individual route."
+					required:    true
+					type: uint: {}
+				}
+			}
+		}
+	}
 }
@@ -35,4 +35,20 @@ base: components: transforms: sample: configuration: {
 			1500,
 		]
 	}
+	rate_expression: {
+		description: """
+			A [VRL][vrl] expression computing the rate at which each event is sampled, expressed as `1/N`.
+
+			The expression must return a positive integer. If it fails, or returns another value, the
+			event is sampled at `rate` instead.
+
+			Events sampled at a computed rate are sampled randomly rather than by counting them. When
+			`key_field` is set, the events with the same key are still sampled together, as long as
+			the expression returns the same rate for them.
+
+			[vrl]: https://vector.dev/docs/reference/vrl
+			"""
+		required: false
+		type: string: examples: ["if .level == \"error\" { 1 } else { 100 }", "to_int(.sample_rate) ?? 10"]
+	}
 }
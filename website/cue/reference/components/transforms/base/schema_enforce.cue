@@ -0,0 +1,69 @@
+package metadata
+
+base: components: transforms: schema_enforce: configuration: {
+	coerce_types: {
+		description: """
+			Converts fields to the type required by the schema, when their value has an unambiguous
+			representation in that type.
+
+			Strings are converted to integers, numbers, and booleans, and integers, numbers, and booleans
+			are converted to strings.
+			"""
+		required: false
+		type: bool: default: false
+	}
+	drop_unknown_fields: {
+		description: """
+			Removes the fields of objects that are not declared in their `properties`, instead of
+			validating them against `additionalProperties`.
+			"""
+		required: false
+		type: bool: default: false
+	}
+	fill_defaults: {
+		description: "Inserts the `default` value of the properties of objects that are missing."
+		required:    false
+		type: bool: default: false
+	}
+	reroute_dropped: {
+		description: """
+			Reroutes the events that do not conform to the schema to a named output instead of
+			dropping them.
+
+			When set to `true`, the original event is forwarded to a specially-named output,
+			`dropped`, for quarantine. The event is annotated with additional fields describing how it
+			violates the schema.
+			"""
+		required: false
+		type: bool: default: true
+	}
+	schema: {
+		description: """
+			The [JSON Schema][json_schema] to validate the events against.
+
+			The supported keywords are `type`, `enum`, `const`, `default`, `properties`, `required`,
+			`additionalProperties`, `items`, `minItems`, `maxItems`, `minLength`, `maxLength`,
+			`pattern`, `minimum`, `maximum`, `exclusiveMinimum`, and `exclusiveMaximum`. Annotations
+			such as `title` and `description` are ignored, and any other keyword is rejected.
+
+			Required if `schema_file` is missing.
+
+			[json_schema]: https://json-schema.org/
+			"""
+		required: false
+		type: string: examples: ["{\"type\": \"object\", \"required\": [\"message\"], \"properties\": {\"message\": {\"type\": \"string\"}}}"]
+	}
+	schema_file: {
+		description: """
+			File path to the [JSON Schema][json_schema] to validate the events against.
+
+			If a relative path is provided, its root is the current working directory.
+
+			Required if `schema` is missing.
+
+			[json_schema]: https://json-schema.org/
+			"""
+		required: false
+		type: string: examples: ["./schemas/access_log.json"]
+	}
+}
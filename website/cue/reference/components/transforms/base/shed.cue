@@ -0,0 +1,43 @@
+package metadata
+
+base: components: transforms: shed: configuration: {
+	max_events_per_sec: {
+		description: """
+			The maximum number of events sent downstream per second.
+
+			When set, the events above this rate wait to be sent, as if a downstream component applied
+			backpressure.
+			"""
+		required: false
+		type: uint: examples: [1000]
+	}
+	max_queued_events: {
+		description: """
+			The maximum number of events waiting to be sent downstream.
+
+			Events wait when downstream components apply backpressure, or when `max_events_per_sec`
+			is reached. Once this many events are waiting, the events with the lowest priority are
+			shed, starting with the most recent ones.
+			"""
+		required: false
+		type: uint: default: 1000
+	}
+	priority: {
+		description: """
+			A [Vector Remap Language][vrl] (VRL) expression that computes the priority of each event.
+
+			The expression must return an integer, and the events with the lowest priorities are shed
+			first. If the expression fails for an event, the event gets a priority of `0`.
+
+			The priority tags the `shed_events_total` internal metric, so the expression should only
+			return a few distinct priorities.
+
+			[vrl]: https://vector.dev/docs/reference/vrl
+			"""
+		required: true
+		type: string: {
+			examples: ["if .level == \"error\" { 2 } else if .level == \"warn\" { 1 } else { 0 }"]
+			syntax: "remap_program"
+		}
+	}
+}
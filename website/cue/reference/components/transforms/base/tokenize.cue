@@ -0,0 +1,100 @@
+package metadata
+
+base: components: transforms: tokenize: configuration: {
+	fields: {
+		description: """
+			The fields whose values are replaced.
+
+			Missing fields are ignored. If a field isn't a string, or its value can't be replaced, the
+			event is dropped, so that sensitive values are never forwarded.
+			"""
+		required: true
+		type: array: items: type: string: examples: ["card_number", "user.email"]
+	}
+	method: {
+		description: "How the tokens are created from the values."
+		required:    true
+		type: object: options: {
+			alphabet: {
+				description:   "The characters encrypted by the `fpe` method."
+				relevant_when: "type = \"fpe\""
+				required:      false
+				type: string: {
+					default: "numeric"
+					enum: {
+						alphanumeric: "The digits, and the lowercase and uppercase ASCII letters."
+						numeric:      "The digits, from `0` to `9`."
+					}
+				}
+			}
+			data_dir: {
+				description: """
+					The directory in which the vault is stored, in a `tokenize` subdirectory.
+
+					By default, the [global `data_dir` option][global_data_dir] is used. The vault is shared
+					by the components using the same directory.
+
+					[global_data_dir]: https://vector.dev/docs/reference/configuration/global-options/#data_dir
+					"""
+				relevant_when: "type = \"mapping\""
+				required:      false
+				type: string: examples: ["/var/lib/vector/tokens/"]
+			}
+			key: {
+				description: """
+					The AES key of the encryption, hex-encoded.
+
+					The key must be 16, 24, or 32 bytes long.
+					"""
+				relevant_when: "type = \"fpe\""
+				required:      true
+				type: string: examples: ["${TOKENIZE_KEY}"]
+			}
+			tweak: {
+				description: """
+					The tweak of the encryption, as 7 hex-encoded bytes.
+
+					The tweak doesn't need to be secret, but different tweaks result in different tokens
+					for the same values. By default, a tweak of zeros is used.
+					"""
+				relevant_when: "type = \"fpe\""
+				required:      false
+				type: string: examples: ["d8e7920afa330a"]
+			}
+			type: {
+				description: "The method of the tokenization."
+				required:    true
+				type: string: enum: {
+					fpe: """
+						Encrypts the values with the FF3-1 format-preserving encryption mode.
+
+						The characters of the values in the alphabet are encrypted into other characters of the
+						alphabet, and the others are kept, so that tokens have the same length and format as their
+						values. The same value is always encrypted into the same token, and tokens can be decrypted
+						by any pipeline with the same key and tweak, without any state.
+						"""
+					mapping: """
+						Replaces the values with random tokens, stored in a vault on disk.
+
+						Digits are replaced with random digits, and letters with random letters of the same case,
+						so that tokens keep the format of their values. The tokens are kept in the vault, so that
+						each value always gets the same token, and tokens can be replaced with their values by the
+						pipelines using the same vault. The vault contains the original values, and must be
+						protected like them.
+						"""
+				}
+			}
+		}
+	}
+	operation: {
+		description: "Whether values are replaced with their tokens, or tokens with their values."
+		required:    false
+		type: string: {
+			default: "tokenize"
+			enum: {
+				detokenize: "Replaces the tokens with their original values."
+				tokenize:   "Replaces the values with their tokens."
+			}
+		}
+	}
+}
@@ -0,0 +1,76 @@
+package metadata
+
+base: components: transforms: trace_sampler: configuration: {
+	decision_wait_secs: {
+		description: """
+			How long to wait for the spans of a trace after its first span, in seconds.
+
+			The spans of each trace are buffered until then, and the policies are applied to all of
+			them at once. The spans received after the decision, within another `decision_wait_secs`,
+			follow the same decision.
+			"""
+		required: false
+		type: float: {
+			default: 30.0
+			unit:    "seconds"
+		}
+	}
+	max_traces: {
+		description: """
+			The maximum number of traces buffered while waiting for their spans.
+
+			Once this many traces are buffered, the policies are applied to the oldest trace early,
+			to make room for the next one.
+			"""
+		required: false
+		type: uint: default: 50000
+	}
+	policies: {
+		description: """
+			The policies that decide whether to sample each trace.
+
+			A trace is sampled when any of the policies samples it, and all of its spans are dropped
+			otherwise.
+			"""
+		required: true
+		type: array: items: type: object: options: {
+			percentage: {
+				description:   "The percentage of the traces to sample, between `0` and `100`."
+				relevant_when: "type = \"probabilistic\""
+				required:      true
+				type: float: examples: [10.0]
+			}
+			threshold_ms: {
+				description:   "The minimum duration of the sampled traces, in milliseconds."
+				relevant_when: "type = \"latency\""
+				required:      true
+				type: uint: examples: [500]
+			}
+			type: {
+				description: "The type of the policy."
+				required:    true
+				type: string: enum: {
+					error: """
+						Sample the traces with at least one failed span.
+
+						A span failed when its `status.code` field is `2`, which is the OpenTelemetry
+						`STATUS_CODE_ERROR` status.
+						"""
+					latency: """
+						Sample the traces that last at least a threshold, from the start of their first span to the
+						end of their last span.
+
+						The bounds of the spans are read from their `start_time_unix_nano` and `end_time_unix_nano`
+						fields.
+						"""
+					probabilistic: """
+						Sample a percentage of the traces.
+
+						The decision is based on a hash of the trace ID, so that each Vector instance makes the same
+						decision for the spans of a trace.
+						"""
+				}
+			}
+		}
+	}
+}
@@ -0,0 +1,48 @@
+package metadata
+
+base: components: transforms: wasm: configuration: {
+	fuel: {
+		description: """
+			The maximum amount of fuel the module can consume to process an event.
+
+			Fuel is consumed by each WebAssembly instruction, and an event whose processing runs out of
+			fuel is dropped, protecting the pipeline from infinite loops. By default, the fuel is not
+			limited.
+			"""
+		required: false
+		type: uint: examples: [10000000]
+	}
+	hot_reload: {
+		description: """
+			Whether or not to reload the module when its file changes.
+
+			The file is checked for changes every second. If the new module is not valid, the previous
+			one is kept.
+			"""
+		required: false
+		type: bool: default: false
+	}
+	max_memory_bytes: {
+		description: "The maximum size, in bytes, of the memory of the module."
+		required:    false
+		type: uint: {
+			default: 67108864
+			unit:    "bytes"
+		}
+	}
+	module: {
+		description: """
+			The path of the WebAssembly module, in the binary or in the text format.
+
+			The module must not import any function, and must export its `memory`, an
+			`alloc(len: i32) -> i32` function returning the address of a buffer of `len` bytes, and a
+			`process(ptr: i32, len: i32) -> i64` function. Each event is written to a buffer as JSON
+			and passed to `process`, which returns the address of the buffer of its output in the high
+			32 bits, and its length in the low 32 bits. The output is JSON as well: an object for the
+			transformed event, an array of objects to replace the event with several events, or `null`
+			to drop the event.
+			"""
+		required: true
+		type: string: examples: ["/etc/vector/transform.wasm"]
+	}
+}
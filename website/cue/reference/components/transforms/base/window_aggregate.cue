@@ -0,0 +1,116 @@
+package metadata
+
+base: components: transforms: window_aggregate: configuration: {
+	aggregations: {
+		description: """
+			A map of the fields of the summary events to the aggregations computed into them.
+
+			Besides these fields and the fields in `group_by`, each summary event contains the bounds
+			of its window, in the `window_start` and `window_end` fields.
+			"""
+		required: false
+		type: object: options: "*": {
+			description: "An aggregation."
+			required:    true
+			type: object: options: {
+				field: {
+					description: """
+						The field to aggregate.
+
+						Events whose field is missing, or isn't a number, are ignored by the aggregation.
+						"""
+					relevant_when: "function = \"sum\" or function = \"min\" or function = \"max\" or function = \"percentile\""
+					required:      true
+					type: string: {}
+				}
+				function: {
+					description: "The function of the aggregation."
+					required:    true
+					type: string: enum: {
+						count: "The number of events in the window."
+						max:   "The maximum of the values of a field."
+						min:   "The minimum of the values of a field."
+						percentile: """
+							A percentile of the values of a field, computed with the nearest-rank method.
+
+							All the values of the field in the window are kept until the window is flushed.
+							"""
+						sum: "The sum of the values of a field."
+					}
+				}
+				percentile: {
+					description:   "The percentile to compute, between `0` and `100`."
+					relevant_when: "function = \"percentile\""
+					required:      true
+					type: float: examples: [50.0, 99.0]
+				}
+			}
+		}
+	}
+	finalizer: {
+		description: """
+			A [Vector Remap Language][vrl] (VRL) program run on each summary event before it's emitted.
+
+			The program can modify the summary event, or drop it with `abort`. If the program fails,
+			the summary event is emitted unmodified.
+
+			[vrl]: https://vector.dev/docs/reference/vrl
+			"""
+		required: false
+		type: string: {
+			examples: [".error_rate = .errors / .count"]
+			syntax: "remap_program"
+		}
+	}
+	group_by: {
+		description: """
+			An ordered list of fields by which to group events.
+
+			Each group with matching values for the specified fields is aggregated independently, into
+			one summary event per window that contains these fields. When no fields are specified, all
+			events are aggregated in a single group.
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: string: examples: ["service", "status"]
+		}
+	}
+	window: {
+		description: """
+			The windows over which events are aggregated.
+
+			Windows are based on the time at which the events are processed, rather than on their
+			timestamps.
+			"""
+		required: false
+		type: object: options: {
+			duration_ms: {
+				description: "The duration of the windows, in milliseconds."
+				required:    true
+				type: uint: unit: "milliseconds"
+			}
+			step_ms: {
+				description: """
+					The interval between the starts of consecutive windows, in milliseconds.
+
+					The duration of the windows must be a multiple of it.
+					"""
+				relevant_when: "type = \"sliding\""
+				required:      true
+				type: uint: unit: "milliseconds"
+			}
+			type: {
+				description: "The type of the windows."
+				required:    true
+				type: string: enum: {
+					sliding: """
+						Overlapping windows that start at every step, such that each event is aggregated in
+						`duration_ms / step_ms` windows.
+						"""
+					tumbling: "Consecutive windows that don't overlap, such that each event is aggregated in one window."
+				}
+			}
+		}
+	}
+}
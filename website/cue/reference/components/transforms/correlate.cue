@@ -0,0 +1,60 @@
+package metadata
+
+components: transforms: correlate: {
+	title: "Correlate"
+
+	description: """
+		Joins the events of two sets of inputs on a key within a time window, such as DNS
+		queries and their responses, or requests and their audit records.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		reduce: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.transforms.correlate.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		joining: {
+			title: "Joining events"
+			body: """
+				Each event from the `left.inputs` waits for an event from the `right.inputs` with
+				the same key, and the other way around, for up to `window_secs`. When several
+				events of a side wait with the same key, they're joined in the order they were
+				received, so each event is joined at most once.
+
+				A joined event contains the event of each side, nested in the `left.field` and
+				`right.field` fields, and is acknowledged once both of the original events would
+				have been. The events that aren't joined within the window are sent downstream
+				with only their side, unless `emit_unmatched` is disabled.
+				"""
+		}
+		bounded_state: {
+			title: "Bounded state"
+			body: """
+				Up to `max_events` events wait in memory. When `spill` is set, the next events
+				wait on disk instead, up to `spill.max_events` events. Once both limits are
+				reached, the oldest waiting event is handled as unmatched for each new event.
+				"""
+		}
+	}
+}
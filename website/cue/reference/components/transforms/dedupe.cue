@@ -47,6 +47,37 @@ components: transforms: dedupe: {
 				"""
 		}
 
+		disk_cache: {
+			title: "Disk Cache"
+			body: """
+				With `cache.disk` set, the cache is persisted in a database in the
+				data directory of the transform, instead of being kept in memory.
+				The cache then survives restarts of Vector, which no longer reset
+				the deduplication window, and its size is bounded by
+				`cache.num_events` on disk, with only a bounded page cache in memory.
+				Each entry stores a SHA-256 digest of the fields considered for
+				matching, instead of their values, along with the time it was
+				first seen. Entries are evicted from the disk cache in the order
+				they were first seen.
+
+				Combined with `cache.ttl_secs`, this allows deduplicating events
+				over windows of hours or days on aggregators. If the disk cache
+				fails, the events are passed through, as they can't be told apart
+				from new events.
+				"""
+		}
+
+		expiration: {
+			title: "Expiration"
+			body: """
+				With `cache.ttl_secs` set, the events in the cache expire once
+				they are older than the time to live. An incoming event that
+				matches an expired event isn't considered a duplicate, is passed
+				through, and replaces the expired event in the cache. Duplicates
+				don't extend the time to live of the event they match.
+				"""
+		}
+
 		memory_usage_details: {
 			title: "Memory Usage Details"
 			body: """
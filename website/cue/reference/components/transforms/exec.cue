@@ -0,0 +1,83 @@
+package metadata
+
+components: transforms: exec: {
+	title: "Exec"
+
+	description: """
+		Pipes log events through a long-running process, for transformation logic that cannot be
+		expressed in VRL or Lua, such as calling into an existing library.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		program: {
+			runtime: {
+				name:    "Any"
+				url:     "https://en.wikipedia.org/wiki/Standard_streams"
+				version: null
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: [
+			"""
+				Each event takes a round trip through the process, which is much slower than
+				transforming it in VRL. Use the `remap` transform when possible.
+				""",
+		]
+		notices: []
+	}
+
+	configuration: base.components.transforms.exec.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		protocol: {
+			title: "Protocol"
+			body: """
+				The command is started when the transform starts, and runs for as long as the
+				transform does. Each event is written to its standard input as a line of JSON, and
+				the command must write one line of JSON to its standard output for each event, in
+				the same order:
+
+				- an object, which replaces the fields of the event,
+				- an array of objects, which replaces the event with one event per object,
+				- `null`, which drops the event.
+
+				The standard error of the command is written along with the logs of Vector. When the
+				events stop, the standard input of the command is closed, and the command should
+				exit after writing the output of the remaining events.
+				"""
+		}
+		failures: {
+			title: "Failures"
+			body: """
+				If the command does not write the output of an event within `timeout_secs`, it is
+				killed. If it exits, it is restarted after `restart_delay_secs`. In both cases, the
+				events it has not written the output of yet are dropped. An event whose output is
+				not valid is dropped as well.
+				"""
+		}
+		concurrency: {
+			title: "Concurrency"
+			body: """
+				With `concurrency` set above `1`, several processes of the command are run, and each
+				event is written to the one with the fewest events waiting for their output, so the
+				order of the events is not preserved between the processes.
+				"""
+		}
+	}
+}
@@ -0,0 +1,84 @@
+package metadata
+
+components: transforms: explode: {
+	title: "Explode"
+
+	description: """
+		Splits the elements of an array field into individual events, such as the records of a
+		batched webhook payload.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		convert: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.transforms.explode.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	examples: [
+		{
+			title: "Split a batch of records"
+			configuration: {
+				field: "records"
+			}
+			input: log: {
+				source: "webhook"
+				records: [
+					{id: 1, status: "ok"},
+					{id: 2, status: "failed"},
+				]
+			}
+			output: [
+				{log: {source: "webhook", id: 1, status: "ok"}},
+				{log: {source: "webhook", id: 2, status: "failed"}},
+			]
+		},
+		{
+			title: "Replace events with their elements"
+			configuration: {
+				field:               "records"
+				merge_parent_fields: false
+			}
+			input: log: {
+				source: "webhook"
+				records: [
+					{id: 1},
+					{id: 2},
+				]
+			}
+			output: [
+				{log: {id: 1}},
+				{log: {id: 2}},
+			]
+		},
+	]
+
+	how_it_works: {
+		metadata: {
+			title: "Metadata"
+			body: """
+				The events of the elements share the metadata of the original event, such as its
+				source and its acknowledgements. The original event is acknowledged once all the
+				events of its elements are delivered.
+				"""
+		}
+	}
+}
@@ -0,0 +1,71 @@
+package metadata
+
+components: transforms: log_cluster: {
+	title: "Log Cluster"
+
+	description: """
+		Clusters log messages into patterns with the [Drain](\(urls.drain)) algorithm, storing the
+		identifier and the template of the pattern of each message in the event, and optionally
+		emitting metrics counting the events of each pattern.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		aggregate: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.transforms.log_cluster.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		patterns: {
+			title: "Patterns"
+			body: """
+				Messages are split into tokens on whitespace. The candidate patterns of a message are
+				the ones with the same number of tokens and the same first `depth - 2` tokens, where
+				tokens containing digits are considered variable. The message joins the candidate
+				whose template has the most tokens in common with it, if at least
+				`similarity_threshold` of its tokens match, and the tokens of the template that differ
+				from the message are replaced by `<*>`. Otherwise, the message starts a new pattern.
+
+				As patterns are generalized by new messages, the template stored in the events of a
+				pattern may change, while its identifier stays the same.
+				"""
+		}
+		volume_reduction: {
+			title: "Volume Reduction"
+			body: """
+				The `metrics` option emits an incremental `counter` of the number of events of each
+				pattern at every interval, tagged with the `pattern_id` and the `pattern` template of
+				the pattern, which can replace the log events themselves in expensive sinks. The
+				`new_pattern_field` option marks the first event of each pattern, to forward only
+				the new kinds of messages.
+				"""
+		}
+		memory_usage: {
+			title: "Memory Usage"
+			body: """
+				The transform keeps the template of each pattern, up to `max_patterns` patterns.
+				When the limit is reached, the least recently matched pattern is forgotten, and its
+				messages start a new pattern with a new identifier.
+				"""
+		}
+	}
+}
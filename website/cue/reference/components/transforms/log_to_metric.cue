@@ -318,6 +318,19 @@ components: transforms: log_to_metric: {
 				individual metrics for reduction in the metrics storage itself.
 				"""
 		}
+		histograms: {
+			title: "Histograms"
+			body: """
+				By default, a `histogram` metric emits each value as a distribution, whose samples
+				are aggregated by the downstream components or the metrics storage. A distribution
+				can instead be aggregated in the transform itself:
+
+				* With `buckets`, each value is counted in the buckets whose upper limits it doesn't
+				  exceed, and an aggregated histogram is emitted, as expected by Prometheus.
+				* With `sketch`, each value is inserted into a [DDSketch](https://www.vldb.org/pvldb/vol12/p2195-masson.pdf), as
+				  expected by Datadog.
+				"""
+		}
 		null_fields: {
 			title: "Null Fields"
 			body: """
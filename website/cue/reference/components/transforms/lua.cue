@@ -296,6 +296,26 @@ components: transforms: lua: {
 				[the manual](\(urls.lua_manual)) would suffice.
 				"""
 		}
+		version_3: {
+			title: "Version 3"
+			body:  """
+				Version 3 of the API accepts the same hooks and timers as version 2, and adds:
+
+				- A `vector.state` table shared by the hooks and the timers, whose size is limited by
+				  `state.max_entries` and `state.max_bytes`. Values are copied in and out of the
+				  table, so a table read from the state must be assigned to it again once changed.
+				  Setting a value above the limits raises an error.
+				- The `vector.set_interval(seconds, handler)` and `vector.set_timeout(seconds, handler)`
+				  functions, which set timers from the Lua code, calling `handler` with `emit` every
+				  `seconds`, or once after `seconds`. They return an identifier that can be passed to
+				  `vector.clear_timer(id)`.
+
+				The Lua code runs on its own thread, and the emitted events are sent downstream as
+				they're emitted, with up to `emit_buffer_size` events waiting. Slow Lua code, such
+				as a timer flushing a large aggregation, therefore does not block the other
+				components.
+				"""
+		}
 		search_dirs: {
 			title: "Search Directories"
 			body:  """
@@ -0,0 +1,60 @@
+package metadata
+
+components: transforms: pii_redact: {
+	title: "PII Redact"
+
+	description: """
+		Detects personally identifiable information, such as email addresses, credit card numbers,
+		and API keys, in the string fields of events, and masks, hashes, or tokenizes it.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		sanitize: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.transforms.pii_redact.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  true
+	}
+
+	how_it_works: {
+		detection: {
+			title: "Detection"
+			body: """
+				Every string in the scanned fields is searched by each detector, including the
+				strings nested in objects and arrays. The candidates found by the patterns of the
+				detectors are then checked, so that, for example, only the credit card numbers
+				passing the Luhn check, the Social Security numbers with a valid area and group,
+				and the IP addresses that parse are redacted. When detections overlap, the one
+				that starts first is redacted. Only the detected values are replaced, and the rest
+				of the strings is left as-is.
+
+				The detectors favor precision over recall, and are not a substitute for removing
+				fields known to hold personally identifiable information with the
+				[`remap`][remap] transform.
+
+				[remap]: https://vector.dev/docs/reference/configuration/transforms/remap/
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		pii_redactions_total: components.sources.internal_metrics.output.metrics.pii_redactions_total
+	}
+}
@@ -0,0 +1,68 @@
+package metadata
+
+components: transforms: quota: {
+	title: "Quota"
+
+	description: """
+		Enforces quotas of events and bytes per key, such as a tenant or a namespace, over a rolling
+		window, and drops, tags, or reroutes the events exceeding them.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		filter: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.transforms.quota.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	outputs: [
+		{
+			name:        "over_quota"
+			description: "With the `reroute` action, the events exceeding their quota can be referenced as an input by other components with the name `<transform_name>.over_quota`."
+		},
+	]
+
+	how_it_works: {
+		rolling_window: {
+			title: "Rolling Window"
+			body: """
+				The usage of each key is counted in tenths of `window_secs`. An event is within the
+				quota of its key if the events of the key forwarded during the last `window_secs`,
+				including this event, are within `max_events` and `max_bytes`. The events exceeding the
+				quota don't count in the usage, and the usage of each tenth of the window expires
+				together, so the quota of a key is restored gradually.
+				"""
+		}
+		state: {
+			title: "State"
+			body: """
+				The usage is kept in memory, and is reset when Vector restarts. The keys without usage
+				in the window are forgotten.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		quota_exceeded_events_total: components.sources.internal_metrics.output.metrics.quota_exceeded_events_total
+		quota_usage_bytes:           components.sources.internal_metrics.output.metrics.quota_usage_bytes
+		quota_usage_events:          components.sources.internal_metrics.output.metrics.quota_usage_events
+	}
+}
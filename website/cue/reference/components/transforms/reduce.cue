@@ -144,6 +144,20 @@ components: transforms: reduce: {
 		},
 	]
 
+	how_it_works: {
+		vrl_fold: {
+			title: "Folding events with VRL"
+			body: """
+				Instead of the merge strategies, a `fold` program can combine the events of each
+				group. It runs on the reduced event for each event of the group, with the event
+				available at `%reduce.event`, and sets the fields of the reduced event from it,
+				such as counters or lists of values. A `finalizer` program can then compute the
+				final fields of the reduced event, such as averages, or drop it with `abort`. The
+				`finalizer` can also be used with the merge strategies.
+				"""
+		}
+	}
+
 	telemetry: metrics: {
 		stale_events_flushed_total: components.sources.internal_metrics.output.metrics.stale_events_flushed_total
 	}
@@ -95,4 +95,21 @@ components: transforms: route: {
 			description: "Each route can be referenced as an input by other components with the name `<transform_name>.<route_id>`."
 		},
 	]
+
+	how_it_works: {
+		weighted_split: {
+			title: "Weighted split"
+			body: """
+				The `split` option sends each event to exactly one of its routes, in proportion to
+				their weights, which is useful to send a small share of the traffic to a canary
+				sink. When `split.key` is set, the value of the field is hashed to pick the route,
+				so the events of an entity, such as a user, consistently take the same route
+				during an experiment, as long as the routes and their weights are unchanged.
+				Changing the weights moves a share of the entities between the routes.
+
+				Since every event takes a split route, none of the events are unmatched, and the
+				`_unmatched` output is disabled when `split` is set.
+				"""
+		}
+	}
 }
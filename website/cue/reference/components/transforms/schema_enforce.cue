@@ -0,0 +1,67 @@
+package metadata
+
+components: transforms: schema_enforce: {
+	title: "Schema Enforce"
+
+	description: """
+		Validates log events against a JSON Schema, optionally conforming them to it, and
+		quarantines the events that do not conform to it.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		filter: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.transforms.schema_enforce.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	outputs: [
+		components._default_output,
+		{
+			name: "dropped"
+			description: """
+				When `reroute_dropped` is set to `true`, the events that do not conform to the
+				schema are sent to the `dropped` output instead of being dropped. For a transform
+				component named `foo`, this output can be accessed by specifying `foo.dropped` as
+				the input to another component. Events sent to this output are in their original
+				form, omitting any coercion, removed fields, or defaults, and are annotated with a
+				`metadata.dropped` field, whose `message` lists the violations.
+				"""
+		},
+	]
+
+	how_it_works: {
+		validation: {
+			title: "Validation"
+			body: """
+				The fields of each event are validated against the schema, after being conformed
+				to it when `coerce_types`, `drop_unknown_fields`, or `fill_defaults` is set.
+				Defaults are filled before the `required` properties are checked, so a required
+				property with a `default` is never missing. The events that conform to the schema
+				are sent to the default output with their conformed fields.
+
+				Only a subset of JSON Schema is supported, and a schema with any other keyword, such
+				as `$ref` or `oneOf`, is rejected when the transform is built rather than being
+				enforced partially. Timestamps are validated as strings.
+				"""
+		}
+	}
+}
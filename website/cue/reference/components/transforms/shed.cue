@@ -0,0 +1,62 @@
+package metadata
+
+components: transforms: shed: {
+	title: "Shed"
+
+	description: """
+		Sheds the lowest priority events when downstream components apply backpressure, or when
+		a rate threshold is reached, so that the most important events keep flowing.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		filter: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.transforms.shed.configuration
+
+	input: {
+		logs: true
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			set:          true
+			summary:      true
+		}
+		traces: true
+	}
+
+	how_it_works: {
+		shedding: {
+			title: "Shedding"
+			body: """
+				Events are sent downstream in the order they're received. When downstream
+				components apply backpressure, or when `max_events_per_sec` is reached, events
+				wait in a queue of up to `max_queued_events` events, instead of applying
+				backpressure to the upstream components. Once the queue is full, the most recent
+				event with the lowest priority is shed for each new event, so the events with
+				higher priorities keep flowing until the queue only contains them.
+
+				When acknowledgements are enabled, shed events are acknowledged as they're dropped.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		shed_events_total: components.sources.internal_metrics.output.metrics.shed_events_total
+	}
+}
@@ -0,0 +1,80 @@
+package metadata
+
+components: transforms: tokenize: {
+	title: "Tokenize"
+
+	description: """
+		Replaces sensitive values with tokens of the same format, so that downstream analytics keep
+		realistic values, and replaces tokens with their original values in authorized pipelines.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		sanitize: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.transforms.tokenize.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	examples: [
+		{
+			title: "Encrypt card numbers"
+			configuration: {
+				fields: ["card"]
+				method: {
+					type:  "fpe"
+					key:   "2DE79D232DF5585D68CE47882AE256D6"
+					tweak: "CBD09280979564"
+				}
+			}
+			input: log: {
+				card: "3992-5202-40"
+			}
+			output: log: {
+				card: "8901-8011-06"
+			}
+		},
+	]
+
+	how_it_works: {
+		methods: {
+			title: "Methods"
+			body: """
+				The `fpe` method encrypts the values with the FF3-1 mode of
+				[NIST SP 800-38G](\(urls.nist_sp_800_38g)). It needs no state, and any pipeline
+				with the same `key` and `tweak` can decrypt the tokens, but the values must have
+				enough characters of the alphabet: between 6 and 56 digits for the `numeric`
+				alphabet, and between 4 and 32 characters for the `alphanumeric` alphabet.
+
+				The `mapping` method replaces values of any length with random tokens, and keeps
+				them in a vault on disk. The vault grows with the number of distinct values, and
+				tokens can only be replaced with their values by the components using the vault.
+				"""
+		}
+		detokenization: {
+			title: "Detokenization"
+			body: """
+				A `tokenize` transform with the `detokenize` operation and the same `method`
+				replaces the tokens with their original values. Events whose tokens can't be
+				replaced, such as tokens unknown to the vault, are dropped.
+				"""
+		}
+	}
+}
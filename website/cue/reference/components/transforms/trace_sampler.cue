@@ -0,0 +1,57 @@
+package metadata
+
+components: transforms: trace_sampler: {
+	title: "Trace Sampler"
+
+	description: """
+		Buffers the spans of each trace for a decision window, and samples or drops whole traces
+		with tail-based policies, such as keeping the traces with errors or high latency.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		filter: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.transforms.trace_sampler.configuration
+
+	input: {
+		logs:    false
+		metrics: null
+		traces:  true
+	}
+
+	how_it_works: {
+		tail_sampling: {
+			title: "Tail-based sampling"
+			body: """
+				Each span is grouped with the other spans of its trace, by its `trace_id` field,
+				and buffered for `decision_wait_secs` after the first span of the trace is
+				received. The policies are then applied to all of the spans at once, so that a
+				trace is sampled or dropped as a whole, even when only one of its spans failed or
+				was slow. The spans are sent downstream in the order they were received, and the
+				spans without a `trace_id` field are sent downstream immediately.
+
+				Since the decisions are made within a single Vector instance, all of the spans of
+				a trace must be sent to the same instance, for example by load balancing them by
+				trace ID.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		sampling_decisions_total: components.sources.internal_metrics.output.metrics.sampling_decisions_total
+	}
+}
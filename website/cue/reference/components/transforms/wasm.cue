@@ -0,0 +1,76 @@
+package metadata
+
+components: transforms: wasm: {
+	title: "WASM"
+
+	description: """
+		Transforms log events with a WebAssembly module, so that custom logic compiled from
+		languages such as Rust, Go, or AssemblyScript can run in Vector without forking it.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		program: {
+			runtime: {
+				name:    "WebAssembly"
+				url:     "https://webassembly.org"
+				version: null
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.transforms.wasm.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		guest_abi: {
+			title: "Guest ABI"
+			body: """
+				For each event, the transform calls the `alloc` function of the module with the
+				length of the event encoded as JSON, writes the event to the returned buffer, and
+				calls the `process` function with the address and the length of the buffer. The
+				`process` function returns the address of its output in the high 32 bits of its
+				result, and the length of its output in the low 32 bits. The memory of the module is
+				kept between events, so the module is responsible for reusing or freeing its buffers.
+
+				The module must not import any function: modules compiled for WASI, or with the
+				default `abort` import of AssemblyScript, are rejected.
+				"""
+		}
+		limits: {
+			title: "Limits"
+			body: """
+				The memory of the module is limited by `max_memory_bytes`, and the number of
+				instructions it can run for each event by `fuel`. When the module runs out of fuel
+				or memory, or traps, the event is dropped, and the module is instantiated again
+				for the next event.
+				"""
+		}
+		hot_reload: {
+			title: "Hot reload"
+			body: """
+				With `hot_reload` enabled, the module is compiled again when its file changes,
+				without restarting the pipeline. A module that fails to compile, or that does not
+				implement the guest ABI, is reported and ignored, and the previous module keeps
+				processing events.
+				"""
+		}
+	}
+}
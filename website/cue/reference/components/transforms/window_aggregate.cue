@@ -0,0 +1,68 @@
+package metadata
+
+components: transforms: window_aggregate: {
+	title: "Window Aggregate"
+
+	description: """
+		Aggregates log events over tumbling or sliding windows, emitting one summary event per
+		group and window with the configured aggregations.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		aggregate: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.transforms.window_aggregate.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		windows: {
+			title: "Windows"
+			body: """
+				Windows are based on the time at which events are processed by the transform. With
+				`tumbling` windows, a summary event is emitted for each group every `duration_ms`,
+				and each event is aggregated in a single window. With `sliding` windows, a summary
+				event is emitted for each group every `step_ms`, covering the events of the last
+				`duration_ms`, so each event is aggregated in several consecutive windows.
+
+				Groups without events in a window don't emit a summary event for it. When Vector
+				shuts down, the windows in progress are flushed.
+				"""
+		}
+		finalizer: {
+			title: "Finalizer"
+			body: """
+				The `finalizer` VRL program runs on each summary event before it's emitted, to
+				derive fields from the aggregations, such as a rate or a ratio, or to drop the
+				summary event with `abort`, for example when a group has too few events to be
+				significant.
+				"""
+		}
+		memory_usage: {
+			title: "Memory Usage"
+			body: """
+				The transform keeps the state of each group for the duration of a window. Most
+				aggregations use a constant amount of memory per group, but `percentile`
+				aggregations keep all the values of their field until the window is flushed.
+				"""
+		}
+	}
+}
@@ -248,6 +248,53 @@ configuration: {
 								examples: ["de", "en", "es", "fr", "ja", "pt-BR", "ru", "zh-CN"]
 							}
 						}
+						auto_update: {
+							description: """
+								Automatically download the database from [MaxMind](\(urls.maxmind)), and
+								update it when a new version is published.
+
+								The database is downloaded to `path` when it doesn't exist. New versions are
+								checked against their published SHA-256 checksum, written next to `path`, and
+								then moved to `path` and loaded without interrupting lookups. The database file
+								is also reloaded when it is replaced by another process, such as `geoipupdate`.
+								"""
+							required: false
+							common:   false
+							type: object: options: {
+								edition_id: {
+									description: "The edition of the database to download."
+									required:    true
+									type: string: {
+										examples: ["GeoLite2-City", "GeoIP2-ISP"]
+									}
+								}
+								endpoint: {
+									description: "The endpoint from which the database is downloaded."
+									required:    false
+									type: string: {
+										default: "https://download.maxmind.com/app/geoip_download"
+									}
+								}
+								interval_secs: {
+									description: """
+										The interval between the checks for new versions of the database. MaxMind
+										updates most databases weekly.
+										"""
+									required: false
+									type: uint: {
+										default: 86400
+										unit:    "seconds"
+									}
+								}
+								license_key: {
+									description: "The license key of the MaxMind account used to download the database."
+									required:    true
+									type: string: {
+										examples: ["${MAXMIND_LICENSE_KEY}"]
+									}
+								}
+							}
+						}
 					}
 				}
 			}
@@ -469,6 +516,87 @@ configuration: {
 			}
 		}
 
+		request_limits: {
+			common: false
+			description: """
+				Configures groups of request limits shared by sinks.
+
+				A sink joins a group by setting its `request_limits` option to the name of the group.
+				The requests of all of the sinks of a group count against the concurrency and rate
+				limits of the group, so that sinks sending to the same destination don't collectively
+				overload it. The concurrency of the requests of these sinks is limited by the group
+				instead of their own `request.concurrency` option, while their own rate limit still
+				applies on top of the rate limit of the group.
+
+				Only the `elasticsearch`, `http`, and `splunk_hec_logs` sinks, and the `loki` sink when
+				its `out_of_order_action` is `accept`, can join a group.
+				"""
+			required: false
+			type: object: {
+				examples: [
+					{
+						elasticsearch_cluster: {
+							concurrency:              "adaptive"
+							rate_limit_duration_secs: 1
+							rate_limit_num:           100
+						}
+					},
+				]
+				options: {
+					"*": {
+						description: "The request limits of the group of the given name."
+						required:    true
+						type: object: options: {
+							concurrency: {
+								common: true
+								description: """
+									The maximum number of in-flight requests of all of the sinks of the
+									group, or `adaptive` to adjust it with the adaptive request
+									concurrency algorithm.
+									"""
+								required: false
+								type: string: default: "adaptive"
+							}
+							rate_limit_duration_secs: {
+								common:      false
+								description: "The time window used for the `rate_limit_num` option."
+								required:    false
+								type: uint: {
+									default: 1
+									unit:    "seconds"
+								}
+							}
+							rate_limit_num: {
+								common: true
+								description: """
+									The maximum number of requests of all of the sinks of the group
+									allowed within the `rate_limit_duration_secs` time window.
+									"""
+								required: false
+								type: uint: {
+									default: 9223372036854775807
+									unit:    "requests"
+								}
+							}
+							adaptive_concurrency: {
+								common:      false
+								description: """
+									Configures the adaptive request concurrency algorithm of the group. It
+									takes the same options as the `request.adaptive_concurrency` option of
+									sinks.
+									"""
+								required: false
+								type: object: {
+									examples: []
+									options: {}
+								}
+							}
+						}
+					}
+				}
+			}
+		}
+
 		secret: {
 			common: false
 			description: """
@@ -0,0 +1,10 @@
+package metadata
+
+services: azure_data_explorer: {
+	name:     "Azure Data Explorer"
+	thing:    "an \(name) cluster"
+	url:      urls.azure_data_explorer
+	versions: null
+
+	description: "[Azure Data Explorer](\(urls.azure_data_explorer)) is a fully-managed analytics service on Azure, also known as Kusto, that ingests and queries large volumes of log and telemetry data with the Kusto Query Language."
+}
@@ -0,0 +1,10 @@
+package metadata
+
+services: azure_event_hubs: {
+	name:     "Azure Event Hubs"
+	thing:    "an \(name) namespace"
+	url:      urls.azure_event_hubs
+	versions: null
+
+	description: "[Azure Event Hubs](\(urls.azure_event_hubs)) is a fully-managed, real-time data ingestion service on Azure that streams millions of events per second into partitioned event hubs, for consumption by stream processors and analytics services."
+}
@@ -0,0 +1,10 @@
+package metadata
+
+services: chat_webhook: {
+	name:     "Chat webhook"
+	thing:    "a \(name)"
+	url:      urls.slack_incoming_webhooks
+	versions: null
+
+	description: "The incoming webhooks of chat platforms, such as [Slack](\(urls.slack_incoming_webhooks)), [Microsoft Teams](\(urls.teams_incoming_webhooks)), and [Discord](\(urls.discord_webhooks)), post the messages that they receive to a channel, without the need for a bot or an app."
+}
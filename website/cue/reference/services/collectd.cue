@@ -0,0 +1,8 @@
+package metadata
+
+services: collectd: {
+	name:     "collectd"
+	thing:    "a \(name) daemon"
+	url:      urls.collectd
+	versions: ">= 4.7"
+}
@@ -0,0 +1,10 @@
+package metadata
+
+services: doris: {
+	name:     "Apache Doris"
+	thing:    "an \(name) database"
+	url:      urls.doris
+	versions: null
+
+	description: "[Apache Doris](\(urls.doris)) is an open-source real-time analytical database based on MPP architecture, which loads data with high throughput and answers queries over large volumes of data with low latency. Its Stream Load API is also implemented by [StarRocks](\(urls.starrocks))."
+}
@@ -0,0 +1,8 @@
+package metadata
+
+services: grpc_client: {
+	name:     "gRPC client"
+	thing:    "a \(name)"
+	url:      urls.grpc
+	versions: null
+}
@@ -0,0 +1,8 @@
+package metadata
+
+services: jaeger: {
+	name:     "Jaeger"
+	thing:    "a \(name) collector or agent"
+	url:      urls.jaeger
+	versions: null
+}
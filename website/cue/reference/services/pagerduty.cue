@@ -0,0 +1,10 @@
+package metadata
+
+services: pagerduty: {
+	name:     "PagerDuty"
+	thing:    "a \(name) service"
+	url:      urls.pagerduty
+	versions: null
+
+	description: "[PagerDuty](\(urls.pagerduty)) is an incident management platform that alerts the people on call for a service when its monitoring detects an issue, and tracks the resulting incidents until they're resolved."
+}
@@ -0,0 +1,10 @@
+package metadata
+
+services: questdb: {
+	name:     "QuestDB"
+	thing:    "a \(name) database"
+	url:      urls.questdb
+	versions: null
+
+	description: "[QuestDB](\(urls.questdb)) is an open-source time-series database for high throughput ingestion and fast SQL queries. It ingests data over the InfluxDB line protocol, and organizes it into tables with a designated timestamp and indexed symbol columns."
+}
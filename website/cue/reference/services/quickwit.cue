@@ -0,0 +1,10 @@
+package metadata
+
+services: quickwit: {
+	name:     "Quickwit"
+	thing:    "a \(name) cluster"
+	url:      urls.quickwit
+	versions: null
+
+	description: "[Quickwit](\(urls.quickwit)) is an open-source search engine for logs and traces, which indexes data into splits stored on object storage such as Amazon S3, and searches them with compute decoupled from storage. Its indexes can be queried with a subset of the Elasticsearch query API."
+}
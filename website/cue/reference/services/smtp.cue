@@ -0,0 +1,10 @@
+package metadata
+
+services: smtp: {
+	name:     "SMTP"
+	thing:    "an \(name) server"
+	url:      urls.smtp
+	versions: null
+
+	description: "The [Simple Mail Transfer Protocol](\(urls.smtp)) (SMTP) is the standard protocol used to send email messages between mail servers, and from mail clients to the mail servers that relay them to their recipients."
+}
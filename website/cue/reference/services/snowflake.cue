@@ -0,0 +1,10 @@
+package metadata
+
+services: snowflake: {
+	name:     "Snowflake"
+	thing:    "a \(name) table"
+	url:      urls.snowflake
+	versions: null
+
+	description: "[Snowflake](\(urls.snowflake)) is a cloud data platform for storing and analyzing large volumes of data with SQL, into which data can be streamed with low latency through Snowpipe Streaming."
+}
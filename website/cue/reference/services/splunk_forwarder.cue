@@ -0,0 +1,8 @@
+package metadata
+
+services: splunk_forwarder: {
+	name:     "Splunk forwarder"
+	thing:    "a \(name)"
+	url:      urls.splunk_forwarder
+	versions: null
+}
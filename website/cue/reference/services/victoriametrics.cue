@@ -0,0 +1,10 @@
+package metadata
+
+services: victoriametrics: {
+	name:     "VictoriaMetrics"
+	thing:    "a \(name) database"
+	url:      urls.victoriametrics
+	versions: null
+
+	description: "[VictoriaMetrics](\(urls.victoriametrics)) is a scalable time-series database and monitoring solution. Besides the Prometheus remote write protocol, it ingests metrics through native import endpoints, such as its JSON line format and the InfluxDB line protocol, both on single-node instances and on the `vminsert` component of its multitenant cluster version."
+}
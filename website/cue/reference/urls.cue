@@ -60,6 +60,7 @@ urls: {
 	aws_kinesis_firehose_http_protocol:         "\(aws_docs)/firehose/latest/dev/create-destination.html#create-destination-http"
 	aws_firehose_http_request_spec:             "\(aws_docs)/firehose/latest/dev/httpdeliveryrequestresponse.html"
 	aws_kinesis_firehose_api:                   "\(aws_docs)/firehose/latest/APIReference/API_PutRecordBatch.html"
+	aws_kinesis_firehose_dynamic_partitioning:  "\(aws_docs)/firehose/latest/dev/dynamic-partitioning.html"
 	aws_kinesis_firehose_service_limits:        "\(aws_docs)/firehose/latest/dev/limits.html"
 	aws_kinesis_firehose_http_setup:            "https://aws.amazon.com/blogs/big-data/stream-data-to-an-http-endpoint-with-amazon-kinesis-data-firehose/"
 	aws_kinesis_partition_key:                  "\(aws_docs)/kinesis/latest/APIReference/API_PutRecordsRequestEntry.html#Streams-Type-PutRecordsRequestEntry-PartitionKey"
@@ -87,12 +88,21 @@ urls: {
 	aws_sqs:                                    "https://aws.amazon.com/sqs/"
 	aws_sqs_api:                                "\(aws_docs)/AWSSimpleQueueService/latest/APIReference/Welcome.html"
 	aws_sqs_create:                             "\(aws_docs)/AWSSimpleQueueService/latest/SQSDeveloperGuide/sqs-configure-create-queue.html"
+	aws_sqs_fifo:                               "\(aws_docs)/AWSSimpleQueueService/latest/SQSDeveloperGuide/sqs-fifo-queues.html"
 	aws_sqs_message_deduplication_id:           "\(aws_docs)/AWSSimpleQueueService/latest/SQSDeveloperGuide/using-messagededuplicationid-property.html"
 	aws_vpc_flow_logs:                          "\(aws_docs)/vpc/latest/userguide/flow-logs.html"
 	axiom:                                      "https://axiom.co"
 	axiom_cloud:                                "https://cloud.axiom.co"
+	axiom_ingest_api:                           "https://axiom.co/docs/restapi/ingest"
 	azure_blob:                                 "https://azure.microsoft.com/en-us/services/storage/blobs/"
 	azure_blob_endpoints:                       "https://docs.microsoft.com/en-us/rest/api/storageservices/blob-service-rest-api"
+	azure_blob_events:                          "https://learn.microsoft.com/en-us/azure/storage/blobs/storage-blob-event-overview"
+	azure_data_explorer:                        "https://learn.microsoft.com/en-us/azure/data-explorer/"
+	azure_data_explorer_ingestion:              "https://learn.microsoft.com/en-us/azure/data-explorer/ingest-data-overview"
+	azure_data_explorer_mappings:               "https://learn.microsoft.com/en-us/azure/data-explorer/kusto/management/mappings"
+	azure_data_explorer_streaming:              "https://learn.microsoft.com/en-us/azure/data-explorer/ingest-data-streaming"
+	azure_event_hubs_sas:                       "https://learn.microsoft.com/en-us/azure/event-hubs/authenticate-shared-access-signature"
+	azure_event_hubs_send_batch:                "https://learn.microsoft.com/en-us/rest/api/eventhub/send-batch-events"
 	azure_monitor:                              "https://azure.microsoft.com/en-us/services/monitor/"
 	azure_monitor_logs_endpoints:               "https://docs.microsoft.com/en-us/rest/api/monitor/"
 	base16:                                     "\(wikipedia)/wiki/Hexadecimal"
@@ -110,6 +120,10 @@ urls: {
 	cgroups_limit_resources:                    "https://the.binbashtheory.com/control-resources-cgroups/"
 	clickhouse:                                 "https://clickhouse.com/"
 	clickhouse_http:                            "https://clickhouse.com/docs/en/interfaces/http/"
+	collectd:                                   "https://collectd.org"
+	collectd_binary_protocol:                   "https://collectd.org/wiki/index.php/Binary_protocol"
+	collectd_network_plugin:                    "https://collectd.org/wiki/index.php/Plugin:Network"
+	collectd_types_db:                          "https://collectd.org/documentation/manpages/types.db.5.shtml"
 	community_id_spec:                          "https://github.com/corelight/community-id-spec"
 	console:                                    "\(wikipedia)/wiki/System_console"
 	contributing:                               "\(vector_repo)/blob/master/CONTRIBUTING.md#setup"
@@ -143,6 +157,7 @@ urls: {
 	debian:                                     "https://www.debian.org/"
 	debian_system_groups:                       "https://wiki.debian.org/SystemGroups"
 	default_configuration:                      "\(vector_repo)/blob/master/config/vector.yaml"
+	discord_webhooks:                           "https://discord.com/developers/docs/resources/webhook#execute-webhook"
 	dnstap:                                     "http://dnstap.info/"
 	docker:                                     "https://www.docker.com/"
 	docker_alpine:                              "\(docker_hub)/_/alpine"
@@ -169,10 +184,15 @@ urls: {
 	docker_setup:                               "\(docker_docs)/get-docker/"
 	dockerfile:                                 "\(vector_repo)/blob/master/Dockerfile"
 	dogstatsd:                                  "\(datadog_docs)/developers/dogstatsd/?tab=hostagent"
+	doris:                                      "https://doris.apache.org/"
+	doris_stream_load:                          "https://doris.apache.org/docs/data-operate/import/stream-load-manual"
 	dot_format:                                 "https://graphviz.org/doc/info/lang.html"
 	dpkg:                                       "https://wiki.debian.org/dpkg"
+	drain:                                      "https://jiemingzhu.github.io/pub/pjhe_icws2017.pdf"
 	dry_code:                                   "\(wikipedia)/wiki/Don%27t_repeat_yourself"
 	cidr:                                       "\(wikipedia)/wiki/Classless_Inter-Domain_Routing"
+	ebpf:                                       "https://ebpf.io/"
+	ebpf_core:                                  "https://docs.kernel.org/bpf/libbpf/libbpf_overview.html#bpf-co-re-compile-once-run-everywhere"
 	elastic_beats:                              "https://www.elastic.co/beats/"
 	elasticsearch:                              "https://www.elastic.co/products/elasticsearch"
 	elasticsearch_bulk:                         "https://www.elastic.co/guide/en/elasticsearch/reference/current/docs-bulk.html"
@@ -207,8 +227,12 @@ urls: {
 	gcp_authentication_api_key:                 "\(gcp)/docs/authentication/api-keys"
 	gcp_authentication_server_to_server:        "\(gcp)/docs/authentication/production"
 	gcp_authentication_service_account:         "\(gcp)/docs/authentication/production#obtaining_and_providing_service_account_credentials_manually"
+	gcp_bigquery:                               "\(gcp)/bigquery"
+	gcp_bigquery_write_api:                     "\(gcp)/bigquery/docs/write-api"
 	gcp_cloud_storage:                          "\(gcp)/storage"
 	gcp_chronicle:                              "https://chronicle.security"
+	gcp_chronicle_ingestion_api:                "https://cloud.google.com/chronicle/docs/reference/ingestion-api"
+	gcp_chronicle_udm:                          "https://cloud.google.com/chronicle/docs/event-processing/udm-overview"
 	gcp_folders:                                "\(gcp)/resource-manager/docs/creating-managing-folders"
 	gcp_pubsub:                                 "\(gcp)/pubsub/"
 	gcp_pubsub_rest:                            "\(gcp)/pubsub/docs/reference/rest/"
@@ -224,20 +248,28 @@ urls: {
 	gcra:                                       "\(wikipedia)/wiki/Generic_cell_rate_algorithm"
 	gcs:                                        "https://cloud.google.com/storage/"
 	gcs_predefined_acl:                         "\(gcp)/storage/docs/access-control/lists#predefined-acl"
+	gcs_pubsub_notifications:                   "\(gcp)/storage/docs/pubsub-notifications"
 	gcs_storage_classes:                        "\(gcp)/storage/docs/storage-classes"
 	gcs_custom_metadata:                        "\(gcp)/storage/docs/metadata#custom-metadata"
+	gcs_cmek:                                   "\(gcp)/storage/docs/encryption/customer-managed-keys"
+	gcs_object_holds:                           "\(gcp)/storage/docs/object-holds"
+	gcs_object_retention:                       "\(gcp)/storage/docs/object-lock"
 	git:                                        "https://git-scm.com/"
 	github:                                     "https://github.com"
 	github_protected_branches:                  "https://help.github.com/en/github/administering-a-repository/about-protected-branches"
 	github_sign_commits:                        "https://help.github.com/en/github/authenticating-to-github/signing-commits"
 	globbing:                                   "\(wikipedia)/wiki/Glob_(programming)"
 	glog:                                       "\(github)/google/glog"
+	graphite:                                   "https://graphiteapp.org"
+	graphite_feeding_carbon:                    "https://graphite.readthedocs.io/en/latest/feeding-carbon.html"
+	graphite_tags:                              "https://graphite.readthedocs.io/en/latest/tags.html"
 	graphql:                                    "https://graphql.org"
 	graphql_playground:                         "\(github)/graphql/graphql-playground"
 	graphviz:                                   "https://graphviz.org/"
 	greptimecloud:                              "https://greptime.cloud"
 	greptimedb:                                 "https://github.com/greptimeteam/greptimedb"
 	greptimedb_grpc:                            "https://docs.greptime.com/"
+	grpc:                                       "https://grpc.io"
 	grpc_status_code:                           "https://grpc.github.io/grpc/core/md_doc_statuscodes.html"
 	grok:                                       "https://github.com/daschl/grok/tree/master/patterns"
 	grok_debugger:                              "https://grokdebug.herokuapp.com/"
@@ -265,6 +297,7 @@ urls: {
 	iam_instance_profile:                       "\(aws_docs)/IAM/latest/UserGuide/id_roles_use_switch-role-ec2_instance-profiles.html"
 	iana_time_zone_format:                      "\(wikipedia)/wiki/Tz_database#Names_of_time_zones"
 	iana_time_zones:                            "\(wikipedia)/wiki/List_of_tz_database_time_zones"
+	icmp:                                       "\(wikipedia)/wiki/Internet_Control_Message_Protocol"
 	ieee_754:                                   "\(wikipedia)/wiki/IEEE_754"
 	ietf_rfc_6750:                              "https://tools.ietf.org/html/rfc6750"
 	idn:                                        "\(wikipedia)/wiki/Internationalized_domain_name"
@@ -274,6 +307,7 @@ urls: {
 	influxdb_http_api_v2:                       "https://v2.docs.influxdata.com/v2.0/api/#tag/Write"
 	influxdb_authentication_token:              "https://v2.docs.influxdata.com/v2.0/security/tokens/"
 	influxdb_line_protocol:                     "https://v2.docs.influxdata.com/v2.0/reference/syntax/line-protocol/"
+	influxdb3_write_api:                        "https://docs.influxdata.com/influxdb/cloud-dedicated/write-data/"
 	inode:                                      "\(wikipedia)/wiki/Inode"
 	ip_aton:                                    "https://linux.die.net/man/3/inet_aton"
 	ip_ntoa:                                    "https://linux.die.net/man/3/inet_ntoa"
@@ -282,6 +316,9 @@ urls: {
 	iso_8601:                                   "\(wikipedia)/wiki/ISO_8601"
 	iso3166_2:                                  "\(wikipedia)/wiki/ISO_3166-2"
 	issue_1694:                                 "\(vector_repo)/issues/1694"
+	jaeger:                                     "https://www.jaegertracing.io"
+	jaeger_apis:                                "\(jaeger)/docs/latest/apis/"
+	jaeger_opentelemetry:                       "https://opentelemetry.io/docs/specs/otel/trace/sdk_exporters/jaeger/"
 	journalctl:                                 "https://www.freedesktop.org/software/systemd/man/journalctl.html"
 	journald:                                   "https://www.freedesktop.org/software/systemd/man/systemd-journald.service.html"
 	json:                                       "\(wikipedia)/wiki/JSON"
@@ -299,6 +336,7 @@ urls: {
 	kubernetes_api_server:                      "\(kubernetes)/docs/reference/command-line-tools-reference/kube-apiserver/"
 	kubernetes_authorization:                   "\(kubernetes)/docs/reference/access-authn-authz/authorization/"
 	kubernetes_daemonset:                       "\(kubernetes)/docs/concepts/workloads/controllers/daemonset/"
+	kubernetes_events:                          "\(kubernetes)/docs/reference/kubernetes-api/cluster-resources/event-v1/"
 	kubernetes_example_daemonset:               "\(vector_repo)/blob/master/config/kubernetes/vector-daemonset.yaml"
 	kubernetes_host_path:                       "\(kubernetes)/docs/concepts/storage/volumes/#hostpath"
 	kubernetes_limit_resources:                 "\(kubernetes)/docs/tasks/configure-pod-container/assign-cpu-resource/"
@@ -321,6 +359,7 @@ urls: {
 	log_namespacing_blog:                       "/blog/log-namespacing/"
 	loki:                                       "https://grafana.com/oss/loki/"
 	loki_multi_tenancy:                         "\(github)/grafana/loki/blob/master/docs/operations/multi-tenancy.md"
+	loki_structured_metadata:                   "https://grafana.com/docs/loki/latest/get-started/labels/structured-metadata/"
 	log_event_source:                           "\(vector_repo)/blob/master/src/event/"
 	logplex:                                    "https://devcenter.heroku.com/articles/logplex"
 	logplex_protocol:                           "\(github)/heroku/logplex/blob/master/doc/README.http_drains.md"
@@ -359,10 +398,14 @@ urls: {
 	metric_event_source:                        "\(vector_repo)/blob/master/src/event/metric.rs"
 	mlua:                                       "\(github)/khvzak/mlua"
 	mongodb:                                    "https://www.mongodb.com"
+	mongodb_change_streams:                     "https://www.mongodb.com/docs/manual/changeStreams/"
 	mongodb_command_server_status:              "https://docs.mongodb.com/manual/reference/command/serverStatus/"
 	mongodb_connection_string_uri_format:       "https://docs.mongodb.com/manual/reference/connection-string/"
 	mqtt:                                       "https://mqtt.org/"
 	musl_builder_docker_image:                  "\(vector_repo)/blob/master/scripts/ci-docker-images/builder-x86_64-unknown-linux-musl/Dockerfile"
+	mysql:                                      "https://www.mysql.com/"
+	mysql_binlog_row_metadata:                  "https://dev.mysql.com/doc/refman/8.0/en/replication-options-binary-log.html#sysvar_binlog_row_metadata"
+	mysql_gtids:                                "https://dev.mysql.com/doc/refman/8.0/en/replication-gtids-concepts.html"
 	native_proto_schema:                        "\(vector_repo)/blob/master/lib/vector-core/proto/event.proto"
 	native_json_schema:                         "\(vector_repo)/blob/master/lib/codecs/tests/data/native_encoding/schema.cue"
 	nats:                                       "https://nats.io/"
@@ -382,6 +425,7 @@ urls: {
 	nginx_ingress_upstreaminfo:                 "https://kubernetes.github.io/ingress-nginx/user-guide/nginx-configuration/log-format/"
 	nginx_error:                                "https://github.com/nginx/nginx/blob/branches/stable-1.18/src/core/ngx_log.c#L102"
 	nginx_stub_status_module:                   "http://nginx.org/en/docs/http/ngx_http_stub_status_module.html"
+	nist_sp_800_38g:                            "https://csrc.nist.gov/pubs/sp/800/38/g/r1/ipd"
 	nix:                                        "https://nixos.org/nix/"
 	nixos:                                      "https://nixos.org/"
 	nixpkgs_9682:                               "\(github)/NixOS/nixpkgs/issues/9682"
@@ -390,6 +434,8 @@ urls: {
 	opentelemetry:                              "https://opentelemetry.io"
 	opentelemetry_protocol:                     "\(opentelemetry)/docs/reference/specification/protocol/otlp/"
 	order_of_ops:                               "\(wikipedia)/wiki/Order_of_operations"
+	pagerduty:                                  "https://www.pagerduty.com/"
+	pagerduty_events_api:                       "https://developer.pagerduty.com/docs/events-api-v2/overview/"
 	papertrail:                                 "https://www.papertrail.com/"
 	papertrail_syslog:                          "https://help.papertrailapp.com/kb/how-it-works/http-api/#submitting-log-messages"
 	perl_windows:                               "https://www.perl.org/get.html#win32"
@@ -404,8 +450,10 @@ urls: {
 	percent_encoding_www_form_urlencoded:       "https://url.spec.whatwg.org/#application-x-www-form-urlencoded-percent-encode-set"
 	posix_acls:                                 "https://www.usenix.org/legacy/publications/library/proceedings/usenix03/tech/freenix03/full_papers/gruenbacher/gruenbacher_html/main.html"
 	postgresql:                                 "https://www.postgresql.org/"
+	postgresql_copy:                            "https://www.postgresql.org/docs/current/sql-copy.html"
 	postgresql_csvlog:                          "https://www.postgresql.org/docs/current/runtime-config-logging.html#RUNTIME-CONFIG-LOGGING-CSVLOG"
 	postgresql_matching:                        "https://www.postgresql.org/docs/current/functions-matching.html#FUNCTIONS-POSIX-REGEXP"
+	postgresql_sqlstate:                        "https://www.postgresql.org/docs/current/errcodes-appendix.html"
 	procfs:                                     "https://en.wikipedia.org/wiki/Procfs#:~:text=The%20proc%20filesystem%20(procfs)%20is,in%20the%20kernel%20than%20traditional"
 	prometheus:                                 "https://prometheus.io/"
 	prometheus_client:                          "https://prometheus.io/docs/instrumenting/clientlibs/"
@@ -426,6 +474,12 @@ urls: {
 	pulsar:                                     "https://pulsar.apache.org/"
 	pulsar_protocol:                            "https://pulsar.apache.org/docs/en/develop-binary-protocol/"
 	punycode:                                   "\(wikipedia)/wiki/Punycode"
+	questdb:                                    "https://questdb.io/"
+	questdb_authentication:                     "https://questdb.io/docs/reference/api/ilp/authenticate/"
+	questdb_ilp:                                "https://questdb.io/docs/reference/api/ilp/overview/"
+	quickwit:                                   "https://quickwit.io/"
+	quickwit_indexing_settings:                 "https://quickwit.io/docs/configuration/index-config#indexing-settings"
+	quickwit_ingest_api:                        "https://quickwit.io/docs/reference/rest-api#ingest-data-into-an-index"
 	raspbian:                                   "https://www.raspbian.org/"
 	rdkafka:                                    "\(github)/edenhill/librdkafka"
 	regex:                                      "\(wikipedia)/wiki/Regular_expression"
@@ -473,10 +527,17 @@ urls: {
 	sha2:                                       "\(wikipedia)/wiki/SHA-2"
 	sha3:                                       "\(wikipedia)/wiki/SHA-3"
 	signal:                                     "\(wikipedia)/wiki/Signal_(IPC)"
+	slack_incoming_webhooks:                    "https://api.slack.com/messaging/webhooks"
+	smtp:                                       "https://datatracker.ietf.org/doc/html/rfc5321"
 	snake_case:                                 "\(wikipedia)/wiki/Snake_case"
 	snappy:                                     "https://google.github.io/snappy/"
+	snowflake:                                  "https://www.snowflake.com/"
+	snowflake_key_pair_auth:                    "https://docs.snowflake.com/en/user-guide/key-pair-auth"
+	snowflake_snowpipe_streaming_api:           "https://docs.snowflake.com/en/user-guide/snowpipe-streaming/snowpipe-streaming-high-performance-rest-api"
 	socket:                                     "\(wikipedia)/wiki/Network_socket"
 	splunk:                                     "https://www.splunk.com"
+	splunk_forwarder:                           "https://docs.splunk.com/Documentation/Forwarder/latest/Forwarder/Abouttheuniversalforwarder"
+	splunk_forwarder_outputs:                   "https://docs.splunk.com/Documentation/Forwarder/latest/Forwarder/Configureforwardingwithoutputs.conf"
 	splunk_hec:                                 "https://dev.splunk.com/enterprise/docs/dataapps/httpeventcollector/"
 	splunk_hec_channel_header:                  "https://docs.splunk.com/Documentation/Splunk/8.2.4/Data/FormateventsforHTTPEventCollector#Channel_identifier_header"
 	splunk_hec_event_endpoint:                  "https://docs.splunk.com/Documentation/Splunk/8.0.0/RESTREF/RESTinput#services.2Fcollector.2Fevent"
@@ -488,6 +549,7 @@ urls: {
 	splunk_hec_metadata:                        "https://docs.splunk.com/Documentation/Splunk/latest/Data/FormateventsforHTTPEventCollector#Event_metadata"
 	specs_instrumentation:                      "\(vector_repo)/blob/master/docs/specs/instrumentation.md)"
 	standard_streams:                           "\(wikipedia)/wiki/Standard_streams"
+	starrocks:                                  "https://www.starrocks.io/"
 	statsd:                                     "\(github)/statsd/statsd"
 	statsd_multi:                               "\(github)/statsd/statsd/blob/master/docs/metric_types.md#multi-metric-packets"
 	statsd_set:                                 "\(github)/statsd/statsd/blob/master/docs/metric_types.md#sets"
@@ -510,7 +572,9 @@ urls: {
 	systemd_resolved:                           "https://wiki.archlinux.org/index.php/Systemd-resolved"
 	tcp:                                        "\(wikipedia)/wiki/Transmission_Control_Protocol"
 	team:                                       "/community#team"
+	teams_incoming_webhooks:                    "https://learn.microsoft.com/en-us/microsoftteams/platform/webhooks-and-connectors/how-to/connectors-using"
 	timber:                                     "https://timber.io"
+	timescaledb:                                "https://www.timescale.com/"
 	toml:                                       "\(github)/toml-lang/toml"
 	toml_array:                                 "\(github)/toml-lang/toml#array"
 	toml_table:                                 "\(github)/toml-lang/toml#table"
@@ -630,6 +694,10 @@ urls: {
 	vector_twitter:                             "https://twitter.com/vectordotdev"
 	vector_unit_tests:                          "\(vector_configuration)/unit-tests"
 	vector_version_branches:                    "\(vector_repo)/branches/all?query=v"
+	victoriametrics:                            "https://victoriametrics.com/"
+	victoriametrics_cluster:                    "https://docs.victoriametrics.com/cluster-victoriametrics/"
+	victoriametrics_influx:                     "https://docs.victoriametrics.com/#how-to-send-data-from-influxdb-compatible-agents-such-as-telegraf"
+	victoriametrics_jsonl:                      "https://docs.victoriametrics.com/#how-to-import-data-in-json-line-format"
 	vrl_announcement:                           "/blog/vector-remap-language"
 	vrl_boolean_expression:                     "\(vrl_reference)#boolean-expressions"
 	vrl_error_handling:                         "\(vrl_errors_reference)#handling"